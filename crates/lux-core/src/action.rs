@@ -22,7 +22,7 @@ pub struct ActionInfo {
     pub icon: Option<String>,
 }
 
-use crate::Groups;
+use crate::{Groups, HandlerError};
 
 /// Result returned by action execution.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,7 +65,35 @@ pub enum ActionResult {
     },
 
     /// Action failed.
-    Fail { error: String },
+    Fail {
+        error: HandlerError,
+        /// Handler key that runs the action again, if the error is retryable.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry: Option<String>,
+    },
+
+    /// Put an item's row into inline edit mode.
+    Edit(InlineEdit),
+
+    /// Play a saved macro (`ctx:play_macro()`).
+    PlayMacro { name: String },
+
+    /// Run a launcher action by name (`ctx:run_command()`).
+    RunCommand { action: String },
+}
+
+/// A row put into inline edit mode (`ctx:edit()`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InlineEdit {
+    /// ID of the item whose row is edited.
+    pub item_id: String,
+    /// Text the editor starts with.
+    pub value: String,
+    /// Shown while the editor is empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placeholder: Option<String>,
+    /// Lua registry key of the callback the submitted text is passed to.
+    pub handler_key: String,
 }
 
 /// A follow-up action shown after completion.
@@ -74,4 +102,7 @@ pub struct FollowUpAction {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
+    /// Lua registry key of the handler run when the follow-up is chosen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handler_key: Option<String>,
 }
@@ -59,6 +59,19 @@ pub fn init_lua_path() -> Option<PathBuf> {
     dirs::config_dir().map(|p| p.join("lux/init.lua"))
 }
 
+/// Find the user's existing init.lua.
+///
+/// Tries paths in order:
+/// 1. XDG-style: ~/.config/lux/init.lua (common for CLI tools)
+/// 2. Platform config: ~/Library/Application Support/lux/init.lua (macOS)
+pub fn find_init_lua() -> Option<PathBuf> {
+    let xdg_path = dirs::home_dir().map(|home| home.join(".config").join("lux").join("init.lua"));
+    xdg_path
+        .into_iter()
+        .chain(init_lua_path())
+        .find(|path| path.exists())
+}
+
 /// Get the config directory path.
 pub fn config_dir() -> Option<PathBuf> {
     dirs::config_dir().map(|p| p.join("lux"))
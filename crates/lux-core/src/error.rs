@@ -1,6 +1,9 @@
 //! Error types for the Lux launcher.
 
+use std::any::Any;
 use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Backend errors - surfaced to UI.
@@ -25,6 +28,91 @@ pub enum BackendError {
     /// Channel communication error.
     #[error("Channel error: {0}")]
     Channel(String),
+
+    /// Request replaced by a newer one before it ran.
+    #[error("Request superseded by a newer one")]
+    Superseded,
+
+    /// The request panicked; the runtime recovered and keeps running.
+    #[error("Request panicked: {0}")]
+    Panicked(String),
+}
+
+/// The message of a panic payload (`panic!("...")` or a formatted one).
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// An error an action handler reported, with `ctx:fail()` or by raising
+/// `error({ code = ..., message = ... })`.
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+#[error("{message}")]
+pub struct HandlerError {
+    /// Short machine-readable code (e.g. "timeout"), counted in the stats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// What went wrong, shown to the user.
+    pub message: String,
+    /// More about it (a status code, a command's output), shown below the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Whether running the action again may work; the UI then offers Retry.
+    #[serde(default)]
+    pub retryable: bool,
+}
+
+impl HandlerError {
+    /// Code of errors that don't set one.
+    pub const DEFAULT_CODE: &'static str = "error";
+
+    /// Create an error with just a message.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            code: None,
+            message: message.into(),
+            detail: None,
+            retryable: false,
+        }
+    }
+
+    /// Set the code.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Set the detail.
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    /// Set whether running the action again may work.
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// The code, or `DEFAULT_CODE`.
+    pub fn code(&self) -> &str {
+        self.code.as_deref().unwrap_or(Self::DEFAULT_CODE)
+    }
+}
+
+impl From<String> for HandlerError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for HandlerError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
 }
 
 /// Configuration errors.
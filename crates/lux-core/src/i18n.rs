@@ -0,0 +1,322 @@
+//! Translation of built-in strings and locale-aware number formatting.
+//!
+//! This module provides:
+//! - `t()` / `t_args()` - Translate a string for the current locale
+//! - `set_locale()` / `locale()` - Select the locale (e.g. "de-DE")
+//! - `add_strings()` - Register translations at runtime (used by `lux.i18n.add`)
+//! - `format_number()` - Numbers with the locale's separators
+//!
+//! Keys are the English source strings (gettext style), so untranslated text
+//! falls back to English without a separate English catalog. Placeholders
+//! are written `{name}` and filled in by `t_args()`.
+//!
+//! Built-in catalogs live in `locales/<language>.json`:
+//!
+//! ```json
+//! {
+//!   "format": { "decimal": ",", "group": "." },
+//!   "strings": { "No results": "Keine Ergebnisse" }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use serde::Deserialize;
+
+/// Locale used when none is set or detected.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Built-in catalogs, keyed by language code.
+const BUILTIN_CATALOGS: &[(&str, &str)] = &[
+    ("de", include_str!("../locales/de.json")),
+    ("es", include_str!("../locales/es.json")),
+    ("fr", include_str!("../locales/fr.json")),
+];
+
+/// Number formatting conventions of a locale.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct NumberFormat {
+    /// Decimal separator.
+    pub decimal: String,
+    /// Thousands separator.
+    pub group: String,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal: ".".to_string(),
+            group: ",".to_string(),
+        }
+    }
+}
+
+/// A locale file.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct Catalog {
+    #[serde(default)]
+    format: Option<NumberFormat>,
+    #[serde(default)]
+    strings: HashMap<String, String>,
+}
+
+/// Translations for every known locale plus the selected locale.
+#[derive(Debug, Default)]
+pub struct Translations {
+    /// Catalogs keyed by locale ("de", "de-AT", ...).
+    catalogs: HashMap<String, Catalog>,
+    /// Selected locale, normalized (e.g. "de-DE").
+    locale: String,
+}
+
+impl Translations {
+    /// Create translations holding the built-in catalogs, set to English.
+    pub fn builtin() -> Self {
+        let catalogs = BUILTIN_CATALOGS
+            .iter()
+            .map(|(language, json)| {
+                let catalog = serde_json::from_str(json)
+                    .unwrap_or_else(|e| panic!("Invalid built-in locale '{}': {}", language, e));
+                (language.to_string(), catalog)
+            })
+            .collect();
+        Self {
+            catalogs,
+            locale: DEFAULT_LOCALE.to_string(),
+        }
+    }
+
+    /// Select a locale. Accepts POSIX (`de_DE.UTF-8`) and BCP 47 (`de-DE`) forms.
+    pub fn set_locale(&mut self, locale: &str) {
+        self.locale = normalize_locale(locale);
+    }
+
+    /// The selected locale.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Add (or override) translations for a locale.
+    pub fn add_strings(&mut self, locale: &str, strings: HashMap<String, String>) {
+        self.catalogs
+            .entry(normalize_locale(locale))
+            .or_default()
+            .strings
+            .extend(strings);
+    }
+
+    /// Catalogs to consult for the selected locale, most specific first.
+    fn candidates(&self) -> impl Iterator<Item = &Catalog> {
+        let language = self.locale.split('-').next().unwrap_or_default();
+        [self.locale.as_str(), language]
+            .into_iter()
+            .filter_map(|locale| self.catalogs.get(locale))
+    }
+
+    /// Translate a string (returns the key when there is no translation).
+    pub fn t(&self, key: &str) -> String {
+        self.candidates()
+            .find_map(|catalog| catalog.strings.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Translate a string and fill in its `{name}` placeholders.
+    pub fn t_args(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.t(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+
+    /// Number format of the selected locale.
+    pub fn number_format(&self) -> NumberFormat {
+        self.candidates()
+            .find_map(|catalog| catalog.format.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Normalize a locale identifier: `de_DE.UTF-8` → `de-DE`, `C` → `en`.
+pub fn normalize_locale(locale: &str) -> String {
+    let locale = locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .replace('_', "-");
+    match locale.as_str() {
+        "" | "C" | "POSIX" => DEFAULT_LOCALE.to_string(),
+        _ => {
+            let mut parts = locale.splitn(2, '-');
+            let language = parts.next().unwrap_or_default().to_lowercase();
+            match parts.next() {
+                Some(region) => format!("{}-{}", language, region),
+                None => language,
+            }
+        }
+    }
+}
+
+/// Detect the locale from `LUX_LOCALE`, `LC_ALL`, `LC_MESSAGES`, or `LANG`.
+pub fn detect_locale() -> Option<String> {
+    ["LUX_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .map(|value| normalize_locale(&value))
+}
+
+/// Format a number with `decimals` fraction digits and the given separators.
+pub fn format_number_with(value: f64, decimals: usize, format: &NumberFormat) -> String {
+    let text = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = match text.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text.as_str(), None),
+    };
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push_str(&format.group);
+        }
+        grouped.push(digit);
+    }
+
+    let sign = if value < 0.0 && text.chars().any(|c| matches!(c, '1'..='9')) {
+        "-"
+    } else {
+        ""
+    };
+    match fraction {
+        Some(fraction) => format!("{}{}{}{}", sign, grouped, format.decimal, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+// =============================================================================
+// Global Translations
+// =============================================================================
+
+static TRANSLATIONS: LazyLock<RwLock<Translations>> =
+    LazyLock::new(|| RwLock::new(Translations::builtin()));
+
+/// Translate a string for the current locale.
+pub fn t(key: &str) -> String {
+    TRANSLATIONS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .t(key)
+}
+
+/// Translate a string for the current locale and fill in its placeholders.
+pub fn t_args(key: &str, args: &[(&str, &str)]) -> String {
+    TRANSLATIONS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .t_args(key, args)
+}
+
+/// Select the current locale.
+pub fn set_locale(locale: &str) {
+    TRANSLATIONS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .set_locale(locale);
+}
+
+/// The current locale (e.g. "de-DE").
+pub fn locale() -> String {
+    TRANSLATIONS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .locale()
+        .to_string()
+}
+
+/// Add (or override) translations for a locale.
+pub fn add_strings(locale: &str, strings: HashMap<String, String>) {
+    TRANSLATIONS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .add_strings(locale, strings);
+}
+
+/// Format a number with the current locale's separators.
+pub fn format_number(value: f64, decimals: usize) -> String {
+    let format = TRANSLATIONS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .number_format();
+    format_number_with(value, decimals, &format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize_locale("de_DE.UTF-8"), "de-DE");
+        assert_eq!(normalize_locale("fr"), "fr");
+        assert_eq!(normalize_locale("EN_us"), "en-us");
+        assert_eq!(normalize_locale("C"), "en");
+    }
+
+    #[test]
+    fn test_translate_with_fallback() {
+        let mut translations = Translations::builtin();
+        assert_eq!(translations.t("No results"), "No results");
+
+        translations.set_locale("de_AT.UTF-8");
+        assert_eq!(translations.t("No results"), "Keine Ergebnisse");
+        assert_eq!(translations.t("Untranslated"), "Untranslated");
+        assert_eq!(
+            translations.t_args("Set Volume to {level}%", &[("level", "40")]),
+            "Lautstärke auf 40 % setzen"
+        );
+    }
+
+    #[test]
+    fn test_added_strings_prefer_region() {
+        let mut translations = Translations::builtin();
+        translations.add_strings("de", HashMap::from([("Hello".into(), "Hallo".into())]));
+        translations.add_strings("de-CH", HashMap::from([("Hello".into(), "Grüezi".into())]));
+
+        translations.set_locale("de-CH");
+        assert_eq!(translations.t("Hello"), "Grüezi");
+        translations.set_locale("de-DE");
+        assert_eq!(translations.t("Hello"), "Hallo");
+    }
+
+    #[test]
+    fn test_builtin_catalogs_keep_placeholders() {
+        let translations = Translations::builtin();
+        for (language, catalog) in &translations.catalogs {
+            for (key, value) in &catalog.strings {
+                for placeholder in key.split('{').skip(1).filter_map(|s| s.split_once('}')) {
+                    assert!(
+                        value.contains(&format!("{{{}}}", placeholder.0)),
+                        "{}: '{}' lost placeholder {{{}}}",
+                        language,
+                        key,
+                        placeholder.0
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_number() {
+        let en = NumberFormat::default();
+        let de = NumberFormat {
+            decimal: ",".to_string(),
+            group: ".".to_string(),
+        };
+        assert_eq!(format_number_with(1234567.891, 2, &en), "1,234,567.89");
+        assert_eq!(format_number_with(1234567.891, 2, &de), "1.234.567,89");
+        assert_eq!(format_number_with(-999.0, 0, &en), "-999");
+        assert_eq!(format_number_with(-0.001, 1, &en), "0.0");
+    }
+}
@@ -1,6 +1,7 @@
 //! Item and Group types for search results.
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::hash::Hash;
 
 /// Stable item identifier.
@@ -52,6 +53,22 @@ pub struct Item {
     /// Arbitrary data for actions to consume.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
+
+    /// Short metadata shown at the end of the row (e.g. a user or a branch).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accessories: Vec<String>,
+
+    /// Payload handed to other apps when the row is dragged out of the launcher.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drag: Option<DragPayload>,
+
+    /// How the row lays out the subtitle and truncates long text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<ItemDisplay>,
+
+    /// Other names the item is found by, e.g. a romanization of its title.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub match_aliases: Vec<String>,
 }
 
 impl Item {
@@ -64,9 +81,55 @@ impl Item {
             icon: None,
             types: Vec::new(),
             data: None,
+            accessories: Vec::new(),
+            drag: None,
+            display: None,
+            match_aliases: Vec::new(),
         }
     }
 
+    /// Set the secondary display text.
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    /// Set the icon (path, emoji, or named icon).
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Add a type tag for action filtering.
+    pub fn with_type(mut self, type_name: impl Into<String>) -> Self {
+        self.types.push(type_name.into());
+        self
+    }
+
+    /// Set the data handed to actions.
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Attach a drag payload to this item.
+    pub fn with_drag(mut self, drag: DragPayload) -> Self {
+        self.drag = Some(drag);
+        self
+    }
+
+    /// Set how the row lays out the subtitle and truncates long text.
+    pub fn with_display(mut self, display: ItemDisplay) -> Self {
+        self.display = Some(display);
+        self
+    }
+
+    /// Add an accessory shown at the end of the row.
+    pub fn with_accessory(mut self, accessory: impl Into<String>) -> Self {
+        self.accessories.push(accessory.into());
+        self
+    }
+
     /// Check if this item has a specific type tag.
     pub fn has_type(&self, type_name: &str) -> bool {
         self.types.iter().any(|t| t == type_name)
@@ -76,6 +139,117 @@ impl Item {
     pub fn item_id(&self) -> ItemId {
         ItemId(self.id.clone())
     }
+
+    /// Number of text lines the row is tall (1 unless `display` asks for more).
+    pub fn row_lines(&self) -> usize {
+        self.display
+            .as_ref()
+            .map_or(1, |display| display.row_lines(self.subtitle.is_some()))
+    }
+}
+
+/// How a result row shows an item.
+///
+/// Lua declares this as
+/// `display = { subtitle = "below", truncate = "middle", lines = 2 }`;
+/// every field is optional.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemDisplay {
+    /// Where the subtitle goes.
+    pub subtitle: SubtitlePosition,
+    /// Where text too long for the row is cut.
+    pub truncate: Truncate,
+    /// Lines of text in the row (1 to `MAX_LINES`). A subtitle below the
+    /// title takes the second line; without one, the title wraps into it.
+    pub lines: u8,
+}
+
+impl ItemDisplay {
+    /// Most lines of text a row can have.
+    pub const MAX_LINES: u8 = 2;
+
+    /// Number of text lines the row is tall.
+    pub fn row_lines(&self, has_subtitle: bool) -> usize {
+        let lines = self.lines.clamp(1, Self::MAX_LINES) as usize;
+        if has_subtitle && self.subtitle == SubtitlePosition::Below {
+            lines.max(2)
+        } else {
+            lines
+        }
+    }
+
+    /// Lines the title may wrap to, leaving room for a subtitle below it.
+    pub fn title_lines(&self, has_subtitle: bool) -> usize {
+        let below = has_subtitle && self.subtitle == SubtitlePosition::Below;
+        self.row_lines(has_subtitle) - below as usize
+    }
+}
+
+impl Default for ItemDisplay {
+    fn default() -> Self {
+        Self {
+            subtitle: SubtitlePosition::default(),
+            truncate: Truncate::default(),
+            lines: 1,
+        }
+    }
+}
+
+/// Where a row shows the subtitle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitlePosition {
+    /// After the title, on the same line.
+    #[default]
+    Trailing,
+    /// On its own line, under the title.
+    Below,
+}
+
+/// Where text too long for the row is cut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Truncate {
+    /// Keep the start: "Quarterly report for…"
+    #[default]
+    End,
+    /// Keep both ends, e.g. of a path: "~/Documents/…/report.pdf"
+    Middle,
+}
+
+/// `text` cut to at most `max_chars` characters by replacing its middle
+/// with "…".
+pub fn truncate_middle(text: &str, max_chars: usize) -> Cow<'_, str> {
+    let len = text.chars().count();
+    if len <= max_chars {
+        return Cow::Borrowed(text);
+    }
+    if max_chars == 0 {
+        return Cow::Borrowed("");
+    }
+    // The end usually says more (a file name), so it keeps the odd character
+    let keep = max_chars - 1;
+    let head = keep / 2;
+    let tail = keep - head;
+    let mut cut: String = text.chars().take(head).collect();
+    cut.push('…');
+    cut.extend(text.chars().skip(len - tail));
+    Cow::Owned(cut)
+}
+
+/// What a result row carries when dragged into another application.
+///
+/// Lua declares this as `drag = { type = "file", path = "..." }`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DragPayload {
+    /// A file on disk (dropped as a file URL).
+    File { path: String },
+    /// A web URL.
+    Url { url: String },
+    /// Plain text.
+    Text { text: String },
 }
 
 /// A group of items with an optional title.
@@ -90,6 +264,11 @@ pub struct Group {
 
     /// Items in this group.
     pub items: Vec<Item>,
+
+    /// Whether the user can fold the group away under its header.
+    /// Only groups with a title can collapse.
+    #[serde(default)]
+    pub collapsible: bool,
 }
 
 impl Group {
@@ -98,12 +277,23 @@ impl Group {
         Self {
             title: Some(title.into()),
             items,
+            collapsible: false,
         }
     }
 
     /// Create an ungrouped group (no title).
     pub fn ungrouped(items: Vec<Item>) -> Self {
-        Self { title: None, items }
+        Self {
+            title: None,
+            items,
+            collapsible: false,
+        }
+    }
+
+    /// Let the user fold the group away under its header.
+    pub fn with_collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
     }
 
     /// Check if the group is empty.
@@ -119,3 +309,58 @@ impl Group {
 
 /// A collection of groups returned by sources.
 pub type Groups = Vec<Group>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("short.txt", 20), "short.txt");
+        assert_eq!(
+            truncate_middle("~/Documents/Reports/2026/report.pdf", 21),
+            "~/Document…report.pdf"
+        );
+        assert_eq!(truncate_middle("abcdef", 1), "…");
+        assert_eq!(truncate_middle("abcdef", 0), "");
+    }
+
+    #[test]
+    fn test_builder() {
+        let item = Item::new("a", "A")
+            .with_subtitle("Sub")
+            .with_icon("📄")
+            .with_type("file")
+            .with_type("pdf")
+            .with_data(serde_json::json!({ "path": "/a.pdf" }))
+            .with_accessory("1 KB");
+        assert_eq!(item.subtitle.as_deref(), Some("Sub"));
+        assert_eq!(item.icon.as_deref(), Some("📄"));
+        assert!(item.has_type("file") && item.has_type("pdf"));
+        assert_eq!(item.data.unwrap()["path"], "/a.pdf");
+        assert_eq!(item.accessories, ["1 KB"]);
+    }
+
+    #[test]
+    fn test_row_lines() {
+        let below = ItemDisplay {
+            subtitle: SubtitlePosition::Below,
+            ..ItemDisplay::default()
+        };
+        let item = Item::new("a", "A").with_display(below);
+        assert_eq!(item.row_lines(), 1);
+
+        let mut with_subtitle = item.clone();
+        with_subtitle.subtitle = Some("b".to_string());
+        assert_eq!(with_subtitle.row_lines(), 2);
+        assert_eq!(below.title_lines(true), 1);
+
+        let wrapping = ItemDisplay {
+            lines: 9,
+            ..ItemDisplay::default()
+        };
+        assert_eq!(wrapping.row_lines(true), 2);
+        assert_eq!(wrapping.title_lines(true), 2);
+        assert_eq!(Item::new("b", "B").row_lines(), 1);
+    }
+}
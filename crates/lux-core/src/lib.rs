@@ -5,19 +5,27 @@
 //! - Selection modes
 //! - Action results
 //! - Configuration types
+//! - Translation of built-in strings (`i18n`)
 //! - Error types
+//! - Lua conversions for items and groups (`lua` feature)
 
 mod action;
 mod config;
 mod error;
+pub mod i18n;
 mod item;
+#[cfg(feature = "lua")]
+pub mod lua;
 mod selection;
 
-pub use action::{ActionInfo, ActionResult, FollowUpAction};
+pub use action::{ActionInfo, ActionResult, FollowUpAction, InlineEdit};
 pub use config::{
-    config_dir, ensure_config_dir, init_lua_path, AppConfig, AppearanceConfig, HotkeyConfig,
-    ThemeMode,
+    config_dir, ensure_config_dir, find_init_lua, init_lua_path, AppConfig, AppearanceConfig,
+    HotkeyConfig, ThemeMode,
+};
+pub use error::{panic_message, BackendError, ConfigError, HandlerError};
+pub use item::{
+    truncate_middle, DragPayload, Group, Groups, Item, ItemDisplay, ItemId, SubtitlePosition,
+    Truncate,
 };
-pub use error::{BackendError, ConfigError};
-pub use item::{Group, Groups, Item, ItemId};
 pub use selection::SelectionMode;
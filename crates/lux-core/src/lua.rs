@@ -0,0 +1,678 @@
+//! Lua conversions for the core types (the `lua` feature).
+//!
+//! This module provides:
+//! - `FromLua` for `Item`, `Group`, and `DragPayload` - Read results straight from Lua tables
+//! - `FromLua` for `HandlerError` - A message, or a `{ code, message, detail, retryable }` table
+//! - `parse_groups()` / `parse_items()` - The same, skipping malformed entries if asked
+//! - `SchemaError` - Which group, item, and field is malformed, and how
+//! - `lua_to_json()` - Convert a Lua value to JSON (an item's `data`)
+//!
+//! Sources hand back their results as Lua tables on every keystroke, so items
+//! are read field by field without an intermediate JSON value. Every field is
+//! type-checked, so a plugin that returns `types = "file"` learns which item
+//! is wrong instead of getting a generic conversion error.
+//!
+//! ```lua
+//! ctx:set_groups({
+//!     { title = "Files", items = { { title = "a.txt", types = { "file" } } } },
+//! })
+//! ```
+
+use std::fmt;
+
+use mlua::{Error, FromLua, Lua, Result, Table, Value};
+
+use crate::{DragPayload, Group, HandlerError, Item, ItemDisplay, SubtitlePosition, Truncate};
+
+impl FromLua for Item {
+    fn from_lua(value: Value, _lua: &Lua) -> Result<Self> {
+        read_item(value).map_err(Error::external)
+    }
+}
+
+impl FromLua for Group {
+    fn from_lua(value: Value, _lua: &Lua) -> Result<Self> {
+        read_group(value, OnInvalid::Fail)
+            .map(|(group, _)| group)
+            .map_err(Error::external)
+    }
+}
+
+/// Expected shapes:
+/// ```lua
+/// drag = { type = "file", path = "/path/to/file" }
+/// drag = { type = "url", url = "https://..." }
+/// drag = { type = "text", text = "..." }
+/// ```
+impl FromLua for DragPayload {
+    fn from_lua(value: Value, _lua: &Lua) -> Result<Self> {
+        read_drag(value).map_err(|e| Error::RuntimeError(e.message))
+    }
+}
+
+/// Expected shapes:
+/// ```lua
+/// "Server unreachable"
+/// { code = "timeout", message = "Server unreachable", detail = "after 10 s", retryable = true }
+/// ```
+impl FromLua for HandlerError {
+    fn from_lua(value: Value, _lua: &Lua) -> Result<Self> {
+        read_handler_error(value).map_err(|e| Error::RuntimeError(e.to_string()))
+    }
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// What to do with a malformed group or item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnInvalid {
+    /// Fail with the first problem.
+    #[default]
+    Fail,
+    /// Leave the malformed group or item out, and report it.
+    Skip,
+}
+
+/// A malformed group or item: where it is, and what is wrong with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// Index of the group (1-based, as in Lua).
+    pub group: Option<usize>,
+    /// Index of the item within its group (1-based).
+    pub item: Option<usize>,
+    /// The offending field (e.g. `types[2]`), if it's a field.
+    pub field: Option<String>,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl SchemaError {
+    fn new(field: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            group: None,
+            item: None,
+            field: field.map(str::to_string),
+            message: message.into(),
+        }
+    }
+
+    /// `field` has a value of the wrong type.
+    fn wrong_type(field: &str, expected: &str, value: &Value) -> Self {
+        Self::new(
+            Some(field),
+            format!(
+                "'{}' should be {}, not {}",
+                field,
+                expected,
+                value.type_name()
+            ),
+        )
+    }
+
+    fn lua(error: Error) -> Self {
+        Self::new(None, error.to_string())
+    }
+
+    fn in_item(mut self, item: usize) -> Self {
+        self.item = Some(item);
+        self
+    }
+
+    fn in_group(mut self, group: usize) -> Self {
+        self.group = Some(group);
+        self
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location: Vec<String> = [("group", self.group), ("item", self.item)]
+            .into_iter()
+            .filter_map(|(name, index)| index.map(|i| format!("{} {}", name, i)))
+            .collect();
+        if location.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.message)
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+type Checked<T> = std::result::Result<T, SchemaError>;
+
+/// Read an array of groups, checking every field.
+///
+/// Returns the groups and, with `OnInvalid::Skip`, the problems of the
+/// groups and items that were left out.
+pub fn parse_groups(
+    table: Table,
+    on_invalid: OnInvalid,
+) -> Checked<(Vec<Group>, Vec<SchemaError>)> {
+    let mut groups = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, value) in entries(table)? {
+        match read_group(value, on_invalid) {
+            Ok((group, problems)) => {
+                groups.push(group);
+                skipped.extend(problems.into_iter().map(|e| e.in_group(index)));
+            }
+            Err(e) if on_invalid == OnInvalid::Skip => skipped.push(e.in_group(index)),
+            Err(e) => return Err(e.in_group(index)),
+        }
+    }
+    Ok((groups, skipped))
+}
+
+/// Read an array of items, checking every field (see `parse_groups`).
+pub fn parse_items(table: Table, on_invalid: OnInvalid) -> Checked<(Vec<Item>, Vec<SchemaError>)> {
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, value) in entries(table)? {
+        match read_item(value) {
+            Ok(item) => items.push(item),
+            Err(e) if on_invalid == OnInvalid::Skip => skipped.push(e.in_item(index)),
+            Err(e) => return Err(e.in_item(index)),
+        }
+    }
+    Ok((items, skipped))
+}
+
+fn read_group(value: Value, on_invalid: OnInvalid) -> Checked<(Group, Vec<SchemaError>)> {
+    let table = as_table(value, "Group")?;
+    let title = optional_string(&table, "title")?;
+    let items = match field(&table, "items")? {
+        Value::Table(items) => items,
+        Value::Nil => {
+            return Err(SchemaError::new(
+                Some("items"),
+                "Group requires 'items' field",
+            ))
+        }
+        other => return Err(SchemaError::wrong_type("items", "a list of items", &other)),
+    };
+    let collapsible = match field(&table, "collapsible")? {
+        Value::Nil => false,
+        Value::Boolean(collapsible) => collapsible,
+        other => return Err(SchemaError::wrong_type("collapsible", "a boolean", &other)),
+    };
+    let (items, skipped) = parse_items(items, on_invalid)?;
+    Ok((
+        Group {
+            title,
+            items,
+            collapsible,
+        },
+        skipped,
+    ))
+}
+
+fn read_item(value: Value) -> Checked<Item> {
+    let table = as_table(value, "Item")?;
+
+    let id = optional_string(&table, "id")?.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let title = optional_string(&table, "title")?
+        .ok_or_else(|| SchemaError::new(Some("title"), "Item requires 'title' field"))?;
+
+    let data = match field(&table, "data")? {
+        Value::Nil => None,
+        value => Some(lua_to_json(value).map_err(SchemaError::lua)?),
+    };
+    let drag = match field(&table, "drag")? {
+        Value::Nil => None,
+        value => Some(read_drag(value)?),
+    };
+    let display = match field(&table, "display")? {
+        Value::Nil => None,
+        value => Some(read_display(value)?),
+    };
+
+    Ok(Item {
+        id,
+        title,
+        subtitle: optional_string(&table, "subtitle")?,
+        icon: optional_string(&table, "icon")?,
+        types: string_list(&table, "types")?,
+        data,
+        accessories: string_list(&table, "accessories")?,
+        drag,
+        display,
+        match_aliases: string_list(&table, "match_aliases")?,
+    })
+}
+
+/// Expected shape (every field optional):
+/// ```lua
+/// display = { subtitle = "below", truncate = "middle", lines = 2 }
+/// ```
+fn read_display(value: Value) -> Checked<ItemDisplay> {
+    let table = match value {
+        Value::Table(table) => table,
+        other => return Err(SchemaError::wrong_type("display", "a table", &other)),
+    };
+    let invalid = |name: &str, value: &str, expected: &str| {
+        SchemaError::new(
+            Some("display"),
+            format!(
+                "Invalid display {} '{}'. Expected {}",
+                name, value, expected
+            ),
+        )
+    };
+
+    let mut display = ItemDisplay::default();
+    if let Some(subtitle) = optional_string(&table, "subtitle")? {
+        display.subtitle = match subtitle.as_str() {
+            "trailing" => SubtitlePosition::Trailing,
+            "below" => SubtitlePosition::Below,
+            _ => return Err(invalid("subtitle", &subtitle, "'trailing' or 'below'")),
+        };
+    }
+    if let Some(truncate) = optional_string(&table, "truncate")? {
+        display.truncate = match truncate.as_str() {
+            "end" => Truncate::End,
+            "middle" => Truncate::Middle,
+            _ => return Err(invalid("truncate", &truncate, "'end' or 'middle'")),
+        };
+    }
+    match field(&table, "lines")? {
+        Value::Nil => {}
+        Value::Integer(lines) if (1..=ItemDisplay::MAX_LINES as i64).contains(&lines) => {
+            display.lines = lines as u8;
+        }
+        Value::Integer(lines) => {
+            return Err(invalid(
+                "lines",
+                &lines.to_string(),
+                &format!("1 to {}", ItemDisplay::MAX_LINES),
+            ))
+        }
+        other => {
+            return Err(SchemaError::wrong_type(
+                "display.lines",
+                "an integer",
+                &other,
+            ))
+        }
+    }
+    Ok(display)
+}
+
+fn read_drag(value: Value) -> Checked<DragPayload> {
+    let table = match value {
+        Value::Table(table) => table,
+        other => {
+            return Err(SchemaError::wrong_type(
+                "drag",
+                "a table with a 'type'",
+                &other,
+            ))
+        }
+    };
+
+    let kind = optional_string(&table, "type")?
+        .ok_or_else(|| SchemaError::new(Some("drag"), "Drag requires 'type' field"))?;
+    let required = |name: &str| -> Checked<String> {
+        optional_string(&table, name)?.ok_or_else(|| {
+            SchemaError::new(
+                Some("drag"),
+                format!("Drag of type '{}' requires '{}' field", kind, name),
+            )
+        })
+    };
+
+    match kind.as_str() {
+        "file" => Ok(DragPayload::File {
+            path: required("path")?,
+        }),
+        "url" => Ok(DragPayload::Url {
+            url: required("url")?,
+        }),
+        "text" => Ok(DragPayload::Text {
+            text: required("text")?,
+        }),
+        _ => Err(SchemaError::new(
+            Some("drag"),
+            format!(
+                "Invalid drag type '{}'. Expected 'file', 'url', or 'text'",
+                kind
+            ),
+        )),
+    }
+}
+
+fn read_handler_error(value: Value) -> Checked<HandlerError> {
+    let table = match value {
+        Value::Table(table) => table,
+        Value::String(message) => {
+            return Ok(HandlerError::new(message.to_string_lossy()));
+        }
+        Value::Integer(n) => return Ok(HandlerError::new(n.to_string())),
+        Value::Number(n) => return Ok(HandlerError::new(n.to_string())),
+        other => {
+            return Err(SchemaError::new(
+                None,
+                format!(
+                    "Error should be a message or a table, not {}",
+                    other.type_name()
+                ),
+            ))
+        }
+    };
+
+    let message = optional_string(&table, "message")?
+        .ok_or_else(|| SchemaError::new(Some("message"), "Error requires 'message' field"))?;
+    let retryable = match field(&table, "retryable")? {
+        Value::Nil => false,
+        Value::Boolean(retryable) => retryable,
+        other => return Err(SchemaError::wrong_type("retryable", "a boolean", &other)),
+    };
+    Ok(HandlerError {
+        code: optional_string(&table, "code")?,
+        message,
+        detail: optional_string(&table, "detail")?,
+        retryable,
+    })
+}
+
+/// `value` as a table; `what` names it in the error.
+fn as_table(value: Value, what: &str) -> Checked<Table> {
+    match value {
+        Value::Table(table) => Ok(table),
+        other => Err(SchemaError::new(
+            None,
+            format!("{} should be a table, not {}", what, other.type_name()),
+        )),
+    }
+}
+
+/// The entries of the array `table` with their indexes, in order.
+fn entries(table: Table) -> Checked<Vec<(usize, Value)>> {
+    let mut entries = Vec::with_capacity(table.raw_len());
+    for pair in table.pairs::<Value, Value>() {
+        let (key, value) = pair.map_err(SchemaError::lua)?;
+        match key {
+            Value::Integer(index) if index >= 1 => entries.push((index as usize, value)),
+            Value::String(key) => {
+                return Err(SchemaError::new(
+                    None,
+                    format!(
+                        "Expected a list, but found the key '{}'",
+                        key.to_string_lossy()
+                    ),
+                ))
+            }
+            other => {
+                return Err(SchemaError::new(
+                    None,
+                    format!("Expected a list, but found a {} key", other.type_name()),
+                ))
+            }
+        }
+    }
+    entries.sort_unstable_by_key(|(index, _)| *index);
+    Ok(entries)
+}
+
+fn field(table: &Table, name: &str) -> Checked<Value> {
+    table.get(name).map_err(SchemaError::lua)
+}
+
+/// The string (or number) in `table[name]`, if set.
+fn optional_string(table: &Table, name: &str) -> Checked<Option<String>> {
+    match field(table, name)? {
+        Value::Nil => Ok(None),
+        Value::String(s) => Ok(Some(s.to_str().map_err(SchemaError::lua)?.to_string())),
+        Value::Integer(i) => Ok(Some(i.to_string())),
+        Value::Number(n) => Ok(Some(n.to_string())),
+        other => Err(SchemaError::wrong_type(name, "a string", &other)),
+    }
+}
+
+/// The strings in the array `table[name]`.
+fn string_list(table: &Table, name: &str) -> Checked<Vec<String>> {
+    let list = match field(table, name)? {
+        Value::Nil => return Ok(Vec::new()),
+        Value::Table(list) => list,
+        other => return Err(SchemaError::wrong_type(name, "a list of strings", &other)),
+    };
+    entries(list)
+        .map_err(|e| SchemaError::new(Some(name), format!("'{}': {}", name, e.message)))?
+        .into_iter()
+        .map(|(index, value)| match value {
+            Value::String(s) => Ok(s.to_str().map_err(SchemaError::lua)?.to_string()),
+            Value::Integer(i) => Ok(i.to_string()),
+            Value::Number(n) => Ok(n.to_string()),
+            other => Err(SchemaError::wrong_type(
+                &format!("{}[{}]", name, index),
+                "a string",
+                &other,
+            )),
+        })
+        .collect()
+}
+
+/// The values of a Lua array, converted in order.
+pub fn list<T: FromLua>(table: Table) -> Result<Vec<T>> {
+    let mut values = Vec::with_capacity(table.raw_len());
+    for pair in table.pairs::<i64, T>() {
+        let (_, value) = pair?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Convert a Lua value to JSON.
+///
+/// Tables with only integer keys become arrays, other tables objects.
+/// Functions, userdata, and non-finite numbers become null.
+pub fn lua_to_json(value: Value) -> Result<serde_json::Value> {
+    match value {
+        Value::Nil => Ok(serde_json::Value::Null),
+        Value::Boolean(b) => Ok(serde_json::Value::Bool(b)),
+        Value::Integer(i) => Ok(serde_json::Value::Number(i.into())),
+        Value::Number(n) => Ok(serde_json::Number::from_f64(n)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null)),
+        Value::String(s) => Ok(serde_json::Value::String(s.to_str()?.to_string())),
+        Value::Table(t) => {
+            // Check if it's an array or object
+            let is_array = t.clone().pairs::<i64, Value>().all(|r| r.is_ok());
+
+            if is_array && t.raw_len() > 0 {
+                let mut arr = Vec::new();
+                for pair in t.pairs::<i64, Value>() {
+                    let (_, v) = pair?;
+                    arr.push(lua_to_json(v)?);
+                }
+                Ok(serde_json::Value::Array(arr))
+            } else {
+                let mut obj = serde_json::Map::new();
+                for pair in t.pairs::<String, Value>() {
+                    let (k, v) = pair?;
+                    obj.insert(k, lua_to_json(v)?);
+                }
+                Ok(serde_json::Value::Object(obj))
+            }
+        }
+        _ => Ok(serde_json::Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_from_lua() {
+        let lua = Lua::new();
+        let table: Table = lua
+            .load(
+                r#"
+                return {
+                    { title = "Files", collapsible = true, items = {
+                        { id = "a", title = "a.txt", types = { "file" }, data = { size = 3 },
+                          drag = { type = "file", path = "/tmp/a.txt" } },
+                        { title = "b.txt", accessories = { "3 KB" }, match_aliases = { "bee" } },
+                    } },
+                    { items = {} },
+                }
+                "#,
+            )
+            .eval()
+            .unwrap();
+
+        let groups: Vec<Group> = list(table).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].title.as_deref(), Some("Files"));
+        assert_eq!(groups[1].title, None);
+        assert!(groups[0].collapsible);
+        assert!(!groups[1].collapsible);
+
+        let a = &groups[0].items[0];
+        assert_eq!(a.id, "a");
+        assert_eq!(a.types, vec!["file"]);
+        assert_eq!(a.data, Some(serde_json::json!({ "size": 3 })));
+        assert_eq!(
+            a.drag,
+            Some(DragPayload::File {
+                path: "/tmp/a.txt".to_string()
+            })
+        );
+
+        // Missing IDs are generated
+        let b = &groups[0].items[1];
+        assert!(!b.id.is_empty());
+        assert_eq!(b.accessories, vec!["3 KB"]);
+        assert_eq!(b.match_aliases, vec!["bee"]);
+        assert!(a.match_aliases.is_empty());
+
+        let err =
+            Item::from_lua(lua.load("return { id = 'x' }").eval().unwrap(), &lua).unwrap_err();
+        assert!(err.to_string().contains("Item requires 'title' field"));
+    }
+
+    #[test]
+    fn test_item_display_from_lua() {
+        let lua = Lua::new();
+        let item = |code: &str| Item::from_lua(lua.load(code).eval().unwrap(), &lua);
+
+        let path = item(
+            r#"return { title = "report.pdf", subtitle = "~/Documents/report.pdf",
+                        display = { subtitle = "below", truncate = "middle" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            path.display,
+            Some(ItemDisplay {
+                subtitle: SubtitlePosition::Below,
+                truncate: Truncate::Middle,
+                lines: 1,
+            })
+        );
+        assert_eq!(path.row_lines(), 2);
+
+        let message = item(r#"return { title = "hi", display = { lines = 2 } }"#).unwrap();
+        assert_eq!(message.display.unwrap().lines, 2);
+        assert_eq!(item(r#"return { title = "x" }"#).unwrap().display, None);
+
+        for (code, problem) in [
+            (
+                "return { title = 'x', display = 'below' }",
+                "should be a table",
+            ),
+            (
+                "return { title = 'x', display = { subtitle = 'above' } }",
+                "Invalid display subtitle 'above'",
+            ),
+            (
+                "return { title = 'x', display = { lines = 3 } }",
+                "Invalid display lines '3'. Expected 1 to 2",
+            ),
+        ] {
+            let err = item(code).unwrap_err().to_string();
+            assert!(err.contains(problem), "{}: {}", code, err);
+        }
+    }
+
+    #[test]
+    fn test_handler_error_from_lua() {
+        let lua = Lua::new();
+        let error = |code: &str| HandlerError::from_lua(lua.load(code).eval().unwrap(), &lua);
+
+        let full = error(
+            r#"return { code = "timeout", message = "Server unreachable",
+                        detail = "after 10 s", retryable = true }"#,
+        )
+        .unwrap();
+        assert_eq!(full.code(), "timeout");
+        assert_eq!(full.to_string(), "Server unreachable");
+        assert_eq!(full.detail.as_deref(), Some("after 10 s"));
+        assert!(full.retryable);
+
+        let plain = error(r#"return "Not found""#).unwrap();
+        assert_eq!(plain, HandlerError::new("Not found"));
+        assert_eq!(plain.code(), HandlerError::DEFAULT_CODE);
+
+        assert!(error(r#"return { code = "x" }"#)
+            .unwrap_err()
+            .to_string()
+            .contains("Error requires 'message' field"));
+        assert!(error(r#"return { message = "x", retryable = "yes" }"#).is_err());
+        assert!(error("return true").is_err());
+    }
+
+    #[test]
+    fn test_parse_groups_reports_locations() {
+        let lua = Lua::new();
+        let groups = || -> Table {
+            lua.load(
+                r#"
+                return {
+                    { title = "Files", items = { { title = "a.txt" } } },
+                    { items = {
+                        { title = "ok" },
+                        { title = "b.txt", types = "file" },
+                        { title = "c.txt", accessories = { "3 KB", {} } },
+                    } },
+                    "not a group",
+                }
+                "#,
+            )
+            .eval()
+            .unwrap()
+        };
+
+        let err = parse_groups(groups(), OnInvalid::Fail).unwrap_err();
+        assert_eq!((err.group, err.item), (Some(2), Some(2)));
+        assert_eq!(err.field.as_deref(), Some("types"));
+        assert_eq!(
+            err.to_string(),
+            "group 2, item 2: 'types' should be a list of strings, not string"
+        );
+
+        let (parsed, skipped) = parse_groups(groups(), OnInvalid::Skip).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].items.len(), 1);
+        let problems: Vec<String> = skipped.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            problems,
+            [
+                "group 2, item 2: 'types' should be a list of strings, not string",
+                "group 2, item 3: 'accessories[2]' should be a string, not table",
+                "group 3: Group should be a table, not string",
+            ]
+        );
+
+        // A single group instead of a list of groups
+        let single: Table = lua
+            .load("return { title = 'x', items = {} }")
+            .eval()
+            .unwrap();
+        let err = parse_groups(single, OnInvalid::Skip).unwrap_err();
+        assert!(err.to_string().contains("Expected a list"));
+    }
+}
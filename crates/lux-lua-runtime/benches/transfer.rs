@@ -0,0 +1,83 @@
+//! Cost of moving a result set from Lua to the UI.
+//!
+//! Run with `cargo bench -p lux-lua-runtime`. Each benchmark converts the
+//! same results table of `SIZES` items:
+//! - `from_lua` - Reading groups straight from the Lua table
+//! - `json_round_trip` - The serialization results used to go through
+//! - `runtime` - A full request: read on the Lua thread, moved to the caller
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lux_core::Group;
+use lux_lua_runtime::LuaRuntime;
+use mlua::{Lua, Table};
+
+const SIZES: &[usize] = &[100, 1000];
+
+/// Lua that builds a results table of `n` items, like a file source.
+fn results_script(n: usize) -> String {
+    format!(
+        r#"
+        local items = {{}}
+        for i = 1, {n} do
+            items[i] = {{
+                id = "file:" .. i,
+                title = "document-" .. i .. ".pdf",
+                subtitle = "~/Documents/project/document-" .. i .. ".pdf",
+                icon = "doc",
+                types = {{ "file", "pdf" }},
+                data = {{ path = "/Users/me/Documents/document-" .. i .. ".pdf", size = i * 1024 }},
+            }}
+        end
+        results = {{ {{ title = "Files", items = items }} }}
+        "#
+    )
+}
+
+fn results_lua(n: usize) -> Lua {
+    let lua = Lua::new();
+    lua.load(results_script(n)).exec().unwrap();
+    lua
+}
+
+fn read_groups(lua: &Lua) -> Vec<Group> {
+    let results: Table = lua.globals().get("results").unwrap();
+    lux_core::lua::list(results).unwrap()
+}
+
+fn bench_transfer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("transfer");
+    let tokio = tokio::runtime::Runtime::new().unwrap();
+
+    for &n in SIZES {
+        let lua = results_lua(n);
+        group.bench_with_input(BenchmarkId::new("from_lua", n), &lua, |b, lua| {
+            b.iter(|| read_groups(lua))
+        });
+
+        let groups = read_groups(&lua);
+        group.bench_with_input(
+            BenchmarkId::new("json_round_trip", n),
+            &groups,
+            |b, groups| {
+                b.iter(|| {
+                    let json = serde_json::to_value(groups).unwrap();
+                    serde_json::from_value::<Vec<Group>>(json).unwrap()
+                })
+            },
+        );
+
+        let runtime = LuaRuntime::new(results_lua(n));
+        group.bench_with_input(BenchmarkId::new("runtime", n), &runtime, |b, runtime| {
+            b.iter(|| {
+                tokio
+                    .block_on(runtime.with_lua(|lua| Ok(read_groups(lua))))
+                    .unwrap()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_transfer);
+criterion_main!(benches);
@@ -1,94 +1,141 @@
 //! Persistent Lua runtime with thread-safe async access.
 //!
 //! mlua::Lua is !Send, so we run it on a dedicated OS thread
-//! and communicate via channels.
+//! and communicate via a prioritized request queue (see `queue`).
+//! Results are moved back to the caller as is, without serialization.
+//! A request that panics fails with `BackendError::Panicked`; the thread
+//! and its Lua state keep serving the requests after it.
+//! While no requests are queued the thread collects garbage in small steps
+//! (see `GcSchedule`), so collection pauses do not land on a keystroke.
+//! On quit, `shutdown_and_wait` lets the running request finish and joins
+//! the thread, closing the Lua state.
 
-use std::sync::mpsc;
+mod queue;
+
+pub use queue::{LuaOutput, Priority, RequestOptions};
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use lux_core::BackendError;
+use lux_core::{panic_message, BackendError};
 use mlua::Lua;
+use parking_lot::Mutex;
 use tokio::sync::oneshot;
 
-/// Type alias for Lua closure functions.
-type LuaFn = Box<dyn FnOnce(&Lua) -> Result<serde_json::Value, String> + Send>;
+use queue::{LuaFn, Next, RequestQueue};
 
-/// Request types for the Lua runtime thread.
-pub enum LuaRequest {
-    /// Execute arbitrary code on the Lua thread.
-    WithLua {
-        func: LuaFn,
-        resp: oneshot::Sender<Result<serde_json::Value, String>>,
-    },
-    Shutdown,
+/// A closure run as part of a batch (see `LuaRuntime::run_batch`).
+pub type BatchFn = Box<dyn FnOnce(&Lua) -> Result<LuaOutput, String> + Send>;
+
+/// When the Lua thread collects garbage between requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GcSchedule {
+    /// How long the thread waits idle before each step.
+    pub interval: Duration,
+    /// Kilobytes of work per incremental step.
+    pub step_kbytes: i32,
+}
+
+impl Default for GcSchedule {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            step_kbytes: 64,
+        }
+    }
 }
 
 /// Persistent Lua runtime that runs on a dedicated OS thread.
 ///
 /// Since mlua::Lua is !Send, we cannot use it across async tasks.
 /// Instead, we spawn a dedicated thread that owns the Lua state
-/// and communicate with it via channels.
+/// and takes requests from a queue, most urgent first.
 pub struct LuaRuntime {
-    tx: mpsc::Sender<LuaRequest>,
-    _handle: JoinHandle<()>,
+    queue: Arc<RequestQueue>,
+    handle: Mutex<Option<JoinHandle<()>>>,
 }
 
 impl LuaRuntime {
-    /// Create a new Lua runtime. MUST use std::thread::spawn, NOT tokio::spawn.
+    /// Create a new Lua runtime with the default GC schedule.
     pub fn new(lua: Lua) -> Self {
-        let (tx, rx) = mpsc::channel();
+        Self::with_gc(lua, Some(GcSchedule::default()))
+    }
+
+    /// Create a new Lua runtime that collects garbage while idle on
+    /// `schedule` (None: only Lua's own collection runs).
+    ///
+    /// MUST use std::thread::spawn, NOT tokio::spawn.
+    pub fn with_gc(lua: Lua, schedule: Option<GcSchedule>) -> Self {
+        let queue = Arc::new(RequestQueue::new());
+        let thread_queue = queue.clone();
 
         // Dedicated OS thread - Lua stays here forever
         let handle = thread::spawn(move || {
             tracing::info!("Lua runtime thread started");
 
-            while let Ok(request) = rx.recv() {
-                match request {
-                    LuaRequest::WithLua { func, resp } => {
-                        let result = func(&lua);
-                        let _ = resp.send(result);
+            // Step until a cycle finishes, then wait for the next request
+            let mut collected = false;
+            loop {
+                let idle = schedule.filter(|_| !collected).map(|s| s.interval);
+                match thread_queue.next(idle) {
+                    Next::Request(request) => {
+                        // The caller gave up (timed out or dropped the future)
+                        if request.resp.is_closed() {
+                            continue;
+                        }
+                        // A panic (say, in one bad search) fails just this request
+                        let result =
+                            match panic::catch_unwind(AssertUnwindSafe(|| (request.func)(&lua))) {
+                                Ok(result) => result.map_err(BackendError::Lua),
+                                Err(payload) => {
+                                    let message = panic_message(payload.as_ref());
+                                    tracing::error!("Lua request panicked: {}", message);
+                                    Err(BackendError::Panicked(message))
+                                }
+                            };
+                        let _ = request.resp.send(result);
+                        collected = false;
                     }
-                    LuaRequest::Shutdown => {
-                        tracing::info!("Lua runtime thread shutting down");
-                        break;
+                    Next::Idle => {
+                        let step = schedule.map_or(0, |s| s.step_kbytes);
+                        match lua.gc_step_kbytes(step) {
+                            Ok(finished) => collected = finished,
+                            Err(e) => {
+                                tracing::warn!("Lua GC step failed: {}", e);
+                                collected = true;
+                            }
+                        }
                     }
+                    Next::Closed => break,
                 }
             }
+
+            tracing::info!("Lua runtime thread shutting down");
         });
 
         Self {
-            tx,
-            _handle: handle,
+            queue,
+            handle: Mutex::new(Some(handle)),
         }
     }
 
     /// Execute arbitrary code on the Lua thread.
     ///
     /// The closure receives a reference to the Lua state and can perform any operations.
-    /// The result is serialized to JSON and returned.
+    /// The result is moved back to the caller.
     pub async fn with_lua<F, T>(&self, f: F) -> Result<T, String>
     where
         F: FnOnce(&Lua) -> Result<T, String> + Send + 'static,
-        T: serde::de::DeserializeOwned + serde::Serialize + 'static,
+        T: Send + 'static,
     {
-        let (resp_tx, resp_rx) = oneshot::channel();
-
-        // Wrap the closure to return JSON
-        let boxed_fn: LuaFn = Box::new(move |lua| {
-            let result = f(lua)?;
-            serde_json::to_value(result).map_err(|e| e.to_string())
-        });
-
-        self.tx
-            .send(LuaRequest::WithLua {
-                func: boxed_fn,
-                resp: resp_tx,
+        self.request(RequestOptions::default(), f)
+            .await
+            .map_err(|e| match e {
+                BackendError::Lua(message) => message,
+                e => e.to_string(),
             })
-            .map_err(|e| e.to_string())?;
-
-        let json_result = resp_rx.await.map_err(|e| e.to_string())??;
-        serde_json::from_value(json_result).map_err(|e| e.to_string())
     }
 
     /// Execute with timeout wrapper.
@@ -98,17 +145,103 @@ impl LuaRuntime {
     pub async fn with_lua_timeout<F, T>(&self, timeout: Duration, f: F) -> Result<T, BackendError>
     where
         F: FnOnce(&Lua) -> Result<T, String> + Send + 'static,
-        T: serde::de::DeserializeOwned + serde::Serialize + 'static,
+        T: Send + 'static,
+    {
+        self.request_timeout(RequestOptions::default(), timeout, f)
+            .await
+    }
+
+    /// Execute on the Lua thread with the given priority and coalescing key.
+    ///
+    /// Returns `BackendError::Superseded` if a newer request with the same
+    /// coalescing key replaced this one before it ran.
+    pub async fn request<F, T>(&self, options: RequestOptions, f: F) -> Result<T, BackendError>
+    where
+        F: FnOnce(&Lua) -> Result<T, String> + Send + 'static,
+        T: Send + 'static,
     {
-        match tokio::time::timeout(timeout, self.with_lua(f)).await {
-            Ok(result) => result.map_err(BackendError::Lua),
+        let (resp_tx, resp_rx) = oneshot::channel();
+
+        // Erase the result type for the queue; it is downcast below
+        let boxed_fn: LuaFn = Box::new(move |lua| Ok(Box::new(f(lua)?) as LuaOutput));
+
+        if !self.queue.push(options, boxed_fn, resp_tx) {
+            return Err(BackendError::RuntimeUnavailable);
+        }
+
+        // The request is dropped unanswered if the runtime shuts down first
+        let output = resp_rx
+            .await
+            .map_err(|_| BackendError::RuntimeUnavailable)??;
+        output
+            .downcast::<T>()
+            .map(|value| *value)
+            .map_err(|_| BackendError::Lua("Unexpected result type".to_string()))
+    }
+
+    /// `request` with a timeout. If the timeout expires, returns a
+    /// BackendError::Timeout and the request is skipped if it has not run yet.
+    pub async fn request_timeout<F, T>(
+        &self,
+        options: RequestOptions,
+        timeout: Duration,
+        f: F,
+    ) -> Result<T, BackendError>
+    where
+        F: FnOnce(&Lua) -> Result<T, String> + Send + 'static,
+        T: Send + 'static,
+    {
+        match tokio::time::timeout(timeout, self.request(options, f)).await {
+            Ok(result) => result,
             Err(_) => Err(BackendError::Timeout { duration: timeout }),
         }
     }
 
-    /// Shutdown the Lua runtime thread.
+    /// Execute several closures in one trip to the Lua thread, in order.
+    ///
+    /// Returns each closure's result in its position; an error in one does
+    /// not stop the ones after it. Use this instead of several `request`s
+    /// when the calls belong to one user interaction.
+    pub async fn run_batch(
+        &self,
+        options: RequestOptions,
+        timeout: Duration,
+        calls: Vec<BatchFn>,
+    ) -> Result<Vec<Result<LuaOutput, String>>, BackendError> {
+        self.request_timeout(options, timeout, move |lua| {
+            Ok(calls.into_iter().map(|call| call(lua)).collect::<Vec<_>>())
+        })
+        .await
+    }
+
+    /// Shutdown the Lua runtime thread. Queued requests are dropped.
     pub fn shutdown(&self) {
-        let _ = self.tx.send(LuaRequest::Shutdown);
+        self.queue.close();
+    }
+
+    /// Shutdown and wait up to `timeout` for the Lua thread to finish.
+    ///
+    /// A request already running is waited for; the Lua state is closed
+    /// (running `__gc` finalizers) when the thread finishes. Returns false
+    /// if the thread is still busy at the timeout.
+    pub fn shutdown_and_wait(&self, timeout: Duration) -> bool {
+        self.shutdown();
+        let deadline = Instant::now() + timeout;
+        let mut handle = self.handle.lock();
+        loop {
+            match handle.take() {
+                None => return true,
+                Some(h) if h.is_finished() => {
+                    let _ = h.join();
+                    return true;
+                }
+                Some(h) => *handle = Some(h),
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
     }
 }
 
@@ -172,4 +305,126 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 42);
     }
+
+    #[tokio::test]
+    async fn test_stale_request_is_superseded() {
+        let runtime = LuaRuntime::new(Lua::new());
+        let search = || RequestOptions::new(Priority::Interactive).with_coalesce_key("search");
+
+        // Hold the Lua thread so both searches are queued together
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let busy = runtime.with_lua(move |_| {
+            let _ = release_rx.recv();
+            Ok(0)
+        });
+        let stale = runtime.request(search(), |_| Ok(1i32));
+        let latest = runtime.request(search(), |_| Ok(2i32));
+        let release = async move {
+            tokio::task::yield_now().await;
+            let _ = release_tx.send(());
+        };
+
+        let (busy, stale, latest, ()) = tokio::join!(busy, stale, latest, release);
+        assert_eq!(busy, Ok(0));
+        assert!(matches!(stale, Err(BackendError::Superseded)));
+        assert_eq!(latest.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_and_wait() {
+        let runtime = LuaRuntime::new(Lua::new());
+        let value: i32 = runtime.with_lua(|_| Ok(1)).await.unwrap();
+        assert_eq!(value, 1);
+        assert!(runtime.shutdown_and_wait(Duration::from_secs(1)));
+        assert!(matches!(
+            runtime.request(RequestOptions::default(), |_| Ok(2)).await,
+            Err(BackendError::RuntimeUnavailable)
+        ));
+
+        // A request still running holds the thread past the timeout
+        let busy = LuaRuntime::new(Lua::new());
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let pending = busy.with_lua(move |_| {
+            let _ = started_tx.send(());
+            let _ = release_rx.recv();
+            Ok(())
+        });
+        let waited = async {
+            tokio::task::yield_now().await;
+            started_rx.recv().unwrap();
+            let stopped = busy.shutdown_and_wait(Duration::from_millis(20));
+            let _ = release_tx.send(());
+            stopped
+        };
+        let (_, stopped) = tokio::join!(pending, waited);
+        assert!(!stopped);
+        assert!(busy.shutdown_and_wait(Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_request_does_not_stop_runtime() {
+        let runtime = LuaRuntime::new(Lua::new());
+
+        let result: Result<i32, BackendError> = runtime
+            .request(RequestOptions::default(), |_| panic!("bad search"))
+            .await;
+        assert!(matches!(result, Err(BackendError::Panicked(m)) if m == "bad search"));
+
+        let value: i32 = runtime.with_lua(|_| Ok(7)).await.unwrap();
+        assert_eq!(value, 7);
+    }
+
+    #[tokio::test]
+    async fn test_idle_gc_collects_garbage() {
+        let schedule = GcSchedule {
+            interval: Duration::from_millis(5),
+            step_kbytes: 1024,
+        };
+        let runtime = LuaRuntime::with_gc(Lua::new(), Some(schedule));
+
+        let before: usize = runtime
+            .with_lua(|lua| {
+                lua.gc_stop();
+                lua.load("local t = {} for i = 1, 100000 do t[i] = tostring(i) end")
+                    .exec()
+                    .map_err(|e| e.to_string())?;
+                lua.gc_restart();
+                Ok(lua.used_memory())
+            })
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let after: usize = runtime.with_lua(|lua| Ok(lua.used_memory())).await.unwrap();
+        assert!(after < before / 2, "{} -> {}", before, after);
+    }
+
+    #[tokio::test]
+    async fn test_run_batch() {
+        let runtime = LuaRuntime::new(Lua::new());
+
+        let calls: Vec<BatchFn> = vec![
+            Box::new(|lua| {
+                lua.globals().set("counter", 1).map_err(|e| e.to_string())?;
+                Ok(Box::new(1_i64))
+            }),
+            Box::new(|_| Err("failed".to_string())),
+            // Later calls see the effects of earlier ones
+            Box::new(|lua| {
+                let counter: i64 = lua.globals().get("counter").map_err(|e| e.to_string())?;
+                Ok(Box::new(counter + 1))
+            }),
+        ];
+        let results = runtime
+            .run_batch(RequestOptions::default(), Duration::from_secs(1), calls)
+            .await
+            .unwrap();
+
+        let values: Vec<Result<i64, String>> = results
+            .into_iter()
+            .map(|result| result.map(|output| *output.downcast::<i64>().unwrap()))
+            .collect();
+        assert_eq!(values, vec![Ok(1), Err("failed".to_string()), Ok(2)]);
+    }
 }
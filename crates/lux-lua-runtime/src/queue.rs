@@ -0,0 +1,245 @@
+//! Prioritized request queue for the Lua thread.
+//!
+//! This module provides:
+//! - `Priority` - How urgently a request should run
+//! - `RequestOptions` - Priority and coalescing key of a request
+//! - `RequestQueue` - The queue the Lua thread takes requests from
+//!
+//! The Lua thread always runs the most urgent queued request next, oldest
+//! first within a priority, so a keystroke's search does not wait behind a
+//! background refresh. Requests sharing a coalescing key replace each other:
+//! a new request drops the queued ones with the same key and the same or a
+//! lower priority, which fail with `BackendError::Superseded` without running.
+
+use std::any::Any;
+use std::time::Duration;
+
+use lux_core::BackendError;
+use mlua::Lua;
+use parking_lot::{Condvar, Mutex};
+use tokio::sync::oneshot;
+
+/// The value a closure returns, handed back to the caller as is.
+pub type LuaOutput = Box<dyn Any + Send>;
+
+/// Type alias for Lua closure functions.
+pub(crate) type LuaFn = Box<dyn FnOnce(&Lua) -> Result<LuaOutput, String> + Send>;
+
+/// Channel the result of a request is sent back on.
+pub(crate) type Responder = oneshot::Sender<Result<LuaOutput, BackendError>>;
+
+/// How urgently a request should run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Work nobody is waiting on (periodic refreshes, timers).
+    Background,
+    /// Everything else.
+    #[default]
+    Normal,
+    /// Work the user is waiting on (searches as they type, key presses).
+    Interactive,
+}
+
+/// Priority and coalescing key of a request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// How urgently the request should run.
+    pub priority: Priority,
+    /// Requests with the same key replace each other while queued.
+    pub coalesce_key: Option<String>,
+}
+
+impl RequestOptions {
+    /// Create options with the given priority.
+    pub fn new(priority: Priority) -> Self {
+        Self {
+            priority,
+            coalesce_key: None,
+        }
+    }
+
+    /// Replace queued requests with the same key (and the same or a lower priority).
+    pub fn with_coalesce_key(mut self, key: impl Into<String>) -> Self {
+        self.coalesce_key = Some(key.into());
+        self
+    }
+}
+
+/// A request waiting for the Lua thread.
+pub(crate) struct Pending {
+    seq: u64,
+    options: RequestOptions,
+    pub(crate) func: LuaFn,
+    pub(crate) resp: Responder,
+}
+
+impl Pending {
+    /// Whether a new request with `options` replaces this one.
+    fn superseded_by(&self, options: &RequestOptions) -> bool {
+        options.coalesce_key.is_some()
+            && self.options.coalesce_key == options.coalesce_key
+            && self.options.priority <= options.priority
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: Vec<Pending>,
+    next_seq: u64,
+    closed: bool,
+}
+
+/// What the Lua thread should do next.
+pub(crate) enum Next {
+    /// Run this request.
+    Request(Pending),
+    /// Nothing was queued for the idle interval.
+    Idle,
+    /// The queue is closed; stop.
+    Closed,
+}
+
+/// Requests waiting for the Lua thread.
+#[derive(Default)]
+pub(crate) struct RequestQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+}
+
+impl RequestQueue {
+    /// Create an empty queue.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a request, dropping the queued requests it supersedes.
+    ///
+    /// Returns false (and drops the request) if the queue is closed.
+    pub(crate) fn push(&self, options: RequestOptions, func: LuaFn, resp: Responder) -> bool {
+        let mut state = self.state.lock();
+        if state.closed {
+            return false;
+        }
+
+        let (superseded, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut state.pending)
+            .into_iter()
+            .partition(|pending| pending.superseded_by(&options));
+        state.pending = kept;
+        for pending in superseded {
+            let _ = pending.resp.send(Err(BackendError::Superseded));
+        }
+
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.pending.push(Pending {
+            seq,
+            options,
+            func,
+            resp,
+        });
+        drop(state);
+
+        self.ready.notify_one();
+        true
+    }
+
+    /// Take the most urgent request, waiting for one to be queued.
+    ///
+    /// With an `idle` interval, returns `Next::Idle` if nothing is queued
+    /// within it; without one, waits until a request arrives.
+    pub(crate) fn next(&self, idle: Option<Duration>) -> Next {
+        let mut state = self.state.lock();
+        loop {
+            if state.closed {
+                return Next::Closed;
+            }
+            if let Some(pending) = Self::take_next(&mut state) {
+                return Next::Request(pending);
+            }
+            match idle {
+                Some(idle) => {
+                    if self.ready.wait_for(&mut state, idle).timed_out()
+                        && state.pending.is_empty()
+                        && !state.closed
+                    {
+                        return Next::Idle;
+                    }
+                }
+                None => self.ready.wait(&mut state),
+            }
+        }
+    }
+
+    /// Close the queue. Queued requests are dropped without running.
+    pub(crate) fn close(&self) {
+        let mut state = self.state.lock();
+        state.closed = true;
+        state.pending.clear();
+        drop(state);
+        self.ready.notify_all();
+    }
+
+    fn take_next(state: &mut QueueState) -> Option<Pending> {
+        let index = state
+            .pending
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, pending)| (pending.options.priority, std::cmp::Reverse(pending.seq)))
+            .map(|(index, _)| index)?;
+        Some(state.pending.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(
+        queue: &RequestQueue,
+        options: RequestOptions,
+        value: i64,
+    ) -> oneshot::Receiver<Result<LuaOutput, BackendError>> {
+        let (tx, rx) = oneshot::channel();
+        queue.push(options, Box::new(move |_| Ok(Box::new(value))), tx);
+        rx
+    }
+
+    #[test]
+    fn test_priority_order_and_coalescing() {
+        let lua = Lua::new();
+        let queue = RequestQueue::new();
+        let search = |priority| RequestOptions::new(priority).with_coalesce_key("search");
+
+        push(&queue, RequestOptions::new(Priority::Background), 1);
+        push(&queue, RequestOptions::default(), 2);
+        let mut stale = push(&queue, search(Priority::Interactive), 3);
+        push(&queue, search(Priority::Interactive), 4);
+        // A background refresh does not replace a queued interactive search
+        push(&queue, search(Priority::Background), 5);
+
+        assert!(matches!(
+            stale.try_recv(),
+            Ok(Err(BackendError::Superseded))
+        ));
+
+        let order: Vec<i64> = std::iter::from_fn(|| {
+            let mut state = queue.state.lock();
+            RequestQueue::take_next(&mut state)
+        })
+        .map(|pending| *(pending.func)(&lua).unwrap().downcast::<i64>().unwrap())
+        .collect();
+        assert_eq!(order, vec![4, 2, 1, 5]);
+
+        assert!(matches!(
+            queue.next(Some(Duration::from_millis(1))),
+            Next::Idle
+        ));
+        queue.close();
+        assert!(matches!(queue.next(None), Next::Closed));
+        assert!(!queue.push(
+            RequestOptions::default(),
+            Box::new(|_| Ok(Box::new(()))),
+            oneshot::channel().0
+        ));
+    }
+}
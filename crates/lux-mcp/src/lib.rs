@@ -0,0 +1,21 @@
+//! Lux MCP - Model Context Protocol server for Lux.
+//!
+//! Exposes the launcher's registered views and their actions as MCP tools,
+//! so AI assistants can search the same sources as the launcher (files,
+//! clipboard, bookmarks, ...) and run actions on the results.
+//!
+//! This crate provides:
+//! - `protocol` - JSON-RPC and MCP message types
+//! - `tools` - The tools and `LuxTools`, which runs them on the `QueryEngine`
+//! - `server` - `McpServer`, which serves the tools over stdio
+//!
+//! The `lux-mcp` binary loads `init.lua` exactly like the launcher does, so
+//! the tools see the user's configured views. Running an action always asks
+//! the user to confirm with a native dialog first.
+
+pub mod protocol;
+pub mod server;
+pub mod tools;
+
+pub use server::McpServer;
+pub use tools::{tool_definitions, LuxTools, ToolError, ToolHandler};
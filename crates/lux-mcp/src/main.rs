@@ -0,0 +1,118 @@
+//! Lux MCP server - main entry point.
+//!
+//! Loads the user's init.lua, initializes the query engine, and serves the
+//! MCP tools on stdin/stdout. Logs go to stderr, since stdout carries the
+//! protocol.
+
+use std::sync::Arc;
+
+use lux_lua_runtime::LuaRuntime;
+use lux_mcp::{LuxTools, McpServer};
+use lux_plugin_api::{lua::register_lux_api, PluginRegistry, QueryEngine};
+use mlua::{Function, Lua, MultiValue};
+
+/// Send Lua's `print` to stderr, so plugin output can't corrupt the protocol.
+fn print_to_stderr(lua: &Lua) -> mlua::Result<()> {
+    let print = lua.create_function(|lua, args: MultiValue| {
+        let tostring: Function = lua.globals().get("tostring")?;
+        let parts = args
+            .into_iter()
+            .map(|value| tostring.call::<String>(value))
+            .collect::<mlua::Result<Vec<_>>>()?;
+        eprintln!("{}", parts.join("\t"));
+        Ok(())
+    })?;
+    lua.globals().set("print", print)
+}
+
+/// Create the tools from the user's configuration.
+///
+/// This mirrors the launcher's startup: registry, locale, Lua API, init.lua,
+/// then the query engine and Lua runtime.
+fn create_tools() -> Result<Arc<LuxTools>, String> {
+    let registry = Arc::new(PluginRegistry::new());
+
+    let locale = lux_core::i18n::detect_locale()
+        .unwrap_or_else(|| lux_core::i18n::DEFAULT_LOCALE.to_string());
+    lux_core::i18n::set_locale(&locale);
+
+    let lua = Lua::new();
+    register_lux_api(&lua, registry.clone())
+        .map_err(|e| format!("Failed to register Lua API: {}", e))?;
+    print_to_stderr(&lua).map_err(|e| format!("Failed to redirect print: {}", e))?;
+
+    // Load init.lua if it exists (graceful degradation on error)
+    if let Some(config_path) = lux_core::find_init_lua() {
+        tracing::info!("Loading config from: {}", config_path.display());
+
+        match std::fs::read_to_string(&config_path) {
+            Ok(init_lua) => {
+                if let Err(e) = lua
+                    .load(&init_lua)
+                    // "@" makes error and warning locations read "path:line"
+                    .set_name(format!("@{}", config_path.display()))
+                    .exec()
+                {
+                    tracing::error!("init.lua error: {} - continuing with no plugins", e);
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to read init.lua: {} - continuing with no plugins",
+                    e
+                );
+            }
+        }
+    } else {
+        tracing::warn!("No init.lua found - no views to expose");
+    }
+
+    // IMPORTANT: Lua must be moved AFTER loading init.lua
+    let engine = Arc::new(QueryEngine::new(registry.clone()));
+    let runtime = Arc::new(LuaRuntime::new(lua));
+
+    Ok(Arc::new(LuxTools::new(engine, runtime, registry)))
+}
+
+fn main() {
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::INFO.into()),
+        )
+        .init();
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("Failed to create tokio runtime");
+    let _guard = rt.enter();
+
+    let tools = match create_tools() {
+        Ok(tools) => tools,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = rt.block_on(async {
+        tools
+            .initialize()
+            .await
+            .map_err(|e| format!("Initialization failed: {}", e))?;
+        tracing::info!("Lux MCP server ready on stdio");
+
+        McpServer::new(tools)
+            .run_stdio()
+            .await
+            .map_err(|e| format!("I/O error: {}", e))
+    });
+
+    if let Err(e) = result {
+        tracing::error!("{}", e);
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,81 @@
+//! JSON-RPC and MCP message types.
+//!
+//! This module provides:
+//! - `Request` - An incoming JSON-RPC request or notification
+//! - `success()` / `error()` - JSON-RPC response builders
+//! - `Tool` - A tool description returned by `tools/list`
+//! - `tool_result()` - The `tools/call` result wrapper
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// MCP protocol revision this server implements.
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Invalid JSON was received.
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON is not a valid request object.
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Invalid method parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// A JSON-RPC request. Requests without an `id` are notifications.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Request {
+    /// Request ID echoed in the response (`None` for notifications).
+    #[serde(default)]
+    pub id: Option<Value>,
+    /// Method name, e.g. "tools/call".
+    pub method: String,
+    /// Method parameters.
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// Build a successful response.
+pub fn success(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+/// Build an error response.
+pub fn error(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+/// A tool description.
+#[derive(Debug, Clone, Serialize)]
+pub struct Tool {
+    /// Tool name used in `tools/call`.
+    pub name: &'static str,
+    /// What the tool does, for the assistant.
+    pub description: &'static str,
+    /// JSON Schema of the arguments.
+    #[serde(rename = "inputSchema")]
+    pub input_schema: Value,
+    /// Behavior hints for the client.
+    pub annotations: ToolAnnotations,
+}
+
+/// Hints clients use to decide whether to ask before calling a tool.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolAnnotations {
+    /// The tool only reads.
+    pub read_only_hint: bool,
+    /// The tool may change or delete things.
+    pub destructive_hint: bool,
+}
+
+/// Wrap a tool's JSON output as a `tools/call` result.
+pub fn tool_result(output: &Value, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": output.to_string() }],
+        "isError": is_error,
+    })
+}
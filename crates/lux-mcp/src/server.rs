@@ -0,0 +1,219 @@
+//! MCP server over stdio.
+//!
+//! This module provides:
+//! - `McpServer` - Handles MCP requests with a `ToolHandler`
+//! - `McpServer::run_stdio()` - Serves newline-delimited JSON-RPC on stdin/stdout
+//!
+//! Supported methods: `initialize`, `ping`, `tools/list`, and `tools/call`.
+//! Notifications (requests without an `id`) are accepted and never answered.
+//! Tool failures are reported as `isError` results so the assistant can see
+//! them; protocol errors use JSON-RPC error responses.
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::protocol::{
+    self, Request, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR, PROTOCOL_VERSION,
+};
+use crate::tools::{tool_definitions, ToolHandler};
+
+/// An MCP server that runs tools with `H`.
+pub struct McpServer<H> {
+    handler: H,
+}
+
+impl<H: ToolHandler> McpServer<H> {
+    /// Create a server for `handler`.
+    pub fn new(handler: H) -> Self {
+        Self { handler }
+    }
+
+    /// Serve requests on stdin/stdout until stdin closes.
+    pub async fn run_stdio(&self) -> std::io::Result<()> {
+        self.serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout())
+            .await
+    }
+
+    /// Serve newline-delimited requests from `reader`, writing responses to `writer`.
+    pub async fn serve<R, W>(&self, reader: R, mut writer: W) -> std::io::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_line(&line).await {
+                writer.write_all(response.to_string().as_bytes()).await?;
+                writer.write_all(b"\n").await?;
+                writer.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle one JSON-RPC message. Returns `None` for notifications.
+    pub async fn handle_line(&self, line: &str) -> Option<Value> {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => return Some(protocol::error(Value::Null, PARSE_ERROR, e.to_string())),
+        };
+        let request: Request = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => return Some(protocol::error(Value::Null, INVALID_REQUEST, e.to_string())),
+        };
+
+        let id = request.id.clone()?;
+        Some(match self.handle(request).await {
+            Ok(result) => protocol::success(id, result),
+            Err((code, message)) => protocol::error(id, code, message),
+        })
+    }
+
+    /// Dispatch a request to its method.
+    async fn handle(&self, request: Request) -> Result<Value, (i64, String)> {
+        match request.method.as_str() {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "lux", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => {
+                let name = request.params["name"]
+                    .as_str()
+                    .ok_or_else(|| (INVALID_PARAMS, "missing tool name".to_string()))?
+                    .to_string();
+                let args = match request.params.get("arguments") {
+                    Some(Value::Null) | None => json!({}),
+                    Some(args) => args.clone(),
+                };
+
+                tracing::debug!("MCP tool call: {}", name);
+                Ok(match self.handler.call(name, args).await {
+                    Ok(output) => protocol::tool_result(&output, false),
+                    Err(e) => protocol::tool_result(&json!({ "error": e.to_string() }), true),
+                })
+            }
+            method => Err((METHOD_NOT_FOUND, format!("Unknown method '{}'", method))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolError;
+    use futures::future::BoxFuture;
+
+    /// Echoes its arguments for "echo" and knows no other tool.
+    struct EchoTools;
+
+    impl ToolHandler for EchoTools {
+        fn call(&self, name: String, args: Value) -> BoxFuture<'static, Result<Value, ToolError>> {
+            Box::pin(async move {
+                match name.as_str() {
+                    "echo" => Ok(args),
+                    _ => Err(ToolError::UnknownTool(name)),
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_initialize_and_list_tools() {
+        let server = McpServer::new(EchoTools);
+
+        let init = server
+            .handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#)
+            .await
+            .unwrap();
+        assert_eq!(init["id"], 1);
+        assert_eq!(init["result"]["protocolVersion"], PROTOCOL_VERSION);
+
+        let notification = server
+            .handle_line(r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#)
+            .await;
+        assert!(notification.is_none());
+
+        let list = server
+            .handle_line(r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#)
+            .await
+            .unwrap();
+        let tools = list["result"]["tools"].as_array().unwrap();
+        let names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["list_views", "search", "get_actions", "run_action"]);
+        assert_eq!(tools[3]["annotations"]["destructiveHint"], true);
+        assert!(tools[1]["inputSchema"]["properties"]["query"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_results() {
+        let server = McpServer::new(EchoTools);
+
+        let ok = server
+            .handle_line(
+                r#"{"jsonrpc":"2.0","id":"a","method":"tools/call","params":{"name":"echo","arguments":{"x":1}}}"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(ok["result"]["isError"], false);
+        assert_eq!(ok["result"]["content"][0]["text"], r#"{"x":1}"#);
+
+        let failed = server
+            .handle_line(
+                r#"{"jsonrpc":"2.0","id":"b","method":"tools/call","params":{"name":"nope"}}"#,
+            )
+            .await
+            .unwrap();
+        assert_eq!(failed["result"]["isError"], true);
+
+        let missing = server
+            .handle_line(r#"{"jsonrpc":"2.0","id":"c","method":"tools/call","params":{}}"#)
+            .await
+            .unwrap();
+        assert_eq!(missing["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_errors() {
+        let server = McpServer::new(EchoTools);
+
+        let parse = server.handle_line("{not json").await.unwrap();
+        assert_eq!(parse["error"]["code"], PARSE_ERROR);
+
+        let unknown = server
+            .handle_line(r#"{"jsonrpc":"2.0","id":3,"method":"resources/list"}"#)
+            .await
+            .unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_serve_writes_one_line_per_response() {
+        let server = McpServer::new(EchoTools);
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#,
+            "\n\n",
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#,
+            "\n",
+        );
+
+        let mut output = Vec::new();
+        server.serve(input.as_bytes(), &mut output).await.unwrap();
+
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["id"], 1);
+        assert_eq!(lines[1]["id"], 2);
+    }
+}
@@ -0,0 +1,347 @@
+//! Lux views and actions as MCP tools.
+//!
+//! This module provides:
+//! - `tool_definitions()` - The tools listed by `tools/list`
+//! - `ToolHandler` - Trait for running a tool call
+//! - `LuxTools` - Runs tool calls against the `QueryEngine`
+//!
+//! Every call starts at the root view. A call that names a `view` pushes that
+//! registered view first, and the stack is reset to the root afterwards, so
+//! calls don't depend on each other.
+//!
+//! `run_action` asks the user with a native dialog before running anything,
+//! whatever the assistant's client decides.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+use lux_lua_runtime::LuaRuntime;
+use lux_plugin_api::{desktop, ActionInfo, PluginRegistry, QueryEngine};
+use mlua::Lua;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::protocol::{Tool, ToolAnnotations};
+
+/// Default timeout for Lua calls made by tools.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Results returned per search unless the call sets `limit`.
+const DEFAULT_LIMIT: usize = 20;
+
+// =============================================================================
+// Tool Definitions
+// =============================================================================
+
+/// Schema properties shared by the tools that search a view.
+fn search_properties() -> Value {
+    json!({
+        "query": { "type": "string", "description": "Search text, as typed into the launcher" },
+        "view": { "type": "string", "description": "ID of a registered view (default: the root view)" },
+    })
+}
+
+/// The tools this server offers.
+pub fn tool_definitions() -> Vec<Tool> {
+    let read_only = ToolAnnotations {
+        read_only_hint: true,
+        destructive_hint: false,
+    };
+
+    let mut search = search_properties();
+    search["limit"] = json!({ "type": "integer", "description": "Maximum results (default 20)" });
+
+    let mut get_actions = search_properties();
+    get_actions["item_id"] = json!({ "type": "string", "description": "ID of a search result" });
+
+    let mut run_action = get_actions.clone();
+    run_action["action_id"] = json!({ "type": "string", "description": "ID from get_actions" });
+
+    vec![
+        Tool {
+            name: "list_views",
+            description: "List the launcher's registered views (files, clipboard, bookmarks, ...).",
+            input_schema: json!({ "type": "object", "properties": {} }),
+            annotations: read_only,
+        },
+        Tool {
+            name: "search",
+            description: "Search a launcher view and return its results grouped by section.",
+            input_schema: json!({ "type": "object", "properties": search, "required": ["query"] }),
+            annotations: read_only,
+        },
+        Tool {
+            name: "get_actions",
+            description: "List the actions available for a search result.",
+            input_schema: json!({
+                "type": "object",
+                "properties": get_actions,
+                "required": ["query", "item_id"],
+            }),
+            annotations: read_only,
+        },
+        Tool {
+            name: "run_action",
+            description: "Run an action on a search result. The user is asked to confirm first.",
+            input_schema: json!({
+                "type": "object",
+                "properties": run_action,
+                "required": ["query", "item_id", "action_id"],
+            }),
+            annotations: ToolAnnotations {
+                read_only_hint: false,
+                destructive_hint: true,
+            },
+        },
+    ]
+}
+
+// =============================================================================
+// Tool Handler
+// =============================================================================
+
+/// Runs tool calls by name.
+pub trait ToolHandler: Send + Sync {
+    /// Run a tool and return its JSON output.
+    fn call(&self, name: String, args: Value) -> BoxFuture<'static, Result<Value, ToolError>>;
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    query: String,
+    view: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct ActionArgs {
+    query: String,
+    view: Option<String>,
+    item_id: String,
+    action_id: Option<String>,
+}
+
+/// Runs tools against the query engine on the Lua thread.
+pub struct LuxTools {
+    engine: Arc<QueryEngine>,
+    runtime: Arc<LuaRuntime>,
+    registry: Arc<PluginRegistry>,
+    timeout: Duration,
+}
+
+impl LuxTools {
+    /// Create tools for an initialized engine.
+    pub fn new(
+        engine: Arc<QueryEngine>,
+        runtime: Arc<LuaRuntime>,
+        registry: Arc<PluginRegistry>,
+    ) -> Self {
+        Self {
+            engine,
+            runtime,
+            registry,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the timeout for Lua calls.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set up the root view. Call once after init.lua has loaded.
+    pub async fn initialize(&self) -> Result<(), ToolError> {
+        let engine = self.engine.clone();
+        self.runtime
+            .with_lua_timeout(self.timeout, move |lua| {
+                engine.initialize(lua);
+                Ok(())
+            })
+            .await
+            .map_err(|e| ToolError::Lua(e.to_string()))
+    }
+
+    /// Registered views the active profile allows.
+    fn list_views(registry: &PluginRegistry) -> Value {
+        let views = registry.views();
+        let profiles = registry.profiles();
+        let list: Vec<Value> = views
+            .list()
+            .into_iter()
+            .filter(|id| profiles.is_view_enabled(id))
+            .filter_map(|id| {
+                views.with_view(&id, |view| {
+                    json!({ "id": view.id, "title": view.title, "placeholder": view.placeholder })
+                })
+            })
+            .collect();
+        json!({ "views": list })
+    }
+
+    /// Run `f` on the Lua thread inside `view`, then reset to the root view.
+    async fn in_view<T, F>(&self, view: Option<String>, f: F) -> Result<T, ToolError>
+    where
+        F: FnOnce(&QueryEngine, &Lua) -> Result<T, String> + Send + 'static,
+        T: Send + 'static,
+    {
+        let view = match view {
+            Some(id) => {
+                if !self.registry.profiles().is_view_enabled(&id) {
+                    return Err(ToolError::UnknownView(id));
+                }
+                let view = self.registry.views().with_view(&id, |def| def.to_view());
+                Some(view.ok_or(ToolError::UnknownView(id))?)
+            }
+            None => None,
+        };
+
+        let engine = self.engine.clone();
+        self.runtime
+            .with_lua_timeout(self.timeout, move |lua| {
+                if let Some(view) = view {
+                    engine.push_view(lua, view);
+                }
+                let result = f(&engine, lua);
+                engine.pop_to_view(lua, 0);
+                result
+            })
+            .await
+            .map_err(|e| ToolError::Lua(e.to_string()))
+    }
+
+    /// Search and find the result with `item_id`.
+    fn find_item(
+        engine: &QueryEngine,
+        lua: &Lua,
+        query: &str,
+        item_id: &str,
+    ) -> Result<Item, String> {
+        engine
+            .search(lua, query)?
+            .into_iter()
+            .flat_map(|group| group.items)
+            .find(|item| item.id == item_id)
+            .ok_or_else(|| format!("No result with ID '{}'", item_id))
+    }
+
+    async fn search(&self, args: SearchArgs) -> Result<Value, ToolError> {
+        let limit = args.limit.unwrap_or(DEFAULT_LIMIT);
+        let query = args.query;
+        let mut groups = self
+            .in_view(args.view, move |engine, lua| engine.search(lua, &query))
+            .await?;
+
+        // Keep the first `limit` results across groups
+        let mut remaining = limit;
+        for group in &mut groups {
+            group.items.truncate(remaining);
+            remaining -= group.items.len();
+        }
+        groups.retain(|group| !group.items.is_empty());
+        Ok(json!({ "groups": groups }))
+    }
+
+    async fn get_actions(&self, args: ActionArgs) -> Result<Value, ToolError> {
+        let (query, item_id) = (args.query, args.item_id);
+        let actions: Vec<ActionInfo> = self
+            .in_view(args.view, move |engine, lua| {
+                let item = Self::find_item(engine, lua, &query, &item_id)?;
+                engine.get_applicable_actions(lua, &[item])
+            })
+            .await?;
+
+        let actions: Vec<Value> = actions
+            .iter()
+            .map(|action| json!({ "id": action.id, "title": action.title }))
+            .collect();
+        Ok(json!({ "actions": actions }))
+    }
+
+    async fn run_action(&self, args: ActionArgs) -> Result<Value, ToolError> {
+        let action_id = args
+            .action_id
+            .ok_or_else(|| ToolError::InvalidArguments("missing action_id".to_string()))?;
+
+        // Resolve the item and action first so the dialog can name them
+        let (query, item_id, id) = (args.query.clone(), args.item_id.clone(), action_id.clone());
+        let (item, action): (Item, ActionInfo) = self
+            .in_view(args.view.clone(), move |engine, lua| {
+                let item = Self::find_item(engine, lua, &query, &item_id)?;
+                let action = engine
+                    .get_applicable_actions(lua, std::slice::from_ref(&item))?
+                    .into_iter()
+                    .find(|action| action.id == id)
+                    .ok_or_else(|| format!("No action with ID '{}'", id))?;
+                Ok((item, action))
+            })
+            .await?;
+
+        let message = t_args(
+            "Run “{action}” on “{item}”?",
+            &[("action", &action.title), ("item", &item.title)],
+        );
+        if !desktop::current()
+            .confirm(&t("Run action"), &message)
+            .map_err(|e| ToolError::Lua(e.to_string()))?
+        {
+            return Err(ToolError::Declined);
+        }
+
+        // Run in a fresh pass so the action sees the view's state
+        let (query, item_id) = (args.query, args.item_id);
+        let result = self
+            .in_view(args.view, move |engine, lua| {
+                let item = Self::find_item(engine, lua, &query, &item_id)?;
+                engine.execute_action(lua, &action.view_id, action.handler_key(), &[item])
+            })
+            .await?;
+        Ok(json!({ "result": result }))
+    }
+}
+
+impl ToolHandler for Arc<LuxTools> {
+    fn call(&self, name: String, args: Value) -> BoxFuture<'static, Result<Value, ToolError>> {
+        let tools = self.clone();
+        Box::pin(async move {
+            match name.as_str() {
+                "list_views" => Ok(LuxTools::list_views(&tools.registry)),
+                "search" => tools.search(parse_args(args)?).await,
+                "get_actions" => tools.get_actions(parse_args(args)?).await,
+                "run_action" => tools.run_action(parse_args(args)?).await,
+                _ => Err(ToolError::UnknownTool(name)),
+            }
+        })
+    }
+}
+
+/// Deserialize tool arguments.
+fn parse_args<T: for<'de> Deserialize<'de>>(args: Value) -> Result<T, ToolError> {
+    serde_json::from_value(args).map_err(|e| ToolError::InvalidArguments(e.to_string()))
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Errors from tool calls.
+#[derive(Debug, thiserror::Error)]
+pub enum ToolError {
+    #[error("Unknown tool '{0}'")]
+    UnknownTool(String),
+
+    #[error("Unknown view '{0}'")]
+    UnknownView(String),
+
+    #[error("Invalid arguments: {0}")]
+    InvalidArguments(String),
+
+    #[error("The user declined to run the action")]
+    Declined,
+
+    #[error("{0}")]
+    Lua(String),
+}
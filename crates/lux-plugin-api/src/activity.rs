@@ -0,0 +1,396 @@
+//! Execution audit log and the "Activity" view.
+//!
+//! This module provides:
+//! - `ActivityEntry` / `Outcome` - One executed action: when, on what, what came of it, and how long it took
+//! - `ActivityLog` - The latest executed actions, saved to the data directory
+//! - `RepeatOutcome` - What repeating the latest action did (`repeat_last_action`, Cmd+R)
+//! - `activity_groups()` - The rows of the Activity view
+//!
+//! Every action the engine runs is recorded, whether it came from the action
+//! menu, Enter, or a keybinding, so the Activity view answers "what did that
+//! just do?". Its "Run Again" action re-runs an entry on the same items, as
+//! long as the handler is still loaded (not after a restart). Cmd+R in the
+//! launcher does the same for the latest action, asking first if it is
+//! destructive:
+//!
+//! ```lua
+//! lux.views.add(lux.builtin.activity())   -- id "builtin.activity"
+//! ```
+//!
+//! The log is local: it is only written to `activity.json` in the data
+//! directory, and only the latest `MAX_ENTRIES` are kept.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use lux_core::i18n::t;
+use lux_core::{ActionResult, Group, Groups, Item};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::recent::format_age;
+use crate::storage;
+
+/// ID of the "Activity" view.
+pub const ACTIVITY_VIEW_ID: &str = "builtin.activity";
+
+/// Action name recorded for keybindings that run a Lua function.
+pub const KEYBINDING_ACTION: &str = "keybinding";
+
+/// Prefix of the IDs of Activity view items (`activity:<entry id>`).
+pub const ACTIVITY_ITEM_PREFIX: &str = "activity:";
+
+/// Entries kept in the log.
+pub const MAX_ENTRIES: usize = 200;
+
+/// Item titles shown in an entry's subtitle.
+const MAX_ITEM_TITLES: usize = 3;
+
+// =============================================================================
+// Entries
+// =============================================================================
+
+/// What came of an executed action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Outcome {
+    /// It ran and the launcher stayed open.
+    Done,
+    /// It closed the launcher.
+    Dismissed,
+    /// It pushed, replaced, or popped a view.
+    ChangedView,
+    /// It failed, or its handler raised an error.
+    Failed { message: String },
+}
+
+impl Outcome {
+    /// What `result` amounts to.
+    pub fn from_result(result: &Result<ActionResult, String>) -> Self {
+        match result {
+            Ok(ActionResult::Dismiss) => Outcome::Dismissed,
+            Ok(
+                ActionResult::PushView { .. }
+                | ActionResult::ReplaceView { .. }
+                | ActionResult::Pop,
+            ) => Outcome::ChangedView,
+            Ok(ActionResult::Fail { error, .. }) => Outcome::Failed {
+                message: error.to_string(),
+            },
+            Err(message) => Outcome::Failed {
+                message: message.clone(),
+            },
+            Ok(_) => Outcome::Done,
+        }
+    }
+
+    /// Label shown in the Activity view.
+    pub fn label(&self) -> String {
+        match self {
+            Outcome::Done => t("Done"),
+            Outcome::Dismissed => t("Dismissed"),
+            Outcome::ChangedView => t("Changed view"),
+            Outcome::Failed { .. } => t("Failed"),
+        }
+    }
+}
+
+/// One executed action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityEntry {
+    /// Assigned by the log, increasing.
+    #[serde(default)]
+    pub id: u64,
+    /// When it started, as a Unix timestamp.
+    pub time: i64,
+    /// ID of the view it ran in.
+    pub view: String,
+    /// Action ID (`KEYBINDING_ACTION` for keybindings).
+    pub action: String,
+    /// Lua registry key of the handler, to run it again.
+    pub handler_key: String,
+    /// The items it ran on.
+    #[serde(default)]
+    pub items: Vec<Item>,
+    /// What came of it.
+    pub outcome: Outcome,
+    /// How long it took, in milliseconds.
+    pub duration_ms: u64,
+    /// Whether the action is destructive (repeating it asks first).
+    #[serde(default)]
+    pub destructive: bool,
+}
+
+impl ActivityEntry {
+    /// Describe an action of `view` that just returned `result` after `duration`.
+    pub fn new(
+        view: &str,
+        action: &str,
+        handler_key: &str,
+        items: &[Item],
+        result: &Result<ActionResult, String>,
+        duration: Duration,
+    ) -> Self {
+        Self {
+            id: 0,
+            time: chrono::Utc::now().timestamp() - duration.as_secs() as i64,
+            view: view.to_string(),
+            action: action.to_string(),
+            handler_key: handler_key.to_string(),
+            items: items.to_vec(),
+            outcome: Outcome::from_result(result),
+            duration_ms: duration.as_millis() as u64,
+            destructive: false,
+        }
+    }
+
+    /// Mark the action as destructive.
+    pub fn with_destructive(mut self, destructive: bool) -> Self {
+        self.destructive = destructive;
+        self
+    }
+
+    /// Title shown in the Activity view.
+    pub fn title(&self) -> String {
+        if self.action == KEYBINDING_ACTION {
+            t("Key binding")
+        } else {
+            self.action.clone()
+        }
+    }
+}
+
+// =============================================================================
+// Log
+// =============================================================================
+
+/// What repeating the latest action did.
+#[derive(Debug, Clone)]
+pub enum RepeatOutcome {
+    /// No action ran since Lux started, or its handler is gone.
+    Nothing,
+    /// The action is destructive: repeat again with this entry's ID to run it.
+    NeedsConfirmation(ActivityEntry),
+    /// The action ran again.
+    Ran(ActionResult),
+}
+
+/// The latest executed actions, newest first.
+///
+/// Saved as a JSON array after every change.
+pub struct ActivityLog {
+    path: Option<PathBuf>,
+    entries: Mutex<Vec<ActivityEntry>>,
+    /// ID of the first entry recorded since the log was loaded.
+    session_start: u64,
+}
+
+impl ActivityLog {
+    /// Load the log saved at `path` (None keeps it in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let entries: Vec<ActivityEntry> = path.as_deref().map(load_entries).unwrap_or_default();
+        let session_start = entries.first().map_or(1, |latest| latest.id + 1);
+        Self {
+            path,
+            entries: Mutex::new(entries),
+            session_start,
+        }
+    }
+
+    /// Where the log is saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("activity.json"))
+    }
+
+    /// Add an entry, dropping the oldest past `MAX_ENTRIES`. Returns its ID.
+    pub fn record(&self, mut entry: ActivityEntry) -> u64 {
+        let mut entries = self.entries.lock();
+        entry.id = entries
+            .first()
+            .map_or(1, |latest| latest.id + 1)
+            .max(self.session_start);
+        let id = entry.id;
+        entries.insert(0, entry);
+        entries.truncate(MAX_ENTRIES);
+        self.save(&entries);
+        id
+    }
+
+    /// The entries, newest first.
+    pub fn entries(&self) -> Vec<ActivityEntry> {
+        self.entries.lock().clone()
+    }
+
+    /// The newest entry recorded since the log was loaded.
+    ///
+    /// Older entries come from an earlier run, whose handlers are gone.
+    pub fn latest_this_session(&self) -> Option<ActivityEntry> {
+        self.entries
+            .lock()
+            .first()
+            .filter(|entry| entry.id >= self.session_start)
+            .cloned()
+    }
+
+    /// The entry with `id`, if it is still in the log.
+    pub fn get(&self, id: u64) -> Option<ActivityEntry> {
+        self.entries.lock().iter().find(|e| e.id == id).cloned()
+    }
+
+    /// Forget every entry, on disk too.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock();
+        entries.clear();
+        self.save(&entries);
+    }
+
+    fn save(&self, entries: &[ActivityEntry]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = save_entries(path, entries) {
+            tracing::warn!("Failed to save activity log: {}", e);
+        }
+    }
+}
+
+impl Default for ActivityLog {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+fn load_entries(path: &Path) -> Vec<ActivityEntry> {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_entries(path: &Path, entries: &[ActivityEntry]) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(entries)?)
+}
+
+// =============================================================================
+// Activity View
+// =============================================================================
+
+/// The entry ID of an Activity view item.
+pub fn activity_entry_id(item_id: &str) -> Option<u64> {
+    item_id.strip_prefix(ACTIVITY_ITEM_PREFIX)?.parse().ok()
+}
+
+/// The entries whose action, view, or items contain `query`, newest first.
+///
+/// The subtitle says when, where, and on what it ran (and why it failed);
+/// the accessory says what came of it and how long it took.
+pub fn activity_groups(entries: &[ActivityEntry], query: &str, now: i64) -> Groups {
+    let query = query.trim().to_lowercase();
+    let items: Vec<Item> = entries
+        .iter()
+        .filter(|entry| {
+            query.is_empty()
+                || entry.title().to_lowercase().contains(&query)
+                || entry.view.to_lowercase().contains(&query)
+                || entry
+                    .items
+                    .iter()
+                    .any(|item| item.title.to_lowercase().contains(&query))
+        })
+        .map(|entry| {
+            let mut subtitle = vec![format_age(entry.time, now), entry.view.clone()];
+            let titles: Vec<&str> = entry
+                .items
+                .iter()
+                .take(MAX_ITEM_TITLES)
+                .map(|item| item.title.as_str())
+                .collect();
+            if !titles.is_empty() {
+                let more = entry.items.len() - titles.len();
+                subtitle.push(match more {
+                    0 => titles.join(", "),
+                    more => format!("{} +{}", titles.join(", "), more),
+                });
+            }
+            if let Outcome::Failed { message } = &entry.outcome {
+                subtitle.push(message.clone());
+            }
+
+            Item::new(
+                format!("{}{}", ACTIVITY_ITEM_PREFIX, entry.id),
+                entry.title(),
+            )
+            .with_subtitle(subtitle.join(" · "))
+            .with_accessory(format!(
+                "{} · {} ms",
+                entry.outcome.label(),
+                entry.duration_ms
+            ))
+        })
+        .collect();
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::new(t("Activity"), items)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lux_core::HandlerError;
+
+    #[test]
+    fn test_log_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("activity.json");
+        let log = ActivityLog::new(Some(path.clone()));
+
+        let items = [Item::new("readme", "README.md")];
+        let ok = Ok(ActionResult::Dismiss);
+        let failed = Ok(ActionResult::Fail {
+            error: HandlerError::new("Server unreachable"),
+            retry: None,
+        });
+        log.record(ActivityEntry::new(
+            "files",
+            "open",
+            "action:open:1",
+            &items,
+            &ok,
+            Duration::from_millis(12),
+        ));
+        let id = log.record(ActivityEntry::new(
+            "files",
+            "upload",
+            "action:upload:1",
+            &items,
+            &failed,
+            Duration::from_millis(250),
+        ));
+
+        assert_eq!(log.latest_this_session().unwrap().id, id);
+
+        let reloaded = ActivityLog::new(Some(path));
+        assert!(reloaded.latest_this_session().is_none());
+        let entries = reloaded.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, id);
+        assert_eq!(entries[0].action, "upload");
+        assert_eq!(entries[1].outcome, Outcome::Dismissed);
+        assert_eq!(reloaded.get(id).unwrap().duration_ms, 250);
+
+        let now = entries[0].time;
+        let groups = activity_groups(&entries, "upload", now);
+        let item = &groups[0].items[0];
+        assert_eq!(activity_entry_id(&item.id), Some(id));
+        assert!(item
+            .subtitle
+            .as_deref()
+            .unwrap()
+            .ends_with("files · README.md · Server unreachable"));
+        assert!(activity_groups(&entries, "nothing", now).is_empty());
+
+        reloaded.clear();
+        assert!(reloaded.entries().is_empty());
+    }
+}
@@ -0,0 +1,1310 @@
+//! Alfred workflow importer.
+//!
+//! This module provides:
+//! - `Workflow` - An Alfred workflow bundle read from its `info.plist`
+//! - `Trigger` / `WorkflowAction` - The workflow objects Lux can run
+//! - `import_workflow()` - Generate a Lux plugin for a workflow
+//! - `installed_workflows()` - Workflows in the user's Alfred preferences
+//!
+//! ## Mapping
+//!
+//! | Alfred | Lux |
+//! |---|---|
+//! | Script Filter | View whose search runs the script and shows its JSON or XML items |
+//! | Keyword | View with one result that passes the query on |
+//! | Open URL, Run Script, Copy to Clipboard, Open File, Reveal in Finder | Actions on the view's results |
+//!
+//! Only actions connected directly to a trigger are followed. Other objects
+//! (hotkeys, file actions, notifications, conditionals, ...) are listed as
+//! skipped at the top of the generated plugin.
+//!
+//! The generated plugin calls `lux.alfred.view()` once per trigger. Scripts
+//! keep running from the workflow folder with Alfred's environment variables,
+//! so the folder has to stay where it was imported from.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use lux_core::i18n::t;
+use lux_core::Item;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::websearch::encode_query;
+
+/// Type tag for items produced by workflows.
+pub const ALFRED_ITEM_TYPE: &str = "alfred";
+
+/// How long a script filter may run before it is killed.
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Escaping Alfred applies to `{query}` when a workflow doesn't say
+/// (backquotes, double quotes, dollars, backslashes).
+const DEFAULT_ESCAPING: i64 = 102;
+
+/// Alfred version reported to scripts that check `alfred_version`.
+const ALFRED_VERSION: &str = "5.0";
+
+// =============================================================================
+// Workflow
+// =============================================================================
+
+/// An Alfred workflow bundle.
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    /// Folder containing `info.plist`.
+    pub dir: PathBuf,
+    /// Display name.
+    pub name: String,
+    /// Bundle identifier (e.g. "com.example.github").
+    pub bundle_id: Option<String>,
+    /// Short description.
+    pub description: Option<String>,
+    /// Workflow environment variables.
+    pub variables: Vec<(String, String)>,
+    /// Script filters and keywords, in `info.plist` order.
+    pub triggers: Vec<Trigger>,
+    /// Object types Lux can't run.
+    pub skipped: Vec<String>,
+}
+
+/// A workflow input that becomes a Lux view.
+#[derive(Debug, Clone)]
+pub struct Trigger {
+    /// Object UID in `info.plist`.
+    pub uid: String,
+    /// Alfred keyword.
+    pub keyword: String,
+    /// Title (the placeholder text for script filters).
+    pub title: String,
+    /// Subtitle shown under keyword results.
+    pub subtitle: Option<String>,
+    /// Icon file for the object, or the workflow icon.
+    pub icon: Option<PathBuf>,
+    /// What the trigger runs.
+    pub kind: TriggerKind,
+    /// Actions connected to the trigger.
+    pub actions: Vec<WorkflowAction>,
+}
+
+/// The kind of workflow input.
+#[derive(Debug, Clone)]
+pub enum TriggerKind {
+    /// Runs a script that returns the results.
+    ScriptFilter(Script),
+    /// Shows a single result carrying the query.
+    Keyword,
+}
+
+/// A script in a workflow object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Script {
+    /// Alfred script type (0 = bash, 5 = zsh, 8 = external file, ...).
+    pub kind: i64,
+    /// Inline source, or the file name for external scripts.
+    pub source: String,
+    /// Pass the query as an argument instead of replacing `{query}`.
+    pub argv: bool,
+    /// Alfred's escaping flags for `{query}` replacement.
+    pub escaping: i64,
+}
+
+/// A workflow output that becomes a Lux action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkflowAction {
+    /// Open a URL template (`{query}` is replaced, percent-encoded).
+    OpenUrl(String),
+    /// Run a script with the result's argument.
+    RunScript(Script),
+    /// Copy text (`{query}` is replaced) to the clipboard.
+    Copy(String),
+    /// Open the argument as a file path.
+    OpenFile,
+    /// Reveal the argument as a file path.
+    RevealFile,
+}
+
+impl Workflow {
+    /// Read a workflow from its folder.
+    pub fn load(dir: &Path) -> Result<Self, AlfredError> {
+        let path = dir.join("info.plist");
+        let text = std::fs::read_to_string(&path).map_err(|source| AlfredError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let plist = parse_plist(&text)?;
+
+        let name = string_field(&plist, "name").unwrap_or_else(|| {
+            dir.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let variables = plist
+            .get("variables")
+            .map(|vars| {
+                vars.entries()
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let objects = plist.get("objects").map(Plist::items).unwrap_or_default();
+        let by_uid: HashMap<&str, &Plist> = objects
+            .iter()
+            .filter_map(|object| Some((object.get("uid")?.as_str()?, object)))
+            .collect();
+        let workflow_icon = Some(dir.join("icon.png")).filter(|p| p.exists());
+
+        let mut triggers = Vec::new();
+        let mut skipped = Vec::new();
+        for object in objects {
+            let kind = object
+                .get("type")
+                .and_then(Plist::as_str)
+                .unwrap_or_default();
+            let uid = object
+                .get("uid")
+                .and_then(Plist::as_str)
+                .unwrap_or_default();
+            let empty = Plist::Dict(Vec::new());
+            let config = object.get("config").unwrap_or(&empty);
+
+            let (trigger_kind, title) = match kind {
+                "alfred.workflow.input.scriptfilter" => (
+                    TriggerKind::ScriptFilter(Script::from_config(config)),
+                    string_field(config, "title"),
+                ),
+                "alfred.workflow.input.keyword" => {
+                    (TriggerKind::Keyword, string_field(config, "text"))
+                }
+                _ => {
+                    if WorkflowAction::from_object(kind, config).is_none() {
+                        skipped.push(kind.to_string());
+                    }
+                    continue;
+                }
+            };
+
+            // Follow connections one hop to the supported actions
+            let actions = plist
+                .get("connections")
+                .and_then(|connections| connections.get(uid))
+                .map(Plist::items)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|connection| by_uid.get(connection.get("destinationuid")?.as_str()?))
+                .filter_map(|target| {
+                    let kind = target.get("type")?.as_str()?;
+                    WorkflowAction::from_object(kind, target.get("config")?)
+                })
+                .collect();
+
+            let keyword = string_field(config, "keyword").unwrap_or_default();
+            triggers.push(Trigger {
+                uid: uid.to_string(),
+                title: title.unwrap_or_else(|| keyword.clone()),
+                keyword,
+                subtitle: string_field(config, "subtext"),
+                icon: Some(dir.join(format!("{}.png", uid)))
+                    .filter(|p| p.exists())
+                    .or_else(|| workflow_icon.clone()),
+                kind: trigger_kind,
+                actions,
+            });
+        }
+        skipped.sort();
+        skipped.dedup();
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            name,
+            bundle_id: string_field(&plist, "bundleid"),
+            description: string_field(&plist, "description"),
+            variables,
+            triggers,
+            skipped,
+        })
+    }
+
+    /// File-name-safe identifier (the bundle ID, or the name).
+    pub fn slug(&self) -> String {
+        slugify(self.bundle_id.as_deref().unwrap_or(&self.name))
+    }
+
+    /// Find a trigger by UID.
+    pub fn trigger(&self, uid: &str) -> Option<&Trigger> {
+        self.triggers.iter().find(|trigger| trigger.uid == uid)
+    }
+
+    /// Default Lux view ID for a trigger.
+    pub fn view_id(&self, trigger: &Trigger) -> String {
+        let key = if trigger.keyword.is_empty() {
+            &trigger.uid
+        } else {
+            &trigger.keyword
+        };
+        format!("alfred.{}.{}", self.slug(), slugify(key))
+    }
+
+    /// Generate the Lux plugin for this workflow.
+    pub fn to_lua(&self) -> String {
+        let mut lua = format!("-- {}", self.name);
+        if let Some(bundle_id) = &self.bundle_id {
+            lua.push_str(&format!(" ({})", bundle_id));
+        }
+        lua.push_str("\n-- Imported from an Alfred workflow by `lux import alfred`.\n");
+        lua.push_str("-- Scripts run from the workflow folder, so keep it in place.\n");
+        if !self.skipped.is_empty() {
+            lua.push_str("--\n-- Skipped (not supported in Lux):\n");
+            for kind in &self.skipped {
+                lua.push_str(&format!("--   {}\n", kind));
+            }
+        }
+        lua.push_str(&format!(
+            "\nlocal workflow = {}\n",
+            lua_string(&self.dir.to_string_lossy())
+        ));
+
+        let mut ids = HashSet::new();
+        for trigger in &self.triggers {
+            let mut id = self.view_id(trigger);
+            if !ids.insert(id.clone()) {
+                id = format!("alfred.{}.{}", self.slug(), slugify(&trigger.uid));
+            }
+            let kind = match trigger.kind {
+                TriggerKind::ScriptFilter(_) => "Script filter",
+                TriggerKind::Keyword => "Keyword",
+            };
+            lua.push_str(&format!(
+                "\n-- {} \"{}\": {}\nlux.views.add(lux.alfred.view(workflow, {}, {{\n  id = {},\n  title = {},\n  placeholder = {},\n}}))\n",
+                kind,
+                trigger.keyword,
+                trigger.title,
+                lua_string(&trigger.uid),
+                lua_string(&id),
+                lua_string(&trigger.title),
+                lua_string(&trigger.keyword),
+            ));
+        }
+        lua
+    }
+
+    /// Run a script filter and return its results.
+    pub fn run_script_filter(
+        &self,
+        script: &Script,
+        query: &str,
+    ) -> Result<Vec<Item>, AlfredError> {
+        use wait_timeout::ChildExt;
+
+        let mut cmd = self.command(script, query);
+        let mut child = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AlfredError::Script(e.to_string()))?;
+
+        let status = match child.wait_timeout(SCRIPT_TIMEOUT) {
+            Ok(Some(status)) => status,
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AlfredError::Script(format!(
+                    "timed out after {}s",
+                    SCRIPT_TIMEOUT.as_secs()
+                )));
+            }
+            Err(e) => return Err(AlfredError::Script(e.to_string())),
+        };
+
+        let mut stdout = String::new();
+        if let Some(mut handle) = child.stdout.take() {
+            let _ = handle.read_to_string(&mut stdout);
+        }
+        if !status.success() {
+            let mut stderr = String::new();
+            if let Some(mut handle) = child.stderr.take() {
+                let _ = handle.read_to_string(&mut stderr);
+            }
+            return Err(AlfredError::Script(format!(
+                "{}: {}",
+                status,
+                stderr.trim()
+            )));
+        }
+
+        parse_script_filter_output(&stdout, &self.dir)
+    }
+
+    /// The single result shown by a keyword trigger.
+    pub fn keyword_item(&self, trigger: &Trigger, query: &str) -> Item {
+        let mut item = Item::new(
+            format!("alfred:{}", trigger.uid),
+            trigger.title.replace("{query}", query),
+        )
+        .with_type(ALFRED_ITEM_TYPE)
+        .with_data(serde_json::json!({ "arg": query, "valid": true }));
+        item.subtitle = trigger
+            .subtitle
+            .as_ref()
+            .map(|subtitle| subtitle.replace("{query}", query));
+        item.icon = trigger
+            .icon
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string());
+        item
+    }
+
+    /// Build the command for a script, with the workflow's environment.
+    fn command(&self, script: &Script, query: &str) -> Command {
+        let mut cmd = script.command(&self.dir, query);
+        let slug = self.slug();
+        let data_dir = dirs::data_dir().map(|d| d.join("lux/alfred").join(&slug));
+        let cache_dir = dirs::cache_dir().map(|d| d.join("lux/alfred").join(&slug));
+
+        cmd.env("alfred_version", ALFRED_VERSION)
+            .env("alfred_workflow_name", &self.name)
+            .env(
+                "alfred_workflow_bundleid",
+                self.bundle_id.as_deref().unwrap_or(""),
+            );
+        if let Some(dir) = data_dir {
+            cmd.env("alfred_workflow_data", dir);
+        }
+        if let Some(dir) = cache_dir {
+            cmd.env("alfred_workflow_cache", dir);
+        }
+        cmd.envs(self.variables.iter().map(|(k, v)| (k, v)));
+        cmd
+    }
+}
+
+impl Script {
+    /// Read a script from an object's `config` dictionary.
+    fn from_config(config: &Plist) -> Self {
+        let kind = config.get("type").and_then(Plist::as_i64).unwrap_or(0);
+        let source_key = if kind == 8 { "scriptfile" } else { "script" };
+        Self {
+            kind,
+            source: string_field(config, source_key).unwrap_or_default(),
+            argv: config.get("scriptargtype").and_then(Plist::as_i64) == Some(1),
+            escaping: config
+                .get("escaping")
+                .and_then(Plist::as_i64)
+                .unwrap_or(DEFAULT_ESCAPING),
+        }
+    }
+
+    /// Build the command that runs the script in `dir` with `query`.
+    fn command(&self, dir: &Path, query: &str) -> Command {
+        let mut cmd = if self.kind == 8 {
+            // External script: run the file, always with the query as an argument
+            let mut cmd = Command::new(dir.join(&self.source));
+            cmd.arg(query);
+            cmd
+        } else {
+            let (program, flags) = interpreter(self.kind);
+            let mut cmd = Command::new(program);
+            cmd.args(flags);
+            if self.argv {
+                cmd.arg(&self.source);
+                // Shells take the script name as $0, so the query lands in $1
+                if matches!(program, "bash" | "zsh") {
+                    cmd.arg("alfred");
+                }
+                cmd.arg(query);
+            } else {
+                cmd.arg(
+                    self.source
+                        .replace("{query}", &escape_query(query, self.escaping)),
+                );
+            }
+            cmd
+        };
+        cmd.current_dir(dir);
+        cmd
+    }
+}
+
+/// Program and flags that run inline source for an Alfred script type.
+fn interpreter(kind: i64) -> (&'static str, &'static [&'static str]) {
+    match kind {
+        1 => ("php", &["-r"]),
+        2 => ("ruby", &["-e"]),
+        3 | 9 => ("python3", &["-c"]),
+        4 => ("perl", &["-e"]),
+        5 => ("zsh", &["-c"]),
+        6 => ("osascript", &["-l", "AppleScript", "-e"]),
+        7 => ("osascript", &["-l", "JavaScript", "-e"]),
+        11 => ("zsh", &["--no-rcs", "-c"]),
+        _ => ("bash", &["-c"]),
+    }
+}
+
+/// Backslash-escape the characters selected by Alfred's escaping flags.
+fn escape_query(query: &str, flags: i64) -> String {
+    const ESCAPES: &[(i64, &str)] = &[
+        (1, " "),
+        (2, "`"),
+        (4, "\""),
+        (8, "()[]{}"),
+        (16, ";"),
+        (32, "$"),
+        (64, "\\"),
+    ];
+    let mut escaped = String::with_capacity(query.len());
+    for c in query.chars() {
+        if ESCAPES
+            .iter()
+            .any(|(flag, chars)| flags & flag != 0 && chars.contains(c))
+        {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl WorkflowAction {
+    /// Read a supported action object.
+    fn from_object(kind: &str, config: &Plist) -> Option<Self> {
+        match kind {
+            "alfred.workflow.action.openurl" => Some(Self::OpenUrl(
+                string_field(config, "url").unwrap_or_else(|| "{query}".to_string()),
+            )),
+            "alfred.workflow.action.script" => Some(Self::RunScript(Script::from_config(config))),
+            "alfred.workflow.output.clipboard" => Some(Self::Copy(
+                string_field(config, "clipboardtext").unwrap_or_else(|| "{query}".to_string()),
+            )),
+            "alfred.workflow.action.openfile" => Some(Self::OpenFile),
+            "alfred.workflow.action.revealfile" => Some(Self::RevealFile),
+            _ => None,
+        }
+    }
+
+    /// Action ID.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::OpenUrl(_) => "open_url",
+            Self::RunScript(_) => "run_script",
+            Self::Copy(_) => "copy",
+            Self::OpenFile => "open",
+            Self::RevealFile => "reveal",
+        }
+    }
+
+    /// Display title.
+    pub fn title(&self) -> String {
+        match self {
+            Self::OpenUrl(_) => t("Open URL"),
+            Self::RunScript(_) => t("Run Script"),
+            Self::Copy(_) => t("Copy to Clipboard"),
+            Self::OpenFile => t("Open"),
+            Self::RevealFile => t("Reveal in Finder"),
+        }
+    }
+
+    /// Run the action with a result's argument.
+    pub fn run(&self, workflow: &Workflow, arg: &str) -> PluginResult<()> {
+        match self {
+            Self::OpenUrl(url) => {
+                desktop::current().open(&url.replace("{query}", &encode_query(arg)))
+            }
+            Self::Copy(text) => desktop::current().clipboard_write(&text.replace("{query}", arg)),
+            Self::OpenFile => desktop::current().open(arg),
+            Self::RevealFile => desktop::current().reveal(arg),
+            Self::RunScript(script) => {
+                // Fire-and-forget, like Alfred's Run Script action
+                workflow
+                    .command(script, arg)
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .map(|_| ())
+                    .map_err(|e| PluginError::Command(format!("Command spawn failed: {}", e)))
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Importing
+// =============================================================================
+
+/// Where imported plugins are written: `alfred/` next to init.lua.
+pub fn plugin_dir() -> Option<PathBuf> {
+    lux_core::find_init_lua()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .or_else(lux_core::config_dir)
+        .map(|dir| dir.join("alfred"))
+}
+
+/// Import a workflow folder or `.alfredworkflow` file into `out_dir`.
+///
+/// `.alfredworkflow` files are unpacked into `out_dir/<name>/` first. Returns
+/// the workflow and the path of the generated plugin.
+pub fn import_workflow(path: &Path, out_dir: &Path) -> Result<(Workflow, PathBuf), AlfredError> {
+    std::fs::create_dir_all(out_dir).map_err(|source| AlfredError::Io {
+        path: out_dir.to_path_buf(),
+        source,
+    })?;
+
+    let dir = if path.extension().is_some_and(|ext| ext == "alfredworkflow") {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let dest = out_dir.join(slugify(&stem));
+        extract_bundle(path, &dest)?;
+        dest
+    } else {
+        path.to_path_buf()
+    };
+    let dir = dir.canonicalize().map_err(|source| AlfredError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+
+    let workflow = Workflow::load(&dir)?;
+    let file = out_dir.join(format!("{}.lua", workflow.slug()));
+    std::fs::write(&file, workflow.to_lua()).map_err(|source| AlfredError::Io {
+        path: file.clone(),
+        source,
+    })?;
+    Ok((workflow, file))
+}
+
+/// Unzip an `.alfredworkflow` file into `dest`.
+fn extract_bundle(file: &Path, dest: &Path) -> Result<(), AlfredError> {
+    let status = Command::new("unzip")
+        .args(["-o", "-q"])
+        .arg(file)
+        .arg("-d")
+        .arg(dest)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| AlfredError::Extract(e.to_string()))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(AlfredError::Extract(format!(
+            "unzip exited with {}",
+            status
+        )))
+    }
+}
+
+/// The workflows folder of the user's Alfred preferences.
+///
+/// Honors a synced preferences folder set in Alfred's `prefs.json`.
+pub fn workflows_dir() -> Option<PathBuf> {
+    let alfred = dirs::home_dir()?.join("Library/Application Support/Alfred");
+    let synced = std::fs::read_to_string(alfred.join("prefs.json"))
+        .ok()
+        .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        .and_then(|prefs| prefs["current"].as_str().map(PathBuf::from));
+    Some(
+        synced
+            .unwrap_or_else(|| alfred.join("Alfred.alfredpreferences"))
+            .join("workflows"),
+    )
+}
+
+/// Workflows installed in Alfred, sorted by name. Unreadable ones are skipped.
+pub fn installed_workflows() -> Vec<Workflow> {
+    let Some(entries) = workflows_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut workflows: Vec<Workflow> = entries
+        .flatten()
+        .filter_map(|entry| Workflow::load(&entry.path()).ok())
+        .collect();
+    workflows.sort_by_key(|workflow| workflow.name.to_lowercase());
+    workflows
+}
+
+/// Lowercase, keeping only characters that are safe in file names and view IDs.
+fn slugify(s: &str) -> String {
+    s.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Quote a string as a Lua string literal.
+fn lua_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// =============================================================================
+// Script Filter Output
+// =============================================================================
+
+/// Parse script filter output (JSON, or the older XML format) into items.
+///
+/// Each item carries `{ arg, valid }` in its data. Relative icon paths are
+/// resolved against the workflow folder.
+pub fn parse_script_filter_output(output: &str, dir: &Path) -> Result<Vec<Item>, AlfredError> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if output.starts_with('<') {
+        let root = parse_xml(output)?;
+        return Ok(root
+            .children
+            .iter()
+            .filter(|el| el.name == "item")
+            .enumerate()
+            .map(|(index, el)| {
+                let text = |name: &str| el.child(name).map(|c| c.text.trim().to_string());
+                let title = text("title").unwrap_or_default();
+                result_item(
+                    index,
+                    el.attr("uid").map(str::to_string),
+                    title.clone(),
+                    text("subtitle").filter(|s| !s.is_empty()),
+                    el.attr("arg")
+                        .map(str::to_string)
+                        .or(text("arg"))
+                        .unwrap_or(title),
+                    !matches!(el.attr("valid"), Some("no" | "false")),
+                    text("icon").filter(|s| !s.is_empty()),
+                    dir,
+                )
+            })
+            .collect());
+    }
+
+    let json: Value =
+        serde_json::from_str(output).map_err(|e| AlfredError::Output(e.to_string()))?;
+    let items = json["items"]
+        .as_array()
+        .ok_or_else(|| AlfredError::Output("missing \"items\" array".to_string()))?;
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let text = |key: &str| item[key].as_str().map(str::to_string);
+            let title = text("title").unwrap_or_default();
+            let arg = match &item["arg"] {
+                Value::String(arg) => arg.clone(),
+                Value::Array(args) => args
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => title.clone(),
+            };
+            // File icons need Alfred's icon service, so they fall back to no icon
+            let icon = match item["icon"]["type"].as_str() {
+                Some("fileicon" | "filetype") => None,
+                _ => item["icon"]["path"].as_str().map(str::to_string),
+            };
+            result_item(
+                index,
+                text("uid"),
+                title,
+                text("subtitle").filter(|s| !s.is_empty()),
+                arg,
+                item["valid"].as_bool().unwrap_or(true),
+                icon,
+                dir,
+            )
+        })
+        .collect())
+}
+
+/// Build a Lux item from a script filter result.
+#[allow(clippy::too_many_arguments)]
+fn result_item(
+    index: usize,
+    uid: Option<String>,
+    title: String,
+    subtitle: Option<String>,
+    arg: String,
+    valid: bool,
+    icon: Option<String>,
+    dir: &Path,
+) -> Item {
+    let mut item = Item::new(uid.unwrap_or_else(|| format!("alfred:{}", index)), title)
+        .with_type(ALFRED_ITEM_TYPE)
+        .with_data(serde_json::json!({ "arg": arg, "valid": valid }));
+    item.subtitle = subtitle;
+    item.icon = icon.map(|path| dir.join(path).to_string_lossy().to_string());
+    item
+}
+
+// =============================================================================
+// Property Lists
+// =============================================================================
+
+/// A value in an XML property list.
+#[derive(Debug, Clone, PartialEq)]
+enum Plist {
+    String(String),
+    Integer(i64),
+    Real(f64),
+    Bool(bool),
+    Array(Vec<Plist>),
+    Dict(Vec<(String, Plist)>),
+}
+
+impl Plist {
+    /// Look up a dictionary key.
+    fn get(&self, key: &str) -> Option<&Plist> {
+        self.entries()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Dictionary entries (empty for other values).
+    fn entries(&self) -> &[(String, Plist)] {
+        match self {
+            Self::Dict(entries) => entries,
+            _ => &[],
+        }
+    }
+
+    /// Array items (empty for other values).
+    fn items(&self) -> &[Plist] {
+        match self {
+            Self::Array(items) => items,
+            _ => &[],
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(n) => Some(*n),
+            Self::Real(n) => Some(*n as i64),
+            Self::Bool(b) => Some(*b as i64),
+            _ => None,
+        }
+    }
+}
+
+/// A non-empty string value from a dictionary.
+fn string_field(dict: &Plist, key: &str) -> Option<String> {
+    dict.get(key)
+        .and_then(Plist::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Parse an XML property list.
+fn parse_plist(text: &str) -> Result<Plist, AlfredError> {
+    let root = parse_xml(text)?;
+    let value = if root.name == "plist" {
+        root.children
+            .first()
+            .ok_or_else(|| AlfredError::Plist("empty <plist>".to_string()))?
+    } else {
+        &root
+    };
+    plist_value(value)
+}
+
+/// Convert an XML element to a property list value.
+fn plist_value(el: &XmlElement) -> Result<Plist, AlfredError> {
+    Ok(match el.name.as_str() {
+        "string" | "data" | "date" => Plist::String(el.text.clone()),
+        "integer" => Plist::Integer(
+            el.text
+                .trim()
+                .parse()
+                .map_err(|_| AlfredError::Plist(format!("bad integer '{}'", el.text)))?,
+        ),
+        "real" => Plist::Real(
+            el.text
+                .trim()
+                .parse()
+                .map_err(|_| AlfredError::Plist(format!("bad real '{}'", el.text)))?,
+        ),
+        "true" => Plist::Bool(true),
+        "false" => Plist::Bool(false),
+        "array" => Plist::Array(
+            el.children
+                .iter()
+                .map(plist_value)
+                .collect::<Result<_, _>>()?,
+        ),
+        "dict" => {
+            let mut entries = Vec::new();
+            let mut children = el.children.iter();
+            while let Some(key) = children.next() {
+                if key.name != "key" {
+                    return Err(AlfredError::Plist(format!(
+                        "expected <key>, found <{}>",
+                        key.name
+                    )));
+                }
+                let value = children.next().ok_or_else(|| {
+                    AlfredError::Plist(format!("no value for key '{}'", key.text))
+                })?;
+                entries.push((key.text.clone(), plist_value(value)?));
+            }
+            Plist::Dict(entries)
+        }
+        other => return Err(AlfredError::Plist(format!("unknown element <{}>", other))),
+    })
+}
+
+// =============================================================================
+// XML
+// =============================================================================
+
+/// An XML element. Text is the concatenated character data of the element.
+#[derive(Debug, Clone, Default)]
+struct XmlElement {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    text: String,
+}
+
+impl XmlElement {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|el| el.name == name)
+    }
+}
+
+/// Parse the root element of an XML document.
+///
+/// Handles what property lists and script filters use: elements,
+/// attributes, entities, comments, CDATA, and the prolog. Namespaces and
+/// DTDs are not interpreted.
+fn parse_xml(input: &str) -> Result<XmlElement, AlfredError> {
+    let mut parser = XmlParser { input, pos: 0 };
+    parser.skip_misc()?;
+    if !parser.rest().starts_with('<') {
+        return Err(AlfredError::Xml("no root element".to_string()));
+    }
+    parser.element()
+}
+
+/// Cursor over an XML document.
+struct XmlParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// Advance past `end`, returning the text before it.
+    fn take_until(&mut self, end: &str) -> Result<&'a str, AlfredError> {
+        let rest = self.rest();
+        let index = rest
+            .find(end)
+            .ok_or_else(|| AlfredError::Xml(format!("missing '{}'", end)))?;
+        self.pos += index + end.len();
+        Ok(&rest[..index])
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip whitespace, the XML declaration, doctype, and comments.
+    fn skip_misc(&mut self) -> Result<(), AlfredError> {
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.take_until("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if rest.starts_with("<!") {
+                self.take_until(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> String {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        rest[..len].to_string()
+    }
+
+    /// Parse an element starting at `<`.
+    fn element(&mut self) -> Result<XmlElement, AlfredError> {
+        self.pos += 1;
+        let mut el = XmlElement {
+            name: self.name(),
+            ..Default::default()
+        };
+
+        // Attributes
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(el);
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            if rest.is_empty() {
+                return Err(AlfredError::Xml(format!("unclosed <{}>", el.name)));
+            }
+            let name = self.name();
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(AlfredError::Xml(format!(
+                    "attribute '{}' has no value",
+                    name
+                )));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err(AlfredError::Xml(format!("unquoted attribute '{}'", name))),
+            };
+            self.pos += 1;
+            let value = self.take_until(&quote.to_string())?;
+            el.attrs.push((name, unescape(value)));
+        }
+
+        // Content
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name();
+                if name != el.name {
+                    return Err(AlfredError::Xml(format!(
+                        "expected </{}>, found </{}>",
+                        el.name, name
+                    )));
+                }
+                self.take_until(">")?;
+                return Ok(el);
+            } else if rest.starts_with("<!--") {
+                self.take_until("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                el.text.push_str(self.take_until("]]>")?);
+            } else if rest.starts_with('<') {
+                el.children.push(self.element()?);
+            } else if rest.is_empty() {
+                return Err(AlfredError::Xml(format!("unclosed <{}>", el.name)));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                self.pos += len;
+                el.text.push_str(&unescape(&rest[..len]));
+            }
+        }
+    }
+}
+
+/// Replace XML entities and character references.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else { break };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Errors from reading, importing, or running Alfred workflows.
+#[derive(Debug, Error)]
+pub enum AlfredError {
+    #[error("Failed to access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid XML: {0}")]
+    Xml(String),
+
+    #[error("Invalid info.plist: {0}")]
+    Plist(String),
+
+    #[error("Failed to unpack workflow: {0}")]
+    Extract(String),
+
+    #[error("Workflow has no trigger '{0}'")]
+    TriggerNotFound(String),
+
+    #[error("Script filter failed: {0}")]
+    Script(String),
+
+    #[error("Invalid script filter output: {0}")]
+    Output(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>bundleid</key>
+	<string>com.example.github</string>
+	<key>name</key>
+	<string>GitHub &amp; Co</string>
+	<key>variables</key>
+	<dict>
+		<key>GH_USER</key>
+		<string>octocat</string>
+	</dict>
+	<key>connections</key>
+	<dict>
+		<key>SF1</key>
+		<array>
+			<dict>
+				<key>destinationuid</key>
+				<string>URL1</string>
+			</dict>
+		</array>
+	</dict>
+	<key>objects</key>
+	<array>
+		<dict>
+			<key>type</key>
+			<string>alfred.workflow.input.scriptfilter</string>
+			<key>uid</key>
+			<string>SF1</string>
+			<key>config</key>
+			<dict>
+				<key>keyword</key>
+				<string>gh</string>
+				<key>title</key>
+				<string>Search GitHub</string>
+				<key>script</key>
+				<string>./search.sh "$1"</string>
+				<key>scriptargtype</key>
+				<integer>1</integer>
+				<key>type</key>
+				<integer>0</integer>
+			</dict>
+		</dict>
+		<dict>
+			<key>type</key>
+			<string>alfred.workflow.action.openurl</string>
+			<key>uid</key>
+			<string>URL1</string>
+			<key>config</key>
+			<dict>
+				<key>url</key>
+				<string>https://github.com/search?q={query}</string>
+			</dict>
+		</dict>
+		<dict>
+			<key>type</key>
+			<string>alfred.workflow.input.keyword</string>
+			<key>uid</key>
+			<string>KW1</string>
+			<key>config</key>
+			<dict>
+				<key>keyword</key>
+				<string>ghn</string>
+				<key>text</key>
+				<string>New issue: {query}</string>
+				<key>withspace</key>
+				<true/>
+			</dict>
+		</dict>
+		<dict>
+			<key>type</key>
+			<string>alfred.workflow.trigger.hotkey</string>
+			<key>uid</key>
+			<string>HK1</string>
+		</dict>
+	</array>
+</dict>
+</plist>
+"#;
+
+    #[test]
+    fn test_load_workflow() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("info.plist"), INFO_PLIST).unwrap();
+
+        let workflow = Workflow::load(dir.path()).unwrap();
+        assert_eq!(workflow.name, "GitHub & Co");
+        assert_eq!(workflow.bundle_id.as_deref(), Some("com.example.github"));
+        assert_eq!(
+            workflow.variables,
+            vec![("GH_USER".to_string(), "octocat".to_string())]
+        );
+        assert_eq!(workflow.skipped, vec!["alfred.workflow.trigger.hotkey"]);
+
+        let search = workflow.trigger("SF1").unwrap();
+        assert_eq!(search.keyword, "gh");
+        assert_eq!(search.title, "Search GitHub");
+        match &search.kind {
+            TriggerKind::ScriptFilter(script) => {
+                assert_eq!(script.source, "./search.sh \"$1\"");
+                assert!(script.argv);
+            }
+            TriggerKind::Keyword => panic!("expected a script filter"),
+        }
+        assert_eq!(
+            search.actions,
+            vec![WorkflowAction::OpenUrl(
+                "https://github.com/search?q={query}".to_string()
+            )]
+        );
+
+        let keyword = workflow.trigger("KW1").unwrap();
+        assert!(matches!(keyword.kind, TriggerKind::Keyword));
+        assert!(keyword.actions.is_empty());
+        assert_eq!(
+            workflow.keyword_item(keyword, "bug").title,
+            "New issue: bug"
+        );
+    }
+
+    #[test]
+    fn test_import_writes_plugin() {
+        let workflow_dir = tempfile::tempdir().unwrap();
+        std::fs::write(workflow_dir.path().join("info.plist"), INFO_PLIST).unwrap();
+        let out_dir = tempfile::tempdir().unwrap();
+
+        let (workflow, file) = import_workflow(workflow_dir.path(), out_dir.path()).unwrap();
+        assert_eq!(file, out_dir.path().join("com.example.github.lua"));
+
+        let lua = std::fs::read_to_string(file).unwrap();
+        assert_eq!(lua, workflow.to_lua());
+        assert!(lua.starts_with("-- GitHub & Co (com.example.github)\n"));
+        assert!(lua.contains("--   alfred.workflow.trigger.hotkey\n"));
+        assert!(lua.contains("lux.views.add(lux.alfred.view(workflow, \"SF1\", {"));
+        assert!(lua.contains("id = \"alfred.com.example.github.gh\","));
+        assert!(lua.contains("id = \"alfred.com.example.github.ghn\","));
+        assert!(lua.contains("title = \"New issue: {query}\","));
+    }
+
+    #[test]
+    fn test_parse_script_filter_output() {
+        let dir = Path::new("/workflows/gh");
+
+        let json = r#"{"items": [
+            {"uid": "r1", "title": "lux", "subtitle": "A launcher", "arg": "https://github.com/lux", "icon": {"path": "repo.png"}},
+            {"title": "Loading...", "valid": false}
+        ]}"#;
+        let items = parse_script_filter_output(json, dir).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "r1");
+        assert_eq!(items[0].icon.as_deref(), Some("/workflows/gh/repo.png"));
+        assert_eq!(
+            items[0].data.as_ref().unwrap()["arg"],
+            "https://github.com/lux"
+        );
+        assert_eq!(items[1].id, "alfred:1");
+        assert_eq!(items[1].data.as_ref().unwrap()["valid"], false);
+
+        let xml = r#"<?xml version="1.0"?>
+<items>
+  <item uid="x1" arg="one &amp; two" valid="yes">
+    <title><![CDATA[First <b>]]></title>
+    <subtitle>Sub</subtitle>
+  </item>
+</items>"#;
+        let items = parse_script_filter_output(xml, dir).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "First <b>");
+        assert_eq!(items[0].subtitle.as_deref(), Some("Sub"));
+        assert_eq!(items[0].data.as_ref().unwrap()["arg"], "one & two");
+
+        assert!(parse_script_filter_output("", dir).unwrap().is_empty());
+        assert!(parse_script_filter_output("{\"nope\": 1}", dir).is_err());
+    }
+
+    #[test]
+    fn test_escape_query() {
+        assert_eq!(
+            escape_query(r#"say "$HOME" `x` \"#, DEFAULT_ESCAPING),
+            r#"say \"\$HOME\" \`x\` \\"#
+        );
+        assert_eq!(escape_query("a b;c", 1 | 16), r"a\ b\;c");
+        assert_eq!(escape_query("a b", 0), "a b");
+    }
+}
@@ -0,0 +1,434 @@
+//! Application discovery helpers.
+//!
+//! This module wraps the platform tooling used to work with installed apps:
+//! - `app_icon()` - An app's icon as an image path
+//! - `applications_for_path()` - Apps registered for a file
+//! - `open_with()` - Open a file with a specific app
+//!
+//! On macOS apps are bundles: icons are converted from `.icns` and Launch
+//! Services lists the apps for a file. On Linux apps are freedesktop
+//! `.desktop` files: icons come from the icon theme and apps are matched by
+//! MIME type (`xdg-mime`).
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// An application that can open a given file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AppInfo {
+    /// Display name (without the `.app` suffix on macOS).
+    pub name: String,
+    /// Path to the application bundle (`.desktop` file on Linux).
+    pub path: String,
+    /// Bundle identifier, if the bundle has one (desktop file ID on Linux).
+    #[serde(default)]
+    pub bundle_id: Option<String>,
+    /// Whether this is the default app for the file.
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+// =============================================================================
+// Icons
+// =============================================================================
+
+/// Get a PNG icon for a macOS app bundle.
+#[cfg(not(target_os = "linux"))]
+///
+/// Icons are converted once with `sips` and cached under the user cache
+/// directory. Returns `None` if the bundle has no icon.
+pub fn app_icon(app_path: &str) -> PluginResult<Option<String>> {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    // Create cache directory
+    let cache_dir = dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("lux")
+        .join("icons");
+    std::fs::create_dir_all(&cache_dir).ok();
+
+    // Generate cache filename from app path hash
+    let mut hasher = DefaultHasher::new();
+    app_path.hash(&mut hasher);
+    let cached_png = cache_dir.join(format!("{:x}.png", hasher.finish()));
+
+    // Return cached version if exists
+    if cached_png.exists() {
+        return Ok(Some(cached_png.to_string_lossy().to_string()));
+    }
+
+    // Find and convert .icns to PNG
+    let script = format!(
+        r#"
+        icon_name=$(/usr/bin/defaults read "{}/Contents/Info.plist" CFBundleIconFile 2>/dev/null || echo "AppIcon")
+        icon_name="${{icon_name%.icns}}.icns"
+        icon_path="{}/Contents/Resources/$icon_name"
+        if [ ! -f "$icon_path" ]; then
+            icon_path="{}/Contents/Resources/AppIcon.icns"
+        fi
+        if [ -f "$icon_path" ]; then
+            /usr/bin/sips -s format png -z 64 64 "$icon_path" --out "{}" >/dev/null 2>&1 && echo "{}"
+        fi
+        "#,
+        app_path,
+        app_path,
+        app_path,
+        cached_png.display(),
+        cached_png.display()
+    );
+
+    let output = Command::new("sh")
+        .args(["-c", &script])
+        .output()
+        .map_err(|e| PluginError::Command(format!("Icon conversion failed: {}", e)))?;
+
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if path.is_empty() || !Path::new(&path).exists() {
+        Ok(None)
+    } else {
+        Ok(Some(path))
+    }
+}
+
+// =============================================================================
+// Open With
+// =============================================================================
+
+/// JXA script that asks Launch Services for the apps able to open `argv[0]`.
+#[cfg(not(target_os = "linux"))]
+const APPS_FOR_PATH_SCRIPT: &str = r#"
+ObjC.import('AppKit');
+function run(argv) {
+  const url = $.NSURL.fileURLWithPath(argv[0]);
+  const ws = $.NSWorkspace.sharedWorkspace;
+  const def = ws.URLForApplicationToOpenURL(url);
+  const defPath = def.isNil() ? null : def.path.js;
+  const urls = ws.URLsForApplicationsToOpenURL(url);
+  const fm = $.NSFileManager.defaultManager;
+  const out = [];
+  for (let i = 0; i < urls.count; i++) {
+    const appUrl = urls.objectAtIndex(i);
+    const bundle = $.NSBundle.bundleWithURL(appUrl);
+    out.push({
+      name: fm.displayNameAtPath(appUrl.path).js.replace(/\.app$/, ''),
+      path: appUrl.path.js,
+      bundle_id: bundle.isNil() ? null : bundle.bundleIdentifier.js,
+      is_default: appUrl.path.js === defPath,
+    });
+  }
+  return JSON.stringify(out);
+}
+"#;
+
+/// List the applications registered to open a file.
+///
+/// The default app comes first, followed by the rest sorted by name.
+#[cfg(not(target_os = "linux"))]
+pub fn applications_for_path(path: &str) -> PluginResult<Vec<AppInfo>> {
+    let output = desktop::current()
+        .run_script(APPS_FOR_PATH_SCRIPT, &[path])
+        .map_err(|e| PluginError::Command(format!("Launch Services query failed: {}", e)))?;
+    parse_applications(&output)
+}
+
+/// Parse the JSON app list produced by the Launch Services query.
+///
+/// Duplicate bundle paths are dropped, the default app is moved to the front,
+/// and the remaining apps are sorted case-insensitively by name.
+pub fn parse_applications(json: &str) -> PluginResult<Vec<AppInfo>> {
+    let apps: Vec<AppInfo> = serde_json::from_str(json.trim())
+        .map_err(|e| PluginError::Command(format!("Invalid app list: {}", e)))?;
+    Ok(sort_applications(apps))
+}
+
+/// Drop duplicate paths and put the default app first, then sort by name.
+fn sort_applications(mut apps: Vec<AppInfo>) -> Vec<AppInfo> {
+    let mut seen = std::collections::HashSet::new();
+    apps.retain(|app| seen.insert(app.path.clone()));
+    apps.sort_by(|a, b| {
+        b.is_default
+            .cmp(&a.is_default)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    apps
+}
+
+/// Open a file with a specific application (a `.desktop` file on Linux).
+pub fn open_with(path: &str, app_path: &str) -> PluginResult<()> {
+    desktop::current().open_with(path, app_path).map_err(|e| {
+        PluginError::Command(format!(
+            "Could not open '{}' with '{}': {}",
+            path, app_path, e
+        ))
+    })
+}
+
+// =============================================================================
+// Desktop Entries (Linux)
+// =============================================================================
+
+/// The fields of a freedesktop `.desktop` file Lux uses.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DesktopEntry {
+    /// Display name (`Name=`).
+    pub name: String,
+    /// Icon name or absolute path (`Icon=`).
+    pub icon: Option<String>,
+    /// MIME types the app opens (`MimeType=`).
+    pub mime_types: Vec<String>,
+    /// Hidden from menus (`NoDisplay=true` or `Hidden=true`).
+    pub hidden: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl DesktopEntry {
+    /// Parse the `[Desktop Entry]` group. Returns `None` without a `Name=`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut entry = Self::default();
+        let mut in_group = false;
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_group = line == "[Desktop Entry]";
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=').filter(|_| in_group) else {
+                continue;
+            };
+            match key.trim() {
+                "Name" => entry.name = value.trim().to_string(),
+                "Icon" => entry.icon = Some(value.trim().to_string()),
+                "MimeType" => {
+                    entry.mime_types = value
+                        .split(';')
+                        .filter(|m| !m.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                }
+                "NoDisplay" | "Hidden" if value.trim() == "true" => entry.hidden = true,
+                _ => {}
+            }
+        }
+        (!entry.name.is_empty()).then_some(entry)
+    }
+
+    /// Read and parse a `.desktop` file.
+    pub fn load(path: &Path) -> Option<Self> {
+        Self::parse(&std::fs::read_to_string(path).ok()?)
+    }
+}
+
+/// XDG data directories (`$XDG_DATA_HOME` first, then `$XDG_DATA_DIRS`).
+#[cfg(target_os = "linux")]
+fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::data_dir().into_iter().collect();
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(system.split(':').map(PathBuf::from));
+    dirs
+}
+
+/// Icon sizes to try, best first.
+#[cfg(target_os = "linux")]
+const ICON_SIZES: &[&str] = &[
+    "scalable", "256x256", "128x128", "96x96", "64x64", "48x48", "32x32",
+];
+
+/// Find an icon by name in the icon themes under `roots` (`<root>/<theme>/<size>/apps/`),
+/// falling back to `hicolor` and then `pixmaps` directories.
+#[cfg(target_os = "linux")]
+pub fn find_icon(name: &str, roots: &[PathBuf], theme: &str) -> Option<PathBuf> {
+    let path = Path::new(name);
+    if path.is_absolute() {
+        return path.exists().then(|| path.to_path_buf());
+    }
+
+    for theme in [theme, "hicolor"] {
+        for root in roots {
+            for size in ICON_SIZES {
+                for ext in ["svg", "png"] {
+                    let candidate = root
+                        .join(theme)
+                        .join(size)
+                        .join("apps")
+                        .join(format!("{}.{}", name, ext));
+                    if candidate.exists() {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+    }
+
+    roots
+        .iter()
+        .filter_map(|root| root.parent())
+        .map(|share| share.join("pixmaps").join(format!("{}.png", name)))
+        .find(|candidate| candidate.exists())
+}
+
+/// Get the icon of a `.desktop` application from the current icon theme.
+#[cfg(target_os = "linux")]
+pub fn app_icon(app_path: &str) -> PluginResult<Option<String>> {
+    let Some(icon) = DesktopEntry::load(Path::new(app_path)).and_then(|entry| entry.icon) else {
+        return Ok(None);
+    };
+
+    // GNOME's icon theme setting; other desktops get hicolor
+    let theme = Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "icon-theme"])
+        .output()
+        .ok()
+        .map(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .trim()
+                .trim_matches('\'')
+                .to_string()
+        })
+        .filter(|theme| !theme.is_empty())
+        .unwrap_or_else(|| "hicolor".to_string());
+
+    let roots: Vec<PathBuf> = data_dirs().iter().map(|dir| dir.join("icons")).collect();
+    Ok(find_icon(&icon, &roots, &theme).map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Run `xdg-mime` and return its trimmed output.
+#[cfg(target_os = "linux")]
+fn xdg_mime(args: &[&str]) -> PluginResult<String> {
+    let output = Command::new("xdg-mime")
+        .args(args)
+        .output()
+        .map_err(|e| PluginError::Command(format!("xdg-mime failed: {}", e)))?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// List the applications whose `.desktop` files declare the file's MIME type.
+///
+/// The default app comes first, followed by the rest sorted by name.
+#[cfg(target_os = "linux")]
+pub fn applications_for_path(path: &str) -> PluginResult<Vec<AppInfo>> {
+    let mime = xdg_mime(&["query", "filetype", path])?;
+    if mime.is_empty() {
+        return Ok(Vec::new());
+    }
+    let default = xdg_mime(&["query", "default", &mime])?;
+
+    let mut apps = Vec::new();
+    let mut seen_ids = std::collections::HashSet::new();
+    for dir in data_dirs().iter().map(|dir| dir.join("applications")) {
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let path = file.path();
+            let id = file.file_name().to_string_lossy().to_string();
+            // Earlier directories override later ones with the same ID
+            if !id.ends_with(".desktop") || !seen_ids.insert(id.clone()) {
+                continue;
+            }
+            let Some(entry) = DesktopEntry::load(&path) else {
+                continue;
+            };
+            if entry.hidden || !entry.mime_types.contains(&mime) {
+                continue;
+            }
+            apps.push(AppInfo {
+                name: entry.name,
+                path: path.to_string_lossy().to_string(),
+                is_default: id == default,
+                bundle_id: Some(id),
+            });
+        }
+    }
+
+    Ok(sort_applications(apps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_applications_orders_default_first() {
+        let json = r#"[
+            {"name": "TextEdit", "path": "/System/Applications/TextEdit.app", "bundle_id": "com.apple.TextEdit", "is_default": false},
+            {"name": "Preview", "path": "/System/Applications/Preview.app", "bundle_id": "com.apple.Preview", "is_default": true},
+            {"name": "atom", "path": "/Applications/Atom.app"}
+        ]"#;
+
+        let apps = parse_applications(json).unwrap();
+        let names: Vec<_> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Preview", "atom", "TextEdit"]);
+        assert!(apps[0].is_default);
+        assert_eq!(apps[1].bundle_id, None);
+    }
+
+    #[test]
+    fn test_parse_applications_dedupes_paths() {
+        let json = r#"[
+            {"name": "Preview", "path": "/System/Applications/Preview.app"},
+            {"name": "Preview", "path": "/System/Applications/Preview.app"}
+        ]"#;
+
+        assert_eq!(parse_applications(json).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_applications_invalid() {
+        let err = parse_applications("not json").unwrap_err();
+        assert!(err.to_string().contains("Invalid app list"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_parse_desktop_entry() {
+        let entry = DesktopEntry::parse(
+            "[Desktop Entry]\nName=Text Editor\nIcon=org.gnome.TextEditor\n\
+             MimeType=text/plain;text/markdown;\n\n[Desktop Action new-window]\nName=New Window\n",
+        )
+        .unwrap();
+        assert_eq!(entry.name, "Text Editor");
+        assert_eq!(entry.icon.as_deref(), Some("org.gnome.TextEditor"));
+        assert_eq!(entry.mime_types, vec!["text/plain", "text/markdown"]);
+        assert!(!entry.hidden);
+
+        assert!(DesktopEntry::parse("[Desktop Entry]\nIcon=x\n").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_find_icon_prefers_theme_then_hicolor() {
+        let share = tempfile::tempdir().unwrap();
+        let icons = share.path().join("icons");
+        let hicolor = icons.join("hicolor/48x48/apps");
+        let themed = icons.join("Papirus/scalable/apps");
+        std::fs::create_dir_all(&hicolor).unwrap();
+        std::fs::create_dir_all(&themed).unwrap();
+        std::fs::write(hicolor.join("editor.png"), b"").unwrap();
+        std::fs::write(hicolor.join("viewer.png"), b"").unwrap();
+        std::fs::write(themed.join("editor.svg"), b"").unwrap();
+
+        let roots = vec![icons];
+        assert_eq!(
+            find_icon("editor", &roots, "Papirus"),
+            Some(themed.join("editor.svg"))
+        );
+        assert_eq!(
+            find_icon("viewer", &roots, "Papirus"),
+            Some(hicolor.join("viewer.png"))
+        );
+        assert_eq!(find_icon("missing", &roots, "Papirus"), None);
+    }
+}
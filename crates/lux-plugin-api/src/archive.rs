@@ -0,0 +1,537 @@
+//! Zip and tar archives, written and read in-process.
+//!
+//! This module provides:
+//! - `ArchiveFormat` - Zip, tar, and gzip-compressed tar
+//! - `compress()` / `compress_as()` - Pack files and folders into an archive (lux.archive.zip/tar)
+//! - `extract()` - Unpack an archive into a folder (lux.archive.extract)
+//! - `archive_actions()` / `start_archive_action()` - "Compress" and "Extract" for file items
+//!
+//! Both directions call a `(done, total)` progress callback after every
+//! entry. The built-in actions run on a background thread: they report
+//! "Compressing…" / "Extracting…" through the action progress message and
+//! post a notification when the archive is done.
+//!
+//! ```lua
+//! local archive = lux.archive.zip({ "~/Desktop/report.pdf", "~/Desktop/data" }, {
+//!     on_progress = function(done, total) print(done .. "/" .. total) end,
+//! })
+//! local folder = lux.archive.extract(archive, { dest = "/tmp/unpacked" })
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::finder::item_path;
+use crate::qr::unused_path;
+
+/// Prefix of the Lua registry keys of the built-in archive actions.
+pub const ARCHIVE_ACTION_KEY_PREFIX: &str = "archive:";
+
+/// IDs of the built-in archive actions.
+pub const ARCHIVE_ACTION_IDS: [&str; 2] = ["compress", "extract"];
+
+/// Folder macOS adds to zip files for resource forks; never extracted.
+const MACOS_METADATA_DIR: &str = "__MACOSX";
+
+// =============================================================================
+// Formats
+// =============================================================================
+
+/// Supported archive formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// The format of an archive, from its file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// File extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::Tar => "tar",
+            Self::TarGz => "tar.gz",
+        }
+    }
+}
+
+/// File name of an archive without its archive extension.
+fn archive_stem(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let lower = name.to_lowercase();
+    for ext in [".tar.gz", ".tgz", ".tar", ".zip"] {
+        if lower.ends_with(ext) {
+            return name[..name.len() - ext.len()].to_string();
+        }
+    }
+    name
+}
+
+/// Where to write an archive of `paths`: next to the first path, named
+/// after it (or "Archive" for several), without overwriting anything.
+pub fn default_archive_path(paths: &[PathBuf], format: ArchiveFormat) -> Option<PathBuf> {
+    let first = paths.first()?;
+    let dir = first.parent()?;
+    let name = match paths {
+        [single] => single.file_name()?.to_string_lossy().to_string(),
+        _ => t("Archive"),
+    };
+    Some(unused_path(dir, &name, format.extension()))
+}
+
+/// Where to extract an archive: a new folder next to it, named after it.
+pub fn default_extract_dir(archive: &Path) -> Option<PathBuf> {
+    let dir = archive.parent()?;
+    let name = archive_stem(archive);
+    let mut path = dir.join(&name);
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} {}", name, n));
+        n += 1;
+    }
+    Some(path)
+}
+
+// =============================================================================
+// Compressing
+// =============================================================================
+
+/// A file or folder to add, with its path inside the archive.
+struct Entry {
+    source: PathBuf,
+    name: String,
+    is_dir: bool,
+}
+
+/// Every file and folder under `paths`, folders before their contents.
+///
+/// Each path becomes a top-level entry with the name it is paired with.
+/// Symlinks to folders are skipped rather than followed.
+fn collect_entries(paths: &[(PathBuf, String)]) -> io::Result<Vec<Entry>> {
+    fn add(path: &Path, name: String, entries: &mut Vec<Entry>) -> io::Result<()> {
+        let metadata = fs::symlink_metadata(path)?;
+        if metadata.is_dir() {
+            let mut children: Vec<PathBuf> = fs::read_dir(path)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<_>>()?;
+            children.sort();
+            entries.push(Entry {
+                source: path.to_path_buf(),
+                name: name.clone(),
+                is_dir: true,
+            });
+            for child in children {
+                let child_name = child.file_name().unwrap_or_default().to_string_lossy();
+                add(&child, format!("{}/{}", name, child_name), entries)?;
+            }
+        } else if path.is_file() {
+            entries.push(Entry {
+                source: path.to_path_buf(),
+                name,
+                is_dir: false,
+            });
+        }
+        Ok(())
+    }
+
+    let mut entries = Vec::new();
+    for (path, name) in paths {
+        add(path, name.clone(), &mut entries)?;
+    }
+    Ok(entries)
+}
+
+/// Pack `paths` (files or folders) into a new archive at `dest`.
+///
+/// `progress` is called with `(done, total)` entries after each one.
+pub fn compress(
+    paths: &[PathBuf],
+    dest: &Path,
+    format: ArchiveFormat,
+    progress: &mut dyn FnMut(usize, usize),
+) -> PluginResult<()> {
+    let named = paths
+        .iter()
+        .map(|path| match path.file_name() {
+            Some(name) => Ok((path.clone(), name.to_string_lossy().to_string())),
+            None => Err(PluginError::Command(format!(
+                "Compressing failed: '{}' has no file name",
+                path.display()
+            ))),
+        })
+        .collect::<PluginResult<Vec<_>>>()?;
+    compress_as(&named, dest, format, progress)
+}
+
+/// Pack `paths` into a new archive at `dest`, each under the name it is
+/// paired with instead of its file name.
+pub fn compress_as(
+    paths: &[(PathBuf, String)],
+    dest: &Path,
+    format: ArchiveFormat,
+    progress: &mut dyn FnMut(usize, usize),
+) -> PluginResult<()> {
+    let result = collect_entries(paths).and_then(|entries| {
+        let file = BufWriter::new(File::create(dest)?);
+        match format {
+            ArchiveFormat::Zip => write_zip(file, &entries, progress),
+            ArchiveFormat::Tar => write_tar(file, &entries, progress)?.flush(),
+            ArchiveFormat::TarGz => {
+                let encoder = GzEncoder::new(file, Compression::default());
+                write_tar(encoder, &entries, progress)?.finish()?.flush()
+            }
+        }
+    });
+
+    result.map_err(|e| {
+        // Don't leave a truncated archive behind
+        let _ = fs::remove_file(dest);
+        PluginError::Command(format!("Compressing failed: {}", e))
+    })
+}
+
+fn write_zip<W: Write + io::Seek>(
+    writer: W,
+    entries: &[Entry],
+    progress: &mut dyn FnMut(usize, usize),
+) -> io::Result<()> {
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.is_dir {
+            zip.add_directory(format!("{}/", entry.name), options)?;
+        } else {
+            let metadata = fs::metadata(&entry.source)?;
+            let mut options = options.large_file(metadata.len() >= u32::MAX as u64);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                options = options.unix_permissions(metadata.permissions().mode());
+            }
+            zip.start_file(entry.name.as_str(), options)?;
+            io::copy(&mut File::open(&entry.source)?, &mut zip)?;
+        }
+        progress(index + 1, entries.len());
+    }
+
+    zip.finish()?.flush()
+}
+
+/// Write a tar stream, returning the inner writer to flush or finish.
+fn write_tar<W: Write>(
+    writer: W,
+    entries: &[Entry],
+    progress: &mut dyn FnMut(usize, usize),
+) -> io::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.is_dir {
+            builder.append_dir(&entry.name, &entry.source)?;
+        } else {
+            builder.append_path_with_name(&entry.source, &entry.name)?;
+        }
+        progress(index + 1, entries.len());
+    }
+
+    builder.into_inner()
+}
+
+// =============================================================================
+// Extracting
+// =============================================================================
+
+/// Unpack an archive into `dest_dir`, creating it if needed.
+///
+/// Entries that would land outside `dest_dir` (absolute paths or `..`) are
+/// skipped. `progress` is called with `(done, total)` entries after each one.
+pub fn extract(
+    archive: &Path,
+    dest_dir: &Path,
+    progress: &mut dyn FnMut(usize, usize),
+) -> PluginResult<()> {
+    let format = ArchiveFormat::from_path(archive).ok_or_else(|| {
+        PluginError::Command(format!(
+            "'{}' is not a zip or tar archive",
+            archive.display()
+        ))
+    })?;
+
+    let result = fs::create_dir_all(dest_dir).and_then(|()| match format {
+        ArchiveFormat::Zip => extract_zip(archive, dest_dir, progress),
+        ArchiveFormat::Tar | ArchiveFormat::TarGz => {
+            extract_tar(archive, format, dest_dir, progress)
+        }
+    });
+
+    result.map_err(|e| PluginError::Command(format!("Extracting failed: {}", e)))
+}
+
+fn extract_zip(
+    archive: &Path,
+    dest_dir: &Path,
+    progress: &mut dyn FnMut(usize, usize),
+) -> io::Result<()> {
+    let mut zip = ZipArchive::new(BufReader::new(File::open(archive)?))?;
+    let total = zip.len();
+
+    for index in 0..total {
+        let mut file = zip.by_index(index)?;
+        let Some(name) = file.enclosed_name() else {
+            continue;
+        };
+        if name.starts_with(MACOS_METADATA_DIR) {
+            continue;
+        }
+
+        let out = dest_dir.join(name);
+        if file.is_dir() {
+            fs::create_dir_all(&out)?;
+        } else {
+            if let Some(parent) = out.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut file, &mut File::create(&out)?)?;
+            #[cfg(unix)]
+            if let Some(mode) = file.unix_mode() {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&out, fs::Permissions::from_mode(mode & 0o777))?;
+            }
+        }
+        progress(index + 1, total);
+    }
+    Ok(())
+}
+
+fn extract_tar(
+    archive: &Path,
+    format: ArchiveFormat,
+    dest_dir: &Path,
+    progress: &mut dyn FnMut(usize, usize),
+) -> io::Result<()> {
+    let open = || -> io::Result<tar::Archive<Box<dyn Read>>> {
+        let file = BufReader::new(File::open(archive)?);
+        let reader: Box<dyn Read> = match format {
+            ArchiveFormat::TarGz => Box::new(GzDecoder::new(file)),
+            _ => Box::new(file),
+        };
+        Ok(tar::Archive::new(reader))
+    };
+
+    // Tar has no index, so count the entries in a first pass
+    let total = open()?.entries()?.count();
+
+    let mut tar = open()?;
+    for (index, entry) in tar.entries()?.enumerate() {
+        // unpack_in skips entries outside dest_dir
+        entry?.unpack_in(dest_dir)?;
+        progress(index + 1, total);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Registry key of an archive action's handler.
+pub fn archive_action_key(action_id: &str) -> String {
+    format!("{}{}", ARCHIVE_ACTION_KEY_PREFIX, action_id)
+}
+
+/// Archive actions for a file item: `(id, title)`.
+///
+/// Archives get "Extract", other files and folders "Compress".
+pub fn archive_actions(item: &Item) -> Vec<(&'static str, String)> {
+    let Some(path) = item_path(item).filter(|_| item.has_type("file")) else {
+        return Vec::new();
+    };
+    if ArchiveFormat::from_path(Path::new(path)).is_some() {
+        vec![("extract", t("Extract"))]
+    } else {
+        vec![("compress", t("Compress"))]
+    }
+}
+
+/// Start an archive action on the items in the background.
+///
+/// Returns the progress message to show; a notification follows when the
+/// archive is written or extracted.
+pub fn start_archive_action(action_id: &str, items: &[Item]) -> PluginResult<String> {
+    let paths: Vec<PathBuf> = items
+        .iter()
+        .filter_map(item_path)
+        .map(PathBuf::from)
+        .collect();
+
+    match action_id {
+        "compress" => {
+            let dest = default_archive_path(&paths, ArchiveFormat::Zip)
+                .ok_or_else(|| PluginError::Command("No files to compress".to_string()))?;
+            let message = match paths.len() {
+                1 => t("Compressing…"),
+                count => t_args(
+                    "Compressing {count} items…",
+                    &[("count", &count.to_string())],
+                ),
+            };
+            std::thread::spawn(move || {
+                let result = compress(&paths, &dest, ArchiveFormat::Zip, &mut |_, _| {});
+                notify_done(result, t("Compressed"), &dest);
+            });
+            Ok(message)
+        }
+        "extract" => {
+            let archive = paths
+                .into_iter()
+                .next()
+                .ok_or_else(|| PluginError::Command("No archive to extract".to_string()))?;
+            let dest = default_extract_dir(&archive).ok_or_else(|| {
+                PluginError::Command(format!("Can't extract '{}'", archive.display()))
+            })?;
+            std::thread::spawn(move || {
+                let result = extract(&archive, &dest, &mut |_, _| {});
+                notify_done(result, t("Extracted"), &dest);
+            });
+            Ok(t("Extracting…"))
+        }
+        _ => Err(PluginError::Command(format!(
+            "Unknown archive action '{}'",
+            action_id
+        ))),
+    }
+}
+
+/// Notify about a finished background archive action.
+fn notify_done(result: PluginResult<()>, title: String, path: &Path) {
+    let desktop = desktop::current();
+    let notified = match result {
+        Ok(()) => {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            desktop.notify(&title, &name)
+        }
+        Err(e) => {
+            tracing::warn!("{}", e);
+            desktop.notify(&t("Archive action failed"), &e.to_string())
+        }
+    };
+    if let Err(e) = notified {
+        tracing::warn!("Failed to post archive notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("/tmp/a.ZIP")),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("/tmp/a.tar.gz")),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path(Path::new("/tmp/a.tgz")),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(ArchiveFormat::from_path(Path::new("/tmp/a.pdf")), None);
+        assert_eq!(archive_stem(Path::new("/tmp/Photos.tar.gz")), "Photos");
+    }
+
+    #[test]
+    fn test_default_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("notes.txt");
+        assert_eq!(
+            default_archive_path(&[file], ArchiveFormat::Zip),
+            Some(dir.path().join("notes.txt.zip"))
+        );
+
+        fs::create_dir(dir.path().join("notes")).unwrap();
+        assert_eq!(
+            default_extract_dir(&dir.path().join("notes.zip")),
+            Some(dir.path().join("notes 2"))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("project");
+        fs::create_dir_all(folder.join("src")).unwrap();
+        fs::write(folder.join("src/main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join("readme.md"), "# Readme").unwrap();
+        let paths = vec![folder, dir.path().join("readme.md")];
+
+        for format in [ArchiveFormat::Zip, ArchiveFormat::Tar, ArchiveFormat::TarGz] {
+            let archive = dir.path().join(format!("out.{}", format.extension()));
+            let mut reported = Vec::new();
+            compress(&paths, &archive, format, &mut |done, total| {
+                reported.push((done, total))
+            })
+            .unwrap();
+            // project, project/src, project/src/main.rs, readme.md
+            assert_eq!(reported.last(), Some(&(4, 4)));
+
+            let out = dir.path().join(format!("unpacked-{}", format.extension()));
+            extract(&archive, &out, &mut |_, _| {}).unwrap();
+            assert_eq!(
+                fs::read_to_string(out.join("project/src/main.rs")).unwrap(),
+                "fn main() {}"
+            );
+            assert_eq!(
+                fs::read_to_string(out.join("readme.md")).unwrap(),
+                "# Readme"
+            );
+        }
+    }
+
+    #[test]
+    fn test_archive_actions() {
+        let item = |path: &str| {
+            Item::new(path.to_string(), path.to_string())
+                .with_type("file")
+                .with_data(serde_json::json!({ "path": path }))
+        };
+        assert_eq!(archive_actions(&item("/tmp/a.zip"))[0].0, "extract");
+        assert_eq!(archive_actions(&item("/tmp/a.pdf"))[0].0, "compress");
+
+        let mut text = item("/tmp/a.pdf");
+        text.types.clear();
+        assert!(archive_actions(&text).is_empty());
+    }
+}
@@ -0,0 +1,322 @@
+//! Sound effects and haptic feedback set from Lua.
+//!
+//! This module provides:
+//! - `SoundEvent` - Launcher events that can play a sound (open, execute, error)
+//! - `SoundSettings` - Per-event sounds, volume, and haptics (`lux.ui.sounds`)
+//! - `SoundStore` - Storage for the settings, merged like theme overrides
+//! - `play` - Play a system sound by name or a sound file
+//!
+//! Feedback is off unless `enabled = true` is set. Each event then plays its
+//! default sound unless set to `false` or to another sound name or file.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use parking_lot::RwLock;
+
+use crate::error::{PluginError, PluginResult};
+
+/// Volume used when none is set.
+pub const DEFAULT_VOLUME: f32 = 0.5;
+
+/// Launcher events with audio feedback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    /// The launcher window opened.
+    Open,
+    /// An action completed.
+    Execute,
+    /// An action failed.
+    Error,
+}
+
+impl SoundEvent {
+    /// All events, in settings order.
+    pub const ALL: [SoundEvent; 3] = [Self::Open, Self::Execute, Self::Error];
+
+    /// Name as used in `lux.ui.sounds`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Execute => "execute",
+            Self::Error => "error",
+        }
+    }
+
+    /// Parse "open", "execute", or "error".
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|event| event.name() == name)
+    }
+
+    /// Sound played for the event when none is configured.
+    pub fn default_sound(&self) -> &'static str {
+        if cfg!(target_os = "linux") {
+            match self {
+                Self::Open => "dialog-information",
+                Self::Execute => "complete",
+                Self::Error => "dialog-error",
+            }
+        } else {
+            match self {
+                Self::Open => "Pop",
+                Self::Execute => "Tink",
+                Self::Error => "Basso",
+            }
+        }
+    }
+}
+
+/// Sound setting for a single event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventSound {
+    /// Play nothing for the event.
+    Off,
+    /// Play the event's default sound.
+    Default,
+    /// Play a system sound by name, or a sound file by path.
+    Named(String),
+}
+
+/// Audio and haptic feedback settings from Lua.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoundSettings {
+    /// Master switch; feedback is off unless this is `Some(true)`.
+    pub enabled: Option<bool>,
+    /// Playback volume (0-1).
+    pub volume: Option<f32>,
+    /// Also perform haptic feedback on trackpads that support it.
+    pub haptics: Option<bool>,
+    /// Sound when the launcher opens.
+    pub open: Option<EventSound>,
+    /// Sound when an action completes.
+    pub execute: Option<EventSound>,
+    /// Sound when an action fails.
+    pub error: Option<EventSound>,
+}
+
+impl SoundSettings {
+    /// Check that the values are in range.
+    pub fn validate(&self) -> Result<(), SoundError> {
+        if let Some(volume) = self.volume {
+            if !(0.0..=1.0).contains(&volume) {
+                return Err(SoundError::InvalidVolume(volume));
+            }
+        }
+        for event in SoundEvent::ALL {
+            if let Some(EventSound::Named(name)) = self.event(event) {
+                if name.trim().is_empty() {
+                    return Err(SoundError::EmptySound(event.name()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Overlay `other` onto these settings (fields set in `other` win).
+    pub fn merge(&mut self, other: SoundSettings) {
+        self.enabled = other.enabled.or(self.enabled);
+        self.volume = other.volume.or(self.volume);
+        self.haptics = other.haptics.or(self.haptics);
+        self.open = other.open.or(self.open.take());
+        self.execute = other.execute.or(self.execute.take());
+        self.error = other.error.or(self.error.take());
+    }
+
+    /// The configured sound for an event, if any.
+    pub fn event(&self, event: SoundEvent) -> Option<&EventSound> {
+        match event {
+            SoundEvent::Open => self.open.as_ref(),
+            SoundEvent::Execute => self.execute.as_ref(),
+            SoundEvent::Error => self.error.as_ref(),
+        }
+    }
+
+    /// Whether feedback is on and the event isn't turned off.
+    pub fn is_event_enabled(&self, event: SoundEvent) -> bool {
+        self.enabled == Some(true) && self.event(event) != Some(&EventSound::Off)
+    }
+
+    /// Sound to play for an event, or `None` when it should stay silent.
+    pub fn sound_for(&self, event: SoundEvent) -> Option<String> {
+        if !self.is_event_enabled(event) {
+            return None;
+        }
+        match self.event(event) {
+            Some(EventSound::Named(name)) => Some(name.clone()),
+            _ => Some(event.default_sound().to_string()),
+        }
+    }
+
+    /// Whether to perform haptic feedback for an event.
+    pub fn haptics_for(&self, event: SoundEvent) -> bool {
+        self.haptics == Some(true) && self.is_event_enabled(event)
+    }
+
+    /// Volume to play at.
+    pub fn volume(&self) -> f32 {
+        self.volume.unwrap_or(DEFAULT_VOLUME)
+    }
+}
+
+/// Storage for sound settings.
+pub struct SoundStore {
+    settings: RwLock<SoundSettings>,
+}
+
+impl SoundStore {
+    /// Create a store with feedback off.
+    pub fn new() -> Self {
+        Self {
+            settings: RwLock::new(SoundSettings::default()),
+        }
+    }
+
+    /// Validate and merge settings into the store.
+    pub fn set(&self, settings: SoundSettings) -> Result<(), SoundError> {
+        settings.validate()?;
+        self.settings.write().merge(settings);
+        Ok(())
+    }
+
+    /// Get the current settings.
+    pub fn get(&self) -> SoundSettings {
+        self.settings.read().clone()
+    }
+
+    /// Clear all settings, turning feedback off.
+    pub fn reset(&self) {
+        *self.settings.write() = SoundSettings::default();
+    }
+}
+
+impl Default for SoundStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors that can occur when setting sounds.
+#[derive(Debug, thiserror::Error)]
+pub enum SoundError {
+    #[error("Invalid volume {0} (expected 0-1)")]
+    InvalidVolume(f32),
+
+    #[error("Empty sound name for '{0}'")]
+    EmptySound(&'static str),
+}
+
+// =============================================================================
+// Playback
+// =============================================================================
+
+/// Play the sound configured for an event, if any.
+pub fn play_event(settings: &SoundSettings, event: SoundEvent) -> PluginResult<()> {
+    match settings.sound_for(event) {
+        Some(sound) => play(&sound, settings.volume()),
+        None => Ok(()),
+    }
+}
+
+/// Play a system sound by name (e.g. "Glass") or a sound file by path.
+#[cfg(not(target_os = "linux"))]
+pub fn play(sound: &str, volume: f32) -> PluginResult<()> {
+    let path = if Path::new(sound).is_file() {
+        sound.to_string()
+    } else {
+        format!("/System/Library/Sounds/{}.aiff", sound)
+    };
+    spawn(Command::new("afplay").args(["-v", &volume.to_string(), &path]))
+}
+
+/// Play a freedesktop sound by name (e.g. "complete") or a sound file by path.
+#[cfg(target_os = "linux")]
+pub fn play(sound: &str, volume: f32) -> PluginResult<()> {
+    // canberra-gtk-play takes volume in decibels; 0.5 maps to about -6 dB
+    let db = (20.0 * volume.max(0.01).log10()).round().to_string();
+    if Path::new(sound).is_file() {
+        spawn(Command::new("canberra-gtk-play").args(["-f", sound, "-V", &db]))
+            .or_else(|_| spawn(Command::new("paplay").arg(sound)))
+    } else {
+        spawn(Command::new("canberra-gtk-play").args(["-i", sound, "-V", &db]))
+    }
+}
+
+/// Start the player without waiting for the sound to finish.
+fn spawn(cmd: &mut Command) -> PluginResult<()> {
+    let mut child = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| PluginError::Command(format!("Sound playback failed: {}", e)))?;
+    // Reap it in the background so it doesn't linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_merges_settings() {
+        let store = SoundStore::new();
+        store
+            .set(SoundSettings {
+                enabled: Some(true),
+                open: Some(EventSound::Off),
+                error: Some(EventSound::Named("Funk".to_string())),
+                ..Default::default()
+            })
+            .unwrap();
+        store
+            .set(SoundSettings {
+                volume: Some(0.8),
+                open: Some(EventSound::Default),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let settings = store.get();
+        assert_eq!(settings.enabled, Some(true));
+        assert_eq!(settings.volume(), 0.8);
+        assert_eq!(settings.open, Some(EventSound::Default));
+        assert_eq!(settings.error, Some(EventSound::Named("Funk".to_string())));
+
+        store.reset();
+        assert_eq!(store.get(), SoundSettings::default());
+        assert!(matches!(
+            store.set(SoundSettings {
+                volume: Some(2.0),
+                ..Default::default()
+            }),
+            Err(SoundError::InvalidVolume(_))
+        ));
+    }
+
+    #[test]
+    fn test_sound_for_respects_flags() {
+        let mut settings = SoundSettings {
+            execute: Some(EventSound::Off),
+            error: Some(EventSound::Named("Funk".to_string())),
+            haptics: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(settings.sound_for(SoundEvent::Open), None);
+        assert!(!settings.haptics_for(SoundEvent::Open));
+
+        settings.enabled = Some(true);
+        assert_eq!(
+            settings.sound_for(SoundEvent::Open).as_deref(),
+            Some(SoundEvent::Open.default_sound())
+        );
+        assert_eq!(settings.sound_for(SoundEvent::Execute), None);
+        assert!(!settings.haptics_for(SoundEvent::Execute));
+        assert_eq!(
+            settings.sound_for(SoundEvent::Error).as_deref(),
+            Some("Funk")
+        );
+        assert!(settings.haptics_for(SoundEvent::Error));
+        assert_eq!(SoundEvent::from_name("error"), Some(SoundEvent::Error));
+        assert_eq!(SoundEvent::from_name("close"), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! Starting Lux at login.
+//!
+//! This module provides:
+//! - `Autostart` - Where the login item lives and the binary it starts (lux.autostart)
+//! - `AutostartStatus` - Whether Lux starts at login, and from where
+//! - `preference_groups()` - The rows of the preferences view
+//!
+//! On macOS the login item is a LaunchAgent in `~/Library/LaunchAgents`.
+//! `SMAppService` would need Lux to run from a signed app bundle that embeds
+//! the agent, so the plain plist is used; it works on every macOS version.
+//! On Linux it is an XDG autostart entry in `~/.config/autostart`.
+//!
+//! Enabling takes effect at the next login; it doesn't start a second Lux
+//! now, and disabling doesn't quit the running one.
+
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::t;
+use lux_core::{Group, Groups, Item};
+
+use crate::error::{PluginError, PluginResult};
+use crate::theme::{DisplayPreferences, ThemeDensity, COMPACT_ITEM_ID, SCALE_ITEM_ID};
+
+/// ID of the built-in preferences view.
+pub const PREFERENCES_VIEW_ID: &str = "builtin.preferences";
+
+/// ID of the "Open at Login" row in the preferences view.
+pub const AUTOSTART_ITEM_ID: &str = "autostart";
+
+/// launchd label of the LaunchAgent (also its file name).
+const AGENT_LABEL: &str = "dev.lux.launcher";
+
+/// Whether Lux starts at login.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutostartStatus {
+    /// A login item for Lux exists.
+    pub enabled: bool,
+    /// Where the login item is (or would be) written.
+    pub path: PathBuf,
+    /// The binary the login item starts, if enabled.
+    pub program: Option<PathBuf>,
+}
+
+/// The login item: where it lives and the binary it starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Autostart {
+    path: PathBuf,
+    program: PathBuf,
+}
+
+impl Autostart {
+    /// Create a login item at `path` that starts `program`.
+    pub fn new(path: impl Into<PathBuf>, program: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            program: program.into(),
+        }
+    }
+
+    /// The login item for the running binary, at the platform's location.
+    ///
+    /// None if there is no home directory or the binary can't be located.
+    pub fn current() -> Option<Self> {
+        let program = std::env::current_exe().ok()?;
+        Some(Self::new(default_path()?, program))
+    }
+
+    /// Write the login item, replacing an older one.
+    pub fn enable(&self) -> PluginResult<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                PluginError::Command(format!("Cannot create {}: {}", dir.display(), e))
+            })?;
+        }
+        std::fs::write(&self.path, self.contents()).map_err(|e| {
+            PluginError::Command(format!("Cannot write {}: {}", self.path.display(), e))
+        })
+    }
+
+    /// Remove the login item. Returns false if there was none.
+    pub fn disable(&self) -> PluginResult<bool> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(PluginError::Command(format!(
+                "Cannot remove {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Whether the login item exists, and the binary it starts.
+    pub fn status(&self) -> AutostartStatus {
+        let program = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|contents| read_program(&contents));
+        AutostartStatus {
+            enabled: self.path.exists(),
+            path: self.path.clone(),
+            program,
+        }
+    }
+
+    /// The login item's file contents.
+    fn contents(&self) -> String {
+        if cfg!(target_os = "macos") {
+            launch_agent(&self.program)
+        } else {
+            desktop_entry(&self.program)
+        }
+    }
+}
+
+/// Where the login item is written on this platform.
+pub fn default_path() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        dirs::home_dir().map(|home| {
+            home.join("Library")
+                .join("LaunchAgents")
+                .join(format!("{}.plist", AGENT_LABEL))
+        })
+    } else {
+        dirs::config_dir().map(|dir| dir.join("autostart").join("lux.desktop"))
+    }
+}
+
+/// A LaunchAgent that starts `program` once at login.
+fn launch_agent(program: &Path) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>ProcessType</key>
+    <string>Interactive</string>
+</dict>
+</plist>
+"#,
+        AGENT_LABEL,
+        xml_escape(&program.to_string_lossy())
+    )
+}
+
+/// An XDG autostart entry that starts `program`.
+fn desktop_entry(program: &Path) -> String {
+    let program = program.to_string_lossy();
+    let exec = program.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        "[Desktop Entry]\nType=Application\nName=Lux\nComment=Keyboard launcher\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+        exec.replace('%', "%%")
+    )
+}
+
+/// The binary a LaunchAgent or autostart entry starts.
+fn read_program(contents: &str) -> Option<PathBuf> {
+    let program = if let Some(exec) = contents.lines().find_map(|line| line.strip_prefix("Exec=")) {
+        exec.trim()
+            .trim_matches('"')
+            .replace("%%", "%")
+            .replace("\\\"", "\"")
+            .replace("\\\\", "\\")
+    } else {
+        let args = contents.split("<key>ProgramArguments</key>").nth(1)?;
+        let start = args.find("<string>")? + "<string>".len();
+        let end = args[start..].find("</string>")? + start;
+        xml_unescape(&args[start..end])
+    };
+    Some(PathBuf::from(program))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+// =============================================================================
+// Preferences View
+// =============================================================================
+
+/// Rows of the preferences view whose title contains `query`.
+pub fn preference_groups(
+    autostart: Option<&Autostart>,
+    display: &DisplayPreferences,
+    query: &str,
+) -> Groups {
+    let query = query.trim().to_lowercase();
+    let mut items = Vec::new();
+
+    let title = t("Open at Login");
+    if title.to_lowercase().contains(&query) {
+        let (subtitle, accessory) = match autostart.map(Autostart::status) {
+            Some(status) if status.enabled => (status.path.display().to_string(), t("On")),
+            Some(status) => (status.path.display().to_string(), t("Off")),
+            None => (t("Not available"), t("Off")),
+        };
+        items.push(
+            Item::new(AUTOSTART_ITEM_ID, title)
+                .with_subtitle(subtitle)
+                .with_accessory(accessory),
+        );
+    }
+
+    let title = t("Compact Mode");
+    if title.to_lowercase().contains(&query) {
+        let compact = display.density == Some(ThemeDensity::Compact);
+        items.push(
+            Item::new(COMPACT_ITEM_ID, title)
+                .with_subtitle(t("Shorter rows and tighter spacing"))
+                .with_accessory(if compact { t("On") } else { t("Off") }),
+        );
+    }
+
+    let title = t("Interface Scale");
+    if title.to_lowercase().contains(&query) {
+        let percent = (display.scale.unwrap_or(1.0) * 100.0).round();
+        items.push(
+            Item::new(SCALE_ITEM_ID, title)
+                .with_subtitle(t("Row heights, paddings, and text size together"))
+                .with_accessory(format!("{}%", percent)),
+        );
+    }
+
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::ungrouped(items)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enable_and_disable() {
+        let dir = tempfile::tempdir().unwrap();
+        let program = PathBuf::from("/Applications/Lux & Co/lux");
+        let autostart = Autostart::new(dir.path().join("agents").join("lux.item"), &program);
+
+        assert!(!autostart.status().enabled);
+        autostart.enable().unwrap();
+        let status = autostart.status();
+        assert!(status.enabled);
+        assert_eq!(status.program, Some(program));
+
+        assert!(autostart.disable().unwrap());
+        assert!(!autostart.status().enabled);
+        assert!(!autostart.disable().unwrap());
+    }
+
+    #[test]
+    fn test_read_program_from_both_formats() {
+        let program = Path::new("/opt/lux \"beta\"/lux");
+        assert_eq!(
+            read_program(&launch_agent(program)).as_deref(),
+            Some(program)
+        );
+        assert_eq!(
+            read_program(&desktop_entry(program)).as_deref(),
+            Some(program)
+        );
+    }
+}
@@ -0,0 +1,666 @@
+//! Backups of the config and data folders, and syncing the data folder.
+//!
+//! This module provides:
+//! - `Backup` - Where the config, data, and backups live (`lux backup`, `lux.builtin.backup()`)
+//! - `Backup::create()` / `Backup::restore()` - One `.tar.gz` of init.lua, plugins, and data stores
+//! - `Backup::apply_pending_restore()` - Put a restored backup in place, at startup
+//! - `Backup::sync_to()` - Move the data folder into a synced folder (iCloud Drive, Dropbox)
+//! - `SyncLock` - Keeps two machines from using a synced data folder at once
+//! - `backup_groups()` - The rows of the Backup view
+//!
+//! A backup has a `config/` folder (init.lua, plugins, and the themes and
+//! settings it sets) and a `data/` folder (history, pins, activity, and the
+//! other stores). Restoring only unpacks the backup next to the data folder;
+//! the next Lux to start moves the current folders aside to
+//! `<folder>.before-restore` and puts the backup in their place, before its
+//! stores load. Done while Lux runs, its stores would save the old data over
+//! the backup when it quits. Encrypted stores (`lux.storage`) need the
+//! Keychain key of the machine that wrote them.
+//!
+//! ```sh
+//! lux backup create                       # into ~/Documents/Lux Backups
+//! lux backup restore lux-backup-2024-05-01-093000.tar.gz
+//! lux backup sync ~/Library/Mobile\ Documents/com~apple~CloudDocs
+//! ```
+//!
+//! Folders are copied under a temporary name and renamed into place last, so
+//! a copy that fails halfway leaves the old folder as it was.
+//!
+//! Syncing replaces the data folder with a link into the synced folder. The
+//! running Lux holds `lux.lock` there, and a Lux on another machine refuses
+//! to start until it is released (on quit) or removed with
+//! `lux backup unlock`, since two of them saving the same stores would lose
+//! changes.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, Item};
+use serde::{Deserialize, Serialize};
+
+use crate::archive::{compress_as, extract, ArchiveFormat};
+use crate::error::{PluginError, PluginResult};
+use crate::recent::format_age;
+
+/// ID of the built-in Backup view.
+pub const BACKUP_VIEW_ID: &str = "builtin.backup";
+
+/// ID of the "Create Backup" row in the Backup view.
+pub const CREATE_ITEM_ID: &str = "backup:create";
+
+/// Name of the lock file in a synced data folder.
+pub const LOCK_FILE: &str = "lux.lock";
+
+/// Start of backup file names.
+const BACKUP_PREFIX: &str = "lux-backup-";
+
+/// Extension of backup files.
+const BACKUP_EXTENSION: &str = ".tar.gz";
+
+/// Folder in a backup holding the config folder.
+const CONFIG_ENTRY: &str = "config";
+
+/// Folder in a backup holding the data folder.
+const DATA_ENTRY: &str = "data";
+
+// =============================================================================
+// Backups
+// =============================================================================
+
+/// Where the config, data, and backups live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+    backups_dir: PathBuf,
+}
+
+impl Backup {
+    /// Back up `config_dir` and `data_dir`, keeping backups in `backups_dir`.
+    pub fn new(
+        config_dir: impl Into<PathBuf>,
+        data_dir: impl Into<PathBuf>,
+        backups_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            config_dir: config_dir.into(),
+            data_dir: data_dir.into(),
+            backups_dir: backups_dir.into(),
+        }
+    }
+
+    /// The folders of this user: the one holding init.lua, `lux` in the
+    /// data directory, and `Lux Backups` in Documents.
+    ///
+    /// None if there is no config or data directory.
+    pub fn current() -> Option<Self> {
+        let config_dir = lux_core::find_init_lua()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .or_else(lux_core::config_dir)?;
+        let data_dir = dirs::data_dir()?.join("lux");
+        let backups_dir = dirs::document_dir()
+            .or_else(dirs::home_dir)?
+            .join("Lux Backups");
+        Some(Self::new(config_dir, data_dir, backups_dir))
+    }
+
+    /// Where backups are written by default.
+    pub fn backups_dir(&self) -> &Path {
+        &self.backups_dir
+    }
+
+    /// Write a backup to `dest` (a new file in the backups folder if None).
+    pub fn create(&self, dest: Option<&Path>) -> PluginResult<PathBuf> {
+        let dest = match dest {
+            Some(dest) => dest.to_path_buf(),
+            None => {
+                fs::create_dir_all(&self.backups_dir).map_err(|e| {
+                    PluginError::Command(format!(
+                        "Cannot create {}: {}",
+                        self.backups_dir.display(),
+                        e
+                    ))
+                })?;
+                let stamp = chrono::Local::now().format("%Y-%m-%d-%H%M%S");
+                self.backups_dir
+                    .join(format!("{}{}{}", BACKUP_PREFIX, stamp, BACKUP_EXTENSION))
+            }
+        };
+
+        let sources: Vec<(PathBuf, String)> = [
+            (&self.config_dir, CONFIG_ENTRY),
+            (&self.data_dir, DATA_ENTRY),
+        ]
+        .into_iter()
+        .filter(|(dir, _)| dir.is_dir())
+        .map(|(dir, name)| (dir.clone(), name.to_string()))
+        .collect();
+        if sources.is_empty() {
+            return Err(PluginError::Command(
+                "Nothing to back up: no config or data folder".to_string(),
+            ));
+        }
+        compress_as(&sources, &dest, ArchiveFormat::TarGz, &mut |_, _| {})?;
+        Ok(dest)
+    }
+
+    /// Unpack `archive` to replace the config and data folders the next
+    /// time Lux starts (see `apply_pending_restore()`).
+    ///
+    /// Replaces a restore that is already pending. Returns the folders that
+    /// will be restored.
+    pub fn restore(&self, archive: &Path) -> PluginResult<Vec<PathBuf>> {
+        let pending = self.pending_restore_dir();
+        let _ = fs::remove_dir_all(&pending);
+        if let Err(e) = extract(archive, &pending, &mut |_, _| {}) {
+            let _ = fs::remove_dir_all(&pending);
+            return Err(e);
+        }
+
+        let restored: Vec<PathBuf> = self
+            .restore_targets(&pending)
+            .into_iter()
+            .map(|(_, target)| target)
+            .collect();
+        if restored.is_empty() {
+            let _ = fs::remove_dir_all(&pending);
+            return Err(PluginError::Command(format!(
+                "'{}' is not a Lux backup",
+                archive.display()
+            )));
+        }
+        Ok(restored)
+    }
+
+    /// Whether a restore waits for the next start.
+    pub fn has_pending_restore(&self) -> bool {
+        self.pending_restore_dir().is_dir()
+    }
+
+    /// Replace the config and data folders with the pending restore, if
+    /// there is one. Lux calls this at startup, before the stores load.
+    ///
+    /// The current folders are moved to `<folder>.before-restore` (replacing
+    /// an older one). Returns the folders that were restored.
+    pub fn apply_pending_restore(&self) -> PluginResult<Vec<PathBuf>> {
+        let pending = self.pending_restore_dir();
+        if !pending.is_dir() {
+            return Ok(Vec::new());
+        }
+        let result = self
+            .restore_targets(&pending)
+            .into_iter()
+            .map(|(source, target)| {
+                replace_dir(&source, &target)
+                    .map(|()| target.clone())
+                    .map_err(|e| {
+                        PluginError::Command(format!("Cannot restore {}: {}", target.display(), e))
+                    })
+            })
+            .collect::<PluginResult<Vec<_>>>();
+        // Not retried on the next start if it failed; restoring again does
+        let _ = fs::remove_dir_all(&pending);
+        result
+    }
+
+    /// Where a restore is unpacked until the next start.
+    fn pending_restore_dir(&self) -> PathBuf {
+        sibling(&self.data_dir, "restore-pending")
+    }
+
+    /// The folders in an unpacked backup, with the folders they replace.
+    fn restore_targets(&self, unpacked: &Path) -> Vec<(PathBuf, PathBuf)> {
+        [
+            (CONFIG_ENTRY, &self.config_dir),
+            (DATA_ENTRY, &self.data_dir),
+        ]
+        .into_iter()
+        .map(|(entry, target)| (unpacked.join(entry), target.clone()))
+        .filter(|(source, _)| source.is_dir())
+        .collect()
+    }
+
+    /// The backups in the backups folder, newest first.
+    pub fn list(&self) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(&self.backups_dir) else {
+            return Vec::new();
+        };
+        let mut backups: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy())
+                    .is_some_and(|name| {
+                        name.starts_with(BACKUP_PREFIX) && name.ends_with(BACKUP_EXTENSION)
+                    })
+            })
+            .collect();
+        // The names are timestamps
+        backups.sort_by(|a, b| b.cmp(a));
+        backups
+    }
+
+    // =========================================================================
+    // Sync
+    // =========================================================================
+
+    /// The folder the data folder is synced to, if it is.
+    pub fn synced_folder(&self) -> Option<PathBuf> {
+        fs::read_link(&self.data_dir).ok()
+    }
+
+    /// Move the data folder to `lux` in `folder` and link it back.
+    ///
+    /// If `folder` already has Lux data (synced from another machine), that
+    /// is used instead and the local data is moved to `<folder>.before-sync`.
+    /// Returns the synced data folder.
+    pub fn sync_to(&self, folder: &Path) -> PluginResult<PathBuf> {
+        if let Some(synced) = self.synced_folder() {
+            return Err(PluginError::Command(format!(
+                "The data folder is already synced to {}",
+                synced.display()
+            )));
+        }
+        let target = folder.join("lux");
+        let result = (|| -> io::Result<()> {
+            // Without Lux data there yet, the local data is copied over, and
+            // no longer needed once the link is in place
+            let copied = !target.is_dir();
+            if copied {
+                let partial = sibling(&target, "partial");
+                let _ = fs::remove_dir_all(&partial);
+                let copy = if self.data_dir.is_dir() {
+                    copy_dir(&self.data_dir, &partial)
+                } else {
+                    fs::create_dir_all(&partial)
+                };
+                if let Err(e) = copy.and_then(|()| fs::rename(&partial, &target)) {
+                    let _ = fs::remove_dir_all(&partial);
+                    return Err(e);
+                }
+            }
+
+            let aside = sibling(&self.data_dir, "before-sync");
+            let moved = self.data_dir.exists();
+            if moved {
+                let _ = fs::remove_dir_all(&aside);
+                fs::rename(&self.data_dir, &aside)?;
+            }
+            if let Some(parent) = self.data_dir.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if let Err(e) = symlink(&target, &self.data_dir) {
+                if moved {
+                    let _ = fs::rename(&aside, &self.data_dir);
+                }
+                return Err(e);
+            }
+            if copied && moved {
+                let _ = fs::remove_dir_all(&aside);
+            }
+            Ok(())
+        })();
+        result.map_err(|e| {
+            PluginError::Command(format!("Cannot sync to {}: {}", target.display(), e))
+        })?;
+        Ok(target)
+    }
+
+    /// Take the lock of the synced data folder. None if it isn't synced.
+    pub fn lock(&self) -> PluginResult<Option<SyncLock>> {
+        match self.synced_folder() {
+            Some(folder) => SyncLock::acquire(&folder).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove the lock of the synced data folder, whoever holds it.
+    ///
+    /// For a lock left behind by a Lux that didn't quit cleanly. Returns
+    /// false if there was none.
+    pub fn unlock(&self) -> PluginResult<bool> {
+        let Some(folder) = self.synced_folder() else {
+            return Ok(false);
+        };
+        match fs::remove_file(folder.join(LOCK_FILE)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(PluginError::Command(format!(
+                "Cannot remove the sync lock: {}",
+                e
+            ))),
+        }
+    }
+}
+
+/// `<dir>.<suffix>` next to `dir`.
+fn sibling(dir: &Path, suffix: &str) -> PathBuf {
+    let name = dir.file_name().unwrap_or_default().to_string_lossy();
+    dir.with_file_name(format!("{}.{}", name, suffix))
+}
+
+/// Replace `target` with a copy of `source`, moving `target` aside first.
+///
+/// The copy is made next to `target` and renamed into place once complete.
+/// A linked `target` (a synced data folder) keeps its link; the folder it
+/// points to is replaced.
+fn replace_dir(source: &Path, target: &Path) -> io::Result<()> {
+    let target = fs::canonicalize(target).unwrap_or_else(|_| target.to_path_buf());
+    let incoming = sibling(&target, "restoring");
+    let _ = fs::remove_dir_all(&incoming);
+    if let Err(e) = copy_dir(source, &incoming) {
+        let _ = fs::remove_dir_all(&incoming);
+        return Err(e);
+    }
+    if target.exists() {
+        let aside = sibling(&target, "before-restore");
+        let _ = fs::remove_dir_all(&aside);
+        if let Err(e) = fs::rename(&target, &aside) {
+            let _ = fs::remove_dir_all(&incoming);
+            return Err(e);
+        }
+    }
+    fs::rename(&incoming, &target)
+}
+
+/// Copy the files, folders, and links under `source` into `dest`.
+///
+/// Links are copied as links, pointing where they did.
+fn copy_dir(source: &Path, dest: &Path) -> io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let path = entry.path();
+        let dest = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&path, &dest)?;
+        } else if file_type.is_symlink() {
+            symlink(&fs::read_link(&path)?, &dest).map_err(|e| {
+                io::Error::new(e.kind(), format!("Cannot copy {}: {}", path.display(), e))
+            })?;
+        } else if file_type.is_file() && entry.file_name() != LOCK_FILE {
+            fs::copy(&path, &dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "symbolic links aren't supported here",
+    ))
+}
+
+// =============================================================================
+// Sync Lock
+// =============================================================================
+
+/// Who holds a synced data folder, as saved in `lux.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockOwner {
+    /// Name of the machine.
+    pub host: String,
+    /// Process ID of the Lux holding it.
+    pub pid: u32,
+    /// When it was taken, as a Unix timestamp.
+    pub since: i64,
+}
+
+impl LockOwner {
+    /// This process on this machine.
+    fn this_process() -> Self {
+        Self {
+            host: host_name(),
+            pid: std::process::id(),
+            since: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// The lock of a synced data folder, released when dropped.
+///
+/// Taking it fails while a Lux on another machine holds it. A lock from this
+/// machine is taken over: only one Lux runs per machine, so it is stale.
+#[derive(Debug)]
+pub struct SyncLock {
+    path: PathBuf,
+    owner: LockOwner,
+}
+
+impl SyncLock {
+    /// Take the lock of the data folder `folder`.
+    pub fn acquire(folder: &Path) -> PluginResult<Self> {
+        Self::acquire_as(folder, LockOwner::this_process())
+    }
+
+    fn acquire_as(folder: &Path, owner: LockOwner) -> PluginResult<Self> {
+        let path = folder.join(LOCK_FILE);
+        let held = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<LockOwner>(&text).ok());
+        if let Some(held) = held.filter(|held| held.host != owner.host) {
+            let since = chrono::DateTime::from_timestamp(held.since, 0)
+                .map(|time| time.with_timezone(&chrono::Local).format("%c").to_string())
+                .unwrap_or_default();
+            return Err(PluginError::Command(format!(
+                "The data folder {} is in use by Lux on {} (since {}). Quit it there, \
+                 or run `lux backup unlock` if it is no longer running",
+                folder.display(),
+                held.host,
+                since
+            )));
+        }
+
+        let text = serde_json::to_string(&owner)
+            .map_err(|e| PluginError::Command(format!("Cannot write the sync lock: {}", e)))?;
+        fs::write(&path, text)
+            .map_err(|e| PluginError::Command(format!("Cannot write the sync lock: {}", e)))?;
+        Ok(Self { path, owner })
+    }
+
+    /// Who holds it (this process).
+    pub fn owner(&self) -> &LockOwner {
+        &self.owner
+    }
+}
+
+impl Drop for SyncLock {
+    fn drop(&mut self) {
+        // Only remove it if another machine didn't take it over meanwhile
+        let still_ours = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<LockOwner>(&text).ok())
+            .is_some_and(|held| held == self.owner);
+        if still_ours {
+            if let Err(e) = fs::remove_file(&self.path) {
+                tracing::warn!("Failed to release the sync lock: {}", e);
+            }
+        }
+    }
+}
+
+/// Name of this machine.
+fn host_name() -> String {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// =============================================================================
+// Backup View
+// =============================================================================
+
+/// The rows of the Backup view: "Create Backup", then the backups (newest
+/// first), each with its path in `data.path`.
+pub fn backup_groups(backup: &Backup, query: &str, now: i64) -> Groups {
+    let query = query.trim().to_lowercase();
+    let mut groups = Vec::new();
+
+    let title = t("Create Backup");
+    if title.to_lowercase().contains(&query) {
+        let mut item = Item::new(CREATE_ITEM_ID, title)
+            .with_subtitle(backup.backups_dir.display().to_string());
+        if let Some(folder) = backup.synced_folder() {
+            item = item.with_accessory(t_args(
+                "Synced to {folder}",
+                &[("folder", &folder.display().to_string())],
+            ));
+        }
+        groups.push(Group::ungrouped(vec![item]));
+    }
+
+    let items: Vec<Item> = backup
+        .list()
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            if !name.to_lowercase().contains(&query) {
+                return None;
+            }
+            let metadata = fs::metadata(&path).ok()?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .map(|time| chrono::DateTime::<chrono::Utc>::from(time).timestamp())
+                .unwrap_or(now);
+            Some(
+                Item::new(format!("backup:{}", name), name)
+                    .with_subtitle(format_age(modified, now))
+                    .with_data(serde_json::json!({ "path": path.to_string_lossy() }))
+                    .with_accessory(format!("{} KB", metadata.len().div_ceil(1024))),
+            )
+        })
+        .collect();
+    if !items.is_empty() {
+        groups.push(Group::new(t("Backups"), items));
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config").join("lux");
+        let data = dir.path().join("data").join("lux");
+        fs::create_dir_all(config.join("plugins")).unwrap();
+        fs::create_dir_all(&data).unwrap();
+        fs::write(config.join("init.lua"), "-- mine").unwrap();
+        fs::write(config.join("plugins").join("weather.lua"), "-- weather").unwrap();
+        fs::write(data.join("pins.json"), "[]").unwrap();
+
+        let backup = Backup::new(&config, &data, dir.path().join("backups"));
+        let archive = backup.create(None).unwrap();
+        assert_eq!(backup.list(), std::slice::from_ref(&archive));
+
+        fs::write(config.join("init.lua"), "-- changed").unwrap();
+        fs::remove_file(data.join("pins.json")).unwrap();
+        assert_eq!(
+            backup.restore(&archive).unwrap(),
+            [config.clone(), data.clone()]
+        );
+
+        // Nothing changes until the next start
+        assert!(backup.has_pending_restore());
+        assert_eq!(
+            fs::read_to_string(config.join("init.lua")).unwrap(),
+            "-- changed"
+        );
+        assert_eq!(
+            backup.apply_pending_restore().unwrap(),
+            [config.clone(), data.clone()]
+        );
+        assert!(!backup.has_pending_restore());
+        assert!(backup.apply_pending_restore().unwrap().is_empty());
+
+        assert_eq!(
+            fs::read_to_string(config.join("init.lua")).unwrap(),
+            "-- mine"
+        );
+        assert!(config.join("plugins").join("weather.lua").is_file());
+        assert_eq!(fs::read_to_string(data.join("pins.json")).unwrap(), "[]");
+        assert_eq!(
+            fs::read_to_string(sibling(&config, "before-restore").join("init.lua")).unwrap(),
+            "-- changed"
+        );
+
+        let groups = backup_groups(&backup, "", 0);
+        assert_eq!(groups[0].items[0].id, CREATE_ITEM_ID);
+        assert_eq!(groups[1].items.len(), 1);
+        assert!(backup.restore(&config.join("init.lua")).is_err());
+        assert!(!backup.has_pending_restore());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_keeps_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("config");
+        let checkout = dir.path().join("checkout");
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(&checkout).unwrap();
+        fs::write(checkout.join("init.lua"), "-- dev").unwrap();
+        symlink(&checkout, &source.join("weather")).unwrap();
+
+        let dest = dir.path().join("copy");
+        copy_dir(&source, &dest).unwrap();
+        assert_eq!(fs::read_link(dest.join("weather")).unwrap(), checkout);
+        assert!(dest.join("weather").join("init.lua").is_file());
+
+        // A failed copy leaves the target as it was
+        let target = dir.path().join("target");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("pins.json"), "[]").unwrap();
+        assert!(replace_dir(&dir.path().join("missing"), &target).is_err());
+        assert!(target.join("pins.json").is_file());
+        assert!(!sibling(&target, "restoring").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sync_lock_keeps_other_machines_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = dir.path().join("data").join("lux");
+        fs::create_dir_all(&data).unwrap();
+        fs::write(data.join("activity.json"), "[]").unwrap();
+
+        let backup = Backup::new(dir.path().join("config"), &data, dir.path().join("backups"));
+        let synced = backup.sync_to(&dir.path().join("Dropbox")).unwrap();
+        assert_eq!(backup.synced_folder(), Some(synced.clone()));
+        assert!(synced.join("activity.json").is_file());
+        assert!(data.join("activity.json").is_file());
+        assert!(backup.sync_to(dir.path()).is_err());
+
+        let other = LockOwner {
+            host: "other-mac".to_string(),
+            pid: 1,
+            since: 0,
+        };
+        let held = SyncLock::acquire_as(&synced, other).unwrap();
+        let err = backup.lock().unwrap_err().to_string();
+        assert!(err.contains("in use by Lux on other-mac"));
+
+        // A lock from this machine is stale and taken over
+        drop(held);
+        let mine = backup.lock().unwrap().unwrap();
+        assert_eq!(mine.owner().pid, std::process::id());
+        drop(mine);
+        assert!(!backup.unlock().unwrap());
+    }
+}
@@ -0,0 +1,321 @@
+//! The basket: items collected across views for bulk actions.
+//!
+//! This module provides:
+//! - `Basket` - Items added from any view (lux.basket), kept until Lux quits
+//! - `basket_action()` / `run_basket_action()` - Add or remove the focused result
+//! - `basket_groups()` / `basket_actions()` / `run_basket_bulk_action()` - The basket view
+//!
+//! Any result can be added with Cmd+B, whatever view it came from. The
+//! launcher shows the item count as a badge; Cmd+Shift+B (or clicking the
+//! badge) opens the basket view, where every item is selected so actions run
+//! on all of them: copy, open, or reveal everything, plus actions added with
+//! `lux.basket.add_action()`.
+//!
+//! ```lua
+//! lux.basket.add_action({ id = "share", title = "Share", handler = function(items, ctx) ... end })
+//! ```
+
+use lux_core::i18n::t;
+use lux_core::{DragPayload, Group, Groups, Item};
+use parking_lot::Mutex;
+
+use crate::desktop;
+use crate::detect::{detect_item, Detection};
+use crate::error::{PluginError, PluginResult};
+
+/// View ID of the basket view.
+pub const BASKET_VIEW_ID: &str = "builtin.basket";
+
+/// Item type of the entries listed in the basket view.
+pub const BASKET_ITEM_TYPE: &str = "basket";
+
+/// Lua registry key prefix of the add and remove action handlers.
+pub const BASKET_ACTION_KEY_PREFIX: &str = "basket:";
+
+/// IDs of the add and remove actions (one handler is registered per ID).
+pub const BASKET_ACTION_IDS: &[&str] = &["add_to_basket", "remove_from_basket"];
+
+/// Shortcut of the add and remove actions.
+pub const BASKET_SHORTCUT: &str = "cmd+b";
+
+/// Lua registry key of the key handler that opens the basket view.
+pub const BASKET_OPEN_KEY: &str = "basket:open";
+
+/// Lua registry key of the list of actions added with `lux.basket.add_action()`.
+pub const BASKET_ACTIONS_KEY: &str = "basket:actions";
+
+/// Handler key for an add or remove action ID.
+pub fn basket_action_key(id: &str) -> String {
+    format!("{}{}", BASKET_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Basket
+// =============================================================================
+
+/// Items in the basket, in the order they were added.
+///
+/// Kept in memory only: the basket empties when Lux quits.
+pub struct Basket {
+    items: Mutex<Vec<Item>>,
+}
+
+impl Basket {
+    /// Create an empty basket.
+    pub fn new() -> Self {
+        Self {
+            items: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Items in the basket, in the order they were added.
+    pub fn items(&self) -> Vec<Item> {
+        self.items.lock().clone()
+    }
+
+    /// Number of items in the basket.
+    pub fn len(&self) -> usize {
+        self.items.lock().len()
+    }
+
+    /// Whether the basket is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.lock().is_empty()
+    }
+
+    /// Whether the item with `id` is in the basket.
+    pub fn contains(&self, id: &str) -> bool {
+        self.items.lock().iter().any(|item| item.id == id)
+    }
+
+    /// Add an item. Returns false if it was already in the basket.
+    pub fn add(&self, item: Item) -> bool {
+        let mut items = self.items.lock();
+        if items.iter().any(|added| added.id == item.id) {
+            return false;
+        }
+        items.push(item);
+        true
+    }
+
+    /// Remove the item with `id`. Returns true if it was in the basket.
+    pub fn remove(&self, id: &str) -> bool {
+        let mut items = self.items.lock();
+        let len = items.len();
+        items.retain(|item| item.id != id);
+        items.len() != len
+    }
+
+    /// Empty the basket.
+    pub fn clear(&self) {
+        self.items.lock().clear();
+    }
+
+    /// The added items with the given IDs, in basket order.
+    pub fn get_all(&self, ids: &[&str]) -> Vec<Item> {
+        self.items
+            .lock()
+            .iter()
+            .filter(|item| ids.contains(&item.id.as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for Basket {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// Add and Remove
+// =============================================================================
+
+/// The basket action offered for an item: `(id, title)`.
+pub fn basket_action(item: &Item, basket: &Basket) -> (&'static str, String) {
+    if basket.contains(&item.id) {
+        ("remove_from_basket", t("Remove from Basket"))
+    } else {
+        ("add_to_basket", t("Add to Basket"))
+    }
+}
+
+/// Run an add or remove action on an item.
+pub fn run_basket_action(action_id: &str, item: &Item, basket: &Basket) -> PluginResult<()> {
+    match action_id {
+        "add_to_basket" => {
+            basket.add(item.clone());
+        }
+        "remove_from_basket" => {
+            basket.remove(&item.id);
+        }
+        _ => {
+            return Err(PluginError::Command(format!(
+                "Unknown basket action '{}'",
+                action_id
+            )))
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Basket View
+// =============================================================================
+
+/// The basket view's results: the items matching `query`.
+///
+/// Entries are tagged with `BASKET_ITEM_TYPE` instead of their own types, so
+/// the basket's actions apply rather than those of the view they came from.
+pub fn basket_groups(basket: &Basket, query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let items: Vec<Item> = basket
+        .items()
+        .into_iter()
+        .filter(|item| query.is_empty() || item.title.to_lowercase().contains(&query))
+        .map(|mut item| {
+            item.types = vec![BASKET_ITEM_TYPE.to_string()];
+            item
+        })
+        .collect();
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::new(t("Basket"), items)]
+}
+
+/// Actions for the items in the basket view: `(id, title)`.
+pub fn basket_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("copy_all", t("Copy All")),
+        ("open_all", t("Open All")),
+        ("reveal_all", t("Reveal All in Finder")),
+        ("remove", t("Remove from Basket")),
+        ("clear", t("Clear Basket")),
+    ]
+}
+
+/// Run a basket view action on the items it was invoked with.
+///
+/// Items are looked up in the basket by ID, so actions see them as added.
+pub fn run_basket_bulk_action(
+    action_id: &str,
+    items: &[Item],
+    basket: &Basket,
+) -> PluginResult<()> {
+    let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+    let items = basket.get_all(&ids);
+    let desktop = desktop::current();
+
+    match action_id {
+        "copy_all" => {
+            let text: Vec<String> = items.iter().map(item_text).collect();
+            desktop.clipboard_write(&text.join("\n"))
+        }
+        "open_all" => items
+            .iter()
+            .filter_map(item_target)
+            .try_for_each(|target| desktop.open(target.value())),
+        "reveal_all" => items
+            .iter()
+            .filter_map(item_target)
+            .filter_map(|target| match target {
+                Target::File(path) => Some(path),
+                Target::Url(_) => None,
+            })
+            .try_for_each(|path| desktop.reveal(&path)),
+        "remove" => {
+            for id in ids {
+                basket.remove(id);
+            }
+            Ok(())
+        }
+        "clear" => {
+            basket.clear();
+            Ok(())
+        }
+        _ => Err(PluginError::Command(format!(
+            "Unknown basket action '{}'",
+            action_id
+        ))),
+    }
+}
+
+/// What opening an item opens.
+#[derive(Debug, PartialEq)]
+enum Target {
+    File(String),
+    Url(String),
+}
+
+impl Target {
+    fn value(&self) -> &str {
+        match self {
+            Target::File(path) | Target::Url(path) => path,
+        }
+    }
+}
+
+/// The file or URL an item stands for: its drag payload, or a value detected
+/// in its title or subtitle.
+fn item_target(item: &Item) -> Option<Target> {
+    match &item.drag {
+        Some(DragPayload::File { path }) => return Some(Target::File(path.clone())),
+        Some(DragPayload::Url { url }) => return Some(Target::Url(url.clone())),
+        _ => {}
+    }
+    match detect_item(item)? {
+        Detection::FilePath(path) => Some(Target::File(path)),
+        Detection::Url(url) => Some(Target::Url(url)),
+        Detection::Email(email) => Some(Target::Url(format!("mailto:{}", email))),
+        _ => None,
+    }
+}
+
+/// Text copied for an item: its file, URL, or drag text, else its title.
+fn item_text(item: &Item) -> String {
+    if let Some(DragPayload::Text { text }) = &item.drag {
+        return text.clone();
+    }
+    match item_target(item) {
+        Some(Target::Url(url)) => url.strip_prefix("mailto:").unwrap_or(&url).to_string(),
+        Some(Target::File(path)) => path,
+        None => item.title.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basket_collects_items_from_any_view() {
+        let basket = Basket::new();
+        let report =
+            Item::new("finder:/tmp/report.pdf", "report.pdf").with_drag(DragPayload::File {
+                path: "/tmp/report.pdf".to_string(),
+            });
+        let link = Item::new("tab:1", "https://lux.dev");
+
+        assert!(basket.add(report.clone()));
+        assert!(!basket.add(report.clone()));
+        assert!(basket.add(link.clone()));
+        assert_eq!(basket_action(&link, &basket).0, "remove_from_basket");
+
+        let groups = basket_groups(&basket, "");
+        assert_eq!(groups[0].items.len(), 2);
+        assert!(groups[0].items[0].has_type(BASKET_ITEM_TYPE));
+        assert_eq!(basket_groups(&basket, "report")[0].items.len(), 1);
+
+        assert_eq!(item_text(&report), "/tmp/report.pdf");
+        assert_eq!(item_text(&link), "https://lux.dev");
+        assert_eq!(item_text(&Item::new("note", "Buy milk")), "Buy milk");
+
+        run_basket_bulk_action("remove", &[link], &basket).unwrap();
+        assert_eq!(basket.len(), 1);
+        run_basket_bulk_action("clear", &[], &basket).unwrap();
+        assert!(basket.is_empty());
+        assert!(basket_groups(&basket, "").is_empty());
+    }
+}
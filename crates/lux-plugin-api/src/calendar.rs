@@ -0,0 +1,299 @@
+//! Calendar events and reminders via EventKit.
+//!
+//! This module provides:
+//! - `upcoming_events()` - Events starting within the next N days
+//! - `incomplete_reminders()` - Open reminders across all lists
+//! - `complete_reminder()` - Mark a reminder done
+//! - `create_reminder()` - Add a reminder to the default list
+//! - `open_event()` / `open_reminder()` - Show an entry in its app
+//! - `meeting_link()` - Extract a Zoom/Meet/Teams/Webex link from an event
+//!
+//! All reads and writes are gated on the EventKit permissions in
+//! [`crate::permissions`].
+
+use serde::Deserialize;
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::permissions::{self, run_jxa_with_args, Permission};
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A calendar event.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CalendarEvent {
+    /// EventKit event identifier.
+    pub id: String,
+    /// Event title.
+    pub title: String,
+    /// Start time (seconds since the Unix epoch).
+    pub start: f64,
+    /// End time (seconds since the Unix epoch).
+    pub end: f64,
+    /// Localized start time for display.
+    pub start_label: String,
+    /// Whether the event lasts all day.
+    #[serde(default)]
+    pub all_day: bool,
+    /// Calendar name.
+    pub calendar: String,
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// An incomplete reminder.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Reminder {
+    /// EventKit calendar item identifier.
+    pub id: String,
+    /// Reminder title.
+    pub title: String,
+    /// Reminder list name.
+    pub list: String,
+    /// Localized due date for display, if the reminder has one.
+    #[serde(default)]
+    pub due_label: Option<String>,
+}
+
+// =============================================================================
+// Scripts
+// =============================================================================
+
+/// JXA script listing events from now until `argv[0]` days ahead.
+const EVENTS_SCRIPT: &str = r#"
+ObjC.import('EventKit');
+function str(v) { return (v && !v.isNil()) ? v.js : null; }
+function run(argv) {
+  const store = $.EKEventStore.alloc.init;
+  const start = $.NSDate.date;
+  const end = $.NSDate.dateWithTimeIntervalSinceNow(parseFloat(argv[0]) * 86400);
+  const pred = store.predicateForEventsWithStartDateEndDateCalendars(start, end, $());
+  const events = store.eventsMatchingPredicate(pred);
+  const out = [];
+  for (let i = 0; i < events.count; i++) {
+    const e = events.objectAtIndex(i);
+    out.push({
+      id: e.eventIdentifier.js,
+      title: str(e.title) || '',
+      start: e.startDate.timeIntervalSince1970,
+      end: e.endDate.timeIntervalSince1970,
+      start_label: $.NSDateFormatter.localizedStringFromDateDateStyleTimeStyle(e.startDate, 1, 1).js,
+      all_day: e.allDay,
+      calendar: str(e.calendar.title) || '',
+      location: str(e.location),
+      url: e.URL.isNil() ? null : e.URL.absoluteString.js,
+      notes: str(e.notes),
+    });
+  }
+  return JSON.stringify(out);
+}
+"#;
+
+/// JXA script listing incomplete reminders (the fetch is async, so poll).
+const REMINDERS_SCRIPT: &str = r#"
+ObjC.import('EventKit');
+function run(argv) {
+  const store = $.EKEventStore.alloc.init;
+  const pred = store.predicateForIncompleteRemindersWithDueDateStartingEndingCalendars($(), $(), $());
+  let result = null;
+  store.fetchRemindersMatchingPredicateCompletion(pred, function(r) { result = r; });
+  for (let i = 0; i < 200 && result === null; i++) delay(0.05);
+  const out = [];
+  if (result === null) return JSON.stringify(out);
+  for (let i = 0; i < result.count; i++) {
+    const r = result.objectAtIndex(i);
+    const due = r.dueDateComponents.isNil() ? null : $.NSCalendar.currentCalendar.dateFromComponents(r.dueDateComponents);
+    out.push({
+      id: r.calendarItemIdentifier.js,
+      title: r.title.isNil() ? '' : r.title.js,
+      list: r.calendar.title.js,
+      due_label: (due && !due.isNil()) ? $.NSDateFormatter.localizedStringFromDateDateStyleTimeStyle(due, 1, 1).js : null,
+    });
+  }
+  return JSON.stringify(out);
+}
+"#;
+
+/// JXA script marking reminder `argv[0]` complete.
+const COMPLETE_REMINDER_SCRIPT: &str = r#"
+ObjC.import('EventKit');
+function run(argv) {
+  const store = $.EKEventStore.alloc.init;
+  const r = store.calendarItemWithIdentifier(argv[0]);
+  if (r.isNil()) throw new Error('Reminder not found');
+  r.completed = true;
+  if (!store.saveReminderCommitError(r, true, null)) throw new Error('Save failed');
+  return 'ok';
+}
+"#;
+
+/// JXA script adding reminder `argv[0]` due at timestamp `argv[1]` to the
+/// default list; `argv[2]` is "true" when the due date has a time of day.
+const CREATE_REMINDER_SCRIPT: &str = r#"
+ObjC.import('EventKit');
+function run(argv) {
+  const store = $.EKEventStore.alloc.init;
+  const list = store.defaultCalendarForNewReminders;
+  if (list.isNil()) throw new Error('No default reminder list');
+  const r = $.EKReminder.reminderWithEventStore(store);
+  r.title = argv[0];
+  r.calendar = list;
+  const due = $.NSDate.dateWithTimeIntervalSince1970(parseFloat(argv[1]));
+  // NSCalendarUnit year | month | day, plus hour | minute
+  const units = argv[2] === 'true' ? (4 | 8 | 16 | 32 | 64) : (4 | 8 | 16);
+  r.dueDateComponents = $.NSCalendar.currentCalendar.componentsFromDate(units, due);
+  if (!store.saveReminderCommitError(r, true, null)) throw new Error('Save failed');
+  return r.calendarItemIdentifier.js;
+}
+"#;
+
+// =============================================================================
+// Queries
+// =============================================================================
+
+/// List events starting between now and `days` days from now, by start time.
+pub fn upcoming_events(days: f64) -> PluginResult<Vec<CalendarEvent>> {
+    permissions::require(Permission::Calendar)?;
+    let output = run_jxa_with_args(EVENTS_SCRIPT, &[&days.to_string()])?;
+    parse_events(&output)
+}
+
+/// List incomplete reminders.
+pub fn incomplete_reminders() -> PluginResult<Vec<Reminder>> {
+    permissions::require(Permission::Reminders)?;
+    let output = run_jxa_with_args(REMINDERS_SCRIPT, &[])?;
+    serde_json::from_str(output.trim())
+        .map_err(|e| PluginError::Command(format!("Invalid reminder list: {}", e)))
+}
+
+/// Mark a reminder as completed.
+pub fn complete_reminder(id: &str) -> PluginResult<()> {
+    permissions::require(Permission::Reminders)?;
+    run_jxa_with_args(COMPLETE_REMINDER_SCRIPT, &[id])?;
+    Ok(())
+}
+
+/// Add a reminder due at a Unix timestamp to the default list.
+///
+/// Without `has_time` only the timestamp's date is kept. Returns the new
+/// reminder's ID.
+pub fn create_reminder(title: &str, due: i64, has_time: bool) -> PluginResult<String> {
+    permissions::require(Permission::Reminders)?;
+    let output = run_jxa_with_args(
+        CREATE_REMINDER_SCRIPT,
+        &[title, &due.to_string(), &has_time.to_string()],
+    )?;
+    Ok(output.trim().to_string())
+}
+
+/// Show an event in Calendar.
+pub fn open_event(id: &str) -> PluginResult<()> {
+    desktop::current().open(&format!("ical://ekevent/{}?method=show&options=more", id))
+}
+
+/// Show a reminder in Reminders.
+pub fn open_reminder(id: &str) -> PluginResult<()> {
+    desktop::current().open(&format!("x-apple-reminderkit://REMCDReminder/{}", id))
+}
+
+/// Parse the JSON event list and sort it by start time.
+pub fn parse_events(json: &str) -> PluginResult<Vec<CalendarEvent>> {
+    let mut events: Vec<CalendarEvent> = serde_json::from_str(json.trim())
+        .map_err(|e| PluginError::Command(format!("Invalid event list: {}", e)))?;
+    events.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Ok(events)
+}
+
+/// Hosts recognized as video meeting links.
+const MEETING_HOSTS: &[&str] = &[
+    "zoom.us/",
+    "meet.google.com/",
+    "teams.microsoft.com/",
+    "teams.live.com/",
+    "webex.com/",
+    "whereby.com/",
+];
+
+/// Find a video meeting link in an event's URL, location, or notes.
+pub fn meeting_link(event: &CalendarEvent) -> Option<String> {
+    [&event.url, &event.location, &event.notes]
+        .into_iter()
+        .flatten()
+        .flat_map(|text| text.split(|c: char| c.is_whitespace() || c == '<' || c == '>'))
+        .map(|word| word.trim_matches(|c: char| matches!(c, '"' | '\'' | '(' | ')' | ',')))
+        .find(|word| {
+            word.starts_with("https://") && MEETING_HOSTS.iter().any(|host| word.contains(host))
+        })
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(url: Option<&str>, location: Option<&str>, notes: Option<&str>) -> CalendarEvent {
+        CalendarEvent {
+            id: "1".to_string(),
+            title: "Standup".to_string(),
+            start: 0.0,
+            end: 900.0,
+            start_label: "9:00 AM".to_string(),
+            all_day: false,
+            calendar: "Work".to_string(),
+            location: location.map(str::to_string),
+            url: url.map(str::to_string),
+            notes: notes.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_events_sorted() {
+        let json = r#"[
+            {"id": "b", "title": "Later", "start": 200, "end": 300, "start_label": "10:00", "calendar": "Work"},
+            {"id": "a", "title": "Sooner", "start": 100, "end": 150, "start_label": "9:00", "calendar": "Home", "all_day": true}
+        ]"#;
+
+        let events = parse_events(json).unwrap();
+        assert_eq!(events[0].id, "a");
+        assert!(events[0].all_day);
+        assert_eq!(events[1].location, None);
+    }
+
+    #[test]
+    fn test_meeting_link_from_notes() {
+        let e = event(
+            None,
+            Some("Room 4"),
+            Some("Join: <https://acme.zoom.us/j/123?pwd=x>\nDial-in: ..."),
+        );
+        assert_eq!(
+            meeting_link(&e),
+            Some("https://acme.zoom.us/j/123?pwd=x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_meeting_link_prefers_url_field() {
+        let e = event(
+            Some("https://meet.google.com/abc-defg-hij"),
+            None,
+            Some("https://teams.microsoft.com/l/meetup-join/xyz"),
+        );
+        assert_eq!(
+            meeting_link(&e),
+            Some("https://meet.google.com/abc-defg-hij".to_string())
+        );
+    }
+
+    #[test]
+    fn test_meeting_link_none() {
+        let e = event(Some("https://example.com/agenda"), Some("Office"), None);
+        assert_eq!(meeting_link(&e), None);
+    }
+}
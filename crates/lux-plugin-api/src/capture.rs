@@ -0,0 +1,511 @@
+//! Screenshots and screen recordings.
+//!
+//! This module provides:
+//! - `CaptureKind` - Area, window, or whole-screen screenshots
+//! - `Captures` - Capture folder, the running recording, and the latest capture (lux.capture)
+//! - `capture_actions()` / `run_capture_action()` - Capture commands, and copy/reveal/annotate
+//!
+//! Captures wrap macOS `screencapture`. Files are named like Finder's own
+//! ("Screenshot 2026-10-16 at 14.03.22.png") and saved into the configured
+//! folder; the newest one there is listed under the capture commands.
+
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::{Mutex, RwLock};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::timers::format_remaining;
+
+/// Type tag for capture items (used to route them to the built-in actions).
+pub const CAPTURE_ITEM_TYPE: &str = "capture";
+
+/// Lua registry key prefix of the built-in capture action handlers.
+pub const CAPTURE_ACTION_KEY_PREFIX: &str = "capture:";
+
+/// IDs of all capture actions (one handler is registered per ID).
+pub const CAPTURE_ACTION_IDS: &[&str] = &[
+    "capture_area",
+    "capture_window",
+    "capture_screen",
+    "start_recording",
+    "stop_recording",
+    "copy",
+    "reveal",
+    "annotate",
+];
+
+/// Words that list the capture commands (a query may be a prefix of one).
+const KEYWORDS: &[&str] = &["screenshot", "capture", "record", "recording"];
+
+/// Shortest query prefix that matches a keyword.
+const MIN_QUERY_LEN: usize = 3;
+
+/// File name prefixes of captures, as Finder names them.
+const SCREENSHOT_PREFIX: &str = "Screenshot";
+const RECORDING_PREFIX: &str = "Screen Recording";
+
+/// Time for the launcher window to close before a non-interactive capture.
+const HIDE_DELAY: Duration = Duration::from_millis(300);
+
+/// Handler key for a capture action ID.
+pub fn capture_action_key(id: &str) -> String {
+    format!("{}{}", CAPTURE_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// What a screenshot captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// A selected rectangle.
+    Area,
+    /// A clicked window.
+    Window,
+    /// The whole main display.
+    Screen,
+}
+
+impl CaptureKind {
+    /// Parse a kind name as used from Lua.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "area" => Some(CaptureKind::Area),
+            "window" => Some(CaptureKind::Window),
+            "screen" => Some(CaptureKind::Screen),
+            _ => None,
+        }
+    }
+
+    /// `screencapture` arguments (before the output path).
+    fn args(&self) -> &'static [&'static str] {
+        match self {
+            CaptureKind::Area => &["-i"],
+            CaptureKind::Window => &["-i", "-W"],
+            CaptureKind::Screen => &[],
+        }
+    }
+
+    fn action_id(&self) -> &'static str {
+        match self {
+            CaptureKind::Area => "capture_area",
+            CaptureKind::Window => "capture_window",
+            CaptureKind::Screen => "capture_screen",
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            CaptureKind::Area => t("Capture Area"),
+            CaptureKind::Window => t("Capture Window"),
+            CaptureKind::Screen => t("Capture Screen"),
+        }
+    }
+}
+
+/// A screen recording in progress.
+struct Recording {
+    child: Child,
+    path: PathBuf,
+    started: Instant,
+}
+
+// =============================================================================
+// Captures
+// =============================================================================
+
+/// Capture folder and the running recording.
+pub struct Captures {
+    folder: RwLock<PathBuf>,
+    recording: Mutex<Option<Recording>>,
+    /// Latest file written by Lux (used before it shows up in a folder scan).
+    latest: Arc<Mutex<Option<PathBuf>>>,
+}
+
+impl Captures {
+    /// Create with captures saved into `folder`.
+    pub fn new(folder: impl Into<PathBuf>) -> Self {
+        Self {
+            folder: RwLock::new(folder.into()),
+            recording: Mutex::new(None),
+            latest: Arc::default(),
+        }
+    }
+
+    /// Where captures are saved by default (`~/Pictures/Screenshots`).
+    pub fn default_folder() -> PathBuf {
+        dirs::picture_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join("Pictures")))
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join("Screenshots")
+    }
+
+    /// Folder captures are saved into.
+    pub fn folder(&self) -> PathBuf {
+        self.folder.read().clone()
+    }
+
+    /// Save captures into a different folder.
+    pub fn set_folder(&self, folder: impl Into<PathBuf>) {
+        *self.folder.write() = folder.into();
+    }
+
+    /// A new capture path in the folder, named after the current time.
+    fn next_path(&self, prefix: &str, ext: &str) -> PluginResult<PathBuf> {
+        let folder = self.folder();
+        std::fs::create_dir_all(&folder).map_err(|e| {
+            PluginError::Command(format!("Cannot create {}: {}", folder.display(), e))
+        })?;
+        let stamp = chrono::Local::now().format("%Y-%m-%d at %H.%M.%S");
+        Ok(capture_path(&folder, &format!("{} {}", prefix, stamp), ext))
+    }
+
+    /// Take a screenshot in the background.
+    ///
+    /// Returns the path it will be saved to; interactive captures that are
+    /// cancelled never create it.
+    pub fn screenshot(&self, kind: CaptureKind) -> PluginResult<PathBuf> {
+        let path = self.next_path(SCREENSHOT_PREFIX, "png")?;
+        let latest = self.latest.clone();
+        let target = path.clone();
+        std::thread::spawn(move || {
+            // Let the launcher close so it isn't in the picture
+            std::thread::sleep(HIDE_DELAY);
+            let status = Command::new("screencapture")
+                .args(kind.args())
+                .arg(&target)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+            match status {
+                Ok(_) if target.exists() => *latest.lock() = Some(target),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("screencapture failed: {}", e),
+            }
+        });
+        Ok(path)
+    }
+
+    /// Whether a recording is running, and since when.
+    pub fn recording_since(&self) -> Option<Instant> {
+        let mut recording = self.recording.lock();
+        // The user may have stopped it from the menu bar
+        if let Some(running) = recording.as_mut() {
+            if !matches!(running.child.try_wait(), Ok(None)) {
+                let finished = recording.take().map(|r| r.path);
+                *self.latest.lock() = finished.filter(|path| path.exists());
+                return None;
+            }
+        }
+        recording.as_ref().map(|r| r.started)
+    }
+
+    /// Start recording the screen.
+    pub fn start_recording(&self) -> PluginResult<PathBuf> {
+        if self.recording_since().is_some() {
+            return Err(PluginError::Command("Already recording".to_string()));
+        }
+        let path = self.next_path(RECORDING_PREFIX, "mov")?;
+        let child = Command::new("screencapture")
+            .arg("-v")
+            .arg(&path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| PluginError::Command(format!("screencapture failed: {}", e)))?;
+        *self.recording.lock() = Some(Recording {
+            child,
+            path: path.clone(),
+            started: Instant::now(),
+        });
+        Ok(path)
+    }
+
+    /// Stop the running recording, returning where it was saved.
+    pub fn stop_recording(&self) -> PluginResult<PathBuf> {
+        let Some(mut recording) = self.recording.lock().take() else {
+            return Err(PluginError::Command("Not recording".to_string()));
+        };
+        // screencapture finishes the file on SIGINT, like Ctrl-C in a terminal
+        let status = Command::new("kill")
+            .args(["-INT", &recording.child.id().to_string()])
+            .status()
+            .map_err(|e| PluginError::Command(format!("Failed to stop recording: {}", e)))?;
+        if !status.success() {
+            recording.child.kill().ok();
+        }
+        recording
+            .child
+            .wait()
+            .map_err(|e| PluginError::Command(format!("Failed to stop recording: {}", e)))?;
+        *self.latest.lock() = Some(recording.path.clone());
+        Ok(recording.path)
+    }
+
+    /// The most recent capture in the folder (or the last one Lux made).
+    pub fn latest(&self) -> Option<PathBuf> {
+        let scanned = latest_capture(&self.folder());
+        let made = self.latest.lock().clone().filter(|path| path.exists());
+        match (scanned, made) {
+            (Some(scanned), Some(made)) => Some(if modified(&made) >= modified(&scanned) {
+                made
+            } else {
+                scanned
+            }),
+            (scanned, made) => scanned.or(made),
+        }
+    }
+
+    /// Build the root-view "Capture" group for a query.
+    ///
+    /// A running recording is listed on every query until it is stopped.
+    pub fn group(&self, query: &str, now: Instant) -> Option<Group> {
+        let recording = self.recording_since();
+        if !matches_keywords(query) {
+            let started = recording?;
+            return Some(Group::new(
+                t("Capture"),
+                vec![stop_item(now.saturating_duration_since(started))],
+            ));
+        }
+
+        let mut items: Vec<Item> = [CaptureKind::Area, CaptureKind::Window, CaptureKind::Screen]
+            .iter()
+            .map(|kind| command_item(kind.action_id(), kind.title(), "📸"))
+            .collect();
+        items.push(match recording {
+            Some(started) => stop_item(now.saturating_duration_since(started)),
+            None => command_item("start_recording", t("Start Recording"), "🎥"),
+        });
+        if let Some(latest) = self.latest() {
+            items.push(latest_item(&latest));
+        }
+        Some(Group::new(t("Capture"), items))
+    }
+}
+
+impl Default for Captures {
+    fn default() -> Self {
+        Self::new(Self::default_folder())
+    }
+}
+
+/// `dir/name.ext`, or `dir/name (2).ext` etc. when that exists.
+fn capture_path(dir: &Path, name: &str, ext: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.{}", name, ext));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} ({}).{}", name, n, ext));
+        n += 1;
+    }
+    path
+}
+
+fn modified(path: &Path) -> SystemTime {
+    path.metadata()
+        .and_then(|meta| meta.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// The newest screenshot or recording in a folder.
+pub fn latest_capture(folder: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(folder)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            name.starts_with(SCREENSHOT_PREFIX) || name.starts_with(RECORDING_PREFIX)
+        })
+        .max_by_key(|path| modified(path))
+}
+
+/// Whether a query asks for the capture commands (`scr`, `screenshot`, `record`).
+fn matches_keywords(query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.chars().count() < MIN_QUERY_LEN {
+        return false;
+    }
+    KEYWORDS
+        .iter()
+        .any(|keyword| keyword.starts_with(&query) || query.starts_with(keyword))
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+fn command_item(action_id: &str, title: String, icon: &str) -> Item {
+    Item::new(format!("capture:{}", action_id), title)
+        .with_icon(icon)
+        .with_type(CAPTURE_ITEM_TYPE)
+        .with_data(serde_json::json!({ "command": action_id }))
+}
+
+fn stop_item(elapsed: Duration) -> Item {
+    command_item("stop_recording", t("Stop Recording"), "⏹").with_subtitle(t_args(
+        "Recording for {time}",
+        &[("time", &format_remaining(elapsed))],
+    ))
+}
+
+fn latest_item(path: &Path) -> Item {
+    let path_str = path.to_string_lossy().to_string();
+    let is_image = path.extension().is_some_and(|ext| ext == "png");
+    Item::new(
+        format!("capture:latest:{}", path_str),
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path_str.clone()),
+    )
+    .with_subtitle(t("Latest Capture"))
+    .with_icon(if is_image {
+        path_str.clone()
+    } else {
+        "🎞".to_string()
+    })
+    .with_type(CAPTURE_ITEM_TYPE)
+    .with_data(serde_json::json!({ "path": path_str, "image": is_image }))
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a capture item: `(id, title)`.
+///
+/// Commands run themselves; the latest capture can be copied, revealed, or
+/// (for screenshots) opened for markup.
+pub fn capture_actions(item: &Item) -> Vec<(&'static str, String)> {
+    let data = item.data.as_ref();
+    if let Some(command) = data.and_then(|data| data.get("command")?.as_str()) {
+        let id = CAPTURE_ACTION_IDS
+            .iter()
+            .find(|id| **id == command)
+            .copied()
+            .unwrap_or("capture_area");
+        return vec![(id, item.title.clone())];
+    }
+
+    let mut actions = vec![
+        ("copy", t("Copy to Clipboard")),
+        ("reveal", t("Reveal in Finder")),
+    ];
+    if data.and_then(|data| data.get("image")?.as_bool()) == Some(true) {
+        actions.push(("annotate", t("Annotate")));
+    }
+    actions
+}
+
+/// Run a capture action.
+pub fn run_capture_action(action_id: &str, item: &Item, captures: &Captures) -> PluginResult<()> {
+    let path = || {
+        item.data
+            .as_ref()
+            .and_then(|data| data.get("path")?.as_str())
+            .ok_or_else(|| PluginError::Command("Item is not a capture".to_string()))
+    };
+    match action_id {
+        "capture_area" => captures.screenshot(CaptureKind::Area).map(|_| ()),
+        "capture_window" => captures.screenshot(CaptureKind::Window).map(|_| ()),
+        "capture_screen" => captures.screenshot(CaptureKind::Screen).map(|_| ()),
+        "start_recording" => captures.start_recording().map(|_| ()),
+        "stop_recording" => {
+            let path = captures.stop_recording()?;
+            desktop::current().reveal(&path.to_string_lossy())
+        }
+        "copy" => {
+            let path = path()?;
+            if path.ends_with(".png") {
+                desktop::current().clipboard_write_image(path)
+            } else {
+                desktop::current().clipboard_write(path)
+            }
+        }
+        "reveal" => desktop::current().reveal(path()?),
+        // Preview opens screenshots with the Markup toolbar available
+        "annotate" => desktop::current().open_with(path()?, "Preview"),
+        _ => Err(PluginError::Command(format!(
+            "Unknown capture action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_keywords() {
+        assert!(matches_keywords("scr"));
+        assert!(matches_keywords("Screenshot"));
+        assert!(matches_keywords("rec"));
+        assert!(matches_keywords("capture window"));
+        assert!(!matches_keywords("sc"));
+        assert!(!matches_keywords("safari"));
+    }
+
+    #[test]
+    fn test_latest_capture_and_naming() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(latest_capture(dir.path()), None);
+
+        std::fs::write(dir.path().join("notes.txt"), b"").unwrap();
+        let first = capture_path(dir.path(), "Screenshot 2026-10-16 at 14.03.22", "png");
+        std::fs::write(&first, b"").unwrap();
+        assert_eq!(latest_capture(dir.path()), Some(first.clone()));
+
+        let second = capture_path(dir.path(), "Screenshot 2026-10-16 at 14.03.22", "png");
+        assert_eq!(
+            second.file_name().unwrap(),
+            "Screenshot 2026-10-16 at 14.03.22 (2).png"
+        );
+    }
+
+    #[test]
+    fn test_group_and_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let captures = Captures::new(dir.path());
+        assert!(captures.group("", Instant::now()).is_none());
+
+        let group = captures.group("screen", Instant::now()).unwrap();
+        let titles: Vec<&str> = group.items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Capture Area",
+                "Capture Window",
+                "Capture Screen",
+                "Start Recording"
+            ]
+        );
+        let ids: Vec<&str> = capture_actions(&group.items[1])
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, ["capture_window"]);
+
+        let path = dir.path().join("Screenshot 1.png");
+        std::fs::write(&path, b"").unwrap();
+        let group = captures.group("screenshot", Instant::now()).unwrap();
+        let latest = group.items.last().unwrap();
+        assert_eq!(latest.title, "Screenshot 1.png");
+        let ids: Vec<&str> = capture_actions(latest)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(ids, ["copy", "reveal", "annotate"]);
+    }
+}
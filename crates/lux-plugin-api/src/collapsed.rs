@@ -0,0 +1,128 @@
+//! Collapsed result groups.
+//!
+//! This module provides:
+//! - `CollapsedGroups` - Which collapsible groups the user folded away, per view, saved to the data directory
+//!
+//! A view marks a group foldable with `collapsible = true`; the launcher
+//! folds and unfolds it (left/right arrows or a click on the header) and
+//! records it here, so the group stays collapsed the next time the view is
+//! shown. Groups are keyed by view ID and group title.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+
+use crate::storage;
+
+/// Key of views without an ID (the root view).
+pub const ROOT_VIEW_KEY: &str = "root";
+
+/// Collapsed group titles, per view ID.
+///
+/// Saved as a JSON object after every change.
+pub struct CollapsedGroups {
+    path: Option<PathBuf>,
+    views: Mutex<BTreeMap<String, BTreeSet<String>>>,
+}
+
+impl CollapsedGroups {
+    /// Load collapsed groups from `path` (None keeps them in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let views = path.as_deref().map(load_views).unwrap_or_default();
+        Self {
+            path,
+            views: Mutex::new(views),
+        }
+    }
+
+    /// Where collapsed groups are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("collapsed.json"))
+    }
+
+    /// Titles of the collapsed groups of `view_id`.
+    pub fn collapsed(&self, view_id: &str) -> BTreeSet<String> {
+        self.views.lock().get(view_id).cloned().unwrap_or_default()
+    }
+
+    /// Whether the group titled `title` of `view_id` is collapsed.
+    pub fn is_collapsed(&self, view_id: &str, title: &str) -> bool {
+        self.views
+            .lock()
+            .get(view_id)
+            .is_some_and(|titles| titles.contains(title))
+    }
+
+    /// Collapse or expand a group. Returns true if that changed anything.
+    pub fn set_collapsed(&self, view_id: &str, title: &str, collapsed: bool) -> bool {
+        let mut views = self.views.lock();
+        let changed = if collapsed {
+            views
+                .entry(view_id.to_string())
+                .or_default()
+                .insert(title.to_string())
+        } else {
+            let removed = views
+                .get_mut(view_id)
+                .is_some_and(|titles| titles.remove(title));
+            views.retain(|_, titles| !titles.is_empty());
+            removed
+        };
+        if changed {
+            self.save(&views);
+        }
+        changed
+    }
+
+    fn save(&self, views: &BTreeMap<String, BTreeSet<String>>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = save_views(path, views) {
+            tracing::warn!("Failed to save collapsed groups: {}", e);
+        }
+    }
+}
+
+impl Default for CollapsedGroups {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+fn load_views(path: &Path) -> BTreeMap<String, BTreeSet<String>> {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_views(path: &Path, views: &BTreeMap<String, BTreeSet<String>>) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(views)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapsed_groups_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("collapsed.json");
+        let collapsed = CollapsedGroups::new(Some(path.clone()));
+
+        assert!(collapsed.set_collapsed("files", "Recent", true));
+        assert!(!collapsed.set_collapsed("files", "Recent", true));
+        assert!(collapsed.is_collapsed("files", "Recent"));
+        assert!(!collapsed.is_collapsed(ROOT_VIEW_KEY, "Recent"));
+
+        let reloaded = CollapsedGroups::new(Some(path));
+        assert_eq!(
+            reloaded.collapsed("files").into_iter().collect::<Vec<_>>(),
+            ["Recent"]
+        );
+        assert!(reloaded.set_collapsed("files", "Recent", false));
+        assert!(reloaded.collapsed("files").is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+//! Color conversion and recently used colors.
+//!
+//! This module provides:
+//! - `ColorFormat` - The formats a color can be copied in (hex, RGB, HSL)
+//! - `ColorHistory` - Recently copied colors, saved to the data directory
+//! - `color_groups()` - Root-view conversions for a typed color, or the history for `colors`
+//! - `color_actions()` / `run_color_action()` - Copy a color in any format
+//!
+//! Color items use the color's hex value as their icon, which the launcher
+//! renders as a swatch.
+
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::t;
+use lux_core::{Group, Item};
+use parking_lot::Mutex;
+
+use crate::detect::{parse_color, Rgba};
+use crate::error::{PluginError, PluginResult};
+use crate::storage;
+
+/// Type tag for color items (used to route them to the built-in actions).
+pub const COLOR_ITEM_TYPE: &str = "color_value";
+
+/// Lua registry key prefix of the built-in color action handlers.
+pub const COLOR_ACTION_KEY_PREFIX: &str = "color:";
+
+/// IDs of all color actions (one handler is registered per ID).
+pub const COLOR_ACTION_IDS: &[&str] = &["copy_hex", "copy_rgb", "copy_hsl"];
+
+/// Queries that list the history.
+const HISTORY_KEYWORDS: &[&str] = &["color", "colors"];
+
+/// Colors kept in the history.
+const HISTORY_SIZE: usize = 20;
+
+/// Handler key for a color action ID.
+pub fn color_action_key(id: &str) -> String {
+    format!("{}{}", COLOR_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Formats
+// =============================================================================
+
+/// A format a color can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    Hex,
+    Rgb,
+    Hsl,
+}
+
+impl ColorFormat {
+    /// All formats, in the order they're listed.
+    pub const ALL: [ColorFormat; 3] = [ColorFormat::Hex, ColorFormat::Rgb, ColorFormat::Hsl];
+
+    /// Short ID (used in action IDs and item data).
+    pub fn id(self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "hex",
+            ColorFormat::Rgb => "rgb",
+            ColorFormat::Hsl => "hsl",
+        }
+    }
+
+    /// Parse a format ID.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.id() == id)
+    }
+
+    /// Display name.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "Hex",
+            ColorFormat::Rgb => "RGB",
+            ColorFormat::Hsl => "HSL",
+        }
+    }
+
+    /// Write a color in this format.
+    pub fn format(self, color: Rgba) -> String {
+        match self {
+            ColorFormat::Hex => color.to_hex(),
+            ColorFormat::Rgb => color.to_rgb(),
+            ColorFormat::Hsl => color.to_hsl(),
+        }
+    }
+
+    /// ID of the action that copies a color in this format.
+    pub fn action_id(self) -> &'static str {
+        match self {
+            ColorFormat::Hex => "copy_hex",
+            ColorFormat::Rgb => "copy_rgb",
+            ColorFormat::Hsl => "copy_hsl",
+        }
+    }
+
+    /// Title of the action that copies a color in this format.
+    pub fn action_title(self) -> String {
+        match self {
+            ColorFormat::Hex => t("Copy as Hex"),
+            ColorFormat::Rgb => t("Copy as RGB"),
+            ColorFormat::Hsl => t("Copy as HSL"),
+        }
+    }
+}
+
+// =============================================================================
+// History
+// =============================================================================
+
+/// Recently copied colors, newest first.
+///
+/// Saved as a JSON array of hex values after every change.
+pub struct ColorHistory {
+    path: Option<PathBuf>,
+    colors: Mutex<Vec<Rgba>>,
+}
+
+impl ColorHistory {
+    /// Load the history from `path` (None keeps it in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let colors = path.as_deref().map(load_history).unwrap_or_default();
+        Self {
+            path,
+            colors: Mutex::new(colors),
+        }
+    }
+
+    /// Where the history is saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("colors.json"))
+    }
+
+    /// Recently copied colors, newest first.
+    pub fn colors(&self) -> Vec<Rgba> {
+        self.colors.lock().clone()
+    }
+
+    /// Move a color to the front of the history and save it.
+    pub fn record(&self, color: Rgba) {
+        let mut colors = self.colors.lock();
+        colors.retain(|c| *c != color);
+        colors.insert(0, color);
+        colors.truncate(HISTORY_SIZE);
+
+        if let Some(path) = &self.path {
+            if let Err(e) = save_history(path, &colors) {
+                tracing::warn!("Failed to save color history: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for ColorHistory {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+fn load_history(path: &Path) -> Vec<Rgba> {
+    let Ok(text) = storage::read(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str::<Vec<String>>(&text)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|hex| parse_color(hex))
+        .take(HISTORY_SIZE)
+        .collect()
+}
+
+fn save_history(path: &Path, colors: &[Rgba]) -> std::io::Result<()> {
+    let hex: Vec<String> = colors.iter().map(|c| c.to_hex()).collect();
+    storage::write(path, &serde_json::to_string(&hex)?)
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item showing a color in one format.
+pub fn format_item(color: Rgba, format: ColorFormat) -> Item {
+    let hex = color.to_hex();
+    Item::new(
+        format!("color:{}:{}", format.id(), hex),
+        format.format(color),
+    )
+    .with_subtitle(format.name().to_string())
+    .with_icon(hex.clone())
+    .with_type(COLOR_ITEM_TYPE)
+    .with_data(serde_json::json!({ "hex": hex, "format": format.id() }))
+}
+
+/// Build the item for a color in the history.
+pub fn history_item(color: Rgba) -> Item {
+    let hex = color.to_hex();
+    Item::new(format!("color:history:{}", hex), hex.clone())
+        .with_subtitle(color.to_rgb())
+        .with_icon(hex.clone())
+        .with_type(COLOR_ITEM_TYPE)
+        .with_data(serde_json::json!({ "hex": hex, "format": ColorFormat::Hex.id() }))
+}
+
+/// Build the root-view groups for a query.
+///
+/// A typed color (hex, `rgb()`, or `hsl()`) lists its conversions; `color`
+/// or `colors` lists the history.
+pub fn color_groups(query: &str, history: &ColorHistory) -> Vec<Group> {
+    let query = query.trim();
+    if let Some(color) = parse_color(query) {
+        let items = ColorFormat::ALL
+            .into_iter()
+            .map(|format| format_item(color, format))
+            .collect();
+        return vec![Group::new(t("Color"), items)];
+    }
+
+    if HISTORY_KEYWORDS.contains(&query.to_lowercase().as_str()) {
+        let items: Vec<Item> = history.colors().into_iter().map(history_item).collect();
+        if !items.is_empty() {
+            return vec![Group::new(t("Recent Colors"), items)];
+        }
+    }
+    Vec::new()
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a color item: `(id, title)`.
+///
+/// The item's own format comes first so it is the default action.
+pub fn color_actions(item: &Item) -> Vec<(&'static str, String)> {
+    let own = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("format")?.as_str())
+        .and_then(ColorFormat::from_id)
+        .unwrap_or(ColorFormat::Hex);
+
+    std::iter::once(own)
+        .chain(ColorFormat::ALL.into_iter().filter(|format| *format != own))
+        .map(|format| (format.action_id(), format.action_title()))
+        .collect()
+}
+
+/// Run a color action and add the color to the history.
+pub fn run_color_action(action_id: &str, item: &Item, history: &ColorHistory) -> PluginResult<()> {
+    let color = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("hex")?.as_str())
+        .and_then(parse_color)
+        .ok_or_else(|| PluginError::Command("Item is not a color".to_string()))?;
+    let format = ColorFormat::ALL
+        .into_iter()
+        .find(|format| format.action_id() == action_id)
+        .ok_or_else(|| PluginError::Command(format!("Unknown color action '{}'", action_id)))?;
+
+    crate::desktop::current().clipboard_write(&format.format(color))?;
+    history.record(color);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_groups_for_typed_color() {
+        let history = ColorHistory::new(None);
+        let groups = color_groups(" rgb(255, 0, 136) ", &history);
+        assert_eq!(groups.len(), 1);
+
+        let titles: Vec<&str> = groups[0].items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["#ff0088", "rgb(255, 0, 136)", "hsl(328, 100%, 50%)"]
+        );
+        assert_eq!(groups[0].items[0].icon.as_deref(), Some("#ff0088"));
+
+        assert!(color_groups("red", &history).is_empty());
+        // The history is only listed once it has colors
+        assert!(color_groups("colors", &history).is_empty());
+    }
+
+    #[test]
+    fn test_actions_start_with_item_format() {
+        let color = parse_color("#123456").unwrap();
+        let ids = |format| -> Vec<&str> {
+            color_actions(&format_item(color, format))
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+        assert_eq!(ids(ColorFormat::Hex), ["copy_hex", "copy_rgb", "copy_hsl"]);
+        assert_eq!(ids(ColorFormat::Hsl), ["copy_hsl", "copy_hex", "copy_rgb"]);
+        for (id, _) in color_actions(&history_item(color)) {
+            assert!(COLOR_ACTION_IDS.contains(&id));
+        }
+    }
+
+    #[test]
+    fn test_history_is_saved_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux").join("colors.json");
+        let (red, blue) = (parse_color("#f00").unwrap(), parse_color("#00f").unwrap());
+
+        let history = ColorHistory::new(Some(path.clone()));
+        history.record(red);
+        history.record(blue);
+        history.record(red);
+        assert_eq!(history.colors(), [red, blue]);
+
+        let reloaded = ColorHistory::new(Some(path));
+        assert_eq!(reloaded.colors(), [red, blue]);
+        let groups = color_groups("Colors", &reloaded);
+        assert_eq!(groups[0].items[1].title, "#0000ff");
+    }
+}
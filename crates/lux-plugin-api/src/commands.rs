@@ -0,0 +1,162 @@
+//! Lux's own commands, reachable by search.
+//!
+//! This module provides:
+//! - `Command` - A launcher action listed in the Commands view
+//! - `commands()` - Every listed command, in display order
+//! - `command_groups()` - The rows of the Commands view
+//!
+//! The Commands view (`lux.builtin.commands()`) lists internal actions such
+//! as `reload_config` or `clear_caches` that may have no keybinding. Running
+//! a row pops the view and runs the action by name (`ctx:run_command()`), the
+//! same as a key bound to it with `lux.keymap.set()` would.
+
+use lux_core::i18n::t;
+use lux_core::{Group, Groups, Item};
+
+use crate::keymap::PendingBinding;
+
+/// ID of the built-in Commands view.
+pub const COMMANDS_VIEW_ID: &str = "builtin.commands";
+
+/// A launcher action listed in the Commands view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// Action name, as `lux.keymap.set()` takes it.
+    pub action: &'static str,
+    /// Title of the row.
+    pub title: String,
+    /// What the action does.
+    pub subtitle: String,
+}
+
+impl Command {
+    fn new(action: &'static str, title: &str, subtitle: &str) -> Self {
+        Self {
+            action,
+            title: t(title),
+            subtitle: t(subtitle),
+        }
+    }
+}
+
+/// Every command of the Commands view, in display order.
+pub fn commands() -> Vec<Command> {
+    vec![
+        Command::new(
+            "reload_config",
+            "Reload Config",
+            "Restart Lux and run init.lua again",
+        ),
+        Command::new(
+            "open_config",
+            "Open Config File",
+            "Open init.lua in your editor",
+        ),
+        Command::new(
+            "toggle_theme",
+            "Toggle Theme",
+            "Switch between light and dark until Lux restarts",
+        ),
+        Command::new("open_logs", "Open Logs", "Open the log file of this run"),
+        Command::new(
+            "toggle_dev_mode",
+            "Toggle Developer Mode",
+            "Show the ID of each result",
+        ),
+        Command::new(
+            "clear_caches",
+            "Clear Caches",
+            "Drop cached results, and fetch exchange rates and Git repositories again",
+        ),
+        Command::new(
+            "toggle_macro_recording",
+            "Start or Stop Macro Recording",
+            "Record the query and launcher actions to a macro",
+        ),
+        Command::new(
+            "undo",
+            "Undo",
+            "Undo the last file operation or plugin action",
+        ),
+    ]
+}
+
+/// Rows of the Commands view, with the key each command is bound to.
+///
+/// The item ID is the action name.
+pub fn command_groups(bindings: &[PendingBinding], query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let items: Vec<Item> = commands()
+        .into_iter()
+        .filter(|command| {
+            [
+                command.action,
+                command.title.as_str(),
+                command.subtitle.as_str(),
+            ]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query))
+        })
+        .map(|command| {
+            let item = Item::new(command.action, command.title).with_subtitle(command.subtitle);
+            // View bindings only work in their view, so they aren't shown
+            let key = bindings.iter().find(|binding| {
+                binding.view.is_none() && binding.action_name() == Some(command.action)
+            });
+            match key {
+                Some(binding) => item.with_accessory(binding.key.clone()),
+                None => item,
+            }
+        })
+        .collect();
+
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::ungrouped(items)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap::KeyHandler;
+
+    fn binding(key: &str, action: &str, view: Option<&str>) -> PendingBinding {
+        PendingBinding {
+            key: key.to_string(),
+            handler: KeyHandler::Action(action.to_string()),
+            context: None,
+            view: view.map(str::to_string),
+            exclusive: false,
+        }
+    }
+
+    #[test]
+    fn test_command_groups() {
+        let bindings = vec![
+            binding("cmd+shift+m", "toggle_macro_recording", None),
+            binding("cmd+k", "clear_caches", Some("builtin.stats")),
+        ];
+
+        let groups = command_groups(&bindings, "");
+        let items = &groups[0].items;
+        assert_eq!(items.len(), commands().len());
+        assert_eq!(items[0].id, "reload_config");
+
+        let recording = items
+            .iter()
+            .find(|item| item.id == "toggle_macro_recording")
+            .unwrap();
+        assert_eq!(recording.accessories, vec!["cmd+shift+m".to_string()]);
+        let caches = items.iter().find(|item| item.id == "clear_caches").unwrap();
+        assert!(caches.accessories.is_empty());
+
+        // Matches titles, subtitles, and action names
+        let groups = command_groups(&bindings, "THEME");
+        assert_eq!(groups[0].items.len(), 1);
+        assert_eq!(groups[0].items[0].id, "toggle_theme");
+        let groups = command_groups(&bindings, "dev_mode");
+        assert_eq!(groups[0].items[0].id, "toggle_dev_mode");
+        assert!(command_groups(&bindings, "nothing like this").is_empty());
+    }
+}
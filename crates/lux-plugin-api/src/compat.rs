@@ -0,0 +1,224 @@
+//! Lua API versions and compatibility shims.
+//!
+//! This module provides:
+//! - `API_VERSION` / `MIN_API_VERSION` - The version `lux.api_version` reports, and the oldest still supported
+//! - `SHIMS` - Functions renamed or removed since `MIN_API_VERSION`
+//! - `check_api_version()` - Reject plugins targeting a version this Lux can't run
+//! - `install_shims()` / `plugin_env()` - The old names, for code that targets an older version
+//!
+//! A plugin names the API version it targets in its manifest (`api_version`,
+//! 1 if missing). It runs with the shims of every function renamed or removed
+//! after that version, and without the ones it shouldn't know about. init.lua
+//! has no manifest, so it gets every shim. Each shim records a deprecation
+//! warning (shown in the Config Warnings view) the first time it is called.
+//!
+//! Only top-level `lux` functions can be shimmed; the replacement may be
+//! nested (`views.add`).
+
+use std::sync::Arc;
+
+use mlua::{Function, Lua, MultiValue, Result as LuaResult, Table, Value};
+
+use crate::error::{PluginError, PluginResult};
+use crate::warnings::{ConfigWarning, ConfigWarnings, WarningKind};
+
+/// API version of this Lux (`lux.api_version`).
+pub const API_VERSION: u32 = 2;
+
+/// Oldest API version plugins can target.
+pub const MIN_API_VERSION: u32 = 1;
+
+/// A `lux` function that was renamed or removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shim {
+    /// The old name, in `lux`.
+    pub name: &'static str,
+    /// What it calls now, relative to `lux`.
+    pub replacement: &'static str,
+    /// First API version without it.
+    pub removed_in: u32,
+}
+
+/// Every shim, oldest first.
+pub const SHIMS: &[Shim] = &[Shim {
+    name: "set_root_view",
+    replacement: "set_root",
+    removed_in: 2,
+}];
+
+/// Builds a plugin's `lux` table (see `plugin_env`).
+const PLUGIN_LUX: &str = r#"
+local lux, shims, hidden = ...
+return setmetatable(shims, {
+    __index = function(_, key)
+        if hidden[key] then return nil end
+        return lux[key]
+    end,
+    __newindex = lux,
+    __metatable = false,
+})
+"#;
+
+/// Builds a trusted plugin's globals (see `plugin_env`).
+const PLUGIN_ENV: &str = r#"
+local globals, lux = ...
+return setmetatable({ lux = lux }, { __index = globals, __newindex = globals })
+"#;
+
+/// The shims code targeting `api_version` needs.
+pub fn shims_for(api_version: u32) -> impl Iterator<Item = &'static Shim> {
+    SHIMS
+        .iter()
+        .filter(move |shim| api_version < shim.removed_in)
+}
+
+/// Error unless this Lux can run the plugin `name`, which targets `api_version`.
+pub fn check_api_version(name: &str, api_version: u32) -> PluginResult<()> {
+    if api_version > API_VERSION {
+        return Err(PluginError::Command(format!(
+            "Plugin '{}' needs Lux API version {}; this Lux has version {}",
+            name, api_version, API_VERSION
+        )));
+    }
+    if api_version < MIN_API_VERSION {
+        return Err(PluginError::Command(format!(
+            "Plugin '{}' targets Lux API version {}, which is no longer supported (oldest: {})",
+            name, api_version, MIN_API_VERSION
+        )));
+    }
+    Ok(())
+}
+
+/// Set the shims for `api_version` in `target`. Calls are recorded in
+/// `warnings` as coming from `subject` ("init.lua", "plugin 'weather'").
+pub fn install_shims(
+    lua: &Lua,
+    target: &Table,
+    api_version: u32,
+    subject: &str,
+    warnings: Arc<ConfigWarnings>,
+) -> LuaResult<()> {
+    for shim in shims_for(api_version) {
+        let subject = subject.to_string();
+        let warnings = warnings.clone();
+        let shim_fn = lua.create_function(move |lua, args: MultiValue| {
+            warnings.record(ConfigWarning {
+                kind: WarningKind::Deprecated,
+                subject: subject.clone(),
+                field: Some(format!("lux.{}", shim.name)),
+                message: format!(
+                    "lux.{} was replaced by lux.{} in API version {}",
+                    shim.name, shim.replacement, shim.removed_in
+                ),
+                location: None,
+            });
+            replacement(lua, shim)?.call::<MultiValue>(args)
+        })?;
+        target.raw_set(shim.name, shim_fn)?;
+    }
+    Ok(())
+}
+
+/// The current `lux` function a shim calls.
+fn replacement(lua: &Lua, shim: &Shim) -> LuaResult<Function> {
+    let mut value = Value::Table(lua.globals().get("lux")?);
+    for part in shim.replacement.split('.') {
+        value = match value {
+            Value::Table(table) => table.get(part)?,
+            _ => Value::Nil,
+        };
+    }
+    match value {
+        Value::Function(func) => Ok(func),
+        _ => Err(mlua::Error::RuntimeError(format!(
+            "lux.{} is missing (shim for lux.{})",
+            shim.replacement, shim.name
+        ))),
+    }
+}
+
+/// The `lux` table of the plugin `name`, which targets `api_version`: the
+/// global one, with the shims it needs and without the ones it doesn't.
+pub fn plugin_lux(
+    lua: &Lua,
+    name: &str,
+    api_version: u32,
+    warnings: Arc<ConfigWarnings>,
+) -> LuaResult<Table> {
+    let shims = lua.create_table()?;
+    install_shims(
+        lua,
+        &shims,
+        api_version,
+        &format!("plugin '{}'", name),
+        warnings,
+    )?;
+    let hidden = lua.create_table()?;
+    for shim in SHIMS {
+        hidden.set(shim.name, !shims.contains_key(shim.name)?)?;
+    }
+    let lux: Table = lua.globals().get("lux")?;
+    lua.load(PLUGIN_LUX)
+        .set_name("=plugin_lux")
+        .call((lux, shims, hidden))
+}
+
+/// Globals for a trusted plugin: the real ones, with `lux` replaced.
+/// New globals still go to the real table.
+pub fn plugin_env(lua: &Lua, lux: Table) -> LuaResult<Table> {
+    lua.load(PLUGIN_ENV)
+        .set_name("=plugin_env")
+        .call((lua.globals(), lux))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lua_with_lux() -> Lua {
+        let lua = Lua::new();
+        lua.load(
+            r#"
+            roots = {}
+            lux = { set_root = function(view) table.insert(roots, view.id) return #roots end }
+            "#,
+        )
+        .exec()
+        .unwrap();
+        lua
+    }
+
+    #[test]
+    fn test_check_api_version() {
+        assert!(check_api_version("weather", API_VERSION).is_ok());
+        assert!(check_api_version("weather", MIN_API_VERSION).is_ok());
+        let err = check_api_version("weather", API_VERSION + 1).unwrap_err();
+        assert!(err.to_string().contains("needs Lux API version"));
+        assert!(check_api_version("weather", 0).is_err());
+    }
+
+    #[test]
+    fn test_shims_follow_target_version() {
+        let lua = lua_with_lux();
+        let warnings = Arc::new(ConfigWarnings::new());
+
+        let old = plugin_lux(&lua, "old", 1, warnings.clone()).unwrap();
+        let env = plugin_env(&lua, old).unwrap();
+        let count: i64 = lua
+            .load("leaked = true; return lux.set_root_view({ id = 'a' })")
+            .set_environment(env)
+            .eval()
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(lua.globals().get::<bool>("leaked").unwrap());
+
+        let list = warnings.list();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].subject, "plugin 'old'");
+        assert_eq!(list[0].field.as_deref(), Some("lux.set_root_view"));
+
+        let current = plugin_lux(&lua, "new", API_VERSION, warnings).unwrap();
+        assert!(current.get::<Value>("set_root_view").unwrap().is_nil());
+        assert!(current.get::<Value>("set_root").unwrap().is_function());
+    }
+}
@@ -0,0 +1,465 @@
+//! Quick access to Lux's own config and data files.
+//!
+//! This module provides:
+//! - `Editor` / `EditorSettings` - The editor config files open in (`editor` in config.toml)
+//! - `ConfigFile` / `ConfigPaths` - init.lua, config.toml, the plugins folder, and the data folder
+//! - `config_files_group()` - The root-view "Config Files" group
+//! - `config_file_actions()` / `run_config_file_action()` - Open, reveal, or copy the path
+//!
+//! Typing `init.lua`, `plugins`, or `lux config` in the root view lists the
+//! matching files, so finding them doesn't take reading the logs. Files and
+//! folders that don't exist yet aren't listed.
+//!
+//! The editor is only set in config.toml (`editor = "zed"`), never from Lua:
+//! it is a command Lux runs, and plugin code shouldn't get to pick it.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{DragPayload, Group, Item};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::spotlight::abbreviate_home;
+
+/// Type tag for config file items (used to route them to the built-in actions).
+pub const CONFIG_FILE_ITEM_TYPE: &str = "config_file";
+
+/// Lua registry key prefix of the built-in config file action handlers.
+pub const CONFIG_FILE_ACTION_KEY_PREFIX: &str = "config_file:";
+
+/// IDs of all config file actions (one handler is registered per ID).
+pub const CONFIG_FILE_ACTION_IDS: &[&str] = &["open_editor", "open_default", "reveal", "copy_path"];
+
+/// Shortest query matched against config file names.
+const MIN_QUERY_LEN: usize = 3;
+
+/// Handler key for a config file action ID.
+pub fn config_file_action_key(id: &str) -> String {
+    format!("{}{}", CONFIG_FILE_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Editor
+// =============================================================================
+
+/// The editor config files open in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Editor {
+    /// `$EDITOR`, or the default app if it isn't set.
+    #[default]
+    Env,
+    /// The app the OS opens the file with.
+    DefaultApp,
+    /// A command run with the path (e.g. `code`, `zed`, `subl -n`).
+    Command(String),
+}
+
+impl Editor {
+    /// Parse a name from config.toml: `"env"`, `"default"`, or a command.
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_lowercase().as_str() {
+            "" | "env" | "$editor" => Self::Env,
+            "default" | "system" => Self::DefaultApp,
+            _ => Self::Command(name.trim().to_string()),
+        }
+    }
+
+    /// Name of the setting, as `from_name()` takes it.
+    pub fn name(&self) -> String {
+        match self {
+            Self::Env => "env".to_string(),
+            Self::DefaultApp => "default".to_string(),
+            Self::Command(command) => command.clone(),
+        }
+    }
+
+    /// The command to run given the value of `$EDITOR`, None for the default app.
+    pub fn command(&self, env_editor: Option<&str>) -> Option<String> {
+        match self {
+            Self::Env => env_editor
+                .map(str::trim)
+                .filter(|editor| !editor.is_empty())
+                .map(str::to_string),
+            Self::DefaultApp => None,
+            Self::Command(command) => Some(command.clone()),
+        }
+    }
+
+    /// The command to run with this process's `$EDITOR`.
+    ///
+    /// Apps started from the Dock or at login don't see the `$EDITOR` of a
+    /// shell profile, and terminal editors such as `vim` need a terminal, so
+    /// setting a GUI editor in config.toml is the reliable choice.
+    pub fn current_command(&self) -> Option<String> {
+        self.command(std::env::var("EDITOR").ok().as_deref())
+    }
+
+    /// Open a file or folder in this editor.
+    pub fn open(&self, path: &Path) -> PluginResult<()> {
+        match self.current_command() {
+            Some(command) => open_in_editor(&command, path),
+            None => desktop::current().open(&path.to_string_lossy()),
+        }
+    }
+}
+
+/// The keys of config.toml read here.
+#[derive(Debug, Default, Deserialize)]
+struct ConfigToml {
+    editor: Option<String>,
+}
+
+/// The editor setting, read from config.toml.
+pub struct EditorSettings {
+    path: Option<PathBuf>,
+}
+
+impl EditorSettings {
+    /// Create settings using `$EDITOR`.
+    pub fn new() -> Self {
+        Self { path: None }
+    }
+
+    /// Read the editor from the config.toml at `path`.
+    pub fn with_path(mut self, path: Option<PathBuf>) -> Self {
+        self.path = path;
+        self
+    }
+
+    /// Where config.toml is by default.
+    pub fn default_path() -> Option<PathBuf> {
+        ConfigPaths::current().map(|paths| ConfigFile::ConfigToml.path(&paths))
+    }
+
+    /// The editor config files open in.
+    ///
+    /// config.toml is read each time, so an edit applies right away. A
+    /// missing file or `editor` key means `$EDITOR`.
+    pub fn editor(&self) -> Editor {
+        let Some(path) = &self.path else {
+            return Editor::default();
+        };
+        let Ok(text) = std::fs::read_to_string(path) else {
+            return Editor::default();
+        };
+        match toml::from_str::<ConfigToml>(&text) {
+            Ok(config) => config
+                .editor
+                .map(|name| Editor::from_name(&name))
+                .unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("Ignoring invalid {}: {}", path.display(), e);
+                Editor::default()
+            }
+        }
+    }
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run an editor command (e.g. `code`, `zed`, `subl -n`) with a path.
+pub fn open_in_editor(editor: &str, path: &Path) -> PluginResult<()> {
+    let mut parts = editor.split_whitespace();
+    let program = parts
+        .next()
+        .ok_or_else(|| PluginError::Command("No editor configured".to_string()))?;
+
+    Command::new(program)
+        .args(parts)
+        .arg(path)
+        .spawn()
+        .map_err(|e| PluginError::Command(format!("Failed to run {}: {}", program, e)))?;
+    Ok(())
+}
+
+// =============================================================================
+// Files
+// =============================================================================
+
+/// One of Lux's config files or folders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFile {
+    InitLua,
+    ConfigToml,
+    Plugins,
+    Data,
+}
+
+impl ConfigFile {
+    /// Every config file, in display order.
+    pub const ALL: [ConfigFile; 4] = [
+        ConfigFile::InitLua,
+        ConfigFile::ConfigToml,
+        ConfigFile::Plugins,
+        ConfigFile::Data,
+    ];
+
+    /// Stable ID, used in item IDs.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::InitLua => "init_lua",
+            Self::ConfigToml => "config_toml",
+            Self::Plugins => "plugins",
+            Self::Data => "data",
+        }
+    }
+
+    /// Title of the item.
+    pub fn title(self) -> String {
+        match self {
+            Self::InitLua => "init.lua".to_string(),
+            Self::ConfigToml => "config.toml".to_string(),
+            Self::Plugins => t("Plugins Folder"),
+            Self::Data => t("Data Folder"),
+        }
+    }
+
+    /// Words the item is found by, besides its title.
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Self::InitLua => &["init.lua", "config", "lux config", "settings"],
+            Self::ConfigToml => &["config.toml", "config", "lux config"],
+            Self::Plugins => &["plugins", "lux plugins"],
+            Self::Data => &["data", "lux data"],
+        }
+    }
+
+    /// Where this file or folder is.
+    pub fn path(self, paths: &ConfigPaths) -> PathBuf {
+        match self {
+            Self::InitLua => paths.config_dir.join("init.lua"),
+            Self::ConfigToml => paths.config_dir.join("config.toml"),
+            Self::Plugins => paths.config_dir.join("plugins"),
+            Self::Data => paths.data_dir.clone(),
+        }
+    }
+
+    fn matches(self, query: &str) -> bool {
+        let title = self.title().to_lowercase();
+        std::iter::once(title.as_str())
+            .chain(self.keywords().iter().copied())
+            .any(|text| text.starts_with(query))
+    }
+}
+
+/// The folders Lux keeps its config and data in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigPaths {
+    config_dir: PathBuf,
+    data_dir: PathBuf,
+}
+
+impl ConfigPaths {
+    /// Paths under the given config and data folders.
+    pub fn new(config_dir: impl Into<PathBuf>, data_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config_dir: config_dir.into(),
+            data_dir: data_dir.into(),
+        }
+    }
+
+    /// The folders of this user: the one holding init.lua, and `lux` in the
+    /// data directory.
+    ///
+    /// None if there is no config or data directory.
+    pub fn current() -> Option<Self> {
+        let config_dir = lux_core::find_init_lua()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .or_else(lux_core::config_dir)?;
+        let data_dir = dirs::data_dir()?.join("lux");
+        Some(Self::new(config_dir, data_dir))
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item for a config file.
+pub fn config_file_item(file: ConfigFile, paths: &ConfigPaths) -> Item {
+    let path = file.path(paths);
+    let path_str = path.to_string_lossy().to_string();
+
+    Item::new(format!("config_file:{}", file.id()), file.title())
+        .with_subtitle(abbreviate_home(&path))
+        .with_icon(if path.is_dir() { "📁" } else { "📝" })
+        .with_type(CONFIG_FILE_ITEM_TYPE)
+        .with_data(serde_json::json!({ "path": path_str }))
+        .with_drag(DragPayload::File { path: path_str })
+}
+
+/// Build the root-view "Config Files" group for a query.
+///
+/// Queries of at least three characters match titles and keywords by prefix,
+/// so `lux` lists every file that exists.
+pub fn config_files_group(query: &str, paths: &ConfigPaths) -> Option<Group> {
+    let query = query.trim().to_lowercase();
+    if query.chars().count() < MIN_QUERY_LEN {
+        return None;
+    }
+
+    let items: Vec<Item> = ConfigFile::ALL
+        .into_iter()
+        .filter(|file| file.matches(&query) && file.path(paths).exists())
+        .map(|file| config_file_item(file, paths))
+        .collect();
+    if items.is_empty() {
+        None
+    } else {
+        Some(Group::new(t("Config Files"), items))
+    }
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a config file item: `(id, title)`.
+///
+/// `editor_command` is the resolved editor (`Editor::current_command()`);
+/// without one, files open in their default app.
+pub fn config_file_actions(editor_command: Option<&str>) -> Vec<(&'static str, String)> {
+    let program = editor_command.and_then(|command| command.split_whitespace().next());
+    let mut actions = match program {
+        Some(program) => vec![
+            ("open_editor", t_args("Open in {app}", &[("app", program)])),
+            ("open_default", t("Open with Default App")),
+        ],
+        None => vec![("open_default", t("Open"))],
+    };
+    actions.push(("reveal", t("Reveal in Finder")));
+    actions.push(("copy_path", t("Copy Path")));
+    actions
+}
+
+/// Run a config file action on the item it was offered for.
+pub fn run_config_file_action(action_id: &str, item: &Item, editor: &Editor) -> PluginResult<()> {
+    let path = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("path")?.as_str())
+        .ok_or_else(|| PluginError::Command("Item is not a config file".to_string()))?;
+
+    match action_id {
+        "open_editor" => editor.open(Path::new(path)),
+        "open_default" => desktop::current().open(path),
+        "reveal" => desktop::current().reveal(path),
+        "copy_path" => desktop::current().clipboard_write(path),
+        _ => Err(PluginError::Command(format!(
+            "Unknown config file action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command() {
+        assert_eq!(Editor::from_name("env"), Editor::Env);
+        assert_eq!(Editor::from_name(" Default "), Editor::DefaultApp);
+        assert_eq!(
+            Editor::from_name("subl -n"),
+            Editor::Command("subl -n".to_string())
+        );
+        assert_eq!(Editor::from_name("zed").name(), "zed");
+
+        assert_eq!(Editor::Env.command(Some("hx")), Some("hx".to_string()));
+        assert_eq!(Editor::Env.command(Some("  ")), None);
+        assert_eq!(Editor::Env.command(None), None);
+        assert_eq!(Editor::DefaultApp.command(Some("hx")), None);
+        assert_eq!(
+            Editor::Command("zed".to_string()).command(Some("hx")),
+            Some("zed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_editor_from_config_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let settings = EditorSettings::new().with_path(Some(path.clone()));
+        assert_eq!(settings.editor(), Editor::Env);
+
+        std::fs::write(&path, "editor = \"subl -n\"\n[other]\nkey = 1\n").unwrap();
+        assert_eq!(settings.editor(), Editor::Command("subl -n".to_string()));
+
+        std::fs::write(&path, "editor = [").unwrap();
+        assert_eq!(settings.editor(), Editor::Env);
+        assert_eq!(EditorSettings::new().editor(), Editor::Env);
+    }
+
+    #[test]
+    fn test_config_files_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("config");
+        let data = dir.path().join("data");
+        std::fs::create_dir_all(config.join("plugins")).unwrap();
+        std::fs::create_dir_all(&data).unwrap();
+        std::fs::write(config.join("init.lua"), "-- config").unwrap();
+        let paths = ConfigPaths::new(&config, &data);
+
+        let ids = |query: &str| -> Vec<String> {
+            config_files_group(query, &paths)
+                .map(|group| group.items.into_iter().map(|item| item.id).collect())
+                .unwrap_or_default()
+        };
+
+        // config.toml doesn't exist, so it isn't listed
+        assert_eq!(
+            ids("lux"),
+            [
+                "config_file:init_lua",
+                "config_file:plugins",
+                "config_file:data"
+            ]
+        );
+        assert_eq!(ids("config"), ["config_file:init_lua"]);
+        assert_eq!(ids("INIT.L"), ["config_file:init_lua"]);
+        assert_eq!(ids("plug"), ["config_file:plugins"]);
+        assert!(ids("in").is_empty());
+        assert!(ids("weather").is_empty());
+
+        std::fs::write(config.join("config.toml"), "").unwrap();
+        assert_eq!(
+            ids("lux config"),
+            ["config_file:init_lua", "config_file:config_toml"]
+        );
+
+        let item = config_file_item(ConfigFile::Plugins, &paths);
+        assert_eq!(item.types, vec![CONFIG_FILE_ITEM_TYPE.to_string()]);
+        assert_eq!(
+            item.data.unwrap()["path"],
+            config.join("plugins").to_string_lossy().as_ref()
+        );
+    }
+
+    #[test]
+    fn test_config_file_actions() {
+        let ids = |editor: Option<&str>| -> Vec<&str> {
+            config_file_actions(editor)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+        assert_eq!(
+            ids(Some("subl -n")),
+            ["open_editor", "open_default", "reveal", "copy_path"]
+        );
+        assert_eq!(ids(None), ["open_default", "reveal", "copy_path"]);
+        assert_eq!(config_file_actions(Some("subl -n"))[0].1, "Open in subl");
+
+        let item = Item::new("x", "x");
+        assert!(run_config_file_action("reveal", &item, &Editor::Env).is_err());
+    }
+}
@@ -0,0 +1,463 @@
+//! Keybinding and hotkey conflicts (the Keymap Conflicts view).
+//!
+//! This module provides:
+//! - `BindingSource` - Who set a binding: the defaults, init.lua, or a plugin
+//! - `KeymapConflict` - A binding that replaced another one, or a hotkey the OS uses
+//! - `KeymapConflicts` - The conflicts found at startup, and how they were resolved
+//! - `system_hotkey()` - What the OS does with a hotkey, if it's reserved
+//! - `conflict_groups()` / `conflict_actions()` / `run_conflict_action()` - The view
+//!
+//! A conflict is recorded when a binding replaces one of the same key,
+//! context, and view set by someone else (init.lua replacing a default, or
+//! one plugin replacing another's), and when a global hotkey is one the OS
+//! already uses. Replacing a binding after `lux.keymap.del()` is deliberate
+//! and isn't a conflict.
+//!
+//! Resolutions are saved, and apply from the next start: "keep new" stops
+//! reporting the conflict, "keep previous" skips the replacing binding.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, Item};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PluginError, PluginResult};
+use crate::storage;
+
+/// ID of the built-in Keymap Conflicts view.
+pub const KEYMAP_CONFLICTS_VIEW_ID: &str = "builtin.keymap_conflicts";
+
+/// Shortcut of the "keep previous" action.
+pub const KEEP_PREVIOUS_SHORTCUT: &str = "cmd+backspace";
+
+// =============================================================================
+// Binding Source
+// =============================================================================
+
+/// Who set a binding or hotkey.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BindingSource {
+    /// Built into Lux.
+    Default,
+    /// The user's init.lua.
+    #[default]
+    Config,
+    /// A plugin loaded with `lux.plugins.load()`.
+    Plugin(String),
+}
+
+impl BindingSource {
+    /// Name shown in the launcher.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Default => t("Default"),
+            Self::Config => "init.lua".to_string(),
+            Self::Plugin(name) => t_args("Plugin: {name}", &[("name", name)]),
+        }
+    }
+}
+
+impl fmt::Display for BindingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Config => write!(f, "config"),
+            Self::Plugin(name) => write!(f, "plugin:{}", name),
+        }
+    }
+}
+
+// =============================================================================
+// Conflicts
+// =============================================================================
+
+/// What a binding conflicts with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// It replaced a binding from another source.
+    Shadowed {
+        /// Who set the replaced binding.
+        source: BindingSource,
+        /// Its action, or `None` for a Lua function.
+        action: Option<String>,
+    },
+    /// The OS already uses the hotkey (the description says for what).
+    System(&'static str),
+}
+
+/// A binding or global hotkey that conflicts with another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapConflict {
+    /// Keystroke (e.g. "cmd+space").
+    pub key: String,
+    /// Context of a GPUI binding, `None` for the default one.
+    pub context: Option<String>,
+    /// View of a view-specific binding.
+    pub view: Option<String>,
+    /// Whether it's a global hotkey rather than a launcher binding.
+    pub global: bool,
+    /// Who set the conflicting binding.
+    pub source: BindingSource,
+    /// Its action, or `None` for a Lua function.
+    pub action: Option<String>,
+    /// What it conflicts with.
+    pub kind: ConflictKind,
+}
+
+impl KeymapConflict {
+    /// Stable ID, used to save its resolution.
+    pub fn id(&self) -> String {
+        format!(
+            "{}:{}|{}|{}|{}",
+            if self.global { "global" } else { "binding" },
+            self.key,
+            self.context.as_deref().unwrap_or_default(),
+            self.view.as_deref().unwrap_or_default(),
+            self.source
+        )
+    }
+
+    /// One-line description, e.g. "Replaces the default binding (cursor_down)".
+    pub fn description(&self) -> String {
+        match &self.kind {
+            ConflictKind::Shadowed { source, action } => t_args(
+                "Replaces {source} ({action})",
+                &[
+                    ("source", &source.label()),
+                    ("action", &action_label(action.as_deref())),
+                ],
+            ),
+            ConflictKind::System(use_) => t_args("Used by the system: {use}", &[("use", use_)]),
+        }
+    }
+}
+
+/// How the user resolved a conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// Keep the new binding and stop reporting the conflict.
+    KeepNew,
+    /// Skip the new binding, keeping the previous one (or leaving the
+    /// hotkey to the OS).
+    KeepPrevious,
+}
+
+/// Conflicts found while bindings were set, and the saved resolutions.
+pub struct KeymapConflicts {
+    path: Option<PathBuf>,
+    conflicts: Mutex<Vec<KeymapConflict>>,
+    resolutions: Mutex<BTreeMap<String, Resolution>>,
+}
+
+impl KeymapConflicts {
+    /// Load resolutions from `path` (None keeps them in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let resolutions = path.as_deref().map(load_resolutions).unwrap_or_default();
+        Self {
+            path,
+            conflicts: Mutex::new(Vec::new()),
+            resolutions: Mutex::new(resolutions),
+        }
+    }
+
+    /// Where resolutions are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("keymap_conflicts.json"))
+    }
+
+    /// Saved resolution of the conflict with `id`.
+    pub fn resolution(&self, id: &str) -> Option<Resolution> {
+        self.resolutions.lock().get(id).copied()
+    }
+
+    /// Record `conflict` unless it was resolved.
+    ///
+    /// Returns whether the new binding should be set, which is `false` only
+    /// when the conflict was resolved by keeping the previous one.
+    pub fn check(&self, conflict: KeymapConflict) -> bool {
+        match self.resolution(&conflict.id()) {
+            Some(Resolution::KeepPrevious) => false,
+            Some(Resolution::KeepNew) => true,
+            None => {
+                self.record(conflict);
+                true
+            }
+        }
+    }
+
+    /// Record a conflict, replacing an earlier one with the same ID.
+    pub fn record(&self, conflict: KeymapConflict) {
+        tracing::warn!(
+            "Keymap conflict: {} {}",
+            conflict.key,
+            conflict.description()
+        );
+        let mut conflicts = self.conflicts.lock();
+        conflicts.retain(|other| other.id() != conflict.id());
+        conflicts.push(conflict);
+    }
+
+    /// Unresolved conflict with `id`.
+    pub fn get(&self, id: &str) -> Option<KeymapConflict> {
+        self.conflicts
+            .lock()
+            .iter()
+            .find(|conflict| conflict.id() == id)
+            .cloned()
+    }
+
+    /// Unresolved conflicts, in the order they were found.
+    pub fn list(&self) -> Vec<KeymapConflict> {
+        self.conflicts.lock().clone()
+    }
+
+    /// Number of unresolved conflicts.
+    pub fn len(&self) -> usize {
+        self.conflicts.lock().len()
+    }
+
+    /// Whether there are no unresolved conflicts.
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.lock().is_empty()
+    }
+
+    /// Resolve the conflict with `id` and save the resolution.
+    pub fn resolve(&self, id: &str, resolution: Resolution) -> PluginResult<()> {
+        self.conflicts.lock().retain(|conflict| conflict.id() != id);
+        let mut resolutions = self.resolutions.lock();
+        resolutions.insert(id.to_string(), resolution);
+        if let Some(path) = &self.path {
+            save_resolutions(path, &resolutions).map_err(|e| {
+                PluginError::Command(format!("Failed to save keymap resolutions: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for KeymapConflicts {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+fn load_resolutions(path: &Path) -> BTreeMap<String, Resolution> {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_resolutions(
+    path: &Path,
+    resolutions: &BTreeMap<String, Resolution>,
+) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(resolutions)?)
+}
+
+/// Action name shown for a binding (Lua functions have none).
+fn action_label(action: Option<&str>) -> String {
+    action.map_or_else(|| t("Lua function"), str::to_string)
+}
+
+// =============================================================================
+// System Hotkeys
+// =============================================================================
+
+/// Hotkeys macOS uses out of the box, and what for.
+const MACOS_HOTKEYS: &[(&str, &str)] = &[
+    ("cmd+space", "Spotlight"),
+    ("alt+cmd+space", "Finder search"),
+    ("ctrl+space", "Select the previous input source"),
+    ("ctrl+alt+space", "Select the next input source"),
+    ("ctrl+cmd+space", "Emoji & Symbols"),
+    ("cmd+tab", "App switcher"),
+    ("cmd+`", "Move focus to the next window"),
+    ("shift+cmd+3", "Screenshot"),
+    ("shift+cmd+4", "Screenshot of a selection"),
+    ("shift+cmd+5", "Screenshot and recording options"),
+    ("alt+cmd+escape", "Force Quit"),
+    ("ctrl+cmd+q", "Lock Screen"),
+    ("shift+cmd+q", "Log Out"),
+    ("alt+cmd+d", "Show or hide the Dock"),
+    ("ctrl+up", "Mission Control"),
+    ("ctrl+down", "Application windows"),
+];
+
+/// Hotkeys common Linux desktops use out of the box, and what for.
+const LINUX_HOTKEYS: &[(&str, &str)] = &[
+    ("cmd+space", "Switch input source"),
+    ("alt+tab", "App switcher"),
+    ("alt+f2", "Run a command"),
+    ("alt+f4", "Close window"),
+    ("cmd+l", "Lock Screen"),
+    ("cmd+a", "Show applications"),
+    ("ctrl+alt+t", "Terminal"),
+    ("ctrl+alt+delete", "Log Out"),
+];
+
+/// What the OS does with `key`, if it uses it.
+pub fn system_hotkey(key: &str) -> Option<&'static str> {
+    let key = crate::keymap::normalize_shortcut(key)?;
+    let hotkeys = if cfg!(target_os = "linux") {
+        LINUX_HOTKEYS
+    } else {
+        MACOS_HOTKEYS
+    };
+    hotkeys
+        .iter()
+        .find(|(hotkey, _)| crate::keymap::normalize_shortcut(hotkey).as_deref() == Some(&key))
+        .map(|(_, use_)| *use_)
+}
+
+// =============================================================================
+// Keymap Conflicts View
+// =============================================================================
+
+/// Rows of the Keymap Conflicts view: global hotkeys, then launcher bindings.
+pub fn conflict_groups(conflicts: &KeymapConflicts, query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let (mut hotkeys, mut bindings) = (Vec::new(), Vec::new());
+    for conflict in conflicts.list() {
+        let description = conflict.description();
+        let matches = [&conflict.key, &description, &conflict.source.label()]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query));
+        if !matches {
+            continue;
+        }
+
+        let subtitle = match &conflict.view {
+            Some(view) => format!("{} · {}", description, view),
+            None => description,
+        };
+        let item = Item::new(conflict.id(), conflict.key.clone())
+            .with_subtitle(subtitle)
+            .with_accessory(conflict.source.label());
+        if conflict.global {
+            hotkeys.push(item);
+        } else {
+            bindings.push(item);
+        }
+    }
+
+    let mut groups = Vec::new();
+    if !hotkeys.is_empty() {
+        groups.push(Group::new(t("Global Hotkeys"), hotkeys));
+    }
+    if !bindings.is_empty() {
+        groups.push(Group::new(t("Keybindings"), bindings));
+    }
+    groups
+}
+
+/// Resolutions offered for a conflict row: `(id, title, shortcut)`.
+///
+/// "Keep New Binding" runs on Enter and the other one has a shortcut, so
+/// either is a single keypress.
+pub fn conflict_actions(
+    item: &Item,
+    conflicts: &KeymapConflicts,
+) -> Vec<(&'static str, String, Option<&'static str>)> {
+    let system = conflicts
+        .get(&item.id)
+        .is_some_and(|conflict| matches!(conflict.kind, ConflictKind::System(_)));
+    let keep_previous = if system {
+        t("Leave to the System")
+    } else {
+        t("Keep Previous Binding")
+    };
+    vec![
+        ("keep_new", t("Keep New Binding"), None),
+        ("keep_previous", keep_previous, Some(KEEP_PREVIOUS_SHORTCUT)),
+    ]
+}
+
+/// Run a resolution from the Keymap Conflicts view.
+pub fn run_conflict_action(
+    action_id: &str,
+    item: &Item,
+    conflicts: &KeymapConflicts,
+) -> PluginResult<()> {
+    let resolution = match action_id {
+        "keep_new" => Resolution::KeepNew,
+        "keep_previous" => Resolution::KeepPrevious,
+        other => {
+            return Err(PluginError::Command(format!(
+                "Unknown conflict action '{}'",
+                other
+            )))
+        }
+    };
+    conflicts.resolve(&item.id, resolution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shadowed(key: &str) -> KeymapConflict {
+        KeymapConflict {
+            key: key.to_string(),
+            context: None,
+            view: None,
+            global: false,
+            source: BindingSource::Config,
+            action: Some("page_down".to_string()),
+            kind: ConflictKind::Shadowed {
+                source: BindingSource::Default,
+                action: Some("cursor_down".to_string()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_resolutions_are_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keymap_conflicts.json");
+        let conflicts = KeymapConflicts::new(Some(path.clone()));
+        conflicts.record(shadowed("ctrl+n"));
+        conflicts.record(shadowed("ctrl+n"));
+        conflicts.record(shadowed("ctrl+p"));
+        assert_eq!(conflicts.len(), 2);
+
+        let groups = conflict_groups(&conflicts, "cursor_down");
+        assert_eq!(groups[0].items.len(), 2);
+        assert_eq!(
+            groups[0].items[0].subtitle.as_deref(),
+            Some("Replaces Default (cursor_down)")
+        );
+
+        let item = &groups[0].items[0];
+        let actions = conflict_actions(item, &conflicts);
+        assert_eq!(actions[0].0, "keep_new");
+        assert_eq!(actions[1].2, Some(KEEP_PREVIOUS_SHORTCUT));
+        run_conflict_action("keep_previous", item, &conflicts).unwrap();
+        assert_eq!(conflicts.len(), 1);
+
+        let reloaded = KeymapConflicts::new(Some(path));
+        assert!(reloaded.is_empty());
+        assert_eq!(
+            reloaded.resolution(&shadowed("ctrl+n").id()),
+            Some(Resolution::KeepPrevious)
+        );
+        assert_eq!(reloaded.resolution(&shadowed("ctrl+p").id()), None);
+    }
+
+    #[test]
+    fn test_system_hotkey() {
+        if cfg!(target_os = "linux") {
+            assert_eq!(system_hotkey("super+l"), Some("Lock Screen"));
+        } else {
+            assert_eq!(system_hotkey("Cmd+Space"), Some("Spotlight"));
+            assert_eq!(system_hotkey("cmd+shift+3"), Some("Screenshot"));
+        }
+        assert_eq!(system_hotkey("cmd+shift+space"), None);
+        assert_eq!(system_hotkey("space"), None);
+    }
+}
@@ -11,12 +11,17 @@
 //! |------|--------------|---------|
 //! | `trigger.match` | Table | query (field only) |
 //! | `trigger.run` | TriggerContext | set_groups, push_view, replace_view, dismiss |
-//! | `source.search` | SourceContext | set_groups |
+//! | `source.search` | SourceContext | query, original_query, parsed, set_groups, set_view_data, update_view_data |
 //! | `action.applies` | Table | item (field only) |
-//! | `action.run` | ActionContext | push_view, replace_view, pop, dismiss, progress, complete, fail |
-//! | `view.on_select` | SelectContext | select, deselect, clear_selection, is_selected, get_selection |
-//! | `view.on_submit` | SubmitContext | push_view, replace_view, pop, dismiss |
+//! | `action.run` | ActionContext | push_view, replace_view, pop, dismiss, progress, complete, fail, edit, play_macro, run_command, set_view_data, update_view_data |
+//! | `view.on_select` | SelectContext | select, deselect, clear_selection, is_selected, get_selection, set_view_data, update_view_data |
+//! | `view.on_selection_changed` | SelectionChangedContext | select, deselect, clear_selection, is_selected, get_selection, set_view_data, update_view_data |
+//! | `view.on_submit` | SubmitContext | push_view, replace_view, pop, dismiss, set_view_data, update_view_data |
+//! | `view.on_open/on_show/on_close` | LifecycleContext | set_view_data, update_view_data |
 //! | new API | UnifiedContext | all methods, runtime capability checks |
+//!
+//! View data set in a callback is stored on the view instance the callback
+//! ran in, so later searches and actions in that view see it.
 
 use std::collections::HashSet;
 
@@ -25,7 +30,8 @@ use mlua::{Lua, Result as LuaResult, Table};
 
 use crate::effect::{Effect, EffectCollector, ViewSpec};
 use crate::lua::json_to_lua_value;
-use lux_core::{Group, Item};
+use crate::operators::{parse_query, ParsedQuery};
+use lux_core::{FollowUpAction, Group, HandlerError, InlineEdit, Item};
 
 // =============================================================================
 // Table-Based Context Builders (for simple hooks)
@@ -141,10 +147,12 @@ impl<'a> TriggerContext<'a> {
 
 /// Context for source.search callbacks.
 ///
-/// Can: set_groups
+/// Can: set_groups, set_view_data
 /// Cannot: push_view, pop, dismiss (sources just return items)
 pub struct SourceContext<'a> {
     query: &'a str,
+    original_query: &'a str,
+    view_id: Option<&'a str>,
     view_data: &'a serde_json::Value,
     effects: &'a EffectCollector,
 }
@@ -158,16 +166,45 @@ impl<'a> SourceContext<'a> {
     ) -> Self {
         Self {
             query,
+            original_query: query,
+            view_id: None,
             view_data,
             effects,
         }
     }
 
+    /// Set the query as typed, when the query was rewritten.
+    pub fn with_original_query(mut self, original_query: &'a str) -> Self {
+        self.original_query = original_query;
+        self
+    }
+
+    /// Set the ID of the view searching (named in errors).
+    pub fn with_view_id(mut self, view_id: &'a str) -> Self {
+        self.view_id = Some(view_id);
+        self
+    }
+
     /// Get the query string.
     pub fn query(&self) -> &str {
         self.query
     }
 
+    /// Get the query as typed, before rewriting.
+    pub fn original_query(&self) -> &str {
+        self.original_query
+    }
+
+    /// Get the ID of the view searching, if known.
+    pub fn view_id(&self) -> Option<&str> {
+        self.view_id
+    }
+
+    /// Get the query split into terms, phrases, and `key:value` filters.
+    pub fn parsed_query(&self) -> ParsedQuery {
+        parse_query(self.query)
+    }
+
     /// Get the view data.
     pub fn view_data(&self) -> &serde_json::Value {
         self.view_data
@@ -178,12 +215,35 @@ impl<'a> SourceContext<'a> {
         self.effects.push(Effect::SetGroups(groups));
     }
 
+    /// Get the view data, including changes made earlier in this callback.
+    pub fn current_view_data(&self) -> serde_json::Value {
+        self.effects
+            .latest_view_data()
+            .unwrap_or_else(|| self.view_data.clone())
+    }
+
+    /// Replace the view data.
+    pub fn set_view_data(&self, data: serde_json::Value) {
+        self.effects.push(Effect::SetViewData(data));
+    }
+
+    /// Replace the view data with `f(current view data)`.
+    pub fn update_view_data<E>(
+        &self,
+        f: impl FnOnce(serde_json::Value) -> Result<serde_json::Value, E>,
+    ) -> Result<(), E> {
+        let data = f(self.current_view_data())?;
+        self.set_view_data(data);
+        Ok(())
+    }
+
     // Note: No push_view, pop, dismiss - sources just return items
 }
 
 /// Context for action.run callbacks.
 ///
-/// Can: push_view, replace_view, pop, dismiss, progress, complete, fail
+/// Can: push_view, replace_view, pop, dismiss, progress, complete, fail, edit, play_macro,
+/// run_command, set_view_data
 /// Cannot: set_groups (actions operate on items, don't produce them)
 pub struct ActionContext<'a> {
     items: &'a [Item],
@@ -247,18 +307,39 @@ impl<'a> ActionContext<'a> {
 
     /// Mark the action as complete.
     pub fn complete(&self, message: impl Into<String>) {
+        self.complete_with_actions(message, Vec::new());
+    }
+
+    /// Mark the action as complete, offering follow-up actions.
+    pub fn complete_with_actions(&self, message: impl Into<String>, actions: Vec<FollowUpAction>) {
         self.effects.push(Effect::Complete {
             message: message.into(),
+            actions,
         });
     }
 
     /// Mark the action as failed.
-    pub fn fail(&self, error: impl Into<String>) {
+    pub fn fail(&self, error: impl Into<HandlerError>) {
         self.effects.push(Effect::Fail {
             error: error.into(),
         });
     }
 
+    /// Put the item's row into inline edit mode.
+    pub fn edit(&self, edit: InlineEdit) {
+        self.effects.push(Effect::Edit(edit));
+    }
+
+    /// Play a saved macro once the action returns.
+    pub fn play_macro(&self, name: impl Into<String>) {
+        self.effects.push(Effect::PlayMacro(name.into()));
+    }
+
+    /// Run a launcher action by name once the action returns.
+    pub fn run_command(&self, action: impl Into<String>) {
+        self.effects.push(Effect::RunCommand(action.into()));
+    }
+
     /// Set grouped results.
     ///
     /// Note: This is primarily for keybinding handlers that need to update
@@ -266,11 +347,33 @@ impl<'a> ActionContext<'a> {
     pub fn set_groups(&self, groups: Vec<Group>) {
         self.effects.push(Effect::SetGroups(groups));
     }
+
+    /// Get the view data, including changes made earlier in this callback.
+    pub fn current_view_data(&self) -> serde_json::Value {
+        self.effects
+            .latest_view_data()
+            .unwrap_or_else(|| self.view_data.clone())
+    }
+
+    /// Replace the view data.
+    pub fn set_view_data(&self, data: serde_json::Value) {
+        self.effects.push(Effect::SetViewData(data));
+    }
+
+    /// Replace the view data with `f(current view data)`.
+    pub fn update_view_data<E>(
+        &self,
+        f: impl FnOnce(serde_json::Value) -> Result<serde_json::Value, E>,
+    ) -> Result<(), E> {
+        let data = f(self.current_view_data())?;
+        self.set_view_data(data);
+        Ok(())
+    }
 }
 
 /// Context for view.on_select callbacks.
 ///
-/// Can: select, deselect, clear_selection
+/// Can: select, deselect, clear_selection, set_view_data
 /// Read-only: item, view_data, is_selected, get_selection
 pub struct SelectContext<'a> {
     item: &'a Item,
@@ -329,11 +432,126 @@ impl<'a> SelectContext<'a> {
     pub fn clear_selection(&self) {
         self.effects.push(Effect::ClearSelection);
     }
+
+    /// Get the view data, including changes made earlier in this callback.
+    pub fn current_view_data(&self) -> serde_json::Value {
+        self.effects
+            .latest_view_data()
+            .unwrap_or_else(|| self.view_data.clone())
+    }
+
+    /// Replace the view data.
+    pub fn set_view_data(&self, data: serde_json::Value) {
+        self.effects.push(Effect::SetViewData(data));
+    }
+
+    /// Replace the view data with `f(current view data)`.
+    pub fn update_view_data<E>(
+        &self,
+        f: impl FnOnce(serde_json::Value) -> Result<serde_json::Value, E>,
+    ) -> Result<(), E> {
+        let data = f(self.current_view_data())?;
+        self.set_view_data(data);
+        Ok(())
+    }
+}
+
+/// Context for view.on_selection_changed callbacks (multi-select views).
+///
+/// Can: select, deselect, clear_selection, set_view_data
+/// Read-only: added, removed, view_data, is_selected, get_selection
+pub struct SelectionChangedContext<'a> {
+    view_data: &'a serde_json::Value,
+    selection: &'a [String],
+    added: &'a [String],
+    removed: &'a [String],
+    effects: &'a EffectCollector,
+}
+
+impl<'a> SelectionChangedContext<'a> {
+    /// Create a new selection changed context.
+    pub fn new(
+        view_data: &'a serde_json::Value,
+        selection: &'a [String],
+        added: &'a [String],
+        removed: &'a [String],
+        effects: &'a EffectCollector,
+    ) -> Self {
+        Self {
+            view_data,
+            selection,
+            added,
+            removed,
+            effects,
+        }
+    }
+
+    /// IDs of the items that were just selected.
+    pub fn added(&self) -> &[String] {
+        self.added
+    }
+
+    /// IDs of the items that were just deselected.
+    pub fn removed(&self) -> &[String] {
+        self.removed
+    }
+
+    /// Get the view data.
+    pub fn view_data(&self) -> &serde_json::Value {
+        self.view_data
+    }
+
+    /// Check if an item is selected, including changes made in this callback.
+    pub fn is_selected(&self, id: &str) -> bool {
+        self.get_selection().iter().any(|selected| selected == id)
+    }
+
+    /// Get the selection, including changes made in this callback.
+    pub fn get_selection(&self) -> Vec<String> {
+        self.effects.apply_selection(self.selection)
+    }
+
+    /// Select an item by ID.
+    pub fn select(&self, id: impl Into<String>) {
+        self.effects.push(Effect::Select(vec![id.into()]));
+    }
+
+    /// Deselect an item by ID.
+    pub fn deselect(&self, id: impl Into<String>) {
+        self.effects.push(Effect::Deselect(vec![id.into()]));
+    }
+
+    /// Clear all selection.
+    pub fn clear_selection(&self) {
+        self.effects.push(Effect::ClearSelection);
+    }
+
+    /// Get the view data, including changes made earlier in this callback.
+    pub fn current_view_data(&self) -> serde_json::Value {
+        self.effects
+            .latest_view_data()
+            .unwrap_or_else(|| self.view_data.clone())
+    }
+
+    /// Replace the view data.
+    pub fn set_view_data(&self, data: serde_json::Value) {
+        self.effects.push(Effect::SetViewData(data));
+    }
+
+    /// Replace the view data with `f(current view data)`.
+    pub fn update_view_data<E>(
+        &self,
+        f: impl FnOnce(serde_json::Value) -> Result<serde_json::Value, E>,
+    ) -> Result<(), E> {
+        let data = f(self.current_view_data())?;
+        self.set_view_data(data);
+        Ok(())
+    }
 }
 
 /// Context for view.on_submit callbacks.
 ///
-/// Can: push_view, replace_view, pop, dismiss
+/// Can: push_view, replace_view, pop, dismiss, set_view_data
 pub struct SubmitContext<'a> {
     query: &'a str,
     view_data: &'a serde_json::Value,
@@ -383,6 +601,71 @@ impl<'a> SubmitContext<'a> {
     pub fn dismiss(&self) {
         self.effects.push(Effect::Dismiss);
     }
+
+    /// Get the view data, including changes made earlier in this callback.
+    pub fn current_view_data(&self) -> serde_json::Value {
+        self.effects
+            .latest_view_data()
+            .unwrap_or_else(|| self.view_data.clone())
+    }
+
+    /// Replace the view data.
+    pub fn set_view_data(&self, data: serde_json::Value) {
+        self.effects.push(Effect::SetViewData(data));
+    }
+
+    /// Replace the view data with `f(current view data)`.
+    pub fn update_view_data<E>(
+        &self,
+        f: impl FnOnce(serde_json::Value) -> Result<serde_json::Value, E>,
+    ) -> Result<(), E> {
+        let data = f(self.current_view_data())?;
+        self.set_view_data(data);
+        Ok(())
+    }
+}
+
+/// Context for view lifecycle callbacks (on_open, on_show, on_close).
+///
+/// Can: set_view_data (no effect in on_close, the view is already gone)
+/// Read-only: view_data
+pub struct LifecycleContext<'a> {
+    view_data: &'a serde_json::Value,
+    effects: &'a EffectCollector,
+}
+
+impl<'a> LifecycleContext<'a> {
+    /// Create a new lifecycle context.
+    pub fn new(view_data: &'a serde_json::Value, effects: &'a EffectCollector) -> Self {
+        Self { view_data, effects }
+    }
+
+    /// Get the view data (read-only).
+    pub fn view_data(&self) -> &serde_json::Value {
+        self.view_data
+    }
+
+    /// Get the view data, including changes made earlier in this callback.
+    pub fn current_view_data(&self) -> serde_json::Value {
+        self.effects
+            .latest_view_data()
+            .unwrap_or_else(|| self.view_data.clone())
+    }
+
+    /// Replace the view data.
+    pub fn set_view_data(&self, data: serde_json::Value) {
+        self.effects.push(Effect::SetViewData(data));
+    }
+
+    /// Replace the view data with `f(current view data)`.
+    pub fn update_view_data<E>(
+        &self,
+        f: impl FnOnce(serde_json::Value) -> Result<serde_json::Value, E>,
+    ) -> Result<(), E> {
+        let data = f(self.current_view_data())?;
+        self.set_view_data(data);
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -569,7 +852,7 @@ impl<'a> UnifiedContext<'a> {
     pub fn set_items(&self, items: Vec<Item>) -> Result<(), ContextError> {
         self.require_capability(ContextCapabilities::SET_ITEMS, "set_items")?;
         self.effects
-            .push(Effect::SetGroups(vec![Group { title: None, items }]));
+            .push(Effect::SetGroups(vec![Group::ungrouped(items)]));
         Ok(())
     }
 
@@ -637,6 +920,7 @@ impl<'a> UnifiedContext<'a> {
         self.require_capability(ContextCapabilities::FEEDBACK, "complete")?;
         self.effects.push(Effect::Complete {
             message: message.unwrap_or_default(),
+            actions: Vec::new(),
         });
         Ok(())
     }
@@ -644,9 +928,11 @@ impl<'a> UnifiedContext<'a> {
     /// Mark the action as failed with an error message.
     ///
     /// Requires: FEEDBACK capability
-    pub fn fail(&self, error: String) -> Result<(), ContextError> {
+    pub fn fail(&self, error: impl Into<HandlerError>) -> Result<(), ContextError> {
         self.require_capability(ContextCapabilities::FEEDBACK, "fail")?;
-        self.effects.push(Effect::Fail { error });
+        self.effects.push(Effect::Fail {
+            error: error.into(),
+        });
         Ok(())
     }
 
@@ -711,14 +997,9 @@ mod tests {
     fn test_action_applies_context() {
         let lua = Lua::new();
 
-        let item = Item {
-            id: "test-id".to_string(),
-            title: "Test Item".to_string(),
-            subtitle: Some("Subtitle".to_string()),
-            icon: None,
-            types: vec!["file".to_string()],
-            data: None,
-        };
+        let item = Item::new("test-id", "Test Item")
+            .with_subtitle("Subtitle")
+            .with_type("file");
 
         let ctx = build_action_applies_context(&lua, &item).unwrap();
 
@@ -738,6 +1019,7 @@ mod tests {
         ctx.set_groups(vec![Group {
             title: None,
             items: vec![],
+            collapsible: false,
         }]);
         ctx.dismiss();
 
@@ -779,16 +1061,49 @@ mod tests {
     }
 
     #[test]
-    fn test_select_context_collects_effects() {
+    fn test_action_context_complete_with_actions() {
         let collector = EffectCollector::new();
-        let item = Item {
-            id: "item1".to_string(),
-            title: "Test Item".to_string(),
-            subtitle: None,
+        let view_data = serde_json::Value::Null;
+        let items = vec![];
+        let ctx = ActionContext::new(&items, &view_data, &collector);
+
+        let follow_up = FollowUpAction {
+            title: "Open in Browser".to_string(),
             icon: None,
-            types: vec![],
-            data: None,
+            handler_key: Some("followup:open".to_string()),
         };
+        ctx.complete_with_actions("Uploaded", vec![follow_up]);
+
+        let effects = collector.take();
+        assert!(matches!(
+            &effects[0],
+            Effect::Complete { message, actions }
+                if message == "Uploaded" && actions[0].title == "Open in Browser"
+        ));
+    }
+
+    #[test]
+    fn test_update_view_data_sees_earlier_changes() {
+        let collector = EffectCollector::new();
+        let view_data = serde_json::json!({ "page": 1 });
+        let ctx = SourceContext::new("query", &view_data, &collector);
+
+        let next_page = |mut data: serde_json::Value| -> Result<_, ()> {
+            data["page"] = (data["page"].as_i64().unwrap_or(0) + 1).into();
+            Ok(data)
+        };
+        ctx.update_view_data(next_page).unwrap();
+        ctx.update_view_data(next_page).unwrap();
+
+        assert_eq!(ctx.view_data(), &serde_json::json!({ "page": 1 }));
+        assert_eq!(ctx.current_view_data(), serde_json::json!({ "page": 3 }));
+        assert_eq!(collector.take().len(), 2);
+    }
+
+    #[test]
+    fn test_select_context_collects_effects() {
+        let collector = EffectCollector::new();
+        let item = Item::new("item1", "Test Item");
         let view_data = serde_json::Value::Null;
         let selection = HashSet::new();
         let ctx = SelectContext::new(&item, &view_data, &selection, &collector);
@@ -804,6 +1119,22 @@ mod tests {
         assert!(matches!(effects[2], Effect::ClearSelection));
     }
 
+    #[test]
+    fn test_selection_changed_context_sees_own_changes() {
+        let collector = EffectCollector::new();
+        let view_data = serde_json::Value::Null;
+        let selection = vec!["a".to_string(), "b".to_string()];
+        let added = vec!["b".to_string()];
+        let ctx = SelectionChangedContext::new(&view_data, &selection, &added, &[], &collector);
+
+        // Enforce a limit of one item by dropping the oldest
+        assert_eq!(ctx.added(), ["b"]);
+        ctx.deselect("a");
+        assert!(!ctx.is_selected("a"));
+        assert_eq!(ctx.get_selection(), ["b"]);
+        assert_eq!(collector.take().len(), 1);
+    }
+
     #[test]
     fn test_submit_context_navigation() {
         let collector = EffectCollector::new();
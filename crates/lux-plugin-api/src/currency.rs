@@ -0,0 +1,594 @@
+//! Currency conversion with periodically refreshed exchange rates.
+//!
+//! This module provides:
+//! - `parse_conversion()` - Read queries like `25 usd in eur`, `€40 to gbp`, or `100 yen`
+//! - `ExchangeRates` - Rates against a base currency, with conversion
+//! - `CurrencyConverter` - Background-refreshed rates and the root-view answers (lux.currency)
+//! - `currency_actions()` / `run_currency_action()` - Copy a converted amount
+//!
+//! Rates are fetched on a background thread every few hours and saved to
+//! the data directory, so conversions keep answering offline with the
+//! last-known rates (marked as outdated once they're a few days old).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+
+use lux_core::i18n::{format_number, t, t_args};
+use lux_core::{Group, Item};
+
+use crate::error::{PluginError, PluginResult};
+use crate::storage;
+
+/// Type tag for converted amounts (used to route them to the built-in actions).
+pub const CURRENCY_ITEM_TYPE: &str = "currency_value";
+
+/// Lua registry key prefix of the built-in currency action handlers.
+pub const CURRENCY_ACTION_KEY_PREFIX: &str = "currency:";
+
+/// IDs of all currency actions (one handler is registered per ID).
+pub const CURRENCY_ACTION_IDS: &[&str] = &["copy_amount", "copy_with_code"];
+
+/// Free exchange rate API (no key, updated daily), based on USD.
+const RATES_URL: &str = "https://open.er-api.com/v6/latest/USD";
+
+/// Time between rate refreshes.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Time before retrying a failed refresh.
+const RETRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Age after which rates are marked as outdated.
+const STALE_AFTER: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+
+/// Currencies a bare amount (`25 usd`) is converted into.
+const DEFAULT_TARGETS: &[&str] = &["USD", "EUR", "GBP", "JPY"];
+
+/// Words between the amount and the target currency.
+const TARGET_SEPARATORS: &[&str] = &[" in ", " to ", " as ", "->", "="];
+
+/// Handler key for a currency action ID.
+pub fn currency_action_key(id: &str) -> String {
+    format!("{}{}", CURRENCY_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Queries
+// =============================================================================
+
+/// A conversion asked for in a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub amount: f64,
+    /// ISO 4217 code of the amount's currency.
+    pub from: String,
+    /// ISO 4217 code to convert into, None for the default targets.
+    pub to: Option<String>,
+}
+
+/// Parse a conversion query.
+///
+/// The amount and currency may come in either order, with or without a
+/// space (`25 usd`, `$25`, `eur 40`), optionally followed by `in`, `to`,
+/// `as`, `->`, or `=` and a target currency. Codes aren't checked against
+/// the known rates here.
+pub fn parse_conversion(query: &str) -> Option<Conversion> {
+    let query = query.trim().to_lowercase();
+    let (source, target) = TARGET_SEPARATORS
+        .iter()
+        .find_map(|sep| query.rsplit_once(sep))
+        .map_or((query.as_str(), None), |(source, target)| {
+            (source, Some(target))
+        });
+
+    let to = match target {
+        Some(target) => Some(currency_code(target.trim())?),
+        None => None,
+    };
+
+    let source = source.trim();
+    let is_numeric = |c: char| c.is_ascii_digit() || c == '.' || c == ',';
+    let number_len = source.len() - source.trim_start_matches(is_numeric).len();
+    let (number, currency) = if number_len > 0 {
+        source.split_at(number_len)
+    } else {
+        let currency_len = source.trim_end_matches(is_numeric).len();
+        let (currency, number) = source.split_at(currency_len);
+        (number, currency)
+    };
+
+    Some(Conversion {
+        amount: parse_amount(number)?,
+        from: currency_code(currency.trim())?,
+        to,
+    })
+}
+
+/// Parse an amount, with `,` as a thousands separator or, when it's the
+/// only separator and followed by one or two digits, as the decimal point.
+fn parse_amount(text: &str) -> Option<f64> {
+    if !text.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let normalized = match text.rsplit_once(',') {
+        Some((_, decimals))
+            if !text.contains('.')
+                && text.matches(',').count() == 1
+                && (1..=2).contains(&decimals.len()) =>
+        {
+            text.replace(',', ".")
+        }
+        _ => text.replace(',', ""),
+    };
+    normalized
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite())
+}
+
+/// ISO 4217 code for a code, symbol, or common currency name.
+fn currency_code(text: &str) -> Option<String> {
+    let code = match text {
+        "$" | "dollar" | "dollars" => "USD",
+        "€" | "euro" | "euros" => "EUR",
+        "£" | "pound" | "pounds" => "GBP",
+        "¥" | "yen" => "JPY",
+        "₹" | "rupee" | "rupees" => "INR",
+        "₩" | "won" => "KRW",
+        "₽" | "ruble" | "rubles" => "RUB",
+        "₺" | "lira" => "TRY",
+        "fr" | "franc" | "francs" => "CHF",
+        code if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) => {
+            return Some(code.to_uppercase());
+        }
+        _ => return None,
+    };
+    Some(code.to_string())
+}
+
+// =============================================================================
+// Rates
+// =============================================================================
+
+/// Exchange rates against a base currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRates {
+    /// Code of the currency every rate is relative to.
+    pub base: String,
+    /// Units of each currency per unit of the base.
+    pub rates: HashMap<String, f64>,
+    /// When the provider last updated the rates (Unix seconds).
+    pub updated: u64,
+}
+
+impl ExchangeRates {
+    /// Convert an amount between two currencies (None for unknown codes).
+    pub fn convert(&self, amount: f64, from: &str, to: &str) -> Option<f64> {
+        let from_rate = self.rate(from)?;
+        let to_rate = self.rate(to)?;
+        Some(amount / from_rate * to_rate)
+    }
+
+    /// Whether a currency code is known.
+    pub fn contains(&self, code: &str) -> bool {
+        self.rate(code).is_some()
+    }
+
+    /// Whether the rates are too old to be trusted as current.
+    pub fn is_stale(&self, now: SystemTime) -> bool {
+        let updated = UNIX_EPOCH + Duration::from_secs(self.updated);
+        now.duration_since(updated)
+            .is_ok_and(|age| age > STALE_AFTER)
+    }
+
+    fn rate(&self, code: &str) -> Option<f64> {
+        if code == self.base {
+            return Some(1.0);
+        }
+        self.rates.get(code).copied().filter(|rate| *rate > 0.0)
+    }
+}
+
+/// Response of the exchange rate API.
+#[derive(Deserialize)]
+struct RatesResponse {
+    result: String,
+    base_code: String,
+    time_last_update_unix: u64,
+    rates: HashMap<String, f64>,
+}
+
+/// Parse the exchange rate API's JSON response.
+pub fn parse_rates_response(json: &str) -> PluginResult<ExchangeRates> {
+    let response: RatesResponse = serde_json::from_str(json)
+        .map_err(|e| PluginError::Command(format!("Invalid exchange rates: {}", e)))?;
+    if response.result != "success" {
+        return Err(PluginError::Command(format!(
+            "Exchange rate request failed: {}",
+            response.result
+        )));
+    }
+    Ok(ExchangeRates {
+        base: response.base_code,
+        rates: response.rates,
+        updated: response.time_last_update_unix,
+    })
+}
+
+/// Fetch the current rates.
+fn fetch_rates() -> PluginResult<ExchangeRates> {
+    let output = Command::new("curl")
+        .args([
+            "--fail",
+            "--silent",
+            "--show-error",
+            "--max-time",
+            "15",
+            RATES_URL,
+        ])
+        .output()
+        .map_err(|e| PluginError::Command(format!("Command spawn failed: {}", e)))?;
+    if !output.status.success() {
+        return Err(PluginError::Command(format!(
+            "Exchange rate request failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    parse_rates_response(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn load_rates(path: &Path) -> Option<ExchangeRates> {
+    let text = storage::read(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn save_rates(path: &Path, rates: &ExchangeRates) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(rates)?)
+}
+
+// =============================================================================
+// Converter
+// =============================================================================
+
+/// Exchange rates kept up to date in the background.
+///
+/// The last-known rates are loaded from disk right away; the refresh thread
+/// starts with the first conversion query (or `refresh()`).
+pub struct CurrencyConverter {
+    path: Option<PathBuf>,
+    rates: Arc<RwLock<Option<ExchangeRates>>>,
+    targets: RwLock<Vec<String>>,
+    /// Wakes the refresh thread; None until it has been started.
+    wake: Mutex<Option<Sender<()>>>,
+}
+
+impl CurrencyConverter {
+    /// Load the last-known rates from `path` (None keeps them in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let rates = path.as_deref().and_then(load_rates);
+        Self {
+            path,
+            rates: Arc::new(RwLock::new(rates)),
+            targets: RwLock::new(DEFAULT_TARGETS.iter().map(|c| c.to_string()).collect()),
+            wake: Mutex::new(None),
+        }
+    }
+
+    /// Where the rates are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("exchange_rates.json"))
+    }
+
+    /// The current rates (None before the first successful fetch).
+    pub fn rates(&self) -> Option<ExchangeRates> {
+        self.rates.read().clone()
+    }
+
+    /// Set the currencies a bare amount is converted into.
+    pub fn set_targets(&self, targets: Vec<String>) {
+        *self.targets.write() = targets.into_iter().map(|c| c.to_uppercase()).collect();
+    }
+
+    /// The currencies a bare amount is converted into.
+    pub fn targets(&self) -> Vec<String> {
+        self.targets.read().clone()
+    }
+
+    /// Fetch the rates now (in the background).
+    pub fn refresh(&self) {
+        let mut wake = self.wake.lock();
+        if let Some(sender) = wake.as_ref() {
+            if sender.send(()).is_ok() {
+                return;
+            }
+        }
+        *wake = Some(self.spawn_refresher());
+    }
+
+    /// Build the root-view "Currency" group for a query.
+    pub fn group(&self, query: &str) -> Option<Group> {
+        let conversion = parse_conversion(query)?;
+        if self.wake.lock().is_none() {
+            self.refresh();
+        }
+        let rates = self.rates.read();
+        let items = conversion_items(
+            &conversion,
+            rates.as_ref()?,
+            &self.targets(),
+            SystemTime::now(),
+        );
+        if items.is_empty() {
+            None
+        } else {
+            Some(Group::new(t("Currency"), items))
+        }
+    }
+
+    fn spawn_refresher(&self) -> Sender<()> {
+        let (sender, receiver) = mpsc::channel::<()>();
+        let rates = self.rates.clone();
+        let path = self.path.clone();
+
+        std::thread::spawn(move || loop {
+            let wait = match fetch_rates() {
+                Ok(fetched) => {
+                    if let Some(path) = &path {
+                        if let Err(e) = save_rates(path, &fetched) {
+                            tracing::warn!("Failed to save exchange rates: {}", e);
+                        }
+                    }
+                    *rates.write() = Some(fetched);
+                    REFRESH_INTERVAL
+                }
+                Err(e) => {
+                    // Keep answering with the last-known rates
+                    tracing::warn!("Failed to fetch exchange rates: {}", e);
+                    RETRY_INTERVAL
+                }
+            };
+
+            match receiver.recv_timeout(wait) {
+                Ok(()) | Err(RecvTimeoutError::Timeout) => {
+                    // Coalesce refreshes requested while fetching
+                    while receiver.try_recv().is_ok() {}
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        });
+
+        sender
+    }
+}
+
+impl Default for CurrencyConverter {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Format an amount with the locale's separators (more digits below 1).
+fn format_amount(amount: f64) -> String {
+    let decimals = if amount != 0.0 && amount.abs() < 1.0 {
+        4
+    } else {
+        2
+    };
+    format_number(amount, decimals)
+}
+
+/// Build the items answering a conversion.
+///
+/// Unknown currencies give no items; a bare amount is converted into each
+/// of `targets` other than its own currency.
+pub fn conversion_items(
+    conversion: &Conversion,
+    rates: &ExchangeRates,
+    targets: &[String],
+    now: SystemTime,
+) -> Vec<Item> {
+    if !rates.contains(&conversion.from) {
+        return Vec::new();
+    }
+    let targets: Vec<&str> = match &conversion.to {
+        Some(to) => vec![to.as_str()],
+        None => targets
+            .iter()
+            .map(String::as_str)
+            .filter(|code| *code != conversion.from)
+            .collect(),
+    };
+    let stale = rates.is_stale(now);
+
+    targets
+        .into_iter()
+        .filter_map(|to| {
+            let value = rates.convert(conversion.amount, &conversion.from, to)?;
+            let rate = rates.convert(1.0, &conversion.from, to)?;
+            let amount = format_amount(value);
+            let mut item = Item::new(
+                format!("currency:{}:{}", conversion.from, to),
+                format!("{} {}", amount, to),
+            )
+            .with_subtitle(t_args(
+                "{amount} {from} · 1 {from} = {rate} {to}",
+                &[
+                    ("amount", &format_amount(conversion.amount)),
+                    ("from", &conversion.from),
+                    ("rate", &format_number(rate, 4)),
+                    ("to", to),
+                ],
+            ))
+            .with_type(CURRENCY_ITEM_TYPE)
+            .with_data(serde_json::json!({ "amount": amount, "code": to }));
+            if stale {
+                item = item.with_accessory(t("Outdated rates"));
+            }
+            Some(item)
+        })
+        .collect()
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a converted amount: `(id, title)`.
+pub fn currency_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("copy_amount", t("Copy Amount")),
+        ("copy_with_code", t("Copy with Currency Code")),
+    ]
+}
+
+/// Run a currency action.
+pub fn run_currency_action(action_id: &str, item: &Item) -> PluginResult<()> {
+    let field = |name: &str| {
+        item.data
+            .as_ref()
+            .and_then(|data| data.get(name)?.as_str())
+            .ok_or_else(|| PluginError::Command("Item is not a converted amount".to_string()))
+    };
+    let text = match action_id {
+        "copy_amount" => field("amount")?.to_string(),
+        "copy_with_code" => format!("{} {}", field("amount")?, field("code")?),
+        _ => {
+            return Err(PluginError::Command(format!(
+                "Unknown currency action '{}'",
+                action_id
+            )))
+        }
+    };
+    crate::desktop::current().clipboard_write(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rates(updated: u64) -> ExchangeRates {
+        ExchangeRates {
+            base: "USD".to_string(),
+            rates: HashMap::from([
+                ("USD".to_string(), 1.0),
+                ("EUR".to_string(), 0.9),
+                ("GBP".to_string(), 0.75),
+            ]),
+            updated,
+        }
+    }
+
+    fn conversion(amount: f64, from: &str, to: Option<&str>) -> Option<Conversion> {
+        Some(Conversion {
+            amount,
+            from: from.to_string(),
+            to: to.map(String::from),
+        })
+    }
+
+    #[test]
+    fn test_parse_conversion() {
+        assert_eq!(
+            parse_conversion("25 usd in eur"),
+            conversion(25.0, "USD", Some("EUR"))
+        );
+        assert_eq!(
+            parse_conversion("€40 to GBP"),
+            conversion(40.0, "EUR", Some("GBP"))
+        );
+        assert_eq!(
+            parse_conversion("1,250.5 chf=jpy"),
+            conversion(1250.5, "CHF", Some("JPY"))
+        );
+        assert_eq!(parse_conversion("12,50 €"), conversion(12.5, "EUR", None));
+        assert_eq!(parse_conversion("gbp 3"), conversion(3.0, "GBP", None));
+        assert_eq!(
+            parse_conversion("100 yen -> dollars"),
+            conversion(100.0, "JPY", Some("USD"))
+        );
+
+        assert_eq!(parse_conversion("25"), None);
+        assert_eq!(parse_conversion("usd"), None);
+        assert_eq!(parse_conversion("25 apples"), None);
+        assert_eq!(parse_conversion("25 usd in euros please"), None);
+    }
+
+    #[test]
+    fn test_conversion_items() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let targets = vec!["USD".to_string(), "EUR".to_string(), "GBP".to_string()];
+
+        let items = conversion_items(
+            &parse_conversion("30 eur in gbp").unwrap(),
+            &rates(0),
+            &targets,
+            now,
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "25.00 GBP");
+        assert_eq!(
+            items[0].subtitle.as_deref(),
+            Some("30.00 EUR · 1 EUR = 0.8333 GBP")
+        );
+        assert!(items[0].accessories.is_empty());
+
+        let titles: Vec<String> = conversion_items(
+            &parse_conversion("10 usd").unwrap(),
+            &rates(0),
+            &targets,
+            now,
+        )
+        .into_iter()
+        .map(|item| item.title)
+        .collect();
+        assert_eq!(titles, ["9.00 EUR", "7.50 GBP"]);
+
+        assert!(conversion_items(
+            &parse_conversion("1 xyz").unwrap(),
+            &rates(0),
+            &targets,
+            now
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_last_known_rates_are_loaded_and_marked_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux").join("exchange_rates.json");
+        save_rates(&path, &rates(0)).unwrap();
+
+        let converter = CurrencyConverter::new(Some(path));
+        assert_eq!(converter.rates(), Some(rates(0)));
+
+        let now = UNIX_EPOCH + STALE_AFTER + Duration::from_secs(1);
+        let items = conversion_items(
+            &parse_conversion("1 usd to eur").unwrap(),
+            &converter.rates().unwrap(),
+            &converter.targets(),
+            now,
+        );
+        assert_eq!(items[0].accessories, ["Outdated rates"]);
+    }
+
+    #[test]
+    fn test_parse_rates_response() {
+        let json = r#"{"result":"success","base_code":"USD","time_last_update_unix":1700000000,
+            "rates":{"USD":1,"EUR":0.92}}"#;
+        let rates = parse_rates_response(json).unwrap();
+        assert_eq!(rates.updated, 1_700_000_000);
+        assert_eq!(rates.convert(0.92, "EUR", "USD"), Some(1.0));
+
+        assert!(parse_rates_response(r#"{"result":"error"}"#).is_err());
+    }
+}
@@ -0,0 +1,837 @@
+//! Long-lived helper processes started by plugins.
+//!
+//! This module provides:
+//! - `DaemonSpec` / `RestartPolicy` - What to run, and whether to run it again when it exits
+//! - `DaemonState` / `DaemonInfo` - What a daemon is doing, for `lux.daemon.list()` and the view
+//! - `Daemons` - The supervisor behind `lux.daemon`: starts, restarts, and stops daemons
+//! - `daemon_groups()` / `daemon_actions()` - The Daemons view
+//!
+//! Each daemon gets a supervising thread that spawns it, copies its stdout
+//! and stderr to `daemons/<name>.log` in the data folder (keeping the last
+//! lines in memory), and waits for it to exit. A daemon that exits is started
+//! again per its restart policy, after a delay that doubles with every quick
+//! exit so a helper failing on start doesn't spin. Daemons get SIGTERM, and
+//! are killed if they don't exit in time, when stopped or when Lux quits.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, Item};
+use parking_lot::Mutex;
+use wait_timeout::ChildExt;
+
+use crate::macros::is_valid_name;
+
+/// ID of the built-in Daemons view.
+pub const DAEMONS_VIEW_ID: &str = "builtin.daemons";
+
+/// Name of the folder in the data folder holding the daemon logs.
+const DAEMONS_DIR: &str = "daemons";
+
+/// Output lines kept in memory per daemon.
+const MAX_LOG_LINES: usize = 200;
+
+/// Delay before the first restart.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between restarts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A run at least this long resets the restart delay.
+const STABLE_RUN: Duration = Duration::from_secs(30);
+
+/// How often a supervisor checks its daemon for an exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long a daemon has to exit after SIGTERM before it is killed.
+const STOP_GRACE: Duration = Duration::from_secs(3);
+
+// =============================================================================
+// Specs
+// =============================================================================
+
+/// When a daemon that exited is started again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// After every exit.
+    Always,
+    /// After an exit with a non-zero status, or a failed spawn.
+    #[default]
+    OnFailure,
+    /// Never.
+    Never,
+}
+
+impl RestartPolicy {
+    /// Parse a policy name: `"always"`, `"on_failure"`, or `"never"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "always" => Some(Self::Always),
+            "on_failure" => Some(Self::OnFailure),
+            "never" => Some(Self::Never),
+            _ => None,
+        }
+    }
+
+    /// Name of the policy, as `from_name()` takes it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::OnFailure => "on_failure",
+            Self::Never => "never",
+        }
+    }
+
+    /// Whether a run that ended this way is followed by another.
+    fn restarts(self, success: bool) -> bool {
+        match self {
+            Self::Always => true,
+            Self::OnFailure => !success,
+            Self::Never => false,
+        }
+    }
+}
+
+/// What a daemon runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaemonSpec {
+    /// Name, also the name of its log file.
+    pub name: String,
+    /// Program and arguments.
+    pub cmd: Vec<String>,
+    /// Working directory (Lux's if None).
+    pub cwd: Option<PathBuf>,
+    /// Environment variables added to Lux's.
+    pub env: Vec<(String, String)>,
+    /// When it's started again after exiting.
+    pub restart: RestartPolicy,
+}
+
+impl DaemonSpec {
+    /// Run a program with arguments.
+    pub fn new(name: impl Into<String>, cmd: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            cmd,
+            cwd: None,
+            env: Vec::new(),
+            restart: RestartPolicy::default(),
+        }
+    }
+
+    /// Run a command line with `sh -c`.
+    pub fn shell(name: impl Into<String>, command: &str) -> Self {
+        Self::new(
+            name,
+            vec!["sh".to_string(), "-c".to_string(), command.to_string()],
+        )
+    }
+
+    /// Set the restart policy.
+    pub fn with_restart(mut self, restart: RestartPolicy) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Set the working directory.
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    /// Add an environment variable.
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// The command as shown to the user.
+    pub fn command_line(&self) -> String {
+        match self.cmd.as_slice() {
+            [sh, c, command] if sh == "sh" && c == "-c" => command.clone(),
+            cmd => cmd.join(" "),
+        }
+    }
+
+    fn spawn(&self) -> std::io::Result<Child> {
+        let (program, args) = self
+            .cmd
+            .split_first()
+            .ok_or_else(|| std::io::Error::other("No command"))?;
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .envs(self.env.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+        // Its own process group, so stopping it reaches the processes it starts
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        command.spawn()
+    }
+}
+
+// =============================================================================
+// State
+// =============================================================================
+
+/// What a daemon is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaemonState {
+    /// Running as this process.
+    Running { pid: u32 },
+    /// Waiting to be started again after exiting.
+    Restarting,
+    /// Exited and not started again; the code is None if it was killed by a
+    /// signal or couldn't be spawned.
+    Exited { code: Option<i32> },
+    /// Stopped with `lux.daemon.stop()` or from the view.
+    Stopped,
+}
+
+impl DaemonState {
+    /// Name of the state, as `lux.daemon.status()` returns it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Running { .. } => "running",
+            Self::Restarting => "restarting",
+            Self::Exited { .. } => "exited",
+            Self::Stopped => "stopped",
+        }
+    }
+
+    /// Whether the supervisor is still running (or waiting to run) the daemon.
+    pub fn is_active(self) -> bool {
+        matches!(self, Self::Running { .. } | Self::Restarting)
+    }
+}
+
+/// A supervised daemon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaemonInfo {
+    /// Name it was started under.
+    pub name: String,
+    /// The command as shown to the user.
+    pub command: String,
+    /// Its restart policy.
+    pub restart: RestartPolicy,
+    /// What it's doing.
+    pub state: DaemonState,
+    /// How often it was started again after exiting.
+    pub restarts: u32,
+    /// Exit code of the last run that ended, if it had one.
+    pub last_exit: Option<i32>,
+    /// Why the last spawn failed.
+    pub error: Option<String>,
+}
+
+/// State shared by a daemon's supervisor, its output readers, and `Daemons`.
+struct Shared {
+    info: Mutex<DaemonInfo>,
+    lines: Mutex<VecDeque<String>>,
+    log: Mutex<Option<File>>,
+}
+
+impl Shared {
+    fn new(spec: &DaemonSpec, log: Option<File>) -> Self {
+        Self {
+            info: Mutex::new(DaemonInfo {
+                name: spec.name.clone(),
+                command: spec.command_line(),
+                restart: spec.restart,
+                state: DaemonState::Restarting,
+                restarts: 0,
+                last_exit: None,
+                error: None,
+            }),
+            lines: Mutex::new(VecDeque::new()),
+            log: Mutex::new(log),
+        }
+    }
+
+    fn set_state(&self, state: DaemonState) {
+        self.info.lock().state = state;
+    }
+
+    /// Keep an output line in memory and append it to the log file.
+    fn push_line(&self, line: String) {
+        if let Some(file) = self.log.lock().as_mut() {
+            let _ = writeln!(file, "{}", line);
+        }
+        let mut lines = self.lines.lock();
+        if lines.len() == MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+}
+
+// =============================================================================
+// Supervisor
+// =============================================================================
+
+/// A daemon and its supervising thread.
+struct Daemon {
+    spec: DaemonSpec,
+    shared: Arc<Shared>,
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Daemon {
+    /// Ask the supervisor to stop the daemon, without waiting.
+    fn signal_stop(&mut self) {
+        self.stop.take();
+    }
+
+    /// Wait for the supervisor to stop the daemon.
+    fn join(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Helper processes started with `lux.daemon.start()`, by name.
+pub struct Daemons {
+    log_dir: Option<PathBuf>,
+    daemons: Mutex<BTreeMap<String, Daemon>>,
+}
+
+impl Daemons {
+    /// Write daemon logs to `log_dir` (None keeps them in memory only).
+    pub fn new(log_dir: Option<PathBuf>) -> Self {
+        Self {
+            log_dir,
+            daemons: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The `daemons` folder in the data folder.
+    pub fn default_log_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join(DAEMONS_DIR))
+    }
+
+    /// Path of the log file of daemon `name`.
+    pub fn log_path(&self, name: &str) -> Option<PathBuf> {
+        self.log_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.log", name)))
+    }
+
+    /// Start a daemon.
+    ///
+    /// Starting a daemon that is running with the same spec does nothing, so
+    /// init.lua can call this on every load. A different spec, or a daemon
+    /// that exited or was stopped, is (re)started with a fresh log.
+    pub fn start(&self, spec: DaemonSpec) -> Result<(), DaemonError> {
+        if !is_valid_name(&spec.name) {
+            return Err(DaemonError::InvalidName(spec.name));
+        }
+        if spec.cmd.is_empty() {
+            return Err(DaemonError::NoCommand(spec.name));
+        }
+
+        let previous = {
+            let mut daemons = self.daemons.lock();
+            if let Some(daemon) = daemons.get(&spec.name) {
+                if daemon.spec == spec && daemon.shared.info.lock().state.is_active() {
+                    return Ok(());
+                }
+            }
+            daemons.remove(&spec.name)
+        };
+        if let Some(mut previous) = previous {
+            previous.signal_stop();
+            previous.join();
+        }
+
+        let log = self.log_path(&spec.name).and_then(|path| {
+            let file = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| File::create(&path));
+            file.map_err(|e| tracing::warn!("Cannot write {}: {}", path.display(), e))
+                .ok()
+        });
+        let shared = Arc::new(Shared::new(&spec, log));
+        let (stop, stopped) = mpsc::channel();
+        let thread = {
+            let (spec, shared) = (spec.clone(), shared.clone());
+            std::thread::Builder::new()
+                .name(format!("daemon-{}", spec.name))
+                .spawn(move || supervise(&spec, &shared, &stopped))
+        }
+        .map_err(|e| DaemonError::Spawn(spec.name.clone(), e.to_string()))?;
+
+        self.daemons.lock().insert(
+            spec.name.clone(),
+            Daemon {
+                spec,
+                shared,
+                stop: Some(stop),
+                thread: Some(thread),
+            },
+        );
+        Ok(())
+    }
+
+    /// Stop daemon `name`, waiting for it to exit.
+    ///
+    /// It stays listed as stopped and can be started again with `restart()`.
+    pub fn stop(&self, name: &str) -> Result<(), DaemonError> {
+        let mut daemon = self
+            .daemons
+            .lock()
+            .remove(name)
+            .ok_or_else(|| DaemonError::NotFound(name.to_string()))?;
+        daemon.signal_stop();
+        daemon.join();
+        self.daemons.lock().insert(name.to_string(), daemon);
+        Ok(())
+    }
+
+    /// Stop daemon `name` if it's running, and start it again.
+    pub fn restart(&self, name: &str) -> Result<(), DaemonError> {
+        let spec = self
+            .daemons
+            .lock()
+            .get(name)
+            .map(|daemon| daemon.spec.clone())
+            .ok_or_else(|| DaemonError::NotFound(name.to_string()))?;
+        self.stop(name)?;
+        self.start(spec)
+    }
+
+    /// Stop and forget daemon `name`.
+    pub fn remove(&self, name: &str) -> Result<(), DaemonError> {
+        self.stop(name)?;
+        self.daemons.lock().remove(name);
+        Ok(())
+    }
+
+    /// Stop every daemon, waiting for them to exit (before Lux quits).
+    pub fn stop_all(&self) {
+        let mut daemons = std::mem::take(&mut *self.daemons.lock());
+        // Signal all first, so their grace periods run at the same time
+        for daemon in daemons.values_mut() {
+            daemon.signal_stop();
+        }
+        for daemon in daemons.values_mut() {
+            daemon.join();
+        }
+    }
+
+    /// Daemon `name`, if it was started.
+    pub fn get(&self, name: &str) -> Option<DaemonInfo> {
+        self.daemons
+            .lock()
+            .get(name)
+            .map(|daemon| daemon.shared.info.lock().clone())
+    }
+
+    /// Every daemon, by name.
+    pub fn list(&self) -> Vec<DaemonInfo> {
+        self.daemons
+            .lock()
+            .values()
+            .map(|daemon| daemon.shared.info.lock().clone())
+            .collect()
+    }
+
+    /// The last `count` output lines of daemon `name`, oldest first.
+    pub fn logs(&self, name: &str, count: usize) -> Vec<String> {
+        let daemons = self.daemons.lock();
+        let Some(daemon) = daemons.get(name) else {
+            return Vec::new();
+        };
+        let lines = daemon.shared.lines.lock();
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    }
+}
+
+impl Default for Daemons {
+    fn default() -> Self {
+        Self::new(Self::default_log_dir())
+    }
+}
+
+impl Drop for Daemons {
+    fn drop(&mut self) {
+        self.stop_all();
+    }
+}
+
+/// How a run of the daemon ended.
+enum RunEnd {
+    Exited(ExitStatus),
+    SpawnFailed(String),
+    Stopped,
+}
+
+/// Run the daemon until it's stopped or exits for good.
+fn supervise(spec: &DaemonSpec, shared: &Arc<Shared>, stop: &Receiver<()>) {
+    let mut backoff = MIN_BACKOFF;
+    loop {
+        let started = Instant::now();
+        let success = match run_once(spec, shared, stop) {
+            RunEnd::Stopped => {
+                shared.set_state(DaemonState::Stopped);
+                return;
+            }
+            RunEnd::Exited(status) => {
+                let mut info = shared.info.lock();
+                info.last_exit = status.code();
+                info.error = None;
+                status.success()
+            }
+            RunEnd::SpawnFailed(error) => {
+                tracing::warn!("Failed to start daemon '{}': {}", spec.name, error);
+                let mut info = shared.info.lock();
+                info.last_exit = None;
+                info.error = Some(error);
+                false
+            }
+        };
+
+        if !spec.restart.restarts(success) {
+            let code = shared.info.lock().last_exit;
+            shared.set_state(DaemonState::Exited { code });
+            return;
+        }
+
+        if started.elapsed() >= STABLE_RUN {
+            backoff = MIN_BACKOFF;
+        }
+        shared.set_state(DaemonState::Restarting);
+        match stop.recv_timeout(backoff) {
+            Err(RecvTimeoutError::Timeout) => {}
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                shared.set_state(DaemonState::Stopped);
+                return;
+            }
+        }
+        shared.info.lock().restarts += 1;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Spawn the daemon once and wait for it to exit or to be stopped.
+fn run_once(spec: &DaemonSpec, shared: &Arc<Shared>, stop: &Receiver<()>) -> RunEnd {
+    let mut child = match spec.spawn() {
+        Ok(child) => child,
+        Err(e) => return RunEnd::SpawnFailed(e.to_string()),
+    };
+    shared.set_state(DaemonState::Running { pid: child.id() });
+    tracing::info!("Started daemon '{}' (pid {})", spec.name, child.id());
+
+    let readers: Vec<JoinHandle<()>> = [
+        child
+            .stdout
+            .take()
+            .map(|out| Box::new(out) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|err| Box::new(err) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|output| read_lines(output, shared.clone()))
+    .collect();
+
+    let end = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                // Processes it left behind would keep its output open
+                signal_group(child.id(), "-TERM");
+                break RunEnd::Exited(status);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("Failed to wait for daemon '{}': {}", spec.name, e);
+                terminate(&mut child);
+                break RunEnd::SpawnFailed(e.to_string());
+            }
+        }
+        match stop.recv_timeout(POLL_INTERVAL) {
+            Err(RecvTimeoutError::Timeout) => {}
+            Ok(()) | Err(RecvTimeoutError::Disconnected) => {
+                terminate(&mut child);
+                break RunEnd::Stopped;
+            }
+        }
+    };
+
+    // Output written before the exit is logged before the exit line
+    for reader in readers {
+        let _ = reader.join();
+    }
+    if let RunEnd::Exited(status) = &end {
+        tracing::info!("Daemon '{}' exited ({})", spec.name, status);
+        shared.push_line(format!("[lux] {}", status));
+    }
+    end
+}
+
+/// Copy the lines of a daemon's stdout or stderr to its log.
+fn read_lines(output: Box<dyn Read + Send>, shared: Arc<Shared>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(output).lines() {
+            match line {
+                Ok(line) => shared.push_line(line),
+                Err(_) => break,
+            }
+        }
+    })
+}
+
+/// Ask a daemon's process group to exit with SIGTERM, and kill it after the
+/// grace period.
+fn terminate(child: &mut Child) {
+    signal_group(child.id(), "-TERM");
+    if !matches!(child.wait_timeout(STOP_GRACE), Ok(Some(_))) {
+        signal_group(child.id(), "-KILL");
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// Send a signal to the process group a daemon leads.
+fn signal_group(pid: u32, signal: &str) {
+    let _ = Command::new("kill")
+        .args([signal, "--", &format!("-{}", pid)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+/// Errors from starting and stopping daemons.
+#[derive(Debug, thiserror::Error)]
+pub enum DaemonError {
+    #[error("Invalid daemon name '{0}' (use letters, digits, '-', and '_')")]
+    InvalidName(String),
+
+    #[error("Daemon '{0}' has no command")]
+    NoCommand(String),
+
+    #[error("Daemon '{0}' not found")]
+    NotFound(String),
+
+    #[error("Cannot supervise daemon '{0}': {1}")]
+    Spawn(String, String),
+}
+
+// =============================================================================
+// View
+// =============================================================================
+
+/// Rows of the Daemons view, one per daemon.
+///
+/// The item ID is the daemon name.
+pub fn daemon_groups(daemons: &Daemons, query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let items: Vec<Item> = daemons
+        .list()
+        .into_iter()
+        .filter(|info| {
+            info.name.to_lowercase().contains(&query)
+                || info.command.to_lowercase().contains(&query)
+        })
+        .map(|info| {
+            let (icon, state) = match info.state {
+                DaemonState::Running { pid } => (
+                    "🟢",
+                    t_args("Running (pid {pid})", &[("pid", &pid.to_string())]),
+                ),
+                DaemonState::Restarting => ("🟡", t("Restarting")),
+                DaemonState::Exited { code: Some(code) } => (
+                    "🔴",
+                    t_args("Exited with code {code}", &[("code", &code.to_string())]),
+                ),
+                DaemonState::Exited { code: None } => ("🔴", t("Exited")),
+                DaemonState::Stopped => ("⚪", t("Stopped")),
+            };
+
+            // The last output line says more than the command once it runs
+            let subtitle = match (&info.error, daemons.logs(&info.name, 1).pop()) {
+                (Some(error), _) => error.clone(),
+                (None, Some(line)) => line,
+                (None, None) => info.command.clone(),
+            };
+            let item = Item::new(info.name.clone(), info.name.clone())
+                .with_subtitle(subtitle)
+                .with_icon(icon)
+                .with_data(serde_json::json!({ "state": info.state.name() }))
+                .with_accessory(state);
+            if info.restarts > 0 {
+                item.with_accessory(t_args(
+                    "{count} restarts",
+                    &[("count", &info.restarts.to_string())],
+                ))
+            } else {
+                item
+            }
+        })
+        .collect();
+
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::ungrouped(items)]
+}
+
+/// Actions offered for a row of the Daemons view: `(id, title, shortcut)`.
+///
+/// Running daemons can be stopped or restarted, others started again.
+pub fn daemon_actions(item: &Item) -> Vec<(&'static str, String, Option<&'static str>)> {
+    let state = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("state")?.as_str());
+    let mut actions = match state {
+        Some("running") | Some("restarting") => vec![
+            ("restart", t("Restart Daemon"), Some("cmd+r")),
+            ("stop", t("Stop Daemon"), Some("cmd+.")),
+        ],
+        _ => vec![("restart", t("Start Daemon"), Some("cmd+r"))],
+    };
+    actions.push(("open_log", t("Open Log"), Some("cmd+o")));
+    actions.push(("remove", t("Remove Daemon"), Some("cmd+backspace")));
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Poll until `done` holds, for at most a few seconds.
+    fn wait_until(mut done: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if done() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn test_restart_policy() {
+        assert_eq!(
+            RestartPolicy::from_name("on-failure"),
+            Some(RestartPolicy::OnFailure)
+        );
+        assert_eq!(
+            RestartPolicy::from_name("Always"),
+            Some(RestartPolicy::Always)
+        );
+        assert_eq!(RestartPolicy::from_name("sometimes"), None);
+        assert!(RestartPolicy::Always.restarts(true));
+        assert!(RestartPolicy::OnFailure.restarts(false));
+        assert!(!RestartPolicy::OnFailure.restarts(true));
+        assert!(!RestartPolicy::Never.restarts(false));
+        assert_eq!(
+            DaemonSpec::shell("x", "sleep 1 && ls").command_line(),
+            "sleep 1 && ls"
+        );
+    }
+
+    #[test]
+    fn test_daemon_logs_and_exits() {
+        let dir = tempfile::tempdir().unwrap();
+        let daemons = Daemons::new(Some(dir.path().to_path_buf()));
+
+        assert!(matches!(
+            daemons.start(DaemonSpec::shell("no/slash", "true")),
+            Err(DaemonError::InvalidName(_))
+        ));
+        assert!(matches!(
+            daemons.start(DaemonSpec::new("empty", Vec::new())),
+            Err(DaemonError::NoCommand(_))
+        ));
+
+        let spec = DaemonSpec::shell("echo", "echo hello; echo oops >&2; exit 3")
+            .with_restart(RestartPolicy::Never);
+        daemons.start(spec).unwrap();
+        assert!(wait_until(|| {
+            daemons.get("echo").unwrap().state == DaemonState::Exited { code: Some(3) }
+        }));
+
+        let mut logs = daemons.logs("echo", 10);
+        assert!(logs.pop().unwrap().starts_with("[lux]"));
+        logs.sort();
+        assert_eq!(logs, ["hello", "oops"]);
+        assert_eq!(daemons.logs("echo", 1).len(), 1);
+        let file = fs::read_to_string(daemons.log_path("echo").unwrap()).unwrap();
+        assert!(file.contains("hello\n"));
+
+        let groups = daemon_groups(&daemons, "ech");
+        let item = &groups[0].items[0];
+        assert_eq!(item.id, "echo");
+        assert_eq!(daemon_actions(item)[0].1, "Start Daemon");
+        assert!(daemon_groups(&daemons, "nothing").is_empty());
+    }
+
+    #[test]
+    fn test_daemon_restarts_and_stops() {
+        let daemons = Daemons::new(None);
+
+        // Exits right away, so it keeps being restarted
+        let failing = DaemonSpec::shell("failing", "exit 1");
+        daemons.start(failing).unwrap();
+        assert!(wait_until(|| daemons.get("failing").unwrap().restarts >= 1));
+        assert_eq!(daemons.get("failing").unwrap().last_exit, Some(1));
+
+        let spec = DaemonSpec::shell("sleeper", "sleep 30");
+        daemons.start(spec.clone()).unwrap();
+        assert!(wait_until(|| matches!(
+            daemons.get("sleeper").unwrap().state,
+            DaemonState::Running { .. }
+        )));
+        let DaemonState::Running { pid } = daemons.get("sleeper").unwrap().state else {
+            unreachable!()
+        };
+
+        // The same spec again leaves the running process alone
+        daemons.start(spec).unwrap();
+        assert_eq!(
+            daemons.get("sleeper").unwrap().state,
+            DaemonState::Running { pid }
+        );
+
+        daemons.stop("sleeper").unwrap();
+        assert_eq!(daemons.get("sleeper").unwrap().state, DaemonState::Stopped);
+        assert!(matches!(
+            daemons.stop("unknown"),
+            Err(DaemonError::NotFound(_))
+        ));
+
+        daemons.restart("sleeper").unwrap();
+        assert!(wait_until(|| daemons
+            .get("sleeper")
+            .unwrap()
+            .state
+            .is_active()));
+
+        daemons.stop_all();
+        assert!(daemons.list().is_empty());
+    }
+}
@@ -0,0 +1,643 @@
+//! Natural-language dates.
+//!
+//! This module provides:
+//! - `parse_date()` - Read dates like `next friday 3pm`, `in 2 hours`, or `dec 25` (lux.date.parse)
+//! - `date_group()` - Root-view answers for dates and `days until <date>` queries
+//! - `date_actions()` / `run_date_action()` - Copy the ISO date or create a reminder
+//!
+//! Text before a date becomes the reminder's title, so `call mom tomorrow
+//! 6pm` answers with tomorrow's date and creates a reminder "call mom".
+
+use chrono::{
+    Datelike, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Weekday,
+};
+
+use lux_core::i18n::{format_number, t, t_args};
+use lux_core::{Group, Item};
+
+use crate::error::{PluginError, PluginResult};
+
+/// Type tag for date answers (used to route them to the built-in actions).
+pub const DATE_ITEM_TYPE: &str = "date_answer";
+
+/// Lua registry key prefix of the built-in date action handlers.
+pub const DATE_ACTION_KEY_PREFIX: &str = "date:";
+
+/// IDs of all date actions (one handler is registered per ID).
+pub const DATE_ACTION_IDS: &[&str] = &["copy_iso", "create_reminder"];
+
+/// Time "tonight" means when no time is given.
+const TONIGHT_HOUR: u32 = 20;
+
+/// Handler key for a date action ID.
+pub fn date_action_key(id: &str) -> String {
+    format!("{}{}", DATE_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Parsing
+// =============================================================================
+
+/// A parsed date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedDate {
+    /// Local date and time (midnight when no time was given).
+    pub datetime: NaiveDateTime,
+    /// Whether the text named a time of day.
+    pub has_time: bool,
+}
+
+impl ParsedDate {
+    fn date(date: NaiveDate) -> Self {
+        Self {
+            datetime: date.and_time(NaiveTime::MIN),
+            has_time: false,
+        }
+    }
+
+    fn moment(datetime: NaiveDateTime) -> Self {
+        Self {
+            datetime,
+            has_time: true,
+        }
+    }
+
+    /// Unix timestamp in the local time zone.
+    pub fn timestamp(&self) -> Option<i64> {
+        Some(
+            Local
+                .from_local_datetime(&self.datetime)
+                .earliest()?
+                .timestamp(),
+        )
+    }
+
+    /// ISO 8601 date, or date-time with the local offset when a time was given.
+    pub fn iso(&self) -> String {
+        if !self.has_time {
+            return self.datetime.format("%Y-%m-%d").to_string();
+        }
+        match Local.from_local_datetime(&self.datetime).earliest() {
+            Some(local) => local.to_rfc3339(),
+            None => self.datetime.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        }
+    }
+}
+
+/// Parse a date relative to `now` (local time).
+///
+/// Understands `today`/`tomorrow`/`yesterday`/`tonight`/`now`, weekdays
+/// (`friday`, `next friday`, `last friday`), offsets (`in 3 days`,
+/// `2 weeks from today`, `3 hours ago`, `next month`), calendar dates
+/// (`dec 25`, `25th of december 2027`, `2027-01-31`), and a time of day
+/// before or after any of them (`3pm`, `at 15:30`, `noon`).
+///
+/// `friday` is the coming Friday (today if it is Friday); `next friday` is
+/// the first Friday after today.
+pub fn parse_date(text: &str, now: NaiveDateTime) -> Option<ParsedDate> {
+    let text = text.trim().to_lowercase().replace(',', " ");
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    // The time of day may come last ("friday 3pm") or first ("3pm friday")
+    for time_len in 0..=tokens.len().min(3) {
+        let (day, time) = tokens.split_at(tokens.len() - time_len);
+        if let Some(parsed) = combine(day, time, now) {
+            return Some(parsed);
+        }
+    }
+    for time_len in 1..tokens.len().min(3) {
+        let (time, day) = tokens.split_at(time_len);
+        if let Some(parsed) = combine(day, time, now) {
+            return Some(parsed);
+        }
+    }
+    None
+}
+
+/// Split text into a leading title and a trailing date.
+///
+/// The longest date suffix wins; the title is empty when all of the text is a date.
+pub fn split_date(text: &str, now: NaiveDateTime) -> Option<(String, ParsedDate)> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    (0..words.len()).find_map(|start| {
+        let parsed = parse_date(&words[start..].join(" "), now)?;
+        Some((words[..start].join(" "), parsed))
+    })
+}
+
+fn combine(day: &[&str], time: &[&str], now: NaiveDateTime) -> Option<ParsedDate> {
+    let time = if time.is_empty() {
+        None
+    } else {
+        Some(parse_time(time)?)
+    };
+    if day == ["tonight"] {
+        let time = time.unwrap_or(NaiveTime::from_hms_opt(TONIGHT_HOUR, 0, 0)?);
+        return Some(ParsedDate::moment(now.date().and_time(time)));
+    }
+    let parsed = parse_day(day, now)?;
+    Some(match time {
+        Some(time) => ParsedDate::moment(parsed.datetime.date().and_time(time)),
+        None => parsed,
+    })
+}
+
+/// Parse a time of day: `3pm`, `3:30 pm`, `15:00`, `at 9`, `noon`, `midnight`.
+fn parse_time(tokens: &[&str]) -> Option<NaiveTime> {
+    let (tokens, at) = match tokens {
+        ["at", rest @ ..] => (rest, true),
+        _ => (tokens, false),
+    };
+    match tokens {
+        ["noon"] => NaiveTime::from_hms_opt(12, 0, 0),
+        ["midnight"] => Some(NaiveTime::MIN),
+        [clock] => {
+            for (suffix, pm) in [("am", false), ("pm", true), ("a.m.", false), ("p.m.", true)] {
+                if let Some(clock) = clock.strip_suffix(suffix) {
+                    return parse_clock(clock, Some(pm), at);
+                }
+            }
+            parse_clock(clock, None, at)
+        }
+        [clock, "am" | "a.m."] => parse_clock(clock, Some(false), at),
+        [clock, "pm" | "p.m."] => parse_clock(clock, Some(true), at),
+        _ => None,
+    }
+}
+
+/// Parse `h`, `h:mm`, or `hh:mm`; a bare hour needs `at` or am/pm.
+fn parse_clock(text: &str, pm: Option<bool>, at: bool) -> Option<NaiveTime> {
+    let (hour, minute) = match text.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour, minute.parse().ok()?),
+        Some(_) => return None,
+        None if pm.is_some() || at => (text, 0),
+        None => return None,
+    };
+    if hour.is_empty() || hour.len() > 2 || !hour.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hour: u32 = hour.parse().ok()?;
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// A unit of date arithmetic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+fn parse_unit(text: &str) -> Option<Unit> {
+    Some(match text {
+        "min" | "mins" | "minute" | "minutes" => Unit::Minute,
+        "h" | "hr" | "hrs" | "hour" | "hours" => Unit::Hour,
+        "day" | "days" => Unit::Day,
+        "wk" | "wks" | "week" | "weeks" => Unit::Week,
+        "month" | "months" => Unit::Month,
+        "yr" | "yrs" | "year" | "years" => Unit::Year,
+        _ => return None,
+    })
+}
+
+fn parse_count(text: &str) -> Option<i64> {
+    const WORDS: &[&str] = &[
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+    match text {
+        "a" | "an" => Some(1),
+        _ => match WORDS.iter().position(|word| *word == text) {
+            Some(i) => Some(i as i64 + 1),
+            None if text.len() <= 4 && text.chars().all(|c| c.is_ascii_digit()) => {
+                text.parse().ok()
+            }
+            None => None,
+        },
+    }
+}
+
+/// Move a date by `count` units. Days and longer keep it a date; hours and
+/// minutes make it a moment.
+fn shift(base: ParsedDate, count: i64, unit: Unit) -> Option<ParsedDate> {
+    let date = base.datetime.date();
+    let months = |count: i64| -> Option<NaiveDate> {
+        let months = Months::new(u32::try_from(count.unsigned_abs()).ok()?);
+        if count >= 0 {
+            date.checked_add_months(months)
+        } else {
+            date.checked_sub_months(months)
+        }
+    };
+    Some(match unit {
+        Unit::Minute => ParsedDate::moment(
+            base.datetime
+                .checked_add_signed(TimeDelta::try_minutes(count)?)?,
+        ),
+        Unit::Hour => ParsedDate::moment(
+            base.datetime
+                .checked_add_signed(TimeDelta::try_hours(count)?)?,
+        ),
+        Unit::Day => ParsedDate::date(date.checked_add_signed(TimeDelta::try_days(count)?)?),
+        Unit::Week => ParsedDate::date(date.checked_add_signed(TimeDelta::try_weeks(count)?)?),
+        Unit::Month => ParsedDate::date(months(count)?),
+        Unit::Year => ParsedDate::date(months(count.checked_mul(12)?)?),
+    })
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    Some(match text {
+        "monday" => Weekday::Mon,
+        "tuesday" => Weekday::Tue,
+        "wednesday" => Weekday::Wed,
+        "thursday" => Weekday::Thu,
+        "friday" => Weekday::Fri,
+        "saturday" => Weekday::Sat,
+        "sunday" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// The first `weekday` on or after `from`.
+fn weekday_from(from: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (7 + weekday.num_days_from_monday() - from.weekday().num_days_from_monday()) % 7;
+    from + TimeDelta::days(ahead as i64)
+}
+
+/// Parse the day part of a date.
+fn parse_day(tokens: &[&str], now: NaiveDateTime) -> Option<ParsedDate> {
+    let today = now.date();
+    let today_date = ParsedDate::date(today);
+    match tokens {
+        ["now"] => Some(ParsedDate::moment(now)),
+        // A bare time of day is today
+        [] | ["today"] => Some(today_date),
+        ["tomorrow"] => shift(today_date, 1, Unit::Day),
+        ["yesterday"] => shift(today_date, -1, Unit::Day),
+        ["on", rest @ ..] if !rest.is_empty() => parse_day(rest, now),
+        [weekday] | ["this", weekday] if parse_weekday(weekday).is_some() => Some(
+            ParsedDate::date(weekday_from(today, parse_weekday(weekday)?)),
+        ),
+        ["next", weekday] if parse_weekday(weekday).is_some() => Some(ParsedDate::date(
+            weekday_from(today + TimeDelta::days(1), parse_weekday(weekday)?),
+        )),
+        ["last", weekday] if parse_weekday(weekday).is_some() => Some(ParsedDate::date(
+            weekday_from(today - TimeDelta::days(7), parse_weekday(weekday)?),
+        )),
+        ["next", unit] => shift(today_date, 1, parse_unit(unit)?),
+        ["last", unit] => shift(today_date, -1, parse_unit(unit)?),
+        ["in", count, unit] => shift(
+            ParsedDate::moment(now),
+            parse_count(count)?,
+            parse_unit(unit)?,
+        ),
+        [count, unit, "ago"] => shift(
+            ParsedDate::moment(now),
+            -parse_count(count)?,
+            parse_unit(unit)?,
+        ),
+        [count, unit, "from" | "after", base @ ..] if !base.is_empty() => shift(
+            parse_day(base, now)?,
+            parse_count(count)?,
+            parse_unit(unit)?,
+        ),
+        [count, unit, "before", base @ ..] if !base.is_empty() => shift(
+            parse_day(base, now)?,
+            -parse_count(count)?,
+            parse_unit(unit)?,
+        ),
+        _ => parse_calendar_date(tokens, today).map(ParsedDate::date),
+    }
+}
+
+fn parse_month(text: &str) -> Option<u32> {
+    const MONTHS: &[&str] = &[
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    if text == "sept" {
+        return Some(9);
+    }
+    MONTHS
+        .iter()
+        .position(|month| *month == text || (text.len() == 3 && month.starts_with(text)))
+        .map(|i| i as u32 + 1)
+}
+
+fn parse_day_of_month(text: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| text.strip_suffix(suffix))
+        .unwrap_or(text);
+    if digits.len() > 2 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// Parse `2027-01-31`, `dec 25`, `25 december`, or `december 25th 2027`.
+///
+/// Without a year, the next such date on or after today, so `feb 29`
+/// rolls forward to the next leap year.
+fn parse_calendar_date(tokens: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    if let [iso] = tokens {
+        return NaiveDate::parse_from_str(iso, "%Y-%m-%d").ok();
+    }
+    let tokens: Vec<&str> = tokens.iter().copied().filter(|t| *t != "of").collect();
+    let (month, day, year) = match tokens.as_slice() {
+        [first, second, rest @ ..] if rest.len() <= 1 => {
+            let (month, day) = match (parse_month(first), parse_month(second)) {
+                (Some(month), None) => (month, parse_day_of_month(second)?),
+                (None, Some(month)) => (month, parse_day_of_month(first)?),
+                _ => return None,
+            };
+            let year = match rest {
+                [year] if year.len() == 4 => Some(year.parse::<i32>().ok()?),
+                [] => None,
+                _ => return None,
+            };
+            (month, day, year)
+        }
+        _ => return None,
+    };
+
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        // Leap years are at most eight years apart (2096 to 2104)
+        None => (today.year()..=today.year() + 8)
+            .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+            .find(|date| *date >= today),
+    }
+}
+
+// =============================================================================
+// Answers
+// =============================================================================
+
+/// Display a parsed date: weekday, ISO date, and the time if there is one.
+pub fn format_parsed(parsed: &ParsedDate) -> String {
+    let weekday = match parsed.datetime.weekday() {
+        Weekday::Mon => t("Monday"),
+        Weekday::Tue => t("Tuesday"),
+        Weekday::Wed => t("Wednesday"),
+        Weekday::Thu => t("Thursday"),
+        Weekday::Fri => t("Friday"),
+        Weekday::Sat => t("Saturday"),
+        Weekday::Sun => t("Sunday"),
+    };
+    let format = if parsed.has_time {
+        "%Y-%m-%d %H:%M"
+    } else {
+        "%Y-%m-%d"
+    };
+    format!("{}, {}", weekday, parsed.datetime.format(format))
+}
+
+/// How far a day is from today: "Today", "In 3 days", "2 days ago".
+fn relative_days(days: i64) -> String {
+    match days {
+        0 => t("Today"),
+        1 => t("Tomorrow"),
+        -1 => t("Yesterday"),
+        days if days > 0 => t_args("In {count} days", &[("count", &days.to_string())]),
+        days => t_args("{count} days ago", &[("count", &(-days).to_string())]),
+    }
+}
+
+/// Parse `days until <date>`, `weeks since <date>`, and similar.
+///
+/// Returns the date and whether to count in weeks.
+fn parse_count_query(query: &str, now: NaiveDateTime) -> Option<(ParsedDate, bool)> {
+    let query = query.strip_prefix("how many ").unwrap_or(query);
+    let (unit, rest) = query.split_once(' ')?;
+    let weeks = match unit {
+        "days" => false,
+        "weeks" => true,
+        _ => return None,
+    };
+    let (preposition, date) = rest.split_once(' ')?;
+    if !matches!(preposition, "until" | "till" | "to" | "since") {
+        return None;
+    }
+    Some((parse_date(date, now)?, weeks))
+}
+
+fn date_item(id: &str, title: String, parsed: &ParsedDate, reminder: Option<&str>) -> Item {
+    Item::new(format!("date:{}:{}", id, parsed.iso()), title)
+        .with_type(DATE_ITEM_TYPE)
+        .with_data(serde_json::json!({
+            "iso": parsed.iso(),
+            "timestamp": parsed.timestamp(),
+            "has_time": parsed.has_time,
+            "reminder": reminder,
+        }))
+}
+
+/// Build the root-view "Date" group for a query.
+///
+/// `days until dec 25` counts the days (or weeks) between today and the
+/// date; any other query ending in a date shows that date.
+pub fn date_group(query: &str, now: NaiveDateTime) -> Option<Group> {
+    let query = query.trim().to_lowercase();
+    let today = now.date();
+
+    let item = if let Some((parsed, weeks)) = parse_count_query(&query, now) {
+        let days = (parsed.datetime.date() - today).num_days().abs();
+        let title = match (weeks, days) {
+            (false, 1) => t("1 day"),
+            (false, days) => t_args("{count} days", &[("count", &days.to_string())]),
+            (true, days) => {
+                let weeks = days as f64 / 7.0;
+                let decimals = if days % 7 == 0 { 0 } else { 1 };
+                t_args(
+                    "{count} weeks",
+                    &[("count", &format_number(weeks, decimals))],
+                )
+            }
+        };
+        date_item("count", title, &parsed, None).with_subtitle(format_parsed(&parsed))
+    } else {
+        let (title, parsed) = split_date(&query, now)?;
+        let reminder = (!title.is_empty()).then_some(title.as_str());
+        let mut item = date_item("answer", format_parsed(&parsed), &parsed, reminder)
+            .with_accessory(relative_days((parsed.datetime.date() - today).num_days()));
+        item.subtitle = reminder.map(|title| t_args("Reminder: {title}", &[("title", title)]));
+        item
+    };
+    Some(Group::new(t("Date"), vec![item]))
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a date answer: `(id, title)`.
+pub fn date_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("copy_iso", t("Copy ISO Date")),
+        ("create_reminder", t("Create Reminder")),
+    ]
+}
+
+/// Run a date action.
+pub fn run_date_action(action_id: &str, item: &Item) -> PluginResult<()> {
+    let data = item
+        .data
+        .as_ref()
+        .ok_or_else(|| PluginError::Command("Item is not a date".to_string()))?;
+    match action_id {
+        "copy_iso" => {
+            let iso = data
+                .get("iso")
+                .and_then(|iso| iso.as_str())
+                .ok_or_else(|| PluginError::Command("Item is not a date".to_string()))?;
+            crate::desktop::current().clipboard_write(iso)
+        }
+        "create_reminder" => {
+            let timestamp = data
+                .get("timestamp")
+                .and_then(|timestamp| timestamp.as_i64())
+                .ok_or_else(|| PluginError::Command("Item is not a date".to_string()))?;
+            let has_time = data
+                .get("has_time")
+                .and_then(|has_time| has_time.as_bool())
+                .unwrap_or(false);
+            let title = data
+                .get("reminder")
+                .and_then(|title| title.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| t("Reminder"));
+            crate::calendar::create_reminder(&title, timestamp, has_time).map(|_| ())
+        }
+        _ => Err(PluginError::Command(format!(
+            "Unknown date action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday, 2026-10-14 at 10:30.
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 14)
+            .unwrap()
+            .and_hms_opt(10, 30, 0)
+            .unwrap()
+    }
+
+    fn parse(text: &str) -> Option<String> {
+        parse_date(text, now()).map(|parsed| {
+            let format = if parsed.has_time {
+                "%Y-%m-%d %H:%M"
+            } else {
+                "%Y-%m-%d"
+            };
+            parsed.datetime.format(format).to_string()
+        })
+    }
+
+    #[test]
+    fn test_relative_days() {
+        assert_eq!(parse("today").as_deref(), Some("2026-10-14"));
+        assert_eq!(parse("Tomorrow").as_deref(), Some("2026-10-15"));
+        assert_eq!(parse("tonight").as_deref(), Some("2026-10-14 20:00"));
+        assert_eq!(parse("friday").as_deref(), Some("2026-10-16"));
+        assert_eq!(parse("wednesday").as_deref(), Some("2026-10-14"));
+        assert_eq!(parse("next wednesday").as_deref(), Some("2026-10-21"));
+        assert_eq!(
+            parse("next friday 3pm").as_deref(),
+            Some("2026-10-16 15:00")
+        );
+        assert_eq!(parse("last monday").as_deref(), Some("2026-10-12"));
+        assert_eq!(parse("next month").as_deref(), Some("2026-11-14"));
+    }
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(parse("in 3 days").as_deref(), Some("2026-10-17"));
+        assert_eq!(parse("2 weeks from today").as_deref(), Some("2026-10-28"));
+        assert_eq!(
+            parse("a week after tomorrow").as_deref(),
+            Some("2026-10-22")
+        );
+        assert_eq!(parse("in 90 minutes").as_deref(), Some("2026-10-14 12:00"));
+        assert_eq!(parse("3 hours ago").as_deref(), Some("2026-10-14 07:30"));
+        assert_eq!(parse("in two years").as_deref(), Some("2028-10-14"));
+        assert_eq!(parse("3 days before dec 25").as_deref(), Some("2026-12-22"));
+    }
+
+    #[test]
+    fn test_calendar_dates_and_times() {
+        assert_eq!(parse("dec 25").as_deref(), Some("2026-12-25"));
+        assert_eq!(parse("25th of December").as_deref(), Some("2026-12-25"));
+        assert_eq!(parse("march 1").as_deref(), Some("2027-03-01"));
+        assert_eq!(parse("jan 31, 2030").as_deref(), Some("2030-01-31"));
+        assert_eq!(
+            parse("2027-02-03 at 9").as_deref(),
+            Some("2027-02-03 09:00")
+        );
+        assert_eq!(
+            parse("3:30 pm tomorrow").as_deref(),
+            Some("2026-10-15 15:30")
+        );
+        assert_eq!(parse("noon").as_deref(), Some("2026-10-14 12:00"));
+        assert_eq!(parse("friday 12am").as_deref(), Some("2026-10-16 00:00"));
+        assert_eq!(parse("feb 29").as_deref(), Some("2028-02-29"));
+        assert_eq!(parse("feb 29 2027"), None);
+
+        for text in [
+            "",
+            "3",
+            "spotify",
+            "feb 30",
+            "friday 13pm",
+            "in days",
+            "may",
+        ] {
+            assert_eq!(parse(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_date_group() {
+        let group = date_group("days until dec 25", now()).unwrap();
+        assert_eq!(group.items[0].title, "72 days");
+        assert_eq!(
+            group.items[0].subtitle.as_deref(),
+            Some("Friday, 2026-12-25")
+        );
+        let weeks = date_group("weeks since 2026-09-30", now()).unwrap();
+        assert_eq!(weeks.items[0].title, "2 weeks");
+
+        let answer = &date_group("Call mom next friday 6pm", now()).unwrap().items[0];
+        assert_eq!(answer.title, "Friday, 2026-10-16 18:00");
+        assert_eq!(answer.subtitle.as_deref(), Some("Reminder: call mom"));
+        assert_eq!(answer.accessories, ["In 2 days"]);
+
+        assert!(date_group("firefox", now()).is_none());
+    }
+}
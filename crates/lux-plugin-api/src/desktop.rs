@@ -0,0 +1,538 @@
+//! Desktop integration: opening things, the clipboard, notifications, and dialogs.
+//!
+//! This module provides:
+//! - `DesktopPlatform` - Trait for the desktop services plugins use
+//! - `MacDesktop` / `LinuxDesktop` - Implementations for each platform
+//! - `current()` - The implementation for the platform Lux was built for
+//! - `open()` / `reveal()` - Check a URL or path, then open or reveal it (`lux.open`, `lux.reveal`)
+//!
+//! Plugin code calls `desktop::current()` instead of platform tools, so
+//! supporting another platform means adding one implementation here. Each
+//! implementation shells out to the platform's standard tools:
+//!
+//! | | macOS | Linux |
+//! |---|---|---|
+//! | open | `open` | `xdg-open` |
+//! | open with | `open -a` | `gio launch` for a `.desktop` file, else the app's command |
+//! | reveal | `open -R` | `FileManager1.ShowItems` over D-Bus (falls back to opening the folder) |
+//! | trash | `delete` (Finder, AppleScript) | `gio trash` |
+//! | clipboard | `pbcopy` / `pbpaste` | `wl-copy` / `wl-paste` on Wayland, `xclip` on X11 |
+//! | clipboard images | `set the clipboard` (AppleScript) | `wl-copy --type image/png`, `xclip -t image/png` |
+//! | paste | `keystroke "v"` (System Events) | `wtype` on Wayland, `xdotool` on X11 |
+//! | notify | `display notification` (AppleScript) | `notify-send` |
+//! | confirm | `display dialog` (AppleScript) | `zenity --question` |
+//! | scripts | `osascript -l JavaScript` | not available |
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use lux_core::i18n::t;
+
+use crate::error::{PluginError, PluginResult};
+
+// =============================================================================
+// Desktop Platform Trait
+// =============================================================================
+
+/// Desktop services that differ between platforms.
+pub trait DesktopPlatform: Send + Sync {
+    /// Platform name ("macos", "linux").
+    fn name(&self) -> &'static str;
+
+    /// Open a file, folder, or URL in its default app.
+    fn open(&self, target: &str) -> PluginResult<()>;
+
+    /// Open a file, folder, or URL with a specific app (a name or path on
+    /// macOS, a `.desktop` file or command on Linux).
+    fn open_with(&self, target: &str, app: &str) -> PluginResult<()>;
+
+    /// Show a file in the file manager.
+    fn reveal(&self, path: &str) -> PluginResult<()>;
+
+    /// Move a file or folder to the Trash.
+    ///
+    /// Returns where the item went in the Trash, when the platform reports it.
+    fn trash(&self, path: &str) -> PluginResult<Option<String>>;
+
+    /// Read text from the clipboard (`None` when it holds no text).
+    fn clipboard_read(&self) -> PluginResult<Option<String>>;
+
+    /// Copy text to the clipboard.
+    fn clipboard_write(&self, text: &str) -> PluginResult<()>;
+
+    /// Copy a PNG image file to the clipboard.
+    fn clipboard_write_image(&self, path: &str) -> PluginResult<()>;
+
+    /// Copy text and paste it into the frontmost app.
+    ///
+    /// The keystroke is sent after a short delay so the launcher window has
+    /// closed by then; this returns without waiting for it.
+    fn paste(&self, text: &str) -> PluginResult<()>;
+
+    /// Post a notification with a sound.
+    fn notify(&self, title: &str, body: &str) -> PluginResult<()>;
+
+    /// Ask the user to confirm with a modal dialog. Returns `false` if they cancel.
+    fn confirm(&self, title: &str, message: &str) -> PluginResult<bool>;
+
+    /// Run an automation script (JXA on macOS), passing `args` to its
+    /// `run(argv)` function, and return what it prints.
+    fn run_script(&self, script: &str, args: &[&str]) -> PluginResult<String>;
+}
+
+/// The desktop platform Lux was built for.
+pub fn current() -> &'static dyn DesktopPlatform {
+    #[cfg(target_os = "linux")]
+    {
+        &LinuxDesktop
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        &MacDesktop
+    }
+}
+
+// =============================================================================
+// macOS
+// =============================================================================
+
+/// macOS desktop services (`open`, `pbcopy`, AppleScript).
+pub struct MacDesktop;
+
+impl DesktopPlatform for MacDesktop {
+    fn name(&self) -> &'static str {
+        "macos"
+    }
+
+    fn open(&self, target: &str) -> PluginResult<()> {
+        run(Command::new("open").arg(target))
+    }
+
+    fn open_with(&self, target: &str, app: &str) -> PluginResult<()> {
+        run(Command::new("open").args(["-a", app, target]))
+    }
+
+    fn reveal(&self, path: &str) -> PluginResult<()> {
+        run(Command::new("open").args(["-R", path]))
+    }
+
+    fn trash(&self, path: &str) -> PluginResult<Option<String>> {
+        // Through Finder so "Put Back" works; Finder returns the trashed item
+        let script = format!(
+            "tell application \"Finder\" to set trashed to delete POSIX file {}",
+            applescript_string(path)
+        );
+        let output = Command::new("osascript")
+            .args(["-e", &script, "-e", "POSIX path of (trashed as alias)"])
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|e| PluginError::Command(format!("Moving to Trash failed: {}", e)))?;
+        if !output.status.success() {
+            return Err(PluginError::Command(format!(
+                "Moving to Trash failed: osascript exited with {}",
+                output.status
+            )));
+        }
+        let trashed = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((!trashed.is_empty()).then_some(trashed))
+    }
+
+    fn clipboard_read(&self) -> PluginResult<Option<String>> {
+        read_stdout(&mut Command::new("pbpaste"))
+    }
+
+    fn clipboard_write(&self, text: &str) -> PluginResult<()> {
+        write_stdin(&mut Command::new("pbcopy"), text)
+    }
+
+    fn clipboard_write_image(&self, path: &str) -> PluginResult<()> {
+        let script = format!(
+            "set the clipboard to (read (POSIX file {}) as «class PNGf»)",
+            applescript_string(path)
+        );
+        run(Command::new("osascript").args(["-e", &script]))
+            .map_err(|e| PluginError::Command(format!("Clipboard write failed: {}", e)))
+    }
+
+    fn paste(&self, text: &str) -> PluginResult<()> {
+        self.clipboard_write(text)?;
+        spawn(Command::new("osascript").args([
+            "-e",
+            "delay 0.2",
+            "-e",
+            "tell application \"System Events\" to keystroke \"v\" using command down",
+        ]))
+    }
+
+    fn notify(&self, title: &str, body: &str) -> PluginResult<()> {
+        let script = format!(
+            "display notification {} with title {} sound name \"Glass\"",
+            applescript_string(body),
+            applescript_string(title)
+        );
+        run(Command::new("osascript").args(["-e", &script]))
+            .map_err(|e| PluginError::Command(format!("Notification failed: {}", e)))
+    }
+
+    fn confirm(&self, title: &str, message: &str) -> PluginResult<bool> {
+        let (ok, cancel) = (
+            applescript_string(&t("Run")),
+            applescript_string(&t("Cancel")),
+        );
+        let script = format!(
+            "display dialog {} with title {} buttons {{{}, {}}} default button {} cancel button {}",
+            applescript_string(message),
+            applescript_string(title),
+            cancel,
+            ok,
+            ok,
+            cancel
+        );
+        // Cancelling makes osascript exit with an error
+        confirmed(Command::new("osascript").args(["-e", &script]))
+    }
+
+    fn run_script(&self, script: &str, args: &[&str]) -> PluginResult<String> {
+        let output = Command::new("osascript")
+            .args(["-l", "JavaScript", "-e", script])
+            .args(args)
+            .output()
+            .map_err(|e| PluginError::Command(format!("osascript failed: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(PluginError::Command(format!(
+                "osascript failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+/// Quote a string as an AppleScript string literal.
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// =============================================================================
+// Linux
+// =============================================================================
+
+/// Linux desktop services (xdg-utils, D-Bus, Wayland/X11 clipboard tools).
+pub struct LinuxDesktop;
+
+impl LinuxDesktop {
+    /// Whether the session is a Wayland session.
+    fn is_wayland() -> bool {
+        std::env::var_os("WAYLAND_DISPLAY").is_some_and(|display| !display.is_empty())
+    }
+}
+
+impl DesktopPlatform for LinuxDesktop {
+    fn name(&self) -> &'static str {
+        "linux"
+    }
+
+    fn open(&self, target: &str) -> PluginResult<()> {
+        run(Command::new("xdg-open").arg(target))
+    }
+
+    fn open_with(&self, target: &str, app: &str) -> PluginResult<()> {
+        if app.ends_with(".desktop") {
+            run(Command::new("gio").args(["launch", app, target]))
+        } else {
+            spawn(Command::new(app).arg(target))
+        }
+    }
+
+    fn reveal(&self, path: &str) -> PluginResult<()> {
+        let uri = format!("array:string:file://{}", path);
+        let shown = run(Command::new("dbus-send").args([
+            "--session",
+            "--print-reply",
+            "--dest=org.freedesktop.FileManager1",
+            "/org/freedesktop/FileManager1",
+            "org.freedesktop.FileManager1.ShowItems",
+            &uri,
+            "string:",
+        ]));
+        if shown.is_ok() {
+            return Ok(());
+        }
+
+        // No file manager service: open the containing folder instead
+        let parent = std::path::Path::new(path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string());
+        self.open(&parent)
+    }
+
+    fn trash(&self, path: &str) -> PluginResult<Option<String>> {
+        // gio doesn't say where the item went, so it can't be moved back
+        run(Command::new("gio").args(["trash", path]))
+            .map_err(|e| PluginError::Command(format!("Moving to Trash failed: {}", e)))?;
+        Ok(None)
+    }
+
+    fn clipboard_read(&self) -> PluginResult<Option<String>> {
+        if Self::is_wayland() {
+            read_stdout(Command::new("wl-paste").arg("--no-newline"))
+        } else {
+            read_stdout(Command::new("xclip").args(["-selection", "clipboard", "-out"]))
+        }
+    }
+
+    fn clipboard_write(&self, text: &str) -> PluginResult<()> {
+        if Self::is_wayland() {
+            write_stdin(&mut Command::new("wl-copy"), text)
+        } else {
+            write_stdin(
+                Command::new("xclip").args(["-selection", "clipboard", "-in"]),
+                text,
+            )
+        }
+    }
+
+    fn clipboard_write_image(&self, path: &str) -> PluginResult<()> {
+        if Self::is_wayland() {
+            let image = std::fs::File::open(path)
+                .map_err(|e| PluginError::Command(format!("Clipboard write failed: {}", e)))?;
+            run(Command::new("wl-copy")
+                .args(["--type", "image/png"])
+                .stdin(image))
+        } else {
+            run(Command::new("xclip").args([
+                "-selection",
+                "clipboard",
+                "-target",
+                "image/png",
+                "-in",
+                path,
+            ]))
+        }
+    }
+
+    fn paste(&self, text: &str) -> PluginResult<()> {
+        self.clipboard_write(text)?;
+        if Self::is_wayland() {
+            spawn(Command::new("wtype").args(["-s", "200", "-M", "ctrl", "v", "-m", "ctrl"]))
+        } else {
+            spawn(Command::new("xdotool").args([
+                "sleep",
+                "0.2",
+                "key",
+                "--clearmodifiers",
+                "ctrl+v",
+            ]))
+        }
+    }
+
+    fn notify(&self, title: &str, body: &str) -> PluginResult<()> {
+        run(Command::new("notify-send").args([
+            "--app-name=Lux",
+            "--hint=string:sound-name:complete",
+            title,
+            body,
+        ]))
+        .map_err(|e| PluginError::Command(format!("Notification failed: {}", e)))
+    }
+
+    fn confirm(&self, title: &str, message: &str) -> PluginResult<bool> {
+        confirmed(Command::new("zenity").args([
+            "--question".to_string(),
+            format!("--title={}", title),
+            format!("--text={}", message),
+            format!("--ok-label={}", t("Run")),
+            format!("--cancel-label={}", t("Cancel")),
+        ]))
+    }
+
+    fn run_script(&self, _script: &str, _args: &[&str]) -> PluginResult<String> {
+        Err(PluginError::Command(
+            "Automation scripts require macOS".to_string(),
+        ))
+    }
+}
+
+// =============================================================================
+// Opening and Revealing
+// =============================================================================
+
+/// Whether `target` is a URL (`https://...`, `mailto:...`) rather than a path.
+pub fn is_url(target: &str) -> bool {
+    match target.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+/// `path` with `~` expanded, or an error if nothing is there.
+fn existing_path(path: &str) -> PluginResult<String> {
+    let expanded = crate::detect::expand_home(path);
+    if std::path::Path::new(&expanded).exists() {
+        Ok(expanded)
+    } else {
+        Err(PluginError::Command(format!(
+            "No such file or folder: {}",
+            path
+        )))
+    }
+}
+
+/// Open a URL, or a file or folder (`~` is expanded), with its default app.
+pub fn open(target: &str) -> PluginResult<()> {
+    let target = if is_url(target) {
+        target.to_string()
+    } else {
+        existing_path(target)?
+    };
+    current()
+        .open(&target)
+        .map_err(|e| PluginError::Command(format!("Cannot open {}: {}", target, e)))
+}
+
+/// Show a file or folder (`~` is expanded) in the file manager.
+pub fn reveal(path: &str) -> PluginResult<()> {
+    let path = existing_path(path)?;
+    current()
+        .reveal(&path)
+        .map_err(|e| PluginError::Command(format!("Cannot reveal {}: {}", path, e)))
+}
+
+// =============================================================================
+// Command Helpers
+// =============================================================================
+
+/// Run a command, discarding its output.
+fn run(cmd: &mut Command) -> PluginResult<()> {
+    let status = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| PluginError::Command(format!("Command spawn failed: {}", e)))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PluginError::Command(format!(
+            "Command exited with {}",
+            status
+        )))
+    }
+}
+
+/// Start a command without waiting for it to finish.
+fn spawn(cmd: &mut Command) -> PluginResult<()> {
+    let mut child = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| PluginError::Command(format!("Command spawn failed: {}", e)))?;
+    // Reap it in the background so it doesn't linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Run a dialog command: success means confirmed, failure means cancelled.
+fn confirmed(cmd: &mut Command) -> PluginResult<bool> {
+    let status = cmd
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| PluginError::Command(format!("Confirmation dialog failed: {}", e)))?;
+    Ok(status.success())
+}
+
+/// Run a command and return its stdout (`None` when it fails).
+fn read_stdout(cmd: &mut Command) -> PluginResult<Option<String>> {
+    let output = cmd
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| PluginError::Command(format!("Clipboard read failed: {}", e)))?;
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string()))
+}
+
+/// Run a command with `text` on its stdin.
+fn write_stdin(cmd: &mut Command, text: &str) -> PluginResult<()> {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| PluginError::Command(format!("Clipboard write failed: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| PluginError::Command(format!("Clipboard write failed: {}", e)))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| PluginError::Command(format!("Clipboard write failed: {}", e)))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PluginError::Command(format!(
+            "Clipboard write exited with {}",
+            status
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_matches_target() {
+        let expected = if cfg!(target_os = "linux") {
+            "linux"
+        } else {
+            "macos"
+        };
+        assert_eq!(current().name(), expected);
+    }
+
+    #[test]
+    fn test_is_url() {
+        assert!(is_url("https://example.com"));
+        assert!(is_url("mailto:hi@example.com"));
+        assert!(is_url("x-apple.systempreferences:com.apple.preference"));
+        assert!(!is_url("/Users/me/report.pdf"));
+        assert!(!is_url("~/notes:draft.txt"));
+        assert!(!is_url("c:"));
+    }
+
+    #[test]
+    fn test_open_missing_path_fails_before_running() {
+        let err = open("/nonexistent/lux/report.pdf").unwrap_err();
+        assert!(err.to_string().contains("No such file or folder"));
+        assert!(reveal("/nonexistent/lux").is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_linux_has_no_scripts() {
+        let err = current().run_script("'ok'", &[]).unwrap_err();
+        assert!(err.to_string().contains("require macOS"));
+    }
+
+    #[test]
+    fn test_applescript_string_escapes() {
+        assert_eq!(applescript_string("Tea"), "\"Tea\"");
+        assert_eq!(
+            applescript_string(r#"say "hi" \ bye"#),
+            r#""say \"hi\" \\ bye""#
+        );
+    }
+}
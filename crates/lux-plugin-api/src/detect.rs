@@ -0,0 +1,532 @@
+//! Smart detection of URLs, emails, paths, colors, and JSON.
+//!
+//! This module provides:
+//! - `detect()` - Recognize a structured value in free text
+//! - `detection_item()` - Result item injected at the top of root searches
+//! - `smart_actions()` - Contextual actions for a detected value
+//! - `run_smart_action()` - Execute one of those actions
+//!
+//! The engine runs detection on the query after the root view's search and on
+//! the focused item when building its action list, so these behave like
+//! built-in `search` and `get_actions` hooks.
+
+use lux_core::Item;
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+
+/// Type tag for items produced by detection.
+pub const DETECTED_ITEM_TYPE: &str = "detected";
+
+/// Prefix of the Lua registry keys for smart action handlers.
+pub const SMART_ACTION_KEY_PREFIX: &str = "detect:";
+
+/// All smart action IDs (one registry handler each).
+pub const SMART_ACTION_IDS: &[&str] = &[
+    "open",
+    "compose",
+    "reveal",
+    "copy",
+    "copy_hex",
+    "copy_rgb",
+    "copy_pretty_json",
+    "show_qr",
+];
+
+// =============================================================================
+// Detection
+// =============================================================================
+
+/// An RGBA color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Format as `#rrggbb` (or `#rrggbbaa` when not opaque).
+    pub fn to_hex(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    /// Format as CSS `rgb()` / `rgba()`.
+    pub fn to_rgb(self) -> String {
+        if self.a == 255 {
+            format!("rgb({}, {}, {})", self.r, self.g, self.b)
+        } else {
+            format!(
+                "rgba({}, {}, {}, {:.2})",
+                self.r,
+                self.g,
+                self.b,
+                self.a as f32 / 255.0
+            )
+        }
+    }
+
+    /// Format as CSS `hsl()` / `hsla()`.
+    pub fn to_hsl(self) -> String {
+        let (h, s, l) = rgb_to_hsl(self.r, self.g, self.b);
+        let (h, s, l) = (h.round(), (s * 100.0).round(), (l * 100.0).round());
+        if self.a == 255 {
+            format!("hsl({}, {}%, {}%)", h, s, l)
+        } else {
+            format!("hsla({}, {}%, {}%, {:.2})", h, s, l, self.a as f32 / 255.0)
+        }
+    }
+}
+
+/// Convert RGB to hue (degrees), saturation, and lightness (0-1).
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    ((h * 60.0) % 360.0, s, l)
+}
+
+/// Convert hue (degrees), saturation, and lightness (0-1) to RGB.
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// A structured value recognized in text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Detection {
+    /// A web URL.
+    Url(String),
+    /// An email address.
+    Email(String),
+    /// An absolute or home-relative file path.
+    FilePath(String),
+    /// A hex, `rgb()`, or `hsl()` color.
+    Color(Rgba),
+    /// A JSON object or array.
+    Json(serde_json::Value),
+}
+
+impl Detection {
+    /// Short kind name, used as an item type tag.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Detection::Url(_) => "url",
+            Detection::Email(_) => "email",
+            Detection::FilePath(_) => "path",
+            Detection::Color(_) => "color",
+            Detection::Json(_) => "json",
+        }
+    }
+
+    /// Text a QR code for this value encodes (URLs and email addresses only).
+    pub fn qr_payload(&self) -> Option<String> {
+        match self {
+            Detection::Url(url) => Some(url.clone()),
+            Detection::Email(email) => Some(format!("mailto:{}", email)),
+            _ => None,
+        }
+    }
+}
+
+/// Recognize a structured value in text.
+///
+/// Only the whole (trimmed) text is considered, so a sentence that merely
+/// contains a URL is not detected.
+pub fn detect(text: &str) -> Option<Detection> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if text.starts_with('{') || text.starts_with('[') {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
+            return Some(Detection::Json(value));
+        }
+    }
+
+    if let Some(color) = parse_color(text) {
+        return Some(Detection::Color(color));
+    }
+
+    if text.contains(char::is_whitespace) {
+        // Paths are the only detected values that may contain spaces
+        return is_path(text).then(|| Detection::FilePath(text.to_string()));
+    }
+
+    if is_url(text) {
+        let url = if text.starts_with("www.") {
+            format!("https://{}", text)
+        } else {
+            text.to_string()
+        };
+        return Some(Detection::Url(url));
+    }
+
+    if is_email(text) {
+        return Some(Detection::Email(text.to_string()));
+    }
+
+    is_path(text).then(|| Detection::FilePath(text.to_string()))
+}
+
+/// Detect a value on an item.
+///
+/// Detection items carry their value in `data.value`; other items are
+/// checked by title, then subtitle.
+pub fn detect_item(item: &Item) -> Option<Detection> {
+    if item.has_type(DETECTED_ITEM_TYPE) {
+        let value = item.data.as_ref()?.get("value")?.as_str()?;
+        return detect(value);
+    }
+
+    detect(&item.title).or_else(|| item.subtitle.as_deref().and_then(detect))
+}
+
+fn is_url(text: &str) -> bool {
+    let rest = ["https://", "http://", "ftp://", "www."]
+        .iter()
+        .find_map(|prefix| text.strip_prefix(prefix));
+    matches!(rest, Some(host) if !host.is_empty() && !host.starts_with('/'))
+}
+
+fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+}
+
+fn is_path(text: &str) -> bool {
+    !text.contains('\n') && (text.starts_with('/') || text.starts_with("~/")) && text.len() > 1
+}
+
+/// Parse a hex, `rgb()`/`rgba()`, or `hsl()`/`hsla()` color.
+pub fn parse_color(text: &str) -> Option<Rgba> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+        let short = |i: usize| channel(&hex[i..i + 1].repeat(2));
+        return match hex.len() {
+            3 => Some(Rgba {
+                r: short(0)?,
+                g: short(1)?,
+                b: short(2)?,
+                a: 255,
+            }),
+            6 | 8 => Some(Rgba {
+                r: channel(&hex[0..2])?,
+                g: channel(&hex[2..4])?,
+                b: channel(&hex[4..6])?,
+                a: if hex.len() == 8 {
+                    channel(&hex[6..8])?
+                } else {
+                    255
+                },
+            }),
+            _ => None,
+        };
+    }
+
+    let (is_hsl, inner) = if let Some(rest) = text
+        .strip_prefix("hsl(")
+        .or_else(|| text.strip_prefix("hsla("))
+    {
+        (true, rest)
+    } else {
+        let rest = text
+            .strip_prefix("rgb(")
+            .or_else(|| text.strip_prefix("rgba("))?;
+        (false, rest)
+    };
+    let parts: Vec<&str> = inner.strip_suffix(')')?.split(',').map(str::trim).collect();
+    if parts.len() != 3 && parts.len() != 4 {
+        return None;
+    }
+    let a = match parts.get(3) {
+        Some(alpha) => {
+            let alpha: f32 = alpha.parse().ok()?;
+            (alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+        }
+        None => 255,
+    };
+
+    if is_hsl {
+        let h: f32 = parts[0].trim_end_matches("deg").parse().ok()?;
+        let percent = |s: &str| -> Option<f32> {
+            let value: f32 = s.strip_suffix('%')?.trim().parse().ok()?;
+            (0.0..=100.0).contains(&value).then_some(value / 100.0)
+        };
+        let (r, g, b) = hsl_to_rgb(h, percent(parts[1])?, percent(parts[2])?);
+        return Some(Rgba { r, g, b, a });
+    }
+
+    Some(Rgba {
+        r: parts[0].parse().ok()?,
+        g: parts[1].parse().ok()?,
+        b: parts[2].parse().ok()?,
+        a,
+    })
+}
+
+// =============================================================================
+// Items and Actions
+// =============================================================================
+
+/// Build the result item for a query that contains a detected value.
+pub fn detection_item(query: &str) -> Option<Item> {
+    let detection = detect(query)?;
+    let value = query.trim().to_string();
+
+    let (title, subtitle) = match &detection {
+        Detection::Url(url) => (format!("Open {}", url), "URL".to_string()),
+        Detection::Email(email) => (format!("Email {}", email), "Email address".to_string()),
+        Detection::FilePath(path) => (format!("Reveal {}", path), "File path".to_string()),
+        Detection::Color(color) => (color.to_hex(), color.to_rgb()),
+        Detection::Json(_) => ("JSON".to_string(), "Copy pretty-printed".to_string()),
+    };
+
+    let item = Item::new(format!("detected:{}", value), title)
+        .with_subtitle(subtitle)
+        .with_type(DETECTED_ITEM_TYPE)
+        .with_type(detection.kind())
+        .with_data(serde_json::json!({ "value": value }));
+    Some(match &detection {
+        Detection::FilePath(path) => item.with_drag(lux_core::DragPayload::File {
+            path: expand_home(path),
+        }),
+        Detection::Url(url) => item.with_drag(lux_core::DragPayload::Url { url: url.clone() }),
+        _ => item,
+    })
+}
+
+/// Contextual actions for a detected value, as `(id, title)` pairs.
+pub fn smart_actions(detection: &Detection) -> Vec<(&'static str, &'static str)> {
+    match detection {
+        Detection::Url(_) => vec![
+            ("open", "Open URL"),
+            ("copy", "Copy URL"),
+            ("show_qr", "Show QR Code"),
+        ],
+        Detection::Email(_) => vec![
+            ("compose", "Compose Email"),
+            ("copy", "Copy Address"),
+            ("show_qr", "Show QR Code"),
+        ],
+        Detection::FilePath(_) => vec![
+            ("reveal", "Reveal in Finder"),
+            ("open", "Open"),
+            ("copy", "Copy Path"),
+        ],
+        Detection::Color(_) => vec![("copy_hex", "Copy as Hex"), ("copy_rgb", "Copy as RGB")],
+        Detection::Json(_) => vec![("copy_pretty_json", "Copy Pretty-Printed JSON")],
+    }
+}
+
+/// Registry key for a smart action handler.
+pub fn smart_action_key(action_id: &str) -> String {
+    format!("{}{}", SMART_ACTION_KEY_PREFIX, action_id)
+}
+
+/// Run a smart action against a detected value.
+pub fn run_smart_action(action_id: &str, detection: &Detection) -> PluginResult<()> {
+    match (action_id, detection) {
+        ("open", Detection::Url(url)) => desktop::current().open(url),
+        ("open", Detection::FilePath(path)) => desktop::current().open(&expand_home(path)),
+        ("compose", Detection::Email(email)) => {
+            desktop::current().open(&format!("mailto:{}", email))
+        }
+        ("reveal", Detection::FilePath(path)) => desktop::current().reveal(&expand_home(path)),
+        ("copy", Detection::Url(s) | Detection::Email(s) | Detection::FilePath(s)) => {
+            desktop::current().clipboard_write(s)
+        }
+        ("copy_hex", Detection::Color(color)) => {
+            desktop::current().clipboard_write(&color.to_hex())
+        }
+        ("copy_rgb", Detection::Color(color)) => {
+            desktop::current().clipboard_write(&color.to_rgb())
+        }
+        ("copy_pretty_json", Detection::Json(value)) => {
+            let pretty = serde_json::to_string_pretty(value)
+                .map_err(|e| PluginError::Command(format!("JSON formatting failed: {}", e)))?;
+            desktop::current().clipboard_write(&pretty)
+        }
+        _ => Err(PluginError::Command(format!(
+            "Action '{}' does not apply to {}",
+            action_id,
+            detection.kind()
+        ))),
+    }
+}
+
+/// Expand a leading `~/` to the home directory.
+pub(crate) fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_url() {
+        assert_eq!(
+            detect("https://example.com/a?b=c"),
+            Some(Detection::Url("https://example.com/a?b=c".to_string()))
+        );
+        assert_eq!(
+            detect("www.rust-lang.org"),
+            Some(Detection::Url("https://www.rust-lang.org".to_string()))
+        );
+        assert_eq!(detect("https://"), None);
+        assert_eq!(detect("see https://example.com"), None);
+    }
+
+    #[test]
+    fn test_detect_email() {
+        assert_eq!(
+            detect("someone@example.com"),
+            Some(Detection::Email("someone@example.com".to_string()))
+        );
+        assert_eq!(detect("@example.com"), None);
+        assert_eq!(detect("someone@localhost"), None);
+    }
+
+    #[test]
+    fn test_detect_path() {
+        assert_eq!(
+            detect("~/Documents/My File.txt"),
+            Some(Detection::FilePath("~/Documents/My File.txt".to_string()))
+        );
+        assert_eq!(
+            detect("/usr/bin"),
+            Some(Detection::FilePath("/usr/bin".to_string()))
+        );
+        assert_eq!(detect("/"), None);
+    }
+
+    #[test]
+    fn test_detect_color() {
+        let color = Rgba {
+            r: 255,
+            g: 0,
+            b: 136,
+            a: 255,
+        };
+        assert_eq!(detect("#ff0088"), Some(Detection::Color(color)));
+        assert_eq!(detect("#f08"), Some(Detection::Color(color)));
+        assert_eq!(detect("rgb(255, 0, 136)"), Some(Detection::Color(color)));
+        assert_eq!(color.to_rgb(), "rgb(255, 0, 136)");
+        assert_eq!(detect("#ff00"), None);
+        assert_eq!(detect("#zzzzzz"), None);
+
+        let translucent = parse_color("rgba(0, 0, 0, 0.5)").unwrap();
+        assert_eq!(translucent.to_hex(), "#00000080");
+    }
+
+    #[test]
+    fn test_hsl_colors() {
+        let color = parse_color("hsl(330, 100%, 50%)").unwrap();
+        assert_eq!(color.to_hex(), "#ff0080");
+        assert_eq!(color.to_hsl(), "hsl(330, 100%, 50%)");
+        assert_eq!(
+            parse_color("hsla(120deg, 100%, 25%, 0.5)")
+                .unwrap()
+                .to_hex(),
+            "#00800080"
+        );
+        assert_eq!(parse_color("#808080").unwrap().to_hsl(), "hsl(0, 0%, 50%)");
+        assert_eq!(parse_color("hsl(0, 120%, 50%)"), None);
+
+        // Round trips through (rounded) HSL stay within a couple of steps
+        for hex in ["#ff0088", "#123456", "#ffffff", "#000000", "#7fbf3f"] {
+            let color = parse_color(hex).unwrap();
+            let back = parse_color(&color.to_hsl()).unwrap();
+            let channels = [(color.r, back.r), (color.g, back.g), (color.b, back.b)];
+            assert!(channels.iter().all(|(a, b)| a.abs_diff(*b) <= 2), "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_detect_json() {
+        assert!(matches!(
+            detect(r#"{"a": [1, 2]}"#),
+            Some(Detection::Json(_))
+        ));
+        assert_eq!(detect("{not json"), None);
+    }
+
+    #[test]
+    fn test_detect_plain_text() {
+        assert_eq!(detect("firefox"), None);
+        assert_eq!(detect("hello world"), None);
+        assert_eq!(detect("   "), None);
+    }
+
+    #[test]
+    fn test_detect_item_prefers_data_value() {
+        let item = detection_item("#fff").unwrap();
+        assert!(item.has_type(DETECTED_ITEM_TYPE));
+        assert!(item.has_type("color"));
+        assert!(matches!(detect_item(&item), Some(Detection::Color(_))));
+
+        let plain = Item::new("1", "Notes").with_subtitle("/Applications/Notes.app");
+        assert!(matches!(detect_item(&plain), Some(Detection::FilePath(_))));
+    }
+
+    #[test]
+    fn test_smart_actions_registered() {
+        let detections = [
+            Detection::Url("https://a.b".to_string()),
+            Detection::Email("a@b.c".to_string()),
+            Detection::FilePath("/tmp".to_string()),
+            Detection::Color(parse_color("#000").unwrap()),
+            Detection::Json(serde_json::json!({})),
+        ];
+        for detection in &detections {
+            for (id, _) in smart_actions(detection) {
+                assert!(SMART_ACTION_IDS.contains(&id), "unregistered action {}", id);
+            }
+        }
+    }
+}
@@ -0,0 +1,463 @@
+//! Dictionary lookups with `define <word>`.
+//!
+//! This module provides:
+//! - `lookup()` - The text of a word's entry from Dictionary Services
+//! - `DictionaryEntry` / `parse_entry()` - An entry split into senses and notes
+//! - `DictionaryCache` - Recent lookups, so typing doesn't run a script per key
+//! - `dictionary_groups()` - Root-view groups of definitions, one per part of speech
+//!
+//! Lookups go through `DCSCopyTextDefinition`, which reads the first
+//! dictionary enabled in Dictionary.app's settings. Moving the Thesaurus to
+//! the top there makes `define` list synonyms instead.
+
+use lux_core::i18n::t;
+use lux_core::{Group, Item};
+use parking_lot::Mutex;
+
+use crate::error::{PluginError, PluginResult};
+
+/// Type tag for definition items (used to route them to the built-in actions).
+pub const DEFINITION_ITEM_TYPE: &str = "definition";
+
+/// Lua registry key prefix of the built-in definition action handlers.
+pub const DICTIONARY_ACTION_KEY_PREFIX: &str = "dictionary:";
+
+/// IDs of all definition actions (one handler is registered per ID).
+pub const DICTIONARY_ACTION_IDS: &[&str] = &["copy", "show_entry", "open_dictionary"];
+
+/// View ID of the full entry view.
+pub const ENTRY_VIEW_ID: &str = "dictionary.entry";
+
+/// Query prefix that looks up a word.
+const DEFINE_KEYWORD: &str = "define";
+
+/// Lookups kept in the cache.
+const CACHE_SIZE: usize = 32;
+
+/// Parts of speech that start a block of senses.
+const PARTS_OF_SPEECH: &[&str] = &[
+    "noun",
+    "verb",
+    "adjective",
+    "adverb",
+    "pronoun",
+    "preposition",
+    "conjunction",
+    "exclamation",
+    "determiner",
+    "abbreviation",
+    "prefix",
+    "suffix",
+];
+
+/// Section headings after the senses.
+const NOTE_HEADINGS: &[&str] = &["PHRASES", "PHRASAL VERBS", "DERIVATIVES", "ORIGIN", "USAGE"];
+
+/// Handler key for a definition action ID.
+pub fn dictionary_action_key(id: &str) -> String {
+    format!("{}{}", DICTIONARY_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Lookup
+// =============================================================================
+
+/// Look up a word. Returns None if no dictionary has an entry for it.
+#[cfg(not(target_os = "linux"))]
+pub fn lookup(word: &str) -> PluginResult<Option<String>> {
+    let script = r#"
+ObjC.import('CoreServices');
+function run(argv) {
+    const word = argv[0];
+    const text = $.DCSCopyTextDefinition(null, $(word), $.CFRangeMake(0, word.length));
+    return text ? ObjC.castRefToObject(text).js : '';
+}
+"#;
+    let output = crate::permissions::run_jxa_with_args(script, &[word])?;
+    let text = output.trim();
+    Ok((!text.is_empty()).then(|| text.to_string()))
+}
+
+/// Look up a word. Dictionary Services is macOS-only.
+#[cfg(target_os = "linux")]
+pub fn lookup(_word: &str) -> PluginResult<Option<String>> {
+    Ok(None)
+}
+
+/// One numbered sense of a word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sense {
+    /// Part of speech the sense belongs to (e.g. `noun`).
+    pub part_of_speech: String,
+    pub text: String,
+}
+
+/// A dictionary entry split into its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryEntry {
+    /// The word as looked up.
+    pub word: String,
+    /// Pronunciation, if the dictionary gives one.
+    pub pronunciation: Option<String>,
+    pub senses: Vec<Sense>,
+    /// Trailing sections as `(heading, text)`, e.g. `("ORIGIN", "Old English ...")`.
+    pub notes: Vec<(String, String)>,
+    /// The entry text as returned by the dictionary.
+    pub text: String,
+}
+
+impl DictionaryEntry {
+    /// Parts of speech in the order they appear.
+    pub fn parts_of_speech(&self) -> Vec<&str> {
+        let mut parts: Vec<&str> = Vec::new();
+        for sense in &self.senses {
+            if !parts.contains(&sense.part_of_speech.as_str()) {
+                parts.push(&sense.part_of_speech);
+            }
+        }
+        parts
+    }
+}
+
+/// Split an entry's text into senses and notes.
+///
+/// Entries read `head·word | pronunciation | noun 1 ... 2 ... verb ...
+/// ORIGIN ...`. Senses are numbered from 1 within each part of speech;
+/// entries with a single sense aren't numbered.
+pub fn parse_entry(word: &str, text: &str) -> DictionaryEntry {
+    let parts: Vec<&str> = text.splitn(3, " | ").collect();
+    let (pronunciation, body) = match parts.as_slice() {
+        [_, pronunciation, body] => (Some(pronunciation.trim().to_string()), *body),
+        [_, body] => (None, *body),
+        _ => (None, text),
+    };
+
+    let mut entry = DictionaryEntry {
+        word: word.to_string(),
+        pronunciation,
+        senses: Vec::new(),
+        notes: Vec::new(),
+        text: text.to_string(),
+    };
+
+    let mut part_of_speech = String::new();
+    let mut next_number = 1;
+    let mut note: Option<String> = None;
+    let mut buffer: Vec<&str> = Vec::new();
+
+    let flush = |entry: &mut DictionaryEntry,
+                 buffer: &mut Vec<&str>,
+                 note: &Option<String>,
+                 part_of_speech: &str| {
+        let text = buffer.join(" ");
+        buffer.clear();
+        if text.is_empty() {
+            return;
+        }
+        match note {
+            Some(heading) => entry.notes.push((heading.clone(), text)),
+            None if !part_of_speech.is_empty() => entry.senses.push(Sense {
+                part_of_speech: part_of_speech.to_string(),
+                text,
+            }),
+            None => {}
+        }
+    };
+
+    let words: Vec<&str> = body.split_whitespace().collect();
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+
+        // Headings are one or two uppercase words
+        let heading = NOTE_HEADINGS.iter().find(|heading| {
+            let len = heading.split(' ').count();
+            i + len <= words.len() && words[i..i + len].join(" ") == **heading
+        });
+        if let Some(heading) = heading {
+            flush(&mut entry, &mut buffer, &note, &part_of_speech);
+            note = Some(heading.to_string());
+            i += heading.split(' ').count();
+            continue;
+        }
+
+        let at_boundary = buffer
+            .last()
+            .is_none_or(|last| last.ends_with(['.', ':', ';']));
+        if note.is_none() && at_boundary {
+            if PARTS_OF_SPEECH.contains(&word) {
+                flush(&mut entry, &mut buffer, &note, &part_of_speech);
+                part_of_speech = word.to_string();
+                next_number = 1;
+                i += 1;
+                continue;
+            }
+            if word == next_number.to_string() {
+                flush(&mut entry, &mut buffer, &note, &part_of_speech);
+                next_number += 1;
+                i += 1;
+                continue;
+            }
+        }
+
+        buffer.push(word);
+        i += 1;
+    }
+    flush(&mut entry, &mut buffer, &note, &part_of_speech);
+
+    entry
+}
+
+/// The word to look up in a `define <word>` query.
+pub fn define_query(query: &str) -> Option<&str> {
+    let rest = query.trim_start().strip_prefix(DEFINE_KEYWORD)?;
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    let word = rest.trim();
+    (!word.is_empty()).then_some(word)
+}
+
+// =============================================================================
+// Cache
+// =============================================================================
+
+/// Recently looked up entries.
+///
+/// Each keystroke of `define <word>` asks for a lookup; the script takes tens
+/// of milliseconds, so entries (and misses) are kept for the last few words.
+pub struct DictionaryCache {
+    entries: Mutex<Vec<(String, Option<DictionaryEntry>)>>,
+}
+
+impl DictionaryCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Get the entry for a word, looking it up if it isn't cached.
+    pub fn get(&self, word: &str) -> Option<DictionaryEntry> {
+        self.get_with(word, || {
+            lookup(word)
+                .map_err(|e| tracing::debug!("Dictionary lookup failed: {}", e))
+                .ok()
+                .flatten()
+                .map(|text| parse_entry(word, &text))
+        })
+    }
+
+    /// Get the cached entry for a word, calling `fetch` if it isn't cached.
+    pub fn get_with(
+        &self,
+        word: &str,
+        fetch: impl FnOnce() -> Option<DictionaryEntry>,
+    ) -> Option<DictionaryEntry> {
+        let key = word.to_lowercase();
+        let mut entries = self.entries.lock();
+        if let Some(pos) = entries.iter().position(|(cached, _)| *cached == key) {
+            // Move to the back so the oldest lookups are dropped first
+            let hit = entries.remove(pos);
+            let value = hit.1.clone();
+            entries.push(hit);
+            return value;
+        }
+
+        let value = fetch();
+        if entries.len() >= CACHE_SIZE {
+            entries.remove(0);
+        }
+        entries.push((key, value.clone()));
+        value
+    }
+
+    /// Drop every entry, so the next lookups run again.
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+}
+
+impl Default for DictionaryCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item for one sense of an entry.
+pub fn sense_item(entry: &DictionaryEntry, index: usize, sense: &Sense) -> Item {
+    let subtitle = match &entry.pronunciation {
+        Some(pronunciation) => format!("{} · {}", entry.word, pronunciation),
+        None => entry.word.clone(),
+    };
+
+    Item::new(
+        format!("definition:{}:{}", entry.word, index),
+        sense.text.clone(),
+    )
+    .with_subtitle(subtitle)
+    .with_icon("📖")
+    .with_type(DEFINITION_ITEM_TYPE)
+    .with_data(serde_json::json!({
+        "word": entry.word,
+        "definition": sense.text,
+        "entry": entry.text,
+    }))
+}
+
+/// Build the root-view groups for a `define <word>` query.
+///
+/// Returns one group per part of speech, in dictionary order.
+pub fn dictionary_groups(query: &str, cache: &DictionaryCache) -> Vec<Group> {
+    let Some(word) = define_query(query) else {
+        return Vec::new();
+    };
+    let Some(entry) = cache.get(word) else {
+        return Vec::new();
+    };
+
+    entry
+        .parts_of_speech()
+        .into_iter()
+        .map(|part_of_speech| {
+            let items = entry
+                .senses
+                .iter()
+                .enumerate()
+                .filter(|(_, sense)| sense.part_of_speech == part_of_speech)
+                .map(|(index, sense)| sense_item(&entry, index, sense))
+                .collect();
+            Group::new(part_of_speech, items)
+        })
+        .collect()
+}
+
+/// Recover the entry from an item built by `sense_item()`.
+pub fn entry_from_item(item: &Item) -> Option<DictionaryEntry> {
+    let data = item.data.as_ref()?;
+    let word = data.get("word")?.as_str()?;
+    let text = data.get("entry")?.as_str()?;
+    Some(parse_entry(word, text))
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a definition item: `(id, title)`.
+pub fn dictionary_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("copy", t("Copy Definition")),
+        ("show_entry", t("Show Full Entry")),
+        ("open_dictionary", t("Open in Dictionary")),
+    ]
+}
+
+/// Run a definition action that doesn't push a view.
+pub fn run_dictionary_action(action_id: &str, item: &Item) -> PluginResult<()> {
+    let field = |key: &str| {
+        item.data
+            .as_ref()
+            .and_then(|data| data.get(key)?.as_str())
+            .ok_or_else(|| PluginError::Command(format!("Definition item has no {}", key)))
+    };
+
+    let desktop = crate::desktop::current();
+    match action_id {
+        "copy" => desktop.clipboard_write(field("definition")?),
+        "open_dictionary" => desktop.open(&dictionary_url(field("word")?)),
+        _ => Err(PluginError::Command(format!(
+            "Unknown dictionary action '{}'",
+            action_id
+        ))),
+    }
+}
+
+/// `dict://` URL that opens Dictionary.app on a word.
+fn dictionary_url(word: &str) -> String {
+    format!("dict://{}", crate::text::url_encode(word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPLE: &str = "ap·ple | ˈapəl | noun 1 the round fruit of a tree of the rose family, \
+        which typically has thin red or green skin and crisp flesh. 2 the tree which bears apples. \
+        PHRASES the apple of one's eye a person of whom one is extremely fond. \
+        DERIVATIVES appley adjective ORIGIN Old English æppel, of Germanic origin.";
+
+    #[test]
+    fn test_parse_entry() {
+        let entry = parse_entry("apple", APPLE);
+        assert_eq!(entry.pronunciation.as_deref(), Some("ˈapəl"));
+        assert_eq!(entry.parts_of_speech(), ["noun"]);
+        assert_eq!(entry.senses.len(), 2);
+        assert_eq!(entry.senses[1].text, "the tree which bears apples.");
+
+        let headings: Vec<&str> = entry.notes.iter().map(|(h, _)| h.as_str()).collect();
+        assert_eq!(headings, ["PHRASES", "DERIVATIVES", "ORIGIN"]);
+        assert_eq!(entry.notes[1].1, "appley adjective");
+    }
+
+    #[test]
+    fn test_parse_entry_parts_of_speech() {
+        let text = "run | rʌn | verb 1 move at a speed faster than a walk. 2 pass or cause to pass \
+            quickly: she ran 2 miles. noun 1 an act of running. 2 a journey. PHRASAL VERBS run off leave.";
+        let entry = parse_entry("run", text);
+        assert_eq!(entry.parts_of_speech(), ["verb", "noun"]);
+        // "2 miles" doesn't start a sense
+        assert_eq!(
+            entry.senses[1].text,
+            "pass or cause to pass quickly: she ran 2 miles."
+        );
+        assert_eq!(entry.senses[3].text, "a journey.");
+        assert_eq!(
+            entry.notes,
+            [("PHRASAL VERBS".to_string(), "run off leave.".to_string())]
+        );
+
+        // Thesaurus entries have no pronunciation and a single unnumbered sense
+        let entry = parse_entry("big", "big | adjective large, sizeable, substantial.");
+        assert_eq!(entry.pronunciation, None);
+        assert_eq!(entry.senses[0].text, "large, sizeable, substantial.");
+    }
+
+    #[test]
+    fn test_define_query() {
+        assert_eq!(define_query("define apple"), Some("apple"));
+        assert_eq!(define_query("define  ice cream "), Some("ice cream"));
+        assert_eq!(define_query("define"), None);
+        assert_eq!(define_query("define "), None);
+        assert_eq!(define_query("defined"), None);
+    }
+
+    #[test]
+    fn test_cache_reuses_lookups() {
+        let cache = DictionaryCache::new();
+        let mut calls = 0;
+        for word in ["apple", "Apple", "apple"] {
+            cache.get_with(word, || {
+                calls += 1;
+                Some(parse_entry("apple", APPLE))
+            });
+        }
+        assert_eq!(calls, 1);
+        cache.clear();
+        cache.get_with("apple", || {
+            calls += 1;
+            Some(parse_entry("apple", APPLE))
+        });
+        assert_eq!(calls, 2);
+
+        let entry = cache.get_with("apple", || None).unwrap();
+        let item = sense_item(&entry, 0, &entry.senses[0]);
+        assert_eq!(entry_from_item(&item), Some(entry));
+        assert_eq!(dictionary_url("ice cream"), "dict://ice%20cream");
+    }
+}
@@ -0,0 +1,485 @@
+//! Diagnostics (`lux doctor` and the Doctor view).
+//!
+//! This module provides:
+//! - `Check` / `Severity` / `Section` - One finding and how bad it is
+//! - `Diagnostics` - Checks only the host can make (config load, accessibility, hotkeys)
+//! - `diagnose()` - The full report: recorded checks, keymap conflicts, plugins, permissions, and indexes
+//! - `DoctorReport` - The report as text for the terminal, or as groups for the view
+//!
+//! The host records what it learns while starting up; `diagnose()` adds what
+//! the registry knows when asked, so the report is current each time it's shown.
+
+use std::process::Command;
+
+use parking_lot::Mutex;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, Item};
+
+use crate::permissions::{self, Permission, PermissionStatus};
+use crate::registry::PluginRegistry;
+
+/// ID of the built-in Doctor view.
+pub const DOCTOR_VIEW_ID: &str = "builtin.doctor";
+
+/// How a check turned out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Working.
+    Ok,
+    /// Working, but something may not behave as expected.
+    Warning,
+    /// Broken.
+    Error,
+}
+
+impl Severity {
+    /// Symbol shown in the terminal.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Severity::Ok => "✓",
+            Severity::Warning => "!",
+            Severity::Error => "✗",
+        }
+    }
+
+    /// Label shown in the launcher.
+    pub fn label(&self) -> String {
+        match self {
+            Severity::Ok => t("OK"),
+            Severity::Warning => t("Warning"),
+            Severity::Error => t("Error"),
+        }
+    }
+}
+
+/// What part of Lux a check is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Section {
+    /// init.lua.
+    Config,
+    /// Plugin views.
+    Plugins,
+    /// Global hotkeys.
+    Hotkeys,
+    /// Privacy permissions.
+    Permissions,
+    /// Background indexes (Spotlight, git repositories, recent files).
+    Index,
+}
+
+impl Section {
+    /// Title shown above the section's checks.
+    pub fn title(&self) -> String {
+        match self {
+            Section::Config => t("Config"),
+            Section::Plugins => t("Plugins"),
+            Section::Hotkeys => t("Hotkeys"),
+            Section::Permissions => t("Permissions"),
+            Section::Index => t("Index"),
+        }
+    }
+
+    fn id(&self) -> &'static str {
+        match self {
+            Section::Config => "config",
+            Section::Plugins => "plugins",
+            Section::Hotkeys => "hotkeys",
+            Section::Permissions => "permissions",
+            Section::Index => "index",
+        }
+    }
+}
+
+/// One finding of the report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    /// What part of Lux it's about.
+    pub section: Section,
+    /// What was checked (e.g. "init.lua", a view ID, a hotkey).
+    pub name: String,
+    /// How it turned out.
+    pub severity: Severity,
+    /// What was found.
+    pub detail: String,
+}
+
+impl Check {
+    /// Create a check.
+    pub fn new(
+        section: Section,
+        name: impl Into<String>,
+        severity: Severity,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            section,
+            name: name.into(),
+            severity,
+            detail: detail.into(),
+        }
+    }
+}
+
+// =============================================================================
+// Recorded Checks
+// =============================================================================
+
+/// Checks recorded by the host while it starts up.
+#[derive(Default)]
+pub struct Diagnostics {
+    checks: Mutex<Vec<Check>>,
+}
+
+impl Diagnostics {
+    /// Create an empty set of checks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a check, replacing an older one with the same section and name.
+    pub fn record(&self, check: Check) {
+        let mut checks = self.checks.lock();
+        match checks
+            .iter_mut()
+            .find(|c| c.section == check.section && c.name == check.name)
+        {
+            Some(existing) => *existing = check,
+            None => checks.push(check),
+        }
+    }
+
+    /// The recorded checks, oldest first.
+    pub fn checks(&self) -> Vec<Check> {
+        self.checks.lock().clone()
+    }
+}
+
+// =============================================================================
+// Report
+// =============================================================================
+
+/// All checks, grouped by section.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DoctorReport {
+    /// Checks in section order.
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// Build a report from checks in any order (each section keeps its order).
+    pub fn new(mut checks: Vec<Check>) -> Self {
+        checks.sort_by_key(|check| check.section);
+        Self { checks }
+    }
+
+    /// Number of checks with `severity`.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.severity == severity)
+            .count()
+    }
+
+    /// The report as text, one line per check.
+    pub fn render(&self) -> String {
+        let mut text = format!("Lux {} doctor\n", env!("CARGO_PKG_VERSION"));
+        let mut section = None;
+        for check in &self.checks {
+            if section != Some(check.section) {
+                section = Some(check.section);
+                text.push_str(&format!("\n{}\n", check.section.title()));
+            }
+            text.push_str(&format!("  {} {}", check.severity.symbol(), check.name));
+            if !check.detail.is_empty() {
+                text.push_str(&format!(": {}", check.detail));
+            }
+            text.push('\n');
+        }
+        text.push_str(&format!(
+            "\n{}\n",
+            t_args(
+                "{warnings} warnings, {errors} errors",
+                &[
+                    ("warnings", &self.count(Severity::Warning).to_string()),
+                    ("errors", &self.count(Severity::Error).to_string()),
+                ],
+            )
+        ));
+        text
+    }
+
+    /// The checks whose name or detail contains `query`, a group per section.
+    pub fn groups(&self, query: &str) -> Groups {
+        let query = query.trim().to_lowercase();
+        let mut groups: Vec<(Section, Vec<Item>)> = Vec::new();
+        for check in &self.checks {
+            if !check.name.to_lowercase().contains(&query)
+                && !check.detail.to_lowercase().contains(&query)
+            {
+                continue;
+            }
+            let item = Item::new(
+                format!("doctor:{}:{}", check.section.id(), check.name),
+                check.name.clone(),
+            )
+            .with_subtitle(check.detail.clone())
+            .with_accessory(check.severity.label());
+            match groups.last_mut() {
+                Some((section, items)) if *section == check.section => items.push(item),
+                _ => groups.push((check.section, vec![item])),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(section, items)| Group::new(section.title(), items))
+            .collect()
+    }
+}
+
+/// Run every check: those the host recorded, then the registry's.
+pub fn diagnose(registry: &PluginRegistry) -> DoctorReport {
+    let mut checks = registry.diagnostics().checks();
+    checks.extend(config_checks(registry));
+    checks.extend(conflict_checks(registry));
+    checks.extend(plugin_checks(registry));
+    checks.extend(third_party_checks(registry));
+    if cfg!(target_os = "macos") {
+        checks.extend(permission_checks());
+    }
+    checks.extend(index_checks(registry));
+    DoctorReport::new(checks)
+}
+
+/// A warning per unknown or deprecated field found in init.lua.
+fn config_checks(registry: &PluginRegistry) -> Vec<Check> {
+    registry
+        .config_warnings()
+        .list()
+        .into_iter()
+        .map(|warning| {
+            let detail = match &warning.location {
+                Some(location) => format!("{} ({})", warning.message, location),
+                None => warning.message.clone(),
+            };
+            Check::new(Section::Config, warning.subject, Severity::Warning, detail)
+        })
+        .collect()
+}
+
+/// A warning per unresolved keymap conflict.
+fn conflict_checks(registry: &PluginRegistry) -> Vec<Check> {
+    registry
+        .keymap()
+        .conflicts()
+        .list()
+        .into_iter()
+        .map(|conflict| {
+            let detail = format!("{} ({})", conflict.description(), conflict.source.label());
+            Check::new(Section::Hotkeys, conflict.key, Severity::Warning, detail)
+        })
+        .collect()
+}
+
+/// A check per plugin view, with errors from the metrics when they're on.
+fn plugin_checks(registry: &PluginRegistry) -> Vec<Check> {
+    let views = registry.views().list();
+    if views.is_empty() {
+        return vec![Check::new(
+            Section::Plugins,
+            t("Views"),
+            Severity::Warning,
+            t("No views registered; add some with lux.views.add() in init.lua"),
+        )];
+    }
+
+    let metrics = registry.metrics();
+    let stats = metrics.is_enabled().then(|| metrics.summary().plugins);
+    let disabled = registry.blocklist();
+    views
+        .into_iter()
+        .map(|view| {
+            let stats = stats
+                .as_ref()
+                .and_then(|plugins| plugins.iter().find(|(id, _)| *id == view))
+                .map(|(_, stats)| *stats);
+            let (severity, detail) = match stats {
+                _ if disabled.is_view_disabled(&view) => (Severity::Ok, t("Turned off")),
+                Some(stats) if stats.errors > 0 => (
+                    Severity::Warning,
+                    t_args(
+                        "{errors} errors in {calls} calls",
+                        &[
+                            ("errors", &stats.errors.to_string()),
+                            ("calls", &stats.calls.to_string()),
+                        ],
+                    ),
+                ),
+                Some(stats) => (
+                    Severity::Ok,
+                    t_args("{calls} calls", &[("calls", &stats.calls.to_string())]),
+                ),
+                None => (Severity::Ok, t("Loaded")),
+            };
+            Check::new(Section::Plugins, view, severity, detail)
+        })
+        .collect()
+}
+
+/// Restricted mode, and a check per plugin loaded with `lux.plugins.load()`.
+fn third_party_checks(registry: &PluginRegistry) -> Vec<Check> {
+    let security = registry.security();
+    let plugins = security.plugins();
+    let mut checks = Vec::new();
+    if !security.is_restricted() && !plugins.is_empty() {
+        checks.push(Check::new(
+            Section::Plugins,
+            t("Restricted mode"),
+            Severity::Warning,
+            t("Off; third-party plugins can run commands and write files"),
+        ));
+    }
+    checks.extend(plugins.into_iter().map(|plugin| {
+        let detail = if plugin.restricted {
+            t("Restricted")
+        } else {
+            t("Trusted")
+        };
+        Check::new(Section::Plugins, plugin.name, Severity::Ok, detail)
+    }));
+    checks
+}
+
+/// Privacy permissions (Accessibility is recorded by the host).
+fn permission_checks() -> Vec<Check> {
+    Permission::ALL
+        .into_iter()
+        .filter(|permission| *permission != Permission::Accessibility)
+        .map(|permission| {
+            let (severity, detail) = match permissions::status(permission) {
+                Ok(PermissionStatus::Granted) => (Severity::Ok, t("Granted")),
+                Ok(PermissionStatus::NotDetermined) => (Severity::Ok, t("Not requested yet")),
+                Ok(PermissionStatus::Denied | PermissionStatus::Restricted) => (
+                    Severity::Warning,
+                    t("Denied; sources using it show no results"),
+                ),
+                Err(e) => (Severity::Warning, e.to_string()),
+            };
+            Check::new(
+                Section::Permissions,
+                t(permission.display_name()),
+                severity,
+                detail,
+            )
+        })
+        .collect()
+}
+
+/// Spotlight, the git repository index, and the Downloads folder.
+fn index_checks(registry: &PluginRegistry) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        let (severity, detail) = match Command::new("mdutil").args(["-s", "/"]).output() {
+            Ok(output) => {
+                let text = String::from_utf8_lossy(&output.stdout);
+                if text.contains("Indexing enabled") {
+                    (Severity::Ok, t("Indexing enabled"))
+                } else {
+                    (
+                        Severity::Warning,
+                        t("Indexing is off; file search finds nothing"),
+                    )
+                }
+            }
+            Err(e) => (Severity::Warning, e.to_string()),
+        };
+        checks.push(Check::new(Section::Index, "Spotlight", severity, detail));
+    }
+
+    let git = registry.git();
+    let roots = git.roots();
+    let detail = if roots.is_empty() {
+        t("No roots set (lux.git.set_roots)")
+    } else {
+        t_args(
+            "{repos} repositories under {roots} roots",
+            &[
+                ("repos", &git.repos().len().to_string()),
+                ("roots", &roots.len().to_string()),
+            ],
+        )
+    };
+    checks.push(Check::new(Section::Index, "Git", Severity::Ok, detail));
+
+    let downloads = registry.recents().downloads_folder();
+    let (severity, detail) = if downloads.is_dir() {
+        (Severity::Ok, downloads.display().to_string())
+    } else {
+        (
+            Severity::Warning,
+            t_args(
+                "{path} doesn't exist",
+                &[("path", &downloads.display().to_string())],
+            ),
+        )
+    };
+    checks.push(Check::new(Section::Index, t("Downloads"), severity, detail));
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_groups_checks_by_section() {
+        let diagnostics = Diagnostics::new();
+        diagnostics.record(Check::new(
+            Section::Hotkeys,
+            "cmd+shift+space",
+            Severity::Ok,
+            "Registered",
+        ));
+        diagnostics.record(Check::new(
+            Section::Config,
+            "init.lua",
+            Severity::Error,
+            "syntax error near 'end'",
+        ));
+        // A newer result replaces the older one
+        diagnostics.record(Check::new(
+            Section::Config,
+            "init.lua",
+            Severity::Ok,
+            "Loaded",
+        ));
+
+        let report = DoctorReport::new(diagnostics.checks());
+        assert_eq!(report.checks.len(), 2);
+        assert_eq!(report.checks[0].section, Section::Config);
+        assert_eq!(report.count(Severity::Error), 0);
+
+        let text = report.render();
+        assert!(text.contains("\nConfig\n  ✓ init.lua: Loaded\n"));
+        assert!(text.ends_with("0 warnings, 0 errors\n"));
+
+        let groups = report.groups("cmd");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].title.as_deref(), Some("Hotkeys"));
+        assert_eq!(groups[0].items[0].id, "doctor:hotkeys:cmd+shift+space");
+    }
+
+    #[test]
+    fn test_diagnose_lists_views() {
+        let registry = PluginRegistry::new();
+        let report = diagnose(&registry);
+        let views = report
+            .checks
+            .iter()
+            .find(|check| check.section == Section::Plugins)
+            .unwrap();
+        assert_eq!(views.severity, Severity::Warning);
+    }
+}
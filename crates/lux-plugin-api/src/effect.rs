@@ -6,7 +6,7 @@
 
 use std::cell::RefCell;
 
-use lux_core::{Group, SelectionMode};
+use lux_core::{FollowUpAction, Group, HandlerError, InlineEdit, SelectionMode};
 
 /// An effect returned by a Lua callback.
 ///
@@ -32,11 +32,23 @@ pub enum Effect {
     /// Show progress indicator (for long-running actions).
     Progress(String),
 
-    /// Mark action as complete.
-    Complete { message: String },
+    /// Mark action as complete, offering follow-up actions.
+    Complete {
+        message: String,
+        actions: Vec<FollowUpAction>,
+    },
 
     /// Mark action as failed.
-    Fail { error: String },
+    Fail { error: HandlerError },
+
+    /// Put the item's row into inline edit mode.
+    Edit(InlineEdit),
+
+    /// Play a saved macro in the launcher.
+    PlayMacro(String),
+
+    /// Run a launcher action (e.g. `reload_config`) by name.
+    RunCommand(String),
 
     /// Show a notification (does not dismiss).
     Notify(String),
@@ -44,6 +56,11 @@ pub enum Effect {
     /// Set loading state.
     SetLoading(bool),
 
+    /// Replace the data of the view the callback ran in.
+    ///
+    /// Kept on the view instance for its later searches and actions.
+    SetViewData(serde_json::Value),
+
     // =========================================================================
     // Selection Effects (for on_select hook)
     // =========================================================================
@@ -71,8 +88,15 @@ pub struct ViewSpec {
     pub(crate) get_actions_fn_key: Option<String>,
     pub(crate) on_select_fn_key: Option<String>,
     pub(crate) on_submit_fn_key: Option<String>,
+    pub(crate) on_open_fn_key: Option<String>,
+    pub(crate) on_show_fn_key: Option<String>,
+    pub(crate) on_close_fn_key: Option<String>,
+    pub(crate) on_selection_changed_fn_key: Option<String>,
     pub(crate) selection_mode: SelectionMode,
     pub(crate) view_data: serde_json::Value,
+    pub(crate) selected: Vec<String>,
+    pub(crate) refresh_ms: Option<u64>,
+    pub(crate) query: Option<String>,
     /// Registry keys that need cleanup when the view is popped.
     pub(crate) registry_keys: Vec<String>,
 }
@@ -89,8 +113,15 @@ impl ViewSpec {
             get_actions_fn_key: None,
             on_select_fn_key: None,
             on_submit_fn_key: None,
+            on_open_fn_key: None,
+            on_show_fn_key: None,
+            on_close_fn_key: None,
+            on_selection_changed_fn_key: None,
             selection_mode: SelectionMode::Single,
             view_data: serde_json::Value::Null,
+            selected: Vec::new(),
+            refresh_ms: None,
+            query: None,
             registry_keys,
         }
     }
@@ -140,12 +171,58 @@ impl ViewSpec {
         self
     }
 
+    /// Set the on_open callback key.
+    pub fn with_on_open(mut self, key: String) -> Self {
+        self.registry_keys.push(key.clone());
+        self.on_open_fn_key = Some(key);
+        self
+    }
+
+    /// Set the on_show callback key.
+    pub fn with_on_show(mut self, key: String) -> Self {
+        self.registry_keys.push(key.clone());
+        self.on_show_fn_key = Some(key);
+        self
+    }
+
+    /// Set the on_close callback key.
+    pub fn with_on_close(mut self, key: String) -> Self {
+        self.registry_keys.push(key.clone());
+        self.on_close_fn_key = Some(key);
+        self
+    }
+
+    /// Set the on_selection_changed callback key.
+    pub fn with_on_selection_changed(mut self, key: String) -> Self {
+        self.registry_keys.push(key.clone());
+        self.on_selection_changed_fn_key = Some(key);
+        self
+    }
+
     /// Set view data.
     pub fn with_view_data(mut self, data: serde_json::Value) -> Self {
         self.view_data = data;
         self
     }
 
+    /// Set the IDs of the items selected when the view opens.
+    pub fn with_selected(mut self, ids: Vec<String>) -> Self {
+        self.selected = ids;
+        self
+    }
+
+    /// Re-run the view's search every `ms` milliseconds while it is shown.
+    pub fn with_refresh_ms(mut self, ms: u64) -> Self {
+        self.refresh_ms = Some(ms);
+        self
+    }
+
+    /// Fill the search input with `query` when the view is pushed.
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
     /// Get the registry keys for cleanup when the view is popped.
     pub fn registry_keys(&self) -> &[String] {
         &self.registry_keys
@@ -203,6 +280,45 @@ impl EffectCollector {
     pub fn len(&self) -> usize {
         self.effects.borrow().len()
     }
+
+    /// The data of the last `SetViewData` effect, if any.
+    pub fn latest_view_data(&self) -> Option<serde_json::Value> {
+        latest_view_data(&self.effects.borrow())
+    }
+
+    /// `selection` after the selection effects collected so far.
+    pub fn apply_selection(&self, selection: &[String]) -> Vec<String> {
+        apply_selection_effects(selection, &self.effects.borrow())
+    }
+}
+
+/// The data of the last `SetViewData` effect in `effects`, if any.
+pub fn latest_view_data(effects: &[Effect]) -> Option<serde_json::Value> {
+    effects.iter().rev().find_map(|effect| match effect {
+        Effect::SetViewData(data) => Some(data.clone()),
+        _ => None,
+    })
+}
+
+/// `selection` after the `Select`, `Deselect`, and `ClearSelection` effects
+/// in `effects`, applied in order.
+pub fn apply_selection_effects(selection: &[String], effects: &[Effect]) -> Vec<String> {
+    let mut selection = selection.to_vec();
+    for effect in effects {
+        match effect {
+            Effect::Select(ids) => {
+                for id in ids {
+                    if !selection.contains(id) {
+                        selection.push(id.clone());
+                    }
+                }
+            }
+            Effect::Deselect(ids) => selection.retain(|id| !ids.contains(id)),
+            Effect::ClearSelection => selection.clear(),
+            _ => {}
+        }
+    }
+    selection
 }
 
 #[cfg(test)]
@@ -226,16 +342,49 @@ mod tests {
         assert!(matches!(effects[1], Effect::Pop));
     }
 
+    #[test]
+    fn test_latest_view_data() {
+        let collector = EffectCollector::new();
+        assert_eq!(collector.latest_view_data(), None);
+
+        collector.push(Effect::SetViewData(serde_json::json!({ "page": 1 })));
+        collector.push(Effect::Dismiss);
+        collector.push(Effect::SetViewData(serde_json::json!({ "page": 2 })));
+        assert_eq!(
+            collector.latest_view_data(),
+            Some(serde_json::json!({ "page": 2 }))
+        );
+    }
+
+    #[test]
+    fn test_apply_selection_effects() {
+        let selection = vec!["a".to_string(), "b".to_string()];
+        let effects = vec![
+            Effect::Deselect(vec!["a".to_string()]),
+            Effect::Select(vec!["c".to_string(), "b".to_string()]),
+            Effect::Dismiss,
+        ];
+        assert_eq!(apply_selection_effects(&selection, &effects), ["b", "c"]);
+
+        let effects = vec![
+            Effect::ClearSelection,
+            Effect::Select(vec!["d".to_string()]),
+        ];
+        assert_eq!(apply_selection_effects(&selection, &effects), ["d"]);
+    }
+
     #[test]
     fn test_view_spec_builder() {
         let spec = ViewSpec::new("test:source".to_string())
             .with_title("Test View")
             .with_placeholder("Search...")
-            .with_selection_mode(SelectionMode::Multi);
+            .with_selection_mode(SelectionMode::Multi)
+            .with_selected(vec!["a".to_string()]);
 
         assert_eq!(spec.title, Some("Test View".to_string()));
         assert_eq!(spec.placeholder, Some("Search...".to_string()));
         assert_eq!(spec.selection_mode, SelectionMode::Multi);
+        assert_eq!(spec.selected, vec!["a".to_string()]);
         assert_eq!(spec.source_fn_key, "test:source");
     }
 }
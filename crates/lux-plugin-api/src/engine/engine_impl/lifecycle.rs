@@ -0,0 +1,96 @@
+//! View lifecycle callbacks for the query engine.
+//!
+//! Views can define three optional callbacks, each called with a
+//! `LifecycleContext`:
+//! - `on_open(ctx)` - after the view is pushed (or replaces another view)
+//! - `on_show(ctx)` - when the launcher is shown while the view is on top
+//! - `on_close(ctx)` - after the view is popped or replaced
+//!
+//! View data set in `on_open` or `on_show` is stored on the view instance.
+//! Callback errors are logged rather than returned, so a failing callback
+//! never blocks navigation.
+
+use mlua::Lua;
+
+use crate::effect::latest_view_data;
+use crate::engine::observable_view_stack::ObservableViewStack;
+use crate::lua::{call_view_lifecycle, cleanup_view_registry_keys};
+use crate::types::{LuaFunctionRef, View, ViewInstance};
+
+/// A view lifecycle callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleHook {
+    /// `on_open` - the view was pushed.
+    Open,
+    /// `on_show` - the launcher was shown on the view.
+    Show,
+    /// `on_close` - the view was popped or replaced.
+    Close,
+}
+
+impl LifecycleHook {
+    /// Name of the callback in the view table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Open => "on_open",
+            Self::Show => "on_show",
+            Self::Close => "on_close",
+        }
+    }
+
+    /// The view's callback for this hook, if it defines one.
+    fn callback(self, view: &View) -> Option<&LuaFunctionRef> {
+        match self {
+            Self::Open => view.on_open_fn.as_ref(),
+            Self::Show => view.on_show_fn.as_ref(),
+            Self::Close => view.on_close_fn.as_ref(),
+        }
+    }
+}
+
+/// Run the top view's `on_open` or `on_show` callback.
+///
+/// Stores the view data the callback set on the view it ran for.
+pub fn run_top_view_hook(lua: &Lua, view_stack: &ObservableViewStack, hook: LifecycleHook) {
+    let Some((fn_key, view_data)) = view_stack
+        .with_top(|view| {
+            hook.callback(&view.view)
+                .map(|f| (f.key.clone(), view.view.view_data.clone()))
+        })
+        .flatten()
+    else {
+        return;
+    };
+
+    let effects = match call_view_lifecycle(lua, &fn_key, &view_data) {
+        Ok(effects) => effects,
+        Err(e) => {
+            tracing::warn!("View {} failed: {}", hook.name(), e);
+            return;
+        }
+    };
+
+    // Keep view data set by the callback, unless the callback changed the view
+    if let Some(data) = latest_view_data(&effects) {
+        view_stack.modify_top(|view| {
+            if hook.callback(&view.view).is_some_and(|f| f.key == fn_key) {
+                view.view.view_data = data;
+            }
+        });
+    }
+}
+
+/// Run `on_close` for views removed from the stack, then free their callbacks.
+///
+/// `views` are in stack order (bottom to top); the topmost view closes first.
+pub fn close_views(lua: &Lua, views: Vec<ViewInstance>) {
+    for view in views.into_iter().rev() {
+        let hook = LifecycleHook::Close;
+        if let Some(on_close) = hook.callback(&view.view) {
+            if let Err(e) = call_view_lifecycle(lua, &on_close.key, &view.view.view_data) {
+                tracing::warn!("View {} failed: {}", hook.name(), e);
+            }
+        }
+        cleanup_view_registry_keys(lua, &view.registry_keys);
+    }
+}
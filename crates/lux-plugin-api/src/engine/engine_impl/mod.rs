@@ -1,7 +1,13 @@
 //! Query engine submodules.
 
+mod lifecycle;
+mod shortcuts;
+mod shutdown;
 mod sources;
 pub mod types;
 
-pub(super) use sources::run_current_view_source;
+pub(super) use lifecycle::{close_views, run_top_view_hook, LifecycleHook};
+pub(super) use shortcuts::ActionShortcuts;
+pub(super) use shutdown::run_shutdown_hooks;
+pub(super) use sources::{run_current_view_source, run_routed_view_source};
 pub use types::*;
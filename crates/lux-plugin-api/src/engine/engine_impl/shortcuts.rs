@@ -0,0 +1,84 @@
+//! Keyboard shortcuts for view actions.
+//!
+//! Actions returned by `get_actions` can declare `shortcut = "cmd+o"`. Each
+//! time the engine lists actions, it registers their shortcuts as ephemeral
+//! bindings for the current view instance, replacing the previous ones.
+//! Bindings registered for a view stop applying once another view is on top.
+
+use super::types::ActionInfo;
+
+/// Ephemeral shortcut bindings for the actions last listed in a view.
+#[derive(Debug, Default)]
+pub struct ActionShortcuts {
+    /// View instance the actions were listed for.
+    instance_id: Option<u64>,
+    /// Listed actions that declare a shortcut.
+    bindings: Vec<ActionInfo>,
+}
+
+impl ActionShortcuts {
+    /// Replace the bindings with the shortcuts of `actions`.
+    pub fn register(&mut self, instance_id: u64, actions: &[ActionInfo]) {
+        self.instance_id = Some(instance_id);
+        self.bindings = actions
+            .iter()
+            .filter(|action| action.shortcut.is_some())
+            .cloned()
+            .collect();
+    }
+
+    /// The action bound to `shortcut` in the view instance, if any.
+    ///
+    /// `shortcut` must be normalized (see [`crate::keymap::normalize_shortcut`]).
+    /// The first listed action wins when two declare the same shortcut.
+    pub fn lookup(&self, instance_id: u64, shortcut: &str) -> Option<&ActionInfo> {
+        if self.instance_id != Some(instance_id) {
+            return None;
+        }
+        self.bindings
+            .iter()
+            .find(|action| action.shortcut.as_deref() == Some(shortcut))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, shortcut: Option<&str>) -> ActionInfo {
+        ActionInfo {
+            view_id: "files".to_string(),
+            id: id.to_string(),
+            title: id.to_string(),
+            icon: None,
+            bulk: false,
+            shortcut: shortcut.map(str::to_string),
+            section: None,
+            destructive: false,
+            handler_key: Some(format!("action:{}", id)),
+        }
+    }
+
+    #[test]
+    fn test_shortcuts_are_scoped_to_the_view_instance() {
+        let mut shortcuts = ActionShortcuts::default();
+        shortcuts.register(
+            1,
+            &[
+                action("open", Some("cmd+o")),
+                action("copy", None),
+                action("reveal", Some("cmd+o")),
+            ],
+        );
+
+        let bound = shortcuts.lookup(1, "cmd+o").map(|a| a.id.as_str());
+        assert_eq!(bound, Some("open"));
+        assert!(shortcuts.lookup(1, "cmd+c").is_none());
+        assert!(shortcuts.lookup(2, "cmd+o").is_none());
+
+        // Listing actions again replaces the bindings
+        shortcuts.register(2, &[action("copy", Some("cmd+c"))]);
+        assert!(shortcuts.lookup(1, "cmd+o").is_none());
+        assert!(shortcuts.lookup(2, "cmd+c").is_some());
+    }
+}
@@ -0,0 +1,34 @@
+//! Shutdown hooks for the query engine.
+//!
+//! Plugins register `lux.hook("on_shutdown", fn)` to save state when Lux
+//! quits. Each hook is called with the milliseconds left before the
+//! deadline; once it passes, the remaining hooks are skipped. Errors are
+//! logged, so one failing hook doesn't stop the others.
+
+use std::time::Instant;
+
+use mlua::Lua;
+
+use crate::registry::PluginRegistry;
+
+/// Run the `on_shutdown` hooks in registration order until `deadline`.
+///
+/// Returns the number of hooks that ran.
+pub fn run_shutdown_hooks(lua: &Lua, registry: &PluginRegistry, deadline: Instant) -> usize {
+    let hooks = registry.hooks().get_chain("on_shutdown", None);
+    let mut ran = 0;
+    for hook in &hooks {
+        let Some(left) = deadline.checked_duration_since(Instant::now()) else {
+            tracing::warn!(
+                "Shutdown deadline passed, skipping {} on_shutdown hooks",
+                hooks.len() - ran
+            );
+            break;
+        };
+        if let Err(e) = hook.call::<_, ()>(lua, left.as_millis() as u64) {
+            tracing::warn!("on_shutdown hook failed: {}", e);
+        }
+        ran += 1;
+    }
+    ran
+}
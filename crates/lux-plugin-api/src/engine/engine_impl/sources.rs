@@ -1,14 +1,17 @@
 //! Source searching for the query engine.
 //!
-//! This module handles running the current view's search function
-//! and extracting results from the effects.
+//! This module handles running the current view's search function (or the
+//! search of the view a query is routed to) and extracting results (and view
+//! data changes) from the effects.
 
 use mlua::Lua;
 
 use crate::effect::Effect;
 use crate::engine::observable_view_stack::ObservableViewStack;
 use crate::lua::call_hooked_search;
+use crate::memory::ROOT_VIEW_NAME;
 use crate::registry::PluginRegistry;
+use crate::rewrite::RewrittenQuery;
 use lux_core::Groups;
 
 /// Run the current view's source function.
@@ -19,11 +22,14 @@ use lux_core::Groups;
 /// If hooks are registered for "search", they are executed in chain:
 /// each hook receives `(query, ctx, original)` and can call `original()`
 /// to continue to the next hook or the actual search function.
+///
+/// The source searches with the rewritten query; `ctx.original_query` is the
+/// query as typed.
 pub fn run_current_view_source(
     registry: &PluginRegistry,
     view_stack: &ObservableViewStack,
     lua: &Lua,
-    query: &str,
+    query: &RewrittenQuery,
 ) -> Result<Groups, String> {
     // Get current view's source function, view_data, and view_id
     let (source_key, view_data, view_id) = view_stack
@@ -36,28 +42,105 @@ pub fn run_current_view_source(
         })
         .ok_or_else(|| "No current view".to_string())?;
 
-    // Get hook chain for "search" (view-specific + global)
+    // Get hook chain for "search" (view-specific + global), minus hooks the
+    // active profile turns off
     let hook_registry = registry.hooks();
-    let hooks = hook_registry.get_chain("search", view_id.as_deref());
+    let profiles = registry.profiles();
+    let hooks = hook_registry.get_chain_where("search", view_id.as_deref(), |path| {
+        profiles.is_hook_enabled(path)
+    });
     let hook_keys: Vec<String> = hooks.iter().map(|h| h.key.clone()).collect();
 
     // Call via the bridge with hook chain (handles empty case transparently)
-    let effects = call_hooked_search(lua, &source_key, &hook_keys, query, &view_data)
+    let metrics = registry.metrics();
+    let effects = registry
+        .memory()
+        .measure(lua, view_id.as_deref(), || {
+            metrics.measure(view_id.as_deref().unwrap_or(ROOT_VIEW_NAME), || {
+                call_hooked_search(
+                    lua,
+                    &source_key,
+                    &hook_keys,
+                    &query.query,
+                    &query.original,
+                    &view_data,
+                    view_id.as_deref().unwrap_or(ROOT_VIEW_NAME),
+                )
+            })
+        })
+        .map_err(|e| format!("Source search failed: {}", e))?;
+
+    let (groups, new_view_data) = split_search_effects(effects);
+
+    // Keep view data set by the search, unless the view changed while it ran
+    if let Some(data) = new_view_data {
+        view_stack.modify_top(|view| {
+            if view.view.source_fn.key == source_key {
+                view.view.view_data = data;
+            }
+        });
+    }
+
+    Ok(groups)
+}
+
+/// Run the search function of the registered view `view_id`, which claimed
+/// the query through a route (see [`crate::routes`]).
+///
+/// The view is not pushed, so it searches without view data and any view
+/// data it sets is dropped.
+pub fn run_routed_view_source(
+    registry: &PluginRegistry,
+    lua: &Lua,
+    view_id: &str,
+    query: &RewrittenQuery,
+) -> Result<Groups, String> {
+    let source_key = registry
+        .views()
+        .with_view(view_id, |view| view.search_fn.key.clone())
+        .ok_or_else(|| format!("View '{}' not found", view_id))?;
+
+    let hook_registry = registry.hooks();
+    let profiles = registry.profiles();
+    let hooks = hook_registry.get_chain_where("search", Some(view_id), |path| {
+        profiles.is_hook_enabled(path)
+    });
+    let hook_keys: Vec<String> = hooks.iter().map(|h| h.key.clone()).collect();
+
+    let metrics = registry.metrics();
+    let effects = registry
+        .memory()
+        .measure(lua, Some(view_id), || {
+            metrics.measure(view_id, || {
+                call_hooked_search(
+                    lua,
+                    &source_key,
+                    &hook_keys,
+                    &query.query,
+                    &query.original,
+                    &serde_json::Value::Null,
+                    view_id,
+                )
+            })
+        })
         .map_err(|e| format!("Source search failed: {}", e))?;
 
-    // Extract groups from the SetGroups effect
-    Ok(extract_groups_from_effects(effects))
+    Ok(split_search_effects(effects).0)
 }
 
-/// Extract groups from a list of effects.
+/// Split a search's effects into its groups and its latest view data.
 ///
-/// Looks for the SetGroups effect and returns its contents.
-/// If no SetGroups effect, returns empty groups.
-fn extract_groups_from_effects(effects: Vec<Effect>) -> Groups {
+/// Uses the first SetGroups effect (empty groups if there is none) and the
+/// last SetViewData effect.
+fn split_search_effects(effects: Vec<Effect>) -> (Groups, Option<serde_json::Value>) {
+    let mut groups = None;
+    let mut view_data = None;
     for effect in effects {
-        if let Effect::SetGroups(groups) = effect {
-            return groups;
+        match effect {
+            Effect::SetGroups(g) if groups.is_none() => groups = Some(g),
+            Effect::SetViewData(data) => view_data = Some(data),
+            _ => {}
         }
     }
-    Groups::new()
+    (groups.unwrap_or_default(), view_data)
 }
@@ -16,6 +16,22 @@ pub struct ActionInfo {
     pub icon: Option<String>,
     /// Whether this action supports bulk selection.
     pub bulk: bool,
+    /// Normalized keyboard shortcut that runs the action, like `"cmd+o"`.
+    #[serde(default)]
+    pub shortcut: Option<String>,
+    /// Menu section the action is listed under (empty for an untitled section).
+    #[serde(default)]
+    pub section: Option<String>,
+    /// Whether the action is destructive and needs confirmation before it runs.
+    #[serde(default)]
+    pub destructive: bool,
     /// Lua registry key for the action handler function.
     pub handler_key: Option<String>,
 }
+
+impl ActionInfo {
+    /// Key to execute the action with: the handler key, or the action ID.
+    pub fn handler_key(&self) -> &str {
+        self.handler_key.as_deref().unwrap_or(&self.id)
+    }
+}
@@ -4,6 +4,16 @@
 //! - View source searching
 //! - Action filtering and execution
 //! - View stack management
+//! - View lifecycle callbacks (on_open, on_show, on_close)
+//! - `on_shutdown` hooks when Lux quits
+//! - Saved UI state per view instance, restored after a pop
+//! - Every executed action recorded in the activity log (see [`crate::activity`]),
+//!   and the latest one repeated on request
+//! - Query rewriting before sources run (see [`crate::rewrite`])
+//! - Query routing at the root view (see [`crate::routes`])
+//! - Opening on a view per frontmost app (see [`crate::rules`])
+//! - Detached engines running a single view in its own window, with their
+//!   own view stack and without the root view's built-in sources
 //!
 //! ## Query Flow
 //!
@@ -11,32 +21,107 @@
 //! User types query
 //!        │
 //!        ▼
+//! Normalization,
+//! aliases, and
+//! query middlewares
+//!        │
+//!        ▼
+//! Routed view's search
+//! (root only, if a
+//! route matches; skips
+//! the steps below
+//! but the last)
+//!        │
+//!        ▼
 //! Current view's
 //! search(query, ctx)
 //!        │
 //!        ▼
+//! Timers, Now Playing,
+//! network, detection,
+//! colors, currency,
+//! dates, world clock,
+//! QR codes, captures,
+//! recent files,
+//! SSH hosts, config files,
+//! git repos, Spotlight,
+//! dictionary, web search
+//! (root only,
+//! unless the active
+//! profile disables them)
+//!        │
+//!        ▼
+//! Pinned results
+//! moved to the top
+//! (root only)
+//!        │
+//!        ▼
+//! Configured group
+//! order and weights
+//!        │
+//!        ▼
 //! Return Groups to frontend
 //! ```
 
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Instant;
 
 use mlua::Lua;
 use parking_lot::Mutex;
-use tokio::sync::watch;
-
-use crate::effect::{Effect, ViewSpec};
+use tokio::sync::{broadcast, watch};
+
+use crate::activity::{ActivityEntry, RepeatOutcome, KEYBINDING_ACTION};
+use crate::archive::{archive_action_key, archive_actions};
+use crate::basket::{basket_action, basket_action_key, BASKET_SHORTCUT, BASKET_VIEW_ID};
+use crate::capture::{capture_action_key, capture_actions, CAPTURE_ITEM_TYPE};
+use crate::colors::{color_action_key, color_actions, color_groups, COLOR_ITEM_TYPE};
+use crate::config_files::{
+    config_file_action_key, config_file_actions, config_files_group, ConfigPaths,
+    CONFIG_FILE_ITEM_TYPE,
+};
+use crate::currency::{currency_action_key, currency_actions, CURRENCY_ITEM_TYPE};
+use crate::dates::{date_action_key, date_actions, date_group, DATE_ITEM_TYPE};
+use crate::detect::{
+    detect_item, detection_item, smart_action_key, smart_actions, DETECTED_ITEM_TYPE,
+};
+use crate::dictionary::{
+    dictionary_action_key, dictionary_actions, dictionary_groups, DEFINITION_ITEM_TYPE,
+};
+use crate::effect::{apply_selection_effects, latest_view_data, Effect, ViewSpec};
+use crate::git::{git_action_key, git_actions, GIT_ITEM_TYPE};
+use crate::hidden::{hide_action, HIDE_ACTION_KEY};
+use crate::keymap::normalize_shortcut;
 use crate::lua::cleanup_view_registry_keys;
+use crate::media::{media_actions, media_command_key, media_group, MEDIA_ITEM_TYPE};
+use crate::memory::ROOT_VIEW_NAME;
+use crate::network::{network_action_key, network_actions, network_group, NETWORK_ITEM_TYPE};
+use crate::pins::{pin_action, pin_action_key, PIN_SHORTCUT};
+use crate::profiles::ProfileContext;
+use crate::qr::{qr_action_key, qr_actions, qr_group, QR_ITEM_TYPE};
+use crate::recent::{recent_action_key, recent_actions, RECENT_ITEM_TYPE};
 use crate::registry::PluginRegistry;
-use crate::types::{LuaFunctionRef, View, ViewInstance, ViewState};
-use lux_core::{ActionResult, Group, Groups, Item, SelectionMode};
+use crate::spotlight::{spotlight_action_key, spotlight_actions, SPOTLIGHT_ITEM_TYPE};
+use crate::ssh::{ssh_action_key, ssh_actions, ssh_group, SSH_ITEM_TYPE};
+use crate::timers::{TIMER_CANCEL_KEY, TIMER_ITEM_TYPE};
+use crate::types::{LuaFunctionRef, SavedViewState, View, ViewInstance, ViewState};
+use crate::undo::{move_back, UndoInfo, UndoOp};
+use crate::websearch::{WEBSEARCH_ITEM_TYPE, WEBSEARCH_OPEN_KEY};
+use crate::worldclock::{clock_action_key, clock_actions, CLOCK_ITEM_TYPE};
+use lux_core::i18n::t;
+use lux_core::{
+    ActionResult, FollowUpAction, Group, Groups, HandlerError, InlineEdit, Item, SelectionMode,
+};
 
 // Import submodules
 mod engine_impl;
 mod observable_view_stack;
 
-// Re-export ActionInfo from submodules
+// Re-export ActionInfo and the view stack change events from submodules
 pub use engine_impl::ActionInfo;
+use engine_impl::{ActionShortcuts, LifecycleHook};
 use observable_view_stack::ObservableViewStack;
+pub use observable_view_stack::{ViewStackChange, ViewStackEvent};
 
 // =============================================================================
 // Query Engine
@@ -49,7 +134,8 @@ use observable_view_stack::ObservableViewStack;
 ///
 /// ## Reactive State
 ///
-/// The view stack is observable - subscribe to changes via `subscribe()`.
+/// The view stack is observable - subscribe to changes via `subscribe()`, or
+/// to typed change events via `subscribe_changes()`.
 /// All mutations (push, pop, replace) automatically broadcast to subscribers.
 pub struct QueryEngine {
     /// Plugin registry containing all registered plugins.
@@ -61,6 +147,24 @@ pub struct QueryEngine {
 
     /// Current query generation for async cancellation.
     query_generation: Mutex<u64>,
+
+    /// Shortcuts of the actions last listed for the current view.
+    action_shortcuts: Mutex<ActionShortcuts>,
+
+    /// Registry keys of the follow-up handlers offered by the last completion.
+    follow_up_keys: Mutex<Vec<String>>,
+
+    /// Registry key of the `on_submit` callback of the last inline edit.
+    edit_key: Mutex<Option<String>>,
+
+    /// View the last root query was routed to, which supplies its actions.
+    routed_view: Mutex<Option<String>>,
+
+    /// Handler keys of the destructive actions listed so far.
+    destructive_keys: Mutex<HashSet<String>>,
+
+    /// Backs a detached window, whose bottom view isn't the launcher root.
+    detached: bool,
 }
 
 impl QueryEngine {
@@ -70,6 +174,23 @@ impl QueryEngine {
             registry,
             view_stack: ObservableViewStack::new(),
             query_generation: Mutex::new(0),
+            action_shortcuts: Mutex::new(ActionShortcuts::default()),
+            follow_up_keys: Mutex::new(Vec::new()),
+            edit_key: Mutex::new(None),
+            routed_view: Mutex::new(None),
+            destructive_keys: Mutex::new(HashSet::new()),
+            detached: false,
+        }
+    }
+
+    /// Create an engine for a view detached into its own window.
+    ///
+    /// Its bottom view isn't the launcher root, so built-in sources, query
+    /// routes, and pins stay off. Start it with `initialize_detached()`.
+    pub fn detached(registry: Arc<PluginRegistry>) -> Self {
+        Self {
+            detached: true,
+            ..Self::new(registry)
         }
     }
 
@@ -81,13 +202,20 @@ impl QueryEngine {
         self.view_stack.subscribe()
     }
 
+    /// Subscribe to view stack change events (pushed, popped, replaced, updated).
+    ///
+    /// Unlike `subscribe()`, no change is skipped; see [`ViewStackEvent`].
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<ViewStackEvent> {
+        self.view_stack.subscribe_changes()
+    }
+
     /// Initialize with the root view.
     ///
     /// Uses the custom root view if set via `lux.set_root()`, otherwise
-    /// creates an empty default view.
-    pub fn initialize(&self, _lua: &Lua) {
+    /// creates an empty default view. Existing views are closed first.
+    pub fn initialize(&self, lua: &Lua) {
         // Clear any existing views
-        self.view_stack.clear();
+        engine_impl::close_views(lua, self.view_stack.clear());
 
         // Use custom root view if set, otherwise create empty default
         let root_view = self.registry.take_root_view().unwrap_or_else(|| {
@@ -95,20 +223,57 @@ impl QueryEngine {
             View {
                 id: None,
                 title: None,
-                placeholder: Some("Search...".to_string()),
+                placeholder: Some(t("Search...")),
                 source_fn: LuaFunctionRef::new("empty:source".to_string()),
                 get_actions_fn: None,
                 selection: SelectionMode::Single,
                 on_select_fn: None,
                 on_submit_fn: None,
+                on_open_fn: None,
+                on_show_fn: None,
+                on_close_fn: None,
+                on_selection_changed_fn: None,
                 view_data: serde_json::Value::Null,
+                selected: Vec::new(),
+                refresh_ms: None,
+                initial_query: None,
             }
         });
 
         self.view_stack.push(ViewInstance::new(root_view));
+        engine_impl::run_top_view_hook(lua, &self.view_stack, LifecycleHook::Open);
         tracing::debug!("QueryEngine initialized with root view");
     }
 
+    /// Initialize with the registered view `view_id` as the bottom view.
+    ///
+    /// Used by detached engines. Existing views are closed first. Returns
+    /// false, leaving the stack alone, if no view with that ID is registered.
+    pub fn initialize_detached(&self, lua: &Lua, view_id: &str) -> bool {
+        let Some(view) = self
+            .registry
+            .views()
+            .with_view(view_id, |view| view.to_view())
+        else {
+            return false;
+        };
+        engine_impl::close_views(lua, self.view_stack.clear());
+        self.open_view(lua, ViewInstance::new(view));
+        true
+    }
+
+    /// Close every view on the stack, topmost first, running their `on_close`.
+    ///
+    /// Call when a detached window closes.
+    pub fn close(&self, lua: &Lua) {
+        engine_impl::close_views(lua, self.view_stack.clear());
+    }
+
+    /// Whether the current view is the launcher's root view.
+    fn at_root(&self) -> bool {
+        !self.detached && self.view_stack.len() == 1
+    }
+
     // =========================================================================
     // View Stack Operations
     // =========================================================================
@@ -123,25 +288,95 @@ impl QueryEngine {
         self.view_stack.get_states()
     }
 
-    /// Push a new view onto the stack.
+    /// Push a new view onto the stack and run its `on_open`.
     ///
     /// Broadcasts the new state to subscribers.
-    pub fn push_view(&self, view: View) {
-        self.view_stack.push(ViewInstance::new(view));
+    pub fn push_view(&self, lua: &Lua, view: View) {
+        self.open_view(lua, ViewInstance::new(view));
     }
 
-    /// Replace the current view.
+    /// Replace the current view, running `on_close` for the old view and
+    /// `on_open` for the new one.
     ///
     /// Broadcasts the new state to subscribers.
-    pub fn replace_view(&self, view: View) {
-        self.view_stack.replace_top(ViewInstance::new(view));
+    pub fn replace_view(&self, lua: &Lua, view: View) {
+        self.replace_top_view(lua, ViewInstance::new(view));
     }
 
-    /// Pop the current view and return to the previous one.
+    /// Pop the current view and return to the previous one, running its `on_close`.
     ///
     /// Returns false if already at root. Broadcasts the new state to subscribers.
-    pub fn pop_view(&self) -> bool {
-        self.view_stack.pop_if_not_root()
+    pub fn pop_view(&self, lua: &Lua) -> bool {
+        match self.view_stack.pop_if_not_root() {
+            Some(view) => {
+                engine_impl::close_views(lua, vec![view]);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Pop back to the view at `index` (0 is the root), e.g. from a breadcrumb.
+    ///
+    /// Runs `on_close` for every popped view, topmost first. Returns false if
+    /// `index` is already the current view or out of range.
+    /// Broadcasts the new state to subscribers.
+    pub fn pop_to_view(&self, lua: &Lua, index: usize) -> bool {
+        let popped = self.view_stack.truncate(index + 1);
+        if popped.is_empty() {
+            return false;
+        }
+        engine_impl::close_views(lua, popped);
+        true
+    }
+
+    /// Run the current view's `on_show`. Call when the launcher is shown.
+    pub fn view_shown(&self, lua: &Lua) {
+        engine_impl::run_top_view_hook(lua, &self.view_stack, LifecycleHook::Show);
+    }
+
+    /// Open on the view of the first frontmost-app rule matching `context`
+    /// (see [`crate::rules`]), on top of the root view.
+    ///
+    /// Call when the launcher is shown, before `view_shown`. Returns the
+    /// view's ID, or None (leaving the stack alone) if no rule matches.
+    pub fn open_for_app(&self, lua: &Lua, context: &ProfileContext) -> Option<String> {
+        let views = self.registry.views();
+        let view_id = self.registry.app_rules().view_for(context, |id| {
+            views.exists(id) && self.is_view_id_enabled(id)
+        })?;
+
+        let states = self.view_stack.get_states();
+        if states.len() == 2 && states[1].id.as_deref() == Some(view_id.as_str()) {
+            // Already open from the last time
+            return Some(view_id);
+        }
+        let view = views.with_view(&view_id, |view| view.to_view())?;
+        self.pop_to_view(lua, 0);
+        self.push_view(lua, view);
+        Some(view_id)
+    }
+
+    /// Save the UI state of a view instance, restored when it is shown again.
+    ///
+    /// Returns `false` if the instance is no longer on the stack.
+    pub fn save_view_state(&self, instance_id: u64, state: SavedViewState) -> bool {
+        self.view_stack
+            .modify_instance(instance_id, |view| view.saved = Some(state))
+    }
+
+    /// Push a view instance and run its `on_open`.
+    fn open_view(&self, lua: &Lua, instance: ViewInstance) {
+        self.view_stack.push(instance);
+        engine_impl::run_top_view_hook(lua, &self.view_stack, LifecycleHook::Open);
+    }
+
+    /// Replace the top view, closing the old one and opening the new one.
+    fn replace_top_view(&self, lua: &Lua, instance: ViewInstance) {
+        if let Some(old_view) = self.view_stack.replace_top(instance) {
+            engine_impl::close_views(lua, vec![old_view]);
+        }
+        engine_impl::run_top_view_hook(lua, &self.view_stack, LifecycleHook::Open);
     }
 
     // =========================================================================
@@ -150,16 +385,199 @@ impl QueryEngine {
 
     /// Execute a search query.
     ///
-    /// Runs the current view's search function and returns the results.
+    /// Rewrites the query (see [`crate::rewrite`]), then runs the current
+    /// view's search function, or at the root the search of the view the
+    /// query is routed to (see [`crate::routes`]), and returns the results.
     pub fn search(&self, lua: &Lua, query: &str) -> Result<Groups, String> {
+        let start = Instant::now();
+        let result = self.run_search(lua, query);
+        self.registry.metrics().record_query(start.elapsed());
+        result
+    }
+
+    fn run_search(&self, lua: &Lua, query: &str) -> Result<Groups, String> {
         // Increment generation for async cancellation
         {
             let mut gen = self.query_generation.lock();
             *gen += 1;
         }
 
+        let view_id = self
+            .view_stack
+            .with_top(|view| view.view.id.clone())
+            .flatten();
+        let rewritten = self
+            .registry
+            .query_rewriter()
+            .rewrite(lua, query, view_id.as_deref());
+
+        // A route claims the whole query at the root view
+        let routed = self
+            .at_root()
+            .then(|| {
+                let views = self.registry.views();
+                self.registry.routes().route(lua, &rewritten.query, |id| {
+                    views.exists(id) && self.is_view_id_enabled(id)
+                })
+            })
+            .flatten();
+        *self.routed_view.lock() = routed.clone();
+        if let Some(routed) = routed {
+            let mut groups =
+                engine_impl::run_routed_view_source(&self.registry, lua, &routed, &rewritten)?;
+            self.registry.ordering().apply(Some(&routed), &mut groups);
+            return Ok(groups);
+        }
+
         // Run current view's source
-        engine_impl::run_current_view_source(&self.registry, &self.view_stack, lua, query)
+        let mut groups = engine_impl::run_current_view_source(
+            &self.registry,
+            &self.view_stack,
+            lua,
+            &rewritten,
+        )?;
+        let query = rewritten.query.as_str();
+
+        // Timers, Now Playing, network info, smart detection, colors, currency
+        // conversions, dates, world clocks, QR codes, screen captures, recent
+        // files, SSH hosts, config files, git repositories, Spotlight, the
+        // dictionary, and web search only apply at the root view
+        if self.at_root() {
+            // Sources turned off by the active profile or the blocklist are skipped
+            let profiles = self.registry.profiles();
+            let blocklist = self.registry.blocklist();
+            let enabled = |source: &str| {
+                profiles.is_source_enabled(source) && !blocklist.is_source_disabled(source)
+            };
+
+            if enabled("media") {
+                let now_playing = self.registry.media().get();
+                if let Some(group) = media_group(query, now_playing.as_ref()) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("network") {
+                if let Some(group) = network_group(query) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("timers") {
+                if let Some(group) = self.registry.timers().group(query, Instant::now()) {
+                    groups.insert(0, group);
+                }
+            }
+
+            // A typed color gets the color tools instead of the detection row
+            let colors = if enabled("colors") {
+                color_groups(query, &self.registry.colors())
+            } else {
+                Vec::new()
+            };
+            if colors.is_empty() && enabled("detect") {
+                if let Some(item) = detection_item(query) {
+                    groups.insert(0, Group::new(t("Detected"), vec![item]));
+                }
+            }
+            groups.splice(0..0, colors);
+
+            if enabled("currency") {
+                if let Some(group) = self.registry.currency().group(query) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("dates") {
+                if let Some(group) = date_group(query, chrono::Local::now().naive_local()) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("worldclock") {
+                let now = chrono::Local::now();
+                let local_offset = now.offset().local_minus_utc() as i64;
+                let clock = self.registry.worldclock();
+                if let Some(group) = clock.group(query, now.timestamp(), local_offset) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("qr") {
+                if let Some(group) = qr_group(query) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("capture") {
+                if let Some(group) = self.registry.captures().group(query, Instant::now()) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("recent") {
+                let now = chrono::Utc::now().timestamp();
+                if let Some(group) = self.registry.recents().group(query, now) {
+                    groups.insert(0, group);
+                }
+            }
+
+            if enabled("ssh") {
+                if let Some(group) = ssh_group(query) {
+                    groups.push(group);
+                }
+            }
+
+            if enabled("config_files") {
+                let group =
+                    ConfigPaths::current().and_then(|paths| config_files_group(query, &paths));
+                if let Some(group) = group {
+                    groups.push(group);
+                }
+            }
+
+            if enabled("git") {
+                if let Some(group) = self.registry.git().group(query) {
+                    groups.push(group);
+                }
+            }
+
+            if enabled("spotlight") {
+                if let Some(group) = self.registry.spotlight().group(query) {
+                    groups.push(group);
+                }
+            }
+
+            if enabled("dictionary") {
+                let definitions = dictionary_groups(query, &self.registry.dictionary());
+                groups.splice(0..0, definitions);
+            }
+
+            if enabled("websearch") {
+                let websearch = self.registry.websearch();
+                if let Some(group) = websearch.keyword_group(query) {
+                    groups.insert(0, group);
+                } else if groups.iter().all(Group::is_empty) {
+                    if let Some(group) = websearch.fallback_group(query) {
+                        groups = vec![group];
+                    }
+                }
+            }
+
+            // Hidden results are dropped; pinned ones are listed even if hidden
+            blocklist.filter(&mut groups);
+
+            if enabled("pins") {
+                self.registry.pins().apply(query, &mut groups);
+            }
+        }
+
+        // Configured group order and weights (lux.results.order)
+        self.registry
+            .ordering()
+            .apply(view_id.as_deref(), &mut groups);
+
+        Ok(groups)
     }
 
     // =========================================================================
@@ -168,11 +586,116 @@ impl QueryEngine {
 
     /// Get actions that apply to the given items.
     ///
-    /// Calls the current view's `get_actions(item, ctx)` function.
+    /// Calls the current view's `get_actions(item, ctx)` function, then appends
+    /// smart actions for any URL, email, path, color, or JSON in the item.
+    /// Root-view results also get "Pin to Top" / "Unpin" and "Hide This Result".
+    /// Action shortcuts are registered for the current view until the next call.
     pub fn get_applicable_actions(
         &self,
         lua: &Lua,
         items: &[Item],
+    ) -> Result<Vec<ActionInfo>, String> {
+        let mut actions = self.list_applicable_actions(lua, items)?;
+
+        // Any result can go in the basket (the basket view has its own actions)
+        let view_id = self
+            .view_stack
+            .with_top(|view| view.view.id.clone().unwrap_or_default())
+            .unwrap_or_default();
+        if let Some(item) = items.first().filter(|_| view_id != BASKET_VIEW_ID) {
+            let (id, title) = basket_action(item, &self.registry.basket());
+            actions.push(ActionInfo {
+                view_id: view_id.clone(),
+                id: id.to_string(),
+                title,
+                icon: None,
+                bulk: false,
+                shortcut: Some(BASKET_SHORTCUT.to_string()),
+                section: None,
+                destructive: false,
+                handler_key: Some(basket_action_key(id)),
+            });
+        }
+
+        if let Some(item) = items.first().filter(|_| self.at_root()) {
+            let (id, title) = pin_action(item, &self.registry.pins());
+            actions.push(ActionInfo {
+                view_id: self
+                    .view_stack
+                    .with_top(|view| view.view.id.clone().unwrap_or_default())
+                    .unwrap_or_default(),
+                id: id.to_string(),
+                title,
+                icon: None,
+                bulk: false,
+                shortcut: Some(PIN_SHORTCUT.to_string()),
+                section: None,
+                destructive: false,
+                handler_key: Some(pin_action_key(id)),
+            });
+
+            let (id, title) = hide_action();
+            actions.push(ActionInfo {
+                view_id: self
+                    .view_stack
+                    .with_top(|view| view.view.id.clone().unwrap_or_default())
+                    .unwrap_or_default(),
+                id: id.to_string(),
+                title,
+                icon: None,
+                bulk: false,
+                shortcut: None,
+                section: None,
+                destructive: false,
+                handler_key: Some(HIDE_ACTION_KEY.to_string()),
+            });
+        }
+        if let Some(instance_id) = self.view_stack.with_top(|view| view.instance_id) {
+            self.action_shortcuts.lock().register(instance_id, &actions);
+        }
+        self.destructive_keys.lock().extend(
+            actions
+                .iter()
+                .filter(|action| action.destructive)
+                .filter_map(|action| action.handler_key.clone()),
+        );
+        Ok(actions)
+    }
+
+    /// Run the action bound to `shortcut` for the given items.
+    ///
+    /// Only shortcuts of the actions last listed for the current view apply.
+    /// Returns `None` if no action has the shortcut.
+    pub fn run_action_shortcut(
+        &self,
+        lua: &Lua,
+        shortcut: &str,
+        items: &[Item],
+    ) -> Result<Option<ActionResult>, String> {
+        let Some(shortcut) = normalize_shortcut(shortcut) else {
+            return Ok(None);
+        };
+        let Some(instance_id) = self.view_stack.with_top(|view| view.instance_id) else {
+            return Ok(None);
+        };
+        let action = self
+            .action_shortcuts
+            .lock()
+            .lookup(instance_id, &shortcut)
+            .cloned();
+        let Some(action) = action else {
+            return Ok(None);
+        };
+
+        self.execute_action(lua, &action.view_id, action.handler_key(), items)
+            .map(Some)
+    }
+
+    /// List the actions for the given items (see `get_applicable_actions`).
+    fn list_applicable_actions(
+        &self,
+        lua: &Lua,
+        items: &[Item],
     ) -> Result<Vec<ActionInfo>, String> {
         // Get the first item (actions are typically for the focused item)
         let item = match items.first() {
@@ -180,35 +703,422 @@ impl QueryEngine {
             None => return Ok(Vec::new()),
         };
 
+        // Timer items use the built-in cancel action
+        if item.has_type(TIMER_ITEM_TYPE) {
+            return Ok(vec![ActionInfo {
+                view_id: self
+                    .view_stack
+                    .with_top(|view| view.view.id.clone().unwrap_or_default())
+                    .unwrap_or_default(),
+                id: "cancel_timer".to_string(),
+                title: t("Cancel Timer"),
+                icon: None,
+                bulk: false,
+                shortcut: None,
+                section: None,
+                destructive: false,
+                handler_key: Some(TIMER_CANCEL_KEY.to_string()),
+            }]);
+        }
+
+        // Web search items use the built-in open action
+        if item.has_type(WEBSEARCH_ITEM_TYPE) {
+            return Ok(vec![ActionInfo {
+                view_id: self
+                    .view_stack
+                    .with_top(|view| view.view.id.clone().unwrap_or_default())
+                    .unwrap_or_default(),
+                id: "open_url".to_string(),
+                title: t("Open in Browser"),
+                icon: None,
+                bulk: false,
+                shortcut: None,
+                section: None,
+                destructive: false,
+                handler_key: Some(WEBSEARCH_OPEN_KEY.to_string()),
+            }]);
+        }
+
+        // Media items use the built-in media commands
+        if item.has_type(MEDIA_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(media_actions(item)
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(media_command_key(id)),
+                })
+                .collect());
+        }
+
+        // Network items use the built-in toggles and connections
+        if item.has_type(NETWORK_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(network_actions(item)
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title: t(title),
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(network_action_key(id)),
+                })
+                .collect());
+        }
+
+        // SSH host items open in the configured terminal
+        if item.has_type(SSH_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(ssh_actions(&self.registry.ssh().terminal())
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(ssh_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Config files open in the configured editor
+        if item.has_type(CONFIG_FILE_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            let editor = self.registry.editor().editor().current_command();
+            return Ok(config_file_actions(editor.as_deref())
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(config_file_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Repository actions depend on its branch and remote
+        if item.has_type(GIT_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(git_actions(item, &self.registry.ssh().terminal())
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(git_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Dates can be copied or turned into a reminder
+        if item.has_type(DATE_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(date_actions()
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(date_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Capture commands run themselves; the latest capture can be copied,
+        // revealed, or annotated
+        if item.has_type(CAPTURE_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(capture_actions(item)
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(capture_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Recent files can be opened, revealed, moved to the Trash, or archived
+        if item.has_type(RECENT_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(recent_actions()
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(recent_action_key(id)),
+                })
+                .chain(archive_action_infos(item, &view_id))
+                .collect());
+        }
+
+        // QR codes can be opened at full size, copied, or saved as PNG
+        if item.has_type(QR_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(qr_actions()
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(qr_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Clocks can be pinned to (or unpinned from) the root view
+        if item.has_type(CLOCK_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(clock_actions(item)
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(clock_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Converted amounts can be copied with or without the currency code
+        if item.has_type(CURRENCY_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(currency_actions()
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(currency_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Colors can be copied in every format
+        if item.has_type(COLOR_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(color_actions(item)
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(color_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Definitions can be copied or shown in full
+        if item.has_type(DEFINITION_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(dictionary_actions()
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(dictionary_action_key(id)),
+                })
+                .collect());
+        }
+
+        // Spotlight items use the built-in file and archive actions
+        if item.has_type(SPOTLIGHT_ITEM_TYPE) {
+            let view_id = self
+                .view_stack
+                .with_top(|view| view.view.id.clone().unwrap_or_default())
+                .unwrap_or_default();
+            return Ok(spotlight_actions()
+                .into_iter()
+                .map(|(id, title)| ActionInfo {
+                    view_id: view_id.clone(),
+                    id: id.to_string(),
+                    title,
+                    icon: None,
+                    bulk: false,
+                    shortcut: None,
+                    section: None,
+                    destructive: false,
+                    handler_key: Some(spotlight_action_key(id)),
+                })
+                .chain(archive_action_infos(item, &view_id))
+                .collect());
+        }
+
         // Get current view's get_actions function and view_data
-        let (get_actions_key, view_data, view_id) = match self.view_stack.with_top(|view| {
-            (
-                view.view.get_actions_fn.as_ref().map(|f| f.key.clone()),
-                view.view.view_data.clone(),
-                view.view.id.clone().unwrap_or_default(),
-            )
-        }) {
-            Some((Some(key), data, id)) => (key, data, id),
-            Some((None, _, _)) => return Ok(Vec::new()), // No get_actions function
-            None => return Err("No current view".to_string()),
-        };
+        let (mut get_actions_key, mut view_data, mut view_id) =
+            match self.view_stack.with_top(|view| {
+                (
+                    view.view.get_actions_fn.as_ref().map(|f| f.key.clone()),
+                    view.view.view_data.clone(),
+                    view.view.id.clone().unwrap_or_default(),
+                )
+            }) {
+                Some(top) => top,
+                None => return Err("No current view".to_string()),
+            };
+
+        // Results of a routed query get the routed view's actions
+        let routed = self.routed_view.lock().clone().filter(|_| self.at_root());
+        if let Some(routed) = routed {
+            if let Some(key) = self
+                .registry
+                .views()
+                .with_view(&routed, |view| view.get_actions_fn.key.clone())
+            {
+                get_actions_key = Some(key);
+                view_data = serde_json::Value::Null;
+                view_id = routed;
+            }
+        }
 
-        // Call the get_actions function
-        let parsed_actions = crate::lua::call_get_actions(lua, &get_actions_key, item, &view_data)
-            .map_err(|e| format!("get_actions failed: {}", e))?;
+        // Call the get_actions function (detected items are owned by the engine)
+        let mut actions = Vec::new();
+        if let Some(key) = get_actions_key.filter(|_| !item.has_type(DETECTED_ITEM_TYPE)) {
+            let parsed_actions = crate::lua::call_get_actions(lua, &key, item, &view_data)
+                .map_err(|e| format!("get_actions failed: {}", e))?;
 
-        // Convert to ActionInfo
-        let actions = parsed_actions
-            .into_iter()
-            .map(|a| ActionInfo {
+            // Convert to ActionInfo
+            actions.extend(parsed_actions.into_iter().map(|a| ActionInfo {
                 view_id: view_id.clone(),
                 id: a.id,
                 title: a.title,
                 icon: a.icon,
                 bulk: false, // TODO: support bulk actions
+                shortcut: a.shortcut,
+                section: a.section,
+                destructive: a.destructive,
                 handler_key: Some(a.handler_key),
-            })
-            .collect();
+            }));
+        }
+
+        // Append smart actions for URLs, paths, colors, etc. in the item
+        if let Some(detection) = detect_item(item) {
+            actions.extend(
+                smart_actions(&detection)
+                    .into_iter()
+                    .map(|(id, title)| ActionInfo {
+                        view_id: view_id.clone(),
+                        id: id.to_string(),
+                        title: t(title),
+                        icon: None,
+                        bulk: false,
+                        shortcut: None,
+                        section: None,
+                        destructive: false,
+                        handler_key: Some(smart_action_key(id)),
+                    }),
+            );
+        }
+
+        // Append "Compress" / "Extract" for files
+        actions.extend(archive_action_infos(item, &view_id));
 
         Ok(actions)
     }
@@ -222,16 +1132,27 @@ impl QueryEngine {
         func_ref: &crate::types::LuaFunctionRef,
         items: &[Item],
     ) -> Result<ActionResult, String> {
-        let view_data = self
+        let (view_data, view_id) = self
             .view_stack
-            .with_top(|v| v.view.view_data.clone())
-            .unwrap_or(serde_json::Value::Null);
-
-        let effects = crate::lua::call_action_run(lua, &func_ref.key, items, &view_data)
-            .map_err(|e| format!("Lua callback failed: {}", e))?;
-
-        let result = self.apply_effects(lua, effects);
-        Ok(self.apply_result_to_action_result(result))
+            .with_top(|v| (v.view.view_data.clone(), v.view.id.clone()))
+            .unwrap_or((serde_json::Value::Null, None));
+
+        let start = Instant::now();
+        let result = crate::lua::call_action_run(lua, &func_ref.key, items, &view_data)
+            .map_err(|e| format!("Lua callback failed: {}", e))
+            .map(|effects| {
+                let result = self.apply_effects(lua, effects);
+                self.apply_result_to_action_result(result)
+            });
+        self.registry.activity().record(ActivityEntry::new(
+            view_id.as_deref().unwrap_or(ROOT_VIEW_NAME),
+            KEYBINDING_ACTION,
+            &func_ref.key,
+            items,
+            &result,
+            start.elapsed(),
+        ));
+        result
     }
 
     /// Execute an action on the given items.
@@ -244,18 +1165,128 @@ impl QueryEngine {
         action_id: &str,
         items: &[Item],
     ) -> Result<ActionResult, String> {
-        // Get view_data from current view
-        let view_data = self
+        // Get view_data and view_id from current view
+        let (view_data, view_id) = self
             .view_stack
-            .with_top(|v| v.view.view_data.clone())
-            .unwrap_or(serde_json::Value::Null);
+            .with_top(|v| (v.view.view_data.clone(), v.view.id.clone()))
+            .unwrap_or((serde_json::Value::Null, None));
+
+        let view_name = view_id.as_deref().unwrap_or(ROOT_VIEW_NAME);
+        let start = Instant::now();
+        let result = self.run_action(lua, view_id.as_deref(), &view_data, action_id, items);
+        let destructive = self.destructive_keys.lock().contains(action_id);
+        self.registry.activity().record(
+            ActivityEntry::new(
+                view_name,
+                action_name(action_id),
+                action_id,
+                items,
+                &result,
+                start.elapsed(),
+            )
+            .with_destructive(destructive),
+        );
+        result
+    }
+
+    /// Run the latest action of this session again, on its original items.
+    ///
+    /// A destructive action only runs when `confirmed` is the ID of its
+    /// activity entry, as returned in `RepeatOutcome::NeedsConfirmation`.
+    pub fn repeat_last_action(
+        &self,
+        lua: &Lua,
+        confirmed: Option<u64>,
+    ) -> Result<RepeatOutcome, String> {
+        let Some(entry) = self.registry.activity().latest_this_session() else {
+            return Ok(RepeatOutcome::Nothing);
+        };
+        // Follow-up and inline edit handlers are freed after they run
+        let loaded = lua
+            .named_registry_value::<Option<mlua::Function>>(&entry.handler_key)
+            .is_ok_and(|handler| handler.is_some());
+        if !loaded {
+            return Ok(RepeatOutcome::Nothing);
+        }
+        if entry.destructive && confirmed != Some(entry.id) {
+            return Ok(RepeatOutcome::NeedsConfirmation(entry));
+        }
 
+        if entry.action == KEYBINDING_ACTION {
+            let func_ref = LuaFunctionRef::new(entry.handler_key);
+            self.execute_lua_callback(lua, &func_ref, &entry.items)
+        } else {
+            self.execute_action(lua, &entry.view, &entry.handler_key, &entry.items)
+        }
+        .map(RepeatOutcome::Ran)
+    }
+
+    /// Run the handler `action_id` of the current view and apply its effects.
+    fn run_action(
+        &self,
+        lua: &Lua,
+        view_id: Option<&str>,
+        view_data: &serde_json::Value,
+        action_id: &str,
+        items: &[Item],
+    ) -> Result<ActionResult, String> {
         // Call the action handler (action_id is the handler_key)
-        let effects = crate::lua::call_action_run(lua, action_id, items, &view_data)
+        let view_name = view_id.unwrap_or(ROOT_VIEW_NAME);
+        let metrics = self.registry.metrics();
+        metrics.record_action(view_name, action_name(action_id));
+        let effects = self
+            .registry
+            .memory()
+            .measure(lua, view_id, || {
+                metrics.measure(view_name, || {
+                    crate::lua::call_action_run(lua, action_id, items, view_data)
+                })
+            })
             .map_err(|e| format!("Action execution failed: {}", e))?;
 
         // Apply effects
         let result = self.apply_effects(lua, effects);
+        Ok(match self.apply_result_to_action_result(result) {
+            // A retryable failure is retried by running the same handler again
+            ActionResult::Fail { error, .. } => {
+                metrics.record_failure(view_name, &error);
+                let retry = error.retryable.then(|| action_id.to_string());
+                ActionResult::Fail { error, retry }
+            }
+            result => result,
+        })
+    }
+
+    /// Pass the text submitted in an inline edit to its `on_submit` callback.
+    ///
+    /// The `handler_key` is the one from `ActionResult::Edit`; `items` holds
+    /// the edited item. A callback that fails (`ctx:fail()`) keeps the row
+    /// in edit mode so the text can be fixed; otherwise the callback is freed.
+    pub fn submit_edit(
+        &self,
+        lua: &Lua,
+        handler_key: &str,
+        text: &str,
+        items: &[Item],
+    ) -> Result<ActionResult, String> {
+        let view_data = self
+            .view_stack
+            .with_top(|v| v.view.view_data.clone())
+            .unwrap_or(serde_json::Value::Null);
+
+        let effects = crate::lua::call_edit_submit(lua, handler_key, text, items, &view_data)
+            .map_err(|e| format!("Edit callback failed: {}", e))?;
+        let result = self.apply_effects(lua, effects);
+
+        // The edit is over unless the callback failed or started a new one
+        if result.error.is_none() {
+            let mut edit_key = self.edit_key.lock();
+            if edit_key.as_deref() == Some(handler_key) {
+                *edit_key = None;
+                cleanup_view_registry_keys(lua, &[handler_key.to_string()]);
+            }
+        }
+
         Ok(self.apply_result_to_action_result(result))
     }
 
@@ -265,18 +1296,30 @@ impl QueryEngine {
             return ActionResult::Dismiss;
         }
 
+        // Played on whatever view is left after a pop
+        if let Some(name) = result.play_macro {
+            return ActionResult::PlayMacro { name };
+        }
+        if let Some(action) = result.run_command {
+            return ActionResult::RunCommand { action };
+        }
+
         if result.popped {
             return ActionResult::Pop;
         }
 
         if let Some(error) = result.error {
-            return ActionResult::Fail { error };
+            return ActionResult::Fail { error, retry: None };
+        }
+
+        if let Some(edit) = result.edit {
+            return ActionResult::Edit(edit);
         }
 
         if let Some(message) = result.completed {
             return ActionResult::Complete {
                 message,
-                actions: Vec::new(),
+                actions: result.follow_ups,
             };
         }
 
@@ -291,10 +1334,11 @@ impl QueryEngine {
 
         // If stack grew, a view was pushed
         if self.view_stack.len() > 1 {
-            return ActionResult::PushView {
-                title: None,
-                query: None,
-            };
+            let (title, query) = self
+                .view_stack
+                .with_top(|view| (view.view.title.clone(), view.view.initial_query.clone()))
+                .unwrap_or_default();
+            return ActionResult::PushView { title, query };
         }
 
         ActionResult::Continue
@@ -344,6 +1388,49 @@ impl QueryEngine {
         Ok(())
     }
 
+    // =========================================================================
+    // Selection Changes (Multi Mode)
+    // =========================================================================
+
+    /// Handle a selection change in multi mode by calling on_selection_changed.
+    ///
+    /// The UI passes the new selection and the IDs added and removed, since it
+    /// owns that state. Returns the selection after the callback's select and
+    /// deselect effects, which the UI adopts (e.g. to enforce a limit).
+    pub fn handle_selection_changed(
+        &self,
+        lua: &Lua,
+        selection: &[String],
+        added: &[String],
+        removed: &[String],
+    ) -> Result<Vec<String>, String> {
+        let (fn_key, view_data) = self
+            .view_stack
+            .with_top(|view| {
+                let key = view
+                    .view
+                    .on_selection_changed_fn
+                    .as_ref()
+                    .map(|f| f.key.clone());
+                (key, view.view.view_data.clone())
+            })
+            .unwrap_or((None, serde_json::Value::Null));
+
+        let Some(fn_key) = fn_key else {
+            return Ok(selection.to_vec());
+        };
+
+        let effects = crate::lua::call_view_on_selection_changed(
+            lua, &fn_key, &view_data, selection, added, removed,
+        )
+        .map_err(|e| format!("on_selection_changed failed: {}", e))?;
+
+        let selection = apply_selection_effects(selection, &effects);
+        self.apply_effects(lua, effects);
+
+        Ok(selection)
+    }
+
     // =========================================================================
     // Submit Hook
     // =========================================================================
@@ -378,6 +1465,51 @@ impl QueryEngine {
         Ok(result.dismissed)
     }
 
+    // =========================================================================
+    // Undo
+    // =========================================================================
+
+    /// Undo the newest operation on the undo stack.
+    ///
+    /// Returns what the operation did, or `None` when there is nothing to undo.
+    /// The entry is removed even when reverting it fails.
+    pub fn undo(&self, lua: &Lua) -> Result<Option<String>, String> {
+        let undo = self.registry.undo();
+        cleanup_view_registry_keys(lua, &undo.take_retired());
+
+        let Some(entry) = undo.pop() else {
+            return Ok(None);
+        };
+        match &entry.op {
+            UndoOp::MoveBack { from, to } => {
+                move_back(from, to).map_err(|e| format!("Undo failed: {}", e))?
+            }
+            UndoOp::Lua(func) => {
+                let result = func.call::<_, ()>(lua, ());
+                let _ = lua.unset_named_registry_value(&func.key);
+                result.map_err(|e| format!("Undo failed: {}", e))?
+            }
+        }
+        Ok(Some(entry.description))
+    }
+
+    /// The newest operation that can be undone.
+    pub fn latest_undo(&self) -> Option<UndoInfo> {
+        self.registry.undo().latest()
+    }
+
+    /// Number of items in the basket, shown as a badge by the launcher.
+    pub fn basket_count(&self) -> usize {
+        self.registry.basket().len()
+    }
+
+    /// Run the `on_shutdown` hooks until `deadline`. Returns how many ran.
+    ///
+    /// The first step of quitting; [`PluginRegistry::shutdown`] follows.
+    pub fn run_shutdown_hooks(&self, lua: &Lua, deadline: Instant) -> usize {
+        engine_impl::run_shutdown_hooks(lua, &self.registry, deadline)
+    }
+
     // =========================================================================
     // Effect-Based Execution (New)
     // =========================================================================
@@ -388,22 +1520,38 @@ impl QueryEngine {
     /// Lua callbacks collect effects, then the engine applies them here.
     ///
     /// View stack mutations (push/pop/replace) auto-broadcast to subscribers.
-    /// Selection effects are ignored - UI owns selection state.
+    /// Selection effects are ignored here - UI owns selection state.
+    /// View data belongs to the view the callback ran in, so it is stored on
+    /// the top view before any navigation effect is applied.
     ///
     /// Returns information about what happened for the caller to act on.
     pub fn apply_effects(&self, lua: &Lua, effects: Vec<Effect>) -> ApplyResult {
         let mut result = ApplyResult::default();
 
+        if let Some(data) = latest_view_data(&effects) {
+            self.view_stack
+                .modify_top(|view| view.view.view_data = data);
+            tracing::debug!("Applied SetViewData");
+        }
+
         for effect in effects {
             match effect {
                 Effect::SetGroups(groups) => {
                     result.groups = Some(groups);
                 }
+                Effect::PushView(spec) if !self.is_view_enabled(&spec) => {
+                    cleanup_view_registry_keys(lua, &spec.registry_keys);
+                    tracing::debug!("Skipped PushView: view disabled by the active profile");
+                }
+                Effect::ReplaceView(spec) if !self.is_view_enabled(&spec) => {
+                    cleanup_view_registry_keys(lua, &spec.registry_keys);
+                    tracing::debug!("Skipped ReplaceView: view disabled by the active profile");
+                }
                 Effect::PushView(spec) => {
                     let view = self.view_from_spec(&spec);
                     let registry_keys = spec.registry_keys.clone();
                     let instance = ViewInstance::with_registry_keys(view, registry_keys);
-                    self.view_stack.push(instance);
+                    self.open_view(lua, instance);
                     tracing::debug!("Applied PushView, stack depth: {}", self.view_stack.len());
                 }
                 Effect::ReplaceView(spec) => {
@@ -411,20 +1559,15 @@ impl QueryEngine {
                     let registry_keys = spec.registry_keys.clone();
                     let instance = ViewInstance::with_registry_keys(view, registry_keys);
 
-                    // Replace and cleanup old view's registry keys
-                    if let Some(old_view) = self.view_stack.replace_top(instance) {
-                        cleanup_view_registry_keys(lua, &old_view.registry_keys);
-                    }
+                    // Replace, closing the old view and cleaning up its registry keys
+                    self.replace_top_view(lua, instance);
                     tracing::debug!(
                         "Applied ReplaceView, stack depth: {}",
                         self.view_stack.len()
                     );
                 }
                 Effect::Pop => {
-                    if self.view_stack.len() > 1 {
-                        if let Some(old_view) = self.view_stack.pop() {
-                            cleanup_view_registry_keys(lua, &old_view.registry_keys);
-                        }
+                    if self.pop_view(lua) {
                         tracing::debug!("Applied Pop, stack depth: {}", self.view_stack.len());
                     }
                     result.popped = true;
@@ -436,18 +1579,32 @@ impl QueryEngine {
                 Effect::Progress(message) => {
                     result.progress = Some(message);
                 }
-                Effect::Complete { message } => {
+                Effect::Complete { message, actions } => {
+                    self.replace_follow_ups(lua, &actions);
                     result.completed = Some(message);
+                    result.follow_ups = actions;
                 }
                 Effect::Fail { error } => {
                     result.error = Some(error);
                 }
+                Effect::Edit(edit) => {
+                    self.replace_edit_key(lua, &edit.handler_key);
+                    result.edit = Some(edit);
+                }
+                Effect::PlayMacro(name) => {
+                    result.play_macro = Some(name);
+                }
+                Effect::RunCommand(action) => {
+                    result.run_command = Some(action);
+                }
                 Effect::Notify(message) => {
                     result.notification = Some(message);
                 }
                 Effect::SetLoading(loading) => {
                     result.loading = Some(loading);
                 }
+                // Applied to the originating view before the loop
+                Effect::SetViewData(_) => {}
                 // Selection effects are ignored - UI owns selection state
                 Effect::Select(_) | Effect::Deselect(_) | Effect::ClearSelection => {
                     tracing::debug!("Ignoring selection effect - UI owns selection state");
@@ -458,7 +1615,43 @@ impl QueryEngine {
         result
     }
 
+    /// Free the follow-up handlers of the previous completion and keep the new ones.
+    ///
+    /// Only the latest completion's follow-ups can be chosen in the frontend.
+    fn replace_follow_ups(&self, lua: &Lua, actions: &[FollowUpAction]) {
+        let keys = actions
+            .iter()
+            .filter_map(|action| action.handler_key.clone())
+            .collect();
+        let old = std::mem::replace(&mut *self.follow_up_keys.lock(), keys);
+        cleanup_view_registry_keys(lua, &old);
+    }
+
+    /// Free the `on_submit` callback of the previous inline edit and keep the new one.
+    ///
+    /// Only one row is edited at a time.
+    fn replace_edit_key(&self, lua: &Lua, key: &str) {
+        let old = self.edit_key.lock().replace(key.to_string());
+        if let Some(old) = old.filter(|old| old != key) {
+            cleanup_view_registry_keys(lua, &[old]);
+        }
+    }
+
     /// Convert a ViewSpec to a View.
+    /// Check if the active profile and the blocklist allow a view (views
+    /// without an ID always are).
+    fn is_view_enabled(&self, spec: &ViewSpec) -> bool {
+        spec.id
+            .as_deref()
+            .is_none_or(|id| self.is_view_id_enabled(id))
+    }
+
+    /// Check if the active profile and the blocklist allow the view `id`.
+    fn is_view_id_enabled(&self, id: &str) -> bool {
+        self.registry.profiles().is_view_enabled(id)
+            && !self.registry.blocklist().is_view_disabled(id)
+    }
+
     fn view_from_spec(&self, spec: &ViewSpec) -> View {
         View {
             id: spec.id.clone(),
@@ -478,11 +1671,59 @@ impl QueryEngine {
                 .on_submit_fn_key
                 .as_ref()
                 .map(|k| LuaFunctionRef::new(k.clone())),
+            on_open_fn: spec
+                .on_open_fn_key
+                .as_ref()
+                .map(|k| LuaFunctionRef::new(k.clone())),
+            on_show_fn: spec
+                .on_show_fn_key
+                .as_ref()
+                .map(|k| LuaFunctionRef::new(k.clone())),
+            on_close_fn: spec
+                .on_close_fn_key
+                .as_ref()
+                .map(|k| LuaFunctionRef::new(k.clone())),
+            on_selection_changed_fn: spec
+                .on_selection_changed_fn_key
+                .as_ref()
+                .map(|k| LuaFunctionRef::new(k.clone())),
             view_data: spec.view_data.clone(),
+            selected: spec.selected.clone(),
+            refresh_ms: spec.refresh_ms,
+            initial_query: spec.query.clone(),
         }
     }
 }
 
+/// The built-in archive actions for a file item.
+fn archive_action_infos<'a>(
+    item: &Item,
+    view_id: &'a str,
+) -> impl Iterator<Item = ActionInfo> + 'a {
+    archive_actions(item)
+        .into_iter()
+        .map(move |(id, title)| ActionInfo {
+            view_id: view_id.to_string(),
+            id: id.to_string(),
+            title,
+            icon: None,
+            bulk: false,
+            shortcut: None,
+            section: None,
+            destructive: false,
+            handler_key: Some(archive_action_key(id)),
+        })
+}
+
+/// The action a handler key runs, for usage counts: the action ID of a Lua
+/// action (`action:<id>:<uuid>` or `action:<id>`), or the built-in key as is.
+fn action_name(handler_key: &str) -> &str {
+    handler_key
+        .strip_prefix("action:")
+        .map(|rest| rest.rsplit_once(':').map_or(rest, |(id, _)| id))
+        .unwrap_or(handler_key)
+}
+
 /// Result of applying effects.
 #[derive(Debug, Default)]
 pub struct ApplyResult {
@@ -496,8 +1737,16 @@ pub struct ApplyResult {
     pub progress: Option<String>,
     /// Completion message, if any.
     pub completed: Option<String>,
-    /// Error message, if any.
-    pub error: Option<String>,
+    /// Follow-up actions offered with the completion message.
+    pub follow_ups: Vec<FollowUpAction>,
+    /// Error, if any.
+    pub error: Option<HandlerError>,
+    /// Inline edit the action started, if any.
+    pub edit: Option<InlineEdit>,
+    /// Macro to play once the action returns, if any.
+    pub play_macro: Option<String>,
+    /// Launcher action to run once the action returns, if any.
+    pub run_command: Option<String>,
     /// Notification message (doesn't dismiss).
     pub notification: Option<String>,
     /// Loading state, if changed.
@@ -525,6 +1774,7 @@ mod tests {
     fn test_view_stack_operations() {
         let registry = Arc::new(PluginRegistry::new());
         let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
 
         // Create test views
         let view1 = View {
@@ -536,7 +1786,14 @@ mod tests {
             selection: SelectionMode::Single,
             on_select_fn: None,
             on_submit_fn: None,
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
             view_data: serde_json::Value::Null,
+            selected: Vec::new(),
+            refresh_ms: None,
+            initial_query: None,
         };
 
         let view2 = View {
@@ -548,14 +1805,21 @@ mod tests {
             selection: SelectionMode::Multi,
             on_select_fn: None,
             on_submit_fn: None,
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
             view_data: serde_json::Value::Null,
+            selected: Vec::new(),
+            refresh_ms: None,
+            initial_query: None,
         };
 
         // Push views
-        engine.push_view(view1);
+        engine.push_view(&lua, view1);
         assert_eq!(engine.get_view_stack().len(), 1);
 
-        engine.push_view(view2);
+        engine.push_view(&lua, view2);
         assert_eq!(engine.get_view_stack().len(), 2);
 
         // Check current view
@@ -564,14 +1828,14 @@ mod tests {
         assert_eq!(current.selection, SelectionMode::Multi);
 
         // Pop view
-        assert!(engine.pop_view());
+        assert!(engine.pop_view(&lua));
         assert_eq!(engine.get_view_stack().len(), 1);
 
         let current = engine.get_current_view_state().unwrap();
         assert_eq!(current.title, Some("View 1".to_string()));
 
         // Can't pop last view
-        assert!(!engine.pop_view());
+        assert!(!engine.pop_view(&lua));
         assert_eq!(engine.get_view_stack().len(), 1);
     }
 
@@ -579,6 +1843,7 @@ mod tests {
     fn test_subscribe_broadcasts_changes() {
         let registry = Arc::new(PluginRegistry::new());
         let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
 
         // Subscribe before any changes
         let rx = engine.subscribe();
@@ -594,10 +1859,17 @@ mod tests {
             selection: SelectionMode::Single,
             on_select_fn: None,
             on_submit_fn: None,
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
             view_data: serde_json::Value::Null,
+            selected: Vec::new(),
+            refresh_ms: None,
+            initial_query: None,
         };
 
-        engine.push_view(view);
+        engine.push_view(&lua, view);
 
         // Subscriber should see the change
         let states = rx.borrow().clone();
@@ -605,4 +1877,709 @@ mod tests {
         assert_eq!(states[0].title, Some("Test View".to_string()));
         assert_eq!(states[0].placeholder, Some("Search...".to_string()));
     }
+
+    #[test]
+    fn test_apply_effects_sets_view_data_before_navigation() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        engine.push_view(
+            &lua,
+            View {
+                id: Some("list".to_string()),
+                title: None,
+                placeholder: None,
+                source_fn: LuaFunctionRef::new("test:source:list".to_string()),
+                get_actions_fn: None,
+                selection: SelectionMode::Single,
+                on_select_fn: None,
+                on_submit_fn: None,
+                on_open_fn: None,
+                on_show_fn: None,
+                on_close_fn: None,
+                on_selection_changed_fn: None,
+                view_data: serde_json::json!({ "page": 1 }),
+                selected: Vec::new(),
+                refresh_ms: None,
+                initial_query: None,
+            },
+        );
+
+        engine.apply_effects(
+            &lua,
+            vec![
+                Effect::PushView(ViewSpec::new("test:source:detail".to_string())),
+                Effect::SetViewData(serde_json::json!({ "page": 2 })),
+            ],
+        );
+
+        let data: Vec<serde_json::Value> = engine
+            .view_stack
+            .with_stack(|stack| stack.iter().map(|v| v.view.view_data.clone()).collect());
+        assert_eq!(
+            data,
+            vec![serde_json::json!({ "page": 2 }), serde_json::Value::Null]
+        );
+    }
+
+    #[test]
+    fn test_push_view_result_carries_query() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+        engine.initialize(&lua);
+
+        let spec = ViewSpec::new("test:source".to_string())
+            .with_title("Files")
+            .with_query("report");
+        let result = engine.apply_effects(&lua, vec![Effect::PushView(spec)]);
+
+        match engine.apply_result_to_action_result(result) {
+            ActionResult::PushView { title, query } => {
+                assert_eq!(title, Some("Files".to_string()));
+                assert_eq!(query, Some("report".to_string()));
+            }
+            other => panic!("expected PushView, got {:?}", other),
+        }
+        assert_eq!(
+            engine.get_current_view_state().unwrap().initial_query,
+            Some("report".to_string())
+        );
+    }
+
+    #[test]
+    fn test_view_lifecycle_callbacks() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        lua.load(
+            r#"
+            events = {}
+            function on_open(ctx)
+                table.insert(events, "open")
+                ctx:set_view_data({ watcher = "w1" })
+            end
+            function on_show(ctx) table.insert(events, "show " .. ctx.view_data.watcher) end
+            function on_close(ctx) table.insert(events, "close " .. ctx.view_data.watcher) end
+        "#,
+        )
+        .exec()
+        .unwrap();
+        for name in ["on_open", "on_show", "on_close"] {
+            let func: mlua::Function = lua.globals().get(name).unwrap();
+            lua.set_named_registry_value(&format!("test:{}", name), func)
+                .unwrap();
+        }
+
+        engine.initialize(&lua);
+        let spec = ViewSpec::new("test:source".to_string())
+            .with_on_open("test:on_open".to_string())
+            .with_on_show("test:on_show".to_string())
+            .with_on_close("test:on_close".to_string());
+        engine.apply_effects(&lua, vec![Effect::PushView(spec)]);
+        engine.view_shown(&lua);
+        assert!(engine.pop_view(&lua));
+
+        let events: Vec<String> = lua.globals().get("events").unwrap();
+        assert_eq!(events, ["open", "show w1", "close w1"]);
+
+        // Closing frees the view's callbacks
+        let on_close: Option<mlua::Function> = lua.named_registry_value("test:on_close").unwrap();
+        assert!(on_close.is_none());
+    }
+
+    #[test]
+    fn test_search_uses_rewritten_query() {
+        let registry = Arc::new(PluginRegistry::new());
+        let rewriter = registry.query_rewriter();
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        lua.load(
+            r#"
+            function source(query, ctx)
+                ctx:set_items({ { id = "q", title = query .. "|" .. ctx.original_query } })
+            end
+            function middleware(query, view_id)
+                if view_id == "apps" then return query .. " app" end
+            end
+        "#,
+        )
+        .exec()
+        .unwrap();
+        let source: mlua::Function = lua.globals().get("source").unwrap();
+        lua.set_named_registry_value("test:source", source).unwrap();
+        let middleware: mlua::Function = lua.globals().get("middleware").unwrap();
+        rewriter.add_lua(
+            LuaFunctionRef::from_function(&lua, middleware, "query:test".to_string()).unwrap(),
+        );
+        rewriter.set_normalize(crate::rewrite::Normalize {
+            trim: true,
+            lowercase: true,
+            collapse_spaces: false,
+        });
+        rewriter.set_alias("ff", "firefox");
+
+        engine.initialize(&lua);
+        let spec = ViewSpec::new("test:source".to_string()).with_id("apps");
+        engine.apply_effects(&lua, vec![Effect::PushView(spec)]);
+
+        let groups = engine.search(&lua, " FF ").unwrap();
+        assert_eq!(groups[0].items[0].title, "firefox app| FF ");
+    }
+
+    #[test]
+    fn test_routed_query_uses_routed_view() {
+        let registry = Arc::new(PluginRegistry::new());
+        let lua = Lua::new();
+        crate::routes::capture_string_find(&lua).unwrap();
+
+        lua.load(
+            r#"
+            function calc_search(query, ctx)
+                ctx:set_items({ { id = "calc", title = "= " .. query } })
+            end
+            function calc_actions(item, ctx)
+                return { { id = "copy_result", title = "Copy", handler = function() end } }
+            end
+            function root_search(query, ctx) end
+        "#,
+        )
+        .exec()
+        .unwrap();
+        for name in ["calc_search", "calc_actions"] {
+            let func: mlua::Function = lua.globals().get(name).unwrap();
+            lua.set_named_registry_value(name, func).unwrap();
+        }
+        // The default root view searches with "empty:source"
+        let root_search: mlua::Function = lua.globals().get("root_search").unwrap();
+        lua.set_named_registry_value("empty:source", root_search)
+            .unwrap();
+        registry
+            .views()
+            .add(crate::views::ViewDefinition {
+                id: "calculator".to_string(),
+                title: None,
+                placeholder: None,
+                selection: SelectionMode::Single,
+                search_fn: LuaFunctionRef::new("calc_search".to_string()),
+                get_actions_fn: LuaFunctionRef::new("calc_actions".to_string()),
+                on_open_fn: None,
+                on_show_fn: None,
+                on_close_fn: None,
+                on_selection_changed_fn: None,
+                refresh_ms: None,
+            })
+            .unwrap();
+        registry
+            .routes()
+            .add(&lua, "^%d+[%+%-/%*]", "calculator")
+            .unwrap();
+        let engine = QueryEngine::new(registry);
+        engine.initialize(&lua);
+
+        let groups = engine.search(&lua, "2+2").unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items[0].title, "= 2+2");
+
+        let actions = engine
+            .get_applicable_actions(&lua, &groups[0].items)
+            .unwrap();
+        assert_eq!(actions[0].id, "copy_result");
+        assert_eq!(actions[0].view_id, "calculator");
+
+        // Unmatched queries go to the root source again (web search may still
+        // offer its fallback group)
+        let groups = engine.search(&lua, "calc").unwrap();
+        assert!(engine.routed_view.lock().is_none());
+        assert!(groups
+            .iter()
+            .flat_map(|g| &g.items)
+            .all(|item| item.id != "calc"));
+    }
+
+    #[test]
+    fn test_open_for_app_pushes_rule_view_on_root() {
+        let registry = Arc::new(PluginRegistry::new());
+        let lua = Lua::new();
+        registry
+            .views()
+            .add(crate::views::ViewDefinition {
+                id: "tabs".to_string(),
+                title: Some("Tabs".to_string()),
+                placeholder: None,
+                selection: SelectionMode::Single,
+                search_fn: LuaFunctionRef::new("tabs_search".to_string()),
+                get_actions_fn: LuaFunctionRef::new("tabs_actions".to_string()),
+                on_open_fn: None,
+                on_show_fn: None,
+                on_close_fn: None,
+                on_selection_changed_fn: None,
+                refresh_ms: None,
+            })
+            .unwrap();
+        registry.app_rules().on_app("com.apple.Safari", "tabs");
+        registry.app_rules().on_app("Terminal", "missing");
+        let engine = QueryEngine::new(registry);
+        engine.initialize(&lua);
+
+        let safari = ProfileContext {
+            bundle_id: Some("com.apple.Safari".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(engine.open_for_app(&lua, &safari).as_deref(), Some("tabs"));
+        assert_eq!(engine.open_for_app(&lua, &safari).as_deref(), Some("tabs"));
+        let stack = engine.get_view_stack();
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack[1].id.as_deref(), Some("tabs"));
+
+        // Unregistered views and unmatched apps leave the stack alone
+        let terminal = ProfileContext {
+            app_name: Some("Terminal".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(engine.open_for_app(&lua, &terminal), None);
+        assert_eq!(engine.get_view_stack().len(), 2);
+    }
+
+    #[test]
+    fn test_detached_engine_has_its_own_stack() {
+        let registry = Arc::new(PluginRegistry::new());
+        let lua = Lua::new();
+        registry
+            .views()
+            .add(crate::views::ViewDefinition {
+                id: "timers".to_string(),
+                title: Some("Timers".to_string()),
+                placeholder: None,
+                selection: SelectionMode::Single,
+                search_fn: LuaFunctionRef::new("timers_search".to_string()),
+                get_actions_fn: LuaFunctionRef::new("timers_actions".to_string()),
+                on_open_fn: None,
+                on_show_fn: None,
+                on_close_fn: None,
+                on_selection_changed_fn: None,
+                refresh_ms: None,
+            })
+            .unwrap();
+        let main = QueryEngine::new(registry.clone());
+        main.initialize(&lua);
+
+        let detached = QueryEngine::detached(registry);
+        assert!(!detached.initialize_detached(&lua, "missing"));
+        assert!(detached.get_view_stack().is_empty());
+        assert!(detached.initialize_detached(&lua, "timers"));
+        let stack = detached.get_view_stack();
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack[0].id.as_deref(), Some("timers"));
+        assert!(!detached.at_root());
+        assert!(!detached.pop_view(&lua));
+
+        // The launcher's stack is untouched
+        assert_eq!(main.get_view_stack().len(), 1);
+        assert!(main.at_root());
+
+        detached.close(&lua);
+        assert!(detached.get_view_stack().is_empty());
+    }
+
+    #[test]
+    fn test_saved_view_state_survives_effect_pop() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        engine.initialize(&lua);
+        let root_id = engine.get_view_stack()[0].instance_id;
+        let saved = SavedViewState {
+            query: "fire".to_string(),
+            cursor_item: Some("firefox".to_string()),
+            scroll_offset: 120.0,
+            selected: vec!["a".to_string()],
+        };
+        assert!(engine.save_view_state(root_id, saved.clone()));
+
+        engine.apply_effects(
+            &lua,
+            vec![Effect::PushView(ViewSpec::new("test:source".to_string()))],
+        );
+        let stack = engine.get_view_stack();
+        assert_ne!(stack[1].instance_id, root_id);
+        assert!(stack[1].saved.is_none());
+
+        engine.apply_effects(&lua, vec![Effect::Pop]);
+        let stack = engine.get_view_stack();
+        assert_eq!(stack.len(), 1);
+        assert_eq!(stack[0].instance_id, root_id);
+        assert_eq!(stack[0].saved, Some(saved));
+
+        // Popped instances can no longer be saved
+        assert!(!engine.save_view_state(u64::MAX, SavedViewState::default()));
+    }
+
+    #[test]
+    fn test_handle_selection_changed_enforces_limit() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        let func: mlua::Function = lua
+            .load(
+                r#"
+            return function(selection, ctx)
+                for _, id in ipairs(ctx.added) do
+                    if #selection > 1 then ctx:deselect(id) end
+                end
+                ctx:set_view_data({ last = ctx.added[1] })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:on_selection_changed", func)
+            .unwrap();
+
+        engine.initialize(&lua);
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+
+        // Views without the callback keep the selection as is
+        let selection = engine
+            .handle_selection_changed(&lua, &ids(&["a", "b"]), &ids(&["b"]), &[])
+            .unwrap();
+        assert_eq!(selection, ["a", "b"]);
+
+        let spec = ViewSpec::new("test:source".to_string())
+            .with_selection_mode(SelectionMode::Multi)
+            .with_on_selection_changed("test:on_selection_changed".to_string());
+        engine.apply_effects(&lua, vec![Effect::PushView(spec)]);
+        assert!(engine.get_view_stack()[1].has_on_selection_changed);
+
+        let selection = engine
+            .handle_selection_changed(&lua, &ids(&["a"]), &ids(&["a"]), &[])
+            .unwrap();
+        assert_eq!(selection, ["a"]);
+
+        let selection = engine
+            .handle_selection_changed(&lua, &ids(&["a", "b"]), &ids(&["b"]), &[])
+            .unwrap();
+        assert_eq!(selection, ["a"]);
+
+        let view_data = engine
+            .view_stack
+            .with_top(|view| view.view.view_data.clone())
+            .unwrap();
+        assert_eq!(view_data, serde_json::json!({ "last": "b" }));
+    }
+
+    #[test]
+    fn test_action_shortcut_runs_listed_action() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        let get_actions: mlua::Function = lua
+            .load(
+                r#"
+            return function(item, ctx)
+                return {
+                    {
+                        id = "open",
+                        title = "Open",
+                        shortcut = "Cmd+O",
+                        handler = function(items, ctx) ctx:complete("opened " .. items[1].id) end,
+                    },
+                    { id = "copy", title = "Copy", handler = function(items, ctx) end },
+                }
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:get_actions", get_actions)
+            .unwrap();
+
+        engine.initialize(&lua);
+        let spec = ViewSpec::new("test:source".to_string())
+            .with_id("files")
+            .with_get_actions("test:get_actions".to_string());
+        engine.apply_effects(&lua, vec![Effect::PushView(spec)]);
+
+        let items = vec![Item::new("a.txt", "a.txt")];
+
+        // Nothing is bound until the view lists its actions
+        let result = engine.run_action_shortcut(&lua, "cmd+o", &items).unwrap();
+        assert!(result.is_none());
+
+        let actions = engine.get_applicable_actions(&lua, &items).unwrap();
+        assert_eq!(actions[0].shortcut.as_deref(), Some("cmd+o"));
+        assert_eq!(actions[1].shortcut, None);
+
+        let result = engine.run_action_shortcut(&lua, "cmd-o", &items).unwrap();
+        assert!(matches!(
+            result,
+            Some(ActionResult::Complete { message, .. }) if message == "opened a.txt"
+        ));
+        assert!(engine
+            .run_action_shortcut(&lua, "cmd+c", &items)
+            .unwrap()
+            .is_none());
+
+        // Bindings belong to the view they were listed in
+        engine.apply_effects(&lua, vec![Effect::Pop]);
+        assert!(engine
+            .run_action_shortcut(&lua, "cmd+o", &items)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_undo_runs_newest_registered_function() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry.clone());
+        let lua = Lua::new();
+
+        assert_eq!(engine.undo(&lua).unwrap(), None);
+
+        let undone: mlua::Function = lua
+            .load("return function() undone = (undone or 0) + 1 end")
+            .eval()
+            .unwrap();
+        let key = "undo:test".to_string();
+        let func = LuaFunctionRef::from_function(&lua, undone, key.clone()).unwrap();
+        registry.undo().push("Archived note", UndoOp::Lua(func));
+        assert_eq!(engine.latest_undo().unwrap().description, "Archived note");
+
+        assert_eq!(engine.undo(&lua).unwrap().as_deref(), Some("Archived note"));
+        assert_eq!(lua.globals().get::<i64>("undone").unwrap(), 1);
+        assert!(engine.latest_undo().is_none());
+        assert!(lua
+            .named_registry_value::<mlua::Value>(&key)
+            .unwrap()
+            .is_nil());
+    }
+
+    #[test]
+    fn test_shutdown_runs_hooks_until_deadline() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry.clone());
+        let lua = Lua::new();
+
+        for (i, source) in [
+            "return function() error('disk full') end",
+            "return function(ms_left) saved = ms_left end",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let func: mlua::Function = lua.load(source).eval().unwrap();
+            let key = format!("hook:on_shutdown:{}", i);
+            let func = LuaFunctionRef::from_function(&lua, func, key).unwrap();
+            registry.hooks().add("on_shutdown", func);
+        }
+        registry
+            .timers()
+            .start(std::time::Duration::from_secs(60), "Tea", None);
+
+        // Past the deadline, no hook runs
+        let past = Instant::now() - std::time::Duration::from_millis(1);
+        assert_eq!(engine.run_shutdown_hooks(&lua, past), 0);
+
+        // A failing hook doesn't stop the next one
+        let deadline = Instant::now() + std::time::Duration::from_secs(5);
+        assert_eq!(engine.run_shutdown_hooks(&lua, deadline), 2);
+        let ms_left: u64 = lua.globals().get("saved").unwrap();
+        assert!(ms_left > 0 && ms_left <= 5000);
+
+        registry.shutdown();
+        assert!(registry.timers().is_empty());
+    }
+
+    #[test]
+    fn test_complete_offers_follow_up_actions() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        let handler: mlua::Function = lua
+            .load(
+                r#"
+            return function(items, ctx)
+                ctx:complete("Uploaded " .. items[1].id, {
+                    { title = "Copy Link", handler = function(items, ctx) ctx:complete("Copied") end },
+                })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:upload", handler)
+            .unwrap();
+        engine.initialize(&lua);
+
+        let items = vec![Item::new("a.png", "a.png")];
+        let result = engine
+            .execute_action(&lua, "files", "test:upload", &items)
+            .unwrap();
+        let ActionResult::Complete { message, actions } = result else {
+            panic!("expected Complete, got {:?}", result);
+        };
+        assert_eq!(message, "Uploaded a.png");
+        assert_eq!(actions[0].title, "Copy Link");
+
+        // The follow-up runs like an action, then its handler is freed
+        let key = actions[0].handler_key.clone().unwrap();
+        let result = engine.execute_action(&lua, "files", &key, &items).unwrap();
+        assert!(matches!(result, ActionResult::Complete { message, .. } if message == "Copied"));
+        assert!(lua
+            .named_registry_value::<Option<mlua::Function>>(&key)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_executed_actions_are_logged() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry.clone());
+        let lua = Lua::new();
+
+        let handler: mlua::Function = lua
+            .load(
+                r#"
+            return function(items, ctx)
+                ctx:fail({ code = "offline", message = "No network" })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("action:sync:1", handler)
+            .unwrap();
+        engine.initialize(&lua);
+
+        let items = vec![Item::new("notes", "Notes")];
+        engine
+            .execute_action(&lua, "files", "action:sync:1", &items)
+            .unwrap();
+
+        let entry = &registry.activity().entries()[0];
+        assert_eq!(entry.action, "sync");
+        assert_eq!(entry.handler_key, "action:sync:1");
+        assert_eq!(entry.items[0].id, "notes");
+        assert_eq!(
+            entry.outcome,
+            crate::activity::Outcome::Failed {
+                message: "No network".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_repeat_last_action_confirms_destructive_actions() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        let handler: mlua::Function = lua
+            .load(
+                r#"
+            deleted = 0
+            return function(items, ctx)
+                deleted = deleted + #items
+                ctx:complete("Deleted " .. items[1].id)
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("action:delete:1", handler)
+            .unwrap();
+        engine.initialize(&lua);
+        assert!(matches!(
+            engine.repeat_last_action(&lua, None).unwrap(),
+            RepeatOutcome::Nothing
+        ));
+
+        engine
+            .destructive_keys
+            .lock()
+            .insert("action:delete:1".to_string());
+        let items = vec![Item::new("a.txt", "a.txt")];
+        engine
+            .execute_action(&lua, "files", "action:delete:1", &items)
+            .unwrap();
+
+        // The first repeat only asks; repeating with the entry's ID runs it
+        let RepeatOutcome::NeedsConfirmation(entry) =
+            engine.repeat_last_action(&lua, None).unwrap()
+        else {
+            panic!("expected NeedsConfirmation");
+        };
+        assert_eq!(entry.action, "delete");
+        let outcome = engine.repeat_last_action(&lua, Some(entry.id)).unwrap();
+        let RepeatOutcome::Ran(ActionResult::Complete { message, .. }) = outcome else {
+            panic!("expected Complete, got {:?}", outcome);
+        };
+        assert_eq!(message, "Deleted a.txt");
+        assert_eq!(lua.globals().get::<i64>("deleted").unwrap(), 2);
+    }
+
+    #[test]
+    fn test_inline_edit_delivers_submitted_text() {
+        let registry = Arc::new(PluginRegistry::new());
+        let engine = QueryEngine::new(registry);
+        let lua = Lua::new();
+
+        let handler: mlua::Function = lua
+            .load(
+                r#"
+            return function(items, ctx)
+                ctx:edit({
+                    on_submit = function(text, ctx)
+                        if text == "" then
+                            ctx:fail("Name can't be empty")
+                        else
+                            ctx:complete("Renamed " .. ctx.item.id .. " to " .. text)
+                        end
+                    end,
+                })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:rename", handler)
+            .unwrap();
+        engine.initialize(&lua);
+
+        let items = vec![Item::new("a.txt", "a.txt")];
+        let result = engine
+            .execute_action(&lua, "files", "test:rename", &items)
+            .unwrap();
+        let ActionResult::Edit(edit) = result else {
+            panic!("expected Edit, got {:?}", result);
+        };
+        assert_eq!(edit.item_id, "a.txt");
+        assert_eq!(edit.value, "a.txt");
+
+        // A failed submit keeps the edit open
+        let result = engine
+            .submit_edit(&lua, &edit.handler_key, "", &items)
+            .unwrap();
+        assert!(matches!(result, ActionResult::Fail { .. }));
+
+        // The callback is freed once the edit succeeds
+        let result = engine
+            .submit_edit(&lua, &edit.handler_key, "b.txt", &items)
+            .unwrap();
+        let ActionResult::Complete { message, .. } = result else {
+            panic!("expected Complete, got {:?}", result);
+        };
+        assert_eq!(message, "Renamed a.txt to b.txt");
+        assert!(lua
+            .named_registry_value::<Option<mlua::Function>>(&edit.handler_key)
+            .unwrap()
+            .is_none());
+    }
 }
@@ -2,12 +2,48 @@
 //!
 //! The key insight: mutation = notification. Every method that changes the stack
 //! also broadcasts the new state. Callers cannot mutate without notifying.
+//!
+//! Two channels carry the changes:
+//! - `subscribe()` - The latest states (a `watch`; intermediate states may be skipped)
+//! - `subscribe_changes()` - Every change as a `ViewStackEvent`, saying what
+//!   changed (pushed, popped, replaced, updated in place, cleared)
 
 use parking_lot::RwLock;
-use tokio::sync::watch;
+use tokio::sync::{broadcast, watch};
 
 use crate::types::{ViewInstance, ViewState};
 
+/// Events buffered per change subscriber before it lags behind.
+const CHANGE_CAPACITY: usize = 64;
+
+// =============================================================================
+// Change Events
+// =============================================================================
+
+/// What changed in the view stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewStackChange {
+    /// A view was pushed on top.
+    Pushed,
+    /// `count` views were popped off the top.
+    Popped { count: usize },
+    /// The top view was replaced by a new instance.
+    Replaced,
+    /// The view at `index` changed in place (same instance).
+    Updated { index: usize },
+    /// Every view was removed.
+    Cleared,
+}
+
+/// A view stack change, with the states after it.
+#[derive(Debug, Clone)]
+pub struct ViewStackEvent {
+    /// What changed.
+    pub change: ViewStackChange,
+    /// View states after the change (bottom = root).
+    pub states: Vec<ViewState>,
+}
+
 // =============================================================================
 // ObservableViewStack
 // =============================================================================
@@ -39,6 +75,7 @@ pub struct ObservableViewStack {
     inner: RwLock<Vec<ViewInstance>>,
     tx: watch::Sender<Vec<ViewState>>,
     rx: watch::Receiver<Vec<ViewState>>,
+    changes: broadcast::Sender<ViewStackEvent>,
 }
 
 #[allow(dead_code)]
@@ -46,10 +83,12 @@ impl ObservableViewStack {
     /// Create a new empty observable view stack.
     pub fn new() -> Self {
         let (tx, rx) = watch::channel(Vec::new());
+        let (changes, _) = broadcast::channel(CHANGE_CAPACITY);
         Self {
             inner: RwLock::new(Vec::new()),
             tx,
             rx,
+            changes,
         }
     }
 
@@ -67,7 +106,7 @@ impl ObservableViewStack {
             tracing::debug!("Pushed view, stack depth: {}", inner.len());
             self.snapshot(&inner)
         };
-        let _ = self.tx.send(states);
+        self.notify(ViewStackChange::Pushed, states);
     }
 
     /// Pop the top view from the stack.
@@ -84,29 +123,51 @@ impl ObservableViewStack {
             (result, self.snapshot(&inner))
         };
         if result.is_some() {
-            let _ = self.tx.send(states);
+            self.notify(ViewStackChange::Popped { count: 1 }, states);
         }
         result
     }
 
     /// Pop the top view only if there's more than one view.
     ///
-    /// Returns `true` if a view was popped, `false` if at root.
+    /// Returns the popped view, or `None` if at root.
     /// Broadcasts the new state only if something was popped.
-    pub fn pop_if_not_root(&self) -> bool {
+    pub fn pop_if_not_root(&self) -> Option<ViewInstance> {
         let (popped, states) = {
             let mut inner = self.inner.write();
             if inner.len() > 1 {
-                inner.pop();
+                let popped = inner.pop();
                 tracing::debug!("Popped view, stack depth: {}", inner.len());
-                (true, self.snapshot(&inner))
+                (popped, self.snapshot(&inner))
             } else {
                 tracing::debug!("Cannot pop: already at root view");
-                (false, Vec::new())
+                (None, Vec::new())
+            }
+        };
+        if popped.is_some() {
+            self.notify(ViewStackChange::Popped { count: 1 }, states);
+        }
+        popped
+    }
+
+    /// Pop views until only the first `len` remain.
+    ///
+    /// Returns the popped views (bottom to top), empty if the stack already has
+    /// `len` views or fewer. Broadcasts the new state only if something was popped.
+    pub fn truncate(&self, len: usize) -> Vec<ViewInstance> {
+        let (popped, states) = {
+            let mut inner = self.inner.write();
+            if inner.len() > len {
+                let popped = inner.split_off(len);
+                tracing::debug!("Truncated views, stack depth: {}", inner.len());
+                (popped, self.snapshot(&inner))
+            } else {
+                (Vec::new(), Vec::new())
             }
         };
-        if popped {
-            let _ = self.tx.send(states);
+        if !popped.is_empty() {
+            let count = popped.len();
+            self.notify(ViewStackChange::Popped { count }, states);
         }
         popped
     }
@@ -124,7 +185,11 @@ impl ObservableViewStack {
             tracing::debug!("Replaced view, stack depth: {}", inner.len());
             (old, self.snapshot(&inner))
         };
-        let _ = self.tx.send(states);
+        let change = match old {
+            Some(_) => ViewStackChange::Replaced,
+            None => ViewStackChange::Pushed,
+        };
+        self.notify(change, states);
         old
     }
 
@@ -137,7 +202,7 @@ impl ObservableViewStack {
             let mut inner = self.inner.write();
             std::mem::take(&mut *inner)
         };
-        let _ = self.tx.send(Vec::new());
+        self.notify(ViewStackChange::Cleared, Vec::new());
         old
     }
 
@@ -159,6 +224,22 @@ impl ObservableViewStack {
         }
     }
 
+    /// Modify the view instance with the given ID in place.
+    ///
+    /// Does NOT broadcast. Returns `true` if the instance is on the stack.
+    pub fn modify_instance<F>(&self, instance_id: u64, f: F) -> bool
+    where
+        F: FnOnce(&mut ViewInstance),
+    {
+        let mut inner = self.inner.write();
+        if let Some(view) = inner.iter_mut().find(|v| v.instance_id == instance_id) {
+            f(view);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Modify the top view and broadcast the change.
     ///
     /// Use this when the modification should notify subscribers.
@@ -177,7 +258,8 @@ impl ObservableViewStack {
             }
         };
         if modified {
-            let _ = self.tx.send(states);
+            let index = states.len() - 1;
+            self.notify(ViewStackChange::Updated { index }, states);
         }
         modified
     }
@@ -245,6 +327,15 @@ impl ObservableViewStack {
         self.rx.clone()
     }
 
+    /// Subscribe to change events.
+    ///
+    /// The receiver gets every change made after subscribing, in order. A
+    /// receiver that falls behind gets `RecvError::Lagged` and should resync
+    /// from `subscribe()`.
+    pub fn subscribe_changes(&self) -> broadcast::Receiver<ViewStackEvent> {
+        self.changes.subscribe()
+    }
+
     /// Force a broadcast of the current state.
     ///
     /// Useful after initialization to ensure subscribers have the initial state.
@@ -257,6 +348,16 @@ impl ObservableViewStack {
     // Internal Helpers
     // =========================================================================
 
+    /// Broadcast the states after a change on both channels.
+    fn notify(&self, change: ViewStackChange, states: Vec<ViewState>) {
+        // No change subscribers is fine
+        let _ = self.changes.send(ViewStackEvent {
+            change,
+            states: states.clone(),
+        });
+        let _ = self.tx.send(states);
+    }
+
     /// Create a snapshot of view states from the inner stack.
     fn snapshot(&self, inner: &[ViewInstance]) -> Vec<ViewState> {
         inner.iter().map(ViewState::from).collect()
@@ -289,7 +390,14 @@ mod tests {
             selection: SelectionMode::Single,
             on_select_fn: None,
             on_submit_fn: None,
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
             view_data: serde_json::Value::Null,
+            selected: Vec::new(),
+            refresh_ms: None,
+            initial_query: None,
         }
     }
 
@@ -344,12 +452,34 @@ mod tests {
         assert_eq!(rx.borrow().len(), 2);
 
         // Can pop child
-        assert!(stack.pop_if_not_root());
+        let popped = stack.pop_if_not_root().unwrap();
+        assert_eq!(popped.view.title, Some("Child".to_string()));
         assert_eq!(rx.borrow().len(), 1);
 
         // Cannot pop root
-        assert!(!stack.pop_if_not_root());
+        assert!(stack.pop_if_not_root().is_none());
+        assert_eq!(rx.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let stack = ObservableViewStack::new();
+        let rx = stack.subscribe();
+
+        stack.push(test_instance("Root"));
+        stack.push(test_instance("Folder"));
+        stack.push(test_instance("File"));
+
+        let popped = stack.truncate(1);
+        assert_eq!(popped.len(), 2);
+        assert_eq!(popped[0].view.title, Some("Folder".to_string()));
         assert_eq!(rx.borrow().len(), 1);
+        assert_eq!(rx.borrow()[0].title, Some("Root".to_string()));
+
+        // Nothing to pop
+        assert!(stack.truncate(1).is_empty());
+        assert!(stack.truncate(5).is_empty());
+        assert_eq!(stack.len(), 1);
     }
 
     #[test]
@@ -399,6 +529,25 @@ mod tests {
         assert_eq!(key_count, 1);
     }
 
+    #[test]
+    fn test_modify_instance() {
+        let stack = ObservableViewStack::new();
+        let root = test_instance("View 1");
+        let root_id = root.instance_id;
+        stack.push(root);
+        stack.push(test_instance("View 2"));
+
+        assert!(stack.modify_instance(root_id, |view| {
+            view.registry_keys.push("test_key".to_string());
+        }));
+        assert!(!stack.modify_instance(u64::MAX, |_| {}));
+
+        let keys = stack.with_stack(|s| (s[0].registry_keys.len(), s[1].registry_keys.len()));
+        assert_eq!(keys, (1, 0));
+        let ids = stack.with_stack(|s| s[0].instance_id != s[1].instance_id);
+        assert!(ids);
+    }
+
     #[test]
     fn test_modify_top_and_broadcast() {
         let stack = ObservableViewStack::new();
@@ -417,6 +566,38 @@ mod tests {
         assert_eq!(rx.borrow().len(), initial_len);
     }
 
+    #[test]
+    fn test_change_events() {
+        let stack = ObservableViewStack::new();
+        let mut changes = stack.subscribe_changes();
+
+        stack.push(test_instance("Root"));
+        stack.push(test_instance("Folder"));
+        stack.push(test_instance("File"));
+        stack.replace_top(test_instance("Other File"));
+        stack.modify_top_and_broadcast(|_| {});
+        stack.truncate(1);
+        stack.pop_if_not_root();
+        stack.clear();
+
+        let mut received = Vec::new();
+        while let Ok(event) = changes.try_recv() {
+            received.push((event.change, event.states.len()));
+        }
+        assert_eq!(
+            received,
+            vec![
+                (ViewStackChange::Pushed, 1),
+                (ViewStackChange::Pushed, 2),
+                (ViewStackChange::Pushed, 3),
+                (ViewStackChange::Replaced, 3),
+                (ViewStackChange::Updated { index: 2 }, 3),
+                (ViewStackChange::Popped { count: 2 }, 1),
+                (ViewStackChange::Cleared, 0),
+            ]
+        );
+    }
+
     #[test]
     fn test_with_top() {
         let stack = ObservableViewStack::new();
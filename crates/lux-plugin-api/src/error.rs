@@ -43,6 +43,14 @@ pub enum PluginError {
     /// Channel receive error.
     #[error("Channel receive failed: {0}")]
     ChannelRecv(String),
+
+    /// External command failed.
+    #[error("{0}")]
+    Command(String),
+
+    /// A privacy permission has not been granted.
+    #[error("Permission '{0}' not granted")]
+    PermissionDenied(String),
 }
 
 impl From<PluginError> for mlua::Error {
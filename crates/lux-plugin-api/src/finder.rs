@@ -0,0 +1,164 @@
+//! Finder integration: acting on the files selected in Finder.
+//!
+//! This module provides:
+//! - `finder_selection()` - Paths selected in the frontmost Finder window (lux.finder.selection)
+//! - `selection_items()` - File items for those paths
+//! - `selection_actions()` / `run_selection_action()` - Open, reveal, or copy every selected file
+//!
+//! The `send_to_lux` global hotkey reads the selection while Finder is still
+//! frontmost and opens the "Selected Files" view with every file selected,
+//! so actions run on all of them:
+//!
+//! ```lua
+//! lux.keymap.set_global("cmd+shift+l", "send_to_lux")
+//!
+//! -- More actions for the selected files (handlers get every selected item)
+//! lux.finder.add_action({ id = "share", title = "Share", handler = share_files })
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::t;
+use lux_core::{DragPayload, Item};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::spotlight::abbreviate_home;
+
+/// View ID of the "Selected Files" view opened by `send_to_lux`.
+pub const SELECTION_VIEW_ID: &str = "builtin.finder_selection";
+
+/// Lua registry key of the `send_to_lux` global hotkey handler.
+pub const SEND_TO_LUX_KEY: &str = "finder:send_to_lux";
+
+/// Lua registry key of the list of actions added with `lux.finder.add_action()`.
+pub const SELECTION_ACTIONS_KEY: &str = "finder:actions";
+
+/// JXA script printing the paths selected in Finder as a JSON array.
+#[cfg(not(target_os = "linux"))]
+const SELECTION_SCRIPT: &str = r#"
+ObjC.import('Foundation');
+const finder = Application('Finder');
+JSON.stringify(finder.selection().map(item => $.NSURL.URLWithString(item.url()).path.js));
+"#;
+
+// =============================================================================
+// Selection
+// =============================================================================
+
+/// Paths of the files and folders selected in the frontmost Finder window.
+///
+/// Empty when nothing is selected.
+#[cfg(not(target_os = "linux"))]
+pub fn finder_selection() -> PluginResult<Vec<PathBuf>> {
+    let output = desktop::current()
+        .run_script(SELECTION_SCRIPT, &[])
+        .map_err(|e| PluginError::Command(format!("Finder selection failed: {}", e)))?;
+    parse_selection(&output)
+}
+
+/// Linux file managers don't expose their selection; always an error.
+#[cfg(target_os = "linux")]
+pub fn finder_selection() -> PluginResult<Vec<PathBuf>> {
+    Err(PluginError::Command(
+        "Reading the file manager selection requires Finder (macOS)".to_string(),
+    ))
+}
+
+/// Parse the JSON path list printed by the selection script.
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn parse_selection(json: &str) -> PluginResult<Vec<PathBuf>> {
+    let paths: Vec<String> = serde_json::from_str(json.trim())
+        .map_err(|e| PluginError::Command(format!("Invalid Finder selection: {}", e)))?;
+    Ok(paths.into_iter().map(PathBuf::from).collect())
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item for a selected file.
+fn selection_item(path: &Path) -> Item {
+    let path_str = path.to_string_lossy().to_string();
+    let mut item = Item::new(
+        format!("finder:{}", path_str),
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path_str.clone()),
+    )
+    .with_icon(if path.is_dir() { "📁" } else { "📄" })
+    .with_type("file")
+    .with_data(serde_json::json!({ "path": path_str }))
+    .with_drag(DragPayload::File { path: path_str });
+    item.subtitle = path.parent().map(abbreviate_home);
+    item
+}
+
+/// Items for the selected paths, in selection order.
+pub fn selection_items(paths: &[PathBuf]) -> Vec<Item> {
+    paths.iter().map(|path| selection_item(path)).collect()
+}
+
+/// The path stored in a file item's data.
+pub fn item_path(item: &Item) -> Option<&str> {
+    item.data.as_ref()?.get("path")?.as_str()
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for the selected files: `(id, title)`.
+pub fn selection_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("open", t("Open")),
+        ("reveal", t("Reveal in Finder")),
+        ("copy_paths", t("Copy Paths")),
+    ]
+}
+
+/// Run a selection action on every item it was invoked with.
+pub fn run_selection_action(action_id: &str, items: &[Item]) -> PluginResult<()> {
+    let paths: Vec<&str> = items.iter().filter_map(item_path).collect();
+    if paths.is_empty() {
+        return Err(PluginError::Command("No files selected".to_string()));
+    }
+
+    let desktop = desktop::current();
+    match action_id {
+        "open" => paths.iter().try_for_each(|path| desktop.open(path)),
+        "reveal" => paths.iter().try_for_each(|path| desktop.reveal(path)),
+        "copy_paths" => desktop.clipboard_write(&paths.join("\n")),
+        _ => Err(PluginError::Command(format!(
+            "Unknown selection action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(
+            parse_selection("[\"/Users/me/a.pdf\",\"/Users/me/My Folder\"]\n").unwrap(),
+            vec![
+                PathBuf::from("/Users/me/a.pdf"),
+                PathBuf::from("/Users/me/My Folder")
+            ]
+        );
+        assert!(parse_selection("[]").unwrap().is_empty());
+        assert!(parse_selection("execution error").is_err());
+    }
+
+    #[test]
+    fn test_selection_items() {
+        let items = selection_items(&[PathBuf::from("/tmp/report.pdf")]);
+        assert_eq!(items[0].id, "finder:/tmp/report.pdf");
+        assert_eq!(items[0].title, "report.pdf");
+        assert!(items[0].has_type("file"));
+        assert_eq!(item_path(&items[0]), Some("/tmp/report.pdf"));
+    }
+}
@@ -0,0 +1,459 @@
+//! Random values: passwords, PINs, tokens, UUIDs, and placeholder text.
+//!
+//! This module provides:
+//! - `PasswordOptions` / `password()` - Passwords with a configurable length and character set
+//! - `uuid_v4()` / `uuid_v7()` - Random and time-ordered UUIDs
+//! - `lorem()` - Lorem ipsum placeholder text
+//! - `generator_items()` - One row per generator, for the built-in Generator view
+//!
+//! Randomness comes from the operating system's generator (`getrandom`), never
+//! from Lua's `math.random`. The built-in Generator view
+//! (`lux.builtin.generator()`) lists a fresh value of each kind;
+//! `lux.generate.*` exposes the same functions to Lua.
+
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+
+use crate::error::{PluginError, PluginResult};
+
+/// View ID of the built-in Generator view.
+pub const GENERATOR_VIEW_ID: &str = "builtin.generator";
+
+/// Type tag for generated values.
+pub const GENERATED_ITEM_TYPE: &str = "generated";
+
+/// Characters of a value shown in a row's title.
+const PREVIEW_LENGTH: usize = 80;
+
+/// Longest password, PIN, or token the view generates.
+const MAX_LENGTH: usize = 1024;
+
+/// Most lorem ipsum words the view generates.
+const MAX_WORDS: usize = 10_000;
+
+/// Words of lorem ipsum generated when the query has no count.
+const DEFAULT_WORDS: usize = 50;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.?/~";
+
+/// Characters that are easy to misread (`l`/`1`/`I`, `O`/`0`).
+const AMBIGUOUS: &str = "lI1O0o";
+
+const LOREM_OPENING: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit";
+const LOREM_WORDS: &str =
+    "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod tempor \
+    incididunt ut labore et dolore magna aliqua enim ad minim veniam quis nostrud \
+    exercitation ullamco laboris nisi aliquip ex ea commodo consequat duis aute \
+    irure in reprehenderit voluptate velit esse cillum eu fugiat nulla pariatur \
+    excepteur sint occaecat cupidatat non proident sunt culpa qui officia deserunt \
+    mollit anim id est laborum";
+
+// =============================================================================
+// Randomness
+// =============================================================================
+
+/// A uniformly random number below `bound` (which must be non-zero).
+fn random_below(bound: usize) -> PluginResult<usize> {
+    let bound = bound as u64;
+    // Reject the top partial range so every value is equally likely
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+        let mut bytes = [0; 8];
+        getrandom::fill(&mut bytes)
+            .map_err(|e| PluginError::Command(format!("Random number generator failed: {}", e)))?;
+        let value = u64::from_le_bytes(bytes);
+        if value < zone {
+            return Ok((value % bound) as usize);
+        }
+    }
+}
+
+/// Pick a random element.
+fn choose<T: Copy>(items: &[T]) -> PluginResult<T> {
+    Ok(items[random_below(items.len())?])
+}
+
+/// Shuffle in place (Fisher-Yates).
+fn shuffle<T>(items: &mut [T]) -> PluginResult<()> {
+    for i in (1..items.len()).rev() {
+        items.swap(i, random_below(i + 1)?);
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Generators
+// =============================================================================
+
+/// Which characters a password uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordOptions {
+    /// Number of characters.
+    pub length: usize,
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+    /// Leave out characters that are easy to misread (`l`, `1`, `O`, `0`, ...).
+    pub exclude_ambiguous: bool,
+}
+
+impl Default for PasswordOptions {
+    fn default() -> Self {
+        Self {
+            length: 20,
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+            exclude_ambiguous: false,
+        }
+    }
+}
+
+impl PasswordOptions {
+    /// The enabled character classes.
+    fn classes(&self) -> Vec<Vec<char>> {
+        [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, chars)| {
+            chars
+                .chars()
+                .filter(|c| !(self.exclude_ambiguous && AMBIGUOUS.contains(*c)))
+                .collect()
+        })
+        .collect()
+    }
+}
+
+/// Generate a password.
+///
+/// Every enabled character class appears at least once when the password is
+/// long enough to hold one of each.
+pub fn password(options: &PasswordOptions) -> PluginResult<String> {
+    let classes = options.classes();
+    let all: Vec<char> = classes.concat();
+    if all.is_empty() {
+        return Err(PluginError::Command(
+            "Password has no characters to choose from".to_string(),
+        ));
+    }
+
+    let mut chars = Vec::with_capacity(options.length);
+    if options.length >= classes.len() {
+        for class in &classes {
+            chars.push(choose(class)?);
+        }
+    }
+    while chars.len() < options.length {
+        chars.push(choose(&all)?);
+    }
+    shuffle(&mut chars)?;
+    Ok(chars.into_iter().collect())
+}
+
+/// Generate a string of `length` characters from `charset`.
+pub fn random_string(length: usize, charset: &str) -> PluginResult<String> {
+    let chars: Vec<char> = charset.chars().collect();
+    if chars.is_empty() {
+        return Err(PluginError::Command("Character set is empty".to_string()));
+    }
+    (0..length).map(|_| choose(&chars)).collect()
+}
+
+/// A random (version 4) UUID.
+pub fn uuid_v4() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// A time-ordered (version 7) UUID.
+pub fn uuid_v7() -> String {
+    uuid::Uuid::now_v7().to_string()
+}
+
+/// Lorem ipsum text of `words` words, starting with the classic opening.
+///
+/// Sentences are 6-14 words long.
+pub fn lorem(words: usize) -> PluginResult<String> {
+    let mut out: Vec<String> = LOREM_OPENING
+        .split(' ')
+        .take(words)
+        .map(String::from)
+        .collect();
+    let vocabulary: Vec<&str> = LOREM_WORDS.split(' ').collect();
+    let mut sentence_len = out.len();
+    let mut sentence_end = 8;
+
+    while out.len() < words {
+        if sentence_len >= sentence_end {
+            end_sentence(&mut out);
+            sentence_len = 0;
+            sentence_end = 6 + random_below(9)?;
+        }
+        let word = choose(&vocabulary)?;
+        out.push(if sentence_len == 0 {
+            capitalize(word)
+        } else {
+            word.to_string()
+        });
+        sentence_len += 1;
+    }
+    if !out.is_empty() {
+        end_sentence(&mut out);
+    }
+    Ok(out.join(" "))
+}
+
+/// End the sentence at the last word with a period.
+fn end_sentence(words: &mut [String]) {
+    if let Some(last) = words.last_mut() {
+        let trimmed = last.trim_end_matches(',').len();
+        last.truncate(trimmed);
+        last.push('.');
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// =============================================================================
+// Generator View
+// =============================================================================
+
+/// A kind of generated value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Generator {
+    Password,
+    Pin,
+    Hex,
+    UuidV4,
+    UuidV7,
+    Lorem,
+}
+
+impl Generator {
+    /// Every generator, in display order.
+    pub const ALL: [Generator; 6] = [
+        Generator::Password,
+        Generator::Pin,
+        Generator::Hex,
+        Generator::UuidV4,
+        Generator::UuidV7,
+        Generator::Lorem,
+    ];
+
+    /// Identifier used from Lua and in the query (e.g. "uuid7").
+    pub fn id(&self) -> &'static str {
+        match self {
+            Generator::Password => "password",
+            Generator::Pin => "pin",
+            Generator::Hex => "hex",
+            Generator::UuidV4 => "uuid",
+            Generator::UuidV7 => "uuid7",
+            Generator::Lorem => "lorem",
+        }
+    }
+
+    /// Name shown in the view.
+    pub fn title(&self) -> String {
+        match self {
+            Generator::Password => t("Password"),
+            Generator::Pin => t("PIN"),
+            Generator::Hex => t("Hex Token"),
+            Generator::UuidV4 => t("UUID v4"),
+            Generator::UuidV7 => t("UUID v7"),
+            Generator::Lorem => t("Lorem Ipsum"),
+        }
+    }
+
+    /// Generate a value. `count` overrides the length (or word count for lorem ipsum).
+    pub fn generate(
+        &self,
+        options: &PasswordOptions,
+        count: Option<usize>,
+    ) -> PluginResult<(String, String)> {
+        let length = |default: usize| count.unwrap_or(default).min(MAX_LENGTH);
+        let characters =
+            |length: usize| t_args("{count} characters", &[("count", &length.to_string())]);
+        Ok(match self {
+            Generator::Password => {
+                let options = PasswordOptions {
+                    length: length(options.length),
+                    ..options.clone()
+                };
+                (password(&options)?, characters(options.length))
+            }
+            Generator::Pin => {
+                let length = length(6);
+                (random_string(length, DIGITS)?, characters(length))
+            }
+            Generator::Hex => {
+                let length = length(32);
+                (
+                    random_string(length, "0123456789abcdef")?,
+                    characters(length),
+                )
+            }
+            Generator::UuidV4 => (uuid_v4(), t("Random")),
+            Generator::UuidV7 => (uuid_v7(), t("Time-ordered")),
+            Generator::Lorem => {
+                let words = count.unwrap_or(DEFAULT_WORDS).min(MAX_WORDS);
+                let detail = t_args("{count} words", &[("count", &words.to_string())]);
+                (lorem(words)?, detail)
+            }
+        })
+    }
+}
+
+/// Build one row per generator matching `query`, each with a fresh value.
+///
+/// A number in the query sets the length (`password 32`, `pin 4`) or the
+/// word count (`lorem 100`); the other words filter the generators.
+pub fn generator_items(query: &str, options: &PasswordOptions) -> PluginResult<Vec<Item>> {
+    let mut count = None;
+    let mut filter = Vec::new();
+    for word in query.split_whitespace() {
+        match word.parse::<usize>() {
+            Ok(n) if n > 0 => count = Some(n),
+            _ => filter.push(word.to_lowercase()),
+        }
+    }
+
+    let mut items = Vec::new();
+    for generator in Generator::ALL {
+        let title = generator.title().to_lowercase();
+        if !filter
+            .iter()
+            .all(|word| generator.id().contains(word.as_str()) || title.contains(word.as_str()))
+        {
+            continue;
+        }
+        // UUIDs have a fixed length
+        let count = match generator {
+            Generator::UuidV4 | Generator::UuidV7 => None,
+            _ => count,
+        };
+        let (value, detail) = generator.generate(options, count)?;
+        items.push(
+            Item::new(format!("generate:{}", generator.id()), preview(&value))
+                .with_subtitle(format!("{} · {}", generator.title(), detail))
+                .with_type(GENERATED_ITEM_TYPE)
+                .with_data(serde_json::json!({
+                    "generator": generator.id(),
+                    "value": value,
+                })),
+        );
+    }
+    Ok(items)
+}
+
+/// The full generated value of a row.
+pub fn generated_value(item: &Item) -> Option<String> {
+    item.data
+        .as_ref()
+        .and_then(|data| data.get("value")?.as_str())
+        .map(String::from)
+}
+
+/// A value shortened to fit a title.
+fn preview(value: &str) -> String {
+    let mut preview: String = value.chars().take(PREVIEW_LENGTH).collect();
+    if preview.len() < value.len() {
+        preview.push('…');
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_options() {
+        let options = PasswordOptions {
+            length: 40,
+            symbols: false,
+            exclude_ambiguous: true,
+            ..PasswordOptions::default()
+        };
+        let generated = password(&options).unwrap();
+        assert_eq!(generated.chars().count(), 40);
+        assert!(generated.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert!(!generated.chars().any(|c| AMBIGUOUS.contains(c)));
+        assert!(generated.chars().any(|c| c.is_ascii_lowercase()));
+        assert!(generated.chars().any(|c| c.is_ascii_uppercase()));
+        assert!(generated.chars().any(|c| c.is_ascii_digit()));
+
+        let short = PasswordOptions {
+            length: 2,
+            ..PasswordOptions::default()
+        };
+        assert_eq!(password(&short).unwrap().len(), 2);
+
+        let none = PasswordOptions {
+            lowercase: false,
+            uppercase: false,
+            digits: false,
+            symbols: false,
+            ..PasswordOptions::default()
+        };
+        assert!(password(&none).is_err());
+        assert_ne!(password(&options).unwrap(), generated);
+    }
+
+    #[test]
+    fn test_uuids_and_lorem() {
+        let v4 = uuid_v4();
+        assert_eq!(v4.len(), 36);
+        assert_eq!(&v4[14..15], "4");
+        assert_eq!(&uuid_v7()[14..15], "7");
+
+        assert_eq!(lorem(2).unwrap(), "Lorem ipsum.");
+        let text = lorem(30).unwrap();
+        assert_eq!(text.split(' ').count(), 30);
+        assert!(text.starts_with("Lorem ipsum dolor sit amet, consectetur adipiscing elit."));
+        assert!(text.ends_with('.'));
+        assert_eq!(lorem(0).unwrap(), "");
+    }
+
+    #[test]
+    fn test_generator_items() {
+        let options = PasswordOptions::default();
+        let ids = |query: &str| -> Vec<String> {
+            generator_items(query, &options)
+                .unwrap()
+                .iter()
+                .map(|item| item.id.clone())
+                .collect()
+        };
+        assert_eq!(ids("").len(), Generator::ALL.len());
+        assert_eq!(ids("uuid"), ["generate:uuid", "generate:uuid7"]);
+        assert_eq!(ids("v7"), ["generate:uuid7"]);
+
+        let items = generator_items("pin 4", &options).unwrap();
+        assert_eq!(items.len(), 1);
+        let value = generated_value(&items[0]).unwrap();
+        assert_eq!(value.len(), 4);
+        assert!(value.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(items[0].subtitle.as_deref(), Some("PIN · 4 characters"));
+
+        let items = generator_items("lorem 200", &options).unwrap();
+        let value = generated_value(&items[0]).unwrap();
+        assert_eq!(value.split(' ').count(), 200);
+        assert!(items[0].title.ends_with('…'));
+    }
+}
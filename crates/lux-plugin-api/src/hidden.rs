@@ -0,0 +1,364 @@
+//! Hidden results, sources, and plugin views.
+//!
+//! This module provides:
+//! - `Blocklist` - Hidden root-view results and turned-off sources and views, saved to the data directory
+//! - `hide_action()` / `run_hide_action()` - "Hide This Result" for any root-view result
+//! - `hidden_groups()` / `hidden_actions()` / `run_hidden_action()` - The "Hidden Results" view
+//!
+//! Unlike a profile, the blocklist applies whichever profile is active. A
+//! turned-off source is one of `profiles::BUILTIN_SOURCES`; a turned-off view
+//! can't be listed or pushed, which switches off the plugin that added it.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::t;
+use lux_core::{Group, Groups, Item};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PluginError, PluginResult};
+use crate::profiles::BUILTIN_SOURCES;
+use crate::storage;
+
+/// ID of the "Hidden Results" view.
+pub const HIDDEN_VIEW_ID: &str = "builtin.hidden";
+
+/// Handler key of the built-in "Hide This Result" action.
+pub const HIDE_ACTION_KEY: &str = "hidden:hide";
+
+/// Type tag for the entries of the "Hidden Results" view.
+const ENTRY_ITEM_TYPE: &str = "hidden_entry";
+
+/// What the blocklist holds, as saved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Entries {
+    /// Copies of hidden results, so they can be listed for un-hiding.
+    #[serde(default)]
+    items: Vec<Item>,
+    /// Turned-off built-in sources.
+    #[serde(default)]
+    sources: BTreeSet<String>,
+    /// Turned-off view IDs.
+    #[serde(default)]
+    views: BTreeSet<String>,
+}
+
+/// Hidden results and turned-off sources and views.
+///
+/// Saved as a JSON object after every change.
+pub struct Blocklist {
+    path: Option<PathBuf>,
+    entries: Mutex<Entries>,
+}
+
+impl Blocklist {
+    /// Load the blocklist from `path` (None keeps it in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let entries = path.as_deref().map(load_entries).unwrap_or_default();
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Where the blocklist is saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("hidden.json"))
+    }
+
+    /// Hidden results, oldest first.
+    pub fn hidden_items(&self) -> Vec<Item> {
+        self.entries.lock().items.clone()
+    }
+
+    /// Whether the result with `id` is hidden.
+    pub fn is_item_hidden(&self, id: &str) -> bool {
+        self.entries.lock().items.iter().any(|item| item.id == id)
+    }
+
+    /// Hide a result from the root view.
+    pub fn hide_item(&self, item: Item) {
+        self.update(|entries| {
+            if entries.items.iter().any(|hidden| hidden.id == item.id) {
+                return false;
+            }
+            entries.items.push(item);
+            true
+        });
+    }
+
+    /// Show a hidden result again. Returns true if it was hidden.
+    pub fn unhide_item(&self, id: &str) -> bool {
+        self.update(|entries| {
+            let len = entries.items.len();
+            entries.items.retain(|item| item.id != id);
+            entries.items.len() != len
+        })
+    }
+
+    /// Whether a built-in source is turned off.
+    pub fn is_source_disabled(&self, source: &str) -> bool {
+        self.entries.lock().sources.contains(source)
+    }
+
+    /// Turn a built-in source on or off.
+    pub fn set_source_enabled(&self, source: &str, enabled: bool) -> PluginResult<()> {
+        if !BUILTIN_SOURCES.contains(&source) {
+            return Err(PluginError::Command(format!(
+                "Unknown built-in source '{}'",
+                source
+            )));
+        }
+        self.update(|entries| toggle(&mut entries.sources, source, enabled));
+        Ok(())
+    }
+
+    /// Whether a view is turned off.
+    pub fn is_view_disabled(&self, view_id: &str) -> bool {
+        self.entries.lock().views.contains(view_id)
+    }
+
+    /// Turn a view on or off.
+    pub fn set_view_enabled(&self, view_id: &str, enabled: bool) {
+        self.update(|entries| toggle(&mut entries.views, view_id, enabled));
+    }
+
+    /// Remove hidden results from `groups`, dropping groups left empty.
+    pub fn filter(&self, groups: &mut Groups) {
+        let entries = self.entries.lock();
+        if entries.items.is_empty() {
+            return;
+        }
+        for group in groups.iter_mut() {
+            group
+                .items
+                .retain(|item| !entries.items.iter().any(|hidden| hidden.id == item.id));
+        }
+        groups.retain(|group| !group.is_empty());
+    }
+
+    /// Apply `f` and save if it reports a change. Returns what `f` returned.
+    fn update(&self, f: impl FnOnce(&mut Entries) -> bool) -> bool {
+        let mut entries = self.entries.lock();
+        let changed = f(&mut entries);
+        if changed {
+            if let Some(path) = &self.path {
+                if let Err(e) = save_entries(path, &entries) {
+                    tracing::warn!("Failed to save hidden results: {}", e);
+                }
+            }
+        }
+        changed
+    }
+}
+
+impl Default for Blocklist {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+/// Add `name` to `set` when turning it off, remove it when turning it on.
+fn toggle(set: &mut BTreeSet<String>, name: &str, enabled: bool) -> bool {
+    if enabled {
+        set.remove(name)
+    } else {
+        set.insert(name.to_string())
+    }
+}
+
+fn load_entries(path: &Path) -> Entries {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_entries(path: &Path, entries: &Entries) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(entries)?)
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// The hide action offered for root-view results: `(id, title)`.
+pub fn hide_action() -> (&'static str, String) {
+    ("hide", t("Hide This Result"))
+}
+
+/// Hide a root-view result.
+pub fn run_hide_action(item: &Item, blocklist: &Blocklist) {
+    blocklist.hide_item(item.clone());
+}
+
+// =============================================================================
+// Hidden Results View
+// =============================================================================
+
+/// Build the groups of the "Hidden Results" view.
+///
+/// Lists hidden results, built-in sources, and registered views (`view_ids`)
+/// whose name matches `query`, each with whether it's shown.
+pub fn hidden_groups(blocklist: &Blocklist, view_ids: &[String], query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let matches = |text: &str| text.to_lowercase().contains(&query);
+    let state = |disabled: bool| if disabled { t("Off") } else { t("On") };
+
+    let items: Vec<Item> = blocklist
+        .hidden_items()
+        .into_iter()
+        .filter(|item| matches(&item.title))
+        .map(|item| entry_item("item", &item.id, &item.title, item.subtitle.clone(), None))
+        .collect();
+    let sources: Vec<Item> = BUILTIN_SOURCES
+        .iter()
+        .filter(|source| matches(source))
+        .map(|source| {
+            let off = blocklist.is_source_disabled(source);
+            entry_item("source", source, source, None, Some(state(off)))
+        })
+        .collect();
+    let views: Vec<Item> = view_ids
+        .iter()
+        .filter(|id| matches(id))
+        .map(|id| {
+            let off = blocklist.is_view_disabled(id);
+            entry_item("view", id, id, None, Some(state(off)))
+        })
+        .collect();
+
+    [
+        (t("Hidden Results"), items),
+        (t("Sources"), sources),
+        (t("Plugins"), views),
+    ]
+    .into_iter()
+    .filter(|(_, items)| !items.is_empty())
+    .map(|(title, items)| Group::new(title, items))
+    .collect()
+}
+
+fn entry_item(
+    kind: &str,
+    id: &str,
+    title: &str,
+    subtitle: Option<String>,
+    state: Option<String>,
+) -> Item {
+    let mut item = Item::new(format!("hidden:{}:{}", kind, id), title)
+        .with_type(ENTRY_ITEM_TYPE)
+        .with_data(serde_json::json!({ "kind": kind, "id": id }));
+    item.subtitle = subtitle;
+    if let Some(state) = state {
+        item = item.with_accessory(state);
+    }
+    item
+}
+
+/// Read an entry of the "Hidden Results" view: `(kind, id)`.
+fn entry(item: &Item) -> Option<(String, String)> {
+    if !item.has_type(ENTRY_ITEM_TYPE) {
+        return None;
+    }
+    let data = item.data.as_ref()?;
+    let kind = data.get("kind")?.as_str()?.to_string();
+    let id = data.get("id")?.as_str()?.to_string();
+    Some((kind, id))
+}
+
+/// Actions for an entry of the "Hidden Results" view: `(id, title)`.
+pub fn hidden_actions(item: &Item, blocklist: &Blocklist) -> Vec<(&'static str, String)> {
+    let Some((kind, id)) = entry(item) else {
+        return Vec::new();
+    };
+    let off = match kind.as_str() {
+        "item" => return vec![("unhide", t("Show Result Again"))],
+        "source" => blocklist.is_source_disabled(&id),
+        _ => blocklist.is_view_disabled(&id),
+    };
+    if off {
+        vec![("enable", t("Turn On"))]
+    } else {
+        vec![("disable", t("Turn Off"))]
+    }
+}
+
+/// Run an action on an entry of the "Hidden Results" view.
+pub fn run_hidden_action(action_id: &str, item: &Item, blocklist: &Blocklist) -> PluginResult<()> {
+    let (kind, id) = entry(item)
+        .ok_or_else(|| PluginError::Command("Item is not a hidden entry".to_string()))?;
+    let enabled = action_id == "enable";
+    match (kind.as_str(), action_id) {
+        ("item", "unhide") => {
+            blocklist.unhide_item(&id);
+        }
+        ("source", "enable" | "disable") => blocklist.set_source_enabled(&id, enabled)?,
+        ("view", "enable" | "disable") => blocklist.set_view_enabled(&id, enabled),
+        _ => {
+            return Err(PluginError::Command(format!(
+                "Unknown action '{}' for hidden entry",
+                action_id
+            )))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocklist_filters_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hidden.json");
+        let blocklist = Blocklist::new(Some(path.clone()));
+
+        run_hide_action(&Item::new("chess", "Chess"), &blocklist);
+        blocklist.set_source_enabled("media", false).unwrap();
+        assert!(blocklist.set_source_enabled("nope", false).is_err());
+        blocklist.set_view_enabled("games", false);
+
+        let mut groups = vec![
+            Group::new("Apps", vec![Item::new("chess", "Chess")]),
+            Group::new("Other", vec![Item::new("mail", "Mail")]),
+        ];
+        blocklist.filter(&mut groups);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items[0].id, "mail");
+
+        let reloaded = Blocklist::new(Some(path));
+        assert!(reloaded.is_item_hidden("chess"));
+        assert!(reloaded.is_source_disabled("media"));
+        assert!(reloaded.is_view_disabled("games"));
+    }
+
+    #[test]
+    fn test_hidden_view_unhides_and_toggles() {
+        let blocklist = Blocklist::new(None);
+        blocklist.hide_item(Item::new("chess", "Chess"));
+        blocklist.set_view_enabled("games", false);
+
+        let groups = hidden_groups(&blocklist, &["games".to_string()], "");
+        let titles: Vec<_> = groups.iter().map(|g| g.title.clone().unwrap()).collect();
+        assert_eq!(titles, ["Hidden Results", "Sources", "Plugins"]);
+
+        let hidden = &groups[0].items[0];
+        assert_eq!(hidden_actions(hidden, &blocklist)[0].0, "unhide");
+        run_hidden_action("unhide", hidden, &blocklist).unwrap();
+        assert!(!blocklist.is_item_hidden("chess"));
+
+        let view = &groups[2].items[0];
+        assert_eq!(view.accessories, ["Off"]);
+        assert_eq!(hidden_actions(view, &blocklist)[0].0, "enable");
+        run_hidden_action("enable", view, &blocklist).unwrap();
+        assert!(!blocklist.is_view_disabled("games"));
+
+        // Filtering by name
+        let groups = hidden_groups(&blocklist, &["games".to_string()], "media");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items[0].title, "media");
+    }
+}
@@ -11,6 +11,10 @@
 //! - `get_actions` - Global actions hook
 //! - `views.{id}.search` - View-specific search hook
 //! - `views.{id}.get_actions` - View-specific actions hook
+//! - `on_shutdown` - Called once when Lux quits (not chained, see below)
+//!
+//! `on_shutdown` hooks receive the milliseconds left before the shutdown
+//! deadline; hooks still waiting when it passes are skipped.
 //!
 //! ## Execution Order
 //!
@@ -156,10 +160,23 @@ impl HookRegistry {
     /// - Global hooks wrap view hooks, which wrap the original
     /// - Result: view hooks see raw results, global hooks see modified results
     pub fn get_chain(&self, hook_name: &str, view_id: Option<&str>) -> Vec<LuaFunctionRef> {
+        self.get_chain_where(hook_name, view_id, |_| true)
+    }
+
+    /// Get the hook chain, skipping hook paths for which `enabled` returns false.
+    ///
+    /// `enabled` receives `views.{id}.{hook_name}` for view hooks and
+    /// `hook_name` for global hooks (used by profiles to switch hooks off).
+    pub fn get_chain_where(
+        &self,
+        hook_name: &str,
+        view_id: Option<&str>,
+        enabled: impl Fn(&str) -> bool,
+    ) -> Vec<LuaFunctionRef> {
         let mut chain = Vec::new();
 
         // View-specific hooks first (inner)
-        if let Some(vid) = view_id {
+        if let Some(vid) = view_id.filter(|vid| enabled(&format!("views.{}.{}", vid, hook_name))) {
             let view_hooks = self.view_hooks.read();
             if let Some(view_map) = view_hooks.get(vid) {
                 if let Some(hooks) = view_map.get(hook_name) {
@@ -169,9 +186,11 @@ impl HookRegistry {
         }
 
         // Global hooks second (outer)
-        let global = self.global_hooks.read();
-        if let Some(hooks) = global.get(hook_name) {
-            chain.extend(hooks.iter().map(|h| h.function.clone()));
+        if enabled(hook_name) {
+            let global = self.global_hooks.read();
+            if let Some(hooks) = global.get(hook_name) {
+                chain.extend(hooks.iter().map(|h| h.function.clone()));
+            }
         }
 
         chain
@@ -264,7 +283,7 @@ fn parse_view_hook_path(path: &str) -> Option<(&str, &str)> {
 /// - `views.{id}.get_actions`
 pub fn validate_hook_path(path: &str) -> Result<(), HookError> {
     match path {
-        "search" | "get_actions" => Ok(()),
+        "search" | "get_actions" | "on_shutdown" => Ok(()),
         _ if path.starts_with("views.") => {
             if let Some((view_id, hook_name)) = parse_view_hook_path(path) {
                 if view_id.is_empty() {
@@ -288,7 +307,7 @@ pub fn validate_hook_path(path: &str) -> Result<(), HookError> {
             }
         }
         _ => Err(HookError::InvalidPath(format!(
-            "Invalid hook path '{}'. Expected 'search', 'get_actions', or 'views.{{id}}.{{hook}}'",
+            "Invalid hook path '{}'. Expected 'search', 'get_actions', 'on_shutdown', or 'views.{{id}}.{{hook}}'",
             path
         ))),
     }
@@ -335,6 +354,7 @@ mod tests {
     fn test_validate_hook_path() {
         assert!(validate_hook_path("search").is_ok());
         assert!(validate_hook_path("get_actions").is_ok());
+        assert!(validate_hook_path("on_shutdown").is_ok());
         assert!(validate_hook_path("views.files.search").is_ok());
         assert!(validate_hook_path("views.files.get_actions").is_ok());
 
@@ -417,4 +437,21 @@ mod tests {
         assert_eq!(chain.len(), 1);
         assert_eq!(chain[0].key, "global1");
     }
+
+    #[test]
+    fn test_get_chain_where_skips_disabled_paths() {
+        let registry = HookRegistry::new();
+
+        registry.add("search", make_test_fn_ref("global1"));
+        registry.add("views.files.search", make_test_fn_ref("view1"));
+
+        let chain = registry.get_chain_where("search", Some("files"), |path| path != "search");
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].key, "view1");
+
+        let chain =
+            registry.get_chain_where("search", Some("files"), |path| !path.starts_with("views."));
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].key, "global1");
+    }
 }
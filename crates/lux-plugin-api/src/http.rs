@@ -0,0 +1,1039 @@
+//! HTTP requests through curl, and the built-in request builder.
+//!
+//! This module provides:
+//! - `HttpRequest` / `HttpResponse` - A request and what came back
+//! - `send()` - Run a request with curl (lux.http.request)
+//! - `HttpClient` - Sends requests in the background and keeps their history
+//! - `builder_groups()` / `builder_step()` - The steps of the request builder view
+//! - `response_groups()` / `json_items()` - The response, with JSON objects folded
+//!
+//! The request builder (`lux.builtin.http()`) starts with the URL, optionally
+//! prefixed by the method (`POST https://...`). The next step lists the
+//! request; typing `Name: value` adds a header, other text sets the body.
+//! Sent requests open the response view, which fills in when curl returns:
+//!
+//! ```lua
+//! lux.views.add(lux.builtin.http())              -- id "builtin.http"
+//! local res = lux.http.request({ method = "POST", url = url, body = "{}" })
+//! ```
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+use crate::error::{PluginError, PluginResult};
+use crate::storage;
+
+/// View ID of the request builder.
+pub const BUILDER_VIEW_ID: &str = "builtin.http";
+
+/// View ID of the response view.
+pub const RESPONSE_VIEW_ID: &str = "builtin.http.response";
+
+/// View ID of the view of a folded JSON value.
+pub const JSON_VIEW_ID: &str = "builtin.http.json";
+
+/// Methods offered by the request builder.
+pub const METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// Timeout of requests sent without one.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many sent requests the history keeps.
+const HISTORY_SIZE: usize = 50;
+
+/// Most body lines shown for responses that aren't JSON.
+const MAX_BODY_ROWS: usize = 200;
+
+/// Length of body and value previews in subtitles.
+const PREVIEW_LENGTH: usize = 80;
+
+// =============================================================================
+// Requests and responses
+// =============================================================================
+
+/// An HTTP request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    /// Create a request without headers or body.
+    pub fn new(method: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// `GET https://...`
+    pub fn summary(&self) -> String {
+        format!("{} {}", self.method, self.url)
+    }
+
+    /// The headers sent: JSON bodies get a `Content-Type` unless one is set.
+    fn effective_headers(&self) -> Vec<(String, String)> {
+        let mut headers = self.headers.clone();
+        let has_content_type = headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("content-type"));
+        let json_body = self
+            .body
+            .as_deref()
+            .is_some_and(|body| serde_json::from_str::<serde_json::Value>(body).is_ok());
+        if json_body && !has_content_type {
+            headers.push(("Content-Type".to_string(), "application/json".to_string()));
+        }
+        headers
+    }
+
+    /// The equivalent curl command line, quoted for a POSIX shell.
+    pub fn to_curl(&self) -> String {
+        let mut parts = vec!["curl".to_string()];
+        match self.method.as_str() {
+            "GET" => {}
+            "HEAD" => parts.push("--head".to_string()),
+            method => parts.push(format!("-X {}", shell_quote(method))),
+        }
+        parts.push(shell_quote(&self.url));
+        for (name, value) in self.effective_headers() {
+            parts.push(format!(
+                "-H {}",
+                shell_quote(&format!("{}: {}", name, value))
+            ));
+        }
+        if let Some(body) = &self.body {
+            parts.push(format!("--data-raw {}", shell_quote(body)));
+        }
+        parts.join(" ")
+    }
+
+    /// The URL, headers and body as a curl config file (`curl --config`).
+    fn curl_config(&self) -> String {
+        let mut config = format!("url = {}\n", config_quote(&self.url));
+        for (name, value) in self.effective_headers() {
+            let header = format!("{}: {}", name, value);
+            config.push_str(&format!("header = {}\n", config_quote(&header)));
+        }
+        if let Some(body) = &self.body {
+            config.push_str(&format!("data-raw = {}\n", config_quote(body)));
+        }
+        config
+    }
+}
+
+/// Quote `text` as a curl config value.
+fn config_quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\x0b' => quoted.push_str("\\v"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quote `text` for a POSIX shell, leaving plain words as they are.
+fn shell_quote(text: &str) -> String {
+    let plain = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if plain {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', "'\\''"))
+    }
+}
+
+/// A received HTTP response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Reason phrase; empty over HTTP/2.
+    pub reason: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    pub elapsed: Duration,
+}
+
+impl HttpResponse {
+    /// `200 OK`
+    pub fn status_line(&self) -> String {
+        format!("{} {}", self.status, self.reason)
+            .trim()
+            .to_string()
+    }
+
+    /// The first value of a header, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The body parsed as JSON, if it is JSON.
+    pub fn json(&self) -> Option<serde_json::Value> {
+        let trimmed = self.body.trim_start();
+        if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+            return None;
+        }
+        serde_json::from_str(trimmed).ok()
+    }
+}
+
+/// Send a request with curl, following redirects.
+///
+/// The URL, headers and body go to curl as a config on stdin, keeping
+/// credentials off the command line.
+pub fn send(request: &HttpRequest, timeout: Duration) -> PluginResult<HttpResponse> {
+    let mut cmd = Command::new("curl");
+    cmd.args([
+        "--silent",
+        "--show-error",
+        "--location",
+        "--dump-header",
+        "-",
+    ])
+    .args(["--max-time", &timeout.as_secs().max(1).to_string()]);
+    match request.method.as_str() {
+        "HEAD" => cmd.arg("--head"),
+        method => cmd.args(["--request", method]),
+    };
+    cmd.args(["--config", "-"]);
+
+    let started = Instant::now();
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| PluginError::Command(format!("Command spawn failed: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(request.curl_config().as_bytes())
+            .map_err(|e| PluginError::Command(format!("Request write failed: {}", e)))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| PluginError::Command(format!("Request failed: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(PluginError::Command(format!(
+            "Request failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let mut response = parse_response(&String::from_utf8_lossy(&output.stdout))?;
+    response.elapsed = started.elapsed();
+    Ok(response)
+}
+
+/// Parse curl's output: header blocks (one per redirect) then the body.
+fn parse_response(output: &str) -> PluginResult<HttpResponse> {
+    let mut rest = output;
+    let mut last = None;
+
+    while rest.starts_with("HTTP/") {
+        let (block, body) = match rest.find("\r\n\r\n") {
+            Some(end) => (&rest[..end], &rest[end + 4..]),
+            None => (rest.trim_end(), ""),
+        };
+        last = Some(block);
+        rest = body;
+    }
+
+    let block = last.ok_or_else(|| PluginError::Command("No HTTP response".to_string()))?;
+    let mut lines = block.lines();
+    let mut status_line = lines.next().unwrap_or_default().splitn(3, ' ').skip(1);
+    let status = status_line
+        .next()
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| PluginError::Command("Invalid HTTP status line".to_string()))?;
+    let reason = status_line.next().unwrap_or_default().trim().to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    Ok(HttpResponse {
+        status,
+        reason,
+        headers,
+        body: rest.to_string(),
+        elapsed: Duration::ZERO,
+    })
+}
+
+// =============================================================================
+// Client
+// =============================================================================
+
+/// A response that is still on its way: `None` until curl returns.
+pub type PendingResponse = Arc<Mutex<Option<Result<HttpResponse, String>>>>;
+
+/// Sends the request builder's requests and keeps their history.
+///
+/// Background responses bump `updates` so the response view re-runs its search.
+pub struct HttpClient {
+    path: Option<PathBuf>,
+    history: Mutex<Vec<HttpRequest>>,
+    updates: Arc<watch::Sender<u64>>,
+}
+
+impl HttpClient {
+    /// Load the history from `path` (None keeps it in memory only).
+    pub fn new(path: Option<PathBuf>, updates: Arc<watch::Sender<u64>>) -> Self {
+        let history = path.as_deref().map(load_history).unwrap_or_default();
+        Self {
+            path,
+            history: Mutex::new(history),
+            updates,
+        }
+    }
+
+    /// Where the history is saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("http_history.json"))
+    }
+
+    /// Sent requests, newest first.
+    pub fn history(&self) -> Vec<HttpRequest> {
+        self.history.lock().clone()
+    }
+
+    /// Forget every sent request.
+    pub fn clear_history(&self) {
+        let mut history = self.history.lock();
+        history.clear();
+        self.save(&history);
+    }
+
+    /// Move a request to the front of the history and save it.
+    pub fn record(&self, request: &HttpRequest) {
+        let mut history = self.history.lock();
+        history.retain(|r| r != request);
+        history.insert(0, request.clone());
+        history.truncate(HISTORY_SIZE);
+        self.save(&history);
+    }
+
+    fn save(&self, history: &[HttpRequest]) {
+        if let Some(path) = &self.path {
+            if let Err(e) = save_history(path, history) {
+                tracing::warn!("Failed to save HTTP history: {}", e);
+            }
+        }
+    }
+
+    /// Record the request and send it on a background thread.
+    pub fn send_in_background(&self, request: HttpRequest) -> PendingResponse {
+        self.record(&request);
+        let pending: PendingResponse = Arc::new(Mutex::new(None));
+        let slot = pending.clone();
+        let updates = self.updates.clone();
+        std::thread::spawn(move || {
+            let result = send(&request, DEFAULT_TIMEOUT).map_err(|e| e.to_string());
+            *slot.lock() = Some(result);
+            updates.send_modify(|count| *count = count.wrapping_add(1));
+        });
+        pending
+    }
+}
+
+fn load_history(path: &Path) -> Vec<HttpRequest> {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_history(path: &Path, history: &[HttpRequest]) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(history)?)
+}
+
+// =============================================================================
+// Request builder
+// =============================================================================
+
+/// What a request builder action leads to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuilderStep {
+    /// Show the builder for this request.
+    Edit(HttpRequest),
+    /// Send the request and show the response.
+    Send(HttpRequest),
+    /// Copy text to the clipboard.
+    Copy(String),
+}
+
+fn builder_item(
+    id: String,
+    title: String,
+    subtitle: Option<String>,
+    data: serde_json::Value,
+) -> Item {
+    let mut item = Item::new(id, title).with_data(data);
+    item.subtitle = subtitle;
+    item
+}
+
+/// Split `POST https://...` into the method and the URL.
+fn parse_request_line(line: &str) -> (Option<&'static str>, &str) {
+    let line = line.trim();
+    if let Some((first, rest)) = line.split_once(char::is_whitespace) {
+        if let Some(method) = METHODS.iter().find(|m| m.eq_ignore_ascii_case(first)) {
+            return (Some(method), rest.trim());
+        }
+    }
+    (None, line)
+}
+
+/// Parse a `Name: value` header.
+fn parse_header(text: &str) -> Option<(String, String)> {
+    let (name, value) = text.split_once(':')?;
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_".contains(c));
+    valid.then(|| (name.to_string(), value.trim().to_string()))
+}
+
+/// First line of `text`, shortened for a subtitle.
+fn preview(text: &str) -> String {
+    let line = text.trim().lines().next().unwrap_or_default();
+    let mut preview: String = line.chars().take(PREVIEW_LENGTH).collect();
+    if preview.len() < text.trim().len() {
+        preview.push('…');
+    }
+    preview
+}
+
+/// Rows of the request builder for `request` and the typed query.
+///
+/// Without a URL, the query becomes the URL and sent requests are listed.
+/// Afterwards the query adds a header or sets the body, URL, or method.
+pub fn builder_groups(request: &HttpRequest, query: &str, history: &[HttpRequest]) -> Vec<Group> {
+    let query = query.trim();
+    let mut groups = Vec::new();
+
+    if request.url.is_empty() {
+        if !query.is_empty() {
+            let (method, url) = parse_request_line(query);
+            let method = method.unwrap_or(request.method.as_str());
+            groups.push(Group {
+                title: None,
+                items: vec![builder_item(
+                    "http:url".to_string(),
+                    format!("{} {}", method, url),
+                    Some(t("Continue to headers and body")),
+                    serde_json::json!({ "kind": "url", "method": method, "url": url }),
+                )],
+                collapsible: false,
+            });
+        }
+
+        let lower = query.to_lowercase();
+        let items: Vec<Item> = history
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.summary().to_lowercase().contains(&lower))
+            .map(|(i, r)| {
+                builder_item(
+                    format!("http:history:{}", i),
+                    r.summary(),
+                    r.body.as_deref().map(preview),
+                    serde_json::json!({ "kind": "history", "index": i }),
+                )
+            })
+            .collect();
+        if !items.is_empty() {
+            groups.push(Group {
+                title: Some(t("History")),
+                items,
+                collapsible: false,
+            });
+        }
+        return groups;
+    }
+
+    if !query.is_empty() {
+        let item = if let Some((name, value)) = parse_header(query) {
+            builder_item(
+                "http:add_header".to_string(),
+                format!("{}: {}", name, value),
+                Some(t("Add Header")),
+                serde_json::json!({ "kind": "add_header", "name": name, "value": value }),
+            )
+        } else if let Some(method) = METHODS.iter().find(|m| m.eq_ignore_ascii_case(query)) {
+            builder_item(
+                "http:set_method".to_string(),
+                method.to_string(),
+                Some(t("Set Method")),
+                serde_json::json!({ "kind": "set_method", "method": method }),
+            )
+        } else if query.starts_with("http://") || query.starts_with("https://") {
+            builder_item(
+                "http:set_url".to_string(),
+                query.to_string(),
+                Some(t("Set URL")),
+                serde_json::json!({ "kind": "set_url", "url": query }),
+            )
+        } else {
+            builder_item(
+                "http:set_body".to_string(),
+                preview(query),
+                Some(t("Set Body")),
+                serde_json::json!({ "kind": "set_body", "body": query }),
+            )
+        };
+        groups.push(Group {
+            title: None,
+            items: vec![item],
+            collapsible: false,
+        });
+    }
+
+    let mut items = vec![
+        builder_item(
+            "http:send".to_string(),
+            request.summary(),
+            Some(t("Send Request")),
+            serde_json::json!({ "kind": "send" }),
+        ),
+        builder_item(
+            "http:method".to_string(),
+            request.method.clone(),
+            Some(t("Method")),
+            serde_json::json!({ "kind": "method" }),
+        ),
+    ];
+    items.extend(
+        request
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(i, (name, value))| {
+                builder_item(
+                    format!("http:header:{}", i),
+                    format!("{}: {}", name, value),
+                    Some(t("Header")),
+                    serde_json::json!({ "kind": "header", "index": i }),
+                )
+            }),
+    );
+    if let Some(body) = &request.body {
+        items.push(builder_item(
+            "http:body".to_string(),
+            preview(body),
+            Some(t("Body")),
+            serde_json::json!({ "kind": "body" }),
+        ));
+    }
+    groups.push(Group {
+        title: Some(t("Request")),
+        items,
+        collapsible: false,
+    });
+    groups
+}
+
+fn item_kind(item: &Item) -> Option<&str> {
+    item.data.as_ref()?.get("kind")?.as_str()
+}
+
+fn item_str<'a>(item: &'a Item, key: &str) -> Option<&'a str> {
+    item.data.as_ref()?.get(key)?.as_str()
+}
+
+fn item_index(item: &Item) -> Option<usize> {
+    item.data
+        .as_ref()?
+        .get("index")?
+        .as_u64()
+        .map(|i| i as usize)
+}
+
+/// Actions for a request builder row: `(id, title)`.
+pub fn builder_actions(item: &Item, request: &HttpRequest) -> Vec<(String, String)> {
+    let action = |id: &str, title: String| (id.to_string(), title);
+    match item_kind(item) {
+        Some("url") => vec![
+            action("continue", t("Continue")),
+            action("send", t("Send Request")),
+        ],
+        Some("history") => vec![
+            action("edit", t("Edit Request")),
+            action("send", t("Send Request")),
+            action("copy_curl", t("Copy as cURL")),
+        ],
+        Some("add_header" | "set_method" | "set_url" | "set_body") => {
+            vec![action("apply", item.subtitle.clone().unwrap_or_default())]
+        }
+        Some("send") => vec![
+            action("send", t("Send Request")),
+            action("copy_curl", t("Copy as cURL")),
+        ],
+        Some("method") => METHODS
+            .iter()
+            .filter(|m| **m != request.method)
+            .map(|m| {
+                (
+                    format!("method:{}", m),
+                    t_args("Use {method}", &[("method", m)]),
+                )
+            })
+            .collect(),
+        Some("header") => vec![action("remove", t("Remove Header"))],
+        Some("body") => vec![
+            action("copy_body", t("Copy Body")),
+            action("remove", t("Clear Body")),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Run a request builder action on a row of the builder for `request`.
+pub fn builder_step(
+    action_id: &str,
+    item: &Item,
+    request: &HttpRequest,
+    history: &[HttpRequest],
+) -> Option<BuilderStep> {
+    let mut next = request.clone();
+
+    if let Some(method) = action_id.strip_prefix("method:") {
+        next.method = method.to_string();
+        return Some(BuilderStep::Edit(next));
+    }
+
+    match (item_kind(item)?, action_id) {
+        ("url", _) => {
+            next.method = item_str(item, "method")?.to_string();
+            next.url = item_str(item, "url")?.to_string();
+            Some(match action_id {
+                "send" => BuilderStep::Send(next),
+                _ => BuilderStep::Edit(next),
+            })
+        }
+        ("history", "edit") => history
+            .get(item_index(item)?)
+            .cloned()
+            .map(BuilderStep::Edit),
+        ("history", "send") => history
+            .get(item_index(item)?)
+            .cloned()
+            .map(BuilderStep::Send),
+        ("history", "copy_curl") => {
+            Some(BuilderStep::Copy(history.get(item_index(item)?)?.to_curl()))
+        }
+        ("add_header", _) => {
+            let name = item_str(item, "name")?.to_string();
+            let value = item_str(item, "value")?.to_string();
+            next.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(&name));
+            next.headers.push((name, value));
+            Some(BuilderStep::Edit(next))
+        }
+        ("set_method", _) => {
+            next.method = item_str(item, "method")?.to_string();
+            Some(BuilderStep::Edit(next))
+        }
+        ("set_url", _) => {
+            next.url = item_str(item, "url")?.to_string();
+            Some(BuilderStep::Edit(next))
+        }
+        ("set_body", _) => {
+            next.body = Some(item_str(item, "body")?.to_string());
+            Some(BuilderStep::Edit(next))
+        }
+        ("send", "send") => Some(BuilderStep::Send(next)),
+        ("send", "copy_curl") => Some(BuilderStep::Copy(next.to_curl())),
+        ("header", "remove") => {
+            let index = item_index(item)?;
+            (index < next.headers.len()).then(|| {
+                next.headers.remove(index);
+                BuilderStep::Edit(next)
+            })
+        }
+        ("body", "copy_body") => next.body.map(BuilderStep::Copy),
+        ("body", "remove") => {
+            next.body = None;
+            Some(BuilderStep::Edit(next))
+        }
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Responses
+// =============================================================================
+
+/// Human-readable size of a body.
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Escape a key for a JSON pointer (RFC 6901).
+fn pointer_segment(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// A JSON value as shown in a subtitle: objects and arrays folded.
+fn folded_preview(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            t_args("{…} {count} keys", &[("count", &map.len().to_string())])
+        }
+        serde_json::Value::Array(items) => {
+            t_args("[…] {count} items", &[("count", &items.len().to_string())])
+        }
+        serde_json::Value::String(s) => preview(s),
+        other => other.to_string(),
+    }
+}
+
+/// One row per key or element of `value`, found at `pointer` in the body.
+///
+/// Objects and arrays are folded: their rows have `folded = true` and the
+/// "Expand" action opens them.
+pub fn json_items(value: &serde_json::Value, pointer: &str) -> Vec<Item> {
+    let children: Vec<(String, &serde_json::Value)> = match value {
+        serde_json::Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i.to_string(), v))
+            .collect(),
+        scalar => {
+            return vec![json_item(t("Value"), scalar, pointer.to_string())];
+        }
+    };
+
+    children
+        .into_iter()
+        .map(|(key, child)| {
+            let title = match value {
+                serde_json::Value::Array(_) => format!("[{}]", key),
+                _ => key.clone(),
+            };
+            json_item(
+                title,
+                child,
+                format!("{}/{}", pointer, pointer_segment(&key)),
+            )
+        })
+        .collect()
+}
+
+fn json_item(title: String, value: &serde_json::Value, pointer: String) -> Item {
+    let folded = value.is_object() || value.is_array();
+    let text = match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    };
+    builder_item(
+        format!("http:json:{}", pointer),
+        title,
+        Some(folded_preview(value)),
+        serde_json::json!({ "kind": "json", "pointer": pointer, "folded": folded, "value": text }),
+    )
+}
+
+/// Rows of the response view: status, body, and headers, filtered by `query`.
+///
+/// While the response is pending there's one "Sending…" row.
+pub fn response_groups(
+    request: &HttpRequest,
+    response: Option<&Result<HttpResponse, String>>,
+    query: &str,
+) -> Vec<Group> {
+    let response = match response {
+        None => {
+            return vec![Group {
+                title: None,
+                items: vec![builder_item(
+                    "http:pending".to_string(),
+                    t("Sending…"),
+                    Some(request.summary()),
+                    serde_json::json!({ "kind": "pending" }),
+                )],
+                collapsible: false,
+            }]
+        }
+        Some(Err(e)) => {
+            return vec![Group {
+                title: None,
+                items: vec![builder_item(
+                    "http:error".to_string(),
+                    t("Request failed"),
+                    Some(e.clone()),
+                    serde_json::json!({ "kind": "error", "value": e }),
+                )],
+                collapsible: false,
+            }]
+        }
+        Some(Ok(response)) => response,
+    };
+
+    let query = query.trim().to_lowercase();
+    let matches = |item: &Item| {
+        item.title.to_lowercase().contains(&query)
+            || item
+                .subtitle
+                .as_deref()
+                .is_some_and(|s| s.to_lowercase().contains(&query))
+    };
+
+    let mut details = vec![format!("{} ms", response.elapsed.as_millis())];
+    details.push(format_size(response.body.len()));
+    if let Some(content_type) = response.header("content-type") {
+        details.push(content_type.to_string());
+    }
+    let status = builder_item(
+        "http:status".to_string(),
+        response.status_line(),
+        Some(details.join(" · ")),
+        serde_json::json!({ "kind": "status", "value": response.status_line() }),
+    );
+
+    let body: Vec<Item> = match response.json() {
+        Some(json) => json_items(&json, ""),
+        None => response
+            .body
+            .lines()
+            .take(MAX_BODY_ROWS)
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                builder_item(
+                    format!("http:line:{}", i),
+                    line.to_string(),
+                    None,
+                    serde_json::json!({ "kind": "line", "value": line }),
+                )
+            })
+            .collect(),
+    };
+
+    let headers: Vec<Item> = response
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, (name, value))| {
+            builder_item(
+                format!("http:response_header:{}", i),
+                name.clone(),
+                Some(value.clone()),
+                serde_json::json!({ "kind": "response_header", "value": value }),
+            )
+        })
+        .collect();
+
+    [
+        (None, vec![status]),
+        (Some(t("Body")), body),
+        (Some(t("Headers")), headers),
+    ]
+    .into_iter()
+    .map(|(title, items)| Group {
+        title,
+        items: items.into_iter().filter(|item| matches(item)).collect(),
+        collapsible: false,
+    })
+    .filter(|group| !group.items.is_empty())
+    .collect()
+}
+
+/// Actions for a response row: `(id, title)`.
+///
+/// Folded JSON rows can be expanded; every row can copy its value, the
+/// whole body, or the request as a curl command.
+pub fn response_actions(item: &Item) -> Vec<(&'static str, String)> {
+    let mut actions = Vec::new();
+    match item_kind(item) {
+        Some("pending") => return vec![("copy_curl", t("Copy as cURL"))],
+        Some("error") => {
+            return vec![
+                ("send_again", t("Send Again")),
+                ("edit", t("Edit Request")),
+                ("copy_curl", t("Copy as cURL")),
+            ]
+        }
+        _ => {}
+    }
+    if item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("folded")?.as_bool())
+        .unwrap_or(false)
+    {
+        actions.push(("expand", t("Expand")));
+    }
+    if item_str(item, "value").is_some() {
+        actions.push(("copy_value", t("Copy Value")));
+    }
+    actions.extend([
+        ("copy_body", t("Copy Body")),
+        ("copy_curl", t("Copy as cURL")),
+        ("send_again", t("Send Again")),
+        ("edit", t("Edit Request")),
+    ]);
+    actions
+}
+
+/// The value a response row shows in full (`copy_value`).
+pub fn response_value(item: &Item) -> Option<&str> {
+    item_str(item, "value")
+}
+
+/// The JSON pointer of a JSON row (`expand`).
+pub fn json_pointer(item: &Item) -> Option<&str> {
+    item_str(item, "pointer")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item_with(data: serde_json::Value) -> Item {
+        builder_item("test".to_string(), String::new(), None, data)
+    }
+
+    #[test]
+    fn test_to_curl() {
+        let mut request = HttpRequest::new("POST", "https://api.example.com/items?a=1&b=2");
+        request
+            .headers
+            .push(("Authorization".to_string(), "Bearer x".to_string()));
+        request.body = Some(r#"{"name":"it's"}"#.to_string());
+        assert_eq!(
+            request.to_curl(),
+            "curl -X POST 'https://api.example.com/items?a=1&b=2' \
+             -H 'Authorization: Bearer x' -H 'Content-Type: application/json' \
+             --data-raw '{\"name\":\"it'\\''s\"}'"
+        );
+        assert_eq!(
+            HttpRequest::new("GET", "https://example.com").to_curl(),
+            "curl https://example.com"
+        );
+    }
+
+    #[test]
+    fn test_curl_config() {
+        let mut request = HttpRequest::new("POST", "https://api.example.com/items");
+        request
+            .headers
+            .push(("Authorization".to_string(), "Bearer x".to_string()));
+        request.body = Some(format!("{}\n", r#"{"path": "C:\\tmp"}"#));
+        assert_eq!(
+            request.curl_config(),
+            r#"url = "https://api.example.com/items"
+header = "Authorization: Bearer x"
+header = "Content-Type: application/json"
+data-raw = "{\"path\": \"C:\\\\tmp\"}\n"
+"#
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let output = "HTTP/1.1 301 Moved Permanently\r\nLocation: /new\r\n\r\n\
+                      HTTP/2 200\r\ncontent-type: application/json\r\n\r\n{\"ok\":true}";
+        let response = parse_response(output).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.status_line(), "200");
+        assert_eq!(response.header("Content-Type"), Some("application/json"));
+        assert_eq!(response.json(), Some(serde_json::json!({ "ok": true })));
+        assert!(parse_response("curl: (6) Could not resolve host").is_err());
+    }
+
+    #[test]
+    fn test_builder_steps() {
+        let groups = builder_groups(&HttpRequest::new("GET", ""), "post example.com/api", &[]);
+        let url_row = &groups[0].items[0];
+        assert_eq!(url_row.title, "POST example.com/api");
+
+        let Some(BuilderStep::Edit(request)) =
+            builder_step("continue", url_row, &HttpRequest::new("GET", ""), &[])
+        else {
+            panic!("expected the next step");
+        };
+        assert_eq!(request.summary(), "POST example.com/api");
+
+        let groups = builder_groups(&request, "Accept: application/json", &[]);
+        let Some(BuilderStep::Edit(request)) =
+            builder_step("apply", &groups[0].items[0], &request, &[])
+        else {
+            panic!("expected the header to be added");
+        };
+        assert_eq!(
+            request.headers,
+            vec![("Accept".to_string(), "application/json".to_string())]
+        );
+
+        let groups = builder_groups(&request, "{\"a\": 1}", &[]);
+        assert_eq!(groups[0].items[0].subtitle.as_deref(), Some("Set Body"));
+        assert_eq!(
+            builder_step("method:PUT", &groups[1].items[1], &request, &[]),
+            Some(BuilderStep::Edit(HttpRequest {
+                method: "PUT".to_string(),
+                ..request.clone()
+            }))
+        );
+    }
+
+    #[test]
+    fn test_json_items_fold_nested_values() {
+        let value = serde_json::json!({ "name": "lux", "tags": ["a", "b"], "a/b": { "c": 1 } });
+        let items = json_items(&value, "");
+        let tags = items.iter().find(|item| item.title == "tags").unwrap();
+        assert_eq!(tags.subtitle.as_deref(), Some("[…] 2 items"));
+        assert_eq!(json_pointer(tags), Some("/tags"));
+        assert!(response_actions(tags).iter().any(|(id, _)| *id == "expand"));
+
+        let slash = items.iter().find(|item| item.title == "a/b").unwrap();
+        assert_eq!(
+            value.pointer(json_pointer(slash).unwrap()),
+            Some(&value["a/b"])
+        );
+
+        let name = items.iter().find(|item| item.title == "name").unwrap();
+        assert_eq!(response_value(name), Some("lux"));
+        assert!(!response_actions(name).iter().any(|(id, _)| *id == "expand"));
+        assert!(
+            response_actions(&item_with(serde_json::json!({ "kind": "pending" })))
+                .iter()
+                .all(|(id, _)| *id == "copy_curl")
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Locale-aware date formatting.
+//!
+//! This module provides:
+//! - `DateStyle` - none/short/medium/long/full, as in `NSDateFormatter`
+//! - `format_date()` - Format a Unix timestamp for a locale
+//!
+//! String translation and number formatting need no platform support and
+//! live in [`lux_core::i18n`]; dates go through `NSDateFormatter` so they
+//! follow the user's time zone and calendar.
+
+use crate::error::{PluginError, PluginResult};
+use crate::permissions::run_jxa_with_args;
+
+/// Date or time style.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateStyle {
+    /// Omit this part.
+    None,
+    /// "1/2/26", "9:41 AM"
+    Short,
+    /// "Jan 2, 2026", "9:41:00 AM"
+    #[default]
+    Medium,
+    /// "January 2, 2026"
+    Long,
+    /// "Friday, January 2, 2026"
+    Full,
+}
+
+impl DateStyle {
+    /// Parse a style name ("none", "short", "medium", "long", "full").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(DateStyle::None),
+            "short" => Some(DateStyle::Short),
+            "medium" => Some(DateStyle::Medium),
+            "long" => Some(DateStyle::Long),
+            "full" => Some(DateStyle::Full),
+            _ => None,
+        }
+    }
+
+    /// `NSDateFormatterStyle` raw value.
+    fn raw(self) -> u8 {
+        match self {
+            DateStyle::None => 0,
+            DateStyle::Short => 1,
+            DateStyle::Medium => 2,
+            DateStyle::Long => 3,
+            DateStyle::Full => 4,
+        }
+    }
+}
+
+/// JXA script formatting timestamp `argv[0]` for locale `argv[1]` with date
+/// style `argv[2]` and time style `argv[3]`.
+const FORMAT_DATE_SCRIPT: &str = r#"
+function run(argv) {
+  const f = $.NSDateFormatter.alloc.init;
+  f.locale = $.NSLocale.localeWithLocaleIdentifier(argv[1]);
+  f.dateStyle = parseInt(argv[2]);
+  f.timeStyle = parseInt(argv[3]);
+  return f.stringFromDate($.NSDate.dateWithTimeIntervalSince1970(parseFloat(argv[0]))).js;
+}
+"#;
+
+/// Format a Unix timestamp (seconds) for a locale such as "de-DE".
+pub fn format_date(
+    timestamp: f64,
+    locale: &str,
+    date_style: DateStyle,
+    time_style: DateStyle,
+) -> PluginResult<String> {
+    if !timestamp.is_finite() {
+        return Err(PluginError::Command(format!(
+            "Invalid timestamp {}",
+            timestamp
+        )));
+    }
+    let output = run_jxa_with_args(
+        FORMAT_DATE_SCRIPT,
+        &[
+            &timestamp.to_string(),
+            &locale.replace('-', "_"),
+            &date_style.raw().to_string(),
+            &time_style.raw().to_string(),
+        ],
+    )?;
+    Ok(output.trim().to_string())
+}
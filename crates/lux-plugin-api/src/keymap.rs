@@ -15,13 +15,43 @@
 //!      └──► PendingBinding ───►│                      ▼
 //!           LuaFunctionRef ───►│               cx.bind_keys()
 //! ```
+//!
+//! ## Resolution Order
+//!
+//! A key is looked up from the most specific scope to the least: bindings for
+//! the current view, then bindings for an explicit context, then global ones
+//! (no context or view, so the "Launcher" context). If the action of the
+//! winning binding isn't available where the key was pressed, the key falls
+//! through to the next binding. `exclusive = true` stops it at that binding.
+//!
+//! `list()` powers `lux.keymap.list()` and the built-in Keybindings view
+//! (`keybinding_groups()`).
+//!
+//! ## Conflicts
+//!
+//! The registry tracks who set each binding (`set_source()`). Replacing a
+//! binding set by someone else, or setting a global hotkey the OS uses, is
+//! recorded in `conflicts()` for the Keymap Conflicts view.
 
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, Item};
 
+use crate::conflicts::{
+    system_hotkey, BindingSource, ConflictKind, KeymapConflict, KeymapConflicts,
+};
 use crate::types::LuaFunctionRef;
 
+/// ID of the built-in Keybindings view.
+pub const KEYBINDINGS_VIEW_ID: &str = "builtin.keybindings";
+
+/// Context of bindings that don't name one.
+pub const DEFAULT_CONTEXT: &str = "Launcher";
+
 // =============================================================================
 // ID Generation
 // =============================================================================
@@ -32,6 +62,53 @@ pub fn generate_handler_id() -> String {
     format!("keyhandler:{}", COUNTER.fetch_add(1, Ordering::Relaxed))
 }
 
+// =============================================================================
+// Action Shortcuts
+// =============================================================================
+
+/// Modifier names in the order they appear in a normalized shortcut.
+const SHORTCUT_MODIFIERS: [&str; 4] = ["ctrl", "alt", "shift", "cmd"];
+
+/// Normalize an action shortcut like `"Cmd+Shift+O"` to `"shift+cmd+o"`.
+///
+/// Accepts `+` or `-` separators and the aliases `control`, `option`/`opt`,
+/// and `command`/`super`. Modifiers are sorted as ctrl, alt, shift, cmd.
+/// Returns `None` unless there is exactly one key and at least one modifier,
+/// since a bare key would fire while typing a query.
+pub fn normalize_shortcut(shortcut: &str) -> Option<String> {
+    let mut modifiers = [false; SHORTCUT_MODIFIERS.len()];
+    let mut key = None;
+
+    for part in shortcut.split(['+', '-']) {
+        let part = part.trim().to_lowercase();
+        let modifier = match part.as_str() {
+            "ctrl" | "control" => Some(0),
+            "alt" | "option" | "opt" => Some(1),
+            "shift" => Some(2),
+            "cmd" | "command" | "super" => Some(3),
+            _ => None,
+        };
+        match modifier {
+            Some(index) => modifiers[index] = true,
+            None if part.is_empty() || key.is_some() => return None,
+            None => key = Some(part),
+        }
+    }
+
+    let key = key?;
+    if !modifiers.contains(&true) {
+        return None;
+    }
+
+    let mut parts: Vec<&str> = SHORTCUT_MODIFIERS
+        .iter()
+        .zip(modifiers)
+        .filter_map(|(name, on)| on.then_some(*name))
+        .collect();
+    parts.push(&key);
+    Some(parts.join("+"))
+}
+
 // =============================================================================
 // Key Handler
 // =============================================================================
@@ -55,6 +132,9 @@ pub enum KeyHandler {
 pub enum BuiltInHotkey {
     /// Toggle launcher visibility.
     ToggleLauncher,
+
+    /// Open the launcher on the files selected in Finder.
+    SendToLux,
 }
 
 impl BuiltInHotkey {
@@ -62,6 +142,7 @@ impl BuiltInHotkey {
     pub fn from_name(name: &str) -> Option<Self> {
         match name {
             "toggle_launcher" => Some(Self::ToggleLauncher),
+            "send_to_lux" => Some(Self::SendToLux),
             _ => None,
         }
     }
@@ -70,6 +151,7 @@ impl BuiltInHotkey {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::ToggleLauncher => "toggle_launcher",
+            Self::SendToLux => "send_to_lux",
         }
     }
 }
@@ -84,6 +166,16 @@ pub enum GlobalHandler {
     Function { id: String },
 }
 
+impl GlobalHandler {
+    /// Built-in action name, or `None` for a Lua function.
+    pub fn action_name(&self) -> Option<&str> {
+        match self {
+            Self::BuiltIn(action) => Some(action.as_str()),
+            Self::Function { .. } => None,
+        }
+    }
+}
+
 /// A pending global hotkey registration.
 #[derive(Clone, Debug)]
 pub struct PendingHotkey {
@@ -118,6 +210,61 @@ pub struct PendingBinding {
     /// Optional Lua view ID for view-specific bindings (e.g., "file_browser").
     /// Combined with context to form: "{context} && view_id == {view}"
     pub view: Option<String>,
+
+    /// Don't fall through to less specific bindings of the same key when
+    /// this binding's action isn't available.
+    pub exclusive: bool,
+}
+
+impl PendingBinding {
+    /// Where the binding applies, which decides its precedence.
+    pub fn scope(&self) -> BindingScope {
+        match (&self.context, &self.view) {
+            (_, Some(_)) => BindingScope::View,
+            (Some(_), None) => BindingScope::Context,
+            (None, None) => BindingScope::Global,
+        }
+    }
+
+    /// The context the binding applies in ("Launcher" unless set).
+    pub fn context_name(&self) -> &str {
+        self.context.as_deref().unwrap_or(DEFAULT_CONTEXT)
+    }
+
+    /// Whether the binding applies in `context` with `view` on top.
+    pub fn applies_in(&self, context: &str, view: Option<&str>) -> bool {
+        self.context_name() == context && (self.view.is_none() || self.view.as_deref() == view)
+    }
+
+    /// Action name, or `None` for a Lua function.
+    pub fn action_name(&self) -> Option<&str> {
+        match &self.handler {
+            KeyHandler::Action(name) => Some(name),
+            KeyHandler::Function { .. } => None,
+        }
+    }
+}
+
+/// Scope of a binding, from least to most specific.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BindingScope {
+    /// No context or view.
+    Global,
+    /// An explicit context (e.g. "SearchInput").
+    Context,
+    /// A single view.
+    View,
+}
+
+impl BindingScope {
+    /// Name as returned by `lux.keymap.list()`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Context => "context",
+            Self::View => "view",
+        }
+    }
 }
 
 // =============================================================================
@@ -140,13 +287,32 @@ type BindingKey = (String, Option<String>, Option<String>);
 #[derive(Default)]
 pub struct KeymapRegistry {
     /// Pending GPUI bindings - HashMap ensures later bindings override earlier.
-    bindings: RwLock<HashMap<BindingKey, PendingBinding>>,
+    /// Each is stored with its order of registration.
+    bindings: RwLock<HashMap<BindingKey, (u64, PendingBinding)>>,
+
+    /// Order of the next registered binding.
+    next_order: AtomicU64,
+
+    /// Bindings already taken for GPUI, in the order they were applied.
+    applied: RwLock<Vec<PendingBinding>>,
 
     /// Pending global hotkeys - keyed by keystroke for deduplication.
     hotkeys: RwLock<HashMap<String, PendingHotkey>>,
 
     /// Lua function refs by ID (for RunLuaHandler dispatch).
     lua_handlers: RwLock<HashMap<String, LuaFunctionRef>>,
+
+    /// Who is setting bindings now.
+    source: RwLock<BindingSource>,
+
+    /// Who set each binding, kept after `take_bindings()`.
+    binding_sources: RwLock<HashMap<BindingKey, BindingSource>>,
+
+    /// Who set each global hotkey.
+    hotkey_sources: RwLock<HashMap<String, BindingSource>>,
+
+    /// Conflicts found while setting bindings.
+    conflicts: Arc<KeymapConflicts>,
 }
 
 impl KeymapRegistry {
@@ -155,14 +321,73 @@ impl KeymapRegistry {
         Self::default()
     }
 
+    /// Use `conflicts` to record conflicts and look up their resolutions.
+    pub fn with_conflicts(mut self, conflicts: Arc<KeymapConflicts>) -> Self {
+        self.conflicts = conflicts;
+        self
+    }
+
+    /// Conflicts found while setting bindings.
+    pub fn conflicts(&self) -> Arc<KeymapConflicts> {
+        self.conflicts.clone()
+    }
+
+    /// Attribute bindings set from now on to `source`.
+    ///
+    /// Returns the previous source, to restore when `source` is done.
+    pub fn set_source(&self, source: BindingSource) -> BindingSource {
+        std::mem::replace(&mut *self.source.write(), source)
+    }
+
     /// Add a binding. If same (key, context, view) exists, it's overwritten.
+    ///
+    /// Overwriting a binding from another source is a conflict. Unless it was
+    /// resolved, it's recorded; if it was resolved by keeping the previous
+    /// binding, this one is skipped.
     pub fn set(&self, binding: PendingBinding) {
         let key = (
             binding.key.clone(),
             binding.context.clone(),
             binding.view.clone(),
         );
-        self.bindings.write().insert(key, binding);
+        let source = self.source.read().clone();
+        let previous = self.binding_sources.read().get(&key).cloned();
+        if let Some(previous) = previous.filter(|previous| *previous != source) {
+            let conflict = KeymapConflict {
+                key: binding.key.clone(),
+                context: binding.context.clone(),
+                view: binding.view.clone(),
+                global: false,
+                source: source.clone(),
+                action: binding.action_name().map(str::to_string),
+                kind: ConflictKind::Shadowed {
+                    source: previous,
+                    action: self.action_of(&key),
+                },
+            };
+            if !self.conflicts.check(conflict) {
+                return;
+            }
+        }
+
+        self.binding_sources.write().insert(key.clone(), source);
+        let order = self.next_order.fetch_add(1, Ordering::Relaxed);
+        self.bindings.write().insert(key, (order, binding));
+    }
+
+    /// Action of the binding at `key`, pending or applied.
+    fn action_of(&self, key: &BindingKey) -> Option<String> {
+        if let Some((_, binding)) = self.bindings.read().get(key) {
+            return binding.action_name().map(str::to_string);
+        }
+        self.applied
+            .read()
+            .iter()
+            .rev()
+            .find(|binding| {
+                (&binding.key, &binding.context, &binding.view) == (&key.0, &key.1, &key.2)
+            })
+            .and_then(|binding| binding.action_name().map(str::to_string))
     }
 
     /// Remove a binding by key, context, and optional view.
@@ -177,6 +402,8 @@ impl KeymapRegistry {
             context.map(|s| s.to_string()),
             view.map(|s| s.to_string()),
         );
+        // Setting the key again afterwards is deliberate, not a conflict
+        self.binding_sources.write().remove(&binding_key);
         self.bindings.write().remove(&binding_key).is_some()
     }
 
@@ -192,11 +419,66 @@ impl KeymapRegistry {
 
     /// Take all pending bindings for GPUI registration.
     ///
-    /// This clears the bindings from the registry.
+    /// This clears the bindings from the registry. They are returned from
+    /// least to most specific scope, each scope in the order it was set, so
+    /// GPUI's last-wins rule gives view > context > global.
     pub fn take_bindings(&self) -> Vec<PendingBinding> {
-        std::mem::take(&mut *self.bindings.write())
+        let mut bindings: Vec<(u64, PendingBinding)> = std::mem::take(&mut *self.bindings.write())
             .into_values()
-            .collect()
+            .collect();
+        bindings.sort_by_key(|(order, binding)| (binding.scope(), *order));
+        let bindings: Vec<PendingBinding> =
+            bindings.into_iter().map(|(_, binding)| binding).collect();
+        self.applied.write().extend(bindings.iter().cloned());
+        bindings
+    }
+
+    /// All bindings, applied and pending, from most to least specific scope.
+    pub fn list(&self) -> Vec<PendingBinding> {
+        let mut bindings: Vec<(u64, PendingBinding)> = self
+            .applied
+            .read()
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(order, binding)| (order as u64, binding))
+            .collect();
+        let applied = bindings.len() as u64;
+        for (order, binding) in self.bindings.read().values() {
+            // A binding set after startup replaces the applied one
+            bindings.retain(|(_, other)| {
+                (&other.key, &other.context, &other.view)
+                    != (&binding.key, &binding.context, &binding.view)
+            });
+            bindings.push((applied + order, binding.clone()));
+        }
+        bindings.sort_by(|(a_order, a), (b_order, b)| {
+            b.scope()
+                .cmp(&a.scope())
+                .then_with(|| a.key.cmp(&b.key))
+                .then_with(|| b_order.cmp(a_order))
+        });
+        bindings.into_iter().map(|(_, binding)| binding).collect()
+    }
+
+    /// Bindings of `key` that apply in `context` with `view` on top, in the
+    /// order they are tried.
+    ///
+    /// Stops after the first exclusive binding, since the key doesn't fall
+    /// through it.
+    pub fn resolve(&self, key: &str, context: &str, view: Option<&str>) -> Vec<PendingBinding> {
+        let mut candidates = Vec::new();
+        for binding in self.list() {
+            if binding.key != key || !binding.applies_in(context, view) {
+                continue;
+            }
+            let exclusive = binding.exclusive;
+            candidates.push(binding);
+            if exclusive {
+                break;
+            }
+        }
+        candidates
     }
 
     /// Get the number of pending bindings.
@@ -216,8 +498,44 @@ impl KeymapRegistry {
     /// Add a global hotkey. If same key exists, it's overwritten.
     ///
     /// Global hotkeys work when the app is hidden (unlike GPUI bindings).
+    ///
+    /// Like `set()`, overwriting a hotkey from another source is a conflict,
+    /// and so is a hotkey the OS already uses.
     pub fn set_global(&self, hotkey: PendingHotkey) {
         let key = hotkey.key.clone();
+        let source = self.source.read().clone();
+        let conflict = |kind| KeymapConflict {
+            key: key.clone(),
+            context: None,
+            view: None,
+            global: true,
+            source: source.clone(),
+            action: hotkey.handler.action_name().map(str::to_string),
+            kind,
+        };
+
+        if let Some(use_) = system_hotkey(&key) {
+            if !self.conflicts.check(conflict(ConflictKind::System(use_))) {
+                return;
+            }
+        }
+        let previous = self.hotkey_sources.read().get(&key).cloned();
+        if let Some(previous) = previous.filter(|previous| *previous != source) {
+            let action = self
+                .hotkeys
+                .read()
+                .get(&key)
+                .and_then(|previous| previous.handler.action_name().map(str::to_string));
+            let kind = ConflictKind::Shadowed {
+                source: previous,
+                action,
+            };
+            if !self.conflicts.check(conflict(kind)) {
+                return;
+            }
+        }
+
+        self.hotkey_sources.write().insert(key.clone(), source);
         self.hotkeys.write().insert(key, hotkey);
     }
 
@@ -228,6 +546,7 @@ impl KeymapRegistry {
     /// **Note:** This only works at startup time. Once hotkeys are registered
     /// with the OS via `take_hotkeys()`, removal requires an app restart.
     pub fn del_global(&self, key: &str) -> bool {
+        self.hotkey_sources.write().remove(key);
         self.hotkeys.write().remove(key).is_some()
     }
 
@@ -240,12 +559,60 @@ impl KeymapRegistry {
             .collect()
     }
 
+    /// The pending hotkeys, without taking them.
+    pub fn hotkeys(&self) -> Vec<PendingHotkey> {
+        self.hotkeys.read().values().cloned().collect()
+    }
+
     /// Get the number of pending hotkeys.
     pub fn hotkey_count(&self) -> usize {
         self.hotkeys.read().len()
     }
 }
 
+// =============================================================================
+// Keybindings View
+// =============================================================================
+
+/// Rows of the Keybindings view: view bindings, then context, then global.
+pub fn keybinding_groups(bindings: &[PendingBinding], query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let mut groups: Vec<Group> = Vec::new();
+    for (index, binding) in bindings.iter().enumerate() {
+        let handler = binding
+            .action_name()
+            .map(str::to_string)
+            .unwrap_or_else(|| t("Lua function"));
+        let scope = match (&binding.view, binding.scope()) {
+            (Some(view), _) => t_args("View: {view}", &[("view", view.as_str())]),
+            (None, BindingScope::Context) => {
+                t_args("Context: {context}", &[("context", binding.context_name())])
+            }
+            _ => t("Global"),
+        };
+        let matches = [&binding.key, &handler, &scope]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query));
+        if !matches {
+            continue;
+        }
+
+        let mut item =
+            Item::new(format!("keybinding:{}", index), binding.key.clone()).with_subtitle(handler);
+        if binding.exclusive {
+            item = item.with_accessory(t("Exclusive"));
+        }
+        match groups
+            .iter_mut()
+            .find(|group| group.title.as_deref() == Some(scope.as_str()))
+        {
+            Some(group) => group.items.push(item),
+            None => groups.push(Group::new(scope, vec![item])),
+        }
+    }
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +626,24 @@ mod tests {
         assert_ne!(id1, id2);
     }
 
+    #[test]
+    fn test_normalize_shortcut() {
+        assert_eq!(normalize_shortcut("cmd+o"), Some("cmd+o".to_string()));
+        assert_eq!(
+            normalize_shortcut("Cmd-Shift+O"),
+            Some("shift+cmd+o".to_string())
+        );
+        assert_eq!(
+            normalize_shortcut("option+control+enter"),
+            Some("ctrl+alt+enter".to_string())
+        );
+        // A bare key, a missing key, or two keys are rejected
+        assert_eq!(normalize_shortcut("o"), None);
+        assert_eq!(normalize_shortcut("cmd+shift"), None);
+        assert_eq!(normalize_shortcut("cmd+o+p"), None);
+        assert_eq!(normalize_shortcut(""), None);
+    }
+
     #[test]
     fn test_keymap_registry_set() {
         let registry = KeymapRegistry::new();
@@ -268,6 +653,7 @@ mod tests {
             handler: KeyHandler::Action("cursor_down".to_string()),
             context: Some("Launcher".to_string()),
             view: None,
+            exclusive: false,
         });
 
         assert_eq!(registry.binding_count(), 1);
@@ -278,6 +664,7 @@ mod tests {
             handler: KeyHandler::Action("cursor_up".to_string()),
             context: Some("Launcher".to_string()),
             view: None,
+            exclusive: false,
         });
 
         assert_eq!(registry.binding_count(), 1);
@@ -288,6 +675,7 @@ mod tests {
             handler: KeyHandler::Action("submit".to_string()),
             context: Some("SearchInput".to_string()),
             view: None,
+            exclusive: false,
         });
 
         assert_eq!(registry.binding_count(), 2);
@@ -298,6 +686,7 @@ mod tests {
             handler: KeyHandler::Action("delete".to_string()),
             context: Some("Launcher".to_string()),
             view: Some("file_browser".to_string()),
+            exclusive: false,
         });
 
         assert_eq!(registry.binding_count(), 3);
@@ -312,6 +701,7 @@ mod tests {
             handler: KeyHandler::Action("cursor_down".to_string()),
             context: Some("Launcher".to_string()),
             view: None,
+            exclusive: false,
         });
 
         assert_eq!(registry.binding_count(), 1);
@@ -329,6 +719,7 @@ mod tests {
             handler: KeyHandler::Action("cursor_down".to_string()),
             context: Some("Launcher".to_string()),
             view: None,
+            exclusive: false,
         });
 
         registry.set(PendingBinding {
@@ -336,11 +727,154 @@ mod tests {
             handler: KeyHandler::Action("cursor_up".to_string()),
             context: Some("Launcher".to_string()),
             view: None,
+            exclusive: false,
         });
 
         let bindings = registry.take_bindings();
         assert_eq!(bindings.len(), 2);
         assert_eq!(registry.binding_count(), 0);
+        // Taken bindings are still listed
+        assert_eq!(registry.list().len(), 2);
+    }
+
+    #[test]
+    fn test_keymap_registry_resolution_order() {
+        let registry = KeymapRegistry::new();
+        let bind = |key: &str, action: &str, context: Option<&str>, view: Option<&str>| {
+            registry.set(PendingBinding {
+                key: key.to_string(),
+                handler: KeyHandler::Action(action.to_string()),
+                context: context.map(str::to_string),
+                view: view.map(str::to_string),
+                exclusive: false,
+            });
+        };
+        bind("ctrl+d", "cursor_down", None, None);
+        bind("ctrl+d", "page_down", Some("Launcher"), Some("files"));
+        bind("ctrl+d", "delete", Some("Launcher"), None);
+        bind("ctrl+u", "page_up", None, None);
+
+        // Applied from least to most specific, so the view binding wins
+        let taken = registry.take_bindings();
+        let order: Vec<_> = taken.iter().filter_map(|b| b.action_name()).collect();
+        assert_eq!(order, ["cursor_down", "page_up", "delete", "page_down"]);
+
+        let actions = |view| {
+            registry
+                .resolve("ctrl+d", DEFAULT_CONTEXT, view)
+                .iter()
+                .filter_map(|b| b.action_name().map(str::to_string))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            actions(Some("files")),
+            ["page_down", "delete", "cursor_down"]
+        );
+        assert_eq!(actions(None), ["delete", "cursor_down"]);
+
+        // An exclusive binding doesn't fall through
+        registry.set(PendingBinding {
+            key: "ctrl+d".to_string(),
+            handler: KeyHandler::Action("delete".to_string()),
+            context: Some("Launcher".to_string()),
+            view: None,
+            exclusive: true,
+        });
+        assert_eq!(actions(Some("files")), ["page_down", "delete"]);
+        assert_eq!(registry.list().len(), 4);
+        assert!(registry.resolve("ctrl+d", "SearchInput", None).is_empty());
+    }
+
+    #[test]
+    fn test_keymap_registry_conflicts() {
+        let conflicts = Arc::new(KeymapConflicts::new(None));
+        let registry = KeymapRegistry::new().with_conflicts(conflicts.clone());
+        let bind = |registry: &KeymapRegistry, key: &str, action: &str| {
+            registry.set(PendingBinding {
+                key: key.to_string(),
+                handler: KeyHandler::Action(action.to_string()),
+                context: None,
+                view: None,
+                exclusive: false,
+            });
+        };
+
+        registry.set_source(BindingSource::Default);
+        bind(&registry, "ctrl+n", "cursor_down");
+        bind(&registry, "ctrl+p", "cursor_up");
+        assert_eq!(
+            registry.set_source(BindingSource::Config),
+            BindingSource::Default
+        );
+
+        // Replacing a default is a conflict, replacing it after del() isn't
+        bind(&registry, "ctrl+n", "page_down");
+        assert!(registry.del("ctrl+p", None, None));
+        bind(&registry, "ctrl+p", "page_up");
+        bind(&registry, "ctrl+n", "page_down");
+        let found = conflicts.list();
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].kind,
+            ConflictKind::Shadowed {
+                source: BindingSource::Default,
+                action: Some("cursor_down".to_string()),
+            }
+        );
+
+        // Keeping the previous binding skips the new one from then on
+        conflicts
+            .resolve(&found[0].id(), crate::conflicts::Resolution::KeepPrevious)
+            .unwrap();
+        let registry = KeymapRegistry::new().with_conflicts(conflicts.clone());
+        registry.set_source(BindingSource::Default);
+        bind(&registry, "ctrl+n", "cursor_down");
+        registry.set_source(BindingSource::Config);
+        bind(&registry, "ctrl+n", "page_down");
+        assert!(conflicts.is_empty());
+        assert_eq!(registry.list()[0].action_name(), Some("cursor_down"));
+
+        let reserved = if cfg!(target_os = "linux") {
+            "super+l"
+        } else {
+            "cmd+space"
+        };
+        registry.set_global(PendingHotkey {
+            key: reserved.to_string(),
+            handler: GlobalHandler::BuiltIn(BuiltInHotkey::ToggleLauncher),
+        });
+        assert!(matches!(conflicts.list()[0].kind, ConflictKind::System(_)));
+    }
+
+    #[test]
+    fn test_keybinding_groups() {
+        let bindings = vec![
+            PendingBinding {
+                key: "ctrl+o".to_string(),
+                handler: KeyHandler::Function {
+                    id: "keyhandler:1".to_string(),
+                },
+                context: None,
+                view: Some("files".to_string()),
+                exclusive: true,
+            },
+            PendingBinding {
+                key: "tab".to_string(),
+                handler: KeyHandler::Action("open_action_menu".to_string()),
+                context: None,
+                view: None,
+                exclusive: false,
+            },
+        ];
+        let groups = keybinding_groups(&bindings, "");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].title.as_deref(), Some("View: files"));
+        assert_eq!(groups[0].items[0].subtitle.as_deref(), Some("Lua function"));
+        assert_eq!(groups[1].title.as_deref(), Some("Global"));
+
+        let groups = keybinding_groups(&bindings, "action_menu");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items[0].title, "tab");
     }
 
     #[test]
@@ -5,33 +5,227 @@
 //! - Hook system for intercepting search/actions
 //! - Effect-based Lua execution model
 //! - View stack management
-//! - Lua-scriptable keybinding system
+//! - Lua-scriptable keybinding system (view > context > global), and a Keybindings view
+//! - Keymap conflicts (replaced defaults, hotkeys the OS uses), and a Keymap Conflicts view
+//! - Desktop integration (open, reveal, clipboard, notifications) behind the `DesktopPlatform` trait
+//! - Configurable web search engines
+//! - Calendar and reminders via EventKit
+//! - Media playback control
+//! - Wi-Fi, Bluetooth, and network info
+//! - Countdown timers
+//! - System-wide file search through Spotlight
+//! - SSH hosts from `~/.ssh/config` and `known_hosts`
+//! - Git repositories under configured roots, indexed in the background
+//! - Config files (init.lua, plugins, the data folder) opened in a configurable editor
+//! - Dictionary lookups with `define <word>`
+//! - Color conversion with a history of recently copied colors
+//! - Currency conversion with periodically refreshed exchange rates
+//! - Natural-language dates, with answers for date arithmetic
+//! - World clock answers from the tz database, with clocks pinned to the root view
+//! - QR codes for text, URLs, and Wi-Fi credentials
+//! - Screenshot and screen recording commands
+//! - Recently opened documents and the Downloads folder, watched for changes
+//! - Finder integration: the selected files, and a hotkey that opens Lux on them
+//! - Zip and tar archives, with Compress / Extract actions for files
+//! - Profiles that switch off views, hooks, and built-in sources
+//! - Theme overrides (appearance, Reduce Motion, Increase Contrast, compact mode, interface scale)
+//! - Sound effects and haptic feedback when opening, running actions, and on errors
+//! - Mouse behavior of result rows (hover to move the cursor)
+//! - Macros: launcher interactions recorded to Lua files and played back by name
+//! - A Commands view for Lux's own actions (reload config, open logs, clear caches)
+//! - Opt-in remote control over localhost HTTP
+//! - Helper processes started by plugins, restarted with backoff and stopped on quit
+//! - Alfred workflow import
+//! - Browser tab switcher (Safari, Chromium browsers, DevTools endpoints)
+//! - Text transformations of the clipboard or the frontmost selection
+//! - Menu bar search of the frontmost app (a command palette for any app)
+//! - Fuzzy matching that finds CJK titles by their romanization (pinyin, romaji)
+//! - Password, UUID, and placeholder text generators
+//! - HTTP requests, and a request builder view with history
+//! - WebSocket connections whose messages go to Lua callbacks
+//! - Locale-aware date formatting (translations live in `lux_core::i18n`)
+//! - Undo for file operations and plugin actions
+//! - Query rewriting (normalization, aliases, middlewares) before sources run
+//! - Search operators (`type:pdf`, `"quoted phrases"`) parsed for sources
+//! - Results pinned to the top of the root view
+//! - Hidden results, and built-in sources and plugin views turned off for good
+//! - Collapsible result groups, remembered per view
+//! - Configurable group order and weights, globally or per view
+//! - Query routing rules that hand query shapes (math, URLs, paths) to a view
+//! - Rules that open the launcher on a view while a given app is frontmost
+//! - A basket collecting results from any view for bulk actions
+//! - Lua memory limits, idle garbage collection, and memory usage per view
+//! - Opt-in local metrics (query latency, plugin run times, action usage) and a Stats view
+//! - Starting Lux at login, and a Preferences view to turn it on
+//! - Diagnostics for `lux doctor` and a Doctor view
+//! - Secrets in the Keychain, redacted from logs
+//! - OAuth 2.0 sign-in through the browser, with tokens refreshed when they expire
+//! - Encryption at rest for history, pins, and other data files
+//! - Third-party plugins with manifests, and restricted mode for untrusted ones
+//! - Warnings for unknown and deprecated view fields, and a Config Warnings view
+//! - Lua API versions, with shims for plugins targeting an older one
+//! - Declarative action outputs (copy, paste, open, detail) instead of handlers
+//! - An audit log of executed actions, and an Activity view to re-run them
+//! - Backups of the config and data folders, and syncing the data folder
 
+pub mod activity;
+pub mod alfred;
+pub mod apps;
+pub mod archive;
+pub mod audio;
+pub mod autostart;
+pub mod backup;
+pub mod basket;
+pub mod calendar;
+pub mod capture;
+pub mod collapsed;
+pub mod colors;
+pub mod commands;
+pub mod compat;
+pub mod config_files;
+pub mod conflicts;
 pub mod context;
+pub mod currency;
+pub mod daemons;
+pub mod dates;
+pub mod desktop;
+pub mod detect;
+pub mod dictionary;
+pub mod doctor;
 pub mod effect;
 pub mod engine;
 pub mod error;
+pub mod finder;
+pub mod generate;
+pub mod git;
 pub mod handle;
+pub mod hidden;
 pub mod hooks;
+pub mod http;
+pub mod i18n;
 pub mod keymap;
 pub mod lua;
+pub mod macros;
+pub mod matching;
+pub mod media;
+pub mod memory;
+pub mod menus;
+pub mod metrics;
+pub mod mouse;
+pub mod network;
+pub mod oauth;
+pub mod operators;
+pub mod ordering;
+pub mod permissions;
+pub mod pins;
+pub mod profiles;
+pub mod qr;
+pub mod recent;
 pub mod registry;
+pub mod rewrite;
+pub mod routes;
+pub mod rules;
+pub mod secrets;
+pub mod security;
+pub mod server;
+pub mod spotlight;
+pub mod ssh;
+pub mod storage;
+pub mod tabs;
+pub mod text;
+pub mod theme;
+pub mod timers;
 pub mod types;
+pub mod undo;
 pub mod views;
+pub mod warnings;
+pub mod websearch;
+pub mod websocket;
+pub mod worldclock;
 
 // Re-export commonly used types
+pub use activity::{ActivityEntry, ActivityLog, Outcome, RepeatOutcome};
+pub use alfred::{AlfredError, Workflow};
+pub use apps::AppInfo;
+pub use archive::ArchiveFormat;
+pub use audio::{EventSound, SoundError, SoundEvent, SoundSettings, SoundStore};
+pub use autostart::{Autostart, AutostartStatus};
+pub use backup::{Backup, LockOwner, SyncLock};
+pub use basket::{Basket, BASKET_OPEN_KEY};
+pub use calendar::{CalendarEvent, Reminder};
+pub use capture::{CaptureKind, Captures};
+pub use collapsed::CollapsedGroups;
+pub use colors::{ColorFormat, ColorHistory};
+pub use commands::{command_groups, commands, Command, COMMANDS_VIEW_ID};
+pub use compat::{Shim, API_VERSION, MIN_API_VERSION};
+pub use config_files::{ConfigFile, ConfigPaths, Editor, EditorSettings};
+pub use conflicts::{
+    system_hotkey, BindingSource, ConflictKind, KeymapConflict, KeymapConflicts, Resolution,
+    KEYMAP_CONFLICTS_VIEW_ID,
+};
+pub use currency::{CurrencyConverter, ExchangeRates};
+pub use daemons::{
+    DaemonError, DaemonInfo, DaemonSpec, DaemonState, Daemons, RestartPolicy, DAEMONS_VIEW_ID,
+};
+pub use dates::{parse_date, ParsedDate};
+pub use desktop::{DesktopPlatform, LinuxDesktop, MacDesktop};
+pub use detect::{detect, Detection};
+pub use dictionary::{DictionaryCache, DictionaryEntry};
+pub use doctor::{diagnose, Check, Diagnostics, DoctorReport, Section, Severity};
 pub use effect::{Effect, EffectCollector, ViewSpec};
-pub use engine::{ActionInfo, ApplyResult, QueryEngine};
+pub use engine::{ActionInfo, ApplyResult, QueryEngine, ViewStackChange, ViewStackEvent};
 pub use error::{PluginError, PluginResult};
+pub use finder::{finder_selection, SEND_TO_LUX_KEY};
+pub use generate::{Generator, PasswordOptions};
+pub use git::{GitIndex, GitRepo, RepoStatus};
+pub use hidden::Blocklist;
 pub use hooks::{HookEntry, HookError, HookRegistry};
+pub use http::{HttpClient, HttpRequest, HttpResponse};
 pub use keymap::{
-    generate_handler_id, BuiltInHotkey, GlobalHandler, KeyHandler, KeymapRegistry, PendingBinding,
-    PendingHotkey,
+    generate_handler_id, keybinding_groups, normalize_shortcut, BindingScope, BuiltInHotkey,
+    GlobalHandler, KeyHandler, KeymapRegistry, PendingBinding, PendingHotkey, DEFAULT_CONTEXT,
+    KEYBINDINGS_VIEW_ID,
 };
 pub use lua::register_lux_api;
+pub use macros::{Macro, MacroError, MacroStep, Macros, MACROS_VIEW_ID};
+pub use matching::{Matcher, Romanization};
+pub use media::{MediaCommand, NowPlaying, NowPlayingCache};
+pub use memory::{GcSettings, MemoryMonitor, ViewMemory};
+pub use menus::{MenuCache, MenuItem, MenuTarget};
+pub use metrics::{Metrics, MetricsSummary, PluginStats};
+pub use mouse::{MouseSettings, MouseStore};
+pub use oauth::{FinishedFlow, OAuthConfig, OAuthError, OAuthFlows, OAuthStatus, OAuthToken};
+pub use operators::{parse_query, ParsedQuery};
+pub use ordering::{OrderRules, ResultOrdering};
+pub use permissions::{Permission, PermissionStatus};
+pub use pins::Pins;
+pub use profiles::{Profile, ProfileCondition, ProfileContext, ProfileError, ProfileRegistry};
+pub use qr::{qr_image, wifi_payload};
+pub use recent::{RecentFile, Recents};
 pub use registry::PluginRegistry;
-pub use types::{LuaFunctionRef, View, ViewInstance, ViewState};
+pub use rewrite::{Normalize, QueryRewriter, RewrittenQuery};
+pub use routes::{Route, RouteTable};
+pub use rules::{AppRule, AppRules};
+pub use secrets::Redacted;
+pub use security::{LoadedPlugin, Manifest, SecuritySettings};
+pub use server::{
+    RemoteCommand, RemoteRequest, RemoteServer, ServerConfig, ServerError, ServerSettings,
+};
+pub use spotlight::{SpotlightQuery, SpotlightSearch};
+pub use ssh::{SshHost, SshSettings, Terminal};
+pub use tabs::{Tab, TabFilter, TabSources};
+pub use text::{TextError, TextSource, Transform};
+pub use theme::{
+    DisplayPreferences, ThemeAppearance, ThemeDensity, ThemeError, ThemeOverrides, ThemeStore,
+};
+pub use timers::{Timer, TimerRegistry};
+pub use types::{LuaFunctionRef, SavedViewState, View, ViewInstance, ViewState};
+pub use undo::{UndoInfo, UndoOp, UndoStack};
 pub use views::{ViewDefinition, ViewDefinitionRef, ViewRegistry, ViewRegistryError};
+pub use warnings::{ConfigWarning, ConfigWarnings, WarningKind};
+pub use websearch::{SearchEngine, WebSearchError, WebSearchRegistry};
+pub use websocket::{WebSockets, WsCallbacks, WsError, WsEvent, WsEventKind, WsInfo, WsState};
+pub use worldclock::{TzInfo, WorldClock, Zone};
 
 // Re-export lux_core types for convenience
 pub use lux_core::{ActionResult, FollowUpAction, Group, Groups, Item, SelectionMode};
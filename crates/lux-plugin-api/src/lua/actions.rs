@@ -0,0 +1,188 @@
+//! Lua bindings for built-in actions.
+//!
+//! This module provides:
+//! - Handlers for the smart, network, Spotlight, color, hide, and definition actions
+
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use lux_core::Item;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::colors::{color_action_key, run_color_action, COLOR_ACTION_IDS};
+use crate::desktop;
+use crate::detect::{detect_item, run_smart_action, smart_action_key, SMART_ACTION_IDS};
+use crate::dictionary::{
+    dictionary_action_key, entry_from_item, run_dictionary_action, DictionaryEntry,
+    DICTIONARY_ACTION_IDS, ENTRY_VIEW_ID,
+};
+use crate::hidden::{run_hide_action, HIDE_ACTION_KEY};
+use crate::network::{network_action_key, run_network_action, NETWORK_ACTION_IDS};
+use crate::registry::PluginRegistry;
+use crate::spotlight::{run_spotlight_action, spotlight_action_key, SPOTLIGHT_ACTION_IDS};
+
+use super::bridge;
+use super::qr::qr_view;
+
+/// Register the built-in handlers.
+pub(super) fn register(lua: &Lua, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in smart action handlers (see crate::detect)
+    //
+    // Each handler re-detects the value on the first item and runs the action.
+    for action_id in SMART_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            let detection = detect_item(&item).ok_or_else(|| {
+                mlua::Error::RuntimeError(format!("Nothing to {} in item", action_id))
+            })?;
+            if *action_id == "show_qr" {
+                let text = detection.qr_payload().ok_or_else(|| {
+                    mlua::Error::RuntimeError(format!("No QR code for {}", detection.kind()))
+                })?;
+                return ctx.call_method::<()>("push", qr_view(lua, &text, &text)?);
+            }
+
+            run_smart_action(action_id, &detection)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&smart_action_key(action_id), handler)?;
+    }
+
+    // Built-in network action handlers (see crate::network)
+    for action_id in NETWORK_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_network_action(action_id, &item)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&network_action_key(action_id), handler)?;
+    }
+
+    // Built-in Spotlight action handlers (see crate::spotlight)
+    for action_id in SPOTLIGHT_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_spotlight_action(action_id, &item)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&spotlight_action_key(action_id), handler)?;
+    }
+
+    // Built-in color action handlers (see crate::colors)
+    for action_id in COLOR_ACTION_IDS {
+        let colors = registry.colors();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_color_action(action_id, &item, &colors)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&color_action_key(action_id), handler)?;
+    }
+
+    // Built-in "Hide This Result" handler (see crate::hidden); the launcher
+    // refreshes the results afterwards
+    {
+        let blocklist = registry.blocklist();
+        let handler = lua.create_function(move |lua, (items, _ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_hide_action(&item, &blocklist);
+            Ok(())
+        })?;
+        lua.set_named_registry_value(HIDE_ACTION_KEY, handler)?;
+    }
+
+    // Built-in definition action handlers (see crate::dictionary)
+    for action_id in DICTIONARY_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            if *action_id == "show_entry" {
+                let entry = entry_from_item(&item).ok_or_else(|| {
+                    mlua::Error::RuntimeError("Item is not a definition".to_string())
+                })?;
+                return ctx.call_method::<()>("push", dictionary_entry_view(lua, entry)?);
+            }
+            run_dictionary_action(action_id, &item)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&dictionary_action_key(action_id), handler)?;
+    }
+
+    Ok(())
+}
+
+/// Build the view showing a whole dictionary entry, one row per sense or note.
+fn dictionary_entry_view(lua: &Lua, entry: DictionaryEntry) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", ENTRY_VIEW_ID)?;
+    view.set("title", entry.word.as_str())?;
+    view.set("placeholder", t("Filter entry..."))?;
+
+    // search(query, ctx) - senses grouped by part of speech, then the notes
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let query = query.trim().to_lowercase();
+        let row = |id: String, text: &str| {
+            Item::new(id, text).with_data(serde_json::json!({ "text": text }))
+        };
+
+        let mut groups: Vec<(String, Vec<Item>)> = Vec::new();
+        for (i, sense) in entry.senses.iter().enumerate() {
+            if !sense.text.to_lowercase().contains(&query) {
+                continue;
+            }
+            let item = row(format!("sense:{}", i), &sense.text);
+            match groups.iter_mut().find(|(t, _)| *t == sense.part_of_speech) {
+                Some((_, items)) => items.push(item),
+                None => groups.push((sense.part_of_speech.clone(), vec![item])),
+            }
+        }
+        for (i, (heading, text)) in entry.notes.iter().enumerate() {
+            if text.to_lowercase().contains(&query) {
+                groups.push((heading.clone(), vec![row(format!("note:{}", i), text)]));
+            }
+        }
+
+        let result = lua.create_table()?;
+        for (title, items) in &groups {
+            let group = lua.create_table()?;
+            group.set("title", title.as_str())?;
+            let group_items = lua.create_table()?;
+            for item in items {
+                group_items.push(bridge::item_to_lua(lua, item)?)?;
+            }
+            group.set("items", group_items)?;
+            result.push(group)?;
+        }
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy the row's text
+    let get_actions_fn = lua.create_function(|lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        let Some(text) = item
+            .data
+            .as_ref()
+            .and_then(|data| data.get("text")?.as_str())
+            .map(String::from)
+        else {
+            return Ok(actions);
+        };
+
+        let action = lua.create_table()?;
+        action.set("id", "copy")?;
+        action.set("title", t("Copy to Clipboard"))?;
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().clipboard_write(&text)?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,248 @@
+//! Lua bindings for Alfred workflows.
+//!
+//! This module provides:
+//! - `lux.alfred.view/import/import_view()` - Alfred workflow import
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::alfred::{AlfredError, TriggerKind, Workflow, WorkflowAction};
+use crate::desktop;
+
+use super::bridge;
+
+/// Register `lux.alfred`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.alfred - Alfred workflow import
+    //
+    // Usage:
+    //   lux.alfred.import("~/Downloads/GitHub.alfredworkflow")  -- returns the plugin path
+    //   ctx:push(lux.alfred.import_view())                      -- pick from installed workflows
+    //   lux.views.add(lux.alfred.view(dir, uid, { id = "gh" }))  -- used by imported plugins
+    {
+        let alfred_table = lua.create_table()?;
+
+        let view_fn =
+            lua.create_function(|lua, (dir, uid, opts): (String, String, Option<Table>)| {
+                alfred_view(lua, &dir, &uid, opts)
+            })?;
+        alfred_table.set("view", view_fn)?;
+
+        let import_fn = lua.create_function(|_lua, path: String| {
+            let out_dir = crate::alfred::plugin_dir().ok_or_else(|| {
+                mlua::Error::RuntimeError("alfred.import: no config directory".to_string())
+            })?;
+            let (_, file) = crate::alfred::import_workflow(
+                std::path::Path::new(&crate::detect::expand_home(&path)),
+                &out_dir,
+            )
+            .map_err(|e| mlua::Error::RuntimeError(format!("alfred.import: {}", e)))?;
+            Ok(file.to_string_lossy().to_string())
+        })?;
+        alfred_table.set("import", import_fn)?;
+
+        let import_view_fn = lua.create_function(|lua, ()| alfred_import_view(lua))?;
+        alfred_table.set("import_view", import_view_fn)?;
+
+        lux.set("alfred", alfred_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the view for one trigger of an Alfred workflow.
+///
+/// `opts` can override the view's `id`, `title`, and `placeholder`.
+fn alfred_view(lua: &Lua, dir: &str, uid: &str, opts: Option<Table>) -> LuaResult<Table> {
+    let workflow = Workflow::load(Path::new(&crate::detect::expand_home(dir)))
+        .map_err(|e| mlua::Error::RuntimeError(format!("alfred.view: {}", e)))?;
+    let trigger = workflow.trigger(uid).cloned().ok_or_else(|| {
+        mlua::Error::RuntimeError(format!(
+            "alfred.view: {}",
+            AlfredError::TriggerNotFound(uid.to_string())
+        ))
+    })?;
+    let opt = |key: &str| -> LuaResult<Option<String>> {
+        match &opts {
+            Some(opts) => opts.get(key),
+            None => Ok(None),
+        }
+    };
+
+    let view = lua.create_table()?;
+    view.set(
+        "id",
+        opt("id")?.unwrap_or_else(|| workflow.view_id(&trigger)),
+    )?;
+    view.set(
+        "title",
+        opt("title")?.unwrap_or_else(|| trigger.title.clone()),
+    )?;
+    view.set(
+        "placeholder",
+        opt("placeholder")?.unwrap_or_else(|| trigger.keyword.clone()),
+    )?;
+
+    let workflow = Arc::new(workflow);
+    let trigger = Arc::new(trigger);
+
+    // search(query, ctx) - run the script filter, or show the keyword's result
+    let (search_workflow, search_trigger) = (workflow.clone(), trigger.clone());
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let items = match &search_trigger.kind {
+            TriggerKind::ScriptFilter(script) => search_workflow
+                .run_script_filter(script, &query)
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?,
+            TriggerKind::Keyword => vec![search_workflow.keyword_item(&search_trigger, &query)],
+        };
+        let group = lua.create_table()?;
+        group.set("title", search_workflow.name.as_str())?;
+        let group_items = lua.create_table()?;
+        for (i, item) in items.iter().enumerate() {
+            group_items.set(i + 1, bridge::item_to_lua(lua, item)?)?;
+        }
+        group.set("items", group_items)?;
+        let result = lua.create_table()?;
+        result.push(group)?;
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - the trigger's connected actions (copy if none)
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let data = item.data.clone().unwrap_or_default();
+        let actions = lua.create_table()?;
+        if data["valid"] == false {
+            return Ok(actions);
+        }
+        let arg = data["arg"].as_str().unwrap_or(&item.title).to_string();
+
+        let connected = if trigger.actions.is_empty() {
+            vec![WorkflowAction::Copy("{query}".to_string())]
+        } else {
+            trigger.actions.clone()
+        };
+        let mut ids = HashSet::new();
+        for (i, action) in connected.into_iter().enumerate() {
+            let id = if ids.insert(action.id()) {
+                action.id().to_string()
+            } else {
+                format!("{}_{}", action.id(), i + 1)
+            };
+            let title = action.title();
+            let (workflow, arg) = (workflow.clone(), arg.clone());
+            let handler =
+                lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                    action.run(&workflow, &arg)?;
+                    ctx.call_method::<()>("dismiss", ())
+                })?;
+            let entry = lua.create_table()?;
+            entry.set("id", id)?;
+            entry.set("title", title)?;
+            entry.set("handler", handler)?;
+            actions.push(entry)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the view that lists installed Alfred workflows for import.
+fn alfred_import_view(lua: &Lua) -> LuaResult<Table> {
+    let items: Vec<Item> = crate::alfred::installed_workflows()
+        .iter()
+        .map(|workflow| {
+            let mut item = Item::new(
+                format!("alfred_workflow:{}", workflow.dir.display()),
+                workflow.name.clone(),
+            )
+            .with_type("alfred_workflow")
+            .with_data(serde_json::json!({ "path": workflow.dir.to_string_lossy() }));
+            item.subtitle = workflow
+                .description
+                .clone()
+                .or_else(|| workflow.bundle_id.clone());
+            item.icon = Some(workflow.dir.join("icon.png"))
+                .filter(|path| path.exists())
+                .map(|path| path.to_string_lossy().to_string());
+            item
+        })
+        .collect();
+
+    let view = lua.create_table()?;
+    view.set("id", "alfred_import")?;
+    view.set("title", t("Alfred Workflows"))?;
+    view.set("placeholder", t("Search Alfred workflows..."))?;
+
+    // search(query, ctx) - filter by name
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let query = query.to_lowercase();
+        let group_items = lua.create_table()?;
+        for item in items
+            .iter()
+            .filter(|item| item.title.to_lowercase().contains(&query))
+        {
+            group_items.push(bridge::item_to_lua(lua, item)?)?;
+        }
+        let group = lua.create_table()?;
+        group.set("items", group_items)?;
+        let result = lua.create_table()?;
+        result.push(group)?;
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - import, or reveal the workflow folder
+    let get_actions_fn = lua.create_function(|lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let path = item
+            .data
+            .as_ref()
+            .and_then(|data| data["path"].as_str())
+            .unwrap_or_default()
+            .to_string();
+        let actions = lua.create_table()?;
+
+        let import_path = path.clone();
+        let import_fn = lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+            let out_dir = crate::alfred::plugin_dir().ok_or_else(|| {
+                mlua::Error::RuntimeError("alfred.import: no config directory".to_string())
+            })?;
+            match crate::alfred::import_workflow(Path::new(&import_path), &out_dir) {
+                Ok((_, file)) => ctx.call_method::<()>(
+                    "complete",
+                    t_args("Imported to {path}", &[("path", &file.to_string_lossy())]),
+                ),
+                Err(e) => ctx.call_method::<()>("fail", e.to_string()),
+            }
+        })?;
+        let import = lua.create_table()?;
+        import.set("id", "import")?;
+        import.set("title", t("Import"))?;
+        import.set("handler", import_fn)?;
+        actions.push(import)?;
+
+        let reveal_fn = lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+            desktop::current().reveal(&path)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        let reveal = lua.create_table()?;
+        reveal.set("id", "reveal")?;
+        reveal.set("title", t("Reveal in Finder"))?;
+        reveal.set("handler", reveal_fn)?;
+        actions.push(reveal)?;
+
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
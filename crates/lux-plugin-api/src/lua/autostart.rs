@@ -0,0 +1,56 @@
+//! Lua bindings for starting at login.
+//!
+//! This module provides:
+//! - `lux.autostart.enable/disable/status()` - Start Lux at login
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::autostart::Autostart;
+
+/// Register `lux.autostart`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.autostart - Start Lux at login (a LaunchAgent on macOS, XDG autostart on Linux)
+    //
+    // Usage:
+    //   lux.autostart.enable()    -- starts the running binary at the next login
+    //   lux.autostart.disable()   -- true if there was a login item to remove
+    //   lux.autostart.status()    -- { enabled = true, path = ".../dev.lux.launcher.plist", program = "/usr/local/bin/lux" }
+    //
+    // lux.builtin.preferences() has an "Open at Login" toggle.
+    {
+        let autostart_table = lua.create_table()?;
+
+        let enable_fn = lua.create_function(|_lua, ()| {
+            autostart()?.enable()?;
+            Ok(())
+        })?;
+        autostart_table.set("enable", enable_fn)?;
+
+        let disable_fn = lua.create_function(|_lua, ()| Ok(autostart()?.disable()?))?;
+        autostart_table.set("disable", disable_fn)?;
+
+        let status_fn = lua.create_function(|lua, ()| {
+            let status = autostart()?.status();
+            let table = lua.create_table()?;
+            table.set("enabled", status.enabled)?;
+            table.set("path", status.path.to_string_lossy().to_string())?;
+            table.set(
+                "program",
+                status.program.map(|p| p.to_string_lossy().to_string()),
+            )?;
+            Ok(table)
+        })?;
+        autostart_table.set("status", status_fn)?;
+
+        lux.set("autostart", autostart_table)?;
+    }
+
+    Ok(())
+}
+
+/// The login item for the running binary, or an error if there is none.
+fn autostart() -> LuaResult<Autostart> {
+    Autostart::current().ok_or_else(|| {
+        mlua::Error::RuntimeError("autostart: no home directory or binary path".to_string())
+    })
+}
@@ -0,0 +1,173 @@
+//! Lua bindings for the basket.
+//!
+//! This module provides:
+//! - `lux.basket.items/add/remove/clear/view/add_action()` - Items collected for bulk actions
+//! - Handlers for the basket actions and the basket key
+
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Function, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::basket::{
+    basket_action_key, basket_actions, basket_groups, run_basket_action, run_basket_bulk_action,
+    Basket, BASKET_ACTIONS_KEY, BASKET_ACTION_IDS, BASKET_OPEN_KEY, BASKET_VIEW_ID,
+};
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+
+use super::{bridge, groups_to_lua};
+
+/// Register `lux.basket`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in basket action handlers (see crate::basket); the launcher
+    // refreshes the results (and the badge) afterwards
+    for action_id in BASKET_ACTION_IDS {
+        let basket = registry.basket();
+        let handler = lua.create_function(move |lua, (items, _ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_basket_action(action_id, &item, &basket)?;
+            Ok(())
+        })?;
+        lua.set_named_registry_value(&basket_action_key(action_id), handler)?;
+    }
+
+    // Built-in key handler opening the basket view (Cmd+Shift+B, or the badge)
+    {
+        let basket = registry.basket();
+        let handler = lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+            ctx.call_method::<()>("push", basket_view(lua, basket.clone())?)
+        })?;
+        lua.set_named_registry_value(BASKET_OPEN_KEY, handler)?;
+        registry.keymap().store_lua_handler(
+            BASKET_OPEN_KEY.to_string(),
+            LuaFunctionRef::new(BASKET_OPEN_KEY.to_string()),
+        );
+    }
+
+    // lux.basket - Items collected across views for bulk actions
+    //
+    // Usage:
+    //   lux.basket.add({ id = "tab:1", title = "https://lux.dev" })   -- false if already added
+    //   lux.basket.remove("tab:1")         -- true if it was in the basket
+    //   lux.basket.items()                 -- { { id, title, ... }, ... }
+    //   lux.basket.clear()
+    //   ctx:push(lux.basket.view())        -- the basket view, every item selected
+    //   lux.basket.add_action({ id = "share", title = "Share", handler = function(items, ctx) ... end })
+    {
+        let basket_table = lua.create_table()?;
+        lua.set_named_registry_value(BASKET_ACTIONS_KEY, lua.create_table()?)?;
+
+        let basket = registry.basket();
+        let items_fn = lua.create_function(move |lua, ()| {
+            let table = lua.create_table()?;
+            for (i, item) in basket.items().iter().enumerate() {
+                table.set(i + 1, bridge::item_to_lua(lua, item)?)?;
+            }
+            Ok(table)
+        })?;
+        basket_table.set("items", items_fn)?;
+
+        let basket = registry.basket();
+        let add_fn = lua.create_function(move |lua, item: Table| {
+            Ok(basket.add(bridge::parse_item(lua, item)?))
+        })?;
+        basket_table.set("add", add_fn)?;
+
+        let basket = registry.basket();
+        let remove_fn = lua.create_function(move |_lua, id: String| Ok(basket.remove(&id)))?;
+        basket_table.set("remove", remove_fn)?;
+
+        let basket = registry.basket();
+        let clear_fn = lua.create_function(move |_lua, ()| {
+            basket.clear();
+            Ok(())
+        })?;
+        basket_table.set("clear", clear_fn)?;
+
+        let basket = registry.basket();
+        let view_fn = lua.create_function(move |lua, ()| basket_view(lua, basket.clone()))?;
+        basket_table.set("view", view_fn)?;
+
+        let add_action_fn = lua.create_function(|lua, action: Table| {
+            action.get::<String>("title").map_err(|_| {
+                mlua::Error::RuntimeError("basket.add_action: 'title' is required".to_string())
+            })?;
+            action.get::<Function>("handler").map_err(|_| {
+                mlua::Error::RuntimeError(
+                    "basket.add_action: 'handler' must be a function".to_string(),
+                )
+            })?;
+            let actions: Table = lua.named_registry_value(BASKET_ACTIONS_KEY)?;
+            actions.push(action)
+        })?;
+        basket_table.set("add_action", add_action_fn)?;
+
+        lux.set("basket", basket_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the basket view, with every item selected.
+///
+/// Actions run on all selected items: the built-in ones first, then those
+/// added with `lux.basket.add_action()`.
+fn basket_view(lua: &Lua, basket: Arc<Basket>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", BASKET_VIEW_ID)?;
+    view.set("title", t("Basket"))?;
+    view.set("placeholder", t("Filter basket..."))?;
+    view.set("selection", "multi")?;
+    view.set(
+        "selected",
+        basket
+            .items()
+            .iter()
+            .map(|item| item.id.clone())
+            .collect::<Vec<_>>(),
+    )?;
+
+    // search(query, ctx) - the items whose title matches
+    let search_basket = basket.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = basket_groups(&search_basket, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - built-in bulk actions, then the added ones
+    let get_actions_fn = lua.create_function(move |lua, (_item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        for (id, title) in basket_actions() {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            let basket = basket.clone();
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+                    let items = items
+                        .sequence_values::<Table>()
+                        .map(|item| bridge::parse_item(lua, item?))
+                        .collect::<LuaResult<Vec<_>>>()?;
+                    run_basket_bulk_action(id, &items, &basket)?;
+                    // Removing keeps the view open; the results refresh
+                    match id {
+                        "remove" | "clear" => Ok(()),
+                        _ => ctx.call_method::<()>("dismiss", ()),
+                    }
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        let added: Table = lua.named_registry_value(BASKET_ACTIONS_KEY)?;
+        for action in added.sequence_values::<Table>() {
+            actions.push(action?)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -4,12 +4,25 @@
 //! All effect collection happens through `EffectCollector`, and the engine applies
 //! effects after the Lua call completes.
 
-use mlua::{Lua, Result as LuaResult, Table, UserData, UserDataMethods};
-
-use crate::context::{ActionContext, SelectContext, SourceContext, SubmitContext, TriggerContext};
+use mlua::{
+    FromLua, Function, IntoLuaMulti, Lua, Result as LuaResult, Table, UserData, UserDataMethods,
+    Value,
+};
+
+use crate::context::{
+    ActionContext, LifecycleContext, SelectContext, SelectionChangedContext, SourceContext,
+    SubmitContext, TriggerContext,
+};
 use crate::effect::{Effect, EffectCollector, ViewSpec};
-use crate::lua::json_to_lua_value;
-use lux_core::{Group, Item, SelectionMode};
+use crate::keymap::normalize_shortcut;
+use crate::lua::output::ActionOutput;
+use crate::lua::{json_to_lua_value, lua_value_to_json};
+use crate::operators::ParsedQuery;
+use lux_core::lua::{OnInvalid, SchemaError};
+use lux_core::{
+    DragPayload, FollowUpAction, Group, HandlerError, InlineEdit, Item, SelectionMode,
+    SubtitlePosition, Truncate,
+};
 
 // =============================================================================
 // Lua Wrappers (delegate to Rust contexts)
@@ -30,18 +43,24 @@ impl UserData for TriggerContextLua<'_> {
 
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         // Convenience: wrap items in a single ungrouped group
-        methods.add_method("set_items", |lua, this, items: Table| {
-            let items = parse_items(lua, items)?;
-            this.inner.set_groups(vec![Group { title: None, items }]);
-            Ok(())
-        });
+        methods.add_method(
+            "set_items",
+            |_, this, (items, options): (Table, Option<Table>)| {
+                let items = parse_result_items(items, options, None)?;
+                this.inner.set_groups(vec![Group::ungrouped(items)]);
+                Ok(())
+            },
+        );
 
         // Full control: set groups directly
-        methods.add_method("set_groups", |lua, this, groups: Table| {
-            let groups = parse_groups(lua, groups)?;
-            this.inner.set_groups(groups);
-            Ok(())
-        });
+        methods.add_method(
+            "set_groups",
+            |_, this, (groups, options): (Table, Option<Table>)| {
+                let groups = parse_results(groups, options, None)?;
+                this.inner.set_groups(groups);
+                Ok(())
+            },
+        );
 
         methods.add_method("push", |lua, this, view_def: Table| {
             let spec = parse_view_spec(lua, view_def)?;
@@ -70,26 +89,51 @@ pub struct SourceContextLua<'a> {
 impl UserData for SourceContextLua<'_> {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("query", |_, this| Ok(this.inner.query().to_string()));
+        fields.add_field_method_get("original_query", |_, this| {
+            Ok(this.inner.original_query().to_string())
+        });
+        fields.add_field_method_get("parsed", |lua, this| {
+            parsed_query_to_lua(lua, &this.inner.parsed_query())
+        });
         fields.add_field_method_get("view_data", |lua, this| {
-            json_to_lua_value(lua, this.inner.view_data())
+            json_to_lua_value(lua, &this.inner.current_view_data())
         });
     }
 
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         // Convenience: wrap items in a single ungrouped group
-        methods.add_method("set_items", |lua, this, items: Table| {
-            let items = parse_items(lua, items)?;
-            this.inner.set_groups(vec![Group { title: None, items }]);
-            Ok(())
-        });
+        methods.add_method(
+            "set_items",
+            |_, this, (items, options): (Table, Option<Table>)| {
+                let items = parse_result_items(items, options, this.inner.view_id())?;
+                this.inner.set_groups(vec![Group::ungrouped(items)]);
+                Ok(())
+            },
+        );
 
         // Full control: set groups directly
-        methods.add_method("set_groups", |lua, this, groups: Table| {
-            let groups = parse_groups(lua, groups)?;
-            this.inner.set_groups(groups);
+        methods.add_method(
+            "set_groups",
+            |_, this, (groups, options): (Table, Option<Table>)| {
+                let groups = parse_results(groups, options, this.inner.view_id())?;
+                this.inner.set_groups(groups);
+                Ok(())
+            },
+        );
+
+        // Persist state on the view instance across searches
+        methods.add_method("set_view_data", |lua, this, data: Value| {
+            this.inner.set_view_data(lua_value_to_json(lua, data)?);
             Ok(())
         });
 
+        methods.add_method("update_view_data", |lua, this, update: Function| {
+            this.inner.update_view_data(|data| {
+                let data: Value = update.call(json_to_lua_value(lua, &data)?)?;
+                lua_value_to_json(lua, data)
+            })
+        });
+
         // Note: No push, replace, dismiss - sources just return items
     }
 }
@@ -107,7 +151,7 @@ impl UserData for ActionContextLua<'_> {
             None => Ok(None),
         });
         fields.add_field_method_get("view_data", |lua, this| {
-            json_to_lua_value(lua, this.inner.view_data())
+            json_to_lua_value(lua, &this.inner.current_view_data())
         });
     }
 
@@ -139,28 +183,75 @@ impl UserData for ActionContextLua<'_> {
             Ok(())
         });
 
-        methods.add_method("complete", |_, this, message: String| {
-            this.inner.complete(message);
+        // Optional follow-ups: { { title = "Open", icon = "...", handler = fn }, ... }
+        methods.add_method(
+            "complete",
+            |lua, this, (message, follow_ups): (String, Option<Table>)| {
+                let actions = match follow_ups {
+                    Some(table) => parse_follow_ups(lua, table)?,
+                    None => Vec::new(),
+                };
+                this.inner.complete_with_actions(message, actions);
+                Ok(())
+            },
+        );
+
+        // A message, or { code = ..., message = ..., detail = ..., retryable = true }
+        methods.add_method("fail", |_, this, error: HandlerError| {
+            this.inner.fail(error);
             Ok(())
         });
 
-        methods.add_method("fail", |_, this, error: String| {
-            this.inner.fail(error);
+        // Rename in place: { value = "...", placeholder = "...", on_submit = fn(text, ctx) }
+        methods.add_method("edit", |lua, this, options: Table| {
+            let edit = parse_inline_edit(lua, this.inner.item(), options)?;
+            this.inner.edit(edit);
             Ok(())
         });
 
-        // set_items and set_groups for keybinding handlers that need to update results
-        methods.add_method("set_items", |lua, this, items: Table| {
-            let items = parse_items(lua, items)?;
-            this.inner.set_groups(vec![Group { title: None, items }]);
+        // Replay a macro from lux.macros (e.g. from a keybinding function)
+        methods.add_method("play_macro", |_, this, name: String| {
+            this.inner.play_macro(name);
+            Ok(())
+        });
+
+        // Run a launcher action such as "reload_config" (see builtin.commands)
+        methods.add_method("run_command", |_, this, action: String| {
+            this.inner.run_command(action);
             Ok(())
         });
 
-        methods.add_method("set_groups", |lua, this, groups: Table| {
-            let groups = parse_groups(lua, groups)?;
-            this.inner.set_groups(groups);
+        // set_items and set_groups for keybinding handlers that need to update results
+        methods.add_method(
+            "set_items",
+            |_, this, (items, options): (Table, Option<Table>)| {
+                let items = parse_result_items(items, options, None)?;
+                this.inner.set_groups(vec![Group::ungrouped(items)]);
+                Ok(())
+            },
+        );
+
+        methods.add_method(
+            "set_groups",
+            |_, this, (groups, options): (Table, Option<Table>)| {
+                let groups = parse_results(groups, options, None)?;
+                this.inner.set_groups(groups);
+                Ok(())
+            },
+        );
+
+        // Persist state on the view instance across searches
+        methods.add_method("set_view_data", |lua, this, data: Value| {
+            this.inner.set_view_data(lua_value_to_json(lua, data)?);
             Ok(())
         });
+
+        methods.add_method("update_view_data", |lua, this, update: Function| {
+            this.inner.update_view_data(|data| {
+                let data: Value = update.call(json_to_lua_value(lua, &data)?)?;
+                lua_value_to_json(lua, data)
+            })
+        });
     }
 }
 
@@ -222,17 +313,23 @@ pub fn call_source_search(
 ///
 /// Hook functions are called in order with `(query, ctx, original)`.
 /// Each hook can call `original(query, ctx)` to continue the chain.
+/// `original_query` is the query as typed, before rewriting (`ctx.original_query`).
+/// `view_id` names the view in errors about malformed results.
 pub fn call_hooked_search(
     lua: &Lua,
     search_fn_key: &str,
     hook_fn_keys: &[String],
     query: &str,
+    original_query: &str,
     view_data: &serde_json::Value,
+    view_id: &str,
 ) -> LuaResult<Vec<Effect>> {
     let collector = EffectCollector::new();
 
     lua.scope(|scope| {
-        let ctx = SourceContext::new(query, view_data, &collector);
+        let ctx = SourceContext::new(query, view_data, &collector)
+            .with_original_query(original_query)
+            .with_view_id(view_id);
         let wrapper = scope.create_userdata(SourceContextLua { inner: ctx })?;
 
         // Get the original search function
@@ -282,7 +379,11 @@ pub fn call_hooked_search(
 /// Call a view's get_actions function.
 ///
 /// Calls the function as `get_actions(item, ctx)`.
-/// Returns the parsed actions list.
+/// Returns the parsed actions list. Each action is a table with `title`,
+/// `handler` (or a declarative `output`, see `ActionOutput`), and optional
+/// `id`, `icon`, `shortcut` (e.g. `"cmd+o"`), and `destructive`. An entry of
+/// the form `{ section = "Danger", items = {...} }` groups its actions under a
+/// section; the section title is optional.
 pub fn call_get_actions(
     lua: &Lua,
     get_actions_fn_key: &str,
@@ -296,42 +397,141 @@ pub fn call_get_actions(
     let func: mlua::Function = lua.named_registry_value(get_actions_fn_key)?;
     let result: Table = func.call((item_table, ctx))?;
 
-    // Parse actions from the returned table
+    // Parse actions from the returned table, flattening sections
     let mut actions = Vec::new();
     for pair in result.pairs::<i64, Table>() {
-        let (_, action_table) = pair?;
-        let id: String = action_table
-            .get("id")
-            .unwrap_or_else(|_| uuid::Uuid::new_v4().to_string());
-        let title: String = action_table.get("title").map_err(|_| {
-            mlua::Error::RuntimeError("Action missing required 'title' field".to_string())
-        })?;
-        let icon: Option<String> = action_table.get("icon")?;
+        let (_, entry) = pair?;
+        match entry.get::<Option<Table>>("items")? {
+            Some(items) => {
+                let section: String = entry.get::<Option<String>>("section")?.unwrap_or_default();
+                for pair in items.pairs::<i64, Table>() {
+                    let (_, action_table) = pair?;
+                    actions.push(parse_action(lua, &action_table, Some(section.clone()))?);
+                }
+            }
+            None => actions.push(parse_action(lua, &entry, None)?),
+        }
+    }
 
-        // Store the handler function in the registry
-        let handler: mlua::Function = action_table.get("handler").map_err(|_| {
-            mlua::Error::RuntimeError("Action missing required 'handler' function".to_string())
+    Ok(actions)
+}
+
+/// Parse one action table returned by get_actions.
+///
+/// Stores the handler function (or the one built for its `output`) in the registry.
+fn parse_action(
+    lua: &Lua,
+    action_table: &Table,
+    section: Option<String>,
+) -> LuaResult<ParsedAction> {
+    let id: String = action_table
+        .get("id")
+        .unwrap_or_else(|_| uuid::Uuid::new_v4().to_string());
+    let title: String = action_table.get("title").map_err(|_| {
+        mlua::Error::RuntimeError("Action missing required 'title' field".to_string())
+    })?;
+    let icon: Option<String> = action_table.get("icon")?;
+    let shortcut = match action_table.get::<Option<String>>("shortcut")? {
+        Some(shortcut) => Some(normalize_shortcut(&shortcut).ok_or_else(|| {
+            mlua::Error::RuntimeError(format!("Invalid action shortcut '{}'", shortcut))
+        })?),
+        None => None,
+    };
+    let destructive = action_table
+        .get::<Option<bool>>("destructive")?
+        .unwrap_or(false);
+
+    let handler = match action_table.get::<Option<Function>>("handler")? {
+        Some(handler) => handler,
+        None => match action_table.get::<Option<Table>>("output")? {
+            Some(output) => ActionOutput::parse(&output)?.handler(lua, &title)?,
+            None => {
+                return Err(mlua::Error::RuntimeError(
+                    "Action missing required 'handler' function (or 'output' table)".to_string(),
+                ))
+            }
+        },
+    };
+    let handler_key = format!("action:{}:{}", id, uuid::Uuid::new_v4());
+    lua.set_named_registry_value(&handler_key, handler)?;
+
+    Ok(ParsedAction {
+        id,
+        title,
+        icon,
+        shortcut,
+        section,
+        destructive,
+        handler_key,
+    })
+}
+
+/// Parse the follow-up actions passed to `ctx:complete(message, actions)`.
+///
+/// Each follow-up is a table with `title`, `handler`, and optional `icon`.
+/// Handlers are stored in the registry and called like action handlers,
+/// as `handler(items, ctx)`.
+fn parse_follow_ups(lua: &Lua, table: Table) -> LuaResult<Vec<FollowUpAction>> {
+    let mut actions = Vec::new();
+    for pair in table.pairs::<i64, Table>() {
+        let (_, follow_up) = pair?;
+        let title: String = follow_up.get("title").map_err(|_| {
+            mlua::Error::RuntimeError("Follow-up action missing required 'title' field".to_string())
+        })?;
+        let icon: Option<String> = follow_up.get("icon")?;
+        let handler: mlua::Function = follow_up.get("handler").map_err(|_| {
+            mlua::Error::RuntimeError(
+                "Follow-up action missing required 'handler' function".to_string(),
+            )
         })?;
-        let handler_key = format!("action:{}:{}", id, uuid::Uuid::new_v4());
+        let handler_key = format!("followup:{}", uuid::Uuid::new_v4());
         lua.set_named_registry_value(&handler_key, handler)?;
 
-        actions.push(ParsedAction {
-            id,
+        actions.push(FollowUpAction {
             title,
             icon,
-            handler_key,
+            handler_key: Some(handler_key),
         });
     }
-
     Ok(actions)
 }
 
+/// Parse the options passed to `ctx:edit(options)`.
+///
+/// `value` defaults to the item's title. The `on_submit` callback is stored
+/// in the registry and called as `on_submit(text, ctx)` with an action context.
+fn parse_inline_edit(lua: &Lua, item: Option<&Item>, options: Table) -> LuaResult<InlineEdit> {
+    let item = item
+        .ok_or_else(|| mlua::Error::RuntimeError("ctx:edit() needs an item to edit".to_string()))?;
+    let value = options
+        .get::<Option<String>>("value")?
+        .unwrap_or_else(|| item.title.clone());
+    let placeholder: Option<String> = options.get("placeholder")?;
+    let on_submit: Function = options.get("on_submit").map_err(|_| {
+        mlua::Error::RuntimeError("ctx:edit() missing required 'on_submit' function".to_string())
+    })?;
+    let handler_key = format!("edit:{}", uuid::Uuid::new_v4());
+    lua.set_named_registry_value(&handler_key, on_submit)?;
+
+    Ok(InlineEdit {
+        item_id: item.id.clone(),
+        value,
+        placeholder,
+        handler_key,
+    })
+}
+
 /// Parsed action from get_actions callback.
 #[derive(Debug)]
 pub struct ParsedAction {
     pub id: String,
     pub title: String,
     pub icon: Option<String>,
+    pub shortcut: Option<String>,
+    /// Section the action was listed in (empty for an untitled section).
+    pub section: Option<String>,
+    /// Whether the action needs confirmation before it runs.
+    pub destructive: bool,
     pub handler_key: String,
 }
 
@@ -356,8 +556,59 @@ pub fn call_action_run(
 
         let func: mlua::Function = lua.named_registry_value(run_fn_key)?;
         // Call as handler(items, ctx)
-        func.call::<()>((items_table, wrapper))?;
-        Ok(())
+        call_handler(lua, func, (items_table, wrapper), &collector)
+    })?;
+
+    Ok(collector.take())
+}
+
+/// Call an action handler with `args`.
+///
+/// An error table the handler raises (`error({ code = "timeout", message = "..." })`)
+/// fails the action like `ctx:fail()` would; any other error is returned.
+fn call_handler(
+    lua: &Lua,
+    func: Function,
+    args: impl IntoLuaMulti,
+    collector: &EffectCollector,
+) -> LuaResult<()> {
+    let pcall: Function = lua.globals().get("pcall")?;
+    let mut args = args.into_lua_multi(lua)?;
+    args.push_front(Value::Function(func));
+    let (ok, error): (bool, Value) = pcall.call(args)?;
+    if ok {
+        return Ok(());
+    }
+    match error {
+        Value::Table(_) => {
+            let error = HandlerError::from_lua(error, lua)?;
+            collector.push(Effect::Fail { error });
+            Ok(())
+        }
+        Value::Error(error) => Err(*error),
+        other => Err(mlua::Error::RuntimeError(other.to_string()?)),
+    }
+}
+
+/// Call the `on_submit` callback of an inline edit with the submitted text.
+///
+/// Calls the function as `on_submit(text, ctx)`, with the edited item as
+/// `ctx.item`. Returns the collected effects for the engine to apply.
+pub fn call_edit_submit(
+    lua: &Lua,
+    handler_key: &str,
+    text: &str,
+    items: &[Item],
+    view_data: &serde_json::Value,
+) -> LuaResult<Vec<Effect>> {
+    let collector = EffectCollector::new();
+
+    lua.scope(|scope| {
+        let ctx = ActionContext::new(items, view_data, &collector);
+        let wrapper = scope.create_userdata(ActionContextLua { inner: ctx })?;
+
+        let func: mlua::Function = lua.named_registry_value(handler_key)?;
+        call_handler(lua, func, (text, wrapper), &collector)
     })?;
 
     Ok(collector.take())
@@ -372,7 +623,7 @@ impl UserData for SelectContextLua<'_> {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("item", |lua, this| item_to_lua(lua, this.inner.item()));
         fields.add_field_method_get("view_data", |lua, this| {
-            json_to_lua_value(lua, this.inner.view_data())
+            json_to_lua_value(lua, &this.inner.current_view_data())
         });
     }
 
@@ -404,6 +655,19 @@ impl UserData for SelectContextLua<'_> {
             }
             Ok(table)
         });
+
+        // Persist state on the view instance across searches
+        methods.add_method("set_view_data", |lua, this, data: Value| {
+            this.inner.set_view_data(lua_value_to_json(lua, data)?);
+            Ok(())
+        });
+
+        methods.add_method("update_view_data", |lua, this, update: Function| {
+            this.inner.update_view_data(|data| {
+                let data: Value = update.call(json_to_lua_value(lua, &data)?)?;
+                lua_value_to_json(lua, data)
+            })
+        });
     }
 }
 
@@ -431,6 +695,85 @@ pub fn call_view_on_select(
     Ok(collector.take())
 }
 
+/// Lua-visible wrapper for SelectionChangedContext.
+pub struct SelectionChangedContextLua<'a> {
+    pub inner: SelectionChangedContext<'a>,
+}
+
+impl UserData for SelectionChangedContextLua<'_> {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("added", |_, this| Ok(this.inner.added().to_vec()));
+        fields.add_field_method_get("removed", |_, this| Ok(this.inner.removed().to_vec()));
+        fields.add_field_method_get("view_data", |lua, this| {
+            json_to_lua_value(lua, &this.inner.current_view_data())
+        });
+    }
+
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("select", |_, this, id: String| {
+            this.inner.select(id);
+            Ok(())
+        });
+
+        methods.add_method("deselect", |_, this, id: String| {
+            this.inner.deselect(id);
+            Ok(())
+        });
+
+        methods.add_method("clear_selection", |_, this, ()| {
+            this.inner.clear_selection();
+            Ok(())
+        });
+
+        methods.add_method("is_selected", |_, this, id: String| {
+            Ok(this.inner.is_selected(&id))
+        });
+
+        methods.add_method("get_selection", |_, this, ()| {
+            Ok(this.inner.get_selection())
+        });
+
+        // Persist state on the view instance across searches
+        methods.add_method("set_view_data", |lua, this, data: Value| {
+            this.inner.set_view_data(lua_value_to_json(lua, data)?);
+            Ok(())
+        });
+
+        methods.add_method("update_view_data", |lua, this, update: Function| {
+            this.inner.update_view_data(|data| {
+                let data: Value = update.call(json_to_lua_value(lua, &data)?)?;
+                lua_value_to_json(lua, data)
+            })
+        });
+    }
+}
+
+/// Call a view's on_selection_changed function using effect-based execution.
+///
+/// Called as `on_selection_changed(selection, ctx)`, where `selection` is the
+/// list of selected item IDs. Returns the collected effects for the engine to apply.
+pub fn call_view_on_selection_changed(
+    lua: &Lua,
+    fn_key: &str,
+    view_data: &serde_json::Value,
+    selection: &[String],
+    added: &[String],
+    removed: &[String],
+) -> LuaResult<Vec<Effect>> {
+    let collector = EffectCollector::new();
+
+    lua.scope(|scope| {
+        let ctx = SelectionChangedContext::new(view_data, selection, added, removed, &collector);
+        let wrapper = scope.create_userdata(SelectionChangedContextLua { inner: ctx })?;
+
+        let func: mlua::Function = lua.named_registry_value(fn_key)?;
+        func.call::<()>((selection.to_vec(), wrapper))?;
+        Ok(())
+    })?;
+
+    Ok(collector.take())
+}
+
 /// Lua-visible wrapper for SubmitContext.
 pub struct SubmitContextLua<'a> {
     pub inner: SubmitContext<'a>,
@@ -440,7 +783,7 @@ impl UserData for SubmitContextLua<'_> {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("query", |_, this| Ok(this.inner.query().to_string()));
         fields.add_field_method_get("view_data", |lua, this| {
-            json_to_lua_value(lua, this.inner.view_data())
+            json_to_lua_value(lua, &this.inner.current_view_data())
         });
     }
 
@@ -466,6 +809,19 @@ impl UserData for SubmitContextLua<'_> {
             this.inner.dismiss();
             Ok(())
         });
+
+        // Persist state on the view instance across searches
+        methods.add_method("set_view_data", |lua, this, data: Value| {
+            this.inner.set_view_data(lua_value_to_json(lua, data)?);
+            Ok(())
+        });
+
+        methods.add_method("update_view_data", |lua, this, update: Function| {
+            this.inner.update_view_data(|data| {
+                let data: Value = update.call(json_to_lua_value(lua, &data)?)?;
+                lua_value_to_json(lua, data)
+            })
+        });
     }
 }
 
@@ -492,14 +848,75 @@ pub fn call_view_on_submit(
     Ok(collector.take())
 }
 
+/// Lua-visible wrapper for LifecycleContext.
+pub struct LifecycleContextLua<'a> {
+    pub inner: LifecycleContext<'a>,
+}
+
+impl UserData for LifecycleContextLua<'_> {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("view_data", |lua, this| {
+            json_to_lua_value(lua, &this.inner.current_view_data())
+        });
+    }
+
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method("set_view_data", |lua, this, data: Value| {
+            this.inner.set_view_data(lua_value_to_json(lua, data)?);
+            Ok(())
+        });
+
+        methods.add_method("update_view_data", |lua, this, update: Function| {
+            this.inner.update_view_data(|data| {
+                let data: Value = update.call(json_to_lua_value(lua, &data)?)?;
+                lua_value_to_json(lua, data)
+            })
+        });
+    }
+}
+
+/// Call a view's on_open, on_show, or on_close function using effect-based execution.
+///
+/// Returns the collected effects for the engine to apply.
+pub fn call_view_lifecycle(
+    lua: &Lua,
+    fn_key: &str,
+    view_data: &serde_json::Value,
+) -> LuaResult<Vec<Effect>> {
+    let collector = EffectCollector::new();
+
+    lua.scope(|scope| {
+        let ctx = LifecycleContext::new(view_data, &collector);
+        let wrapper = scope.create_userdata(LifecycleContextLua { inner: ctx })?;
+
+        let func: mlua::Function = lua.named_registry_value(fn_key)?;
+        func.call::<()>(wrapper)?;
+        Ok(())
+    })?;
+
+    Ok(collector.take())
+}
+
 // =============================================================================
 // Parsing Helpers
 // =============================================================================
 
 /// Parse a ViewSpec from a Lua table.
 ///
-/// Uses inline search functions stored in Lua registry.
+/// Uses inline search functions stored in Lua registry. The table is either
+/// the view itself or wraps it with push options:
+/// `ctx:push({ lux.qr.view(text), query = "wifi" })`. A `query` pre-fills the
+/// new view's search input.
 fn parse_view_spec(lua: &Lua, table: Table) -> LuaResult<ViewSpec> {
+    if let mlua::Value::Table(view) = table.get::<mlua::Value>(1)? {
+        let spec = parse_view_spec(lua, view)?;
+        return Ok(match table.get::<Option<String>>("query")? {
+            Some(query) => spec.with_query(query),
+            None => spec,
+        });
+    }
+
+    let id: Option<String> = table.get("id")?;
     let title: Option<String> = table.get("title")?;
     let placeholder: Option<String> = table.get("placeholder")?;
 
@@ -524,6 +941,16 @@ fn parse_view_spec(lua: &Lua, table: Table) -> LuaResult<ViewSpec> {
         None => SelectionMode::Single,
     };
 
+    // Parse get_actions callback
+    let get_actions_fn_key = match table.get::<Option<mlua::Function>>("get_actions")? {
+        Some(func) => {
+            let key = format!("view:get_actions:{}", uuid::Uuid::new_v4());
+            lua.set_named_registry_value(&key, func)?;
+            Some(key)
+        }
+        None => None,
+    };
+
     // Parse on_select callback
     let on_select_fn_key = match table.get::<Option<mlua::Function>>("on_select")? {
         Some(func) => {
@@ -544,102 +971,149 @@ fn parse_view_spec(lua: &Lua, table: Table) -> LuaResult<ViewSpec> {
         None => None,
     };
 
+    // Parse lifecycle callbacks
+    let on_open_fn_key = store_view_callback(lua, &table, "on_open")?;
+    let on_show_fn_key = store_view_callback(lua, &table, "on_show")?;
+    let on_close_fn_key = store_view_callback(lua, &table, "on_close")?;
+    let on_selection_changed_fn_key = store_view_callback(lua, &table, "on_selection_changed")?;
+
     // Parse view_data
     let view_data = match table.get::<Option<Table>>("view_data")? {
         Some(data_table) => super::lua_value_to_json(lua, mlua::Value::Table(data_table))?,
         None => serde_json::Value::Null,
     };
 
+    // Parse the refresh interval and initial query
+    let refresh_ms = super::parse::parse_refresh_ms(&table)?;
+    let query: Option<String> = table.get("query")?;
+
+    // Parse the initially selected item IDs
+    let selected = table
+        .get::<Option<Vec<String>>>("selected")?
+        .unwrap_or_default();
+
     let mut spec = ViewSpec::new(source_key)
         .with_selection_mode(selection_mode)
-        .with_view_data(view_data);
+        .with_view_data(view_data)
+        .with_selected(selected);
 
+    if let Some(i) = id {
+        spec = spec.with_id(i);
+    }
     if let Some(t) = title {
         spec = spec.with_title(t);
     }
     if let Some(p) = placeholder {
         spec = spec.with_placeholder(p);
     }
+    if let Some(k) = get_actions_fn_key {
+        spec = spec.with_get_actions(k);
+    }
     if let Some(k) = on_select_fn_key {
         spec = spec.with_on_select(k);
     }
     if let Some(k) = on_submit_fn_key {
         spec = spec.with_on_submit(k);
     }
+    if let Some(k) = on_open_fn_key {
+        spec = spec.with_on_open(k);
+    }
+    if let Some(k) = on_show_fn_key {
+        spec = spec.with_on_show(k);
+    }
+    if let Some(k) = on_close_fn_key {
+        spec = spec.with_on_close(k);
+    }
+    if let Some(k) = on_selection_changed_fn_key {
+        spec = spec.with_on_selection_changed(k);
+    }
+    if let Some(ms) = refresh_ms {
+        spec = spec.with_refresh_ms(ms);
+    }
+    if let Some(q) = query {
+        spec = spec.with_query(q);
+    }
 
     Ok(spec)
 }
 
-/// Parse items from a Lua table.
-fn parse_items(lua: &Lua, table: Table) -> LuaResult<Vec<Item>> {
-    let mut items = Vec::new();
-
-    for pair in table.pairs::<i64, Table>() {
-        let (_, item_table) = pair?;
-        items.push(parse_item(lua, item_table)?);
+/// Store the optional callback `table[name]` in the registry and return its key.
+fn store_view_callback(lua: &Lua, table: &Table, name: &str) -> LuaResult<Option<String>> {
+    match table.get::<Option<mlua::Function>>(name)? {
+        Some(func) => {
+            let key = format!("view:{}:{}", name, uuid::Uuid::new_v4());
+            lua.set_named_registry_value(&key, func)?;
+            Ok(Some(key))
+        }
+        None => Ok(None),
     }
+}
 
-    Ok(items)
+/// Parse the groups handed to `ctx:set_groups(groups, options)`.
+///
+/// Errors name the view, group, item, and field. With
+/// `{ skip_invalid = true }` malformed groups and items are left out and
+/// logged instead.
+fn parse_results(
+    groups: Table,
+    options: Option<Table>,
+    view_id: Option<&str>,
+) -> LuaResult<Vec<Group>> {
+    let (groups, skipped) = lux_core::lua::parse_groups(groups, on_invalid(options)?)
+        .map_err(|e| schema_error(e, view_id))?;
+    log_skipped(&skipped, view_id);
+    Ok(groups)
 }
 
-/// Parse groups from a Lua table.
-fn parse_groups(lua: &Lua, table: Table) -> LuaResult<Vec<Group>> {
-    let mut groups = Vec::new();
+/// Parse the items handed to `ctx:set_items(items, options)` (see `parse_results`).
+fn parse_result_items(
+    items: Table,
+    options: Option<Table>,
+    view_id: Option<&str>,
+) -> LuaResult<Vec<Item>> {
+    let (items, skipped) = lux_core::lua::parse_items(items, on_invalid(options)?)
+        .map_err(|e| schema_error(e, view_id))?;
+    log_skipped(&skipped, view_id);
+    Ok(items)
+}
 
-    for pair in table.pairs::<i64, Table>() {
-        let (_, group_table) = pair?;
+fn on_invalid(options: Option<Table>) -> LuaResult<OnInvalid> {
+    let skip = match options {
+        Some(options) => options.get::<Option<bool>>("skip_invalid")?,
+        None => None,
+    };
+    Ok(if skip == Some(true) {
+        OnInvalid::Skip
+    } else {
+        OnInvalid::Fail
+    })
+}
 
-        let title: Option<String> = group_table.get("title")?;
-        let items_table: Table = group_table.get("items").map_err(|e| {
-            mlua::Error::RuntimeError(format!("Group requires 'items' field: {}", e))
-        })?;
-        let items = parse_items(lua, items_table)?;
+fn schema_error(error: SchemaError, view_id: Option<&str>) -> mlua::Error {
+    mlua::Error::RuntimeError(match view_id {
+        Some(view_id) => format!("Invalid results from '{}': {}", view_id, error),
+        None => format!("Invalid results: {}", error),
+    })
+}
 
-        groups.push(Group { title, items });
+fn log_skipped(skipped: &[SchemaError], view_id: Option<&str>) {
+    for error in skipped {
+        match view_id {
+            Some(view_id) => {
+                tracing::warn!("Skipped a malformed result from '{}': {}", view_id, error)
+            }
+            None => tracing::warn!("Skipped a malformed result: {}", error),
+        }
     }
-
-    Ok(groups)
 }
 
 /// Parse a single item from a Lua table.
-fn parse_item(lua: &Lua, table: Table) -> LuaResult<Item> {
-    let id: String = table
-        .get::<Option<String>>("id")?
-        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-
-    let title: String = table
-        .get("title")
-        .map_err(|e| mlua::Error::RuntimeError(format!("Item requires 'title' field: {}", e)))?;
-
-    let subtitle: Option<String> = table.get("subtitle")?;
-    let icon: Option<String> = table.get("icon")?;
-
-    let types: Vec<String> = table
-        .get::<Option<Table>>("types")?
-        .map(|t| {
-            t.pairs::<i64, String>()
-                .filter_map(|r| r.ok().map(|(_, v)| v))
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let data: Option<serde_json::Value> = table
-        .get::<Option<mlua::Value>>("data")?
-        .map(|v| super::lua_value_to_json(lua, v))
-        .transpose()?;
-
-    Ok(Item {
-        id,
-        title,
-        subtitle,
-        icon,
-        types,
-        data,
-    })
+pub(crate) fn parse_item(lua: &Lua, table: Table) -> LuaResult<Item> {
+    Item::from_lua(Value::Table(table), lua)
 }
 
 /// Convert an Item to a Lua table.
-fn item_to_lua(lua: &Lua, item: &Item) -> LuaResult<Table> {
+pub(crate) fn item_to_lua(lua: &Lua, item: &Item) -> LuaResult<Table> {
     let table = lua.create_table()?;
     table.set("id", item.id.as_str())?;
     table.set("title", item.title.as_str())?;
@@ -658,15 +1132,62 @@ fn item_to_lua(lua: &Lua, item: &Item) -> LuaResult<Table> {
     }
     table.set("types", types_table)?;
 
+    if !item.accessories.is_empty() {
+        let accessories_table = lua.create_table()?;
+        for (i, accessory) in item.accessories.iter().enumerate() {
+            accessories_table.set(i + 1, accessory.as_str())?;
+        }
+        table.set("accessories", accessories_table)?;
+    }
+
+    if !item.match_aliases.is_empty() {
+        table.set("match_aliases", item.match_aliases.clone())?;
+    }
+
     if let Some(ref data) = item.data {
         table.set("data", json_to_lua_value(lua, data)?)?;
     }
 
+    if let Some(ref drag) = item.drag {
+        let drag_table = lua.create_table()?;
+        match drag {
+            DragPayload::File { path } => {
+                drag_table.set("type", "file")?;
+                drag_table.set("path", path.as_str())?;
+            }
+            DragPayload::Url { url } => {
+                drag_table.set("type", "url")?;
+                drag_table.set("url", url.as_str())?;
+            }
+            DragPayload::Text { text } => {
+                drag_table.set("type", "text")?;
+                drag_table.set("text", text.as_str())?;
+            }
+        }
+        table.set("drag", drag_table)?;
+    }
+
+    if let Some(ref display) = item.display {
+        let display_table = lua.create_table()?;
+        let subtitle = match display.subtitle {
+            SubtitlePosition::Trailing => "trailing",
+            SubtitlePosition::Below => "below",
+        };
+        let truncate = match display.truncate {
+            Truncate::End => "end",
+            Truncate::Middle => "middle",
+        };
+        display_table.set("subtitle", subtitle)?;
+        display_table.set("truncate", truncate)?;
+        display_table.set("lines", display.lines)?;
+        table.set("display", display_table)?;
+    }
+
     Ok(table)
 }
 
 /// Convert a slice of Items to a Lua table.
-fn items_to_lua(lua: &Lua, items: &[Item]) -> LuaResult<Table> {
+pub(crate) fn items_to_lua(lua: &Lua, items: &[Item]) -> LuaResult<Table> {
     let table = lua.create_table()?;
     for (i, item) in items.iter().enumerate() {
         table.set(i + 1, item_to_lua(lua, item)?)?;
@@ -674,6 +1195,28 @@ fn items_to_lua(lua: &Lua, items: &[Item]) -> LuaResult<Table> {
     Ok(table)
 }
 
+/// Convert a ParsedQuery to a Lua table: `{ terms, phrases, filters = { key = { values } } }`.
+pub(crate) fn parsed_query_to_lua(lua: &Lua, parsed: &ParsedQuery) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set(
+        "terms",
+        lua.create_sequence_from(parsed.terms.iter().cloned())?,
+    )?;
+    table.set(
+        "phrases",
+        lua.create_sequence_from(parsed.phrases.iter().cloned())?,
+    )?;
+    let filters = lua.create_table()?;
+    for (key, values) in &parsed.filters {
+        filters.set(
+            key.as_str(),
+            lua.create_sequence_from(values.iter().cloned())?,
+        )?;
+    }
+    table.set("filters", filters)?;
+    Ok(table)
+}
+
 /// Clean up registry keys for a view.
 ///
 /// Call this when popping a view to prevent memory leaks.
@@ -688,6 +1231,82 @@ pub fn cleanup_view_registry_keys(lua: &Lua, keys: &[String]) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_source_ctx_exposes_parsed_query() {
+        let lua = Lua::new();
+        let search: Function = lua
+            .load(
+                r#"
+            return function(query, ctx)
+                local parsed = ctx.parsed
+                ctx:set_items({
+                    { id = "1", title = parsed.terms[1] .. "|" .. parsed.phrases[1] },
+                    { id = "2", title = table.concat(parsed.filters["in"], ",") },
+                })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:search", search).unwrap();
+
+        let query = r#"report in:downloads "q3 budget" in:desktop"#;
+        let effects =
+            call_source_search(&lua, "test:search", query, &serde_json::Value::Null).unwrap();
+        let Some(Effect::SetGroups(groups)) = effects.into_iter().next() else {
+            panic!("expected groups");
+        };
+        assert_eq!(groups[0].items[0].title, "report|q3 budget");
+        assert_eq!(groups[0].items[1].title, "downloads,desktop");
+    }
+
+    #[test]
+    fn test_set_groups_names_the_malformed_item() {
+        let lua = Lua::new();
+        let search: Function = lua
+            .load(
+                r#"
+            return function(query, ctx)
+                local groups = {
+                    { title = "Files", items = {
+                        { title = "a.txt" },
+                        { title = "b.txt", types = "file" },
+                    } },
+                }
+                ctx:set_groups(groups, { skip_invalid = query == "skip" })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:search", search).unwrap();
+        let search = |query| {
+            call_hooked_search(
+                &lua,
+                "test:search",
+                &[],
+                query,
+                query,
+                &serde_json::Value::Null,
+                "files",
+            )
+        };
+
+        let err = search("fail").unwrap_err().to_string();
+        assert!(
+            err.contains("Invalid results from 'files': group 1, item 2: 'types' should be"),
+            "{}",
+            err
+        );
+
+        let effects = search("skip").unwrap();
+        let Some(Effect::SetGroups(groups)) = effects.into_iter().next() else {
+            panic!("expected groups");
+        };
+        assert_eq!(groups[0].items.len(), 1);
+        assert_eq!(groups[0].items[0].title, "a.txt");
+    }
+
     #[test]
     fn test_parse_view_spec() {
         let lua = Lua::new();
@@ -700,6 +1319,7 @@ mod tests {
                 placeholder = "Search...",
                 source = function(ctx) return {} end,
                 selection = "multi",
+                selected = { "a", "b" },
             }
         "#,
             )
@@ -710,6 +1330,7 @@ mod tests {
         assert_eq!(spec.title, Some("Test View".to_string()));
         assert_eq!(spec.placeholder, Some("Search...".to_string()));
         assert_eq!(spec.selection_mode, SelectionMode::Multi);
+        assert_eq!(spec.selected, vec!["a".to_string(), "b".to_string()]);
         // Registry keys should be tracked
         assert_eq!(spec.registry_keys.len(), 1); // Just source
     }
@@ -737,6 +1358,243 @@ mod tests {
         assert_eq!(spec.registry_keys.len(), 3);
     }
 
+    #[test]
+    fn test_parse_view_spec_with_lifecycle_callbacks() {
+        let lua = Lua::new();
+
+        let table = lua
+            .load(
+                r#"
+            return {
+                search = function(query, ctx) end,
+                on_open = function(ctx) end,
+                on_show = function(ctx) end,
+                on_close = function(ctx) end,
+            }
+        "#,
+            )
+            .eval::<Table>()
+            .unwrap();
+
+        let spec = parse_view_spec(&lua, table).unwrap();
+        assert!(spec.on_open_fn_key.is_some());
+        assert!(spec.on_show_fn_key.is_some());
+        assert!(spec.on_close_fn_key.is_some());
+        // Registry keys should track search + the three lifecycle callbacks
+        assert_eq!(spec.registry_keys.len(), 4);
+    }
+
+    #[test]
+    fn test_call_view_on_selection_changed() {
+        let lua = Lua::new();
+
+        // Keep at most two items selected
+        let func: mlua::Function = lua
+            .load(
+                r#"
+            return function(selection, ctx)
+                if #selection > 2 then
+                    for _, id in ipairs(selection) do
+                        if id ~= ctx.added[1] then
+                            ctx:deselect(id)
+                            break
+                        end
+                    end
+                end
+                ctx:set_view_data({ count = #ctx:get_selection() })
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:on_selection_changed", func)
+            .unwrap();
+
+        let selection = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let effects = call_view_on_selection_changed(
+            &lua,
+            "test:on_selection_changed",
+            &serde_json::Value::Null,
+            &selection,
+            &["c".to_string()],
+            &[],
+        )
+        .unwrap();
+
+        assert!(matches!(&effects[0], Effect::Deselect(ids) if ids == &["a"]));
+        assert!(
+            matches!(&effects[1], Effect::SetViewData(data) if data == &serde_json::json!({ "count": 2 }))
+        );
+    }
+
+    #[test]
+    fn test_call_action_run_with_error_table() {
+        let lua = Lua::new();
+        let func: mlua::Function = lua
+            .load(
+                r#"
+            return function(items, ctx)
+                if items[1].id == "raise" then
+                    error({ code = "timeout", message = "Server unreachable", retryable = true })
+                elseif items[1].id == "fail" then
+                    ctx:fail({ code = "denied", message = "Not allowed", detail = "HTTP 403" })
+                else
+                    error("plain failure")
+                end
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:upload", func).unwrap();
+        let run = |id: &str| {
+            call_action_run(
+                &lua,
+                "test:upload",
+                &[Item::new(id, id)],
+                &serde_json::Value::Null,
+            )
+        };
+
+        let effects = run("raise").unwrap();
+        let [Effect::Fail { error }] = effects.as_slice() else {
+            panic!("expected Fail, got {:?}", effects);
+        };
+        assert_eq!(error.code(), "timeout");
+        assert!(error.retryable);
+
+        let effects = run("fail").unwrap();
+        let [Effect::Fail { error }] = effects.as_slice() else {
+            panic!("expected Fail, got {:?}", effects);
+        };
+        assert_eq!(error.message, "Not allowed");
+        assert_eq!(error.detail.as_deref(), Some("HTTP 403"));
+        assert!(!error.retryable);
+
+        // Other errors are still errors
+        assert!(run("other")
+            .unwrap_err()
+            .to_string()
+            .contains("plain failure"));
+    }
+
+    #[test]
+    fn test_parse_view_spec_with_query() {
+        let lua = Lua::new();
+
+        let (inline, wrapped): (Table, Table) = lua
+            .load(
+                r#"
+            local view = { title = "Picker", search = function(query, ctx) end }
+            return
+                { search = function(query, ctx) end, query = "abc" },
+                { view, query = "def" }
+        "#,
+            )
+            .eval()
+            .unwrap();
+
+        let spec = parse_view_spec(&lua, inline).unwrap();
+        assert_eq!(spec.query, Some("abc".to_string()));
+
+        let spec = parse_view_spec(&lua, wrapped).unwrap();
+        assert_eq!(spec.title, Some("Picker".to_string()));
+        assert_eq!(spec.query, Some("def".to_string()));
+    }
+
+    #[test]
+    fn test_call_get_actions_flattens_sections() {
+        let lua = Lua::new();
+
+        let func: mlua::Function = lua
+            .load(
+                r#"
+            return function(item, ctx)
+                return {
+                    { id = "open", title = "Open", handler = function() end },
+                    {
+                        section = "Danger",
+                        items = {
+                            { id = "trash", title = "Trash", destructive = true, handler = function() end },
+                        },
+                    },
+                    { items = { { id = "info", title = "Info", handler = function() end } } },
+                }
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:get_actions", func)
+            .unwrap();
+
+        let item = Item::new("a.txt", "a.txt");
+        let actions =
+            call_get_actions(&lua, "test:get_actions", &item, &serde_json::Value::Null).unwrap();
+
+        let ids: Vec<_> = actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, ["open", "trash", "info"]);
+        assert_eq!(actions[0].section, None);
+        assert!(!actions[0].destructive);
+        assert_eq!(actions[1].section.as_deref(), Some("Danger"));
+        assert!(actions[1].destructive);
+        assert_eq!(actions[2].section.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_call_get_actions_with_output() {
+        let lua = Lua::new();
+
+        let func: mlua::Function = lua
+            .load(
+                r#"
+            return function(item, ctx)
+                if item.id == "bad" then return { { title = "Nothing" } } end
+                return { { id = "copy", title = "Copy", output = { copy = item.title } } }
+            end
+        "#,
+            )
+            .eval()
+            .unwrap();
+        lua.set_named_registry_value("test:get_actions", func)
+            .unwrap();
+
+        let item = Item::new("url", "https://example.com");
+        let actions =
+            call_get_actions(&lua, "test:get_actions", &item, &serde_json::Value::Null).unwrap();
+        assert_eq!(actions[0].id, "copy");
+        let handler: Value = lua.named_registry_value(&actions[0].handler_key).unwrap();
+        assert!(handler.is_function());
+
+        let bad = Item::new("bad", "bad");
+        let err =
+            call_get_actions(&lua, "test:get_actions", &bad, &serde_json::Value::Null).unwrap_err();
+        assert!(err.to_string().contains("'output' table"));
+    }
+
+    #[test]
+    fn test_parse_view_spec_with_get_actions() {
+        let lua = Lua::new();
+
+        let table = lua
+            .load(
+                r#"
+            return {
+                id = "picker",
+                search = function(query, ctx) end,
+                get_actions = function(item, ctx) return {} end,
+            }
+        "#,
+            )
+            .eval::<Table>()
+            .unwrap();
+
+        let spec = parse_view_spec(&lua, table).unwrap();
+        assert_eq!(spec.id, Some("picker".to_string()));
+        assert!(spec.get_actions_fn_key.is_some());
+        assert_eq!(spec.registry_keys.len(), 2);
+    }
+
     #[test]
     fn test_parse_items() {
         let lua = Lua::new();
@@ -753,7 +1611,7 @@ mod tests {
             .eval::<Table>()
             .unwrap();
 
-        let items = parse_items(&lua, table).unwrap();
+        let items = parse_result_items(table, None, None).unwrap();
         assert_eq!(items.len(), 2);
         assert_eq!(items[0].id, "1");
         assert_eq!(items[0].title, "Item 1");
@@ -761,6 +1619,97 @@ mod tests {
         assert_eq!(items[1].subtitle, Some("Sub".to_string()));
     }
 
+    #[test]
+    fn test_parse_items_with_drag() {
+        let lua = Lua::new();
+
+        let table = lua
+            .load(
+                r#"
+            return {
+                { title = "Report", drag = { type = "file", path = "/tmp/report.pdf" } },
+                { title = "Docs", drag = { type = "url", url = "https://example.com" } },
+                { title = "Plain" },
+            }
+        "#,
+            )
+            .eval::<Table>()
+            .unwrap();
+
+        let items = parse_result_items(table, None, None).unwrap();
+        assert_eq!(
+            items[0].drag,
+            Some(DragPayload::File {
+                path: "/tmp/report.pdf".to_string()
+            })
+        );
+        assert_eq!(
+            items[1].drag,
+            Some(DragPayload::Url {
+                url: "https://example.com".to_string()
+            })
+        );
+        assert_eq!(items[2].drag, None);
+
+        // Round-trips through Lua
+        let back = item_to_lua(&lua, &items[0]).unwrap();
+        let drag: Table = back.get("drag").unwrap();
+        assert_eq!(drag.get::<String>("type").unwrap(), "file");
+        assert_eq!(drag.get::<String>("path").unwrap(), "/tmp/report.pdf");
+    }
+
+    #[test]
+    fn test_parse_items_with_accessories() {
+        let lua = Lua::new();
+
+        let table = lua
+            .load(r#"return { { title = "prod", accessories = { "deploy", ":2222" } }, { title = "Plain" } }"#)
+            .eval::<Table>()
+            .unwrap();
+
+        let items = parse_result_items(table, None, None).unwrap();
+        assert_eq!(items[0].accessories, ["deploy", ":2222"]);
+        assert!(items[1].accessories.is_empty());
+
+        // Round-trips through Lua
+        let back = item_to_lua(&lua, &items[0]).unwrap();
+        let accessories: Vec<String> = back.get("accessories").unwrap();
+        assert_eq!(accessories, ["deploy", ":2222"]);
+    }
+
+    #[test]
+    fn test_parse_items_with_display() {
+        let lua = Lua::new();
+
+        let table = lua
+            .load(r#"return { { title = "report.pdf", subtitle = "~/Documents", display = { subtitle = "below", truncate = "middle" } } }"#)
+            .eval::<Table>()
+            .unwrap();
+
+        let items = parse_result_items(table, None, None).unwrap();
+        assert_eq!(items[0].row_lines(), 2);
+
+        // Round-trips through Lua
+        let back = item_to_lua(&lua, &items[0]).unwrap();
+        let display: Table = back.get("display").unwrap();
+        assert_eq!(display.get::<String>("subtitle").unwrap(), "below");
+        assert_eq!(display.get::<String>("truncate").unwrap(), "middle");
+        assert_eq!(display.get::<u8>("lines").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_parse_item_drag_missing_path() {
+        let lua = Lua::new();
+
+        let table = lua
+            .load(r#"return { { title = "Bad", drag = { type = "file" } } }"#)
+            .eval::<Table>()
+            .unwrap();
+
+        let err = parse_result_items(table, None, None).unwrap_err();
+        assert!(err.to_string().contains("path"));
+    }
+
     #[test]
     fn test_parse_view_spec_missing_search() {
         let lua = Lua::new();
@@ -0,0 +1,813 @@
+//! Lua bindings for built-in views.
+//!
+//! This module provides:
+//! - `lux.builtin.*()` - Views built into Lux
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use lux_core::Item;
+use mlua::{AnyUserData, Function, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::activity::{activity_entry_id, activity_groups, ActivityLog, ACTIVITY_VIEW_ID};
+use crate::autostart::{preference_groups, Autostart, AUTOSTART_ITEM_ID, PREFERENCES_VIEW_ID};
+use crate::backup::{backup_groups, Backup, BACKUP_VIEW_ID, CREATE_ITEM_ID};
+use crate::desktop;
+use crate::doctor::{diagnose, DOCTOR_VIEW_ID};
+use crate::finder::item_path;
+use crate::hidden::{hidden_actions, hidden_groups, run_hidden_action, Blocklist, HIDDEN_VIEW_ID};
+use crate::http::{HttpRequest, BUILDER_VIEW_ID};
+use crate::menus::{
+    list_menu_items, menu_groups, press_menu_item, MenuCache, MenuTarget, MENUS_VIEW_ID,
+};
+use crate::profiles::ProfileRegistry;
+use crate::registry::PluginRegistry;
+use crate::tabs::{
+    close_tab, focus_tab, matches_text, tab_item, DevToolsEndpoint, Tab, TabCache, TabFilter,
+    TabSources, TABS_VIEW_ID,
+};
+use crate::theme::{DisplayPreferences, ThemeDensity, ThemeStore, COMPACT_ITEM_ID, SCALE_ITEM_ID};
+use crate::views::ViewRegistry;
+use crate::warnings::{warning_groups, ConfigWarnings, CONFIG_WARNINGS_VIEW_ID};
+
+use super::daemons::daemons_view;
+use super::http::http_builder_view;
+use super::keymap::{commands_view, keybindings_view, keymap_conflicts_view};
+use super::macros::macros_view;
+use super::metrics::stats_view;
+use super::text::{generator_view, transform_view};
+use super::{bridge, groups_to_lua};
+
+/// Register `lux.builtin`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.builtin - Views built into Lux
+    //
+    // Usage:
+    //   lux.views.add(lux.builtin.tabs())                          -- id "builtin.tabs"
+    //   ctx:push(lux.builtin.tabs({ browser = "safari", window = 1 }))
+    //   lux.builtin.tabs({
+    //     id = "work-tabs",
+    //     profile = "Work",
+    //     devtools = { { port = 9222, profile = "Work" }, { port = 9223, profile = "Personal" } },
+    //   })
+    //
+    // Typing `browser:`, `window:`, or `profile:` in the view narrows it further.
+    //
+    //   lux.views.add(lux.builtin.transform())                     -- id "builtin.transform"
+    //   lux.builtin.transform({ source = "clipboard" })            -- "auto", "selection", "clipboard"
+    //
+    //   lux.views.add(lux.builtin.menus())                         -- id "builtin.menus"
+    //
+    // The menu bar view searches the menu commands of the app the launcher opened over.
+    //
+    //   lux.views.add(lux.builtin.generator())                     -- id "builtin.generator"
+    //   lux.builtin.generator({ length = 32, symbols = false, paste = true })
+    //
+    // Typing a number in the generator view sets the length (`pin 4`, `lorem 100`).
+    //
+    //   lux.views.add(lux.builtin.http())                          -- id "builtin.http"
+    //   ctx:push(lux.builtin.http({ method = "POST", url = "https://api.example.com/items" }))
+    //
+    // In the request builder, `Name: value` adds a header and other text sets the body.
+    //
+    //   lux.views.add(lux.builtin.hidden())                        -- id "builtin.hidden"
+    //
+    // The hidden view un-hides results and turns built-in sources and plugin views on or off.
+    //
+    //   lux.views.add(lux.builtin.stats())                         -- id "builtin.stats"
+    //
+    // The stats view shows what lux.metrics recorded, once enabled.
+    //
+    //   lux.views.add(lux.builtin.preferences())                   -- id "builtin.preferences"
+    //
+    // The preferences view turns settings like "Open at Login" and "Compact Mode" on and off,
+    // and sets the interface scale.
+    //
+    //   lux.views.add(lux.builtin.doctor())                        -- id "builtin.doctor"
+    //
+    // The doctor view shows the `lux doctor` report: config, plugins, hotkeys, permissions, indexes.
+    //
+    //   lux.views.add(lux.builtin.config_warnings())               -- id "builtin.config_warnings"
+    //
+    // The config warnings view lists unknown view fields and deprecated APIs used in init.lua.
+    //
+    //   lux.views.add(lux.builtin.activity())                      -- id "builtin.activity"
+    //
+    // The activity view lists the latest executed actions, and runs them again.
+    //
+    //   lux.views.add(lux.builtin.backup())                        -- id "builtin.backup"
+    //
+    // The backup view creates backups of the config and data folders, and restores them
+    // (restarting Lux, which puts the backup in place before its stores load).
+    //
+    //   lux.views.add(lux.builtin.keybindings())                   -- id "builtin.keybindings"
+    //
+    // The keybindings view is a cheatsheet of every binding from lux.keymap.list().
+    //
+    //   lux.views.add(lux.builtin.keymap_conflicts())              -- id "builtin.keymap_conflicts"
+    //
+    // The keymap conflicts view lists bindings that replaced a default or another
+    // plugin's binding, and hotkeys the OS uses. Enter keeps the new binding,
+    // cmd+backspace the previous one (from the next start).
+    //
+    //   lux.views.add(lux.builtin.macros())                        -- id "builtin.macros"
+    //
+    // The macros view starts and stops recording (type a name first to record under it),
+    // and plays, opens, renames, and deletes the saved macros.
+    //
+    //   lux.views.add(lux.builtin.commands())                      -- id "builtin.commands"
+    //
+    // The commands view lists Lux's own actions (reload config, toggle theme, open logs,
+    // clear caches, ...) with the keys bound to them, and runs them without a keybinding.
+    //
+    //   lux.views.add(lux.builtin.daemons())                       -- id "builtin.daemons"
+    //
+    // The daemons view lists the processes started with lux.daemon.start(), with their
+    // state and last output line, and starts, stops, or restarts them, or opens their log.
+    {
+        let builtin_table = lua.create_table()?;
+
+        let tabs_fn = lua.create_function(|lua, opts: Option<Table>| tabs_view(lua, opts))?;
+        builtin_table.set("tabs", tabs_fn)?;
+
+        let profiles = registry.profiles();
+        let transform_fn = lua.create_function(move |lua, opts: Option<Table>| {
+            transform_view(lua, profiles.clone(), opts)
+        })?;
+        builtin_table.set("transform", transform_fn)?;
+
+        let profiles = registry.profiles();
+        let menus_fn = lua.create_function(move |lua, opts: Option<Table>| {
+            menus_view(lua, profiles.clone(), opts)
+        })?;
+        builtin_table.set("menus", menus_fn)?;
+
+        let generator_fn =
+            lua.create_function(|lua, opts: Option<Table>| generator_view(lua, opts))?;
+        builtin_table.set("generator", generator_fn)?;
+
+        let hidden_registry = Arc::clone(registry);
+        let hidden_fn = lua.create_function(move |lua, ()| {
+            hidden_view(lua, hidden_registry.blocklist(), hidden_registry.views())
+        })?;
+        builtin_table.set("hidden", hidden_fn)?;
+
+        let metrics = registry.metrics();
+        let stats_fn = lua.create_function(move |lua, ()| stats_view(lua, metrics.clone()))?;
+        builtin_table.set("stats", stats_fn)?;
+
+        let theme = registry.theme();
+        let preferences_fn =
+            lua.create_function(move |lua, ()| preferences_view(lua, theme.clone()))?;
+        builtin_table.set("preferences", preferences_fn)?;
+
+        let doctor_registry = Arc::clone(registry);
+        let doctor_fn =
+            lua.create_function(move |lua, ()| doctor_view(lua, doctor_registry.clone()))?;
+        builtin_table.set("doctor", doctor_fn)?;
+
+        let config_warnings = registry.config_warnings();
+        let config_warnings_fn =
+            lua.create_function(move |lua, ()| config_warnings_view(lua, config_warnings.clone()))?;
+        builtin_table.set("config_warnings", config_warnings_fn)?;
+
+        let activity = registry.activity();
+        let activity_fn =
+            lua.create_function(move |lua, ()| activity_view(lua, activity.clone()))?;
+        builtin_table.set("activity", activity_fn)?;
+
+        let backup_fn = lua.create_function(|lua, ()| backup_view(lua))?;
+        builtin_table.set("backup", backup_fn)?;
+
+        let keymap = registry.keymap();
+        let keybindings_fn =
+            lua.create_function(move |lua, ()| keybindings_view(lua, keymap.clone()))?;
+        builtin_table.set("keybindings", keybindings_fn)?;
+
+        let conflicts = registry.keymap().conflicts();
+        let keymap_conflicts_fn =
+            lua.create_function(move |lua, ()| keymap_conflicts_view(lua, conflicts.clone()))?;
+        builtin_table.set("keymap_conflicts", keymap_conflicts_fn)?;
+
+        let macros = registry.macros();
+        let macros_fn = lua.create_function(move |lua, ()| macros_view(lua, macros.clone()))?;
+        builtin_table.set("macros", macros_fn)?;
+
+        let keymap = registry.keymap();
+        let commands_fn = lua.create_function(move |lua, ()| commands_view(lua, keymap.clone()))?;
+        builtin_table.set("commands", commands_fn)?;
+
+        let (daemons, editor) = (registry.daemons(), registry.editor());
+        let daemons_fn =
+            lua.create_function(move |lua, ()| daemons_view(lua, daemons.clone(), editor.clone()))?;
+        builtin_table.set("daemons", daemons_fn)?;
+
+        let client = registry.http();
+        let http_fn = lua.create_function(move |lua, opts: Option<Table>| {
+            let mut request = HttpRequest::new("GET", "");
+            let mut id = BUILDER_VIEW_ID.to_string();
+            if let Some(opts) = opts {
+                if let Some(method) = opts.get::<Option<String>>("method")? {
+                    request.method = method.to_uppercase();
+                }
+                request.url = opts.get::<Option<String>>("url")?.unwrap_or_default();
+                id = opts.get::<Option<String>>("id")?.unwrap_or(id);
+            }
+            let view = http_builder_view(lua, client.clone(), request)?;
+            view.set("id", id)?;
+            Ok(view)
+        })?;
+        builtin_table.set("http", http_fn)?;
+
+        lux.set("builtin", builtin_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the browser tab switcher view.
+///
+/// `opts` can override the view's `id` and `title`, set the `browsers` read
+/// by script, add `devtools` endpoints, and filter by `browser`, `window`,
+/// and `profile`.
+fn tabs_view(lua: &Lua, opts: Option<Table>) -> LuaResult<Table> {
+    let opts = match opts {
+        Some(opts) => opts,
+        None => lua.create_table()?,
+    };
+
+    let mut sources = TabSources::default();
+    if let Some(browsers) = opts.get::<Option<Vec<String>>>("browsers")? {
+        sources.browsers = browsers;
+    }
+    if let Some(endpoints) = opts.get::<Option<Vec<Table>>>("devtools")? {
+        for endpoint in endpoints {
+            let port: u16 = endpoint.get::<Option<u16>>("port")?.ok_or_else(|| {
+                mlua::Error::RuntimeError(
+                    "builtin.tabs: devtools entry requires 'port'".to_string(),
+                )
+            })?;
+            let profile = endpoint
+                .get::<Option<String>>("profile")?
+                .unwrap_or_else(|| port.to_string());
+            let mut endpoint_def = DevToolsEndpoint::new(port, profile);
+            if let Some(browser) = endpoint.get::<Option<String>>("browser")? {
+                endpoint_def = endpoint_def.with_browser(browser);
+            }
+            sources.devtools.push(endpoint_def);
+        }
+    }
+    let filter = TabFilter {
+        browser: opts.get("browser")?,
+        window: opts.get("window")?,
+        profile: opts.get("profile")?,
+    };
+
+    let view = lua.create_table()?;
+    view.set(
+        "id",
+        opts.get::<Option<String>>("id")?
+            .unwrap_or_else(|| TABS_VIEW_ID.to_string()),
+    )?;
+    view.set(
+        "title",
+        opts.get::<Option<String>>("title")?
+            .unwrap_or_else(|| t("Browser Tabs")),
+    )?;
+    view.set("placeholder", t("Search tabs..."))?;
+
+    let cache = Arc::new(TabCache::default());
+
+    // search(query, ctx) - filter by title and URL, grouped by window or profile
+    let search_cache = cache.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let (filter, text) = filter.with_query(&query);
+        let tabs = search_cache.get_with(|| sources.list())?;
+
+        let mut groups: Vec<(String, Vec<Item>)> = Vec::new();
+        for tab in tabs
+            .iter()
+            .filter(|tab| filter.matches(tab) && matches_text(tab, &text))
+        {
+            let title = tab.group_title();
+            match groups.iter_mut().find(|(t, _)| *t == title) {
+                Some((_, items)) => items.push(tab_item(tab)),
+                None => groups.push((title, vec![tab_item(tab)])),
+            }
+        }
+
+        let result = lua.create_table()?;
+        for (title, items) in &groups {
+            let group = lua.create_table()?;
+            group.set("title", title.as_str())?;
+            let group_items = lua.create_table()?;
+            for item in items {
+                group_items.push(bridge::item_to_lua(lua, item)?)?;
+            }
+            group.set("items", group_items)?;
+            result.push(group)?;
+        }
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - switch to, close, or copy the tab
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let Some(tab) = Tab::from_item(&item) else {
+            return lua.create_table();
+        };
+        let actions = lua.create_table()?;
+
+        let add = |id: &str, title: String, handler: mlua::Function| {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            action.set("handler", handler)?;
+            actions.push(action)
+        };
+
+        let focus = tab.clone();
+        add(
+            "focus",
+            t("Switch to Tab"),
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                focus_tab(&focus)?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+
+        // Closing keeps the view open; it re-searches without the tab
+        let (close, close_cache) = (tab.clone(), cache.clone());
+        add(
+            "close",
+            t("Close Tab"),
+            lua.create_function(move |_lua, (_items, _ctx): (Table, AnyUserData)| {
+                close_tab(&close)?;
+                close_cache.invalidate();
+                Ok(())
+            })?,
+        )?;
+
+        add(
+            "copy_url",
+            t("Copy URL"),
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().clipboard_write(&tab.url)?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the menu bar search view.
+///
+/// Lists the menu commands of the app the launcher opened over (see
+/// [`crate::menus`]); choosing one brings the app back and runs it.
+fn menus_view(lua: &Lua, profiles: Arc<ProfileRegistry>, opts: Option<Table>) -> LuaResult<Table> {
+    let opts = match opts {
+        Some(opts) => opts,
+        None => lua.create_table()?,
+    };
+
+    let view = lua.create_table()?;
+    view.set(
+        "id",
+        opts.get::<Option<String>>("id")?
+            .unwrap_or_else(|| MENUS_VIEW_ID.to_string()),
+    )?;
+    view.set(
+        "title",
+        opts.get::<Option<String>>("title")?
+            .unwrap_or_else(|| t("Menu Bar")),
+    )?;
+    view.set("placeholder", t("Search menu commands..."))?;
+
+    let cache = Arc::new(MenuCache::default());
+
+    // search(query, ctx) - the frontmost app's commands, best matches first
+    let search_cache = cache.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let context = profiles.last_context();
+        let Some(bundle_id) = context.bundle_id.as_deref() else {
+            return ctx.call_method::<()>("set_groups", lua.create_table()?);
+        };
+        let items = search_cache.get_with(bundle_id, || list_menu_items(bundle_id))?;
+        let groups = menu_groups(bundle_id, context.app_name.as_deref(), &items, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - run the command in its app
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        let Some(target) = MenuTarget::from_item(&item) else {
+            return Ok(actions);
+        };
+
+        let press_cache = cache.clone();
+        let action = lua.create_table()?;
+        action.set("id", "press")?;
+        action.set("title", t("Run Command"))?;
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                press_menu_item(&target)?;
+                press_cache.invalidate(&target.bundle_id);
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the "Hidden Results" view: hidden results, built-in sources, and
+/// plugin views, each with an action to show or turn it on again (or off).
+fn hidden_view(lua: &Lua, blocklist: Arc<Blocklist>, views: Arc<ViewRegistry>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", HIDDEN_VIEW_ID)?;
+    view.set("title", t("Hidden Results"))?;
+    view.set(
+        "placeholder",
+        t("Filter hidden results, sources, and plugins..."),
+    )?;
+
+    // search(query, ctx) - everything that can be hidden or turned off
+    let search_blocklist = blocklist.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        // This view can't turn itself off
+        let view_ids: Vec<String> = views
+            .list()
+            .into_iter()
+            .filter(|id| id != HIDDEN_VIEW_ID)
+            .collect();
+        let groups = hidden_groups(&search_blocklist, &view_ids, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - un-hide, or turn on or off
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        for (id, title) in hidden_actions(&item, &blocklist) {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            let (blocklist, item) = (blocklist.clone(), item.clone());
+            action.set(
+                "handler",
+                lua.create_function(move |_lua, (_items, _ctx): (Table, AnyUserData)| {
+                    run_hidden_action(id, &item, &blocklist)?;
+                    Ok(())
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the "Preferences" view: settings with an action to turn each on or off.
+///
+/// Compact mode and the interface scale are saved with the theme and apply
+/// the next time the launcher opens.
+fn preferences_view(lua: &Lua, theme: Arc<ThemeStore>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", PREFERENCES_VIEW_ID)?;
+    view.set("title", t("Preferences"))?;
+    view.set("placeholder", t("Filter preferences..."))?;
+
+    // search(query, ctx) - every setting
+    let search_theme = Arc::clone(&theme);
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = preference_groups(
+            Autostart::current().as_ref(),
+            &search_theme.preferences(),
+            &query,
+        );
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - turn the setting on or off, or step the scale
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        let id: String = item.get("id")?;
+        let display = theme.preferences();
+
+        // (action id, title, new display preferences)
+        let choices = match id.as_str() {
+            AUTOSTART_ITEM_ID => {
+                let Some(autostart) = Autostart::current() else {
+                    return Ok(actions);
+                };
+                let enabled = autostart.status().enabled;
+                let action = lua.create_table()?;
+                action.set("id", "toggle")?;
+                action.set("title", if enabled { t("Turn Off") } else { t("Turn On") })?;
+                let theme = Arc::clone(&theme);
+                action.set(
+                    "handler",
+                    lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                        if enabled {
+                            autostart.disable()?;
+                        } else {
+                            autostart.enable()?;
+                        }
+                        let groups = preference_groups(Some(&autostart), &theme.preferences(), "");
+                        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+                    })?,
+                )?;
+                actions.push(action)?;
+                return Ok(actions);
+            }
+            COMPACT_ITEM_ID => {
+                let compact = display.density == Some(ThemeDensity::Compact);
+                let density = (!compact).then_some(ThemeDensity::Compact);
+                let title = if compact { t("Turn Off") } else { t("Turn On") };
+                vec![("toggle", title, DisplayPreferences { density, ..display })]
+            }
+            SCALE_ITEM_ID => vec![
+                (
+                    "larger",
+                    t("Larger"),
+                    DisplayPreferences {
+                        scale: Some(display.step_scale(true)),
+                        ..display
+                    },
+                ),
+                (
+                    "smaller",
+                    t("Smaller"),
+                    DisplayPreferences {
+                        scale: Some(display.step_scale(false)),
+                        ..display
+                    },
+                ),
+                (
+                    "reset",
+                    t("Reset to 100%"),
+                    DisplayPreferences {
+                        scale: None,
+                        ..display
+                    },
+                ),
+            ],
+            _ => return Ok(actions),
+        };
+
+        for (action_id, title, preferences) in choices {
+            let action = lua.create_table()?;
+            action.set("id", action_id)?;
+            action.set("title", title)?;
+            let theme = Arc::clone(&theme);
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                    theme
+                        .set_preferences(preferences)
+                        .map_err(|e| mlua::Error::RuntimeError(format!("preferences: {}", e)))?;
+                    let groups = preference_groups(Autostart::current().as_ref(), &preferences, "");
+                    ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the "Doctor" view: the `lux doctor` report, with an action to copy it.
+fn doctor_view(lua: &Lua, registry: Arc<PluginRegistry>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", DOCTOR_VIEW_ID)?;
+    view.set("title", t("Doctor"))?;
+    view.set("placeholder", t("Filter checks..."))?;
+
+    // search(query, ctx) - every check, run again each time
+    let search_registry = registry.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = diagnose(&search_registry).groups(&query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy the whole report
+    let get_actions_fn = lua.create_function(move |lua, (_item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        let action = lua.create_table()?;
+        action.set("id", "copy")?;
+        action.set("title", t("Copy Report"))?;
+        let registry = registry.clone();
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().clipboard_write(&diagnose(&registry).render())?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the activity view returned by `lux.builtin.activity()`.
+fn activity_view(lua: &Lua, log: Arc<ActivityLog>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", ACTIVITY_VIEW_ID)?;
+    view.set("title", t("Activity"))?;
+    view.set("placeholder", t("Filter activity..."))?;
+
+    // search(query, ctx) - the latest entries, newest first
+    let search_log = log.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let now = chrono::Utc::now().timestamp();
+        let groups = activity_groups(&search_log.entries(), &query, now);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - run the entry again (while its handler is
+    // loaded), or clear the log
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        let id: String = item.get("id")?;
+        let entry = activity_entry_id(&id).and_then(|id| log.get(id));
+        if let Some(entry) = entry {
+            if let Ok(handler) = lua.named_registry_value::<Function>(&entry.handler_key) {
+                let action = lua.create_table()?;
+                action.set("id", "run_again")?;
+                action.set("title", t("Run Again"))?;
+                action.set("destructive", entry.destructive)?;
+                let items = entry.items;
+                action.set(
+                    "handler",
+                    lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                        let items = bridge::items_to_lua(lua, &items)?;
+                        handler.call::<()>((items, ctx))
+                    })?,
+                )?;
+                actions.push(action)?;
+            }
+        }
+
+        let action = lua.create_table()?;
+        action.set("id", "clear")?;
+        action.set("title", t("Clear Activity"))?;
+        action.set("destructive", true)?;
+        let log = log.clone();
+        action.set(
+            "handler",
+            lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                log.clear();
+                let groups = activity_groups(&[], "", 0);
+                ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// The backup folders of this user, or an error if there are none.
+fn backup() -> LuaResult<Backup> {
+    Backup::current()
+        .ok_or_else(|| mlua::Error::RuntimeError("backup: no config or data directory".to_string()))
+}
+
+/// Build the backup view returned by `lux.builtin.backup()`.
+fn backup_view(lua: &Lua) -> LuaResult<Table> {
+    let backup = backup()?;
+    let view = lua.create_table()?;
+    view.set("id", BACKUP_VIEW_ID)?;
+    view.set("title", t("Backup"))?;
+    view.set("placeholder", t("Filter backups..."))?;
+
+    // search(query, ctx) - "Create Backup", then the backups, newest first
+    let search_backup = backup.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let now = chrono::Utc::now().timestamp();
+        let groups = backup_groups(&search_backup, &query, now);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - create a backup, or restore or reveal one
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        if item.id == CREATE_ITEM_ID {
+            let action = lua.create_table()?;
+            action.set("id", "create")?;
+            action.set("title", t("Create Backup"))?;
+            let backup = backup.clone();
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                    backup.create(None)?;
+                    let now = chrono::Utc::now().timestamp();
+                    let groups = backup_groups(&backup, "", now);
+                    ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+                })?,
+            )?;
+            actions.push(action)?;
+            return Ok(actions);
+        }
+        let Some(path) = item_path(&item).map(PathBuf::from) else {
+            return Ok(actions);
+        };
+
+        let action = lua.create_table()?;
+        action.set("id", "restore")?;
+        action.set("title", t("Restore"))?;
+        action.set("destructive", true)?;
+        let (backup, archive) = (backup.clone(), path.clone());
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                // Applied as Lux starts again, after the running stores are saved
+                backup.restore(&archive)?;
+                ctx.call_method::<()>("run_command", "reload_config")
+            })?,
+        )?;
+        actions.push(action)?;
+
+        let action = lua.create_table()?;
+        action.set("id", "reveal")?;
+        action.set("title", t("Reveal in Finder"))?;
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().reveal(&path.to_string_lossy())?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the config warnings view returned by `lux.builtin.config_warnings()`.
+fn config_warnings_view(lua: &Lua, warnings: Arc<ConfigWarnings>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", CONFIG_WARNINGS_VIEW_ID)?;
+    view.set("title", t("Config Warnings"))?;
+    view.set("placeholder", t("Filter warnings..."))?;
+
+    // search(query, ctx) - every warning recorded so far
+    let search_warnings = warnings.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = warning_groups(&search_warnings.list(), &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy every warning, one per line
+    let get_actions_fn = lua.create_function(move |lua, (_item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        let action = lua.create_table()?;
+        action.set("id", "copy")?;
+        action.set("title", t("Copy Warnings"))?;
+        let warnings = warnings.clone();
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                let text: Vec<String> = warnings.list().iter().map(|w| w.to_string()).collect();
+                desktop::current().clipboard_write(&text.join("\n"))?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
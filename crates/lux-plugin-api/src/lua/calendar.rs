@@ -0,0 +1,263 @@
+//! Lua bindings for calendars and reminders.
+//!
+//! This module provides:
+//! - `lux.calendar/reminders` - EventKit sources
+
+use lux_core::i18n::t;
+use lux_core::Item;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::calendar::{CalendarEvent, Reminder};
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::permissions::Permission;
+
+use super::bridge;
+use super::permissions::permission_item;
+
+/// Register `lux.calendar` and `lux.reminders`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.calendar / lux.reminders - EventKit data
+    //
+    // Usage:
+    //   lux.calendar.events({ days = 7 })   -- upcoming events
+    //   ctx:push(lux.calendar.view())       -- agenda view with actions
+    //   lux.reminders.list()                -- incomplete reminders
+    //   lux.reminders.complete(id)
+    {
+        let calendar_table = lua.create_table()?;
+
+        let events_fn = lua.create_function(|lua, opts: Option<Table>| {
+            let days = calendar_days(opts.as_ref())?;
+            let events = crate::calendar::upcoming_events(days)?;
+            let table = lua.create_table()?;
+            for (i, event) in events.iter().enumerate() {
+                table.set(i + 1, calendar_event_to_lua(lua, event)?)?;
+            }
+            Ok(table)
+        })?;
+        calendar_table.set("events", events_fn)?;
+
+        let view_fn = lua.create_function(|lua, opts: Option<Table>| {
+            let days = calendar_days(opts.as_ref())?;
+            calendar_view(lua, days)
+        })?;
+        calendar_table.set("view", view_fn)?;
+
+        lux.set("calendar", calendar_table)?;
+
+        let reminders_table = lua.create_table()?;
+
+        let list_fn = lua.create_function(|lua, ()| {
+            let reminders = crate::calendar::incomplete_reminders()?;
+            let table = lua.create_table()?;
+            for (i, reminder) in reminders.iter().enumerate() {
+                table.set(i + 1, reminder_to_lua(lua, reminder)?)?;
+            }
+            Ok(table)
+        })?;
+        reminders_table.set("list", list_fn)?;
+
+        let complete_fn = lua.create_function(|_lua, id: String| {
+            crate::calendar::complete_reminder(&id).map_err(mlua::Error::from)
+        })?;
+        reminders_table.set("complete", complete_fn)?;
+
+        lux.set("reminders", reminders_table)?;
+    }
+
+    Ok(())
+}
+
+/// Read the `days` option for calendar queries (default 7).
+fn calendar_days(opts: Option<&Table>) -> LuaResult<f64> {
+    let days = match opts {
+        Some(opts) => opts.get::<Option<f64>>("days")?.unwrap_or(7.0),
+        None => 7.0,
+    };
+    if days <= 0.0 {
+        return Err(mlua::Error::RuntimeError(
+            "calendar: 'days' must be positive".to_string(),
+        ));
+    }
+    Ok(days)
+}
+
+/// Convert a CalendarEvent to a Lua table.
+fn calendar_event_to_lua(lua: &Lua, event: &CalendarEvent) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("id", event.id.as_str())?;
+    table.set("title", event.title.as_str())?;
+    table.set("start", event.start)?;
+    table.set("end", event.end)?;
+    table.set("start_label", event.start_label.as_str())?;
+    table.set("all_day", event.all_day)?;
+    table.set("calendar", event.calendar.as_str())?;
+    table.set("location", event.location.as_deref())?;
+    table.set("url", event.url.as_deref())?;
+    table.set("notes", event.notes.as_deref())?;
+    table.set("meeting_link", crate::calendar::meeting_link(event))?;
+    Ok(table)
+}
+
+/// Convert a Reminder to a Lua table.
+fn reminder_to_lua(lua: &Lua, reminder: &Reminder) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("id", reminder.id.as_str())?;
+    table.set("title", reminder.title.as_str())?;
+    table.set("list", reminder.list.as_str())?;
+    table.set("due_label", reminder.due_label.as_deref())?;
+    Ok(table)
+}
+
+/// Build the agenda view returned by `lux.calendar.view()`.
+///
+/// Events and reminders are loaded once when the view is built. A source
+/// whose permission is not granted shows a single "Allow access" item
+/// instead, so the user can grant it from the launcher.
+fn calendar_view(lua: &Lua, days: f64) -> LuaResult<Table> {
+    let mut groups: Vec<(String, Vec<Item>)> = Vec::new();
+
+    let events = match crate::calendar::upcoming_events(days) {
+        Ok(events) => events.iter().map(calendar_event_item).collect(),
+        Err(PluginError::PermissionDenied(_)) => vec![permission_item(Permission::Calendar)],
+        Err(e) => return Err(e.into()),
+    };
+    groups.push((t("Upcoming"), events));
+
+    let reminders = match crate::calendar::incomplete_reminders() {
+        Ok(reminders) => reminders.iter().map(reminder_item).collect(),
+        Err(PluginError::PermissionDenied(_)) => vec![permission_item(Permission::Reminders)],
+        Err(e) => return Err(e.into()),
+    };
+    groups.push((t("Reminders"), reminders));
+
+    let view = lua.create_table()?;
+    view.set("id", "calendar")?;
+    view.set("title", t("Calendar"))?;
+    view.set("placeholder", t("Search events and reminders..."))?;
+
+    // search(query, ctx) - filter by title
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let query = query.to_lowercase();
+        let result = lua.create_table()?;
+        for (title, items) in &groups {
+            let matches: Vec<&Item> = items
+                .iter()
+                .filter(|item| item.title.to_lowercase().contains(&query))
+                .collect();
+            if matches.is_empty() {
+                continue;
+            }
+            let group = lua.create_table()?;
+            group.set("title", title.as_str())?;
+            let group_items = lua.create_table()?;
+            for (i, item) in matches.into_iter().enumerate() {
+                group_items.set(i + 1, bridge::item_to_lua(lua, item)?)?;
+            }
+            group.set("items", group_items)?;
+            result.push(group)?;
+        }
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - per-type event/reminder actions
+    let get_actions_fn = lua.create_function(|lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let data = item.data.clone().unwrap_or_default();
+        let field = |key: &str| data[key].as_str().unwrap_or_default().to_string();
+        let actions = lua.create_table()?;
+
+        let add = |id: &str, title: &str, run: Box<dyn Fn() -> PluginResult<()> + Send>| {
+            let handler =
+                lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                    run()?;
+                    ctx.call_method::<()>("dismiss", ())
+                })?;
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            action.set("handler", handler)?;
+            actions.push(action)
+        };
+
+        if item.has_type("calendar_event") {
+            let id = field("id");
+            add(
+                "open",
+                &t("Open in Calendar"),
+                Box::new(move || crate::calendar::open_event(&id)),
+            )?;
+            let link = field("meeting_link");
+            if !link.is_empty() {
+                let join = link.clone();
+                add(
+                    "join",
+                    &t("Join Meeting"),
+                    Box::new(move || desktop::current().open(&join)),
+                )?;
+                add(
+                    "copy_link",
+                    &t("Copy Meeting Link"),
+                    Box::new(move || desktop::current().clipboard_write(&link)),
+                )?;
+            }
+        } else if item.has_type("reminder") {
+            let id = field("id");
+            let complete_id = id.clone();
+            add(
+                "complete",
+                &t("Complete Reminder"),
+                Box::new(move || crate::calendar::complete_reminder(&complete_id)),
+            )?;
+            add(
+                "open",
+                &t("Open in Reminders"),
+                Box::new(move || crate::calendar::open_reminder(&id)),
+            )?;
+        } else if item.has_type("permission") {
+            if let Some(permission) = Permission::from_name(&field("permission")) {
+                add(
+                    "grant",
+                    &t("Allow Access"),
+                    Box::new(move || crate::permissions::request(permission).map(|_| ())),
+                )?;
+            }
+        }
+
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the list item for a calendar event.
+fn calendar_event_item(event: &CalendarEvent) -> Item {
+    let link = crate::calendar::meeting_link(event);
+    let subtitle = match &event.location {
+        Some(location) if !location.is_empty() => {
+            format!("{} · {} · {}", event.start_label, event.calendar, location)
+        }
+        _ => format!("{} · {}", event.start_label, event.calendar),
+    };
+    Item::new(format!("event:{}", event.id), event.title.clone())
+        .with_subtitle(subtitle)
+        .with_icon(if link.is_some() { "📹" } else { "📅" })
+        .with_type("calendar_event")
+        .with_data(serde_json::json!({ "id": event.id, "meeting_link": link }))
+}
+
+/// Build the list item for a reminder.
+fn reminder_item(reminder: &Reminder) -> Item {
+    let subtitle = match &reminder.due_label {
+        Some(due) => format!("{} · Due {}", reminder.list, due),
+        None => reminder.list.clone(),
+    };
+    Item::new(format!("reminder:{}", reminder.id), reminder.title.clone())
+        .with_subtitle(subtitle)
+        .with_icon("☑️")
+        .with_type("reminder")
+        .with_data(serde_json::json!({ "id": reminder.id }))
+}
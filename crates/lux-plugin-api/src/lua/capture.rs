@@ -0,0 +1,91 @@
+//! Lua bindings for screen captures.
+//!
+//! This module provides:
+//! - `lux.capture.set_folder/screenshot/start_recording/stop_recording/latest()` - Screen captures
+//! - Handlers for the capture actions
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::capture::{capture_action_key, run_capture_action, CaptureKind, CAPTURE_ACTION_IDS};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.capture`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in capture action handlers (see crate::capture)
+    for action_id in CAPTURE_ACTION_IDS {
+        let captures = registry.captures();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_capture_action(action_id, &item, &captures)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&capture_action_key(action_id), handler)?;
+    }
+
+    // lux.capture - Screenshots and screen recordings
+    //
+    // Usage:
+    //   lux.capture.set_folder("~/Desktop/Captures")  -- default ~/Pictures/Screenshots
+    //   lux.capture.screenshot("area")                -- "area", "window", or "screen"; returns the path
+    //   lux.capture.start_recording()                 -- returns the path of the recording
+    //   lux.capture.stop_recording()
+    //   lux.capture.latest()                          -- newest capture in the folder, or nil
+    {
+        let capture_table = lua.create_table()?;
+
+        let captures = registry.captures();
+        let set_folder_fn = lua.create_function(move |_lua, folder: String| {
+            captures.set_folder(crate::detect::expand_home(&folder));
+            Ok(())
+        })?;
+        capture_table.set("set_folder", set_folder_fn)?;
+
+        let captures = registry.captures();
+        let folder_fn = lua
+            .create_function(move |_lua, ()| Ok(captures.folder().to_string_lossy().to_string()))?;
+        capture_table.set("folder", folder_fn)?;
+
+        let captures = registry.captures();
+        let screenshot_fn = lua.create_function(move |_lua, kind: Option<String>| {
+            let kind = match kind.as_deref() {
+                Some(name) => CaptureKind::from_name(name).ok_or_else(|| {
+                    mlua::Error::RuntimeError(format!(
+                        "capture.screenshot: unknown kind '{}' (expected area, window, or screen)",
+                        name
+                    ))
+                })?,
+                None => CaptureKind::Area,
+            };
+            Ok(captures.screenshot(kind)?.to_string_lossy().to_string())
+        })?;
+        capture_table.set("screenshot", screenshot_fn)?;
+
+        let captures = registry.captures();
+        let start_fn = lua.create_function(move |_lua, ()| {
+            Ok(captures.start_recording()?.to_string_lossy().to_string())
+        })?;
+        capture_table.set("start_recording", start_fn)?;
+
+        let captures = registry.captures();
+        let stop_fn = lua.create_function(move |_lua, ()| {
+            Ok(captures.stop_recording()?.to_string_lossy().to_string())
+        })?;
+        capture_table.set("stop_recording", stop_fn)?;
+
+        let captures = registry.captures();
+        let latest_fn = lua.create_function(move |_lua, ()| {
+            Ok(captures
+                .latest()
+                .map(|path| path.to_string_lossy().to_string()))
+        })?;
+        capture_table.set("latest", latest_fn)?;
+
+        lux.set("capture", capture_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! Lua bindings for currency conversion.
+//!
+//! This module provides:
+//! - `lux.currency.set_targets/refresh/rates/convert()` - Exchange rates
+//! - Handlers for the currency actions
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::currency::{currency_action_key, run_currency_action, CURRENCY_ACTION_IDS};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.currency`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in currency action handlers (see crate::currency)
+    for action_id in CURRENCY_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_currency_action(action_id, &item)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&currency_action_key(action_id), handler)?;
+    }
+
+    // lux.currency - Exchange rates for conversions like "25 usd in eur"
+    //
+    // Usage:
+    //   lux.currency.set_targets({ "EUR", "GBP", "CHF" })  -- what "25 usd" converts into
+    //   lux.currency.refresh()                             -- fetch rates now
+    //   lux.currency.rates()                               -- { base, updated, rates = { EUR = 0.92, ... } } or nil
+    //   lux.currency.convert(25, "usd", "eur")             -- number, or nil for unknown codes
+    //
+    // Rates are refreshed in the background every few hours; the last-known
+    // rates are used while offline.
+    {
+        let currency_table = lua.create_table()?;
+
+        let currency = registry.currency();
+        let set_targets_fn = lua.create_function(move |_lua, targets: Vec<String>| {
+            currency.set_targets(targets);
+            Ok(())
+        })?;
+        currency_table.set("set_targets", set_targets_fn)?;
+
+        let currency = registry.currency();
+        let refresh_fn = lua.create_function(move |_lua, ()| {
+            currency.refresh();
+            Ok(())
+        })?;
+        currency_table.set("refresh", refresh_fn)?;
+
+        let currency = registry.currency();
+        let rates_fn = lua.create_function(move |lua, ()| {
+            let Some(rates) = currency.rates() else {
+                return Ok(None);
+            };
+            let result = lua.create_table()?;
+            result.set("base", rates.base)?;
+            result.set("updated", rates.updated)?;
+            result.set("rates", rates.rates)?;
+            Ok(Some(result))
+        })?;
+        currency_table.set("rates", rates_fn)?;
+
+        let currency = registry.currency();
+        let convert_fn =
+            lua.create_function(move |_lua, (amount, from, to): (f64, String, String)| {
+                Ok(currency.rates().and_then(|rates| {
+                    rates.convert(amount, &from.to_uppercase(), &to.to_uppercase())
+                }))
+            })?;
+        currency_table.set("convert", convert_fn)?;
+
+        lux.set("currency", currency_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+//! Lua bindings for plugin daemons.
+//!
+//! This module provides:
+//! - `lux.daemon.start/stop/restart/remove/status/list/logs()` - Supervised helper processes
+
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table, Value};
+
+use crate::config_files::EditorSettings;
+use crate::daemons::{
+    daemon_actions, daemon_groups, DaemonError, DaemonInfo, DaemonSpec, DaemonState, Daemons,
+    RestartPolicy, DAEMONS_VIEW_ID,
+};
+use crate::registry::PluginRegistry;
+
+use super::{bridge, groups_to_lua};
+
+/// Register `lux.daemon`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.daemon - Long-lived helper processes, supervised by Lux
+    //
+    // Usage:
+    //   lux.daemon.start({
+    //     name = "indexer",                     -- letters, digits, '-', and '_'
+    //     cmd = { "my-indexer", "--watch" },    -- or a string, run with sh -c
+    //     restart = "always",                   -- "always", "on_failure" (default), or "never"
+    //     cwd = "~/notes",                      -- optional
+    //     env = { RUST_LOG = "info" },          -- optional, added to Lux's
+    //   })
+    //   lux.daemon.stop("indexer")              -- SIGTERM, killed after a few seconds
+    //   lux.daemon.restart("indexer")
+    //   lux.daemon.remove("indexer")            -- stop it and drop it from the list
+    //   lux.daemon.status("indexer")            -- { name, state, pid, restarts, exit_code, error } or nil
+    //   lux.daemon.list()                       -- { status, ... }
+    //   lux.daemon.logs("indexer", 50)          -- its last output lines (default 20)
+    //
+    // Starting a daemon that runs with the same options does nothing, so this
+    // can stay in init.lua. Output goes to daemons/<name>.log in the data
+    // folder. Daemons are stopped when Lux quits; lux.builtin.daemons() lists them.
+    {
+        let daemon_table = lua.create_table()?;
+        let fail = |e: DaemonError| mlua::Error::RuntimeError(format!("daemon: {}", e));
+
+        let daemons = registry.daemons();
+        let start_fn = lua.create_function(move |_lua, opts: Table| {
+            let name: String = opts.get("name").map_err(|_| {
+                mlua::Error::RuntimeError("daemon.start requires a 'name' field".to_string())
+            })?;
+            let mut spec = match opts.get::<Value>("cmd")? {
+                Value::String(command) => DaemonSpec::shell(name, &command.to_str()?),
+                Value::Table(cmd) => {
+                    let cmd = cmd.sequence_values().collect::<LuaResult<Vec<String>>>()?;
+                    DaemonSpec::new(name, cmd)
+                }
+                _ => {
+                    return Err(mlua::Error::RuntimeError(
+                        "daemon.start requires 'cmd' (a string or a list)".to_string(),
+                    ))
+                }
+            };
+            if let Some(restart) = opts.get::<Option<String>>("restart")? {
+                let policy = RestartPolicy::from_name(&restart).ok_or_else(|| {
+                    mlua::Error::RuntimeError(format!(
+                        "daemon.start: unknown restart policy '{}'",
+                        restart
+                    ))
+                })?;
+                spec = spec.with_restart(policy);
+            }
+            if let Some(cwd) = opts.get::<Option<String>>("cwd")? {
+                spec = spec.with_cwd(crate::detect::expand_home(&cwd));
+            }
+            if let Some(env) = opts.get::<Option<Table>>("env")? {
+                for pair in env.pairs::<String, String>() {
+                    let (key, value) = pair?;
+                    spec = spec.with_env(key, value);
+                }
+                // Table order varies, and the spec is compared on each start
+                spec.env.sort();
+            }
+            daemons.start(spec).map_err(fail)
+        })?;
+        daemon_table.set("start", start_fn)?;
+
+        let daemons = registry.daemons();
+        let stop_fn =
+            lua.create_function(move |_lua, name: String| daemons.stop(&name).map_err(fail))?;
+        daemon_table.set("stop", stop_fn)?;
+
+        let daemons = registry.daemons();
+        let restart_fn =
+            lua.create_function(move |_lua, name: String| daemons.restart(&name).map_err(fail))?;
+        daemon_table.set("restart", restart_fn)?;
+
+        let daemons = registry.daemons();
+        let remove_fn =
+            lua.create_function(move |_lua, name: String| daemons.remove(&name).map_err(fail))?;
+        daemon_table.set("remove", remove_fn)?;
+
+        let daemons = registry.daemons();
+        let status_fn = lua.create_function(move |lua, name: String| {
+            daemons
+                .get(&name)
+                .map(|info| daemon_info_to_lua(lua, &info))
+                .transpose()
+        })?;
+        daemon_table.set("status", status_fn)?;
+
+        let daemons = registry.daemons();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let infos = daemons
+                .list()
+                .iter()
+                .map(|info| daemon_info_to_lua(lua, info))
+                .collect::<LuaResult<Vec<_>>>()?;
+            lua.create_sequence_from(infos)
+        })?;
+        daemon_table.set("list", list_fn)?;
+
+        let daemons = registry.daemons();
+        let logs_fn = lua.create_function(move |lua, (name, count): (String, Option<usize>)| {
+            lua.create_sequence_from(daemons.logs(&name, count.unwrap_or(20)))
+        })?;
+        daemon_table.set("logs", logs_fn)?;
+
+        lux.set("daemon", daemon_table)?;
+    }
+
+    Ok(())
+}
+
+/// Convert a daemon to the table `lux.daemon.status()` returns.
+fn daemon_info_to_lua(lua: &Lua, info: &DaemonInfo) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("name", info.name.as_str())?;
+    table.set("command", info.command.as_str())?;
+    table.set("restart", info.restart.name())?;
+    table.set("state", info.state.name())?;
+    if let DaemonState::Running { pid } = info.state {
+        table.set("pid", pid)?;
+    }
+    table.set("restarts", info.restarts)?;
+    table.set("exit_code", info.last_exit)?;
+    table.set("error", info.error.clone())?;
+    Ok(table)
+}
+
+/// Build the daemons view returned by `lux.builtin.daemons()`.
+pub(super) fn daemons_view(
+    lua: &Lua,
+    daemons: Arc<Daemons>,
+    editor: Arc<EditorSettings>,
+) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", DAEMONS_VIEW_ID)?;
+    view.set("title", t("Daemons"))?;
+    view.set("placeholder", t("Filter daemons..."))?;
+
+    // search(query, ctx) - every daemon, with its state
+    let search_daemons = daemons.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = daemon_groups(&search_daemons, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - start, stop, restart, or remove a daemon, or open its log
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        for (id, title, shortcut) in daemon_actions(&item) {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            if let Some(shortcut) = shortcut {
+                action.set("shortcut", shortcut)?;
+            }
+            if id == "remove" {
+                action.set("destructive", true)?;
+            }
+            let (daemons, editor, name) = (daemons.clone(), editor.clone(), item.id.clone());
+            action.set(
+                "handler",
+                lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                    let fail = |e: DaemonError| mlua::Error::RuntimeError(e.to_string());
+                    match id {
+                        "restart" => daemons.restart(&name).map_err(fail),
+                        "stop" => daemons.stop(&name).map_err(fail),
+                        "remove" => daemons.remove(&name).map_err(fail),
+                        "open_log" => {
+                            if let Some(path) = daemons.log_path(&name) {
+                                editor.editor().open(&path)?;
+                            }
+                            ctx.call_method::<()>("dismiss", ())
+                        }
+                        _ => Ok(()),
+                    }
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
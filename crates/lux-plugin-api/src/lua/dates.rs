@@ -0,0 +1,44 @@
+//! Lua bindings for dates.
+//!
+//! This module provides:
+//! - `lux.date.parse()` - Natural-language dates
+//! - Handlers for the date actions
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::dates::{date_action_key, parse_date, run_date_action, DATE_ACTION_IDS};
+
+use super::bridge;
+
+/// Register `lux.date`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // Built-in date action handlers (see crate::dates)
+    for action_id in DATE_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_date_action(action_id, &item)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&date_action_key(action_id), handler)?;
+    }
+
+    // lux.date - Natural-language dates
+    //
+    // Usage:
+    //   local due = lux.date.parse("next friday 3pm")  -- Unix timestamp, or nil
+    //   lux.date.parse("in 2 weeks")                   -- dates without a time are at midnight
+    //   lux.i18n.format_date(lux.date.parse("dec 25"))
+    {
+        let date_table = lua.create_table()?;
+
+        let parse_fn = lua.create_function(|_lua, text: String| {
+            let now = chrono::Local::now().naive_local();
+            Ok(parse_date(&text, now).and_then(|parsed| parsed.timestamp()))
+        })?;
+        date_table.set("parse", parse_fn)?;
+
+        lux.set("date", date_table)?;
+    }
+
+    Ok(())
+}
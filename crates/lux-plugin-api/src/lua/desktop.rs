@@ -0,0 +1,213 @@
+//! Lua bindings for the desktop.
+//!
+//! This module provides:
+//! - `lux.icon(app_path)` - Icon file for an app
+//! - `lux.open(target)` / `lux.reveal(path)` - Open with the default app, show in Finder
+//! - `lux.open_with(path)` - "Open With" app picker
+//! - `lux.clipboard` - Clipboard operations
+
+use std::sync::Arc;
+
+use lux_core::i18n::{t, t_args};
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::apps::AppInfo;
+use crate::desktop;
+use crate::matching::Matcher;
+use crate::registry::PluginRegistry;
+
+/// Register `lux.icon`, `lux.open`, `lux.reveal`, `lux.open_with`, and `lux.clipboard`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.icon(app_path) - Get icon file path for macOS app (converts to PNG)
+    {
+        let icon_fn = lua.create_function(|_lua, app_path: String| {
+            crate::apps::app_icon(&app_path).map_err(mlua::Error::from)
+        })?;
+        lux.set("icon", icon_fn)?;
+    }
+
+    // lux.open(target) / lux.reveal(path) - Open with the default app, show in Finder
+    //
+    // Usage:
+    //   lux.open("https://example.com")
+    //   lux.open("~/Downloads/report.pdf")
+    //   lux.reveal("~/Downloads/report.pdf")
+    //   local ok, err = lux.open(path)   -- nil and the reason if it failed
+    {
+        let open_fn = lua.create_function(|_lua, target: String| {
+            Ok(match desktop::open(&target) {
+                Ok(()) => (Some(true), None),
+                Err(e) => (None, Some(e.to_string())),
+            })
+        })?;
+        lux.set("open", open_fn)?;
+
+        let reveal_fn = lua.create_function(|_lua, path: String| {
+            Ok(match desktop::reveal(&path) {
+                Ok(()) => (Some(true), None),
+                Err(e) => (None, Some(e.to_string())),
+            })
+        })?;
+        lux.set("reveal", reveal_fn)?;
+    }
+
+    // lux.open_with - Open files with a chosen application
+    //
+    // Usage:
+    //   ctx:push(lux.open_with(path))          -- app picker view
+    //   lux.open_with.apps(path)               -- list candidate apps
+    //   lux.open_with.open(path, app_path)     -- open directly
+    {
+        let open_with_table = lua.create_table()?;
+
+        // lux.open_with.apps(path) - Apps registered for the file, default first
+        let apps_fn = lua.create_function(|lua, path: String| {
+            let apps = crate::apps::applications_for_path(&path)?;
+            let table = lua.create_table()?;
+            for (i, app) in apps.iter().enumerate() {
+                table.set(i + 1, app_info_to_lua(lua, app)?)?;
+            }
+            Ok(table)
+        })?;
+        open_with_table.set("apps", apps_fn)?;
+
+        // lux.open_with.open(path, app_path) - Open with a specific app
+        let open_fn = lua.create_function(|_lua, (path, app_path): (String, String)| {
+            crate::apps::open_with(&path, &app_path).map_err(mlua::Error::from)
+        })?;
+        open_with_table.set("open", open_fn)?;
+
+        // Set __call metamethod for lux.open_with(path) - returns picker view
+        let metatable = lua.create_table()?;
+        let matcher = registry.matcher();
+        let call_fn = lua.create_function(move |lua, (_, path): (Table, String)| {
+            open_with_view(lua, matcher.clone(), path)
+        })?;
+        metatable.set("__call", call_fn)?;
+        open_with_table.set_metatable(Some(metatable))?;
+
+        lux.set("open_with", open_with_table)?;
+    }
+
+    // lux.clipboard - Clipboard operations
+    {
+        let clipboard_table = lua.create_table()?;
+
+        // lux.clipboard.read() - Read text from clipboard
+        let read_fn = lua.create_function(|_lua, ()| {
+            desktop::current()
+                .clipboard_read()
+                .map_err(mlua::Error::from)
+        })?;
+        clipboard_table.set("read", read_fn)?;
+
+        // lux.clipboard.write(text) - Write text to clipboard
+        let write_fn = lua.create_function(|_lua, text: String| {
+            desktop::current().clipboard_write(&text)?;
+            Ok(true)
+        })?;
+        clipboard_table.set("write", write_fn)?;
+
+        // lux.clipboard.paste(text) - Copy text and paste it into the frontmost app
+        let paste_fn = lua.create_function(|_lua, text: String| {
+            desktop::current().paste(&text)?;
+            Ok(true)
+        })?;
+        clipboard_table.set("paste", paste_fn)?;
+
+        lux.set("clipboard", clipboard_table)?;
+    }
+
+    Ok(())
+}
+
+/// Convert an AppInfo to a Lua table.
+fn app_info_to_lua(lua: &Lua, app: &AppInfo) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("name", app.name.as_str())?;
+    table.set("path", app.path.as_str())?;
+    if let Some(ref bundle_id) = app.bundle_id {
+        table.set("bundle_id", bundle_id.as_str())?;
+    }
+    table.set("is_default", app.is_default)?;
+    Ok(table)
+}
+
+/// Build the "Open With" picker view returned by `lux.open_with(path)`.
+///
+/// Apps and their icons are resolved once when the view is built. The search
+/// function fuzzy-matches their names (romanized too, see [`crate::matching`]),
+/// and the single action opens the file with the chosen app and dismisses the
+/// launcher.
+fn open_with_view(lua: &Lua, matcher: Arc<Matcher>, path: String) -> LuaResult<Table> {
+    let apps: Vec<(AppInfo, Option<String>)> = crate::apps::applications_for_path(&path)?
+        .into_iter()
+        .map(|app| {
+            let icon = crate::apps::app_icon(&app.path).ok().flatten();
+            (app, icon)
+        })
+        .collect();
+
+    let file_name = std::path::Path::new(&path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.clone());
+
+    let view = lua.create_table()?;
+    view.set("id", "open_with")?;
+    view.set("title", t_args("Open {file} With", &[("file", &file_name)]))?;
+    view.set("placeholder", t("Search applications..."))?;
+
+    let view_data = lua.create_table()?;
+    view_data.set("path", path.as_str())?;
+    view.set("view_data", view_data)?;
+
+    // search(query, ctx) - apps matching by name, best first
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let items = lua.create_table()?;
+        let mut matches: Vec<(u32, &(AppInfo, Option<String>))> = apps
+            .iter()
+            .filter_map(|entry| {
+                let score = matcher.score_text(&entry.0.name, &query)?;
+                Some((score, entry))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        for (i, (_, (app, icon))) in matches.into_iter().enumerate() {
+            let item = lua.create_table()?;
+            item.set("id", app.path.as_str())?;
+            item.set("title", app.name.as_str())?;
+            if app.is_default {
+                item.set("subtitle", t("Default"))?;
+            }
+            if let Some(icon) = icon {
+                item.set("icon", icon.as_str())?;
+            }
+            item.set("types", lua.create_sequence_from(["application"])?)?;
+            items.set(i + 1, item)?;
+        }
+        ctx.call_method::<()>("set_items", items)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - open the file with the chosen app
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let app_path: String = item.get("id")?;
+        let app_name: String = item.get("title")?;
+        let path = path.clone();
+
+        let handler = lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+            crate::apps::open_with(&path, &app_path)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+
+        let action = lua.create_table()?;
+        action.set("id", "open_with")?;
+        action.set("title", t_args("Open with {app}", &[("app", &app_name)]))?;
+        action.set("handler", handler)?;
+        lua.create_sequence_from([action])
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,62 @@
+//! Lua bindings for the editor.
+//!
+//! This module provides:
+//! - `lux.editor.get/open()` - The editor config files open in (set in config.toml)
+//! - Handlers for the config file actions
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::config_files::{config_file_action_key, run_config_file_action, CONFIG_FILE_ACTION_IDS};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.editor`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in config file action handlers (see crate::config_files)
+    for action_id in CONFIG_FILE_ACTION_IDS {
+        let editor = registry.editor();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_config_file_action(action_id, &item, &editor.editor())?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&config_file_action_key(action_id), handler)?;
+    }
+
+    // lux.editor - The editor init.lua, plugins, and the data folder open in
+    //
+    // Set in config.toml, not from Lua, since it is a command Lux runs:
+    //   editor = "zed"        -- any command, run with the path ("subl -n", "code")
+    //   editor = "default"    -- the app the OS opens the file with
+    //   editor = "env"        -- $EDITOR, or the default app if unset (the default)
+    //
+    // Usage:
+    //   lux.editor.get()              -- "zed"
+    //   lux.editor.open("~/notes.md") -- open any file or folder in it
+    //
+    // Typing "init.lua", "plugins", or "lux config" in the root view lists
+    // the config files. Repositories use lux.git.set_editor().
+    {
+        let editor_table = lua.create_table()?;
+
+        let editor = registry.editor();
+        let get_fn = lua.create_function(move |_lua, ()| Ok(editor.editor().name()))?;
+        editor_table.set("get", get_fn)?;
+
+        let editor = registry.editor();
+        let open_fn = lua.create_function(move |_lua, path: String| {
+            let path = PathBuf::from(crate::detect::expand_home(&path));
+            editor.editor().open(&path)?;
+            Ok(())
+        })?;
+        editor_table.set("open", open_fn)?;
+
+        lux.set("editor", editor_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,359 @@
+//! Lua bindings for files.
+//!
+//! This module provides:
+//! - `lux.recent.set_downloads_folder/documents/downloads()` - Recent documents and downloads
+//! - `lux.finder.selection/view/add_action()` - The files selected in Finder
+//! - `lux.archive.zip/tar/extract/unzip()` - Zip and tar archives
+//! - Handlers for the recent file and archive actions, and the send_to_lux hotkey
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Function, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::archive::{
+    archive_action_key, compress, default_archive_path, default_extract_dir, extract,
+    start_archive_action, ArchiveFormat, ARCHIVE_ACTION_IDS,
+};
+use crate::desktop;
+use crate::finder::{
+    finder_selection, run_selection_action, selection_actions, selection_items,
+    SELECTION_ACTIONS_KEY, SELECTION_VIEW_ID, SEND_TO_LUX_KEY,
+};
+use crate::recent::{recent_action_key, run_recent_action, RecentFile, RECENT_ACTION_IDS};
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+
+use super::bridge;
+
+/// Register `lux.recent`, `lux.finder`, and `lux.archive`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in recent file action handlers (see crate::recent)
+    for action_id in RECENT_ACTION_IDS {
+        let recents = registry.recents();
+        let undo = registry.undo();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_recent_action(action_id, &item, &recents, &undo)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&recent_action_key(action_id), handler)?;
+    }
+
+    // Built-in archive action handlers (see crate::archive). The work runs in
+    // the background; a notification follows when it's done.
+    for action_id in ARCHIVE_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let items = items
+                .sequence_values::<Table>()
+                .map(|item| bridge::parse_item(lua, item?))
+                .collect::<LuaResult<Vec<_>>>()?;
+            let message = start_archive_action(action_id, &items)?;
+            ctx.call_method::<()>("progress", message)
+        })?;
+        lua.set_named_registry_value(&archive_action_key(action_id), handler)?;
+    }
+
+    // lux.recent - Recently opened documents and the Downloads folder
+    //
+    // Usage:
+    //   lux.recent.set_downloads_folder("~/Desktop/Incoming")  -- default ~/Downloads
+    //   lux.recent.documents()   -- { { path = "...", date = 1760000000 }, ... } newest first
+    //   lux.recent.downloads()
+    {
+        let recent_table = lua.create_table()?;
+
+        let recents = registry.recents();
+        let set_folder_fn = lua.create_function(move |_lua, folder: String| {
+            recents.set_downloads_folder(crate::detect::expand_home(&folder));
+            Ok(())
+        })?;
+        recent_table.set("set_downloads_folder", set_folder_fn)?;
+
+        let recents = registry.recents();
+        let folder_fn = lua.create_function(move |_lua, ()| {
+            Ok(recents.downloads_folder().to_string_lossy().to_string())
+        })?;
+        recent_table.set("downloads_folder", folder_fn)?;
+
+        let recents = registry.recents();
+        let documents_fn =
+            lua.create_function(move |lua, ()| recent_files_table(lua, &recents.documents()))?;
+        recent_table.set("documents", documents_fn)?;
+
+        let recents = registry.recents();
+        let downloads_fn =
+            lua.create_function(move |lua, ()| recent_files_table(lua, &recents.downloads()))?;
+        recent_table.set("downloads", downloads_fn)?;
+
+        lux.set("recent", recent_table)?;
+    }
+
+    // lux.finder - The files selected in Finder
+    //
+    // Usage:
+    //   lux.finder.selection()             -- { "/Users/me/a.pdf", ... }, empty when nothing is selected
+    //   ctx:push(lux.finder.view(paths))   -- "Selected Files" view with every file selected
+    //   lux.finder.add_action({ id = "share", title = "Share", handler = function(items, ctx) ... end })
+    //
+    // The send_to_lux global hotkey opens the view on the current selection.
+    {
+        let finder_table = lua.create_table()?;
+        lua.set_named_registry_value(SELECTION_ACTIONS_KEY, lua.create_table()?)?;
+
+        let selection_fn = lua.create_function(|_lua, ()| {
+            Ok(finder_selection()?
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>())
+        })?;
+        finder_table.set("selection", selection_fn)?;
+
+        let view_fn = lua.create_function(|lua, paths: Vec<String>| {
+            selection_view(lua, paths.into_iter().map(PathBuf::from).collect())
+        })?;
+        finder_table.set("view", view_fn)?;
+
+        let add_action_fn = lua.create_function(|lua, action: Table| {
+            action.get::<String>("title").map_err(|_| {
+                mlua::Error::RuntimeError("finder.add_action: 'title' is required".to_string())
+            })?;
+            action.get::<Function>("handler").map_err(|_| {
+                mlua::Error::RuntimeError(
+                    "finder.add_action: 'handler' must be a function".to_string(),
+                )
+            })?;
+            let actions: Table = lua.named_registry_value(SELECTION_ACTIONS_KEY)?;
+            actions.push(action)
+        })?;
+        finder_table.set("add_action", add_action_fn)?;
+
+        lux.set("finder", finder_table)?;
+    }
+
+    // lux.archive - Zip and tar archives (see crate::archive)
+    //
+    // Usage:
+    //   lux.archive.zip({ "~/Desktop/report.pdf" })  -- writes ~/Desktop/report.pdf.zip
+    //   lux.archive.tar(paths, { dest = "/tmp/backup.tar.gz", gzip = true })
+    //   lux.archive.extract("~/Downloads/photos.zip")  -- into ~/Downloads/photos
+    //   lux.archive.unzip(path, {
+    //       dest = "/tmp/photos",
+    //       on_progress = function(done, total) ctx:progress(done .. "/" .. total) end,
+    //   })
+    // Each returns the path of the archive or folder it wrote.
+    {
+        let archive_table = lua.create_table()?;
+
+        let zip_fn = lua.create_function(|_lua, (paths, opts): (Vec<String>, Option<Table>)| {
+            lua_compress("archive.zip", paths, ArchiveFormat::Zip, opts)
+        })?;
+        archive_table.set("zip", zip_fn)?;
+
+        let tar_fn = lua.create_function(|_lua, (paths, opts): (Vec<String>, Option<Table>)| {
+            let gzip = match &opts {
+                Some(opts) => opts.get::<Option<bool>>("gzip")?.unwrap_or(false),
+                None => false,
+            };
+            let format = if gzip {
+                ArchiveFormat::TarGz
+            } else {
+                ArchiveFormat::Tar
+            };
+            lua_compress("archive.tar", paths, format, opts)
+        })?;
+        archive_table.set("tar", tar_fn)?;
+
+        let extract_fn = lua.create_function(|_lua, (path, opts): (String, Option<Table>)| {
+            lua_extract("archive.extract", path, opts)
+        })?;
+        archive_table.set("extract", extract_fn)?;
+
+        let unzip_fn = lua.create_function(|_lua, (path, opts): (String, Option<Table>)| {
+            lua_extract("archive.unzip", path, opts)
+        })?;
+        archive_table.set("unzip", unzip_fn)?;
+
+        lux.set("archive", archive_table)?;
+    }
+
+    // Built-in send_to_lux global hotkey handler (see crate::finder)
+    {
+        let handler = lua.create_function(|lua, (_items, ctx): (Table, AnyUserData)| {
+            let paths = finder_selection()?;
+            if paths.is_empty() {
+                desktop::current().notify("Lux", &t("No files selected in Finder"))?;
+                return Ok(());
+            }
+            ctx.call_method::<()>("push", selection_view(lua, paths)?)
+        })?;
+        lua.set_named_registry_value(SEND_TO_LUX_KEY, handler)?;
+        registry.keymap().store_lua_handler(
+            SEND_TO_LUX_KEY.to_string(),
+            LuaFunctionRef::new(SEND_TO_LUX_KEY.to_string()),
+        );
+    }
+
+    Ok(())
+}
+
+/// Convert recent files to a Lua list of `{ path, date }` tables.
+fn recent_files_table(lua: &Lua, files: &[RecentFile]) -> LuaResult<Table> {
+    let list = lua.create_table()?;
+    for (i, file) in files.iter().enumerate() {
+        let table = lua.create_table()?;
+        table.set("path", file.path.to_string_lossy().to_string())?;
+        table.set("date", file.date)?;
+        list.set(i + 1, table)?;
+    }
+    Ok(list)
+}
+
+/// The `dest` and `on_progress` options of the `lux.archive` functions.
+fn archive_options(opts: Option<&Table>) -> LuaResult<(Option<PathBuf>, Option<Function>)> {
+    let Some(opts) = opts else {
+        return Ok((None, None));
+    };
+    let dest = opts
+        .get::<Option<String>>("dest")?
+        .map(|dest| PathBuf::from(crate::detect::expand_home(&dest)));
+    Ok((dest, opts.get("on_progress")?))
+}
+
+/// Call `on_progress` from an archive progress callback, keeping the first error.
+fn report_archive_progress(
+    on_progress: Option<&Function>,
+    error: &mut Option<mlua::Error>,
+    done: usize,
+    total: usize,
+) {
+    if let Some(callback) = on_progress.filter(|_| error.is_none()) {
+        if let Err(e) = callback.call::<()>((done, total)) {
+            *error = Some(e);
+        }
+    }
+}
+
+/// Implementation of `lux.archive.zip()` / `lux.archive.tar()`.
+fn lua_compress(
+    fn_name: &str,
+    paths: Vec<String>,
+    format: ArchiveFormat,
+    opts: Option<Table>,
+) -> LuaResult<String> {
+    let paths: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| PathBuf::from(crate::detect::expand_home(path)))
+        .collect();
+    let (dest, on_progress) = archive_options(opts.as_ref())?;
+    let dest = match dest.or_else(|| default_archive_path(&paths, format)) {
+        Some(dest) => dest,
+        None => {
+            return Err(mlua::Error::RuntimeError(format!(
+                "{}: no paths to compress",
+                fn_name
+            )))
+        }
+    };
+
+    let mut callback_error = None;
+    compress(&paths, &dest, format, &mut |done, total| {
+        report_archive_progress(on_progress.as_ref(), &mut callback_error, done, total)
+    })?;
+    match callback_error {
+        Some(e) => Err(e),
+        None => Ok(dest.to_string_lossy().to_string()),
+    }
+}
+
+/// Implementation of `lux.archive.extract()` / `lux.archive.unzip()`.
+fn lua_extract(fn_name: &str, path: String, opts: Option<Table>) -> LuaResult<String> {
+    let archive = PathBuf::from(crate::detect::expand_home(&path));
+    let (dest, on_progress) = archive_options(opts.as_ref())?;
+    let dest = match dest.or_else(|| default_extract_dir(&archive)) {
+        Some(dest) => dest,
+        None => {
+            return Err(mlua::Error::RuntimeError(format!(
+                "{}: can't extract '{}'",
+                fn_name, path
+            )))
+        }
+    };
+
+    let mut callback_error = None;
+    extract(&archive, &dest, &mut |done, total| {
+        report_archive_progress(on_progress.as_ref(), &mut callback_error, done, total)
+    })?;
+    match callback_error {
+        Some(e) => Err(e),
+        None => Ok(dest.to_string_lossy().to_string()),
+    }
+}
+
+/// Build the "Selected Files" view for `paths`, with every file selected.
+///
+/// Actions run on all selected files: the built-in ones first, then those
+/// added with `lux.finder.add_action()`.
+fn selection_view(lua: &Lua, paths: Vec<PathBuf>) -> LuaResult<Table> {
+    let items = selection_items(&paths);
+
+    let view = lua.create_table()?;
+    view.set("id", SELECTION_VIEW_ID)?;
+    view.set("title", t("Selected Files"))?;
+    view.set("placeholder", t("Filter selected files..."))?;
+    view.set("selection", "multi")?;
+    view.set(
+        "selected",
+        items.iter().map(|item| item.id.clone()).collect::<Vec<_>>(),
+    )?;
+
+    // search(query, ctx) - the selected files whose name matches
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let query = query.trim().to_lowercase();
+        let group = lua.create_table()?;
+        group.set("title", t("Selected Files"))?;
+        let group_items = lua.create_table()?;
+        for item in items
+            .iter()
+            .filter(|item| item.title.to_lowercase().contains(&query))
+        {
+            group_items.push(bridge::item_to_lua(lua, item)?)?;
+        }
+        group.set("items", group_items)?;
+        let result = lua.create_table()?;
+        result.push(group)?;
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - built-in file actions, then the added ones
+    let get_actions_fn = lua.create_function(|lua, (_item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        for (id, title) in selection_actions() {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+                    let items = items
+                        .sequence_values::<Table>()
+                        .map(|item| bridge::parse_item(lua, item?))
+                        .collect::<LuaResult<Vec<_>>>()?;
+                    run_selection_action(id, &items)?;
+                    ctx.call_method::<()>("dismiss", ())
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        let added: Table = lua.named_registry_value(SELECTION_ACTIONS_KEY)?;
+        for action in added.sequence_values::<Table>() {
+            actions.push(action?)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,131 @@
+//! Lua bindings for the filesystem.
+//!
+//! This module provides:
+//! - `lux.fs` - Filesystem operations
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+use crate::undo::{move_file, rename_file, trash_file};
+
+/// Register `lux.fs`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.fs - Filesystem operations
+    {
+        let fs_table = lua.create_table()?;
+
+        // lux.fs.read(path) - Read file contents
+        let read_fn =
+            lua.create_function(|_lua, path: String| match std::fs::read_to_string(&path) {
+                Ok(content) => Ok(Some(content)),
+                Err(_) => Ok(None),
+            })?;
+        fs_table.set("read", read_fn)?;
+
+        // lux.fs.write(path, content) - Write content to file
+        let write_fn = lua.create_function(|_lua, (path, content): (String, String)| {
+            std::fs::write(&path, content)
+                .map_err(|e| mlua::Error::RuntimeError(format!("File write failed: {}", e)))?;
+            Ok(true)
+        })?;
+        fs_table.set("write", write_fn)?;
+
+        // lux.fs.exists(path) - Check if path exists
+        let exists_fn =
+            lua.create_function(|_lua, path: String| Ok(std::path::Path::new(&path).exists()))?;
+        fs_table.set("exists", exists_fn)?;
+
+        // lux.fs.is_dir(path) - Check if path is a directory
+        let is_dir_fn =
+            lua.create_function(|_lua, path: String| Ok(std::path::Path::new(&path).is_dir()))?;
+        fs_table.set("is_dir", is_dir_fn)?;
+
+        // lux.fs.list(dir) - List directory contents
+        let list_fn = lua.create_function(|lua, dir: String| {
+            let entries: Vec<String> = std::fs::read_dir(&dir)
+                .map_err(|e| mlua::Error::RuntimeError(format!("Directory read failed: {}", e)))?
+                .filter_map(|entry| {
+                    entry.ok().and_then(|e| {
+                        e.path()
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                    })
+                })
+                .collect();
+
+            let table = lua.create_table()?;
+            for (i, name) in entries.iter().enumerate() {
+                table.set(i + 1, name.as_str())?;
+            }
+            Ok(table)
+        })?;
+        fs_table.set("list", list_fn)?;
+
+        // lux.fs.glob(pattern) - Glob pattern matching
+        let glob_fn = lua.create_function(|lua, pattern: String| {
+            use std::process::Command;
+
+            // Use shell glob expansion
+            let output = Command::new("sh")
+                .args(["-c", &format!("ls -1 {} 2>/dev/null || true", pattern)])
+                .output()
+                .map_err(|e| mlua::Error::RuntimeError(format!("Glob failed: {}", e)))?;
+
+            let output_str = String::from_utf8_lossy(&output.stdout);
+            let paths: Vec<&str> = output_str
+                .trim()
+                .split('\n')
+                .filter(|s| !s.is_empty())
+                .collect();
+
+            let table = lua.create_table()?;
+            for (i, path) in paths.iter().enumerate() {
+                table.set(i + 1, *path)?;
+            }
+            Ok(table)
+        })?;
+        fs_table.set("glob", glob_fn)?;
+
+        // lux.fs.trash(path) - Move to the Trash (undoable with Cmd+Z)
+        let undo = registry.undo();
+        let trash_fn = lua.create_function(move |_lua, path: String| {
+            trash_file(&undo, &path)?;
+            Ok(true)
+        })?;
+        fs_table.set("trash", trash_fn)?;
+
+        // lux.fs.move(from, to) - Move a file, or into a folder; returns the new path
+        let undo = registry.undo();
+        let move_fn = lua.create_function(move |_lua, (from, to): (String, String)| {
+            let dest = move_file(&undo, &from, &to)?;
+            Ok(dest.to_string_lossy().to_string())
+        })?;
+        fs_table.set("move", move_fn)?;
+
+        // lux.fs.rename(path, new_name) - Rename within the folder; returns the new path
+        let undo = registry.undo();
+        let rename_fn = lua.create_function(move |_lua, (path, new_name): (String, String)| {
+            let dest = rename_file(&undo, &path, &new_name)?;
+            Ok(dest.to_string_lossy().to_string())
+        })?;
+        fs_table.set("rename", rename_fn)?;
+
+        // lux.fs.home() - Get home directory
+        let home_fn = lua.create_function(|_lua, ()| {
+            Ok(dirs::home_dir().map(|p| p.to_string_lossy().to_string()))
+        })?;
+        fs_table.set("home", home_fn)?;
+
+        // lux.fs.config() - Get config directory
+        let config_fn = lua.create_function(|_lua, ()| {
+            Ok(dirs::config_dir().map(|p| p.to_string_lossy().to_string()))
+        })?;
+        fs_table.set("config", config_fn)?;
+
+        lux.set("fs", fs_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! Lua bindings for Git repositories.
+//!
+//! This module provides:
+//! - `lux.git.set_roots/set_editor/refresh/repos()` - Git repository index
+//! - Handlers for the repository actions
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::git::{git_action_key, run_git_action, DEFAULT_DEPTH, GIT_ACTION_IDS};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.git`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in repository action handlers (see crate::git)
+    for action_id in GIT_ACTION_IDS {
+        let git = registry.git();
+        let ssh = registry.ssh();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_git_action(action_id, &item, &git.editor(), &ssh.terminal())?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&git_action_key(action_id), handler)?;
+    }
+
+    // lux.git - Git repositories (root view)
+    //
+    // Usage:
+    //   lux.git.set_roots({ "~/code", "~/work" })      -- indexed in the background
+    //   lux.git.set_roots({ "~/src" }, { depth = 5 })  -- look deeper (default 3)
+    //   lux.git.set_editor("zed")                      -- command run with the repo path
+    //   lux.git.refresh()                              -- reindex now
+    //   lux.git.repos()                                -- { { name, path, branch, changes }, ... }
+    //
+    // Repositories open in the terminal set with lux.ssh.set_terminal().
+    {
+        let git_table = lua.create_table()?;
+
+        let git = registry.git();
+        let set_roots_fn =
+            lua.create_function(move |_lua, (roots, opts): (Vec<String>, Option<Table>)| {
+                let depth = match opts {
+                    Some(opts) => opts.get::<Option<usize>>("depth")?,
+                    None => None,
+                };
+                let roots = roots
+                    .iter()
+                    .map(|root| PathBuf::from(crate::detect::expand_home(root)))
+                    .collect();
+                git.set_roots(roots, depth.unwrap_or(DEFAULT_DEPTH));
+                Ok(())
+            })?;
+        git_table.set("set_roots", set_roots_fn)?;
+
+        let git = registry.git();
+        let set_editor_fn = lua.create_function(move |_lua, editor: String| {
+            git.set_editor(editor);
+            Ok(())
+        })?;
+        git_table.set("set_editor", set_editor_fn)?;
+
+        let git = registry.git();
+        let refresh_fn = lua.create_function(move |_lua, ()| {
+            git.refresh();
+            Ok(())
+        })?;
+        git_table.set("refresh", refresh_fn)?;
+
+        let git = registry.git();
+        let repos_fn = lua.create_function(move |lua, ()| {
+            let result = lua.create_table()?;
+            for (i, repo) in git.repos().iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("name", repo.name.as_str())?;
+                entry.set("path", repo.path.to_string_lossy().to_string())?;
+                entry.set("branch", repo.status.branch.clone())?;
+                entry.set("changes", repo.status.changes)?;
+                entry.set("remote", repo.remote.clone())?;
+                result.set(i + 1, entry)?;
+            }
+            Ok(result)
+        })?;
+        git_table.set("repos", repos_fn)?;
+
+        lux.set("git", git_table)?;
+    }
+
+    Ok(())
+}
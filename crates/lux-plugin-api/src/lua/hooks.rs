@@ -0,0 +1,48 @@
+//! Lua bindings for hooks.
+//!
+//! This module provides:
+//! - `lux.hook(path, fn)` - Register hooks
+
+use std::sync::Arc;
+
+use mlua::{Function, Lua, Result as LuaResult, Table};
+
+use crate::hooks::validate_hook_path;
+use crate::keymap::generate_handler_id;
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+
+/// Register `lux.hook`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.hook(path, fn) - register a hook, returns unhook function
+    //
+    //   lux.hook("on_shutdown", function(ms_left) save_state() end)
+    {
+        let registry = Arc::clone(registry);
+        let hook_fn = lua.create_function(move |lua, (path, func): (String, Function)| {
+            // Validate hook path
+            validate_hook_path(&path).map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+
+            // Generate a unique key and store the function
+            let key = format!("hook:{}:{}", path, generate_handler_id());
+            let func_ref = LuaFunctionRef::from_function(lua, func, key.clone())?;
+
+            // Add to hook registry
+            let hook_registry = registry.hooks();
+            let hook_id = hook_registry.add(&path, func_ref);
+
+            // Create unhook function
+            let registry_for_unhook = Arc::clone(&registry);
+            let hook_id_for_unhook = hook_id.clone();
+            let unhook_fn = lua.create_function(move |_lua, ()| {
+                let removed = registry_for_unhook.hooks().remove(&hook_id_for_unhook);
+                Ok(removed)
+            })?;
+
+            Ok(unhook_fn)
+        })?;
+        lux.set("hook", hook_fn)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,320 @@
+//! Lua bindings for HTTP requests.
+//!
+//! This module provides:
+//! - `lux.http.request/curl/history/clear_history()` - HTTP requests
+//! - The request builder and response views
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use lux_core::i18n::t;
+use lux_core::Group;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::desktop;
+use crate::http::{
+    builder_actions, builder_groups, builder_step, json_items, json_pointer, response_actions,
+    response_groups, response_value, BuilderStep, HttpClient, HttpRequest, PendingResponse,
+    BUILDER_VIEW_ID, DEFAULT_TIMEOUT, JSON_VIEW_ID, RESPONSE_VIEW_ID,
+};
+use crate::registry::PluginRegistry;
+
+use super::{bridge, groups_to_lua, json_to_lua_value};
+
+/// Register `lux.http`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.http - HTTP requests through curl (see crate::http)
+    //
+    // Usage:
+    //   local res = lux.http.request({
+    //       method = "POST",                              -- default "GET"
+    //       url = "https://api.example.com/items",
+    //       headers = { Authorization = "Bearer " .. token },
+    //       body = '{"name": "lux"}',                     -- JSON bodies get a Content-Type
+    //       timeout = 10,                                 -- seconds, default 30
+    //   })
+    //   -- res.status, res.reason, res.headers, res.body, and res.json if the body is JSON
+    //   lux.http.curl(request)        -- the request as a curl command line
+    //   lux.http.history()            -- requests sent from the request builder, newest first
+    //   lux.http.clear_history()
+    {
+        let http_table = lua.create_table()?;
+
+        let request_fn = lua.create_function(|lua, table: Table| {
+            let request = http_request_from_lua(&table, "http.request")?;
+            let timeout = match table.get::<Option<f64>>("timeout")? {
+                Some(secs) if secs > 0.0 => Duration::from_secs_f64(secs),
+                _ => DEFAULT_TIMEOUT,
+            };
+            let response = crate::http::send(&request, timeout)?;
+
+            let result = lua.create_table()?;
+            result.set("status", response.status)?;
+            result.set("reason", response.reason.as_str())?;
+            let headers = lua.create_table()?;
+            for (name, value) in &response.headers {
+                headers.set(name.to_lowercase(), value.as_str())?;
+            }
+            result.set("headers", headers)?;
+            result.set("body", response.body.as_str())?;
+            if let Some(json) = response.json() {
+                result.set("json", json_to_lua_value(lua, &json)?)?;
+            }
+            Ok(result)
+        })?;
+        http_table.set("request", request_fn)?;
+
+        let curl_fn = lua.create_function(|_lua, table: Table| {
+            Ok(http_request_from_lua(&table, "http.curl")?.to_curl())
+        })?;
+        http_table.set("curl", curl_fn)?;
+
+        let client = registry.http();
+        let history_fn = lua.create_function(move |lua, ()| {
+            let list = lua.create_table()?;
+            for request in client.history() {
+                list.push(http_request_to_lua(lua, &request)?)?;
+            }
+            Ok(list)
+        })?;
+        http_table.set("history", history_fn)?;
+
+        let client = registry.http();
+        let clear_fn = lua.create_function(move |_lua, ()| {
+            client.clear_history();
+            Ok(())
+        })?;
+        http_table.set("clear_history", clear_fn)?;
+
+        lux.set("http", http_table)?;
+    }
+
+    Ok(())
+}
+
+/// Parse a `{ method, url, headers, body }` request table.
+fn http_request_from_lua(table: &Table, fn_name: &str) -> LuaResult<HttpRequest> {
+    let url: String = table
+        .get::<Option<String>>("url")?
+        .ok_or_else(|| mlua::Error::RuntimeError(format!("{}: 'url' is required", fn_name)))?;
+    let method = table
+        .get::<Option<String>>("method")?
+        .map(|method| method.to_uppercase())
+        .unwrap_or_else(|| "GET".to_string());
+
+    let mut request = HttpRequest::new(method, url);
+    if let Some(headers) = table.get::<Option<Table>>("headers")? {
+        for pair in headers.pairs::<String, String>() {
+            request.headers.push(pair?);
+        }
+        // Lua tables have no order; keep curl commands stable
+        request.headers.sort();
+    }
+    request.body = table.get("body")?;
+    Ok(request)
+}
+
+/// Convert a request to a `{ method, url, headers, body }` table.
+fn http_request_to_lua(lua: &Lua, request: &HttpRequest) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("method", request.method.as_str())?;
+    table.set("url", request.url.as_str())?;
+    let headers = lua.create_table()?;
+    for (name, value) in &request.headers {
+        headers.set(name.as_str(), value.as_str())?;
+    }
+    table.set("headers", headers)?;
+    table.set("body", request.body.as_deref())?;
+    Ok(table)
+}
+
+/// Build the request builder view for `request`.
+///
+/// Each step replaces the view with the updated request; sending pushes the
+/// response view.
+pub(super) fn http_builder_view(
+    lua: &Lua,
+    client: Arc<HttpClient>,
+    request: HttpRequest,
+) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", BUILDER_VIEW_ID)?;
+    view.set("title", t("HTTP Request"))?;
+    if request.url.is_empty() {
+        view.set("placeholder", t("Type a URL, e.g. POST https://..."))?;
+    } else {
+        view.set(
+            "placeholder",
+            t("Add a header (Name: value) or type a body..."),
+        )?;
+    }
+
+    // search(query, ctx) - the query fills in the next part of the request
+    let search_client = client.clone();
+    let search_request = request.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = builder_groups(&search_request, &query, &search_client.history());
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - edit, send, or copy the request
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        for (id, title) in builder_actions(&item, &request) {
+            let action = lua.create_table()?;
+            action.set("id", id.as_str())?;
+            action.set("title", title)?;
+            let (client, request, item) = (client.clone(), request.clone(), item.clone());
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                    match builder_step(&id, &item, &request, &client.history()) {
+                        Some(BuilderStep::Edit(next)) => ctx.call_method::<()>(
+                            "replace",
+                            http_builder_view(lua, client.clone(), next)?,
+                        ),
+                        Some(BuilderStep::Send(next)) => {
+                            let pending = client.send_in_background(next.clone());
+                            ctx.call_method::<()>(
+                                "push",
+                                http_response_view(lua, client.clone(), next, pending, None)?,
+                            )
+                        }
+                        Some(BuilderStep::Copy(text)) => {
+                            desktop::current().clipboard_write(&text)?;
+                            ctx.call_method::<()>("dismiss", ())
+                        }
+                        None => Ok(()),
+                    }
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the view of a sent request's response.
+///
+/// It shows "Sending…" until `pending` is filled in; the client's update
+/// re-runs the search then. With a `pointer`, the view shows that folded
+/// JSON value of the body instead.
+fn http_response_view(
+    lua: &Lua,
+    client: Arc<HttpClient>,
+    request: HttpRequest,
+    pending: PendingResponse,
+    pointer: Option<String>,
+) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    match &pointer {
+        Some(pointer) => {
+            view.set("id", JSON_VIEW_ID)?;
+            view.set("title", pointer.as_str())?;
+        }
+        None => {
+            view.set("id", RESPONSE_VIEW_ID)?;
+            view.set("title", request.summary())?;
+        }
+    }
+    view.set("placeholder", t("Filter response..."))?;
+
+    // search(query, ctx) - status, body, and headers (or the JSON value)
+    let (search_request, search_pending, search_pointer) =
+        (request.clone(), pending.clone(), pointer.clone());
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let response = search_pending.lock();
+        let groups = match (&search_pointer, response.as_ref()) {
+            (Some(pointer), Some(Ok(response))) => {
+                let query = query.trim().to_lowercase();
+                let items = response
+                    .json()
+                    .as_ref()
+                    .and_then(|json| json.pointer(pointer))
+                    .map(|value| json_items(value, pointer))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|item| item.title.to_lowercase().contains(&query))
+                    .collect();
+                vec![Group::ungrouped(items)]
+            }
+            (_, response) => response_groups(&search_request, response, &query),
+        };
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - expand, copy, send again, or edit
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        for (id, title) in response_actions(&item) {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            let (client, request, pending, item) = (
+                client.clone(),
+                request.clone(),
+                pending.clone(),
+                item.clone(),
+            );
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                    let body = || match pending.lock().as_ref() {
+                        Some(Ok(response)) => Some(response.body.clone()),
+                        _ => None,
+                    };
+                    let copy = |text: &str| -> LuaResult<()> {
+                        desktop::current().clipboard_write(text)?;
+                        ctx.call_method::<()>("dismiss", ())
+                    };
+                    match id {
+                        "expand" => {
+                            let Some(pointer) = json_pointer(&item) else {
+                                return Ok(());
+                            };
+                            let view = http_response_view(
+                                lua,
+                                client.clone(),
+                                request.clone(),
+                                pending.clone(),
+                                Some(pointer.to_string()),
+                            )?;
+                            ctx.call_method::<()>("push", view)
+                        }
+                        "copy_value" => copy(response_value(&item).unwrap_or_default()),
+                        "copy_body" => copy(&body().unwrap_or_default()),
+                        "copy_curl" => copy(&request.to_curl()),
+                        "send_again" => {
+                            let pending = client.send_in_background(request.clone());
+                            let view = http_response_view(
+                                lua,
+                                client.clone(),
+                                request.clone(),
+                                pending,
+                                None,
+                            )?;
+                            ctx.call_method::<()>("replace", view)
+                        }
+                        "edit" => ctx.call_method::<()>(
+                            "replace",
+                            http_builder_view(lua, client.clone(), request.clone())?,
+                        ),
+                        _ => Ok(()),
+                    }
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
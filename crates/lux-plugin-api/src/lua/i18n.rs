@@ -0,0 +1,114 @@
+//! Lua bindings for translations.
+//!
+//! This module provides:
+//! - `lux.i18n.t/add/locale/set_locale/format_number/format_date()` - Translations
+
+use std::collections::HashMap;
+
+use lux_core::i18n::{t, t_args};
+use mlua::{Lua, Result as LuaResult, Table, Value};
+
+use crate::i18n::DateStyle;
+
+/// Register `lux.i18n`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.i18n - Translations and locale-aware formatting
+    //
+    // Usage:
+    //   lux.i18n.add("de", { ["Open Project"] = "Projekt öffnen", ["{n} files"] = "{n} Dateien" })
+    //   lux.i18n.t("Open Project")             -- "Projekt öffnen" when the locale is German
+    //   lux.i18n.t("{n} files", { n = 3 })
+    //   lux.i18n.locale()                      -- "de-DE"
+    //   lux.i18n.set_locale("fr")
+    //   lux.i18n.format_number(1234.5, 2)      -- "1.234,50"
+    //   lux.i18n.format_date(os.time(), { date = "long", time = "short" })
+    {
+        let i18n_table = lua.create_table()?;
+
+        let t_fn = lua.create_function(|_lua, (key, args): (String, Option<Table>)| {
+            let Some(args) = args else {
+                return Ok(t(&key));
+            };
+            let mut pairs = Vec::new();
+            for pair in args.pairs::<String, Value>() {
+                let (name, value) = pair?;
+                let value = match value {
+                    Value::String(s) => s.to_str()?.to_string(),
+                    Value::Integer(n) => n.to_string(),
+                    Value::Number(n) => n.to_string(),
+                    Value::Boolean(b) => b.to_string(),
+                    other => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "i18n.t: argument '{}' must be a string or number, got {}",
+                            name,
+                            other.type_name()
+                        )))
+                    }
+                };
+                pairs.push((name, value));
+            }
+            let pairs: Vec<(&str, &str)> = pairs
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            Ok(t_args(&key, &pairs))
+        })?;
+        i18n_table.set("t", t_fn)?;
+
+        let add_fn = lua.create_function(|_lua, (locale, strings): (String, Table)| {
+            let strings = strings
+                .pairs::<String, String>()
+                .collect::<LuaResult<HashMap<_, _>>>()?;
+            lux_core::i18n::add_strings(&locale, strings);
+            Ok(())
+        })?;
+        i18n_table.set("add", add_fn)?;
+
+        let locale_fn = lua.create_function(|_lua, ()| Ok(lux_core::i18n::locale()))?;
+        i18n_table.set("locale", locale_fn)?;
+
+        let set_locale_fn = lua.create_function(|_lua, locale: String| {
+            lux_core::i18n::set_locale(&locale);
+            Ok(())
+        })?;
+        i18n_table.set("set_locale", set_locale_fn)?;
+
+        let format_number_fn =
+            lua.create_function(|_lua, (value, decimals): (f64, Option<usize>)| {
+                Ok(lux_core::i18n::format_number(value, decimals.unwrap_or(0)))
+            })?;
+        i18n_table.set("format_number", format_number_fn)?;
+
+        let format_date_fn =
+            lua.create_function(|_lua, (timestamp, opts): (f64, Option<Table>)| {
+                let style = |key: &str, default: DateStyle| -> LuaResult<DateStyle> {
+                    let name = match &opts {
+                        Some(opts) => opts.get::<Option<String>>(key)?,
+                        None => None,
+                    };
+                    match name {
+                        None => Ok(default),
+                        Some(name) => DateStyle::from_name(&name).ok_or_else(|| {
+                            mlua::Error::RuntimeError(format!(
+                                "i18n.format_date: unknown {} style '{}'",
+                                key, name
+                            ))
+                        }),
+                    }
+                };
+                let date_style = style("date", DateStyle::Medium)?;
+                let time_style = style("time", DateStyle::None)?;
+                Ok(crate::i18n::format_date(
+                    timestamp,
+                    &lux_core::i18n::locale(),
+                    date_style,
+                    time_style,
+                )?)
+            })?;
+        i18n_table.set("format_date", format_date_fn)?;
+
+        lux.set("i18n", i18n_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Lua bindings for items.
+//!
+//! This module provides:
+//! - `lux.item_id(item)` - Stable identity for an item
+//! - `lux.map_items(result, fn)` - Transform items preserving group structure
+
+use mlua::{Function, Lua, Result as LuaResult, Table, Value};
+
+/// Register `lux.item_id` and `lux.map_items`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.item_id(item) - Get stable identity for an item
+    {
+        let item_id_fn = lua.create_function(|_lua, item: Table| {
+            // Try to get 'id' field first, then fall back to 'title'
+            if let Ok(Some(id)) = item.get::<Option<String>>("id") {
+                return Ok(id);
+            }
+            if let Ok(Some(title)) = item.get::<Option<String>>("title") {
+                return Ok(title);
+            }
+            Err(mlua::Error::RuntimeError(
+                "item_id: item must have 'id' or 'title' field".to_string(),
+            ))
+        })?;
+        lux.set("item_id", item_id_fn)?;
+    }
+
+    // lux.map_items(result, fn) - Transform items preserving group structure
+    {
+        let map_items_fn = lua.create_function(|lua, (result, mapper): (Table, Function)| {
+            // Check if result has 'groups' field
+            if let Ok(groups) = result.get::<Table>("groups") {
+                // Has groups - map each group's items
+                let new_groups = lua.create_table()?;
+                for pair in groups.pairs::<i64, Table>() {
+                    let (idx, group) = pair?;
+                    let new_group = lua.create_table()?;
+
+                    // Copy title if present
+                    if let Ok(Some(t)) = group.get::<Option<String>>("title") {
+                        new_group.set("title", t)?;
+                    }
+
+                    // Map items
+                    if let Ok(items) = group.get::<Table>("items") {
+                        let new_items = lua.create_table()?;
+                        for item_pair in items.pairs::<i64, Value>() {
+                            let (i, item) = item_pair?;
+                            let mapped: Value = mapper.call(item)?;
+                            new_items.set(i, mapped)?;
+                        }
+                        new_group.set("items", new_items)?;
+                    }
+
+                    new_groups.set(idx, new_group)?;
+                }
+
+                let new_result = lua.create_table()?;
+                new_result.set("groups", new_groups)?;
+                Ok(new_result)
+            } else {
+                // No groups - treat as flat array of items
+                let new_items = lua.create_table()?;
+                for pair in result.pairs::<i64, Value>() {
+                    let (idx, item) = pair?;
+                    let mapped: Value = mapper.call(item)?;
+                    new_items.set(idx, mapped)?;
+                }
+                Ok(new_items)
+            }
+        })?;
+        lux.set("map_items", map_items_fn)?;
+    }
+
+    Ok(())
+}
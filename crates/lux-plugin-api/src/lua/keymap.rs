@@ -0,0 +1,394 @@
+//! Lua bindings for keybindings.
+//!
+//! This module provides:
+//! - `lux.keymap.set/del/list/set_global/del_global()` - Keybindings
+//! - Keybinding cheatsheet, command, and keymap conflict views
+
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Function, Lua, MultiValue, ObjectLike, Result as LuaResult, Table, Value};
+
+use crate::commands::{command_groups, COMMANDS_VIEW_ID};
+use crate::conflicts::{
+    conflict_actions, conflict_groups, run_conflict_action, KeymapConflicts,
+    KEYMAP_CONFLICTS_VIEW_ID,
+};
+use crate::desktop;
+use crate::keymap::{
+    generate_handler_id, keybinding_groups, BuiltInHotkey, GlobalHandler, KeyHandler,
+    KeymapRegistry, PendingBinding, PendingHotkey, KEYBINDINGS_VIEW_ID,
+};
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+
+use super::{bridge, groups_to_lua};
+
+/// Register `lux.keymap`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.keymap namespace
+    let keymap_table = lua.create_table()?;
+
+    // lux.keymap.set(key, handler, opts?)
+    //
+    // Examples:
+    //   lux.keymap.set("ctrl+n", "cursor_down")
+    //   lux.keymap.set("ctrl+n", "cursor_down", { context = "Launcher" })
+    //   lux.keymap.set("enter", "submit", { context = "SearchInput" })
+    //   lux.keymap.set("ctrl+o", "open_finder", { context = "Launcher", view = "files" })
+    //   lux.keymap.set("ctrl+d", function(ctx) ... end, { view = "files" })
+    //   lux.keymap.set("ctrl+d", "page_down", { view = "files", exclusive = true })
+    //
+    // A key is looked up in the view's bindings, then the context's, then the
+    // global ones (no context or view). When the action isn't available there,
+    // the key falls through to the next binding, unless `exclusive = true`.
+    {
+        let registry = Arc::clone(registry);
+        let set_fn = lua.create_function(move |lua, args: MultiValue| {
+            let mut args_iter = args.into_iter();
+
+            // First arg: key (required)
+            let key: String = match args_iter.next() {
+                Some(v) => lua
+                    .unpack(v)
+                    .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?,
+                None => {
+                    return Err(mlua::Error::RuntimeError(
+                        "keymap.set requires key argument".to_string(),
+                    ))
+                }
+            };
+
+            // Second arg: handler (required) - string or function
+            let handler_val = match args_iter.next() {
+                Some(v) => v,
+                None => {
+                    return Err(mlua::Error::RuntimeError(
+                        "keymap.set requires handler argument".to_string(),
+                    ))
+                }
+            };
+
+            // Third arg: opts (optional)
+            let opts: Option<Table> = args_iter.next().and_then(|v| lua.unpack(v).ok());
+            let (context, view, exclusive) = if let Some(ref t) = opts {
+                (
+                    t.get::<Option<String>>("context").ok().flatten(),
+                    t.get::<Option<String>>("view").ok().flatten(),
+                    t.get::<Option<bool>>("exclusive")
+                        .ok()
+                        .flatten()
+                        .unwrap_or(false),
+                )
+            } else {
+                (None, None, false)
+            };
+
+            // Parse handler
+            let handler = if let Ok(action_name) = lua.unpack::<String>(handler_val.clone()) {
+                // Action name binding
+                KeyHandler::Action(action_name)
+            } else if let Ok(func) = lua.unpack::<Function>(handler_val) {
+                // Lua function binding - store in registry
+                let id = generate_handler_id();
+                let func_ref = LuaFunctionRef::from_function(lua, func, id.clone())?;
+                registry.keymap().store_lua_handler(id.clone(), func_ref);
+                KeyHandler::Function { id }
+            } else {
+                return Err(mlua::Error::RuntimeError(
+                    "handler must be string or function".to_string(),
+                ));
+            };
+
+            registry.keymap().set(PendingBinding {
+                key,
+                handler,
+                context,
+                view,
+                exclusive,
+            });
+            Ok(())
+        })?;
+        keymap_table.set("set", set_fn)?;
+    }
+
+    // lux.keymap.list()
+    //
+    // Every binding, most specific first:
+    //   { key = "ctrl+d", action = "page_down", context = "Launcher", view = "files",
+    //     scope = "view", exclusive = true }
+    // `action` is nil for Lua function bindings.
+    {
+        let registry = Arc::clone(registry);
+        let list_fn = lua.create_function(move |lua, ()| {
+            let list = lua.create_table()?;
+            for binding in registry.keymap().list() {
+                let entry = lua.create_table()?;
+                entry.set("key", binding.key.as_str())?;
+                entry.set("action", binding.action_name())?;
+                entry.set("context", binding.context_name())?;
+                entry.set("view", binding.view.as_deref())?;
+                entry.set("scope", binding.scope().as_str())?;
+                entry.set("exclusive", binding.exclusive)?;
+                list.push(entry)?;
+            }
+            Ok(list)
+        })?;
+        keymap_table.set("list", list_fn)?;
+    }
+
+    // lux.keymap.del(key, opts?)
+    //
+    // Examples:
+    //   lux.keymap.del("ctrl+n")
+    //   lux.keymap.del("ctrl+n", { view = "files" })
+    {
+        let registry = Arc::clone(registry);
+        let del_fn = lua.create_function(move |lua, args: MultiValue| {
+            let mut args_iter = args.into_iter();
+
+            // First arg: key (required)
+            let key: String = match args_iter.next() {
+                Some(v) => lua
+                    .unpack(v)
+                    .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?,
+                None => {
+                    return Err(mlua::Error::RuntimeError(
+                        "keymap.del requires key argument".to_string(),
+                    ))
+                }
+            };
+
+            // Second arg: opts (optional)
+            let opts: Option<Table> = args_iter.next().and_then(|v| lua.unpack(v).ok());
+            let (context, view) = if let Some(ref t) = opts {
+                (
+                    t.get::<Option<String>>("context").ok().flatten(),
+                    t.get::<Option<String>>("view").ok().flatten(),
+                )
+            } else {
+                (None, None)
+            };
+
+            let removed = registry
+                .keymap()
+                .del(&key, context.as_deref(), view.as_deref());
+            Ok(removed)
+        })?;
+        keymap_table.set("del", del_fn)?;
+    }
+
+    // lux.keymap.set_global(key, handler)
+    //
+    // Examples:
+    //   lux.keymap.set_global("cmd+shift+space", "toggle_launcher")
+    //   lux.keymap.set_global("cmd+shift+n", function() lux.shell("open -a Notes") end)
+    //   lux.keymap.set_global("cmd+shift+l", "send_to_lux")   -- open on the Finder selection
+    {
+        let registry = Arc::clone(registry);
+        let set_global_fn = lua.create_function(move |lua, args: MultiValue| {
+            let mut args_iter = args.into_iter();
+
+            // First arg: key (required)
+            let key: String = match args_iter.next() {
+                Some(v) => lua
+                    .unpack(v)
+                    .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?,
+                None => {
+                    return Err(mlua::Error::RuntimeError(
+                        "keymap.set_global requires key argument".to_string(),
+                    ))
+                }
+            };
+
+            // Second arg: handler (required) - string or function
+            let handler_val = match args_iter.next() {
+                Some(v) => v,
+                None => {
+                    return Err(mlua::Error::RuntimeError(
+                        "keymap.set_global requires handler argument".to_string(),
+                    ))
+                }
+            };
+
+            // Parse handler
+            let handler = if let Ok(action_name) = lua.unpack::<String>(handler_val.clone()) {
+                // Built-in action
+                if let Some(builtin) = BuiltInHotkey::from_name(&action_name) {
+                    GlobalHandler::BuiltIn(builtin)
+                } else {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "Unknown global action: '{}'. Available: toggle_launcher, send_to_lux",
+                        action_name
+                    )));
+                }
+            } else if let Ok(func) = lua.unpack::<Function>(handler_val) {
+                // Lua function binding - store in registry
+                let id = generate_handler_id();
+                let func_ref = LuaFunctionRef::from_function(lua, func, id.clone())?;
+                registry.keymap().store_lua_handler(id.clone(), func_ref);
+                GlobalHandler::Function { id }
+            } else {
+                return Err(mlua::Error::RuntimeError(
+                    "handler must be string or function".to_string(),
+                ));
+            };
+
+            registry.keymap().set_global(PendingHotkey { key, handler });
+            Ok(())
+        })?;
+        keymap_table.set("set_global", set_global_fn)?;
+    }
+
+    // lux.keymap.del_global(key)
+    //
+    // Remove a global hotkey.
+    //
+    // Examples:
+    //   lux.keymap.del_global("cmd+space")
+    {
+        let registry = Arc::clone(registry);
+        let del_global_fn = lua.create_function(move |lua, key: Value| {
+            let key: String = lua
+                .unpack(key)
+                .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?;
+
+            let removed = registry.keymap().del_global(&key);
+            Ok(removed)
+        })?;
+        keymap_table.set("del_global", del_global_fn)?;
+    }
+
+    lux.set("keymap", keymap_table)?;
+
+    Ok(())
+}
+
+/// Build the keybindings view returned by `lux.builtin.keybindings()`.
+pub(super) fn keybindings_view(lua: &Lua, keymap: Arc<KeymapRegistry>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", KEYBINDINGS_VIEW_ID)?;
+    view.set("title", t("Keybindings"))?;
+    view.set("placeholder", t("Filter keybindings..."))?;
+
+    // search(query, ctx) - bindings by scope, most specific first
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = keybinding_groups(&keymap.list(), &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy the selected bindings, one per line
+    let get_actions_fn = lua.create_function(move |lua, (_item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        let action = lua.create_table()?;
+        action.set("id", "copy")?;
+        action.set("title", t("Copy Keybinding"))?;
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (items, ctx): (Table, AnyUserData)| {
+                let lines = items
+                    .sequence_values::<Table>()
+                    .map(|item| {
+                        let item = item?;
+                        let key: String = item.get("title")?;
+                        let handler: Option<String> = item.get("subtitle")?;
+                        Ok(match handler {
+                            Some(handler) => format!("{} → {}", key, handler),
+                            None => key,
+                        })
+                    })
+                    .collect::<LuaResult<Vec<_>>>()?;
+                desktop::current().clipboard_write(&lines.join("\n"))?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the commands view returned by `lux.builtin.commands()`.
+pub(super) fn commands_view(lua: &Lua, keymap: Arc<KeymapRegistry>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", COMMANDS_VIEW_ID)?;
+    view.set("title", t("Commands"))?;
+    view.set("placeholder", t("Search Lux commands..."))?;
+
+    // search(query, ctx) - every command, with its keybinding
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = command_groups(&keymap.list(), &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - run the command on the view this one was opened from
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let command: String = item.get("id")?;
+        let actions = lua.create_table()?;
+        let action = lua.create_table()?;
+        action.set("id", "run")?;
+        action.set("title", t("Run Command"))?;
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                ctx.call_method::<()>("pop", ())?;
+                ctx.call_method::<()>("run_command", command.clone())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Build the keymap conflicts view returned by `lux.builtin.keymap_conflicts()`.
+pub(super) fn keymap_conflicts_view(
+    lua: &Lua,
+    conflicts: Arc<KeymapConflicts>,
+) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", KEYMAP_CONFLICTS_VIEW_ID)?;
+    view.set("title", t("Keymap Conflicts"))?;
+    view.set("placeholder", t("Filter keymap conflicts..."))?;
+
+    // search(query, ctx) - unresolved conflicts, hotkeys first
+    let search_conflicts = conflicts.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = conflict_groups(&search_conflicts, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - keep the new binding or the previous one
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        for (id, title, shortcut) in conflict_actions(&item, &conflicts) {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            if let Some(shortcut) = shortcut {
+                action.set("shortcut", shortcut)?;
+            }
+            let (conflicts, item) = (conflicts.clone(), item.clone());
+            action.set(
+                "handler",
+                lua.create_function(move |_lua, (_items, _ctx): (Table, AnyUserData)| {
+                    run_conflict_action(id, &item, &conflicts)?;
+                    Ok(())
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,171 @@
+//! Lua bindings for macros.
+//!
+//! This module provides:
+//! - `lux.macros.record/stop/cancel/recording/list/path()` - Macro recording and playback
+
+use std::sync::Arc;
+
+use lux_core::i18n::{t, t_args};
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::desktop;
+use crate::macros::{macro_actions, macro_groups, macro_name, MacroError, Macros, MACROS_VIEW_ID};
+use crate::registry::PluginRegistry;
+
+use super::{bridge, groups_to_lua};
+
+/// Register `lux.macros`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.macros - Launcher interactions recorded to macros/<name>.lua and played back
+    //
+    // Usage:
+    //   lux.macros.record("open-downloads")   -- start recording (cmd+shift+m toggles it too)
+    //   lux.macros.record()                   -- under the next free name, "macro-1", ...
+    //   lux.macros.stop()                     -- save it, returns the path of the file
+    //   lux.macros.cancel()                   -- stop without saving
+    //   lux.macros.recording()                -- "open-downloads" or nil
+    //   lux.macros.list()                     -- { "open-downloads", ... }
+    //   lux.macros.path("open-downloads")     -- the file, to edit by hand
+    //   lux.keymap.set("ctrl+1", "play_macro(open-downloads)")
+    //   lux.keymap.set("ctrl+2", function(ctx) ctx:play_macro("open-downloads") end)
+    {
+        let macros_table = lua.create_table()?;
+
+        let macros = registry.macros();
+        let record_fn = lua.create_function(move |_lua, name: Option<String>| {
+            match name {
+                Some(name) => macros.start(&name).map(|()| name),
+                None => macros.start_unnamed(),
+            }
+            .map_err(|e| mlua::Error::RuntimeError(format!("macros.record: {}", e)))
+        })?;
+        macros_table.set("record", record_fn)?;
+
+        let macros = registry.macros();
+        let stop_fn = lua.create_function(move |_lua, ()| {
+            let recorded = macros
+                .stop()
+                .map_err(|e| mlua::Error::RuntimeError(format!("macros.stop: {}", e)))?;
+            Ok(macros
+                .path(&recorded.name)
+                .map(|path| path.to_string_lossy().into_owned()))
+        })?;
+        macros_table.set("stop", stop_fn)?;
+
+        let macros = registry.macros();
+        let cancel_fn = lua.create_function(move |_lua, ()| Ok(macros.cancel()))?;
+        macros_table.set("cancel", cancel_fn)?;
+
+        let macros = registry.macros();
+        let recording_fn = lua.create_function(move |_lua, ()| Ok(macros.recording()))?;
+        macros_table.set("recording", recording_fn)?;
+
+        let macros = registry.macros();
+        let list_fn = lua.create_function(move |_lua, ()| Ok(macros.list()))?;
+        macros_table.set("list", list_fn)?;
+
+        let macros = registry.macros();
+        let path_fn = lua.create_function(move |_lua, name: String| {
+            Ok(macros
+                .path(&name)
+                .map(|path| path.to_string_lossy().into_owned()))
+        })?;
+        macros_table.set("path", path_fn)?;
+
+        lux.set("macros", macros_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the macros view returned by `lux.builtin.macros()`.
+pub(super) fn macros_view(lua: &Lua, macros: Arc<Macros>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", MACROS_VIEW_ID)?;
+    view.set("title", t("Macros"))?;
+    view.set("placeholder", t("Filter macros, or name a new one..."))?;
+
+    // search(query, ctx) - the record row, then the saved macros
+    let search_macros = macros.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = macro_groups(&search_macros, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - record, or play, open, rename, and delete a macro
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        for (id, title, shortcut) in macro_actions(&item) {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title)?;
+            if let Some(shortcut) = shortcut {
+                action.set("shortcut", shortcut)?;
+            }
+            if id == "delete" {
+                action.set("destructive", true)?;
+            }
+            let (macros, item) = (macros.clone(), item.clone());
+            action.set(
+                "handler",
+                lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                    let name = macro_name(&item).map(str::to_string);
+                    let fail = |e: MacroError| mlua::Error::RuntimeError(e.to_string());
+                    match (id, name) {
+                        // Recording starts back on the view the macros view was opened from
+                        ("record", name) => {
+                            if macros.recording().is_some() {
+                                let recorded = macros.stop().map_err(fail)?;
+                                return ctx.call_method::<()>(
+                                    "complete",
+                                    t_args("Saved macro {name}", &[("name", &recorded.name)]),
+                                );
+                            }
+                            match name {
+                                Some(name) => macros.start(&name).map_err(fail)?,
+                                None => {
+                                    macros.start_unnamed().map_err(fail)?;
+                                }
+                            }
+                            ctx.call_method::<()>("pop", ())
+                        }
+                        ("play", Some(name)) => {
+                            ctx.call_method::<()>("pop", ())?;
+                            ctx.call_method::<()>("play_macro", name)
+                        }
+                        ("open", Some(name)) => {
+                            if let Some(path) = macros.path(&name) {
+                                desktop::current().open(&path.to_string_lossy())?;
+                            }
+                            ctx.call_method::<()>("dismiss", ())
+                        }
+                        ("rename", Some(name)) => {
+                            let options = lua.create_table()?;
+                            options.set("value", name.clone())?;
+                            options.set("placeholder", t("Macro name"))?;
+                            let macros = macros.clone();
+                            options.set(
+                                "on_submit",
+                                lua.create_function(
+                                    move |_lua, (text, _ctx): (String, AnyUserData)| {
+                                        macros.rename(&name, text.trim()).map_err(fail)
+                                    },
+                                )?,
+                            )?;
+                            ctx.call_method::<()>("edit", options)
+                        }
+                        ("delete", Some(name)) => macros.delete(&name).map_err(fail),
+                        _ => Ok(()),
+                    }
+                })?,
+            )?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
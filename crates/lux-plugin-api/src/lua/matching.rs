@@ -0,0 +1,85 @@
+//! Lua bindings for fuzzy matching.
+//!
+//! This module provides:
+//! - `lux.matching.add/load/romanize/score/filter()` - Fuzzy matching with romanized CJK titles
+
+use std::sync::Arc;
+
+use lux_core::Item;
+use mlua::{Lua, Result as LuaResult, Table, Value};
+
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.matching`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.matching - Fuzzy matching with romanized CJK titles
+    //
+    // Usage:
+    //   lux.matching.add({ ["微信"] = "wei xin", ["音"] = "yin" })  -- romanization table
+    //   lux.matching.load("~/.config/lux/pinyin.txt")             -- "微 wei" per line; returns count
+    //   lux.matching.romanize("카카오톡")                          -- { full = "kakaotok", initials = "kkot" } or nil
+    //   lux.matching.score(item_or_text, query)                   -- number, or nil if it doesn't match
+    //   lux.matching.filter(items, query)                         -- matching items, best first
+    //
+    // Items are matched by title and `match_aliases`; hangul, kana, and common Chinese
+    // characters are romanized built in.
+    {
+        let matching_table = lua.create_table()?;
+
+        let matcher = registry.matcher();
+        let add_fn = lua.create_function(move |_lua, entries: Table| {
+            for pair in entries.pairs::<String, String>() {
+                let (text, romanization) = pair?;
+                matcher.add(&text, &romanization);
+            }
+            Ok(())
+        })?;
+        matching_table.set("add", add_fn)?;
+
+        let matcher = registry.matcher();
+        let load_fn = lua.create_function(move |_lua, path: String| {
+            let path = crate::detect::expand_home(&path);
+            matcher
+                .load_table(std::path::Path::new(&path))
+                .map_err(|e| mlua::Error::RuntimeError(format!("matching.load: {}: {}", path, e)))
+        })?;
+        matching_table.set("load", load_fn)?;
+
+        let matcher = registry.matcher();
+        let romanize_fn = lua.create_function(move |lua, text: String| {
+            let Some(romanization) = matcher.romanize(&text) else {
+                return Ok(Value::Nil);
+            };
+            let table = lua.create_table()?;
+            table.set("full", romanization.full)?;
+            table.set("initials", romanization.initials)?;
+            Ok(Value::Table(table))
+        })?;
+        matching_table.set("romanize", romanize_fn)?;
+
+        let matcher = registry.matcher();
+        let score_fn = lua.create_function(move |lua, (target, query): (Value, String)| {
+            Ok(match target {
+                Value::Table(item) => matcher.score(&bridge::parse_item(lua, item)?, &query),
+                other => matcher.score_text(&lua.unpack::<String>(other)?, &query),
+            })
+        })?;
+        matching_table.set("score", score_fn)?;
+
+        let matcher = registry.matcher();
+        let filter_fn = lua.create_function(move |lua, (items, query): (Vec<Table>, String)| {
+            let items = items
+                .into_iter()
+                .map(|item| bridge::parse_item(lua, item))
+                .collect::<LuaResult<Vec<Item>>>()?;
+            bridge::items_to_lua(lua, &matcher.filter(items, &query))
+        })?;
+        matching_table.set("filter", filter_fn)?;
+
+        lux.set("matching", matching_table)?;
+    }
+
+    Ok(())
+}
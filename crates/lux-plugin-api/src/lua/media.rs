@@ -0,0 +1,95 @@
+//! Lua bindings for media playback.
+//!
+//! This module provides:
+//! - `lux.media` - Media playback control
+//! - Handlers for the built-in media commands
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::media::{media_command_key, MediaCommand, MEDIA_COMMAND_IDS};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.media`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in media command handlers (see crate::media)
+    //
+    // `set_volume` reads its level from the item; the rest only need the ID.
+    for command_id in MEDIA_COMMAND_IDS {
+        let media = registry.media();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            let command = MediaCommand::from_item(&item)
+                .filter(|c| c.id() == *command_id)
+                .or_else(|| MediaCommand::from_id(command_id, None))
+                .ok_or_else(|| {
+                    mlua::Error::RuntimeError(format!("Invalid media command '{}'", command_id))
+                })?;
+
+            crate::media::run_command(command)?;
+            media.invalidate();
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&media_command_key(command_id), handler)?;
+    }
+
+    // lux.media - Media playback control
+    //
+    // Usage:
+    //   local track = lux.media.now_playing()   -- { app, title, artist, album, playing } or nil
+    //   lux.media.play_pause()
+    //   lux.media.next() / lux.media.previous()
+    //   lux.media.volume()                      -- current output volume (0-100)
+    //   lux.media.volume(40)                    -- set output volume
+    {
+        let media_table = lua.create_table()?;
+
+        let media = registry.media();
+        let now_playing_fn = lua.create_function(move |lua, ()| {
+            media.invalidate();
+            match media.get() {
+                Some(np) => {
+                    let table = lua.create_table()?;
+                    table.set("app", np.app)?;
+                    table.set("title", np.title)?;
+                    table.set("artist", np.artist)?;
+                    table.set("album", np.album)?;
+                    table.set("playing", np.playing)?;
+                    Ok(Some(table))
+                }
+                None => Ok(None),
+            }
+        })?;
+        media_table.set("now_playing", now_playing_fn)?;
+
+        for command in [
+            MediaCommand::PlayPause,
+            MediaCommand::Next,
+            MediaCommand::Previous,
+        ] {
+            let media = registry.media();
+            let command_fn = lua.create_function(move |_lua, ()| {
+                crate::media::run_command(command)?;
+                media.invalidate();
+                Ok(())
+            })?;
+            media_table.set(command.id(), command_fn)?;
+        }
+
+        let volume_fn = lua.create_function(|_lua, level: Option<u8>| match level {
+            Some(level) => {
+                crate::media::set_volume(level)?;
+                Ok(level.min(100))
+            }
+            None => Ok(crate::media::volume()?),
+        })?;
+        media_table.set("volume", volume_fn)?;
+
+        lux.set("media", media_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+//! Lua bindings for usage metrics.
+//!
+//! This module provides:
+//! - `lux.metrics.enable/disable/enabled/summary/reset()` - Opt-in local usage metrics
+//! - The usage stats view
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::metrics::{stats_groups, Metrics, STATS_VIEW_ID};
+use crate::registry::PluginRegistry;
+
+use super::groups_to_lua;
+
+/// Register `lux.metrics`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.metrics - Local usage metrics, off until enabled
+    //
+    // Usage:
+    //   lux.metrics.enable()    -- record query latency, plugin run times and errors, action usage
+    //   lux.metrics.disable()
+    //   lux.metrics.enabled()   -- true/false
+    //   lux.metrics.summary()   -- { queries, p50_ms, p90_ms, p99_ms, plugins = {...}, actions = {...} }
+    //   lux.metrics.reset()
+    //
+    // Metrics are only written to metrics.json in the config directory.
+    // lux.builtin.stats() shows them.
+    {
+        let metrics_table = lua.create_table()?;
+
+        let metrics = registry.metrics();
+        let enable_fn = lua.create_function(move |_lua, ()| {
+            metrics.set_enabled(true);
+            Ok(())
+        })?;
+        metrics_table.set("enable", enable_fn)?;
+
+        let metrics = registry.metrics();
+        let disable_fn = lua.create_function(move |_lua, ()| {
+            metrics.set_enabled(false);
+            Ok(())
+        })?;
+        metrics_table.set("disable", disable_fn)?;
+
+        let metrics = registry.metrics();
+        let enabled_fn = lua.create_function(move |_lua, ()| Ok(metrics.is_enabled()))?;
+        metrics_table.set("enabled", enabled_fn)?;
+
+        let metrics = registry.metrics();
+        let summary_fn = lua.create_function(move |lua, ()| {
+            let summary = metrics.summary();
+            let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+
+            let table = lua.create_table()?;
+            table.set("queries", summary.queries)?;
+            table.set("p50_ms", summary.p50.map(ms))?;
+            table.set("p90_ms", summary.p90.map(ms))?;
+            table.set("p99_ms", summary.p99.map(ms))?;
+
+            let plugins = lua.create_table()?;
+            for (view, stats) in summary.plugins {
+                let entry = lua.create_table()?;
+                entry.set("calls", stats.calls)?;
+                entry.set("errors", stats.errors)?;
+                entry.set("avg_ms", ms(stats.average()))?;
+                entry.set("max_ms", ms(Duration::from_micros(stats.max_us)))?;
+                plugins.set(view, entry)?;
+            }
+            table.set("plugins", plugins)?;
+
+            let actions = lua.create_table()?;
+            for (action, count) in summary.actions {
+                actions.set(action, count)?;
+            }
+            table.set("actions", actions)?;
+            Ok(table)
+        })?;
+        metrics_table.set("summary", summary_fn)?;
+
+        let metrics = registry.metrics();
+        let reset_fn = lua.create_function(move |_lua, ()| {
+            metrics.reset();
+            Ok(())
+        })?;
+        metrics_table.set("reset", reset_fn)?;
+
+        lux.set("metrics", metrics_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the "Stats" view: query latency percentiles, plugin run times and
+/// error rates, and action usage, with an action to reset them.
+pub(super) fn stats_view(lua: &Lua, metrics: Arc<Metrics>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", STATS_VIEW_ID)?;
+    view.set("title", t("Stats"))?;
+    view.set("placeholder", t("Filter stats..."))?;
+
+    // search(query, ctx) - everything recorded so far
+    let search_metrics = metrics.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = stats_groups(&search_metrics, &query);
+        ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - reset
+    let get_actions_fn = lua.create_function(move |lua, (_item, _ctx): (Table, Table)| {
+        let actions = lua.create_table()?;
+        if !metrics.is_enabled() {
+            return Ok(actions);
+        }
+        let action = lua.create_table()?;
+        action.set("id", "reset")?;
+        action.set("title", t("Reset Stats"))?;
+        action.set("destructive", true)?;
+        let metrics = metrics.clone();
+        action.set(
+            "handler",
+            lua.create_function(move |lua, (_items, ctx): (Table, AnyUserData)| {
+                metrics.reset();
+                let groups = stats_groups(&metrics, "");
+                ctx.call_method::<()>("set_groups", groups_to_lua(lua, &groups)?)
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -3,31 +3,129 @@
 //! This module implements the `lux` global namespace with:
 //! - `lux.views.add/get/list()` - View registry
 //! - `lux.set_root(view)` - Set the root view
+//! - `lux.api_version` - API version, with shims for renamed functions (`compat`)
 //! - `lux.hook(path, fn)` - Register hooks
-//! - `lux.keymap.set/del/set_global/del_global()` - Keybindings
+//! - `lux.keymap.set/del/list/set_global/del_global()` - Keybindings
 //! - `lux.shell/clipboard/fs/ui` - Utilities
+//! - `lux.ui.sounds/play_sound()` - Sound effects and haptics on open, execute, and error
+//! - `lux.macros.record/stop/cancel/recording/list/path()` - Macro recording and playback
+//! - `lux.open(target)` / `lux.reveal(path)` - Open with the default app, show in Finder
+//! - `lux.open_with(path)` - "Open With" app picker
+//! - `lux.websearch.add/remove/list()` - Web search engines
+//! - `lux.permissions.check/request/open_settings()` - Privacy permissions (TCC)
+//! - `lux.calendar/reminders` - EventKit sources
+//! - `lux.media` - Media playback control
+//! - `lux.timers.start/cancel/list()` - Countdown timers
+//! - `lux.profile.add/set/current/list/view()` - Profiles
+//! - `lux.i18n.t/add/locale/set_locale/format_number/format_date()` - Translations
+//! - `lux.theme.set/get/reset()` - Theme overrides
+//! - `lux.server.enable/disable()` - Remote control HTTP server
+//! - `lux.alfred.view/import/import_view()` - Alfred workflow import
+//! - `lux.builtin.tabs/transform/menus/generator/http/hidden/stats/preferences/doctor/config_warnings/activity/backup/keybindings/keymap_conflicts/macros/commands/daemons()` - Browser tab switcher, text transforms, menu bar search, generators, request builder, hidden results, usage stats, preferences, diagnostics, config warnings, executed actions, backups, a keybinding cheatsheet, keymap conflicts, macros, Lux's own commands, plugin daemons
+//! - `lux.text.*` - Text transformations and the frontmost selection
+//! - `lux.generate.password/string/uuid/lorem()` - Random values from the OS generator
+//! - `lux.ssh.set_terminal/terminal()` - Terminal app for SSH hosts
+//! - `lux.git.set_roots/set_editor/refresh/repos()` - Git repository index
+//! - `lux.editor.get/open()` - The editor config files open in (set in config.toml)
+//! - `lux.daemon.start/stop/restart/remove/status/list/logs()` - Supervised helper processes
+//! - `lux.currency.set_targets/refresh/rates/convert()` - Exchange rates
+//! - `lux.date.parse()` - Natural-language dates
+//! - `lux.worldclock.pin/unpin/pinned/search()` - World clocks
+//! - `lux.qr.view/image/wifi()` - QR codes
+//! - `lux.capture.set_folder/screenshot/start_recording/stop_recording/latest()` - Screen captures
+//! - `lux.recent.set_downloads_folder/documents/downloads()` - Recent documents and downloads
+//! - `lux.finder.selection/view/add_action()` - The files selected in Finder
+//! - `lux.archive.zip/tar/extract/unzip()` - Zip and tar archives
+//! - `lux.http.request/curl/history/clear_history()` - HTTP requests
+//! - `lux.ws.connect/send/close/list()` - WebSocket connections with Lua callbacks
+//! - `lux.undo.register/latest()` - Undo stack (Cmd+Z in the launcher)
+//! - `lux.query.normalize/aliases/use/remove/rewrite/parse()` - Query rewriting and search operators
+//! - `lux.pins.list/add/remove()` - Results pinned to the top of the root view
+//! - `lux.results.order/reset()` - Group order and weights, globally or per view
+//! - `lux.routes.add/remove/list()` - Query routing rules for the root view
+//! - `lux.rules.on_app/remove/list()` - Views to open on per frontmost app
+//! - `lux.matching.add/load/romanize/score/filter()` - Fuzzy matching with romanized CJK titles
+//! - `lux.basket.items/add/remove/clear/view/add_action()` - Items collected for bulk actions
+//! - `lux.runtime.set_memory_limit/gc/stats()` - Lua memory limit, idle GC, and memory per view
+//! - `lux.metrics.enable/disable/enabled/summary/reset()` - Opt-in local usage metrics
+//! - `lux.autostart.enable/disable/status()` - Start Lux at login
+//! - `lux.secrets.get/set/delete()` - Secrets in the Keychain, redacted from logs
+//! - `lux.oauth.authorize/token/status/logout()` - OAuth 2.0 sign-in, tokens kept in the Keychain
+//! - `lux.storage.set_encrypted/status()` - Encrypted data files, key in the Keychain
+//! - `lux.security.set_restricted/trust/status()` - Restricted mode for third-party plugins
+//! - `lux.plugins.load/list()` - Third-party plugins with a manifest.json
+//!
+//! Each namespace lives in its own submodule with a `register()` function;
+//! `register_lux_api()` calls them in turn.
 
 use std::sync::Arc;
 
-use mlua::{Function, Lua, MultiValue, Result as LuaResult, Table, Value};
+use lux_core::Group;
+use mlua::{Lua, Result as LuaResult, Table, Value};
 
-use crate::keymap::{
-    generate_handler_id, BuiltInHotkey, GlobalHandler, KeyHandler, PendingBinding, PendingHotkey,
-};
+use crate::compat::{install_shims, API_VERSION, MIN_API_VERSION};
 use crate::registry::PluginRegistry;
-use crate::types::LuaFunctionRef;
 
+mod actions;
+mod alfred;
+mod autostart;
+mod basket;
 pub mod bridge;
+mod builtin;
+mod calendar;
+mod capture;
+mod currency;
+mod daemons;
+mod dates;
+mod desktop;
+mod editor;
+mod files;
+mod fs;
+mod git;
+mod hooks;
+mod http;
+mod i18n;
+mod items;
+mod keymap;
+mod macros;
+mod matching;
+mod media;
+mod metrics;
+mod oauth;
+pub mod output;
 mod parse;
+mod permissions;
+mod pins;
+mod plugins;
+mod profile;
+mod qr;
+mod query;
+mod results;
+mod routes;
+mod runtime;
+mod secrets;
+mod server;
+mod shell;
+mod ssh;
+mod text;
+mod theme;
+mod timers;
+mod ui;
+mod undo;
+mod views;
+mod websearch;
+mod websocket;
+mod worldclock;
 
 pub use bridge::{
-    call_action_run, call_get_actions, call_hooked_search, call_source_search, call_trigger_run,
-    call_view_on_select, call_view_on_submit, cleanup_view_registry_keys, ParsedAction,
+    call_action_run, call_edit_submit, call_get_actions, call_hooked_search, call_source_search,
+    call_trigger_run, call_view_lifecycle, call_view_on_select, call_view_on_selection_changed,
+    call_view_on_submit, cleanup_view_registry_keys, ParsedAction,
 };
+pub use oauth::oauth_token_to_lua;
 pub use parse::*;
-
-use crate::hooks::validate_hook_path;
-use crate::views::ViewRegistryError;
+pub use timers::timer_to_lua;
+pub use websocket::deliver_ws_event;
 
 /// Register the new `lux` API in a Lua state.
 ///
@@ -37,838 +135,73 @@ use crate::views::ViewRegistryError;
 /// - `lux.views.add/get/list()` - View registry
 /// - `lux.set_root(view)` - Set the root view
 /// - `lux.hook(path, fn)` - Register hooks
-/// - `lux.keymap.set/del/set_global/del_global()` - Keybindings
+/// - `lux.keymap.set/del/list/set_global/del_global()` - Keybindings
 /// - `lux.shell/clipboard/fs/ui` - Utilities
 pub fn register_lux_api(lua: &Lua, registry: Arc<PluginRegistry>) -> LuaResult<()> {
     let lux = lua.create_table()?;
 
-    // lux.set_root_view(view) - legacy alias
-    {
-        let registry = Arc::clone(&registry);
-        let set_root_view_fn = lua.create_function(move |lua, table: Table| {
-            let view = parse_view(lua, table)?;
-
-            registry.set_root_view(view);
-            Ok(())
-        })?;
-        lux.set("set_root_view", set_root_view_fn)?;
-    }
-
-    // lux.set_root(view) - new API
-    {
-        let registry = Arc::clone(&registry);
-        let set_root_fn = lua.create_function(move |lua, table: Table| {
-            let view = parse_view(lua, table)?;
-
-            registry.set_root_view(view);
-            Ok(())
-        })?;
-        lux.set("set_root", set_root_fn)?;
-    }
-
-    // lux.views namespace
-    let views_table = lua.create_table()?;
-
-    // lux.views.add(def) - register a view
-    {
-        let registry = Arc::clone(&registry);
-        let add_fn = lua.create_function(move |lua, table: Table| {
-            let view_def = parse_view_definition(lua, table)?;
-            let view_registry = registry.views();
-
-            view_registry.add(view_def).map_err(|e| match e {
-                ViewRegistryError::ViewAlreadyExists(id) => {
-                    mlua::Error::RuntimeError(format!("View '{}' already exists", id))
-                }
-                _ => mlua::Error::RuntimeError(e.to_string()),
-            })?;
-
-            Ok(())
-        })?;
-        views_table.set("add", add_fn)?;
-    }
-
-    // lux.views.get(id) - get a view by ID (returns table with id, title, placeholder, selection)
-    {
-        let registry = Arc::clone(&registry);
-        let get_fn = lua.create_function(move |lua, id: String| {
-            let view_registry = registry.views();
-
-            match view_registry.with_view(&id, |view| {
-                let table = lua.create_table()?;
-                table.set("id", view.id.as_str())?;
-                if let Some(ref title) = view.title {
-                    table.set("title", title.as_str())?;
-                }
-                if let Some(ref placeholder) = view.placeholder {
-                    table.set("placeholder", placeholder.as_str())?;
-                }
-                let selection_str = match view.selection {
-                    lux_core::SelectionMode::Single => "single",
-                    lux_core::SelectionMode::Multi => "multi",
-                    lux_core::SelectionMode::Custom => "custom",
-                };
-                table.set("selection", selection_str)?;
-                Ok::<_, mlua::Error>(table)
-            }) {
-                Some(result) => result.map(Value::Table),
-                None => Ok(Value::Nil),
-            }
-        })?;
-        views_table.set("get", get_fn)?;
-    }
-
-    // lux.views.list() - list all registered view IDs
-    {
-        let registry = Arc::clone(&registry);
-        let list_fn = lua.create_function(move |lua, ()| {
-            let view_registry = registry.views();
-            let ids = view_registry.list();
-
-            let table = lua.create_table()?;
-            for (i, id) in ids.iter().enumerate() {
-                table.set(i + 1, id.as_str())?;
-            }
-
-            Ok(table)
-        })?;
-        views_table.set("list", list_fn)?;
-    }
-
-    lux.set("views", views_table)?;
-
-    // lux.hook(path, fn) - register a hook, returns unhook function
-    {
-        let registry = Arc::clone(&registry);
-        let hook_fn = lua.create_function(move |lua, (path, func): (String, Function)| {
-            // Validate hook path
-            validate_hook_path(&path).map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
-
-            // Generate a unique key and store the function
-            let key = format!("hook:{}:{}", path, generate_handler_id());
-            let func_ref = LuaFunctionRef::from_function(lua, func, key.clone())?;
-
-            // Add to hook registry
-            let hook_registry = registry.hooks();
-            let hook_id = hook_registry.add(&path, func_ref);
-
-            // Create unhook function
-            let registry_for_unhook = Arc::clone(&registry);
-            let hook_id_for_unhook = hook_id.clone();
-            let unhook_fn = lua.create_function(move |_lua, ()| {
-                let removed = registry_for_unhook.hooks().remove(&hook_id_for_unhook);
-                Ok(removed)
-            })?;
-
-            Ok(unhook_fn)
-        })?;
-        lux.set("hook", hook_fn)?;
-    }
-
-    // lux.keymap namespace
-    let keymap_table = lua.create_table()?;
-
-    // lux.keymap.set(key, handler, opts?)
+    // lux.api_version - Version of this API; plugins target one in their manifest
     //
-    // Examples:
-    //   lux.keymap.set("ctrl+n", "cursor_down")
-    //   lux.keymap.set("ctrl+n", "cursor_down", { context = "Launcher" })
-    //   lux.keymap.set("enter", "submit", { context = "SearchInput" })
-    //   lux.keymap.set("ctrl+o", "open_finder", { context = "Launcher", view = "files" })
-    //   lux.keymap.set("ctrl+d", function(ctx) ... end, { view = "files" })
-    {
-        let registry = Arc::clone(&registry);
-        let set_fn = lua.create_function(move |lua, args: MultiValue| {
-            let mut args_iter = args.into_iter();
-
-            // First arg: key (required)
-            let key: String = match args_iter.next() {
-                Some(v) => lua
-                    .unpack(v)
-                    .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?,
-                None => {
-                    return Err(mlua::Error::RuntimeError(
-                        "keymap.set requires key argument".to_string(),
-                    ))
-                }
-            };
-
-            // Second arg: handler (required) - string or function
-            let handler_val = match args_iter.next() {
-                Some(v) => v,
-                None => {
-                    return Err(mlua::Error::RuntimeError(
-                        "keymap.set requires handler argument".to_string(),
-                    ))
-                }
-            };
-
-            // Third arg: opts (optional)
-            let opts: Option<Table> = args_iter.next().and_then(|v| lua.unpack(v).ok());
-            let (context, view) = if let Some(ref t) = opts {
-                (
-                    t.get::<Option<String>>("context").ok().flatten(),
-                    t.get::<Option<String>>("view").ok().flatten(),
-                )
-            } else {
-                (None, None)
-            };
-
-            // Parse handler
-            let handler = if let Ok(action_name) = lua.unpack::<String>(handler_val.clone()) {
-                // Action name binding
-                KeyHandler::Action(action_name)
-            } else if let Ok(func) = lua.unpack::<Function>(handler_val) {
-                // Lua function binding - store in registry
-                let id = generate_handler_id();
-                let func_ref = LuaFunctionRef::from_function(lua, func, id.clone())?;
-                registry.keymap().store_lua_handler(id.clone(), func_ref);
-                KeyHandler::Function { id }
-            } else {
-                return Err(mlua::Error::RuntimeError(
-                    "handler must be string or function".to_string(),
-                ));
-            };
-
-            registry.keymap().set(PendingBinding {
-                key,
-                handler,
-                context,
-                view,
-            });
-            Ok(())
-        })?;
-        keymap_table.set("set", set_fn)?;
-    }
-
-    // lux.keymap.del(key, opts?)
-    //
-    // Examples:
-    //   lux.keymap.del("ctrl+n")
-    //   lux.keymap.del("ctrl+n", { view = "files" })
-    {
-        let registry = Arc::clone(&registry);
-        let del_fn = lua.create_function(move |lua, args: MultiValue| {
-            let mut args_iter = args.into_iter();
-
-            // First arg: key (required)
-            let key: String = match args_iter.next() {
-                Some(v) => lua
-                    .unpack(v)
-                    .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?,
-                None => {
-                    return Err(mlua::Error::RuntimeError(
-                        "keymap.del requires key argument".to_string(),
-                    ))
-                }
-            };
-
-            // Second arg: opts (optional)
-            let opts: Option<Table> = args_iter.next().and_then(|v| lua.unpack(v).ok());
-            let (context, view) = if let Some(ref t) = opts {
-                (
-                    t.get::<Option<String>>("context").ok().flatten(),
-                    t.get::<Option<String>>("view").ok().flatten(),
-                )
-            } else {
-                (None, None)
-            };
-
-            let removed = registry
-                .keymap()
-                .del(&key, context.as_deref(), view.as_deref());
-            Ok(removed)
-        })?;
-        keymap_table.set("del", del_fn)?;
-    }
-
-    // lux.keymap.set_global(key, handler)
-    //
-    // Examples:
-    //   lux.keymap.set_global("cmd+shift+space", "toggle_launcher")
-    //   lux.keymap.set_global("cmd+shift+n", function() lux.shell("open -a Notes") end)
-    {
-        let registry = Arc::clone(&registry);
-        let set_global_fn = lua.create_function(move |lua, args: MultiValue| {
-            let mut args_iter = args.into_iter();
-
-            // First arg: key (required)
-            let key: String = match args_iter.next() {
-                Some(v) => lua
-                    .unpack(v)
-                    .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?,
-                None => {
-                    return Err(mlua::Error::RuntimeError(
-                        "keymap.set_global requires key argument".to_string(),
-                    ))
-                }
-            };
-
-            // Second arg: handler (required) - string or function
-            let handler_val = match args_iter.next() {
-                Some(v) => v,
-                None => {
-                    return Err(mlua::Error::RuntimeError(
-                        "keymap.set_global requires handler argument".to_string(),
-                    ))
-                }
-            };
-
-            // Parse handler
-            let handler = if let Ok(action_name) = lua.unpack::<String>(handler_val.clone()) {
-                // Built-in action
-                if let Some(builtin) = BuiltInHotkey::from_name(&action_name) {
-                    GlobalHandler::BuiltIn(builtin)
-                } else {
-                    return Err(mlua::Error::RuntimeError(format!(
-                        "Unknown global action: '{}'. Available: toggle_launcher",
-                        action_name
-                    )));
-                }
-            } else if let Ok(func) = lua.unpack::<Function>(handler_val) {
-                // Lua function binding - store in registry
-                let id = generate_handler_id();
-                let func_ref = LuaFunctionRef::from_function(lua, func, id.clone())?;
-                registry.keymap().store_lua_handler(id.clone(), func_ref);
-                GlobalHandler::Function { id }
-            } else {
-                return Err(mlua::Error::RuntimeError(
-                    "handler must be string or function".to_string(),
-                ));
-            };
-
-            registry.keymap().set_global(PendingHotkey { key, handler });
-            Ok(())
-        })?;
-        keymap_table.set("set_global", set_global_fn)?;
-    }
-
-    // lux.keymap.del_global(key)
-    //
-    // Remove a global hotkey.
-    //
-    // Examples:
-    //   lux.keymap.del_global("cmd+space")
-    {
-        let registry = Arc::clone(&registry);
-        let del_global_fn = lua.create_function(move |lua, key: Value| {
-            let key: String = lua
-                .unpack(key)
-                .map_err(|_| mlua::Error::RuntimeError("key must be a string".to_string()))?;
-
-            let removed = registry.keymap().del_global(&key);
-            Ok(removed)
-        })?;
-        keymap_table.set("del_global", del_global_fn)?;
-    }
-
-    lux.set("keymap", keymap_table)?;
-
-    // lux.shell - Shell command execution namespace
-    //
-    // Usage:
-    //   lux.shell("open", path)         -- async fire-and-forget
-    //   lux.shell.sync("ls", "-la")     -- blocking, returns output
-    //   lux.shell.run({cmd, cwd, env})  -- advanced options
-    {
-        let shell_table = lua.create_table()?;
-
-        // lux.shell.sync(command) - Blocking execution, returns output
-        let sync_fn = lua.create_function(|lua, command: String| {
-            use std::io::Read;
-            use std::process::{Command, Stdio};
-            use std::time::Duration;
-            use wait_timeout::ChildExt;
-
-            let timeout_ms = 30_000u64;
-
-            let mut cmd = Command::new("sh");
-            cmd.args(["-c", &command])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-
-            let mut child = cmd
-                .spawn()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Command spawn failed: {}", e)))?;
-
-            let timeout = Duration::from_millis(timeout_ms);
-
-            let status = match child.wait_timeout(timeout) {
-                Ok(Some(status)) => status,
-                Ok(None) => {
-                    let _ = child.kill();
-                    let _ = child.wait();
-
-                    let result = lua.create_table()?;
-                    result.set("stdout", "")?;
-                    result.set(
-                        "stderr",
-                        format!("Command timed out after {}ms", timeout_ms),
-                    )?;
-                    result.set("exit_code", -1)?;
-                    result.set("success", false)?;
-                    result.set("timed_out", true)?;
-                    return Ok(result);
-                }
-                Err(e) => {
-                    return Err(mlua::Error::RuntimeError(format!(
-                        "Command wait failed: {}",
-                        e
-                    )));
-                }
-            };
-
-            let mut stdout = String::new();
-            let mut stderr = String::new();
-
-            if let Some(mut stdout_handle) = child.stdout.take() {
-                let _ = stdout_handle.read_to_string(&mut stdout);
-            }
-            if let Some(mut stderr_handle) = child.stderr.take() {
-                let _ = stderr_handle.read_to_string(&mut stderr);
-            }
-
-            let result = lua.create_table()?;
-            result.set("stdout", stdout)?;
-            result.set("stderr", stderr)?;
-            result.set("exit_code", status.code().unwrap_or(-1))?;
-            result.set("success", status.success())?;
-            result.set("timed_out", false)?;
-
-            Ok(result)
-        })?;
-        shell_table.set("sync", sync_fn)?;
-
-        // lux.shell.run({ cmd, cwd?, env?, timeout_ms? }) - Advanced options
-        let run_fn = lua.create_function(|lua, opts: Table| {
-            use std::io::Read;
-            use std::process::{Command, Stdio};
-            use std::time::Duration;
-            use wait_timeout::ChildExt;
-
-            let command: String = opts.get("cmd").map_err(|_| {
-                mlua::Error::RuntimeError("shell.run requires 'cmd' field".to_string())
-            })?;
-
-            let timeout_ms = opts
-                .get::<Option<u64>>("timeout_ms")
-                .ok()
-                .flatten()
-                .unwrap_or(30_000);
-
-            let cwd = opts.get::<Option<String>>("cwd").ok().flatten();
-
-            let env: Option<Table> = opts.get("env").ok();
-
-            let mut cmd = Command::new("sh");
-            cmd.args(["-c", &command])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped());
-
-            if let Some(dir) = cwd {
-                cmd.current_dir(dir);
-            }
-
-            if let Some(env_table) = env {
-                for (key, value) in env_table.pairs::<String, String>().flatten() {
-                    cmd.env(key, value);
-                }
-            }
-
-            let mut child = cmd
-                .spawn()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Command spawn failed: {}", e)))?;
-
-            let timeout = Duration::from_millis(timeout_ms);
-
-            let status = match child.wait_timeout(timeout) {
-                Ok(Some(status)) => status,
-                Ok(None) => {
-                    let _ = child.kill();
-                    let _ = child.wait();
-
-                    let result = lua.create_table()?;
-                    result.set("stdout", "")?;
-                    result.set(
-                        "stderr",
-                        format!("Command timed out after {}ms", timeout_ms),
-                    )?;
-                    result.set("exit_code", -1)?;
-                    result.set("success", false)?;
-                    result.set("timed_out", true)?;
-                    return Ok(result);
-                }
-                Err(e) => {
-                    return Err(mlua::Error::RuntimeError(format!(
-                        "Command wait failed: {}",
-                        e
-                    )));
-                }
-            };
-
-            let mut stdout = String::new();
-            let mut stderr = String::new();
-
-            if let Some(mut stdout_handle) = child.stdout.take() {
-                let _ = stdout_handle.read_to_string(&mut stdout);
-            }
-            if let Some(mut stderr_handle) = child.stderr.take() {
-                let _ = stderr_handle.read_to_string(&mut stderr);
-            }
-
-            let result = lua.create_table()?;
-            result.set("stdout", stdout)?;
-            result.set("stderr", stderr)?;
-            result.set("exit_code", status.code().unwrap_or(-1))?;
-            result.set("success", status.success())?;
-            result.set("timed_out", false)?;
-
-            Ok(result)
-        })?;
-        shell_table.set("run", run_fn)?;
-
-        // Set __call metamethod for lux.shell("command", ...) - fire-and-forget
-        let metatable = lua.create_table()?;
-        let call_fn = lua.create_function(|_lua, args: MultiValue| {
-            use std::process::{Command, Stdio};
-
-            let mut args_iter = args.into_iter();
-            args_iter.next(); // Skip 'self' (the shell table)
-
-            // Collect all arguments as strings and join them
-            let parts: Vec<String> = args_iter
-                .filter_map(|v| match v {
-                    Value::String(s) => Some(s.to_str().ok()?.to_string()),
-                    Value::Number(n) => Some(n.to_string()),
-                    Value::Integer(i) => Some(i.to_string()),
-                    _ => None,
-                })
-                .collect();
-
-            if parts.is_empty() {
-                return Err(mlua::Error::RuntimeError(
-                    "shell() requires at least one argument".to_string(),
-                ));
-            }
-
-            let command = parts.join(" ");
-
-            // Fire-and-forget: spawn detached process
-            Command::new("sh")
-                .args(["-c", &command])
-                .stdout(Stdio::null())
-                .stderr(Stdio::null())
-                .spawn()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Command spawn failed: {}", e)))?;
-
-            Ok(())
-        })?;
-        metatable.set("__call", call_fn)?;
-        shell_table.set_metatable(Some(metatable))?;
-
-        lux.set("shell", shell_table)?;
-    }
-
-    // lux.icon(app_path) - Get icon file path for macOS app (converts to PNG)
-    {
-        let icon_fn = lua.create_function(|_lua, app_path: String| {
-            use std::process::Command;
-            use std::path::Path;
-
-            // Create cache directory
-            let cache_dir = dirs::cache_dir()
-                .unwrap_or_else(|| std::path::PathBuf::from("/tmp"))
-                .join("lux")
-                .join("icons");
-            std::fs::create_dir_all(&cache_dir).ok();
-
-            // Generate cache filename from app path hash
-            let hash = {
-                use std::collections::hash_map::DefaultHasher;
-                use std::hash::{Hash, Hasher};
-                let mut hasher = DefaultHasher::new();
-                app_path.hash(&mut hasher);
-                hasher.finish()
-            };
-            let cached_png = cache_dir.join(format!("{:x}.png", hash));
-
-            // Return cached version if exists
-            if cached_png.exists() {
-                return Ok(Some(cached_png.to_string_lossy().to_string()));
-            }
-
-            // Find and convert .icns to PNG
-            let script = format!(
-                r#"
-                icon_name=$(/usr/bin/defaults read "{}/Contents/Info.plist" CFBundleIconFile 2>/dev/null || echo "AppIcon")
-                icon_name="${{icon_name%.icns}}.icns"
-                icon_path="{}/Contents/Resources/$icon_name"
-                if [ ! -f "$icon_path" ]; then
-                    icon_path="{}/Contents/Resources/AppIcon.icns"
-                fi
-                if [ -f "$icon_path" ]; then
-                    /usr/bin/sips -s format png -z 64 64 "$icon_path" --out "{}" >/dev/null 2>&1 && echo "{}"
-                fi
-                "#,
-                app_path, app_path, app_path,
-                cached_png.display(), cached_png.display()
-            );
-
-            let output = Command::new("sh")
-                .args(["-c", &script])
-                .output()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Icon conversion failed: {}", e)))?;
-
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if path.is_empty() || !Path::new(&path).exists() {
-                Ok(None)
-            } else {
-                Ok(Some(path))
-            }
-        })?;
-        lux.set("icon", icon_fn)?;
-    }
-
-    // lux.clipboard - Clipboard operations
-    {
-        let clipboard_table = lua.create_table()?;
-
-        // lux.clipboard.read() - Read text from clipboard
-        let read_fn = lua.create_function(|_lua, ()| {
-            use std::process::Command;
-
-            let output = Command::new("pbpaste")
-                .output()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Clipboard read failed: {}", e)))?;
-
-            if output.status.success() {
-                Ok(Some(String::from_utf8_lossy(&output.stdout).to_string()))
-            } else {
-                Ok(None)
-            }
-        })?;
-        clipboard_table.set("read", read_fn)?;
-
-        // lux.clipboard.write(text) - Write text to clipboard
-        let write_fn = lua.create_function(|_lua, text: String| {
-            use std::io::Write;
-            use std::process::{Command, Stdio};
-
-            let mut child = Command::new("pbcopy")
-                .stdin(Stdio::piped())
-                .spawn()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Clipboard write failed: {}", e)))?;
-
-            if let Some(mut stdin) = child.stdin.take() {
-                stdin.write_all(text.as_bytes()).map_err(|e| {
-                    mlua::Error::RuntimeError(format!("Clipboard write failed: {}", e))
-                })?;
-            }
-
-            let status = child
-                .wait()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Clipboard write failed: {}", e)))?;
-
-            Ok(status.success())
-        })?;
-        clipboard_table.set("write", write_fn)?;
-
-        lux.set("clipboard", clipboard_table)?;
-    }
-
-    // lux.fs - Filesystem operations
-    {
-        let fs_table = lua.create_table()?;
-
-        // lux.fs.read(path) - Read file contents
-        let read_fn =
-            lua.create_function(|_lua, path: String| match std::fs::read_to_string(&path) {
-                Ok(content) => Ok(Some(content)),
-                Err(_) => Ok(None),
-            })?;
-        fs_table.set("read", read_fn)?;
-
-        // lux.fs.write(path, content) - Write content to file
-        let write_fn = lua.create_function(|_lua, (path, content): (String, String)| {
-            std::fs::write(&path, content)
-                .map_err(|e| mlua::Error::RuntimeError(format!("File write failed: {}", e)))?;
-            Ok(true)
-        })?;
-        fs_table.set("write", write_fn)?;
-
-        // lux.fs.exists(path) - Check if path exists
-        let exists_fn =
-            lua.create_function(|_lua, path: String| Ok(std::path::Path::new(&path).exists()))?;
-        fs_table.set("exists", exists_fn)?;
-
-        // lux.fs.is_dir(path) - Check if path is a directory
-        let is_dir_fn =
-            lua.create_function(|_lua, path: String| Ok(std::path::Path::new(&path).is_dir()))?;
-        fs_table.set("is_dir", is_dir_fn)?;
-
-        // lux.fs.list(dir) - List directory contents
-        let list_fn = lua.create_function(|lua, dir: String| {
-            let entries: Vec<String> = std::fs::read_dir(&dir)
-                .map_err(|e| mlua::Error::RuntimeError(format!("Directory read failed: {}", e)))?
-                .filter_map(|entry| {
-                    entry.ok().and_then(|e| {
-                        e.path()
-                            .file_name()
-                            .map(|n| n.to_string_lossy().to_string())
-                    })
-                })
-                .collect();
-
-            let table = lua.create_table()?;
-            for (i, name) in entries.iter().enumerate() {
-                table.set(i + 1, name.as_str())?;
-            }
-            Ok(table)
-        })?;
-        fs_table.set("list", list_fn)?;
-
-        // lux.fs.glob(pattern) - Glob pattern matching
-        let glob_fn = lua.create_function(|lua, pattern: String| {
-            use std::process::Command;
-
-            // Use shell glob expansion
-            let output = Command::new("sh")
-                .args(["-c", &format!("ls -1 {} 2>/dev/null || true", pattern)])
-                .output()
-                .map_err(|e| mlua::Error::RuntimeError(format!("Glob failed: {}", e)))?;
-
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let paths: Vec<&str> = output_str
-                .trim()
-                .split('\n')
-                .filter(|s| !s.is_empty())
-                .collect();
-
-            let table = lua.create_table()?;
-            for (i, path) in paths.iter().enumerate() {
-                table.set(i + 1, *path)?;
-            }
-            Ok(table)
-        })?;
-        fs_table.set("glob", glob_fn)?;
-
-        // lux.fs.home() - Get home directory
-        let home_fn = lua.create_function(|_lua, ()| {
-            Ok(dirs::home_dir().map(|p| p.to_string_lossy().to_string()))
-        })?;
-        fs_table.set("home", home_fn)?;
-
-        // lux.fs.config() - Get config directory
-        let config_fn = lua.create_function(|_lua, ()| {
-            Ok(dirs::config_dir().map(|p| p.to_string_lossy().to_string()))
-        })?;
-        fs_table.set("config", config_fn)?;
-
-        lux.set("fs", fs_table)?;
-    }
-
-    // lux.ui - UI control operations
-    // Note: These create effects that need to be handled by the UI layer
-    {
-        let ui_table = lua.create_table()?;
-
-        // lux.ui.show() - Show the launcher window
-        let show_fn = lua.create_function(|_lua, ()| {
-            // TODO: Connect to UI layer - for now just log
-            tracing::debug!("lux.ui.show() called");
-            Ok(())
-        })?;
-        ui_table.set("show", show_fn)?;
-
-        // lux.ui.hide() - Hide the launcher window
-        let hide_fn = lua.create_function(|_lua, ()| {
-            tracing::debug!("lux.ui.hide() called");
-            Ok(())
-        })?;
-        ui_table.set("hide", hide_fn)?;
-
-        // lux.ui.toggle() - Toggle the launcher window
-        let toggle_fn = lua.create_function(|_lua, ()| {
-            tracing::debug!("lux.ui.toggle() called");
-            Ok(())
-        })?;
-        ui_table.set("toggle", toggle_fn)?;
-
-        // lux.ui.notify(message, opts?) - Show a notification
-        let notify_fn =
-            lua.create_function(|_lua, (message, _opts): (String, Option<Table>)| {
-                // TODO: Connect to notification system
-                tracing::info!("Notification: {}", message);
-                Ok(())
-            })?;
-        ui_table.set("notify", notify_fn)?;
-
-        lux.set("ui", ui_table)?;
-    }
-
-    // lux.item_id(item) - Get stable identity for an item
-    {
-        let item_id_fn = lua.create_function(|_lua, item: Table| {
-            // Try to get 'id' field first, then fall back to 'title'
-            if let Ok(Some(id)) = item.get::<Option<String>>("id") {
-                return Ok(id);
-            }
-            if let Ok(Some(title)) = item.get::<Option<String>>("title") {
-                return Ok(title);
-            }
-            Err(mlua::Error::RuntimeError(
-                "item_id: item must have 'id' or 'title' field".to_string(),
-            ))
-        })?;
-        lux.set("item_id", item_id_fn)?;
-    }
-
-    // lux.map_items(result, fn) - Transform items preserving group structure
-    {
-        let map_items_fn = lua.create_function(|lua, (result, mapper): (Table, Function)| {
-            // Check if result has 'groups' field
-            if let Ok(groups) = result.get::<Table>("groups") {
-                // Has groups - map each group's items
-                let new_groups = lua.create_table()?;
-                for pair in groups.pairs::<i64, Table>() {
-                    let (idx, group) = pair?;
-                    let new_group = lua.create_table()?;
-
-                    // Copy title if present
-                    if let Ok(Some(t)) = group.get::<Option<String>>("title") {
-                        new_group.set("title", t)?;
-                    }
-
-                    // Map items
-                    if let Ok(items) = group.get::<Table>("items") {
-                        let new_items = lua.create_table()?;
-                        for item_pair in items.pairs::<i64, Value>() {
-                            let (i, item) = item_pair?;
-                            let mapped: Value = mapper.call(item)?;
-                            new_items.set(i, mapped)?;
-                        }
-                        new_group.set("items", new_items)?;
-                    }
-
-                    new_groups.set(idx, new_group)?;
-                }
-
-                let new_result = lua.create_table()?;
-                new_result.set("groups", new_groups)?;
-                Ok(new_result)
-            } else {
-                // No groups - treat as flat array of items
-                let new_items = lua.create_table()?;
-                for pair in result.pairs::<i64, Value>() {
-                    let (idx, item) = pair?;
-                    let mapped: Value = mapper.call(item)?;
-                    new_items.set(idx, mapped)?;
-                }
-                Ok(new_items)
-            }
-        })?;
-        lux.set("map_items", map_items_fn)?;
-    }
+    // Renamed and removed functions (`compat::SHIMS`) are still there for
+    // init.lua and for plugins targeting an older version, with a warning.
+    lux.set("api_version", API_VERSION)?;
+
+    views::register(lua, &lux, &registry)?;
+    hooks::register(lua, &lux, &registry)?;
+    keymap::register(lua, &lux, &registry)?;
+    shell::register(lua, &lux)?;
+    desktop::register(lua, &lux, &registry)?;
+    permissions::register(lua, &lux)?;
+    calendar::register(lua, &lux)?;
+    websearch::register(lua, &lux, &registry)?;
+    actions::register(lua, &registry)?;
+    media::register(lua, &lux, &registry)?;
+    ssh::register(lua, &lux, &registry)?;
+    currency::register(lua, &lux, &registry)?;
+    dates::register(lua, &lux)?;
+    capture::register(lua, &lux, &registry)?;
+    files::register(lua, &lux, &registry)?;
+    qr::register(lua, &lux)?;
+    worldclock::register(lua, &lux, &registry)?;
+    pins::register(lua, &lux, &registry)?;
+    basket::register(lua, &lux, &registry)?;
+    git::register(lua, &lux, &registry)?;
+    editor::register(lua, &lux, &registry)?;
+    results::register(lua, &lux, &registry)?;
+    routes::register(lua, &lux, &registry)?;
+    matching::register(lua, &lux, &registry)?;
+    runtime::register(lua, &lux, &registry)?;
+    metrics::register(lua, &lux, &registry)?;
+    autostart::register(lua, &lux)?;
+    secrets::register(lua, &lux)?;
+    oauth::register(lua, &lux, &registry)?;
+    plugins::register(lua, &lux, &registry)?;
+    timers::register(lua, &lux, &registry)?;
+    undo::register(lua, &lux, &registry)?;
+    query::register(lua, &lux, &registry)?;
+    profile::register(lua, &lux, &registry)?;
+    i18n::register(lua, &lux)?;
+    theme::register(lua, &lux, &registry)?;
+    server::register(lua, &lux, &registry)?;
+    alfred::register(lua, &lux)?;
+    builtin::register(lua, &lux, &registry)?;
+    http::register(lua, &lux, &registry)?;
+    websocket::register(lua, &lux, &registry)?;
+    text::register(lua, &lux, &registry)?;
+    fs::register(lua, &lux, &registry)?;
+    ui::register(lua, &lux, &registry)?;
+    macros::register(lua, &lux, &registry)?;
+    daemons::register(lua, &lux, &registry)?;
+    items::register(lua, &lux)?;
+
+    // init.lua has no manifest; it gets the shims of every version
+    install_shims(
+        lua,
+        &lux,
+        MIN_API_VERSION,
+        "init.lua",
+        registry.config_warnings(),
+    )?;
 
     // Set as global
     lua.globals().set("lux", lux)?;
@@ -876,42 +209,30 @@ pub fn register_lux_api(lua: &Lua, registry: Arc<PluginRegistry>) -> LuaResult<(
     Ok(())
 }
 
-/// Convert a Lua value to a JSON value.
-pub fn lua_value_to_json(_lua: &Lua, value: Value) -> LuaResult<serde_json::Value> {
-    match value {
-        Value::Nil => Ok(serde_json::Value::Null),
-        Value::Boolean(b) => Ok(serde_json::Value::Bool(b)),
-        Value::Integer(i) => Ok(serde_json::Value::Number(i.into())),
-        Value::Number(n) => {
-            if let Some(num) = serde_json::Number::from_f64(n) {
-                Ok(serde_json::Value::Number(num))
-            } else {
-                Ok(serde_json::Value::Null)
-            }
+/// Convert groups to the list `ctx:set_groups()` takes.
+fn groups_to_lua(lua: &Lua, groups: &[Group]) -> LuaResult<Table> {
+    let result = lua.create_table()?;
+    for group in groups {
+        let table = lua.create_table()?;
+        if let Some(title) = &group.title {
+            table.set("title", title.as_str())?;
         }
-        Value::String(s) => Ok(serde_json::Value::String(s.to_str()?.to_string())),
-        Value::Table(t) => {
-            // Check if it's an array or object
-            let is_array = t.clone().pairs::<i64, Value>().all(|r| r.is_ok());
-
-            if is_array && t.raw_len() > 0 {
-                let mut arr = Vec::new();
-                for pair in t.pairs::<i64, Value>() {
-                    let (_, v) = pair?;
-                    arr.push(lua_value_to_json(_lua, v)?);
-                }
-                Ok(serde_json::Value::Array(arr))
-            } else {
-                let mut obj = serde_json::Map::new();
-                for pair in t.pairs::<String, Value>() {
-                    let (k, v) = pair?;
-                    obj.insert(k, lua_value_to_json(_lua, v)?);
-                }
-                Ok(serde_json::Value::Object(obj))
-            }
+        if group.collapsible {
+            table.set("collapsible", true)?;
         }
-        _ => Ok(serde_json::Value::Null),
+        let items = lua.create_table()?;
+        for item in &group.items {
+            items.push(bridge::item_to_lua(lua, item)?)?;
+        }
+        table.set("items", items)?;
+        result.push(table)?;
     }
+    Ok(result)
+}
+
+/// Convert a Lua value to a JSON value.
+pub fn lua_value_to_json(_lua: &Lua, value: Value) -> LuaResult<serde_json::Value> {
+    lux_core::lua::lua_to_json(value)
 }
 
 /// Convert a JSON value to a Lua value.
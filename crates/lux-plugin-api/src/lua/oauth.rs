@@ -0,0 +1,125 @@
+//! Lua bindings for OAuth sign-in.
+//!
+//! This module provides:
+//! - `lux.oauth.authorize/token/status/logout()` - OAuth 2.0 sign-in, tokens kept in the Keychain
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mlua::{Function, Lua, Result as LuaResult, Table};
+
+use crate::keymap::generate_handler_id;
+use crate::oauth::{OAuthConfig, OAuthError, OAuthToken};
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+
+/// Register `lux.oauth`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.oauth - OAuth 2.0 sign-in (authorization code with PKCE, see crate::oauth)
+    //
+    // Usage:
+    //   lux.oauth.authorize({
+    //       name = "github",                                   -- letters, digits, '-', and '_'
+    //       auth_url = "https://github.com/login/oauth/authorize",
+    //       token_url = "https://github.com/login/oauth/access_token",
+    //       client_id = "Iv1.abc",
+    //       client_secret = "...",                             -- optional, for providers that want one
+    //       scopes = { "repo", "read:user" },
+    //       params = { access_type = "offline" },              -- optional extra authorization parameters
+    //       port = 8765,                                       -- optional fixed redirect port
+    //       timeout = 300,                                     -- seconds to sign in, default 300
+    //       on_done = function(token, err) end,                -- token = { access_token, token_type, expires_at, scope }
+    //   })
+    //   lux.oauth.token("github")    -- a valid access token, or nil
+    //   lux.oauth.status("github")   -- "pending", "authorized", "expired", "refreshing", or nil
+    //   lux.oauth.logout("github")   -- forget the tokens; true if there were any
+    //
+    // authorize() opens the browser and returns the consent page URL right
+    // away; on_done runs once the provider redirected back to
+    // http://127.0.0.1:<port>/callback and the tokens are stored. token()
+    // never waits on the network: an expired token is refreshed in the
+    // background, and token() gives nil (status "refreshing") until it is.
+    {
+        let oauth_table = lua.create_table()?;
+        let fail = |e: OAuthError| mlua::Error::RuntimeError(format!("oauth: {}", e));
+
+        let flows = registry.oauth();
+        let authorize_fn = lua.create_function(move |lua, opts: Table| {
+            let field = |key: &str| -> LuaResult<String> {
+                opts.get::<Option<String>>(key)?.ok_or_else(|| {
+                    mlua::Error::RuntimeError(format!("oauth.authorize requires a '{}' field", key))
+                })
+            };
+            let name = field("name")?;
+            let mut config =
+                OAuthConfig::new(field("auth_url")?, field("token_url")?, field("client_id")?);
+            config.client_secret = opts.get("client_secret")?;
+            config.scopes = opts
+                .get::<Option<Vec<String>>>("scopes")?
+                .unwrap_or_default();
+            if let Some(params) = opts.get::<Option<Table>>("params")? {
+                for pair in params.pairs::<String, String>() {
+                    config.params.push(pair?);
+                }
+                config.params.sort();
+            }
+            config.port = opts.get("port")?;
+            let timeout = match opts.get::<Option<f64>>("timeout")? {
+                Some(secs) if secs > 0.0 => Duration::from_secs_f64(secs),
+                _ => crate::oauth::DEFAULT_TIMEOUT,
+            };
+            let on_done = match opts.get::<Option<Function>>("on_done")? {
+                Some(func) => {
+                    let key = format!("oauth:done:{}", generate_handler_id());
+                    Some(LuaFunctionRef::from_function(lua, func, key)?)
+                }
+                None => None,
+            };
+
+            let key = on_done.as_ref().map(|on_done| on_done.key.clone());
+            flows
+                .authorize(&name, config, timeout, on_done)
+                .or_else(|e| {
+                    if let Some(key) = key {
+                        lua.unset_named_registry_value(&key)?;
+                    }
+                    Err(fail(e))
+                })
+        })?;
+        oauth_table.set("authorize", authorize_fn)?;
+
+        let flows = registry.oauth();
+        let token_fn =
+            lua.create_function(move |_lua, name: String| flows.access_token(&name).map_err(fail))?;
+        oauth_table.set("token", token_fn)?;
+
+        let flows = registry.oauth();
+        let status_fn = lua.create_function(move |_lua, name: String| {
+            Ok(flows
+                .status(&name)
+                .map_err(fail)?
+                .map(|status| status.name()))
+        })?;
+        oauth_table.set("status", status_fn)?;
+
+        let logout_fn = lua
+            .create_function(move |_lua, name: String| crate::oauth::logout(&name).map_err(fail))?;
+        oauth_table.set("logout", logout_fn)?;
+
+        lux.set("oauth", oauth_table)?;
+    }
+
+    Ok(())
+}
+
+/// Convert tokens to the table `lux.oauth.authorize()` passes to `on_done`.
+///
+/// The refresh token stays out of Lua; `lux.oauth.token()` uses it.
+pub fn oauth_token_to_lua(lua: &Lua, token: &OAuthToken) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("access_token", token.access_token.as_str())?;
+    table.set("token_type", token.token_type.as_str())?;
+    table.set("expires_at", token.expires_at)?;
+    table.set("scope", token.scope.clone())?;
+    Ok(table)
+}
@@ -0,0 +1,210 @@
+//! Declarative action outputs.
+//!
+//! This module provides:
+//! - `ActionOutput` - What an action with an `output` table instead of a handler does
+//! - `ActionOutput::handler()` - The handler the engine runs for it
+//!
+//! Simple actions don't need a handler function:
+//!
+//! ```lua
+//! get_actions = function(item)
+//!   return {
+//!     { title = "Copy", output = { copy = item.data.url } },
+//!     { title = "Open", output = { open = item.data.url } },
+//!   }
+//! end
+//! ```
+//!
+//! - `copy = text` - Copy the text and close the launcher
+//! - `paste = text` - Paste the text into the frontmost app
+//! - `open = target` - Open a URL or path with its default app
+//! - `detail = text` - Show the text in a view, one row per line
+//!
+//! The desktop layer (`desktop::current()`) does the work, as it does for
+//! `lux.clipboard`.
+
+use mlua::{AnyUserData, Function, Lua, ObjectLike, Result as LuaResult, Table, Value};
+
+use lux_core::i18n::t;
+use lux_core::{Group, Item};
+
+use crate::desktop;
+
+/// ID of the view a `detail` output pushes.
+pub const DETAIL_VIEW_ID: &str = "builtin.detail";
+
+/// What an action with an `output` table does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionOutput {
+    /// Copy the text and close the launcher.
+    Copy(String),
+    /// Paste the text into the frontmost app.
+    Paste(String),
+    /// Open a URL or path with its default app.
+    Open(String),
+    /// Show the text in a view, one row per line.
+    Detail(String),
+}
+
+impl ActionOutput {
+    /// Keys an `output` table can have (exactly one of them).
+    pub const KEYS: [&'static str; 4] = ["copy", "paste", "open", "detail"];
+
+    /// Parse an `output` table.
+    pub fn parse(table: &Table) -> LuaResult<Self> {
+        let mut output = None;
+        for pair in table.pairs::<Value, Value>() {
+            let (key, value) = pair?;
+            let key = match &key {
+                Value::String(key) => key.to_string_lossy(),
+                _ => String::new(),
+            };
+            if !Self::KEYS.contains(&key.as_str()) {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "Unknown action output '{}' (expected one of: {})",
+                    key,
+                    Self::KEYS.join(", ")
+                )));
+            }
+            if output.is_some() {
+                return Err(mlua::Error::RuntimeError(
+                    "An action output has exactly one of copy, paste, open, detail".to_string(),
+                ));
+            }
+            let text = match value {
+                Value::String(text) => text.to_string_lossy(),
+                Value::Integer(n) => n.to_string(),
+                Value::Number(n) => n.to_string(),
+                other => {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "Action output '{}' should be a string, not {}",
+                        key,
+                        other.type_name()
+                    )))
+                }
+            };
+            output = Some(match key.as_str() {
+                "copy" => ActionOutput::Copy(text),
+                "paste" => ActionOutput::Paste(text),
+                "open" => ActionOutput::Open(text),
+                _ => ActionOutput::Detail(text),
+            });
+        }
+        output.ok_or_else(|| {
+            mlua::Error::RuntimeError(
+                "An action output needs one of copy, paste, open, detail".to_string(),
+            )
+        })
+    }
+
+    /// The handler for the action titled `title`, called as `handler(items, ctx)`.
+    pub fn handler(self, lua: &Lua, title: &str) -> LuaResult<Function> {
+        let title = title.to_string();
+        lua.create_function(
+            move |lua, (_items, ctx): (Table, AnyUserData)| match &self {
+                ActionOutput::Copy(text) => {
+                    desktop::current().clipboard_write(text)?;
+                    ctx.call_method::<()>("dismiss", ())
+                }
+                ActionOutput::Paste(text) => {
+                    desktop::current().paste(text)?;
+                    ctx.call_method::<()>("dismiss", ())
+                }
+                ActionOutput::Open(target) => {
+                    desktop::current().open(target)?;
+                    ctx.call_method::<()>("dismiss", ())
+                }
+                ActionOutput::Detail(text) => {
+                    ctx.call_method::<()>("push", detail_view(lua, &title, text.clone())?)
+                }
+            },
+        )
+    }
+}
+
+/// The rows of a detail view: each line containing `query`.
+pub fn detail_groups(text: &str, query: &str) -> Vec<Group> {
+    let query = query.trim().to_lowercase();
+    let items: Vec<Item> = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && line.to_lowercase().contains(&query))
+        .map(|(i, line)| Item::new(format!("line:{}", i + 1), line))
+        .collect();
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::ungrouped(items)]
+}
+
+/// Build the view a `detail` output pushes.
+fn detail_view(lua: &Lua, title: &str, text: String) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", DETAIL_VIEW_ID)?;
+    view.set("title", title)?;
+    view.set("placeholder", t("Filter lines..."))?;
+
+    // search(query, ctx) - the lines containing the query
+    let search_text = text.clone();
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let groups = detail_groups(&search_text, &query);
+        ctx.call_method::<()>("set_groups", super::groups_to_lua(lua, &groups)?)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy the line, or the whole text
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let line: String = item.get("title")?;
+        let actions = lua.create_table()?;
+        for (id, title, text) in [
+            ("copy", t("Copy"), line),
+            ("copy_all", t("Copy All"), text.clone()),
+        ] {
+            let action = lua.create_table()?;
+            action.set("id", id)?;
+            action.set("title", title.as_str())?;
+            action.set("handler", ActionOutput::Copy(text).handler(lua, &title)?)?;
+            actions.push(action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_output() {
+        let lua = Lua::new();
+        let parse = |code: &str| ActionOutput::parse(&lua.load(code).eval::<Table>().unwrap());
+
+        assert_eq!(
+            parse(r#"return { copy = "https://example.com" }"#).unwrap(),
+            ActionOutput::Copy("https://example.com".to_string())
+        );
+        assert_eq!(
+            parse("return { detail = 42 }").unwrap(),
+            ActionOutput::Detail("42".to_string())
+        );
+        assert!(parse("return {}").is_err());
+        assert!(parse(r#"return { copy = "a", open = "b" }"#).is_err());
+        assert!(parse(r#"return { show = "a" }"#)
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown action output 'show'"));
+        assert!(parse("return { open = {} }").is_err());
+    }
+
+    #[test]
+    fn test_detail_groups_filter_lines() {
+        let groups = detail_groups("Name: Lux\n\nLicense: MIT\n", "lic");
+        assert_eq!(groups[0].items.len(), 1);
+        assert_eq!(groups[0].items[0].id, "line:3");
+        assert_eq!(groups[0].items[0].title, "License: MIT");
+        assert!(detail_groups("Name: Lux", "nothing").is_empty());
+    }
+}
@@ -1,6 +1,11 @@
 //! Lua table parsing for Plugin API types.
 //!
 //! This module converts Lua tables into Rust types for views.
+//!
+//! `VIEW_FIELDS` lists every field a view table may have, with its type and
+//! default, for `lux.views.add` and `lux.set_root`. `check_view_table()`
+//! checks a table against it before it is parsed: a wrong type is an error,
+//! while unknown, ignored, and deprecated fields become config warnings.
 
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -8,10 +13,15 @@ use mlua::{Function, Lua, Result as LuaResult, Table, Value};
 
 use crate::types::{LuaFunctionRef, View};
 use crate::views::ViewDefinition;
+use crate::warnings::{ConfigWarning, WarningKind};
+use crate::worldclock::edit_distance;
 use lux_core::SelectionMode;
 
 use super::lua_value_to_json;
 
+/// Shortest allowed `refresh_ms`; faster intervals are raised to this.
+const MIN_REFRESH_MS: u64 = 100;
+
 /// Global counter for generating unique function keys.
 static FUNCTION_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -29,7 +39,7 @@ fn store_function(lua: &Lua, func: Function, prefix: &str) -> LuaResult<LuaFunct
 
 /// Parse a view definition (for lux.set_root or ctx:push).
 ///
-/// Expected table shape:
+/// Expected table shape (types and defaults: `VIEW_FIELDS`):
 /// ```lua
 /// {
 ///   id = "string",            -- optional: stable view identifier
@@ -39,6 +49,11 @@ fn store_function(lua: &Lua, func: Function, prefix: &str) -> LuaResult<LuaFunct
 ///   selection = "single",     -- optional: "single" | "multi" | "custom"
 ///   on_select = function(ctx),-- optional (required if selection = "custom")
 ///   on_submit = function(ctx),-- optional
+///   on_open = function(ctx),  -- optional: after the view is pushed
+///   on_show = function(ctx),  -- optional: launcher shown on this view
+///   on_close = function(ctx), -- optional: after the view is popped or replaced
+///   on_selection_changed = function(selection, ctx), -- optional: multi-select changes
+///   refresh_ms = 1000,        -- optional: re-run search while the view is shown
 ///   view_data = { ... },      -- optional
 /// }
 /// ```
@@ -115,6 +130,18 @@ pub fn parse_view(lua: &Lua, table: Table) -> LuaResult<View> {
         None => None,
     };
 
+    // Optional: lifecycle hooks
+    let on_open_fn = parse_optional_function(lua, &table, "on_open", &view_key)?;
+    let on_show_fn = parse_optional_function(lua, &table, "on_show", &view_key)?;
+    let on_close_fn = parse_optional_function(lua, &table, "on_close", &view_key)?;
+
+    // Optional: multi-select change hook
+    let on_selection_changed_fn =
+        parse_optional_function(lua, &table, "on_selection_changed", &view_key)?;
+
+    // Optional: refresh interval
+    let refresh_ms = parse_refresh_ms(&table)?;
+
     // Optional: view_data
     let view_data = match table.get::<Option<Table>>("view_data")? {
         Some(data_table) => lua_value_to_json(lua, Value::Table(data_table))?,
@@ -130,13 +157,20 @@ pub fn parse_view(lua: &Lua, table: Table) -> LuaResult<View> {
         selection,
         on_select_fn,
         on_submit_fn,
+        on_open_fn,
+        on_show_fn,
+        on_close_fn,
+        on_selection_changed_fn,
         view_data,
+        selected: Vec::new(),
+        refresh_ms,
+        initial_query: None,
     })
 }
 
 /// Parse a view definition for the new API (lux.views.add).
 ///
-/// Expected table shape:
+/// Expected table shape (types and defaults: `VIEW_FIELDS`):
 /// ```lua
 /// {
 ///   id = "string",              -- required: unique view identifier
@@ -145,6 +179,11 @@ pub fn parse_view(lua: &Lua, table: Table) -> LuaResult<View> {
 ///   selection = "single",       -- optional: "single" | "multi"
 ///   search = function(query, ctx),    -- required: returns items
 ///   get_actions = function(item, ctx),-- required: returns actions
+///   on_open = function(ctx),          -- optional: after the view is pushed
+///   on_show = function(ctx),          -- optional: launcher shown on this view
+///   on_close = function(ctx),         -- optional: after the view is popped or replaced
+///   on_selection_changed = function(selection, ctx), -- optional: multi-select changes
+///   refresh_ms = 1000,                -- optional: re-run search while the view is shown
 /// }
 /// ```
 pub fn parse_view_definition(lua: &Lua, table: Table) -> LuaResult<ViewDefinition> {
@@ -174,9 +213,10 @@ pub fn parse_view_definition(lua: &Lua, table: Table) -> LuaResult<ViewDefinitio
         None => SelectionMode::Single,
     };
 
-    // Required: search function
+    // Required: search function ('source' is its deprecated name)
     let search_fn = table
         .get::<Function>("search")
+        .or_else(|_| table.get::<Function>("source"))
         .map_err(|_| mlua::Error::RuntimeError("View missing required 'search' function".into()))?;
     let search_fn = store_function(lua, search_fn, &format!("view:{}:search", id))?;
 
@@ -186,6 +226,19 @@ pub fn parse_view_definition(lua: &Lua, table: Table) -> LuaResult<ViewDefinitio
     })?;
     let get_actions_fn = store_function(lua, get_actions_fn, &format!("view:{}:get_actions", id))?;
 
+    // Optional: lifecycle hooks
+    let view_key = format!("view:{}", id);
+    let on_open_fn = parse_optional_function(lua, &table, "on_open", &view_key)?;
+    let on_show_fn = parse_optional_function(lua, &table, "on_show", &view_key)?;
+    let on_close_fn = parse_optional_function(lua, &table, "on_close", &view_key)?;
+
+    // Optional: multi-select change hook
+    let on_selection_changed_fn =
+        parse_optional_function(lua, &table, "on_selection_changed", &view_key)?;
+
+    // Optional: refresh interval
+    let refresh_ms = parse_refresh_ms(&table)?;
+
     tracing::debug!(
         "Parsed view definition '{}': title={:?}, placeholder={:?}, selection={:?}",
         id,
@@ -201,9 +254,363 @@ pub fn parse_view_definition(lua: &Lua, table: Table) -> LuaResult<ViewDefinitio
         selection,
         search_fn,
         get_actions_fn,
+        on_open_fn,
+        on_show_fn,
+        on_close_fn,
+        on_selection_changed_fn,
+        refresh_ms,
     })
 }
 
+/// Parse the optional `refresh_ms` field (at least `MIN_REFRESH_MS`).
+pub(crate) fn parse_refresh_ms(table: &Table) -> LuaResult<Option<u64>> {
+    Ok(table
+        .get::<Option<u64>>("refresh_ms")?
+        .filter(|&ms| ms > 0)
+        .map(|ms| ms.max(MIN_REFRESH_MS)))
+}
+
+/// Store the optional function `table[name]` under `<view_key>:<name>`.
+fn parse_optional_function(
+    lua: &Lua,
+    table: &Table,
+    name: &str,
+    view_key: &str,
+) -> LuaResult<Option<LuaFunctionRef>> {
+    match table.get::<Option<Function>>(name)? {
+        Some(func) => Ok(Some(store_function(
+            lua,
+            func,
+            &format!("{}:{}", view_key, name),
+        )?)),
+        None => Ok(None),
+    }
+}
+
+// =============================================================================
+// View Schema
+// =============================================================================
+
+/// The function a view table is given to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewApi {
+    /// `lux.views.add(def)`.
+    Add,
+    /// `lux.set_root(view)`.
+    Root,
+}
+
+impl ViewApi {
+    fn name(&self) -> &'static str {
+        match self {
+            ViewApi::Add => "lux.views.add",
+            ViewApi::Root => "lux.set_root",
+        }
+    }
+}
+
+/// Lua type of a view field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    /// A string (numbers are converted).
+    String,
+    /// A function.
+    Function,
+    /// A whole number.
+    Integer,
+    /// A table.
+    Table,
+}
+
+impl FieldType {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            FieldType::String => {
+                matches!(
+                    value,
+                    Value::String(_) | Value::Integer(_) | Value::Number(_)
+                )
+            }
+            FieldType::Function => matches!(value, Value::Function(_)),
+            FieldType::Integer => match value {
+                Value::Integer(_) => true,
+                Value::Number(n) => n.fract() == 0.0,
+                _ => false,
+            },
+            FieldType::Table => matches!(value, Value::Table(_)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            FieldType::String => "a string",
+            FieldType::Function => "a function",
+            FieldType::Integer => "a whole number",
+            FieldType::Table => "a table",
+        }
+    }
+}
+
+/// Whether an API reads a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Presence {
+    /// The view is rejected without it.
+    Required,
+    /// `ViewField::default` is used without it.
+    Optional,
+    /// Not read by this API; setting it is warned about.
+    Ignored,
+}
+
+/// One field of a view table.
+#[derive(Debug, Clone, Copy)]
+pub struct ViewField {
+    /// Field name.
+    pub name: &'static str,
+    /// Lua type it takes.
+    pub ty: FieldType,
+    /// Whether `lux.views.add` reads it.
+    pub add: Presence,
+    /// Whether `lux.set_root` reads it.
+    pub root: Presence,
+    /// What is used when it's missing.
+    pub default: &'static str,
+    /// The field to use instead, if it is deprecated.
+    pub replaced_by: Option<&'static str>,
+}
+
+impl ViewField {
+    const fn new(
+        name: &'static str,
+        ty: FieldType,
+        add: Presence,
+        root: Presence,
+        default: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            ty,
+            add,
+            root,
+            default,
+            replaced_by: None,
+        }
+    }
+
+    const fn deprecated(name: &'static str, ty: FieldType, replaced_by: &'static str) -> Self {
+        Self {
+            name,
+            ty,
+            add: Presence::Optional,
+            root: Presence::Optional,
+            default: "",
+            replaced_by: Some(replaced_by),
+        }
+    }
+
+    /// Whether `api` reads this field.
+    pub fn presence(&self, api: ViewApi) -> Presence {
+        match api {
+            ViewApi::Add => self.add,
+            ViewApi::Root => self.root,
+        }
+    }
+}
+
+/// Every field of a view table, with its type and default.
+pub const VIEW_FIELDS: &[ViewField] = &[
+    ViewField::new(
+        "id",
+        FieldType::String,
+        Presence::Required,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "title",
+        FieldType::String,
+        Presence::Optional,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "placeholder",
+        FieldType::String,
+        Presence::Optional,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "selection",
+        FieldType::String,
+        Presence::Optional,
+        Presence::Optional,
+        "\"single\" (\"multi\"; \"custom\" for the root view)",
+    ),
+    ViewField::new(
+        "search",
+        FieldType::Function,
+        Presence::Required,
+        Presence::Required,
+        "required",
+    ),
+    ViewField::deprecated("source", FieldType::Function, "search"),
+    ViewField::new(
+        "get_actions",
+        FieldType::Function,
+        Presence::Required,
+        Presence::Optional,
+        "no actions",
+    ),
+    ViewField::new(
+        "on_select",
+        FieldType::Function,
+        Presence::Ignored,
+        Presence::Optional,
+        "none (required with selection = \"custom\")",
+    ),
+    ViewField::new(
+        "on_submit",
+        FieldType::Function,
+        Presence::Ignored,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "on_open",
+        FieldType::Function,
+        Presence::Optional,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "on_show",
+        FieldType::Function,
+        Presence::Optional,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "on_close",
+        FieldType::Function,
+        Presence::Optional,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "on_selection_changed",
+        FieldType::Function,
+        Presence::Optional,
+        Presence::Optional,
+        "none",
+    ),
+    ViewField::new(
+        "refresh_ms",
+        FieldType::Integer,
+        Presence::Optional,
+        Presence::Optional,
+        "off (at least 100)",
+    ),
+    ViewField::new(
+        "view_data",
+        FieldType::Table,
+        Presence::Ignored,
+        Presence::Optional,
+        "{}",
+    ),
+];
+
+/// Check a view table given to `api` against `VIEW_FIELDS`.
+///
+/// A field of the wrong type is an error. Unknown fields, fields `api`
+/// ignores, and deprecated fields are returned as warnings, located at the
+/// view's search function.
+pub fn check_view_table(table: &Table, api: ViewApi) -> LuaResult<Vec<ConfigWarning>> {
+    let subject = match table.get::<Value>("id")? {
+        Value::String(id) => format!("view '{}'", id.to_string_lossy()),
+        _ if api == ViewApi::Root => "root view".to_string(),
+        _ => "view".to_string(),
+    };
+    let location = view_location(table);
+    let warning = |kind, field: &str, message: String| ConfigWarning {
+        kind,
+        subject: subject.clone(),
+        field: Some(field.to_string()),
+        message,
+        location: location.clone(),
+    };
+
+    let mut warnings = Vec::new();
+    for pair in table.pairs::<Value, Value>() {
+        let (key, value) = pair?;
+        let Value::String(key) = key else {
+            continue;
+        };
+        let key = key.to_string_lossy();
+        match VIEW_FIELDS.iter().find(|field| field.name == key) {
+            Some(field) if field.presence(api) != Presence::Ignored => {
+                if !field.ty.matches(&value) {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "{}: '{}' should be {}, not {}",
+                        subject,
+                        key,
+                        field.ty.name(),
+                        value.type_name()
+                    )));
+                }
+                if let Some(replacement) = field.replaced_by {
+                    warnings.push(warning(
+                        WarningKind::Deprecated,
+                        &key,
+                        format!("'{}' is deprecated; use '{}'", key, replacement),
+                    ));
+                }
+            }
+            Some(_) => warnings.push(warning(
+                WarningKind::UnknownField,
+                &key,
+                format!("'{}' is ignored by {}", key, api.name()),
+            )),
+            None => {
+                let message = match closest_field(&key, api) {
+                    Some(name) => format!("Unknown field '{}' (did you mean '{}'?)", key, name),
+                    None => format!("Unknown field '{}'", key),
+                };
+                warnings.push(warning(WarningKind::UnknownField, &key, message));
+            }
+        }
+    }
+    warnings.sort_by(|a, b| a.field.cmp(&b.field));
+    Ok(warnings)
+}
+
+/// The field `api` reads whose name is closest to `name`, if any is close.
+fn closest_field(name: &str, api: ViewApi) -> Option<&'static str> {
+    VIEW_FIELDS
+        .iter()
+        .filter(|field| field.presence(api) != Presence::Ignored && field.replaced_by.is_none())
+        .map(|field| (edit_distance(name, field.name), field.name))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Where the search function of a view table is defined, if it has one.
+pub(crate) fn view_location(table: &Table) -> Option<String> {
+    ["search", "source"]
+        .into_iter()
+        .find_map(|name| match table.get::<Value>(name) {
+            Ok(Value::Function(func)) => function_location(&func),
+            _ => None,
+        })
+}
+
+/// Where a Lua function is defined (`init.lua:12`); None for Rust functions.
+pub(crate) fn function_location(func: &Function) -> Option<String> {
+    let info = func.info();
+    Some(format!("{}:{}", info.short_src?, info.line_defined?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -271,6 +678,57 @@ mod tests {
         assert_eq!(view_def.title, Some("Test View".to_string()));
     }
 
+    #[test]
+    fn test_parse_view_lifecycle_hooks() {
+        let lua = Lua::new();
+
+        let result = lua
+            .load(
+                r#"
+            return {
+                search = function(query, ctx) end,
+                on_open = function(ctx) end,
+                on_close = function(ctx) end,
+            }
+        "#,
+            )
+            .eval::<Table>()
+            .unwrap();
+
+        let view = parse_view(&lua, result).unwrap();
+        assert!(view.on_open_fn.is_some());
+        assert!(view.on_show_fn.is_none());
+        assert!(view.on_close_fn.is_some());
+    }
+
+    #[test]
+    fn test_parse_refresh_ms() {
+        let lua = Lua::new();
+
+        let result = lua
+            .load(
+                r#"
+            return {
+                { id = "procs", search = function() end, get_actions = function() end, refresh_ms = 2000 },
+                { id = "fast", search = function() end, get_actions = function() end, refresh_ms = 10 },
+                { id = "off", search = function() end, get_actions = function() end, refresh_ms = 0 },
+            }
+        "#,
+            )
+            .eval::<Table>()
+            .unwrap();
+
+        let refresh: Vec<Option<u64>> = result
+            .sequence_values::<Table>()
+            .map(|def| {
+                parse_view_definition(&lua, def.unwrap())
+                    .unwrap()
+                    .refresh_ms
+            })
+            .collect();
+        assert_eq!(refresh, vec![Some(2000), Some(MIN_REFRESH_MS), None]);
+    }
+
     #[test]
     fn test_parse_view_definition_missing_id() {
         let lua = Lua::new();
@@ -290,4 +748,42 @@ mod tests {
         let err = parse_view_definition(&lua, result).unwrap_err();
         assert!(err.to_string().contains("id"));
     }
+
+    #[test]
+    fn test_check_view_table() {
+        let lua = Lua::new();
+        let view: Table = lua
+            .load(
+                r#"
+            return {
+                id = "files",
+                placholder = "Search files",
+                source = function(query, ctx) end,
+                view_data = {},
+            }
+        "#,
+            )
+            .set_name("@init.lua")
+            .eval()
+            .unwrap();
+
+        let warnings = check_view_table(&view, ViewApi::Add).unwrap();
+        let messages: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "init.lua:5: view 'files': Unknown field 'placholder' (did you mean 'placeholder'?)",
+                "init.lua:5: view 'files': 'source' is deprecated; use 'search'",
+                "init.lua:5: view 'files': 'view_data' is ignored by lux.views.add",
+            ]
+        );
+        assert!(check_view_table(&view, ViewApi::Root)
+            .unwrap()
+            .iter()
+            .all(|w| w.field.as_deref() != Some("view_data")));
+
+        view.set("refresh_ms", "fast").unwrap();
+        let err = check_view_table(&view, ViewApi::Add).unwrap_err();
+        assert!(err.to_string().contains("'refresh_ms' should be"));
+    }
 }
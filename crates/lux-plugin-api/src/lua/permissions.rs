@@ -0,0 +1,81 @@
+//! Lua bindings for privacy permissions.
+//!
+//! This module provides:
+//! - `lux.permissions.check/request/open_settings()` - Privacy permissions (TCC)
+
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::permissions::Permission;
+
+/// Register `lux.permissions`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.permissions - Privacy permission status
+    //
+    // Usage:
+    //   lux.permissions.check("calendar")        -- "granted", "denied", "not_determined", "restricted"
+    //   lux.permissions.request("reminders")     -- prompt if not yet decided, else open System Settings
+    //   lux.permissions.open_settings("full_disk_access")
+    //   lux.permissions.list()                   -- { accessibility = "granted", ... }
+    //
+    // Names: accessibility, full_disk_access, automation, contacts, calendar, reminders.
+    // lux.permissions.status() is the older name of check().
+    {
+        let permissions_table = lua.create_table()?;
+
+        let check_fn = lua.create_function(|_lua, name: String| {
+            let status = crate::permissions::status(parse_permission(&name)?)?;
+            Ok(status.name())
+        })?;
+        permissions_table.set("check", check_fn.clone())?;
+        permissions_table.set("status", check_fn)?;
+
+        let list_fn = lua.create_function(|lua, ()| {
+            let table = lua.create_table()?;
+            for permission in Permission::ALL {
+                let status = crate::permissions::status(permission)?;
+                table.set(permission.name(), status.name())?;
+            }
+            Ok(table)
+        })?;
+        permissions_table.set("list", list_fn)?;
+
+        let open_settings_fn = lua.create_function(|_lua, name: String| {
+            crate::permissions::open_settings(parse_permission(&name)?)?;
+            Ok(())
+        })?;
+        permissions_table.set("open_settings", open_settings_fn)?;
+
+        let request_fn = lua.create_function(|_lua, name: String| {
+            let status = crate::permissions::request(parse_permission(&name)?)?;
+            Ok(status.name())
+        })?;
+        permissions_table.set("request", request_fn)?;
+
+        lux.set("permissions", permissions_table)?;
+    }
+
+    Ok(())
+}
+
+/// Parse a permission name passed from Lua.
+fn parse_permission(name: &str) -> LuaResult<Permission> {
+    Permission::from_name(name)
+        .ok_or_else(|| mlua::Error::RuntimeError(format!("Unknown permission '{}'", name)))
+}
+
+/// Build the placeholder item shown when a permission is missing.
+pub(super) fn permission_item(permission: Permission) -> Item {
+    Item::new(
+        format!("permission:{}", permission.name()),
+        t_args(
+            "Allow Lux to access {permission}",
+            &[("permission", &t(permission.display_name()))],
+        ),
+    )
+    .with_subtitle(t("Access has not been granted"))
+    .with_icon("🔒")
+    .with_type("permission")
+    .with_data(serde_json::json!({ "permission": permission.name() }))
+}
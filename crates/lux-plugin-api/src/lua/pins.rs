@@ -0,0 +1,63 @@
+//! Lua bindings for pinned results.
+//!
+//! This module provides:
+//! - `lux.pins.list/add/remove()` - Results pinned to the top of the root view
+//! - Handlers for the pin actions
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, Result as LuaResult, Table};
+
+use crate::pins::{pin_action_key, run_pin_action, PIN_ACTION_IDS};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.pins`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in pin action handlers (see crate::pins); the launcher refreshes
+    // the results afterwards
+    for action_id in PIN_ACTION_IDS {
+        let pins = registry.pins();
+        let handler = lua.create_function(move |lua, (items, _ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_pin_action(action_id, &item, &pins)?;
+            Ok(())
+        })?;
+        lua.set_named_registry_value(&pin_action_key(action_id), handler)?;
+    }
+
+    // lux.pins - Results pinned to the top of the root view
+    //
+    // Usage:
+    //   lux.pins.add({ id = "app:safari", title = "Safari", types = { "application" } })
+    //   lux.pins.remove("app:safari")    -- true if it was pinned
+    //   lux.pins.list()                  -- { { id, title, ... }, ... }
+    {
+        let pins_table = lua.create_table()?;
+
+        let pins = registry.pins();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let table = lua.create_table()?;
+            for (i, item) in pins.list().iter().enumerate() {
+                table.set(i + 1, bridge::item_to_lua(lua, item)?)?;
+            }
+            Ok(table)
+        })?;
+        pins_table.set("list", list_fn)?;
+
+        let pins = registry.pins();
+        let add_fn = lua.create_function(move |lua, item: Table| {
+            Ok(pins.pin(bridge::parse_item(lua, item)?))
+        })?;
+        pins_table.set("add", add_fn)?;
+
+        let pins = registry.pins();
+        let remove_fn = lua.create_function(move |_lua, id: String| Ok(pins.unpin(&id)))?;
+        pins_table.set("remove", remove_fn)?;
+
+        lux.set("pins", pins_table)?;
+    }
+
+    Ok(())
+}
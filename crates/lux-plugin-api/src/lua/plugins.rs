@@ -0,0 +1,144 @@
+//! Lua bindings for third-party plugins.
+//!
+//! This module provides:
+//! - `lux.security.set_restricted/trust/status()` - Restricted mode for third-party plugins
+//! - `lux.plugins.load/list()` - Third-party plugins with a manifest.json
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use mlua::{Lua, MultiValue, Result as LuaResult, Table};
+
+use crate::compat::{check_api_version, plugin_env, plugin_lux};
+use crate::conflicts::BindingSource;
+use crate::registry::PluginRegistry;
+use crate::security::{restricted_env, LoadedPlugin, Manifest};
+
+/// Register `lux.security` and `lux.plugins`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.security - Restricted mode for third-party plugins
+    //
+    // Usage:
+    //   lux.security.set_restricted(false)  -- on by default; off trusts every plugin
+    //   lux.security.trust("weather")       -- by the name in its manifest.json
+    //   lux.security.status()               -- { restricted = true, trusted = { "weather" } }
+    //
+    // Untrusted plugins loaded with lux.plugins.load() only get the APIs in
+    // security::ALLOWED_APIS (not lux.shell, lux.http, lux.security, or
+    // lux.plugins, among others), without the lux.fs functions that write.
+    {
+        let security_table = lua.create_table()?;
+
+        let security = registry.security();
+        let set_restricted_fn = lua.create_function(move |_lua, restricted: bool| {
+            security.set_restricted(restricted);
+            Ok(())
+        })?;
+        security_table.set("set_restricted", set_restricted_fn)?;
+
+        let security = registry.security();
+        let trust_fn = lua.create_function(move |_lua, name: String| {
+            security.trust(&name);
+            Ok(())
+        })?;
+        security_table.set("trust", trust_fn)?;
+
+        let security = registry.security();
+        let status_fn = lua.create_function(move |lua, ()| {
+            let table = lua.create_table()?;
+            table.set("restricted", security.is_restricted())?;
+            table.set("trusted", lua.create_sequence_from(security.trusted())?)?;
+            Ok(table)
+        })?;
+        security_table.set("status", status_fn)?;
+
+        lux.set("security", security_table)?;
+    }
+
+    // lux.plugins - Third-party plugins in folders with a manifest.json
+    //
+    // Usage:
+    //   lux.plugins.load("~/.config/lux/plugins/weather")  -- runs its entry file, returns what it returns
+    //   lux.plugins.list()   -- { { name, version, path, restricted, api_version }, ... }
+    //
+    // manifest.json: { "name": "weather", "version": "1.0", "main": "init.lua", "api_version": 2 }
+    //
+    // A plugin runs with the shims for the API version it targets (1 if missing).
+    {
+        let plugins_table = lua.create_table()?;
+
+        let security = registry.security();
+        let config_warnings = registry.config_warnings();
+        let keymap = registry.keymap();
+        let load_fn = lua.create_function(move |lua, dir: String| {
+            let dir = PathBuf::from(crate::detect::expand_home(&dir));
+            let manifest = Manifest::load(&dir)?;
+            check_api_version(&manifest.name, manifest.api_version)?;
+            let entry = manifest.entry(&dir)?;
+            let source = std::fs::read_to_string(&entry).map_err(|e| {
+                mlua::Error::RuntimeError(format!("Cannot read {}: {}", entry.display(), e))
+            })?;
+
+            let restricted = security.restricts(&manifest);
+            let plugin_lux = plugin_lux(
+                lua,
+                &manifest.name,
+                manifest.api_version,
+                config_warnings.clone(),
+            )?;
+            let env = if restricted {
+                // Secrets follow the folder, which the plugin can't choose
+                let folder = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+                restricted_env(lua, &manifest.name, &folder, plugin_lux)?
+            } else {
+                plugin_env(lua, plugin_lux)?
+            };
+            // Bindings it sets are attributed to it, for keymap conflicts
+            let previous_source = keymap.set_source(BindingSource::Plugin(manifest.name.clone()));
+            let result = lua
+                .load(source)
+                .set_name(format!("@{}", entry.display()))
+                .set_mode(mlua::ChunkMode::Text)
+                .set_environment(env)
+                .call::<MultiValue>(());
+            keymap.set_source(previous_source);
+            let result = result?;
+
+            tracing::info!(
+                "Loaded plugin '{}' from {}{}",
+                manifest.name,
+                dir.display(),
+                if restricted { " (restricted)" } else { "" }
+            );
+            security.record(LoadedPlugin {
+                name: manifest.name,
+                version: manifest.version,
+                path: dir,
+                restricted,
+                api_version: manifest.api_version,
+            });
+            Ok(result)
+        })?;
+        plugins_table.set("load", load_fn)?;
+
+        let security = registry.security();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let list = lua.create_table()?;
+            for (i, plugin) in security.plugins().into_iter().enumerate() {
+                let table = lua.create_table()?;
+                table.set("name", plugin.name)?;
+                table.set("version", plugin.version)?;
+                table.set("path", plugin.path.to_string_lossy().to_string())?;
+                table.set("restricted", plugin.restricted)?;
+                table.set("api_version", plugin.api_version)?;
+                list.set(i + 1, table)?;
+            }
+            Ok(list)
+        })?;
+        plugins_table.set("list", list_fn)?;
+
+        lux.set("plugins", plugins_table)?;
+    }
+
+    Ok(())
+}
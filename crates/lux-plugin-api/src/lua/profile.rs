@@ -0,0 +1,193 @@
+//! Lua bindings for profiles.
+//!
+//! This module provides:
+//! - `lux.profile.add/set/current/list/view()` - Profiles
+
+use std::sync::Arc;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::profiles::{parse_time, Profile, ProfileCondition, ProfileRegistry};
+use crate::registry::PluginRegistry;
+
+use super::bridge;
+
+/// Register `lux.profile`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.profile - Profiles (focus filters)
+    //
+    // Usage:
+    //   lux.profile.add("work", {
+    //     disable = { views = { "games" }, hooks = { "search" }, sources = { "media" } },
+    //     when = { apps = { "com.tinyspeck.slackmacgap" }, hours = { "09:00", "17:30" }, days = { 1, 2, 3, 4, 5 } },
+    //   })
+    //   lux.profile.set("work")      -- pin a profile (nil returns to automatic switching)
+    //   lux.profile.current()        -- "work" or nil
+    //   lux.profile.list()           -- { "work", ... }
+    //   ctx:push(lux.profile.view()) -- profile switcher
+    {
+        let profile_table = lua.create_table()?;
+
+        let profiles = registry.profiles();
+        let add_fn = lua.create_function(move |_lua, (name, opts): (String, Option<Table>)| {
+            let profile = parse_profile(name, opts)?;
+            profiles
+                .add(profile)
+                .map_err(|e| mlua::Error::RuntimeError(format!("profile.add: {}", e)))
+        })?;
+        profile_table.set("add", add_fn)?;
+
+        let profiles = registry.profiles();
+        let set_fn = lua.create_function(move |_lua, name: Option<String>| {
+            profiles
+                .set(name.as_deref())
+                .map_err(|e| mlua::Error::RuntimeError(format!("profile.set: {}", e)))
+        })?;
+        profile_table.set("set", set_fn)?;
+
+        let profiles = registry.profiles();
+        let current_fn = lua.create_function(move |_lua, ()| Ok(profiles.active_name()))?;
+        profile_table.set("current", current_fn)?;
+
+        let profiles = registry.profiles();
+        let list_fn =
+            lua.create_function(move |lua, ()| lua.create_sequence_from(profiles.list()))?;
+        profile_table.set("list", list_fn)?;
+
+        let profiles = registry.profiles();
+        let view_fn =
+            lua.create_function(move |lua, ()| profile_view(lua, Arc::clone(&profiles)))?;
+        profile_table.set("view", view_fn)?;
+
+        lux.set("profile", profile_table)?;
+    }
+
+    Ok(())
+}
+
+/// Parse the options table of `lux.profile.add(name, opts)`.
+fn parse_profile(name: String, opts: Option<Table>) -> LuaResult<Profile> {
+    let mut profile = Profile::new(name);
+    let Some(opts) = opts else {
+        return Ok(profile);
+    };
+
+    if let Some(disable) = opts.get::<Option<Table>>("disable")? {
+        let list = |key: &str| -> LuaResult<Vec<String>> {
+            Ok(disable.get::<Option<Vec<String>>>(key)?.unwrap_or_default())
+        };
+        profile.disabled_views = list("views")?.into_iter().collect();
+        profile.disabled_hooks = list("hooks")?.into_iter().collect();
+        profile.disabled_sources = list("sources")?.into_iter().collect();
+    }
+
+    if let Some(when) = opts.get::<Option<Table>>("when")? {
+        let hours = match when.get::<Option<Vec<String>>>("hours")? {
+            None => None,
+            Some(hours) => match hours.as_slice() {
+                [start, end] => Some(parse_time(start).zip(parse_time(end)).ok_or_else(|| {
+                    mlua::Error::RuntimeError(format!(
+                        "profile.add: invalid hours '{}'-'{}' (expected \"HH:MM\")",
+                        start, end
+                    ))
+                })?),
+                _ => {
+                    return Err(mlua::Error::RuntimeError(
+                        "profile.add: hours must be { start, end }".to_string(),
+                    ))
+                }
+            },
+        };
+        let days = when.get::<Option<Vec<u8>>>("days")?.unwrap_or_default();
+        if let Some(day) = days.iter().find(|d| !(1..=7).contains(*d)) {
+            return Err(mlua::Error::RuntimeError(format!(
+                "profile.add: invalid day {} (expected 1 = Monday ... 7 = Sunday)",
+                day
+            )));
+        }
+        profile.when = Some(ProfileCondition {
+            apps: when.get::<Option<Vec<String>>>("apps")?.unwrap_or_default(),
+            hours,
+            days,
+        });
+    }
+
+    Ok(profile)
+}
+
+/// Build the profile switcher view returned by `lux.profile.view()`.
+///
+/// Lists "Automatic" followed by every profile; the action pins the chosen
+/// profile (or returns to automatic switching) and dismisses the launcher.
+fn profile_view(lua: &Lua, profiles: Arc<ProfileRegistry>) -> LuaResult<Table> {
+    let view = lua.create_table()?;
+    view.set("id", "profiles")?;
+    view.set("title", t("Switch Profile"))?;
+    view.set("placeholder", t("Search profiles..."))?;
+
+    // search(query, ctx) - filter profiles by name
+    let search_profiles = Arc::clone(&profiles);
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let query = query.to_lowercase();
+        let active = search_profiles.active_name();
+        let pinned = search_profiles.is_pinned();
+
+        let mut entries = vec![(None, t("Automatic"), !pinned)];
+        entries.extend(search_profiles.list().into_iter().map(|name| {
+            let current = pinned && active.as_deref() == Some(name.as_str());
+            (Some(name.clone()), name, current)
+        }));
+
+        let items = lua.create_table()?;
+        for (name, title, current) in entries
+            .into_iter()
+            .filter(|(_, title, _)| title.to_lowercase().contains(&query))
+        {
+            let subtitle = match (&name, current) {
+                (None, true) => Some(match &active {
+                    Some(active) => t_args("Current · {profile} is active", &[("profile", active)]),
+                    None => t("Current · No profile is active"),
+                }),
+                (_, true) => Some(t("Current")),
+                _ => None,
+            };
+            let mut item = Item::new(format!("profile:{}", name.as_deref().unwrap_or("")), title)
+                .with_icon(if name.is_some() { "👤" } else { "🔄" })
+                .with_type("profile")
+                .with_data(serde_json::json!({ "profile": name }));
+            item.subtitle = subtitle;
+            items.push(bridge::item_to_lua(lua, &item)?)?;
+        }
+        ctx.call_method::<()>("set_items", items)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - switch to the chosen profile
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let name = item
+            .data
+            .as_ref()
+            .and_then(|d| d["profile"].as_str())
+            .map(str::to_string);
+
+        let profiles = Arc::clone(&profiles);
+        let handler = lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+            profiles
+                .set(name.as_deref())
+                .map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+
+        let action = lua.create_table()?;
+        action.set("id", "switch_profile")?;
+        action.set("title", t("Switch Profile"))?;
+        action.set("handler", handler)?;
+        lua.create_sequence_from([action])
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,91 @@
+//! Lua bindings for QR codes.
+//!
+//! This module provides:
+//! - `lux.qr.view/image/wifi()` - QR codes
+//! - Handlers for the QR code actions
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::qr::{
+    qr_action_key, qr_image, qr_item, run_qr_action, wifi_payload, QR_ACTION_IDS, QR_VIEW_ID,
+};
+
+use super::bridge;
+
+/// Register `lux.qr`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // Built-in QR code action handlers (see crate::qr)
+    for action_id in QR_ACTION_IDS {
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_qr_action(action_id, &item)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&qr_action_key(action_id), handler)?;
+    }
+
+    // lux.qr - QR codes
+    //
+    // Usage:
+    //   ctx:push(lux.qr.view("https://example.com"))      -- view showing the code
+    //   lux.qr.view(lux.qr.wifi("Home", "hunter2"), "Home Wi-Fi")
+    //   lux.qr.image("some text")                          -- path of the cached PNG
+    //   lux.qr.wifi("Guest", nil, true)                    -- "WIFI:T:nopass;S:Guest;H:true;;"
+    {
+        let qr_table = lua.create_table()?;
+
+        let view_fn = lua.create_function(|lua, (text, label): (String, Option<String>)| {
+            let label = label.unwrap_or_else(|| text.clone());
+            qr_view(lua, &text, &label)
+        })?;
+        qr_table.set("view", view_fn)?;
+
+        let image_fn = lua.create_function(|_lua, text: String| {
+            Ok(qr_image(&text)?.to_string_lossy().to_string())
+        })?;
+        qr_table.set("image", image_fn)?;
+
+        let wifi_fn = lua.create_function(
+            |_lua, (ssid, password, hidden): (String, Option<String>, Option<bool>)| {
+                Ok(wifi_payload(
+                    &ssid,
+                    password.as_deref(),
+                    hidden.unwrap_or(false),
+                ))
+            },
+        )?;
+        qr_table.set("wifi", wifi_fn)?;
+
+        lux.set("qr", qr_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the view showing the QR code for `text`.
+///
+/// Its one row shows the code as its icon; the QR code actions open it at
+/// full size, copy it, or save it.
+pub(super) fn qr_view(lua: &Lua, text: &str, label: &str) -> LuaResult<Table> {
+    let item = qr_item(text, label)?;
+
+    let view = lua.create_table()?;
+    view.set("id", QR_VIEW_ID)?;
+    view.set("title", t("QR Code"))?;
+    view.set("placeholder", label)?;
+
+    let search_fn = lua.create_function(move |lua, (_query, ctx): (String, AnyUserData)| {
+        let group = lua.create_table()?;
+        group.set("title", t("QR Code"))?;
+        let group_items = lua.create_table()?;
+        group_items.push(bridge::item_to_lua(lua, &item)?)?;
+        group.set("items", group_items)?;
+        let result = lua.create_table()?;
+        result.push(group)?;
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,96 @@
+//! Lua bindings for query rewriting.
+//!
+//! This module provides:
+//! - `lux.query.normalize/aliases/use/remove/rewrite/parse()` - Query rewriting and search operators
+
+use std::sync::Arc;
+
+use mlua::{Function, Lua, Result as LuaResult, Table};
+
+use crate::keymap::generate_handler_id;
+use crate::operators::parse_query;
+use crate::registry::PluginRegistry;
+use crate::rewrite::Normalize;
+use crate::types::LuaFunctionRef;
+
+use super::bridge;
+
+/// Register `lux.query`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.query - Query rewriting before sources run
+    //
+    // Usage:
+    //   lux.query.normalize({ trim = true, lowercase = true, collapse_spaces = true })
+    //   lux.query.aliases({ ff = "firefox", teh = "the" })   -- "" removes an alias
+    //   local id = lux.query.use(function(query, view_id)
+    //     return query:gsub("^g ", "google ")                -- nil keeps the query
+    //   end)
+    //   lux.query.remove(id)
+    //   lux.query.rewrite("ff")                               -- "firefox"
+    //   lux.query.parse('report type:pdf "q3 budget"')
+    //     -- { terms = { "report" }, phrases = { "q3 budget" }, filters = { type = { "pdf" } } }
+    {
+        let query_table = lua.create_table()?;
+
+        let rewriter = registry.query_rewriter();
+        let normalize_fn = lua.create_function(move |_lua, opts: Table| {
+            // Options left out keep their current setting
+            let current = rewriter.normalize();
+            rewriter.set_normalize(Normalize {
+                trim: opts.get::<Option<bool>>("trim")?.unwrap_or(current.trim),
+                lowercase: opts
+                    .get::<Option<bool>>("lowercase")?
+                    .unwrap_or(current.lowercase),
+                collapse_spaces: opts
+                    .get::<Option<bool>>("collapse_spaces")?
+                    .unwrap_or(current.collapse_spaces),
+            });
+            Ok(())
+        })?;
+        query_table.set("normalize", normalize_fn)?;
+
+        let rewriter = registry.query_rewriter();
+        let aliases_fn = lua.create_function(move |_lua, aliases: Table| {
+            for pair in aliases.pairs::<String, String>() {
+                let (word, replacement) = pair?;
+                rewriter.set_alias(&word, &replacement);
+            }
+            Ok(())
+        })?;
+        query_table.set("aliases", aliases_fn)?;
+
+        let rewriter = registry.query_rewriter();
+        let use_fn = lua.create_function(move |lua, func: Function| {
+            let key = format!("query:{}", generate_handler_id());
+            let func = LuaFunctionRef::from_function(lua, func, key)?;
+            Ok(rewriter.add_lua(func))
+        })?;
+        query_table.set("use", use_fn)?;
+
+        let rewriter = registry.query_rewriter();
+        let remove_fn = lua.create_function(move |lua, id: String| {
+            let removed = rewriter.remove(&id);
+            if removed {
+                lua.unset_named_registry_value(&id)?;
+            }
+            Ok(removed)
+        })?;
+        query_table.set("remove", remove_fn)?;
+
+        let rewriter = registry.query_rewriter();
+        let rewrite_fn =
+            lua.create_function(move |lua, (query, view_id): (String, Option<String>)| {
+                Ok(rewriter.rewrite(lua, &query, view_id.as_deref()).query)
+            })?;
+        query_table.set("rewrite", rewrite_fn)?;
+
+        let parse_fn = lua.create_function(|lua, query: String| {
+            bridge::parsed_query_to_lua(lua, &parse_query(&query))
+        })?;
+        query_table.set("parse", parse_fn)?;
+
+        lux.set("query", query_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Lua bindings for result ordering.
+//!
+//! This module provides:
+//! - `lux.results.order/reset()` - Group order and weights, globally or per view
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::ordering::OrderRules;
+use crate::registry::PluginRegistry;
+
+/// Register `lux.results`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.results - Group order and weights
+    //
+    // Usage:
+    //   lux.results.order({ order = { "Pinned", "Apps", "Files" }, weight = { files = 0.5 } })
+    //   lux.results.order({ view = "file_browser", order = { "Folders" } })  -- just this view
+    //   lux.results.reset()                  -- or reset("file_browser")
+    //
+    // Listed groups come first; the rest follow by weight (default 1, 0 hides).
+    {
+        let results_table = lua.create_table()?;
+
+        let ordering = registry.ordering();
+        let order_fn = lua.create_function(move |_lua, opts: Table| {
+            let order: Vec<String> = opts
+                .get::<Option<Vec<String>>>("order")?
+                .unwrap_or_default();
+            let mut rules = OrderRules::new(order);
+            if let Some(weights) = opts.get::<Option<Table>>("weight")? {
+                for pair in weights.pairs::<String, f64>() {
+                    let (title, weight) = pair?;
+                    rules = rules.with_weight(&title, weight);
+                }
+            }
+            match opts.get::<Option<String>>("view")? {
+                Some(view_id) => ordering.set_view(&view_id, Some(rules)),
+                None => ordering.set_global(Some(rules)),
+            }
+            Ok(())
+        })?;
+        results_table.set("order", order_fn)?;
+
+        let ordering = registry.ordering();
+        let reset_fn = lua.create_function(move |_lua, view_id: Option<String>| {
+            match view_id {
+                Some(view_id) => ordering.set_view(&view_id, None),
+                None => ordering.set_global(None),
+            }
+            Ok(())
+        })?;
+        results_table.set("reset", reset_fn)?;
+
+        lux.set("results", results_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Lua bindings for query routes and app rules.
+//!
+//! This module provides:
+//! - `lux.routes.add/remove/list()` - Query routing rules for the root view
+//! - `lux.rules.on_app/remove/list()` - Views to open on per frontmost app
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+
+/// Register `lux.routes` and `lux.rules`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.routes - Query routing rules for the root view
+    //
+    // Usage:
+    //   local id = lux.routes.add({ pattern = "^%d+[%+%-/%*]", view = "calculator" })
+    //   lux.routes.remove(id)
+    //   lux.routes.list()          -- { { id, pattern, view }, ... }
+    //
+    // Patterns are Lua patterns; the first matching route's view claims the query.
+    {
+        crate::routes::capture_string_find(lua)?;
+        let routes_table = lua.create_table()?;
+
+        let routes = registry.routes();
+        let add_fn = lua.create_function(move |lua, opts: Table| {
+            let pattern: String = opts.get("pattern").map_err(|_| {
+                mlua::Error::RuntimeError("routes.add requires 'pattern' field".to_string())
+            })?;
+            let view: String = opts.get("view").map_err(|_| {
+                mlua::Error::RuntimeError("routes.add requires 'view' field".to_string())
+            })?;
+            routes.add(lua, &pattern, &view)
+        })?;
+        routes_table.set("add", add_fn)?;
+
+        let routes = registry.routes();
+        let remove_fn = lua.create_function(move |_lua, id: String| Ok(routes.remove(&id)))?;
+        routes_table.set("remove", remove_fn)?;
+
+        let routes = registry.routes();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let list = lua.create_table()?;
+            for (i, route) in routes.list().into_iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("id", route.id)?;
+                entry.set("pattern", route.pattern)?;
+                entry.set("view", route.view)?;
+                list.set(i + 1, entry)?;
+            }
+            Ok(list)
+        })?;
+        routes_table.set("list", list_fn)?;
+
+        lux.set("routes", routes_table)?;
+    }
+
+    // lux.rules - Views to open on per frontmost app
+    //
+    // Usage:
+    //   local id = lux.rules.on_app({ bundle_id = "com.apple.Safari", view = "builtin.tabs" })
+    //   lux.rules.remove(id)
+    //   lux.rules.list()           -- { { id, bundle_id, view }, ... }
+    //
+    // bundle_id also matches the app name; the first matching rule wins.
+    {
+        let rules_table = lua.create_table()?;
+
+        let rules = registry.app_rules();
+        let on_app_fn = lua.create_function(move |_lua, opts: Table| {
+            let bundle_id: String = opts.get("bundle_id").map_err(|_| {
+                mlua::Error::RuntimeError("rules.on_app requires 'bundle_id' field".to_string())
+            })?;
+            let view: String = opts.get("view").map_err(|_| {
+                mlua::Error::RuntimeError("rules.on_app requires 'view' field".to_string())
+            })?;
+            Ok(rules.on_app(&bundle_id, &view))
+        })?;
+        rules_table.set("on_app", on_app_fn)?;
+
+        let rules = registry.app_rules();
+        let remove_fn = lua.create_function(move |_lua, id: String| Ok(rules.remove(&id)))?;
+        rules_table.set("remove", remove_fn)?;
+
+        let rules = registry.app_rules();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let list = lua.create_table()?;
+            for (i, rule) in rules.list().into_iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("id", rule.id)?;
+                entry.set("bundle_id", rule.bundle_id)?;
+                entry.set("view", rule.view)?;
+                list.set(i + 1, entry)?;
+            }
+            Ok(list)
+        })?;
+        rules_table.set("list", list_fn)?;
+
+        lux.set("rules", rules_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+//! Lua bindings for the Lua runtime.
+//!
+//! This module provides:
+//! - `lux.runtime.set_memory_limit/gc/stats()` - Lua memory limit, idle GC, and memory per view
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+
+/// Register `lux.runtime`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.runtime - Lua memory limit, garbage collection, and memory per view
+    //
+    // Usage:
+    //   lux.runtime.set_memory_limit(256)   -- MB; nil or 0 removes the limit
+    //   lux.runtime.gc({ interval_ms = 500, step_kb = 128 })   -- read at launch; 0 turns it off
+    //   lux.runtime.stats()   -- { used, limit, views = { [id] = { calls, retained, peak } } }
+    {
+        let runtime_table = lua.create_table()?;
+
+        let memory = registry.memory();
+        let set_limit_fn = lua.create_function(move |lua, mb: Option<f64>| {
+            let bytes = mb
+                .filter(|mb| *mb > 0.0)
+                .map(|mb| (mb * 1024.0 * 1024.0) as usize);
+            memory.set_limit(lua, bytes)
+        })?;
+        runtime_table.set("set_memory_limit", set_limit_fn)?;
+
+        let memory = registry.memory();
+        let gc_fn = lua.create_function(move |_lua, opts: Table| {
+            let mut settings = memory.gc_settings();
+            if let Some(ms) = opts.get::<Option<u64>>("interval_ms")? {
+                settings.interval = (ms > 0).then(|| Duration::from_millis(ms));
+            }
+            if let Some(kb) = opts.get::<Option<u32>>("step_kb")? {
+                settings.step_kbytes = kb;
+            }
+            memory.set_gc_settings(settings);
+            Ok(())
+        })?;
+        runtime_table.set("gc", gc_fn)?;
+
+        let memory = registry.memory();
+        let stats_fn = lua.create_function(move |lua, ()| {
+            let stats = lua.create_table()?;
+            stats.set("used", lua.used_memory())?;
+            stats.set("limit", memory.limit())?;
+            let views = lua.create_table()?;
+            for (view, usage) in memory.usage() {
+                let entry = lua.create_table()?;
+                entry.set("calls", usage.calls)?;
+                entry.set("retained", usage.retained)?;
+                entry.set("peak", usage.peak)?;
+                views.set(view, entry)?;
+            }
+            stats.set("views", views)?;
+            Ok(stats)
+        })?;
+        runtime_table.set("stats", stats_fn)?;
+
+        lux.set("runtime", runtime_table)?;
+    }
+
+    Ok(())
+}
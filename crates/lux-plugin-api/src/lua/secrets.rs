@@ -0,0 +1,77 @@
+//! Lua bindings for secrets and storage.
+//!
+//! This module provides:
+//! - `lux.secrets.get/set/delete()` - Secrets in the Keychain, redacted from logs
+//! - `lux.storage.set_encrypted/status()` - Encrypted data files, key in the Keychain
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+/// Register `lux.secrets` and `lux.storage`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.secrets - Passwords and tokens in the Keychain (Secret Service on Linux)
+    //
+    // Usage:
+    //   lux.secrets.set("github", token)
+    //   lux.secrets.get("github")      -- the token, or nil
+    //   lux.secrets.delete("github")   -- true if there was one
+    //
+    // Names starting with "oauth." (lux.oauth) and "storage-key" (lux.storage)
+    // are Lux's own and raise an error. Values read or stored here show up as
+    // [redacted] in the log. An untrusted plugin's names are prefixed with
+    // "plugin:<folder>:", so it only sees its own.
+    {
+        let secrets_table = lua.create_table()?;
+
+        let get_fn = lua.create_function(|_lua, name: String| {
+            crate::secrets::check_reserved(&name)?;
+            Ok(crate::secrets::get(&name)?)
+        })?;
+        secrets_table.set("get", get_fn)?;
+
+        let set_fn = lua.create_function(|_lua, (name, value): (String, String)| {
+            crate::secrets::check_reserved(&name)?;
+            crate::secrets::set(&name, &value)?;
+            Ok(())
+        })?;
+        secrets_table.set("set", set_fn)?;
+
+        let delete_fn = lua.create_function(|_lua, name: String| {
+            crate::secrets::check_reserved(&name)?;
+            Ok(crate::secrets::delete(&name)?)
+        })?;
+        secrets_table.set("delete", delete_fn)?;
+
+        lux.set("secrets", secrets_table)?;
+    }
+
+    // lux.storage - Encrypted data files, with the key in the Keychain
+    //
+    // Usage:
+    //   lux.storage.set_encrypted(true)   -- returns how many plain files it encrypted
+    //   lux.storage.status()              -- { encrypted = true }
+    //
+    // Covers the activity log, HTTP history, pins, hidden results, color
+    // history, metrics, collapsed groups, and cached exchange rates. Plain
+    // files still load, and are encrypted on save.
+    {
+        let storage_table = lua.create_table()?;
+
+        let set_encrypted_fn = lua.create_function(|_lua, encrypted: bool| {
+            crate::storage::set_encrypted(encrypted).map_err(|e| {
+                mlua::Error::RuntimeError(format!("Cannot change storage encryption: {}", e))
+            })
+        })?;
+        storage_table.set("set_encrypted", set_encrypted_fn)?;
+
+        let status_fn = lua.create_function(|lua, ()| {
+            let table = lua.create_table()?;
+            table.set("encrypted", crate::storage::is_encrypted())?;
+            Ok(table)
+        })?;
+        storage_table.set("status", status_fn)?;
+
+        lux.set("storage", storage_table)?;
+    }
+
+    Ok(())
+}
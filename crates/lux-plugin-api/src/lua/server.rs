@@ -0,0 +1,50 @@
+//! Lua bindings for the remote control server.
+//!
+//! This module provides:
+//! - `lux.server.enable/disable()` - Remote control HTTP server
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+
+/// Register `lux.server`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.server - Remote control over localhost HTTP (read at launch)
+    //
+    // Usage:
+    //   local token = lux.server.enable()              -- port 7437, random token
+    //   lux.server.enable({ port = 9000, token = os.getenv("LUX_TOKEN") })
+    //   lux.server.disable()
+    //
+    // Clients send `Authorization: Bearer <token>`:
+    //   curl -H "Authorization: Bearer $TOKEN" "http://127.0.0.1:7437/search?q=safari"
+    {
+        let server_table = lua.create_table()?;
+
+        let server = registry.server();
+        let enable_fn = lua.create_function(move |_lua, opts: Option<Table>| {
+            let (port, token) = match opts {
+                Some(opts) => (opts.get("port")?, opts.get("token")?),
+                None => (None, None),
+            };
+            let config = server
+                .enable(port, token)
+                .map_err(|e| mlua::Error::RuntimeError(format!("server.enable: {}", e)))?;
+            Ok(config.token)
+        })?;
+        server_table.set("enable", enable_fn)?;
+
+        let server = registry.server();
+        let disable_fn = lua.create_function(move |_lua, ()| {
+            server.disable();
+            Ok(())
+        })?;
+        server_table.set("disable", disable_fn)?;
+
+        lux.set("server", server_table)?;
+    }
+
+    Ok(())
+}
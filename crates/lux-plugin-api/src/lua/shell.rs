@@ -0,0 +1,218 @@
+//! Lua bindings for shell commands.
+//!
+//! This module provides:
+//! - `lux.shell` - Shell command execution
+
+use mlua::{Lua, MultiValue, Result as LuaResult, Table, Value};
+
+/// Register `lux.shell`.
+pub(super) fn register(lua: &Lua, lux: &Table) -> LuaResult<()> {
+    // lux.shell - Shell command execution namespace
+    //
+    // Usage:
+    //   lux.shell("say", "done")        -- async fire-and-forget
+    //   lux.shell.sync("ls", "-la")     -- blocking, returns output
+    //   lux.shell.run({cmd, cwd, env})  -- advanced options
+    //
+    // To open a file or URL, use lux.open(target) instead.
+    {
+        let shell_table = lua.create_table()?;
+
+        // lux.shell.sync(command) - Blocking execution, returns output
+        let sync_fn = lua.create_function(|lua, command: String| {
+            use std::io::Read;
+            use std::process::{Command, Stdio};
+            use std::time::Duration;
+            use wait_timeout::ChildExt;
+
+            let timeout_ms = 30_000u64;
+
+            let mut cmd = Command::new("sh");
+            cmd.args(["-c", &command])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            let mut child = cmd
+                .spawn()
+                .map_err(|e| mlua::Error::RuntimeError(format!("Command spawn failed: {}", e)))?;
+
+            let timeout = Duration::from_millis(timeout_ms);
+
+            let status = match child.wait_timeout(timeout) {
+                Ok(Some(status)) => status,
+                Ok(None) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+
+                    let result = lua.create_table()?;
+                    result.set("stdout", "")?;
+                    result.set(
+                        "stderr",
+                        format!("Command timed out after {}ms", timeout_ms),
+                    )?;
+                    result.set("exit_code", -1)?;
+                    result.set("success", false)?;
+                    result.set("timed_out", true)?;
+                    return Ok(result);
+                }
+                Err(e) => {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "Command wait failed: {}",
+                        e
+                    )));
+                }
+            };
+
+            let mut stdout = String::new();
+            let mut stderr = String::new();
+
+            if let Some(mut stdout_handle) = child.stdout.take() {
+                let _ = stdout_handle.read_to_string(&mut stdout);
+            }
+            if let Some(mut stderr_handle) = child.stderr.take() {
+                let _ = stderr_handle.read_to_string(&mut stderr);
+            }
+
+            let result = lua.create_table()?;
+            result.set("stdout", stdout)?;
+            result.set("stderr", stderr)?;
+            result.set("exit_code", status.code().unwrap_or(-1))?;
+            result.set("success", status.success())?;
+            result.set("timed_out", false)?;
+
+            Ok(result)
+        })?;
+        shell_table.set("sync", sync_fn)?;
+
+        // lux.shell.run({ cmd, cwd?, env?, timeout_ms? }) - Advanced options
+        let run_fn = lua.create_function(|lua, opts: Table| {
+            use std::io::Read;
+            use std::process::{Command, Stdio};
+            use std::time::Duration;
+            use wait_timeout::ChildExt;
+
+            let command: String = opts.get("cmd").map_err(|_| {
+                mlua::Error::RuntimeError("shell.run requires 'cmd' field".to_string())
+            })?;
+
+            let timeout_ms = opts
+                .get::<Option<u64>>("timeout_ms")
+                .ok()
+                .flatten()
+                .unwrap_or(30_000);
+
+            let cwd = opts.get::<Option<String>>("cwd").ok().flatten();
+
+            let env: Option<Table> = opts.get("env").ok();
+
+            let mut cmd = Command::new("sh");
+            cmd.args(["-c", &command])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped());
+
+            if let Some(dir) = cwd {
+                cmd.current_dir(dir);
+            }
+
+            if let Some(env_table) = env {
+                for (key, value) in env_table.pairs::<String, String>().flatten() {
+                    cmd.env(key, value);
+                }
+            }
+
+            let mut child = cmd
+                .spawn()
+                .map_err(|e| mlua::Error::RuntimeError(format!("Command spawn failed: {}", e)))?;
+
+            let timeout = Duration::from_millis(timeout_ms);
+
+            let status = match child.wait_timeout(timeout) {
+                Ok(Some(status)) => status,
+                Ok(None) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+
+                    let result = lua.create_table()?;
+                    result.set("stdout", "")?;
+                    result.set(
+                        "stderr",
+                        format!("Command timed out after {}ms", timeout_ms),
+                    )?;
+                    result.set("exit_code", -1)?;
+                    result.set("success", false)?;
+                    result.set("timed_out", true)?;
+                    return Ok(result);
+                }
+                Err(e) => {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "Command wait failed: {}",
+                        e
+                    )));
+                }
+            };
+
+            let mut stdout = String::new();
+            let mut stderr = String::new();
+
+            if let Some(mut stdout_handle) = child.stdout.take() {
+                let _ = stdout_handle.read_to_string(&mut stdout);
+            }
+            if let Some(mut stderr_handle) = child.stderr.take() {
+                let _ = stderr_handle.read_to_string(&mut stderr);
+            }
+
+            let result = lua.create_table()?;
+            result.set("stdout", stdout)?;
+            result.set("stderr", stderr)?;
+            result.set("exit_code", status.code().unwrap_or(-1))?;
+            result.set("success", status.success())?;
+            result.set("timed_out", false)?;
+
+            Ok(result)
+        })?;
+        shell_table.set("run", run_fn)?;
+
+        // Set __call metamethod for lux.shell("command", ...) - fire-and-forget
+        let metatable = lua.create_table()?;
+        let call_fn = lua.create_function(|_lua, args: MultiValue| {
+            use std::process::{Command, Stdio};
+
+            let mut args_iter = args.into_iter();
+            args_iter.next(); // Skip 'self' (the shell table)
+
+            // Collect all arguments as strings and join them
+            let parts: Vec<String> = args_iter
+                .filter_map(|v| match v {
+                    Value::String(s) => Some(s.to_str().ok()?.to_string()),
+                    Value::Number(n) => Some(n.to_string()),
+                    Value::Integer(i) => Some(i.to_string()),
+                    _ => None,
+                })
+                .collect();
+
+            if parts.is_empty() {
+                return Err(mlua::Error::RuntimeError(
+                    "shell() requires at least one argument".to_string(),
+                ));
+            }
+
+            let command = parts.join(" ");
+
+            // Fire-and-forget: spawn detached process
+            Command::new("sh")
+                .args(["-c", &command])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| mlua::Error::RuntimeError(format!("Command spawn failed: {}", e)))?;
+
+            Ok(())
+        })?;
+        metatable.set("__call", call_fn)?;
+        shell_table.set_metatable(Some(metatable))?;
+
+        lux.set("shell", shell_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Lua bindings for SSH hosts.
+//!
+//! This module provides:
+//! - `lux.ssh.set_terminal/terminal()` - Terminal app for SSH hosts
+//! - Handlers for the SSH actions
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+use crate::ssh::{run_ssh_action, ssh_action_key, Terminal, SSH_ACTION_IDS};
+
+use super::bridge;
+
+/// Register `lux.ssh`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in SSH action handlers (see crate::ssh)
+    for action_id in SSH_ACTION_IDS {
+        let ssh = registry.ssh();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_ssh_action(action_id, &item, &ssh.terminal())?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&ssh_action_key(action_id), handler)?;
+    }
+
+    // lux.ssh - SSH hosts (root view)
+    //
+    // Usage:
+    //   lux.ssh.set_terminal("iterm")     -- "terminal", "iterm", "warp", or any app name
+    //   lux.ssh.terminal()                -- "iTerm"
+    {
+        let ssh_table = lua.create_table()?;
+
+        let ssh = registry.ssh();
+        let set_terminal_fn = lua.create_function(move |_lua, name: String| {
+            ssh.set_terminal(Terminal::from_name(&name));
+            Ok(())
+        })?;
+        ssh_table.set("set_terminal", set_terminal_fn)?;
+
+        let ssh = registry.ssh();
+        let terminal_fn =
+            lua.create_function(move |_lua, ()| Ok(ssh.terminal().app_name().to_string()))?;
+        ssh_table.set("terminal", terminal_fn)?;
+
+        lux.set("ssh", ssh_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,314 @@
+//! Lua bindings for text and generated values.
+//!
+//! This module provides:
+//! - `lux.text.*` - Text transformations and the frontmost selection
+//! - `lux.generate.password/string/uuid/lorem()` - Random values from the OS generator
+//! - The transform and generator views
+
+use std::sync::Arc;
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+use parking_lot::Mutex;
+
+use crate::desktop;
+use crate::generate::{
+    generated_value, generator_items, lorem, password, random_string, uuid_v4, uuid_v7,
+    PasswordOptions, GENERATOR_VIEW_ID,
+};
+use crate::profiles::ProfileRegistry;
+use crate::registry::PluginRegistry;
+use crate::text::{
+    read_input, selected_text, transform_items, transform_result, TextSource, Transform,
+    TRANSFORM_VIEW_ID,
+};
+
+use super::bridge;
+
+/// Register `lux.text` and `lux.generate`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.text - Text transformations
+    //
+    // Usage:
+    //   lux.text.slug("Hello, World!")         -- "hello-world"
+    //   lux.text.sha256(lux.clipboard.read())
+    //   lux.text.transform("base64_decode", s) -- by transformation ID
+    //   lux.text.selection()                   -- text selected in the app the launcher opened over
+    //
+    // Every function raises an error when the transformation can't apply
+    // (e.g. invalid JSON).
+    {
+        let text_table = lua.create_table()?;
+
+        for transform in Transform::ALL {
+            let transform_fn = lua.create_function(move |_lua, text: String| {
+                transform.apply(&text).map_err(|e| {
+                    mlua::Error::RuntimeError(format!("text.{}: {}", transform.id(), e))
+                })
+            })?;
+            text_table.set(transform.id(), transform_fn)?;
+        }
+
+        let transform_fn = lua.create_function(|_lua, (id, text): (String, String)| {
+            let transform = Transform::from_id(&id).ok_or_else(|| {
+                mlua::Error::RuntimeError(format!(
+                    "text.transform: unknown transformation '{}'",
+                    id
+                ))
+            })?;
+            transform
+                .apply(&text)
+                .map_err(|e| mlua::Error::RuntimeError(format!("text.transform: {}", e)))
+        })?;
+        text_table.set("transform", transform_fn)?;
+
+        let profiles = registry.profiles();
+        let selection_fn = lua.create_function(move |_lua, ()| {
+            let context = profiles.last_context();
+            Ok(selected_text(context.bundle_id.as_deref())?)
+        })?;
+        text_table.set("selection", selection_fn)?;
+
+        lux.set("text", text_table)?;
+    }
+
+    // lux.generate - Random values from the operating system's generator
+    //
+    // Usage:
+    //   lux.generate.password()                                  -- 20 characters, all classes
+    //   lux.generate.password({ length = 32, symbols = false, exclude_ambiguous = true })
+    //   lux.generate.string(8, "abcdef0123456789")
+    //   lux.generate.uuid()                                      -- v4; lux.generate.uuid(7) is time-ordered
+    //   lux.generate.lorem(50)                                   -- words of lorem ipsum
+    {
+        let generate_table = lua.create_table()?;
+
+        let password_fn = lua.create_function(|_lua, opts: Option<Table>| {
+            let options = match opts {
+                Some(opts) => password_options(&opts)?,
+                None => PasswordOptions::default(),
+            };
+            Ok(password(&options)?)
+        })?;
+        generate_table.set("password", password_fn)?;
+
+        let string_fn = lua.create_function(|_lua, (length, charset): (usize, String)| {
+            Ok(random_string(length, &charset)?)
+        })?;
+        generate_table.set("string", string_fn)?;
+
+        let uuid_fn = lua.create_function(|_lua, version: Option<u8>| match version {
+            None | Some(4) => Ok(uuid_v4()),
+            Some(7) => Ok(uuid_v7()),
+            Some(v) => Err(mlua::Error::RuntimeError(format!(
+                "generate.uuid: unsupported version {} (expected 4 or 7)",
+                v
+            ))),
+        })?;
+        generate_table.set("uuid", uuid_fn)?;
+
+        let lorem_fn = lua.create_function(|_lua, words: usize| Ok(lorem(words)?))?;
+        generate_table.set("lorem", lorem_fn)?;
+
+        lux.set("generate", generate_table)?;
+    }
+
+    Ok(())
+}
+
+/// Build the text transform view.
+///
+/// The input is read when the view opens (an empty query), from the
+/// selection in the app the launcher opened over or the clipboard, per
+/// `opts.source`. The query filters transformations by name.
+pub(super) fn transform_view(
+    lua: &Lua,
+    profiles: Arc<ProfileRegistry>,
+    opts: Option<Table>,
+) -> LuaResult<Table> {
+    let opts = match opts {
+        Some(opts) => opts,
+        None => lua.create_table()?,
+    };
+    let source = match opts.get::<Option<String>>("source")? {
+        Some(name) => TextSource::from_name(&name).ok_or_else(|| {
+            mlua::Error::RuntimeError(format!("builtin.transform: unknown source '{}'", name))
+        })?,
+        None => TextSource::default(),
+    };
+
+    let view = lua.create_table()?;
+    view.set(
+        "id",
+        opts.get::<Option<String>>("id")?
+            .unwrap_or_else(|| TRANSFORM_VIEW_ID.to_string()),
+    )?;
+    view.set(
+        "title",
+        opts.get::<Option<String>>("title")?
+            .unwrap_or_else(|| t("Transform Text")),
+    )?;
+    view.set("placeholder", t("Filter transformations..."))?;
+
+    let input: Arc<Mutex<Option<String>>> = Arc::default();
+
+    // search(query, ctx) - every transformation of the input
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let mut input = input.lock();
+        if query.is_empty() || input.is_none() {
+            let context = profiles.last_context();
+            *input = read_input(source, context.bundle_id.as_deref())?;
+        }
+        let Some(text) = input.as_deref().filter(|text| !text.is_empty()) else {
+            return ctx.call_method::<()>("set_groups", lua.create_table()?);
+        };
+
+        let group = lua.create_table()?;
+        group.set("title", t("Transformations"))?;
+        let group_items = lua.create_table()?;
+        for item in transform_items(text, &query) {
+            group_items.push(bridge::item_to_lua(lua, &item)?)?;
+        }
+        group.set("items", group_items)?;
+        let result = lua.create_table()?;
+        result.push(group)?;
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy the result
+    let get_actions_fn = lua.create_function(|lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        let Some(result) = transform_result(&item) else {
+            return Ok(actions);
+        };
+
+        let action = lua.create_table()?;
+        action.set("id", "copy")?;
+        action.set("title", t("Copy Result"))?;
+        action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().clipboard_write(&result)?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+        actions.push(action)?;
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
+
+/// Read password options from a Lua table (missing fields keep the defaults).
+fn password_options(opts: &Table) -> LuaResult<PasswordOptions> {
+    let defaults = PasswordOptions::default();
+    Ok(PasswordOptions {
+        length: opts
+            .get::<Option<usize>>("length")?
+            .unwrap_or(defaults.length),
+        lowercase: opts
+            .get::<Option<bool>>("lowercase")?
+            .unwrap_or(defaults.lowercase),
+        uppercase: opts
+            .get::<Option<bool>>("uppercase")?
+            .unwrap_or(defaults.uppercase),
+        digits: opts
+            .get::<Option<bool>>("digits")?
+            .unwrap_or(defaults.digits),
+        symbols: opts
+            .get::<Option<bool>>("symbols")?
+            .unwrap_or(defaults.symbols),
+        exclude_ambiguous: opts
+            .get::<Option<bool>>("exclude_ambiguous")?
+            .unwrap_or(defaults.exclude_ambiguous),
+    })
+}
+
+/// Build the built-in Generator view (passwords, PINs, tokens, UUIDs, lorem ipsum).
+///
+/// Values are regenerated as the query changes. With `paste = true` the
+/// primary action pastes into the frontmost app instead of copying.
+pub(super) fn generator_view(lua: &Lua, opts: Option<Table>) -> LuaResult<Table> {
+    let opts = match opts {
+        Some(opts) => opts,
+        None => lua.create_table()?,
+    };
+    let options = password_options(&opts)?;
+    let paste = opts.get::<Option<bool>>("paste")?.unwrap_or(false);
+
+    let view = lua.create_table()?;
+    view.set(
+        "id",
+        opts.get::<Option<String>>("id")?
+            .unwrap_or_else(|| GENERATOR_VIEW_ID.to_string()),
+    )?;
+    view.set(
+        "title",
+        opts.get::<Option<String>>("title")?
+            .unwrap_or_else(|| t("Generate")),
+    )?;
+    view.set("placeholder", t("Filter generators, or type a length..."))?;
+
+    // search(query, ctx) - a fresh value from every matching generator
+    let search_fn = lua.create_function(move |lua, (query, ctx): (String, AnyUserData)| {
+        let group = lua.create_table()?;
+        group.set("title", t("Generated"))?;
+        let group_items = lua.create_table()?;
+        for item in generator_items(&query, &options)? {
+            group_items.push(bridge::item_to_lua(lua, &item)?)?;
+        }
+        group.set("items", group_items)?;
+        let result = lua.create_table()?;
+        result.push(group)?;
+        ctx.call_method::<()>("set_groups", result)
+    })?;
+    view.set("search", search_fn)?;
+
+    // get_actions(item, ctx) - copy or paste the value
+    let get_actions_fn = lua.create_function(move |lua, (item, _ctx): (Table, Table)| {
+        let item = bridge::parse_item(lua, item)?;
+        let actions = lua.create_table()?;
+        let Some(value) = generated_value(&item) else {
+            return Ok(actions);
+        };
+
+        let copy = lua.create_table()?;
+        copy.set("id", "copy")?;
+        copy.set("title", t("Copy"))?;
+        let copied = value.clone();
+        copy.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().clipboard_write(&copied)?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+
+        let paste_action = lua.create_table()?;
+        paste_action.set("id", "paste")?;
+        paste_action.set("title", t("Paste"))?;
+        paste_action.set(
+            "handler",
+            lua.create_function(move |_lua, (_items, ctx): (Table, AnyUserData)| {
+                desktop::current().paste(&value)?;
+                ctx.call_method::<()>("dismiss", ())
+            })?,
+        )?;
+
+        // The first action is the default
+        if paste {
+            actions.push(paste_action)?;
+            actions.push(copy)?;
+        } else {
+            actions.push(copy)?;
+            actions.push(paste_action)?;
+        }
+        Ok(actions)
+    })?;
+    view.set("get_actions", get_actions_fn)?;
+
+    Ok(view)
+}
@@ -0,0 +1,96 @@
+//! Lua bindings for the theme.
+//!
+//! This module provides:
+//! - `lux.theme.set/get/reset()` - Theme overrides
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+use crate::theme::{ThemeAppearance, ThemeDensity, ThemeOverrides};
+
+/// Register `lux.theme`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.theme - Theme overrides (applied the next time the launcher opens)
+    //
+    // Usage:
+    //   lux.theme.set({ appearance = "dark", accent_hue = 280, font_size = 15 })
+    //   lux.theme.set({ reduce_motion = true, increase_contrast = true }) -- override System Settings
+    //   lux.theme.set({ density = "compact", scale = 1.25 })  -- rows, paddings, and text together
+    //   lux.theme.get()    -- { appearance = "dark", reduce_motion = true, ... }
+    //   lux.theme.reset()  -- back to defaults and system settings
+    {
+        let theme_table = lua.create_table()?;
+
+        let theme = registry.theme();
+        let set_fn = lua.create_function(move |_lua, opts: Table| {
+            let overrides = parse_theme_overrides(&opts)?;
+            theme
+                .set(overrides)
+                .map_err(|e| mlua::Error::RuntimeError(format!("theme.set: {}", e)))
+        })?;
+        theme_table.set("set", set_fn)?;
+
+        let theme = registry.theme();
+        let get_fn = lua.create_function(move |lua, ()| {
+            let overrides = theme.get();
+            let table = lua.create_table()?;
+            table.set("appearance", overrides.appearance.map(|a| a.as_str()))?;
+            table.set("accent_hue", overrides.accent_hue)?;
+            table.set("font_family", overrides.font_family)?;
+            table.set("font_size", overrides.font_size)?;
+            table.set("reduce_motion", overrides.reduce_motion)?;
+            table.set("increase_contrast", overrides.increase_contrast)?;
+            table.set("density", overrides.density.map(|d| d.as_str()))?;
+            table.set("scale", overrides.scale)?;
+            Ok(table)
+        })?;
+        theme_table.set("get", get_fn)?;
+
+        let theme = registry.theme();
+        let reset_fn = lua.create_function(move |_lua, ()| {
+            theme.reset();
+            Ok(())
+        })?;
+        theme_table.set("reset", reset_fn)?;
+
+        lux.set("theme", theme_table)?;
+    }
+
+    Ok(())
+}
+
+/// Parse the options table of `lux.theme.set(opts)`.
+fn parse_theme_overrides(opts: &Table) -> LuaResult<ThemeOverrides> {
+    let appearance = match opts.get::<Option<String>>("appearance")? {
+        None => None,
+        Some(name) => Some(ThemeAppearance::from_name(&name).ok_or_else(|| {
+            mlua::Error::RuntimeError(format!(
+                "theme.set: unknown appearance '{}' (expected \"light\", \"dark\", or \"system\")",
+                name
+            ))
+        })?),
+    };
+
+    let density = match opts.get::<Option<String>>("density")? {
+        None => None,
+        Some(name) => Some(ThemeDensity::from_name(&name).ok_or_else(|| {
+            mlua::Error::RuntimeError(format!(
+                "theme.set: unknown density '{}' (expected \"compact\" or \"comfortable\")",
+                name
+            ))
+        })?),
+    };
+
+    Ok(ThemeOverrides {
+        appearance,
+        accent_hue: opts.get("accent_hue")?,
+        font_family: opts.get("font_family")?,
+        font_size: opts.get("font_size")?,
+        reduce_motion: opts.get("reduce_motion")?,
+        increase_contrast: opts.get("increase_contrast")?,
+        density,
+        scale: opts.get("scale")?,
+    })
+}
@@ -0,0 +1,119 @@
+//! Lua bindings for timers.
+//!
+//! This module provides:
+//! - `lux.timers.start/cancel/list()` - Countdown timers
+//! - The cancel action for timer items
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use lux_core::i18n::t;
+use mlua::{AnyUserData, Function, Lua, ObjectLike, Result as LuaResult, Table, Value};
+
+use crate::keymap::generate_handler_id;
+use crate::registry::PluginRegistry;
+use crate::timers::{parse_duration, Timer, TIMER_CANCEL_KEY};
+use crate::types::LuaFunctionRef;
+
+use super::bridge;
+
+/// Register `lux.timers`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.timers - Countdown timers
+    //
+    // Usage:
+    //   local id = lux.timers.start("25m", "Focus", function(timer)
+    //     lux.timers.start("5m", "Break")
+    //   end)
+    //   lux.timers.start(90, "Tea")       -- seconds
+    //   lux.timers.cancel(id)
+    //   lux.timers.list()                 -- { { id, label, remaining }, ... }
+    {
+        let timers_table = lua.create_table()?;
+
+        let timers = registry.timers();
+        let start_fn = lua.create_function(
+            move |lua, (duration, label, on_done): (Value, Option<String>, Option<Function>)| {
+                let duration = match duration {
+                    Value::Integer(n) if n > 0 => Duration::from_secs(n as u64),
+                    Value::Number(n) if n > 0.0 => Duration::from_secs_f64(n),
+                    Value::String(ref s) => parse_duration(&s.to_str()?)
+                        .filter(|d| !d.is_zero())
+                        .ok_or_else(|| {
+                        mlua::Error::RuntimeError(format!(
+                            "timers.start: invalid duration '{}'",
+                            s.to_string_lossy()
+                        ))
+                    })?,
+                    _ => return Err(mlua::Error::RuntimeError(
+                        "timers.start: duration must be positive seconds or a string like '25m'"
+                            .to_string(),
+                    )),
+                };
+
+                let on_done = match on_done {
+                    Some(func) => {
+                        let key = format!("timer:done:{}", generate_handler_id());
+                        Some(LuaFunctionRef::from_function(lua, func, key)?)
+                    }
+                    None => None,
+                };
+
+                let label = label.unwrap_or_else(|| t("Timer"));
+                Ok(timers.start(duration, label, on_done))
+            },
+        )?;
+        timers_table.set("start", start_fn)?;
+
+        let timers = registry.timers();
+        let cancel_fn = lua.create_function(move |lua, id: u64| match timers.cancel(id) {
+            Some(timer) => {
+                if let Some(on_done) = timer.on_done {
+                    lua.unset_named_registry_value(&on_done.key)?;
+                }
+                Ok(true)
+            }
+            None => Ok(false),
+        })?;
+        timers_table.set("cancel", cancel_fn)?;
+
+        let timers = registry.timers();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let now = Instant::now();
+            let table = lua.create_table()?;
+            for (i, timer) in timers.list().iter().enumerate() {
+                table.set(i + 1, timer_to_lua(lua, timer, now)?)?;
+            }
+            Ok(table)
+        })?;
+        timers_table.set("list", list_fn)?;
+
+        lux.set("timers", timers_table)?;
+    }
+
+    // Built-in cancel action for timer items in the root view
+    {
+        let timers = registry.timers();
+        let cancel_fn = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            let id = item.data.as_ref().and_then(|d| d["timer_id"].as_u64());
+            if let Some(on_done) = id.and_then(|id| timers.cancel(id)?.on_done) {
+                lua.unset_named_registry_value(&on_done.key)?;
+            }
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(TIMER_CANCEL_KEY, cancel_fn)?;
+    }
+
+    Ok(())
+}
+
+/// Convert a Timer to a Lua table (`remaining` in whole seconds).
+pub fn timer_to_lua(lua: &Lua, timer: &Timer, now: Instant) -> LuaResult<Table> {
+    let table = lua.create_table()?;
+    table.set("id", timer.id)?;
+    table.set("label", timer.label.as_str())?;
+    table.set("duration", timer.duration.as_secs())?;
+    table.set("remaining", timer.remaining(now).as_secs())?;
+    Ok(table)
+}
@@ -0,0 +1,140 @@
+//! Lua bindings for the launcher UI.
+//!
+//! This module provides:
+//! - `lux.ui` - UI control operations
+//! - `lux.ui.sounds/play_sound()` - Sound effects and haptics on open, execute, and error
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table, Value};
+
+use crate::audio::{EventSound, SoundSettings};
+use crate::mouse::MouseSettings;
+use crate::registry::PluginRegistry;
+
+/// Register `lux.ui`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.ui - UI control operations
+    // Note: These create effects that need to be handled by the UI layer
+    //
+    // Sounds (off until enabled; read each time the launcher opens or runs an action):
+    //   lux.ui.sounds{ enabled = true, volume = 0.4, haptics = true }
+    //   lux.ui.sounds{ open = false, error = "Funk" }  -- per event: false, true, sound name or file
+    //   lux.ui.sounds()                                -- current settings
+    //   lux.ui.play_sound("Glass")                     -- play a sound now
+    //
+    // Mouse (right-click always opens a row's actions at the pointer):
+    //   lux.ui.mouse{ hover = true }                   -- hovering a row moves the cursor to it
+    //   lux.ui.mouse()                                 -- current settings
+    {
+        let ui_table = lua.create_table()?;
+
+        // lux.ui.show() - Show the launcher window
+        let show_fn = lua.create_function(|_lua, ()| {
+            // TODO: Connect to UI layer - for now just log
+            tracing::debug!("lux.ui.show() called");
+            Ok(())
+        })?;
+        ui_table.set("show", show_fn)?;
+
+        // lux.ui.hide() - Hide the launcher window
+        let hide_fn = lua.create_function(|_lua, ()| {
+            tracing::debug!("lux.ui.hide() called");
+            Ok(())
+        })?;
+        ui_table.set("hide", hide_fn)?;
+
+        // lux.ui.toggle() - Toggle the launcher window
+        let toggle_fn = lua.create_function(|_lua, ()| {
+            tracing::debug!("lux.ui.toggle() called");
+            Ok(())
+        })?;
+        ui_table.set("toggle", toggle_fn)?;
+
+        // lux.ui.notify(message, opts?) - Show a notification
+        let notify_fn =
+            lua.create_function(|_lua, (message, _opts): (String, Option<Table>)| {
+                // TODO: Connect to notification system
+                tracing::info!("Notification: {}", message);
+                Ok(())
+            })?;
+        ui_table.set("notify", notify_fn)?;
+
+        // lux.ui.sounds(opts?) - Configure sounds, returns the current settings
+        let sounds = registry.sounds();
+        let sounds_fn = lua.create_function(move |lua, opts: Option<Table>| {
+            if let Some(opts) = opts {
+                let settings = parse_sound_settings(lua, &opts)?;
+                sounds
+                    .set(settings)
+                    .map_err(|e| mlua::Error::RuntimeError(format!("ui.sounds: {}", e)))?;
+            }
+            let settings = sounds.get();
+            let table = lua.create_table()?;
+            table.set("enabled", settings.enabled.unwrap_or(false))?;
+            table.set("volume", settings.volume())?;
+            table.set("haptics", settings.haptics.unwrap_or(false))?;
+            for (name, sound) in [
+                ("open", &settings.open),
+                ("execute", &settings.execute),
+                ("error", &settings.error),
+            ] {
+                match sound {
+                    Some(EventSound::Off) => table.set(name, false)?,
+                    Some(EventSound::Named(sound)) => table.set(name, sound.as_str())?,
+                    Some(EventSound::Default) | None => table.set(name, true)?,
+                }
+            }
+            Ok(table)
+        })?;
+        ui_table.set("sounds", sounds_fn)?;
+
+        // lux.ui.play_sound(name, volume?) - Play a system sound or a sound file
+        let sounds = registry.sounds();
+        let play_sound_fn =
+            lua.create_function(move |_lua, (name, volume): (String, Option<f32>)| {
+                let volume = volume.unwrap_or_else(|| sounds.get().volume());
+                Ok(crate::audio::play(&name, volume.clamp(0.0, 1.0))?)
+            })?;
+        ui_table.set("play_sound", play_sound_fn)?;
+
+        // lux.ui.mouse(opts?) - Configure mouse behavior, returns the current settings
+        let mouse = registry.mouse();
+        let mouse_fn = lua.create_function(move |lua, opts: Option<Table>| {
+            if let Some(opts) = opts {
+                mouse.set(MouseSettings {
+                    hover: opts.get("hover")?,
+                });
+            }
+            let table = lua.create_table()?;
+            table.set("hover", mouse.get().hover_moves_cursor())?;
+            Ok(table)
+        })?;
+        ui_table.set("mouse", mouse_fn)?;
+
+        lux.set("ui", ui_table)?;
+    }
+
+    Ok(())
+}
+
+/// Parse the options table of `lux.ui.sounds(opts)`.
+fn parse_sound_settings(lua: &Lua, opts: &Table) -> LuaResult<SoundSettings> {
+    let event = |name: &str| -> LuaResult<Option<EventSound>> {
+        match opts.get::<Value>(name)? {
+            Value::Nil => Ok(None),
+            Value::Boolean(false) => Ok(Some(EventSound::Off)),
+            Value::Boolean(true) => Ok(Some(EventSound::Default)),
+            other => Ok(Some(EventSound::Named(lua.unpack::<String>(other)?))),
+        }
+    };
+
+    Ok(SoundSettings {
+        enabled: opts.get("enabled")?,
+        volume: opts.get("volume")?,
+        haptics: opts.get("haptics")?,
+        open: event("open")?,
+        execute: event("execute")?,
+        error: event("error")?,
+    })
+}
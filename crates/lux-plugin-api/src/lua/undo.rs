@@ -0,0 +1,49 @@
+//! Lua bindings for undo.
+//!
+//! This module provides:
+//! - `lux.undo.register/latest()` - Undo stack (Cmd+Z in the launcher)
+
+use std::sync::Arc;
+
+use mlua::{Function, Lua, Result as LuaResult, Table};
+
+use crate::keymap::generate_handler_id;
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+use crate::undo::UndoOp;
+
+/// Register `lux.undo`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.undo - Undo stack, shared with the built-in file operations
+    //
+    // Usage:
+    //   lux.undo.register("Archived 3 notes", function()
+    //     -- put the notes back
+    //   end)
+    //   lux.undo.latest()                 -- "Moved report.pdf to Trash" or nil
+    {
+        let undo_table = lua.create_table()?;
+
+        let undo = registry.undo();
+        let register_fn =
+            lua.create_function(move |lua, (description, func): (String, Function)| {
+                // Free the functions of entries that fell off the stack
+                for key in undo.take_retired() {
+                    lua.unset_named_registry_value(&key)?;
+                }
+                let key = format!("undo:{}", generate_handler_id());
+                let func = LuaFunctionRef::from_function(lua, func, key)?;
+                Ok(undo.push(description, UndoOp::Lua(func)))
+            })?;
+        undo_table.set("register", register_fn)?;
+
+        let undo = registry.undo();
+        let latest_fn =
+            lua.create_function(move |_lua, ()| Ok(undo.latest().map(|info| info.description)))?;
+        undo_table.set("latest", latest_fn)?;
+
+        lux.set("undo", undo_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! Lua bindings for views and the root view.
+//!
+//! This module provides:
+//! - `lux.views.add/get/list()` - View registry
+//! - `lux.set_root(view)` - Set the root view
+
+use std::sync::Arc;
+
+use mlua::{Lua, Result as LuaResult, Table, Value};
+
+use crate::registry::PluginRegistry;
+use crate::views::ViewRegistryError;
+use crate::warnings::ConfigWarning;
+
+use super::parse::{check_view_table, parse_view, parse_view_definition, ViewApi};
+
+/// Register `lux.views` and `lux.set_root`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.set_root(view) - set the root view
+    {
+        let registry = Arc::clone(registry);
+        let set_root_fn = lua.create_function(move |lua, table: Table| {
+            let warnings = check_view_table(&table, ViewApi::Root)?;
+            let view = parse_view(lua, table)?;
+
+            record_warnings(&registry, warnings);
+            registry.set_root_view(view);
+            Ok(())
+        })?;
+        lux.set("set_root", set_root_fn)?;
+    }
+
+    // lux.views namespace
+    let views_table = lua.create_table()?;
+
+    // lux.views.add(def) - register a view
+    {
+        let registry = Arc::clone(registry);
+        let add_fn = lua.create_function(move |lua, table: Table| {
+            let warnings = check_view_table(&table, ViewApi::Add)?;
+            let view_def = parse_view_definition(lua, table)?;
+            record_warnings(&registry, warnings);
+            let view_registry = registry.views();
+
+            view_registry.add(view_def).map_err(|e| match e {
+                ViewRegistryError::ViewAlreadyExists(id) => {
+                    mlua::Error::RuntimeError(format!("View '{}' already exists", id))
+                }
+                _ => mlua::Error::RuntimeError(e.to_string()),
+            })?;
+
+            Ok(())
+        })?;
+        views_table.set("add", add_fn)?;
+    }
+
+    // lux.views.get(id) - get a view by ID (returns table with id, title, placeholder, selection)
+    {
+        let registry = Arc::clone(registry);
+        let get_fn = lua.create_function(move |lua, id: String| {
+            let view_registry = registry.views();
+
+            match view_registry.with_view(&id, |view| {
+                let table = lua.create_table()?;
+                table.set("id", view.id.as_str())?;
+                if let Some(ref title) = view.title {
+                    table.set("title", title.as_str())?;
+                }
+                if let Some(ref placeholder) = view.placeholder {
+                    table.set("placeholder", placeholder.as_str())?;
+                }
+                let selection_str = match view.selection {
+                    lux_core::SelectionMode::Single => "single",
+                    lux_core::SelectionMode::Multi => "multi",
+                    lux_core::SelectionMode::Custom => "custom",
+                };
+                table.set("selection", selection_str)?;
+                Ok::<_, mlua::Error>(table)
+            }) {
+                Some(result) => result.map(Value::Table),
+                None => Ok(Value::Nil),
+            }
+        })?;
+        views_table.set("get", get_fn)?;
+    }
+
+    // lux.views.list() - list registered view IDs (minus views the active profile
+    // or the blocklist disables)
+    {
+        let registry = Arc::clone(registry);
+        let list_fn = lua.create_function(move |lua, ()| {
+            let view_registry = registry.views();
+            let profiles = registry.profiles();
+            let blocklist = registry.blocklist();
+            let ids: Vec<String> = view_registry
+                .list()
+                .into_iter()
+                .filter(|id| profiles.is_view_enabled(id) && !blocklist.is_view_disabled(id))
+                .collect();
+
+            let table = lua.create_table()?;
+            for (i, id) in ids.iter().enumerate() {
+                table.set(i + 1, id.as_str())?;
+            }
+
+            Ok(table)
+        })?;
+        views_table.set("list", list_fn)?;
+    }
+
+    lux.set("views", views_table)?;
+
+    Ok(())
+}
+
+/// Record the warnings about a view table given to `lux.views.add` or `lux.set_root`.
+fn record_warnings(registry: &PluginRegistry, warnings: Vec<ConfigWarning>) {
+    let config_warnings = registry.config_warnings();
+    for warning in warnings {
+        config_warnings.record(warning);
+    }
+}
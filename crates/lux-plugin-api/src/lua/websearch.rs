@@ -0,0 +1,97 @@
+//! Lua bindings for web search.
+//!
+//! This module provides:
+//! - `lux.websearch.add/remove/list()` - Web search engines
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::desktop;
+use crate::registry::PluginRegistry;
+use crate::websearch::{SearchEngine, WEBSEARCH_OPEN_KEY};
+
+/// Register `lux.websearch`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.websearch - Web search engines
+    //
+    // Usage:
+    //   lux.websearch.add({ keyword = "yt", name = "YouTube",
+    //                       url = "https://youtube.com/results?q=%s" })
+    //   lux.websearch.add({ keyword = "gh", url = "https://github.com/search?q=%s", fallback = true })
+    //   lux.websearch.remove("ddg")
+    //   lux.websearch.list()
+    {
+        let websearch_table = lua.create_table()?;
+
+        // lux.websearch.add({ keyword, url, name?, fallback? })
+        {
+            let websearch = registry.websearch();
+            let add_fn = lua.create_function(move |_lua, opts: Table| {
+                let keyword: String = opts.get("keyword").map_err(|_| {
+                    mlua::Error::RuntimeError("websearch.add requires 'keyword' field".to_string())
+                })?;
+                let url: String = opts.get("url").map_err(|_| {
+                    mlua::Error::RuntimeError("websearch.add requires 'url' field".to_string())
+                })?;
+                let name: String = opts
+                    .get::<Option<String>>("name")?
+                    .unwrap_or_else(|| keyword.clone());
+                let fallback = opts.get::<Option<bool>>("fallback")?.unwrap_or(false);
+
+                let engine = SearchEngine::new(keyword, name, url).with_fallback(fallback);
+                websearch
+                    .add(engine)
+                    .map_err(|e| mlua::Error::RuntimeError(e.to_string()))
+            })?;
+            websearch_table.set("add", add_fn)?;
+        }
+
+        // lux.websearch.remove(keyword) - returns true if removed
+        {
+            let websearch = registry.websearch();
+            let remove_fn =
+                lua.create_function(move |_lua, keyword: String| Ok(websearch.remove(&keyword)))?;
+            websearch_table.set("remove", remove_fn)?;
+        }
+
+        // lux.websearch.list() - list engines as { keyword, name, url, fallback }
+        {
+            let websearch = registry.websearch();
+            let list_fn = lua.create_function(move |lua, ()| {
+                let table = lua.create_table()?;
+                for (i, engine) in websearch.list().iter().enumerate() {
+                    let entry = lua.create_table()?;
+                    entry.set("keyword", engine.keyword.as_str())?;
+                    entry.set("name", engine.name.as_str())?;
+                    entry.set("url", engine.url.as_str())?;
+                    entry.set("fallback", engine.fallback)?;
+                    table.set(i + 1, entry)?;
+                }
+                Ok(table)
+            })?;
+            websearch_table.set("list", list_fn)?;
+        }
+
+        lux.set("websearch", websearch_table)?;
+
+        // Built-in handler for web search items: open each URL, then dismiss
+        let open_fn = lua.create_function(|_lua, (items, ctx): (Table, AnyUserData)| {
+            for item in items.sequence_values::<Table>() {
+                let url: Option<String> = item?
+                    .get::<Option<Table>>("data")?
+                    .map(|data| data.get("url"))
+                    .transpose()?;
+                if let Some(url) = url {
+                    desktop::current().open(&url).map_err(|e| {
+                        mlua::Error::RuntimeError(format!("Failed to open URL: {}", e))
+                    })?;
+                }
+            }
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(WEBSEARCH_OPEN_KEY, open_fn)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! Lua bindings for WebSocket connections.
+//!
+//! This module provides:
+//! - `lux.ws.connect/send/close/list()` - WebSocket connections with Lua callbacks
+
+use std::sync::Arc;
+
+use mlua::{Function, Lua, Result as LuaResult, Table, Value};
+
+use crate::keymap::generate_handler_id;
+use crate::registry::PluginRegistry;
+use crate::types::LuaFunctionRef;
+use crate::websocket::{WebSockets, WsCallbacks, WsError, WsEvent, WsEventKind};
+
+use super::json_to_lua_value;
+
+/// Register `lux.ws`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // lux.ws - WebSocket connections (see crate::websocket)
+    //
+    // Usage:
+    //   local ws = lux.ws.connect("wss://stream.example.com/ticker", {
+    //       headers = { Authorization = "Bearer " .. token },   -- optional
+    //       on_open = function(ws) ws:send('{"subscribe": "BTC"}') end,
+    //       on_message = function(msg, ws)                      -- msg.text, and msg.json if JSON
+    //           price = msg.json.price
+    //       end,
+    //       on_error = function(err, ws) end,
+    //       on_close = function(code, reason, ws) end,          -- code is nil if the server sent none
+    //       view = "crypto.ticker",                             -- re-search this view after each event while shown
+    //   })
+    //   ws:send(text)        -- or lux.ws.send(ws.id, text)
+    //   ws:close()           -- or lux.ws.close(ws.id); on_close still runs
+    //   lux.ws.list()        -- { { id, url, state }, ... }
+    //
+    // Connections run in the background and stay open while the launcher is
+    // hidden; callbacks run on the Lua thread. They are closed when Lux quits.
+    {
+        let ws_table = lua.create_table()?;
+        let fail = |e: WsError| mlua::Error::RuntimeError(format!("ws: {}", e));
+
+        let sockets = registry.websockets();
+        let connect_fn =
+            lua.create_function(move |lua, (url, opts): (String, Option<Table>)| {
+                let mut headers = Vec::new();
+                let mut callbacks = WsCallbacks::default();
+                if let Some(opts) = opts {
+                    if let Some(table) = opts.get::<Option<Table>>("headers")? {
+                        for pair in table.pairs::<String, String>() {
+                            headers.push(pair?);
+                        }
+                    }
+                    let callback = |field: &str| -> LuaResult<Option<LuaFunctionRef>> {
+                        match opts.get::<Option<Function>>(field)? {
+                            Some(func) => {
+                                let key = format!("ws:{}:{}", field, generate_handler_id());
+                                Ok(Some(LuaFunctionRef::from_function(lua, func, key)?))
+                            }
+                            None => Ok(None),
+                        }
+                    };
+                    callbacks.on_open = callback("on_open")?;
+                    callbacks.on_message = callback("on_message")?;
+                    callbacks.on_error = callback("on_error")?;
+                    callbacks.on_close = callback("on_close")?;
+                    callbacks.view = opts.get("view")?;
+                }
+
+                let released = callbacks.clone();
+                let id = match sockets.connect(&url, headers, callbacks) {
+                    Ok(id) => id,
+                    Err(e) => {
+                        for callback in released.all() {
+                            lua.unset_named_registry_value(&callback.key)?;
+                        }
+                        return Err(fail(e));
+                    }
+                };
+                let handle = ws_handle_to_lua(lua, sockets.clone(), id, &url)?;
+                // Callbacks get the same handle; released with the callbacks on close
+                lua.set_named_registry_value(&ws_handle_key(id), handle.clone())?;
+                Ok(handle)
+            })?;
+        ws_table.set("connect", connect_fn)?;
+
+        let sockets = registry.websockets();
+        let send_fn = lua.create_function(move |_lua, (id, text): (u64, String)| {
+            sockets.send(id, text).map_err(fail)
+        })?;
+        ws_table.set("send", send_fn)?;
+
+        let sockets = registry.websockets();
+        let close_fn = lua.create_function(move |_lua, id: u64| sockets.close(id).map_err(fail))?;
+        ws_table.set("close", close_fn)?;
+
+        let sockets = registry.websockets();
+        let list_fn = lua.create_function(move |lua, ()| {
+            let list = lua.create_table()?;
+            for info in sockets.list() {
+                let table = lua.create_table()?;
+                table.set("id", info.id)?;
+                table.set("url", info.url)?;
+                table.set("state", info.state.name())?;
+                list.push(table)?;
+            }
+            Ok(list)
+        })?;
+        ws_table.set("list", list_fn)?;
+
+        lux.set("ws", ws_table)?;
+    }
+
+    Ok(())
+}
+
+/// Lua registry key of the handle `lux.ws.connect()` returned.
+fn ws_handle_key(id: u64) -> String {
+    format!("ws:handle:{}", id)
+}
+
+/// Build the handle `lux.ws.connect()` returns: `ws.id`, `ws.url`,
+/// `ws:send(text)`, and `ws:close()`.
+fn ws_handle_to_lua(lua: &Lua, sockets: Arc<WebSockets>, id: u64, url: &str) -> LuaResult<Table> {
+    let fail = |e: WsError| mlua::Error::RuntimeError(format!("ws: {}", e));
+    let handle = lua.create_table()?;
+    handle.set("id", id)?;
+    handle.set("url", url)?;
+
+    let send_sockets = sockets.clone();
+    let send_fn = lua.create_function(move |_lua, (_this, text): (Table, String)| {
+        send_sockets.send(id, text).map_err(fail)
+    })?;
+    handle.set("send", send_fn)?;
+
+    let close_fn =
+        lua.create_function(move |_lua, _this: Table| sockets.close(id).map_err(fail))?;
+    handle.set("close", close_fn)?;
+    Ok(handle)
+}
+
+/// Run the Lua callback of a WebSocket event.
+///
+/// Called by the UI on the Lua thread for each event `WebSockets::take_events()`
+/// returns. After the close event, the callbacks and the handle are released.
+pub fn deliver_ws_event(lua: &Lua, event: &WsEvent) -> LuaResult<()> {
+    let handle: Value = lua.named_registry_value(&ws_handle_key(event.id))?;
+    let result = match event.callbacks.callback(&event.kind) {
+        Some(callback) => match &event.kind {
+            WsEventKind::Open => callback.call::<_, ()>(lua, handle),
+            WsEventKind::Message(text) => {
+                let message = lua.create_table()?;
+                message.set("text", text.as_str())?;
+                if let Ok(json) = serde_json::from_str::<serde_json::Value>(text) {
+                    message.set("json", json_to_lua_value(lua, &json)?)?;
+                }
+                callback.call::<_, ()>(lua, (message, handle))
+            }
+            WsEventKind::Error(error) => callback.call::<_, ()>(lua, (error.as_str(), handle)),
+            WsEventKind::Close { code, reason } => {
+                callback.call::<_, ()>(lua, (*code, reason.as_str(), handle))
+            }
+        },
+        None => Ok(()),
+    };
+
+    if matches!(event.kind, WsEventKind::Close { .. }) {
+        for callback in event.callbacks.all() {
+            lua.unset_named_registry_value(&callback.key)?;
+        }
+        lua.unset_named_registry_value(&ws_handle_key(event.id))?;
+    }
+    result
+}
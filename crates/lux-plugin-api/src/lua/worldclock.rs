@@ -0,0 +1,79 @@
+//! Lua bindings for world clocks.
+//!
+//! This module provides:
+//! - `lux.worldclock.pin/unpin/pinned/search()` - World clocks
+//! - Handlers for the clock actions
+
+use std::sync::Arc;
+
+use mlua::{AnyUserData, Lua, ObjectLike, Result as LuaResult, Table};
+
+use crate::registry::PluginRegistry;
+use crate::worldclock::{clock_action_key, run_clock_action, CLOCK_ACTION_IDS};
+
+use super::bridge;
+
+/// Register `lux.worldclock`.
+pub(super) fn register(lua: &Lua, lux: &Table, registry: &Arc<PluginRegistry>) -> LuaResult<()> {
+    // Built-in clock action handlers (see crate::worldclock)
+    for action_id in CLOCK_ACTION_IDS {
+        let clock = registry.worldclock();
+        let handler = lua.create_function(move |lua, (items, ctx): (Table, AnyUserData)| {
+            let item = bridge::parse_item(lua, items.get(1)?)?;
+            run_clock_action(action_id, &item, &clock)?;
+            ctx.call_method::<()>("dismiss", ())
+        })?;
+        lua.set_named_registry_value(&clock_action_key(action_id), handler)?;
+    }
+
+    // lux.worldclock - Clocks pinned to the root view ("time in tokyo" answers)
+    //
+    // Usage:
+    //   lux.worldclock.pin("tokyo")            -- city, alias ("pst"), or zone ID
+    //   lux.worldclock.unpin("Asia/Tokyo")
+    //   lux.worldclock.pinned()                -- { "Asia/Tokyo", ... }
+    //   lux.worldclock.search("buenos")        -- { { zone, city, country }, ... }
+    {
+        let clock_table = lua.create_table()?;
+
+        let clock = registry.worldclock();
+        let pin_fn = lua.create_function(move |_lua, name: String| {
+            let zone = clock.resolve(&name).ok_or_else(|| {
+                mlua::Error::RuntimeError(format!("worldclock.pin: unknown time zone '{}'", name))
+            })?;
+            clock.pin(&zone.id);
+            Ok(zone.id)
+        })?;
+        clock_table.set("pin", pin_fn)?;
+
+        let clock = registry.worldclock();
+        let unpin_fn = lua.create_function(move |_lua, name: String| {
+            let id = clock.resolve(&name).map_or(name, |zone| zone.id);
+            clock.unpin(&id);
+            Ok(())
+        })?;
+        clock_table.set("unpin", unpin_fn)?;
+
+        let clock = registry.worldclock();
+        let pinned_fn = lua.create_function(move |_lua, ()| Ok(clock.pinned()))?;
+        clock_table.set("pinned", pinned_fn)?;
+
+        let clock = registry.worldclock();
+        let search_fn = lua.create_function(move |lua, place: String| {
+            let results = lua.create_table()?;
+            for (i, zone) in clock.search(&place).into_iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("zone", zone.id)?;
+                entry.set("city", zone.city)?;
+                entry.set("country", zone.country)?;
+                results.set(i + 1, entry)?;
+            }
+            Ok(results)
+        })?;
+        clock_table.set("search", search_fn)?;
+
+        lux.set("worldclock", clock_table)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,539 @@
+//! Macros: launcher interactions recorded on a tape and played back by name.
+//!
+//! This module provides:
+//! - `MacroStep` - One event on the tape: a query, a launcher action, or an action shortcut
+//! - `Macro` - A named tape, saved as an editable Lua file
+//! - `Macros` - The tape being recorded, and the saved macros (`lux.macros`)
+//! - `macro_groups()` / `macro_actions()` - The Macros view
+//!
+//! While recording, the launcher appends what the user does to the tape: the
+//! query as typed (consecutive edits collapse into the last one), the
+//! launcher actions that ran (`cursor_down`, `submit`, `execute_item(2)`),
+//! and item actions run by their shortcut. Stopping saves the tape to
+//! `macros/<name>.lua` next to init.lua:
+//!
+//! ```lua
+//! return {
+//!   name = "open-downloads",
+//!   steps = {
+//!     { query = "downloads" },
+//!     { action = "cursor_down" },
+//!     { action = "submit" },
+//!   },
+//! }
+//! ```
+//!
+//! The file can be edited by hand. It's read again on each playback, in an
+//! environment without globals, so it can only return data.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, Item};
+use mlua::{Lua, Table};
+use parking_lot::Mutex;
+
+/// ID of the built-in Macros view.
+pub const MACROS_VIEW_ID: &str = "builtin.macros";
+
+/// Item ID of the row that starts or stops recording.
+pub const RECORD_ITEM_ID: &str = "macros:record";
+
+/// Name of the folder holding the macro files.
+const MACROS_DIR: &str = "macros";
+
+// =============================================================================
+// Steps
+// =============================================================================
+
+/// One event on a macro tape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroStep {
+    /// Set the search text.
+    Query(String),
+    /// Run a launcher action by the name `lux.keymap.set()` takes.
+    Action(String),
+    /// Run the cursor item's action with this shortcut.
+    Shortcut(String),
+}
+
+impl MacroStep {
+    /// The step as a Lua table constructor, e.g. `{ action = "submit" }`.
+    fn to_lua(&self) -> String {
+        let (field, value) = match self {
+            Self::Query(query) => ("query", query),
+            Self::Action(action) => ("action", action),
+            Self::Shortcut(shortcut) => ("shortcut", shortcut),
+        };
+        format!("{{ {} = {} }}", field, lua_string(value))
+    }
+}
+
+// =============================================================================
+// Macro
+// =============================================================================
+
+/// A named macro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Macro {
+    /// Name, also the file name without `.lua`.
+    pub name: String,
+    /// Steps in the order they are played.
+    pub steps: Vec<MacroStep>,
+}
+
+impl Macro {
+    /// Create a macro.
+    pub fn new(name: impl Into<String>, steps: Vec<MacroStep>) -> Self {
+        Self {
+            name: name.into(),
+            steps,
+        }
+    }
+
+    /// Read a macro from the source of its Lua file.
+    ///
+    /// `name` is used when the file doesn't set one.
+    pub fn parse(name: &str, source: &str) -> Result<Self, MacroError> {
+        let lua = Lua::new();
+        let table: Table = lua
+            .load(source)
+            .set_name(format!("={}.lua", name))
+            .set_environment(lua.create_table()?)
+            .eval()?;
+
+        let mut steps = Vec::new();
+        let step_tables: Table = table.get("steps")?;
+        for (index, step) in step_tables.sequence_values::<Table>().enumerate() {
+            let step = step?;
+            let step = if let Some(query) = step.get::<Option<String>>("query")? {
+                MacroStep::Query(query)
+            } else if let Some(action) = step.get::<Option<String>>("action")? {
+                MacroStep::Action(action)
+            } else if let Some(shortcut) = step.get::<Option<String>>("shortcut")? {
+                MacroStep::Shortcut(shortcut)
+            } else {
+                return Err(MacroError::InvalidStep(index + 1));
+            };
+            steps.push(step);
+        }
+
+        let name = table
+            .get::<Option<String>>("name")?
+            .unwrap_or_else(|| name.to_string());
+        Ok(Self::new(name, steps))
+    }
+
+    /// The macro as the source of its Lua file.
+    pub fn to_lua(&self) -> String {
+        let mut source = String::from(
+            "-- Recorded by Lux. Steps are { query = \"...\" }, { action = \"...\" }\n\
+             -- (the names lux.keymap.set() takes), or { shortcut = \"...\" }.\n",
+        );
+        source.push_str("return {\n");
+        source.push_str(&format!("  name = {},\n", lua_string(&self.name)));
+        source.push_str("  steps = {\n");
+        for step in &self.steps {
+            source.push_str(&format!("    {},\n", step.to_lua()));
+        }
+        source.push_str("  },\n}\n");
+        source
+    }
+}
+
+/// Quote `text` as a Lua string literal.
+fn lua_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\{:03}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Whether `name` can be used as a macro (and file) name.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+// =============================================================================
+// Macros
+// =============================================================================
+
+/// A macro being recorded.
+struct Tape {
+    name: String,
+    steps: Vec<MacroStep>,
+}
+
+/// The macro being recorded, and the saved macros.
+pub struct Macros {
+    dir: Option<PathBuf>,
+    tape: Mutex<Option<Tape>>,
+}
+
+impl Macros {
+    /// Keep macro files in `dir` (None keeps nothing).
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            tape: Mutex::new(None),
+        }
+    }
+
+    /// The `macros` folder next to init.lua (or in the config folder).
+    pub fn default_dir() -> Option<PathBuf> {
+        let config_dir = lux_core::find_init_lua()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .or_else(lux_core::config_dir)?;
+        Some(config_dir.join(MACROS_DIR))
+    }
+
+    /// Path of the file of macro `name`.
+    pub fn path(&self, name: &str) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.lua", name)))
+    }
+
+    // -------------------------------------------------------------------------
+    // Recording
+    // -------------------------------------------------------------------------
+
+    /// Start recording macro `name`, dropping a recording in progress.
+    pub fn start(&self, name: &str) -> Result<(), MacroError> {
+        if !is_valid_name(name) {
+            return Err(MacroError::InvalidName(name.to_string()));
+        }
+        *self.tape.lock() = Some(Tape {
+            name: name.to_string(),
+            steps: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Start recording under the first free name "macro-1", "macro-2", ...
+    pub fn start_unnamed(&self) -> Result<String, MacroError> {
+        let saved = self.list();
+        let name = (1..)
+            .map(|number| format!("macro-{}", number))
+            .find(|name| !saved.contains(name))
+            .unwrap_or_default();
+        self.start(&name)?;
+        Ok(name)
+    }
+
+    /// Name of the macro being recorded, if any.
+    pub fn recording(&self) -> Option<String> {
+        self.tape.lock().as_ref().map(|tape| tape.name.clone())
+    }
+
+    /// Append `step` to the tape, if recording.
+    ///
+    /// A query right after another query replaces it, so typing records
+    /// only the final text.
+    pub fn record(&self, step: MacroStep) {
+        let mut tape = self.tape.lock();
+        let Some(tape) = tape.as_mut() else {
+            return;
+        };
+        if let (MacroStep::Query(_), Some(MacroStep::Query(_))) = (&step, tape.steps.last()) {
+            tape.steps.pop();
+        }
+        tape.steps.push(step);
+    }
+
+    /// Stop recording and save the macro.
+    pub fn stop(&self) -> Result<Macro, MacroError> {
+        let tape = self.tape.lock().take().ok_or(MacroError::NotRecording)?;
+        if tape.steps.is_empty() {
+            return Err(MacroError::Empty(tape.name));
+        }
+        let recorded = Macro::new(tape.name, tape.steps);
+        self.save(&recorded)?;
+        Ok(recorded)
+    }
+
+    /// Stop recording without saving. Returns whether it was recording.
+    pub fn cancel(&self) -> bool {
+        self.tape.lock().take().is_some()
+    }
+
+    // -------------------------------------------------------------------------
+    // Saved Macros
+    // -------------------------------------------------------------------------
+
+    /// Write `recorded` to its file, replacing a macro of the same name.
+    pub fn save(&self, recorded: &Macro) -> Result<PathBuf, MacroError> {
+        if !is_valid_name(&recorded.name) {
+            return Err(MacroError::InvalidName(recorded.name.clone()));
+        }
+        let path = self.path(&recorded.name).ok_or(MacroError::NoFolder)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, recorded.to_lua())?;
+        Ok(path)
+    }
+
+    /// Read macro `name` from its file.
+    pub fn load(&self, name: &str) -> Result<Macro, MacroError> {
+        let path = self
+            .path(name)
+            .filter(|path| is_valid_name(name) && path.is_file())
+            .ok_or_else(|| MacroError::NotFound(name.to_string()))?;
+        Macro::parse(name, &fs::read_to_string(path)?)
+    }
+
+    /// Names of the saved macros, sorted.
+    pub fn list(&self) -> Vec<String> {
+        let Some(entries) = self.dir.as_ref().and_then(|dir| fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "lua"))
+            .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+            .filter(|name| is_valid_name(name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Rename macro `name` to `new_name`.
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<(), MacroError> {
+        if !is_valid_name(new_name) {
+            return Err(MacroError::InvalidName(new_name.to_string()));
+        }
+        if self.list().iter().any(|saved| saved == new_name) {
+            return Err(MacroError::Exists(new_name.to_string()));
+        }
+        let mut renamed = self.load(name)?;
+        renamed.name = new_name.to_string();
+        self.save(&renamed)?;
+        self.delete(name)
+    }
+
+    /// Delete the file of macro `name`.
+    pub fn delete(&self, name: &str) -> Result<(), MacroError> {
+        let path = self
+            .path(name)
+            .filter(|path| is_valid_name(name) && path.is_file())
+            .ok_or_else(|| MacroError::NotFound(name.to_string()))?;
+        fs::remove_file(path)?;
+        Ok(())
+    }
+}
+
+impl Default for Macros {
+    fn default() -> Self {
+        Self::new(Self::default_dir())
+    }
+}
+
+/// Errors that can occur when recording, saving, or loading macros.
+#[derive(Debug, thiserror::Error)]
+pub enum MacroError {
+    #[error("Invalid macro name '{0}' (use letters, digits, '-', and '_')")]
+    InvalidName(String),
+
+    #[error("Macro '{0}' already exists")]
+    Exists(String),
+
+    #[error("Macro '{0}' not found")]
+    NotFound(String),
+
+    #[error("Nothing was recorded for macro '{0}'")]
+    Empty(String),
+
+    #[error("Not recording a macro")]
+    NotRecording,
+
+    #[error("No folder for macros")]
+    NoFolder,
+
+    #[error("Step {0} needs a query, action, or shortcut")]
+    InvalidStep(usize),
+
+    #[error("Invalid macro file: {0}")]
+    Lua(#[from] mlua::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+// =============================================================================
+// Macros View
+// =============================================================================
+
+/// Rows of the Macros view: start or stop recording, then the saved macros.
+///
+/// Typing a name and choosing the first row records under that name.
+pub fn macro_groups(macros: &Macros, query: &str) -> Groups {
+    let query = query.trim();
+    let record = match macros.recording() {
+        Some(name) => Item::new(
+            RECORD_ITEM_ID,
+            t_args("Stop Recording {name}", &[("name", &name)]),
+        ),
+        None if is_valid_name(query) => Item::new(
+            RECORD_ITEM_ID,
+            t_args("Record Macro {name}", &[("name", query)]),
+        )
+        .with_data(serde_json::json!({ "name": query })),
+        None => Item::new(RECORD_ITEM_ID, t("Record Macro")),
+    };
+    let mut groups = vec![Group::new(t("Recording"), vec![record])];
+
+    let filter = query.to_lowercase();
+    let saved: Vec<Item> = macros
+        .list()
+        .into_iter()
+        .filter(|name| name.to_lowercase().contains(&filter))
+        .map(|name| {
+            let steps = macros.load(&name).map(|m| m.steps.len());
+            let subtitle = match steps {
+                Ok(count) => t_args("{count} steps", &[("count", &count.to_string())]),
+                Err(e) => e.to_string(),
+            };
+            Item::new(format!("macro:{}", name), name.clone()).with_subtitle(subtitle)
+        })
+        .collect();
+    if !saved.is_empty() {
+        groups.push(Group::new(t("Macros"), saved));
+    }
+    groups
+}
+
+/// Actions offered for a row of the Macros view: `(id, title, shortcut)`.
+pub fn macro_actions(item: &Item) -> Vec<(&'static str, String, Option<&'static str>)> {
+    if item.id == RECORD_ITEM_ID {
+        return vec![("record", item.title.clone(), None)];
+    }
+    vec![
+        ("play", t("Play Macro"), None),
+        ("open", t("Open in Editor"), Some("cmd+o")),
+        ("rename", t("Rename Macro"), Some("cmd+e")),
+        ("delete", t("Delete Macro"), Some("cmd+backspace")),
+    ]
+}
+
+/// Name of the saved macro a row of the Macros view shows, or the name the
+/// record row records under.
+pub fn macro_name(item: &Item) -> Option<&str> {
+    if item.id == RECORD_ITEM_ID {
+        return item.data.as_ref()?.get("name")?.as_str();
+    }
+    item.id.strip_prefix("macro:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let macros = Macros::new(Some(dir.path().to_path_buf()));
+
+        // Not recording, so nothing is taped
+        macros.record(MacroStep::Action("cursor_down".to_string()));
+        assert!(matches!(macros.stop(), Err(MacroError::NotRecording)));
+
+        assert_eq!(macros.start_unnamed().unwrap(), "macro-1");
+        macros.record(MacroStep::Query("do".to_string()));
+        macros.record(MacroStep::Query("down \"x\"\n".to_string()));
+        macros.record(MacroStep::Action("cursor_down".to_string()));
+        macros.record(MacroStep::Shortcut("cmd+c".to_string()));
+        let recorded = macros.stop().unwrap();
+        assert_eq!(recorded.steps.len(), 3);
+        assert_eq!(macros.recording(), None);
+
+        assert_eq!(macros.load("macro-1").unwrap(), recorded);
+        assert_eq!(macros.start_unnamed().unwrap(), "macro-2");
+        assert!(matches!(macros.stop(), Err(MacroError::Empty(_))));
+
+        macros.rename("macro-1", "copy-first").unwrap();
+        assert_eq!(macros.list(), ["copy-first"]);
+        assert_eq!(macros.load("copy-first").unwrap().name, "copy-first");
+        assert!(matches!(
+            macros.load("../init"),
+            Err(MacroError::NotFound(_))
+        ));
+        assert!(matches!(
+            macros.start("two words"),
+            Err(MacroError::InvalidName(_))
+        ));
+        macros.delete("copy-first").unwrap();
+        assert!(macros.list().is_empty());
+    }
+
+    #[test]
+    fn test_macro_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let macros = Macros::new(Some(dir.path().to_path_buf()));
+        let steps = vec![MacroStep::Action("submit".to_string())];
+        macros.save(&Macro::new("open-mail", steps)).unwrap();
+
+        // Typing a name records under it
+        let groups = macro_groups(&macros, "standup");
+        let record = &groups[0].items[0];
+        assert_eq!(macro_name(record), Some("standup"));
+        assert_eq!(groups.len(), 1);
+
+        let groups = macro_groups(&macros, "");
+        assert_eq!(macro_name(&groups[0].items[0]), None);
+        let saved = &groups[1].items[0];
+        assert_eq!(macro_name(saved), Some("open-mail"));
+        assert_eq!(macro_actions(saved)[0].0, "play");
+
+        macros.start("standup").unwrap();
+        let groups = macro_groups(&macros, "open");
+        assert_eq!(groups[0].items[0].title, "Stop Recording standup");
+        assert_eq!(macro_actions(&groups[0].items[0])[0].0, "record");
+    }
+
+    #[test]
+    fn test_parse_edited_file() {
+        let source = r#"
+            return {
+              steps = {
+                { query = "safari" },
+                { action = "execute_item(1)" },
+              },
+            }
+        "#;
+        let parsed = Macro::parse("safari", source).unwrap();
+        assert_eq!(parsed.name, "safari");
+        assert_eq!(
+            parsed.steps[1],
+            MacroStep::Action("execute_item(1)".to_string())
+        );
+
+        // Files can only return data
+        let source = r#"os.execute("true") return { steps = {} }"#;
+        assert!(matches!(
+            Macro::parse("bad", source),
+            Err(MacroError::Lua(_))
+        ));
+        let source = r#"return { steps = { { wait = 1 } } }"#;
+        assert!(matches!(
+            Macro::parse("bad", source),
+            Err(MacroError::InvalidStep(1))
+        ));
+    }
+}
@@ -0,0 +1,498 @@
+//! Fuzzy matching, with romanized Chinese, Japanese, and Korean.
+//!
+//! This module provides:
+//! - `fuzzy_score()` - How well a query matches text, letters in order
+//! - `Romanization` - Latin spellings of text with CJK characters
+//! - `Matcher` - Scores items by title, `match_aliases`, and romanization (lux.matching)
+//!
+//! Items with CJK titles are found by typing Latin letters: "카카오톡" by
+//! `kakaotok`, "カメラ" by `kamera`, and "微信" by `weixin` or its initials
+//! `wx`. Hangul and kana are romanized built in (Revised Romanization and
+//! Hepburn, without sound changes). Chinese characters are romanized from a
+//! table: a built-in one (`data/pinyin.txt`) covers about 900 common
+//! simplified characters with their most frequent Mandarin reading, and the
+//! config adds to it or overrides it:
+//!
+//! ```lua
+//! lux.matching.add({ ["微信"] = "wei xin", ["音"] = "yin", ["乐"] = "yue" })
+//! lux.matching.load("~/.config/lux/pinyin.txt")   -- "微 wei" per line
+//! ```
+//!
+//! Table entries may be whole words, so a word can override the reading of
+//! its characters ("重庆" = "chong qing"); the longest entry wins, and spaces
+//! split syllables for the initials. Items can also list `match_aliases`,
+//! other names they are found by.
+//!
+//! Traditional characters, rarer characters, and Japanese readings of kanji
+//! are not in the built-in table; add them to the config's table or list
+//! them in `match_aliases`.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use lux_core::Item;
+use parking_lot::RwLock;
+
+/// Score added to matches on an item's title over its aliases.
+const TITLE_BONUS: u32 = 10;
+
+/// Score added to matches on the text itself over its romanization.
+const DIRECT_BONUS: u32 = 50;
+
+/// Pinyin for common Chinese characters, in the table file format.
+const BUILTIN_TABLE: &str = include_str!("../data/pinyin.txt");
+
+// =============================================================================
+// Scoring
+// =============================================================================
+
+/// How well `query` (lowercase) fuzzy-matches `text`, if it does.
+///
+/// Every query character must appear in order; runs of adjacent characters
+/// and characters at the start of a word score higher.
+pub fn fuzzy_score(text: &str, query: &str) -> Option<u32> {
+    let mut score = 0;
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut previous: Option<char> = None;
+    let mut run = 0;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        if c == wanted {
+            query_chars.next();
+            run += 1;
+            score += run;
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+        } else {
+            run = 0;
+        }
+        previous = Some(c);
+    }
+    query_chars.peek().is_none().then_some(score)
+}
+
+// =============================================================================
+// Romanization
+// =============================================================================
+
+/// Latin spellings of a text with CJK characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Romanization {
+    /// Every syllable spelled out, e.g. "weixin".
+    pub full: String,
+    /// The first letter of each syllable, e.g. "wx".
+    pub initials: String,
+}
+
+/// Hangul initial consonants (Revised Romanization).
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+
+/// Hangul vowels (Revised Romanization).
+const HANGUL_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+
+/// Hangul final consonants, as pronounced at the end of a syllable.
+const HANGUL_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+/// Hiragana (katakana are mapped onto them), spelled in `KANA_ROMAJI`.
+const KANA: &str =
+    "あいうえおかきくけこがぎぐげごさしすせそざじずぜぞたちつてとだぢづでどなにぬねの\
+                    はひふへほばびぶべぼぱぴぷぺぽまみむめもやゆよらりるれろわゐゑをんゔ\
+                    ぁぃぅぇぉゃゅょゎゕゖ";
+
+/// Hepburn for each of `KANA`, in the same order.
+const KANA_ROMAJI: &str = "a i u e o ka ki ku ke ko ga gi gu ge go sa shi su se so za ji zu ze zo \
+                           ta chi tsu te to da ji zu de do na ni nu ne no \
+                           ha hi fu he ho ba bi bu be bo pa pi pu pe po ma mi mu me mo \
+                           ya yu yo ra ri ru re ro wa i e o n vu \
+                           a i u e o ya yu yo wa ka ke";
+
+/// The hiragana for a katakana character (others are returned as is).
+fn to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Hepburn for a hiragana or katakana character.
+fn kana_romaji(c: char) -> Option<&'static str> {
+    let c = to_hiragana(c);
+    KANA.chars()
+        .zip(KANA_ROMAJI.split_whitespace())
+        .find(|(kana, _)| *kana == c)
+        .map(|(_, romaji)| romaji)
+}
+
+/// Revised Romanization for a precomposed Hangul syllable.
+fn hangul_romaji(c: char) -> Option<String> {
+    let index = (c as u32).checked_sub(0xAC00).filter(|i| *i < 11172)? as usize;
+    Some(format!(
+        "{}{}{}",
+        HANGUL_INITIALS[index / (21 * 28)],
+        HANGUL_VOWELS[index % (21 * 28) / 28],
+        HANGUL_FINALS[index % 28]
+    ))
+}
+
+/// Latin letters of a text, split into syllables and other words.
+#[derive(Default)]
+struct Syllables {
+    units: Vec<String>,
+    /// Whether the last unit is a kana (small kana and marks change it).
+    last_kana: bool,
+    /// Whether a plain word is being read into the last unit.
+    in_word: bool,
+    /// A small tsu is waiting to double the next consonant.
+    double_next: bool,
+    romanized: bool,
+}
+
+impl Syllables {
+    fn push(&mut self, syllable: &str, kana: bool) {
+        let mut syllable = syllable.to_string();
+        if std::mem::take(&mut self.double_next) {
+            if let Some(first) = syllable.chars().next().filter(|c| !"aeiou".contains(*c)) {
+                // "chi" doubles as "tchi"
+                let doubled = if syllable.starts_with("ch") {
+                    't'
+                } else {
+                    first
+                };
+                syllable.insert(0, doubled);
+            }
+        }
+        self.units.push(syllable);
+        self.last_kana = kana;
+        self.in_word = false;
+        self.romanized = true;
+    }
+
+    fn push_kana(&mut self, c: char) {
+        let hiragana = to_hiragana(c);
+        let last = self.units.last_mut().filter(|_| self.last_kana);
+        match (hiragana, last) {
+            ('っ', _) => self.double_next = true,
+            ('ー', Some(last)) => {
+                if let Some(vowel) = last.chars().last().filter(|c| "aeiou".contains(*c)) {
+                    last.push(vowel);
+                }
+            }
+            // Contracted sounds: き + ゃ = kya, し + ゃ = sha
+            ('ゃ' | 'ゅ' | 'ょ', Some(last)) if last.ends_with('i') && last.len() > 1 => {
+                last.pop();
+                let glide = kana_romaji(hiragana).unwrap_or_default();
+                if last.ends_with("sh") || last.ends_with("ch") || last.ends_with('j') {
+                    last.push_str(&glide[1..]);
+                } else {
+                    last.push_str(glide);
+                }
+            }
+            // Extended sounds: ふ + ぁ = fa, て + ぃ = ti
+            ('ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ', Some(last))
+                if last.ends_with(['u', 'i', 'e', 'o']) && last.len() > 1 =>
+            {
+                last.pop();
+                last.push_str(kana_romaji(hiragana).unwrap_or_default());
+            }
+            _ => {
+                if let Some(romaji) = kana_romaji(hiragana) {
+                    self.push(romaji, true);
+                }
+            }
+        }
+    }
+
+    fn push_plain(&mut self, c: char) {
+        if !c.is_alphanumeric() {
+            self.in_word = false;
+            self.last_kana = false;
+            return;
+        }
+        if !self.in_word {
+            self.units.push(String::new());
+            self.in_word = true;
+            self.last_kana = false;
+        }
+        if let Some(word) = self.units.last_mut() {
+            word.extend(c.to_lowercase());
+        }
+    }
+
+    fn finish(self) -> Option<Romanization> {
+        if !self.romanized {
+            return None;
+        }
+        Some(Romanization {
+            full: self.units.concat(),
+            initials: self.units.iter().filter_map(|u| u.chars().next()).collect(),
+        })
+    }
+}
+
+// =============================================================================
+// Matcher
+// =============================================================================
+
+/// Romanization of Chinese characters and words, added by the config.
+#[derive(Default)]
+struct Table {
+    entries: HashMap<String, String>,
+    /// Length in characters of the longest entry.
+    longest: usize,
+}
+
+/// Scores items against a query, romanizing CJK titles.
+pub struct Matcher {
+    table: RwLock<Table>,
+}
+
+impl Matcher {
+    /// Create a matcher with an empty table.
+    pub fn new() -> Self {
+        Self {
+            table: RwLock::new(Table::default()),
+        }
+    }
+
+    /// Add the built-in pinyin for common Chinese characters.
+    pub fn with_builtin_table(self) -> Self {
+        self.add_table(BUILTIN_TABLE);
+        self
+    }
+
+    /// Romanize `text` (a character or word) as `romanization`, with spaces
+    /// between syllables.
+    pub fn add(&self, text: &str, romanization: &str) {
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        let mut table = self.table.write();
+        table.longest = table.longest.max(text.chars().count());
+        table
+            .entries
+            .insert(text.to_string(), romanization.trim().to_lowercase());
+    }
+
+    /// Add the entries of a table file: a character or word, then its
+    /// romanization, per line. Returns how many entries were added.
+    ///
+    /// Empty lines and lines starting with `#` are skipped.
+    pub fn load_table(&self, path: &Path) -> io::Result<usize> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(self.add_table(&text))
+    }
+
+    /// Add the entries of a table in the `load_table` format.
+    fn add_table(&self, text: &str) -> usize {
+        let mut added = 0;
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((word, romanization)) = line.split_once(char::is_whitespace) {
+                self.add(word, romanization);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Number of entries in the table.
+    pub fn table_len(&self) -> usize {
+        self.table.read().entries.len()
+    }
+
+    /// Latin spellings of `text`, if it has characters to romanize.
+    pub fn romanize(&self, text: &str) -> Option<Romanization> {
+        let table = self.table.read();
+        let chars: Vec<char> = text.chars().collect();
+        let mut syllables = Syllables::default();
+
+        let mut i = 0;
+        while i < chars.len() {
+            let entry = (1..=table.longest.min(chars.len() - i))
+                .rev()
+                .find_map(|len| {
+                    let word: String = chars[i..i + len].iter().collect();
+                    table
+                        .entries
+                        .get(&word)
+                        .map(|romanization| (len, romanization))
+                });
+            if let Some((len, romanization)) = entry {
+                for syllable in romanization.split_whitespace() {
+                    syllables.push(syllable, false);
+                }
+                i += len;
+                continue;
+            }
+
+            let c = chars[i];
+            if let Some(romaji) = hangul_romaji(c) {
+                syllables.push(&romaji, false);
+            } else if kana_romaji(c).is_some() || matches!(to_hiragana(c), 'っ' | 'ー') {
+                syllables.push_kana(c);
+            } else {
+                syllables.push_plain(c);
+            }
+            i += 1;
+        }
+        syllables.finish()
+    }
+
+    /// How well `query` matches `text`, or its romanization.
+    ///
+    /// Every text matches an empty query with a score of 0.
+    pub fn score_text(&self, text: &str, query: &str) -> Option<u32> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Some(0);
+        }
+        let direct = fuzzy_score(text, &query).map(|score| score + DIRECT_BONUS);
+        let romanized = self.romanize(text).and_then(|romanization| {
+            fuzzy_score(&romanization.full, &query).max(fuzzy_score(&romanization.initials, &query))
+        });
+        direct.max(romanized)
+    }
+
+    /// How well `query` matches an item's title or one of its `match_aliases`.
+    pub fn score(&self, item: &Item, query: &str) -> Option<u32> {
+        let title = self
+            .score_text(&item.title, query)
+            .map(|score| score + TITLE_BONUS);
+        item.match_aliases
+            .iter()
+            .filter_map(|alias| self.score_text(alias, query))
+            .chain(title)
+            .max()
+    }
+
+    /// The items matching `query`, best first (ties keep their order).
+    pub fn filter(&self, items: Vec<Item>, query: &str) -> Vec<Item> {
+        let mut scored: Vec<(u32, Item)> = items
+            .into_iter()
+            .filter_map(|item| self.score(&item, query).map(|score| (score, item)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+impl Default for Matcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn romanized(matcher: &Matcher, text: &str) -> (String, String) {
+        let romanization = matcher.romanize(text).unwrap();
+        (romanization.full, romanization.initials)
+    }
+
+    #[test]
+    fn test_romanize_hangul_and_kana() {
+        assert_eq!(KANA.chars().count(), KANA_ROMAJI.split_whitespace().count());
+        let matcher = Matcher::new();
+        assert_eq!(romanized(&matcher, "카카오톡").0, "kakaotok");
+        assert_eq!(romanized(&matcher, "한글").0, "hangeul");
+        assert_eq!(romanized(&matcher, "カメラ").0, "kamera");
+        assert_eq!(romanized(&matcher, "コーヒー").0, "koohii");
+        assert_eq!(romanized(&matcher, "きって").0, "kitte");
+        assert_eq!(romanized(&matcher, "しゃしん").0, "shashin");
+        assert_eq!(romanized(&matcher, "ファイル").0, "fairu");
+        assert_eq!(
+            romanized(&matcher, "LINE ミュージック"),
+            ("linemyuujikku".to_string(), "lmjk".to_string())
+        );
+        assert_eq!(matcher.romanize("Safari"), None);
+        // Chinese characters need a table
+        assert_eq!(matcher.romanize("微信"), None);
+    }
+
+    #[test]
+    fn test_table_entries_and_longest_match() {
+        let matcher = Matcher::new();
+        matcher.add("微", "wei");
+        matcher.add("信", "xin");
+        matcher.add("重", "zhong");
+        matcher.add("重庆", "Chong Qing");
+        assert_eq!(
+            romanized(&matcher, "微信"),
+            ("weixin".to_string(), "wx".to_string())
+        );
+        assert_eq!(romanized(&matcher, "重庆").0, "chongqing");
+        assert_eq!(matcher.romanize("QQ音乐"), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pinyin.txt");
+        std::fs::write(&path, "# readings\n音 yin\n\n乐\tyue\n").unwrap();
+        assert_eq!(matcher.load_table(&path).unwrap(), 2);
+        assert_eq!(
+            romanized(&matcher, "QQ音乐"),
+            ("qqyinyue".to_string(), "qyy".to_string())
+        );
+    }
+
+    #[test]
+    fn test_builtin_table() {
+        let matcher = Matcher::new().with_builtin_table();
+        assert!(matcher.table_len() > 900);
+        assert_eq!(
+            romanized(&matcher, "微信"),
+            ("weixin".to_string(), "wx".to_string())
+        );
+        assert_eq!(romanized(&matcher, "网易云音乐").0, "wangyiyunyinyue");
+        assert_eq!(romanized(&matcher, "招商银行").1, "zsyh");
+
+        // The config's entries override it
+        matcher.add("乐", "yue");
+        assert_eq!(romanized(&matcher, "快乐").0, "kuaiyue");
+    }
+
+    #[test]
+    fn test_filter_ranks_titles_aliases_and_romanizations() {
+        let matcher = Matcher::new();
+        matcher.add("微信", "wei xin");
+        let mut settings = Item::new("settings", "設定");
+        settings.match_aliases = vec!["Settings".to_string(), "settei".to_string()];
+        let items = vec![
+            Item::new("wechat", "微信"),
+            Item::new("kakao", "카카오톡"),
+            settings,
+            Item::new("wxwork", "WeCom wx"),
+        ];
+
+        let ids = |query: &str| -> Vec<String> {
+            matcher
+                .filter(items.clone(), query)
+                .into_iter()
+                .map(|item| item.id)
+                .collect()
+        };
+        assert_eq!(ids("weixin"), ["wechat"]);
+        // Typed text beats a romanization
+        assert_eq!(ids("wx"), ["wxwork", "wechat"]);
+        assert_eq!(ids("kakao"), ["kakao"]);
+        assert_eq!(ids("settings"), ["settings"]);
+        assert_eq!(ids("").len(), 4);
+        assert!(ids("zzz").is_empty());
+    }
+}
@@ -0,0 +1,519 @@
+//! Media playback control.
+//!
+//! This module provides:
+//! - `NowPlaying` - The track playing in Music or Spotify
+//! - `MediaCommand` - Play/pause, track skipping, and output volume
+//! - `NowPlayingCache` - Short-lived cache so typing doesn't spawn a script per key
+//! - `media_group()` - The root-view "Now Playing" / media command group
+//!
+//! MediaRemote is a private framework that third-party apps can no longer
+//! query on recent macOS releases, so playback state comes from the players'
+//! scripting interfaces (JXA) instead.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Deserialize;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+use crate::error::{PluginError, PluginResult};
+use crate::permissions::{run_jxa, run_jxa_with_args};
+
+/// Type tag for media items (used to route them to the built-in actions).
+pub const MEDIA_ITEM_TYPE: &str = "media";
+
+/// Lua registry key prefix of the built-in media command handlers.
+pub const MEDIA_ACTION_KEY_PREFIX: &str = "media:";
+
+/// IDs of all media commands (one handler is registered per ID).
+pub const MEDIA_COMMAND_IDS: &[&str] = &[
+    "play_pause",
+    "next",
+    "previous",
+    "volume_up",
+    "volume_down",
+    "mute",
+    "set_volume",
+];
+
+/// Volume change applied by `VolumeUp` / `VolumeDown`.
+const VOLUME_STEP: u8 = 10;
+
+/// Players queried for now-playing state, in priority order.
+const PLAYERS: &[&str] = &["Spotify", "Music"];
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// The track currently loaded in a media player.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct NowPlaying {
+    /// Player application name.
+    pub app: String,
+    /// Track title.
+    pub title: String,
+    #[serde(default)]
+    pub artist: Option<String>,
+    #[serde(default)]
+    pub album: Option<String>,
+    /// Whether the track is playing (false when paused).
+    pub playing: bool,
+}
+
+/// A media control command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaCommand {
+    PlayPause,
+    Next,
+    Previous,
+    VolumeUp,
+    VolumeDown,
+    /// Toggle output mute.
+    Mute,
+    /// Set output volume (0-100).
+    SetVolume(u8),
+}
+
+impl MediaCommand {
+    /// Command ID (see `MEDIA_COMMAND_IDS`).
+    pub fn id(&self) -> &'static str {
+        match self {
+            MediaCommand::PlayPause => "play_pause",
+            MediaCommand::Next => "next",
+            MediaCommand::Previous => "previous",
+            MediaCommand::VolumeUp => "volume_up",
+            MediaCommand::VolumeDown => "volume_down",
+            MediaCommand::Mute => "mute",
+            MediaCommand::SetVolume(_) => "set_volume",
+        }
+    }
+
+    /// Display title (translated).
+    pub fn title(&self) -> String {
+        match self {
+            MediaCommand::PlayPause => t("Play/Pause"),
+            MediaCommand::Next => t("Next Track"),
+            MediaCommand::Previous => t("Previous Track"),
+            MediaCommand::VolumeUp => t("Volume Up"),
+            MediaCommand::VolumeDown => t("Volume Down"),
+            MediaCommand::Mute => t("Toggle Mute"),
+            MediaCommand::SetVolume(level) => {
+                t_args("Set Volume to {level}%", &[("level", &level.to_string())])
+            }
+        }
+    }
+
+    /// Parse a command ID. `set_volume` takes its level from `level`.
+    pub fn from_id(id: &str, level: Option<u8>) -> Option<Self> {
+        match id {
+            "play_pause" => Some(MediaCommand::PlayPause),
+            "next" => Some(MediaCommand::Next),
+            "previous" => Some(MediaCommand::Previous),
+            "volume_up" => Some(MediaCommand::VolumeUp),
+            "volume_down" => Some(MediaCommand::VolumeDown),
+            "mute" => Some(MediaCommand::Mute),
+            "set_volume" => level.map(|l| MediaCommand::SetVolume(l.min(100))),
+            _ => None,
+        }
+    }
+
+    /// Recover the command from an item built by `command_item()`.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        let data = item.data.as_ref()?;
+        let id = data.get("command")?.as_str()?;
+        let level = data
+            .get("level")
+            .and_then(|l| l.as_u64())
+            .map(|l| l.min(100) as u8);
+        Self::from_id(id, level)
+    }
+}
+
+/// Handler key for a media command ID.
+pub fn media_command_key(id: &str) -> String {
+    format!("{}{}", MEDIA_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Platform
+// =============================================================================
+
+/// JXA script reporting the first running player with a loaded track.
+const NOW_PLAYING_SCRIPT: &str = r#"
+function run(argv) {
+  for (const name of argv) {
+    try {
+      const app = Application(name);
+      if (!app.running()) continue;
+      const state = app.playerState();
+      if (state === 'stopped') continue;
+      const track = app.currentTrack;
+      return JSON.stringify({
+        app: name,
+        title: track.name(),
+        artist: track.artist() || null,
+        album: track.album() || null,
+        playing: state === 'playing',
+      });
+    } catch (e) {}
+  }
+  return '';
+}
+"#;
+
+/// JXA script sending a playback command (`argv[1]`) to a player (`argv[0]`).
+const PLAYER_COMMAND_SCRIPT: &str = r#"
+function run(argv) {
+  const app = Application(argv[0]);
+  if (argv[1] === 'play_pause') app.playpause();
+  else if (argv[1] === 'next') app.nextTrack();
+  else if (argv[1] === 'previous') app.previousTrack();
+  return 'ok';
+}
+"#;
+
+/// Query the currently playing track.
+///
+/// Returns `None` when no supported player is running with a loaded track.
+pub fn now_playing() -> PluginResult<Option<NowPlaying>> {
+    let output = run_jxa_with_args(NOW_PLAYING_SCRIPT, PLAYERS)?;
+    parse_now_playing(&output)
+}
+
+/// Parse the output of the now-playing script (empty means nothing playing).
+pub fn parse_now_playing(output: &str) -> PluginResult<Option<NowPlaying>> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(None);
+    }
+    serde_json::from_str(output)
+        .map(Some)
+        .map_err(|e| PluginError::Command(format!("Invalid now-playing state: {}", e)))
+}
+
+/// Current output volume (0-100).
+pub fn volume() -> PluginResult<u8> {
+    let output = run_jxa(
+        "const app = Application.currentApplication(); \
+         app.includeStandardAdditions = true; \
+         app.getVolumeSettings().outputVolume",
+    )?;
+    output
+        .trim()
+        .parse()
+        .map_err(|_| PluginError::Command(format!("Unexpected volume '{}'", output.trim())))
+}
+
+/// Set the output volume (clamped to 0-100).
+pub fn set_volume(level: u8) -> PluginResult<()> {
+    run_jxa(&format!(
+        "const app = Application.currentApplication(); \
+         app.includeStandardAdditions = true; \
+         app.setVolume(null, {{ outputVolume: {} }})",
+        level.min(100)
+    ))?;
+    Ok(())
+}
+
+/// Run a media command.
+///
+/// Playback commands go to the player reported by `now_playing()`, falling
+/// back to Music when nothing is loaded.
+pub fn run_command(command: MediaCommand) -> PluginResult<()> {
+    match command {
+        MediaCommand::VolumeUp => set_volume(volume()?.saturating_add(VOLUME_STEP)),
+        MediaCommand::VolumeDown => set_volume(volume()?.saturating_sub(VOLUME_STEP)),
+        MediaCommand::SetVolume(level) => set_volume(level),
+        MediaCommand::Mute => {
+            run_jxa(
+                "const app = Application.currentApplication(); \
+                 app.includeStandardAdditions = true; \
+                 app.setVolume(null, { outputMuted: !app.getVolumeSettings().outputMuted })",
+            )?;
+            Ok(())
+        }
+        MediaCommand::PlayPause | MediaCommand::Next | MediaCommand::Previous => {
+            let player = now_playing()?
+                .map(|np| np.app)
+                .unwrap_or_else(|| "Music".to_string());
+            run_jxa_with_args(PLAYER_COMMAND_SCRIPT, &[&player, command.id()])?;
+            Ok(())
+        }
+    }
+}
+
+// =============================================================================
+// Cache
+// =============================================================================
+
+/// Caches the now-playing state for a short time.
+///
+/// The root view asks for it on every keystroke; the script takes tens of
+/// milliseconds, so results are reused until they are `ttl` old or a media
+/// command invalidates them.
+pub struct NowPlayingCache {
+    ttl: Duration,
+    state: Mutex<Option<(Instant, Option<NowPlaying>)>>,
+}
+
+impl NowPlayingCache {
+    /// Create an empty cache.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Get the now-playing state, querying the players if the cache is stale.
+    pub fn get(&self) -> Option<NowPlaying> {
+        self.get_with(|| now_playing().ok().flatten())
+    }
+
+    /// Get the cached state, calling `fetch` if the cache is stale.
+    pub fn get_with(&self, fetch: impl FnOnce() -> Option<NowPlaying>) -> Option<NowPlaying> {
+        let mut state = self.state.lock();
+        if let Some((at, value)) = state.as_ref() {
+            if at.elapsed() < self.ttl {
+                return value.clone();
+            }
+        }
+        let value = fetch();
+        *state = Some((Instant::now(), value.clone()));
+        value
+    }
+
+    /// Drop the cached state (e.g. after play/pause or skipping a track).
+    pub fn invalidate(&self) {
+        *self.state.lock() = None;
+    }
+}
+
+impl Default for NowPlayingCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2))
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the "Now Playing" item.
+pub fn now_playing_item(now_playing: &NowPlaying) -> Item {
+    let subtitle = [now_playing.artist.as_deref(), now_playing.album.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .chain(std::iter::once(now_playing.app.as_str()))
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    Item::new("media:now_playing", now_playing.title.clone())
+        .with_subtitle(subtitle)
+        .with_icon(if now_playing.playing { "▶️" } else { "⏸" })
+        .with_type(MEDIA_ITEM_TYPE)
+        .with_type("now_playing")
+}
+
+/// Build the item for a media command.
+pub fn command_item(command: MediaCommand) -> Item {
+    let icon = match command {
+        MediaCommand::PlayPause => "⏯",
+        MediaCommand::Next => "⏭",
+        MediaCommand::Previous => "⏮",
+        MediaCommand::VolumeUp => "🔊",
+        MediaCommand::VolumeDown => "🔉",
+        MediaCommand::Mute => "🔇",
+        MediaCommand::SetVolume(_) => "🔈",
+    };
+    let mut data = serde_json::json!({ "command": command.id() });
+    if let MediaCommand::SetVolume(level) = command {
+        data["level"] = level.into();
+    }
+
+    Item::new(format!("media:{}", command.id()), command.title())
+        .with_subtitle("Media")
+        .with_icon(icon.to_string())
+        .with_type(MEDIA_ITEM_TYPE)
+        .with_type("media_command")
+        .with_data(data)
+}
+
+/// Commands matching a root-view query.
+///
+/// `volume 40` / `vol 40` yields `SetVolume(40)`; otherwise commands whose
+/// title or keywords start with the query are returned.
+pub fn matching_commands(query: &str) -> Vec<MediaCommand> {
+    let query = query.trim().to_lowercase();
+    if query.len() < 3 {
+        return Vec::new();
+    }
+
+    if let Some((word, level)) = query.split_once(char::is_whitespace) {
+        if matches!(word, "vol" | "volume") {
+            if let Ok(level) = level.trim().trim_end_matches('%').parse::<u8>() {
+                return vec![MediaCommand::SetVolume(level.min(100))];
+            }
+        }
+    }
+
+    let keywords: &[(MediaCommand, &[&str])] = &[
+        (MediaCommand::PlayPause, &["play", "pause", "resume"]),
+        (MediaCommand::Next, &["next track", "skip"]),
+        (MediaCommand::Previous, &["previous track", "back"]),
+        (MediaCommand::VolumeUp, &["volume up", "louder"]),
+        (MediaCommand::VolumeDown, &["volume down", "quieter"]),
+        (MediaCommand::Mute, &["mute", "unmute"]),
+    ];
+
+    keywords
+        .iter()
+        .filter(|(_, words)| words.iter().any(|w| w.starts_with(&query)))
+        .map(|(command, _)| *command)
+        .collect()
+}
+
+/// Build the root-view media group for a query.
+///
+/// A blank query shows the now-playing track (when there is one); otherwise
+/// the group holds the matching commands, plus the track if its title or
+/// artist matches.
+pub fn media_group(query: &str, now_playing: Option<&NowPlaying>) -> Option<Group> {
+    let trimmed = query.trim().to_lowercase();
+    let track_matches = |np: &NowPlaying| {
+        trimmed.is_empty()
+            || np.title.to_lowercase().contains(&trimmed)
+            || np
+                .artist
+                .as_ref()
+                .is_some_and(|a| a.to_lowercase().contains(&trimmed))
+    };
+
+    let mut items: Vec<Item> = now_playing
+        .filter(|np| track_matches(np))
+        .map(now_playing_item)
+        .into_iter()
+        .collect();
+    items.extend(matching_commands(query).into_iter().map(command_item));
+
+    if items.is_empty() {
+        None
+    } else {
+        Some(Group::new(t("Now Playing"), items))
+    }
+}
+
+/// Actions offered for a media item, as `(command id, title)` pairs.
+pub fn media_actions(item: &Item) -> Vec<(&'static str, String)> {
+    if item.has_type("now_playing") {
+        [
+            MediaCommand::PlayPause,
+            MediaCommand::Next,
+            MediaCommand::Previous,
+        ]
+        .into_iter()
+        .map(|c| (c.id(), c.title()))
+        .collect()
+    } else {
+        MediaCommand::from_item(item)
+            .map(|c| vec![(c.id(), c.title())])
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track() -> NowPlaying {
+        NowPlaying {
+            app: "Music".to_string(),
+            title: "Windowlicker".to_string(),
+            artist: Some("Aphex Twin".to_string()),
+            album: None,
+            playing: true,
+        }
+    }
+
+    #[test]
+    fn test_parse_now_playing() {
+        assert_eq!(parse_now_playing("\n").unwrap(), None);
+
+        let np = parse_now_playing(
+            r#"{"app": "Spotify", "title": "Song", "artist": "Band", "album": null, "playing": false}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(np.app, "Spotify");
+        assert!(!np.playing);
+    }
+
+    #[test]
+    fn test_matching_commands() {
+        assert_eq!(matching_commands("pau"), vec![MediaCommand::PlayPause]);
+        assert_eq!(matching_commands("next"), vec![MediaCommand::Next]);
+        assert_eq!(
+            matching_commands("volume 40"),
+            vec![MediaCommand::SetVolume(40)]
+        );
+        assert_eq!(
+            matching_commands("vol 300%"),
+            Vec::<MediaCommand>::new(),
+            "out of range for u8"
+        );
+        assert_eq!(
+            matching_commands("volume"),
+            vec![MediaCommand::VolumeUp, MediaCommand::VolumeDown]
+        );
+        assert!(matching_commands("pa").is_empty());
+    }
+
+    #[test]
+    fn test_command_item_round_trip() {
+        for command in [MediaCommand::Next, MediaCommand::SetVolume(30)] {
+            assert_eq!(
+                MediaCommand::from_item(&command_item(command)),
+                Some(command)
+            );
+        }
+    }
+
+    #[test]
+    fn test_media_group() {
+        let np = track();
+        let group = media_group("", Some(&np)).unwrap();
+        assert_eq!(group.items[0].title, "Windowlicker");
+        assert_eq!(
+            group.items[0].subtitle.as_deref(),
+            Some("Aphex Twin · Music")
+        );
+
+        assert!(media_group("", None).is_none());
+        assert!(media_group("calculator", Some(&np)).is_none());
+        assert_eq!(media_group("aphex", Some(&np)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_media_actions() {
+        let actions = media_actions(&now_playing_item(&track()));
+        let ids: Vec<_> = actions.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec!["play_pause", "next", "previous"]);
+
+        let actions = media_actions(&command_item(MediaCommand::Mute));
+        assert_eq!(actions, vec![("mute", "Toggle Mute".to_string())]);
+    }
+
+    #[test]
+    fn test_cache_reuses_until_invalidated() {
+        let cache = NowPlayingCache::new(Duration::from_secs(60));
+        assert_eq!(cache.get_with(|| Some(track())), Some(track()));
+        assert_eq!(cache.get_with(|| None), Some(track()));
+
+        cache.invalidate();
+        assert_eq!(cache.get_with(|| None), None);
+    }
+}
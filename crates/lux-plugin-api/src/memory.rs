@@ -0,0 +1,179 @@
+//! Lua memory accounting, limits, and garbage collection settings.
+//!
+//! This module provides:
+//! - `MemoryMonitor` - Memory used by each view's searches and actions (lux.runtime)
+//! - `ViewMemory` - The totals for one view
+//! - `GcSettings` - How often the Lua thread collects garbage while idle
+//!
+//! All plugins share one Lua state, so memory is attributed by measuring the
+//! state's usage around each view callback. A view whose `retained` keeps
+//! growing across calls is holding on to what it allocates.
+//!
+//! ```lua
+//! lux.runtime.set_memory_limit(256)              -- MB; allocations past it fail
+//! lux.runtime.gc({ interval_ms = 500, step_kb = 128 })
+//! for view, usage in pairs(lux.runtime.stats().views) do
+//!   print(view, usage.calls, usage.retained, usage.peak)
+//! end
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use mlua::Lua;
+use parking_lot::{Mutex, RwLock};
+
+/// Name the root view's usage is recorded under when it has no ID.
+pub const ROOT_VIEW_NAME: &str = "root";
+
+/// Memory used by one view's callbacks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ViewMemory {
+    /// Number of measured calls.
+    pub calls: u64,
+    /// Net bytes the calls left allocated (negative if they freed more).
+    pub retained: i64,
+    /// Most bytes a single call left allocated.
+    pub peak: u64,
+}
+
+/// When the Lua thread collects garbage between requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GcSettings {
+    /// How long the Lua thread waits idle before a step (None: never).
+    pub interval: Option<Duration>,
+    /// Kilobytes of work per incremental step.
+    pub step_kbytes: u32,
+}
+
+impl Default for GcSettings {
+    fn default() -> Self {
+        Self {
+            interval: Some(Duration::from_secs(1)),
+            step_kbytes: 64,
+        }
+    }
+}
+
+/// Memory usage per view, the memory limit, and the garbage collection settings.
+pub struct MemoryMonitor {
+    usage: Mutex<HashMap<String, ViewMemory>>,
+    limit: RwLock<Option<usize>>,
+    gc: RwLock<GcSettings>,
+}
+
+impl MemoryMonitor {
+    /// Create a monitor with no usage recorded and the default GC settings.
+    pub fn new() -> Self {
+        Self {
+            usage: Mutex::new(HashMap::new()),
+            limit: RwLock::new(None),
+            gc: RwLock::new(GcSettings::default()),
+        }
+    }
+
+    /// Run a callback of `view`, recording the memory it leaves allocated.
+    pub fn measure<T>(&self, lua: &Lua, view: Option<&str>, f: impl FnOnce() -> T) -> T {
+        let before = lua.used_memory();
+        let result = f();
+        let delta = lua.used_memory() as i64 - before as i64;
+
+        let mut usage = self.usage.lock();
+        let entry = usage
+            .entry(view.unwrap_or(ROOT_VIEW_NAME).to_string())
+            .or_default();
+        entry.calls += 1;
+        entry.retained += delta;
+        entry.peak = entry.peak.max(delta.max(0) as u64);
+        result
+    }
+
+    /// Usage per view, the views retaining the most memory first.
+    pub fn usage(&self) -> Vec<(String, ViewMemory)> {
+        let mut usage: Vec<_> = self
+            .usage
+            .lock()
+            .iter()
+            .map(|(view, memory)| (view.clone(), *memory))
+            .collect();
+        usage.sort_by(|a, b| b.1.retained.cmp(&a.1.retained).then(a.0.cmp(&b.0)));
+        usage
+    }
+
+    /// Forget the recorded usage.
+    pub fn reset(&self) {
+        self.usage.lock().clear();
+    }
+
+    /// Limit the Lua state to `bytes` of memory (None: no limit).
+    ///
+    /// An allocation past the limit fails with a memory error in the
+    /// callback that made it, so one runaway view cannot take the rest down.
+    pub fn set_limit(&self, lua: &Lua, bytes: Option<usize>) -> mlua::Result<()> {
+        lua.set_memory_limit(bytes.unwrap_or(0))?;
+        *self.limit.write() = bytes;
+        Ok(())
+    }
+
+    /// The memory limit in bytes, if one is set.
+    pub fn limit(&self) -> Option<usize> {
+        *self.limit.read()
+    }
+
+    /// The garbage collection settings (read when the Lua thread starts).
+    pub fn gc_settings(&self) -> GcSettings {
+        *self.gc.read()
+    }
+
+    /// Change the garbage collection settings.
+    pub fn set_gc_settings(&self, settings: GcSettings) {
+        *self.gc.write() = settings;
+    }
+}
+
+impl Default for MemoryMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_attributes_memory_to_views() {
+        let lua = Lua::new();
+        let monitor = MemoryMonitor::new();
+
+        monitor.measure(&lua, Some("leaky"), || {
+            lua.load("cache = {} for i = 1, 10000 do cache[i] = tostring(i) end")
+                .exec()
+                .unwrap()
+        });
+        let answer = monitor.measure(&lua, None, || 42);
+        assert_eq!(answer, 42);
+
+        let usage = monitor.usage();
+        assert_eq!(usage[0].0, "leaky");
+        assert_eq!(usage[0].1.calls, 1);
+        assert!(usage[0].1.retained > 100_000);
+        assert_eq!(usage[0].1.peak, usage[0].1.retained as u64);
+        assert_eq!(usage[1].0, ROOT_VIEW_NAME);
+
+        monitor.reset();
+        assert!(monitor.usage().is_empty());
+
+        // Allocations past the limit fail instead of growing the state
+        monitor
+            .set_limit(&lua, Some(lua.used_memory() + 64 * 1024))
+            .unwrap();
+        let err = lua
+            .load("local t = {} for i = 1, 1000000 do t[i] = tostring(i) end")
+            .exec()
+            .unwrap_err();
+        assert!(matches!(err, mlua::Error::MemoryError(_)));
+        monitor.set_limit(&lua, None).unwrap();
+        assert_eq!(monitor.limit(), None);
+    }
+}
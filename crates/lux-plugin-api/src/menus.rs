@@ -0,0 +1,404 @@
+//! Menu bar search for the frontmost app.
+//!
+//! This module provides:
+//! - `MenuItem` - A command in an app's menu bar: its menu path and shortcut
+//! - `list_menu_items()` / `press_menu_item()` - Read an app's menu bar and choose a command, through the Accessibility API
+//! - `MenuCache` - Menu items per app, so typing doesn't walk the menu bar per key
+//! - `menu_groups()` - The rows of the Menu Bar view, best fuzzy matches first
+//!
+//! The view lists the menu commands of the app the launcher opened over, so
+//! any app gets a command palette:
+//!
+//! ```lua
+//! lux.views.add(lux.builtin.menus())   -- id "builtin.menus"
+//! lux.rules.on_app({ bundle_id = "com.apple.Preview", view = "builtin.menus" })
+//! ```
+//!
+//! Menus are read through System Events, which needs the Accessibility
+//! permission. Disabled commands and the Apple menu are left out; submenus
+//! are followed `MAX_DEPTH` levels deep. Walking a big app's menu bar takes
+//! a moment, so each app's commands are cached for `CACHE_TTL`, and dropped
+//! after a command is chosen (it may have changed its menu, e.g. "Show
+//! Sidebar" becoming "Hide Sidebar").
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use lux_core::i18n::t;
+use lux_core::{Group, Groups, Item};
+
+use crate::error::{PluginError, PluginResult};
+use crate::matching::fuzzy_score;
+
+/// ID of the Menu Bar view (`lux.builtin.menus()`).
+pub const MENUS_VIEW_ID: &str = "builtin.menus";
+
+/// Type tag for menu command items.
+pub const MENU_ITEM_TYPE: &str = "menu_item";
+
+/// How long an app's menu commands are cached.
+pub const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Menu levels read below the menu bar (a menu, then its submenus).
+pub const MAX_DEPTH: usize = 3;
+
+/// Separator between menu names in a path.
+const PATH_SEPARATOR: &str = " › ";
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A command in an app's menu bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    /// Menu names from the menu bar down, ending with the command's own.
+    pub path: Vec<String>,
+    /// Keyboard shortcut, e.g. "⇧⌘N".
+    pub shortcut: Option<String>,
+}
+
+impl MenuItem {
+    /// The command's name.
+    pub fn title(&self) -> &str {
+        self.path.last().map_or("", String::as_str)
+    }
+
+    /// The menus it is in, e.g. "View › Sort By".
+    pub fn menus(&self) -> String {
+        self.path[..self.path.len().saturating_sub(1)].join(PATH_SEPARATOR)
+    }
+}
+
+/// The app and menu path of a Menu Bar view item, kept in its data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MenuTarget {
+    /// Bundle ID of the app.
+    pub bundle_id: String,
+    /// Menu path of the command (`MenuItem::path`).
+    pub path: Vec<String>,
+}
+
+impl MenuTarget {
+    /// Read the target back from a Menu Bar view item.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        serde_json::from_value(item.data.clone()?).ok()
+    }
+}
+
+// =============================================================================
+// Accessibility
+// =============================================================================
+
+/// JXA script listing the enabled menu commands of app `argv[0]` (a bundle
+/// ID), `argv[1]` levels deep.
+///
+/// Names, states, and shortcuts are read a menu at a time; one Apple event
+/// per attribute of every command is too slow for big menus.
+#[cfg(not(target_os = "linux"))]
+const LIST_MENUS_SCRIPT: &str = r#"
+function run(argv) {
+  const se = Application('System Events');
+  const procs = se.processes.whose({ bundleIdentifier: argv[0] });
+  if (procs.length === 0) return '[]';
+  const maxDepth = Number(argv[1]);
+  const items = [];
+  const walk = (menu, path, depth) => {
+    let names, enabled, keys, modifiers;
+    try {
+      const entries = menu.menuItems;
+      names = entries.name();
+      enabled = entries.enabled();
+      try {
+        keys = entries.attributes.byName('AXMenuItemCmdChar').value();
+        modifiers = entries.attributes.byName('AXMenuItemCmdModifiers').value();
+      } catch (e) { keys = []; modifiers = []; }
+    } catch (e) { return; }
+    names.forEach((name, i) => {
+      if (!name) return;
+      const entry = menu.menuItems[i];
+      let submenus = 0;
+      if (depth < maxDepth) { try { submenus = entry.menus.length; } catch (e) {} }
+      if (submenus > 0) walk(entry.menus[0], path.concat([name]), depth + 1);
+      else if (enabled[i]) items.push({
+        path: path.concat([name]),
+        key: keys[i] || '',
+        modifiers: modifiers[i] || 0,
+      });
+    });
+  };
+  const bar = procs[0].menuBars[0];
+  bar.menuBarItems.name().forEach((title, i) => {
+    // The first menu is the Apple menu
+    if (i === 0 || !title) return;
+    try { walk(bar.menuBarItems[i].menus[0], [title], 1); } catch (e) {}
+  });
+  return JSON.stringify(items);
+}
+"#;
+
+/// JXA script bringing app `argv[0]` to the front and choosing the menu
+/// command at path `argv[1..]`.
+#[cfg(not(target_os = "linux"))]
+const PRESS_MENU_SCRIPT: &str = r#"
+function run(argv) {
+  const se = Application('System Events');
+  const proc = se.processes.whose({ bundleIdentifier: argv[0] })[0];
+  proc.frontmost = true;
+  let item = proc.menuBars[0].menuBarItems.byName(argv[1]);
+  for (const name of argv.slice(2)) item = item.menus[0].menuItems.byName(name);
+  item.click();
+  return 'ok';
+}
+"#;
+
+/// A command as printed by `LIST_MENUS_SCRIPT`.
+#[derive(Deserialize)]
+struct ScriptMenuItem {
+    path: Vec<String>,
+    #[serde(default)]
+    key: String,
+    #[serde(default)]
+    modifiers: u32,
+}
+
+/// The enabled menu commands of the running app `bundle_id`.
+#[cfg(not(target_os = "linux"))]
+pub fn list_menu_items(bundle_id: &str) -> PluginResult<Vec<MenuItem>> {
+    use crate::permissions::{self, Permission};
+
+    permissions::require(Permission::Accessibility)?;
+    let output =
+        permissions::run_jxa_with_args(LIST_MENUS_SCRIPT, &[bundle_id, &MAX_DEPTH.to_string()])?;
+    parse_menu_items(&output)
+}
+
+/// Linux apps have no menu bar to read.
+#[cfg(target_os = "linux")]
+pub fn list_menu_items(_bundle_id: &str) -> PluginResult<Vec<MenuItem>> {
+    Err(PluginError::Command(
+        "Menu bar search is only available on macOS".to_string(),
+    ))
+}
+
+/// Bring the app `target.bundle_id` to the front and choose the command.
+#[cfg(not(target_os = "linux"))]
+pub fn press_menu_item(target: &MenuTarget) -> PluginResult<()> {
+    let mut args = vec![target.bundle_id.as_str()];
+    args.extend(target.path.iter().map(String::as_str));
+    crate::permissions::run_jxa_with_args(PRESS_MENU_SCRIPT, &args)?;
+    Ok(())
+}
+
+/// Linux apps have no menu bar to read.
+#[cfg(target_os = "linux")]
+pub fn press_menu_item(_target: &MenuTarget) -> PluginResult<()> {
+    Err(PluginError::Command(
+        "Menu bar search is only available on macOS".to_string(),
+    ))
+}
+
+/// Parse the output of the menu listing script.
+pub fn parse_menu_items(output: &str) -> PluginResult<Vec<MenuItem>> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(Vec::new());
+    }
+    let items: Vec<ScriptMenuItem> = serde_json::from_str(output)
+        .map_err(|e| PluginError::Command(format!("Invalid menu list: {}", e)))?;
+
+    Ok(items
+        .into_iter()
+        .map(|item| MenuItem {
+            shortcut: format_shortcut(&item.key, item.modifiers),
+            path: item.path,
+        })
+        .collect())
+}
+
+/// Format a menu shortcut from its key and `AXMenuItemCmdModifiers` mask.
+///
+/// The mask has Shift (1), Option (2), Control (4), and "no Command" (8).
+fn format_shortcut(key: &str, modifiers: u32) -> Option<String> {
+    if key.is_empty() {
+        return None;
+    }
+    let mut shortcut = String::new();
+    for (bit, symbol) in [(4, "⌃"), (2, "⌥"), (1, "⇧")] {
+        if modifiers & bit != 0 {
+            shortcut.push_str(symbol);
+        }
+    }
+    if modifiers & 8 == 0 {
+        shortcut.push('⌘');
+    }
+    shortcut.push_str(&key.to_uppercase());
+    Some(shortcut)
+}
+
+// =============================================================================
+// Cache
+// =============================================================================
+
+/// Menu commands per app, re-read once older than the TTL.
+pub struct MenuCache {
+    ttl: Duration,
+    apps: Mutex<HashMap<String, (Instant, Vec<MenuItem>)>>,
+}
+
+impl MenuCache {
+    /// Create an empty cache.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            apps: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the cached commands of `bundle_id`, calling `fetch` if they are stale.
+    pub fn get_with(
+        &self,
+        bundle_id: &str,
+        fetch: impl FnOnce() -> PluginResult<Vec<MenuItem>>,
+    ) -> PluginResult<Vec<MenuItem>> {
+        let mut apps = self.apps.lock();
+        if let Some((at, items)) = apps.get(bundle_id) {
+            if at.elapsed() < self.ttl {
+                return Ok(items.clone());
+            }
+        }
+        let items = fetch()?;
+        apps.insert(bundle_id.to_string(), (Instant::now(), items.clone()));
+        Ok(items)
+    }
+
+    /// Drop the cached commands of `bundle_id`.
+    pub fn invalidate(&self, bundle_id: &str) {
+        self.apps.lock().remove(bundle_id);
+    }
+}
+
+impl Default for MenuCache {
+    fn default() -> Self {
+        Self::new(CACHE_TTL)
+    }
+}
+
+// =============================================================================
+// Menu Bar View
+// =============================================================================
+
+/// Build the Menu Bar view item for a command of `bundle_id`.
+pub fn menu_item(bundle_id: &str, item: &MenuItem) -> Item {
+    let target = MenuTarget {
+        bundle_id: bundle_id.to_string(),
+        path: item.path.clone(),
+    };
+    let mut row = Item::new(
+        format!("menu:{}:{}", bundle_id, item.path.join(PATH_SEPARATOR)),
+        item.title(),
+    )
+    .with_type(MENU_ITEM_TYPE);
+    row.subtitle = Some(item.menus()).filter(|menus| !menus.is_empty());
+    row.data = serde_json::to_value(&target).ok();
+    match &item.shortcut {
+        Some(shortcut) => row.with_accessory(shortcut.clone()),
+        None => row,
+    }
+}
+
+/// The commands of `bundle_id` matching `query`, best matches first.
+///
+/// Matches on the command's name rank above matches on its menu path; an
+/// empty query lists every command in menu order. The group is titled with
+/// the app's name when known.
+pub fn menu_groups(
+    bundle_id: &str,
+    app_name: Option<&str>,
+    items: &[MenuItem],
+    query: &str,
+) -> Groups {
+    let query = query.trim().to_lowercase();
+    let mut scored: Vec<(u32, &MenuItem)> = items
+        .iter()
+        .filter_map(|item| {
+            if query.is_empty() {
+                return Some((0, item));
+            }
+            fuzzy_score(item.title(), &query)
+                .map(|score| score + 100)
+                .or_else(|| fuzzy_score(&item.path.join(" "), &query))
+                .map(|score| (score, item))
+        })
+        .collect();
+    // Stable, so equal scores keep menu order
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let rows: Vec<Item> = scored
+        .into_iter()
+        .map(|(_, item)| menu_item(bundle_id, item))
+        .collect();
+    if rows.is_empty() {
+        return Vec::new();
+    }
+    let title = app_name.map_or_else(|| t("Menu Bar"), str::to_string);
+    vec![Group::new(title, rows)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_menu_items() {
+        let output = r#"[
+            {"path": ["File", "New Window"], "key": "n", "modifiers": 1},
+            {"path": ["View", "Sort By", "Name"], "key": "1", "modifiers": 6},
+            {"path": ["Window", "Zoom"]}
+        ]"#;
+        let items = parse_menu_items(output).unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].shortcut.as_deref(), Some("⇧⌘N"));
+        assert_eq!(items[1].shortcut.as_deref(), Some("⌃⌥⌘1"));
+        assert_eq!(items[1].title(), "Name");
+        assert_eq!(items[1].menus(), "View › Sort By");
+        assert_eq!(items[2].shortcut, None);
+        assert!(parse_menu_items("\n").unwrap().is_empty());
+        assert_eq!(format_shortcut("k", 8).as_deref(), Some("K"));
+    }
+
+    #[test]
+    fn test_menu_groups_rank_fuzzy_matches() {
+        let items = parse_menu_items(
+            r#"[
+                {"path": ["File", "Export as PDF…"]},
+                {"path": ["View", "Show Sidebar"], "key": "s", "modifiers": 2},
+                {"path": ["Edit", "Paste and Match Style"]}
+            ]"#,
+        )
+        .unwrap();
+
+        let groups = menu_groups("com.apple.Preview", Some("Preview"), &items, "");
+        assert_eq!(groups[0].title.as_deref(), Some("Preview"));
+        assert_eq!(groups[0].items.len(), 3);
+
+        // Letters in order, not necessarily adjacent
+        let groups = menu_groups("com.apple.Preview", None, &items, "sb");
+        let titles: Vec<&str> = groups[0].items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["Show Sidebar"]);
+
+        let groups = menu_groups("com.apple.Preview", None, &items, "file pdf");
+        let item = &groups[0].items[0];
+        assert_eq!(item.title, "Export as PDF…");
+        assert_eq!(item.subtitle.as_deref(), Some("File"));
+        assert_eq!(
+            MenuTarget::from_item(item).unwrap().path,
+            ["File", "Export as PDF…"]
+        );
+
+        assert!(menu_groups("com.apple.Preview", None, &items, "zzz").is_empty());
+    }
+}
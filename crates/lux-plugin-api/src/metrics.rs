@@ -0,0 +1,443 @@
+//! Local usage metrics and the "Stats" view (opt-in).
+//!
+//! This module provides:
+//! - `Metrics` - Query latency, plugin run times and errors (by error code), and action usage, saved to the config directory
+//! - `MetricsSummary` / `PluginStats` - What has been recorded so far
+//! - `stats_groups()` - The "Stats" view
+//!
+//! Nothing is recorded until metrics are turned on with `lux.metrics.enable()`,
+//! and nothing ever leaves the machine: the numbers are only written to
+//! `metrics.json` in the config directory.
+//!
+//! ```lua
+//! lux.metrics.enable()
+//! lux.views.add(lux.builtin.stats())   -- id "builtin.stats"
+//! ```
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Groups, HandlerError, Item};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+/// ID of the "Stats" view.
+pub const STATS_VIEW_ID: &str = "builtin.stats";
+
+/// Query latencies kept for the percentiles.
+const MAX_LATENCY_SAMPLES: usize = 1000;
+
+/// How often recorded metrics are written out.
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Run times and errors of one view's searches and actions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginStats {
+    /// Number of calls.
+    pub calls: u64,
+    /// Calls that failed.
+    pub errors: u64,
+    /// Total run time in microseconds.
+    pub total_us: u64,
+    /// Longest run time in microseconds.
+    pub max_us: u64,
+}
+
+impl PluginStats {
+    /// Average run time.
+    pub fn average(&self) -> Duration {
+        Duration::from_micros(self.total_us.checked_div(self.calls).unwrap_or(0))
+    }
+
+    /// Share of calls that failed, from 0 to 1.
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+}
+
+/// What is recorded, as saved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Recorded {
+    /// Number of queries.
+    #[serde(default)]
+    queries: u64,
+    /// Latencies of the latest queries in microseconds, oldest first.
+    #[serde(default)]
+    latencies_us: VecDeque<u64>,
+    /// Stats per view ID.
+    #[serde(default)]
+    plugins: BTreeMap<String, PluginStats>,
+    /// Uses per action, keyed `view/action`.
+    #[serde(default)]
+    actions: BTreeMap<String, u64>,
+    /// Failed actions per error code, keyed `view/code`.
+    #[serde(default)]
+    error_codes: BTreeMap<String, u64>,
+}
+
+/// Everything recorded so far.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSummary {
+    /// Number of queries.
+    pub queries: u64,
+    /// Median query latency.
+    pub p50: Option<Duration>,
+    /// 90th percentile query latency.
+    pub p90: Option<Duration>,
+    /// 99th percentile query latency.
+    pub p99: Option<Duration>,
+    /// Stats per view, slowest on average first.
+    pub plugins: Vec<(String, PluginStats)>,
+    /// Uses per action, most used first.
+    pub actions: Vec<(String, u64)>,
+    /// Failed actions per `view/code`, most frequent first.
+    pub error_codes: Vec<(String, u64)>,
+}
+
+/// Local usage metrics, off until turned on.
+///
+/// Saved as a JSON object at most every `SAVE_INTERVAL`, and on `flush()`.
+pub struct Metrics {
+    path: Option<PathBuf>,
+    enabled: AtomicBool,
+    recorded: Mutex<Recorded>,
+    last_saved: Mutex<Instant>,
+}
+
+impl Metrics {
+    /// Load the metrics saved at `path` (None keeps them in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let recorded = path.as_deref().map(load_recorded).unwrap_or_default();
+        Self {
+            path,
+            enabled: AtomicBool::new(false),
+            recorded: Mutex::new(recorded),
+            last_saved: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Where metrics are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        lux_core::config_dir().map(|dir| dir.join("metrics.json"))
+    }
+
+    /// Whether metrics are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Turn recording on or off. Turning it off saves what was recorded.
+    pub fn set_enabled(&self, enabled: bool) {
+        let was_enabled = self.enabled.swap(enabled, Ordering::Relaxed);
+        if was_enabled && !enabled {
+            self.save();
+        }
+    }
+
+    /// Record how long a query took, from keystroke to results.
+    pub fn record_query(&self, elapsed: Duration) {
+        self.record(|recorded| {
+            recorded.queries += 1;
+            if recorded.latencies_us.len() == MAX_LATENCY_SAMPLES {
+                recorded.latencies_us.pop_front();
+            }
+            recorded.latencies_us.push_back(elapsed.as_micros() as u64);
+        });
+    }
+
+    /// Run a search or action of `view`, recording its run time and whether it failed.
+    pub fn measure<T, E>(&self, view: &str, f: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+        if !self.is_enabled() {
+            return f();
+        }
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed().as_micros() as u64;
+        self.record(|recorded| {
+            let stats = recorded.plugins.entry(view.to_string()).or_default();
+            stats.calls += 1;
+            stats.errors += result.is_err() as u64;
+            stats.total_us += elapsed;
+            stats.max_us = stats.max_us.max(elapsed);
+        });
+        result
+    }
+
+    /// Record an action of `view` that failed with `error` (`ctx:fail()`).
+    ///
+    /// Counts as an error of the view, and under the error's code.
+    pub fn record_failure(&self, view: &str, error: &HandlerError) {
+        self.record(|recorded| {
+            recorded.plugins.entry(view.to_string()).or_default().errors += 1;
+            *recorded
+                .error_codes
+                .entry(format!("{}/{}", view, error.code()))
+                .or_default() += 1;
+        });
+    }
+
+    /// Record a use of `action` in `view`.
+    pub fn record_action(&self, view: &str, action: &str) {
+        self.record(|recorded| {
+            *recorded
+                .actions
+                .entry(format!("{}/{}", view, action))
+                .or_default() += 1;
+        });
+    }
+
+    /// Everything recorded so far.
+    pub fn summary(&self) -> MetricsSummary {
+        let recorded = self.recorded.lock();
+
+        let mut latencies: Vec<u64> = recorded.latencies_us.iter().copied().collect();
+        latencies.sort_unstable();
+        let percentile = |p: usize| {
+            (!latencies.is_empty()).then(|| {
+                let index = (latencies.len() * p).div_ceil(100).saturating_sub(1);
+                Duration::from_micros(latencies[index])
+            })
+        };
+
+        let mut plugins: Vec<_> = recorded
+            .plugins
+            .iter()
+            .map(|(view, stats)| (view.clone(), *stats))
+            .collect();
+        plugins.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.average()));
+
+        let mut actions: Vec<_> = recorded
+            .actions
+            .iter()
+            .map(|(action, count)| (action.clone(), *count))
+            .collect();
+        actions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        let mut error_codes: Vec<_> = recorded
+            .error_codes
+            .iter()
+            .map(|(code, count)| (code.clone(), *count))
+            .collect();
+        error_codes.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        MetricsSummary {
+            queries: recorded.queries,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            plugins,
+            actions,
+            error_codes,
+        }
+    }
+
+    /// Forget everything recorded, on disk too.
+    pub fn reset(&self) {
+        *self.recorded.lock() = Recorded::default();
+        self.save();
+    }
+
+    /// Write out what was recorded since the last save.
+    pub fn flush(&self) {
+        if self.is_enabled() {
+            self.save();
+        }
+    }
+
+    /// Apply `f` if recording is on, saving if the last save is old enough.
+    fn record(&self, f: impl FnOnce(&mut Recorded)) {
+        if !self.is_enabled() {
+            return;
+        }
+        f(&mut self.recorded.lock());
+        if self.last_saved.lock().elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        *self.last_saved.lock() = Instant::now();
+        let Some(path) = &self.path else {
+            return;
+        };
+        let recorded = self.recorded.lock().clone();
+        if let Err(e) = save_recorded(path, &recorded) {
+            tracing::warn!("Failed to save metrics: {}", e);
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+fn load_recorded(path: &Path) -> Recorded {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_recorded(path: &Path, recorded: &Recorded) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(recorded)?)
+}
+
+// =============================================================================
+// Stats View
+// =============================================================================
+
+/// Build the groups of the "Stats" view.
+///
+/// Lists the query latency percentiles, then the views, actions, and error
+/// codes whose name matches `query`.
+pub fn stats_groups(metrics: &Metrics, query: &str) -> Groups {
+    if !metrics.is_enabled() {
+        let item = Item::new("stats:off", t("Stats are off"))
+            .with_subtitle(t("Turn them on with lux.metrics.enable() in init.lua"));
+        return vec![Group::new(t("Stats"), vec![item])];
+    }
+
+    let query = query.trim().to_lowercase();
+    let matches = |text: &str| text.to_lowercase().contains(&query);
+    let summary = metrics.summary();
+
+    let latency: Vec<Item> = [
+        ("p50", t("Median"), summary.p50),
+        ("p90", t("90th Percentile"), summary.p90),
+        ("p99", t("99th Percentile"), summary.p99),
+    ]
+    .into_iter()
+    .filter_map(|(id, title, latency)| {
+        let subtitle = t_args(
+            "{count} queries",
+            &[("count", &summary.queries.to_string())],
+        );
+        Some(
+            Item::new(format!("stats:{}", id), title)
+                .with_subtitle(subtitle)
+                .with_accessory(millis(latency?)),
+        )
+    })
+    .filter(|item| matches(&item.title))
+    .collect();
+
+    let plugins: Vec<Item> = summary
+        .plugins
+        .iter()
+        .filter(|(view, _)| matches(view))
+        .map(|(view, stats)| {
+            Item::new(format!("stats:plugin:{}", view), view.clone())
+                .with_subtitle(t_args(
+                    "{calls} calls, {errors} errors ({rate}%), up to {max}",
+                    &[
+                        ("calls", &stats.calls.to_string()),
+                        ("errors", &stats.errors.to_string()),
+                        ("rate", &format!("{:.1}", stats.error_rate() * 100.0)),
+                        ("max", &millis(Duration::from_micros(stats.max_us))),
+                    ],
+                ))
+                .with_accessory(millis(stats.average()))
+        })
+        .collect();
+
+    let actions: Vec<Item> = summary
+        .actions
+        .iter()
+        .filter(|(action, _)| matches(action))
+        .map(|(action, count)| {
+            Item::new(format!("stats:action:{}", action), action.clone())
+                .with_accessory(t_args("{count} uses", &[("count", &count.to_string())]))
+        })
+        .collect();
+
+    let errors: Vec<Item> = summary
+        .error_codes
+        .iter()
+        .filter(|(code, _)| matches(code))
+        .map(|(code, count)| {
+            Item::new(format!("stats:error:{}", code), code.clone())
+                .with_accessory(t_args("{count} times", &[("count", &count.to_string())]))
+        })
+        .collect();
+
+    [
+        (t("Query Latency"), latency),
+        (t("Plugins"), plugins),
+        (t("Actions"), actions),
+        (t("Errors"), errors),
+    ]
+    .into_iter()
+    .filter(|(_, items)| !items.is_empty())
+    .map(|(title, items)| Group::new(title, items))
+    .collect()
+}
+
+/// A duration in milliseconds, with one decimal under 10 ms.
+fn millis(duration: Duration) -> String {
+    let ms = duration.as_secs_f64() * 1000.0;
+    if ms < 10.0 {
+        format!("{:.1} ms", ms)
+    } else {
+        format!("{:.0} ms", ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_only_when_enabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.json");
+        let metrics = Metrics::new(Some(path.clone()));
+
+        metrics.record_query(Duration::from_millis(5));
+        assert_eq!(metrics.summary().queries, 0);
+        assert_eq!(stats_groups(&metrics, "")[0].items[0].id, "stats:off");
+
+        metrics.set_enabled(true);
+        for ms in 1..=100 {
+            metrics.record_query(Duration::from_millis(ms));
+        }
+        let _ = metrics.measure("files", || Ok::<_, String>(()));
+        let _ = metrics.measure("files", || Err::<(), _>("boom".to_string()));
+        metrics.record_action("files", "open");
+        metrics.record_action("files", "open");
+        metrics.record_failure(
+            "files",
+            &HandlerError::new("Server unreachable").with_code("timeout"),
+        );
+
+        let summary = metrics.summary();
+        assert_eq!(summary.queries, 100);
+        assert_eq!(summary.p50, Some(Duration::from_millis(50)));
+        assert_eq!(summary.p99, Some(Duration::from_millis(99)));
+        assert_eq!(summary.plugins[0].0, "files");
+        assert_eq!(summary.plugins[0].1.calls, 2);
+        assert_eq!(summary.plugins[0].1.errors, 2);
+        assert_eq!(summary.actions, vec![("files/open".to_string(), 2)]);
+        assert_eq!(summary.error_codes, vec![("files/timeout".to_string(), 1)]);
+
+        // Turning it off writes what was recorded
+        metrics.set_enabled(false);
+        let reloaded = Metrics::new(Some(path));
+        assert_eq!(reloaded.summary(), summary);
+
+        reloaded.set_enabled(true);
+        let groups = stats_groups(&reloaded, "open");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].items[0].accessories, ["2 uses"]);
+    }
+}
@@ -0,0 +1,86 @@
+//! Mouse behavior set from Lua.
+//!
+//! This module provides:
+//! - `MouseSettings` - How result rows respond to the pointer (`lux.ui.mouse`)
+//! - `MouseStore` - Storage for the settings, merged like sound settings
+//!
+//! Right-clicking a row always opens its actions at the pointer. Moving the
+//! cursor by hovering is off unless `hover = true` is set, so resting the
+//! pointer on the list doesn't fight the keyboard.
+
+use parking_lot::RwLock;
+
+/// Mouse settings from Lua.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MouseSettings {
+    /// Move the cursor to the row under the pointer.
+    pub hover: Option<bool>,
+}
+
+impl MouseSettings {
+    /// Overlay `other` onto these settings (fields set in `other` win).
+    pub fn merge(&mut self, other: MouseSettings) {
+        self.hover = other.hover.or(self.hover);
+    }
+
+    /// Whether hovering a row moves the cursor to it.
+    pub fn hover_moves_cursor(&self) -> bool {
+        self.hover == Some(true)
+    }
+}
+
+/// Storage for mouse settings.
+pub struct MouseStore {
+    settings: RwLock<MouseSettings>,
+}
+
+impl MouseStore {
+    /// Create a store with the defaults (no hover).
+    pub fn new() -> Self {
+        Self {
+            settings: RwLock::new(MouseSettings::default()),
+        }
+    }
+
+    /// Merge settings into the store.
+    pub fn set(&self, settings: MouseSettings) {
+        self.settings.write().merge(settings);
+    }
+
+    /// Get the current settings.
+    pub fn get(&self) -> MouseSettings {
+        self.settings.read().clone()
+    }
+
+    /// Clear all settings.
+    pub fn reset(&self) {
+        *self.settings.write() = MouseSettings::default();
+    }
+}
+
+impl Default for MouseStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_merges_settings() {
+        let store = MouseStore::new();
+        assert!(!store.get().hover_moves_cursor());
+
+        store.set(MouseSettings { hover: Some(true) });
+        store.set(MouseSettings::default());
+        assert!(store.get().hover_moves_cursor());
+
+        store.set(MouseSettings { hover: Some(false) });
+        assert!(!store.get().hover_moves_cursor());
+
+        store.reset();
+        assert_eq!(store.get(), MouseSettings::default());
+    }
+}
@@ -0,0 +1,635 @@
+//! Wi-Fi, Bluetooth, and network information.
+//!
+//! This module provides:
+//! - `NetworkTopic` - Which info a root-view query asks for (`wifi`, `ip`, ...)
+//! - `NetworkSnapshot` - Wi-Fi, addresses, VPN services, and Bluetooth state
+//! - `network_group()` - Root-view items for a query
+//! - `network_actions()` / `run_network_action()` - Toggles and connections
+//!
+//! State is read from `networksetup`, `ifconfig`, `scutil`, and
+//! `system_profiler`. Bluetooth power and device connections go through
+//! `blueutil`, which must be installed separately.
+
+use std::process::Command;
+
+use lux_core::i18n::t;
+use lux_core::{Group, Item};
+
+use crate::error::{PluginError, PluginResult};
+
+/// Type tag for network items (used to route them to the built-in actions).
+pub const NETWORK_ITEM_TYPE: &str = "network";
+
+/// Lua registry key prefix of the built-in network action handlers.
+pub const NETWORK_ACTION_KEY_PREFIX: &str = "network:";
+
+/// IDs of all network actions (one handler is registered per ID).
+pub const NETWORK_ACTION_IDS: &[&str] = &[
+    "wifi_on",
+    "wifi_off",
+    "bluetooth_on",
+    "bluetooth_off",
+    "vpn_connect",
+    "vpn_disconnect",
+    "device_connect",
+    "device_disconnect",
+    "copy",
+];
+
+/// Handler key for a network action ID.
+pub fn network_action_key(id: &str) -> String {
+    format!("{}{}", NETWORK_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A kind of network information requested from the root view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkTopic {
+    Wifi,
+    Ip,
+    Vpn,
+    Bluetooth,
+}
+
+impl NetworkTopic {
+    const ALL: [NetworkTopic; 4] = [
+        NetworkTopic::Wifi,
+        NetworkTopic::Ip,
+        NetworkTopic::Vpn,
+        NetworkTopic::Bluetooth,
+    ];
+
+    /// Topics requested by a query.
+    ///
+    /// The first word must be (a prefix of) a topic keyword: `wi` / `wifi`,
+    /// `ip`, `vpn`, `blu` / `bluetooth`, or `network` for everything.
+    pub fn from_query(query: &str) -> Vec<NetworkTopic> {
+        let word = match query.split_whitespace().next() {
+            Some(word) if word.len() >= 2 => word.to_lowercase(),
+            _ => return Vec::new(),
+        };
+
+        if word.len() >= 3 && "network".starts_with(&word) {
+            return Self::ALL.to_vec();
+        }
+
+        let keywords: &[(NetworkTopic, &str)] = &[
+            (NetworkTopic::Wifi, "wifi"),
+            (NetworkTopic::Wifi, "wi-fi"),
+            (NetworkTopic::Ip, "ip"),
+            (NetworkTopic::Vpn, "vpn"),
+            (NetworkTopic::Bluetooth, "bluetooth"),
+        ];
+        let mut topics = Vec::new();
+        for (topic, keyword) in keywords {
+            if keyword.starts_with(&word) && !topics.contains(topic) {
+                topics.push(*topic);
+            }
+        }
+        topics
+    }
+}
+
+/// Wi-Fi radio state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WifiStatus {
+    /// Interface name (e.g. "en0").
+    pub interface: String,
+    pub enabled: bool,
+    /// Current network name, if associated (and not redacted by the OS).
+    pub ssid: Option<String>,
+}
+
+/// An IP address assigned to an interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpAddress {
+    pub interface: String,
+    pub address: String,
+}
+
+/// A VPN service configured in Network settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VpnService {
+    pub name: String,
+    pub connected: bool,
+}
+
+/// A paired Bluetooth device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BluetoothDevice {
+    pub name: String,
+    /// Device address (e.g. "AA-BB-CC-DD-EE-FF").
+    pub address: String,
+    pub connected: bool,
+}
+
+/// Bluetooth controller state and paired devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BluetoothStatus {
+    pub enabled: bool,
+    pub devices: Vec<BluetoothDevice>,
+}
+
+/// Network state for the requested topics (unrequested parts stay empty).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkSnapshot {
+    pub wifi: Option<WifiStatus>,
+    pub addresses: Vec<IpAddress>,
+    pub vpns: Vec<VpnService>,
+    pub bluetooth: Option<BluetoothStatus>,
+}
+
+// =============================================================================
+// Queries
+// =============================================================================
+
+/// Read network state for the given topics.
+///
+/// Each part is best-effort: a failing tool leaves its part empty.
+pub fn snapshot(topics: &[NetworkTopic]) -> NetworkSnapshot {
+    let mut snapshot = NetworkSnapshot::default();
+    for topic in topics {
+        match topic {
+            NetworkTopic::Wifi => snapshot.wifi = wifi_status().ok().flatten(),
+            NetworkTopic::Ip => {
+                snapshot.addresses = run("ifconfig", &[])
+                    .map(|out| parse_ifconfig(&out))
+                    .unwrap_or_default()
+            }
+            NetworkTopic::Vpn => {
+                snapshot.vpns = run("scutil", &["--nc", "list"])
+                    .map(|out| parse_vpn_list(&out))
+                    .unwrap_or_default()
+            }
+            NetworkTopic::Bluetooth => {
+                snapshot.bluetooth = run("system_profiler", &["SPBluetoothDataType", "-json"])
+                    .ok()
+                    .and_then(|out| parse_bluetooth(&out))
+            }
+        }
+    }
+    snapshot
+}
+
+/// Read the Wi-Fi radio state (None if the Mac has no Wi-Fi interface).
+pub fn wifi_status() -> PluginResult<Option<WifiStatus>> {
+    let ports = run("networksetup", &["-listallhardwareports"])?;
+    let Some(interface) = parse_wifi_interface(&ports) else {
+        return Ok(None);
+    };
+
+    let power = run("networksetup", &["-getairportpower", &interface])?;
+    let enabled = power.trim_end().ends_with("On");
+    let ssid = if enabled {
+        run("networksetup", &["-getairportnetwork", &interface])
+            .ok()
+            .and_then(|out| parse_ssid(&out))
+    } else {
+        None
+    };
+
+    Ok(Some(WifiStatus {
+        interface,
+        enabled,
+        ssid,
+    }))
+}
+
+/// Find the Wi-Fi device in `networksetup -listallhardwareports` output.
+pub fn parse_wifi_interface(output: &str) -> Option<String> {
+    let mut lines = output.lines();
+    while let Some(line) = lines.next() {
+        if matches!(
+            line.trim(),
+            "Hardware Port: Wi-Fi" | "Hardware Port: AirPort"
+        ) {
+            return lines
+                .next()?
+                .trim()
+                .strip_prefix("Device: ")
+                .map(str::to_string);
+        }
+    }
+    None
+}
+
+/// Extract the network name from `networksetup -getairportnetwork` output.
+pub fn parse_ssid(output: &str) -> Option<String> {
+    output
+        .trim()
+        .strip_prefix("Current Wi-Fi Network: ")
+        .map(str::to_string)
+}
+
+/// Extract non-loopback, non-link-local addresses from `ifconfig` output.
+pub fn parse_ifconfig(output: &str) -> Vec<IpAddress> {
+    let mut addresses = Vec::new();
+    let mut interface = "";
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            interface = line.split(':').next().unwrap_or_default();
+            continue;
+        }
+        let mut words = line.split_whitespace();
+        let address = match (words.next(), words.next()) {
+            (Some("inet" | "inet6"), Some(address)) => address.split('%').next().unwrap_or(address),
+            _ => continue,
+        };
+        if interface.starts_with("lo") || address.starts_with("fe80:") {
+            continue;
+        }
+        addresses.push(IpAddress {
+            interface: interface.to_string(),
+            address: address.to_string(),
+        });
+    }
+    addresses
+}
+
+/// Parse VPN services from `scutil --nc list` output.
+pub fn parse_vpn_list(output: &str) -> Vec<VpnService> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let status = line.split_once('(')?.1.split_once(')')?.0;
+            let name = line.split_once('"')?.1.split_once('"')?.0;
+            Some(VpnService {
+                name: name.to_string(),
+                connected: status == "Connected",
+            })
+        })
+        .collect()
+}
+
+/// Parse `system_profiler SPBluetoothDataType -json` output.
+pub fn parse_bluetooth(output: &str) -> Option<BluetoothStatus> {
+    let json: serde_json::Value = serde_json::from_str(output).ok()?;
+    let controller = json.get("SPBluetoothDataType")?.get(0)?;
+    let enabled = controller
+        .pointer("/controller_properties/controller_state")
+        .and_then(|s| s.as_str())
+        == Some("attrib_on");
+
+    let mut devices = Vec::new();
+    for (key, connected) in [("device_connected", true), ("device_not_connected", false)] {
+        let entries = controller.get(key).and_then(|d| d.as_array());
+        for entry in entries.into_iter().flatten() {
+            let Some(map) = entry.as_object() else {
+                continue;
+            };
+            for (name, props) in map {
+                if let Some(address) = props.get("device_address").and_then(|a| a.as_str()) {
+                    devices.push(BluetoothDevice {
+                        name: name.clone(),
+                        address: address.to_string(),
+                        connected,
+                    });
+                }
+            }
+        }
+    }
+
+    Some(BluetoothStatus { enabled, devices })
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+fn network_item(
+    id: String,
+    title: String,
+    subtitle: &str,
+    icon: &str,
+    data: serde_json::Value,
+) -> Item {
+    Item::new(id, title)
+        .with_subtitle(subtitle)
+        .with_icon(icon)
+        .with_type(NETWORK_ITEM_TYPE)
+        .with_data(data)
+}
+
+/// Build the items for a snapshot.
+pub fn snapshot_items(snapshot: &NetworkSnapshot) -> Vec<Item> {
+    let mut items = Vec::new();
+
+    if let Some(wifi) = &snapshot.wifi {
+        let title = match (&wifi.ssid, wifi.enabled) {
+            (Some(ssid), _) => format!("Wi-Fi: {}", ssid),
+            (None, true) => "Wi-Fi: Not Connected".to_string(),
+            (None, false) => "Wi-Fi: Off".to_string(),
+        };
+        items.push(network_item(
+            "network:wifi".to_string(),
+            title,
+            &wifi.interface,
+            "📶",
+            serde_json::json!({ "kind": "wifi", "enabled": wifi.enabled, "value": wifi.ssid }),
+        ));
+    }
+
+    for ip in &snapshot.addresses {
+        items.push(network_item(
+            format!("network:ip:{}:{}", ip.interface, ip.address),
+            ip.address.clone(),
+            &format!("IP Address · {}", ip.interface),
+            "🌐",
+            serde_json::json!({ "kind": "ip", "value": ip.address }),
+        ));
+    }
+
+    for vpn in &snapshot.vpns {
+        items.push(network_item(
+            format!("network:vpn:{}", vpn.name),
+            vpn.name.clone(),
+            if vpn.connected {
+                "VPN · Connected"
+            } else {
+                "VPN · Disconnected"
+            },
+            "🔐",
+            serde_json::json!({ "kind": "vpn", "name": vpn.name, "connected": vpn.connected }),
+        ));
+    }
+
+    if let Some(bluetooth) = &snapshot.bluetooth {
+        items.push(network_item(
+            "network:bluetooth".to_string(),
+            format!(
+                "Bluetooth: {}",
+                if bluetooth.enabled { "On" } else { "Off" }
+            ),
+            "Bluetooth",
+            "🔵",
+            serde_json::json!({ "kind": "bluetooth", "enabled": bluetooth.enabled }),
+        ));
+        for device in &bluetooth.devices {
+            items.push(network_item(
+                format!("network:device:{}", device.address),
+                device.name.clone(),
+                if device.connected {
+                    "Bluetooth · Connected"
+                } else {
+                    "Bluetooth · Not Connected"
+                },
+                "🎧",
+                serde_json::json!({
+                    "kind": "bluetooth_device",
+                    "address": device.address,
+                    "connected": device.connected,
+                }),
+            ));
+        }
+    }
+
+    items
+}
+
+/// Build the root-view network group for a query.
+///
+/// Only queries naming a topic (see `NetworkTopic::from_query`) read any
+/// state, so ordinary searches don't spawn the platform tools.
+pub fn network_group(query: &str) -> Option<Group> {
+    let topics = NetworkTopic::from_query(query);
+    if topics.is_empty() {
+        return None;
+    }
+
+    let items = snapshot_items(&snapshot(&topics));
+    if items.is_empty() {
+        None
+    } else {
+        Some(Group::new(t("Network"), items))
+    }
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+fn data_str<'a>(item: &'a Item, key: &str) -> Option<&'a str> {
+    item.data.as_ref()?.get(key)?.as_str()
+}
+
+fn data_bool(item: &Item, key: &str) -> bool {
+    item.data
+        .as_ref()
+        .and_then(|d| d.get(key))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+/// Actions offered for a network item, as `(action id, title)` pairs.
+pub fn network_actions(item: &Item) -> Vec<(&'static str, &'static str)> {
+    match data_str(item, "kind") {
+        Some("wifi") if data_bool(item, "enabled") => {
+            let mut actions = vec![("wifi_off", "Turn Wi-Fi Off")];
+            if data_str(item, "value").is_some() {
+                actions.push(("copy", "Copy Network Name"));
+            }
+            actions
+        }
+        Some("wifi") => vec![("wifi_on", "Turn Wi-Fi On")],
+        Some("ip") => vec![("copy", "Copy IP Address")],
+        Some("vpn") if data_bool(item, "connected") => vec![("vpn_disconnect", "Disconnect VPN")],
+        Some("vpn") => vec![("vpn_connect", "Connect VPN")],
+        Some("bluetooth") if data_bool(item, "enabled") => {
+            vec![("bluetooth_off", "Turn Bluetooth Off")]
+        }
+        Some("bluetooth") => vec![("bluetooth_on", "Turn Bluetooth On")],
+        Some("bluetooth_device") if data_bool(item, "connected") => {
+            vec![("device_disconnect", "Disconnect")]
+        }
+        Some("bluetooth_device") => vec![("device_connect", "Connect")],
+        _ => Vec::new(),
+    }
+}
+
+/// Run a network action on the item it was offered for.
+pub fn run_network_action(action_id: &str, item: &Item) -> PluginResult<()> {
+    let field = |key: &str| {
+        data_str(item, key).ok_or_else(|| {
+            PluginError::Command(format!("Item has no '{}' for action '{}'", key, action_id))
+        })
+    };
+
+    match action_id {
+        "wifi_on" | "wifi_off" => {
+            let interface = wifi_status()?
+                .map(|w| w.interface)
+                .ok_or_else(|| PluginError::Command("No Wi-Fi interface".to_string()))?;
+            let state = if action_id == "wifi_on" { "on" } else { "off" };
+            run("networksetup", &["-setairportpower", &interface, state]).map(|_| ())
+        }
+        "bluetooth_on" => blueutil(&["--power", "1"]),
+        "bluetooth_off" => blueutil(&["--power", "0"]),
+        "vpn_connect" => run("scutil", &["--nc", "start", field("name")?]).map(|_| ()),
+        "vpn_disconnect" => run("scutil", &["--nc", "stop", field("name")?]).map(|_| ()),
+        "device_connect" => blueutil(&["--connect", field("address")?]),
+        "device_disconnect" => blueutil(&["--disconnect", field("address")?]),
+        "copy" => crate::desktop::current().clipboard_write(field("value")?),
+        _ => Err(PluginError::Command(format!(
+            "Unknown network action '{}'",
+            action_id
+        ))),
+    }
+}
+
+fn blueutil(args: &[&str]) -> PluginResult<()> {
+    run("blueutil", args).map(|_| ()).map_err(|e| match e {
+        PluginError::Command(msg) if msg.contains("not found") => PluginError::Command(
+            "Bluetooth control requires blueutil (brew install blueutil)".to_string(),
+        ),
+        e => e,
+    })
+}
+
+/// Run a tool and return its stdout.
+fn run(program: &str, args: &[&str]) -> PluginResult<String> {
+    let output = Command::new(program).args(args).output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            PluginError::Command(format!("{} not found", program))
+        } else {
+            PluginError::Command(format!("{} failed: {}", program, e))
+        }
+    })?;
+
+    if !output.status.success() {
+        return Err(PluginError::Command(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topics_from_query() {
+        assert_eq!(NetworkTopic::from_query("wi"), vec![NetworkTopic::Wifi]);
+        assert_eq!(NetworkTopic::from_query("IP"), vec![NetworkTopic::Ip]);
+        assert_eq!(
+            NetworkTopic::from_query("blue"),
+            vec![NetworkTopic::Bluetooth]
+        );
+        assert_eq!(NetworkTopic::from_query("net").len(), 4);
+        assert!(NetworkTopic::from_query("v").is_empty());
+        assert!(NetworkTopic::from_query("safari").is_empty());
+    }
+
+    #[test]
+    fn test_parse_wifi_interface() {
+        let output = "\
+Hardware Port: Ethernet
+Device: en1
+Ethernet Address: aa:bb
+
+Hardware Port: Wi-Fi
+Device: en0
+Ethernet Address: cc:dd
+";
+        assert_eq!(parse_wifi_interface(output), Some("en0".to_string()));
+        assert_eq!(
+            parse_ssid("Current Wi-Fi Network: Home 5G\n"),
+            Some("Home 5G".to_string())
+        );
+        assert_eq!(
+            parse_ssid("You are not associated with an AirPort network."),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_ifconfig() {
+        let output = "\
+lo0: flags=8049<UP,LOOPBACK,RUNNING,MULTICAST> mtu 16384
+\tinet 127.0.0.1 netmask 0xff000000
+en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+\tinet6 fe80::1c2b%en0 prefixlen 64 secured scopeid 0xe
+\tinet 192.168.1.23 netmask 0xffffff00 broadcast 192.168.1.255
+utun3: flags=8051<UP,POINTOPOINT,RUNNING,MULTICAST> mtu 1380
+\tinet 10.8.0.2 --> 10.8.0.1 netmask 0xffffffff
+";
+        let addresses = parse_ifconfig(output);
+        assert_eq!(
+            addresses,
+            vec![
+                IpAddress {
+                    interface: "en0".to_string(),
+                    address: "192.168.1.23".to_string()
+                },
+                IpAddress {
+                    interface: "utun3".to_string(),
+                    address: "10.8.0.2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_vpn_list() {
+        let output = r#"Available network connection services in the current set (*=enabled):
+* (Connected)      6B1F-44 VPN (com.wireguard.macos) "Work"          [VPN/com.wireguard.macos]
+* (Disconnected)   9C2A-11 PPP --> L2TP                "Home VPN"      [PPP/L2TP]
+"#;
+        let vpns = parse_vpn_list(output);
+        assert_eq!(vpns.len(), 2);
+        assert_eq!(vpns[0].name, "Work");
+        assert!(vpns[0].connected);
+        assert_eq!(vpns[1].name, "Home VPN");
+        assert!(!vpns[1].connected);
+    }
+
+    #[test]
+    fn test_parse_bluetooth() {
+        let output = r#"{"SPBluetoothDataType": [{
+            "controller_properties": {"controller_state": "attrib_on"},
+            "device_connected": [{"AirPods Pro": {"device_address": "AA-BB-CC-DD-EE-FF"}}],
+            "device_not_connected": [{"Keyboard": {"device_address": "11-22-33-44-55-66"}}]
+        }]}"#;
+        let status = parse_bluetooth(output).unwrap();
+        assert!(status.enabled);
+        assert_eq!(status.devices.len(), 2);
+        assert_eq!(status.devices[0].name, "AirPods Pro");
+        assert!(status.devices[0].connected);
+        assert!(!status.devices[1].connected);
+    }
+
+    #[test]
+    fn test_network_actions() {
+        let snapshot = NetworkSnapshot {
+            wifi: Some(WifiStatus {
+                interface: "en0".to_string(),
+                enabled: true,
+                ssid: Some("Home".to_string()),
+            }),
+            vpns: vec![VpnService {
+                name: "Work".to_string(),
+                connected: false,
+            }],
+            ..Default::default()
+        };
+        let items = snapshot_items(&snapshot);
+        assert_eq!(items[0].title, "Wi-Fi: Home");
+        assert_eq!(
+            network_actions(&items[0]),
+            vec![
+                ("wifi_off", "Turn Wi-Fi Off"),
+                ("copy", "Copy Network Name")
+            ]
+        );
+        assert_eq!(
+            network_actions(&items[1]),
+            vec![("vpn_connect", "Connect VPN")]
+        );
+    }
+}
@@ -0,0 +1,919 @@
+//! OAuth 2.0 sign-in for plugins.
+//!
+//! This module provides:
+//! - `OAuthConfig` - A provider's endpoints and the client registered with it
+//! - `OAuthToken` - Tokens from the token endpoint, with their expiry
+//! - `OAuthFlows` - Sign-ins and token refreshes in progress behind `lux.oauth`
+//!
+//! Sign-in uses the authorization code flow with PKCE. A listener on a
+//! localhost port is the redirect URI; the browser opens the provider's
+//! consent page, and the code the provider redirects back with is exchanged
+//! for tokens at the token endpoint (with curl, like `lux.http`). Flows run on
+//! a background thread, since the user may take a while in the browser; the
+//! UI runs the `on_done` callbacks of finished flows on its timer tick.
+//!
+//! Tokens are stored in the secrets store as `oauth.<name>`, together with
+//! what refreshing them takes, so `lux.oauth.token()` works across restarts.
+//! `lux.secrets` can't reach them (see `secrets::check_reserved`). An expired
+//! token is refreshed on a background thread too, so `lux.oauth.token()`
+//! never waits on the network.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufReader, ErrorKind, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL;
+use base64::Engine as _;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::http::HttpRequest;
+use crate::macros::is_valid_name;
+use crate::server::HttpRequest as RedirectRequest;
+use crate::text::{url_decode, url_encode};
+use crate::types::LuaFunctionRef;
+
+/// Path of the redirect URI on the localhost listener.
+const REDIRECT_PATH: &str = "/callback";
+
+/// How long the user has to sign in, unless `authorize()` is given a timeout.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// How long the token endpoint has to answer.
+const TOKEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the listener checks for a timeout or a newer sign-in.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the browser has to send its request once connected.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tokens this close to expiring are refreshed (and handed out meanwhile).
+const EXPIRY_MARGIN: u64 = 60;
+
+/// How long after a failed refresh the next one is tried.
+const REFRESH_RETRY: Duration = Duration::from_secs(60);
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Errors from `lux.oauth`.
+#[derive(Debug, Error)]
+pub enum OAuthError {
+    /// The name can't be used as a secret name.
+    #[error("Invalid name '{0}' (use letters, digits, '-', and '_')")]
+    InvalidName(String),
+
+    /// An endpoint isn't an http(s) URL.
+    #[error("Not an http(s) URL: {0}")]
+    InvalidUrl(String),
+
+    /// The redirect listener couldn't be started.
+    #[error("Failed to listen for the redirect: {0}")]
+    Listen(#[from] std::io::Error),
+
+    /// The browser couldn't be opened.
+    #[error("Failed to open the browser: {0}")]
+    Browser(String),
+
+    /// The provider redirected back with an error (e.g. access_denied).
+    #[error("Authorization failed: {0}")]
+    Denied(String),
+
+    /// The redirect didn't carry the state this flow sent.
+    #[error("Authorization failed: the state doesn't match")]
+    StateMismatch,
+
+    /// The token endpoint failed or answered with an error.
+    #[error("Token request failed: {0}")]
+    Token(String),
+
+    /// The user didn't finish signing in in time.
+    #[error("Timed out waiting for the browser")]
+    TimedOut,
+
+    /// A newer sign-in with the same name replaced this one.
+    #[error("Replaced by a newer sign-in")]
+    Cancelled,
+
+    /// The OS had no random bytes for the PKCE verifier.
+    #[error("Failed to get random bytes: {0}")]
+    Random(String),
+
+    /// The sign-in thread couldn't be started.
+    #[error("Failed to start the sign-in: {0}")]
+    Spawn(String),
+
+    /// Reading or writing the secrets store failed.
+    #[error("Secrets store failed: {0}")]
+    Secrets(String),
+}
+
+// =============================================================================
+// Config and tokens
+// =============================================================================
+
+/// A provider's endpoints and the client registered with it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OAuthConfig {
+    /// Authorization endpoint the browser opens.
+    pub auth_url: String,
+    /// Token endpoint the code is exchanged at.
+    pub token_url: String,
+    /// Client ID.
+    pub client_id: String,
+    /// Client secret, for providers that want one even with PKCE.
+    pub client_secret: Option<String>,
+    /// Requested scopes.
+    pub scopes: Vec<String>,
+    /// Extra authorization parameters (e.g. `access_type=offline`).
+    pub params: Vec<(String, String)>,
+    /// Fixed redirect port, for providers that only accept a registered
+    /// redirect URI (a free port if None).
+    pub port: Option<u16>,
+}
+
+impl OAuthConfig {
+    /// Create with the two endpoints and a client ID.
+    pub fn new(
+        auth_url: impl Into<String>,
+        token_url: impl Into<String>,
+        client_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            auth_url: auth_url.into(),
+            token_url: token_url.into(),
+            client_id: client_id.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the requested scopes.
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Check that both endpoints are http(s) URLs.
+    fn validate(&self) -> Result<(), OAuthError> {
+        for url in [&self.auth_url, &self.token_url] {
+            let lower = url.to_lowercase();
+            if !lower.starts_with("https://") && !lower.starts_with("http://") {
+                return Err(OAuthError::InvalidUrl(url.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The consent page URL, for a redirect URI, state, and PKCE challenge.
+    pub fn authorize_url(&self, redirect_uri: &str, state: &str, challenge: &str) -> String {
+        let mut params = vec![
+            ("response_type", "code"),
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", redirect_uri),
+            ("state", state),
+            ("code_challenge", challenge),
+            ("code_challenge_method", "S256"),
+        ];
+        let scope = self.scopes.join(" ");
+        if !scope.is_empty() {
+            params.push(("scope", &scope));
+        }
+        for (name, value) in &self.params {
+            params.push((name, value));
+        }
+        let separator = if self.auth_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        format!("{}{}{}", self.auth_url, separator, form_encode(&params))
+    }
+}
+
+/// Tokens from the token endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthToken {
+    pub access_token: String,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    /// Usually "bearer".
+    #[serde(default)]
+    pub token_type: String,
+    /// Unix time the access token expires at (None if it doesn't say).
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// Granted scopes, if they differ from the requested ones.
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl OAuthToken {
+    /// Whether the access token has expired (or will within a minute) at `now`.
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now + EXPIRY_MARGIN >= expires_at)
+    }
+
+    /// Whether the access token still works at `now`, even if it is about
+    /// to expire.
+    pub fn is_usable(&self, now: u64) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+/// What's stored in the secrets store: the tokens and how to refresh them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredToken {
+    token: OAuthToken,
+    token_url: String,
+    client_id: String,
+    #[serde(default)]
+    client_secret: Option<String>,
+}
+
+/// Whether a name has a sign-in, for `lux.oauth.status()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OAuthStatus {
+    /// The browser is open; waiting for the redirect.
+    Pending,
+    /// Tokens are stored and the access token is valid.
+    Authorized,
+    /// The access token expired; `access_token()` refreshes it if it can.
+    Expired,
+    /// The access token is being refreshed in the background.
+    Refreshing,
+}
+
+impl OAuthStatus {
+    /// Name of the status, as `lux.oauth.status()` reports it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Authorized => "authorized",
+            Self::Expired => "expired",
+            Self::Refreshing => "refreshing",
+        }
+    }
+}
+
+// =============================================================================
+// Token store
+// =============================================================================
+
+/// Secret name the tokens of `name` are stored as.
+fn secret_name(name: &str) -> String {
+    format!("oauth.{}", name)
+}
+
+fn check_name(name: &str) -> Result<(), OAuthError> {
+    if is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(OAuthError::InvalidName(name.to_string()))
+    }
+}
+
+fn load(name: &str) -> Result<Option<StoredToken>, OAuthError> {
+    check_name(name)?;
+    let Some(text) =
+        crate::secrets::get(&secret_name(name)).map_err(|e| OAuthError::Secrets(e.to_string()))?
+    else {
+        return Ok(None);
+    };
+    let stored: StoredToken =
+        serde_json::from_str(&text).map_err(|e| OAuthError::Secrets(e.to_string()))?;
+    remember_tokens(&stored.token);
+    Ok(Some(stored))
+}
+
+fn save(name: &str, stored: &StoredToken) -> Result<(), OAuthError> {
+    let text = serde_json::to_string(stored).map_err(|e| OAuthError::Secrets(e.to_string()))?;
+    crate::secrets::set(&secret_name(name), &text).map_err(|e| OAuthError::Secrets(e.to_string()))
+}
+
+/// Redact the tokens themselves from the log, not just the stored JSON.
+fn remember_tokens(token: &OAuthToken) {
+    crate::secrets::remember(&token.access_token);
+    if let Some(refresh_token) = &token.refresh_token {
+        crate::secrets::remember(refresh_token);
+    }
+}
+
+/// Exchange the refresh token of `name` for new tokens and store them.
+///
+/// Blocks on the token endpoint; `OAuthFlows::access_token()` runs it on a
+/// background thread.
+fn refresh(name: &str, mut stored: StoredToken) -> Result<(), OAuthError> {
+    let Some(refresh_token) = stored.token.refresh_token.clone() else {
+        return Ok(());
+    };
+
+    let mut params = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", stored.client_id.as_str()),
+    ];
+    if let Some(secret) = &stored.client_secret {
+        params.push(("client_secret", secret));
+    }
+    let mut token = request_token(&stored.token_url, &params)?;
+    // Providers that don't rotate refresh tokens leave it out of the answer
+    if token.refresh_token.is_none() {
+        token.refresh_token = Some(refresh_token);
+    }
+    stored.token = token;
+    save(name, &stored)?;
+    tracing::debug!("Refreshed the OAuth token of '{}'", name);
+    Ok(())
+}
+
+/// Whether `name` is signed in, ignoring sign-ins in progress.
+pub fn stored_status(name: &str) -> Result<Option<OAuthStatus>, OAuthError> {
+    Ok(load(name)?.map(|stored| {
+        if stored.token.is_expired(unix_now()) {
+            OAuthStatus::Expired
+        } else {
+            OAuthStatus::Authorized
+        }
+    }))
+}
+
+/// Forget the tokens of `name`. Returns false if there were none.
+pub fn logout(name: &str) -> Result<bool, OAuthError> {
+    check_name(name)?;
+    crate::secrets::delete(&secret_name(name)).map_err(|e| OAuthError::Secrets(e.to_string()))
+}
+
+/// POST a form to the token endpoint and parse the tokens.
+fn request_token(token_url: &str, params: &[(&str, &str)]) -> Result<OAuthToken, OAuthError> {
+    let mut request = HttpRequest::new("POST", token_url);
+    request.headers = vec![
+        (
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        ),
+        // GitHub answers with a form instead of JSON without this
+        ("Accept".to_string(), "application/json".to_string()),
+    ];
+    request.body = Some(form_encode(params));
+    let response =
+        crate::http::send(&request, TOKEN_TIMEOUT).map_err(|e| OAuthError::Token(e.to_string()))?;
+    let success = (200..300).contains(&response.status);
+    let token = match parse_token_response(&response.body, unix_now()) {
+        Ok(token) if success => token,
+        // Errors in a JSON answer say more than the status
+        Err(e) if success || response.json().is_some() => return Err(e),
+        _ => return Err(OAuthError::Token(response.status_line())),
+    };
+    remember_tokens(&token);
+    Ok(token)
+}
+
+/// Parse a token endpoint answer (JSON, or a form from older providers).
+fn parse_token_response(body: &str, now: u64) -> Result<OAuthToken, OAuthError> {
+    let fields: BTreeMap<String, String> = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Object(map)) => map
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    serde_json::Value::String(text) => text,
+                    other => other.to_string(),
+                };
+                (key, value)
+            })
+            .collect(),
+        _ => body
+            .trim()
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| {
+                let value = url_decode(value).unwrap_or_else(|_| value.to_string());
+                (key.to_string(), value)
+            })
+            .collect(),
+    };
+
+    if let Some(error) = fields.get("error") {
+        let description = fields.get("error_description").unwrap_or(error);
+        return Err(OAuthError::Token(description.clone()));
+    }
+    let access_token = fields
+        .get("access_token")
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| OAuthError::Token("no access_token in the answer".to_string()))?;
+    Ok(OAuthToken {
+        access_token: access_token.clone(),
+        refresh_token: fields.get("refresh_token").cloned(),
+        token_type: fields.get("token_type").cloned().unwrap_or_default(),
+        expires_at: fields
+            .get("expires_in")
+            .and_then(|secs| secs.parse::<u64>().ok())
+            .map(|secs| now + secs),
+        scope: fields.get("scope").cloned(),
+    })
+}
+
+// =============================================================================
+// Sign-in flows
+// =============================================================================
+
+/// A finished sign-in, waiting for its `on_done` callback to run.
+#[derive(Debug)]
+pub struct FinishedFlow {
+    /// Name the tokens are stored as.
+    pub name: String,
+    /// The tokens, or what went wrong.
+    pub result: Result<OAuthToken, String>,
+    /// Lua function called with the result.
+    pub on_done: Option<LuaFunctionRef>,
+}
+
+/// Sign-ins and token refreshes in progress, by name.
+pub struct OAuthFlows {
+    pending: Arc<Mutex<BTreeMap<String, Arc<AtomicBool>>>>,
+    finished: Arc<Mutex<Vec<FinishedFlow>>>,
+    refreshing: Arc<Mutex<BTreeSet<String>>>,
+    /// When a refresh last failed, so a revoked token isn't retried on every call.
+    failed: Arc<Mutex<BTreeMap<String, Instant>>>,
+}
+
+impl OAuthFlows {
+    /// Create with no sign-ins in progress.
+    pub fn new() -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(BTreeMap::new())),
+            finished: Arc::new(Mutex::new(Vec::new())),
+            refreshing: Arc::new(Mutex::new(BTreeSet::new())),
+            failed: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// A valid access token for `name`, without waiting on the network.
+    ///
+    /// A token that expired (or expires within a minute) is refreshed on a
+    /// background thread. Until that is done, a token that still works is
+    /// returned and one that doesn't gives None, with `status()` reporting
+    /// `Refreshing`. Also None when `name` never signed in, or when the token
+    /// expired and there is no refresh token.
+    pub fn access_token(&self, name: &str) -> Result<Option<String>, OAuthError> {
+        let Some(stored) = load(name)? else {
+            return Ok(None);
+        };
+        let now = unix_now();
+        if !stored.token.is_expired(now) {
+            return Ok(Some(stored.token.access_token));
+        }
+        let usable = stored.token.is_usable(now);
+        let access_token = stored.token.access_token.clone();
+        if stored.token.refresh_token.is_some() {
+            self.refresh_in_background(name, stored)?;
+        }
+        Ok(usable.then_some(access_token))
+    }
+
+    /// Refresh the tokens of `name` on a background thread, unless a refresh
+    /// is running or failed within `REFRESH_RETRY`.
+    fn refresh_in_background(&self, name: &str, stored: StoredToken) -> Result<(), OAuthError> {
+        if self
+            .failed
+            .lock()
+            .get(name)
+            .is_some_and(|failed| failed.elapsed() < REFRESH_RETRY)
+        {
+            return Ok(());
+        }
+        if !self.refreshing.lock().insert(name.to_string()) {
+            return Ok(());
+        }
+
+        let name = name.to_string();
+        let refreshing = self.refreshing.clone();
+        let failed = self.failed.clone();
+        let thread_name = name.clone();
+        let spawned = std::thread::Builder::new()
+            .name(format!("lux-oauth-refresh-{}", thread_name))
+            .spawn(move || {
+                match refresh(&name, stored) {
+                    Ok(()) => {
+                        failed.lock().remove(&name);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Refreshing the OAuth token of '{}' failed: {}", name, e);
+                        failed.lock().insert(name.clone(), Instant::now());
+                    }
+                }
+                refreshing.lock().remove(&name);
+            });
+        if let Err(e) = spawned {
+            self.refreshing.lock().remove(&thread_name);
+            return Err(OAuthError::Spawn(e.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Open the consent page in the browser and wait for the redirect in
+    /// the background. Returns the consent page URL.
+    ///
+    /// Starting a sign-in for a name that has one in progress cancels the
+    /// older one. Once signed in, the tokens are stored as `name`.
+    pub fn authorize(
+        &self,
+        name: &str,
+        config: OAuthConfig,
+        timeout: Duration,
+        on_done: Option<LuaFunctionRef>,
+    ) -> Result<String, OAuthError> {
+        check_name(name)?;
+        config.validate()?;
+        let (listener, redirect_uri) = listen(config.port)?;
+        let verifier = random_token()?;
+        let state = random_token()?;
+        let url = config.authorize_url(&redirect_uri, &state, &pkce_challenge(&verifier));
+        crate::desktop::open(&url).map_err(|e| OAuthError::Browser(e.to_string()))?;
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        if let Some(older) = self.pending.lock().insert(name.to_string(), cancel.clone()) {
+            older.store(true, Ordering::Relaxed);
+        }
+
+        let name = name.to_string();
+        let pending = self.pending.clone();
+        let finished = self.finished.clone();
+        let thread_name = name.clone();
+        let flow = Flow {
+            config,
+            redirect_uri,
+            state,
+            verifier,
+        };
+        let spawned = std::thread::Builder::new()
+            .name(format!("lux-oauth-{}", thread_name))
+            .spawn(move || {
+                let deadline = Instant::now() + timeout;
+                let result = flow
+                    .run(&listener, deadline, &cancel)
+                    .and_then(|stored| {
+                        save(&name, &stored)?;
+                        Ok(stored.token)
+                    })
+                    .map_err(|e| e.to_string());
+                match &result {
+                    Ok(_) => tracing::info!("Signed in to '{}'", name),
+                    Err(e) => tracing::warn!("Sign-in to '{}' failed: {}", name, e),
+                }
+
+                let mut pending = pending.lock();
+                if pending
+                    .get(&name)
+                    .is_some_and(|current| Arc::ptr_eq(current, &cancel))
+                {
+                    pending.remove(&name);
+                }
+                finished.lock().push(FinishedFlow {
+                    name,
+                    result,
+                    on_done,
+                });
+            });
+        if let Err(e) = spawned {
+            self.pending.lock().remove(&thread_name);
+            return Err(OAuthError::Spawn(e.to_string()));
+        }
+        Ok(url)
+    }
+
+    /// Whether `name` is signed in, signing in, or refreshing its token.
+    pub fn status(&self, name: &str) -> Result<Option<OAuthStatus>, OAuthError> {
+        if self.pending.lock().contains_key(name) {
+            return Ok(Some(OAuthStatus::Pending));
+        }
+        if self.refreshing.lock().contains(name) {
+            return Ok(Some(OAuthStatus::Refreshing));
+        }
+        stored_status(name)
+    }
+
+    /// Cancel the sign-ins in progress (when Lux quits).
+    pub fn cancel_all(&self) {
+        for cancel in self.pending.lock().values() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Take the sign-ins that finished since the last call.
+    pub fn take_finished(&self) -> Vec<FinishedFlow> {
+        std::mem::take(&mut *self.finished.lock())
+    }
+}
+
+impl Default for OAuthFlows {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One sign-in: what was sent to the provider, to check and finish the redirect.
+struct Flow {
+    config: OAuthConfig,
+    redirect_uri: String,
+    state: String,
+    verifier: String,
+}
+
+impl Flow {
+    /// Wait for the redirect and exchange its code for tokens.
+    fn run(
+        &self,
+        listener: &TcpListener,
+        deadline: Instant,
+        cancel: &AtomicBool,
+    ) -> Result<StoredToken, OAuthError> {
+        let (mut stream, code) = wait_for_code(listener, &self.state, deadline, cancel)?;
+
+        let mut params = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", self.verifier.as_str()),
+        ];
+        if let Some(secret) = &self.config.client_secret {
+            params.push(("client_secret", secret));
+        }
+        let result = request_token(&self.config.token_url, &params);
+
+        // The browser tab waits for this page until the exchange is done
+        let page = match &result {
+            Ok(_) => "Signed in. You can close this tab and go back to Lux.".to_string(),
+            Err(e) => format!("Sign-in failed: {}", e),
+        };
+        let _ = write_page(&mut stream, 200, &page);
+
+        Ok(StoredToken {
+            token: result?,
+            token_url: self.config.token_url.clone(),
+            client_id: self.config.client_id.clone(),
+            client_secret: self.config.client_secret.clone(),
+        })
+    }
+}
+
+/// Listen on 127.0.0.1 for the redirect. Returns the redirect URI.
+fn listen(port: Option<u16>) -> Result<(TcpListener, String), OAuthError> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port.unwrap_or(0)))?;
+    // Polled, so the thread notices a timeout or a newer sign-in
+    listener.set_nonblocking(true)?;
+    let port = listener.local_addr()?.port();
+    Ok((
+        listener,
+        format!("http://127.0.0.1:{}{}", port, REDIRECT_PATH),
+    ))
+}
+
+/// Accept browser requests until the redirect arrives. Returns the
+/// connection (to answer once the tokens are in) and the code.
+fn wait_for_code(
+    listener: &TcpListener,
+    state: &str,
+    deadline: Instant,
+    cancel: &AtomicBool,
+) -> Result<(TcpStream, String), OAuthError> {
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(OAuthError::Cancelled);
+        }
+        if Instant::now() >= deadline {
+            return Err(OAuthError::TimedOut);
+        }
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        let Ok(request) = RedirectRequest::read_from(&mut BufReader::new(stream)) else {
+            continue;
+        };
+        // Browsers also ask for /favicon.ico
+        if request.path != REDIRECT_PATH {
+            let _ = write_page(&mut writer, 404, "Not found");
+            continue;
+        }
+
+        if let Some(error) = request.param("error") {
+            let description = request.param("error_description").unwrap_or(error);
+            let _ = write_page(
+                &mut writer,
+                200,
+                &format!("Sign-in failed: {}", description),
+            );
+            return Err(OAuthError::Denied(description.to_string()));
+        }
+        if request.param("state") != Some(state) {
+            let _ = write_page(&mut writer, 400, "Sign-in failed: the state doesn't match.");
+            return Err(OAuthError::StateMismatch);
+        }
+        match request.param("code") {
+            Some(code) => return Ok((writer, code.to_string())),
+            None => {
+                let _ = write_page(&mut writer, 400, "Sign-in failed: no code.");
+                return Err(OAuthError::Denied("no code in the redirect".to_string()));
+            }
+        }
+    }
+}
+
+/// Answer the browser with a short page.
+fn write_page(stream: &mut impl Write, status: u16, message: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    let body = format!(
+        "<!doctype html><meta charset=\"utf-8\"><title>Lux</title>\
+         <p style=\"font: 16px system-ui; margin: 3em; text-align: center\">{}</p>",
+        html_escape(message)
+    );
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// `a=1&b=two%20words`
+fn form_encode(params: &[(&str, &str)]) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{}={}", url_encode(name), url_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// 32 random bytes, base64url: a PKCE verifier or a state.
+fn random_token() -> Result<String, OAuthError> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|e| OAuthError::Random(e.to_string()))?;
+    Ok(BASE64_URL.encode(bytes))
+}
+
+/// The S256 PKCE challenge of a verifier.
+fn pkce_challenge(verifier: &str) -> String {
+    BASE64_URL.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_authorize_url() {
+        // RFC 7636, appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+
+        let mut config = OAuthConfig::new(
+            "https://example.com/authorize",
+            "https://example.com/token",
+            "lux app",
+        )
+        .with_scopes(vec!["repo".to_string(), "read:user".to_string()]);
+        config.params = vec![("access_type".to_string(), "offline".to_string())];
+        let url = config.authorize_url("http://127.0.0.1:8765/callback", "st", "ch");
+        assert_eq!(
+            url,
+            "https://example.com/authorize?response_type=code&client_id=lux%20app\
+             &redirect_uri=http%3A%2F%2F127.0.0.1%3A8765%2Fcallback&state=st\
+             &code_challenge=ch&code_challenge_method=S256&scope=repo%20read%3Auser\
+             &access_type=offline"
+        );
+
+        config.token_url = "file:///etc/passwd".to_string();
+        assert!(matches!(config.validate(), Err(OAuthError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn test_parse_token_response() {
+        let token = parse_token_response(
+            r#"{"access_token": "abc", "refresh_token": "def", "token_type": "Bearer", "expires_in": 3600}"#,
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert_eq!(token.refresh_token.as_deref(), Some("def"));
+        assert_eq!(token.expires_at, Some(4_600));
+        assert!(!token.is_expired(1_000));
+        assert!(token.is_expired(4_550));
+        assert!(token.is_usable(4_550));
+        assert!(!token.is_usable(4_600));
+
+        // Form answers, as GitHub sends without Accept: application/json
+        let token =
+            parse_token_response("access_token=gho_1&scope=repo%2Cuser&token_type=bearer", 0)
+                .unwrap();
+        assert_eq!(token.access_token, "gho_1");
+        assert_eq!(token.scope.as_deref(), Some("repo,user"));
+        assert_eq!(token.expires_at, None);
+        assert!(!token.is_expired(u64::MAX - EXPIRY_MARGIN));
+
+        let error = parse_token_response(
+            r#"{"error": "invalid_grant", "error_description": "Code expired"}"#,
+            0,
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "Token request failed: Code expired");
+        assert!(parse_token_response("{}", 0).is_err());
+    }
+
+    /// Send browser requests to the listener, one after the other, and read
+    /// the pages they get.
+    fn browse(port: u16, targets: &[&str]) -> std::thread::JoinHandle<Vec<String>> {
+        let targets: Vec<String> = targets.iter().map(|target| target.to_string()).collect();
+        std::thread::spawn(move || {
+            targets
+                .iter()
+                .map(|target| {
+                    let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).unwrap();
+                    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).unwrap();
+                    let mut page = String::new();
+                    stream.read_to_string(&mut page).unwrap();
+                    page
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_wait_for_code() {
+        let cancel = AtomicBool::new(false);
+        let deadline = Instant::now() + Duration::from_secs(5);
+
+        let (listener, redirect_uri) = listen(None).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert_eq!(redirect_uri, format!("http://127.0.0.1:{}/callback", port));
+
+        let browser = browse(port, &["/favicon.ico", "/callback?code=c%2B1&state=st"]);
+        let (mut stream, code) = wait_for_code(&listener, "st", deadline, &cancel).unwrap();
+        assert_eq!(code, "c+1");
+        write_page(&mut stream, 200, "Signed in <ok>").unwrap();
+        drop(stream);
+        let pages = browser.join().unwrap();
+        assert!(pages[0].starts_with("HTTP/1.1 404"));
+        assert!(pages[1].starts_with("HTTP/1.1 200 OK"));
+        assert!(pages[1].contains("Signed in &lt;ok&gt;"));
+
+        let browser = browse(port, &["/callback?code=c&state=forged"]);
+        let result = wait_for_code(&listener, "st", deadline, &cancel);
+        assert!(matches!(result, Err(OAuthError::StateMismatch)));
+        assert!(browser.join().unwrap()[0].starts_with("HTTP/1.1 400"));
+
+        let browser = browse(port, &["/callback?error=access_denied&state=st"]);
+        let result = wait_for_code(&listener, "st", deadline, &cancel);
+        assert!(matches!(result, Err(OAuthError::Denied(ref e)) if e == "access_denied"));
+        browser.join().unwrap();
+
+        cancel.store(true, Ordering::Relaxed);
+        let result = wait_for_code(&listener, "st", deadline, &cancel);
+        assert!(matches!(result, Err(OAuthError::Cancelled)));
+        let cancel = AtomicBool::new(false);
+        let result = wait_for_code(&listener, "st", Instant::now(), &cancel);
+        assert!(matches!(result, Err(OAuthError::TimedOut)));
+    }
+}
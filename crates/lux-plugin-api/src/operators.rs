@@ -0,0 +1,163 @@
+//! Search operators in queries.
+//!
+//! This module provides:
+//! - `ParsedQuery` - A query split into terms, quoted phrases, and filters
+//! - `parse_query()` - The parser, shared by every source
+//!
+//! ## Syntax
+//!
+//! ```text
+//! report "q3 budget" type:pdf in:downloads in:"My Files"
+//! ```
+//!
+//! - Words are terms: `report`
+//! - Double quotes group a phrase: `"q3 budget"` (an unclosed quote runs to the end)
+//! - `key:value` is a filter; the value may be quoted, and a key may repeat
+//!
+//! A filter key starts with a letter and holds only letters, digits, `_`, and
+//! `-`. Keys are lowercased. Words that don't fit, such as `12:30` or
+//! `https://...`, stay terms.
+//!
+//! Sources read the result as `ctx.parsed` (Lua) or
+//! [`SourceContext::parsed_query`](crate::context::SourceContext::parsed_query).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A query split into terms, phrases, and filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedQuery {
+    /// Unquoted words that aren't filters.
+    pub terms: Vec<String>,
+    /// Quoted phrases, without the quotes.
+    pub phrases: Vec<String>,
+    /// Filter values by lowercased key, in query order.
+    pub filters: BTreeMap<String, Vec<String>>,
+}
+
+impl ParsedQuery {
+    /// Values of the filter `key`.
+    pub fn filter(&self, key: &str) -> &[String] {
+        self.filters.get(key).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Terms and phrases joined with spaces: the free text to match against.
+    pub fn text(&self) -> String {
+        self.terms
+            .iter()
+            .chain(&self.phrases)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether the query has no terms, phrases, or filters.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.phrases.is_empty() && self.filters.is_empty()
+    }
+}
+
+/// Parse search operators out of `query`.
+pub fn parse_query(query: &str) -> ParsedQuery {
+    let mut parsed = ParsedQuery::default();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' {
+            chars.next();
+            let phrase = read_quoted(&mut chars);
+            if !phrase.trim().is_empty() {
+                parsed.phrases.push(phrase);
+            }
+            continue;
+        }
+
+        // Read a word, up to whitespace or a quote that opens a filter value
+        let mut word = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() || (c == '"' && word.ends_with(':')) {
+                break;
+            }
+            word.push(c);
+            chars.next();
+        }
+
+        match word.split_once(':') {
+            Some((key, "")) if is_filter_key(key) && chars.peek() == Some(&'"') => {
+                chars.next();
+                let value = read_quoted(&mut chars);
+                push_filter(&mut parsed, key, value);
+            }
+            Some((key, value)) if is_filter_key(key) && is_filter_value(value) => {
+                push_filter(&mut parsed, key, value.to_string());
+            }
+            _ => parsed.terms.push(word),
+        }
+    }
+
+    parsed
+}
+
+/// Read up to the closing quote (consumed) or the end of the query.
+fn read_quoted(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut text = String::new();
+    for c in chars.by_ref() {
+        if c == '"' {
+            break;
+        }
+        text.push(c);
+    }
+    text
+}
+
+fn push_filter(parsed: &mut ParsedQuery, key: &str, value: String) {
+    parsed
+        .filters
+        .entry(key.to_lowercase())
+        .or_default()
+        .push(value);
+}
+
+fn is_filter_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Values are non-empty and not the `//` of a URL.
+fn is_filter_value(value: &str) -> bool {
+    !value.is_empty() && !value.starts_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_terms_phrases_and_filters() {
+        let parsed = parse_query(r#"report "q3 budget" Type:pdf in:downloads in:"My Files""#);
+        assert_eq!(parsed.terms, ["report"]);
+        assert_eq!(parsed.phrases, ["q3 budget"]);
+        assert_eq!(parsed.filter("type"), ["pdf"]);
+        assert_eq!(parsed.filter("in"), ["downloads", "My Files"]);
+        assert!(parsed.filter("tag").is_empty());
+        assert_eq!(parsed.text(), "report q3 budget");
+    }
+
+    #[test]
+    fn test_parse_leaves_non_filters_as_terms() {
+        let parsed = parse_query(r#"https://example.com 12:30 note: "unclosed phrase"#);
+        assert_eq!(parsed.terms, ["https://example.com", "12:30", "note:"]);
+        assert_eq!(parsed.phrases, ["unclosed phrase"]);
+        assert!(parsed.filters.is_empty());
+
+        assert!(parse_query("   ").is_empty());
+        assert!(parse_query(r#""""#).is_empty());
+    }
+}
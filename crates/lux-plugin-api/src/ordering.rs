@@ -0,0 +1,188 @@
+//! Result group ordering.
+//!
+//! This module provides:
+//! - `OrderRules` - Group order and weights for one view, or for every view
+//! - `ResultOrdering` - Global and per-view rules (lux.results.order)
+//!
+//! Without rules, groups keep the order the engine merged them in. With
+//! rules, groups named in `order` come first, in that order; the rest follow
+//! by weight (highest first, default 1), keeping the merged order for ties. A
+//! weight of 0 hides the group. Group names match titles, ignoring case.
+
+use std::collections::HashMap;
+
+use lux_core::Groups;
+use parking_lot::RwLock;
+
+/// Weight of groups without one.
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+
+/// Group order and weights.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderRules {
+    /// Group titles listed first, in this order.
+    pub order: Vec<String>,
+    /// Weight by group title.
+    pub weights: HashMap<String, f64>,
+}
+
+impl OrderRules {
+    /// Create rules listing `order` first.
+    pub fn new(order: Vec<String>) -> Self {
+        Self {
+            order: order
+                .into_iter()
+                .map(|title| title.to_lowercase())
+                .collect(),
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Set the weight of a group.
+    pub fn with_weight(mut self, title: &str, weight: f64) -> Self {
+        self.weights.insert(title.to_lowercase(), weight);
+        self
+    }
+
+    /// Reorder `groups`, dropping those weighted 0.
+    pub fn apply(&self, groups: &mut Groups) {
+        let key = |title: &str| {
+            let title = title.to_lowercase();
+            let rank = self
+                .order
+                .iter()
+                .position(|name| *name == title)
+                .unwrap_or(self.order.len());
+            let weight = self.weights.get(&title).copied().unwrap_or(DEFAULT_WEIGHT);
+            (rank, weight)
+        };
+
+        let mut keyed: Vec<_> = std::mem::take(groups)
+            .into_iter()
+            .map(|group| (key(group.title.as_deref().unwrap_or_default()), group))
+            .filter(|((_, weight), _)| *weight > 0.0)
+            .collect();
+        // Stable, so ties keep the merged order
+        keyed.sort_by(|((rank_a, weight_a), _), ((rank_b, weight_b), _)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| weight_b.total_cmp(weight_a))
+        });
+        *groups = keyed.into_iter().map(|(_, group)| group).collect();
+    }
+}
+
+/// Global and per-view ordering rules.
+pub struct ResultOrdering {
+    global: RwLock<Option<OrderRules>>,
+    views: RwLock<HashMap<String, OrderRules>>,
+}
+
+impl ResultOrdering {
+    /// Create an empty ordering (merged order everywhere).
+    pub fn new() -> Self {
+        Self {
+            global: RwLock::new(None),
+            views: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Set the rules for every view without its own (`None` clears them).
+    pub fn set_global(&self, rules: Option<OrderRules>) {
+        *self.global.write() = rules;
+    }
+
+    /// Set the rules for one view, replacing the global rules there
+    /// (`None` clears them).
+    pub fn set_view(&self, view_id: &str, rules: Option<OrderRules>) {
+        let mut views = self.views.write();
+        match rules {
+            Some(rules) => {
+                views.insert(view_id.to_string(), rules);
+            }
+            None => {
+                views.remove(view_id);
+            }
+        }
+    }
+
+    /// The rules that apply in a view.
+    pub fn rules_for(&self, view_id: Option<&str>) -> Option<OrderRules> {
+        view_id
+            .and_then(|id| self.views.read().get(id).cloned())
+            .or_else(|| self.global.read().clone())
+    }
+
+    /// Reorder the groups of a view's results.
+    pub fn apply(&self, view_id: Option<&str>, groups: &mut Groups) {
+        if let Some(rules) = self.rules_for(view_id) {
+            rules.apply(groups);
+        }
+    }
+}
+
+impl Default for ResultOrdering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lux_core::{Group, Item};
+
+    fn groups(titles: &[&str]) -> Groups {
+        titles
+            .iter()
+            .map(|title| Group::new(*title, vec![Item::new(*title, *title)]))
+            .collect()
+    }
+
+    fn titles(groups: &Groups) -> Vec<&str> {
+        groups
+            .iter()
+            .map(|group| group.title.as_deref().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn test_order_then_weight() {
+        let rules = OrderRules::new(vec!["Pinned".to_string(), "apps".to_string()])
+            .with_weight("Files", 0.5)
+            .with_weight("Web Search", 2.0)
+            .with_weight("Media", 0.0);
+
+        let mut results = groups(&["Files", "Media", "Apps", "Git", "Web Search", "Pinned"]);
+        rules.apply(&mut results);
+        assert_eq!(
+            titles(&results),
+            ["Pinned", "Apps", "Web Search", "Git", "Files"]
+        );
+    }
+
+    #[test]
+    fn test_view_rules_replace_global_rules() {
+        let ordering = ResultOrdering::new();
+        let mut results = groups(&["Files", "Apps"]);
+        ordering.apply(None, &mut results);
+        assert_eq!(titles(&results), ["Files", "Apps"]);
+
+        ordering.set_global(Some(OrderRules::new(vec!["Apps".to_string()])));
+        ordering.set_view(
+            "files",
+            Some(OrderRules::default().with_weight("Apps", 0.0)),
+        );
+
+        ordering.apply(Some("root"), &mut results);
+        assert_eq!(titles(&results), ["Apps", "Files"]);
+        ordering.apply(Some("files"), &mut results);
+        assert_eq!(titles(&results), ["Files"]);
+
+        ordering.set_view("files", None);
+        assert_eq!(
+            ordering.rules_for(Some("files")),
+            Some(OrderRules::new(vec!["apps".to_string()]))
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! Privacy permission checks.
+//!
+//! Some native sources read data protected by macOS privacy consent (TCC).
+//! This module provides:
+//! - `Permission` - A protected resource
+//! - `PermissionStatus` - Current authorization state
+//! - `status()` / `request()` - Query and prompt for access
+//! - `open_settings()` - Open the permission's pane in System Settings
+//! - `require()` - Gate a native source on granted access
+//!
+//! Accessibility and Full Disk Access have no prompt; they read as granted
+//! or denied, and requesting them opens System Settings. Automation is
+//! checked against System Events without prompting. Other platforms have no
+//! privacy consent, so every permission reads as granted there.
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+
+/// Bundle ID of the app Automation access is checked against.
+const AUTOMATION_TARGET: &str = "com.apple.systemevents";
+
+/// A resource protected by a privacy permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Controlling the computer: global hotkeys, pasting (AXIsProcessTrusted).
+    Accessibility,
+    /// Files in other apps' containers, Mail, Messages, Safari data.
+    FullDiskAccess,
+    /// Sending Apple Events to other apps (checked against System Events).
+    Automation,
+    /// Contacts (Contacts framework).
+    Contacts,
+    /// Calendar events (EventKit).
+    Calendar,
+    /// Reminders (EventKit).
+    Reminders,
+}
+
+impl Permission {
+    /// Every permission, in the order System Settings lists them.
+    pub const ALL: [Permission; 6] = [
+        Permission::Accessibility,
+        Permission::FullDiskAccess,
+        Permission::Automation,
+        Permission::Contacts,
+        Permission::Calendar,
+        Permission::Reminders,
+    ];
+
+    /// Parse a permission name as used from Lua.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "accessibility" => Some(Permission::Accessibility),
+            "full_disk_access" => Some(Permission::FullDiskAccess),
+            "automation" => Some(Permission::Automation),
+            "contacts" => Some(Permission::Contacts),
+            "calendar" => Some(Permission::Calendar),
+            "reminders" => Some(Permission::Reminders),
+            _ => None,
+        }
+    }
+
+    /// Name used from Lua.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Permission::Accessibility => "accessibility",
+            Permission::FullDiskAccess => "full_disk_access",
+            Permission::Automation => "automation",
+            Permission::Contacts => "contacts",
+            Permission::Calendar => "calendar",
+            Permission::Reminders => "reminders",
+        }
+    }
+
+    /// Name shown to the user.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Permission::Accessibility => "Accessibility",
+            Permission::FullDiskAccess => "Full Disk Access",
+            Permission::Automation => "Automation",
+            Permission::Contacts => "Contacts",
+            Permission::Calendar => "Calendar",
+            Permission::Reminders => "Reminders",
+        }
+    }
+
+    /// Deep link to the permission's pane in System Settings.
+    pub fn settings_url(&self) -> String {
+        let pane = match self {
+            Permission::Accessibility => "Privacy_Accessibility",
+            Permission::FullDiskAccess => "Privacy_AllFiles",
+            Permission::Automation => "Privacy_Automation",
+            Permission::Contacts => "Privacy_Contacts",
+            Permission::Calendar => "Privacy_Calendars",
+            Permission::Reminders => "Privacy_Reminders",
+        };
+        format!(
+            "x-apple.systempreferences:com.apple.preference.security?{}",
+            pane
+        )
+    }
+
+    /// EventKit entity type (`EKEntityTypeEvent` / `EKEntityTypeReminder`).
+    fn entity_type(&self) -> u8 {
+        match self {
+            Permission::Reminders => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Authorization state of a permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionStatus {
+    /// The user has not been asked yet.
+    NotDetermined,
+    /// Access is blocked by policy (e.g. parental controls, MDM).
+    Restricted,
+    /// The user declined access.
+    Denied,
+    /// Access granted.
+    Granted,
+}
+
+impl PermissionStatus {
+    /// Map an `EKAuthorizationStatus` or `CNAuthorizationStatus` value.
+    ///
+    /// Full access (3) and write-only access (4, macOS 14+) both map to
+    /// `Granted`; write-only cannot read, so callers should treat failed
+    /// reads as empty results.
+    fn from_ek_status(status: i64) -> Self {
+        match status {
+            0 => PermissionStatus::NotDetermined,
+            1 => PermissionStatus::Restricted,
+            2 => PermissionStatus::Denied,
+            _ => PermissionStatus::Granted,
+        }
+    }
+
+    /// Map the result of `AEDeterminePermissionToAutomateTarget`.
+    ///
+    /// The target not running (-600) reads as not determined.
+    fn from_ae_status(status: i64) -> Option<Self> {
+        match status {
+            0 => Some(PermissionStatus::Granted),
+            -1743 => Some(PermissionStatus::Denied),
+            -1744 | -600 => Some(PermissionStatus::NotDetermined),
+            _ => None,
+        }
+    }
+
+    /// Name used from Lua.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PermissionStatus::NotDetermined => "not_determined",
+            PermissionStatus::Restricted => "restricted",
+            PermissionStatus::Denied => "denied",
+            PermissionStatus::Granted => "granted",
+        }
+    }
+}
+
+/// Query the current authorization status of a permission, without prompting.
+pub fn status(permission: Permission) -> PluginResult<PermissionStatus> {
+    if !cfg!(target_os = "macos") {
+        return Ok(PermissionStatus::Granted);
+    }
+    match permission {
+        Permission::Accessibility => Ok(if accessibility_trusted() {
+            PermissionStatus::Granted
+        } else {
+            PermissionStatus::Denied
+        }),
+        Permission::FullDiskAccess => Ok(full_disk_access()),
+        Permission::Automation => {
+            let script = format!(
+                r#"
+                ObjC.import('CoreServices');
+                const target = $.NSAppleEventDescriptor.descriptorWithBundleIdentifier('{}');
+                $.AEDeterminePermissionToAutomateTarget(target.aeDesc, 0x2A2A2A2A, 0x2A2A2A2A, false)
+                "#,
+                AUTOMATION_TARGET
+            );
+            let code = parse_code(&run_jxa(&script)?)?;
+            PermissionStatus::from_ae_status(code).ok_or_else(|| {
+                PluginError::Command(format!("Unexpected Automation status {}", code))
+            })
+        }
+        Permission::Contacts => {
+            let script =
+                "ObjC.import('Contacts'); $.CNContactStore.authorizationStatusForEntityType(0)";
+            Ok(PermissionStatus::from_ek_status(parse_code(&run_jxa(
+                script,
+            )?)?))
+        }
+        Permission::Calendar | Permission::Reminders => {
+            let script = format!(
+                "ObjC.import('EventKit'); $.EKEventStore.authorizationStatusForEntityType({})",
+                permission.entity_type()
+            );
+            Ok(PermissionStatus::from_ek_status(parse_code(&run_jxa(
+                &script,
+            )?)?))
+        }
+    }
+}
+
+/// Prompt the user for a permission if it has not been decided yet.
+///
+/// A permission the user declined, or one without a prompt (Accessibility,
+/// Full Disk Access), opens its System Settings pane instead. Returns the
+/// status after the prompt (or the existing status if nothing changed).
+pub fn request(permission: Permission) -> PluginResult<PermissionStatus> {
+    let current = status(permission)?;
+    match (current, permission) {
+        (PermissionStatus::Granted | PermissionStatus::Restricted, _) => return Ok(current),
+        (PermissionStatus::Denied, _)
+        | (_, Permission::Accessibility | Permission::FullDiskAccess) => {
+            open_settings(permission)?;
+            return Ok(current);
+        }
+        _ => {}
+    }
+
+    // The completion handlers run asynchronously; poll until they fire
+    let script = match permission {
+        Permission::Automation => format!(
+            "Application('{}').processes.length",
+            AUTOMATION_TARGET
+        ),
+        Permission::Contacts => r#"
+            ObjC.import('Contacts');
+            const store = $.CNContactStore.alloc.init;
+            let done = false;
+            store.requestAccessForEntityTypeCompletionHandler(0, function(granted, error) { done = true; });
+            for (let i = 0; i < 1200 && !done; i++) delay(0.05);
+            "#
+        .to_string(),
+        _ => format!(
+            r#"
+            ObjC.import('EventKit');
+            const store = $.EKEventStore.alloc.init;
+            let done = false;
+            store.requestAccessToEntityTypeCompletion({}, function(granted, error) {{ done = true; }});
+            for (let i = 0; i < 1200 && !done; i++) delay(0.05);
+            "#,
+            permission.entity_type()
+        ),
+    };
+    if let Err(e) = run_jxa(&script) {
+        // Declining Automation fails the event that asked for it
+        if permission != Permission::Automation {
+            return Err(e);
+        }
+        tracing::debug!("Automation prompt: {}", e);
+    }
+    status(permission)
+}
+
+/// Open the permission's pane in System Settings.
+pub fn open_settings(permission: Permission) -> PluginResult<()> {
+    desktop::current().open(&permission.settings_url())
+}
+
+/// Parse the number a status script printed.
+fn parse_code(output: &str) -> PluginResult<i64> {
+    output.trim().parse().map_err(|_| {
+        PluginError::Command(format!(
+            "Unexpected authorization status '{}'",
+            output.trim()
+        ))
+    })
+}
+
+/// Whether Lux may control the computer (`AXIsProcessTrusted`).
+#[cfg(target_os = "macos")]
+fn accessibility_trusted() -> bool {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> u8;
+    }
+    // SAFETY: AXIsProcessTrusted takes no arguments and only reads state
+    unsafe { AXIsProcessTrusted() != 0 }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn accessibility_trusted() -> bool {
+    true
+}
+
+/// Full Disk Access, read from whether the TCC database can be opened.
+fn full_disk_access() -> PermissionStatus {
+    let Some(home) = dirs::home_dir() else {
+        return PermissionStatus::NotDetermined;
+    };
+    let db = home
+        .join("Library")
+        .join("Application Support")
+        .join("com.apple.TCC")
+        .join("TCC.db");
+    match std::fs::File::open(db) {
+        Ok(_) => PermissionStatus::Granted,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => PermissionStatus::Denied,
+        Err(_) => PermissionStatus::NotDetermined,
+    }
+}
+
+/// Fail with `PermissionDenied` unless the permission is granted.
+pub fn require(permission: Permission) -> PluginResult<()> {
+    match status(permission)? {
+        PermissionStatus::Granted => Ok(()),
+        _ => Err(PluginError::PermissionDenied(permission.name().to_string())),
+    }
+}
+
+/// Run a JXA (JavaScript for Automation) script and return its stdout.
+pub(crate) fn run_jxa(script: &str) -> PluginResult<String> {
+    run_jxa_with_args(script, &[])
+}
+
+/// Run a JXA script with arguments passed to its `run(argv)` function.
+pub(crate) fn run_jxa_with_args(script: &str, args: &[&str]) -> PluginResult<String> {
+    crate::desktop::current().run_script(script, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_names_round_trip() {
+        for permission in Permission::ALL {
+            assert_eq!(Permission::from_name(permission.name()), Some(permission));
+        }
+        assert_eq!(Permission::from_name("camera"), None);
+        assert_eq!(
+            Permission::FullDiskAccess.settings_url(),
+            "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles"
+        );
+    }
+
+    #[test]
+    fn test_status_from_ae_status() {
+        assert_eq!(
+            PermissionStatus::from_ae_status(0),
+            Some(PermissionStatus::Granted)
+        );
+        assert_eq!(
+            PermissionStatus::from_ae_status(-1743),
+            Some(PermissionStatus::Denied)
+        );
+        assert_eq!(
+            PermissionStatus::from_ae_status(-1744),
+            Some(PermissionStatus::NotDetermined)
+        );
+        assert_eq!(PermissionStatus::from_ae_status(-50), None);
+    }
+
+    #[test]
+    fn test_status_from_ek_status() {
+        assert_eq!(
+            PermissionStatus::from_ek_status(0),
+            PermissionStatus::NotDetermined
+        );
+        assert_eq!(
+            PermissionStatus::from_ek_status(2),
+            PermissionStatus::Denied
+        );
+        assert_eq!(
+            PermissionStatus::from_ek_status(3),
+            PermissionStatus::Granted
+        );
+        assert_eq!(
+            PermissionStatus::from_ek_status(4),
+            PermissionStatus::Granted
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Pinned results in the root view.
+//!
+//! This module provides:
+//! - `Pins` - Items pinned to the top of the root view (lux.pins), saved to the data directory
+//! - `pin_action()` / `run_pin_action()` - Pin or unpin any root-view result
+//!
+//! Pins are keyed by item ID and keep a copy of the item, so a pinned result
+//! is listed even when its source no longer returns it. The "Pinned" group is
+//! shown while the query is empty or matches a pinned item, and a pinned item
+//! is removed from the group it came from so it isn't listed twice.
+
+use std::path::{Path, PathBuf};
+
+use lux_core::i18n::t;
+use lux_core::{Group, Groups, Item};
+use parking_lot::Mutex;
+
+use crate::error::{PluginError, PluginResult};
+use crate::storage;
+
+/// Lua registry key prefix of the built-in pin action handlers.
+pub const PIN_ACTION_KEY_PREFIX: &str = "pin:";
+
+/// IDs of all pin actions (one handler is registered per ID).
+pub const PIN_ACTION_IDS: &[&str] = &["pin", "unpin"];
+
+/// Shortcut of the pin action.
+pub const PIN_SHORTCUT: &str = "cmd+shift+p";
+
+/// Handler key for a pin action ID.
+pub fn pin_action_key(id: &str) -> String {
+    format!("{}{}", PIN_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Pins
+// =============================================================================
+
+/// Items pinned to the root view, in pin order.
+///
+/// Saved as a JSON array of items after every change.
+pub struct Pins {
+    path: Option<PathBuf>,
+    items: Mutex<Vec<Item>>,
+}
+
+impl Pins {
+    /// Load pins from `path` (None keeps them in memory only).
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let items = path.as_deref().map(load_pins).unwrap_or_default();
+        Self {
+            path,
+            items: Mutex::new(items),
+        }
+    }
+
+    /// Where pins are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("pins.json"))
+    }
+
+    /// Pinned items, in pin order.
+    pub fn list(&self) -> Vec<Item> {
+        self.items.lock().clone()
+    }
+
+    /// Whether the item with `id` is pinned.
+    pub fn is_pinned(&self, id: &str) -> bool {
+        self.items.lock().iter().any(|item| item.id == id)
+    }
+
+    /// Pin an item, or update the copy of an already pinned one.
+    ///
+    /// Returns true if the item was not pinned before.
+    pub fn pin(&self, item: Item) -> bool {
+        let mut items = self.items.lock();
+        let added = match items.iter_mut().find(|pinned| pinned.id == item.id) {
+            Some(pinned) => {
+                *pinned = item;
+                false
+            }
+            None => {
+                items.push(item);
+                true
+            }
+        };
+        self.save(&items);
+        added
+    }
+
+    /// Unpin the item with `id`. Returns true if it was pinned.
+    pub fn unpin(&self, id: &str) -> bool {
+        let mut items = self.items.lock();
+        let len = items.len();
+        items.retain(|item| item.id != id);
+        let removed = items.len() != len;
+        if removed {
+            self.save(&items);
+        }
+        removed
+    }
+
+    /// Add the "Pinned" group to the top of the root view's results.
+    ///
+    /// Pinned items matching `query` (all of them while it's empty) are moved
+    /// out of the other groups.
+    pub fn apply(&self, query: &str, groups: &mut Groups) {
+        let query = query.trim().to_lowercase();
+        let pinned: Vec<Item> = self
+            .items
+            .lock()
+            .iter()
+            .filter(|item| query.is_empty() || matches_query(item, &query))
+            .cloned()
+            .collect();
+        if pinned.is_empty() {
+            return;
+        }
+
+        for group in groups.iter_mut() {
+            group
+                .items
+                .retain(|item| !pinned.iter().any(|p| p.id == item.id));
+        }
+        groups.retain(|group| !group.is_empty());
+        groups.insert(0, Group::new(t("Pinned"), pinned));
+    }
+
+    fn save(&self, items: &[Item]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(e) = save_pins(path, items) {
+            tracing::warn!("Failed to save pins: {}", e);
+        }
+    }
+}
+
+impl Default for Pins {
+    fn default() -> Self {
+        Self::new(Self::default_path())
+    }
+}
+
+fn matches_query(item: &Item, query: &str) -> bool {
+    item.title.to_lowercase().contains(query)
+        || item
+            .subtitle
+            .as_ref()
+            .is_some_and(|subtitle| subtitle.to_lowercase().contains(query))
+}
+
+fn load_pins(path: &Path) -> Vec<Item> {
+    storage::read(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_pins(path: &Path, items: &[Item]) -> std::io::Result<()> {
+    storage::write(path, &serde_json::to_string(items)?)
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// The pin action offered for an item: `(id, title)`.
+pub fn pin_action(item: &Item, pins: &Pins) -> (&'static str, String) {
+    if pins.is_pinned(&item.id) {
+        ("unpin", t("Unpin"))
+    } else {
+        ("pin", t("Pin to Top"))
+    }
+}
+
+/// Run a pin action on an item.
+pub fn run_pin_action(action_id: &str, item: &Item, pins: &Pins) -> PluginResult<()> {
+    match action_id {
+        "pin" => {
+            pins.pin(item.clone());
+        }
+        "unpin" => {
+            pins.unpin(&item.id);
+        }
+        _ => {
+            return Err(PluginError::Command(format!(
+                "Unknown pin action '{}'",
+                action_id
+            )))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pins_move_items_to_the_top() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pins.json");
+        let pins = Pins::new(Some(path.clone()));
+
+        assert!(pins.pin(Item::new("safari", "Safari")));
+        assert!(!pins.pin(Item::new("safari", "Safari Browser")));
+        pins.pin(Item::new("notes", "Notes"));
+
+        let mut groups = vec![Group::new(
+            "Apps",
+            vec![Item::new("safari", "Safari"), Item::new("mail", "Mail")],
+        )];
+        pins.apply("", &mut groups);
+        let titles: Vec<&str> = groups[0].items.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["Safari Browser", "Notes"]);
+        assert_eq!(groups[1].items.len(), 1);
+
+        // Only matching pins are shown for a query
+        let mut groups = vec![Group::new("Apps", vec![Item::new("safari", "Safari")])];
+        pins.apply("not", &mut groups);
+        assert_eq!(groups[0].items[0].id, "notes");
+        assert_eq!(groups[1].items[0].id, "safari");
+
+        // Pins survive a restart
+        assert!(pins.unpin("notes"));
+        assert!(!pins.unpin("notes"));
+        let reloaded = Pins::new(Some(path));
+        assert!(reloaded.is_pinned("safari"));
+        assert!(!reloaded.is_pinned("notes"));
+        assert_eq!(
+            pin_action(&Item::new("safari", "Safari"), &reloaded).0,
+            "unpin"
+        );
+    }
+}
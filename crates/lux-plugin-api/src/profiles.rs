@@ -0,0 +1,391 @@
+//! Profiles (focus filters).
+//!
+//! This module provides:
+//! - `Profile` - A named set of disabled views, hooks, and built-in sources
+//! - `ProfileCondition` - When a profile switches on automatically
+//! - `ProfileRegistry` - Storage for profiles and the active one
+//!
+//! A profile is either pinned with `lux.profile.set(name)` or chosen
+//! automatically: each time the launcher opens, the first profile whose
+//! `when` condition matches the frontmost app and local time becomes active.
+
+use std::collections::HashSet;
+
+use parking_lot::RwLock;
+
+/// Built-in root-view sources a profile can disable.
+pub const BUILTIN_SOURCES: &[&str] = &[
+    "timers",
+    "network",
+    "media",
+    "detect",
+    "colors",
+    "currency",
+    "dates",
+    "worldclock",
+    "qr",
+    "capture",
+    "recent",
+    "ssh",
+    "config_files",
+    "git",
+    "spotlight",
+    "dictionary",
+    "websearch",
+    "pins",
+];
+
+/// A named profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    /// Profile name (e.g. "work").
+    pub name: String,
+    /// View IDs that can't be listed or pushed.
+    pub disabled_views: HashSet<String>,
+    /// Hook paths that are skipped (e.g. "search", "views.files.search").
+    pub disabled_hooks: HashSet<String>,
+    /// Built-in sources hidden from the root view (see `BUILTIN_SOURCES`).
+    pub disabled_sources: HashSet<String>,
+    /// Automatic activation condition.
+    pub when: Option<ProfileCondition>,
+}
+
+impl Profile {
+    /// Create a profile that disables nothing.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// When a profile activates automatically. Empty parts match anything; all
+/// given parts must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileCondition {
+    /// Frontmost app names or bundle IDs (case-insensitive).
+    pub apps: Vec<String>,
+    /// Local time window as minutes since midnight, `[start, end)`.
+    /// Wraps past midnight when `start > end`.
+    pub hours: Option<(u32, u32)>,
+    /// ISO weekdays (1 = Monday ... 7 = Sunday).
+    pub days: Vec<u8>,
+}
+
+impl ProfileCondition {
+    /// Check the condition against the current context.
+    pub fn matches(&self, context: &ProfileContext) -> bool {
+        let app_matches = self.apps.is_empty()
+            || self.apps.iter().any(|app| {
+                [&context.app_name, &context.bundle_id]
+                    .into_iter()
+                    .flatten()
+                    .any(|value| value.eq_ignore_ascii_case(app))
+            });
+
+        let hours_match = match self.hours {
+            None => true,
+            Some((start, end)) if start <= end => (start..end).contains(&context.minutes),
+            Some((start, end)) => context.minutes >= start || context.minutes < end,
+        };
+
+        let day_matches = self.days.is_empty() || self.days.contains(&context.weekday);
+
+        app_matches && hours_match && day_matches
+    }
+
+    /// Whether the condition constrains anything (an empty condition never
+    /// activates a profile on its own).
+    pub fn is_empty(&self) -> bool {
+        self.apps.is_empty() && self.hours.is_none() && self.days.is_empty()
+    }
+}
+
+/// What automatic switching looks at.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileContext {
+    /// Frontmost application name.
+    pub app_name: Option<String>,
+    /// Frontmost application bundle ID.
+    pub bundle_id: Option<String>,
+    /// Local ISO weekday (1 = Monday ... 7 = Sunday).
+    pub weekday: u8,
+    /// Local time as minutes since midnight.
+    pub minutes: u32,
+}
+
+/// Parse a `"HH:MM"` time into minutes since midnight.
+pub fn parse_time(text: &str) -> Option<u32> {
+    let (hours, minutes) = text.trim().split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours <= 24 && minutes < 60 && hours * 60 + minutes <= 24 * 60).then_some(hours * 60 + minutes)
+}
+
+/// Registry for profiles.
+///
+/// With no active profile everything is enabled.
+pub struct ProfileRegistry {
+    /// Profiles in registration order (auto-switching picks the first match).
+    profiles: RwLock<Vec<Profile>>,
+    /// Name of the active profile.
+    active: RwLock<Option<String>>,
+    /// Whether the active profile was pinned with `set()` (disables auto-switching).
+    pinned: RwLock<bool>,
+    /// Context the launcher was last opened in.
+    context: RwLock<ProfileContext>,
+}
+
+impl ProfileRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            profiles: RwLock::new(Vec::new()),
+            active: RwLock::new(None),
+            pinned: RwLock::new(false),
+            context: RwLock::new(ProfileContext::default()),
+        }
+    }
+
+    /// Add a profile, replacing any profile with the same name.
+    pub fn add(&self, profile: Profile) -> Result<(), ProfileError> {
+        if profile.name.trim().is_empty() {
+            return Err(ProfileError::InvalidName(profile.name));
+        }
+        if let Some(source) = profile
+            .disabled_sources
+            .iter()
+            .find(|s| !BUILTIN_SOURCES.contains(&s.as_str()))
+        {
+            return Err(ProfileError::UnknownSource(source.clone()));
+        }
+
+        let mut profiles = self.profiles.write();
+        match profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+        Ok(())
+    }
+
+    /// List profile names in registration order.
+    pub fn list(&self) -> Vec<String> {
+        self.profiles
+            .read()
+            .iter()
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    /// Get a profile by name.
+    pub fn get(&self, name: &str) -> Option<Profile> {
+        self.profiles
+            .read()
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+    }
+
+    /// Pin a profile, or clear the pin with `None` to return to automatic
+    /// switching (which takes effect the next time the launcher opens).
+    pub fn set(&self, name: Option<&str>) -> Result<(), ProfileError> {
+        match name {
+            Some(name) => {
+                if self.get(name).is_none() {
+                    return Err(ProfileError::NotFound(name.to_string()));
+                }
+                *self.active.write() = Some(name.to_string());
+                *self.pinned.write() = true;
+            }
+            None => {
+                *self.active.write() = None;
+                *self.pinned.write() = false;
+            }
+        }
+        Ok(())
+    }
+
+    /// Name of the active profile.
+    pub fn active_name(&self) -> Option<String> {
+        self.active.read().clone()
+    }
+
+    /// Whether the active profile was pinned (automatic switching is off).
+    pub fn is_pinned(&self) -> bool {
+        *self.pinned.read()
+    }
+
+    /// Pick the active profile from the context unless one is pinned.
+    ///
+    /// Returns the active profile name after switching.
+    pub fn auto_switch(&self, context: &ProfileContext) -> Option<String> {
+        *self.context.write() = context.clone();
+        if !self.is_pinned() {
+            let matched = self
+                .profiles
+                .read()
+                .iter()
+                .find(|p| {
+                    p.when
+                        .as_ref()
+                        .is_some_and(|when| !when.is_empty() && when.matches(context))
+                })
+                .map(|p| p.name.clone());
+            *self.active.write() = matched;
+        }
+        self.active_name()
+    }
+
+    /// Context the launcher was last opened in (the app the user came from).
+    pub fn last_context(&self) -> ProfileContext {
+        self.context.read().clone()
+    }
+
+    /// Run `f` on the active profile (false when no profile is active).
+    fn active_disables(&self, f: impl Fn(&Profile) -> bool) -> bool {
+        let Some(name) = self.active_name() else {
+            return false;
+        };
+        self.profiles
+            .read()
+            .iter()
+            .find(|p| p.name == name)
+            .is_some_and(f)
+    }
+
+    /// Check if a view is enabled in the active profile.
+    pub fn is_view_enabled(&self, view_id: &str) -> bool {
+        !self.active_disables(|p| p.disabled_views.contains(view_id))
+    }
+
+    /// Check if a hook path is enabled in the active profile.
+    pub fn is_hook_enabled(&self, path: &str) -> bool {
+        !self.active_disables(|p| p.disabled_hooks.contains(path))
+    }
+
+    /// Check if a built-in root-view source is enabled in the active profile.
+    pub fn is_source_enabled(&self, source: &str) -> bool {
+        !self.active_disables(|p| p.disabled_sources.contains(source))
+    }
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors that can occur when configuring or switching profiles.
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    #[error("Invalid profile name '{0}'")]
+    InvalidName(String),
+
+    #[error("Profile '{0}' not found")]
+    NotFound(String),
+
+    #[error(
+        "Unknown built-in source '{0}' (expected one of: {expected})",
+        expected = BUILTIN_SOURCES.join(", ")
+    )]
+    UnknownSource(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work() -> Profile {
+        Profile {
+            disabled_views: HashSet::from(["games".to_string()]),
+            disabled_sources: HashSet::from(["media".to_string()]),
+            when: Some(ProfileCondition {
+                apps: vec!["com.tinyspeck.slackmacgap".to_string()],
+                hours: Some((9 * 60, 17 * 60)),
+                days: vec![1, 2, 3, 4, 5],
+            }),
+            ..Profile::new("work")
+        }
+    }
+
+    fn context(bundle_id: &str, weekday: u8, minutes: u32) -> ProfileContext {
+        ProfileContext {
+            app_name: None,
+            bundle_id: Some(bundle_id.to_string()),
+            weekday,
+            minutes,
+        }
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("09:30"), Some(570));
+        assert_eq!(parse_time("24:00"), Some(1440));
+        assert_eq!(parse_time("24:30"), None);
+        assert_eq!(parse_time("9"), None);
+    }
+
+    #[test]
+    fn test_condition_matches() {
+        let when = work().when.unwrap();
+        assert!(when.matches(&context("com.tinyspeck.slackmacgap", 2, 600)));
+        assert!(!when.matches(&context("com.tinyspeck.slackmacgap", 6, 600)));
+        assert!(!when.matches(&context("com.tinyspeck.slackmacgap", 2, 17 * 60)));
+        assert!(!when.matches(&context("com.apple.Safari", 2, 600)));
+
+        let night = ProfileCondition {
+            hours: Some((22 * 60, 6 * 60)),
+            ..Default::default()
+        };
+        assert!(night.matches(&context("x", 1, 23 * 60)));
+        assert!(night.matches(&context("x", 1, 60)));
+        assert!(!night.matches(&context("x", 1, 12 * 60)));
+    }
+
+    #[test]
+    fn test_auto_switch_and_pinning() {
+        let registry = ProfileRegistry::new();
+        registry.add(work()).unwrap();
+        registry.add(Profile::new("personal")).unwrap();
+
+        let at_work = context("com.tinyspeck.slackmacgap", 1, 600);
+        assert_eq!(registry.auto_switch(&at_work).as_deref(), Some("work"));
+        assert!(!registry.is_view_enabled("games"));
+        assert!(!registry.is_source_enabled("media"));
+        assert!(registry.is_source_enabled("timers"));
+
+        assert_eq!(registry.auto_switch(&context("x", 1, 600)), None);
+        assert!(registry.is_view_enabled("games"));
+
+        registry.set(Some("personal")).unwrap();
+        assert_eq!(
+            registry.auto_switch(&at_work).as_deref(),
+            Some("personal"),
+            "pinned profile wins"
+        );
+        assert_eq!(registry.last_context(), at_work);
+
+        registry.set(None).unwrap();
+        assert_eq!(registry.auto_switch(&at_work).as_deref(), Some("work"));
+    }
+
+    #[test]
+    fn test_add_validates() {
+        let registry = ProfileRegistry::new();
+        assert!(matches!(
+            registry.add(Profile::new(" ")),
+            Err(ProfileError::InvalidName(_))
+        ));
+
+        let mut profile = Profile::new("work");
+        profile.disabled_sources.insert("calendar".to_string());
+        let err = registry.add(profile).unwrap_err();
+        assert!(matches!(err, ProfileError::UnknownSource(_)));
+        assert!(err.to_string().contains("ssh, config_files, git"));
+
+        assert!(matches!(
+            registry.set(Some("missing")),
+            Err(ProfileError::NotFound(_))
+        ));
+    }
+}
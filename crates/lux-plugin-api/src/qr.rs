@@ -0,0 +1,306 @@
+//! QR codes for text, URLs, and Wi-Fi credentials.
+//!
+//! This module provides:
+//! - `render_png()` / `qr_image()` - Encode text as a QR code PNG (cached on disk)
+//! - `wifi_payload()` - The `WIFI:` text phones read as network credentials
+//! - `qr_group()` - Root-view answer for `qr <text>` and `qr wifi <ssid> <password>`
+//! - `qr_actions()` / `run_qr_action()` - Open, copy, or save the image
+//!
+//! Images are rendered once per text and cached under the user cache
+//! directory; items show them through their icon path. URLs and email
+//! addresses in any item also get a "Show QR Code" action (see `crate::detect`).
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use qrcodegen::{QrCode, QrCodeEcc};
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+
+/// View ID of the QR code view.
+pub const QR_VIEW_ID: &str = "builtin.qr";
+
+/// Type tag for QR code items (used to route them to the built-in actions).
+pub const QR_ITEM_TYPE: &str = "qr_code";
+
+/// Lua registry key prefix of the built-in QR code action handlers.
+pub const QR_ACTION_KEY_PREFIX: &str = "qr:";
+
+/// IDs of all QR code actions (one handler is registered per ID).
+pub const QR_ACTION_IDS: &[&str] = &["open_image", "copy_image", "save_image"];
+
+/// Pixels per module of a rendered code.
+const MODULE_SIZE: usize = 8;
+
+/// Light modules around the code, as the spec requires for scanning.
+const QUIET_ZONE: usize = 4;
+
+/// Characters of the encoded text shown in an item's title.
+const PREVIEW_LENGTH: usize = 60;
+
+/// Handler key for a QR code action ID.
+pub fn qr_action_key(id: &str) -> String {
+    format!("{}{}", QR_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Rendering
+// =============================================================================
+
+/// Encode text as a QR code and render it as a grayscale PNG.
+pub fn render_png(text: &str) -> PluginResult<Vec<u8>> {
+    let code = QrCode::encode_text(text, QrCodeEcc::Medium)
+        .map_err(|e| PluginError::Command(format!("QR code: {}", e)))?;
+    let size = code.size() as usize;
+    let side = (size + 2 * QUIET_ZONE) * MODULE_SIZE;
+
+    let mut pixels = vec![255u8; side * side];
+    for y in 0..size {
+        for x in 0..size {
+            if !code.get_module(x as i32, y as i32) {
+                continue;
+            }
+            let (left, top) = (
+                (x + QUIET_ZONE) * MODULE_SIZE,
+                (y + QUIET_ZONE) * MODULE_SIZE,
+            );
+            for row in top..top + MODULE_SIZE {
+                pixels[row * side + left..row * side + left + MODULE_SIZE].fill(0);
+            }
+        }
+    }
+
+    let png_error = |e: png::EncodingError| PluginError::Command(format!("PNG encoding: {}", e));
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, side as u32, side as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_error)?;
+    writer.write_image_data(&pixels).map_err(png_error)?;
+    writer.finish().map_err(png_error)?;
+    Ok(out)
+}
+
+/// Path of the cached PNG for a text, rendering it on first use.
+pub fn qr_image(text: &str) -> PluginResult<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+        .join("lux")
+        .join("qr");
+    qr_image_in(&cache_dir, text)
+}
+
+fn qr_image_in(cache_dir: &Path, text: &str) -> PluginResult<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    let path = cache_dir.join(format!("{:x}.png", hasher.finish()));
+    if !path.exists() {
+        let png = render_png(text)?;
+        std::fs::create_dir_all(cache_dir)
+            .and_then(|_| std::fs::write(&path, png))
+            .map_err(|e| PluginError::Command(format!("Failed to save QR code: {}", e)))?;
+    }
+    Ok(path)
+}
+
+/// The text phones read as Wi-Fi credentials (`WIFI:T:WPA;S:name;P:secret;;`).
+///
+/// Networks without a password are encoded as open networks.
+pub fn wifi_payload(ssid: &str, password: Option<&str>, hidden: bool) -> String {
+    fn escape(s: &str) -> String {
+        s.chars()
+            .flat_map(|c| match c {
+                '\\' | ';' | ',' | ':' | '"' => vec!['\\', c],
+                c => vec![c],
+            })
+            .collect()
+    }
+
+    let mut payload = match password.filter(|p| !p.is_empty()) {
+        Some(password) => format!("WIFI:T:WPA;S:{};P:{};", escape(ssid), escape(password)),
+        None => format!("WIFI:T:nopass;S:{};", escape(ssid)),
+    };
+    if hidden {
+        payload.push_str("H:true;");
+    }
+    payload.push(';');
+    payload
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// What a root-view query asks to encode: `(text, label)`.
+///
+/// `qr <text>` encodes the text; `qr wifi <ssid> [password]` encodes network
+/// credentials (quote an SSID with spaces).
+pub fn parse_qr_query(query: &str) -> Option<(String, String)> {
+    let rest = query.trim_start().strip_prefix("qr ")?.trim();
+    if rest.is_empty() {
+        return None;
+    }
+
+    if let Some(credentials) = rest.strip_prefix("wifi ") {
+        let credentials = credentials.trim();
+        let (ssid, password) = match credentials.strip_prefix('"') {
+            Some(quoted) => {
+                let (ssid, rest) = quoted.split_once('"')?;
+                (ssid, rest.trim())
+            }
+            None => credentials
+                .split_once(' ')
+                .map_or((credentials, ""), |(ssid, rest)| (ssid, rest.trim())),
+        };
+        if ssid.is_empty() {
+            return None;
+        }
+        let label = t_args("Wi-Fi: {network}", &[("network", ssid)]);
+        return Some((wifi_payload(ssid, Some(password), false), label));
+    }
+
+    Some((rest.to_string(), rest.to_string()))
+}
+
+/// Build the item showing the QR code for `text`, labelled `label`.
+pub fn qr_item(text: &str, label: &str) -> PluginResult<Item> {
+    let image = qr_image(text)?.to_string_lossy().to_string();
+    let mut title: String = label.chars().take(PREVIEW_LENGTH).collect();
+    if title.len() < label.len() {
+        title.push('…');
+    }
+    Ok(Item::new(format!("qr:{}", image), title)
+        .with_subtitle(t("QR Code"))
+        .with_icon(image.clone())
+        .with_type(QR_ITEM_TYPE)
+        .with_data(serde_json::json!({ "text": text, "image": image })))
+}
+
+/// Build the root-view answer group for `qr ...` queries.
+pub fn qr_group(query: &str) -> Option<Group> {
+    let (text, label) = parse_qr_query(query)?;
+    match qr_item(&text, &label) {
+        Ok(item) => Some(Group::new(t("QR Code"), vec![item])),
+        Err(e) => {
+            tracing::debug!("No QR code for '{}': {}", label, e);
+            None
+        }
+    }
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a QR code item: `(id, title)`.
+pub fn qr_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("open_image", t("Open Image")),
+        ("copy_image", t("Copy Image")),
+        ("save_image", t("Save to Downloads")),
+    ]
+}
+
+/// Run a QR code action.
+pub fn run_qr_action(action_id: &str, item: &Item) -> PluginResult<()> {
+    let image = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("image")?.as_str())
+        .ok_or_else(|| PluginError::Command("Item is not a QR code".to_string()))?;
+    match action_id {
+        "open_image" => desktop::current().open(image),
+        "copy_image" => desktop::current().clipboard_write_image(image),
+        "save_image" => {
+            let downloads = dirs::download_dir()
+                .or_else(|| dirs::home_dir().map(|home| home.join("Downloads")))
+                .ok_or_else(|| PluginError::Command("No Downloads folder".to_string()))?;
+            let path = unused_path(&downloads, "QR Code", "png");
+            std::fs::copy(image, &path)
+                .map_err(|e| PluginError::Command(format!("Failed to save QR code: {}", e)))?;
+            desktop::current().reveal(&path.to_string_lossy())
+        }
+        _ => Err(PluginError::Command(format!(
+            "Unknown QR code action '{}'",
+            action_id
+        ))),
+    }
+}
+
+/// `dir/name.ext`, or `dir/name 2.ext` etc. when that exists.
+pub(crate) fn unused_path(dir: &Path, name: &str, ext: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.{}", name, ext));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} {}.{}", name, n, ext));
+        n += 1;
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wifi_payload() {
+        assert_eq!(
+            wifi_payload("Home", Some("pa;ss"), false),
+            "WIFI:T:WPA;S:Home;P:pa\\;ss;;"
+        );
+        assert_eq!(
+            wifi_payload("Café", None, true),
+            "WIFI:T:nopass;S:Café;H:true;;"
+        );
+    }
+
+    #[test]
+    fn test_parse_qr_query() {
+        assert_eq!(
+            parse_qr_query("qr https://example.com"),
+            Some((
+                "https://example.com".to_string(),
+                "https://example.com".to_string()
+            ))
+        );
+        let (text, label) = parse_qr_query("qr wifi \"My Net\" hunter2").unwrap();
+        assert_eq!(text, "WIFI:T:WPA;S:My Net;P:hunter2;;");
+        assert_eq!(label, "Wi-Fi: My Net");
+        let (text, _) = parse_qr_query("qr wifi Guest").unwrap();
+        assert_eq!(text, "WIFI:T:nopass;S:Guest;;");
+        assert_eq!(parse_qr_query("qr "), None);
+        assert_eq!(parse_qr_query("qrcode"), None);
+    }
+
+    #[test]
+    fn test_qr_image_is_cached_png() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = qr_image_in(dir.path(), "https://example.com").unwrap();
+        let png = std::fs::read(&path).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(
+            qr_image_in(dir.path(), "https://example.com").unwrap(),
+            path
+        );
+        assert_ne!(qr_image_in(dir.path(), "other").unwrap(), path);
+    }
+
+    #[test]
+    fn test_unused_path() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            unused_path(dir.path(), "QR Code", "png"),
+            dir.path().join("QR Code.png")
+        );
+        std::fs::write(dir.path().join("QR Code.png"), b"").unwrap();
+        assert_eq!(
+            unused_path(dir.path(), "QR Code", "png"),
+            dir.path().join("QR Code 2.png")
+        );
+    }
+}
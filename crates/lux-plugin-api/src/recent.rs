@@ -0,0 +1,641 @@
+//! Recently opened documents and the Downloads folder.
+//!
+//! This module provides:
+//! - `RecentFile` - A file with the date it was last used (or downloaded)
+//! - `Recents` - Both listings, kept fresh by a background watcher (lux.recent)
+//! - `recent_actions()` / `run_recent_action()` - Open, reveal, or trash a file
+//!
+//! Type `recent` or `downloads` in the root view, optionally followed by part
+//! of a file name:
+//!
+//! ```text
+//! recent
+//! recent budget
+//! downloads .dmg
+//! ```
+//!
+//! On macOS recent documents come from Spotlight's `kMDItemLastUsedDate`,
+//! which the system sets whenever an app opens a document; on Linux they come
+//! from the freedesktop `recently-used.xbel` list. The watcher thread polls
+//! the Downloads folder and re-reads the recent documents now and then, and
+//! pings the shared results channel when either changes so the UI re-runs
+//! the search.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::watch;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{DragPayload, Group, Item};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::spotlight::abbreviate_home;
+use crate::undo::{trash_file, UndoStack};
+
+/// Type tag for recent file items (used to route them to the built-in actions).
+pub const RECENT_ITEM_TYPE: &str = "recent_file";
+
+/// Lua registry key prefix of the built-in recent file action handlers.
+pub const RECENT_ACTION_KEY_PREFIX: &str = "recent:";
+
+/// IDs of all recent file actions (one handler is registered per ID).
+pub const RECENT_ACTION_IDS: &[&str] = &["open", "reveal", "trash"];
+
+/// Shortest keyword prefix that lists files (`rec`, `dow`).
+const MIN_KEYWORD_LEN: usize = 3;
+
+/// Files kept per listing.
+const MAX_FILES: usize = 50;
+
+/// Files shown in a group.
+const MAX_RESULTS: usize = 20;
+
+/// How far back recent documents go.
+const RECENT_DAYS: i64 = 30;
+
+/// Time between checks of the Downloads folder.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time between reads of the recent documents.
+const DOCUMENTS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Extensions of downloads that are still in progress.
+const PARTIAL_EXTENSIONS: &[&str] = &["crdownload", "download", "part", "partial"];
+
+/// Handler key for a recent file action ID.
+pub fn recent_action_key(id: &str) -> String {
+    format!("{}{}", RECENT_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// A recently used or downloaded file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    /// Last used (documents) or modified (downloads), as a Unix timestamp.
+    pub date: i64,
+}
+
+/// Which listing a query asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Listing {
+    /// Recently opened documents.
+    Documents,
+    /// The Downloads folder.
+    Downloads,
+}
+
+impl Listing {
+    /// The keyword that lists it (a query may type a prefix of it).
+    fn keyword(&self) -> &'static str {
+        match self {
+            Listing::Documents => "recents",
+            Listing::Downloads => "downloads",
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            Listing::Documents => t("Recent Documents"),
+            Listing::Downloads => t("Downloads"),
+        }
+    }
+}
+
+/// Split a query into the listing it asks for and the name filter.
+pub fn parse_recent_query(query: &str) -> Option<(Listing, String)> {
+    let query = query.trim();
+    let (word, filter) = query.split_once(' ').unwrap_or((query, ""));
+    if word.chars().count() < MIN_KEYWORD_LEN {
+        return None;
+    }
+    let word = word.to_lowercase();
+    [Listing::Documents, Listing::Downloads]
+        .into_iter()
+        .find(|listing| listing.keyword().starts_with(&word))
+        .map(|listing| (listing, filter.trim().to_lowercase()))
+}
+
+// =============================================================================
+// Listings
+// =============================================================================
+
+/// Both listings, as last read by the watcher.
+#[derive(Default)]
+struct Listings {
+    documents: Vec<RecentFile>,
+    downloads: Vec<RecentFile>,
+}
+
+/// Recent documents and downloads for the root view.
+///
+/// The watcher thread starts with the first `group()` call and stops once
+/// this is dropped.
+pub struct Recents {
+    downloads_folder: Arc<RwLock<PathBuf>>,
+    listings: Arc<Mutex<Listings>>,
+    updates: Arc<watch::Sender<u64>>,
+    watching: Mutex<bool>,
+}
+
+impl Recents {
+    /// Watch `downloads_folder`, notifying changes on `updates`.
+    pub fn new(downloads_folder: impl Into<PathBuf>, updates: Arc<watch::Sender<u64>>) -> Self {
+        Self {
+            downloads_folder: Arc::new(RwLock::new(downloads_folder.into())),
+            listings: Arc::new(Mutex::new(Listings::default())),
+            updates,
+            watching: Mutex::new(false),
+        }
+    }
+
+    /// `~/Downloads` (or the platform's download directory).
+    pub fn default_downloads_folder() -> PathBuf {
+        dirs::download_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join("Downloads")))
+            .unwrap_or_else(|| PathBuf::from("Downloads"))
+    }
+
+    /// The folder listed as Downloads.
+    pub fn downloads_folder(&self) -> PathBuf {
+        self.downloads_folder.read().clone()
+    }
+
+    /// Change the folder listed as Downloads.
+    pub fn set_downloads_folder(&self, folder: impl Into<PathBuf>) {
+        let folder = folder.into();
+        self.listings.lock().downloads = list_folder(&folder);
+        *self.downloads_folder.write() = folder;
+    }
+
+    /// Recently opened documents, newest first.
+    pub fn documents(&self) -> Vec<RecentFile> {
+        self.watch();
+        self.listings.lock().documents.clone()
+    }
+
+    /// Files in the Downloads folder, newest first.
+    pub fn downloads(&self) -> Vec<RecentFile> {
+        self.watch();
+        self.listings.lock().downloads.clone()
+    }
+
+    /// Build the root-view group for `recent ...` and `downloads ...`.
+    pub fn group(&self, query: &str, now: i64) -> Option<Group> {
+        let (listing, filter) = parse_recent_query(query)?;
+        let files = match listing {
+            Listing::Documents => self.documents(),
+            Listing::Downloads => self.downloads(),
+        };
+        let items: Vec<Item> = filter_files(&files, &filter)
+            .map(|file| recent_item(file, now))
+            .collect();
+        if items.is_empty() {
+            return None;
+        }
+        Some(Group::new(listing.title(), items))
+    }
+
+    /// Start the watcher thread unless it is running.
+    fn watch(&self) {
+        let mut watching = self.watching.lock();
+        if *watching {
+            return;
+        }
+        *watching = true;
+
+        let listings = Arc::downgrade(&self.listings);
+        let folder = self.downloads_folder.clone();
+        let updates = self.updates.clone();
+        std::thread::spawn(move || watch_loop(listings, folder, &updates));
+    }
+}
+
+impl Default for Recents {
+    fn default() -> Self {
+        let (updates, _) = watch::channel(0);
+        Self::new(Self::default_downloads_folder(), Arc::new(updates))
+    }
+}
+
+/// Keep the listings fresh until the `Recents` they belong to is dropped.
+fn watch_loop(
+    listings: Weak<Mutex<Listings>>,
+    folder: Arc<RwLock<PathBuf>>,
+    updates: &watch::Sender<u64>,
+) {
+    let mut watched = (PathBuf::new(), None);
+    let mut documents_read: Option<Instant> = None;
+
+    loop {
+        let Some(shared) = listings.upgrade() else {
+            return;
+        };
+        let mut changed = false;
+
+        // A folder's modification time changes when files are added or removed
+        let folder = folder.read().clone();
+        let modified = std::fs::metadata(&folder).and_then(|m| m.modified()).ok();
+        if (&folder, modified) != (&watched.0, watched.1) {
+            let downloads = list_folder(&folder);
+            watched = (folder, modified);
+            let mut listings = shared.lock();
+            changed |= listings.downloads != downloads;
+            listings.downloads = downloads;
+        }
+
+        if documents_read.is_none_or(|read| read.elapsed() >= DOCUMENTS_INTERVAL) {
+            documents_read = Some(Instant::now());
+            let documents = recent_documents();
+            let mut listings = shared.lock();
+            changed |= listings.documents != documents;
+            listings.documents = documents;
+        }
+
+        drop(shared);
+        if changed {
+            updates.send_modify(|count| *count = count.wrapping_add(1));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Files in a folder, newest first, without hidden files and partial downloads.
+pub fn list_folder(folder: &Path) -> Vec<RecentFile> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    let mut files: Vec<RecentFile> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .filter(|entry| {
+            let path = entry.path();
+            !path
+                .extension()
+                .is_some_and(|ext| PARTIAL_EXTENSIONS.iter().any(|p| ext == *p))
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some(RecentFile {
+                path: entry.path(),
+                date: unix_time(modified),
+            })
+        })
+        .collect();
+    newest_first(&mut files);
+    files
+}
+
+/// Sort files newest first and keep at most `MAX_FILES`.
+fn newest_first(files: &mut Vec<RecentFile>) {
+    files.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.path.cmp(&b.path)));
+    files.truncate(MAX_FILES);
+}
+
+fn unix_time(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Files whose name contains `filter` (already lowercase).
+fn filter_files<'a>(
+    files: &'a [RecentFile],
+    filter: &'a str,
+) -> impl Iterator<Item = &'a RecentFile> {
+    files
+        .iter()
+        .filter(move |file| {
+            filter.is_empty()
+                || file
+                    .path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().to_lowercase().contains(filter))
+        })
+        .take(MAX_RESULTS)
+}
+
+// =============================================================================
+// Recent Documents
+// =============================================================================
+
+/// Documents opened in the last `RECENT_DAYS` days, newest first.
+#[cfg(not(target_os = "linux"))]
+pub fn recent_documents() -> Vec<RecentFile> {
+    use std::process::Command;
+
+    let expression = format!(
+        "kMDItemLastUsedDate >= $time.today(-{}) && kMDItemContentTypeTree == \"public.content\"",
+        RECENT_DAYS
+    );
+    let Some(found) = command_output(Command::new("mdfind").arg(&expression)) else {
+        return Vec::new();
+    };
+    let paths: Vec<&str> = found.lines().filter(|line| !line.is_empty()).collect();
+    if paths.is_empty() {
+        return Vec::new();
+    }
+
+    // One mdls call for all files; values are NUL-separated in path order
+    let Some(dates) = command_output(
+        Command::new("mdls")
+            .args(["-raw", "-name", "kMDItemLastUsedDate"])
+            .args(&paths),
+    ) else {
+        return Vec::new();
+    };
+    let mut files = parse_last_used(&paths, &dates);
+    newest_first(&mut files);
+    files
+}
+
+/// Documents from the freedesktop recently-used list, newest first.
+#[cfg(target_os = "linux")]
+pub fn recent_documents() -> Vec<RecentFile> {
+    let Some(path) = dirs::data_dir().map(|dir| dir.join("recently-used.xbel")) else {
+        return Vec::new();
+    };
+    let Ok(xbel) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let cutoff = unix_time(SystemTime::now()) - RECENT_DAYS * 86_400;
+    let mut files: Vec<RecentFile> = parse_xbel(&xbel)
+        .into_iter()
+        .filter(|file| file.date >= cutoff && file.path.exists())
+        .collect();
+    newest_first(&mut files);
+    files
+}
+
+/// Run a command and return its stdout, if it succeeds.
+#[cfg(not(target_os = "linux"))]
+fn command_output(cmd: &mut std::process::Command) -> Option<String> {
+    use std::process::Stdio;
+
+    let output = cmd
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| tracing::debug!("Failed to list recent documents: {}", e))
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Pair paths with `mdls -raw` dates (`2026-10-16 09:30:00 +0000`, NUL-separated).
+///
+/// Files without a date (`(null)`) are left out.
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn parse_last_used(paths: &[&str], dates: &str) -> Vec<RecentFile> {
+    paths
+        .iter()
+        .zip(dates.split('\0'))
+        .filter_map(|(path, date)| {
+            let date =
+                chrono::DateTime::parse_from_str(date.trim(), "%Y-%m-%d %H:%M:%S %z").ok()?;
+            Some(RecentFile {
+                path: PathBuf::from(path),
+                date: date.timestamp(),
+            })
+        })
+        .collect()
+}
+
+/// Local files from an XBEL bookmark list, dated by their last visit.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xbel(xbel: &str) -> Vec<RecentFile> {
+    xbel.split("<bookmark")
+        .skip(1)
+        .filter_map(|bookmark| {
+            let tag = bookmark.split('>').next()?;
+            let href = xml_attribute(tag, "href")?.strip_prefix("file://")?;
+            // `+` is literal in file URIs; only percent escapes need decoding
+            let path = crate::text::url_decode(&href.replace('+', "%2B")).ok()?;
+            let visited =
+                xml_attribute(tag, "visited").or_else(|| xml_attribute(tag, "modified"))?;
+            let date = chrono::DateTime::parse_from_rfc3339(visited).ok()?;
+            Some(RecentFile {
+                path: PathBuf::from(path),
+                date: date.timestamp(),
+            })
+        })
+        .collect()
+}
+
+/// The value of `name="..."` in an XML start tag.
+fn xml_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// How long ago a timestamp was: "Just now", "5 min ago", "Yesterday".
+pub(crate) fn format_age(date: i64, now: i64) -> String {
+    let seconds = (now - date).max(0);
+    match seconds {
+        0..60 => t("Just now"),
+        60..3_600 => t_args("{count} min ago", &[("count", &(seconds / 60).to_string())]),
+        3_600..86_400 => t_args(
+            "{count} h ago",
+            &[("count", &(seconds / 3_600).to_string())],
+        ),
+        86_400..172_800 => t("Yesterday"),
+        _ => t_args(
+            "{count} days ago",
+            &[("count", &(seconds / 86_400).to_string())],
+        ),
+    }
+}
+
+/// Build the item for a recent file.
+fn recent_item(file: &RecentFile, now: i64) -> Item {
+    let path_str = file.path.to_string_lossy().to_string();
+    let title = file
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_str.clone());
+
+    let mut item = Item::new(format!("recent:{}", path_str), title)
+        .with_icon(if file.path.is_dir() { "📁" } else { "📄" })
+        .with_type(RECENT_ITEM_TYPE)
+        .with_type("file")
+        .with_data(serde_json::json!({ "path": path_str }))
+        .with_accessory(format_age(file.date, now))
+        .with_drag(DragPayload::File { path: path_str });
+    item.subtitle = file.path.parent().map(abbreviate_home);
+    item
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a recent file: `(id, title)`.
+pub fn recent_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("open", t("Open")),
+        ("reveal", t("Reveal in Finder")),
+        ("trash", t("Move to Trash")),
+    ]
+}
+
+/// Run a recent file action on the item it was offered for.
+///
+/// Trashing also drops the file from the listings right away, without
+/// waiting for the watcher, and records how to undo it.
+pub fn run_recent_action(
+    action_id: &str,
+    item: &Item,
+    recents: &Recents,
+    undo: &UndoStack,
+) -> PluginResult<()> {
+    let path = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("path")?.as_str())
+        .ok_or_else(|| PluginError::Command("Item is not a recent file".to_string()))?;
+
+    let desktop = desktop::current();
+    match action_id {
+        "open" => desktop.open(path),
+        "reveal" => desktop.reveal(path),
+        "trash" => {
+            trash_file(undo, path)?;
+            let mut listings = recents.listings.lock();
+            listings
+                .documents
+                .retain(|file| file.path != Path::new(path));
+            listings
+                .downloads
+                .retain(|file| file.path != Path::new(path));
+            Ok(())
+        }
+        _ => Err(PluginError::Command(format!(
+            "Unknown recent file action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recent_query() {
+        assert_eq!(
+            parse_recent_query("recent"),
+            Some((Listing::Documents, String::new()))
+        );
+        assert_eq!(
+            parse_recent_query("rec Budget"),
+            Some((Listing::Documents, "budget".to_string()))
+        );
+        assert_eq!(
+            parse_recent_query("Downloads .dmg"),
+            Some((Listing::Downloads, ".dmg".to_string()))
+        );
+        assert_eq!(parse_recent_query("re"), None);
+        assert_eq!(parse_recent_query("recently"), None);
+        assert_eq!(
+            parse_recent_query("download"),
+            Some((Listing::Downloads, String::new()))
+        );
+    }
+
+    #[test]
+    fn test_list_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["old.zip", "new.pdf", ".DS_Store", "big.iso.crdownload"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+        let set_modified = |name: &str, secs: u64| {
+            let file = std::fs::File::options()
+                .write(true)
+                .open(dir.path().join(name))
+                .unwrap();
+            file.set_modified(UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+        set_modified("old.zip", 1_000);
+        set_modified("new.pdf", 2_000);
+
+        let files = list_folder(dir.path());
+        assert_eq!(
+            files,
+            vec![
+                RecentFile {
+                    path: dir.path().join("new.pdf"),
+                    date: 2_000
+                },
+                RecentFile {
+                    path: dir.path().join("old.zip"),
+                    date: 1_000
+                },
+            ]
+        );
+        assert_eq!(filter_files(&files, "zip").count(), 1);
+        assert!(list_folder(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_parse_recent_documents() {
+        let files = parse_last_used(
+            &["/a.pdf", "/b.txt", "/c.key"],
+            "2026-10-16 09:30:00 +0000\0(null)\x002026-10-15 09:30:00 +0200",
+        );
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, PathBuf::from("/a.pdf"));
+        assert_eq!(files[0].date, 1_792_143_000);
+        assert_eq!(files[1].date, 1_792_049_400);
+
+        let xbel = r#"<?xml version="1.0"?>
+<xbel version="1.0">
+  <bookmark href="file:///home/me/My%20Notes+v2.txt" added="2026-10-01T10:00:00Z" modified="2026-10-02T10:00:00Z" visited="2026-10-16T09:30:00Z">
+  </bookmark>
+  <bookmark href="https://example.com" visited="2026-10-16T09:30:00Z"/>
+</xbel>"#;
+        assert_eq!(
+            parse_xbel(xbel),
+            vec![RecentFile {
+                path: PathBuf::from("/home/me/My Notes+v2.txt"),
+                date: 1_792_143_000
+            }]
+        );
+    }
+
+    #[test]
+    fn test_group_and_age() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("report.pdf"), b"").unwrap();
+        let recents = Recents::new(dir.path(), Arc::new(watch::channel(0).0));
+        recents.set_downloads_folder(dir.path());
+
+        let now = unix_time(SystemTime::now());
+        let group = recents.group("downloads rep", now).unwrap();
+        assert_eq!(group.title.as_deref(), Some("Downloads"));
+        assert_eq!(group.items[0].title, "report.pdf");
+        assert_eq!(group.items[0].accessories, vec!["Just now".to_string()]);
+        assert!(recents.group("downloads xyz", now).is_none());
+        assert!(recents.group("weather", now).is_none());
+
+        assert_eq!(format_age(0, 150), "2 min ago");
+        assert_eq!(format_age(0, 7_200), "2 h ago");
+        assert_eq!(format_age(0, 90_000), "Yesterday");
+        assert_eq!(format_age(0, 3 * 86_400), "3 days ago");
+    }
+}
@@ -1,14 +1,62 @@
 //! Plugin Registry
 //!
-//! Stores the root view and provides registries for views, hooks, and keybindings.
+//! Stores the root view and provides registries for views, hooks, keybindings, web search
+//! engines, timers, profiles, theme overrides, sound and mouse settings, macros, remote control,
+//! SSH, and editor settings, and plugin daemons, plus the shared now-playing cache, dictionary
+//! cache, color history, Spotlight search, and git repository index, world clock, screen
+//! captures, recent files, HTTP request history, WebSocket connections, OAuth sign-ins, undo
+//! stack, query rewriting pipeline, pinned results, hidden results, collapsed groups, result
+//! ordering, query routes, frontmost-app rules, the fuzzy matcher, the basket, the Lua memory
+//! monitor, local metrics, the checks recorded for `lux doctor`, restricted mode for third-party
+//! plugins, the config warnings, the activity log, and the lock of a synced data folder.
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 
+use crate::activity::ActivityLog;
+use crate::audio::SoundStore;
+use crate::backup::SyncLock;
+use crate::basket::Basket;
+use crate::capture::Captures;
+use crate::collapsed::CollapsedGroups;
+use crate::colors::ColorHistory;
+use crate::config_files::EditorSettings;
+use crate::currency::CurrencyConverter;
+use crate::daemons::Daemons;
+use crate::dictionary::DictionaryCache;
+use crate::doctor::Diagnostics;
+use crate::git::GitIndex;
+use crate::hidden::Blocklist;
 use crate::hooks::HookRegistry;
+use crate::http::HttpClient;
 use crate::keymap::KeymapRegistry;
+use crate::macros::Macros;
+use crate::matching::Matcher;
+use crate::media::NowPlayingCache;
+use crate::memory::MemoryMonitor;
+use crate::metrics::Metrics;
+use crate::mouse::MouseStore;
+use crate::oauth::OAuthFlows;
+use crate::ordering::ResultOrdering;
+use crate::pins::Pins;
+use crate::profiles::ProfileRegistry;
+use crate::recent::Recents;
+use crate::rewrite::QueryRewriter;
+use crate::routes::RouteTable;
+use crate::rules::AppRules;
+use crate::security::SecuritySettings;
+use crate::server::ServerSettings;
+use crate::spotlight::SpotlightSearch;
+use crate::ssh::SshSettings;
+use crate::theme::ThemeStore;
+use crate::timers::TimerRegistry;
 use crate::types::View;
+use crate::undo::UndoStack;
 use crate::views::ViewRegistry;
+use crate::warnings::ConfigWarnings;
+use crate::websearch::WebSearchRegistry;
+use crate::websocket::WebSockets;
+use crate::worldclock::WorldClock;
 
 /// The plugin registry stores the root view and sub-registries.
 pub struct PluginRegistry {
@@ -23,17 +71,228 @@ pub struct PluginRegistry {
 
     /// Hook registry for the new API (lux.hook).
     hook_registry: Arc<HookRegistry>,
+
+    /// Web search engines (lux.websearch).
+    websearch: Arc<WebSearchRegistry>,
+
+    /// Now-playing state shown in the root view.
+    media: Arc<NowPlayingCache>,
+
+    /// Background Spotlight search shown in the root view.
+    spotlight: Arc<SpotlightSearch>,
+
+    /// Recent `define` lookups shown in the root view.
+    dictionary: Arc<DictionaryCache>,
+
+    /// Recently copied colors shown in the root view.
+    colors: Arc<ColorHistory>,
+
+    /// Exchange rates for currency conversions in the root view (lux.currency).
+    currency: Arc<CurrencyConverter>,
+
+    /// Git repositories shown in the root view (lux.git).
+    git: Arc<GitIndex>,
+
+    /// Zone search and clocks pinned to the root view (lux.worldclock).
+    worldclock: Arc<WorldClock>,
+
+    /// Screenshot folder and the running screen recording (lux.capture).
+    captures: Arc<Captures>,
+
+    /// Recent documents and downloads shown in the root view (lux.recent).
+    recents: Arc<Recents>,
+
+    /// Requests sent from the request builder (lux.http).
+    http: Arc<HttpClient>,
+
+    /// WebSocket connections opened by plugins (lux.ws).
+    websockets: Arc<WebSockets>,
+
+    /// OAuth sign-ins in progress (lux.oauth).
+    oauth: Arc<OAuthFlows>,
+
+    /// Running timers (lux.timers).
+    timers: Arc<TimerRegistry>,
+
+    /// Profiles and the active profile (lux.profile).
+    profiles: Arc<ProfileRegistry>,
+
+    /// Theme overrides (lux.theme).
+    theme: Arc<ThemeStore>,
+    /// Sound effects and haptics (lux.ui.sounds).
+    sounds: Arc<SoundStore>,
+    /// Mouse behavior of result rows (lux.ui.mouse).
+    mouse: Arc<MouseStore>,
+    /// Macro recording and the saved macros (lux.macros).
+    macros: Arc<Macros>,
+
+    /// Remote control server settings (lux.server).
+    server: Arc<ServerSettings>,
+
+    /// SSH settings (lux.ssh).
+    ssh: Arc<SshSettings>,
+
+    /// The editor config files open in (set in config.toml).
+    editor: Arc<EditorSettings>,
+
+    /// Helper processes started by plugins (lux.daemon).
+    daemons: Arc<Daemons>,
+
+    /// Undoable file operations and plugin actions (lux.undo).
+    undo: Arc<UndoStack>,
+
+    /// Query normalization, aliases, and middlewares (lux.query).
+    query_rewriter: Arc<QueryRewriter>,
+
+    /// Results pinned to the top of the root view (lux.pins).
+    pins: Arc<Pins>,
+
+    /// Hidden results and turned-off sources and views (lux.builtin.hidden).
+    blocklist: Arc<Blocklist>,
+
+    /// Groups the user collapsed, per view.
+    collapsed_groups: Arc<CollapsedGroups>,
+
+    /// Group order and weights (lux.results).
+    ordering: Arc<ResultOrdering>,
+
+    /// Query routing rules (lux.routes).
+    routes: Arc<RouteTable>,
+
+    /// Views to open on per frontmost app (lux.rules).
+    app_rules: Arc<AppRules>,
+
+    /// Fuzzy matcher and its romanization table (lux.matching).
+    matcher: Arc<Matcher>,
+
+    /// Items collected for bulk actions (lux.basket).
+    basket: Arc<Basket>,
+
+    /// Lua memory used per view, and GC settings (lux.runtime).
+    memory: Arc<MemoryMonitor>,
+
+    /// Opt-in local usage metrics (lux.metrics).
+    metrics: Arc<Metrics>,
+
+    /// Checks the host recorded while starting up (`lux doctor`).
+    diagnostics: Arc<Diagnostics>,
+
+    /// Restricted mode and the loaded third-party plugins (lux.security, lux.plugins).
+    security: Arc<SecuritySettings>,
+
+    /// Unknown and deprecated fields found while init.lua ran.
+    config_warnings: Arc<ConfigWarnings>,
+
+    /// Actions executed lately (lux.builtin.activity).
+    activity: Arc<ActivityLog>,
+
+    /// Lock of the synced data folder, held until shutdown.
+    sync_lock: Mutex<Option<SyncLock>>,
 }
 
 impl PluginRegistry {
     /// Create a new empty registry.
     pub fn new() -> Self {
+        // New downloads and HTTP responses refresh the results through the
+        // Spotlight channel
+        let spotlight = Arc::new(SpotlightSearch::new());
+        let recents = Arc::new(Recents::new(
+            Recents::default_downloads_folder(),
+            spotlight.updates(),
+        ));
+        let http = Arc::new(HttpClient::new(
+            HttpClient::default_path(),
+            spotlight.updates(),
+        ));
         Self {
             root_view: RwLock::new(None),
             keymap: Arc::new(KeymapRegistry::new()),
             view_registry: Arc::new(ViewRegistry::new()),
             hook_registry: Arc::new(HookRegistry::new()),
+            websearch: Arc::new(WebSearchRegistry::new()),
+            media: Arc::new(NowPlayingCache::default()),
+            spotlight,
+            dictionary: Arc::new(DictionaryCache::new()),
+            colors: Arc::new(ColorHistory::default()),
+            currency: Arc::new(CurrencyConverter::default()),
+            git: Arc::new(GitIndex::new()),
+            worldclock: Arc::new(WorldClock::default()),
+            captures: Arc::new(Captures::default()),
+            recents,
+            http,
+            websockets: Arc::new(WebSockets::new()),
+            oauth: Arc::new(OAuthFlows::new()),
+            timers: Arc::new(TimerRegistry::new()),
+            profiles: Arc::new(ProfileRegistry::new()),
+            theme: Arc::new(ThemeStore::new().with_path(ThemeStore::default_path())),
+            sounds: Arc::new(SoundStore::new()),
+            mouse: Arc::new(MouseStore::new()),
+            macros: Arc::new(Macros::default()),
+            server: Arc::new(ServerSettings::new()),
+            ssh: Arc::new(SshSettings::new()),
+            editor: Arc::new(EditorSettings::new().with_path(EditorSettings::default_path())),
+            daemons: Arc::new(Daemons::default()),
+            undo: Arc::new(UndoStack::new()),
+            query_rewriter: Arc::new(QueryRewriter::new()),
+            pins: Arc::new(Pins::default()),
+            blocklist: Arc::new(Blocklist::default()),
+            collapsed_groups: Arc::new(CollapsedGroups::default()),
+            ordering: Arc::new(ResultOrdering::new()),
+            routes: Arc::new(RouteTable::new()),
+            app_rules: Arc::new(AppRules::new()),
+            matcher: Arc::new(Matcher::new().with_builtin_table()),
+            basket: Arc::new(Basket::new()),
+            memory: Arc::new(MemoryMonitor::new()),
+            metrics: Arc::new(Metrics::default()),
+            diagnostics: Arc::new(Diagnostics::new()),
+            security: Arc::new(SecuritySettings::new()),
+            config_warnings: Arc::new(ConfigWarnings::new()),
+            activity: Arc::new(ActivityLog::default()),
+            sync_lock: Mutex::new(None),
+        }
+    }
+
+    /// Stop background work and write out unsaved state before Lux quits.
+    ///
+    /// Stops the Spotlight search, a running screen recording, and plugin
+    /// daemons, closes WebSocket connections, cancels timers and OAuth
+    /// sign-ins, flushes metrics, and releases the lock of a synced data
+    /// folder. Other stores save on every change.
+    pub fn shutdown(&self) {
+        self.spotlight.cancel();
+        self.daemons.stop_all();
+        self.websockets.close_all();
+        self.oauth.cancel_all();
+        if self.captures.recording_since().is_some() {
+            if let Err(e) = self.captures.stop_recording() {
+                tracing::warn!("Failed to stop screen recording: {}", e);
+            }
         }
+        for timer in self.timers.list() {
+            self.timers.cancel(timer.id);
+        }
+        self.metrics.flush();
+        self.sync_lock.lock().take();
+    }
+
+    /// Drop cached results (now playing, dictionary lookups), and fetch
+    /// exchange rates and index Git repositories again in the background.
+    ///
+    /// Rates and repositories are only fetched if they are in use.
+    pub fn clear_caches(&self) {
+        self.media.invalidate();
+        self.dictionary.clear();
+        if self.currency.rates().is_some() {
+            self.currency.refresh();
+        }
+        if !self.git.roots().is_empty() {
+            self.git.refresh();
+        }
+    }
+
+    /// Hold the lock of the synced data folder until shutdown.
+    pub fn hold_sync_lock(&self, lock: SyncLock) {
+        *self.sync_lock.lock() = Some(lock);
     }
 
     /// Get the keymap registry (shared Arc).
@@ -51,6 +310,201 @@ impl PluginRegistry {
         self.hook_registry.clone()
     }
 
+    /// Get the web search registry (shared Arc).
+    pub fn websearch(&self) -> Arc<WebSearchRegistry> {
+        self.websearch.clone()
+    }
+
+    /// Get the now-playing cache (shared Arc).
+    pub fn media(&self) -> Arc<NowPlayingCache> {
+        self.media.clone()
+    }
+
+    /// Get the Spotlight search (shared Arc).
+    pub fn spotlight(&self) -> Arc<SpotlightSearch> {
+        self.spotlight.clone()
+    }
+
+    /// Get the dictionary cache (shared Arc).
+    pub fn dictionary(&self) -> Arc<DictionaryCache> {
+        self.dictionary.clone()
+    }
+
+    /// Get the color history (shared Arc).
+    pub fn colors(&self) -> Arc<ColorHistory> {
+        self.colors.clone()
+    }
+
+    /// Get the currency converter (shared Arc).
+    pub fn currency(&self) -> Arc<CurrencyConverter> {
+        self.currency.clone()
+    }
+
+    /// Get the git repository index (shared Arc).
+    pub fn git(&self) -> Arc<GitIndex> {
+        self.git.clone()
+    }
+
+    /// Get the world clock (shared Arc).
+    pub fn worldclock(&self) -> Arc<WorldClock> {
+        self.worldclock.clone()
+    }
+
+    /// Get the screen captures (shared Arc).
+    pub fn captures(&self) -> Arc<Captures> {
+        self.captures.clone()
+    }
+
+    /// Get the undo stack (shared Arc).
+    pub fn undo(&self) -> Arc<UndoStack> {
+        self.undo.clone()
+    }
+
+    /// Get the query rewriting pipeline (shared Arc).
+    pub fn query_rewriter(&self) -> Arc<QueryRewriter> {
+        self.query_rewriter.clone()
+    }
+
+    /// Get the pinned results (shared Arc).
+    pub fn pins(&self) -> Arc<Pins> {
+        self.pins.clone()
+    }
+
+    /// Get the blocklist of hidden results (shared Arc).
+    pub fn blocklist(&self) -> Arc<Blocklist> {
+        self.blocklist.clone()
+    }
+
+    /// Get the collapsed result groups (shared Arc).
+    pub fn collapsed_groups(&self) -> Arc<CollapsedGroups> {
+        self.collapsed_groups.clone()
+    }
+
+    /// Get the result ordering rules (shared Arc).
+    pub fn ordering(&self) -> Arc<ResultOrdering> {
+        self.ordering.clone()
+    }
+
+    /// Get the query routing rules (shared Arc).
+    pub fn routes(&self) -> Arc<RouteTable> {
+        self.routes.clone()
+    }
+
+    /// Get the frontmost-app rules (shared Arc).
+    pub fn app_rules(&self) -> Arc<AppRules> {
+        self.app_rules.clone()
+    }
+
+    /// Get the fuzzy matcher (shared Arc).
+    pub fn matcher(&self) -> Arc<Matcher> {
+        self.matcher.clone()
+    }
+
+    /// Get the basket (shared Arc).
+    pub fn basket(&self) -> Arc<Basket> {
+        self.basket.clone()
+    }
+
+    /// Get the Lua memory monitor (shared Arc).
+    pub fn memory(&self) -> Arc<MemoryMonitor> {
+        self.memory.clone()
+    }
+
+    /// Get the local usage metrics (shared Arc).
+    pub fn metrics(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
+    /// Get the checks recorded for `lux doctor` (shared Arc).
+    pub fn diagnostics(&self) -> Arc<Diagnostics> {
+        self.diagnostics.clone()
+    }
+
+    /// Get the restricted mode settings (shared Arc).
+    pub fn security(&self) -> Arc<SecuritySettings> {
+        self.security.clone()
+    }
+
+    /// Get the config warnings (shared Arc).
+    pub fn config_warnings(&self) -> Arc<ConfigWarnings> {
+        self.config_warnings.clone()
+    }
+
+    /// Get the activity log (shared Arc).
+    pub fn activity(&self) -> Arc<ActivityLog> {
+        self.activity.clone()
+    }
+
+    /// Get the recent documents and downloads (shared Arc).
+    pub fn recents(&self) -> Arc<Recents> {
+        self.recents.clone()
+    }
+
+    /// Get the HTTP client (shared Arc).
+    pub fn http(&self) -> Arc<HttpClient> {
+        self.http.clone()
+    }
+
+    /// Get the WebSocket connections (shared Arc).
+    pub fn websockets(&self) -> Arc<WebSockets> {
+        self.websockets.clone()
+    }
+
+    /// Get the OAuth sign-ins in progress (shared Arc).
+    pub fn oauth(&self) -> Arc<OAuthFlows> {
+        self.oauth.clone()
+    }
+
+    /// Get the timer registry (shared Arc).
+    pub fn timers(&self) -> Arc<TimerRegistry> {
+        self.timers.clone()
+    }
+
+    /// Get the profile registry (shared Arc).
+    pub fn profiles(&self) -> Arc<ProfileRegistry> {
+        self.profiles.clone()
+    }
+
+    /// Get the theme overrides (shared Arc).
+    pub fn theme(&self) -> Arc<ThemeStore> {
+        self.theme.clone()
+    }
+
+    /// Get the sound settings (shared Arc).
+    pub fn sounds(&self) -> Arc<SoundStore> {
+        self.sounds.clone()
+    }
+
+    /// Get the mouse settings (shared Arc).
+    pub fn mouse(&self) -> Arc<MouseStore> {
+        self.mouse.clone()
+    }
+
+    /// Get the macro recorder (shared Arc).
+    pub fn macros(&self) -> Arc<Macros> {
+        self.macros.clone()
+    }
+
+    /// Get the remote control settings (shared Arc).
+    pub fn server(&self) -> Arc<ServerSettings> {
+        self.server.clone()
+    }
+
+    /// Get the SSH settings (shared Arc).
+    pub fn ssh(&self) -> Arc<SshSettings> {
+        self.ssh.clone()
+    }
+
+    /// Get the editor settings (shared Arc).
+    pub fn editor(&self) -> Arc<EditorSettings> {
+        self.editor.clone()
+    }
+
+    /// Get the plugin daemons (shared Arc).
+    pub fn daemons(&self) -> Arc<Daemons> {
+        self.daemons.clone()
+    }
+
     /// Set a custom root view.
     pub fn set_root_view(&self, view: View) {
         let mut root = self.root_view.write();
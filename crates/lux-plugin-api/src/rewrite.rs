@@ -0,0 +1,298 @@
+//! Query rewriting before sources run.
+//!
+//! This module provides:
+//! - `QueryRewriter` - The pipeline the engine runs on every query
+//! - `Normalize` - Built-in normalization (trim, lowercase, collapse spaces)
+//! - `RewrittenQuery` - The rewritten query, plus the query as typed
+//!
+//! ## Pipeline
+//!
+//! 1. Normalization, as configured with `lux.query.normalize`
+//! 2. Aliases: whole words replaced with `lux.query.aliases` (synonyms, typo fixes)
+//! 3. Middlewares in registration order, Rust and Lua (`lux.query.use`)
+//!
+//! Sources and the root view's built-in results see the rewritten query. The
+//! search input keeps what the user typed, and Lua sources can read it as
+//! `ctx.original_query`.
+//!
+//! ## Error Isolation
+//!
+//! A Lua middleware that throws is logged and skipped; the pipeline
+//! continues with the query as it was before that middleware.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use mlua::{Function, Lua};
+use parking_lot::RwLock;
+
+use crate::keymap::generate_handler_id;
+use crate::types::LuaFunctionRef;
+
+/// A Rust middleware: `(query, view_id) -> rewritten query`, or `None` to keep it.
+pub type RustMiddleware = Arc<dyn Fn(&str, Option<&str>) -> Option<String> + Send + Sync>;
+
+/// Built-in normalization, all off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalize {
+    /// Strip leading and trailing whitespace.
+    pub trim: bool,
+    /// Lowercase the query.
+    pub lowercase: bool,
+    /// Replace runs of whitespace with a single space.
+    pub collapse_spaces: bool,
+}
+
+impl Normalize {
+    /// Apply the enabled steps to `query`.
+    pub fn apply(&self, query: &str) -> String {
+        let mut query = if self.collapse_spaces {
+            collapse_spaces(query)
+        } else {
+            query.to_string()
+        };
+        if self.trim {
+            query = query.trim().to_string();
+        }
+        if self.lowercase {
+            query = query.to_lowercase();
+        }
+        query
+    }
+}
+
+/// A query after the pipeline ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewrittenQuery {
+    /// The query as typed.
+    pub original: String,
+    /// The query sources run with.
+    pub query: String,
+}
+
+impl RewrittenQuery {
+    /// Whether the pipeline changed the query.
+    pub fn is_rewritten(&self) -> bool {
+        self.original != self.query
+    }
+}
+
+#[derive(Clone)]
+enum Middleware {
+    Rust(RustMiddleware),
+    Lua(LuaFunctionRef),
+}
+
+/// Query rewriting pipeline.
+pub struct QueryRewriter {
+    normalize: RwLock<Normalize>,
+    /// Lowercased word -> replacement.
+    aliases: RwLock<HashMap<String, String>>,
+    /// Middlewares by ID, in registration order.
+    middlewares: RwLock<Vec<(String, Middleware)>>,
+}
+
+impl QueryRewriter {
+    /// Create a pipeline that leaves queries unchanged.
+    pub fn new() -> Self {
+        Self {
+            normalize: RwLock::new(Normalize::default()),
+            aliases: RwLock::new(HashMap::new()),
+            middlewares: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Replace the normalization settings.
+    pub fn set_normalize(&self, normalize: Normalize) {
+        *self.normalize.write() = normalize;
+    }
+
+    /// Current normalization settings.
+    pub fn normalize(&self) -> Normalize {
+        *self.normalize.read()
+    }
+
+    /// Replace the whole word `word` (matched case-insensitively) with `replacement`.
+    ///
+    /// An empty replacement removes the alias.
+    pub fn set_alias(&self, word: &str, replacement: &str) {
+        let word = word.trim().to_lowercase();
+        if word.is_empty() {
+            return;
+        }
+        let mut aliases = self.aliases.write();
+        if replacement.is_empty() {
+            aliases.remove(&word);
+        } else {
+            aliases.insert(word, replacement.to_string());
+        }
+    }
+
+    /// Add a Rust middleware. Returns its ID for `remove`.
+    pub fn add(&self, middleware: RustMiddleware) -> String {
+        let id = format!("query:{}", generate_handler_id());
+        self.middlewares
+            .write()
+            .push((id.clone(), Middleware::Rust(middleware)));
+        id
+    }
+
+    /// Add a Lua middleware. Its ID is the registry key of the function.
+    pub fn add_lua(&self, func: LuaFunctionRef) -> String {
+        let id = func.key.clone();
+        self.middlewares
+            .write()
+            .push((id.clone(), Middleware::Lua(func)));
+        id
+    }
+
+    /// Remove a middleware by ID.
+    ///
+    /// Returns true if it was found. The caller frees a Lua middleware's function.
+    pub fn remove(&self, id: &str) -> bool {
+        let mut middlewares = self.middlewares.write();
+        let before = middlewares.len();
+        middlewares.retain(|(mid, _)| mid != id);
+        middlewares.len() != before
+    }
+
+    /// Run the pipeline on `query` typed in the view `view_id`.
+    pub fn rewrite(&self, lua: &Lua, query: &str, view_id: Option<&str>) -> RewrittenQuery {
+        let mut rewritten = self.normalize().apply(query);
+        rewritten = self.expand_aliases(&rewritten);
+
+        // Clone the functions out so a middleware can register another one
+        let middlewares: Vec<Middleware> = self
+            .middlewares
+            .read()
+            .iter()
+            .map(|(_, m)| m.clone())
+            .collect();
+        for middleware in middlewares {
+            let result = match &middleware {
+                Middleware::Rust(f) => Ok(f(&rewritten, view_id)),
+                Middleware::Lua(f) => lua
+                    .named_registry_value::<Function>(&f.key)
+                    .and_then(|func| func.call::<Option<String>>((rewritten.as_str(), view_id))),
+            };
+            match result {
+                Ok(Some(next)) => rewritten = next,
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Query middleware failed: {}", e),
+            }
+        }
+
+        RewrittenQuery {
+            original: query.to_string(),
+            query: rewritten,
+        }
+    }
+
+    /// Replace aliased words, keeping the spacing between words.
+    fn expand_aliases(&self, query: &str) -> String {
+        let aliases = self.aliases.read();
+        if aliases.is_empty() {
+            return query.to_string();
+        }
+        query
+            .split(' ')
+            .map(|word| {
+                aliases
+                    .get(&word.to_lowercase())
+                    .map(String::as_str)
+                    .unwrap_or(word)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Default for QueryRewriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replace runs of whitespace with a single space, keeping a leading or trailing one.
+fn collapse_spaces(query: &str) -> String {
+    let mut out = String::with_capacity(query.len());
+    let mut in_space = false;
+    for c in query.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+        } else {
+            out.push(c);
+            in_space = false;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_aliases() {
+        let lua = Lua::new();
+        let rewriter = QueryRewriter::new();
+
+        // Unchanged by default
+        let result = rewriter.rewrite(&lua, "  Open  FF ", None);
+        assert!(!result.is_rewritten());
+
+        rewriter.set_normalize(Normalize {
+            trim: true,
+            lowercase: true,
+            collapse_spaces: true,
+        });
+        rewriter.set_alias("FF", "firefox");
+        rewriter.set_alias("teh", "the");
+        let result = rewriter.rewrite(&lua, "  Open  FF teh\tdocs ", None);
+        assert_eq!(result.query, "open firefox the docs");
+        assert_eq!(result.original, "  Open  FF teh\tdocs ");
+
+        // Aliases match whole words only, and an empty replacement removes one
+        rewriter.set_alias("teh", "");
+        assert_eq!(
+            rewriter.rewrite(&lua, "ffmpeg teh", None).query,
+            "ffmpeg teh"
+        );
+    }
+
+    #[test]
+    fn test_middlewares_run_in_order() {
+        let lua = Lua::new();
+        let rewriter = QueryRewriter::new();
+        rewriter.set_alias("gh", "github");
+
+        let rust_id = rewriter.add(Arc::new(|query, view_id| {
+            (view_id == Some("files")).then(|| format!("{} files", query))
+        }));
+
+        let func: Function = lua
+            .load(r#"function(query, view_id) return query .. "!" end"#)
+            .eval()
+            .unwrap();
+        let failing: Function = lua.load(r#"function() error("boom") end"#).eval().unwrap();
+        let lua_id = rewriter
+            .add_lua(LuaFunctionRef::from_function(&lua, func, "query:test".to_string()).unwrap());
+        rewriter.add_lua(
+            LuaFunctionRef::from_function(&lua, failing, "query:failing".to_string()).unwrap(),
+        );
+
+        assert_eq!(
+            rewriter.rewrite(&lua, "gh", Some("files")).query,
+            "github files!"
+        );
+        assert_eq!(rewriter.rewrite(&lua, "gh", None).query, "github!");
+
+        assert!(rewriter.remove(&rust_id));
+        assert!(!rewriter.remove(&rust_id));
+        assert!(rewriter.remove(&lua_id));
+        assert_eq!(rewriter.rewrite(&lua, "gh", Some("files")).query, "github");
+    }
+}
@@ -0,0 +1,197 @@
+//! Query routing rules.
+//!
+//! This module provides:
+//! - `Route` - A Lua pattern and the view that claims queries matching it
+//! - `RouteTable` - Routes in registration order (lux.routes)
+//!
+//! At the root view, the engine checks the rewritten query against the routes
+//! before running the root source. The first route whose pattern matches (and
+//! whose view is registered and turned on) claims the query: its view's search
+//! runs instead of the root source and the root view's built-in results, and
+//! its `get_actions` supplies the actions. An empty query is never routed.
+//!
+//! Patterns are Lua patterns, matched with the stdlib `string.find` captured
+//! by `capture_string_find` before any plugin runs, so a plugin that replaces
+//! `string.find` cannot change how queries are routed:
+//!
+//! ```lua
+//! lux.routes.add({ pattern = "^%d+[%+%-/%*]", view = "calculator" })
+//! lux.routes.add({ pattern = "^https?://", view = "bookmarks" })
+//! lux.routes.add({ pattern = "^[/~]", view = "file_browser" })
+//! ```
+
+use mlua::{Function, Lua, Table};
+use parking_lot::RwLock;
+
+use crate::keymap::generate_handler_id;
+
+/// Registry key of the captured stdlib `string.find`.
+const STRING_FIND_KEY: &str = "lux.routes.string_find";
+
+/// A query routing rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    /// ID for `RouteTable::remove`.
+    pub id: String,
+    /// Lua pattern the query must match.
+    pub pattern: String,
+    /// ID of the view that claims matching queries.
+    pub view: String,
+}
+
+/// Query routing rules, checked in registration order.
+pub struct RouteTable {
+    routes: RwLock<Vec<Route>>,
+}
+
+impl RouteTable {
+    /// Create an empty route table.
+    pub fn new() -> Self {
+        Self {
+            routes: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Add a route. Returns its ID, or an error if `pattern` is not a valid
+    /// Lua pattern.
+    pub fn add(&self, lua: &Lua, pattern: &str, view: &str) -> mlua::Result<String> {
+        // Malformed patterns only fail when matched, so try one now
+        string_find(lua)?.call::<Option<i64>>(("", pattern))?;
+
+        let id = format!("route:{}", generate_handler_id());
+        self.routes.write().push(Route {
+            id: id.clone(),
+            pattern: pattern.to_string(),
+            view: view.to_string(),
+        });
+        Ok(id)
+    }
+
+    /// Remove a route by ID. Returns true if it was found.
+    pub fn remove(&self, id: &str) -> bool {
+        let mut routes = self.routes.write();
+        let before = routes.len();
+        routes.retain(|route| route.id != id);
+        routes.len() != before
+    }
+
+    /// All routes, in the order they are checked.
+    pub fn list(&self) -> Vec<Route> {
+        self.routes.read().clone()
+    }
+
+    /// The view of the first route matching `query` for which `available`
+    /// returns true.
+    pub fn route(
+        &self,
+        lua: &Lua,
+        query: &str,
+        available: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        if query.trim().is_empty() {
+            return None;
+        }
+        let routes = self.list();
+        if routes.is_empty() {
+            return None;
+        }
+        let find = match string_find(lua) {
+            Ok(find) => find,
+            Err(e) => {
+                tracing::warn!("Query routing unavailable: {}", e);
+                return None;
+            }
+        };
+
+        routes
+            .into_iter()
+            .filter(|route| available(&route.view))
+            .find(|route| {
+                find.call::<Option<i64>>((query, route.pattern.as_str()))
+                    .inspect_err(|e| tracing::warn!("Route '{}' failed: {}", route.pattern, e))
+                    .is_ok_and(|start| start.is_some())
+            })
+            .map(|route| route.view)
+    }
+}
+
+impl Default for RouteTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keep the stdlib `string.find` for matching routes.
+///
+/// Call before any plugin code runs, while the global is untouched.
+pub fn capture_string_find(lua: &Lua) -> mlua::Result<()> {
+    let find: Function = lua.globals().get::<Table>("string")?.get("find")?;
+    lua.set_named_registry_value(STRING_FIND_KEY, find)
+}
+
+fn string_find(lua: &Lua) -> mlua::Result<Function> {
+    lua.named_registry_value::<Option<Function>>(STRING_FIND_KEY)?
+        .ok_or_else(|| mlua::Error::RuntimeError("string.find was not captured".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_matching_route_wins() {
+        let lua = Lua::new();
+        capture_string_find(&lua).unwrap();
+        let routes = RouteTable::new();
+        assert!(routes.add(&lua, "[%d", "broken").is_err());
+
+        routes.add(&lua, "^%d+[%+%-/%*]", "calculator").unwrap();
+        let url = routes.add(&lua, "^https?://", "bookmarks").unwrap();
+        routes.add(&lua, "^%a+://", "links").unwrap();
+
+        let all = |_: &str| true;
+        assert_eq!(
+            routes.route(&lua, "12*4", all).as_deref(),
+            Some("calculator")
+        );
+        assert_eq!(
+            routes.route(&lua, "https://lux.dev", all).as_deref(),
+            Some("bookmarks")
+        );
+        assert_eq!(routes.route(&lua, "safari", all), None);
+        assert_eq!(routes.route(&lua, "  ", all), None);
+
+        // Unavailable views are skipped
+        assert_eq!(
+            routes
+                .route(&lua, "https://lux.dev", |view| view != "bookmarks")
+                .as_deref(),
+            Some("links")
+        );
+
+        assert!(routes.remove(&url));
+        assert!(!routes.remove(&url));
+        assert_eq!(
+            routes.route(&lua, "https://lux.dev", all).as_deref(),
+            Some("links")
+        );
+    }
+
+    #[test]
+    fn test_replaced_string_find_is_ignored() {
+        let lua = Lua::new();
+        capture_string_find(&lua).unwrap();
+        let routes = RouteTable::new();
+        routes.add(&lua, "^https?://", "bookmarks").unwrap();
+
+        lua.load("string.find = function() return 1 end")
+            .exec()
+            .unwrap();
+        let all = |_: &str| true;
+        assert_eq!(routes.route(&lua, "safari", all), None);
+        assert_eq!(
+            routes.route(&lua, "https://lux.dev", all).as_deref(),
+            Some("bookmarks")
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Frontmost-app rules.
+//!
+//! This module provides:
+//! - `AppRule` - An app and the view the launcher opens on while it is frontmost
+//! - `AppRules` - Rules in registration order (lux.rules)
+//!
+//! Each time the launcher opens, the first rule whose app is frontmost (and
+//! whose view is registered and turned on) picks the view it opens on: the
+//! view stack goes back to the root and the rule's view is pushed, so Escape
+//! still returns to the root. With no matching rule the launcher opens as
+//! usual.
+//!
+//! ```lua
+//! lux.rules.on_app({ bundle_id = "com.apple.Safari", view = "builtin.tabs" })
+//! lux.rules.on_app({ bundle_id = "com.googlecode.iterm2", view = "git" })
+//! ```
+//!
+//! `bundle_id` also matches the app name ("Safari"), ignoring case, as
+//! profile conditions do.
+
+use parking_lot::RwLock;
+
+use crate::keymap::generate_handler_id;
+use crate::profiles::ProfileContext;
+
+/// A frontmost-app rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppRule {
+    /// ID for `AppRules::remove`.
+    pub id: String,
+    /// Bundle ID or name of the app.
+    pub bundle_id: String,
+    /// ID of the view to open on.
+    pub view: String,
+}
+
+impl AppRule {
+    /// Whether the rule's app is the frontmost one in `context`.
+    pub fn matches(&self, context: &ProfileContext) -> bool {
+        [&context.bundle_id, &context.app_name]
+            .into_iter()
+            .flatten()
+            .any(|app| app.eq_ignore_ascii_case(&self.bundle_id))
+    }
+}
+
+/// Frontmost-app rules, checked in registration order.
+pub struct AppRules {
+    rules: RwLock<Vec<AppRule>>,
+}
+
+impl AppRules {
+    /// Create an empty rule list.
+    pub fn new() -> Self {
+        Self {
+            rules: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Open on `view` while `bundle_id` is frontmost. Returns the rule's ID.
+    pub fn on_app(&self, bundle_id: &str, view: &str) -> String {
+        let id = format!("rule:{}", generate_handler_id());
+        self.rules.write().push(AppRule {
+            id: id.clone(),
+            bundle_id: bundle_id.to_string(),
+            view: view.to_string(),
+        });
+        id
+    }
+
+    /// Remove a rule by ID. Returns true if it was found.
+    pub fn remove(&self, id: &str) -> bool {
+        let mut rules = self.rules.write();
+        let before = rules.len();
+        rules.retain(|rule| rule.id != id);
+        rules.len() != before
+    }
+
+    /// All rules, in the order they are checked.
+    pub fn list(&self) -> Vec<AppRule> {
+        self.rules.read().clone()
+    }
+
+    /// The view of the first rule matching `context` for which `available`
+    /// returns true.
+    pub fn view_for(
+        &self,
+        context: &ProfileContext,
+        available: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        self.rules
+            .read()
+            .iter()
+            .filter(|rule| available(&rule.view))
+            .find(|rule| rule.matches(context))
+            .map(|rule| rule.view.clone())
+    }
+}
+
+impl Default for AppRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmost(app_name: &str, bundle_id: &str) -> ProfileContext {
+        ProfileContext {
+            app_name: Some(app_name.to_string()),
+            bundle_id: Some(bundle_id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        let rules = AppRules::new();
+        let safari = rules.on_app("com.apple.Safari", "builtin.tabs");
+        rules.on_app("safari", "bookmarks");
+        rules.on_app("iTerm2", "git");
+
+        let all = |_: &str| true;
+        let browser = frontmost("Safari", "com.apple.Safari");
+        assert_eq!(
+            rules.view_for(&browser, all).as_deref(),
+            Some("builtin.tabs")
+        );
+        assert_eq!(
+            rules
+                .view_for(&frontmost("iTerm2", "com.googlecode.iterm2"), all)
+                .as_deref(),
+            Some("git")
+        );
+        assert_eq!(rules.view_for(&ProfileContext::default(), all), None);
+
+        // Unavailable views are skipped
+        assert_eq!(
+            rules
+                .view_for(&browser, |view| view != "builtin.tabs")
+                .as_deref(),
+            Some("bookmarks")
+        );
+
+        assert!(rules.remove(&safari));
+        assert!(!rules.remove(&safari));
+        assert_eq!(rules.view_for(&browser, all).as_deref(), Some("bookmarks"));
+    }
+}
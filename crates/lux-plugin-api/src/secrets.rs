@@ -0,0 +1,276 @@
+//! Secrets (lux.secrets).
+//!
+//! This module provides:
+//! - `get()` / `set()` / `delete()` - Passwords and tokens in the Keychain (Secret Service on Linux)
+//! - `check_reserved()` - Reject the names Lux stores its own secrets under
+//! - `redact()` - Replace every secret handed out so far with `[redacted]`
+//! - `Redacted` - A writer that redacts what passes through it (for tracing output)
+//!
+//! Each value read or stored through this module is remembered for the rest
+//! of the session, so a plugin that logs a token by accident doesn't leak it
+//! into the terminal or a crash report. Values shorter than `MIN_REDACTED_LEN`
+//! aren't redacted; they would mangle unrelated log text.
+//!
+//! Values are written to the storing command's stdin rather than passed as
+//! arguments, where other processes could read them from `ps`. On macOS that
+//! is `security -i`, which reads `add-generic-password` from stdin with the
+//! value hex-encoded.
+
+use std::borrow::Cow;
+use std::io::Write;
+use std::process::{Command, ExitStatus, Stdio};
+
+use parking_lot::RwLock;
+
+use crate::error::{PluginError, PluginResult};
+
+/// Keychain service (Secret Service attribute) the secrets are stored under.
+const SERVICE: &str = "dev.lux.launcher";
+
+/// Prefixes of the secrets Lux stores itself (OAuth tokens and client
+/// secrets), which `lux.secrets` can't read or change.
+const RESERVED_PREFIXES: &[&str] = &["oauth."];
+
+/// Names of the other secrets Lux stores itself (the data file key).
+const RESERVED_NAMES: &[&str] = &[crate::storage::KEY_SECRET];
+
+/// Shortest value that is redacted.
+pub const MIN_REDACTED_LEN: usize = 4;
+
+/// What redacted values are replaced with.
+pub const REDACTED: &str = "[redacted]";
+
+/// Secret values handed out this session, longest first.
+static SECRET_VALUES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// The secret stored as `name`, or None if there is none.
+pub fn get(name: &str) -> PluginResult<Option<String>> {
+    check_name(name)?;
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", SERVICE, "-a", name, "-w"])
+            .stderr(Stdio::null())
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", SERVICE, "account", name])
+            .stderr(Stdio::null())
+            .output()
+    }
+    .map_err(|e| PluginError::Command(format!("Secret lookup failed: {}", e)))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let value = String::from_utf8_lossy(&output.stdout);
+    // `security -w` ends the value with a newline; `secret-tool` doesn't
+    let value = value.strip_suffix('\n').unwrap_or(&value).to_string();
+    remember(&value);
+    Ok(Some(value))
+}
+
+/// Store `value` as `name`, replacing an older value.
+pub fn set(name: &str, value: &str) -> PluginResult<()> {
+    check_name(name)?;
+    remember(value);
+    let status = if cfg!(target_os = "macos") {
+        let command = format!(
+            "add-generic-password -U -s {} -a {} -X {}\n",
+            SERVICE,
+            quote(name),
+            hex(value)
+        );
+        run_with_input(Command::new("security").arg("-i"), &command)
+    } else {
+        let label = format!("Lux: {}", name);
+        run_with_input(
+            Command::new("secret-tool").args([
+                "store", "--label", &label, "service", SERVICE, "account", name,
+            ]),
+            value,
+        )
+    }
+    .map_err(|e| PluginError::Command(format!("Secret store failed: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PluginError::Command(format!(
+            "Secret store exited with {}",
+            status
+        )))
+    }
+}
+
+/// Remove the secret stored as `name`. Returns false if there was none.
+pub fn delete(name: &str) -> PluginResult<bool> {
+    check_name(name)?;
+    if get(name)?.is_none() {
+        return Ok(false);
+    }
+    let status = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["delete-generic-password", "-s", SERVICE, "-a", name])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+    } else {
+        Command::new("secret-tool")
+            .args(["clear", "service", SERVICE, "account", name])
+            .stderr(Stdio::null())
+            .status()
+    }
+    .map_err(|e| PluginError::Command(format!("Secret delete failed: {}", e)))?;
+    Ok(status.success())
+}
+
+fn check_name(name: &str) -> PluginResult<()> {
+    if name.trim().is_empty() {
+        return Err(PluginError::Command("Secret name is empty".to_string()));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(PluginError::Command(format!(
+            "Secret name '{}' contains control characters",
+            name.escape_debug()
+        )));
+    }
+    Ok(())
+}
+
+/// Error if Lux stores a secret of its own as `name`.
+///
+/// `lux.secrets` checks this; the modules that own those secrets don't.
+pub fn check_reserved(name: &str) -> PluginResult<()> {
+    if RESERVED_NAMES.contains(&name)
+        || RESERVED_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+    {
+        return Err(PluginError::Command(format!(
+            "Secret name '{}' is reserved by Lux",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Run `command` with `input` on its stdin.
+fn run_with_input(command: &mut Command, input: &str) -> std::io::Result<ExitStatus> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    child.wait()
+}
+
+/// `text` as one argument of a `security -i` command.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// `text` as hex, for `security add-generic-password -X`.
+fn hex(text: &str) -> String {
+    text.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+// =============================================================================
+// Redaction
+// =============================================================================
+
+/// Redact `value` from now on.
+pub fn remember(value: &str) {
+    if value.chars().count() < MIN_REDACTED_LEN {
+        return;
+    }
+    let mut values = SECRET_VALUES.write();
+    if values.iter().any(|v| v == value) {
+        return;
+    }
+    values.push(value.to_string());
+    // Longest first, so a secret containing another is redacted whole
+    values.sort_by_key(|v| std::cmp::Reverse(v.len()));
+}
+
+/// `text` with every remembered secret replaced by `REDACTED`.
+pub fn redact(text: &str) -> Cow<'_, str> {
+    let values = SECRET_VALUES.read();
+    let mut text = Cow::Borrowed(text);
+    for value in values.iter() {
+        if text.contains(value.as_str()) {
+            text = Cow::Owned(text.replace(value.as_str(), REDACTED));
+        }
+    }
+    text
+}
+
+/// A writer that redacts remembered secrets before passing text on.
+///
+/// Each `write` is redacted on its own; tracing writes a whole event at once.
+pub struct Redacted<W> {
+    inner: W,
+}
+
+impl<W: Write> Redacted<W> {
+    /// Redact what is written to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+}
+
+impl<W: Write> Write for Redacted<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => self.inner.write_all(redact(text).as_bytes())?,
+            Err(_) => self.inner.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_remembered_values() {
+        remember("ghp_token_abc");
+        remember("ghp_token_abc_long");
+        remember("abc");
+
+        assert_eq!(
+            redact("token=ghp_token_abc_long, short=abc"),
+            "token=[redacted], short=abc"
+        );
+        assert!(matches!(redact("nothing here"), Cow::Borrowed(_)));
+
+        let mut out = Vec::new();
+        Redacted::new(&mut out)
+            .write_all(b"Authorization: ghp_token_abc\n")
+            .unwrap();
+        assert_eq!(out, b"Authorization: [redacted]\n");
+    }
+
+    #[test]
+    fn test_security_arguments() {
+        assert_eq!(hex("ab\u{e9}"), "6162c3a9");
+        assert_eq!(quote(r#"my "gh" \ token"#), r#""my \"gh\" \\ token""#);
+        assert!(check_name("line\nbreak").is_err());
+    }
+
+    #[test]
+    fn test_reserved_names() {
+        assert!(check_reserved("oauth.github").is_err());
+        assert!(check_reserved(crate::storage::KEY_SECRET).is_err());
+        assert!(check_reserved("storage-key-2").is_ok());
+        assert!(check_reserved("github").is_ok());
+        assert!(check_reserved("plugin:weather:oauth.github").is_ok());
+    }
+}
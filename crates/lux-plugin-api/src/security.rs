@@ -0,0 +1,421 @@
+//! Restricted mode for third-party plugins.
+//!
+//! This module provides:
+//! - `Manifest` - A plugin folder's `manifest.json` (name and entry file)
+//! - `SecuritySettings` - Whether restricted mode is on, and which plugins are trusted
+//! - `LoadedPlugin` - A plugin loaded with `lux.plugins.load()`, and whether it was restricted
+//! - `ALLOWED_APIS` - The `lux` APIs an untrusted plugin can use
+//! - `restricted_env()` - The globals an untrusted plugin runs with
+//!
+//! Restricted mode is on by default. A plugin loaded from a folder runs with
+//! its own globals unless init.lua trusted it by name (`lux.security.trust`).
+//! Only the `lux` APIs in `ALLOWED_APIS` work; every other one (`lux.shell`,
+//! `lux.security`, `lux.plugins`, and APIs added later) raises an error when
+//! called, as do the writing `lux.fs` functions, `io`, `os.execute`/`remove`/`rename`,
+//! `require`, and `debug`. `lux.secrets` only reaches the secrets of the
+//! plugin's folder.
+//! Trust comes from init.lua rather than the manifest, since a plugin could
+//! claim anything about itself. Code in init.lua is never restricted, and
+//! turning restricted mode off trusts every plugin.
+//!
+//! This hides APIs from plugin code; it is not a process sandbox, and a
+//! plugin can still read files and use the APIs left to it.
+
+use std::collections::BTreeSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use mlua::{Lua, Result as LuaResult, Table};
+use parking_lot::RwLock;
+use serde::Deserialize;
+
+use crate::compat::{MIN_API_VERSION, SHIMS};
+use crate::error::{PluginError, PluginResult};
+
+/// Name of the manifest file in a plugin folder.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// `lux` APIs an untrusted plugin can use, besides the shims for them.
+///
+/// They show views and results, or read what the user could see anyway.
+/// APIs that run programs (`open` launches apps and scripts), reach the
+/// network, touch the clipboard, or change Lux itself stay out, and so do
+/// APIs added later until they are listed here.
+pub const ALLOWED_APIS: &[&str] = &[
+    "api_version",
+    "basket",
+    "calendar",
+    "currency",
+    "date",
+    "finder",
+    "fs",
+    "generate",
+    "hook",
+    "i18n",
+    "icon",
+    "item_id",
+    "keymap",
+    "map_items",
+    "matching",
+    "media",
+    "permissions",
+    "pins",
+    "qr",
+    "query",
+    "recent",
+    "reminders",
+    "results",
+    "reveal",
+    "routes",
+    "rules",
+    "secrets",
+    "set_root",
+    "text",
+    "theme",
+    "timers",
+    "ui",
+    "undo",
+    "views",
+    "websearch",
+    "worldclock",
+];
+
+/// Builds the environment of a restricted plugin (see `restricted_env`).
+const RESTRICTED_ENV: &str = r#"
+local name, folder, globals, lux, allowed = ...
+local load, loadfile, setmetatable, error, type = load, loadfile, setmetatable, error, type
+
+local function blocked(what)
+    return function()
+        error(what .. " is not available to plugin '" .. name .. "' in restricted mode", 2)
+    end
+end
+
+local function blocked_table(what)
+    return setmetatable({}, {
+        __index = function(_, key) return blocked(what .. "." .. key) end,
+        __call = blocked(what),
+        __metatable = false,
+    })
+end
+
+local function override(base, fields)
+    return setmetatable(fields, { __index = base, __metatable = false })
+end
+
+-- The plugin's secrets are stored as "plugin:<folder>:<key>", since its
+-- manifest name is whatever the plugin claims
+local secrets = lux.secrets
+local function scoped(key)
+    if type(key) ~= "string" then
+        error("secret name must be a string", 3)
+    end
+    return "plugin:" .. folder .. ":" .. key
+end
+
+local env = {}
+env.lux = setmetatable({
+    fs = override(lux.fs, {
+        write = blocked("lux.fs.write"),
+        trash = blocked("lux.fs.trash"),
+        move = blocked("lux.fs.move"),
+        rename = blocked("lux.fs.rename"),
+    }),
+    secrets = {
+        get = function(key) return secrets.get(scoped(key)) end,
+        set = function(key, value) return secrets.set(scoped(key), value) end,
+        delete = function(key) return secrets.delete(scoped(key)) end,
+    },
+}, {
+    __index = function(_, key)
+        if allowed[key] then return lux[key] end
+        return blocked_table("lux." .. key)
+    end,
+    __metatable = false,
+})
+env.os = override(globals.os, {
+    execute = blocked("os.execute"),
+    remove = blocked("os.remove"),
+    rename = blocked("os.rename"),
+    exit = blocked("os.exit"),
+})
+env.io = blocked_table("io")
+env.debug = blocked_table("debug")
+env.package = blocked_table("package")
+env.require = blocked("require")
+
+-- Chunks the plugin loads run with its globals too (and never as bytecode)
+env.load = function(chunk, chunkname, _, e)
+    return load(chunk, chunkname, "t", e or env)
+end
+env.loadfile = function(path, _, e)
+    return loadfile(path, "t", e or env)
+end
+env.dofile = function(path)
+    local fn, err = loadfile(path, "t", env)
+    if not fn then error(err, 2) end
+    return fn()
+end
+env._G = env
+
+return setmetatable(env, { __index = globals, __metatable = false })
+"#;
+
+/// A plugin folder's `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Manifest {
+    /// Name the plugin is trusted by (`lux.security.trust`).
+    pub name: String,
+    /// Entry file, relative to the folder.
+    #[serde(default = "default_main")]
+    pub main: String,
+    /// Shown in `lux.plugins.list()`.
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Lux API version the plugin targets (`lux.api_version`).
+    #[serde(default = "default_api_version")]
+    pub api_version: u32,
+}
+
+fn default_main() -> String {
+    "init.lua".to_string()
+}
+
+fn default_api_version() -> u32 {
+    MIN_API_VERSION
+}
+
+impl Manifest {
+    /// Read `manifest.json` in `dir`.
+    pub fn load(dir: &Path) -> PluginResult<Self> {
+        let path = dir.join(MANIFEST_FILE);
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| PluginError::Command(format!("Cannot read {}: {}", path.display(), e)))?;
+        Self::parse(&text)
+    }
+
+    /// Parse a manifest.
+    pub fn parse(text: &str) -> PluginResult<Self> {
+        let manifest: Manifest = serde_json::from_str(text)
+            .map_err(|e| PluginError::Command(format!("Invalid {}: {}", MANIFEST_FILE, e)))?;
+        if manifest.name.trim().is_empty() {
+            return Err(PluginError::Command(format!(
+                "{} has an empty name",
+                MANIFEST_FILE
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Path of the entry file in `dir`.
+    ///
+    /// Errors if `main` is absolute or leaves the folder.
+    pub fn entry(&self, dir: &Path) -> PluginResult<PathBuf> {
+        let main = Path::new(&self.main);
+        if !main
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(PluginError::Command(format!(
+                "Entry file '{}' of plugin '{}' is outside its folder",
+                self.main, self.name
+            )));
+        }
+        Ok(dir.join(main))
+    }
+}
+
+/// A plugin loaded with `lux.plugins.load()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedPlugin {
+    /// Name from the manifest.
+    pub name: String,
+    /// Version from the manifest.
+    pub version: Option<String>,
+    /// The plugin's folder.
+    pub path: PathBuf,
+    /// Whether the restricted APIs were blocked for it.
+    pub restricted: bool,
+    /// Lux API version it targets.
+    pub api_version: u32,
+}
+
+/// Restricted mode, the trusted plugins, and the plugins loaded so far.
+pub struct SecuritySettings {
+    restricted: AtomicBool,
+    trusted: RwLock<BTreeSet<String>>,
+    plugins: RwLock<Vec<LoadedPlugin>>,
+}
+
+impl Default for SecuritySettings {
+    fn default() -> Self {
+        Self {
+            restricted: AtomicBool::new(true),
+            trusted: RwLock::new(BTreeSet::new()),
+            plugins: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl SecuritySettings {
+    /// Create settings with restricted mode on and no trusted plugins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether restricted mode is on.
+    pub fn is_restricted(&self) -> bool {
+        self.restricted.load(Ordering::Relaxed)
+    }
+
+    /// Turn restricted mode on or off (for plugins loaded afterwards).
+    pub fn set_restricted(&self, restricted: bool) {
+        self.restricted.store(restricted, Ordering::Relaxed);
+    }
+
+    /// Trust the plugin named `name` with every API.
+    pub fn trust(&self, name: &str) {
+        self.trusted.write().insert(name.to_string());
+    }
+
+    /// Names of the trusted plugins, sorted.
+    pub fn trusted(&self) -> Vec<String> {
+        self.trusted.read().iter().cloned().collect()
+    }
+
+    /// Whether the plugin described by `manifest` runs restricted.
+    pub fn restricts(&self, manifest: &Manifest) -> bool {
+        self.is_restricted() && !self.trusted.read().contains(&manifest.name)
+    }
+
+    /// Record a loaded plugin, replacing an older load of the same folder.
+    pub fn record(&self, plugin: LoadedPlugin) {
+        let mut plugins = self.plugins.write();
+        plugins.retain(|p| p.path != plugin.path);
+        plugins.push(plugin);
+    }
+
+    /// The plugins loaded so far, in load order.
+    pub fn plugins(&self) -> Vec<LoadedPlugin> {
+        self.plugins.read().clone()
+    }
+}
+
+/// Globals for the untrusted plugin `name` in `folder`: the real globals, with
+/// `lux` (the plugin's, see `compat::plugin_lux`) and the restricted APIs
+/// replaced by functions that raise an error.
+pub fn restricted_env(lua: &Lua, name: &str, folder: &Path, lux: Table) -> LuaResult<Table> {
+    let allowed = lua.create_table()?;
+    for api in ALLOWED_APIS {
+        allowed.set(*api, true)?;
+    }
+    // A shim is allowed when what it calls now is
+    for shim in SHIMS {
+        let target = shim.replacement.split('.').next().unwrap_or_default();
+        if ALLOWED_APIS.contains(&target) {
+            allowed.set(shim.name, true)?;
+        }
+    }
+    let folder = folder.to_string_lossy();
+    let globals = lua.globals();
+    lua.load(RESTRICTED_ENV)
+        .set_name("=restricted_env")
+        .call((name, folder, globals, lux, allowed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest() {
+        let manifest = Manifest::parse(r#"{ "name": "weather", "version": "1.2" }"#).unwrap();
+        assert_eq!(manifest.main, "init.lua");
+        assert_eq!(manifest.api_version, MIN_API_VERSION);
+        assert_eq!(
+            manifest.entry(Path::new("/plugins/weather")).unwrap(),
+            PathBuf::from("/plugins/weather/init.lua")
+        );
+
+        let escaping = Manifest::parse(r#"{ "name": "x", "main": "../init.lua" }"#).unwrap();
+        assert!(escaping.entry(Path::new("/plugins/x")).is_err());
+        assert!(Manifest::parse(r#"{ "name": " " }"#).is_err());
+
+        let settings = SecuritySettings::new();
+        assert!(settings.restricts(&manifest));
+        settings.trust("weather");
+        assert!(!settings.restricts(&manifest));
+    }
+
+    #[test]
+    fn test_restricted_env_blocks_apis() {
+        let lua = Lua::new();
+        lua.load(
+            r#"
+            lux = {
+                shell = { run = function() return "ran" end },
+                fs = {
+                    read = function() return "contents" end,
+                    write = function() return true end,
+                },
+                secrets = { get = function(name) return name end },
+                security = { set_restricted = function() end },
+                plugins = { load = function() end },
+                alfred = { import = function() end },
+                set_root_view = function() return "root" end,
+                api_version = 2,
+            }
+            "#,
+        )
+        .exec()
+        .unwrap();
+
+        let lux: Table = lua.globals().get("lux").unwrap();
+        let env = restricted_env(&lua, "weather", Path::new("/plugins/weather"), lux).unwrap();
+        let run = |code: &str| lua.load(code).set_environment(env.clone()).eval::<String>();
+
+        assert_eq!(run("return lux.fs.read('x')").unwrap(), "contents");
+        assert_eq!(run("return tostring(lux.api_version)").unwrap(), "2");
+        assert_eq!(run("return lux.set_root_view()").unwrap(), "root");
+        assert_eq!(run("return string.upper('ok')").unwrap(), "OK");
+
+        // Secrets are scoped to the plugin's folder
+        assert_eq!(
+            run("return lux.secrets.get('token')").unwrap(),
+            "plugin:/plugins/weather:token"
+        );
+
+        for code in [
+            "return lux.shell.run('ls')",
+            "return lux.shell('ls')",
+            "return lux.daemon.start({ name = 'x', cmd = 'ls' })",
+            "return lux.ws.connect('wss://example.com')",
+            "return lux.oauth.token('github')",
+            "return lux.security.set_restricted(false)",
+            "return lux.plugins.load('~/plugins/other')",
+            "return lux.alfred.import('x')",
+            "return lux.fs.write('x', 'y')",
+            "return lux.open('/Applications/Terminal.app')",
+            "return lux.clipboard.paste()",
+            "return os.execute('ls')",
+            "return io.open('x')",
+            "return _G.lux.shell.run('ls')",
+            "return load('return lux.shell.run(\"ls\")')()",
+        ] {
+            let err = run(code).unwrap_err().to_string();
+            assert!(err.contains("restricted mode"), "{}: {}", code, err);
+        }
+
+        // The metatables that lead back to the real tables are hidden
+        let hidden: bool = lua
+            .load("return getmetatable(lux) == false and getmetatable(_ENV) == false")
+            .set_environment(env.clone())
+            .eval()
+            .unwrap();
+        assert!(hidden);
+
+        // The real globals are untouched
+        let ran: String = lua.load("return lux.shell.run('ls')").eval().unwrap();
+        assert_eq!(ran, "ran");
+    }
+}
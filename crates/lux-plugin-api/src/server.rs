@@ -0,0 +1,568 @@
+//! Remote control over a localhost HTTP/JSON endpoint.
+//!
+//! This module provides:
+//! - `ServerSettings` - Whether the server is enabled, its port and token (`lux.server`)
+//! - `RemoteCommand` - What a request asks the launcher to do
+//! - `RemoteServer` - The listener thread that forwards commands to the UI
+//!
+//! The server is off unless `lux.server.enable()` is called, only binds to
+//! 127.0.0.1, and requires `Authorization: Bearer <token>` on every request.
+//!
+//! ## Routes
+//!
+//! | Route | Body | Reply |
+//! |---|---|---|
+//! | `GET /search?q=...` | | `{ "groups": [...] }` |
+//! | `POST /action` | `{ "query", "item"?, "action"? }` | `{ "result": ... }` |
+//! | `POST /show`, `/hide`, `/toggle` | | `{ "ok": true }` |
+//! | `POST /notify` | `{ "title", "body"? }` | `{ "ok": true }` |
+//!
+//! `/action` searches for `query`, picks the item with ID `item` (default:
+//! the first result), and runs the action with ID `action` (default: the
+//! first applicable action).
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+
+/// Port used when `lux.server.enable()` doesn't set one.
+pub const DEFAULT_PORT: u16 = 7437;
+
+/// Largest request body accepted.
+const MAX_BODY: usize = 64 * 1024;
+
+/// Shortest token accepted from config.
+const MIN_TOKEN_LEN: usize = 16;
+
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// =============================================================================
+// Settings
+// =============================================================================
+
+/// Port and token of an enabled server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// Localhost port to listen on.
+    pub port: u16,
+    /// Bearer token clients must send.
+    pub token: String,
+}
+
+/// Remote control settings set from Lua.
+pub struct ServerSettings {
+    config: RwLock<Option<ServerConfig>>,
+}
+
+impl ServerSettings {
+    /// Create settings with the server disabled.
+    pub fn new() -> Self {
+        Self {
+            config: RwLock::new(None),
+        }
+    }
+
+    /// Enable the server. Without a token, a random one is generated.
+    pub fn enable(
+        &self,
+        port: Option<u16>,
+        token: Option<String>,
+    ) -> Result<ServerConfig, ServerError> {
+        let port = port.unwrap_or(DEFAULT_PORT);
+        if port < 1024 {
+            return Err(ServerError::InvalidPort(port));
+        }
+        let token = match token {
+            Some(token) if token.len() < MIN_TOKEN_LEN => {
+                return Err(ServerError::TokenTooShort(MIN_TOKEN_LEN))
+            }
+            Some(token) => token,
+            None => uuid::Uuid::new_v4().simple().to_string(),
+        };
+
+        let config = ServerConfig { port, token };
+        *self.config.write() = Some(config.clone());
+        Ok(config)
+    }
+
+    /// Disable the server.
+    pub fn disable(&self) {
+        *self.config.write() = None;
+    }
+
+    /// The server configuration, if enabled.
+    pub fn config(&self) -> Option<ServerConfig> {
+        self.config.read().clone()
+    }
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// Commands
+// =============================================================================
+
+/// A request for the launcher, parsed from an HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteCommand {
+    /// Search the current view.
+    Search { query: String },
+    /// Run an action on a search result.
+    RunAction {
+        query: String,
+        item: Option<String>,
+        action: Option<String>,
+    },
+    /// Show and focus the launcher.
+    Show,
+    /// Hide the launcher.
+    Hide,
+    /// Show the launcher if hidden, hide it otherwise.
+    Toggle,
+    /// Post a notification.
+    Notify { title: String, body: String },
+}
+
+/// A command sent to the UI with a channel for the JSON reply.
+pub struct RemoteRequest {
+    /// The command to run.
+    pub command: RemoteCommand,
+    /// Receives the reply body, or an error message.
+    pub reply: oneshot::Sender<Result<Value, String>>,
+}
+
+#[derive(Deserialize)]
+struct ActionBody {
+    query: String,
+    item: Option<String>,
+    action: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct NotifyBody {
+    title: String,
+    #[serde(default)]
+    body: String,
+}
+
+// =============================================================================
+// HTTP
+// =============================================================================
+
+/// A parsed HTTP/1.1 request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    /// Method, e.g. "GET".
+    pub method: String,
+    /// Path without the query string.
+    pub path: String,
+    /// Decoded query parameters.
+    pub query: Vec<(String, String)>,
+    /// Headers with lowercase names.
+    pub headers: Vec<(String, String)>,
+    /// Request body.
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Read a request from a stream.
+    pub fn read_from(reader: &mut impl BufRead) -> Result<Self, ServerError> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err(ServerError::BadRequest(
+                "malformed request line".to_string(),
+            ));
+        };
+        let method = method.to_string();
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), parse_query(query)),
+            None => (target.to_string(), Vec::new()),
+        };
+
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+
+        let length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .map(|(_, value)| value.parse::<usize>())
+            .transpose()
+            .map_err(|_| ServerError::BadRequest("invalid Content-Length".to_string()))?
+            .unwrap_or(0);
+        if length > MAX_BODY {
+            return Err(ServerError::BadRequest("body too large".to_string()));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Self {
+            method,
+            path,
+            query,
+            headers,
+            body,
+        })
+    }
+
+    /// Value of a header (name in lowercase).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Value of a query parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the request carries `Authorization: Bearer <token>`.
+    pub fn is_authorized(&self, token: &str) -> bool {
+        self.header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|sent| constant_time_eq(sent.trim().as_bytes(), token.as_bytes()))
+    }
+
+    /// Parse the route and body into a command.
+    pub fn command(&self) -> Result<RemoteCommand, ServerError> {
+        match (self.method.as_str(), self.path.as_str()) {
+            ("GET", "/search") => Ok(RemoteCommand::Search {
+                query: self.param("q").unwrap_or_default().to_string(),
+            }),
+            ("POST", "/action") => {
+                let body: ActionBody = self.json()?;
+                Ok(RemoteCommand::RunAction {
+                    query: body.query,
+                    item: body.item,
+                    action: body.action,
+                })
+            }
+            ("POST", "/show") => Ok(RemoteCommand::Show),
+            ("POST", "/hide") => Ok(RemoteCommand::Hide),
+            ("POST", "/toggle") => Ok(RemoteCommand::Toggle),
+            ("POST", "/notify") => {
+                let body: NotifyBody = self.json()?;
+                Ok(RemoteCommand::Notify {
+                    title: body.title,
+                    body: body.body,
+                })
+            }
+            (_, "/search" | "/action" | "/show" | "/hide" | "/toggle" | "/notify") => {
+                Err(ServerError::MethodNotAllowed)
+            }
+            _ => Err(ServerError::NotFound(self.path.clone())),
+        }
+    }
+
+    fn json<T: for<'de> Deserialize<'de>>(&self) -> Result<T, ServerError> {
+        serde_json::from_slice(&self.body).map_err(|e| ServerError::BadRequest(e.to_string()))
+    }
+}
+
+/// Decode `a=1&b=two%20words` into pairs.
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` as space.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 2;
+                    }
+                    None => out.push(b'%'),
+                }
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Compare without returning early, so timing doesn't leak the token.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Write a JSON response.
+fn write_response(stream: &mut impl Write, status: u16, body: &Value) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// =============================================================================
+// Server
+// =============================================================================
+
+/// The listener thread. Dropping it stops the server.
+pub struct RemoteServer {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl RemoteServer {
+    /// Listen on 127.0.0.1 and forward authorized commands to `tx`.
+    pub fn start(
+        config: ServerConfig,
+        tx: mpsc::Sender<RemoteRequest>,
+    ) -> Result<Self, ServerError> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, config.port))?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_stop = stop.clone();
+        let thread = std::thread::Builder::new()
+            .name("lux-remote".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if thread_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    match stream {
+                        Ok(stream) => handle_connection(stream, &config.token, &tx),
+                        Err(e) => tracing::debug!("Remote control accept failed: {}", e),
+                    }
+                }
+            })?;
+
+        tracing::info!("Remote control listening on http://{}", addr);
+        Ok(Self {
+            addr,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// The address the server listens on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the blocking accept so the thread sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Read one request, run it, and write the reply.
+fn handle_connection(stream: TcpStream, token: &str, tx: &mpsc::Sender<RemoteRequest>) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(stream);
+
+    let (status, body) = match HttpRequest::read_from(&mut reader) {
+        Ok(request) if !request.is_authorized(token) => {
+            (401, json!({ "error": "missing or invalid token" }))
+        }
+        Ok(request) => match request.command() {
+            Ok(command) => dispatch(command, tx),
+            Err(e) => (e.status(), json!({ "error": e.to_string() })),
+        },
+        Err(e) => (e.status(), json!({ "error": e.to_string() })),
+    };
+
+    if let Err(e) = write_response(&mut writer, status, &body) {
+        tracing::debug!("Remote control reply failed: {}", e);
+    }
+}
+
+/// Send a command to the UI and wait for its reply.
+fn dispatch(command: RemoteCommand, tx: &mpsc::Sender<RemoteRequest>) -> (u16, Value) {
+    let (reply, rx) = oneshot::channel();
+    if tx.blocking_send(RemoteRequest { command, reply }).is_err() {
+        return (500, json!({ "error": "launcher is shutting down" }));
+    }
+    match rx.blocking_recv() {
+        Ok(Ok(body)) => (200, body),
+        Ok(Err(message)) => (500, json!({ "error": message })),
+        Err(_) => (500, json!({ "error": "no reply from launcher" })),
+    }
+}
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Errors from the remote control server.
+#[derive(Debug, thiserror::Error)]
+pub enum ServerError {
+    #[error("Invalid port {0} (expected 1024-65535)")]
+    InvalidPort(u16),
+
+    #[error("Token must be at least {0} characters")]
+    TokenTooShort(usize),
+
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Method not allowed")]
+    MethodNotAllowed,
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl ServerError {
+    /// HTTP status for the error.
+    fn status(&self) -> u16 {
+        match self {
+            Self::BadRequest(_) | Self::Io(_) => 400,
+            Self::NotFound(_) => 404,
+            Self::MethodNotAllowed => 405,
+            Self::InvalidPort(_) | Self::TokenTooShort(_) => 500,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> HttpRequest {
+        HttpRequest::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_search_request() {
+        let request = parse(
+            "GET /search?q=safari%20tabs&x HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer 0123456789abcdef\r\n\r\n",
+        );
+        assert!(request.is_authorized("0123456789abcdef"));
+        assert!(!request.is_authorized("0123456789abcdeX"));
+        assert_eq!(
+            request.command().unwrap(),
+            RemoteCommand::Search {
+                query: "safari tabs".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_json_routes() {
+        let body = r#"{"query":"saf","action":"open"}"#;
+        let request = parse(&format!(
+            "POST /action HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        ));
+        assert!(!request.is_authorized("0123456789abcdef"));
+        assert_eq!(
+            request.command().unwrap(),
+            RemoteCommand::RunAction {
+                query: "saf".to_string(),
+                item: None,
+                action: Some("open".to_string()),
+            }
+        );
+
+        let request = parse("POST /notify HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}");
+        assert!(matches!(request.command(), Err(ServerError::BadRequest(_))));
+
+        let request = parse("GET /hide HTTP/1.1\r\n\r\n");
+        assert!(matches!(
+            request.command(),
+            Err(ServerError::MethodNotAllowed)
+        ));
+
+        let request = parse("POST /quit HTTP/1.1\r\n\r\n");
+        assert!(matches!(request.command(), Err(ServerError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_enable_validates() {
+        let settings = ServerSettings::new();
+        assert!(settings.config().is_none());
+        assert!(matches!(
+            settings.enable(Some(80), None),
+            Err(ServerError::InvalidPort(80))
+        ));
+        assert!(matches!(
+            settings.enable(None, Some("short".to_string())),
+            Err(ServerError::TokenTooShort(_))
+        ));
+
+        let config = settings.enable(None, None).unwrap();
+        assert_eq!(config.port, DEFAULT_PORT);
+        assert_eq!(config.token.len(), 32);
+        assert_eq!(settings.config(), Some(config));
+
+        settings.disable();
+        assert!(settings.config().is_none());
+    }
+}
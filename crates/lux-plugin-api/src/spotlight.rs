@@ -0,0 +1,665 @@
+//! System-wide file search through Spotlight.
+//!
+//! This module provides:
+//! - `SpotlightQuery` - Search text plus `kind:` and date filters, as an `mdfind` query
+//! - `SpotlightSearch` - Runs `mdfind` in the background and streams its results
+//! - `spotlight_actions()` / `run_spotlight_action()` - Open, reveal, and copy a result
+//!
+//! Filters are typed into the launcher alongside the search text:
+//!
+//! ```text
+//! report kind:pdf modified:week
+//! kind:image created:2024-01-01..2024-03-31
+//! invoice modified:..2023-12-31
+//! ```
+//!
+//! Spotlight already indexes the whole disk, so results show up while Lux's
+//! own indexes are still being built. `mdfind` prints matches as it finds
+//! them; each batch is published on a watch channel so the UI can re-run the
+//! search and pick them up.
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+use lux_core::i18n::t;
+use lux_core::{DragPayload, Group, Item};
+
+use crate::error::{PluginError, PluginResult};
+
+/// Type tag for Spotlight items (used to route them to the built-in actions).
+pub const SPOTLIGHT_ITEM_TYPE: &str = "spotlight";
+
+/// Lua registry key prefix of the built-in Spotlight action handlers.
+pub const SPOTLIGHT_ACTION_KEY_PREFIX: &str = "spotlight:";
+
+/// IDs of all Spotlight actions (one handler is registered per ID).
+pub const SPOTLIGHT_ACTION_IDS: &[&str] = &["open", "reveal", "copy_path"];
+
+/// Shortest search text that starts a search without filters.
+const MIN_QUERY_LEN: usize = 3;
+
+/// Results kept per search; `mdfind` is stopped once it has found this many.
+const MAX_RESULTS: usize = 50;
+
+/// Minimum time between result updates while `mdfind` is running.
+const UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Handler key for a Spotlight action ID.
+pub fn spotlight_action_key(id: &str) -> String {
+    format!("{}{}", SPOTLIGHT_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Query
+// =============================================================================
+
+/// A file kind for the `kind:` filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Application,
+    Folder,
+    Document,
+    Image,
+    Movie,
+    Audio,
+    Pdf,
+    Text,
+    Presentation,
+    Spreadsheet,
+    Archive,
+}
+
+impl FileKind {
+    /// Parse a `kind:` value (singular, plural, and common short names).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "app" | "apps" | "application" | "applications" => Some(FileKind::Application),
+            "folder" | "folders" | "dir" | "directory" => Some(FileKind::Folder),
+            "doc" | "docs" | "document" | "documents" => Some(FileKind::Document),
+            "image" | "images" | "img" | "photo" | "photos" => Some(FileKind::Image),
+            "movie" | "movies" | "video" | "videos" => Some(FileKind::Movie),
+            "audio" | "music" | "sound" => Some(FileKind::Audio),
+            "pdf" | "pdfs" => Some(FileKind::Pdf),
+            "text" | "txt" => Some(FileKind::Text),
+            "presentation" | "presentations" | "slides" => Some(FileKind::Presentation),
+            "spreadsheet" | "spreadsheets" | "sheet" => Some(FileKind::Spreadsheet),
+            "archive" | "archives" | "zip" => Some(FileKind::Archive),
+            _ => None,
+        }
+    }
+
+    /// Uniform type identifier matched against `kMDItemContentTypeTree`.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FileKind::Application => "com.apple.application",
+            FileKind::Folder => "public.folder",
+            FileKind::Document => "public.content",
+            FileKind::Image => "public.image",
+            FileKind::Movie => "public.movie",
+            FileKind::Audio => "public.audio",
+            FileKind::Pdf => "com.adobe.pdf",
+            FileKind::Text => "public.text",
+            FileKind::Presentation => "public.presentation",
+            FileKind::Spreadsheet => "public.spreadsheet",
+            FileKind::Archive => "public.archive",
+        }
+    }
+}
+
+/// The file date a date filter applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    /// `modified:` (also `date:`)
+    Modified,
+    /// `created:`
+    Created,
+}
+
+impl DateField {
+    fn attribute(&self) -> &'static str {
+        match self {
+            DateField::Modified => "kMDItemFSContentChangeDate",
+            DateField::Created => "kMDItemFSCreationDate",
+        }
+    }
+}
+
+/// A date range for a date filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateRange {
+    Today,
+    Yesterday,
+    ThisWeek,
+    ThisMonth,
+    ThisYear,
+    /// Days from `start` to `end`, both inclusive (`YYYY-MM-DD`; open if `None`).
+    Between {
+        start: Option<String>,
+        end: Option<String>,
+    },
+}
+
+impl DateRange {
+    /// Parse a date filter value: `today`, `yesterday`, `week`, `month`,
+    /// `year`, `YYYY-MM-DD`, or a `START..END` range with either end optional.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "today" => return Some(DateRange::Today),
+            "yesterday" => return Some(DateRange::Yesterday),
+            "week" | "thisweek" => return Some(DateRange::ThisWeek),
+            "month" | "thismonth" => return Some(DateRange::ThisMonth),
+            "year" | "thisyear" => return Some(DateRange::ThisYear),
+            _ => {}
+        }
+
+        let bound = |s: &str| -> Option<Option<String>> {
+            if s.is_empty() {
+                Some(None)
+            } else {
+                is_date(s).then(|| Some(s.to_string()))
+            }
+        };
+
+        let (start, end) = match value.split_once("..") {
+            Some((start, end)) => (bound(start)?, bound(end)?),
+            None => {
+                let day = bound(value)?;
+                (day.clone(), day)
+            }
+        };
+        if start.is_none() && end.is_none() {
+            return None;
+        }
+        Some(DateRange::Between { start, end })
+    }
+
+    /// `mdfind` conditions restricting `attribute` to this range.
+    fn conditions(&self, attribute: &str) -> Vec<String> {
+        let since = |time: &str| format!("{} >= {}", attribute, time);
+        match self {
+            DateRange::Today => vec![since("$time.today")],
+            DateRange::Yesterday => vec![
+                since("$time.today(-1)"),
+                format!("{} < $time.today", attribute),
+            ],
+            DateRange::ThisWeek => vec![since("$time.this_week")],
+            DateRange::ThisMonth => vec![since("$time.this_month")],
+            DateRange::ThisYear => vec![since("$time.this_year")],
+            DateRange::Between { start, end } => {
+                let mut conditions = Vec::new();
+                if let Some(start) = start {
+                    conditions.push(since(&format!("$time.iso({}T00:00:00)", start)));
+                }
+                if let Some(end) = end {
+                    conditions.push(format!("{} <= $time.iso({}T23:59:59)", attribute, end));
+                }
+                conditions
+            }
+        }
+    }
+}
+
+/// Check for a `YYYY-MM-DD` date.
+fn is_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    let number = |part: &str, len: usize, max: u32| {
+        part.len() == len
+            && part
+                .parse::<u32>()
+                .is_ok_and(|n| (len == 4 || n >= 1) && n <= max)
+    };
+    parts.len() == 3
+        && number(parts[0], 4, 9999)
+        && number(parts[1], 2, 12)
+        && number(parts[2], 2, 31)
+}
+
+/// A launcher query split into search text and Spotlight filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpotlightQuery {
+    /// Words matched against file names.
+    pub text: Vec<String>,
+    /// `kind:` filters (any of them matches).
+    pub kinds: Vec<FileKind>,
+    /// Date filters (all of them must match).
+    pub dates: Vec<(DateField, DateRange)>,
+}
+
+impl SpotlightQuery {
+    /// Parse a query. Words that aren't valid filters are search text.
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+
+        for word in query.split_whitespace() {
+            let filter =
+                word.split_once(':')
+                    .and_then(|(key, value)| match key.to_lowercase().as_str() {
+                        "kind" => FileKind::from_name(value).map(|kind| parsed.kinds.push(kind)),
+                        "modified" | "date" => DateRange::parse(value)
+                            .map(|range| parsed.dates.push((DateField::Modified, range))),
+                        "created" => DateRange::parse(value)
+                            .map(|range| parsed.dates.push((DateField::Created, range))),
+                        _ => None,
+                    });
+            if filter.is_none() {
+                parsed.text.push(word.to_string());
+            }
+        }
+
+        parsed
+    }
+
+    /// Whether the query is specific enough to search for.
+    ///
+    /// Short queries without filters would match most of the disk.
+    pub fn is_searchable(&self) -> bool {
+        let text_len: usize = self.text.iter().map(|w| w.chars().count()).sum();
+        text_len >= MIN_QUERY_LEN || !self.kinds.is_empty() || !self.dates.is_empty()
+    }
+
+    /// Build the `mdfind` query expression.
+    pub fn to_mdfind(&self) -> String {
+        let mut conditions: Vec<String> = self
+            .text
+            .iter()
+            .map(|word| format!("kMDItemDisplayName == \"*{}*\"cd", escape(word)))
+            .collect();
+
+        if !self.kinds.is_empty() {
+            let kinds: Vec<String> = self
+                .kinds
+                .iter()
+                .map(|kind| format!("kMDItemContentTypeTree == \"{}\"", kind.content_type()))
+                .collect();
+            conditions.push(format!("({})", kinds.join(" || ")));
+        }
+
+        for (field, range) in &self.dates {
+            conditions.extend(range.conditions(field.attribute()));
+        }
+
+        conditions.join(" && ")
+    }
+}
+
+/// Escape a value for a quoted `mdfind` string.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | '*' | '?') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// =============================================================================
+// Search
+// =============================================================================
+
+/// State of the current `mdfind` run.
+#[derive(Default)]
+struct SearchState {
+    /// `mdfind` expression being searched (empty when idle).
+    expression: String,
+    /// Bumped for every search so readers of stopped runs drop their output.
+    generation: u64,
+    /// Paths found so far.
+    results: Vec<PathBuf>,
+    /// The running `mdfind` process.
+    child: Option<Child>,
+}
+
+impl SearchState {
+    /// Kill and reap the running `mdfind`, if any.
+    fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Runs one Spotlight search at a time in the background.
+///
+/// `group()` is called on every keystroke. A changed query stops the previous
+/// `mdfind` and starts a new one; the same query returns what has been found
+/// so far. Subscribers are notified whenever more results arrive.
+pub struct SpotlightSearch {
+    state: Arc<Mutex<SearchState>>,
+    updates: Arc<watch::Sender<u64>>,
+}
+
+impl SpotlightSearch {
+    /// Create an idle search.
+    pub fn new() -> Self {
+        let (updates, _) = watch::channel(0);
+        Self {
+            state: Arc::new(Mutex::new(SearchState::default())),
+            updates: Arc::new(updates),
+        }
+    }
+
+    /// Subscribe to result updates. The value changes whenever new results
+    /// are available; re-run the search to get them.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.updates.subscribe()
+    }
+
+    /// The sender behind `subscribe()`, for other background sources whose
+    /// results should also make the UI re-run the search.
+    pub(crate) fn updates(&self) -> Arc<watch::Sender<u64>> {
+        self.updates.clone()
+    }
+
+    /// Build the root-view "Spotlight" group for a query.
+    ///
+    /// Returns None while nothing has been found or when the query is too
+    /// short to search for.
+    pub fn group(&self, query: &str) -> Option<Group> {
+        let query = SpotlightQuery::parse(query);
+        if !query.is_searchable() {
+            self.cancel();
+            return None;
+        }
+
+        let items: Vec<Item> = self.results(&query).iter().map(|p| file_item(p)).collect();
+        if items.is_empty() {
+            None
+        } else {
+            Some(Group::new(t("Spotlight"), items))
+        }
+    }
+
+    /// Paths found so far for `query`, starting a search if the query changed.
+    pub fn results(&self, query: &SpotlightQuery) -> Vec<PathBuf> {
+        let expression = query.to_mdfind();
+        let mut state = self.state.lock();
+        if state.expression != expression {
+            self.start(&mut state, expression);
+        }
+        state.results.clone()
+    }
+
+    /// Stop the running search.
+    pub fn cancel(&self) {
+        let mut state = self.state.lock();
+        state.stop();
+        state.generation += 1;
+        state.expression.clear();
+        state.results.clear();
+    }
+
+    fn start(&self, state: &mut SearchState, expression: String) {
+        state.stop();
+        state.generation += 1;
+        state.results.clear();
+
+        let spawned = Command::new("mdfind")
+            .arg(&expression)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        state.expression = expression;
+
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                tracing::debug!("Failed to run mdfind: {}", e);
+                return;
+            }
+        };
+        let Some(stdout) = child.stdout.take() else {
+            return;
+        };
+        state.child = Some(child);
+
+        let generation = state.generation;
+        let shared = self.state.clone();
+        let updates = self.updates.clone();
+        std::thread::spawn(move || {
+            read_results(BufReader::new(stdout), generation, &shared, &updates)
+        });
+    }
+}
+
+impl Default for SpotlightSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for SpotlightSearch {
+    fn drop(&mut self) {
+        self.state.lock().stop();
+    }
+}
+
+/// Collect `mdfind` output into the search state, notifying in batches.
+///
+/// Stops at `MAX_RESULTS` or as soon as a newer search replaces this one.
+fn read_results(
+    reader: impl BufRead,
+    generation: u64,
+    state: &Mutex<SearchState>,
+    updates: &watch::Sender<u64>,
+) {
+    let notify = || updates.send_modify(|count| *count = count.wrapping_add(1));
+    let mut last_update = Instant::now();
+    let mut pending = false;
+
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.is_empty() {
+            continue;
+        }
+
+        let full = {
+            let mut state = state.lock();
+            if state.generation != generation {
+                return;
+            }
+            state.results.push(PathBuf::from(line));
+            state.results.len() >= MAX_RESULTS
+        };
+        pending = true;
+
+        if full {
+            break;
+        }
+        if last_update.elapsed() >= UPDATE_INTERVAL {
+            notify();
+            last_update = Instant::now();
+            pending = false;
+        }
+    }
+
+    let mut state = state.lock();
+    if state.generation != generation {
+        return;
+    }
+    state.stop();
+    drop(state);
+
+    if pending {
+        notify();
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item for a file found by Spotlight.
+pub fn file_item(path: &Path) -> Item {
+    let path_str = path.to_string_lossy().to_string();
+    let title = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path_str.clone());
+    let subtitle = path.parent().map(abbreviate_home);
+
+    let mut item = Item::new(format!("spotlight:{}", path_str), title)
+        .with_icon(if path.is_dir() { "📁" } else { "📄" })
+        .with_type(SPOTLIGHT_ITEM_TYPE)
+        .with_type("file")
+        .with_data(serde_json::json!({ "path": path_str }))
+        .with_drag(DragPayload::File { path: path_str });
+    item.subtitle = subtitle;
+    item
+}
+
+/// Show a path under the home directory as `~/...`.
+pub(crate) fn abbreviate_home(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a Spotlight item: `(id, title)`.
+pub fn spotlight_actions() -> Vec<(&'static str, String)> {
+    vec![
+        ("open", t("Open")),
+        ("reveal", t("Reveal in Finder")),
+        ("copy_path", t("Copy Path")),
+    ]
+}
+
+/// Run a Spotlight action on the item it was offered for.
+pub fn run_spotlight_action(action_id: &str, item: &Item) -> PluginResult<()> {
+    let path = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("path"))
+        .and_then(|path| path.as_str())
+        .ok_or_else(|| PluginError::Command("Spotlight item has no path".to_string()))?;
+
+    let desktop = crate::desktop::current();
+    match action_id {
+        "open" => desktop.open(path),
+        "reveal" => desktop.reveal(path),
+        "copy_path" => desktop.clipboard_write(path),
+        _ => Err(PluginError::Command(format!(
+            "Unknown Spotlight action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters() {
+        let query = SpotlightQuery::parse("quarterly report kind:pdf KIND:Images modified:week");
+        assert_eq!(query.text, ["quarterly", "report"]);
+        assert_eq!(query.kinds, [FileKind::Pdf, FileKind::Image]);
+        assert_eq!(query.dates, [(DateField::Modified, DateRange::ThisWeek)]);
+
+        // Unknown keys and invalid values stay in the search text
+        let query = SpotlightQuery::parse("kind:spaceship created:soon http://x");
+        assert_eq!(query.text, ["kind:spaceship", "created:soon", "http://x"]);
+        assert!(query.kinds.is_empty());
+        assert!(query.dates.is_empty());
+    }
+
+    #[test]
+    fn test_parse_date_ranges() {
+        let between = |start: Option<&str>, end: Option<&str>| DateRange::Between {
+            start: start.map(String::from),
+            end: end.map(String::from),
+        };
+
+        assert_eq!(DateRange::parse("today"), Some(DateRange::Today));
+        assert_eq!(
+            DateRange::parse("2024-03-01"),
+            Some(between(Some("2024-03-01"), Some("2024-03-01")))
+        );
+        assert_eq!(
+            DateRange::parse("2024-01-01..2024-03-31"),
+            Some(between(Some("2024-01-01"), Some("2024-03-31")))
+        );
+        assert_eq!(
+            DateRange::parse("..2023-12-31"),
+            Some(between(None, Some("2023-12-31")))
+        );
+        assert_eq!(DateRange::parse(".."), None);
+        assert_eq!(DateRange::parse("2024-13-01"), None);
+        assert_eq!(DateRange::parse("2024-1-1"), None);
+    }
+
+    #[test]
+    fn test_to_mdfind() {
+        let query = SpotlightQuery::parse("my\"notes kind:image kind:pdf created:2024-01-01..");
+        assert_eq!(
+            query.to_mdfind(),
+            "kMDItemDisplayName == \"*my\\\"notes*\"cd \
+             && (kMDItemContentTypeTree == \"public.image\" || kMDItemContentTypeTree == \"com.adobe.pdf\") \
+             && kMDItemFSCreationDate >= $time.iso(2024-01-01T00:00:00)"
+        );
+
+        let query = SpotlightQuery::parse("modified:yesterday");
+        assert_eq!(
+            query.to_mdfind(),
+            "kMDItemFSContentChangeDate >= $time.today(-1) && kMDItemFSContentChangeDate < $time.today"
+        );
+    }
+
+    #[test]
+    fn test_is_searchable() {
+        assert!(!SpotlightQuery::parse("").is_searchable());
+        assert!(!SpotlightQuery::parse("ab").is_searchable());
+        assert!(SpotlightQuery::parse("abc").is_searchable());
+        assert!(SpotlightQuery::parse("kind:pdf").is_searchable());
+    }
+
+    #[test]
+    fn test_read_results_stops_when_replaced() {
+        let state = Mutex::new(SearchState {
+            generation: 2,
+            ..Default::default()
+        });
+        let (updates, rx) = watch::channel(0);
+
+        // A reader from an older search drops its output
+        read_results("/a\n/b\n".as_bytes(), 1, &state, &updates);
+        assert!(state.lock().results.is_empty());
+        assert!(!rx.has_changed().unwrap());
+
+        read_results("/a\n\n/b\n".as_bytes(), 2, &state, &updates);
+        assert_eq!(
+            state.lock().results,
+            [PathBuf::from("/a"), PathBuf::from("/b")]
+        );
+        assert!(rx.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_file_item() {
+        let item = file_item(Path::new("/tmp/lux-spotlight-test/report.pdf"));
+        assert_eq!(item.title, "report.pdf");
+        assert_eq!(item.subtitle.as_deref(), Some("/tmp/lux-spotlight-test"));
+        assert!(item.has_type(SPOTLIGHT_ITEM_TYPE));
+        assert_eq!(
+            item.data.unwrap()["path"],
+            "/tmp/lux-spotlight-test/report.pdf"
+        );
+    }
+}
@@ -0,0 +1,667 @@
+//! SSH hosts from `~/.ssh/config` and `known_hosts`.
+//!
+//! This module provides:
+//! - `SshHost` - A host to connect to, with its user and port
+//! - `parse_ssh_config()` / `parse_known_hosts()` - Read the OpenSSH files
+//! - `Terminal` / `SshSettings` - The terminal app connections open in (lux.ssh)
+//! - `ssh_group()` - The root-view "SSH Hosts" group
+//!
+//! Hosts from the config are connected to by alias so its settings
+//! (identity files, jump hosts, ...) apply. Hashed `known_hosts` entries
+//! can't be read back and are skipped.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use parking_lot::RwLock;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::permissions::run_jxa_with_args;
+
+/// Type tag for SSH host items (used to route them to the built-in actions).
+pub const SSH_ITEM_TYPE: &str = "ssh_host";
+
+/// Lua registry key prefix of the built-in SSH action handlers.
+pub const SSH_ACTION_KEY_PREFIX: &str = "ssh:";
+
+/// IDs of all SSH actions (one handler is registered per ID).
+pub const SSH_ACTION_IDS: &[&str] = &["open", "copy_command"];
+
+/// Query prefix that lists every host.
+const SSH_KEYWORD: &str = "ssh";
+
+/// Shortest query matched against host names without the keyword.
+const MIN_QUERY_LEN: usize = 2;
+
+/// Handler key for an SSH action ID.
+pub fn ssh_action_key(id: &str) -> String {
+    format!("{}{}", SSH_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// Hosts
+// =============================================================================
+
+/// Where a host was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostSource {
+    /// A `Host` entry in `~/.ssh/config`.
+    Config,
+    /// A `known_hosts` line.
+    KnownHosts,
+}
+
+/// A host to connect to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshHost {
+    /// Config alias, or the host name for known hosts.
+    pub alias: String,
+    /// `HostName`, if it differs from the alias.
+    pub hostname: Option<String>,
+    /// `User`.
+    pub user: Option<String>,
+    /// `Port`, if not the default.
+    pub port: Option<u16>,
+    pub source: HostSource,
+}
+
+impl SshHost {
+    fn new(alias: impl Into<String>, source: HostSource) -> Self {
+        Self {
+            alias: alias.into(),
+            hostname: None,
+            user: None,
+            port: None,
+            source,
+        }
+    }
+
+    /// Arguments to `ssh` for connecting to this host.
+    ///
+    /// Config hosts connect by alias; known hosts pass their port.
+    pub fn ssh_args(&self) -> Vec<String> {
+        match (self.source, self.port) {
+            (HostSource::KnownHosts, Some(port)) => {
+                vec!["-p".to_string(), port.to_string(), self.alias.clone()]
+            }
+            _ => vec![self.alias.clone()],
+        }
+    }
+
+    /// The `ssh` command line for this host.
+    pub fn command(&self) -> String {
+        let mut parts = vec!["ssh".to_string()];
+        parts.extend(self.ssh_args());
+        parts.join(" ")
+    }
+}
+
+/// Parse an `ssh_config` file.
+///
+/// Reads `Host` entries with their `HostName`, `User`, and `Port`. Patterns
+/// (`*`, `?`, `!`) aren't hosts and are skipped; `Match` blocks are ignored.
+/// `Include` lines are returned separately so the caller can resolve them.
+pub fn parse_ssh_config(text: &str) -> (Vec<SshHost>, Vec<String>) {
+    let mut hosts: Vec<SshHost> = Vec::new();
+    let mut includes = Vec::new();
+    // Indices into `hosts` the current block's settings apply to
+    let mut current: Vec<usize> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+            Some((key, value)) => (
+                key.to_lowercase(),
+                value.trim_start_matches([' ', '\t', '=']).trim(),
+            ),
+            None => continue,
+        };
+        let value = value.trim_matches('"');
+
+        match key.as_str() {
+            "host" => {
+                current.clear();
+                for alias in value.split_whitespace() {
+                    if alias.contains(['*', '?', '!']) {
+                        continue;
+                    }
+                    current.push(hosts.len());
+                    hosts.push(SshHost::new(alias, HostSource::Config));
+                }
+            }
+            "match" => current.clear(),
+            "include" => includes.extend(value.split_whitespace().map(String::from)),
+            "hostname" | "user" | "port" => {
+                for &i in &current {
+                    let host = &mut hosts[i];
+                    // The first value for a setting wins, as in ssh
+                    match key.as_str() {
+                        "hostname" if host.hostname.is_none() && value != host.alias => {
+                            host.hostname = Some(value.to_string())
+                        }
+                        "user" if host.user.is_none() => host.user = Some(value.to_string()),
+                        "port" if host.port.is_none() => {
+                            host.port = value.parse().ok().filter(|&port| port != 22)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (hosts, includes)
+}
+
+/// Parse a `known_hosts` file.
+///
+/// Hashed entries (`|1|...`), markers (`@cert-authority`, `@revoked`), and
+/// bare IP addresses are skipped; `[host]:port` entries keep their port.
+pub fn parse_known_hosts(text: &str) -> Vec<SshHost> {
+    let mut hosts = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('@') {
+            continue;
+        }
+        let Some(names) = line.split_whitespace().next() else {
+            continue;
+        };
+
+        for name in names.split(',') {
+            if name.starts_with('|') || name.contains(['*', '?', '!']) {
+                continue;
+            }
+            let (host, port) = match name
+                .strip_prefix('[')
+                .and_then(|rest| rest.split_once("]:"))
+            {
+                Some((host, port)) => (host, port.parse().ok().filter(|&port| port != 22)),
+                None => (name, None),
+            };
+            if host.parse::<std::net::IpAddr>().is_ok() {
+                continue;
+            }
+            let mut entry = SshHost::new(host, HostSource::KnownHosts);
+            entry.port = port;
+            hosts.push(entry);
+        }
+    }
+
+    hosts
+}
+
+/// Read hosts from `~/.ssh/config` (with its includes) and `known_hosts`.
+///
+/// Known hosts already named by the config are left out.
+pub fn load_hosts() -> Vec<SshHost> {
+    let Some(ssh_dir) = dirs::home_dir().map(|home| home.join(".ssh")) else {
+        return Vec::new();
+    };
+
+    let mut hosts = Vec::new();
+    let mut pending = vec![ssh_dir.join("config")];
+    let mut visited = HashSet::new();
+    while let Some(path) = pending.pop() {
+        if !visited.insert(path.clone()) {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let (found, includes) = parse_ssh_config(&text);
+        hosts.extend(found);
+        for include in includes {
+            pending.extend(resolve_include(&ssh_dir, &include));
+        }
+    }
+
+    let mut seen: HashSet<String> = hosts
+        .iter()
+        .flat_map(|h| std::iter::once(h.alias.clone()).chain(h.hostname.clone()))
+        .collect();
+    if let Ok(text) = std::fs::read_to_string(ssh_dir.join("known_hosts")) {
+        for host in parse_known_hosts(&text) {
+            if seen.insert(host.alias.clone()) {
+                hosts.push(host);
+            }
+        }
+    }
+
+    hosts
+}
+
+/// Files named by an `Include` line (relative to `~/.ssh`, `*` in the file name only).
+fn resolve_include(ssh_dir: &Path, include: &str) -> Vec<PathBuf> {
+    let path = PathBuf::from(crate::detect::expand_home(include));
+    let path = if path.is_absolute() {
+        path
+    } else {
+        ssh_dir.join(path)
+    };
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Some((prefix, suffix)) = name.split_once('*') else {
+        return vec![path];
+    };
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|p| {
+                    p.file_name().is_some_and(|n| {
+                        let n = n.to_string_lossy();
+                        n.starts_with(prefix) && n.ends_with(suffix)
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// =============================================================================
+// Terminal
+// =============================================================================
+
+/// The terminal app SSH connections (and repositories) open in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Terminal {
+    /// Terminal.app (scripted).
+    #[default]
+    Terminal,
+    /// iTerm2 (scripted).
+    ITerm,
+    /// Warp.
+    Warp,
+    /// Any other app, handed an `ssh://` URL.
+    Other(String),
+}
+
+impl Terminal {
+    /// Parse a terminal name as used from Lua (`"terminal"`, `"iterm"`,
+    /// `"warp"`, or any application name).
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "terminal" | "terminal.app" => Terminal::Terminal,
+            "iterm" | "iterm2" => Terminal::ITerm,
+            "warp" => Terminal::Warp,
+            _ => Terminal::Other(name.to_string()),
+        }
+    }
+
+    /// Application name.
+    pub fn app_name(&self) -> &str {
+        match self {
+            Terminal::Terminal => "Terminal",
+            Terminal::ITerm => "iTerm",
+            Terminal::Warp => "Warp",
+            Terminal::Other(name) => name,
+        }
+    }
+
+    /// Open an SSH session to `host`.
+    pub fn open(&self, host: &SshHost) -> PluginResult<()> {
+        if !is_safe_host(&host.alias) {
+            return Err(PluginError::Command(format!(
+                "Refusing to open unusual host name '{}'",
+                host.alias
+            )));
+        }
+
+        match self {
+            Terminal::Terminal => {
+                run_jxa_with_args(
+                    "function run(argv) { const app = Application('Terminal'); \
+                     app.doScript(argv[0]); app.activate(); return 'ok'; }",
+                    &[&host.command()],
+                )?;
+            }
+            Terminal::ITerm => {
+                run_jxa_with_args(
+                    "function run(argv) { const app = Application('iTerm'); app.activate(); \
+                     const win = app.createWindowWithDefaultProfile(); \
+                     win.currentSession().write({ text: argv[0] }); return 'ok'; }",
+                    &[&host.command()],
+                )?;
+            }
+            Terminal::Warp | Terminal::Other(_) => {
+                desktop::current()
+                    .open_with(&ssh_url(host), self.app_name())
+                    .map_err(|e| {
+                        PluginError::Command(format!(
+                            "Failed to open {} in {}: {}",
+                            host.alias,
+                            self.app_name(),
+                            e
+                        ))
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Open a new window in `dir`.
+    pub fn open_directory(&self, dir: &Path) -> PluginResult<()> {
+        desktop::current()
+            .open_with(&dir.to_string_lossy(), self.app_name())
+            .map_err(|e| {
+                PluginError::Command(format!(
+                    "Failed to open {} in {}: {}",
+                    dir.display(),
+                    self.app_name(),
+                    e
+                ))
+            })
+    }
+}
+
+/// `ssh://[user@]host[:port]` URL for apps that handle SSH links.
+fn ssh_url(host: &SshHost) -> String {
+    let mut url = "ssh://".to_string();
+    if let Some(user) = &host.user {
+        url.push_str(user);
+        url.push('@');
+    }
+    url.push_str(host.hostname.as_deref().unwrap_or(&host.alias));
+    if let Some(port) = host.port {
+        url.push_str(&format!(":{}", port));
+    }
+    url
+}
+
+/// Whether a host name is safe to pass to a shell command line.
+fn is_safe_host(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':' | '%'))
+}
+
+/// SSH settings set from Lua.
+pub struct SshSettings {
+    terminal: RwLock<Terminal>,
+}
+
+impl SshSettings {
+    /// Create settings using Terminal.app.
+    pub fn new() -> Self {
+        Self {
+            terminal: RwLock::new(Terminal::default()),
+        }
+    }
+
+    /// Set the terminal app connections open in.
+    pub fn set_terminal(&self, terminal: Terminal) {
+        *self.terminal.write() = terminal;
+    }
+
+    /// The terminal app connections open in.
+    pub fn terminal(&self) -> Terminal {
+        self.terminal.read().clone()
+    }
+}
+
+impl Default for SshSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item for a host.
+///
+/// The user and a non-default port are shown as accessories.
+pub fn host_item(host: &SshHost) -> Item {
+    let subtitle = match (&host.hostname, host.source) {
+        (Some(hostname), _) => Some(hostname.clone()),
+        (None, HostSource::KnownHosts) => Some(t("Known host")),
+        (None, HostSource::Config) => None,
+    };
+
+    let mut accessories = Vec::new();
+    if let Some(user) = &host.user {
+        accessories.push(user.clone());
+    }
+    if let Some(port) = host.port {
+        accessories.push(format!(":{}", port));
+    }
+
+    let mut item = Item::new(format!("ssh:{}", host.alias), host.alias.clone())
+        .with_icon("🖥")
+        .with_type(SSH_ITEM_TYPE)
+        .with_data(serde_json::json!({
+            "alias": host.alias,
+            "hostname": host.hostname,
+            "user": host.user,
+            "port": host.port,
+            "known_host": host.source == HostSource::KnownHosts,
+        }));
+    item.subtitle = subtitle;
+    item.accessories = accessories;
+    item
+}
+
+/// Recover the host from an item built by `host_item()`.
+pub fn host_from_item(item: &Item) -> Option<SshHost> {
+    let data = item.data.as_ref()?;
+    let field = |key: &str| data.get(key)?.as_str().map(String::from);
+    let source = if data.get("known_host")?.as_bool()? {
+        HostSource::KnownHosts
+    } else {
+        HostSource::Config
+    };
+    Some(SshHost {
+        alias: field("alias")?,
+        hostname: field("hostname"),
+        user: field("user"),
+        port: data
+            .get("port")
+            .and_then(|p| p.as_u64())
+            .and_then(|p| u16::try_from(p).ok()),
+        source,
+    })
+}
+
+/// Hosts matching a root-view query.
+///
+/// `ssh` lists every host and `ssh <text>` filters them; other queries of
+/// at least two characters match host names by prefix.
+pub fn matching_hosts(hosts: Vec<SshHost>, query: &str) -> Vec<SshHost> {
+    let query = query.trim().to_lowercase();
+    let (filter, by_keyword) = match query.strip_prefix(SSH_KEYWORD) {
+        Some("") => (String::new(), true),
+        Some(rest) if rest.starts_with(' ') => (rest.trim().to_string(), true),
+        _ if query.chars().count() >= MIN_QUERY_LEN => (query, false),
+        _ => return Vec::new(),
+    };
+
+    hosts
+        .into_iter()
+        .filter(|host| {
+            let alias = host.alias.to_lowercase();
+            let hostname = host.hostname.as_deref().unwrap_or_default().to_lowercase();
+            if by_keyword {
+                alias.contains(&filter) || hostname.contains(&filter)
+            } else {
+                alias.starts_with(&filter) || hostname.starts_with(&filter)
+            }
+        })
+        .collect()
+}
+
+/// Build the root-view "SSH Hosts" group for a query.
+pub fn ssh_group(query: &str) -> Option<Group> {
+    let query = query.trim();
+    if query.chars().count() < MIN_QUERY_LEN {
+        return None;
+    }
+
+    let items: Vec<Item> = matching_hosts(load_hosts(), query)
+        .iter()
+        .map(host_item)
+        .collect();
+    if items.is_empty() {
+        None
+    } else {
+        Some(Group::new(t("SSH Hosts"), items))
+    }
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for an SSH host item: `(id, title)`.
+pub fn ssh_actions(terminal: &Terminal) -> Vec<(&'static str, String)> {
+    vec![
+        (
+            "open",
+            t_args("Open in {app}", &[("app", terminal.app_name())]),
+        ),
+        ("copy_command", t("Copy SSH Command")),
+    ]
+}
+
+/// Run an SSH action on the item it was offered for.
+pub fn run_ssh_action(action_id: &str, item: &Item, terminal: &Terminal) -> PluginResult<()> {
+    let host = host_from_item(item)
+        .ok_or_else(|| PluginError::Command("Item is not an SSH host".to_string()))?;
+
+    match action_id {
+        "open" => terminal.open(&host),
+        "copy_command" => crate::desktop::current().clipboard_write(&host.command()),
+        _ => Err(PluginError::Command(format!(
+            "Unknown SSH action '{}'",
+            action_id
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "
+# Work
+Include config.d/*
+
+Host prod prod-eu
+    HostName 10.0.0.5
+    User deploy
+    Port 2222
+
+Host bastion
+  HostName=bastion.example.com
+  User admin
+  Port 22
+
+Host *.internal !secret
+    User ops
+
+Match host foo
+    User nobody
+
+Host *
+    User fallback
+";
+
+    #[test]
+    fn test_parse_ssh_config() {
+        let (hosts, includes) = parse_ssh_config(CONFIG);
+        assert_eq!(includes, ["config.d/*"]);
+
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, ["prod", "prod-eu", "bastion"]);
+
+        assert_eq!(hosts[1].hostname.as_deref(), Some("10.0.0.5"));
+        assert_eq!(hosts[1].user.as_deref(), Some("deploy"));
+        assert_eq!(hosts[1].port, Some(2222));
+
+        // Port 22 is the default and isn't shown
+        assert_eq!(hosts[2].hostname.as_deref(), Some("bastion.example.com"));
+        assert_eq!(hosts[2].port, None);
+        assert_eq!(hosts[2].command(), "ssh bastion");
+    }
+
+    #[test]
+    fn test_parse_known_hosts() {
+        let text = "\
+github.com,140.82.121.4 ssh-ed25519 AAAA
+[git.example.com]:7999 ssh-rsa AAAA
+|1|abc=|def= ssh-ed25519 AAAA
+@cert-authority *.example.com ssh-rsa AAAA
+192.168.1.10 ssh-ed25519 AAAA
+";
+        let hosts = parse_known_hosts(text);
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, ["github.com", "git.example.com"]);
+        assert_eq!(hosts[1].port, Some(7999));
+        assert_eq!(hosts[1].command(), "ssh -p 7999 git.example.com");
+    }
+
+    #[test]
+    fn test_matching_hosts() {
+        let (hosts, _) = parse_ssh_config(CONFIG);
+        let names = |query: &str| -> Vec<String> {
+            matching_hosts(hosts.clone(), query)
+                .into_iter()
+                .map(|h| h.alias)
+                .collect()
+        };
+
+        assert_eq!(names("ssh"), ["prod", "prod-eu", "bastion"]);
+        assert_eq!(names("ssh eu"), ["prod-eu"]);
+        assert_eq!(names("bas"), ["bastion"]);
+        assert_eq!(names("10.0"), ["prod", "prod-eu"]);
+        assert!(names("b").is_empty());
+        assert!(names("sshx").is_empty());
+    }
+
+    #[test]
+    fn test_host_item_round_trip() {
+        let (hosts, _) = parse_ssh_config(CONFIG);
+        let item = host_item(&hosts[0]);
+        assert_eq!(item.accessories, ["deploy", ":2222"]);
+        assert_eq!(item.subtitle.as_deref(), Some("10.0.0.5"));
+        assert_eq!(host_from_item(&item), Some(hosts[0].clone()));
+    }
+
+    #[test]
+    fn test_terminal_names_and_urls() {
+        assert_eq!(Terminal::from_name("iTerm2"), Terminal::ITerm);
+        assert_eq!(
+            Terminal::from_name("Ghostty"),
+            Terminal::Other("Ghostty".to_string())
+        );
+
+        let (hosts, _) = parse_ssh_config(CONFIG);
+        assert_eq!(ssh_url(&hosts[0]), "ssh://deploy@10.0.0.5:2222");
+
+        assert!(is_safe_host("prod-eu.example.com"));
+        assert!(!is_safe_host("prod; rm -rf ~"));
+        assert!(!is_safe_host("-oProxyCommand=x"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Encrypted data files (lux.storage).
+//!
+//! This module provides:
+//! - `read()` / `write()` - Load and save a data file, encrypted when encryption is on
+//! - `set_encrypted()` - Turn encryption on or off, rewriting the data files read so far
+//! - `encrypt()` / `decrypt()` - The file format, for a given key
+//!
+//! The activity log, HTTP history, pins, hidden results, color history,
+//! metrics, collapsed groups, and cached exchange rates are plain JSON files
+//! unless encryption is turned on:
+//!
+//! ```lua
+//! lux.storage.set_encrypted(true)
+//! ```
+//!
+//! Encrypted files are XChaCha20-Poly1305 with a random nonce per write; the
+//! key is generated on first use and kept in the Keychain (Secret Service on
+//! Linux) through `secrets`, where `lux.secrets` can't reach it.
+//!
+//! Reading doesn't depend on the setting: plain files still load after
+//! encryption is turned on, and are rewritten encrypted right away (or on
+//! their next save, for files not read yet).
+//!
+//! A file that can't be decrypted (the key is gone, or it was changed) is
+//! never overwritten, so its data isn't lost to an empty store.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use parking_lot::Mutex;
+
+use crate::secrets;
+
+/// Start of every encrypted file.
+pub const MAGIC: &[u8] = b"LUXENC1\n";
+
+/// Name of the secret holding the key.
+pub const KEY_SECRET: &str = "storage-key";
+
+/// Length of the key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Length of the nonce in bytes.
+const NONCE_LEN: usize = 24;
+
+/// An encryption key.
+pub type Key = [u8; KEY_LEN];
+
+/// Whether files are written encrypted.
+static ENCRYPTED: AtomicBool = AtomicBool::new(false);
+
+/// The key, once read from or stored in the Keychain.
+static KEY: Mutex<Option<Key>> = Mutex::new(None);
+
+/// Data files read or written this session.
+static FILES: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Data files that couldn't be decrypted this session.
+static UNREADABLE: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+// =============================================================================
+// Files
+// =============================================================================
+
+/// The text of the data file at `path`, decrypted if it is encrypted.
+pub fn read(path: &Path) -> io::Result<String> {
+    FILES.lock().insert(path.to_path_buf());
+    let bytes = std::fs::read(path)?;
+    if !is_encrypted_data(&bytes) {
+        return String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+    }
+    let text = key(false).and_then(|key| decrypt(&key, &bytes));
+    if let Err(e) = &text {
+        tracing::warn!("Can't decrypt {}: {}", path.display(), e);
+        UNREADABLE.lock().insert(path.to_path_buf());
+    }
+    text
+}
+
+/// Save `text` to the data file at `path`, encrypted if encryption is on.
+pub fn write(path: &Path, text: &str) -> io::Result<()> {
+    if UNREADABLE.lock().contains(path) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "{} couldn't be decrypted; not overwriting it",
+                path.display()
+            ),
+        ));
+    }
+    FILES.lock().insert(path.to_path_buf());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    if is_encrypted() {
+        std::fs::write(path, encrypt(&key(true)?, text)?)
+    } else {
+        std::fs::write(path, text)
+    }
+}
+
+/// Whether files are written encrypted.
+pub fn is_encrypted() -> bool {
+    ENCRYPTED.load(Ordering::Relaxed)
+}
+
+/// Turn encryption on or off.
+///
+/// Rewrites the data files read or written so far that aren't in the new
+/// format yet, and returns how many it rewrote.
+pub fn set_encrypted(encrypted: bool) -> io::Result<usize> {
+    if encrypted {
+        // Fail before anything is rewritten if the Keychain is unavailable
+        key(true)?;
+    }
+    ENCRYPTED.store(encrypted, Ordering::Relaxed);
+
+    let files: Vec<PathBuf> = FILES.lock().iter().cloned().collect();
+    let mut rewritten = 0;
+    for path in files {
+        let Ok(bytes) = std::fs::read(&path) else {
+            continue;
+        };
+        if is_encrypted_data(&bytes) == encrypted {
+            continue;
+        }
+        write(&path, &read(&path)?)?;
+        rewritten += 1;
+    }
+    Ok(rewritten)
+}
+
+/// Whether `bytes` are the contents of an encrypted file.
+pub fn is_encrypted_data(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+// =============================================================================
+// Format
+// =============================================================================
+
+/// `text` encrypted with `key`: `MAGIC`, then the nonce, then the ciphertext.
+pub fn encrypt(key: &Key, text: &str) -> io::Result<Vec<u8>> {
+    let mut nonce = [0; NONCE_LEN];
+    getrandom::fill(&mut nonce).map_err(|e| io::Error::other(e.to_string()))?;
+    let ciphertext = XChaCha20Poly1305::new(key.into())
+        .encrypt(XNonce::from_slice(&nonce), text.as_bytes())
+        .map_err(|_| io::Error::other("Encryption failed"))?;
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(bytes)
+}
+
+/// The text of `bytes` (from `encrypt()`), if `key` decrypts them.
+pub fn decrypt(key: &Key, bytes: &[u8]) -> io::Result<String> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let rest = bytes
+        .strip_prefix(MAGIC)
+        .ok_or_else(|| invalid("Not an encrypted file"))?;
+    if rest.len() < NONCE_LEN {
+        return Err(invalid("Encrypted file is truncated"));
+    }
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let plaintext = XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| invalid("Wrong key, or the file was changed"))?;
+    String::from_utf8(plaintext).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// The key from the Keychain, generated and stored there first if `create`.
+fn key(create: bool) -> io::Result<Key> {
+    let mut cached = KEY.lock();
+    if let Some(key) = *cached {
+        return Ok(key);
+    }
+
+    let key = match secrets::get(KEY_SECRET).map_err(io::Error::other)? {
+        Some(encoded) => BASE64
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| Key::try_from(bytes).ok())
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "The storage key is malformed")
+            })?,
+        None if create => {
+            let mut key = [0; KEY_LEN];
+            getrandom::fill(&mut key).map_err(|e| io::Error::other(e.to_string()))?;
+            secrets::set(KEY_SECRET, &BASE64.encode(key)).map_err(io::Error::other)?;
+            key
+        }
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "The storage key isn't in the Keychain",
+            ))
+        }
+    };
+    *cached = Some(key);
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypted_files_round_trip() {
+        let key = [7; KEY_LEN];
+        let bytes = encrypt(&key, r#"["README.md"]"#).unwrap();
+        assert!(is_encrypted_data(&bytes));
+        assert!(!bytes.windows(6).any(|w| w == b"README"));
+        assert_eq!(decrypt(&key, &bytes).unwrap(), r#"["README.md"]"#);
+
+        // A fresh nonce each time
+        assert_ne!(
+            encrypt(&key, "same").unwrap(),
+            encrypt(&key, "same").unwrap()
+        );
+
+        assert!(decrypt(&[8; KEY_LEN], &bytes).is_err());
+        let mut changed = bytes.clone();
+        *changed.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, &changed).is_err());
+        assert!(decrypt(&key, MAGIC).is_err());
+        assert!(decrypt(&key, b"[]").is_err());
+    }
+
+    #[test]
+    fn test_plain_files_still_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux").join("pins.json");
+        write(&path, "[]").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+        assert_eq!(read(&path).unwrap(), "[]");
+    }
+}
@@ -0,0 +1,650 @@
+//! Open browser tabs.
+//!
+//! This module provides:
+//! - `Tab` - An open tab in Safari, a Chromium browser, or a DevTools endpoint
+//! - `TabFilter` - Narrow tabs by browser, window, or profile
+//! - `TabSources` / `TabSources::list()` - Where tabs are read from
+//! - `focus_tab()` / `close_tab()` - Switch to or close a tab
+//! - `TabCache` - Short-lived cache so typing doesn't run a script per key
+//!
+//! Safari and Chromium browsers are read through their scripting interfaces
+//! (JXA). AppleScript can't tell Chrome profiles apart, so a browser started
+//! with `--remote-debugging-port` can be listed as a named profile through the
+//! DevTools HTTP endpoint instead; its tabs are then not read by script.
+
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use lux_core::i18n::t_args;
+use lux_core::{DragPayload, Item};
+
+use crate::error::{PluginError, PluginResult};
+use crate::permissions::run_jxa_with_args;
+
+/// Default view ID of the tab switcher (`lux.builtin.tabs()`).
+pub const TABS_VIEW_ID: &str = "builtin.tabs";
+
+/// Type tag for tab items.
+pub const TAB_ITEM_TYPE: &str = "browser_tab";
+
+/// Browsers read by script unless the view sets `browsers`.
+pub const DEFAULT_BROWSERS: &[&str] = &[
+    "Safari",
+    "Google Chrome",
+    "Brave Browser",
+    "Microsoft Edge",
+    "Chromium",
+];
+
+/// Browser a DevTools endpoint belongs to unless it sets `browser`.
+const DEFAULT_DEVTOOLS_BROWSER: &str = "Google Chrome";
+
+/// Connect and read timeout for DevTools requests.
+const DEVTOOLS_TIMEOUT: Duration = Duration::from_millis(500);
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Where a tab was read from (and how to reach it again).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum TabTarget {
+    /// A scripted browser window and 1-based tab index.
+    Script { window_id: i64, index: usize },
+    /// A DevTools target on a local debugging port.
+    DevTools { port: u16, id: String },
+}
+
+/// An open browser tab.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Tab {
+    /// Browser application name.
+    pub browser: String,
+    /// Profile name (DevTools endpoints only).
+    pub profile: Option<String>,
+    /// 1-based window number, front to back (scripted browsers only).
+    pub window: Option<usize>,
+    pub title: String,
+    pub url: String,
+    /// Whether this is the window's current tab.
+    pub active: bool,
+    pub target: TabTarget,
+}
+
+impl Tab {
+    /// Recover the tab from an item built by `tab_item()`.
+    pub fn from_item(item: &Item) -> Option<Self> {
+        serde_json::from_value(item.data.clone()?).ok()
+    }
+
+    /// Title of the group the tab is listed under.
+    pub fn group_title(&self) -> String {
+        match (&self.profile, self.window) {
+            (Some(profile), _) => format!("{} · {}", self.browser, profile),
+            (None, Some(window)) => t_args(
+                "{browser} · Window {number}",
+                &[("browser", &self.browser), ("number", &window.to_string())],
+            ),
+            (None, None) => self.browser.clone(),
+        }
+    }
+}
+
+/// A Chromium browser's DevTools endpoint, listed as a named profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevToolsEndpoint {
+    /// Local debugging port (`--remote-debugging-port`).
+    pub port: u16,
+    /// Profile name shown for its tabs.
+    pub profile: String,
+    /// Browser application name (activated when focusing a tab).
+    pub browser: String,
+}
+
+impl DevToolsEndpoint {
+    /// Create an endpoint for a Google Chrome profile.
+    pub fn new(port: u16, profile: impl Into<String>) -> Self {
+        Self {
+            port,
+            profile: profile.into(),
+            browser: DEFAULT_DEVTOOLS_BROWSER.to_string(),
+        }
+    }
+
+    /// Set the browser application name.
+    pub fn with_browser(mut self, browser: impl Into<String>) -> Self {
+        self.browser = browser.into();
+        self
+    }
+}
+
+// =============================================================================
+// Filters
+// =============================================================================
+
+/// Narrows tabs by browser, window, and profile.
+///
+/// Browser and profile match case-insensitively by substring, so
+/// `browser:chrome` matches "Google Chrome".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TabFilter {
+    pub browser: Option<String>,
+    pub window: Option<usize>,
+    pub profile: Option<String>,
+}
+
+impl TabFilter {
+    /// Apply `browser:`, `window:`, and `profile:` words in `query` on top of
+    /// this filter. Returns the filter and the remaining search text.
+    pub fn with_query(&self, query: &str) -> (TabFilter, String) {
+        let mut filter = self.clone();
+        let mut text = Vec::new();
+
+        for word in query.split_whitespace() {
+            let applied = match word.split_once(':') {
+                Some(("browser", value)) if !value.is_empty() => {
+                    filter.browser = Some(value.to_string());
+                    true
+                }
+                Some(("profile", value)) if !value.is_empty() => {
+                    filter.profile = Some(value.to_string());
+                    true
+                }
+                Some(("window", value)) => match value.parse() {
+                    Ok(window) => {
+                        filter.window = Some(window);
+                        true
+                    }
+                    Err(_) => false,
+                },
+                _ => false,
+            };
+            if !applied {
+                text.push(word);
+            }
+        }
+
+        (filter, text.join(" "))
+    }
+
+    /// Whether `tab` passes the filter.
+    pub fn matches(&self, tab: &Tab) -> bool {
+        let contains =
+            |value: &str, needle: &str| value.to_lowercase().contains(&needle.to_lowercase());
+
+        self.browser
+            .as_deref()
+            .is_none_or(|browser| contains(&tab.browser, browser))
+            && self.window.is_none_or(|window| tab.window == Some(window))
+            && self.profile.as_deref().is_none_or(|profile| {
+                tab.profile
+                    .as_deref()
+                    .is_some_and(|name| contains(name, profile))
+            })
+    }
+}
+
+/// Whether `tab` matches the search text (title or URL).
+pub fn matches_text(tab: &Tab, text: &str) -> bool {
+    let text = text.to_lowercase();
+    text.split_whitespace().all(|word| {
+        tab.title.to_lowercase().contains(word) || tab.url.to_lowercase().contains(word)
+    })
+}
+
+// =============================================================================
+// Sources
+// =============================================================================
+
+/// JXA script listing the tabs of each running browser in `argv`.
+///
+/// Reads titles and URLs a window at a time; one Apple event per tab is too
+/// slow with many tabs open.
+const LIST_TABS_SCRIPT: &str = r#"
+function run(argv) {
+  const tabs = [];
+  for (const name of argv) {
+    try {
+      const app = Application(name);
+      if (!app.running()) continue;
+      const safari = name === 'Safari';
+      app.windows().forEach((win, w) => {
+        try {
+          const titles = safari ? win.tabs.name() : win.tabs.title();
+          const urls = win.tabs.url();
+          const current = safari ? win.currentTab.index() : win.activeTabIndex();
+          titles.forEach((title, i) => tabs.push({
+            browser: name,
+            window: w + 1,
+            window_id: win.id(),
+            index: i + 1,
+            title: title || '',
+            url: urls[i] || '',
+            active: current === i + 1,
+          }));
+        } catch (e) {}
+      });
+    } catch (e) {}
+  }
+  return JSON.stringify(tabs);
+}
+"#;
+
+/// JXA script switching browser `argv[0]` to tab `argv[2]` of window `argv[1]`.
+const FOCUS_TAB_SCRIPT: &str = r#"
+function run(argv) {
+  const app = Application(argv[0]);
+  const win = app.windows.byId(Number(argv[1]));
+  const index = Number(argv[2]);
+  if (argv[0] === 'Safari') win.currentTab = win.tabs[index - 1];
+  else win.activeTabIndex = index;
+  win.index = 1;
+  app.activate();
+  return 'ok';
+}
+"#;
+
+/// JXA script closing tab `argv[2]` of window `argv[1]` in browser `argv[0]`.
+const CLOSE_TAB_SCRIPT: &str = r#"
+function run(argv) {
+  const app = Application(argv[0]);
+  app.windows.byId(Number(argv[1])).tabs[Number(argv[2]) - 1].close();
+  return 'ok';
+}
+"#;
+
+/// A tab as printed by `LIST_TABS_SCRIPT`.
+#[derive(Deserialize)]
+struct ScriptTab {
+    browser: String,
+    window: usize,
+    window_id: i64,
+    index: usize,
+    title: String,
+    url: String,
+    active: bool,
+}
+
+/// A DevTools `/json/list` entry.
+#[derive(Deserialize)]
+struct DevToolsTarget {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+}
+
+/// Where tabs are read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabSources {
+    /// Browsers read by script.
+    pub browsers: Vec<String>,
+    /// DevTools endpoints read over HTTP.
+    pub devtools: Vec<DevToolsEndpoint>,
+}
+
+impl TabSources {
+    /// Read every source.
+    ///
+    /// Browsers that have a DevTools endpoint are not also read by script,
+    /// so their tabs aren't listed twice. An endpoint that doesn't answer
+    /// (its browser isn't running) is skipped.
+    pub fn list(&self) -> PluginResult<Vec<Tab>> {
+        let browsers: Vec<&str> = self
+            .browsers
+            .iter()
+            .filter(|b| !self.devtools.iter().any(|d| &d.browser == *b))
+            .map(String::as_str)
+            .collect();
+
+        let mut tabs = if browsers.is_empty() {
+            Vec::new()
+        } else {
+            parse_script_tabs(&run_jxa_with_args(LIST_TABS_SCRIPT, &browsers)?)?
+        };
+
+        for endpoint in &self.devtools {
+            match devtools_get(endpoint.port, "/json/list") {
+                Ok(body) => tabs.extend(parse_devtools_tabs(&body, endpoint)?),
+                Err(e) => tracing::debug!(
+                    "DevTools endpoint '{}' (port {}) unavailable: {}",
+                    endpoint.profile,
+                    endpoint.port,
+                    e
+                ),
+            }
+        }
+
+        Ok(tabs)
+    }
+}
+
+impl Default for TabSources {
+    fn default() -> Self {
+        Self {
+            browsers: DEFAULT_BROWSERS.iter().map(|b| b.to_string()).collect(),
+            devtools: Vec::new(),
+        }
+    }
+}
+
+/// Parse the output of the tab listing script.
+pub fn parse_script_tabs(output: &str) -> PluginResult<Vec<Tab>> {
+    let output = output.trim();
+    if output.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tabs: Vec<ScriptTab> = serde_json::from_str(output)
+        .map_err(|e| PluginError::Command(format!("Invalid tab list: {}", e)))?;
+
+    Ok(tabs
+        .into_iter()
+        .map(|tab| Tab {
+            browser: tab.browser,
+            profile: None,
+            window: Some(tab.window),
+            title: tab.title,
+            url: tab.url,
+            active: tab.active,
+            target: TabTarget::Script {
+                window_id: tab.window_id,
+                index: tab.index,
+            },
+        })
+        .collect())
+}
+
+/// Parse a DevTools `/json/list` response, keeping page targets.
+pub fn parse_devtools_tabs(body: &str, endpoint: &DevToolsEndpoint) -> PluginResult<Vec<Tab>> {
+    let targets: Vec<DevToolsTarget> = serde_json::from_str(body)
+        .map_err(|e| PluginError::Command(format!("Invalid DevTools target list: {}", e)))?;
+
+    Ok(targets
+        .into_iter()
+        .filter(|target| target.kind == "page")
+        .map(|target| Tab {
+            browser: endpoint.browser.clone(),
+            profile: Some(endpoint.profile.clone()),
+            window: None,
+            title: target.title,
+            url: target.url,
+            active: false,
+            target: TabTarget::DevTools {
+                port: endpoint.port,
+                id: target.id,
+            },
+        })
+        .collect())
+}
+
+/// GET `path` from a local DevTools endpoint and return the response body.
+fn devtools_get(port: u16, path: &str) -> PluginResult<String> {
+    let err = |e: std::io::Error| PluginError::Command(format!("DevTools request failed: {}", e));
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&addr, DEVTOOLS_TIMEOUT).map_err(err)?;
+    stream
+        .set_read_timeout(Some(DEVTOOLS_TIMEOUT))
+        .map_err(err)?;
+    write!(
+        stream,
+        "GET {} HTTP/1.0\r\nHost: localhost:{}\r\n\r\n",
+        path, port
+    )
+    .map_err(err)?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(err)?;
+    parse_http_response(&response)
+}
+
+/// Split an HTTP/1.0 response into its body, failing on a non-2xx status.
+fn parse_http_response(response: &str) -> PluginResult<String> {
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((response, ""));
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_default();
+    if !status.starts_with('2') {
+        return Err(PluginError::Command(format!(
+            "DevTools request failed with status '{}'",
+            status
+        )));
+    }
+    Ok(body.to_string())
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Bring a tab's window to the front, select the tab, and activate the browser.
+pub fn focus_tab(tab: &Tab) -> PluginResult<()> {
+    match &tab.target {
+        TabTarget::Script { window_id, index } => {
+            run_jxa_with_args(
+                FOCUS_TAB_SCRIPT,
+                &[&tab.browser, &window_id.to_string(), &index.to_string()],
+            )?;
+        }
+        TabTarget::DevTools { port, id } => {
+            devtools_get(*port, &format!("/json/activate/{}", id))?;
+            run_jxa_with_args(
+                "function run(argv) { Application(argv[0]).activate(); return 'ok'; }",
+                &[&tab.browser],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Close a tab.
+pub fn close_tab(tab: &Tab) -> PluginResult<()> {
+    match &tab.target {
+        TabTarget::Script { window_id, index } => {
+            run_jxa_with_args(
+                CLOSE_TAB_SCRIPT,
+                &[&tab.browser, &window_id.to_string(), &index.to_string()],
+            )?;
+        }
+        TabTarget::DevTools { port, id } => {
+            devtools_get(*port, &format!("/json/close/{}", id))?;
+        }
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Cache
+// =============================================================================
+
+/// Caches the tab list for a short time.
+///
+/// The tab view lists tabs on every keystroke; reading many windows by
+/// script takes a noticeable moment, so the list is reused until it is `ttl`
+/// old or closing a tab invalidates it.
+pub struct TabCache {
+    ttl: Duration,
+    state: Mutex<Option<(Instant, Vec<Tab>)>>,
+}
+
+impl TabCache {
+    /// Create an empty cache.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Get the cached tabs, calling `fetch` if the cache is stale.
+    pub fn get_with(
+        &self,
+        fetch: impl FnOnce() -> PluginResult<Vec<Tab>>,
+    ) -> PluginResult<Vec<Tab>> {
+        let mut state = self.state.lock();
+        if let Some((at, tabs)) = state.as_ref() {
+            if at.elapsed() < self.ttl {
+                return Ok(tabs.clone());
+            }
+        }
+        let tabs = fetch()?;
+        *state = Some((Instant::now(), tabs.clone()));
+        Ok(tabs)
+    }
+
+    /// Drop the cached tabs (e.g. after closing one).
+    pub fn invalidate(&self) {
+        *self.state.lock() = None;
+    }
+}
+
+impl Default for TabCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2))
+    }
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build the item for a tab.
+pub fn tab_item(tab: &Tab) -> Item {
+    let id = match &tab.target {
+        TabTarget::Script { window_id, index } => {
+            format!("tab:{}:{}:{}", tab.browser, window_id, index)
+        }
+        TabTarget::DevTools { port, id } => format!("tab:{}:{}", port, id),
+    };
+
+    let mut item = Item::new(
+        id,
+        if tab.title.is_empty() {
+            tab.url.clone()
+        } else {
+            tab.title.clone()
+        },
+    )
+    .with_subtitle(tab.url.clone())
+    .with_icon(if tab.active { "🔵" } else { "🌐" })
+    .with_type(TAB_ITEM_TYPE)
+    .with_drag(DragPayload::Url {
+        url: tab.url.clone(),
+    });
+    item.data = serde_json::to_value(tab).ok();
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script_tab(browser: &str, window: usize, title: &str) -> Tab {
+        Tab {
+            browser: browser.to_string(),
+            profile: None,
+            window: Some(window),
+            title: title.to_string(),
+            url: format!("https://example.com/{}", title),
+            active: false,
+            target: TabTarget::Script {
+                window_id: 100 + window as i64,
+                index: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_script_tabs() {
+        let output = r#"[{"browser":"Safari","window":1,"window_id":42,"index":2,
+            "title":"Docs","url":"https://docs.rs","active":true}]"#;
+        let tabs = parse_script_tabs(output).unwrap();
+        assert_eq!(tabs.len(), 1);
+        assert_eq!(tabs[0].window, Some(1));
+        assert!(tabs[0].active);
+        assert_eq!(
+            tabs[0].target,
+            TabTarget::Script {
+                window_id: 42,
+                index: 2
+            }
+        );
+
+        assert!(parse_script_tabs("").unwrap().is_empty());
+        assert!(parse_script_tabs("nope").is_err());
+    }
+
+    #[test]
+    fn test_parse_devtools_tabs() {
+        let body = r#"[
+            {"id":"A1","type":"page","title":"Inbox","url":"https://mail.example.com"},
+            {"id":"B2","type":"service_worker","url":"https://mail.example.com/sw.js"}
+        ]"#;
+        let endpoint = DevToolsEndpoint::new(9222, "Work");
+        let tabs = parse_devtools_tabs(body, &endpoint).unwrap();
+        assert_eq!(tabs.len(), 1);
+        assert_eq!(tabs[0].browser, "Google Chrome");
+        assert_eq!(tabs[0].profile.as_deref(), Some("Work"));
+        assert_eq!(tabs[0].group_title(), "Google Chrome · Work");
+        assert_eq!(
+            tabs[0].target,
+            TabTarget::DevTools {
+                port: 9222,
+                id: "A1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_http_response() {
+        let ok = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n[]";
+        assert_eq!(parse_http_response(ok).unwrap(), "[]");
+        assert!(parse_http_response("HTTP/1.1 404 Not Found\r\n\r\nNo such target").is_err());
+    }
+
+    #[test]
+    fn test_filter_with_query() {
+        let base = TabFilter {
+            browser: Some("chrome".to_string()),
+            ..Default::default()
+        };
+        let (filter, text) = base.with_query("rust window:2 docs window:x");
+        assert_eq!(filter.window, Some(2));
+        assert_eq!(filter.browser.as_deref(), Some("chrome"));
+        assert_eq!(text, "rust docs window:x");
+
+        assert!(filter.matches(&script_tab("Google Chrome", 2, "a")));
+        assert!(!filter.matches(&script_tab("Google Chrome", 1, "a")));
+        assert!(!filter.matches(&script_tab("Safari", 2, "a")));
+
+        // Profile filters only match tabs with a profile
+        let (filter, _) = TabFilter::default().with_query("profile:work");
+        assert!(!filter.matches(&script_tab("Safari", 1, "a")));
+        let work = parse_devtools_tabs(
+            r#"[{"id":"A","type":"page","title":"t","url":"u"}]"#,
+            &DevToolsEndpoint::new(9222, "Work"),
+        )
+        .unwrap();
+        assert!(filter.matches(&work[0]));
+    }
+
+    #[test]
+    fn test_tab_item_round_trip() {
+        let tab = script_tab("Safari", 1, "rust");
+        assert!(matches_text(&tab, "RUST example"));
+        assert!(!matches_text(&tab, "python"));
+
+        let item = tab_item(&tab);
+        assert!(item.has_type(TAB_ITEM_TYPE));
+        assert_eq!(Tab::from_item(&item), Some(tab));
+    }
+}
@@ -0,0 +1,559 @@
+//! Text transformations.
+//!
+//! This module provides:
+//! - `Transform` - Case conversion, slugify, Base64, URL encoding, JSON, and hashes
+//! - `TextSource` / `read_input()` - Text from the clipboard or the frontmost selection
+//! - `url_encode()` / `url_decode()` - Percent-encoding shared with other sources
+//! - `transform_items()` - One row per transformation, previewing its result
+//!
+//! The built-in Transform view (`lux.builtin.transform()`) previews every
+//! transformation of the input; `lux.text.*` exposes the same functions to Lua.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use md5::Md5;
+use sha2::{Digest, Sha256, Sha512};
+use thiserror::Error;
+
+use lux_core::i18n::t;
+use lux_core::Item;
+
+use crate::error::PluginResult;
+
+/// View ID of the built-in Transform view.
+pub const TRANSFORM_VIEW_ID: &str = "builtin.transform";
+
+/// Type tag for transformation results.
+pub const TRANSFORM_ITEM_TYPE: &str = "text_transform";
+
+/// Characters of a result shown in a row's subtitle.
+const PREVIEW_LENGTH: usize = 80;
+
+/// Errors from transformations that can't apply to their input.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TextError {
+    #[error("Not valid Base64")]
+    InvalidBase64,
+
+    #[error("Decoded text is not UTF-8")]
+    InvalidUtf8,
+
+    #[error("Not valid percent-encoding")]
+    InvalidPercentEncoding,
+
+    #[error("Not valid JSON: {0}")]
+    InvalidJson(String),
+}
+
+// =============================================================================
+// Transforms
+// =============================================================================
+
+/// A text transformation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    Upper,
+    Lower,
+    Title,
+    Camel,
+    Snake,
+    Kebab,
+    Slug,
+    Base64Encode,
+    Base64Decode,
+    UrlEncode,
+    UrlDecode,
+    JsonPretty,
+    JsonMinify,
+    Md5,
+    Sha256,
+    Sha512,
+}
+
+impl Transform {
+    /// All transformations, in the order the Transform view lists them.
+    pub const ALL: [Transform; 16] = [
+        Transform::Upper,
+        Transform::Lower,
+        Transform::Title,
+        Transform::Camel,
+        Transform::Snake,
+        Transform::Kebab,
+        Transform::Slug,
+        Transform::Base64Encode,
+        Transform::Base64Decode,
+        Transform::UrlEncode,
+        Transform::UrlDecode,
+        Transform::JsonPretty,
+        Transform::JsonMinify,
+        Transform::Md5,
+        Transform::Sha256,
+        Transform::Sha512,
+    ];
+
+    /// ID (also the `lux.text` function name).
+    pub fn id(self) -> &'static str {
+        match self {
+            Transform::Upper => "upper",
+            Transform::Lower => "lower",
+            Transform::Title => "title",
+            Transform::Camel => "camel",
+            Transform::Snake => "snake",
+            Transform::Kebab => "kebab",
+            Transform::Slug => "slug",
+            Transform::Base64Encode => "base64_encode",
+            Transform::Base64Decode => "base64_decode",
+            Transform::UrlEncode => "url_encode",
+            Transform::UrlDecode => "url_decode",
+            Transform::JsonPretty => "json_pretty",
+            Transform::JsonMinify => "json_minify",
+            Transform::Md5 => "md5",
+            Transform::Sha256 => "sha256",
+            Transform::Sha512 => "sha512",
+        }
+    }
+
+    /// Parse a transformation ID.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|transform| transform.id() == id)
+    }
+
+    /// Display name.
+    pub fn title(self) -> String {
+        match self {
+            Transform::Upper => t("UPPERCASE"),
+            Transform::Lower => t("lowercase"),
+            Transform::Title => t("Title Case"),
+            Transform::Camel => "camelCase".to_string(),
+            Transform::Snake => "snake_case".to_string(),
+            Transform::Kebab => "kebab-case".to_string(),
+            Transform::Slug => t("Slug"),
+            Transform::Base64Encode => t("Base64 Encode"),
+            Transform::Base64Decode => t("Base64 Decode"),
+            Transform::UrlEncode => t("URL Encode"),
+            Transform::UrlDecode => t("URL Decode"),
+            Transform::JsonPretty => t("Pretty-Print JSON"),
+            Transform::JsonMinify => t("Minify JSON"),
+            Transform::Md5 => "MD5".to_string(),
+            Transform::Sha256 => "SHA-256".to_string(),
+            Transform::Sha512 => "SHA-512".to_string(),
+        }
+    }
+
+    /// Apply the transformation.
+    pub fn apply(self, text: &str) -> Result<String, TextError> {
+        Ok(match self {
+            Transform::Upper => text.to_uppercase(),
+            Transform::Lower => text.to_lowercase(),
+            Transform::Title => words(text)
+                .iter()
+                .map(|word| capitalize(word))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Transform::Camel => words(text)
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            Transform::Snake => join_lower(text, "_"),
+            Transform::Kebab => join_lower(text, "-"),
+            Transform::Slug => slugify(text),
+            Transform::Base64Encode => BASE64.encode(text),
+            Transform::Base64Decode => {
+                let bytes = BASE64
+                    .decode(text.trim())
+                    .map_err(|_| TextError::InvalidBase64)?;
+                String::from_utf8(bytes).map_err(|_| TextError::InvalidUtf8)?
+            }
+            Transform::UrlEncode => url_encode(text),
+            Transform::UrlDecode => url_decode(text)?,
+            Transform::JsonPretty | Transform::JsonMinify => {
+                let value: serde_json::Value = serde_json::from_str(text)
+                    .map_err(|e| TextError::InvalidJson(e.to_string()))?;
+                let formatted = if self == Transform::JsonPretty {
+                    serde_json::to_string_pretty(&value)
+                } else {
+                    serde_json::to_string(&value)
+                };
+                formatted.map_err(|e| TextError::InvalidJson(e.to_string()))?
+            }
+            Transform::Md5 => format!("{:x}", Md5::digest(text)),
+            Transform::Sha256 => format!("{:x}", Sha256::digest(text)),
+            Transform::Sha512 => format!("{:x}", Sha512::digest(text)),
+        })
+    }
+}
+
+/// Split text into words at spaces, punctuation, and camelCase boundaries.
+fn words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        // "fooBar" splits before the capital, "HTTPServer" before the last one
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1);
+        let boundary = c.is_uppercase()
+            && match prev {
+                Some(p) if p.is_lowercase() || p.is_numeric() => true,
+                Some(p) if p.is_uppercase() => next.is_some_and(|n| n.is_lowercase()),
+                _ => false,
+            };
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+fn join_lower(text: &str, separator: &str) -> String {
+    words(text)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Lowercase ASCII words joined by `-`, with accents removed.
+fn slugify(text: &str) -> String {
+    let folded: String = text.chars().map(fold_accent).collect();
+    let mut slug = String::new();
+    for c in folded.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Replace common accented Latin letters with their base letter.
+fn fold_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => 'A',
+        'ç' => 'c',
+        'Ç' => 'C',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'È' | 'É' | 'Ê' | 'Ë' => 'E',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+        'ñ' => 'n',
+        'Ñ' => 'N',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => 'O',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+        'ý' | 'ÿ' => 'y',
+        'Ý' => 'Y',
+        _ => c,
+    }
+}
+
+/// Percent-encode everything but unreserved characters (RFC 3986).
+pub fn url_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Decode percent-encoding (and `+` as a space, as in query strings).
+pub fn url_decode(text: &str) -> Result<String, TextError> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = text
+                    .get(i + 1..i + 3)
+                    .ok_or(TextError::InvalidPercentEncoding)?;
+                let byte =
+                    u8::from_str_radix(hex, 16).map_err(|_| TextError::InvalidPercentEncoding)?;
+                decoded.push(byte);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| TextError::InvalidUtf8)
+}
+
+// =============================================================================
+// Input
+// =============================================================================
+
+/// Where the Transform view reads its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextSource {
+    /// The selection, or the clipboard when nothing is selected.
+    #[default]
+    Auto,
+    Clipboard,
+    Selection,
+}
+
+impl TextSource {
+    /// Parse a source name as used from Lua.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(TextSource::Auto),
+            "clipboard" => Some(TextSource::Clipboard),
+            "selection" => Some(TextSource::Selection),
+            _ => None,
+        }
+    }
+}
+
+/// Read the input text.
+///
+/// `bundle_id` is the app that was frontmost when the launcher opened; its
+/// selection is read through the Accessibility API.
+pub fn read_input(source: TextSource, bundle_id: Option<&str>) -> PluginResult<Option<String>> {
+    let selection = || selected_text(bundle_id).map(|text| text.filter(|t| !t.is_empty()));
+    match source {
+        TextSource::Clipboard => crate::desktop::current().clipboard_read(),
+        TextSource::Selection => selection(),
+        TextSource::Auto => match selection().ok().flatten() {
+            Some(text) => Ok(Some(text)),
+            None => crate::desktop::current().clipboard_read(),
+        },
+    }
+}
+
+/// The text selected in an app (the frontmost app when `bundle_id` is None).
+#[cfg(not(target_os = "linux"))]
+pub fn selected_text(bundle_id: Option<&str>) -> PluginResult<Option<String>> {
+    let script = "function run(argv) { \
+        const se = Application('System Events'); \
+        const procs = argv[0] ? se.processes.whose({ bundleIdentifier: argv[0] }) \
+                              : se.processes.whose({ frontmost: true }); \
+        if (procs.length === 0) return ''; \
+        try { \
+            const focused = procs[0].attributes.byName('AXFocusedUIElement').value(); \
+            return focused.attributes.byName('AXSelectedText').value() || ''; \
+        } catch (e) { return ''; } }";
+    let output = crate::permissions::run_jxa_with_args(script, &[bundle_id.unwrap_or("")])?;
+    // osascript ends the result with a newline
+    let text = output.strip_suffix('\n').unwrap_or(&output);
+    Ok((!text.is_empty()).then(|| text.to_string()))
+}
+
+/// The primary selection (X11 and Wayland have no per-app selection).
+#[cfg(target_os = "linux")]
+pub fn selected_text(_bundle_id: Option<&str>) -> PluginResult<Option<String>> {
+    use std::process::{Command, Stdio};
+
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some_and(|d| !d.is_empty());
+    let mut cmd = if wayland {
+        let mut cmd = Command::new("wl-paste");
+        cmd.args(["--primary", "--no-newline"]);
+        cmd
+    } else {
+        let mut cmd = Command::new("xclip");
+        cmd.args(["-selection", "primary", "-out"]);
+        cmd
+    };
+    let output = cmd
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| crate::error::PluginError::Command(format!("Selection read failed: {}", e)))?;
+    let text = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok((output.status.success() && !text.is_empty()).then_some(text))
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Build one row per transformation whose title matches `query`.
+///
+/// The subtitle previews the result (or why it failed); the full result is
+/// kept in the item's data.
+pub fn transform_items(input: &str, query: &str) -> Vec<Item> {
+    let query = query.trim().to_lowercase();
+    Transform::ALL
+        .into_iter()
+        .filter(|transform| {
+            transform.title().to_lowercase().contains(&query) || transform.id().contains(&query)
+        })
+        .map(|transform| {
+            let result = transform.apply(input);
+            let subtitle = match &result {
+                Ok(text) => preview(text),
+                Err(e) => e.to_string(),
+            };
+            Item::new(format!("transform:{}", transform.id()), transform.title())
+                .with_subtitle(subtitle)
+                .with_type(TRANSFORM_ITEM_TYPE)
+                .with_data(serde_json::json!({
+                    "transform": transform.id(),
+                    "result": result.ok(),
+                }))
+        })
+        .collect()
+}
+
+/// The full result of a transformation row (None when it failed).
+pub fn transform_result(item: &Item) -> Option<String> {
+    item.data
+        .as_ref()
+        .and_then(|data| data.get("result")?.as_str())
+        .map(String::from)
+}
+
+/// First line of a result, shortened to fit a subtitle.
+fn preview(text: &str) -> String {
+    let line = text.lines().next().unwrap_or("");
+    let mut preview: String = line.chars().take(PREVIEW_LENGTH).collect();
+    if preview.len() < line.len() || line.len() < text.trim_end().len() {
+        preview.push('…');
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(transform: Transform, text: &str) -> String {
+        transform.apply(text).unwrap()
+    }
+
+    #[test]
+    fn test_case_conversions() {
+        let text = "parseHTTPResponse for user_id";
+        assert_eq!(
+            apply(Transform::Snake, text),
+            "parse_http_response_for_user_id"
+        );
+        assert_eq!(apply(Transform::Kebab, "Hello, World"), "hello-world");
+        assert_eq!(apply(Transform::Camel, "hello big-world"), "helloBigWorld");
+        assert_eq!(
+            apply(Transform::Title, "the QUICK brown_fox"),
+            "The Quick Brown Fox"
+        );
+        assert_eq!(apply(Transform::Upper, "straße"), "STRASSE");
+        assert_eq!(apply(Transform::Snake, "fooBar2Baz"), "foo_bar2_baz");
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            apply(Transform::Slug, "  Café au Lait: Ça va?  "),
+            "cafe-au-lait-ca-va"
+        );
+        assert_eq!(apply(Transform::Slug, "---"), "");
+    }
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(apply(Transform::Base64Encode, "lux ✨"), "bHV4IOKcqA==");
+        assert_eq!(apply(Transform::Base64Decode, " bHV4IOKcqA==\n"), "lux ✨");
+        assert_eq!(
+            Transform::Base64Decode.apply("not base64!"),
+            Err(TextError::InvalidBase64)
+        );
+
+        assert_eq!(
+            apply(Transform::UrlEncode, "a b&c=✓"),
+            "a%20b%26c%3D%E2%9C%93"
+        );
+        assert_eq!(apply(Transform::UrlDecode, "a%20b+c%E2%9C%93"), "a b c✓");
+        assert_eq!(
+            Transform::UrlDecode.apply("100%"),
+            Err(TextError::InvalidPercentEncoding)
+        );
+    }
+
+    #[test]
+    fn test_json_and_hashes() {
+        assert_eq!(
+            apply(Transform::JsonPretty, r#"{"a":[1]}"#),
+            "{\n  \"a\": [\n    1\n  ]\n}"
+        );
+        assert_eq!(apply(Transform::JsonMinify, "{ \"a\" : 1 }"), r#"{"a":1}"#);
+        assert!(matches!(
+            Transform::JsonPretty.apply("{"),
+            Err(TextError::InvalidJson(_))
+        ));
+
+        assert_eq!(
+            apply(Transform::Md5, "abc"),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        assert_eq!(
+            apply(Transform::Sha256, "abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(apply(Transform::Sha512, "").len(), 128);
+    }
+
+    #[test]
+    fn test_transform_items() {
+        let items = transform_items("{\"a\": 1}", "json");
+        let ids: Vec<&str> = items.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["transform:json_pretty", "transform:json_minify"]);
+        assert_eq!(items[0].subtitle.as_deref(), Some("{…"));
+        assert_eq!(transform_result(&items[1]).as_deref(), Some(r#"{"a":1}"#));
+
+        let failed = &transform_items("%zz", "url decode")[0];
+        assert_eq!(
+            failed.subtitle.as_deref(),
+            Some("Not valid percent-encoding")
+        );
+        assert_eq!(transform_result(failed), None);
+
+        assert_eq!(transform_items("x", "").len(), Transform::ALL.len());
+    }
+
+    #[test]
+    fn test_ids_round_trip() {
+        for transform in Transform::ALL {
+            assert_eq!(Transform::from_id(transform.id()), Some(transform));
+        }
+    }
+}
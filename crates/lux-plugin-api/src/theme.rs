@@ -0,0 +1,374 @@
+//! Theme overrides set from Lua.
+//!
+//! This module provides:
+//! - `ThemeOverrides` - Settings that replace the UI's defaults and system state
+//! - `ThemeStore` - Storage for the overrides (`lux.theme.set/get/reset`)
+//! - `DisplayPreferences` - Density and scale chosen in the Preferences view, saved to the data directory
+//!
+//! The UI reads the overrides each time the launcher opens, so changes apply
+//! on the next open. `None` fields follow the defaults or the system settings
+//! (appearance, Reduce Motion, Increase Contrast). Display preferences win
+//! over `lux.theme.set()`, being the last explicit choice.
+
+use std::path::{Path, PathBuf};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// ID of the "Compact Mode" row in the preferences view.
+pub const COMPACT_ITEM_ID: &str = "compact";
+
+/// ID of the "Interface Scale" row in the preferences view.
+pub const SCALE_ITEM_ID: &str = "scale";
+
+/// Scales the preferences view steps through with "Larger" and "Smaller".
+pub const SCALE_STEPS: [f32; 6] = [0.85, 1.0, 1.15, 1.3, 1.5, 1.75];
+
+/// Appearance override.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeAppearance {
+    Light,
+    Dark,
+    System,
+}
+
+impl ThemeAppearance {
+    /// Parse "light", "dark", or "system".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            "system" => Some(Self::System),
+            _ => None,
+        }
+    }
+
+    /// Name as accepted by `from_name`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::System => "system",
+        }
+    }
+}
+
+/// UI density: how tightly rows and paddings are packed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeDensity {
+    Compact,
+    Comfortable,
+}
+
+impl ThemeDensity {
+    /// Parse "compact" or "comfortable".
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "compact" => Some(Self::Compact),
+            "comfortable" => Some(Self::Comfortable),
+            _ => None,
+        }
+    }
+
+    /// Name as accepted by `from_name`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Compact => "compact",
+            Self::Comfortable => "comfortable",
+        }
+    }
+}
+
+/// Theme settings overridden from Lua.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemeOverrides {
+    /// Light, dark, or follow the system.
+    pub appearance: Option<ThemeAppearance>,
+    /// Accent hue in degrees (0-360).
+    pub accent_hue: Option<f32>,
+    /// Main font family.
+    pub font_family: Option<String>,
+    /// Base font size in points.
+    pub font_size: Option<f32>,
+    /// Disable animations regardless of the system "Reduce Motion" setting.
+    pub reduce_motion: Option<bool>,
+    /// Raise contrast regardless of the system "Increase Contrast" setting.
+    pub increase_contrast: Option<bool>,
+    /// Compact or comfortable rows.
+    pub density: Option<ThemeDensity>,
+    /// Scale factor for row heights, paddings, and font sizes together (0.5-2).
+    pub scale: Option<f32>,
+}
+
+impl ThemeOverrides {
+    /// Check that the values are in range.
+    pub fn validate(&self) -> Result<(), ThemeError> {
+        if let Some(hue) = self.accent_hue {
+            if !(0.0..=360.0).contains(&hue) {
+                return Err(ThemeError::InvalidAccentHue(hue));
+            }
+        }
+        if let Some(size) = self.font_size {
+            if !(6.0..=72.0).contains(&size) {
+                return Err(ThemeError::InvalidFontSize(size));
+            }
+        }
+        if let Some(scale) = self.scale {
+            if !(0.5..=2.0).contains(&scale) {
+                return Err(ThemeError::InvalidScale(scale));
+            }
+        }
+        Ok(())
+    }
+
+    /// Overlay `other` onto these overrides (fields set in `other` win).
+    pub fn merge(&mut self, other: ThemeOverrides) {
+        self.appearance = other.appearance.or(self.appearance);
+        self.accent_hue = other.accent_hue.or(self.accent_hue);
+        self.font_family = other.font_family.or(self.font_family.take());
+        self.font_size = other.font_size.or(self.font_size);
+        self.reduce_motion = other.reduce_motion.or(self.reduce_motion);
+        self.increase_contrast = other.increase_contrast.or(self.increase_contrast);
+        self.density = other.density.or(self.density);
+        self.scale = other.scale.or(self.scale);
+    }
+}
+
+/// Density and scale chosen in the Preferences view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DisplayPreferences {
+    /// Compact or comfortable rows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<ThemeDensity>,
+    /// Scale factor (one of `SCALE_STEPS`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+}
+
+impl DisplayPreferences {
+    /// The scale one step up (`larger`) or down from the current one.
+    pub fn step_scale(&self, larger: bool) -> f32 {
+        let current = self.scale.unwrap_or(1.0);
+        let next = if larger {
+            SCALE_STEPS.iter().find(|&&step| step > current + 0.001)
+        } else {
+            SCALE_STEPS
+                .iter()
+                .rev()
+                .find(|&&step| step < current - 0.001)
+        };
+        next.copied().unwrap_or(current)
+    }
+}
+
+/// Storage for theme overrides.
+pub struct ThemeStore {
+    overrides: RwLock<ThemeOverrides>,
+    /// Saved display preferences, applied over the overrides.
+    preferences: RwLock<DisplayPreferences>,
+    path: Option<PathBuf>,
+}
+
+impl ThemeStore {
+    /// Create a store with no overrides, keeping display preferences in memory.
+    pub fn new() -> Self {
+        Self {
+            overrides: RwLock::new(ThemeOverrides::default()),
+            preferences: RwLock::new(DisplayPreferences::default()),
+            path: None,
+        }
+    }
+
+    /// Load display preferences from `path` and save them there on change.
+    pub fn with_path(mut self, path: Option<PathBuf>) -> Self {
+        *self.preferences.get_mut() = path.as_deref().map(load_preferences).unwrap_or_default();
+        self.path = path;
+        self
+    }
+
+    /// Where display preferences are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("display.json"))
+    }
+
+    /// Validate and merge overrides into the store.
+    pub fn set(&self, overrides: ThemeOverrides) -> Result<(), ThemeError> {
+        overrides.validate()?;
+        self.overrides.write().merge(overrides);
+        Ok(())
+    }
+
+    /// Get the current overrides, with the display preferences applied.
+    pub fn get(&self) -> ThemeOverrides {
+        let preferences = *self.preferences.read();
+        let mut overrides = self.overrides.read().clone();
+        overrides.merge(ThemeOverrides {
+            density: preferences.density,
+            scale: preferences.scale,
+            ..Default::default()
+        });
+        overrides
+    }
+
+    /// Get the display preferences.
+    pub fn preferences(&self) -> DisplayPreferences {
+        *self.preferences.read()
+    }
+
+    /// Validate, store, and save display preferences (`None` fields are cleared).
+    pub fn set_preferences(&self, preferences: DisplayPreferences) -> Result<(), ThemeError> {
+        ThemeOverrides {
+            scale: preferences.scale,
+            ..Default::default()
+        }
+        .validate()?;
+        *self.preferences.write() = preferences;
+        if let Some(path) = &self.path {
+            if let Err(e) = save_preferences(path, &preferences) {
+                tracing::warn!("Failed to save display preferences: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Clear all overrides (display preferences are kept).
+    pub fn reset(&self) {
+        *self.overrides.write() = ThemeOverrides::default();
+    }
+}
+
+impl Default for ThemeStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors that can occur when setting theme overrides.
+#[derive(Debug, thiserror::Error)]
+pub enum ThemeError {
+    #[error("Invalid accent hue {0} (expected 0-360)")]
+    InvalidAccentHue(f32),
+
+    #[error("Invalid font size {0} (expected 6-72)")]
+    InvalidFontSize(f32),
+
+    #[error("Invalid scale {0} (expected 0.5-2)")]
+    InvalidScale(f32),
+}
+
+fn load_preferences(path: &Path) -> DisplayPreferences {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_preferences(path: &Path, preferences: &DisplayPreferences) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string(preferences)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_merges_overrides() {
+        let store = ThemeStore::new();
+        store
+            .set(ThemeOverrides {
+                appearance: Some(ThemeAppearance::Dark),
+                reduce_motion: Some(true),
+                ..Default::default()
+            })
+            .unwrap();
+        store
+            .set(ThemeOverrides {
+                increase_contrast: Some(true),
+                reduce_motion: Some(false),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let overrides = store.get();
+        assert_eq!(overrides.appearance, Some(ThemeAppearance::Dark));
+        assert_eq!(overrides.reduce_motion, Some(false));
+        assert_eq!(overrides.increase_contrast, Some(true));
+
+        store.reset();
+        assert_eq!(store.get(), ThemeOverrides::default());
+    }
+
+    #[test]
+    fn test_set_validates() {
+        let store = ThemeStore::new();
+        assert!(matches!(
+            store.set(ThemeOverrides {
+                accent_hue: Some(400.0),
+                ..Default::default()
+            }),
+            Err(ThemeError::InvalidAccentHue(_))
+        ));
+        assert!(matches!(
+            store.set(ThemeOverrides {
+                font_size: Some(0.0),
+                ..Default::default()
+            }),
+            Err(ThemeError::InvalidFontSize(_))
+        ));
+        assert_eq!(store.get(), ThemeOverrides::default());
+
+        assert_eq!(
+            ThemeAppearance::from_name("dark"),
+            Some(ThemeAppearance::Dark)
+        );
+        assert_eq!(ThemeAppearance::from_name("auto"), None);
+        assert!(matches!(
+            store.set(ThemeOverrides {
+                scale: Some(3.0),
+                ..Default::default()
+            }),
+            Err(ThemeError::InvalidScale(_))
+        ));
+    }
+
+    #[test]
+    fn test_display_preferences_win_and_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("display.json");
+        let store = ThemeStore::new().with_path(Some(path.clone()));
+        store
+            .set(ThemeOverrides {
+                density: Some(ThemeDensity::Comfortable),
+                scale: Some(1.2),
+                ..Default::default()
+            })
+            .unwrap();
+        store
+            .set_preferences(DisplayPreferences {
+                density: Some(ThemeDensity::Compact),
+                scale: None,
+            })
+            .unwrap();
+
+        let overrides = store.get();
+        assert_eq!(overrides.density, Some(ThemeDensity::Compact));
+        assert_eq!(overrides.scale, Some(1.2));
+
+        let reloaded = ThemeStore::new().with_path(Some(path));
+        assert_eq!(reloaded.preferences().density, Some(ThemeDensity::Compact));
+        assert_eq!(reloaded.preferences().step_scale(true), 1.15);
+        assert_eq!(reloaded.preferences().step_scale(false), 0.85);
+        assert_eq!(
+            DisplayPreferences {
+                scale: Some(0.85),
+                ..Default::default()
+            }
+            .step_scale(false),
+            0.85
+        );
+    }
+}
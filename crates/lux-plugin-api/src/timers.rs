@@ -0,0 +1,288 @@
+//! Countdown timers.
+//!
+//! This module provides:
+//! - `TimerRegistry` - Running timers started via `lux.timers.start()`
+//! - `parse_duration()` - "25m", "1h30m", "90s", "5:00"
+//! - `format_remaining()` - "24:59" / "1:02:03" countdown text
+//!
+//! Timers live in the registry rather than the UI so they keep running while
+//! the launcher is hidden. The UI ticks the backend once a second, which
+//! fires finished timers and refreshes the menu-bar countdown.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+use crate::types::LuaFunctionRef;
+
+/// Type tag for timer items (used to route them to the built-in action).
+pub const TIMER_ITEM_TYPE: &str = "timer";
+
+/// Lua registry key of the built-in "cancel timer" action handler.
+pub const TIMER_CANCEL_KEY: &str = "timer:cancel";
+
+/// A running countdown timer.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    /// Unique timer ID (returned to Lua).
+    pub id: u64,
+    /// User-facing label.
+    pub label: String,
+    /// Total duration.
+    pub duration: Duration,
+    /// When the timer was started.
+    pub started_at: Instant,
+    /// Lua function called when the timer finishes.
+    pub on_done: Option<LuaFunctionRef>,
+}
+
+impl Timer {
+    /// Time left at `now` (zero once finished).
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.duration
+            .saturating_sub(now.saturating_duration_since(self.started_at))
+    }
+
+    /// Whether the timer has finished at `now`.
+    pub fn is_finished(&self, now: Instant) -> bool {
+        self.remaining(now).is_zero()
+    }
+
+    /// Build the root-view item for this timer.
+    pub fn item(&self, now: Instant) -> Item {
+        Item::new(format!("timer:{}", self.id), self.label.clone())
+            .with_subtitle(t_args(
+                "{time} remaining",
+                &[("time", &format_remaining(self.remaining(now)))],
+            ))
+            .with_icon("⏱")
+            .with_type(TIMER_ITEM_TYPE)
+            .with_data(serde_json::json!({ "timer_id": self.id }))
+    }
+}
+
+/// Registry for running timers.
+pub struct TimerRegistry {
+    /// Timers ordered by start.
+    timers: RwLock<Vec<Timer>>,
+    next_id: AtomicU64,
+}
+
+impl TimerRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            timers: RwLock::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Start a timer. Returns its ID.
+    pub fn start(
+        &self,
+        duration: Duration,
+        label: impl Into<String>,
+        on_done: Option<LuaFunctionRef>,
+    ) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.timers.write().push(Timer {
+            id,
+            label: label.into(),
+            duration,
+            started_at: Instant::now(),
+            on_done,
+        });
+        id
+    }
+
+    /// Cancel a timer. Returns the removed timer, if it was running.
+    pub fn cancel(&self, id: u64) -> Option<Timer> {
+        let mut timers = self.timers.write();
+        let index = timers.iter().position(|t| t.id == id)?;
+        Some(timers.remove(index))
+    }
+
+    /// List running timers.
+    pub fn list(&self) -> Vec<Timer> {
+        self.timers.read().clone()
+    }
+
+    /// Number of running timers.
+    pub fn len(&self) -> usize {
+        self.timers.read().len()
+    }
+
+    /// Check if no timers are running.
+    pub fn is_empty(&self) -> bool {
+        self.timers.read().is_empty()
+    }
+
+    /// Remove and return the timers that have finished at `now`.
+    pub fn take_finished(&self, now: Instant) -> Vec<Timer> {
+        let mut timers = self.timers.write();
+        let (finished, running) = timers.drain(..).partition(|t| t.is_finished(now));
+        *timers = running;
+        finished
+    }
+
+    /// Menu-bar text for the timer finishing soonest (e.g. "⏱ 24:59").
+    pub fn status_text(&self, now: Instant) -> Option<String> {
+        self.timers
+            .read()
+            .iter()
+            .map(|t| t.remaining(now))
+            .min()
+            .map(|remaining| format!("⏱ {}", format_remaining(remaining)))
+    }
+
+    /// Build the root-view group of running timers, soonest first.
+    ///
+    /// A blank query shows every timer; otherwise only timers whose label
+    /// contains the query.
+    pub fn group(&self, query: &str, now: Instant) -> Option<Group> {
+        let query = query.trim().to_lowercase();
+        let mut timers: Vec<Timer> = self
+            .list()
+            .into_iter()
+            .filter(|t| t.label.to_lowercase().contains(&query))
+            .collect();
+        if timers.is_empty() {
+            return None;
+        }
+        timers.sort_by_key(|t| t.remaining(now));
+        Some(Group::new(
+            t("Timers"),
+            timers.iter().map(|t| t.item(now)).collect(),
+        ))
+    }
+}
+
+impl Default for TimerRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a duration like `"25m"`, `"1h30m"`, `"90s"`, `"90"` (seconds), or
+/// `"5:00"` / `"1:05:00"` (clock style).
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+
+    if text.contains(':') {
+        let mut seconds = 0u64;
+        for part in text.split(':') {
+            seconds = seconds.checked_mul(60)? + part.parse::<u64>().ok()?;
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+
+    if let Ok(seconds) = text.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut seconds = 0u64;
+    let mut number = String::new();
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        seconds += number.parse::<u64>().ok()? * unit;
+        number.clear();
+    }
+    if !number.is_empty() {
+        return None;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Format remaining time as `m:ss`, or `h:mm:ss` from an hour up.
+///
+/// Partial seconds round up, so a timer shows `0:01` until it finishes.
+pub fn format_remaining(remaining: Duration) -> String {
+    let total = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("25m"), Some(Duration::from_secs(1500)));
+        assert_eq!(parse_duration("1h 30m"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("5:00"), Some(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1:05:00"), Some(Duration::from_secs(3900)));
+        assert_eq!(parse_duration("10x"), None);
+        assert_eq!(parse_duration("10m5"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_secs(1500)), "25:00");
+        assert_eq!(format_remaining(Duration::from_millis(200)), "0:01");
+        assert_eq!(format_remaining(Duration::ZERO), "0:00");
+        assert_eq!(format_remaining(Duration::from_secs(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn test_take_finished() {
+        let registry = TimerRegistry::new();
+        let short = registry.start(Duration::from_secs(1), "Tea", None);
+        let long = registry.start(Duration::from_secs(1500), "Focus", None);
+
+        let later = Instant::now() + Duration::from_secs(2);
+        let finished = registry.take_finished(later);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].id, short);
+
+        let remaining = registry.list();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, long);
+    }
+
+    #[test]
+    fn test_status_text_and_group() {
+        let registry = TimerRegistry::new();
+        assert!(registry.status_text(Instant::now()).is_none());
+        assert!(registry.group("", Instant::now()).is_none());
+
+        registry.start(Duration::from_secs(1500), "Focus", None);
+        let id = registry.start(Duration::from_secs(300), "Break", None);
+
+        let now = Instant::now();
+        assert_eq!(registry.status_text(now).as_deref(), Some("⏱ 5:00"));
+
+        let group = registry.group("", now).unwrap();
+        assert_eq!(group.items[0].title, "Break");
+        assert_eq!(group.items[0].subtitle.as_deref(), Some("5:00 remaining"));
+        assert_eq!(registry.group("foc", now).unwrap().len(), 1);
+        assert!(registry.group("tea", now).is_none());
+
+        assert!(registry.cancel(id).is_some());
+        assert!(registry.cancel(id).is_none());
+        assert_eq!(registry.len(), 1);
+    }
+}
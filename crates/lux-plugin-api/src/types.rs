@@ -5,6 +5,8 @@
 //!
 //! Common types (Item, Group, SelectionMode, ActionResult) are re-exported from lux_core.
 
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use mlua::{Function, Lua, Result as LuaResult};
 use serde::{Deserialize, Serialize};
 
@@ -95,8 +97,29 @@ pub struct View {
     /// Submission hook: `on_submit(ctx)`
     pub on_submit_fn: Option<LuaFunctionRef>,
 
+    /// Lifecycle hook run after the view is pushed: `on_open(ctx)`
+    pub on_open_fn: Option<LuaFunctionRef>,
+
+    /// Lifecycle hook run when the launcher is shown on this view: `on_show(ctx)`
+    pub on_show_fn: Option<LuaFunctionRef>,
+
+    /// Lifecycle hook run after the view is popped or replaced: `on_close(ctx)`
+    pub on_close_fn: Option<LuaFunctionRef>,
+
+    /// Multi-select hook run when the selection changes: `on_selection_changed(selection, ctx)`
+    pub on_selection_changed_fn: Option<LuaFunctionRef>,
+
     /// Data available to source and actions.
     pub view_data: serde_json::Value,
+
+    /// IDs of the items selected when the view opens (multi-select views).
+    pub selected: Vec<String>,
+
+    /// Re-run the search every this many milliseconds while the view is shown.
+    pub refresh_ms: Option<u64>,
+
+    /// Query the search input is filled with when the view is pushed.
+    pub initial_query: Option<String>,
 }
 
 impl std::fmt::Debug for View {
@@ -109,38 +132,54 @@ impl std::fmt::Debug for View {
             .field("has_get_actions", &self.get_actions_fn.is_some())
             .field("has_on_select", &self.on_select_fn.is_some())
             .field("has_on_submit", &self.on_submit_fn.is_some())
+            .field("has_on_open", &self.on_open_fn.is_some())
+            .field("has_on_show", &self.on_show_fn.is_some())
+            .field("has_on_close", &self.on_close_fn.is_some())
+            .field(
+                "has_on_selection_changed",
+                &self.on_selection_changed_fn.is_some(),
+            )
             .finish()
     }
 }
 
+/// Source of unique view instance IDs.
+static NEXT_INSTANCE_ID: AtomicU64 = AtomicU64::new(1);
+
 /// A view instance in the view stack.
 ///
 /// Contains the view definition and Lua registry keys for cleanup.
-/// Ephemeral state (cursor, selection, query) is owned by the UI.
+/// Ephemeral state (cursor, selection, query) is owned by the UI, which saves
+/// it here before the view is covered so it can be restored after a pop.
 #[derive(Debug)]
 pub struct ViewInstance {
+    /// Unique ID of this instance, stable while it is on the stack.
+    pub instance_id: u64,
+
     /// The view definition.
     pub view: View,
 
     /// Lua registry keys to clean up when this view is popped.
     /// Used for inline source functions and callbacks.
     pub registry_keys: Vec<String>,
+
+    /// UI state saved by the frontend, restored when the view is shown again.
+    pub saved: Option<SavedViewState>,
 }
 
 impl ViewInstance {
     /// Create a new view instance.
     pub fn new(view: View) -> Self {
-        Self {
-            view,
-            registry_keys: Vec::new(),
-        }
+        Self::with_registry_keys(view, Vec::new())
     }
 
     /// Create a new view instance with registry keys for cleanup.
     pub fn with_registry_keys(view: View, registry_keys: Vec<String>) -> Self {
         Self {
+            instance_id: NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed),
             view,
             registry_keys,
+            saved: None,
         }
     }
 }
@@ -149,12 +188,34 @@ impl ViewInstance {
 // View State (for frontend)
 // =============================================================================
 
+/// Ephemeral UI state of a view, saved so it can be restored after a pop.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SavedViewState {
+    /// Search query.
+    pub query: String,
+
+    /// ID of the item under the cursor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor_item: Option<String>,
+
+    /// Vertical scroll offset of the results list, in pixels.
+    #[serde(default)]
+    pub scroll_offset: f32,
+
+    /// IDs of the selected items (multi-select views).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected: Vec<String>,
+}
+
 /// View configuration state sent to frontend.
 ///
-/// Contains only structural configuration (id, title, placeholder, selection mode).
-/// Ephemeral state (cursor, selection, query) is owned by the UI.
+/// Contains structural configuration (id, title, placeholder, selection mode)
+/// and the UI state last saved for the view instance.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ViewState {
+    /// ID of the view instance this state describes.
+    pub instance_id: u64,
+
     /// View identifier (for keybindings, logging, etc).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
@@ -169,15 +230,42 @@ pub struct ViewState {
 
     /// Selection mode.
     pub selection: SelectionMode,
+
+    /// IDs of the items selected when the view opens.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub selected: Vec<String>,
+
+    /// Whether selection changes should be sent to the view's
+    /// `on_selection_changed` callback.
+    #[serde(default)]
+    pub has_on_selection_changed: bool,
+
+    /// Interval for re-running the search while the view is shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_ms: Option<u64>,
+
+    /// Query the search input is filled with when the view is pushed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_query: Option<String>,
+
+    /// UI state saved before the view was covered by another view.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved: Option<SavedViewState>,
 }
 
 impl From<&ViewInstance> for ViewState {
     fn from(instance: &ViewInstance) -> Self {
         Self {
+            instance_id: instance.instance_id,
             id: instance.view.id.clone(),
             title: instance.view.title.clone(),
             placeholder: instance.view.placeholder.clone(),
             selection: instance.view.selection,
+            selected: instance.view.selected.clone(),
+            has_on_selection_changed: instance.view.on_selection_changed_fn.is_some(),
+            refresh_ms: instance.view.refresh_ms,
+            initial_query: instance.view.initial_query.clone(),
+            saved: instance.saved.clone(),
         }
     }
 }
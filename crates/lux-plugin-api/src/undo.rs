@@ -0,0 +1,295 @@
+//! Undo for file operations and plugin actions.
+//!
+//! This module provides:
+//! - `UndoStack` - Recent undoable operations, newest last
+//! - `UndoOp` - How to revert an operation
+//! - `UndoInfo` - The newest operation, as shown in the launcher's undo toast
+//! - `trash_file()` / `move_file()` / `rename_file()` - File operations that record their undo
+//!
+//! Built-in file operations push an entry when they succeed, and plugins push
+//! their own with `lux.undo.register(description, fn)`. The launcher undoes
+//! the newest entry on Cmd+Z or from the toast shown after the action.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use lux_core::i18n::t_args;
+
+use crate::desktop;
+use crate::error::{PluginError, PluginResult};
+use crate::types::LuaFunctionRef;
+
+/// Most operations kept on the stack; older ones can no longer be undone.
+pub const UNDO_LIMIT: usize = 50;
+
+/// How to revert an operation.
+#[derive(Debug, Clone)]
+pub enum UndoOp {
+    /// Move a file from `from` back to `to` (reverts a trash, move, or rename).
+    MoveBack { from: PathBuf, to: PathBuf },
+    /// Call a plugin's Lua function.
+    Lua(LuaFunctionRef),
+}
+
+/// An operation recorded on the undo stack.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    /// Unique entry ID.
+    pub id: u64,
+    /// What the operation did, e.g. "Moved report.pdf to Trash".
+    pub description: String,
+    /// How to revert it.
+    pub op: UndoOp,
+}
+
+/// The newest undoable operation, for the frontend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoInfo {
+    /// Entry ID, so the frontend can tell a new operation from one it has shown.
+    pub id: u64,
+    /// What the operation did.
+    pub description: String,
+}
+
+/// Recent undoable operations.
+pub struct UndoStack {
+    entries: Mutex<Vec<UndoEntry>>,
+    /// Lua functions of entries dropped past the limit, freed on the next undo.
+    retired: Mutex<Vec<String>>,
+    next_id: AtomicU64,
+}
+
+impl UndoStack {
+    /// Create an empty stack.
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+            retired: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Record an operation. Returns its entry ID.
+    pub fn push(&self, description: impl Into<String>, op: UndoOp) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.entries.lock();
+        entries.push(UndoEntry {
+            id,
+            description: description.into(),
+            op,
+        });
+        if entries.len() > UNDO_LIMIT {
+            let dropped = entries.len() - UNDO_LIMIT;
+            let mut retired = self.retired.lock();
+            for entry in entries.drain(..dropped) {
+                if let UndoOp::Lua(func) = entry.op {
+                    retired.push(func.key);
+                }
+            }
+        }
+        id
+    }
+
+    /// Remove and return the newest entry.
+    pub fn pop(&self) -> Option<UndoEntry> {
+        self.entries.lock().pop()
+    }
+
+    /// The newest entry.
+    pub fn latest(&self) -> Option<UndoInfo> {
+        self.entries.lock().last().map(|entry| UndoInfo {
+            id: entry.id,
+            description: entry.description.clone(),
+        })
+    }
+
+    /// Take the registry keys of Lua functions that can no longer be called.
+    pub fn take_retired(&self) -> Vec<String> {
+        std::mem::take(&mut *self.retired.lock())
+    }
+
+    /// Number of operations that can be undone.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Whether there is nothing to undo.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// File Operations
+// =============================================================================
+
+/// Move a file or folder to the Trash.
+///
+/// The operation can be undone when the platform reports where the item went.
+pub fn trash_file(undo: &UndoStack, path: &str) -> PluginResult<()> {
+    if let Some(trashed) = desktop::current().trash(path)? {
+        undo.push(
+            t_args(
+                "Moved {name} to Trash",
+                &[("name", &file_name(Path::new(path)))],
+            ),
+            UndoOp::MoveBack {
+                from: PathBuf::from(trashed),
+                to: PathBuf::from(path),
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Move a file or folder to `to`, or into it when `to` is a folder.
+///
+/// Never overwrites. Returns the new path.
+pub fn move_file(undo: &UndoStack, from: &str, to: &str) -> PluginResult<PathBuf> {
+    let from = Path::new(from);
+    let mut dest = PathBuf::from(to);
+    if dest.is_dir() {
+        dest.push(from.file_name().unwrap_or_default());
+    }
+    rename_path(from, &dest)?;
+
+    let folder = dest.parent().map(file_name).unwrap_or_default();
+    undo.push(
+        t_args(
+            "Moved {name} to {folder}",
+            &[("name", &file_name(from)), ("folder", &folder)],
+        ),
+        UndoOp::MoveBack {
+            from: dest.clone(),
+            to: from.to_path_buf(),
+        },
+    );
+    Ok(dest)
+}
+
+/// Rename a file or folder within its folder. Never overwrites.
+///
+/// Returns the new path.
+pub fn rename_file(undo: &UndoStack, path: &str, new_name: &str) -> PluginResult<PathBuf> {
+    if new_name.is_empty() || new_name.contains('/') {
+        return Err(PluginError::Command(format!(
+            "Invalid file name '{}'",
+            new_name
+        )));
+    }
+    let path = Path::new(path);
+    let dest = path.with_file_name(new_name);
+    rename_path(path, &dest)?;
+
+    undo.push(
+        t_args(
+            "Renamed {name} to {new_name}",
+            &[("name", &file_name(path)), ("new_name", new_name)],
+        ),
+        UndoOp::MoveBack {
+            from: dest.clone(),
+            to: path.to_path_buf(),
+        },
+    );
+    Ok(dest)
+}
+
+/// Revert a file operation by moving the file back.
+pub fn move_back(from: &Path, to: &Path) -> PluginResult<()> {
+    rename_path(from, to)
+}
+
+/// Rename `from` to `to`, refusing to replace an existing file.
+fn rename_path(from: &Path, to: &Path) -> PluginResult<()> {
+    if to.exists() {
+        return Err(PluginError::Command(format!(
+            "'{}' already exists",
+            to.display()
+        )));
+    }
+    std::fs::rename(from, to).map_err(|e| {
+        PluginError::Command(format!(
+            "Moving '{}' to '{}' failed: {}",
+            from.display(),
+            to.display(),
+            e
+        ))
+    })
+}
+
+/// Display name of a path's last component.
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_stack_drops_oldest_past_limit() {
+        let undo = UndoStack::new();
+        undo.push(
+            "lua",
+            UndoOp::Lua(LuaFunctionRef::new("undo:first".to_string())),
+        );
+        for i in 0..UNDO_LIMIT {
+            undo.push(
+                format!("op {}", i),
+                UndoOp::MoveBack {
+                    from: PathBuf::from("/a"),
+                    to: PathBuf::from("/b"),
+                },
+            );
+        }
+
+        assert_eq!(undo.len(), UNDO_LIMIT);
+        assert_eq!(undo.take_retired(), ["undo:first"]);
+        assert!(undo.take_retired().is_empty());
+
+        let latest = undo.latest().unwrap();
+        assert_eq!(latest.description, format!("op {}", UNDO_LIMIT - 1));
+        assert_eq!(undo.pop().unwrap().id, latest.id);
+    }
+
+    #[test]
+    fn test_move_and_rename_can_be_undone() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("report.txt");
+        let archive = dir.path().join("archive");
+        std::fs::write(&file, b"q3").unwrap();
+        std::fs::create_dir(&archive).unwrap();
+        let undo = UndoStack::new();
+
+        let moved = move_file(&undo, file.to_str().unwrap(), archive.to_str().unwrap()).unwrap();
+        assert_eq!(moved, archive.join("report.txt"));
+        let renamed = rename_file(&undo, moved.to_str().unwrap(), "final.txt").unwrap();
+        assert!(renamed.exists());
+
+        // Renaming onto an existing file is refused
+        std::fs::write(archive.join("taken.txt"), b"").unwrap();
+        assert!(rename_file(&undo, renamed.to_str().unwrap(), "taken.txt").is_err());
+        assert!(rename_file(&undo, renamed.to_str().unwrap(), "a/b").is_err());
+
+        // Undo newest first
+        while let Some(entry) = undo.pop() {
+            let UndoOp::MoveBack { from, to } = entry.op else {
+                panic!("expected a file operation");
+            };
+            move_back(&from, &to).unwrap();
+        }
+        assert!(file.exists());
+        assert!(!renamed.exists());
+    }
+}
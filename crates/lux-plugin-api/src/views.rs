@@ -1,7 +1,8 @@
 //! View Registry for the new Lua API.
 //!
 //! This module provides:
-//! - `ViewDefinition` - A registered view with search and get_actions functions
+//! - `ViewDefinition` - A registered view with search and get_actions functions,
+//!   and optional lifecycle hooks (on_open, on_show, on_close)
 //! - `ViewRegistry` - Storage for registered views
 
 use parking_lot::RwLock;
@@ -9,7 +10,7 @@ use std::collections::HashMap;
 
 use lux_core::SelectionMode;
 
-use crate::types::LuaFunctionRef;
+use crate::types::{LuaFunctionRef, View};
 
 /// A registered view definition.
 ///
@@ -34,6 +35,45 @@ pub struct ViewDefinition {
 
     /// Get actions function: `get_actions(item, ctx) -> { action, ... }`
     pub get_actions_fn: LuaFunctionRef,
+
+    /// Lifecycle hook run after the view is pushed: `on_open(ctx)`
+    pub on_open_fn: Option<LuaFunctionRef>,
+
+    /// Lifecycle hook run when the launcher is shown on this view: `on_show(ctx)`
+    pub on_show_fn: Option<LuaFunctionRef>,
+
+    /// Lifecycle hook run after the view is popped or replaced: `on_close(ctx)`
+    pub on_close_fn: Option<LuaFunctionRef>,
+
+    /// Multi-select hook run when the selection changes: `on_selection_changed(selection, ctx)`
+    pub on_selection_changed_fn: Option<LuaFunctionRef>,
+
+    /// Re-run the search every this many milliseconds while the view is shown.
+    pub refresh_ms: Option<u64>,
+}
+
+impl ViewDefinition {
+    /// Build a `View` that can be pushed onto the view stack.
+    pub fn to_view(&self) -> View {
+        View {
+            id: Some(self.id.clone()),
+            title: self.title.clone(),
+            placeholder: self.placeholder.clone(),
+            source_fn: self.search_fn.clone(),
+            get_actions_fn: Some(self.get_actions_fn.clone()),
+            selection: self.selection,
+            on_select_fn: None,
+            on_submit_fn: None,
+            on_open_fn: self.on_open_fn.clone(),
+            on_show_fn: self.on_show_fn.clone(),
+            on_close_fn: self.on_close_fn.clone(),
+            on_selection_changed_fn: self.on_selection_changed_fn.clone(),
+            view_data: serde_json::Value::Null,
+            selected: Vec::new(),
+            refresh_ms: self.refresh_ms,
+            initial_query: None,
+        }
+    }
 }
 
 /// Registry for storing view definitions.
@@ -157,6 +197,11 @@ mod tests {
             selection: SelectionMode::Single,
             search_fn: make_test_fn_ref("files:search"),
             get_actions_fn: make_test_fn_ref("files:get_actions"),
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
+            refresh_ms: None,
         };
 
         registry.add(view).unwrap();
@@ -179,6 +224,11 @@ mod tests {
             selection: SelectionMode::Single,
             search_fn: make_test_fn_ref("files:search"),
             get_actions_fn: make_test_fn_ref("files:get_actions"),
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
+            refresh_ms: None,
         };
 
         let view2 = ViewDefinition {
@@ -188,6 +238,11 @@ mod tests {
             selection: SelectionMode::Multi,
             search_fn: make_test_fn_ref("files:search2"),
             get_actions_fn: make_test_fn_ref("files:get_actions2"),
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
+            refresh_ms: None,
         };
 
         registry.add(view1).unwrap();
@@ -210,6 +265,11 @@ mod tests {
             selection: SelectionMode::Single,
             search_fn: make_test_fn_ref("files:search"),
             get_actions_fn: make_test_fn_ref("files:get_actions"),
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
+            refresh_ms: None,
         };
 
         let view2 = ViewDefinition {
@@ -219,6 +279,11 @@ mod tests {
             selection: SelectionMode::Single,
             search_fn: make_test_fn_ref("clipboard:search"),
             get_actions_fn: make_test_fn_ref("clipboard:get_actions"),
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
+            refresh_ms: None,
         };
 
         registry.add(view1).unwrap();
@@ -241,6 +306,11 @@ mod tests {
             selection: SelectionMode::Multi,
             search_fn: make_test_fn_ref("files:search"),
             get_actions_fn: make_test_fn_ref("files:get_actions"),
+            on_open_fn: None,
+            on_show_fn: None,
+            on_close_fn: None,
+            on_selection_changed_fn: None,
+            refresh_ms: None,
         };
 
         registry.add(view).unwrap();
@@ -0,0 +1,157 @@
+//! Config warnings (the Config Warnings view).
+//!
+//! This module provides:
+//! - `ConfigWarning` / `WarningKind` - An unknown view field or a deprecated API, and where it is
+//! - `ConfigWarnings` - The warnings collected while init.lua ran
+//! - `warning_groups()` - The rows of the Config Warnings view
+//!
+//! Warnings don't stop init.lua: the view is still added, with the unknown
+//! field ignored. They show up in the log, in `lux doctor`, and in
+//! `lux.builtin.config_warnings()`.
+
+use std::fmt;
+
+use parking_lot::Mutex;
+
+use lux_core::i18n::t;
+use lux_core::{Group, Groups, Item};
+
+/// ID of the built-in Config Warnings view.
+pub const CONFIG_WARNINGS_VIEW_ID: &str = "builtin.config_warnings";
+
+/// What a warning is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// A field the API doesn't read (often a typo).
+    UnknownField,
+    /// A field or function that still works but has a replacement.
+    Deprecated,
+}
+
+impl WarningKind {
+    /// Label shown in the launcher.
+    pub fn label(&self) -> String {
+        match self {
+            WarningKind::UnknownField => t("Unknown field"),
+            WarningKind::Deprecated => t("Deprecated"),
+        }
+    }
+}
+
+/// One problem in init.lua that didn't stop it from loading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// What it's about.
+    pub kind: WarningKind,
+    /// What has the problem (e.g. "view 'files'", "plugin 'weather'").
+    pub subject: String,
+    /// The field, if it's about one.
+    pub field: Option<String>,
+    /// What is wrong, and what to do instead.
+    pub message: String,
+    /// Where in the config (`init.lua:12`), if known.
+    pub location: Option<String>,
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(location) = &self.location {
+            write!(f, "{}: ", location)?;
+        }
+        write!(f, "{}: {}", self.subject, self.message)
+    }
+}
+
+/// The warnings collected while init.lua ran, oldest first.
+#[derive(Default)]
+pub struct ConfigWarnings {
+    warnings: Mutex<Vec<ConfigWarning>>,
+}
+
+impl ConfigWarnings {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record and log a warning, unless the same one was recorded already.
+    pub fn record(&self, warning: ConfigWarning) {
+        let mut warnings = self.warnings.lock();
+        if warnings.contains(&warning) {
+            return;
+        }
+        tracing::warn!("{}", warning);
+        warnings.push(warning);
+    }
+
+    /// The warnings, oldest first.
+    pub fn list(&self) -> Vec<ConfigWarning> {
+        self.warnings.lock().clone()
+    }
+
+    /// Number of warnings.
+    pub fn len(&self) -> usize {
+        self.warnings.lock().len()
+    }
+
+    /// Whether there are no warnings.
+    pub fn is_empty(&self) -> bool {
+        self.warnings.lock().is_empty()
+    }
+}
+
+/// The warnings whose subject or message contains `query`.
+pub fn warning_groups(warnings: &[ConfigWarning], query: &str) -> Groups {
+    let query = query.trim().to_lowercase();
+    let items: Vec<Item> = warnings
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| {
+            w.subject.to_lowercase().contains(&query) || w.message.to_lowercase().contains(&query)
+        })
+        .map(|(i, w)| {
+            let location = match &w.location {
+                Some(location) => format!("{} · {}", w.subject, location),
+                None => w.subject.clone(),
+            };
+            Item::new(format!("warning:{}", i), w.message.clone())
+                .with_subtitle(location)
+                .with_accessory(w.kind.label())
+        })
+        .collect();
+    if items.is_empty() {
+        return Vec::new();
+    }
+    vec![Group::new(t("Config Warnings"), items)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_each_warning_once() {
+        let warnings = ConfigWarnings::new();
+        let warning = ConfigWarning {
+            kind: WarningKind::UnknownField,
+            subject: "view 'files'".to_string(),
+            field: Some("placholder".to_string()),
+            message: "Unknown field 'placholder' (did you mean 'placeholder'?)".to_string(),
+            location: Some("init.lua:12".to_string()),
+        };
+        warnings.record(warning.clone());
+        warnings.record(warning.clone());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warning.to_string(),
+            "init.lua:12: view 'files': Unknown field 'placholder' (did you mean 'placeholder'?)"
+        );
+
+        let groups = warning_groups(&warnings.list(), "files");
+        assert_eq!(
+            groups[0].items[0].subtitle.as_deref(),
+            Some("view 'files' · init.lua:12")
+        );
+        assert!(warning_groups(&warnings.list(), "nothing").is_empty());
+    }
+}
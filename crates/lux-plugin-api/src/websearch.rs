@@ -0,0 +1,312 @@
+//! Web search engine registry.
+//!
+//! This module provides:
+//! - `SearchEngine` - A keyword-triggered search URL template
+//! - `WebSearchRegistry` - Storage for engines configured via `lux.websearch`
+//!
+//! The query engine uses the registry at the root view: a query starting with
+//! an engine keyword (`yt cats`) gets a "Search YouTube" item on top, and a
+//! query with no results falls back to the engines marked `fallback`.
+
+use parking_lot::RwLock;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+/// Type tag for web search items (used to route them to the built-in action).
+pub const WEBSEARCH_ITEM_TYPE: &str = "websearch";
+
+/// Lua registry key of the built-in "open in browser" action handler.
+pub const WEBSEARCH_OPEN_KEY: &str = "websearch:open";
+
+/// Placeholder replaced by the encoded query in URL templates.
+const QUERY_PLACEHOLDER: &str = "%s";
+
+/// A web search engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchEngine {
+    /// Keyword that triggers the engine (e.g. "yt").
+    pub keyword: String,
+    /// Display name (e.g. "YouTube").
+    pub name: String,
+    /// URL template with `%s` where the query goes.
+    pub url: String,
+    /// Whether the engine is offered when the root view has no results.
+    pub fallback: bool,
+}
+
+impl SearchEngine {
+    /// Create a new engine (not used as a fallback).
+    pub fn new(
+        keyword: impl Into<String>,
+        name: impl Into<String>,
+        url: impl Into<String>,
+    ) -> Self {
+        Self {
+            keyword: keyword.into(),
+            name: name.into(),
+            url: url.into(),
+            fallback: false,
+        }
+    }
+
+    /// Offer this engine when the root view has no results.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Build the search URL for a query.
+    pub fn url_for(&self, query: &str) -> String {
+        self.url.replace(QUERY_PLACEHOLDER, &encode_query(query))
+    }
+
+    /// Build the result item for a query.
+    pub fn item_for(&self, query: &str) -> Item {
+        let url = self.url_for(query);
+        Item::new(
+            format!("websearch:{}:{}", self.keyword, query),
+            t_args(
+                "Search {engine} for '{query}'",
+                &[("engine", &self.name), ("query", query)],
+            ),
+        )
+        .with_subtitle(url.clone())
+        .with_icon("🔍")
+        .with_type(WEBSEARCH_ITEM_TYPE)
+        .with_type("url")
+        .with_data(serde_json::json!({
+            "url": url,
+            "engine": self.keyword,
+            "query": query,
+        }))
+    }
+}
+
+/// Percent-encode a query for use in a URL.
+///
+/// Unreserved characters (RFC 3986) are kept; everything else, including
+/// spaces, is encoded byte by byte.
+pub fn encode_query(query: &str) -> String {
+    let mut encoded = String::with_capacity(query.len());
+    for byte in query.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Registry for web search engines.
+///
+/// Starts with Google and DuckDuckGo as fallbacks. Engines are added or
+/// replaced via `lux.websearch.add()`.
+pub struct WebSearchRegistry {
+    /// Engines in registration order.
+    engines: RwLock<Vec<SearchEngine>>,
+}
+
+impl WebSearchRegistry {
+    /// Create a registry with the default engines.
+    pub fn new() -> Self {
+        Self {
+            engines: RwLock::new(vec![
+                SearchEngine::new("g", "Google", "https://www.google.com/search?q=%s")
+                    .with_fallback(true),
+                SearchEngine::new("ddg", "DuckDuckGo", "https://duckduckgo.com/?q=%s")
+                    .with_fallback(true),
+            ]),
+        }
+    }
+
+    /// Add an engine, replacing any engine with the same keyword.
+    pub fn add(&self, engine: SearchEngine) -> Result<(), WebSearchError> {
+        if engine.keyword.is_empty() || engine.keyword.contains(char::is_whitespace) {
+            return Err(WebSearchError::InvalidKeyword(engine.keyword));
+        }
+        if !engine.url.contains(QUERY_PLACEHOLDER) {
+            return Err(WebSearchError::MissingPlaceholder(engine.url));
+        }
+
+        let mut engines = self.engines.write();
+        match engines.iter_mut().find(|e| e.keyword == engine.keyword) {
+            Some(existing) => *existing = engine,
+            None => engines.push(engine),
+        }
+        Ok(())
+    }
+
+    /// Remove an engine by keyword.
+    ///
+    /// Returns true if an engine was removed.
+    pub fn remove(&self, keyword: &str) -> bool {
+        let mut engines = self.engines.write();
+        let before = engines.len();
+        engines.retain(|e| e.keyword != keyword);
+        engines.len() != before
+    }
+
+    /// Get an engine by keyword.
+    pub fn get(&self, keyword: &str) -> Option<SearchEngine> {
+        self.engines
+            .read()
+            .iter()
+            .find(|e| e.keyword == keyword)
+            .cloned()
+    }
+
+    /// List all engines in registration order.
+    pub fn list(&self) -> Vec<SearchEngine> {
+        self.engines.read().clone()
+    }
+
+    /// Match a keyword trigger like `"yt cats"`.
+    ///
+    /// Returns the engine and the remaining query if the first word is a
+    /// registered keyword and something follows it.
+    pub fn match_keyword(&self, query: &str) -> Option<(SearchEngine, String)> {
+        let (keyword, rest) = query.trim_start().split_once(char::is_whitespace)?;
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return None;
+        }
+        self.get(keyword).map(|engine| (engine, rest.to_string()))
+    }
+
+    /// Build the keyword-trigger group for a query, if it matches an engine.
+    pub fn keyword_group(&self, query: &str) -> Option<Group> {
+        let (engine, rest) = self.match_keyword(query)?;
+        Some(Group::new(t("Web Search"), vec![engine.item_for(&rest)]))
+    }
+
+    /// Build the no-results fallback group for a query.
+    ///
+    /// Returns None for a blank query or when no engine is a fallback.
+    pub fn fallback_group(&self, query: &str) -> Option<Group> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+
+        let items: Vec<Item> = self
+            .engines
+            .read()
+            .iter()
+            .filter(|e| e.fallback)
+            .map(|e| e.item_for(query))
+            .collect();
+
+        if items.is_empty() {
+            None
+        } else {
+            Some(Group::new(t("Web Search"), items))
+        }
+    }
+}
+
+impl Default for WebSearchRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors that can occur when configuring search engines.
+#[derive(Debug, thiserror::Error)]
+pub enum WebSearchError {
+    #[error("Invalid search keyword '{0}': must be non-empty without spaces")]
+    InvalidKeyword(String),
+
+    #[error("Search URL '{0}' must contain '%s' for the query")]
+    MissingPlaceholder(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_query() {
+        assert_eq!(encode_query("rust lang"), "rust%20lang");
+        assert_eq!(encode_query("a&b=c"), "a%26b%3Dc");
+        assert_eq!(encode_query("café"), "caf%C3%A9");
+        assert_eq!(encode_query("safe-_.~"), "safe-_.~");
+    }
+
+    #[test]
+    fn test_url_templating() {
+        let engine = SearchEngine::new("yt", "YouTube", "https://youtube.com/results?q=%s");
+        assert_eq!(
+            engine.url_for("lo fi"),
+            "https://youtube.com/results?q=lo%20fi"
+        );
+    }
+
+    #[test]
+    fn test_add_replaces_by_keyword() {
+        let registry = WebSearchRegistry::new();
+        registry
+            .add(SearchEngine::new(
+                "g",
+                "Google UK",
+                "https://google.co.uk/?q=%s",
+            ))
+            .unwrap();
+
+        assert_eq!(registry.list().len(), 2);
+        assert_eq!(registry.get("g").unwrap().name, "Google UK");
+    }
+
+    #[test]
+    fn test_add_validates() {
+        let registry = WebSearchRegistry::new();
+        assert!(matches!(
+            registry.add(SearchEngine::new("y t", "Bad", "https://x/%s")),
+            Err(WebSearchError::InvalidKeyword(_))
+        ));
+        assert!(matches!(
+            registry.add(SearchEngine::new("yt", "Bad", "https://x/")),
+            Err(WebSearchError::MissingPlaceholder(_))
+        ));
+    }
+
+    #[test]
+    fn test_match_keyword() {
+        let registry = WebSearchRegistry::new();
+        registry
+            .add(SearchEngine::new(
+                "yt",
+                "YouTube",
+                "https://youtube.com/results?q=%s",
+            ))
+            .unwrap();
+
+        let (engine, rest) = registry.match_keyword("yt  cat videos ").unwrap();
+        assert_eq!(engine.keyword, "yt");
+        assert_eq!(rest, "cat videos");
+
+        assert!(registry.match_keyword("yt").is_none());
+        assert!(registry.match_keyword("yt ").is_none());
+        assert!(registry.match_keyword("youtube cats").is_none());
+    }
+
+    #[test]
+    fn test_fallback_group() {
+        let registry = WebSearchRegistry::new();
+        let group = registry.fallback_group("rust").unwrap();
+        assert_eq!(group.len(), 2);
+        assert!(group.items[0].has_type(WEBSEARCH_ITEM_TYPE));
+        assert_eq!(
+            group.items[0].data.as_ref().unwrap()["url"],
+            "https://www.google.com/search?q=rust"
+        );
+
+        assert!(registry.fallback_group("  ").is_none());
+
+        registry.remove("g");
+        registry.remove("ddg");
+        assert!(registry.fallback_group("rust").is_none());
+    }
+}
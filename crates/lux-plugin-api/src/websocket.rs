@@ -0,0 +1,642 @@
+//! WebSocket connections opened by plugins.
+//!
+//! This module provides:
+//! - `WebSockets` - Open connections behind `lux.ws`, and the events they received
+//! - `WsCallbacks` - The Lua callbacks of a connection, and the view it refreshes
+//! - `WsEvent` / `WsEventKind` - Opened, a message, an error, closed
+//!
+//! Each connection runs on its own thread, which connects, sends what Lua
+//! queued with `send()`, and reads messages into a shared queue. The queue is
+//! announced on a watch channel; the UI then takes the events and calls the
+//! Lua callbacks on the Lua thread (see `lux::deliver_ws_event`), and re-runs
+//! the search of the connection's view if it's the one shown. Connections are
+//! closed when Lux quits.
+
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use thiserror::Error;
+use tokio::sync::watch;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::http::{HeaderName, HeaderValue};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+use crate::types::LuaFunctionRef;
+
+/// How long a read waits before the thread checks for messages to send.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long the server has to answer a close before the connection is dropped.
+const CLOSE_GRACE: Duration = Duration::from_secs(3);
+
+/// Events kept per connection while nobody takes them.
+const MAX_QUEUED_EVENTS: usize = 1000;
+
+// =============================================================================
+// Errors
+// =============================================================================
+
+/// Errors from `lux.ws`.
+#[derive(Debug, Error)]
+pub enum WsError {
+    /// The URL isn't a ws:// or wss:// URL.
+    #[error("Not a WebSocket URL (ws:// or wss://): {0}")]
+    InvalidUrl(String),
+
+    /// A header name or value can't be sent.
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+
+    /// No open connection has this ID.
+    #[error("No WebSocket connection with ID {0}")]
+    NotFound(u64),
+
+    /// The connection thread couldn't be started.
+    #[error("Failed to start WebSocket connection: {0}")]
+    Spawn(String),
+}
+
+// =============================================================================
+// Events
+// =============================================================================
+
+/// The Lua side of a connection.
+#[derive(Debug, Clone, Default)]
+pub struct WsCallbacks {
+    /// Called once connected.
+    pub on_open: Option<LuaFunctionRef>,
+    /// Called with each text message (binary messages as lossy UTF-8).
+    pub on_message: Option<LuaFunctionRef>,
+    /// Called when connecting or reading fails; `on_close` follows.
+    pub on_error: Option<LuaFunctionRef>,
+    /// Called once the connection is closed, by either side or by an error.
+    pub on_close: Option<LuaFunctionRef>,
+    /// View whose search is re-run after each event while it's shown.
+    pub view: Option<String>,
+}
+
+impl WsCallbacks {
+    /// The callback for an event.
+    pub fn callback(&self, kind: &WsEventKind) -> Option<&LuaFunctionRef> {
+        match kind {
+            WsEventKind::Open => self.on_open.as_ref(),
+            WsEventKind::Message(_) => self.on_message.as_ref(),
+            WsEventKind::Error(_) => self.on_error.as_ref(),
+            WsEventKind::Close { .. } => self.on_close.as_ref(),
+        }
+    }
+
+    /// All callbacks, to release them from the Lua registry once closed.
+    pub fn all(&self) -> impl Iterator<Item = &LuaFunctionRef> {
+        [
+            &self.on_open,
+            &self.on_message,
+            &self.on_error,
+            &self.on_close,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// What happened on a connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsEventKind {
+    /// The handshake succeeded.
+    Open,
+    /// A message arrived.
+    Message(String),
+    /// Connecting or reading failed.
+    Error(String),
+    /// The connection is closed; always the last event.
+    Close {
+        /// Close code sent by the server, if any.
+        code: Option<u16>,
+        /// Close reason sent by the server, or empty.
+        reason: String,
+    },
+}
+
+/// An event of a connection, with the callbacks it goes to.
+#[derive(Debug, Clone)]
+pub struct WsEvent {
+    /// Connection ID.
+    pub id: u64,
+    /// What happened.
+    pub kind: WsEventKind,
+    /// Callbacks of the connection.
+    pub callbacks: WsCallbacks,
+}
+
+/// Whether a connection is open, for `lux.ws.list()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsState {
+    /// Handshake in progress.
+    Connecting,
+    /// Connected.
+    Open,
+    /// Closing, waiting for the server to answer.
+    Closing,
+}
+
+impl WsState {
+    /// Name of the state, as `lux.ws.list()` reports it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Connecting => "connecting",
+            Self::Open => "open",
+            Self::Closing => "closing",
+        }
+    }
+}
+
+/// A connection, for `lux.ws.list()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WsInfo {
+    /// Connection ID.
+    pub id: u64,
+    /// URL it was opened with.
+    pub url: String,
+    /// Whether it's open.
+    pub state: WsState,
+}
+
+// =============================================================================
+// Connections
+// =============================================================================
+
+/// What Lua asked a connection thread to do.
+enum Outgoing {
+    Text(String),
+    Close,
+}
+
+struct Connection {
+    url: String,
+    state: WsState,
+    callbacks: WsCallbacks,
+    outgoing: Sender<Outgoing>,
+}
+
+/// State shared with the connection threads.
+#[derive(Default)]
+struct Shared {
+    connections: Mutex<BTreeMap<u64, Connection>>,
+    events: Mutex<Vec<WsEvent>>,
+}
+
+impl Shared {
+    /// Queue an event for the callbacks of connection `id`.
+    ///
+    /// A closed connection is forgotten here, so its close event is the
+    /// last one queued.
+    fn push(&self, id: u64, kind: WsEventKind) {
+        let callbacks = {
+            let mut connections = self.connections.lock();
+            let closed = matches!(kind, WsEventKind::Close { .. });
+            let callbacks = match connections.get_mut(&id) {
+                Some(connection) => {
+                    if kind == WsEventKind::Open && connection.state == WsState::Connecting {
+                        connection.state = WsState::Open;
+                    }
+                    connection.callbacks.clone()
+                }
+                None => return,
+            };
+            if closed {
+                connections.remove(&id);
+            }
+            callbacks
+        };
+
+        let mut events = self.events.lock();
+        // Events nobody takes (the UI is gone or stuck) shouldn't grow Lux
+        // without bound; drop the oldest messages of this connection
+        if matches!(kind, WsEventKind::Message(_))
+            && events.iter().filter(|event| event.id == id).count() >= MAX_QUEUED_EVENTS
+        {
+            if let Some(index) = events
+                .iter()
+                .position(|event| event.id == id && matches!(event.kind, WsEventKind::Message(_)))
+            {
+                events.remove(index);
+            }
+        }
+        events.push(WsEvent {
+            id,
+            kind,
+            callbacks,
+        });
+    }
+}
+
+/// WebSocket connections opened with `lux.ws.connect()`.
+pub struct WebSockets {
+    shared: Arc<Shared>,
+    updates: Arc<watch::Sender<u64>>,
+    next_id: AtomicU64,
+}
+
+impl WebSockets {
+    /// Create with no connections.
+    pub fn new() -> Self {
+        let (updates, _) = watch::channel(0);
+        Self {
+            shared: Arc::new(Shared::default()),
+            updates: Arc::new(updates),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Subscribe to events. The value changes whenever events are waiting;
+    /// take them with `take_events()`.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.updates.subscribe()
+    }
+
+    /// Open a connection in the background. Returns its ID.
+    ///
+    /// `headers` are sent with the handshake (e.g. `Authorization`).
+    pub fn connect(
+        &self,
+        url: &str,
+        headers: Vec<(String, String)>,
+        callbacks: WsCallbacks,
+    ) -> Result<u64, WsError> {
+        let url = url.trim();
+        let lower = url.to_lowercase();
+        if !lower.starts_with("ws://") && !lower.starts_with("wss://") {
+            return Err(WsError::InvalidUrl(url.to_string()));
+        }
+        let mut request = url
+            .into_client_request()
+            .map_err(|_| WsError::InvalidUrl(url.to_string()))?;
+        for (name, value) in headers {
+            let header = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| WsError::InvalidHeader(name.clone()))?;
+            let value =
+                HeaderValue::from_str(&value).map_err(|_| WsError::InvalidHeader(name.clone()))?;
+            request.headers_mut().insert(header, value);
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (outgoing, rx) = mpsc::channel();
+        self.shared.connections.lock().insert(
+            id,
+            Connection {
+                url: url.to_string(),
+                state: WsState::Connecting,
+                callbacks,
+                outgoing,
+            },
+        );
+
+        let shared = self.shared.clone();
+        let updates = self.updates.clone();
+        let spawned = std::thread::Builder::new()
+            .name(format!("lux-ws-{}", id))
+            .spawn(move || {
+                let events = Events {
+                    id,
+                    shared,
+                    updates,
+                };
+                let close = run(request, rx, &events);
+                events.push(close);
+            });
+        if let Err(e) = spawned {
+            self.shared.connections.lock().remove(&id);
+            return Err(WsError::Spawn(e.to_string()));
+        }
+
+        tracing::debug!("WebSocket {} connecting to {}", id, url);
+        Ok(id)
+    }
+
+    /// Send a text message once the connection is open.
+    pub fn send(&self, id: u64, text: impl Into<String>) -> Result<(), WsError> {
+        let connections = self.shared.connections.lock();
+        let connection = connections.get(&id).ok_or(WsError::NotFound(id))?;
+        connection
+            .outgoing
+            .send(Outgoing::Text(text.into()))
+            .map_err(|_| WsError::NotFound(id))
+    }
+
+    /// Close a connection. Its `on_close` callback still runs.
+    pub fn close(&self, id: u64) -> Result<(), WsError> {
+        let mut connections = self.shared.connections.lock();
+        let connection = connections.get_mut(&id).ok_or(WsError::NotFound(id))?;
+        connection.state = WsState::Closing;
+        let _ = connection.outgoing.send(Outgoing::Close);
+        Ok(())
+    }
+
+    /// Close every connection (when Lux quits).
+    pub fn close_all(&self) {
+        let ids: Vec<u64> = self.shared.connections.lock().keys().copied().collect();
+        for id in ids {
+            let _ = self.close(id);
+        }
+    }
+
+    /// Open connections, oldest first.
+    pub fn list(&self) -> Vec<WsInfo> {
+        self.shared
+            .connections
+            .lock()
+            .iter()
+            .map(|(id, connection)| WsInfo {
+                id: *id,
+                url: connection.url.clone(),
+                state: connection.state,
+            })
+            .collect()
+    }
+
+    /// Take the events received since the last call, in order.
+    pub fn take_events(&self) -> Vec<WsEvent> {
+        std::mem::take(&mut *self.shared.events.lock())
+    }
+}
+
+impl Default for WebSockets {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for WebSockets {
+    fn drop(&mut self) {
+        self.close_all();
+    }
+}
+
+/// Where a connection thread reports its events.
+struct Events {
+    id: u64,
+    shared: Arc<Shared>,
+    updates: Arc<watch::Sender<u64>>,
+}
+
+impl Events {
+    fn push(&self, kind: WsEventKind) {
+        self.shared.push(self.id, kind);
+        self.updates
+            .send_modify(|count| *count = count.wrapping_add(1));
+    }
+}
+
+/// Connect and pump messages until the connection closes.
+///
+/// Returns the close event.
+fn run(
+    request: tungstenite::handshake::client::Request,
+    outgoing: Receiver<Outgoing>,
+    events: &Events,
+) -> WsEventKind {
+    let mut socket = match tungstenite::connect(request) {
+        Ok((socket, _response)) => socket,
+        Err(e) => {
+            events.push(WsEventKind::Error(e.to_string()));
+            return WsEventKind::Close {
+                code: None,
+                reason: String::new(),
+            };
+        }
+    };
+    // Reads time out so queued messages go out while the server is quiet
+    if let Some(stream) = tcp_stream(&socket) {
+        if let Err(e) = stream.set_read_timeout(Some(POLL_INTERVAL)) {
+            tracing::warn!("Failed to set the WebSocket read timeout: {}", e);
+        }
+    }
+    events.push(WsEventKind::Open);
+
+    let mut close_frame: Option<(u16, String)> = None;
+    let mut closing_since: Option<Instant> = None;
+    loop {
+        if closing_since.is_none() {
+            loop {
+                let result = match outgoing.try_recv() {
+                    Ok(Outgoing::Text(text)) => socket.send(Message::text(text)),
+                    // Lux dropped the connection without closing it
+                    Ok(Outgoing::Close) | Err(TryRecvError::Disconnected) => {
+                        closing_since = Some(Instant::now());
+                        socket.close(None)
+                    }
+                    Err(TryRecvError::Empty) => break,
+                };
+                if let Err(e) = result {
+                    if !is_timeout(&e) {
+                        events.push(WsEventKind::Error(e.to_string()));
+                        break;
+                    }
+                }
+                if closing_since.is_some() {
+                    break;
+                }
+            }
+        } else if closing_since.is_some_and(|since| since.elapsed() > CLOSE_GRACE) {
+            break;
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => events.push(WsEventKind::Message(text.to_string())),
+            Ok(Message::Binary(data)) => {
+                events.push(WsEventKind::Message(
+                    String::from_utf8_lossy(&data).into_owned(),
+                ));
+            }
+            Ok(Message::Close(frame)) => {
+                // tungstenite answers the close; keep reading until it's done
+                close_frame = frame.map(|frame| (u16::from(frame.code), frame.reason.to_string()));
+                closing_since.get_or_insert_with(Instant::now);
+            }
+            Ok(_) => {}
+            Err(e) if is_timeout(&e) => {
+                // Writes that timed out are retried by flush()
+                let _ = socket.flush();
+            }
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => break,
+            Err(e) => {
+                events.push(WsEventKind::Error(e.to_string()));
+                break;
+            }
+        }
+    }
+
+    let (code, reason) = match close_frame {
+        Some((code, reason)) => (Some(code), reason),
+        None => (None, String::new()),
+    };
+    WsEventKind::Close { code, reason }
+}
+
+/// The TCP stream under a (possibly TLS) WebSocket.
+fn tcp_stream(socket: &WebSocket<MaybeTlsStream<TcpStream>>) -> Option<&TcpStream> {
+    match socket.get_ref() {
+        MaybeTlsStream::Plain(stream) => Some(stream),
+        MaybeTlsStream::Rustls(stream) => Some(stream.get_ref()),
+        _ => None,
+    }
+}
+
+/// Whether an error is a read timeout rather than a failure.
+fn is_timeout(error: &tungstenite::Error) -> bool {
+    matches!(
+        error,
+        tungstenite::Error::Io(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use tungstenite::protocol::frame::coding::CloseCode;
+    use tungstenite::protocol::CloseFrame;
+
+    /// Wait until `done` holds for the events taken so far.
+    fn wait_for(sockets: &WebSockets, done: impl Fn(&[WsEvent]) -> bool) -> Vec<WsEvent> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut events = Vec::new();
+        while Instant::now() < deadline {
+            events.extend(sockets.take_events());
+            if done(&events) {
+                return events;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("Timed out waiting for events: {:?}", events);
+    }
+
+    fn kinds(events: &[WsEvent]) -> Vec<WsEventKind> {
+        events.iter().map(|event| event.kind.clone()).collect()
+    }
+
+    /// Server that greets, upper-cases messages, and closes on "bye".
+    fn echo_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            socket.send(Message::text("hello")).unwrap();
+            while let Ok(message) = socket.read() {
+                if let Message::Text(text) = message {
+                    if text.as_str() == "bye" {
+                        let frame = CloseFrame {
+                            code: CloseCode::Normal,
+                            reason: "done".into(),
+                        };
+                        socket.close(Some(frame)).unwrap();
+                        continue;
+                    }
+                    socket.send(Message::text(text.to_uppercase())).unwrap();
+                }
+            }
+        });
+        format!("ws://{}", addr)
+    }
+
+    #[test]
+    fn test_connect_send_close() {
+        let sockets = WebSockets::new();
+        let id = sockets
+            .connect(&echo_server(), Vec::new(), WsCallbacks::default())
+            .unwrap();
+        assert_eq!(sockets.list()[0].id, id);
+
+        let events = wait_for(&sockets, |events| events.len() >= 2);
+        assert_eq!(
+            kinds(&events),
+            vec![WsEventKind::Open, WsEventKind::Message("hello".to_string())]
+        );
+        assert_eq!(sockets.list()[0].state, WsState::Open);
+
+        sockets.send(id, "ping").unwrap();
+        let events = wait_for(&sockets, |events| !events.is_empty());
+        assert_eq!(
+            kinds(&events),
+            vec![WsEventKind::Message("PING".to_string())]
+        );
+
+        // The server closes; on_close is the last event and the connection is gone
+        sockets.send(id, "bye").unwrap();
+        let events = wait_for(&sockets, |events| {
+            events
+                .iter()
+                .any(|event| matches!(event.kind, WsEventKind::Close { .. }))
+        });
+        assert_eq!(
+            events.last().unwrap().kind,
+            WsEventKind::Close {
+                code: Some(1000),
+                reason: "done".to_string()
+            }
+        );
+        assert!(sockets.list().is_empty());
+        assert!(matches!(sockets.send(id, "x"), Err(WsError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_close_from_lux() {
+        let sockets = WebSockets::new();
+        let id = sockets
+            .connect(&echo_server(), Vec::new(), WsCallbacks::default())
+            .unwrap();
+        wait_for(&sockets, |events| events.len() >= 2);
+
+        sockets.close(id).unwrap();
+        assert_eq!(sockets.list()[0].state, WsState::Closing);
+        let events = wait_for(&sockets, |events| {
+            events
+                .iter()
+                .any(|event| matches!(event.kind, WsEventKind::Close { .. }))
+        });
+        assert_eq!(events.len(), 1);
+        assert!(sockets.list().is_empty());
+    }
+
+    #[test]
+    fn test_connect_errors() {
+        let sockets = WebSockets::new();
+        assert!(matches!(
+            sockets.connect("https://example.com", Vec::new(), WsCallbacks::default()),
+            Err(WsError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            sockets.connect(
+                "ws://localhost:1",
+                vec![("bad header".to_string(), "x".to_string())],
+                WsCallbacks::default()
+            ),
+            Err(WsError::InvalidHeader(_))
+        ));
+
+        // Nothing listens on the port: an error, then the close
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        drop(listener);
+        sockets
+            .connect(&url, Vec::new(), WsCallbacks::default())
+            .unwrap();
+        let events = wait_for(&sockets, |events| events.len() >= 2);
+        assert!(matches!(events[0].kind, WsEventKind::Error(_)));
+        assert_eq!(
+            events[1].kind,
+            WsEventKind::Close {
+                code: None,
+                reason: String::new()
+            }
+        );
+    }
+}
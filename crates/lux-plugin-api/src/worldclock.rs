@@ -0,0 +1,1007 @@
+//! World clock answers from the tz database.
+//!
+//! This module provides:
+//! - `TzInfo` - A parsed TZif file (`/usr/share/zoneinfo/...`), with its POSIX TZ rule
+//! - `WorldClock` - Zone search with fuzzy city matching and pinned clocks (lux.worldclock)
+//! - `clock_actions()` / `run_clock_action()` - Pin a clock to the root view or copy the time
+//!
+//! `time in tokyo` answers with the current time there; pinned clocks are
+//! listed in the root view while the query is empty (or `time`/`clock`).
+//! Zones come from the system's `zone.tab`, so city names follow the tz
+//! database (plus a few common aliases like `san francisco` or `pst`).
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::{Datelike, NaiveDate};
+use parking_lot::Mutex;
+
+use lux_core::i18n::{t, t_args};
+use lux_core::{Group, Item};
+
+use crate::error::{PluginError, PluginResult};
+
+/// Type tag for clock items (used to route them to the built-in actions).
+pub const CLOCK_ITEM_TYPE: &str = "world_clock";
+
+/// Lua registry key prefix of the built-in clock action handlers.
+pub const CLOCK_ACTION_KEY_PREFIX: &str = "clock:";
+
+/// IDs of all clock actions (one handler is registered per ID).
+pub const CLOCK_ACTION_IDS: &[&str] = &["pin", "unpin", "copy_time"];
+
+/// Where the tz database is installed on macOS and Linux.
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+/// Query prefixes that ask for the time somewhere.
+const QUERY_PREFIXES: &[&str] = &[
+    "what time is it in ",
+    "what's the time in ",
+    "time in ",
+    "time at ",
+];
+
+/// Queries that list the pinned clocks.
+const PINNED_KEYWORDS: &[&str] = &["time", "clock", "world clock"];
+
+/// Zones shown for one query.
+const MAX_RESULTS: usize = 3;
+
+/// Places and abbreviations that aren't city names in the tz database.
+const ALIASES: &[(&str, &str)] = &[
+    ("san francisco", "America/Los_Angeles"),
+    ("seattle", "America/Los_Angeles"),
+    ("washington", "America/New_York"),
+    ("boston", "America/New_York"),
+    ("miami", "America/New_York"),
+    ("dallas", "America/Chicago"),
+    ("houston", "America/Chicago"),
+    ("montreal", "America/Toronto"),
+    ("beijing", "Asia/Shanghai"),
+    ("delhi", "Asia/Kolkata"),
+    ("new delhi", "Asia/Kolkata"),
+    ("mumbai", "Asia/Kolkata"),
+    ("bangalore", "Asia/Kolkata"),
+    ("osaka", "Asia/Tokyo"),
+    ("munich", "Europe/Berlin"),
+    ("frankfurt", "Europe/Berlin"),
+    ("barcelona", "Europe/Madrid"),
+    ("milan", "Europe/Rome"),
+    ("geneva", "Europe/Zurich"),
+    ("utc", "UTC"),
+    ("gmt", "UTC"),
+    ("pst", "America/Los_Angeles"),
+    ("pdt", "America/Los_Angeles"),
+    ("mst", "America/Denver"),
+    ("mdt", "America/Denver"),
+    ("cst", "America/Chicago"),
+    ("cdt", "America/Chicago"),
+    ("est", "America/New_York"),
+    ("edt", "America/New_York"),
+    ("bst", "Europe/London"),
+    ("cet", "Europe/Paris"),
+    ("cest", "Europe/Paris"),
+    ("ist", "Asia/Kolkata"),
+    ("jst", "Asia/Tokyo"),
+    ("kst", "Asia/Seoul"),
+    ("aest", "Australia/Sydney"),
+];
+
+/// Handler key for a clock action ID.
+pub fn clock_action_key(id: &str) -> String {
+    format!("{}{}", CLOCK_ACTION_KEY_PREFIX, id)
+}
+
+// =============================================================================
+// POSIX TZ rules
+// =============================================================================
+
+/// When a daylight saving transition happens in a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransitionDay {
+    /// `Jn`: day 1-365, never counting February 29.
+    JulianNoLeap(u16),
+    /// `n`: day 0-365, counting February 29.
+    Julian(u16),
+    /// `Mm.w.d`: weekday `d` (0 = Sunday) of week `w` (5 = last) of month `m`.
+    MonthWeekDay { month: u32, week: u32, weekday: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TransitionRule {
+    day: TransitionDay,
+    /// Local time of day of the transition, in seconds (may be negative or past 24h).
+    time: i64,
+}
+
+impl TransitionRule {
+    /// The transition's local time in a year, as seconds since the epoch.
+    fn local_time(&self, year: i32) -> Option<i64> {
+        let date = match self.day {
+            TransitionDay::JulianNoLeap(day) => {
+                let leap = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+                let ordinal = if leap && day >= 60 { day + 1 } else { day };
+                NaiveDate::from_yo_opt(year, ordinal as u32)?
+            }
+            TransitionDay::Julian(day) => NaiveDate::from_yo_opt(year, day as u32 + 1)?,
+            TransitionDay::MonthWeekDay {
+                month,
+                week,
+                weekday,
+            } => {
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let first_weekday = first.weekday().num_days_from_sunday();
+                let mut day = 1 + (weekday + 7 - first_weekday) % 7 + (week - 1) * 7;
+                while NaiveDate::from_ymd_opt(year, month, day).is_none() {
+                    day -= 7;
+                }
+                NaiveDate::from_ymd_opt(year, month, day)?
+            }
+        };
+        Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp() + self.time)
+    }
+}
+
+/// A POSIX TZ string such as `EST5EDT,M3.2.0,M11.1.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PosixTz {
+    std_abbr: String,
+    /// Standard offset in seconds east of UTC.
+    std_offset: i64,
+    dst: Option<PosixDst>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PosixDst {
+    abbr: String,
+    /// Daylight offset in seconds east of UTC.
+    offset: i64,
+    start: TransitionRule,
+    end: TransitionRule,
+}
+
+impl PosixTz {
+    fn parse(text: &str) -> Option<Self> {
+        let mut rest = text;
+        let std_abbr = take_abbr(&mut rest)?;
+        // POSIX offsets count west of UTC
+        let std_offset = -take_duration(&mut rest)?;
+        if rest.is_empty() {
+            return Some(Self {
+                std_abbr,
+                std_offset,
+                dst: None,
+            });
+        }
+
+        let abbr = take_abbr(&mut rest)?;
+        let offset = if rest.starts_with(',') || rest.is_empty() {
+            std_offset + 3600
+        } else {
+            -take_duration(&mut rest)?
+        };
+        let (start, end) = match rest.strip_prefix(',') {
+            Some(rules) => {
+                let (start, end) = rules.split_once(',')?;
+                (parse_rule(start)?, parse_rule(end)?)
+            }
+            // The POSIX default is the US rules
+            None => (parse_rule("M3.2.0")?, parse_rule("M11.1.0")?),
+        };
+        Some(Self {
+            std_abbr,
+            std_offset,
+            dst: Some(PosixDst {
+                abbr,
+                offset,
+                start,
+                end,
+            }),
+        })
+    }
+
+    /// Offset and abbreviation in effect at a Unix time.
+    fn offset_at(&self, time: i64) -> (i64, String) {
+        let Some(dst) = &self.dst else {
+            return (self.std_offset, self.std_abbr.clone());
+        };
+        let year = chrono::DateTime::from_timestamp(time + self.std_offset, 0)
+            .map_or(1970, |date| date.year());
+        let in_dst = match (dst.start.local_time(year), dst.end.local_time(year)) {
+            (Some(start), Some(end)) => {
+                let (start, end) = (start - self.std_offset, end - dst.offset);
+                if start < end {
+                    start <= time && time < end
+                } else {
+                    // Southern hemisphere: daylight time spans the new year
+                    !(end <= time && time < start)
+                }
+            }
+            _ => false,
+        };
+        if in_dst {
+            (dst.offset, dst.abbr.clone())
+        } else {
+            (self.std_offset, self.std_abbr.clone())
+        }
+    }
+}
+
+/// Take an abbreviation: letters, or anything between `<` and `>`.
+fn take_abbr(rest: &mut &str) -> Option<String> {
+    let (abbr, remaining) = if let Some(quoted) = rest.strip_prefix('<') {
+        let (abbr, remaining) = quoted.split_once('>')?;
+        (abbr, remaining)
+    } else {
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        rest.split_at(len)
+    };
+    if abbr.len() < 3 {
+        return None;
+    }
+    *rest = remaining;
+    Some(abbr.to_string())
+}
+
+/// Take a `[+-]hh[:mm[:ss]]` duration in seconds.
+fn take_duration(rest: &mut &str) -> Option<i64> {
+    let len = rest
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, ':' | '+' | '-')))
+        .unwrap_or(rest.len());
+    let (text, remaining) = rest.split_at(len);
+    *rest = remaining;
+    parse_duration(text)
+}
+
+fn parse_duration(text: &str) -> Option<i64> {
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(text) => (-1, text),
+        None => (1, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let mut seconds = 0;
+    let mut parts = 0;
+    for (part, scale) in text.split(':').zip([3600, 60, 1]) {
+        seconds += part.parse::<i64>().ok()? * scale;
+        parts += 1;
+    }
+    (parts > 0 && parts <= 3 && text.split(':').count() == parts).then_some(sign * seconds)
+}
+
+/// Parse a rule such as `M3.2.0`, `M10.5.0/3`, `J60`, or `59/-1`.
+fn parse_rule(text: &str) -> Option<TransitionRule> {
+    let (day, time) = match text.split_once('/') {
+        Some((day, time)) => (day, parse_duration(time)?),
+        None => (text, 2 * 3600),
+    };
+    let day = if let Some(spec) = day.strip_prefix('M') {
+        let mut fields = spec.split('.').map(|f| f.parse::<u32>().ok());
+        let (Some(Some(month)), Some(Some(week)), Some(Some(weekday)), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return None;
+        };
+        if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+            return None;
+        }
+        TransitionDay::MonthWeekDay {
+            month,
+            week,
+            weekday,
+        }
+    } else if let Some(day) = day.strip_prefix('J') {
+        TransitionDay::JulianNoLeap(day.parse().ok().filter(|d| (1..=365).contains(d))?)
+    } else {
+        TransitionDay::Julian(day.parse().ok().filter(|d| *d <= 365)?)
+    };
+    Some(TransitionRule { day, time })
+}
+
+// =============================================================================
+// TZif files
+// =============================================================================
+
+/// A local time type in a TZif file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalTimeType {
+    /// Offset in seconds east of UTC.
+    offset: i64,
+    abbr: String,
+}
+
+/// A parsed TZif file (RFC 8536).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TzInfo {
+    /// Transition times, ascending, with the index of the type in effect after each.
+    transitions: Vec<(i64, usize)>,
+    types: Vec<LocalTimeType>,
+    /// Rule for times after the last transition.
+    footer: Option<PosixTz>,
+}
+
+impl TzInfo {
+    /// Read the file for a zone such as `Asia/Tokyo`.
+    pub fn load(dir: &Path, zone: &str) -> PluginResult<Self> {
+        if zone.split('/').any(|part| part.is_empty() || part == "..") {
+            return Err(PluginError::Command(format!(
+                "Invalid time zone '{}'",
+                zone
+            )));
+        }
+        let bytes = std::fs::read(dir.join(zone))
+            .map_err(|e| PluginError::Command(format!("Time zone '{}': {}", zone, e)))?;
+        Self::parse(&bytes)
+            .ok_or_else(|| PluginError::Command(format!("Invalid time zone file for '{}'", zone)))
+    }
+
+    /// Parse TZif data, preferring the 64-bit block of version 2+ files.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let header = Header::parse(bytes)?;
+        if header.version < b'2' {
+            return Self::parse_block(bytes, 44, &header, 4).map(|(info, _)| info);
+        }
+        let v2_start = 44 + header.block_len(4);
+        let v2_header = Header::parse(bytes.get(v2_start..)?)?;
+        let (mut info, end) = Self::parse_block(bytes, v2_start + 44, &v2_header, 8)?;
+        let footer = std::str::from_utf8(bytes.get(end..)?).ok()?;
+        info.footer = footer
+            .trim_matches('\n')
+            .lines()
+            .next()
+            .and_then(PosixTz::parse);
+        Some(info)
+    }
+
+    fn parse_block(
+        bytes: &[u8],
+        start: usize,
+        header: &Header,
+        time_size: usize,
+    ) -> Option<(Self, usize)> {
+        let block = bytes.get(start..start + header.block_len(time_size))?;
+        let read_int = |offset: usize, size: usize| -> Option<i64> {
+            let field = block.get(offset..offset + size)?;
+            Some(match size {
+                4 => i32::from_be_bytes(field.try_into().ok()?) as i64,
+                _ => i64::from_be_bytes(field.try_into().ok()?),
+            })
+        };
+
+        let times_len = header.timecnt * time_size;
+        let types_start = times_len + header.timecnt;
+        let abbrs_start = types_start + header.typecnt * 6;
+        let abbrs = block.get(abbrs_start..abbrs_start + header.charcnt)?;
+
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for i in 0..header.timecnt {
+            let time = read_int(i * time_size, time_size)?;
+            let index = *block.get(times_len + i)? as usize;
+            if index >= header.typecnt {
+                return None;
+            }
+            transitions.push((time, index));
+        }
+
+        let mut types = Vec::with_capacity(header.typecnt);
+        for i in 0..header.typecnt {
+            let entry = types_start + i * 6;
+            let abbr_index = *block.get(entry + 5)? as usize;
+            let abbr = abbrs.get(abbr_index..)?;
+            let abbr_len = abbr.iter().position(|b| *b == 0).unwrap_or(abbr.len());
+            types.push(LocalTimeType {
+                offset: read_int(entry, 4)?,
+                abbr: String::from_utf8_lossy(&abbr[..abbr_len]).to_string(),
+            });
+        }
+
+        let info = Self {
+            transitions,
+            types,
+            footer: None,
+        };
+        Some((info, start + block.len()))
+    }
+
+    /// Offset (seconds east of UTC) and abbreviation in effect at a Unix time.
+    pub fn offset_at(&self, time: i64) -> Option<(i64, String)> {
+        match self.transitions.last() {
+            Some((last, _)) if time >= *last => {
+                if let Some(footer) = &self.footer {
+                    return Some(footer.offset_at(time));
+                }
+            }
+            None => {
+                if let Some(footer) = &self.footer {
+                    return Some(footer.offset_at(time));
+                }
+            }
+            _ => {}
+        }
+        let index = match self.transitions.partition_point(|(t, _)| *t <= time) {
+            0 => 0,
+            after => self.transitions[after - 1].1,
+        };
+        let local = self.types.get(index)?;
+        Some((local.offset, local.abbr.clone()))
+    }
+}
+
+/// Counts from a TZif header.
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes.get(..4)? != b"TZif" {
+            return None;
+        }
+        let count = |i: usize| -> Option<usize> {
+            let field = bytes.get(20 + i * 4..24 + i * 4)?;
+            Some(u32::from_be_bytes(field.try_into().ok()?) as usize)
+        };
+        Some(Self {
+            version: *bytes.get(4)?,
+            isutcnt: count(0)?,
+            isstdcnt: count(1)?,
+            leapcnt: count(2)?,
+            timecnt: count(3)?,
+            typecnt: count(4)?,
+            charcnt: count(5)?,
+        })
+    }
+
+    /// Length of the data block after this header.
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+// =============================================================================
+// Zones
+// =============================================================================
+
+/// A zone from `zone.tab`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Zone {
+    /// tz database ID, e.g. `America/Argentina/Buenos_Aires`.
+    pub id: String,
+    /// City name from the ID, e.g. `Buenos Aires`.
+    pub city: String,
+    /// Country name from `iso3166.tab`.
+    pub country: Option<String>,
+}
+
+impl Zone {
+    fn new(id: &str, country: Option<String>) -> Self {
+        let city = id.rsplit('/').next().unwrap_or(id).replace('_', " ");
+        Self {
+            id: id.to_string(),
+            city,
+            country,
+        }
+    }
+}
+
+/// Parse `zone.tab` (country code, coordinates, zone ID) with country names
+/// from `iso3166.tab`, plus UTC.
+pub fn parse_zone_tab(zone_tab: &str, iso3166_tab: &str) -> Vec<Zone> {
+    let countries: Vec<(&str, &str)> = iso3166_tab
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('\t'))
+        .collect();
+
+    let mut zones: Vec<Zone> = zone_tab
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let code = fields.next()?;
+            let id = fields.nth(1)?;
+            let country = countries
+                .iter()
+                .find(|(c, _)| *c == code)
+                .map(|(_, name)| name.to_string());
+            Some(Zone::new(id, country))
+        })
+        .collect();
+    zones.push(Zone::new("UTC", None));
+    zones
+}
+
+/// How well `query` matches a name (0 = not at all).
+fn match_score(name: &str, query: &str) -> u32 {
+    if name == query {
+        100
+    } else if name.starts_with(query) {
+        80
+    } else if name.split([' ', '-']).any(|word| word.starts_with(query)) {
+        60
+    } else if query.chars().count() >= 4 && edit_distance(name, query) <= 1 {
+        // One typo: "tokio", "londn", "tokoy"
+        40
+    } else {
+        0
+    }
+}
+
+/// Edit distance between two strings, counting a swap of adjacent letters as one edit.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Find zones for a place name, best first.
+///
+/// Matches city names (with one typo allowed), aliases, and country names.
+pub fn search_zones<'a>(zones: &'a [Zone], place: &str) -> Vec<&'a Zone> {
+    let place = place.trim().to_lowercase();
+    if place.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(u32, &Zone)> = zones
+        .iter()
+        .filter_map(|zone| {
+            let city = match_score(&zone.city.to_lowercase(), &place);
+            let alias = ALIASES
+                .iter()
+                .filter(|(_, id)| *id == zone.id)
+                .map(|(alias, _)| match_score(alias, &place))
+                .max()
+                .unwrap_or(0);
+            // A country matches all its zones, so it ranks below cities
+            let country = zone.country.as_ref().map_or(0, |country| {
+                match_score(&country.to_lowercase(), &place) / 2
+            });
+            let score = city.max(alias).max(country);
+            (score > 0).then_some((score, zone))
+        })
+        .collect();
+    scored.sort_by(|(a, _), (b, _)| b.cmp(a));
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, zone)| zone)
+        .collect()
+}
+
+/// The place named in a world clock query (`time in tokyo`, `tokyo time`).
+pub fn clock_query(query: &str) -> Option<String> {
+    let query = query.trim().to_lowercase();
+    let place = QUERY_PREFIXES
+        .iter()
+        .find_map(|prefix| query.strip_prefix(prefix))
+        .or_else(|| query.strip_suffix(" time"))?;
+    let place = place.trim().trim_end_matches('?').trim();
+    (!place.is_empty()).then(|| place.to_string())
+}
+
+// =============================================================================
+// World Clock
+// =============================================================================
+
+/// Zone search and pinned clocks.
+///
+/// Zones are read from the tz database on first use; pins are saved as a
+/// JSON array of zone IDs after every change.
+pub struct WorldClock {
+    dir: PathBuf,
+    zones: OnceLock<Vec<Zone>>,
+    path: Option<PathBuf>,
+    pins: Mutex<Vec<String>>,
+}
+
+impl WorldClock {
+    /// Read zones from `dir` and pins from `path` (None keeps them in memory only).
+    pub fn new(dir: impl Into<PathBuf>, path: Option<PathBuf>) -> Self {
+        let pins = path.as_deref().map(load_pins).unwrap_or_default();
+        Self {
+            dir: dir.into(),
+            zones: OnceLock::new(),
+            path,
+            pins: Mutex::new(pins),
+        }
+    }
+
+    /// Where pins are saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("clocks.json"))
+    }
+
+    /// All zones in the tz database.
+    pub fn zones(&self) -> &[Zone] {
+        self.zones.get_or_init(|| {
+            let read =
+                |name: &str| std::fs::read_to_string(self.dir.join(name)).unwrap_or_default();
+            parse_zone_tab(&read("zone.tab"), &read("iso3166.tab"))
+        })
+    }
+
+    /// Find zones for a place name, best first.
+    pub fn search(&self, place: &str) -> Vec<Zone> {
+        search_zones(self.zones(), place)
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Look up a zone by ID, or the best match for a place name.
+    pub fn resolve(&self, name: &str) -> Option<Zone> {
+        self.zones()
+            .iter()
+            .find(|zone| zone.id == name)
+            .cloned()
+            .or_else(|| self.search(name).into_iter().next())
+    }
+
+    /// IDs of the pinned zones, in pin order.
+    pub fn pinned(&self) -> Vec<String> {
+        self.pins.lock().clone()
+    }
+
+    /// Pin a zone to the root view.
+    pub fn pin(&self, id: &str) {
+        let mut pins = self.pins.lock();
+        if !pins.iter().any(|pin| pin == id) {
+            pins.push(id.to_string());
+            self.save(&pins);
+        }
+    }
+
+    /// Remove a zone from the root view.
+    pub fn unpin(&self, id: &str) {
+        let mut pins = self.pins.lock();
+        let len = pins.len();
+        pins.retain(|pin| pin != id);
+        if pins.len() != len {
+            self.save(&pins);
+        }
+    }
+
+    /// Offset (seconds east of UTC) and abbreviation of a zone at a Unix time.
+    pub fn offset_at(&self, zone: &str, time: i64) -> PluginResult<(i64, String)> {
+        TzInfo::load(&self.dir, zone)?
+            .offset_at(time)
+            .ok_or_else(|| PluginError::Command(format!("Invalid time zone file for '{}'", zone)))
+    }
+
+    /// Build the root-view "World Clock" group for a query.
+    ///
+    /// `local_offset` is the local offset at `now` (seconds east of UTC).
+    pub fn group(&self, query: &str, now: i64, local_offset: i64) -> Option<Group> {
+        let trimmed = query.trim().to_lowercase();
+        let zones: Vec<Zone> = if trimmed.is_empty() || PINNED_KEYWORDS.contains(&trimmed.as_str())
+        {
+            let pins = self.pinned();
+            pins.iter().filter_map(|id| self.resolve(id)).collect()
+        } else {
+            self.search(&clock_query(&trimmed)?)
+        };
+
+        let pins = self.pinned();
+        let items: Vec<Item> = zones
+            .iter()
+            .filter_map(|zone| {
+                let (offset, abbr) = self.offset_at(&zone.id, now).ok()?;
+                Some(clock_item(
+                    zone,
+                    now,
+                    offset,
+                    &abbr,
+                    local_offset,
+                    pins.contains(&zone.id),
+                ))
+            })
+            .collect();
+        if items.is_empty() {
+            None
+        } else {
+            Some(Group::new(t("World Clock"), items))
+        }
+    }
+
+    fn save(&self, pins: &[String]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(path, serde_json::to_string(pins)?));
+        if let Err(e) = result {
+            tracing::warn!("Failed to save pinned clocks: {}", e);
+        }
+    }
+}
+
+impl Default for WorldClock {
+    fn default() -> Self {
+        Self::new(ZONEINFO_DIR, Self::default_path())
+    }
+}
+
+fn load_pins(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+// =============================================================================
+// Items
+// =============================================================================
+
+/// Format an offset as `UTC+9`, `UTC-3:30`, or `UTC`.
+fn format_offset(offset: i64) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 3600, offset.abs() % 3600 / 60);
+    match (hours, minutes) {
+        (0, 0) => "UTC".to_string(),
+        (hours, 0) => format!("UTC{}{}", sign, hours),
+        (hours, minutes) => format!("UTC{}{}:{:02}", sign, hours, minutes),
+    }
+}
+
+/// Build the item showing the time in a zone.
+///
+/// The difference from local time (and the day, when it differs) are shown
+/// as accessories.
+pub fn clock_item(
+    zone: &Zone,
+    now: i64,
+    offset: i64,
+    abbr: &str,
+    local_offset: i64,
+    pinned: bool,
+) -> Item {
+    let time = chrono::DateTime::from_timestamp(now + offset, 0).unwrap_or_default();
+    let local = chrono::DateTime::from_timestamp(now + local_offset, 0).unwrap_or_default();
+
+    let place = match &zone.country {
+        Some(country) => format!("{}, {}", zone.city, country),
+        None => zone.city.clone(),
+    };
+    // Numeric abbreviations like "+09" repeat the offset
+    let zone_name = if abbr.starts_with(['+', '-']) || abbr == "UTC" {
+        format_offset(offset)
+    } else {
+        format!("{} ({})", abbr, format_offset(offset))
+    };
+
+    let mut accessories = Vec::new();
+    let difference = offset - local_offset;
+    if difference != 0 {
+        let hours = difference as f64 / 3600.0;
+        let text = if difference % 3600 == 0 {
+            format!("{:+}h", hours)
+        } else {
+            format!("{:+.1}h", hours)
+        };
+        accessories.push(t_args("{offset} from here", &[("offset", &text)]));
+    }
+    match (time.date_naive() - local.date_naive()).num_days() {
+        1 => accessories.push(t("Tomorrow")),
+        -1 => accessories.push(t("Yesterday")),
+        _ => {}
+    }
+
+    let mut item = Item::new(
+        format!("clock:{}", zone.id),
+        time.format("%H:%M").to_string(),
+    )
+    .with_subtitle(format!("{} · {}", place, zone_name))
+    .with_type(CLOCK_ITEM_TYPE)
+    .with_data(serde_json::json!({
+        "zone": zone.id,
+        "time": time.format("%H:%M").to_string(),
+        "pinned": pinned,
+    }));
+    item.accessories = accessories;
+    item
+}
+
+// =============================================================================
+// Actions
+// =============================================================================
+
+/// Actions offered for a clock item: `(id, title)`.
+///
+/// Pinned clocks offer unpinning instead of pinning.
+pub fn clock_actions(item: &Item) -> Vec<(&'static str, String)> {
+    let pinned = item
+        .data
+        .as_ref()
+        .and_then(|data| data.get("pinned")?.as_bool())
+        .unwrap_or(false);
+    let pin = if pinned {
+        ("unpin", t("Unpin from Root"))
+    } else {
+        ("pin", t("Pin to Root"))
+    };
+    vec![pin, ("copy_time", t("Copy Time"))]
+}
+
+/// Run a clock action.
+pub fn run_clock_action(action_id: &str, item: &Item, clock: &WorldClock) -> PluginResult<()> {
+    let field = |name: &str| {
+        item.data
+            .as_ref()
+            .and_then(|data| data.get(name)?.as_str())
+            .ok_or_else(|| PluginError::Command("Item is not a clock".to_string()))
+    };
+    match action_id {
+        "pin" => clock.pin(field("zone")?),
+        "unpin" => clock.unpin(field("zone")?),
+        "copy_time" => crate::desktop::current().clipboard_write(field("time")?)?,
+        _ => {
+            return Err(PluginError::Command(format!(
+                "Unknown clock action '{}'",
+                action_id
+            )))
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZONE_TAB: &str = "# comment\nJP\t+353916+1394441\tAsia/Tokyo\n\
+        US\t+404251-0740023\tAmerica/New_York\n\
+        US\t+340308-1181434\tAmerica/Los_Angeles\n\
+        AR\t-3436-05827\tAmerica/Argentina/Buenos_Aires\n\
+        IN\t+2232+08822\tAsia/Kolkata\n";
+    const ISO3166_TAB: &str = "JP\tJapan\nUS\tUnited States\nAR\tArgentina\nIN\tIndia\n";
+
+    /// A version 2 TZif file with no transitions, only a footer rule.
+    fn tzif(footer: &str) -> Vec<u8> {
+        let header = |typecnt: u32, charcnt: u32| {
+            let mut bytes = b"TZif2".to_vec();
+            bytes.extend([0; 15]);
+            for count in [0, 0, 0, 0, typecnt, charcnt] {
+                bytes.extend(u32::to_be_bytes(count));
+            }
+            bytes
+        };
+        let mut bytes = header(1, 4);
+        bytes.extend([0, 0, 0, 0, 0, 0]);
+        bytes.extend(b"UTC\0");
+        bytes.extend(header(1, 4));
+        bytes.extend((-5 * 3600i32).to_be_bytes());
+        bytes.extend([0, 0]);
+        bytes.extend(b"EST\0");
+        bytes.extend(format!("\n{}\n", footer).bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_posix_rules() {
+        let new_york = PosixTz::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        // 2026-03-08 06:59 UTC is 01:59 EST; a minute later it's 03:00 EDT
+        assert_eq!(
+            new_york.offset_at(1_772_953_140),
+            (-5 * 3600, "EST".to_string())
+        );
+        assert_eq!(
+            new_york.offset_at(1_772_953_200),
+            (-4 * 3600, "EDT".to_string())
+        );
+        // 2026-11-01 06:00 UTC is 01:00 EST again
+        assert_eq!(new_york.offset_at(1_793_512_800).0, -5 * 3600);
+
+        let sydney = PosixTz::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(sydney.offset_at(1_768_435_200).1, "AEDT"); // January
+        assert_eq!(sydney.offset_at(1_783_987_200).1, "AEST"); // July
+
+        let kolkata = PosixTz::parse("IST-5:30").unwrap();
+        assert_eq!(kolkata.offset_at(0), (19_800, "IST".to_string()));
+        let numeric = PosixTz::parse("<+0330>-3:30").unwrap();
+        assert_eq!(numeric.offset_at(0).1, "+0330");
+
+        assert_eq!(PosixTz::parse("X5"), None);
+    }
+
+    #[test]
+    fn test_parse_tzif_uses_footer() {
+        let info = TzInfo::parse(&tzif("EST5EDT,M3.2.0,M11.1.0")).unwrap();
+        assert_eq!(
+            info.offset_at(1_768_435_200),
+            Some((-5 * 3600, "EST".to_string()))
+        );
+        assert_eq!(
+            info.offset_at(1_783_987_200),
+            Some((-4 * 3600, "EDT".to_string()))
+        );
+        assert_eq!(TzInfo::parse(b"TZif"), None);
+    }
+
+    #[test]
+    fn test_search_zones() {
+        let zones = parse_zone_tab(ZONE_TAB, ISO3166_TAB);
+        let first = |place: &str| search_zones(&zones, place).first().map(|z| z.id.as_str());
+        assert_eq!(first("Tokyo"), Some("Asia/Tokyo"));
+        assert_eq!(first("tokio"), Some("Asia/Tokyo"));
+        assert_eq!(first("tokoy"), Some("Asia/Tokyo"));
+        assert_eq!(first("buenos"), Some("America/Argentina/Buenos_Aires"));
+        assert_eq!(first("aires"), Some("America/Argentina/Buenos_Aires"));
+        assert_eq!(first("san francisco"), Some("America/Los_Angeles"));
+        assert_eq!(first("mumbai"), Some("Asia/Kolkata"));
+        assert_eq!(first("utc"), Some("UTC"));
+        assert_eq!(search_zones(&zones, "united states").len(), 2);
+        assert_eq!(first("atlantis"), None);
+
+        let zone = &zones[3];
+        assert_eq!(
+            (zone.city.as_str(), zone.country.as_deref()),
+            ("Buenos Aires", Some("Argentina"))
+        );
+    }
+
+    #[test]
+    fn test_clock_query_and_item() {
+        assert_eq!(clock_query("time in Tokyo").as_deref(), Some("tokyo"));
+        assert_eq!(
+            clock_query("What time is it in New York?").as_deref(),
+            Some("new york")
+        );
+        assert_eq!(clock_query("london time").as_deref(), Some("london"));
+        assert_eq!(clock_query("time"), None);
+        assert_eq!(clock_query("tokyo"), None);
+
+        let zones = parse_zone_tab(ZONE_TAB, ISO3166_TAB);
+        // 2026-10-13 22:00 UTC: 07:00 the next day in Tokyo, 18:00 in New York (EDT)
+        let item = clock_item(&zones[0], 1_791_928_800, 9 * 3600, "JST", -4 * 3600, false);
+        assert_eq!(item.title, "07:00");
+        assert_eq!(item.subtitle.as_deref(), Some("Tokyo, Japan · JST (UTC+9)"));
+        assert_eq!(item.accessories, ["+13h from here", "Tomorrow"]);
+        let ids: Vec<&str> = clock_actions(&item).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, ["pin", "copy_time"]);
+    }
+
+    #[test]
+    fn test_pins_are_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux").join("clocks.json");
+        let clock = WorldClock::new(dir.path(), Some(path.clone()));
+        clock.pin("Asia/Tokyo");
+        clock.pin("UTC");
+        clock.pin("Asia/Tokyo");
+        clock.unpin("UTC");
+        assert_eq!(
+            WorldClock::new(dir.path(), Some(path)).pinned(),
+            ["Asia/Tokyo"]
+        );
+    }
+}
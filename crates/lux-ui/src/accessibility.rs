@@ -0,0 +1,178 @@
+//! VoiceOver support.
+//!
+//! GPUI draws the launcher itself and exposes no accessibility tree, so
+//! VoiceOver can't inspect rows or the search field. Instead the panel posts
+//! announcements (`NSAccessibilityAnnouncementRequestedNotification`) as
+//! focus moves:
+//!
+//! - Opening the launcher announces the search field and its placeholder
+//! - Moving the cursor announces the row: title, subtitle, accessories, position, selection
+//! - New results announce the result count and the row under the cursor
+//! - The action menu announces itself and each action as it is highlighted
+//!
+//! ## Focus Order
+//!
+//! Keyboard focus never leaves the search input. Everything else is driven
+//! by keybindings on the panel (defaults shown): Up/Down move the cursor, or
+//! the action-menu highlight while the menu is open; Enter runs; Tab opens
+//! the action menu; Cmd+Enter toggles selection; Escape closes the menu,
+//! clears the query, pops the view, then hides the launcher, in that order.
+
+use lux_core::i18n::{t, t_args};
+use lux_core::Item;
+
+use crate::model::ActionMenuItem;
+
+/// Spoken label for a result row, e.g. "Safari, Application, 2 of 12".
+pub fn item_label(item: &Item, position: usize, total: usize, selected: bool) -> String {
+    let mut parts = vec![item.title.clone()];
+    if let Some(subtitle) = item.subtitle.as_ref().filter(|s| !s.is_empty()) {
+        parts.push(subtitle.clone());
+    }
+    parts.extend(item.accessories.iter().cloned());
+    parts.push(position_label(position, total));
+    if selected {
+        parts.push(t("selected"));
+    }
+    parts.join(", ")
+}
+
+/// Spoken label for the search field, e.g. "Search field, Search files".
+pub fn search_field_label(placeholder: &str, view_title: Option<&str>) -> String {
+    let mut parts = vec![t("Search field")];
+    parts.extend(view_title.filter(|s| !s.is_empty()).map(str::to_string));
+    if !placeholder.is_empty() {
+        parts.push(placeholder.to_string());
+    }
+    parts.join(", ")
+}
+
+/// Spoken label for a result count ("No results", "1 result", "12 results").
+pub fn results_label(count: usize) -> String {
+    match count {
+        0 => t("No results"),
+        1 => t("1 result"),
+        n => t_args("{count} results", &[("count", &n.to_string())]),
+    }
+}
+
+/// Spoken label for a highlighted action, e.g. "Open in Browser, 1 of 3".
+pub fn action_label(action: &ActionMenuItem, position: usize, total: usize) -> String {
+    format!("{}, {}", action.title, position_label(position, total))
+}
+
+/// Spoken prompt for a destructive action awaiting confirmation.
+pub fn confirm_label(action: &ActionMenuItem) -> String {
+    t_args(
+        "Press again to confirm {action}",
+        &[("action", &action.title)],
+    )
+}
+
+/// "2 of 12" (`position` is zero-based).
+fn position_label(position: usize, total: usize) -> String {
+    t_args(
+        "{position} of {total}",
+        &[
+            ("position", &(position + 1).to_string()),
+            ("total", &total.to_string()),
+        ],
+    )
+}
+
+/// Posts announcements to VoiceOver, skipping repeats.
+///
+/// Announcements are only posted while VoiceOver is running, so the panel
+/// can call this unconditionally.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    /// Last announcement posted (repeats are dropped).
+    last: Option<String>,
+}
+
+impl Announcer {
+    /// Create an announcer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Announce text unless it repeats the previous announcement.
+    ///
+    /// Returns whether the text was new.
+    pub fn announce(&mut self, text: impl Into<String>) -> bool {
+        let text = text.into();
+        if text.is_empty() || self.last.as_ref() == Some(&text) {
+            return false;
+        }
+        #[cfg(target_os = "macos")]
+        let platform = crate::platform::current();
+        if platform.voiceover_enabled() {
+            platform.announce(&text);
+        }
+        self.last = Some(text);
+        true
+    }
+
+    /// Forget the last announcement (e.g. when the launcher is reopened).
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(title: &str) -> ActionMenuItem {
+        ActionMenuItem {
+            view_id: String::new(),
+            action_id: "open".to_string(),
+            handler_key: None,
+            title: title.to_string(),
+            icon: None,
+            shortcut: None,
+            section: None,
+            destructive: false,
+        }
+    }
+
+    #[test]
+    fn test_item_label() {
+        let mut item = Item::new("1", "Safari");
+        assert_eq!(item_label(&item, 0, 3, false), "Safari, 1 of 3");
+
+        item.subtitle = Some("Application".to_string());
+        assert_eq!(
+            item_label(&item, 1, 12, true),
+            "Safari, Application, 2 of 12, selected"
+        );
+
+        let item = Item::new("2", "prod").with_accessory("deploy");
+        assert_eq!(item_label(&item, 0, 1, false), "prod, deploy, 1 of 1");
+    }
+
+    #[test]
+    fn test_search_field_and_results_labels() {
+        assert_eq!(
+            search_field_label("Search files...", Some("Files")),
+            "Search field, Files, Search files..."
+        );
+        assert_eq!(search_field_label("", None), "Search field");
+        assert_eq!(results_label(0), "No results");
+        assert_eq!(results_label(1), "1 result");
+        assert_eq!(results_label(7), "7 results");
+        assert_eq!(action_label(&action("Open"), 0, 2), "Open, 1 of 2");
+    }
+
+    #[test]
+    fn test_announcer_skips_repeats() {
+        let mut announcer = Announcer::new();
+        assert!(announcer.announce("Safari, 1 of 3"));
+        assert!(!announcer.announce("Safari, 1 of 3"));
+        assert!(announcer.announce("Notes, 2 of 3"));
+
+        announcer.reset();
+        assert!(announcer.announce("Notes, 2 of 3"));
+        assert!(!announcer.announce(""));
+    }
+}
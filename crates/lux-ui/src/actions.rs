@@ -4,6 +4,7 @@
 //! Actions are dispatched by GPUI's key binding system.
 
 use gpui::actions;
+use lux_plugin_api::macros::is_valid_name;
 
 // =============================================================================
 // Navigation Actions
@@ -11,9 +12,29 @@ use gpui::actions;
 
 actions!(
     lux,
-    [CursorUp, CursorDown, CursorHome, CursorEnd, PageUp, PageDown,]
+    [CursorUp, CursorDown, CursorHome, CursorEnd, PageUp, PageDown, NextGroup, PrevGroup,]
 );
 
+/// Move the cursor to the first item of a group.
+///
+/// Bound by name as `jump_to_group(n)`.
+#[derive(Clone, PartialEq, Debug, gpui::Action)]
+#[action(no_json, namespace = lux)]
+pub struct JumpToGroup {
+    /// Group number, 1 for the first group with listed items.
+    pub number: usize,
+}
+
+/// Run the default action of one of the first nine results.
+///
+/// Bound by name as `execute_item(n)`.
+#[derive(Clone, PartialEq, Debug, gpui::Action)]
+#[action(no_json, namespace = lux)]
+pub struct ExecuteItem {
+    /// Result number, 1 for the first listed item.
+    pub number: usize,
+}
+
 // =============================================================================
 // Selection Actions
 // =============================================================================
@@ -24,7 +45,46 @@ actions!(lux, [ToggleSelection, SelectAll, ClearSelection,]);
 // Execution Actions
 // =============================================================================
 
-actions!(lux, [Submit, OpenActionMenu, Dismiss, Pop,]);
+actions!(
+    lux,
+    [
+        Submit,
+        OpenActionMenu,
+        Dismiss,
+        Pop,
+        Undo,
+        RepeatLastAction,
+        TogglePin,
+        DetachView,
+        ToggleMacroRecording,
+    ]
+);
+
+/// Play a macro saved with `lux.macros`.
+///
+/// Bound by name as `play_macro(name)`.
+#[derive(Clone, PartialEq, Debug, gpui::Action)]
+#[action(no_json, namespace = lux)]
+pub struct PlayMacro {
+    /// Macro name, the file name in the macros folder without `.lua`.
+    pub name: String,
+}
+
+// =============================================================================
+// App Actions
+// =============================================================================
+
+actions!(
+    lux,
+    [
+        ReloadConfig,
+        OpenConfig,
+        ToggleTheme,
+        OpenLogs,
+        ToggleDevMode,
+        ClearCaches,
+    ]
+);
 
 // =============================================================================
 // Text Editing Actions
@@ -78,6 +138,18 @@ pub fn action_from_name(name: &str) -> Option<Box<dyn gpui::Action>> {
         "cursor_end" => Some(Box::new(CursorEnd)),
         "page_up" => Some(Box::new(PageUp)),
         "page_down" => Some(Box::new(PageDown)),
+        "next_group" => Some(Box::new(NextGroup)),
+        "prev_group" => Some(Box::new(PrevGroup)),
+        name if name.starts_with("jump_to_group(") => {
+            let number = name
+                .strip_prefix("jump_to_group(")?
+                .strip_suffix(')')?
+                .trim()
+                .parse()
+                .ok()
+                .filter(|&number| number > 0)?;
+            Some(Box::new(JumpToGroup { number }))
+        }
 
         // Selection
         "toggle_selection" => Some(Box::new(ToggleSelection)),
@@ -89,6 +161,42 @@ pub fn action_from_name(name: &str) -> Option<Box<dyn gpui::Action>> {
         "open_action_menu" => Some(Box::new(OpenActionMenu)),
         "dismiss" => Some(Box::new(Dismiss)),
         "pop" => Some(Box::new(Pop)),
+        "undo" => Some(Box::new(Undo)),
+        "repeat_last_action" => Some(Box::new(RepeatLastAction)),
+        "toggle_pin" => Some(Box::new(TogglePin)),
+        "detach_view" => Some(Box::new(DetachView)),
+        name if name.starts_with("execute_item(") => {
+            let number = name
+                .strip_prefix("execute_item(")?
+                .strip_suffix(')')?
+                .trim()
+                .parse()
+                .ok()
+                .filter(|number| (1..=9).contains(number))?;
+            Some(Box::new(ExecuteItem { number }))
+        }
+        "toggle_macro_recording" => Some(Box::new(ToggleMacroRecording)),
+        name if name.starts_with("play_macro(") => {
+            let name = name
+                .strip_prefix("play_macro(")?
+                .strip_suffix(')')?
+                .trim()
+                .trim_matches('"');
+            if !is_valid_name(name) {
+                return None;
+            }
+            Some(Box::new(PlayMacro {
+                name: name.to_string(),
+            }))
+        }
+
+        // App
+        "reload_config" => Some(Box::new(ReloadConfig)),
+        "open_config" => Some(Box::new(OpenConfig)),
+        "toggle_theme" => Some(Box::new(ToggleTheme)),
+        "open_logs" => Some(Box::new(OpenLogs)),
+        "toggle_dev_mode" => Some(Box::new(ToggleDevMode)),
+        "clear_caches" => Some(Box::new(ClearCaches)),
 
         // Text editing
         "backspace" => Some(Box::new(Backspace)),
@@ -118,6 +226,9 @@ pub fn available_actions() -> &'static [&'static str] {
         "cursor_end",
         "page_up",
         "page_down",
+        "next_group",
+        "prev_group",
+        // `jump_to_group(n)` takes a group number, so it isn't listed
         // Selection
         "toggle_selection",
         "select_all",
@@ -127,6 +238,19 @@ pub fn available_actions() -> &'static [&'static str] {
         "open_action_menu",
         "dismiss",
         "pop",
+        "undo",
+        "repeat_last_action",
+        "toggle_pin",
+        "detach_view",
+        "toggle_macro_recording",
+        // `execute_item(n)` and `play_macro(name)` take an argument, so they aren't listed
+        // App
+        "reload_config",
+        "open_config",
+        "toggle_theme",
+        "open_logs",
+        "toggle_dev_mode",
+        "clear_caches",
         // Text editing
         "backspace",
         "delete",
@@ -151,7 +275,40 @@ mod tests {
     fn test_action_from_name() {
         assert!(action_from_name("cursor_up").is_some());
         assert!(action_from_name("submit").is_some());
+        assert!(action_from_name("repeat_last_action").is_some());
+        assert!(action_from_name("toggle_pin").is_some());
+        assert!(action_from_name("detach_view").is_some());
         assert!(action_from_name("unknown_action").is_none());
+
+        let jump = action_from_name("jump_to_group(3)").unwrap();
+        assert_eq!(
+            jump.as_any().downcast_ref::<JumpToGroup>(),
+            Some(&JumpToGroup { number: 3 })
+        );
+        assert!(action_from_name("jump_to_group(0)").is_none());
+        assert!(action_from_name("jump_to_group(x)").is_none());
+
+        let execute = action_from_name("execute_item(9)").unwrap();
+        assert_eq!(
+            execute.as_any().downcast_ref::<ExecuteItem>(),
+            Some(&ExecuteItem { number: 9 })
+        );
+        assert!(action_from_name("execute_item(10)").is_none());
+        assert!(action_from_name("execute_item(0)").is_none());
+
+        assert!(action_from_name("toggle_macro_recording").is_some());
+        let play = action_from_name("play_macro(open-downloads)").unwrap();
+        assert_eq!(
+            play.as_any().downcast_ref::<PlayMacro>(),
+            Some(&PlayMacro {
+                name: "open-downloads".to_string()
+            })
+        );
+        assert!(action_from_name("play_macro(../init)").is_none());
+        assert!(action_from_name("play_macro()").is_none());
+
+        assert!(action_from_name("reload_config").is_some());
+        assert!(action_from_name("clear_caches").is_some());
     }
 
     #[test]
@@ -160,5 +317,11 @@ mod tests {
         assert!(actions.contains(&"cursor_up"));
         assert!(actions.contains(&"submit"));
         assert!(actions.contains(&"dismiss"));
+
+        // Everything the Commands view lists can be bound
+        for command in lux_plugin_api::commands() {
+            assert!(actions.contains(&command.action), "{}", command.action);
+            assert!(action_from_name(command.action).is_some());
+        }
     }
 }
@@ -7,15 +7,24 @@
 //!
 //! The engine broadcasts view stack changes automatically via `tokio::sync::watch`.
 //! The UI subscribes to these changes and reacts to configuration updates.
-//! View stack mutations (push/pop/replace) in the engine auto-notify subscribers.
+//! View stack mutations (push/pop/replace) in the engine auto-notify subscribers,
+//! and each one is also sent as a typed `ViewStackEvent` saying what changed.
 
 use futures::future::BoxFuture;
+use lux_core::i18n::t;
 use lux_core::{ActionResult, BackendError, Groups, Item};
-use lux_lua_runtime::LuaRuntime;
-use lux_plugin_api::{ActionInfo, PluginRegistry, QueryEngine, ViewState};
+use lux_lua_runtime::{BatchFn, LuaOutput, LuaRuntime, Priority, RequestOptions};
+use lux_plugin_api::{
+    ActionInfo, Check, Editor, Macros, MouseSettings, PluginRegistry, ProfileContext, QueryEngine,
+    RepeatOutcome, SavedViewState, ServerConfig, SoundSettings, ThemeAppearance, ThemeOverrides,
+    UndoInfo, ViewStackEvent, ViewState,
+};
+use mlua::Lua;
+use parking_lot::Mutex;
+use std::collections::BTreeSet;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::watch;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch};
 
 // =============================================================================
 // Backend State (Type Alias)
@@ -27,6 +36,17 @@ use tokio::sync::watch;
 /// Ephemeral state (cursor, selection, query) is owned by the UI.
 pub type BackendState = Vec<ViewState>;
 
+/// What running the default (first) action did.
+#[derive(Debug, Clone)]
+pub enum DefaultActionOutcome {
+    /// No action applies to the items.
+    NoAction,
+    /// The first action is destructive: these actions await confirmation.
+    NeedsConfirmation(Vec<ActionInfo>),
+    /// The first action ran.
+    Ran(ActionResult),
+}
+
 // =============================================================================
 // Backend Trait
 // =============================================================================
@@ -42,13 +62,36 @@ pub type BackendState = Vec<ViewState>;
 /// - `execute_action()` may return `ActionResult::PushView` or `ActionResult::Pop`
 /// - State changes are broadcast via `subscribe()` for reactive UI updates
 /// - `pop_view()` is for UI-initiated navigation (e.g., Escape key)
+/// - `pop_to_view()` jumps back several views at once (e.g., a breadcrumb)
+/// - The current view stack is the latest value from `subscribe()`
 pub trait Backend: Send + Sync {
     /// Subscribe to state changes. Clone the receiver for each subscriber.
     fn subscribe(&self) -> watch::Receiver<BackendState>;
 
+    /// Subscribe to view stack change events, one per change, in order.
+    ///
+    /// Changes made before subscribing are not replayed; read `subscribe()`
+    /// for the current stack.
+    fn subscribe_changes(&self) -> broadcast::Receiver<ViewStackEvent>;
+
+    /// Subscribe to background results (e.g. Spotlight matches, new
+    /// downloads) arriving after a search returned. Re-run the search when the value changes.
+    fn subscribe_results(&self) -> watch::Receiver<u64>;
+
+    /// Subscribe to WebSocket events opened by plugins (`lux.ws`). Run
+    /// `dispatch_ws_events()` when the value changes.
+    fn subscribe_ws(&self) -> watch::Receiver<u64>;
+
     /// Search with the current query. Returns groups of results.
     fn search(&self, query: String) -> BoxFuture<'static, Result<Groups, BackendError>>;
 
+    /// Re-run a search nobody is waiting on (periodic and background refreshes).
+    ///
+    /// Runs behind interactive requests; backends without priorities search normally.
+    fn refresh(&self, query: String) -> BoxFuture<'static, Result<Groups, BackendError>> {
+        self.search(query)
+    }
+
     /// Get available actions for the given items.
     fn get_actions(
         &self,
@@ -64,7 +107,10 @@ pub trait Backend: Send + Sync {
     /// - `ActionResult::Continue` - stay on current view
     /// - `ActionResult::Complete` - show success feedback
     /// - `ActionResult::Progress` - show progress feedback
-    /// - `ActionResult::Fail` - show error feedback
+    /// - `ActionResult::Fail` - show the error, with "Retry" if `retry` is set
+    /// - `ActionResult::Edit` - put the item's row into inline edit mode
+    /// - `ActionResult::PlayMacro` - play a saved macro
+    /// - `ActionResult::RunCommand` - run a launcher action by name
     ///
     /// View stack changes are also broadcast via subscription.
     fn execute_action(
@@ -74,15 +120,117 @@ pub trait Backend: Send + Sync {
         items: Vec<Item>,
     ) -> BoxFuture<'static, Result<ActionResult, BackendError>>;
 
+    /// Pass the text submitted in an inline edit to its Lua callback.
+    ///
+    /// `handler_key` comes from `ActionResult::Edit`; `items` holds the
+    /// edited item. A `Fail` result means the row stays in edit mode.
+    fn submit_edit(
+        &self,
+        handler_key: String,
+        text: String,
+        items: Vec<Item>,
+    ) -> BoxFuture<'static, Result<ActionResult, BackendError>>;
+
+    /// Run the latest action of this session again on its original items.
+    ///
+    /// A destructive action runs only when `confirmed` is the ID of the
+    /// activity entry returned in `RepeatOutcome::NeedsConfirmation`.
+    fn repeat_last_action(
+        &self,
+        confirmed: Option<u64>,
+    ) -> BoxFuture<'static, Result<RepeatOutcome, BackendError>>;
+
+    /// Run the first action for the items (Enter without the action menu).
+    ///
+    /// A destructive first action does not run; the actions are returned
+    /// so the user can confirm it.
+    fn execute_default_action(
+        &self,
+        items: Vec<Item>,
+    ) -> BoxFuture<'static, Result<DefaultActionOutcome, BackendError>>;
+
+    /// Run the action bound to a keyboard shortcut (e.g. `"cmd+o"`).
+    ///
+    /// Shortcuts come from the actions last listed with `get_actions` for the
+    /// current view. Returns `None` if no action has the shortcut.
+    fn run_action_shortcut(
+        &self,
+        shortcut: String,
+        items: Vec<Item>,
+    ) -> BoxFuture<'static, Result<Option<ActionResult>, BackendError>>;
+
+    /// Tell the current multi-select view its selection changed.
+    ///
+    /// Runs the view's `on_selection_changed` callback with the new selection
+    /// and the IDs added and removed. Returns the selection after the
+    /// callback's changes, which the UI adopts.
+    fn selection_changed(
+        &self,
+        selection: Vec<String>,
+        added: Vec<String>,
+        removed: Vec<String>,
+    ) -> BoxFuture<'static, Result<Vec<String>, BackendError>>;
+
     /// Pop the current view (UI-initiated, e.g., Escape key).
     /// Returns true if a view was popped, false if already at root.
     /// State changes are broadcast via subscription.
     fn pop_view(&self) -> BoxFuture<'static, Result<bool, BackendError>>;
 
+    /// Pop back to the view at `index` in the stack (0 is the root).
+    /// Returns true if any view was popped.
+    /// State changes are broadcast via subscription.
+    fn pop_to_view(&self, index: usize) -> BoxFuture<'static, Result<bool, BackendError>>;
+
     /// Initialize the engine with the root view.
     /// State changes are broadcast via subscription.
     fn initialize(&self) -> BoxFuture<'static, Result<(), BackendError>>;
 
+    /// Run the current view's `on_show` callback. Called when the launcher opens.
+    fn view_shown(&self) -> BoxFuture<'static, Result<(), BackendError>>;
+
+    /// Open on the view of the first `lux.rules.on_app()` rule matching the
+    /// frontmost app. Called when the launcher opens, before it is shown.
+    ///
+    /// Returns the view's ID, or `None` if no rule matches.
+    fn open_for_app(
+        &self,
+        context: ProfileContext,
+    ) -> BoxFuture<'static, Result<Option<String>, BackendError>>;
+
+    /// Open the registered view `view_id` in a backend of its own, with a
+    /// separate view stack, for a detached window (see [`crate::detached`]).
+    fn detach_view(
+        &self,
+        view_id: String,
+    ) -> BoxFuture<'static, Result<Arc<dyn Backend>, BackendError>>;
+
+    /// Close every view on the stack, running their `on_close` callbacks.
+    /// Called when a detached window closes.
+    fn close(&self) -> BoxFuture<'static, Result<(), BackendError>>;
+
+    /// Save the UI state of a view instance so it survives being covered.
+    ///
+    /// The saved state is broadcast with the view's `ViewState` and used to
+    /// restore the view after a pop, including pops run from Lua.
+    fn save_view_state(&self, instance_id: u64, state: SavedViewState);
+
+    /// Undo the newest file operation or plugin action on the undo stack.
+    ///
+    /// Returns what the operation did, or `None` when there is nothing to undo.
+    fn undo(&self) -> BoxFuture<'static, Result<Option<String>, BackendError>>;
+
+    /// The newest operation that can be undone, offered in the undo toast.
+    fn latest_undo(&self) -> Option<UndoInfo>;
+
+    /// Number of items in the basket, shown as a badge next to the search input.
+    fn basket_count(&self) -> usize;
+
+    /// Titles of the groups of `view_id` the user collapsed, kept across launches.
+    fn collapsed_groups(&self, view_id: &str) -> BTreeSet<String>;
+
+    /// Remember that a group of `view_id` was collapsed or expanded.
+    fn set_group_collapsed(&self, view_id: &str, title: &str, collapsed: bool);
+
     /// Run a Lua key handler by ID.
     ///
     /// This is used for keybindings that map to Lua functions.
@@ -100,21 +248,88 @@ pub trait Backend: Send + Sync {
         &self,
         handler_id: &str,
     ) -> BoxFuture<'static, Result<ActionResult, BackendError>>;
+
+    /// Fire finished timers and return the menu-bar countdown text.
+    ///
+    /// Called by the UI once a second, whether or not the launcher is shown.
+    /// Finished timers post a notification and run their Lua `on_done`
+    /// callback. Returns `None` when no timers are running.
+    fn tick_timers(&self) -> BoxFuture<'static, Result<Option<String>, BackendError>>;
+
+    /// Run the `on_done` callbacks of OAuth sign-ins that finished.
+    ///
+    /// Called by the UI with the timer tick, since sign-ins finish in the
+    /// browser while the launcher is hidden.
+    fn finish_oauth_flows(&self) -> BoxFuture<'static, Result<(), BackendError>>;
+
+    /// Run the Lua callbacks of the WebSocket events received so far.
+    ///
+    /// Returns the IDs of the views the connections asked to refresh
+    /// (`view` in `lux.ws.connect()`), for re-running their search if shown.
+    fn dispatch_ws_events(&self) -> BoxFuture<'static, Result<Vec<String>, BackendError>>;
+
+    /// Pick the active profile from the frontmost app and local time.
+    ///
+    /// Called when the launcher opens. A profile pinned with
+    /// `lux.profile.set()` stays active. Returns the active profile name.
+    fn auto_switch_profile(&self, context: &ProfileContext) -> Option<String>;
+
+    /// Theme overrides set with `lux.theme.set()`.
+    fn theme_overrides(&self) -> ThemeOverrides;
+
+    /// Sound and haptic feedback settings set with `lux.ui.sounds{...}`.
+    fn sound_settings(&self) -> SoundSettings;
+
+    /// Mouse behavior of result rows set with `lux.ui.mouse{...}`.
+    fn mouse_settings(&self) -> MouseSettings;
+
+    /// The macro being recorded and the saved macros (`lux.macros`).
+    fn macros(&self) -> Arc<Macros>;
+
+    /// Override the light or dark appearance until Lux restarts (`toggle_theme`).
+    fn set_appearance(&self, appearance: ThemeAppearance);
+
+    /// Drop cached results, and refresh exchange rates and the Git index (`clear_caches`).
+    fn clear_caches(&self);
+
+    /// The editor config files and logs open in (`editor` in config.toml).
+    fn editor(&self) -> Editor;
+
+    /// Remote control server settings, if `lux.server.enable()` was called.
+    fn server_config(&self) -> Option<ServerConfig>;
+
+    /// Record a check for `lux doctor` and the Doctor view.
+    fn record_check(&self, check: Check);
+
+    /// Shut down before Lux quits, taking at most about `deadline`.
+    ///
+    /// Runs the `on_shutdown` hooks, stops background work, writes out
+    /// unsaved state, and stops the Lua thread, in that order. Blocks the
+    /// calling thread (the app is quitting, there is nothing else to run).
+    fn shutdown(&self, deadline: Duration);
 }
 
 // =============================================================================
 // Runtime Backend
 // =============================================================================
 
+/// Coalescing key of searches: a newer search drops the queued ones.
+const SEARCH_KEY: &str = "search";
+
 /// Real backend implementation using QueryEngine and LuaRuntime.
 ///
 /// View stack changes are broadcast automatically by the engine.
 /// RuntimeBackend forwards the engine's subscription channel.
+///
+/// Requests the user is waiting on (searches, actions, key presses) run at
+/// interactive priority, ahead of refreshes and timers.
 pub struct RuntimeBackend {
     engine: Arc<QueryEngine>,
     runtime: Arc<LuaRuntime>,
     registry: Arc<PluginRegistry>,
     timeout: Duration,
+    /// Coalescing key of this backend's searches (detached windows have their own).
+    search_key: String,
 }
 
 impl RuntimeBackend {
@@ -129,6 +344,7 @@ impl RuntimeBackend {
             runtime,
             registry,
             timeout: Duration::from_secs(5),
+            search_key: SEARCH_KEY.to_string(),
         }
     }
 
@@ -147,6 +363,55 @@ impl RuntimeBackend {
     pub fn runtime(&self) -> &Arc<LuaRuntime> {
         &self.runtime
     }
+
+    /// Get the plugin registry.
+    pub fn registry(&self) -> &Arc<PluginRegistry> {
+        &self.registry
+    }
+
+    /// Run several engine calls in one trip to the Lua thread, in order.
+    ///
+    /// Returns each call's result in its position; an error in one does not
+    /// stop the ones after it. Later calls see what earlier ones did, so
+    /// calls that depend on each other can share state through the closures.
+    pub fn run_batch(
+        &self,
+        options: RequestOptions,
+        calls: Vec<EngineFn>,
+    ) -> BoxFuture<'static, Result<Vec<Result<LuaOutput, String>>, BackendError>> {
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+        let calls: Vec<BatchFn> = calls
+            .into_iter()
+            .map(|call| {
+                let engine = self.engine.clone();
+                Box::new(move |lua: &Lua| call(&engine, lua)) as BatchFn
+            })
+            .collect();
+
+        Box::pin(async move { runtime.run_batch(options, timeout, calls).await })
+    }
+}
+
+/// An engine call run as part of a batch (see `RuntimeBackend::run_batch`).
+pub type EngineFn = Box<dyn FnOnce(&QueryEngine, &Lua) -> Result<LuaOutput, String> + Send>;
+
+/// Box the result of a batched call.
+fn batch_output<T: Send + 'static>(value: Result<T, String>) -> Result<LuaOutput, String> {
+    Ok(Box::new(value?))
+}
+
+/// Take the next result of a batch.
+fn next_batch_output<T: 'static>(
+    results: &mut impl Iterator<Item = Result<LuaOutput, String>>,
+) -> Result<T, BackendError> {
+    results
+        .next()
+        .ok_or_else(|| BackendError::Lua("Missing batch result".to_string()))?
+        .map_err(BackendError::Lua)?
+        .downcast::<T>()
+        .map(|value| *value)
+        .map_err(|_| BackendError::Lua("Unexpected batch result type".to_string()))
 }
 
 impl Backend for RuntimeBackend {
@@ -156,16 +421,48 @@ impl Backend for RuntimeBackend {
         self.engine.subscribe()
     }
 
+    fn subscribe_changes(&self) -> broadcast::Receiver<ViewStackEvent> {
+        self.engine.subscribe_changes()
+    }
+
+    fn subscribe_results(&self) -> watch::Receiver<u64> {
+        self.registry.spotlight().subscribe()
+    }
+
+    fn subscribe_ws(&self) -> watch::Receiver<u64> {
+        self.registry.websockets().subscribe()
+    }
+
     fn search(&self, query: String) -> BoxFuture<'static, Result<Groups, BackendError>> {
         let engine = self.engine.clone();
         let runtime = self.runtime.clone();
         let timeout = self.timeout;
+        let search_key = self.search_key.clone();
 
         Box::pin(async move {
             runtime
-                .with_lua_timeout(timeout, move |lua| {
-                    engine.search(lua, &query).map_err(|e| e.to_string())
-                })
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive).with_coalesce_key(search_key),
+                    timeout,
+                    move |lua| engine.search(lua, &query).map_err(|e| e.to_string()),
+                )
+                .await
+        })
+    }
+
+    fn refresh(&self, query: String) -> BoxFuture<'static, Result<Groups, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+        let search_key = self.search_key.clone();
+
+        Box::pin(async move {
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Background).with_coalesce_key(search_key),
+                    timeout,
+                    move |lua| engine.search(lua, &query).map_err(|e| e.to_string()),
+                )
                 .await
         })
     }
@@ -180,11 +477,15 @@ impl Backend for RuntimeBackend {
 
         Box::pin(async move {
             runtime
-                .with_lua_timeout(timeout, move |lua| {
-                    engine
-                        .get_applicable_actions(lua, &items)
-                        .map_err(|e| e.to_string())
-                })
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| {
+                        engine
+                            .get_applicable_actions(lua, &items)
+                            .map_err(|e| e.to_string())
+                    },
+                )
                 .await
         })
     }
@@ -202,21 +503,163 @@ impl Backend for RuntimeBackend {
         Box::pin(async move {
             // View stack changes are auto-broadcast by the engine
             runtime
-                .with_lua_timeout(timeout, move |lua| {
-                    engine
-                        .execute_action(lua, &view_id, &action_id, &items)
-                        .map_err(|e| e.to_string())
-                })
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| {
+                        engine
+                            .execute_action(lua, &view_id, &action_id, &items)
+                            .map_err(|e| e.to_string())
+                    },
+                )
+                .await
+        })
+    }
+
+    fn submit_edit(
+        &self,
+        handler_key: String,
+        text: String,
+        items: Vec<Item>,
+    ) -> BoxFuture<'static, Result<ActionResult, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| engine.submit_edit(lua, &handler_key, &text, &items),
+                )
+                .await
+        })
+    }
+
+    fn repeat_last_action(
+        &self,
+        confirmed: Option<u64>,
+    ) -> BoxFuture<'static, Result<RepeatOutcome, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| engine.repeat_last_action(lua, confirmed),
+                )
+                .await
+        })
+    }
+
+    fn execute_default_action(
+        &self,
+        items: Vec<Item>,
+    ) -> BoxFuture<'static, Result<DefaultActionOutcome, BackendError>> {
+        // Listing the actions leaves the first one for the second call to
+        // run, so Enter takes one trip to the Lua thread instead of two
+        let first: Arc<Mutex<Option<ActionInfo>>> = Arc::default();
+        let list_items = items.clone();
+        let list_first = first.clone();
+        let list: EngineFn = Box::new(move |engine, lua| {
+            let actions = engine.get_applicable_actions(lua, &list_items)?;
+            *list_first.lock() = actions.first().filter(|a| !a.destructive).cloned();
+            batch_output(Ok(actions))
+        });
+        let run: EngineFn = Box::new(move |engine, lua| {
+            let Some(action) = first.lock().take() else {
+                return batch_output(Ok(()));
+            };
+            batch_output(engine.execute_action(lua, &action.view_id, action.handler_key(), &items))
+        });
+        let batch = self.run_batch(RequestOptions::new(Priority::Interactive), vec![list, run]);
+
+        Box::pin(async move {
+            let mut results = batch.await?.into_iter();
+            let actions: Vec<ActionInfo> = next_batch_output(&mut results)?;
+            match actions.first().map(|first| first.destructive) {
+                None => Ok(DefaultActionOutcome::NoAction),
+                Some(true) => Ok(DefaultActionOutcome::NeedsConfirmation(actions)),
+                Some(false) => Ok(DefaultActionOutcome::Ran(next_batch_output(&mut results)?)),
+            }
+        })
+    }
+
+    fn run_action_shortcut(
+        &self,
+        shortcut: String,
+        items: Vec<Item>,
+    ) -> BoxFuture<'static, Result<Option<ActionResult>, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| engine.run_action_shortcut(lua, &shortcut, &items),
+                )
+                .await
+        })
+    }
+
+    fn selection_changed(
+        &self,
+        selection: Vec<String>,
+        added: Vec<String>,
+        removed: Vec<String>,
+    ) -> BoxFuture<'static, Result<Vec<String>, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| engine.handle_selection_changed(lua, &selection, &added, &removed),
+                )
                 .await
         })
     }
 
     fn pop_view(&self) -> BoxFuture<'static, Result<bool, BackendError>> {
         let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            // pop_view auto-broadcasts via ObservableViewStack and runs on_close
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| Ok(engine.pop_view(lua)),
+                )
+                .await
+        })
+    }
+
+    fn pop_to_view(&self, index: usize) -> BoxFuture<'static, Result<bool, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
 
         Box::pin(async move {
-            // pop_view auto-broadcasts via ObservableViewStack
-            Ok(engine.pop_view())
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| Ok(engine.pop_to_view(lua, index)),
+                )
+                .await
         })
     }
 
@@ -236,6 +679,112 @@ impl Backend for RuntimeBackend {
         })
     }
 
+    fn view_shown(&self) -> BoxFuture<'static, Result<(), BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .with_lua_timeout(timeout, move |lua| {
+                    engine.view_shown(lua);
+                    Ok(())
+                })
+                .await
+        })
+    }
+
+    fn open_for_app(
+        &self,
+        context: ProfileContext,
+    ) -> BoxFuture<'static, Result<Option<String>, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .with_lua_timeout(timeout, move |lua| Ok(engine.open_for_app(lua, &context)))
+                .await
+        })
+    }
+
+    fn detach_view(
+        &self,
+        view_id: String,
+    ) -> BoxFuture<'static, Result<Arc<dyn Backend>, BackendError>> {
+        let engine = Arc::new(QueryEngine::detached(self.registry.clone()));
+        let backend = RuntimeBackend {
+            engine: engine.clone(),
+            runtime: self.runtime.clone(),
+            registry: self.registry.clone(),
+            timeout: self.timeout,
+            search_key: format!("{}:{}", SEARCH_KEY, view_id),
+        };
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            let id = view_id.clone();
+            let opened = runtime
+                .with_lua_timeout(timeout, move |lua| Ok(engine.initialize_detached(lua, &id)))
+                .await?;
+            if !opened {
+                return Err(BackendError::Lua(format!("View not found: {}", view_id)));
+            }
+            Ok(Arc::new(backend) as Arc<dyn Backend>)
+        })
+    }
+
+    fn close(&self) -> BoxFuture<'static, Result<(), BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .with_lua_timeout(timeout, move |lua| {
+                    engine.close(lua);
+                    Ok(())
+                })
+                .await
+        })
+    }
+
+    fn save_view_state(&self, instance_id: u64, state: SavedViewState) {
+        self.engine.save_view_state(instance_id, state);
+    }
+
+    fn undo(&self) -> BoxFuture<'static, Result<Option<String>, BackendError>> {
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            runtime
+                .with_lua_timeout(timeout, move |lua| engine.undo(lua))
+                .await
+        })
+    }
+
+    fn latest_undo(&self) -> Option<UndoInfo> {
+        self.engine.latest_undo()
+    }
+
+    fn basket_count(&self) -> usize {
+        self.engine.basket_count()
+    }
+
+    fn collapsed_groups(&self, view_id: &str) -> BTreeSet<String> {
+        self.registry.collapsed_groups().collapsed(view_id)
+    }
+
+    fn set_group_collapsed(&self, view_id: &str, title: &str, collapsed: bool) {
+        self.registry
+            .collapsed_groups()
+            .set_collapsed(view_id, title, collapsed);
+    }
+
     fn run_key_handler(
         &self,
         handler_id: &str,
@@ -258,11 +807,15 @@ impl Backend for RuntimeBackend {
 
             // Execute via the engine
             runtime
-                .with_lua_timeout(timeout, move |lua| {
-                    engine
-                        .execute_lua_callback(lua, &func_ref, &items)
-                        .map_err(|e| e.to_string())
-                })
+                .request_timeout(
+                    RequestOptions::new(Priority::Interactive),
+                    timeout,
+                    move |lua| {
+                        engine
+                            .execute_lua_callback(lua, &func_ref, &items)
+                            .map_err(|e| e.to_string())
+                    },
+                )
                 .await
         })
     }
@@ -274,6 +827,198 @@ impl Backend for RuntimeBackend {
         // Global hotkey handlers receive empty context
         self.run_key_handler(handler_id, vec![])
     }
+
+    fn tick_timers(&self) -> BoxFuture<'static, Result<Option<String>, BackendError>> {
+        let timers = self.registry.timers();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            let now = Instant::now();
+            for timer in timers.take_finished(now) {
+                if let Err(e) =
+                    lux_plugin_api::desktop::current().notify(&timer.label, &t("Timer finished"))
+                {
+                    tracing::warn!("Timer notification failed: {}", e);
+                }
+
+                let Some(on_done) = timer.on_done.clone() else {
+                    continue;
+                };
+                let result = runtime
+                    .request_timeout(
+                        RequestOptions::new(Priority::Background),
+                        timeout,
+                        move |lua| {
+                            let info = lux_plugin_api::lua::timer_to_lua(lua, &timer, now)
+                                .map_err(|e| e.to_string())?;
+                            let result = on_done.call::<_, ()>(lua, info);
+                            let _ = lua.unset_named_registry_value(&on_done.key);
+                            result.map_err(|e| e.to_string())
+                        },
+                    )
+                    .await;
+                if let Err(e) = result {
+                    tracing::error!("Timer callback failed: {:?}", e);
+                }
+            }
+
+            Ok(timers.status_text(Instant::now()))
+        })
+    }
+
+    fn finish_oauth_flows(&self) -> BoxFuture<'static, Result<(), BackendError>> {
+        let flows = self.registry.oauth();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            for flow in flows.take_finished() {
+                let Some(on_done) = flow.on_done else {
+                    continue;
+                };
+                let result = flow.result;
+                let outcome = runtime
+                    .request_timeout(
+                        RequestOptions::new(Priority::Background),
+                        timeout,
+                        move |lua| {
+                            // on_done(token, err)
+                            let args = match result {
+                                Ok(token) => {
+                                    let token =
+                                        lux_plugin_api::lua::oauth_token_to_lua(lua, &token)
+                                            .map_err(|e| e.to_string())?;
+                                    (Some(token), None)
+                                }
+                                Err(e) => (None, Some(e)),
+                            };
+                            let result = on_done.call::<_, ()>(lua, args);
+                            let _ = lua.unset_named_registry_value(&on_done.key);
+                            result.map_err(|e| e.to_string())
+                        },
+                    )
+                    .await;
+                if let Err(e) = outcome {
+                    tracing::error!("OAuth callback of '{}' failed: {:?}", flow.name, e);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn dispatch_ws_events(&self) -> BoxFuture<'static, Result<Vec<String>, BackendError>> {
+        let sockets = self.registry.websockets();
+        let runtime = self.runtime.clone();
+        let timeout = self.timeout;
+
+        Box::pin(async move {
+            let events = sockets.take_events();
+            if events.is_empty() {
+                return Ok(Vec::new());
+            }
+            let views: BTreeSet<String> = events
+                .iter()
+                .filter_map(|event| event.callbacks.view.clone())
+                .collect();
+
+            // One request for the batch, so a busy stream doesn't queue a
+            // Lua request per message
+            runtime
+                .request_timeout(
+                    RequestOptions::new(Priority::Background),
+                    timeout,
+                    move |lua| {
+                        for event in &events {
+                            if let Err(e) = lux_plugin_api::lua::deliver_ws_event(lua, event) {
+                                tracing::error!("WebSocket callback failed: {}", e);
+                            }
+                        }
+                        Ok(())
+                    },
+                )
+                .await?;
+
+            Ok(views.into_iter().collect())
+        })
+    }
+
+    fn auto_switch_profile(&self, context: &ProfileContext) -> Option<String> {
+        self.registry.profiles().auto_switch(context)
+    }
+
+    fn theme_overrides(&self) -> ThemeOverrides {
+        self.registry.theme().get()
+    }
+
+    fn sound_settings(&self) -> SoundSettings {
+        self.registry.sounds().get()
+    }
+
+    fn mouse_settings(&self) -> MouseSettings {
+        self.registry.mouse().get()
+    }
+
+    fn macros(&self) -> Arc<Macros> {
+        self.registry.macros()
+    }
+
+    fn set_appearance(&self, appearance: ThemeAppearance) {
+        let overrides = ThemeOverrides {
+            appearance: Some(appearance),
+            ..Default::default()
+        };
+        if let Err(e) = self.registry.theme().set(overrides) {
+            tracing::warn!("Failed to set the appearance: {}", e);
+        }
+    }
+
+    fn clear_caches(&self) {
+        self.registry.clear_caches();
+    }
+
+    fn editor(&self) -> Editor {
+        self.registry.editor().editor()
+    }
+
+    fn server_config(&self) -> Option<ServerConfig> {
+        self.registry.server().config()
+    }
+
+    fn record_check(&self, check: Check) {
+        self.registry.diagnostics().record(check);
+    }
+
+    fn shutdown(&self, deadline: Duration) {
+        let deadline_at = Instant::now() + deadline;
+
+        // 1. on_shutdown hooks. Waited on from a helper thread, so a hook
+        //    that never returns holds up quitting only until the deadline.
+        let engine = self.engine.clone();
+        let runtime = self.runtime.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let request = runtime.request(RequestOptions::new(Priority::Interactive), move |lua| {
+                Ok(engine.run_shutdown_hooks(lua, deadline_at))
+            });
+            let _ = tx.send(futures::executor::block_on(request));
+        });
+        let left = deadline_at.saturating_duration_since(Instant::now());
+        match rx.recv_timeout(left) {
+            Ok(Ok(ran)) => tracing::info!("Ran {} on_shutdown hooks", ran),
+            Ok(Err(e)) => tracing::warn!("on_shutdown hooks failed: {}", e),
+            Err(_) => tracing::warn!("on_shutdown hooks still running at the deadline"),
+        }
+
+        // 2. Timers, recordings, and unsaved state (no Lua needed)
+        self.registry.shutdown();
+
+        // 3. The Lua thread, closing the Lua state
+        let left = deadline_at.saturating_duration_since(Instant::now());
+        if !self.runtime.shutdown_and_wait(left) {
+            tracing::warn!("Lua thread still busy at the shutdown deadline, not waiting");
+        }
+    }
 }
 
 // Keep BackendHandle as an alias for backwards compatibility
@@ -287,6 +1032,7 @@ pub type BackendHandle = RuntimeBackend;
 pub mod mock {
     use super::*;
     use lux_core::SelectionMode;
+    use lux_plugin_api::CollapsedGroups;
     use parking_lot::Mutex;
 
     /// Mock backend for testing.
@@ -295,29 +1041,42 @@ pub mod mock {
         pub search_delay: Duration,
         pub actions: Arc<Mutex<Vec<ActionInfo>>>,
         pub can_pop: Arc<Mutex<bool>>,
+        pub collapsed: CollapsedGroups,
+        pub macros: Arc<Macros>,
         /// Kept alive to keep watch channel active.
         _state_tx: watch::Sender<BackendState>,
         state_rx: watch::Receiver<BackendState>,
+        changes_tx: broadcast::Sender<ViewStackEvent>,
     }
 
     impl MockBackend {
         /// Create a new mock backend.
         pub fn new() -> Self {
             let initial_state: BackendState = vec![ViewState {
+                instance_id: 1,
                 id: None,
                 title: None,
                 placeholder: Some("Search...".to_string()),
                 selection: SelectionMode::Single,
+                selected: Vec::new(),
+                has_on_selection_changed: false,
+                refresh_ms: None,
+                initial_query: None,
+                saved: None,
             }];
             let (state_tx, state_rx) = watch::channel(initial_state);
+            let (changes_tx, _) = broadcast::channel(16);
 
             Self {
                 search_results: Arc::new(Mutex::new(vec![])),
                 search_delay: Duration::ZERO,
                 actions: Arc::new(Mutex::new(vec![])),
                 can_pop: Arc::new(Mutex::new(true)),
+                collapsed: CollapsedGroups::new(None),
+                macros: Arc::new(Macros::new(None)),
                 _state_tx: state_tx,
                 state_rx,
+                changes_tx,
             }
         }
 
@@ -351,6 +1110,20 @@ pub mod mock {
             self.state_rx.clone()
         }
 
+        fn subscribe_changes(&self) -> broadcast::Receiver<ViewStackEvent> {
+            self.changes_tx.subscribe()
+        }
+
+        fn subscribe_results(&self) -> watch::Receiver<u64> {
+            // Mock: no background results
+            watch::channel(0).1
+        }
+
+        fn subscribe_ws(&self) -> watch::Receiver<u64> {
+            // Mock: no WebSocket connections
+            watch::channel(0).1
+        }
+
         fn search(&self, _query: String) -> BoxFuture<'static, Result<Groups, BackendError>> {
             let results = self.search_results.clone();
             let delay = self.search_delay;
@@ -380,15 +1153,119 @@ pub mod mock {
             Box::pin(async move { Ok(ActionResult::Dismiss) })
         }
 
+        fn submit_edit(
+            &self,
+            _handler_key: String,
+            _text: String,
+            _items: Vec<Item>,
+        ) -> BoxFuture<'static, Result<ActionResult, BackendError>> {
+            Box::pin(async move { Ok(ActionResult::Continue) })
+        }
+
+        fn repeat_last_action(
+            &self,
+            _confirmed: Option<u64>,
+        ) -> BoxFuture<'static, Result<RepeatOutcome, BackendError>> {
+            // Mock: no actions ran
+            Box::pin(async move { Ok(RepeatOutcome::Nothing) })
+        }
+
+        fn execute_default_action(
+            &self,
+            _items: Vec<Item>,
+        ) -> BoxFuture<'static, Result<DefaultActionOutcome, BackendError>> {
+            let actions = self.actions.lock().clone();
+            Box::pin(async move {
+                Ok(match actions.first().map(|first| first.destructive) {
+                    None => DefaultActionOutcome::NoAction,
+                    Some(true) => DefaultActionOutcome::NeedsConfirmation(actions),
+                    Some(false) => DefaultActionOutcome::Ran(ActionResult::Dismiss),
+                })
+            })
+        }
+
+        fn run_action_shortcut(
+            &self,
+            _shortcut: String,
+            _items: Vec<Item>,
+        ) -> BoxFuture<'static, Result<Option<ActionResult>, BackendError>> {
+            // Mock: no shortcuts
+            Box::pin(async move { Ok(None) })
+        }
+
+        fn selection_changed(
+            &self,
+            selection: Vec<String>,
+            _added: Vec<String>,
+            _removed: Vec<String>,
+        ) -> BoxFuture<'static, Result<Vec<String>, BackendError>> {
+            // Mock: no callback, the selection stands
+            Box::pin(async move { Ok(selection) })
+        }
+
         fn pop_view(&self) -> BoxFuture<'static, Result<bool, BackendError>> {
             let can_pop = self.can_pop.clone();
             Box::pin(async move { Ok(*can_pop.lock()) })
         }
 
+        fn pop_to_view(&self, _index: usize) -> BoxFuture<'static, Result<bool, BackendError>> {
+            let can_pop = self.can_pop.clone();
+            Box::pin(async move { Ok(*can_pop.lock()) })
+        }
+
         fn initialize(&self) -> BoxFuture<'static, Result<(), BackendError>> {
             Box::pin(async move { Ok(()) })
         }
 
+        fn view_shown(&self) -> BoxFuture<'static, Result<(), BackendError>> {
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn open_for_app(
+            &self,
+            _context: ProfileContext,
+        ) -> BoxFuture<'static, Result<Option<String>, BackendError>> {
+            Box::pin(async move { Ok(None) })
+        }
+
+        fn detach_view(
+            &self,
+            _view_id: String,
+        ) -> BoxFuture<'static, Result<Arc<dyn Backend>, BackendError>> {
+            // Mock: a fresh mock with the same results
+            let backend = MockBackend::new().with_results(self.search_results.lock().clone());
+            Box::pin(async move { Ok(Arc::new(backend) as Arc<dyn Backend>) })
+        }
+
+        fn close(&self) -> BoxFuture<'static, Result<(), BackendError>> {
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn save_view_state(&self, _instance_id: u64, _state: SavedViewState) {
+            // Mock: nothing to restore
+        }
+
+        fn undo(&self) -> BoxFuture<'static, Result<Option<String>, BackendError>> {
+            // Mock: nothing to undo
+            Box::pin(async move { Ok(None) })
+        }
+
+        fn latest_undo(&self) -> Option<UndoInfo> {
+            None
+        }
+
+        fn basket_count(&self) -> usize {
+            0
+        }
+
+        fn collapsed_groups(&self, view_id: &str) -> BTreeSet<String> {
+            self.collapsed.collapsed(view_id)
+        }
+
+        fn set_group_collapsed(&self, view_id: &str, title: &str, collapsed: bool) {
+            self.collapsed.set_collapsed(view_id, title, collapsed);
+        }
+
         fn run_key_handler(
             &self,
             _handler_id: &str,
@@ -405,6 +1282,71 @@ pub mod mock {
             // Mock: global hotkey handlers are a no-op
             Box::pin(async move { Ok(ActionResult::Continue) })
         }
+
+        fn tick_timers(&self) -> BoxFuture<'static, Result<Option<String>, BackendError>> {
+            // Mock: no timers
+            Box::pin(async move { Ok(None) })
+        }
+
+        fn finish_oauth_flows(&self) -> BoxFuture<'static, Result<(), BackendError>> {
+            // Mock: no sign-ins
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn dispatch_ws_events(&self) -> BoxFuture<'static, Result<Vec<String>, BackendError>> {
+            // Mock: no WebSocket connections
+            Box::pin(async move { Ok(Vec::new()) })
+        }
+
+        fn auto_switch_profile(&self, _context: &ProfileContext) -> Option<String> {
+            // Mock: no profiles
+            None
+        }
+
+        fn theme_overrides(&self) -> ThemeOverrides {
+            // Mock: no overrides
+            ThemeOverrides::default()
+        }
+
+        fn sound_settings(&self) -> SoundSettings {
+            // Mock: sounds off
+            SoundSettings::default()
+        }
+
+        fn mouse_settings(&self) -> MouseSettings {
+            // Mock: no hover
+            MouseSettings::default()
+        }
+
+        fn macros(&self) -> Arc<Macros> {
+            self.macros.clone()
+        }
+
+        fn set_appearance(&self, _appearance: ThemeAppearance) {
+            // Mock: theme isn't overridden
+        }
+
+        fn clear_caches(&self) {
+            // Mock: nothing cached
+        }
+
+        fn editor(&self) -> Editor {
+            // Mock: the default app
+            Editor::DefaultApp
+        }
+
+        fn server_config(&self) -> Option<ServerConfig> {
+            // Mock: server disabled
+            None
+        }
+
+        fn record_check(&self, _check: Check) {
+            // Mock: no diagnostics
+        }
+
+        fn shutdown(&self, _deadline: Duration) {
+            // Mock: nothing to stop
+        }
     }
 }
 
@@ -465,6 +1407,7 @@ mod tests {
 
         let backend = MockBackend::new().with_can_pop(false);
         assert!(!backend.pop_view().await.unwrap());
+        assert!(!backend.pop_to_view(0).await.unwrap());
     }
 
     #[tokio::test]
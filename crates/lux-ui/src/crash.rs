@@ -0,0 +1,320 @@
+//! Crash reports.
+//!
+//! This module provides:
+//! - `LogBuffer` - The latest log lines, kept for crash reports
+//! - `LogFile` - The log of this run on disk, opened by `open_logs`
+//! - `install_panic_hook()` - Write a crash report to disk when anything panics
+//! - `set_active_view()` - The view a crash report names
+//! - `CrashReport` / `take_report()` - Reports, and the one left by the last run
+//!
+//! A panic on the Lua thread fails just the request that panicked (see
+//! `lux_lua_runtime`), but it still leaves a report. The launcher offers the
+//! report left by the previous run once, with a "Copy Report" button.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Log lines kept for a crash report.
+const LOG_LINES: usize = 200;
+
+/// The current view, named in crash reports.
+static ACTIVE_VIEW: Mutex<Option<String>> = Mutex::new(None);
+
+/// Record the view on top of the stack (None: the root view).
+pub fn set_active_view(view_id: Option<&str>) {
+    *ACTIVE_VIEW.lock() = view_id.map(str::to_string);
+}
+
+// =============================================================================
+// Log Buffer
+// =============================================================================
+
+/// The latest `LOG_LINES` log lines, for use as a tracing writer.
+///
+/// Text is kept back until its line ends, so a line written in pieces is one
+/// entry.
+#[derive(Clone, Default)]
+pub struct LogBuffer {
+    lines: Arc<Mutex<BufferedLines>>,
+}
+
+#[derive(Default)]
+struct BufferedLines {
+    complete: VecDeque<String>,
+    /// Bytes written since the last newline.
+    partial: Vec<u8>,
+}
+
+impl LogBuffer {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The buffered lines, oldest first.
+    ///
+    /// Empty if the buffer is locked (a panic while logging).
+    pub fn lines(&self) -> Vec<String> {
+        self.lines
+            .try_lock()
+            .map(|lines| lines.complete.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut lines = self.lines.lock();
+        let BufferedLines { complete, partial } = &mut *lines;
+        partial.extend_from_slice(buf);
+        while let Some(end) = partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = partial.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                continue;
+            }
+            if complete.len() == LOG_LINES {
+                complete.pop_front();
+            }
+            complete.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+// =============================================================================
+// Log File
+// =============================================================================
+
+/// The log written to a file, for use as a tracing writer.
+///
+/// Lines are appended, so a later launch that only hands off to the running
+/// instance doesn't overwrite its log; the instance empties the file once it
+/// knows it is the one running (`clear()`).
+#[derive(Clone)]
+pub struct LogFile {
+    file: Arc<File>,
+}
+
+impl LogFile {
+    /// Open the log file at `path` for appending, creating it if needed.
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Arc::new(file),
+        })
+    }
+
+    /// Drop the lines written so far (the last run's).
+    pub fn clear(&self) -> std::io::Result<()> {
+        self.file.set_len(0)
+    }
+
+    /// Where the log is written by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("lux.log"))
+    }
+}
+
+impl Write for LogFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        (&*self.file).write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        (&*self.file).flush()
+    }
+}
+
+// =============================================================================
+// Reports
+// =============================================================================
+
+/// What was going on when Lux panicked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashReport {
+    /// When it panicked (RFC 3339).
+    pub time: String,
+    /// Name of the thread that panicked.
+    pub thread: String,
+    /// The panic message.
+    pub message: String,
+    /// Source location of the panic.
+    pub location: Option<String>,
+    /// The view on top of the stack.
+    pub view: Option<String>,
+    /// Backtrace of the panicking thread.
+    pub backtrace: String,
+    /// The latest log lines.
+    pub log: Vec<String>,
+}
+
+impl CrashReport {
+    /// Describe the panic `info`, with the lines in `log`.
+    pub fn capture(info: &PanicHookInfo<'_>, log: &LogBuffer) -> Self {
+        let thread = std::thread::current();
+        Self {
+            time: chrono::Local::now().to_rfc3339(),
+            thread: thread.name().unwrap_or("unnamed").to_string(),
+            message: lux_core::panic_message(info.payload()),
+            location: info.location().map(|l| l.to_string()),
+            view: ACTIVE_VIEW.try_lock().and_then(|view| view.clone()),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            log: log.lines(),
+        }
+    }
+
+    /// Where the report of the last crash is saved by default.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("lux").join("crash-report.txt"))
+    }
+
+    /// The report as text, ready to paste into an issue.
+    pub fn render(&self) -> String {
+        let mut text = format!(
+            "Lux {} panicked at {}\n\nThread: {}\nView: {}\nPanic: {}",
+            env!("CARGO_PKG_VERSION"),
+            self.time,
+            self.thread,
+            self.view.as_deref().unwrap_or("root"),
+            self.message,
+        );
+        if let Some(location) = &self.location {
+            text.push_str(&format!(" ({})", location));
+        }
+        text.push_str(&format!("\n\nBacktrace:\n{}\n", self.backtrace.trim_end()));
+        text.push_str("\nRecent log:\n");
+        for line in &self.log {
+            text.push_str(line);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Write the report to `path`, replacing an older one.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.render())
+    }
+}
+
+/// Take the report a previous run left at `path`, if any.
+///
+/// The report is moved aside (to `<name>.seen.txt`), so it's offered once.
+pub fn take_report(path: &Path) -> Option<String> {
+    let report = std::fs::read_to_string(path).ok()?;
+    if let Err(e) = std::fs::rename(path, path.with_extension("seen.txt")) {
+        tracing::warn!("Failed to move crash report aside: {}", e);
+        let _ = std::fs::remove_file(path);
+    }
+    Some(report)
+}
+
+/// Save a crash report to `CrashReport::default_path()` whenever a thread
+/// panics, then run the default hook (which prints the panic).
+pub fn install_panic_hook(log: LogBuffer) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = CrashReport::capture(info, &log);
+        if let Some(path) = CrashReport::default_path() {
+            match report.save(&path) {
+                Ok(()) => eprintln!("Crash report saved to {}", path.display()),
+                Err(e) => eprintln!("Failed to save crash report: {}", e),
+            }
+        }
+        default_hook(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_buffer_keeps_latest_lines() {
+        let mut log = LogBuffer::new();
+        for i in 0..LOG_LINES + 5 {
+            writeln!(log, "line {}", i).unwrap();
+        }
+        let lines = log.lines();
+        assert_eq!(lines.len(), LOG_LINES);
+        assert_eq!(lines[0], "line 5");
+    }
+
+    #[test]
+    fn test_log_buffer_joins_partial_writes() {
+        let mut log = LogBuffer::new();
+        log.write_all(b"first ").unwrap();
+        log.write_all(b"line\r\nsecond").unwrap();
+        assert_eq!(log.lines(), ["first line"]);
+        log.write_all(b" line\n").unwrap();
+        assert_eq!(log.lines(), ["first line", "second line"]);
+    }
+
+    #[test]
+    fn test_log_file_clears_last_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux").join("lux.log");
+        writeln!(LogFile::open(&path).unwrap(), "last run").unwrap();
+
+        // Clones (one per tracing event) append to the same file
+        let mut log = LogFile::open(&path).unwrap();
+        log.clear().unwrap();
+        let mut writer = log.clone();
+        writeln!(log, "INFO starting").unwrap();
+        writeln!(writer, "INFO ready").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "INFO starting\nINFO ready\n"
+        );
+    }
+
+    #[test]
+    fn test_report_is_taken_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crash-report.txt");
+        let report = CrashReport {
+            time: "2026-01-02T03:04:05+00:00".to_string(),
+            thread: "lua".to_string(),
+            message: "index out of bounds".to_string(),
+            location: Some("src/engine.rs:10:5".to_string()),
+            view: Some("files".to_string()),
+            backtrace: "0: main".to_string(),
+            log: vec!["INFO searching".to_string()],
+        };
+        report.save(&path).unwrap();
+
+        let text = take_report(&path).unwrap();
+        assert!(text.contains("View: files"));
+        assert!(text.contains("Panic: index out of bounds (src/engine.rs:10:5)"));
+        assert!(text.ends_with("Recent log:\nINFO searching\n"));
+
+        assert_eq!(take_report(&path), None);
+        assert!(dir.path().join("crash-report.seen.txt").exists());
+    }
+}
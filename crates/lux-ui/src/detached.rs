@@ -0,0 +1,175 @@
+//! Detached mini-windows for single views.
+//!
+//! This module provides `DetachedWindows`, the registry of views popped out
+//! of the launcher (`detach_view`) into small always-on-top windows, e.g.
+//! timers, now playing, or the clipboard.
+//!
+//! Each window runs its own `LauncherPanel` on a backend from
+//! `Backend::detach_view`, so it has its own view stack and navigating in it
+//! leaves the launcher alone. A view is detached at most once; detaching it
+//! again focuses its window. Closing the window (Escape at its bottom view,
+//! or the close button) runs the views' `on_close` callbacks.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use gpui::{
+    point, px, size, App, AppContext, AsyncApp, Bounds, Global, Task, TitlebarOptions,
+    WindowBackgroundAppearance, WindowBounds, WindowHandle, WindowKind, WindowOptions,
+};
+
+use crate::backend::Backend;
+use crate::platform;
+use crate::views::{LauncherPanel, LauncherPanelEvent};
+
+/// Detached window dimensions.
+pub const DETACHED_WIDTH: f32 = 380.0;
+pub const DETACHED_HEIGHT: f32 = 320.0;
+
+/// Open detached windows by view ID (a GPUI global).
+#[derive(Default)]
+pub struct DetachedWindows {
+    windows: HashMap<String, WindowHandle<LauncherPanel>>,
+}
+
+impl Global for DetachedWindows {}
+
+impl DetachedWindows {
+    /// Whether any view is detached.
+    pub fn any_open(cx: &App) -> bool {
+        cx.try_global::<Self>()
+            .is_some_and(|detached| !detached.windows.is_empty())
+    }
+
+    /// Open `view_id` in a detached window, or focus its window if it has one.
+    ///
+    /// The task resolves to whether the view is now in a window.
+    pub fn open(backend: Arc<dyn Backend>, view_id: String, cx: &mut App) -> Task<bool> {
+        let existing = cx.default_global::<Self>().windows.get(&view_id).copied();
+        if let Some(handle) = existing {
+            let focused = handle.update(cx, |_panel, window, _cx| window.activate_window());
+            if focused.is_ok() {
+                return Task::ready(true);
+            }
+            cx.global_mut::<Self>().windows.remove(&view_id);
+        }
+
+        let detach = backend.detach_view(view_id.clone());
+        cx.spawn(async move |cx: &mut AsyncApp| match detach.await {
+            Ok(detached) => cx
+                .update(|cx| Self::open_window(detached, view_id, cx))
+                .unwrap_or(false),
+            Err(e) => {
+                tracing::warn!("Failed to detach view '{}': {:?}", view_id, e);
+                false
+            }
+        })
+    }
+
+    /// Close the detached window of `view_id`, closing its views.
+    pub fn close(view_id: &str, cx: &mut App) {
+        let Some(handle) = cx.default_global::<Self>().windows.remove(view_id) else {
+            return;
+        };
+        let _ = handle.update(cx, |panel, window, cx| {
+            close_backend(panel.backend(), cx);
+            window.remove_window();
+        });
+    }
+
+    /// Create the window for a detached backend and register it.
+    fn open_window(backend: Arc<dyn Backend>, view_id: String, cx: &mut App) -> bool {
+        let title = window_title(&view_id);
+        let window_size = size(px(DETACHED_WIDTH), px(DETACHED_HEIGHT));
+        let bounds = Bounds::centered(None, window_size, cx);
+        let options = WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(bounds)),
+            titlebar: Some(TitlebarOptions {
+                title: Some(title.clone().into()),
+                appears_transparent: true,
+                traffic_light_position: Some(point(px(10.0), px(10.0))),
+            }),
+            focus: true,
+            show: true,
+            kind: WindowKind::PopUp,
+            is_movable: true,
+            window_background: WindowBackgroundAppearance::Blurred,
+            ..Default::default()
+        };
+
+        let mut panel_entity = None;
+        let opened = cx.open_window(options, |window, cx| {
+            let panel = cx.new(|inner_cx| {
+                let mut panel = LauncherPanel::new(backend.clone(), window, inner_cx);
+                panel.set_detached(inner_cx);
+                panel
+            });
+            panel_entity = Some(panel.clone());
+            panel
+        });
+        let (handle, panel) = match (opened, panel_entity) {
+            (Ok(handle), Some(panel)) => (handle, panel),
+            (Err(e), _) => {
+                tracing::warn!("Failed to open detached window '{}': {}", view_id, e);
+                close_backend(backend, cx);
+                return false;
+            }
+            (Ok(_), None) => return false,
+        };
+
+        // Escape at the bottom view closes the window
+        let id = view_id.clone();
+        cx.subscribe(&panel, move |_, event: &LauncherPanelEvent, cx| {
+            if let LauncherPanelEvent::Dismiss = event {
+                Self::close(&id, cx);
+            }
+        })
+        .detach();
+
+        // So does the close button
+        let id = view_id.clone();
+        let weak_panel = panel.downgrade();
+        let _ = handle.update(cx, |_panel, window, cx| {
+            window.on_window_should_close(cx, move |_window, cx| {
+                cx.default_global::<Self>().windows.remove(&id);
+                if let Some(panel) = weak_panel.upgrade() {
+                    let backend = panel.read(cx).backend();
+                    close_backend(backend, cx);
+                }
+                true
+            });
+        });
+
+        platform::current().keep_window_visible(&title);
+        cx.default_global::<Self>().windows.insert(view_id, handle);
+        true
+    }
+}
+
+/// Title of the detached window for `view_id`, which also tells the
+/// platform layer which windows to keep visible.
+fn window_title(view_id: &str) -> String {
+    format!("Lux — {}", view_id)
+}
+
+/// Close the views of a detached backend in the background.
+fn close_backend(backend: Arc<dyn Backend>, cx: &App) {
+    cx.background_executor()
+        .spawn(async move {
+            if let Err(e) = backend.close().await {
+                tracing::warn!("Closing detached view failed: {:?}", e);
+            }
+        })
+        .detach();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_title() {
+        assert_eq!(window_title("timers"), "Lux — timers");
+        assert_ne!(window_title("timers"), window_title("clipboard"));
+    }
+}
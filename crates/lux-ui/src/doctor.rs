@@ -0,0 +1,58 @@
+//! Checks for `lux doctor` that need the platform layer.
+//!
+//! This module provides:
+//! - `accessibility_check()` - Whether global hotkeys can be registered at all
+//! - `hotkey_check()` - Whether one global hotkey is valid, and registered
+//! - `run_doctor()` - The `lux doctor` command
+//!
+//! The rest of the report (plugins, permissions, indexes) comes from
+//! `lux_plugin_api::doctor`.
+
+use lux_plugin_api::{diagnose, Check, PluginRegistry, Section, Severity};
+
+use lux_core::i18n::t;
+
+use crate::platform::{self, parse_hotkey};
+
+/// Accessibility access, needed for global hotkeys and pasting.
+pub fn accessibility_check() -> Check {
+    let (severity, detail) = if platform::current().has_accessibility_permission() {
+        (Severity::Ok, t("Granted"))
+    } else {
+        (
+            Severity::Error,
+            t("Not granted; global hotkeys and pasting won't work"),
+        )
+    };
+    Check::new(Section::Permissions, t("Accessibility"), severity, detail)
+}
+
+/// A global hotkey: an error if `key` doesn't parse, else whether it was
+/// registered (None: not registered yet, as in `lux doctor`).
+pub fn hotkey_check(key: &str, registered: Option<bool>) -> Check {
+    let (severity, detail) = match (parse_hotkey(key), registered) {
+        (None, _) => (Severity::Error, t("Not a valid hotkey")),
+        (Some(_), Some(true)) => (Severity::Ok, t("Registered")),
+        (Some(_), Some(false)) => (Severity::Error, t("Registration failed")),
+        (Some(_), None) => (Severity::Ok, t("Valid")),
+    };
+    Check::new(Section::Hotkeys, key, severity, detail)
+}
+
+/// Print the report for `registry` (with init.lua loaded). Returns the exit
+/// code: 1 if any check failed.
+pub fn run_doctor(registry: &PluginRegistry) -> i32 {
+    let diagnostics = registry.diagnostics();
+    diagnostics.record(accessibility_check());
+    for hotkey in registry.keymap().hotkeys() {
+        diagnostics.record(hotkey_check(&hotkey.key, None));
+    }
+
+    let report = diagnose(registry);
+    print!("{}", report.render());
+    if report.count(Severity::Error) > 0 {
+        1
+    } else {
+        0
+    }
+}
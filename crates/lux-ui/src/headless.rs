@@ -0,0 +1,209 @@
+//! The engine without a window.
+//!
+//! This module provides:
+//! - `load_init_lua()` - Run the user's init.lua, returning the outcome as a `lux doctor` check
+//! - `HeadlessBackend` - A `RuntimeBackend` with its own tokio runtime and blocking calls
+//!
+//! Nothing here needs GPUI: with `default-features = false` this crate is
+//! the engine library, for the CLI (`lux query --json`), other apps, and
+//! integration tests.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use lux_core::i18n::t;
+use lux_core::{ActionResult, BackendError, Groups, Item};
+use lux_lua_runtime::LuaRuntime;
+use lux_plugin_api::{
+    lua::register_lux_api, ActionInfo, Check, PluginRegistry, QueryEngine, Section, Severity,
+};
+use mlua::Lua;
+use tokio::runtime::Runtime;
+
+use crate::backend::{Backend, BackendState, RuntimeBackend};
+
+/// How long dropping a `HeadlessBackend` waits for `on_shutdown` hooks.
+const SHUTDOWN_DEADLINE: Duration = Duration::from_secs(1);
+
+/// Run `path` (the user's init.lua if None) in `lua`.
+///
+/// A missing or broken config is logged and leaves the defaults; the
+/// returned check says which it was.
+pub fn load_init_lua(lua: &Lua, path: Option<&Path>) -> Check {
+    let config_check =
+        |severity, detail: String| Check::new(Section::Config, "init.lua", severity, detail);
+    let Some(config_path) = path.map(Path::to_path_buf).or_else(lux_core::find_init_lua) else {
+        tracing::warn!("No init.lua found - using default configuration");
+        tracing::info!("Create ~/.config/lux/init.lua to customize");
+        return config_check(
+            Severity::Warning,
+            t("Not found; create ~/.config/lux/init.lua to customize"),
+        );
+    };
+    tracing::info!("Loading config from: {}", config_path.display());
+
+    match std::fs::read_to_string(&config_path) {
+        Ok(init_lua) => {
+            if let Err(e) = lua
+                .load(&init_lua)
+                // "@" makes error and warning locations read "path:line"
+                .set_name(format!("@{}", config_path.display()))
+                .exec()
+            {
+                tracing::error!("init.lua error: {} - continuing with no plugins", e);
+                config_check(Severity::Error, e.to_string())
+            } else {
+                tracing::info!("Config loaded successfully");
+                config_check(Severity::Ok, config_path.display().to_string())
+            }
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to read init.lua: {} - continuing with no plugins",
+                e
+            );
+            config_check(Severity::Error, format!("{}: {}", config_path.display(), e))
+        }
+    }
+}
+
+/// A `RuntimeBackend` to call from ordinary (non-async) code.
+///
+/// Owns a current-thread tokio runtime and blocks on each call, so don't
+/// call it from inside another tokio runtime; use `backend()` there.
+pub struct HeadlessBackend {
+    backend: Arc<RuntimeBackend>,
+    rt: Runtime,
+}
+
+impl HeadlessBackend {
+    /// Load `init_lua` (the user's init.lua if None) and show the root view.
+    pub fn new(init_lua: Option<&Path>) -> Result<Self, BackendError> {
+        let registry = Arc::new(PluginRegistry::new());
+        let lua = Lua::new();
+        register_lux_api(&lua, registry.clone()).map_err(|e| BackendError::Lua(e.to_string()))?;
+        registry.diagnostics().record(load_init_lua(&lua, init_lua));
+        Self::from_lua(lua, registry)
+    }
+
+    /// Use a Lua state that already has the lux API registered with
+    /// `registry` and its config run, and show the root view.
+    pub fn from_lua(lua: Lua, registry: Arc<PluginRegistry>) -> Result<Self, BackendError> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .map_err(|e| BackendError::Channel(e.to_string()))?;
+        let _guard = rt.enter();
+
+        // Lua moves to the runtime thread after its config has run
+        let engine = Arc::new(QueryEngine::new(registry.clone()));
+        let runtime = Arc::new(LuaRuntime::new(lua));
+        let backend = Arc::new(RuntimeBackend::new(engine, runtime, registry));
+        rt.block_on(backend.initialize())?;
+        Ok(Self { backend, rt })
+    }
+
+    /// The backend, for async callers and the `Backend` calls not wrapped here.
+    pub fn backend(&self) -> &Arc<RuntimeBackend> {
+        &self.backend
+    }
+
+    /// Get the plugin registry.
+    pub fn registry(&self) -> &Arc<PluginRegistry> {
+        self.backend.registry()
+    }
+
+    /// The view stack, root first.
+    pub fn views(&self) -> BackendState {
+        self.backend.subscribe().borrow().clone()
+    }
+
+    /// Search the current view.
+    pub fn search(&self, query: &str) -> Result<Groups, BackendError> {
+        self.rt.block_on(self.backend.search(query.to_string()))
+    }
+
+    /// Actions of the current view for `items`.
+    pub fn get_actions(&self, items: Vec<Item>) -> Result<Vec<ActionInfo>, BackendError> {
+        self.rt.block_on(self.backend.get_actions(items))
+    }
+
+    /// Run `action` (from `get_actions`) on `items`.
+    pub fn execute_action(
+        &self,
+        action: &ActionInfo,
+        items: Vec<Item>,
+    ) -> Result<ActionResult, BackendError> {
+        self.rt.block_on(self.backend.execute_action(
+            action.view_id.clone(),
+            action.handler_key().to_string(),
+            items,
+        ))
+    }
+
+    /// Pop the current view. Returns false at the root view.
+    pub fn pop_view(&self) -> Result<bool, BackendError> {
+        self.rt.block_on(self.backend.pop_view())
+    }
+}
+
+impl Drop for HeadlessBackend {
+    fn drop(&mut self) {
+        let _guard = self.rt.enter();
+        self.backend.shutdown(SHUTDOWN_DEADLINE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headless(config: &str) -> HeadlessBackend {
+        let registry = Arc::new(PluginRegistry::new());
+        let lua = Lua::new();
+        register_lux_api(&lua, registry.clone()).unwrap();
+        lua.load(config).exec().unwrap();
+        HeadlessBackend::from_lua(lua, registry).unwrap()
+    }
+
+    #[test]
+    fn test_search_and_run_action() {
+        let backend = headless(
+            r#"
+            lux.set_root({
+                search = function(query, ctx)
+                    ctx:set_items({ { id = "echo", title = "Echo " .. query } })
+                end,
+                get_actions = function(item, ctx)
+                    return { {
+                        id = "shout",
+                        title = "Shout",
+                        handler = function(items, ctx)
+                            ctx:dismiss()
+                        end,
+                    } }
+                end,
+            })
+            "#,
+        );
+
+        let groups = backend.search("hi").unwrap();
+        let item = groups[0].items[0].clone();
+        assert_eq!(item.title, "Echo hi");
+
+        let actions = backend.get_actions(vec![item.clone()]).unwrap();
+        assert_eq!(actions[0].id, "shout");
+        let result = backend.execute_action(&actions[0], vec![item]).unwrap();
+        assert!(matches!(result, ActionResult::Dismiss));
+        assert_eq!(backend.views().len(), 1);
+        assert!(!backend.pop_view().unwrap());
+    }
+
+    #[test]
+    fn test_unreadable_config_is_an_error() {
+        let lua = Lua::new();
+        let check = load_init_lua(&lua, Some(Path::new("/nonexistent/lux/init.lua")));
+        assert_eq!(check.severity, Severity::Error);
+    }
+}
@@ -0,0 +1,328 @@
+//! Single-instance enforcement.
+//!
+//! This module provides:
+//! - `claim()` - Become the running instance, or hand off to the one already running
+//! - `InstanceListener` - The socket a running instance listens on
+//! - `InstanceServer` - The thread that forwards commands from later launches
+//! - `relaunch()` / `wait_for_exit()` - Replace the running instance with a new one
+//!
+//! The running instance listens on a Unix socket (`default_socket_path()`).
+//! A second `lux` connects to it, sends `show`, and exits instead of opening
+//! another window and fighting over the global hotkey. A socket nobody
+//! listens on is left over from a crash and is replaced; an instance that
+//! doesn't answer in time fails the launch instead.
+//!
+//! The protocol is one line each way: a command (`show`), then `ok` or
+//! `error <message>`.
+//!
+//! `relaunch()` (Reload Config) starts a new `lux` with `RELAUNCH_ENV` set
+//! before this one quits. The new one waits until nobody answers on the
+//! socket, instead of showing the instance that is quitting.
+
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long a launch waits for the running instance to answer.
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Set for a launch that replaces the running instance.
+pub const RELAUNCH_ENV: &str = "LUX_RELAUNCH";
+
+/// How long a relaunch waits for the instance it replaces to quit.
+pub const RELAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often a relaunch checks whether the old instance has quit.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A command a later launch sends to the running instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceCommand {
+    /// Show and focus the launcher.
+    Show,
+}
+
+impl InstanceCommand {
+    /// The command's name on the wire.
+    pub fn name(self) -> &'static str {
+        match self {
+            InstanceCommand::Show => "show",
+        }
+    }
+
+    /// Parse a command name.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "show" => Some(InstanceCommand::Show),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of `claim()`.
+pub enum Claim {
+    /// No other instance is running; this process is now the instance.
+    Primary(InstanceListener),
+    /// Another instance is running and was sent the command.
+    Forwarded,
+}
+
+/// Where the running instance listens by default.
+///
+/// The runtime directory where there is one (Linux), else the data directory.
+pub fn default_socket_path() -> Option<PathBuf> {
+    dirs::runtime_dir()
+        .or_else(dirs::data_dir)
+        .map(|dir| dir.join("lux").join("lux.sock"))
+}
+
+/// Become the instance listening on `path`, or send `command` to the
+/// instance already listening there.
+pub fn claim(path: &Path, command: InstanceCommand) -> std::io::Result<Claim> {
+    match forward(path, command) {
+        Ok(()) => return Ok(Claim::Forwarded),
+        Err(e) if matches!(e.kind(), ErrorKind::ConnectionRefused | ErrorKind::NotFound) => {
+            tracing::debug!("No running instance at {}: {}", path.display(), e)
+        }
+        // Something is listening, so its socket must not be replaced
+        Err(e) => return Err(e),
+    }
+
+    // Nobody is listening, so a socket at `path` was left by a crash
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    Ok(Claim::Primary(InstanceListener {
+        listener,
+        path: path.to_path_buf(),
+    }))
+}
+
+/// Send `command` to the instance listening on `path` and wait for its reply.
+pub fn forward(path: &Path, command: InstanceCommand) -> std::io::Result<()> {
+    let mut stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+    stream.set_write_timeout(Some(FORWARD_TIMEOUT))?;
+    writeln!(stream, "{}", command.name())?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    match reply.trim_end() {
+        "ok" => Ok(()),
+        other => Err(std::io::Error::other(format!(
+            "running instance replied {:?}",
+            other.strip_prefix("error ").unwrap_or(other)
+        ))),
+    }
+}
+
+/// Start a new `lux` that takes over once this process quits.
+///
+/// The caller quits next; the new process runs init.lua from scratch.
+pub fn relaunch() -> std::io::Result<()> {
+    let program = std::env::current_exe()?;
+    std::process::Command::new(program)
+        .env(RELAUNCH_ENV, "1")
+        .spawn()?;
+    Ok(())
+}
+
+/// Wait until nobody answers on `path`, as the instance a relaunch replaces
+/// quits.
+///
+/// Returns false if it still answers after `timeout`.
+pub fn wait_for_exit(path: &Path, timeout: Duration) -> bool {
+    let started = Instant::now();
+    while UnixStream::connect(path).is_ok() {
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+    true
+}
+
+// =============================================================================
+// Listener
+// =============================================================================
+
+/// The socket of the running instance, before it serves commands.
+pub struct InstanceListener {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+impl InstanceListener {
+    /// Path of the socket.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Serve commands from later launches on a thread, calling `on_command`
+    /// for each.
+    pub fn serve(
+        self,
+        on_command: impl Fn(InstanceCommand) + Send + 'static,
+    ) -> std::io::Result<InstanceServer> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let listener = self.listener;
+        let thread = std::thread::Builder::new()
+            .name("lux-instance".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if thread_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    match stream {
+                        Ok(stream) => handle_connection(stream, &on_command),
+                        Err(e) => tracing::debug!("Instance socket accept failed: {}", e),
+                    }
+                }
+            })?;
+
+        Ok(InstanceServer {
+            path: self.path,
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// Read one command, run it, and write the reply.
+fn handle_connection(stream: UnixStream, on_command: &impl Fn(InstanceCommand)) {
+    let _ = stream.set_read_timeout(Some(FORWARD_TIMEOUT));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut line = String::new();
+    if BufReader::new(stream).read_line(&mut line).is_err() {
+        return;
+    }
+
+    let reply = match InstanceCommand::parse(line.trim()) {
+        Some(command) => {
+            tracing::info!("Another launch asked to {}", command.name());
+            on_command(command);
+            "ok".to_string()
+        }
+        None => format!("error unknown command {:?}", line.trim()),
+    };
+    let _ = writeln!(writer, "{}", reply);
+}
+
+/// Forwards commands from later launches while alive.
+///
+/// Dropping it stops the thread and removes the socket.
+pub struct InstanceServer {
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for InstanceServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Wake the blocking accept so the thread sees the flag
+        let _ = UnixStream::connect(&self.path);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_second_launch_forwards_show() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux").join("lux.sock");
+
+        let Claim::Primary(listener) = claim(&path, InstanceCommand::Show).unwrap() else {
+            panic!("first launch should become the instance");
+        };
+        let (tx, rx) = mpsc::channel();
+        let server = listener
+            .serve(move |command| tx.send(command).unwrap())
+            .unwrap();
+
+        assert!(matches!(
+            claim(&path, InstanceCommand::Show).unwrap(),
+            Claim::Forwarded
+        ));
+        assert_eq!(rx.recv().unwrap(), InstanceCommand::Show);
+
+        // Once the instance is gone, the next launch takes over
+        drop(server);
+        assert!(!path.exists());
+        assert!(matches!(
+            claim(&path, InstanceCommand::Show).unwrap(),
+            Claim::Primary(_)
+        ));
+    }
+
+    #[test]
+    fn test_relaunch_waits_for_exit() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux.sock");
+        assert!(wait_for_exit(&path, Duration::ZERO));
+
+        let Claim::Primary(listener) = claim(&path, InstanceCommand::Show).unwrap() else {
+            panic!("first launch should become the instance");
+        };
+        let server = listener.serve(|_| {}).unwrap();
+        assert!(!wait_for_exit(&path, Duration::from_millis(120)));
+
+        let quit = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            drop(server);
+        });
+        assert!(wait_for_exit(&path, Duration::from_secs(5)));
+        quit.join().unwrap();
+    }
+
+    #[test]
+    fn test_stale_socket_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux.sock");
+        // A socket file with no listener, as a crash leaves it
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        assert!(matches!(
+            claim(&path, InstanceCommand::Show).unwrap(),
+            Claim::Primary(_)
+        ));
+    }
+
+    #[test]
+    fn test_failed_forward_keeps_running_instance() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lux.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        let instance = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut line = String::new();
+            BufReader::new(stream.try_clone().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            writeln!(&stream, "error busy").unwrap();
+        });
+
+        assert!(claim(&path, InstanceCommand::Show).is_err());
+        assert!(path.exists());
+        instance.join().unwrap();
+    }
+}
@@ -1,21 +1,28 @@
 //! GPUI keybinding registration.
 //!
 //! This module provides `apply_keybindings()` which registers all pending
-//! keybindings from the KeymapRegistry with GPUI at startup.
+//! keybindings from the KeymapRegistry with GPUI at startup, and helpers for
+//! matching and displaying action shortcuts declared in `get_actions`.
 //!
 //! ## Binding Order
 //!
 //! GPUI uses last-wins semantics: later bindings override earlier ones at the
-//! same context depth. We register defaults first, then user bindings, so user
-//! bindings take precedence.
+//! same context depth. Bindings are registered from least to most specific
+//! scope (global, context, view), each in the order it was set, so view
+//! bindings win over context ones, which win over global ones, and user
+//! bindings win over the defaults of the same scope.
+//!
+//! When the winning binding's action isn't handled where the key was pressed,
+//! GPUI tries the next one. An exclusive binding is registered after a
+//! `NoAction` binding of the same key and context, which stops that fallthrough.
 
 use std::rc::Rc;
 
-#[cfg(test)]
-use gpui::Keystroke;
-use gpui::{App, DummyKeyboardMapper, KeyBinding, KeyBindingContextPredicate};
+use gpui::{App, DummyKeyboardMapper, KeyBinding, KeyBindingContextPredicate, Keystroke, NoAction};
 
-use lux_plugin_api::{KeyHandler, KeymapRegistry, PendingBinding};
+use lux_plugin_api::{
+    normalize_shortcut, KeyHandler, KeymapRegistry, PendingBinding, DEFAULT_CONTEXT,
+};
 
 use crate::actions::{action_from_name, RunLuaHandler};
 
@@ -40,6 +47,56 @@ fn parse_keystroke(s: &str) -> Result<Keystroke, String> {
     Keystroke::parse(&normalized).map_err(|e| format!("Invalid keystroke '{}': {:?}", s, e))
 }
 
+// =============================================================================
+// Action Shortcuts
+// =============================================================================
+
+/// The normalized action shortcut for a keystroke, like `"cmd+o"`.
+///
+/// Returns `None` for keystrokes without a modifier.
+pub fn shortcut_from_keystroke(keystroke: &Keystroke) -> Option<String> {
+    let modifiers = &keystroke.modifiers;
+    let mut parts = Vec::new();
+    if modifiers.control {
+        parts.push("ctrl");
+    }
+    if modifiers.alt {
+        parts.push("alt");
+    }
+    if modifiers.shift {
+        parts.push("shift");
+    }
+    if modifiers.platform {
+        parts.push("cmd");
+    }
+    parts.push(&keystroke.key);
+    normalize_shortcut(&parts.join("+"))
+}
+
+/// Display label for a normalized shortcut, like `"⇧⌘O"` for `"shift+cmd+o"`.
+pub fn shortcut_label(shortcut: &str) -> String {
+    shortcut
+        .split('+')
+        .map(|part| match part {
+            "ctrl" => "⌃".to_string(),
+            "alt" => "⌥".to_string(),
+            "shift" => "⇧".to_string(),
+            "cmd" => "⌘".to_string(),
+            "enter" => "↩".to_string(),
+            "backspace" => "⌫".to_string(),
+            "delete" => "⌦".to_string(),
+            "escape" => "⎋".to_string(),
+            "tab" => "⇥".to_string(),
+            "space" => "␣".to_string(),
+            "up" => "↑".to_string(),
+            "down" => "↓".to_string(),
+            "left" => "←".to_string(),
+            "right" => "→".to_string(),
+            key => key.to_uppercase(),
+        })
+        .collect()
+}
+
 // =============================================================================
 // Context Building
 // =============================================================================
@@ -54,7 +111,7 @@ fn build_context_predicate(
     context: Option<&str>,
     view: Option<&str>,
 ) -> Option<Rc<KeyBindingContextPredicate>> {
-    let base = context.unwrap_or("Launcher");
+    let base = context.unwrap_or(DEFAULT_CONTEXT);
     let context_str = match view {
         Some(v) => format!("{} && view_id == {}", base, v),
         None => base.to_string(),
@@ -90,6 +147,23 @@ fn apply_binding(pending: PendingBinding, cx: &mut App) {
         build_context_predicate(pending.context.as_deref(), pending.view.as_deref());
     let keystroke = normalize_keystroke(&pending.key);
 
+    // Bindings registered before this one are only reached through it
+    if pending.exclusive {
+        match KeyBinding::load(
+            &keystroke,
+            Box::new(NoAction),
+            context_predicate.clone(),
+            false, // use_key_equivalents
+            None,  // action_input
+            &DummyKeyboardMapper,
+        ) {
+            Ok(binding) => cx.bind_keys([binding]),
+            Err(e) => {
+                tracing::warn!("Failed to create binding for '{}': {:?}", pending.key, e);
+            }
+        }
+    }
+
     match pending.handler {
         KeyHandler::Action(name) => {
             // Look up built-in action and register using KeyBinding::load
@@ -165,4 +239,22 @@ mod tests {
         assert!(parse_keystroke("ctrl+n").is_ok());
         assert!(parse_keystroke("cmd-shift-z").is_ok());
     }
+
+    #[test]
+    fn test_shortcut_from_keystroke() {
+        let keystroke = parse_keystroke("cmd+shift+o").unwrap();
+        assert_eq!(
+            shortcut_from_keystroke(&keystroke),
+            Some("shift+cmd+o".to_string())
+        );
+        let keystroke = parse_keystroke("o").unwrap();
+        assert_eq!(shortcut_from_keystroke(&keystroke), None);
+    }
+
+    #[test]
+    fn test_shortcut_label() {
+        assert_eq!(shortcut_label("cmd+o"), "⌘O");
+        assert_eq!(shortcut_label("ctrl+alt+shift+cmd+enter"), "⌃⌥⇧⌘↩");
+        assert_eq!(shortcut_label("alt+f5"), "⌥F5");
+    }
 }
@@ -1,30 +1,62 @@
-//! GPUI frontend for the Lux launcher.
+//! GPUI frontend for the Lux launcher, and the engine behind it.
 //!
 //! This crate provides the native GPUI user interface including:
 //! - LauncherWindow for window management
 //! - LauncherPanel for UI composition
+//! - Detached mini-windows running a single view
 //! - Views and components
 //! - Backend integration
 //! - Lua-scriptable keybinding system
+//! - VoiceOver announcements
+//! - Crash reports
+//! - `lux doctor` checks that need the platform layer
+//! - Single-instance enforcement
+//! - A headless backend for the CLI, other apps, and tests
+//!
+//! The window and everything else that needs GPUI is behind the default
+//! `gui` feature. With `default-features = false` the crate builds only
+//! the backend, the UI model, crash reports, and the instance socket.
 
+#[cfg(feature = "gui")]
+pub mod accessibility;
+#[cfg(feature = "gui")]
 pub mod actions;
 pub mod backend;
+pub mod crash;
+#[cfg(feature = "gui")]
+pub mod detached;
+#[cfg(feature = "gui")]
+pub mod doctor;
+pub mod headless;
+pub mod instance;
+#[cfg(feature = "gui")]
 pub mod keymap;
 pub mod model;
+#[cfg(feature = "gui")]
 pub mod platform;
+#[cfg(feature = "gui")]
 pub mod theme;
+#[cfg(feature = "gui")]
 pub mod views;
+#[cfg(feature = "gui")]
 pub mod window;
 
 // Re-export commonly used types
-pub use backend::{Backend, BackendHandle, BackendState, RuntimeBackend};
+pub use backend::{Backend, BackendHandle, BackendState, DefaultActionOutcome, RuntimeBackend};
+#[cfg(feature = "gui")]
+pub use detached::DetachedWindows;
+pub use headless::HeadlessBackend;
 pub use lux_core::SelectionMode;
 pub use model::{
-    ActionMenuItem, ActionMenuState, ActiveState, ExecutionFeedback, LauncherPhase, ListEntry,
-    ViewFrame, ViewId, ViewStack,
+    ease_scroll, group_starts, max_scroll, next_group_start, page_rows, prev_group_start,
+    scroll_into_view, sticky_header, ActionMenuItem, ActionMenuState, ActiveState, CompletionState,
+    ExecutionFeedback, LauncherPhase, ListEntry, ViewFrame, ViewId, ViewStack,
 };
-pub use theme::{Appearance, Theme, ThemeExt, ThemeSettings};
+#[cfg(feature = "gui")]
+pub use theme::{Appearance, SystemPreferences, Theme, ThemeExt, ThemeSettings};
+#[cfg(feature = "gui")]
 pub use views::{
     scroll_to_cursor, LauncherPanel, LauncherPanelEvent, SearchInput, SearchInputEvent,
 };
+#[cfg(feature = "gui")]
 pub use window::{run_launcher, LauncherWindow};
@@ -3,45 +3,23 @@
 //! Initializes the plugin system, creates the RuntimeBackend,
 //! and starts the GPUI application.
 
+use std::path::Path;
 use std::sync::Arc;
 
-use lux_lua_runtime::LuaRuntime;
+use lux_lua_runtime::{GcSchedule, LuaRuntime};
 use lux_plugin_api::{
-    lua::register_lux_api, BuiltInHotkey, GlobalHandler, KeyHandler, KeymapRegistry,
-    PendingBinding, PendingHotkey, PluginRegistry, QueryEngine,
+    lua::register_lux_api, Backup, BindingSource, BuiltInHotkey, GlobalHandler, KeyHandler,
+    KeymapRegistry, PendingBinding, PendingHotkey, PluginRegistry, QueryEngine, Redacted,
+    BASKET_OPEN_KEY,
 };
 use lux_ui::backend::{Backend, RuntimeBackend};
+use lux_ui::crash::{self, LogBuffer, LogFile};
+use lux_ui::headless::{load_init_lua, HeadlessBackend};
+use lux_ui::instance::{self, Claim, InstanceCommand};
 use lux_ui::platform::Hotkey;
 use lux_ui::window::run_launcher;
 use mlua::Lua;
-
-// =============================================================================
-// Configuration
-// =============================================================================
-
-/// Get the path to the user's init.lua configuration file.
-///
-/// Tries paths in order:
-/// 1. XDG-style: ~/.config/lux/init.lua (common for CLI tools)
-/// 2. Platform config: ~/Library/Application Support/lux/init.lua (macOS)
-fn get_config_path() -> Option<std::path::PathBuf> {
-    // Try XDG-style first (common for CLI tools)
-    if let Some(home) = dirs::home_dir() {
-        let xdg_path = home.join(".config").join("lux").join("init.lua");
-        if xdg_path.exists() {
-            return Some(xdg_path);
-        }
-    }
-
-    // Fall back to platform-specific config dir
-    let config_dir = dirs::config_dir()?;
-    let path = config_dir.join("lux").join("init.lua");
-    if path.exists() {
-        Some(path)
-    } else {
-        None
-    }
-}
+use tracing_subscriber::prelude::*;
 
 // =============================================================================
 // Default Keybindings
@@ -58,30 +36,125 @@ fn register_default_bindings(keymap: &KeymapRegistry) {
         handler: KeyHandler::Action("cursor_up".to_string()),
         context: Some("Launcher".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "down".to_string(),
         handler: KeyHandler::Action("cursor_down".to_string()),
         context: Some("Launcher".to_string()),
         view: None,
+        exclusive: false,
     });
+    keymap.set(PendingBinding {
+        key: "pageup".to_string(),
+        handler: KeyHandler::Action("page_up".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "pagedown".to_string(),
+        handler: KeyHandler::Action("page_down".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "ctrl+down".to_string(),
+        handler: KeyHandler::Action("next_group".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "ctrl+up".to_string(),
+        handler: KeyHandler::Action("prev_group".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    for number in 1..=9 {
+        keymap.set(PendingBinding {
+            key: format!("alt+{}", number),
+            handler: KeyHandler::Action(format!("jump_to_group({})", number)),
+            context: Some("Launcher".to_string()),
+            view: None,
+            exclusive: false,
+        });
+    }
+    for number in 1..=9 {
+        keymap.set(PendingBinding {
+            key: format!("cmd+{}", number),
+            handler: KeyHandler::Action(format!("execute_item({})", number)),
+            context: Some("Launcher".to_string()),
+            view: None,
+            exclusive: false,
+        });
+    }
     keymap.set(PendingBinding {
         key: "tab".to_string(),
         handler: KeyHandler::Action("open_action_menu".to_string()),
         context: Some("Launcher".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "cmd+enter".to_string(),
         handler: KeyHandler::Action("toggle_selection".to_string()),
         context: Some("Launcher".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "escape".to_string(),
         handler: KeyHandler::Action("dismiss".to_string()),
         context: Some("Launcher".to_string()),
         view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "cmd+z".to_string(),
+        handler: KeyHandler::Action("undo".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "cmd+r".to_string(),
+        handler: KeyHandler::Action("repeat_last_action".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "cmd+shift+p".to_string(),
+        handler: KeyHandler::Action("toggle_pin".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "cmd+shift+d".to_string(),
+        handler: KeyHandler::Action("detach_view".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "cmd+shift+m".to_string(),
+        handler: KeyHandler::Action("toggle_macro_recording".to_string()),
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
+    });
+    keymap.set(PendingBinding {
+        key: "cmd+shift+b".to_string(),
+        handler: KeyHandler::Function {
+            id: BASKET_OPEN_KEY.to_string(),
+        },
+        context: Some("Launcher".to_string()),
+        view: None,
+        exclusive: false,
     });
 
     // Text editing - SearchInput context
@@ -90,78 +163,91 @@ fn register_default_bindings(keymap: &KeymapRegistry) {
         handler: KeyHandler::Action("backspace".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "delete".to_string(),
         handler: KeyHandler::Action("delete".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "left".to_string(),
         handler: KeyHandler::Action("move_left".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "right".to_string(),
         handler: KeyHandler::Action("move_right".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "shift+left".to_string(),
         handler: KeyHandler::Action("select_left".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "shift+right".to_string(),
         handler: KeyHandler::Action("select_right".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "cmd+a".to_string(),
         handler: KeyHandler::Action("text_select_all".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "home".to_string(),
         handler: KeyHandler::Action("home".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "end".to_string(),
         handler: KeyHandler::Action("end".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "cmd+c".to_string(),
         handler: KeyHandler::Action("copy".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "cmd+v".to_string(),
         handler: KeyHandler::Action("paste".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "cmd+x".to_string(),
         handler: KeyHandler::Action("cut".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
     keymap.set(PendingBinding {
         key: "enter".to_string(),
         handler: KeyHandler::Action("submit".to_string()),
         context: Some("SearchInput".to_string()),
         view: None,
+        exclusive: false,
     });
 
     tracing::debug!(
@@ -190,6 +276,13 @@ fn create_backend() -> Result<(Arc<RuntimeBackend>, Arc<KeymapRegistry>), String
     let registry = Arc::new(PluginRegistry::new());
     tracing::info!("Plugin registry created");
 
+    // Step 1.5: Pick the UI language (init.lua can override with lux.i18n.set_locale)
+    let locale = lux_core::i18n::detect_locale()
+        .or_else(|| lux_ui::platform::current().system_locale())
+        .unwrap_or_else(|| lux_core::i18n::DEFAULT_LOCALE.to_string());
+    lux_core::i18n::set_locale(&locale);
+    tracing::info!("Locale: {}", lux_core::i18n::locale());
+
     // Step 2: Create Lua state and register the lux API
     let lua = Lua::new();
     register_lux_api(&lua, registry.clone())
@@ -198,6 +291,8 @@ fn create_backend() -> Result<(Arc<RuntimeBackend>, Arc<KeymapRegistry>), String
 
     // Step 2.5: Register default global hotkey (before user config loads)
     // User can override this in init.lua with lux.keymap.del_global() + set_global()
+    // (a plain set_global() is reported in the Keymap Conflicts view)
+    registry.keymap().set_source(BindingSource::Default);
     registry.keymap().set_global(PendingHotkey {
         key: "cmd+shift+space".to_string(),
         handler: GlobalHandler::BuiltIn(BuiltInHotkey::ToggleLauncher),
@@ -207,34 +302,11 @@ fn create_backend() -> Result<(Arc<RuntimeBackend>, Arc<KeymapRegistry>), String
     // Step 2.6: Register default GPUI bindings (before user config loads)
     // User can override these in init.lua with lux.keymap.del() + lux.keymap.set()
     register_default_bindings(registry.keymap().as_ref());
+    registry.keymap().set_source(BindingSource::Config);
 
     // Step 3: Load init.lua if it exists (graceful degradation on error)
-    if let Some(config_path) = get_config_path() {
-        tracing::info!("Loading config from: {}", config_path.display());
-
-        match std::fs::read_to_string(&config_path) {
-            Ok(init_lua) => {
-                if let Err(e) = lua
-                    .load(&init_lua)
-                    .set_name(config_path.to_string_lossy())
-                    .exec()
-                {
-                    tracing::error!("init.lua error: {} - continuing with no plugins", e);
-                } else {
-                    tracing::info!("Config loaded successfully");
-                }
-            }
-            Err(e) => {
-                tracing::error!(
-                    "Failed to read init.lua: {} - continuing with no plugins",
-                    e
-                );
-            }
-        }
-    } else {
-        tracing::warn!("No init.lua found - using default configuration");
-        tracing::info!("Create ~/.config/lux/init.lua to customize");
-    }
+    // The outcome is recorded for `lux doctor`
+    registry.diagnostics().record(load_init_lua(&lua, None));
 
     // Get keymap from registry (holds Lua function handlers + pending bindings + hotkeys)
     let keymap = registry.keymap();
@@ -251,7 +323,13 @@ fn create_backend() -> Result<(Arc<RuntimeBackend>, Arc<KeymapRegistry>), String
 
     // Step 5: Move Lua to dedicated runtime thread
     // IMPORTANT: Lua must be moved AFTER loading init.lua
-    let runtime = Arc::new(LuaRuntime::new(lua));
+    // (init.lua can change the GC settings with lux.runtime.gc)
+    let gc = registry.memory().gc_settings();
+    let schedule = gc.interval.map(|interval| GcSchedule {
+        interval,
+        step_kbytes: gc.step_kbytes.min(i32::MAX as u32) as i32,
+    });
+    let runtime = Arc::new(LuaRuntime::with_gc(lua, schedule));
     tracing::info!("Lua runtime started");
 
     // Step 6: Create the backend (connects engine, runtime, and registry)
@@ -274,21 +352,232 @@ fn initialize_backend(backend: &Arc<RuntimeBackend>) -> Result<(), String> {
     Ok(())
 }
 
+// =============================================================================
+// Subcommands
+// =============================================================================
+
+/// Run a `lux <command>` subcommand instead of the launcher.
+///
+/// Returns the exit code, or `None` if the arguments don't name a subcommand.
+fn run_command(args: &[String]) -> Option<i32> {
+    match args.first().map(String::as_str) {
+        Some("import") => Some(import_command(&args[1..])),
+        Some("doctor") => Some(doctor_command()),
+        Some("query") => Some(query_command(&args[1..])),
+        Some("backup") => Some(backup_command(&args[1..])),
+        _ => None,
+    }
+}
+
+/// `lux query [--json] <text>` - search the root view and print the results.
+fn query_command(args: &[String]) -> i32 {
+    let json = args.iter().any(|arg| arg == "--json");
+    let words: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--json")
+        .collect();
+    if words.is_empty() {
+        eprintln!("Usage: lux query [--json] <text>");
+        return 2;
+    }
+
+    let locale = lux_core::i18n::detect_locale()
+        .unwrap_or_else(|| lux_core::i18n::DEFAULT_LOCALE.to_string());
+    lux_core::i18n::set_locale(&locale);
+
+    let groups =
+        match HeadlessBackend::new(None).and_then(|backend| backend.search(&words.join(" "))) {
+            Ok(groups) => groups,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return 1;
+            }
+        };
+    if json {
+        match serde_json::to_string_pretty(&groups) {
+            Ok(text) => println!("{}", text),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return 1;
+            }
+        }
+    } else {
+        for group in &groups {
+            println!("{}", group.title.as_deref().unwrap_or(""));
+            for item in &group.items {
+                match &item.subtitle {
+                    Some(subtitle) => println!("  {} - {}", item.title, subtitle),
+                    None => println!("  {}", item.title),
+                }
+            }
+        }
+    }
+    0
+}
+
+/// `lux doctor` - load init.lua and print the diagnostics report.
+fn doctor_command() -> i32 {
+    match create_backend() {
+        Ok((backend, _keymap)) => lux_ui::doctor::run_doctor(backend.registry()),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// `lux backup create/restore/sync/unlock` - back up, restore, or sync the
+/// config and data folders.
+fn backup_command(args: &[String]) -> i32 {
+    const USAGE: &str =
+        "Usage: lux backup create [<file>] | restore <file> | sync <folder> | unlock";
+    let Some(backup) = Backup::current() else {
+        eprintln!("Error: no config or data directory");
+        return 1;
+    };
+    let path = args.get(1).map(Path::new);
+
+    let result = match (args.first().map(String::as_str), path) {
+        (Some("create"), dest) => backup
+            .create(dest)
+            .map(|file| println!("Backed up to {}", file.display())),
+        (Some("restore"), Some(file)) => backup.restore(file).map(|restored| {
+            for dir in restored {
+                println!("Will restore {}", dir.display());
+            }
+            println!("Lux restores the backup the next time it starts");
+        }),
+        (Some("sync"), Some(folder)) => backup.sync_to(folder).map(|synced| {
+            println!("The data folder is now {}", synced.display());
+            println!("Restart Lux to use it");
+        }),
+        (Some("unlock"), None) => backup.unlock().map(|removed| {
+            if removed {
+                println!("Removed the sync lock");
+            } else {
+                println!("No sync lock to remove");
+            }
+        }),
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+/// `lux import alfred <path>...` - generate Lux plugins from Alfred workflows.
+fn import_command(args: &[String]) -> i32 {
+    let paths = match args.split_first() {
+        Some((source, paths)) if source == "alfred" && !paths.is_empty() => paths,
+        _ => {
+            eprintln!("Usage: lux import alfred <workflow folder or .alfredworkflow>...");
+            return 2;
+        }
+    };
+    let Some(out_dir) = lux_plugin_api::alfred::plugin_dir() else {
+        eprintln!("Error: no config directory");
+        return 1;
+    };
+
+    let mut code = 0;
+    for path in paths {
+        match lux_plugin_api::alfred::import_workflow(Path::new(path), &out_dir) {
+            Ok((workflow, file)) => {
+                println!("Imported \"{}\" to {}", workflow.name, file.display());
+                for kind in &workflow.skipped {
+                    println!("  skipped: {}", kind);
+                }
+                println!("  load it from init.lua: dofile(\"{}\")", file.display());
+            }
+            Err(e) => {
+                eprintln!("Error: {}: {}", path, e);
+                code = 1;
+            }
+        }
+    }
+    code
+}
+
 // =============================================================================
 // Entry Point
 // =============================================================================
 
 fn main() {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
+    // Subcommands (`lux import ...`) exit without starting the launcher
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = run_command(&args) {
+        std::process::exit(code);
+    }
+
+    // Initialize logging, keeping the latest lines for crash reports and the
+    // whole log for "Open Logs" (values from lux.secrets are redacted from the
+    // terminal, the log file, and the crash report)
+    let log = LogBuffer::new();
+    let log_writer = log.clone();
+    let log_file = LogFile::default_path().and_then(|path| {
+        LogFile::open(&path)
+            .map_err(|e| eprintln!("Failed to create {}: {}", path.display(), e))
+            .ok()
+    });
+    tracing_subscriber::registry()
+        .with(
             tracing_subscriber::EnvFilter::from_default_env()
                 .add_directive(tracing::Level::INFO.into()),
         )
+        .with(tracing_subscriber::fmt::layer().with_writer(|| Redacted::new(std::io::stdout())))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(move || Redacted::new(log_writer.clone()))
+                .with_ansi(false),
+        )
+        .with(log_file.clone().map(|file| {
+            tracing_subscriber::fmt::layer()
+                .with_writer(move || Redacted::new(file.clone()))
+                .with_ansi(false)
+        }))
         .init();
+    crash::install_panic_hook(log);
 
     tracing::info!("Lux Launcher starting...");
 
+    // A relaunch (Reload Config) waits for the instance it replaces to quit
+    let relaunched = std::env::var_os(instance::RELAUNCH_ENV).is_some();
+    if let Some(path) = instance::default_socket_path().filter(|_| relaunched) {
+        if !instance::wait_for_exit(&path, instance::RELAUNCH_TIMEOUT) {
+            tracing::warn!("The instance being replaced is still running, showing it instead");
+        }
+    }
+
+    // Hand off to a running instance instead of starting a second one
+    let instance = match instance::default_socket_path() {
+        Some(path) => match instance::claim(&path, InstanceCommand::Show) {
+            Ok(Claim::Primary(listener)) => Some(listener),
+            Ok(Claim::Forwarded) => {
+                tracing::info!("Lux is already running, showed it instead");
+                return;
+            }
+            Err(e) => {
+                tracing::error!("Failed to claim {}: {}", path.display(), e);
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // This process is the running instance, so the log file is its own
+    if let Some(Err(e)) = log_file.as_ref().map(LogFile::clear) {
+        tracing::warn!("Failed to clear the log file: {}", e);
+    }
+
     // Create a tokio runtime and enter its context.
     // This keeps tokio available for the entire lifetime of the app,
     // which is needed for tokio channels used in RuntimeBackend and LuaRuntime.
@@ -298,6 +587,30 @@ fn main() {
         .expect("Failed to create tokio runtime");
     let _guard = rt.enter();
 
+    // A restored backup replaces the folders before the stores load, so
+    // nothing still running saves over it
+    if let Some(backup) = Backup::current() {
+        match backup.apply_pending_restore() {
+            Ok(restored) => {
+                for dir in restored {
+                    tracing::info!("Restored {} from a backup", dir.display());
+                }
+            }
+            Err(e) => tracing::error!("Failed to restore the backup: {}", e),
+        }
+    }
+
+    // A synced data folder is used by one machine at a time; take it before
+    // the stores load
+    let sync_lock = match Backup::current().map(|backup| backup.lock()).transpose() {
+        Ok(lock) => lock.flatten(),
+        Err(e) => {
+            tracing::error!("{}", e);
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Create and initialize the backend
     let (backend, keymap) = match create_backend() {
         Ok(result) => result,
@@ -307,6 +620,9 @@ fn main() {
             std::process::exit(1);
         }
     };
+    if let Some(lock) = sync_lock {
+        backend.registry().hold_sync_lock(lock);
+    }
 
     if let Err(e) = initialize_backend(&backend) {
         tracing::error!("Failed to initialize backend: {}", e);
@@ -321,5 +637,5 @@ fn main() {
 
     // Run the GPUI application with keymap for binding registration
     tracing::info!("Starting GPUI application...");
-    run_launcher(hotkey, backend, keymap);
+    run_launcher(hotkey, backend, keymap, instance);
 }
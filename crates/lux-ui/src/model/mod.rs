@@ -6,6 +6,7 @@
 mod state;
 
 pub use state::{
-    ActionMenuItem, ActionMenuState, ActiveState, ExecutionFeedback, LauncherPhase, ListEntry,
-    ViewFrame, ViewId, ViewStack,
+    ease_scroll, group_starts, max_scroll, next_group_start, page_rows, prev_group_start,
+    scroll_into_view, sticky_header, ActionMenuItem, ActionMenuState, ActiveState, CompletionState,
+    ExecutionFeedback, LauncherPhase, ListEntry, ViewFrame, ViewId, ViewStack,
 };
@@ -3,8 +3,10 @@
 //! This module defines the state machine and data structures for the UI layer.
 //! These types are GPUI-independent to enable testing and clear separation.
 
-use lux_core::{Group, Item, ItemId, SelectionMode};
+use lux_core::i18n::t;
+use lux_core::{FollowUpAction, Group, HandlerError, Item, ItemId, SelectionMode};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 // =============================================================================
 // Launcher Phase State Machine
@@ -92,6 +94,9 @@ pub struct ActionMenuState {
 
     /// Currently highlighted action index.
     pub cursor_index: usize,
+
+    /// Whether the highlighted destructive action is awaiting confirmation.
+    pub confirming: bool,
 }
 
 impl ActionMenuState {
@@ -100,6 +105,7 @@ impl ActionMenuState {
         Self {
             actions,
             cursor_index: 0,
+            confirming: false,
         }
     }
 
@@ -107,6 +113,7 @@ impl ActionMenuState {
     pub fn cursor_up(&mut self) {
         if self.cursor_index > 0 {
             self.cursor_index -= 1;
+            self.confirming = false;
         }
     }
 
@@ -114,6 +121,7 @@ impl ActionMenuState {
     pub fn cursor_down(&mut self) {
         if self.cursor_index + 1 < self.actions.len() {
             self.cursor_index += 1;
+            self.confirming = false;
         }
     }
 
@@ -121,6 +129,28 @@ impl ActionMenuState {
     pub fn selected_action(&self) -> Option<&ActionMenuItem> {
         self.actions.get(self.cursor_index)
     }
+
+    /// Confirm the selected action, returning whether it should run now.
+    ///
+    /// The first confirmation of a destructive action only arms it.
+    pub fn confirm_selected(&mut self) -> bool {
+        let destructive = self
+            .selected_action()
+            .is_some_and(|action| action.destructive);
+        if destructive && !self.confirming {
+            self.confirming = true;
+            return false;
+        }
+        true
+    }
+
+    /// Whether the action at `index` begins a new section of the menu.
+    pub fn starts_section(&self, index: usize) -> bool {
+        match (index.checked_sub(1), self.actions.get(index)) {
+            (Some(prev), Some(action)) => self.actions[prev].section != action.section,
+            _ => false,
+        }
+    }
 }
 
 /// An action in the menu.
@@ -140,6 +170,22 @@ pub struct ActionMenuItem {
 
     /// Optional icon.
     pub icon: Option<String>,
+
+    /// Normalized keyboard shortcut that runs the action, like `"cmd+o"`.
+    pub shortcut: Option<String>,
+
+    /// Menu section the action is listed under (empty for an untitled section).
+    pub section: Option<String>,
+
+    /// Whether the action needs confirmation before it runs.
+    pub destructive: bool,
+}
+
+impl ActionMenuItem {
+    /// Key to execute the action with: the handler key, or the action ID.
+    pub fn handler_key(&self) -> &str {
+        self.handler_key.as_deref().unwrap_or(&self.action_id)
+    }
 }
 
 // =============================================================================
@@ -159,6 +205,71 @@ pub enum ExecutionFeedback {
     Failed { error: String },
 }
 
+// =============================================================================
+// Completion View
+// =============================================================================
+
+/// Transient view shown after an action completes with follow-up actions,
+/// or after it fails.
+#[derive(Debug)]
+pub struct CompletionState {
+    /// Completion message, or the error message.
+    pub message: String,
+
+    /// Whether the action failed.
+    pub failed: bool,
+
+    /// More about the failure, shown under the message.
+    pub detail: Option<String>,
+
+    /// Follow-up actions, navigated like the action menu.
+    pub follow_ups: ActionMenuState,
+}
+
+impl CompletionState {
+    /// Create a completion view for follow-ups offered by a view's action.
+    pub fn new(message: String, view_id: &str, follow_ups: Vec<FollowUpAction>) -> Self {
+        let actions = follow_ups
+            .into_iter()
+            .map(|follow_up| ActionMenuItem {
+                view_id: view_id.to_string(),
+                action_id: follow_up
+                    .handler_key
+                    .clone()
+                    .unwrap_or_else(|| follow_up.title.clone()),
+                handler_key: follow_up.handler_key,
+                title: follow_up.title,
+                icon: follow_up.icon,
+                shortcut: None,
+                section: None,
+                destructive: false,
+            })
+            .collect();
+        Self {
+            message,
+            failed: false,
+            detail: None,
+            follow_ups: ActionMenuState::new(actions),
+        }
+    }
+
+    /// Create a failure view for an action of `view_id`.
+    ///
+    /// `retry` is the action to run again, offered as "Retry".
+    pub fn failed(error: &HandlerError, view_id: &str, retry: Option<String>) -> Self {
+        let retry = retry.map(|handler_key| FollowUpAction {
+            title: t("Retry"),
+            icon: None,
+            handler_key: Some(handler_key),
+        });
+        Self {
+            failed: true,
+            detail: error.detail.clone(),
+            ..Self::new(error.message.clone(), view_id, retry.into_iter().collect())
+        }
+    }
+}
+
 // =============================================================================
 // View Stack
 // =============================================================================
@@ -324,7 +435,7 @@ impl ViewFrame {
             cursor_index: 0,
             selection_mode: SelectionMode::Single,
             selected_ids: HashSet::new(),
-            placeholder: "Search...".to_string(),
+            placeholder: lux_core::i18n::t("Search..."),
             title: None,
             scroll_position: 0.0,
         }
@@ -386,13 +497,16 @@ impl ViewFrame {
             if let Some(title) = &group.title {
                 entries.push(ListEntry::GroupHeader {
                     title: title.clone(),
+                    collapsible: group.collapsible,
+                    collapsed: false,
+                    item_count: group.items.len(),
                 });
             }
 
             // Add items
             for item in &group.items {
                 entries.push(ListEntry::Item {
-                    item: item.clone(),
+                    item: Arc::new(item.clone()),
                     flat_index,
                 });
                 flat_index += 1;
@@ -491,16 +605,130 @@ impl ViewFrame {
 #[derive(Debug, Clone)]
 pub enum ListEntry {
     /// A group header row.
-    GroupHeader { title: String },
+    GroupHeader {
+        title: String,
+        /// Whether the group can fold away under the header.
+        collapsible: bool,
+        /// Whether it is folded away (its items are not listed).
+        collapsed: bool,
+        /// Number of items in the group, listed or not.
+        item_count: usize,
+    },
 
-    /// An item row.
+    /// An item row (shared, so rebuilding the list does not copy items).
     Item {
-        item: Item,
+        item: Arc<Item>,
         /// Index into the flat item list (for cursor matching).
         flat_index: usize,
     },
 }
 
+/// Item indexes (`flat_index`) where each group with listed items starts.
+///
+/// A collapsed group lists no items, so it has no start and group
+/// navigation skips it.
+pub fn group_starts(entries: &[ListEntry]) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut after_header = true;
+    for entry in entries {
+        match entry {
+            ListEntry::GroupHeader { .. } => after_header = true,
+            ListEntry::Item { flat_index, .. } => {
+                if after_header {
+                    starts.push(*flat_index);
+                }
+                after_header = false;
+            }
+        }
+    }
+    starts
+}
+
+/// Where `next_group` moves the cursor: the start of the next group.
+pub fn next_group_start(starts: &[usize], cursor: usize) -> Option<usize> {
+    starts.iter().copied().find(|&start| start > cursor)
+}
+
+/// Where `prev_group` moves the cursor: the start of its group, or of the
+/// previous group when it's already there.
+pub fn prev_group_start(starts: &[usize], cursor: usize) -> Option<usize> {
+    starts.iter().copied().rev().find(|&start| start < cursor)
+}
+
+/// The header to pin at the top of a list scrolled `scroll_top` pixels
+/// down, given each entry's height: the header of the group the top row
+/// belongs to, once that header has scrolled out of view.
+pub fn sticky_header(
+    entries: &[ListEntry],
+    heights: impl IntoIterator<Item = f32>,
+    scroll_top: f32,
+) -> Option<usize> {
+    let mut top = 0.0;
+    let mut header = None;
+    for (index, (entry, height)) in entries.iter().zip(heights).enumerate() {
+        let is_header = matches!(entry, ListEntry::GroupHeader { .. });
+        if top + height > scroll_top {
+            // The top row: a header still (partly) in view is its own
+            return match is_header {
+                true if top >= scroll_top => None,
+                true => Some(index),
+                false => header,
+            };
+        }
+        if is_header {
+            header = Some(index);
+        }
+        top += height;
+    }
+    header
+}
+
+/// Where to scroll (pixels from the top) so entry `index` is in a viewport
+/// `viewport` pixels tall, moving as little as possible. `None` when it is
+/// already in view.
+pub fn scroll_into_view(
+    heights: impl IntoIterator<Item = f32>,
+    index: usize,
+    scroll_top: f32,
+    viewport: f32,
+) -> Option<f32> {
+    let mut top = 0.0;
+    for (i, height) in heights.into_iter().enumerate() {
+        if i == index {
+            return if top < scroll_top {
+                Some(top)
+            } else if top + height > scroll_top + viewport {
+                Some(top + height - viewport)
+            } else {
+                None
+            };
+        }
+        top += height;
+    }
+    None
+}
+
+/// The largest scroll position of a list of entries in a viewport.
+pub fn max_scroll(heights: impl IntoIterator<Item = f32>, viewport: f32) -> f32 {
+    (heights.into_iter().sum::<f32>() - viewport).max(0.0)
+}
+
+/// How many rows PageUp/PageDown move the cursor: the rows that fit in the
+/// viewport, less one kept in view for context.
+pub fn page_rows(viewport: f32, row_height: f32) -> usize {
+    if row_height <= 0.0 {
+        return 1;
+    }
+    ((viewport / row_height) as usize).saturating_sub(1).max(1)
+}
+
+/// Scroll position `progress` (0-1) of the way through an animated scroll
+/// from `from` to `to`, easing out.
+pub fn ease_scroll(from: f32, to: f32, progress: f32) -> f32 {
+    let t = progress.clamp(0.0, 1.0);
+    from + (to - from) * (1.0 - (1.0 - t).powi(3))
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -643,6 +871,9 @@ mod tests {
                 handler_key: None,
                 title: "Open".to_string(),
                 icon: None,
+                shortcut: None,
+                section: None,
+                destructive: false,
             },
             ActionMenuItem {
                 view_id: "test".to_string(),
@@ -650,6 +881,9 @@ mod tests {
                 handler_key: None,
                 title: "Delete".to_string(),
                 icon: None,
+                shortcut: None,
+                section: None,
+                destructive: false,
             },
         ];
 
@@ -669,6 +903,170 @@ mod tests {
         assert_eq!(menu.cursor_index, 0);
     }
 
+    #[test]
+    fn test_action_menu_confirms_destructive_actions() {
+        let action = |id: &str, section: Option<&str>, destructive: bool| ActionMenuItem {
+            view_id: "test".to_string(),
+            action_id: id.to_string(),
+            handler_key: None,
+            title: id.to_string(),
+            icon: None,
+            shortcut: None,
+            section: section.map(str::to_string),
+            destructive,
+        };
+        let mut menu = ActionMenuState::new(vec![
+            action("open", None, false),
+            action("copy", None, false),
+            action("trash", Some("Danger"), true),
+        ]);
+
+        assert!(!menu.starts_section(0));
+        assert!(!menu.starts_section(1));
+        assert!(menu.starts_section(2));
+
+        assert!(menu.confirm_selected());
+
+        menu.cursor_down();
+        menu.cursor_down();
+        assert!(!menu.confirm_selected());
+        assert!(menu.confirming);
+        assert!(menu.confirm_selected());
+
+        // Moving the cursor disarms the confirmation
+        menu.cursor_up();
+        menu.cursor_down();
+        assert!(!menu.confirm_selected());
+    }
+
+    #[test]
+    fn test_completion_follow_ups() {
+        let follow_up = |title: &str| FollowUpAction {
+            title: title.to_string(),
+            icon: None,
+            handler_key: Some(format!("followup:{}", title)),
+        };
+        let mut completion = CompletionState::new(
+            "Uploaded".to_string(),
+            "files",
+            vec![follow_up("Open"), follow_up("Copy Link")],
+        );
+
+        completion.follow_ups.cursor_down();
+        let action = completion.follow_ups.selected_action().unwrap();
+        assert_eq!(action.title, "Copy Link");
+        assert_eq!(action.view_id, "files");
+        assert_eq!(action.handler_key.as_deref(), Some("followup:Copy Link"));
+    }
+
+    #[test]
+    fn test_handler_key_falls_back_to_action_id() {
+        let mut action = ActionMenuItem {
+            view_id: "files".to_string(),
+            action_id: "open".to_string(),
+            handler_key: None,
+            title: "Open".to_string(),
+            icon: None,
+            shortcut: None,
+            section: None,
+            destructive: false,
+        };
+        assert_eq!(action.handler_key(), "open");
+
+        action.handler_key = Some("action:open".to_string());
+        assert_eq!(action.handler_key(), "action:open");
+    }
+
+    #[test]
+    fn test_failure_offers_retry_when_retryable() {
+        let error = HandlerError::new("Server unreachable")
+            .with_detail("Connection refused")
+            .with_retryable(true);
+        let completion = CompletionState::failed(&error, "files", Some("upload".to_string()));
+        assert!(completion.failed);
+        assert_eq!(completion.detail.as_deref(), Some("Connection refused"));
+        let action = completion.follow_ups.selected_action().unwrap();
+        assert_eq!(action.handler_key.as_deref(), Some("upload"));
+
+        let completion = CompletionState::failed(&error, "files", None);
+        assert!(completion.follow_ups.selected_action().is_none());
+    }
+
+    #[test]
+    fn test_group_navigation() {
+        let mut frame = ViewFrame::root();
+        frame.set_groups(vec![
+            Group::ungrouped(vec![test_item("0", "Top hit")]),
+            Group::new(
+                "Recent",
+                vec![test_item("1", "Item 1"), test_item("2", "Item 2")],
+            ),
+            Group::new("Empty", vec![]),
+            Group::new("All", vec![test_item("3", "Item 3")]),
+        ]);
+        let starts = group_starts(&frame.flat_entries);
+        assert_eq!(starts, [0, 1, 3]);
+
+        assert_eq!(next_group_start(&starts, 0), Some(1));
+        assert_eq!(next_group_start(&starts, 2), Some(3));
+        assert_eq!(next_group_start(&starts, 3), None);
+        assert_eq!(prev_group_start(&starts, 2), Some(1));
+        assert_eq!(prev_group_start(&starts, 1), Some(0));
+        assert_eq!(prev_group_start(&starts, 0), None);
+    }
+
+    #[test]
+    fn test_sticky_header() {
+        let mut frame = ViewFrame::root();
+        frame.set_groups(test_groups());
+        // Recent, two items, All, one item
+        let heights = || {
+            frame.flat_entries.iter().map(|entry| match entry {
+                ListEntry::GroupHeader { .. } => 28.0,
+                ListEntry::Item { .. } => 40.0,
+            })
+        };
+        let sticky = |scroll_top| sticky_header(&frame.flat_entries, heights(), scroll_top);
+
+        assert_eq!(sticky(0.0), None);
+        assert_eq!(sticky(10.0), Some(0));
+        assert_eq!(sticky(100.0), Some(0));
+        assert_eq!(sticky(108.0), None);
+        assert_eq!(sticky(110.0), Some(3));
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        // Header 28, then rows of 40: tops at 0, 28, 68, 108, 148
+        let heights = [28.0, 40.0, 40.0, 40.0, 40.0];
+        let scroll = |index, scroll_top| scroll_into_view(heights, index, scroll_top, 100.0);
+
+        assert_eq!(scroll(1, 0.0), None);
+        // Below the viewport: scroll just far enough to show its bottom
+        assert_eq!(scroll(2, 0.0), Some(8.0));
+        assert_eq!(scroll(4, 0.0), Some(88.0));
+        // Above it: scroll up to its top
+        assert_eq!(scroll(1, 88.0), Some(28.0));
+        assert_eq!(scroll(9, 0.0), None);
+
+        assert_eq!(max_scroll(heights, 100.0), 88.0);
+        assert_eq!(max_scroll(heights, 500.0), 0.0);
+    }
+
+    #[test]
+    fn test_page_rows_and_easing() {
+        assert_eq!(page_rows(400.0, 40.0), 9);
+        assert_eq!(page_rows(50.0, 40.0), 1);
+        assert_eq!(page_rows(400.0, 0.0), 1);
+
+        assert_eq!(ease_scroll(0.0, 100.0, 0.0), 0.0);
+        assert_eq!(ease_scroll(0.0, 100.0, 1.0), 100.0);
+        assert_eq!(ease_scroll(0.0, 100.0, 2.0), 100.0);
+        // Eases out: more than half way at the half
+        assert!(ease_scroll(0.0, 100.0, 0.5) > 50.0);
+        assert!(ease_scroll(100.0, 0.0, 0.5) < 50.0);
+    }
+
     #[test]
     fn test_breadcrumbs() {
         let mut stack = ViewStack::new_root();
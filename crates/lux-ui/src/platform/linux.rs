@@ -0,0 +1,805 @@
+//! Linux platform support (Wayland and X11).
+//!
+//! This module mirrors the macOS module's API so the rest of the UI doesn't
+//! care which platform it runs on:
+//! - Global hotkeys: key grabs on X11, the `GlobalShortcuts` desktop portal
+//!   on Wayland (the compositor asks the user to confirm the bindings)
+//! - Window placement: a centered layer-shell overlay on Wayland, a pop-up
+//!   window on X11
+//! - Appearance and accessibility settings from GNOME's `gsettings`
+//! - The frontmost app (X11 only) and local time for profile switching
+//!
+//! Things without a portable Linux equivalent are no-ops: the menu-bar
+//! status item, VoiceOver announcements, native drags out of the launcher,
+//! and window animations. Opening files and the clipboard live in
+//! `lux_plugin_api::desktop`.
+//!
+//! In hotkey strings `cmd` means the Super (logo) key.
+
+use std::process::Command;
+use std::sync::Arc;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::StreamExt;
+use gpui::WindowKind;
+use lux_core::DragPayload;
+use parking_lot::RwLock;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{AtomEnum, ConnectionExt as _, GrabMode, ModMask, Window};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+use lux_plugin_api::{DesktopPlatform, LinuxDesktop};
+
+use super::Platform;
+use crate::theme::SystemPreferences;
+
+/// Whether the session is a Wayland session.
+fn is_wayland() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_some_and(|display| !display.is_empty())
+}
+
+// =============================================================================
+// Activation Policy (Dock Visibility)
+// =============================================================================
+
+/// No-op: the launcher window already skips the taskbar (layer-shell overlay
+/// or pop-up window).
+fn set_activation_policy_accessory() {}
+
+// =============================================================================
+// Menu-Bar Status Item
+// =============================================================================
+
+/// No-op: there is no portable menu-bar item on Linux.
+fn set_status_text(text: Option<&str>) {
+    if let Some(text) = text {
+        tracing::trace!("Status text (not shown on Linux): {}", text);
+    }
+}
+
+// =============================================================================
+// Locale
+// =============================================================================
+
+/// The user's locale (`LANG` etc. are already consulted by `detect_locale`).
+fn system_locale() -> Option<String> {
+    None
+}
+
+// =============================================================================
+// Window Placement
+// =============================================================================
+
+/// Window kind for the launcher.
+///
+/// On Wayland the launcher is a layer-shell overlay: centered (no anchors),
+/// above other windows, with exclusive keyboard focus while shown.
+fn window_kind() -> WindowKind {
+    if is_wayland() {
+        use gpui::layer_shell::{KeyboardInteractivity, Layer, LayerShellOptions};
+        WindowKind::LayerShell(LayerShellOptions {
+            namespace: "lux".to_string(),
+            layer: Layer::Overlay,
+            keyboard_interactivity: KeyboardInteractivity::Exclusive,
+            ..Default::default()
+        })
+    } else {
+        WindowKind::PopUp
+    }
+}
+
+// =============================================================================
+// Appearance and Accessibility Display Settings
+// =============================================================================
+
+/// Read a GNOME setting with `gsettings` (e.g. `'prefer-dark'`, `true`).
+fn gsetting(schema: &str, key: &str) -> Option<String> {
+    let output = Command::new("gsettings")
+        .args(["get", schema, key])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Read dark mode, animations, and high contrast from GNOME settings.
+///
+/// Other desktops fall back to dark with animations and normal contrast.
+fn system_preferences() -> SystemPreferences {
+    let color_scheme = gsetting("org.gnome.desktop.interface", "color-scheme");
+    let animations = gsetting("org.gnome.desktop.interface", "enable-animations");
+    let high_contrast = gsetting("org.gnome.desktop.a11y.interface", "high-contrast");
+
+    SystemPreferences {
+        is_dark: color_scheme.map_or(true, |scheme| scheme.contains("dark")),
+        reduce_motion: animations.as_deref() == Some("false"),
+        increase_contrast: high_contrast.as_deref() == Some("true"),
+    }
+}
+
+/// No-op: GPUI doesn't animate window show/hide on Linux.
+fn set_window_animations(_enabled: bool) {}
+
+/// No-op: the overlay layer (Wayland) and pop-up window (X11) already stay
+/// above other windows, and hiding on focus loss is up to the launcher.
+fn set_keep_visible(_keep: bool) {}
+
+/// No-op: detached windows are ordinary pop-up windows here.
+fn keep_window_visible(_title: &str) {}
+
+/// No-op: dismissing hides the launcher the same way with or without
+/// detached windows open.
+fn order_out_launcher() {}
+
+// =============================================================================
+// Profile Context
+// =============================================================================
+
+/// Name and instance of the focused window's application (X11 `WM_CLASS`).
+///
+/// Wayland doesn't expose the focused window to clients, so this returns
+/// `(None, None)` there and app-based profile conditions never match.
+fn frontmost_application() -> (Option<String>, Option<String>) {
+    if is_wayland() {
+        return (None, None);
+    }
+    active_window_class().unwrap_or_default()
+}
+
+fn active_window_class() -> Option<(Option<String>, Option<String>)> {
+    let (conn, screen) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots[screen].root;
+
+    let active = conn
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")
+        .ok()?
+        .reply()
+        .ok()?
+        .atom;
+    let window: Window = conn
+        .get_property(false, root, active, AtomEnum::WINDOW, 0, 1)
+        .ok()?
+        .reply()
+        .ok()?
+        .value32()?
+        .next()?;
+
+    // WM_CLASS is "instance\0class\0"
+    let class = conn
+        .get_property(false, window, AtomEnum::WM_CLASS, AtomEnum::STRING, 0, 256)
+        .ok()?
+        .reply()
+        .ok()?
+        .value;
+    let mut parts = class
+        .split(|b| *b == 0)
+        .filter(|part| !part.is_empty())
+        .map(|part| String::from_utf8_lossy(part).to_string());
+    let instance = parts.next();
+    let name = parts.next();
+    Some((name, instance))
+}
+
+/// Local ISO weekday (1 = Monday ... 7 = Sunday) and minutes since midnight.
+fn local_time() -> (u8, u32) {
+    let output = Command::new("date").arg("+%u %H %M").output().ok();
+    let text = output
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let mut fields = text.split_whitespace().map(|f| f.parse::<u32>().ok());
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some(Some(weekday)), Some(Some(hours)), Some(Some(minutes))) => {
+            (weekday as u8, hours * 60 + minutes)
+        }
+        _ => (1, 0),
+    }
+}
+
+// =============================================================================
+// Hotkey Configuration
+// =============================================================================
+
+/// X11 modifier masks used in hotkeys.
+pub mod modifiers {
+    pub const SHIFT: u16 = 1 << 0;
+    pub const CONTROL: u16 = 1 << 2;
+    /// Alt (Mod1).
+    pub const ALT: u16 = 1 << 3;
+    /// Super / logo key (Mod4).
+    pub const SUPER: u16 = 1 << 6;
+
+    /// Caps Lock and Num Lock, which must not affect matching.
+    pub(super) const LOCKS: [u16; 4] = [0, 1 << 1, 1 << 4, (1 << 1) | (1 << 4)];
+}
+
+/// A hotkey combination (modifier mask + X keysym).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    /// Modifier mask (see [`modifiers`]).
+    pub modifiers: u16,
+    /// X keysym of the key.
+    pub keysym: u32,
+}
+
+impl Hotkey {
+    /// Create a new hotkey.
+    pub fn new(modifiers: u16, keysym: u32) -> Self {
+        Self { modifiers, keysym }
+    }
+
+    /// Trigger in the shortcuts spec format used by the portal ("LOGO+SHIFT+space").
+    pub fn portal_trigger(&self) -> String {
+        let mut parts = Vec::new();
+        for (mask, name) in [
+            (modifiers::CONTROL, "CTRL"),
+            (modifiers::ALT, "ALT"),
+            (modifiers::SHIFT, "SHIFT"),
+            (modifiers::SUPER, "LOGO"),
+        ] {
+            if self.modifiers & mask != 0 {
+                parts.push(name.to_string());
+            }
+        }
+        parts.push(xkb_key_name(self.keysym));
+        parts.join("+")
+    }
+}
+
+impl Default for Hotkey {
+    fn default() -> Self {
+        // Super+Shift+Space, matching Cmd+Shift+Space on macOS
+        Self {
+            modifiers: modifiers::SUPER | modifiers::SHIFT,
+            keysym: keysyms::SPACE,
+        }
+    }
+}
+
+// =============================================================================
+// Accessibility Permissions
+// =============================================================================
+
+/// Always true: Linux needs no permission for the hotkey listeners.
+fn has_accessibility_permission() -> bool {
+    true
+}
+
+/// No-op (see `has_accessibility_permission`).
+fn prompt_accessibility_permission() -> bool {
+    true
+}
+
+// =============================================================================
+// VoiceOver
+// =============================================================================
+
+/// Always false: announcements are macOS-only for now.
+fn voiceover_enabled() -> bool {
+    false
+}
+
+/// No-op (see `voiceover_enabled`).
+fn announce(_text: &str) {}
+
+// =============================================================================
+// Haptic Feedback
+// =============================================================================
+
+/// No-op: there is no haptic API for Linux trackpads.
+fn perform_haptic() {}
+
+// =============================================================================
+// Hotkey Listeners
+// =============================================================================
+
+/// Callback type for hotkey handlers.
+pub type HotkeyCallback = Arc<dyn Fn() + Send + Sync + 'static>;
+
+/// Entry for a registered hotkey.
+struct HotkeyEntry {
+    hotkey: Hotkey,
+    callback: HotkeyCallback,
+}
+
+type Entries = Arc<RwLock<Vec<HotkeyEntry>>>;
+
+/// Delivers key presses for registered hotkeys.
+enum Listener {
+    /// Passive key grabs on the X11 root window.
+    X11 {
+        conn: Arc<RustConnection>,
+        root: Window,
+    },
+    /// `org.freedesktop.portal.GlobalShortcuts`; each change rebinds the
+    /// whole set.
+    Portal { rebind: UnboundedSender<()> },
+}
+
+impl Listener {
+    /// Start the listener thread for the session type.
+    fn start(entries: Entries) -> Option<Self> {
+        if is_wayland() {
+            let (rebind, updates) = unbounded();
+            std::thread::Builder::new()
+                .name("lux-hotkeys".to_string())
+                .spawn(move || {
+                    if let Err(e) = futures::executor::block_on(run_portal(entries, updates)) {
+                        tracing::error!("Global shortcuts portal failed: {}", e);
+                    }
+                })
+                .ok()?;
+            Some(Self::Portal { rebind })
+        } else {
+            let (conn, screen) = x11rb::connect(None)
+                .map_err(|e| tracing::error!("Cannot connect to X server: {}", e))
+                .ok()?;
+            let conn = Arc::new(conn);
+            let root = conn.setup().roots[screen].root;
+
+            let thread_conn = conn.clone();
+            std::thread::Builder::new()
+                .name("lux-hotkeys".to_string())
+                .spawn(move || run_x11(&thread_conn, &entries))
+                .ok()?;
+            Some(Self::X11 { conn, root })
+        }
+    }
+
+    /// Start listening for a newly registered hotkey.
+    fn listen(&self, hotkey: Hotkey) {
+        match self {
+            Self::X11 { conn, root } => {
+                if let Err(e) = grab_key(conn, *root, hotkey) {
+                    tracing::warn!("Failed to grab {}: {}", hotkey.portal_trigger(), e);
+                }
+            }
+            Self::Portal { rebind } => {
+                let _ = rebind.unbounded_send(());
+            }
+        }
+    }
+}
+
+/// Find the keycode producing a keysym in the current keyboard layout.
+fn keycode_for(conn: &RustConnection, keysym: u32) -> Option<u8> {
+    let setup = conn.setup();
+    let (min, max) = (setup.min_keycode, setup.max_keycode);
+    let mapping = conn
+        .get_keyboard_mapping(min, max - min + 1)
+        .ok()?
+        .reply()
+        .ok()?;
+    let per_keycode = mapping.keysyms_per_keycode.max(1) as usize;
+    mapping
+        .keysyms
+        .chunks(per_keycode)
+        .position(|syms| syms.contains(&keysym))
+        .map(|index| min + index as u8)
+}
+
+/// Grab a hotkey on the root window, once per lock-modifier combination.
+fn grab_key(conn: &RustConnection, root: Window, hotkey: Hotkey) -> Result<(), String> {
+    let keycode = keycode_for(conn, hotkey.keysym)
+        .ok_or_else(|| format!("no keycode for keysym {:#x}", hotkey.keysym))?;
+    for locks in modifiers::LOCKS {
+        conn.grab_key(
+            true,
+            root,
+            ModMask::from(hotkey.modifiers | locks),
+            keycode,
+            GrabMode::ASYNC,
+            GrabMode::ASYNC,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    conn.flush().map_err(|e| e.to_string())
+}
+
+/// Dispatch grabbed key presses until the connection closes.
+fn run_x11(conn: &RustConnection, entries: &Entries) {
+    let mask = modifiers::SHIFT | modifiers::CONTROL | modifiers::ALT | modifiers::SUPER;
+    loop {
+        let event = match conn.wait_for_event() {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!("X11 hotkey listener stopped: {}", e);
+                return;
+            }
+        };
+        let Event::KeyPress(press) = event else {
+            continue;
+        };
+
+        let state = u16::from(press.state) & mask;
+        let entries = entries.read();
+        for entry in entries.iter() {
+            if entry.hotkey.modifiers == state
+                && keycode_for(conn, entry.hotkey.keysym) == Some(press.detail)
+            {
+                (entry.callback)();
+                break; // First match wins
+            }
+        }
+    }
+}
+
+/// Bind hotkeys through the portal and dispatch activations.
+async fn run_portal(entries: Entries, mut updates: UnboundedReceiver<()>) -> ashpd::Result<()> {
+    use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+
+    let portal = GlobalShortcuts::new().await?;
+    let mut activated = portal.receive_activated().await?.fuse();
+    let mut session = None;
+
+    loop {
+        futures::select! {
+            update = updates.next() => {
+                if update.is_none() {
+                    return Ok(());
+                }
+                // Registrations arrive in bursts; bind once for all of them
+                while let Ok(Some(())) = updates.try_next() {}
+
+                let shortcuts: Vec<NewShortcut> = entries
+                    .read()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, entry)| {
+                        let trigger = entry.hotkey.portal_trigger();
+                        NewShortcut::new(format!("lux-{}", index), format!("Lux ({})", trigger))
+                            .preferred_trigger(trigger.as_str())
+                    })
+                    .collect();
+
+                if let Some(old) = session.take() {
+                    let _ = old.close().await;
+                }
+                let new_session = portal.create_session().await?;
+                portal
+                    .bind_shortcuts(&new_session, &shortcuts, None)
+                    .await?
+                    .response()?;
+                session = Some(new_session);
+            }
+            event = activated.next() => {
+                let Some(event) = event else {
+                    return Ok(());
+                };
+                let index = event
+                    .shortcut_id()
+                    .strip_prefix("lux-")
+                    .and_then(|index| index.parse::<usize>().ok());
+                let entries = entries.read();
+                if let Some(entry) = index.and_then(|index| entries.get(index)) {
+                    (entry.callback)();
+                }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Hotkey Manager
+// =============================================================================
+
+/// Global hotkey manager for a single hotkey.
+///
+/// Dropping this struct stops delivering the hotkey.
+///
+/// ## Thread Safety
+///
+/// The callback is invoked on a background thread. Use a channel to send
+/// events to the GPUI context.
+pub struct HotkeyManager {
+    /// Shared with the listener thread.
+    _entries: Entries,
+    _listener: Listener,
+    /// Current hotkey configuration.
+    hotkey: Hotkey,
+}
+
+impl HotkeyManager {
+    /// Create a new hotkey manager with the given hotkey and callback.
+    ///
+    /// Returns `None` if no listener could be started (e.g. no X server).
+    pub fn new<F>(hotkey: Hotkey, callback: F) -> Option<Self>
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let entries: Entries = Arc::new(RwLock::new(vec![HotkeyEntry {
+            hotkey,
+            callback: Arc::new(callback),
+        }]));
+        let listener = Listener::start(entries.clone())?;
+        listener.listen(hotkey);
+
+        Some(Self {
+            _entries: entries,
+            _listener: listener,
+            hotkey,
+        })
+    }
+
+    /// Get the current hotkey configuration.
+    pub fn hotkey(&self) -> Hotkey {
+        self.hotkey
+    }
+}
+
+// =============================================================================
+// Multi-Hotkey Manager
+// =============================================================================
+
+/// Manager for multiple global hotkeys.
+///
+/// ## Thread Safety
+///
+/// Callbacks are invoked on a background thread. Use a channel to send
+/// events to the GPUI context.
+pub struct MultiHotkeyManager {
+    /// Registered hotkeys (shared with the listener thread).
+    hotkeys: Entries,
+    listener: Listener,
+}
+
+impl MultiHotkeyManager {
+    /// Create a new empty multi-hotkey manager.
+    ///
+    /// Returns `None` if no listener could be started (e.g. no X server).
+    pub fn new() -> Option<Self> {
+        let hotkeys: Entries = Arc::new(RwLock::new(Vec::new()));
+        let listener = Listener::start(hotkeys.clone())?;
+        Some(Self { hotkeys, listener })
+    }
+
+    /// Register a hotkey with its callback.
+    pub fn register(&self, hotkey: Hotkey, callback: HotkeyCallback) {
+        self.hotkeys.write().push(HotkeyEntry { hotkey, callback });
+        self.listener.listen(hotkey);
+        tracing::debug!("Registered hotkey: {}", hotkey.portal_trigger());
+    }
+
+    /// Register a hotkey from a string like "ctrl+space".
+    ///
+    /// Returns `true` if the hotkey was successfully parsed and registered.
+    pub fn register_from_str(&self, key: &str, callback: HotkeyCallback) -> bool {
+        if let Some(hotkey) = parse_hotkey(key) {
+            self.register(hotkey, callback);
+            true
+        } else {
+            tracing::warn!("Failed to parse hotkey string: '{}'", key);
+            false
+        }
+    }
+
+    /// Get the number of registered hotkeys.
+    pub fn count(&self) -> usize {
+        self.hotkeys.read().len()
+    }
+}
+
+// =============================================================================
+// Drag and Drop
+// =============================================================================
+
+/// Native drags out of the launcher aren't supported on Linux yet.
+///
+/// Always returns `false`.
+fn begin_drag(payload: &DragPayload, _animate: bool) -> bool {
+    tracing::debug!("Native drag not supported on Linux: {:?}", payload);
+    false
+}
+
+// =============================================================================
+// Keysym Constants
+// =============================================================================
+
+/// Common X keysyms.
+pub mod keysyms {
+    pub const SPACE: u32 = 0x0020;
+    pub const RETURN: u32 = 0xff0d;
+    pub const TAB: u32 = 0xff09;
+    pub const ESCAPE: u32 = 0xff1b;
+    pub const F1: u32 = 0xffbe;
+}
+
+/// Named keys: (name in hotkey strings, keysym, xkb name).
+const KEYS: &[(&str, u32, &str)] = &[
+    ("space", keysyms::SPACE, "space"),
+    ("return", keysyms::RETURN, "Return"),
+    ("enter", keysyms::RETURN, "Return"),
+    ("tab", keysyms::TAB, "Tab"),
+    ("escape", keysyms::ESCAPE, "Escape"),
+    ("esc", keysyms::ESCAPE, "Escape"),
+];
+
+/// xkb name of a keysym ("space", "k", "F5").
+fn xkb_key_name(keysym: u32) -> String {
+    if let Some((_, _, name)) = KEYS.iter().find(|(_, sym, _)| *sym == keysym) {
+        return name.to_string();
+    }
+    if (keysyms::F1..keysyms::F1 + 12).contains(&keysym) {
+        return format!("F{}", keysym - keysyms::F1 + 1);
+    }
+    match char::from_u32(keysym).filter(|c| c.is_ascii_graphic()) {
+        Some(c) => c.to_string(),
+        None => format!("{:#x}", keysym),
+    }
+}
+
+// =============================================================================
+// Hotkey Parsing
+// =============================================================================
+
+/// Parse a hotkey string like "ctrl+space" or "cmd+shift+p" (cmd = Super).
+pub fn parse_hotkey(s: &str) -> Option<Hotkey> {
+    let parts: Vec<String> = s.split('+').map(|p| p.trim().to_lowercase()).collect();
+
+    let mut mods = 0;
+    let mut keysym = None;
+
+    for part in &parts {
+        match part.as_str() {
+            "cmd" | "command" | "super" | "meta" | "\u{2318}" => mods |= modifiers::SUPER,
+            "ctrl" | "control" | "\u{2303}" => mods |= modifiers::CONTROL,
+            "alt" | "option" | "opt" | "\u{2325}" => mods |= modifiers::ALT,
+            "shift" | "\u{21E7}" => mods |= modifiers::SHIFT,
+            key => keysym = key_name_to_keysym(key),
+        }
+    }
+
+    keysym.map(|keysym| Hotkey::new(mods, keysym))
+}
+
+fn key_name_to_keysym(name: &str) -> Option<u32> {
+    if let Some((_, keysym, _)) = KEYS.iter().find(|(key, _, _)| *key == name) {
+        return Some(*keysym);
+    }
+    if name == " " {
+        return Some(keysyms::SPACE);
+    }
+
+    // Letters and digits have ASCII keysyms
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_lowercase() || c.is_ascii_digit() {
+            return Some(c as u32);
+        }
+    }
+
+    // F1-F12
+    let number: u32 = name.strip_prefix('f')?.parse().ok()?;
+    (1..=12).contains(&number).then(|| keysyms::F1 + number - 1)
+}
+
+// =============================================================================
+// Platform Implementation
+// =============================================================================
+
+/// The Linux (Wayland/X11) platform.
+pub struct LinuxPlatform;
+
+impl Platform for LinuxPlatform {
+    fn desktop(&self) -> &'static dyn DesktopPlatform {
+        &LinuxDesktop
+    }
+
+    fn set_activation_policy_accessory(&self) {
+        set_activation_policy_accessory();
+    }
+
+    fn window_kind(&self) -> gpui::WindowKind {
+        window_kind()
+    }
+
+    fn set_window_animations(&self, enabled: bool) {
+        set_window_animations(enabled);
+    }
+
+    fn set_keep_visible(&self, keep: bool) {
+        set_keep_visible(keep);
+    }
+
+    fn keep_window_visible(&self, title: &str) {
+        keep_window_visible(title);
+    }
+
+    fn order_out_launcher(&self) {
+        order_out_launcher();
+    }
+
+    fn set_status_text(&self, text: Option<&str>) {
+        set_status_text(text);
+    }
+
+    fn begin_drag(&self, payload: &DragPayload, animate: bool) -> bool {
+        begin_drag(payload, animate)
+    }
+
+    fn has_accessibility_permission(&self) -> bool {
+        has_accessibility_permission()
+    }
+
+    fn prompt_accessibility_permission(&self) -> bool {
+        prompt_accessibility_permission()
+    }
+
+    fn hotkey_manager(&self, hotkey: Hotkey, callback: HotkeyCallback) -> Option<HotkeyManager> {
+        HotkeyManager::new(hotkey, move || callback())
+    }
+
+    fn multi_hotkey_manager(&self) -> Option<MultiHotkeyManager> {
+        MultiHotkeyManager::new()
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        system_locale()
+    }
+
+    fn system_preferences(&self) -> SystemPreferences {
+        system_preferences()
+    }
+
+    fn frontmost_application(&self) -> (Option<String>, Option<String>) {
+        frontmost_application()
+    }
+
+    fn local_time(&self) -> (u8, u32) {
+        local_time()
+    }
+
+    fn voiceover_enabled(&self) -> bool {
+        voiceover_enabled()
+    }
+
+    fn announce(&self, text: &str) {
+        announce(text);
+    }
+
+    fn perform_haptic(&self) {
+        perform_haptic();
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hotkey_default() {
+        let hotkey = Hotkey::default();
+        assert_eq!(hotkey.keysym, keysyms::SPACE);
+        assert_eq!(hotkey.modifiers, modifiers::SUPER | modifiers::SHIFT);
+    }
+
+    #[test]
+    fn test_parse_hotkey() {
+        let hotkey = parse_hotkey("ctrl+shift+p").unwrap();
+        assert_eq!(hotkey.keysym, 'p' as u32);
+        assert_eq!(hotkey.modifiers, modifiers::CONTROL | modifiers::SHIFT);
+
+        let hotkey = parse_hotkey("cmd+space").unwrap();
+        assert_eq!(hotkey.modifiers, modifiers::SUPER);
+        assert_eq!(parse_hotkey("alt+f5").unwrap().keysym, keysyms::F1 + 4);
+
+        assert!(parse_hotkey("invalid").is_none());
+        assert!(parse_hotkey("cmd+f13").is_none());
+    }
+
+    #[test]
+    fn test_portal_trigger() {
+        assert_eq!(Hotkey::default().portal_trigger(), "SHIFT+LOGO+space");
+        assert_eq!(
+            parse_hotkey("ctrl+alt+k").unwrap().portal_trigger(),
+            "CTRL+ALT+k"
+        );
+        assert_eq!(parse_hotkey("alt+f5").unwrap().portal_trigger(), "ALT+F5");
+    }
+}
@@ -1,18 +1,37 @@
 //! macOS platform support.
 //!
-//! This module provides macOS-specific functionality including global hotkey management.
+//! This module provides macOS-specific functionality including global hotkey management,
+//! native drag sessions for result rows, the menu-bar status item, VoiceOver
+//! announcements, trackpad haptics, the system locale, appearance and accessibility display
+//! settings, and the frontmost app / local time used for automatic profile
+//! switching.
 
 use block2::RcBlock;
+use lux_core::DragPayload;
 use objc2::rc::Retained;
-use objc2::runtime::AnyObject;
+use objc2::runtime::{AnyObject, NSObject, NSObjectProtocol, ProtocolObject};
+use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
 use objc2_app_kit::{
-    NSApplication, NSApplicationActivationPolicy, NSEvent, NSEventMask, NSEventModifierFlags,
+    NSApplication, NSApplicationActivationPolicy, NSDragOperation, NSDraggingContext,
+    NSDraggingItem, NSDraggingSession, NSDraggingSource, NSEvent, NSEventMask,
+    NSEventModifierFlags, NSHapticFeedbackManager, NSHapticFeedbackPattern,
+    NSHapticFeedbackPerformanceTime, NSHapticFeedbackPerformer, NSPanel, NSPasteboardWriting,
+    NSStatusBar, NSStatusItem, NSVariableStatusItemLength, NSWindowAnimationBehavior, NSWorkspace,
+};
+use objc2_foundation::{
+    MainThreadMarker, NSArray, NSCalendar, NSCalendarUnit, NSDate, NSLocale, NSPoint, NSRect,
+    NSSize, NSString, NSURL,
 };
-use objc2_foundation::MainThreadMarker;
 use parking_lot::RwLock;
+use std::cell::RefCell;
 use std::ptr::NonNull;
 use std::sync::Arc;
 
+use lux_plugin_api::{DesktopPlatform, MacDesktop};
+
+use super::Platform;
+use crate::theme::SystemPreferences;
+
 // =============================================================================
 // Activation Policy (Dock Visibility)
 // =============================================================================
@@ -24,13 +43,187 @@ use std::sync::Arc;
 ///
 /// # Safety
 /// This must be called from the main thread (e.g., inside GPUI's run callback).
-pub fn set_activation_policy_accessory() {
+fn set_activation_policy_accessory() {
     // SAFETY: This is called from the GPUI run callback, which runs on the main thread.
     let mtm = unsafe { MainThreadMarker::new_unchecked() };
     let app = NSApplication::sharedApplication(mtm);
     app.setActivationPolicy(NSApplicationActivationPolicy::Accessory);
 }
 
+// =============================================================================
+// Menu-Bar Status Item
+// =============================================================================
+
+thread_local! {
+    /// The status item, created on first use (AppKit objects stay on the main thread).
+    static STATUS_ITEM: RefCell<Option<Retained<NSStatusItem>>> = const { RefCell::new(None) };
+}
+
+/// Show text in the menu bar (e.g. a timer countdown), or remove it with `None`.
+///
+/// Must be called from the main thread.
+fn set_status_text(text: Option<&str>) {
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+
+    STATUS_ITEM.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        match text {
+            Some(text) => {
+                let item = slot.get_or_insert_with(|| unsafe {
+                    NSStatusBar::systemStatusBar().statusItemWithLength(NSVariableStatusItemLength)
+                });
+                if let Some(button) = unsafe { item.button(mtm) } {
+                    unsafe { button.setTitle(&NSString::from_str(text)) };
+                }
+            }
+            None => {
+                if let Some(item) = slot.take() {
+                    unsafe { NSStatusBar::systemStatusBar().removeStatusItem(&item) };
+                }
+            }
+        }
+    });
+}
+
+// =============================================================================
+// Window Placement
+// =============================================================================
+
+/// Window kind for the launcher (a floating panel above other windows).
+fn window_kind() -> gpui::WindowKind {
+    gpui::WindowKind::PopUp
+}
+
+// =============================================================================
+// Locale
+// =============================================================================
+
+/// The user's preferred locale identifier (e.g. "de_DE"), from System Settings.
+fn system_locale() -> Option<String> {
+    let locale = unsafe { NSLocale::currentLocale() };
+    let identifier = unsafe { locale.localeIdentifier() }.to_string();
+    (!identifier.is_empty()).then_some(identifier)
+}
+
+// =============================================================================
+// Appearance and Accessibility Display Settings
+// =============================================================================
+
+/// Read dark mode and the "Reduce Motion" / "Increase Contrast" settings.
+///
+/// Must be called from the main thread.
+fn system_preferences() -> SystemPreferences {
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+    let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+
+    let appearance = unsafe { app.effectiveAppearance().name() };
+    SystemPreferences {
+        is_dark: appearance.to_string().contains("Dark"),
+        reduce_motion: unsafe { workspace.accessibilityDisplayShouldReduceMotion() },
+        increase_contrast: unsafe { workspace.accessibilityDisplayShouldIncreaseContrast() },
+    }
+}
+
+/// Turn AppKit's window show/hide animations on or off for all windows.
+///
+/// Must be called from the main thread.
+fn set_window_animations(enabled: bool) {
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+
+    let behavior = if enabled {
+        NSWindowAnimationBehavior::Default
+    } else {
+        NSWindowAnimationBehavior::None
+    };
+    for window in app.windows().iter() {
+        unsafe { window.setAnimationBehavior(behavior) };
+    }
+}
+
+/// Keep the launcher windows visible while another app is active.
+///
+/// The pop-up window level already keeps them above other windows.
+/// Must be called from the main thread.
+fn set_keep_visible(keep: bool) {
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+
+    // Detached windows stay visible either way
+    for window in app.windows().iter().filter(|w| unsafe { w.canHide() }) {
+        unsafe { window.setHidesOnDeactivate(!keep) };
+    }
+}
+
+/// Keep the window titled `title` visible when the app hides or deactivates.
+///
+/// Must be called from the main thread.
+fn keep_window_visible(title: &str) {
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+
+    for window in app.windows().iter() {
+        if unsafe { window.title() }.to_string() == title {
+            unsafe {
+                window.setCanHide(false);
+                window.setHidesOnDeactivate(false);
+            }
+        }
+    }
+}
+
+/// Order out the launcher panel, leaving windows that can't hide (detached
+/// views) on screen.
+///
+/// Must be called from the main thread.
+fn order_out_launcher() {
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+
+    for window in app.windows().iter() {
+        let is_panel = window.isKindOfClass(NSPanel::class());
+        if is_panel && unsafe { window.canHide() } {
+            unsafe { window.orderOut(None) };
+        }
+    }
+}
+
+// =============================================================================
+// Profile Context
+// =============================================================================
+
+/// Name and bundle ID of the frontmost application.
+///
+/// Call before activating the launcher, or the launcher itself is frontmost.
+fn frontmost_application() -> (Option<String>, Option<String>) {
+    let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+    let Some(app) = (unsafe { workspace.frontmostApplication() }) else {
+        return (None, None);
+    };
+    let name = unsafe { app.localizedName() }.map(|n| n.to_string());
+    let bundle_id = unsafe { app.bundleIdentifier() }.map(|b| b.to_string());
+    (name, bundle_id)
+}
+
+/// Local ISO weekday (1 = Monday ... 7 = Sunday) and minutes since midnight.
+fn local_time() -> (u8, u32) {
+    let calendar = unsafe { NSCalendar::currentCalendar() };
+    let now = unsafe { NSDate::now() };
+    let component = |unit| unsafe { calendar.component_fromDate(unit, &now) };
+
+    // NSCalendar weekdays run 1 = Sunday ... 7 = Saturday
+    let weekday = ((component(NSCalendarUnit::Weekday) + 5) % 7 + 1) as u8;
+    let minutes = (component(NSCalendarUnit::Hour) * 60 + component(NSCalendarUnit::Minute)) as u32;
+    (weekday, minutes)
+}
+
 // =============================================================================
 // Hotkey Configuration
 // =============================================================================
@@ -95,7 +288,7 @@ extern "C" {
 /// Global hotkey monitoring requires accessibility permissions on macOS.
 /// If this returns false, you should prompt the user to enable permissions
 /// in System Preferences > Security & Privacy > Privacy > Accessibility.
-pub fn has_accessibility_permission() -> bool {
+fn has_accessibility_permission() -> bool {
     unsafe { AXIsProcessTrustedWithOptions(std::ptr::null()) }
 }
 
@@ -103,7 +296,7 @@ pub fn has_accessibility_permission() -> bool {
 ///
 /// This opens the accessibility preferences pane with the app selected.
 /// Returns true if the app is already trusted.
-pub fn prompt_accessibility_permission() -> bool {
+fn prompt_accessibility_permission() -> bool {
     use objc2::runtime::ProtocolObject;
     use objc2_foundation::{NSDictionary, NSNumber, NSString};
 
@@ -120,6 +313,65 @@ pub fn prompt_accessibility_permission() -> bool {
     unsafe { AXIsProcessTrustedWithOptions(Retained::as_ptr(&options) as *const _) }
 }
 
+// =============================================================================
+// VoiceOver
+// =============================================================================
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    fn NSAccessibilityPostNotificationWithUserInfo(
+        element: *const AnyObject,
+        notification: *const NSString,
+        user_info: *const AnyObject,
+    );
+}
+
+/// Check if VoiceOver is running.
+fn voiceover_enabled() -> bool {
+    unsafe { NSWorkspace::sharedWorkspace().isVoiceOverEnabled() }
+}
+
+/// Ask VoiceOver to speak `text`.
+///
+/// Must be called from the main thread.
+fn announce(text: &str) {
+    use objc2_foundation::NSDictionary;
+
+    // SAFETY: Called from GPUI foreground tasks, which run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+
+    // NSAccessibilityAnnouncementRequestedNotification / NSAccessibilityAnnouncementKey
+    let notification = NSString::from_str("AXAnnouncementRequested");
+    let key = NSString::from_str("AXAnnouncementKey");
+    let value = NSString::from_str(text);
+    let user_info: Retained<NSDictionary<NSString, NSString>> = unsafe {
+        NSDictionary::dictionaryWithObject_forKey(&value, ProtocolObject::from_ref(&*key))
+    };
+
+    unsafe {
+        NSAccessibilityPostNotificationWithUserInfo(
+            Retained::as_ptr(&app).cast(),
+            Retained::as_ptr(&notification),
+            Retained::as_ptr(&user_info).cast(),
+        )
+    };
+}
+
+// =============================================================================
+// Haptic Feedback
+// =============================================================================
+
+/// Tap the Force Touch trackpad (nothing on trackpads without haptics).
+fn perform_haptic() {
+    unsafe {
+        NSHapticFeedbackManager::defaultPerformer().performFeedbackPattern_performanceTime(
+            NSHapticFeedbackPattern::Generic,
+            NSHapticFeedbackPerformanceTime::Default,
+        )
+    };
+}
+
 // =============================================================================
 // Hotkey Manager
 // =============================================================================
@@ -363,6 +615,124 @@ impl MultiHotkeyManager {
     }
 }
 
+// =============================================================================
+// Drag and Drop
+// =============================================================================
+
+/// Size of the drag image shown under the cursor.
+const DRAG_IMAGE_SIZE: f64 = 32.0;
+
+declare_class!(
+    /// Minimal `NSDraggingSource` for rows dragged out of the launcher.
+    ///
+    /// Only copy operations are offered, so dropping a file into Finder copies
+    /// it rather than moving the original.
+    struct LuxDragSource;
+
+    unsafe impl ClassType for LuxDragSource {
+        type Super = NSObject;
+        type Mutability = mutability::MainThreadOnly;
+        const NAME: &'static str = "LuxDragSource";
+    }
+
+    impl DeclaredClass for LuxDragSource {}
+
+    unsafe impl NSObjectProtocol for LuxDragSource {}
+
+    unsafe impl NSDraggingSource for LuxDragSource {
+        #[method(draggingSession:sourceOperationMaskForDraggingContext:)]
+        fn source_operation_mask(
+            &self,
+            _session: &NSDraggingSession,
+            _context: NSDraggingContext,
+        ) -> NSDragOperation {
+            NSDragOperation::Copy
+        }
+    }
+);
+
+impl LuxDragSource {
+    fn new(mtm: MainThreadMarker) -> Retained<Self> {
+        let this = mtm.alloc::<Self>().set_ivars(());
+        unsafe { msg_send_id![super(this), init] }
+    }
+}
+
+/// Start a native drag session carrying the given payload.
+///
+/// Call this from a GPUI drag handler: it uses the application's current
+/// mouse event and key window to start an AppKit drag, which lets the payload
+/// be dropped into Finder, Mail, or any other app.
+///
+/// With `animate` off (Reduce Motion), a cancelled drag doesn't slide back.
+///
+/// Returns `false` if there is no key window or mouse event to drag from.
+fn begin_drag(payload: &DragPayload, animate: bool) -> bool {
+    // SAFETY: GPUI drag handlers run on the main thread.
+    let mtm = unsafe { MainThreadMarker::new_unchecked() };
+    let app = NSApplication::sharedApplication(mtm);
+
+    let Some(event) = (unsafe { app.currentEvent() }) else {
+        tracing::warn!("Cannot start drag: no current event");
+        return false;
+    };
+    let Some(view) = app.keyWindow().and_then(|window| window.contentView()) else {
+        tracing::warn!("Cannot start drag: no key window");
+        return false;
+    };
+
+    // Build the pasteboard writer for the payload
+    let writer: Retained<ProtocolObject<dyn NSPasteboardWriting>> = match payload {
+        DragPayload::File { path } => {
+            let url = unsafe { NSURL::fileURLWithPath(&NSString::from_str(path)) };
+            ProtocolObject::from_retained(url)
+        }
+        DragPayload::Url { url } => {
+            let Some(url) = (unsafe { NSURL::URLWithString(&NSString::from_str(url)) }) else {
+                tracing::warn!("Cannot start drag: invalid URL '{}'", url);
+                return false;
+            };
+            ProtocolObject::from_retained(url)
+        }
+        DragPayload::Text { text } => ProtocolObject::from_retained(NSString::from_str(text)),
+    };
+
+    let item = unsafe { NSDraggingItem::initWithPasteboardWriter(mtm.alloc(), &writer) };
+
+    // Center the drag image on the cursor; files get their Finder icon
+    let location = unsafe { view.convertPoint_fromView(event.locationInWindow(), None) };
+    let frame = NSRect::new(
+        NSPoint::new(
+            location.x - DRAG_IMAGE_SIZE / 2.0,
+            location.y - DRAG_IMAGE_SIZE / 2.0,
+        ),
+        NSSize::new(DRAG_IMAGE_SIZE, DRAG_IMAGE_SIZE),
+    );
+    let icon = match payload {
+        DragPayload::File { path } => {
+            let workspace = unsafe { NSWorkspace::sharedWorkspace() };
+            Some(unsafe { workspace.iconForFile(&NSString::from_str(path)) })
+        }
+        _ => None,
+    };
+    let contents: Option<&AnyObject> = icon.as_deref().map(|icon| &**icon);
+    unsafe { item.setDraggingFrame_contents(frame, contents) };
+
+    let source = LuxDragSource::new(mtm);
+    let items = NSArray::from_vec(vec![item]);
+    unsafe {
+        let session = view.beginDraggingSessionWithItems_event_source(
+            &items,
+            &event,
+            ProtocolObject::from_ref(&*source),
+        );
+        session.setAnimatesToStartingPositionsOnCancelOrFail(animate);
+    }
+
+    tracing::debug!("Started native drag: {:?}", payload);
+    true
+}
+
 // =============================================================================
 // Key Code Constants
 // =============================================================================
@@ -467,6 +837,95 @@ fn key_name_to_code(name: &str) -> Option<u16> {
     })
 }
 
+// =============================================================================
+// Platform Implementation
+// =============================================================================
+
+/// The macOS (AppKit) platform.
+pub struct MacPlatform;
+
+impl Platform for MacPlatform {
+    fn desktop(&self) -> &'static dyn DesktopPlatform {
+        &MacDesktop
+    }
+
+    fn set_activation_policy_accessory(&self) {
+        set_activation_policy_accessory();
+    }
+
+    fn window_kind(&self) -> gpui::WindowKind {
+        window_kind()
+    }
+
+    fn set_window_animations(&self, enabled: bool) {
+        set_window_animations(enabled);
+    }
+
+    fn set_keep_visible(&self, keep: bool) {
+        set_keep_visible(keep);
+    }
+
+    fn keep_window_visible(&self, title: &str) {
+        keep_window_visible(title);
+    }
+
+    fn order_out_launcher(&self) {
+        order_out_launcher();
+    }
+
+    fn set_status_text(&self, text: Option<&str>) {
+        set_status_text(text);
+    }
+
+    fn begin_drag(&self, payload: &DragPayload, animate: bool) -> bool {
+        begin_drag(payload, animate)
+    }
+
+    fn has_accessibility_permission(&self) -> bool {
+        has_accessibility_permission()
+    }
+
+    fn prompt_accessibility_permission(&self) -> bool {
+        prompt_accessibility_permission()
+    }
+
+    fn hotkey_manager(&self, hotkey: Hotkey, callback: HotkeyCallback) -> Option<HotkeyManager> {
+        HotkeyManager::new(hotkey, move || callback())
+    }
+
+    fn multi_hotkey_manager(&self) -> Option<MultiHotkeyManager> {
+        MultiHotkeyManager::new()
+    }
+
+    fn system_locale(&self) -> Option<String> {
+        system_locale()
+    }
+
+    fn system_preferences(&self) -> SystemPreferences {
+        system_preferences()
+    }
+
+    fn frontmost_application(&self) -> (Option<String>, Option<String>) {
+        frontmost_application()
+    }
+
+    fn local_time(&self) -> (u8, u32) {
+        local_time()
+    }
+
+    fn voiceover_enabled(&self) -> bool {
+        voiceover_enabled()
+    }
+
+    fn announce(&self, text: &str) {
+        announce(text);
+    }
+
+    fn perform_haptic(&self) {
+        perform_haptic();
+    }
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -1,9 +1,142 @@
 //! Platform-specific implementations.
 //!
-//! This module provides platform-specific functionality like global hotkeys.
+//! This module provides the `Platform` trait, which covers everything the UI
+//! needs from the operating system:
+//! - Window and panel behavior (activation policy, window kind, animations,
+//!   detached windows that stay visible)
+//! - The menu-bar status item and native drag sessions
+//! - Global hotkeys and the permission they need
+//! - System state (locale, appearance, frontmost app, local time)
+//! - Screen reader announcements
+//! - Desktop services shared with plugins (`DesktopPlatform`: open/reveal,
+//!   clipboard, notifications)
+//!
+//! UI code calls `platform::current()` rather than the macOS or Linux
+//! modules, which are private. Only the hotkey types are re-exported, since
+//! their representation (key codes vs keysyms) differs per platform.
+
+use gpui::WindowKind;
+use lux_core::DragPayload;
+use lux_plugin_api::DesktopPlatform;
+
+use crate::theme::SystemPreferences;
 
 #[cfg(target_os = "macos")]
-pub mod macos;
+mod macos;
 
 #[cfg(target_os = "macos")]
-pub use macos::*;
+pub use macos::{
+    keycodes, parse_hotkey, Hotkey, HotkeyCallback, HotkeyManager, MultiHotkeyManager,
+};
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+#[cfg(target_os = "linux")]
+pub use linux::{
+    keysyms, modifiers, parse_hotkey, Hotkey, HotkeyCallback, HotkeyManager, MultiHotkeyManager,
+};
+
+// =============================================================================
+// Platform Trait
+// =============================================================================
+
+/// Operating system services used by the launcher UI.
+pub trait Platform: Send + Sync {
+    /// Desktop services shared with plugins (open, reveal, clipboard, notifications).
+    fn desktop(&self) -> &'static dyn DesktopPlatform;
+
+    // -------------------------------------------------------------------------
+    // Window and Panel
+    // -------------------------------------------------------------------------
+
+    /// Run as a background app without a Dock icon or menu bar.
+    fn set_activation_policy_accessory(&self);
+
+    /// Kind of window the launcher panel opens as.
+    fn window_kind(&self) -> WindowKind;
+
+    /// Enable or disable window show/hide animations (off for Reduce Motion).
+    fn set_window_animations(&self, enabled: bool);
+
+    /// Keep the launcher above other windows and visible while another app
+    /// is active (pinned mode), or go back to hiding with the app.
+    fn set_keep_visible(&self, keep: bool);
+
+    /// Keep the window titled `title` (a detached view) on screen while the
+    /// launcher is hidden and another app is active.
+    fn keep_window_visible(&self, title: &str);
+
+    /// Take the launcher panel off screen before the app hides, so unhiding
+    /// the app for a detached window doesn't bring the launcher back.
+    fn order_out_launcher(&self);
+
+    /// Show `text` in the menu bar, or remove the status item with `None`.
+    fn set_status_text(&self, text: Option<&str>);
+
+    /// Start a native drag session for a result row.
+    ///
+    /// Returns `false` if the drag could not be started.
+    fn begin_drag(&self, payload: &DragPayload, animate: bool) -> bool;
+
+    // -------------------------------------------------------------------------
+    // Hotkeys
+    // -------------------------------------------------------------------------
+
+    /// Whether global hotkeys are allowed to observe key events.
+    fn has_accessibility_permission(&self) -> bool;
+
+    /// Ask the user for the permission global hotkeys need.
+    fn prompt_accessibility_permission(&self) -> bool;
+
+    /// Register a single global hotkey. Dropping the manager unregisters it.
+    fn hotkey_manager(&self, hotkey: Hotkey, callback: HotkeyCallback) -> Option<HotkeyManager>;
+
+    /// Create a manager for hotkeys registered from Lua.
+    fn multi_hotkey_manager(&self) -> Option<MultiHotkeyManager>;
+
+    // -------------------------------------------------------------------------
+    // System State
+    // -------------------------------------------------------------------------
+
+    /// The user's preferred locale (e.g. "de-DE").
+    fn system_locale(&self) -> Option<String>;
+
+    /// Appearance and accessibility display settings.
+    fn system_preferences(&self) -> SystemPreferences;
+
+    /// Name and identifier of the frontmost app.
+    fn frontmost_application(&self) -> (Option<String>, Option<String>);
+
+    /// Local weekday (1 = Monday) and minutes since midnight.
+    fn local_time(&self) -> (u8, u32);
+
+    // -------------------------------------------------------------------------
+    // Screen Reader
+    // -------------------------------------------------------------------------
+
+    /// Whether a screen reader is running.
+    fn voiceover_enabled(&self) -> bool;
+
+    /// Have the screen reader speak `text`.
+    fn announce(&self, text: &str);
+
+    // -------------------------------------------------------------------------
+    // Feedback
+    // -------------------------------------------------------------------------
+
+    /// Tap the trackpad, if it supports haptic feedback.
+    fn perform_haptic(&self);
+}
+
+/// The platform Lux was built for.
+pub fn current() -> &'static dyn Platform {
+    #[cfg(target_os = "macos")]
+    {
+        &macos::MacPlatform
+    }
+    #[cfg(target_os = "linux")]
+    {
+        &linux::LinuxPlatform
+    }
+}
@@ -3,8 +3,17 @@
 //! Provides a two-layer theming system:
 //! - `ThemeSettings`: User-configurable preferences (persisted)
 //! - `Theme`: Computed colors derived from settings + system appearance
+//!
+//! The macOS accessibility settings "Reduce Motion" and "Increase Contrast"
+//! are part of the system state (`SystemPreferences`); `ThemeSettings` can
+//! override both.
+//!
+//! Density and scale size the UI: density picks the row heights and spacing,
+//! and the scale multiplies them along with the font sizes and the rem size
+//! that paddings and text helpers (`px_2`, `text_sm`) are relative to.
 
 use gpui::{hsla, px, App, Global, Hsla, Pixels, SharedString};
+use lux_plugin_api::{ThemeAppearance, ThemeDensity, ThemeOverrides};
 
 // =============================================================================
 // Theme Settings (User-Configurable)
@@ -24,6 +33,48 @@ pub struct ThemeSettings {
     pub font_family: SharedString,
     /// Base font size.
     pub font_size: Pixels,
+    /// Disable animations (`None` follows the system "Reduce Motion" setting).
+    pub reduce_motion: Option<bool>,
+    /// Raise contrast (`None` follows the system "Increase Contrast" setting).
+    pub increase_contrast: Option<bool>,
+    /// Compact or comfortable rows.
+    pub density: Density,
+    /// Scale factor for row heights, paddings, and font sizes.
+    pub scale: f32,
+}
+
+impl ThemeSettings {
+    /// Apply overrides set with `lux.theme.set()`.
+    pub fn with_overrides(mut self, overrides: &ThemeOverrides) -> Self {
+        if let Some(appearance) = overrides.appearance {
+            self.appearance = match appearance {
+                ThemeAppearance::Light => Appearance::Light,
+                ThemeAppearance::Dark => Appearance::Dark,
+                ThemeAppearance::System => Appearance::System,
+            };
+        }
+        if let Some(hue) = overrides.accent_hue {
+            self.accent_hue = hue / 360.0;
+        }
+        if let Some(family) = &overrides.font_family {
+            self.font_family = family.clone().into();
+        }
+        if let Some(size) = overrides.font_size {
+            self.font_size = px(size);
+        }
+        self.reduce_motion = overrides.reduce_motion.or(self.reduce_motion);
+        self.increase_contrast = overrides.increase_contrast.or(self.increase_contrast);
+        if let Some(density) = overrides.density {
+            self.density = match density {
+                ThemeDensity::Compact => Density::Compact,
+                ThemeDensity::Comfortable => Density::Comfortable,
+            };
+        }
+        if let Some(scale) = overrides.scale {
+            self.scale = scale;
+        }
+        self
+    }
 }
 
 impl Default for ThemeSettings {
@@ -33,6 +84,10 @@ impl Default for ThemeSettings {
             accent_hue: 210.0 / 360.0, // Blue
             font_family: "Inter".into(),
             font_size: px(14.0),
+            reduce_motion: None,
+            increase_contrast: None,
+            density: Density::Comfortable,
+            scale: 1.0,
         }
     }
 }
@@ -48,6 +103,25 @@ pub enum Appearance {
     System,
 }
 
+/// UI density preference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Density {
+    Compact,
+    #[default]
+    Comfortable,
+}
+
+/// System appearance and accessibility display settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemPreferences {
+    /// Dark mode is on.
+    pub is_dark: bool,
+    /// "Reduce Motion" is on.
+    pub reduce_motion: bool,
+    /// "Increase Contrast" is on.
+    pub increase_contrast: bool,
+}
+
 // =============================================================================
 // Theme (Computed)
 // =============================================================================
@@ -108,6 +182,8 @@ pub struct Theme {
     pub border: Hsla,
     /// Focused border (derived from accent).
     pub border_focused: Hsla,
+    /// Border around the row under the cursor (derived from accent).
+    pub cursor_border: Hsla,
 
     // -------------------------------------------------------------------------
     // Typography
@@ -124,6 +200,8 @@ pub struct Theme {
     // -------------------------------------------------------------------------
     // Spacing
     // -------------------------------------------------------------------------
+    /// Size of one rem, which paddings and text helpers are relative to.
+    pub rem_size: Pixels,
     /// Base spacing unit.
     pub spacing: Pixels,
     /// Border radius for rounded elements.
@@ -132,27 +210,67 @@ pub struct Theme {
     pub icon_size: Pixels,
     /// Height of result item rows.
     pub item_height: Pixels,
+    /// Height each extra line of text adds to a result row.
+    pub line_height: Pixels,
     /// Height of group header rows.
     pub group_header_height: Pixels,
+
+    // -------------------------------------------------------------------------
+    // Accessibility
+    // -------------------------------------------------------------------------
+    /// Animations are disabled.
+    pub reduce_motion: bool,
+    /// Colors and borders use the high-contrast palette.
+    pub high_contrast: bool,
 }
 
 impl Theme {
     /// Create a theme from settings and system appearance.
     pub fn from_settings(settings: &ThemeSettings, system_is_dark: bool) -> Self {
+        let system = SystemPreferences {
+            is_dark: system_is_dark,
+            ..Default::default()
+        };
+        Self::from_system(settings, &system)
+    }
+
+    /// Create a theme from settings and the full system state.
+    pub fn from_system(settings: &ThemeSettings, system: &SystemPreferences) -> Self {
         let is_dark = match settings.appearance {
             Appearance::Dark => true,
             Appearance::Light => false,
-            Appearance::System => system_is_dark,
+            Appearance::System => system.is_dark,
         };
+        let reduce_motion = settings.reduce_motion.unwrap_or(system.reduce_motion);
+        let high_contrast = settings
+            .increase_contrast
+            .unwrap_or(system.increase_contrast);
 
-        let palette = if is_dark {
+        let mut palette = if is_dark {
             Palette::dark(settings.accent_hue)
         } else {
             Palette::light(settings.accent_hue)
         };
+        if high_contrast {
+            palette = palette.with_increased_contrast(is_dark);
+        }
+
+        let selection_alpha = match (is_dark, high_contrast) {
+            (true, false) => 0.3,
+            (false, false) => 0.2,
+            (true, true) => 0.5,
+            (false, true) => 0.4,
+        };
 
         // Convert font_size to f32 for arithmetic
-        let base_size: f32 = settings.font_size.into();
+        let scale = settings.scale;
+        let base_size = f32::from(settings.font_size) * scale;
+
+        // (spacing, icon, item, line, group header, rem) at scale 1
+        let (spacing, icon, item, line, header, rem) = match settings.density {
+            Density::Comfortable => (8.0, 24.0, 40.0, 18.0, 28.0, 16.0),
+            Density::Compact => (6.0, 20.0, 32.0, 16.0, 24.0, 14.0),
+        };
 
         Self {
             is_dark,
@@ -169,7 +287,7 @@ impl Theme {
 
             // Interactive - derived from accent
             cursor: palette.bg_hover,
-            selection: palette.accent.with_alpha(if is_dark { 0.3 } else { 0.2 }),
+            selection: palette.accent.with_alpha(selection_alpha),
             accent: palette.accent,
 
             // Semantic
@@ -180,22 +298,36 @@ impl Theme {
             // Borders - focused derived from accent
             border: palette.border,
             border_focused: palette.accent,
+            cursor_border: palette
+                .accent
+                .with_alpha(if high_contrast { 1.0 } else { 0.5 }),
 
             // Typography - derived from settings
             font_family: settings.font_family.clone(),
-            font_size: settings.font_size,
-            font_size_small: px(base_size - 2.0),
-            font_size_large: px(base_size + 2.0),
-
-            // Spacing
-            spacing: px(8.0),
-            radius: px(8.0),
-            icon_size: px(24.0),
-            item_height: px(40.0),
-            group_header_height: px(28.0),
+            font_size: px(base_size),
+            font_size_small: px(base_size - 2.0 * scale),
+            font_size_large: px(base_size + 2.0 * scale),
+
+            // Spacing - from density, times the scale
+            rem_size: px(rem * scale),
+            spacing: px(spacing * scale),
+            radius: px(8.0 * scale),
+            icon_size: px(icon * scale),
+            item_height: px(item * scale),
+            line_height: px(line * scale),
+            group_header_height: px(header * scale),
+
+            // Accessibility
+            reduce_motion,
+            high_contrast,
         }
     }
 
+    /// Height of a result row with `lines` lines of text.
+    pub fn row_height(&self, lines: usize) -> Pixels {
+        self.item_height + self.line_height * lines.saturating_sub(1) as f32
+    }
+
     /// Create default dark theme.
     pub fn dark() -> Self {
         Self::from_settings(&ThemeSettings::default(), true)
@@ -270,6 +402,25 @@ impl Palette {
             error: hsla(0.0, 0.80, 0.45, 1.0),
         }
     }
+
+    /// Raise contrast: near-opaque background (less vibrancy bleed-through),
+    /// secondary text closer to primary, and stronger borders and hover.
+    fn with_increased_contrast(self, is_dark: bool) -> Self {
+        let (secondary, tertiary, edge) = if is_dark {
+            (0.80, 0.65, 1.0)
+        } else {
+            (0.25, 0.40, 0.0)
+        };
+        Self {
+            bg_base: self.bg_base.with_alpha(0.95),
+            bg_hover: self.bg_hover.with_alpha(self.bg_hover.a * 2.0),
+            fg_primary: self.fg_primary.with_alpha(1.0),
+            fg_secondary: hsla(0.0, 0.0, secondary, 1.0),
+            fg_tertiary: hsla(0.0, 0.0, tertiary, 1.0),
+            border: hsla(0.0, 0.0, edge, 0.50),
+            ..self
+        }
+    }
 }
 
 // =============================================================================
@@ -382,6 +533,77 @@ mod tests {
         assert!((large - 18.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_increase_contrast_follows_system_and_override() {
+        let settings = ThemeSettings::default();
+        let system = SystemPreferences {
+            is_dark: true,
+            increase_contrast: true,
+            ..Default::default()
+        };
+
+        let normal = Theme::from_settings(&settings, true);
+        let high = Theme::from_system(&settings, &system);
+        assert!(high.high_contrast);
+        assert!(high.border.a > normal.border.a);
+        assert!(high.cursor_border.a > normal.cursor_border.a);
+        assert!(high.text_muted.l > normal.text_muted.l);
+
+        // Settings override the system
+        let settings = ThemeSettings {
+            increase_contrast: Some(false),
+            ..Default::default()
+        };
+        assert!(!Theme::from_system(&settings, &system).high_contrast);
+    }
+
+    #[test]
+    fn test_reduce_motion_and_overrides() {
+        let system = SystemPreferences {
+            reduce_motion: true,
+            ..Default::default()
+        };
+        assert!(Theme::from_system(&ThemeSettings::default(), &system).reduce_motion);
+
+        let overrides = ThemeOverrides {
+            appearance: Some(ThemeAppearance::Dark),
+            accent_hue: Some(0.0),
+            reduce_motion: Some(false),
+            ..Default::default()
+        };
+        let settings = ThemeSettings::default().with_overrides(&overrides);
+        assert_eq!(settings.appearance, Appearance::Dark);
+        assert_eq!(settings.increase_contrast, None);
+
+        let theme = Theme::from_system(&settings, &system);
+        assert!(!theme.reduce_motion);
+        assert!(theme.is_dark);
+        assert!((theme.accent.h - 0.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_density_and_scale() {
+        let comfortable = Theme::from_settings(&ThemeSettings::default(), true);
+        assert_eq!(comfortable.item_height, px(40.0));
+        assert_eq!(comfortable.rem_size, px(16.0));
+
+        let overrides = ThemeOverrides {
+            density: Some(ThemeDensity::Compact),
+            scale: Some(1.5),
+            ..Default::default()
+        };
+        let settings = ThemeSettings::default().with_overrides(&overrides);
+        assert_eq!(settings.density, Density::Compact);
+
+        let theme = Theme::from_settings(&settings, true);
+        assert_eq!(theme.item_height, px(48.0));
+        assert_eq!(theme.group_header_height, px(36.0));
+        assert_eq!(theme.rem_size, px(21.0));
+        assert!((f32::from(theme.font_size) - 21.0).abs() < 0.001);
+        assert!((f32::from(theme.font_size_small) - 18.0).abs() < 0.001);
+        assert_eq!(theme.row_height(2), px(72.0));
+    }
+
     #[test]
     fn test_selection_alpha_differs_by_mode() {
         let settings = ThemeSettings::default();
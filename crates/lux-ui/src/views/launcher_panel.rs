@@ -1,34 +1,123 @@
 //! Launcher panel view - the main UI composition.
 //!
 //! This view coordinates the search input, results list, and action menu.
-//! It subscribes to backend state changes for reactive updates.
+//! It subscribes to backend state changes for reactive updates, and posts
+//! VoiceOver announcements as focus moves (see [`crate::accessibility`]).
 //!
 //! ## Architecture
 //!
 //! - Backend owns view configuration (placeholder, title, selection_mode)
 //! - UI owns ephemeral display state (cursor, scroll, cached results)
+//! - UI saves that state on the view instance in the backend when another
+//!   view covers it, and restores it when the view is on top again
+//! - Groups marked `collapsible` fold away under their header (Left/Right on
+//!   an empty query, or a click); the backend remembers them per view
+//! - An action can put a row into inline edit mode (`ctx:edit()`); Enter
+//!   passes the text to its Lua callback and Escape cancels
+//! - Sounds and haptics from `lux.ui.sounds{...}` play when the launcher
+//!   opens and when an action completes or fails
+//! - Pinning (`toggle_pin`, or the pin button) keeps the launcher open after
+//!   actions and when it loses focus, for working through a list of items
+//! - `detach_view` pops the current view out into its own window (see
+//!   [`crate::detached`]); panels in those windows stay open like pinned ones
+//! - Keyboard moves (arrows, groups, PageUp/PageDown) scroll the cursor into
+//!   view with a short animation (a jump under Reduce Motion); refreshed
+//!   results keep the scroll position, and a new query starts at the top
+//! - Cmd+1..9 (`execute_item(n)`) run the default action of the first nine
+//!   results, whose numbers show as badges while Cmd is held
+//! - Right-clicking a row opens its actions at the pointer, and clicking an
+//!   action runs it; with `lux.ui.mouse{ hover = true }` the cursor follows
+//!   the pointer
+//! - Cmd+Shift+M (`toggle_macro_recording`) records the query, launcher
+//!   actions, and action shortcuts to a macro (see [`lux_plugin_api::macros`]);
+//!   `play_macro(name)` plays one back by dispatching the same actions
+//! - App actions (`reload_config`, `toggle_theme`, `open_logs`, ...) run here,
+//!   from a keybinding or from the Commands view (`ctx:run_command()`);
+//!   developer mode shows each row's item ID
 //! - State changes flow reactively via subscription
 
-use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use gpui::{
-    div, img, prelude::*, px, size, App, AsyncApp, Context, ElementId, Entity, EventEmitter,
-    FocusHandle, Focusable, InteractiveElement, IntoElement, KeyContext, ParentElement, Pixels,
-    Render, SharedString, Size, Styled, WeakEntity, Window,
+    anchored, deferred, div, img, prelude::*, px, size, AnyWindowHandle, App, AsyncApp,
+    ClipboardItem, Context, ElementId, Entity, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, KeyContext, KeyDownEvent, ModifiersChangedEvent, MouseButton,
+    MouseDownEvent, MouseMoveEvent, ParentElement, Pixels, Point, Render, SharedString, Size,
+    Styled, Task, WeakEntity, Window,
 };
 use gpui_component::{v_virtual_list, VirtualListScrollHandle};
-use lux_core::{ActionResult, BackendError, Group, Item, ItemId, SelectionMode};
+use lux_core::i18n::{t, t_args};
+use lux_core::{
+    truncate_middle, ActionResult, BackendError, Group, InlineEdit, Item, ItemId, SelectionMode,
+    SubtitlePosition, Truncate,
+};
+use lux_plugin_api::collapsed::ROOT_VIEW_KEY;
+use lux_plugin_api::{
+    audio, MacroStep, RepeatOutcome, SavedViewState, SoundEvent, ThemeAppearance, UndoInfo,
+    ViewStackChange, ViewState, BASKET_OPEN_KEY,
+};
+use tokio::sync::broadcast::error::RecvError;
 
+use crate::accessibility::{
+    action_label, confirm_label, item_label, results_label, search_field_label, Announcer,
+};
 use crate::actions::{
-    CursorDown, CursorUp, Dismiss, OpenActionMenu, RunLuaHandler, ToggleSelection,
+    action_from_name, ClearCaches, CursorDown, CursorUp, DetachView, Dismiss, ExecuteItem,
+    JumpToGroup, NextGroup, OpenActionMenu, OpenConfig, OpenLogs, PageDown, PageUp, PlayMacro,
+    PrevGroup, ReloadConfig, RepeatLastAction, RunLuaHandler, ToggleDevMode, ToggleMacroRecording,
+    TogglePin, ToggleSelection, ToggleTheme, Undo,
+};
+use crate::backend::{Backend, BackendState, DefaultActionOutcome};
+use crate::crash::{self, LogFile};
+use crate::instance;
+use crate::keymap::{shortcut_from_keystroke, shortcut_label};
+use crate::model::{
+    ease_scroll, group_starts, max_scroll, next_group_start, page_rows, prev_group_start,
+    scroll_into_view, sticky_header, ActionMenuItem, ActionMenuState, CompletionState,
+    ExecutionFeedback, ListEntry,
 };
-use crate::backend::{Backend, BackendState};
-use crate::model::{ActionMenuItem, ActionMenuState, ExecutionFeedback, ListEntry};
+use crate::platform;
 use crate::theme::ThemeExt;
-use crate::views::{scroll_to_cursor, SearchInput, SearchInputEvent};
+use crate::views::{
+    scroll_offset, scroll_to_cursor, set_scroll_offset, viewport_height, DragPreview, DraggedItem,
+    SearchInput, SearchInputEvent,
+};
+use crate::window::refresh_theme;
+
+/// How long the undo toast stays up after an undoable action.
+const UNDO_TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// How long scrolling the cursor into view is animated for.
+const SCROLL_DURATION: Duration = Duration::from_millis(120);
+
+/// Time between frames of the scroll animation.
+const SCROLL_FRAME: Duration = Duration::from_millis(16);
+
+/// Results that get a Cmd+number shortcut (`execute_item(n)`).
+const QUICK_SELECT_COUNT: usize = 9;
+
+/// Shortest time between searches re-run for WebSocket messages.
+const WS_REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Pause before each step of a macro being played, for results and views to catch up.
+const MACRO_STEP_DELAY: Duration = Duration::from_millis(100);
+
+/// Longest a macro step waits for the search the previous step started.
+const MACRO_STEP_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Width of the action menu opened by right-clicking a row.
+const CONTEXT_MENU_WIDTH: f32 = 260.0;
+
+/// Characters that fit on a row's first line, for `truncate = "middle"`
+/// (GPUI itself only cuts text at the end).
+const MIDDLE_TRUNCATE_CHARS: usize = 56;
+
+/// Characters of a subtitle that fit after the title, for `truncate = "middle"`.
+const MIDDLE_TRUNCATE_TRAILING_CHARS: usize = 32;
 
 // =============================================================================
 // Events
@@ -39,18 +128,32 @@ use crate::views::{scroll_to_cursor, SearchInput, SearchInputEvent};
 pub enum LauncherPanelEvent {
     /// Request to dismiss the launcher.
     Dismiss,
+    /// The launcher was pinned (kept open) or unpinned.
+    PinChanged(bool),
+    /// Request to open the view with this ID in a detached window.
+    Detach(String),
 }
 
+/// Developer mode (`toggle_dev_mode`): result rows show their item IDs.
+///
+/// Shared by the launcher and detached windows; off at each start.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DevMode(pub bool);
+
+impl gpui::Global for DevMode {}
+
 // =============================================================================
 // View Display State
 // =============================================================================
 
-/// Ephemeral display state per view depth.
+/// Ephemeral display state per view instance.
 ///
-/// This is UI-owned state that resets on view push/pop.
+/// This is UI-owned state, kept while the view instance is on the stack.
 /// Backend owns the view configuration (placeholder, title, selection_mode).
 #[derive(Debug)]
 struct ViewDisplayState {
+    /// Backend view instance this state belongs to.
+    instance_id: Option<u64>,
     /// View identifier for keybinding context.
     view_id: Option<String>,
     /// Cursor position as index into items.
@@ -59,10 +162,23 @@ struct ViewDisplayState {
     selection_mode: SelectionMode,
     /// Selected item IDs.
     selected_ids: HashSet<ItemId>,
+    /// Whether selection changes go to the view's `on_selection_changed`.
+    has_on_selection_changed: bool,
+    /// Counter for discarding stale `on_selection_changed` results.
+    selection_generation: u64,
     /// Current query text.
     query: String,
-    /// Cached search results.
-    cached_groups: Vec<Group>,
+    /// View title from backend.
+    title: Option<String>,
+    /// Search placeholder from backend.
+    placeholder: String,
+    /// Search results as grouped by the view, including collapsed groups.
+    sections: Vec<Section>,
+    /// Titles of the collapsed groups.
+    collapsed: BTreeSet<String>,
+    /// Listed results in display order, shared with `flat_entries`
+    /// (collapsed groups left out).
+    items: Vec<Arc<Item>>,
     /// Flattened entries for rendering.
     flat_entries: Vec<ListEntry>,
     /// Item IDs in display order.
@@ -71,53 +187,279 @@ struct ViewDisplayState {
     generation: u64,
     /// Whether a search is in progress.
     loading: bool,
+    /// Interval for re-running the search, from the view's `refresh_ms`.
+    refresh: Option<Duration>,
+    /// When the last search started.
+    last_search: Option<Instant>,
+    /// Saved state to restore once the next search results arrive.
+    restore: Option<SavedViewState>,
+    /// Query the listed results are for.
+    results_query: Option<String>,
+}
+
+/// A group of search results, kept whole while it is collapsed.
+#[derive(Debug)]
+struct Section {
+    title: Option<String>,
+    collapsible: bool,
+    items: Vec<Arc<Item>>,
 }
 
 impl Default for ViewDisplayState {
     fn default() -> Self {
         Self {
+            instance_id: None,
             view_id: None,
             cursor_index: 0,
             selection_mode: SelectionMode::Single,
             selected_ids: HashSet::new(),
+            has_on_selection_changed: false,
+            selection_generation: 0,
             query: String::new(),
-            cached_groups: Vec::new(),
+            title: None,
+            placeholder: t("Search..."),
+            sections: Vec::new(),
+            collapsed: BTreeSet::new(),
+            items: Vec::new(),
             flat_entries: Vec::new(),
             item_ids: Vec::new(),
             generation: 0,
             loading: false,
+            refresh: None,
+            last_search: None,
+            restore: None,
+            results_query: None,
         }
     }
 }
 
 impl ViewDisplayState {
-    /// Update groups and rebuild indices.
+    /// Display state for a view instance the UI has not shown yet.
+    ///
+    /// Starts from the state saved in the backend, if any, otherwise from the
+    /// view's initial query and selection.
+    fn for_view(view: &ViewState) -> Self {
+        let mut display = Self {
+            instance_id: Some(view.instance_id),
+            ..Self::default()
+        };
+        match &view.saved {
+            Some(saved) => {
+                display.query = saved.query.clone();
+                display.selected_ids = saved.selected.iter().cloned().map(ItemId).collect();
+                display.restore = Some(saved.clone());
+            }
+            None => {
+                display.query = view.initial_query.clone().unwrap_or_default();
+                display.selected_ids = view.selected.iter().cloned().map(ItemId).collect();
+            }
+        }
+        display.sync_config(view);
+        display
+    }
+
+    /// Copy the view configuration from the backend.
+    fn sync_config(&mut self, view: &ViewState) {
+        self.selection_mode = view.selection;
+        self.has_on_selection_changed = view.has_on_selection_changed;
+        self.view_id = view.id.clone();
+        self.title = view.title.clone();
+        self.refresh = view.refresh_ms.map(Duration::from_millis);
+        // A view without a placeholder gets the default, not the last view's
+        self.placeholder = view.placeholder.clone().unwrap_or_else(|| t("Search..."));
+    }
+
+    /// Snapshot of the state to save in the backend.
+    fn saved_state(&self, scroll_offset: f32) -> SavedViewState {
+        SavedViewState {
+            query: self.query.clone(),
+            cursor_item: self.item_ids.get(self.cursor_index).map(|id| id.0.clone()),
+            scroll_offset,
+            selected: sorted_ids(&self.selected_ids),
+        }
+    }
+
+    /// Move the cursor back to the saved cursor item, if it is still listed.
+    ///
+    /// Returns the saved scroll offset, or `None` if there is nothing to restore.
+    fn restore_cursor(&mut self) -> Option<f32> {
+        let saved = self.restore.as_ref()?;
+        let index = saved
+            .cursor_item
+            .as_ref()
+            .and_then(|cursor| self.item_ids.iter().position(|id| id.0 == *cursor));
+        if let Some(index) = index {
+            self.cursor_index = index;
+        }
+        Some(saved.scroll_offset)
+    }
+
+    /// Key the collapsed groups of this view are saved under.
+    fn view_key(&self) -> &str {
+        self.view_id.as_deref().unwrap_or(ROOT_VIEW_KEY)
+    }
+
+    /// Update groups and rebuild indices. The cursor stays on its item
+    /// while it is listed.
+    ///
+    /// Items move into `Arc`s shared by the entries and the item list, so a
+    /// result set is never copied.
     fn set_groups(&mut self, groups: Vec<Group>) {
-        self.cached_groups = groups;
-        self.rebuild_indices();
+        let cursor_id = self.item_ids.get(self.cursor_index).cloned();
+        self.sections = groups
+            .into_iter()
+            .map(|group| Section {
+                // Only a group with a header can fold away under it
+                collapsible: group.collapsible && group.title.is_some(),
+                title: group.title,
+                items: group.items.into_iter().map(Arc::new).collect(),
+            })
+            .collect();
+        self.rebuild_entries();
+        if let Some(index) = cursor_id.and_then(|id| self.item_ids.iter().position(|i| *i == id)) {
+            self.cursor_index = index;
+        }
         self.clamp_cursor();
     }
 
-    fn rebuild_indices(&mut self) {
+    /// Show the results of the search for `query`. Returns true if they
+    /// replace the results of another query; the cursor then starts over at
+    /// the first item.
+    fn set_results(&mut self, groups: Vec<Group>) -> bool {
+        self.set_groups(groups);
+        let new_query = self.results_query.as_ref() != Some(&self.query);
+        if new_query {
+            self.cursor_index = 0;
+            self.results_query = Some(self.query.clone());
+        }
+        new_query
+    }
+
+    /// Height of each entry in `flat_entries`, in pixels.
+    fn entry_heights<'a>(
+        &'a self,
+        theme: &'a crate::theme::Theme,
+    ) -> impl Iterator<Item = f32> + 'a {
+        self.flat_entries.iter().map(|entry| match entry {
+            ListEntry::GroupHeader { .. } => f32::from(theme.group_header_height),
+            ListEntry::Item { item, .. } => f32::from(theme.row_height(item.row_lines())),
+        })
+    }
+
+    /// Rebuild `flat_entries`, `item_ids`, and `items` from the sections,
+    /// leaving out the items of collapsed groups.
+    fn rebuild_entries(&mut self) {
         self.flat_entries.clear();
         self.item_ids.clear();
-        let mut flat_index = 0;
+        self.items.clear();
 
-        for group in &self.cached_groups {
-            if let Some(title) = &group.title {
+        for section in &self.sections {
+            let collapsed = self.is_collapsed(section);
+            if let Some(title) = &section.title {
                 self.flat_entries.push(ListEntry::GroupHeader {
                     title: title.clone(),
+                    collapsible: section.collapsible,
+                    collapsed,
+                    item_count: section.items.len(),
                 });
             }
-            for item in &group.items {
+            if collapsed {
+                continue;
+            }
+            for item in &section.items {
                 self.flat_entries.push(ListEntry::Item {
                     item: item.clone(),
-                    flat_index,
+                    flat_index: self.items.len(),
                 });
                 self.item_ids.push(item.item_id());
-                flat_index += 1;
+                self.items.push(item.clone());
+            }
+        }
+    }
+
+    fn is_collapsed(&self, section: &Section) -> bool {
+        section.collapsible
+            && section
+                .title
+                .as_ref()
+                .is_some_and(|title| self.collapsed.contains(title))
+    }
+
+    /// Index of the section the cursor item is in.
+    fn cursor_section(&self) -> Option<usize> {
+        let mut listed = 0;
+        for (index, section) in self.sections.iter().enumerate() {
+            if self.is_collapsed(section) {
+                continue;
+            }
+            listed += section.items.len();
+            if self.cursor_index < listed {
+                return Some(index);
             }
         }
+        None
+    }
+
+    /// The group Left collapses: the cursor's, if it can collapse.
+    fn group_to_collapse(&self) -> Option<String> {
+        let section = &self.sections[self.cursor_section()?];
+        section.collapsible.then(|| section.title.clone()).flatten()
+    }
+
+    /// The group Right expands: the collapsed group just above the cursor's
+    /// (where the cursor lands after collapsing), else the one below it.
+    /// Without a cursor item, the first collapsed group.
+    fn group_to_expand(&self) -> Option<String> {
+        let collapsed_title = |index: usize| {
+            self.sections
+                .get(index)
+                .filter(|section| self.is_collapsed(section))
+                .and_then(|section| section.title.clone())
+        };
+        match self.cursor_section() {
+            Some(index) => index
+                .checked_sub(1)
+                .and_then(collapsed_title)
+                .or_else(|| collapsed_title(index + 1)),
+            None => (0..self.sections.len()).find_map(collapsed_title),
+        }
+    }
+
+    /// Collapse or expand the group titled `title`. Returns true if it changed.
+    ///
+    /// The cursor stays on its item if it is still listed; otherwise it
+    /// moves to the first item after the collapsed group.
+    fn set_group_collapsed(&mut self, title: &str, collapsed: bool) -> bool {
+        let Some(position) = self
+            .sections
+            .iter()
+            .position(|section| section.collapsible && section.title.as_deref() == Some(title))
+        else {
+            return false;
+        };
+        let changed = if collapsed {
+            self.collapsed.insert(title.to_string())
+        } else {
+            self.collapsed.remove(title)
+        };
+        if !changed {
+            return false;
+        }
+
+        let cursor_id = self.item_ids.get(self.cursor_index).cloned();
+        self.rebuild_entries();
+        self.cursor_index =
+            match cursor_id.and_then(|id| self.item_ids.iter().position(|i| *i == id)) {
+                Some(index) => index,
+                // Items listed before the collapsed group
+                None => self.sections[..position]
+                    .iter()
+                    .filter(|section| !self.is_collapsed(section))
+                    .map(|section| section.items.len())
+                    .sum(),
+            };
+        self.clamp_cursor();
+        true
     }
 
     fn clamp_cursor(&mut self) {
@@ -138,17 +480,69 @@ impl ViewDisplayState {
         }
     }
 
+    /// Move the cursor `rows` items down (or up), stopping at the ends.
+    fn cursor_by_page(&mut self, rows: usize, down: bool) {
+        self.cursor_index = if down {
+            (self.cursor_index + rows).min(self.item_ids.len().saturating_sub(1))
+        } else {
+            self.cursor_index.saturating_sub(rows)
+        };
+    }
+
+    /// Move the cursor to the next group's first item.
+    fn cursor_next_group(&mut self) {
+        if let Some(start) = next_group_start(&group_starts(&self.flat_entries), self.cursor_index)
+        {
+            self.cursor_index = start;
+        }
+    }
+
+    /// Move the cursor to its group's first item, or the previous group's.
+    fn cursor_prev_group(&mut self) {
+        if let Some(start) = prev_group_start(&group_starts(&self.flat_entries), self.cursor_index)
+        {
+            self.cursor_index = start;
+        }
+    }
+
+    /// Move the cursor to the first item of group `number` (1-based).
+    fn cursor_to_group(&mut self, number: usize) {
+        let starts = group_starts(&self.flat_entries);
+        if let Some(&start) = number.checked_sub(1).and_then(|index| starts.get(index)) {
+            self.cursor_index = start;
+        }
+    }
+
     fn cursor_item(&self) -> Option<&Item> {
-        self.item_ids.get(self.cursor_index).and_then(|id| {
-            for group in &self.cached_groups {
-                for item in &group.items {
-                    if item.item_id() == *id {
-                        return Some(item);
-                    }
-                }
-            }
-            None
-        })
+        self.items.get(self.cursor_index).map(|item| item.as_ref())
+    }
+
+    /// Move the cursor to the `number`th listed item (from 1), returning the item.
+    fn cursor_to_number(&mut self, number: usize) -> Option<Item> {
+        let index = number
+            .checked_sub(1)
+            .filter(|&index| index < self.items.len())?;
+        self.cursor_index = index;
+        Some(Item::clone(&self.items[index]))
+    }
+
+    /// Move the cursor to the listed item with ID `id`, returning the item.
+    fn cursor_to_item(&mut self, id: &str) -> Option<Item> {
+        let index = self.items.iter().position(|item| item.id == id)?;
+        self.cursor_index = index;
+        Some(Item::clone(&self.items[index]))
+    }
+
+    /// Spoken label for the row under the cursor.
+    fn cursor_label(&self) -> Option<String> {
+        let item = self.cursor_item()?;
+        let selected = self.selected_ids.contains(&item.item_id());
+        Some(item_label(
+            item,
+            self.cursor_index,
+            self.item_ids.len(),
+            selected,
+        ))
     }
 
     fn cursor_to_list_index(&self) -> usize {
@@ -182,17 +576,30 @@ impl ViewDisplayState {
         }
     }
 
-    fn selected_items(&self) -> Vec<Item> {
-        let mut items = Vec::new();
-        for group in &self.cached_groups {
-            for item in &group.items {
-                if self.selected_ids.contains(&item.item_id()) {
-                    items.push(item.clone());
-                }
-            }
+    /// Items an action runs on: the selection, or else the cursor item.
+    fn action_items(&self) -> Vec<Item> {
+        if self.selected_ids.is_empty() {
+            self.cursor_item().cloned().into_iter().collect()
+        } else {
+            self.selected_items()
         }
-        items
     }
+
+    fn selected_items(&self) -> Vec<Item> {
+        self.items
+            .iter()
+            .filter(|item| self.selected_ids.contains(&item.item_id()))
+            .map(|item| Item::clone(item))
+            .collect()
+    }
+}
+
+/// A row in inline edit mode.
+struct RowEdit {
+    /// What the action asked for.
+    edit: InlineEdit,
+    /// The edited item, passed back with the submitted text.
+    item: Item,
 }
 
 // =============================================================================
@@ -203,18 +610,63 @@ impl ViewDisplayState {
 pub struct LauncherPanel {
     /// Backend for search/actions.
     backend: Arc<dyn Backend>,
-    /// Display state per view depth.
+    /// Display state per view in the stack, bottom to top.
     view_states: Vec<ViewDisplayState>,
     /// Action menu state when open.
     action_menu: Option<ActionMenuState>,
+    /// Where the action menu floats when it was opened by a right click.
+    menu_anchor: Option<Point<Pixels>>,
+    /// Cmd is held, so the first results show their quick-select numbers.
+    quick_select: bool,
     /// Execution feedback.
     execution_feedback: Option<ExecutionFeedback>,
+    /// Completion view with follow-up actions, shown in place of the results.
+    completion: Option<CompletionState>,
     /// Search input view.
     search_input: Entity<SearchInput>,
+    /// Text input shown in the row being edited inline.
+    row_editor: Entity<SearchInput>,
+    /// The row in inline edit mode, if any.
+    row_edit: Option<RowEdit>,
+    /// Focus to move on the next render (action results arrive without a window).
+    focus_request: Option<FocusHandle>,
     /// Focus handle.
     focus_handle: FocusHandle,
     /// Scroll handle for results list.
     scroll_handle: VirtualListScrollHandle,
+    /// VoiceOver announcements.
+    announcer: Announcer,
+    /// Re-runs the current view's search while the launcher is shown.
+    refresh_task: Option<Task<()>>,
+    /// Actions of the cursor item that have a shortcut.
+    action_hints: Vec<ActionMenuItem>,
+    /// View instance and item the action hints were fetched for.
+    action_hints_for: Option<(Option<u64>, ItemId)>,
+    /// Shortcut of a destructive action pressed once, awaiting a second press.
+    confirm_shortcut: Option<String>,
+    /// Activity entry ID and title of a destructive action `repeat_last_action`
+    /// would run, awaiting a second press.
+    confirm_repeat: Option<(u64, String)>,
+    /// Undoable operation offered in the toast.
+    undo_toast: Option<UndoInfo>,
+    /// ID of the newest undo entry already offered.
+    undo_seen: u64,
+    /// Hides the undo toast after a delay.
+    undo_toast_task: Option<Task<()>>,
+    /// Report of the crash the last run ended with, until dismissed.
+    crash_report: Option<String>,
+    /// Keep the launcher open after actions and when it loses focus.
+    pinned: bool,
+    /// Runs in a detached window rather than the launcher window.
+    detached: bool,
+    /// Animates scrolling the cursor into view.
+    scroll_task: Option<Task<()>>,
+    /// Scroll position the running animation ends at.
+    scroll_target: Option<f32>,
+    /// Window the panel is in, to dispatch the actions of a macro being played.
+    window_handle: AnyWindowHandle,
+    /// Plays a macro, one step at a time.
+    macro_task: Option<Task<()>>,
 }
 
 impl LauncherPanel {
@@ -223,23 +675,83 @@ impl LauncherPanel {
         let focus_handle = cx.focus_handle();
 
         // Create search input
-        let search_input = cx.new(|cx| SearchInput::new("Search...", window, cx));
+        let search_input = cx.new(|cx| SearchInput::new(t("Search..."), window, cx));
 
         // Subscribe to search input events
         cx.subscribe(&search_input, Self::on_search_input_event)
             .detach();
 
+        let row_editor = cx.new(|cx| SearchInput::new("", window, cx));
+        cx.subscribe(&row_editor, Self::on_row_editor_event)
+            .detach();
+
         let scroll_handle = VirtualListScrollHandle::new();
 
-        // Subscribe to backend state changes
+        // Subscribe to backend state changes. Change events arrive one per
+        // mutation; the watch channel is read for the initial stack and to
+        // resync after falling behind.
         let state_rx = backend.subscribe();
+        let mut changes = backend.subscribe_changes();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let initial = state_rx.borrow().clone();
+            if !initial.is_empty() {
+                let _ = this.update(cx, |this, cx| {
+                    this.on_backend_state_changed(None, initial, cx);
+                });
+            }
+            loop {
+                let (change, state) = match changes.recv().await {
+                    Ok(event) => (Some(event.change), event.states),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Missed {} view stack changes, resyncing", skipped);
+                        (None, state_rx.borrow().clone())
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                let _ = this.update(cx, |this, cx| {
+                    this.on_backend_state_changed(change, state, cx);
+                });
+            }
+        })
+        .detach();
+
+        // Re-run the search when background sources (Spotlight) find more
+        let results_rx = backend.subscribe_results();
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-            let mut rx = state_rx;
+            let mut rx = results_rx;
             while rx.changed().await.is_ok() {
-                let state = rx.borrow().clone();
                 let _ = this.update(cx, |this, cx| {
-                    this.on_backend_state_changed(state, cx);
+                    this.refresh_search(cx);
+                });
+            }
+        })
+        .detach();
+
+        // Run the callbacks of WebSocket messages as they arrive, and re-run
+        // the search of the view they update while it's shown
+        let ws_rx = backend.subscribe_ws();
+        let ws_backend = backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let mut rx = ws_rx;
+            while rx.changed().await.is_ok() {
+                let views = match ws_backend.dispatch_ws_events().await {
+                    Ok(views) => views,
+                    Err(e) => {
+                        tracing::warn!("WebSocket callbacks failed: {:?}", e);
+                        continue;
+                    }
+                };
+                if views.is_empty() {
+                    continue;
+                }
+                let updated = this.update(cx, |this, cx| {
+                    this.refresh_views(&views, cx);
                 });
+                if updated.is_err() {
+                    break;
+                }
+                // Messages arriving meanwhile are handled together
+                cx.background_executor().timer(WS_REFRESH_INTERVAL).await;
             }
         })
         .detach();
@@ -247,22 +759,51 @@ impl LauncherPanel {
         // Initialize with one view state - subscription will sync
         let view_states = vec![ViewDisplayState::default()];
 
-        // Hide when window loses focus (user clicks outside)
-        cx.observe_window_activation(window, |_this, window, cx| {
+        // Hide when window loses focus (user clicks outside), unless pinned
+        cx.observe_window_activation(window, |this, window, cx| {
             if !window.is_window_active() {
-                cx.emit(LauncherPanelEvent::Dismiss);
+                // Cmd may be released while another app is in front
+                this.quick_select = false;
+                if !this.pinned {
+                    this.dismiss(cx);
+                }
             }
         })
         .detach();
 
+        // Only operations from now on get a toast
+        let undo_seen = backend.latest_undo().map_or(0, |info| info.id);
+
         let mut this = Self {
             backend,
             view_states,
             action_menu: None,
+            menu_anchor: None,
+            quick_select: false,
             execution_feedback: None,
+            completion: None,
             search_input,
+            row_editor,
+            row_edit: None,
+            focus_request: None,
             focus_handle,
             scroll_handle,
+            announcer: Announcer::new(),
+            refresh_task: None,
+            action_hints: Vec::new(),
+            action_hints_for: None,
+            confirm_shortcut: None,
+            confirm_repeat: None,
+            undo_toast: None,
+            undo_seen,
+            undo_toast_task: None,
+            crash_report: None,
+            pinned: false,
+            detached: false,
+            scroll_task: None,
+            scroll_target: None,
+            window_handle: window.window_handle(),
+            macro_task: None,
         };
 
         // Trigger initial search
@@ -271,10 +812,45 @@ impl LauncherPanel {
         this
     }
 
+    /// Run in a detached window: kept open, and its view can't be detached again.
+    pub fn set_detached(&mut self, cx: &mut Context<Self>) {
+        self.detached = true;
+        self.pinned = true;
+        cx.notify();
+    }
+
+    /// The backend this panel runs on.
+    pub fn backend(&self) -> Arc<dyn Backend> {
+        self.backend.clone()
+    }
+
+    /// Take the view that was just detached off this panel's stack.
+    pub fn view_detached(&mut self, cx: &mut Context<Self>) {
+        if self.view_states.len() > 1 {
+            self.pop_view(cx);
+        }
+    }
+
     /// Show the launcher and focus it.
     pub fn show(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Let the current view know the launcher is shown
+        let backend = self.backend.clone();
+        cx.background_executor()
+            .spawn(async move {
+                if let Err(e) = backend.view_shown().await {
+                    tracing::warn!("View on_show failed: {:?}", e);
+                }
+            })
+            .detach();
+
         // Reset to fresh state
         self.reset_state(cx);
+        self.restart_refresh(cx);
+        self.play_feedback(SoundEvent::Open);
+
+        // Tell VoiceOver where focus landed
+        self.announcer.reset();
+        self.announce_search_field();
 
         // Focus search input
         self.search_input.update(cx, |input, cx| {
@@ -284,6 +860,21 @@ impl LauncherPanel {
         cx.notify();
     }
 
+    /// Announce the search field of the current view.
+    fn announce_search_field(&mut self) {
+        if let Some(display) = self.view_states.last() {
+            let label = search_field_label(&display.placeholder, display.title.as_deref());
+            self.announcer.announce(label);
+        }
+    }
+
+    /// Announce the row under the cursor.
+    fn announce_cursor(&mut self) {
+        if let Some(label) = self.view_states.last().and_then(|d| d.cursor_label()) {
+            self.announcer.announce(label);
+        }
+    }
+
     /// Reset launcher to fresh state (clear input, trigger fresh search).
     fn reset_state(&mut self, cx: &mut Context<Self>) {
         // Clear search input
@@ -295,141 +886,487 @@ impl LauncherPanel {
         self.trigger_search(String::new(), cx);
     }
 
+    /// Put `text` in the search input (a change re-runs the search).
+    fn set_input_text(&mut self, text: String, cx: &mut Context<Self>) {
+        self.search_input.update(cx, |input, cx| {
+            if input.text(cx) != text {
+                input.set_text(text, cx);
+            }
+        });
+    }
+
     /// Hide the launcher.
     pub fn hide(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
-        cx.emit(LauncherPanelEvent::Dismiss);
+        self.dismiss(cx);
         cx.notify();
     }
 
+    /// Stop refreshing and ask the window to hide the launcher.
+    fn dismiss(&mut self, cx: &mut Context<Self>) {
+        self.refresh_task = None;
+        self.macro_task = None;
+        self.confirm_repeat = None;
+        self.hide_undo_toast();
+        cx.emit(LauncherPanelEvent::Dismiss);
+    }
+
     // -------------------------------------------------------------------------
     // Backend State Changes
     // -------------------------------------------------------------------------
 
-    fn on_backend_state_changed(&mut self, state: BackendState, cx: &mut Context<Self>) {
-        let new_depth = state.len();
-        let current_depth = self.view_states.len();
-
+    /// Reconcile the view displays with the backend stack.
+    ///
+    /// `change` is what changed, or None when resyncing the whole stack.
+    fn on_backend_state_changed(
+        &mut self,
+        change: Option<ViewStackChange>,
+        state: BackendState,
+        cx: &mut Context<Self>,
+    ) {
         tracing::info!(
-            "on_backend_state_changed: backend_depth={}, ui_depth={}",
-            new_depth,
-            current_depth
+            "on_backend_state_changed: {:?}, backend_depth={}, ui_depth={}",
+            change,
+            state.len(),
+            self.view_states.len()
         );
 
-        match new_depth.cmp(&current_depth) {
-            Ordering::Greater => {
-                // View pushed - create new display state
-                tracing::info!(
-                    "View pushed, adding {} display states",
-                    new_depth - current_depth
-                );
-                for _ in current_depth..new_depth {
-                    self.view_states.push(ViewDisplayState::default());
-                }
-                // Trigger search for new view
-                self.trigger_search(String::new(), cx);
-            }
-            Ordering::Less => {
-                // View popped - restore previous display state
-                while self.view_states.len() > new_depth && self.view_states.len() > 1 {
-                    self.view_states.pop();
+        // The top view was covered by another view: save its state so a pop
+        // (from the UI or from Lua) brings it back as it was
+        let old_top = self.view_states.last().and_then(|d| d.instance_id);
+        let covered = old_top.is_some_and(|id| {
+            state
+                .iter()
+                .rev()
+                .skip(1)
+                .any(|view| view.instance_id == id)
+        });
+        if covered {
+            self.save_top_state();
+        }
+
+        // Keep the display state of every view instance still on the stack
+        let mut old_states = std::mem::take(&mut self.view_states);
+        for view in &state {
+            let existing = old_states
+                .iter()
+                .position(|d| d.instance_id == Some(view.instance_id));
+            let display = match existing {
+                Some(index) => {
+                    let mut display = old_states.swap_remove(index);
+                    display.sync_config(view);
+                    display
                 }
-                // Scroll to preserved cursor
-                if let Some(display) = self.view_states.last() {
-                    scroll_to_cursor(&self.scroll_handle, display.cursor_to_list_index());
+                None => {
+                    let mut display = ViewDisplayState::for_view(view);
+                    display.collapsed = self.backend.collapsed_groups(display.view_key());
+                    display
                 }
-            }
-            Ordering::Equal => {}
+            };
+            self.view_states.push(display);
+        }
+        if self.view_states.is_empty() {
+            self.view_states.push(ViewDisplayState::default());
         }
 
-        // Sync view config from backend (selection_mode, placeholder, view_id)
-        if let Some(view) = state.last() {
-            if let Some(display) = self.view_states.last_mut() {
-                display.selection_mode = view.selection;
-                display.view_id = view.id.clone();
-            }
-            if let Some(placeholder) = &view.placeholder {
-                self.search_input.update(cx, |input, cx| {
-                    input.set_placeholder(placeholder.clone(), cx);
-                });
+        // A view updated in place takes the selection the backend gave it
+        if let Some(ViewStackChange::Updated { index }) = change {
+            if let (Some(display), Some(view)) = (self.view_states.get_mut(index), state.get(index))
+            {
+                display.selected_ids = view.selected.iter().cloned().map(ItemId).collect();
             }
         }
 
+        if let Some(placeholder) = self.view_states.last().map(|d| d.placeholder.clone()) {
+            self.search_input.update(cx, |input, cx| {
+                input.set_placeholder(placeholder, cx);
+            });
+        }
+
+        let top_changed = self.view_states.last().and_then(|d| d.instance_id) != old_top;
+        if top_changed {
+            self.end_row_edit(cx);
+            crash::set_active_view(state.last().and_then(|view| view.id.as_deref()));
+            self.restore_top_view(state.last().and_then(|view| view.saved.clone()), cx);
+            self.announce_search_field();
+        }
+        self.restart_refresh(cx);
+
         cx.notify();
     }
 
+    /// Restore the query, cursor, scroll position, and selection of the view
+    /// that just came on top, then re-run its search.
+    fn restore_top_view(&mut self, saved: Option<SavedViewState>, cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last_mut() else {
+            return;
+        };
+
+        // A view the UI is still showing results for restores right away;
+        // the search below restores it again against fresh results
+        if let Some(saved) = saved {
+            if display.restore.is_none() {
+                display.query = saved.query.clone();
+                display.selected_ids = saved.selected.iter().cloned().map(ItemId).collect();
+                display.restore = Some(saved);
+            }
+        }
+        match display.restore_cursor() {
+            Some(offset) => set_scroll_offset(&self.scroll_handle, offset),
+            None => scroll_to_cursor(&self.scroll_handle, display.cursor_to_list_index()),
+        }
+
+        let query = display.query.clone();
+        self.set_input_text(query.clone(), cx);
+        self.trigger_search(query, cx);
+    }
+
+    /// Save the current view's state in the backend.
+    fn save_top_state(&self) {
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+        let Some(instance_id) = display.instance_id else {
+            return;
+        };
+        let state = display.saved_state(scroll_offset(&self.scroll_handle));
+        self.backend.save_view_state(instance_id, state);
+    }
+
     // -------------------------------------------------------------------------
     // Action Handlers
     // -------------------------------------------------------------------------
 
     fn on_cursor_up(&mut self, _: &CursorUp, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("cursor_up");
+
+        // The cursor stays on a row being edited
+        if self.row_edit.is_some() {
+            return;
+        }
+
+        // The open action menu or completion view takes the arrow keys
+        if let Some(menu) = self.open_menu_mut() {
+            menu.cursor_up();
+            self.announce_action();
+            cx.notify();
+            return;
+        }
+
         if let Some(display) = self.view_states.last_mut() {
             display.cursor_up();
-            scroll_to_cursor(&self.scroll_handle, display.cursor_to_list_index());
+            self.scroll_cursor_into_view(cx);
+            self.announce_cursor();
+            self.refresh_action_hints(cx);
             cx.notify();
         }
     }
 
     fn on_cursor_down(&mut self, _: &CursorDown, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("cursor_down");
+        if self.row_edit.is_some() {
+            return;
+        }
+
+        if let Some(menu) = self.open_menu_mut() {
+            menu.cursor_down();
+            self.announce_action();
+            cx.notify();
+            return;
+        }
+
         if let Some(display) = self.view_states.last_mut() {
             display.cursor_down();
-            scroll_to_cursor(&self.scroll_handle, display.cursor_to_list_index());
+            self.scroll_cursor_into_view(cx);
+            self.announce_cursor();
+            self.refresh_action_hints(cx);
             cx.notify();
         }
     }
 
-    fn on_open_action_menu(
+    fn on_page_up(&mut self, _: &PageUp, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("page_up");
+        self.move_cursor_by_page(false, cx);
+    }
+
+    fn on_page_down(&mut self, _: &PageDown, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("page_down");
+        self.move_cursor_by_page(true, cx);
+    }
+
+    /// Move the results cursor by the rows that fit in the list.
+    fn move_cursor_by_page(&mut self, down: bool, cx: &mut Context<Self>) {
+        let viewport = viewport_height(&self.scroll_handle);
+        let rows = page_rows(viewport, f32::from(cx.theme().item_height));
+        self.move_cursor(|display| display.cursor_by_page(rows, down), cx);
+    }
+
+    fn on_next_group(&mut self, _: &NextGroup, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("next_group");
+        self.move_cursor(ViewDisplayState::cursor_next_group, cx);
+    }
+
+    fn on_prev_group(&mut self, _: &PrevGroup, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("prev_group");
+        self.move_cursor(ViewDisplayState::cursor_prev_group, cx);
+    }
+
+    fn on_jump_to_group(
         &mut self,
-        _: &OpenActionMenu,
+        action: &JumpToGroup,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let number = action.number;
+        self.tape_action(&format!("jump_to_group({})", number));
+        self.move_cursor(|display| display.cursor_to_group(number), cx);
+    }
+
+    /// Run the default action of a numbered result (Cmd+1..9).
+    fn on_execute_item(
+        &mut self,
+        action: &ExecuteItem,
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if self.action_menu.is_some() {
+        self.tape_action(&format!("execute_item({})", action.number));
+        if self.open_menu().is_some() || self.row_edit.is_some() {
             return;
         }
-
-        let Some(display) = self.view_states.last() else {
+        let Some(item) = self
+            .view_states
+            .last_mut()
+            .and_then(|display| display.cursor_to_number(action.number))
+        else {
             return;
         };
-
-        let items: Vec<_> = if display.selected_ids.is_empty() {
-            display.cursor_item().cloned().into_iter().collect()
-        } else {
-            display.selected_items()
-        };
-
-        if !items.is_empty() {
-            self.fetch_actions(items, cx);
-        }
+        self.quick_select = false;
+        self.scroll_cursor_into_view(cx);
+        self.refresh_action_hints(cx);
+        cx.notify();
+        self.run_default_action(vec![item], cx);
     }
 
-    fn on_toggle_selection(
+    /// Show the quick-select numbers while Cmd alone is held.
+    fn on_modifiers_changed(
         &mut self,
-        _: &ToggleSelection,
+        event: &ModifiersChangedEvent,
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(display) = self.view_states.last_mut() {
-            display.toggle_selection_at_cursor();
+        let modifiers = event.modifiers;
+        let quick_select =
+            modifiers.platform && !modifiers.shift && !modifiers.alt && !modifiers.control;
+        if quick_select != self.quick_select {
+            self.quick_select = quick_select;
             cx.notify();
         }
     }
 
-    fn on_run_lua_handler(
-        &mut self,
-        action: &RunLuaHandler,
-        _window: &mut Window,
-        cx: &mut Context<Self>,
-    ) {
-        // Get items to pass to the handler
-        let Some(display) = self.view_states.last() else {
+    /// Move the results cursor between groups or by pages (not while a menu
+    /// is open or a row is edited).
+    fn move_cursor(&mut self, step: impl FnOnce(&mut ViewDisplayState), cx: &mut Context<Self>) {
+        if self.open_menu().is_some() || self.row_edit.is_some() {
+            return;
+        }
+        if let Some(display) = self.view_states.last_mut() {
+            step(display);
+            self.scroll_cursor_into_view(cx);
+            self.announce_cursor();
+            self.refresh_action_hints(cx);
+            cx.notify();
+        }
+    }
+
+    /// Scroll the cursor row into view, animated unless motion is reduced.
+    fn scroll_cursor_into_view(&mut self, cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last() else {
             return;
         };
+        let index = display.cursor_to_list_index();
+        let viewport = viewport_height(&self.scroll_handle);
+        let theme = cx.theme();
+        if viewport <= 0.0 || theme.reduce_motion {
+            self.stop_scroll();
+            scroll_to_cursor(&self.scroll_handle, index);
+            return;
+        }
 
-        let items: Vec<_> = if display.selected_ids.is_empty() {
-            display.cursor_item().cloned().into_iter().collect()
-        } else {
-            display.selected_items()
+        // Measured from where a running animation is headed
+        let from = -scroll_offset(&self.scroll_handle);
+        let scroll_top = self.scroll_target.unwrap_or(from);
+        let Some(target) =
+            scroll_into_view(display.entry_heights(theme), index, scroll_top, viewport)
+        else {
+            return;
+        };
+
+        self.scroll_target = Some(target);
+        self.scroll_task = Some(cx.spawn(
+            async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+                let start = Instant::now();
+                loop {
+                    cx.background_executor().timer(SCROLL_FRAME).await;
+                    let progress = start.elapsed().as_secs_f32() / SCROLL_DURATION.as_secs_f32();
+                    let updated = this.update(cx, |this, cx| {
+                        let top = ease_scroll(from, target, progress);
+                        set_scroll_offset(&this.scroll_handle, -top);
+                        if progress >= 1.0 {
+                            this.scroll_target = None;
+                        }
+                        cx.notify();
+                    });
+                    if updated.is_err() || progress >= 1.0 {
+                        break;
+                    }
+                }
+            },
+        ));
+    }
+
+    /// Stop a running scroll animation where it is.
+    fn stop_scroll(&mut self) {
+        self.scroll_task = None;
+        self.scroll_target = None;
+    }
+
+    /// The action menu, or the follow-ups of the completion view, when shown.
+    fn open_menu(&self) -> Option<&ActionMenuState> {
+        self.action_menu
+            .as_ref()
+            .or(self.completion.as_ref().map(|c| &c.follow_ups))
+    }
+
+    /// Mutable access to the menu returned by [`Self::open_menu`].
+    fn open_menu_mut(&mut self) -> Option<&mut ActionMenuState> {
+        match self.action_menu.as_mut() {
+            Some(menu) => Some(menu),
+            None => self.completion.as_mut().map(|c| &mut c.follow_ups),
+        }
+    }
+
+    /// Announce the highlighted action in the action menu.
+    fn announce_action(&mut self) {
+        let Some(menu) = self.open_menu() else {
+            return;
         };
+        if let Some(action) = menu.selected_action() {
+            let label = action_label(action, menu.cursor_index, menu.actions.len());
+            self.announcer.announce(label);
+        }
+    }
+
+    fn on_open_action_menu(
+        &mut self,
+        _: &OpenActionMenu,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.tape_action("open_action_menu");
+        if self.action_menu.is_some() || self.row_edit.is_some() {
+            return;
+        }
+
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+
+        let items = display.action_items();
+
+        if !items.is_empty() {
+            self.menu_anchor = None;
+            self.fetch_actions(items, cx);
+        }
+    }
+
+    fn on_toggle_selection(
+        &mut self,
+        _: &ToggleSelection,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.tape_action("toggle_selection");
+        if let Some(display) = self.view_states.last_mut() {
+            let before = display.selected_ids.clone();
+            display.toggle_selection_at_cursor();
+            if display.has_on_selection_changed
+                && display.selection_mode == SelectionMode::Multi
+                && display.selected_ids != before
+            {
+                self.notify_selection_changed(&before, cx);
+            }
+            self.announce_cursor();
+            cx.notify();
+        }
+    }
+
+    /// Send a selection change to the view's `on_selection_changed` callback
+    /// and adopt the selection the callback leaves.
+    fn notify_selection_changed(&mut self, before: &HashSet<ItemId>, cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last_mut() else {
+            return;
+        };
+        display.selection_generation += 1;
+        let generation = display.selection_generation;
+        let instance_id = display.instance_id;
+
+        let selection = sorted_ids(&display.selected_ids);
+        let added = sorted_ids(&display.selected_ids.difference(before).cloned().collect());
+        let removed = sorted_ids(&before.difference(&display.selected_ids).cloned().collect());
+
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = backend.selection_changed(selection, added, removed).await;
+            let _ = this.update(cx, |this, cx| {
+                this.apply_selection(instance_id, generation, result, cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Adopt the selection returned by `on_selection_changed`, unless the
+    /// selection changed again since.
+    fn apply_selection(
+        &mut self,
+        instance_id: Option<u64>,
+        generation: u64,
+        result: Result<Vec<String>, BackendError>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(display) = self
+            .view_states
+            .iter_mut()
+            .find(|d| d.instance_id == instance_id)
+        else {
+            return;
+        };
+        if display.selection_generation != generation {
+            return;
+        }
+
+        match result {
+            Ok(selection) => {
+                display.selected_ids = selection.into_iter().map(ItemId).collect();
+            }
+            Err(e) => {
+                tracing::error!("on_selection_changed failed: {}", e);
+            }
+        }
+
+        cx.notify();
+    }
+
+    fn on_run_lua_handler(
+        &mut self,
+        action: &RunLuaHandler,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Get items to pass to the handler
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+
+        let items = display.action_items();
 
         // Call the Lua handler via backend
         let handler_id = action.id.clone();
@@ -443,7 +1380,453 @@ impl LauncherPanel {
         .detach();
     }
 
+    fn on_undo(&mut self, _: &Undo, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("undo");
+        self.undo(cx);
+    }
+
+    /// Undo the newest operation, then refresh the results it may have changed.
+    fn undo(&mut self, cx: &mut Context<Self>) {
+        self.hide_undo_toast();
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = backend.undo().await;
+            let _ = this.update(cx, |this, cx| {
+                match result {
+                    Ok(Some(description)) => {
+                        let message = t_args("Undid: {action}", &[("action", &description)]);
+                        this.announcer.announce(message.clone());
+                        this.execution_feedback = Some(ExecutionFeedback::Complete { message });
+                        this.refresh_search(cx);
+                    }
+                    Ok(None) => {
+                        this.announcer.announce(t("Nothing to undo"));
+                    }
+                    Err(e) => {
+                        tracing::error!("Undo failed: {}", e);
+                        this.execution_feedback = Some(ExecutionFeedback::Failed {
+                            error: e.to_string(),
+                        });
+                    }
+                }
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    fn on_repeat_last_action(
+        &mut self,
+        _: &RepeatLastAction,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.tape_action("repeat_last_action");
+        if self.row_edit.is_some() {
+            return;
+        }
+        self.repeat_last_action(cx);
+    }
+
+    /// Run the latest action again on its original items.
+    ///
+    /// A destructive action runs on the second press.
+    fn repeat_last_action(&mut self, cx: &mut Context<Self>) {
+        let confirmed = self.confirm_repeat.take().map(|(id, _)| id);
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let outcome = backend.repeat_last_action(confirmed).await;
+            let _ = this.update(cx, |this, cx| match outcome {
+                Ok(RepeatOutcome::Ran(result)) => this.apply_action_result(Ok(result), cx),
+                Ok(RepeatOutcome::NeedsConfirmation(entry)) => {
+                    let title = entry.title();
+                    this.announcer.announce(t_args(
+                        "Press again to confirm {action}",
+                        &[("action", &title)],
+                    ));
+                    this.confirm_repeat = Some((entry.id, title));
+                    cx.notify();
+                }
+                Ok(RepeatOutcome::Nothing) => {
+                    this.announcer.announce(t("Nothing to repeat"));
+                }
+                Err(e) => this.apply_action_result(Err(e), cx),
+            });
+        })
+        .detach();
+    }
+
+    /// Open the basket view (clicking the badge; Cmd+Shift+B is a key binding).
+    fn open_basket(&mut self, cx: &mut Context<Self>) {
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = backend.run_key_handler(BASKET_OPEN_KEY, Vec::new()).await;
+            let _ = this.update(cx, |this, cx| {
+                this.apply_action_result(result, cx);
+            });
+        })
+        .detach();
+    }
+
+    fn on_toggle_pin(&mut self, _: &TogglePin, _window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_pin(cx);
+    }
+
+    fn on_detach_view(&mut self, _: &DetachView, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.detached {
+            return;
+        }
+        // The root view has no ID, and only registered views can be detached
+        if let Some(view_id) = self.view_states.last().and_then(|d| d.view_id.clone()) {
+            cx.emit(LauncherPanelEvent::Detach(view_id));
+        }
+    }
+
+    /// Pin or unpin the launcher, and tell the window to keep it visible.
+    fn toggle_pin(&mut self, cx: &mut Context<Self>) {
+        if self.detached {
+            return;
+        }
+        self.pinned = !self.pinned;
+        self.announcer.announce(if self.pinned {
+            t("Launcher pinned")
+        } else {
+            t("Launcher unpinned")
+        });
+        cx.emit(LauncherPanelEvent::PinChanged(self.pinned));
+        cx.notify();
+    }
+
+    // -------------------------------------------------------------------------
+    // Macros
+    // -------------------------------------------------------------------------
+
+    /// Append `step` to the macro being recorded, unless a macro is playing.
+    fn tape(&self, step: MacroStep) {
+        if self.macro_task.is_none() {
+            self.backend.macros().record(step);
+        }
+    }
+
+    /// Append the launcher action `name` to the macro being recorded.
+    fn tape_action(&self, name: &str) {
+        self.tape(MacroStep::Action(name.to_string()));
+    }
+
+    fn on_toggle_macro_recording(
+        &mut self,
+        _: &ToggleMacroRecording,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_macro_recording(cx);
+    }
+
+    /// Start recording a macro under the next free name, or stop and save it.
+    fn toggle_macro_recording(&mut self, cx: &mut Context<Self>) {
+        let macros = self.backend.macros();
+        if macros.recording().is_none() {
+            match macros.start_unnamed() {
+                Ok(name) => {
+                    // Played from the query typed so far
+                    if let Some(query) = self.view_states.last().map(|d| d.query.clone()) {
+                        if !query.is_empty() {
+                            macros.record(MacroStep::Query(query));
+                        }
+                    }
+                    self.announcer
+                        .announce(t_args("Recording {name}", &[("name", &name)]));
+                }
+                Err(e) => self.macro_failed(e.to_string(), cx),
+            }
+            cx.notify();
+            return;
+        }
+        match macros.stop() {
+            Ok(recorded) => {
+                let message = t_args("Saved macro {name}", &[("name", &recorded.name)]);
+                self.announcer.announce(message.clone());
+                self.execution_feedback = Some(ExecutionFeedback::Complete { message });
+            }
+            Err(e) => self.macro_failed(e.to_string(), cx),
+        }
+        cx.notify();
+    }
+
+    fn on_play_macro(&mut self, action: &PlayMacro, _window: &mut Window, cx: &mut Context<Self>) {
+        // Recorded as one step, not as the steps it plays
+        self.tape_action(&format!("play_macro({})", action.name));
+        self.play_macro(&action.name, cx);
+    }
+
+    /// Play macro `name`: set its queries and dispatch its actions in this
+    /// window, as if typed, waiting for the results of each step.
+    ///
+    /// Nothing runs if a step has an unknown action; playing stops when the
+    /// launcher hides.
+    fn play_macro(&mut self, name: &str, cx: &mut Context<Self>) {
+        if self.macro_task.is_some() {
+            self.macro_failed(t("A macro is already playing"), cx);
+            return;
+        }
+        let recorded = match self.backend.macros().load(name) {
+            Ok(recorded) => recorded,
+            Err(e) => {
+                self.macro_failed(e.to_string(), cx);
+                return;
+            }
+        };
+        // An unknown action fails the macro before any step runs
+        let unknown = recorded
+            .steps
+            .iter()
+            .enumerate()
+            .find_map(|(index, step)| match step {
+                MacroStep::Action(action) if action_from_name(action).is_none() => {
+                    Some((index + 1, action.clone()))
+                }
+                _ => None,
+            });
+        if let Some((step, action)) = unknown {
+            let error = t_args(
+                "Step {step} of macro {name} has an unknown action: {action}",
+                &[
+                    ("step", &step.to_string()),
+                    ("name", &recorded.name),
+                    ("action", &action),
+                ],
+            );
+            self.macro_failed(error, cx);
+            return;
+        }
+
+        let window = self.window_handle;
+        self.macro_task = Some(
+            cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+                for step in recorded.steps {
+                    let started = Instant::now();
+                    loop {
+                        cx.background_executor().timer(MACRO_STEP_DELAY).await;
+                        let loading = this.update(cx, |this, _cx| {
+                            this.view_states.last().is_some_and(|d| d.loading)
+                        });
+                        match loading {
+                            Ok(true) if started.elapsed() < MACRO_STEP_TIMEOUT => continue,
+                            Ok(_) => break,
+                            Err(_) => return,
+                        }
+                    }
+
+                    let played = match step {
+                        MacroStep::Query(query) => {
+                            this.update(cx, |this, cx| this.set_input_text(query, cx))
+                        }
+                        MacroStep::Shortcut(shortcut) => this.update(cx, |this, cx| {
+                            let items = this
+                                .view_states
+                                .last()
+                                .map(|d| d.action_items())
+                                .unwrap_or_default();
+                            if !items.is_empty() {
+                                this.run_action_shortcut(shortcut, items, cx);
+                            }
+                        }),
+                        MacroStep::Action(name) => {
+                            let Some(action) = action_from_name(&name) else {
+                                continue;
+                            };
+                            cx.update_window(window, |_, window, cx| {
+                                window.dispatch_action(action, cx);
+                            })
+                        }
+                    };
+                    if played.is_err() {
+                        return;
+                    }
+                }
+                let _ = this.update(cx, |this, cx| {
+                    this.macro_task = None;
+                    cx.notify();
+                });
+            }),
+        );
+    }
+
+    /// Show why recording or playing a macro failed.
+    fn macro_failed(&mut self, error: String, cx: &mut Context<Self>) {
+        tracing::warn!("Macro failed: {}", error);
+        self.announcer.announce(error.clone());
+        self.execution_feedback = Some(ExecutionFeedback::Failed { error });
+        self.play_feedback(SoundEvent::Error);
+        cx.notify();
+    }
+
+    // -------------------------------------------------------------------------
+    // App Commands
+    // -------------------------------------------------------------------------
+
+    /// Dispatch the launcher action `name` in this window (`ctx:run_command()`).
+    fn run_command(&mut self, name: &str, cx: &mut Context<Self>) {
+        let Some(action) = action_from_name(name) else {
+            self.command_failed(t_args("Unknown command: {action}", &[("action", name)]), cx);
+            return;
+        };
+        let window = self.window_handle;
+        cx.spawn(async move |_this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let _ = cx.update_window(window, |_, window, cx| {
+                window.dispatch_action(action, cx);
+            });
+        })
+        .detach();
+    }
+
+    fn on_reload_config(&mut self, _: &ReloadConfig, _window: &mut Window, cx: &mut Context<Self>) {
+        // Not taped: the recording ends with this process
+        match instance::relaunch() {
+            Ok(()) => {
+                tracing::info!("Restarting to reload the config");
+                cx.quit();
+            }
+            Err(e) => self.command_failed(
+                t_args("Cannot restart Lux: {error}", &[("error", &e.to_string())]),
+                cx,
+            ),
+        }
+    }
+
+    fn on_open_config(&mut self, _: &OpenConfig, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("open_config");
+        match lux_core::find_init_lua() {
+            Some(path) => self.open_file(&path, cx),
+            None => self.command_failed(t("No init.lua found"), cx),
+        }
+    }
+
+    fn on_open_logs(&mut self, _: &OpenLogs, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("open_logs");
+        match LogFile::default_path().filter(|path| path.exists()) {
+            Some(path) => self.open_file(&path, cx),
+            None => self.command_failed(t("Lux isn't writing a log file"), cx),
+        }
+    }
+
+    /// Open `path` in the configured editor and hide the launcher.
+    fn open_file(&mut self, path: &Path, cx: &mut Context<Self>) {
+        match self.backend.editor().open(path) {
+            Ok(()) => self.dismiss(cx),
+            Err(e) => self.command_failed(e.to_string(), cx),
+        }
+    }
+
+    fn on_toggle_theme(&mut self, _: &ToggleTheme, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("toggle_theme");
+        let appearance = if cx.theme().is_dark {
+            ThemeAppearance::Light
+        } else {
+            ThemeAppearance::Dark
+        };
+        self.backend.set_appearance(appearance);
+        refresh_theme(self.backend.as_ref(), cx);
+        cx.refresh_windows();
+    }
+
+    fn on_toggle_dev_mode(
+        &mut self,
+        _: &ToggleDevMode,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.tape_action("toggle_dev_mode");
+        let on = !cx
+            .try_global::<DevMode>()
+            .is_some_and(|dev_mode| dev_mode.0);
+        cx.set_global(DevMode(on));
+        self.announcer.announce(if on {
+            t("Developer mode on")
+        } else {
+            t("Developer mode off")
+        });
+        cx.refresh_windows();
+    }
+
+    fn on_clear_caches(&mut self, _: &ClearCaches, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("clear_caches");
+        self.backend.clear_caches();
+        self.action_hints_for = None;
+        let message = t("Cleared caches");
+        self.announcer.announce(message.clone());
+        self.execution_feedback = Some(ExecutionFeedback::Complete { message });
+        self.refresh_search(cx);
+        cx.notify();
+    }
+
+    /// Show why an app command failed.
+    fn command_failed(&mut self, error: String, cx: &mut Context<Self>) {
+        tracing::warn!("Command failed: {}", error);
+        self.announcer.announce(error.clone());
+        self.execution_feedback = Some(ExecutionFeedback::Failed { error });
+        self.play_feedback(SoundEvent::Error);
+        cx.notify();
+    }
+
+    /// Play the sound and haptic configured for `event` (off by default).
+    fn play_feedback(&self, event: SoundEvent) {
+        let settings = self.backend.sound_settings();
+        if let Err(e) = audio::play_event(&settings, event) {
+            tracing::debug!("Sound for {} failed: {}", event.name(), e);
+        }
+        if settings.haptics_for(event) {
+            platform::current().perform_haptic();
+        }
+    }
+
+    /// Show the undo toast if the last action recorded an undoable operation.
+    fn offer_undo(&mut self, cx: &mut Context<Self>) {
+        let Some(info) = self
+            .backend
+            .latest_undo()
+            .filter(|info| info.id > self.undo_seen)
+        else {
+            return;
+        };
+        self.undo_seen = info.id;
+        self.announcer.announce(info.description.clone());
+        self.undo_toast = Some(info);
+        self.undo_toast_task = Some(cx.spawn(
+            async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+                cx.background_executor().timer(UNDO_TOAST_DURATION).await;
+                let _ = this.update(cx, |this, cx| {
+                    this.hide_undo_toast();
+                    cx.notify();
+                });
+            },
+        ));
+    }
+
+    fn hide_undo_toast(&mut self) {
+        self.undo_toast = None;
+        self.undo_toast_task = None;
+    }
+
+    /// Offer the report of the crash the last run ended with.
+    pub fn show_crash_report(&mut self, report: String, cx: &mut Context<Self>) {
+        self.announcer
+            .announce(t("Lux quit unexpectedly last time"));
+        self.crash_report = Some(report);
+        cx.notify();
+    }
+
+    /// Copy the crash report to the clipboard and stop offering it.
+    fn copy_crash_report(&mut self, cx: &mut Context<Self>) {
+        if let Some(report) = self.crash_report.take() {
+            cx.write_to_clipboard(ClipboardItem::new_string(report));
+            self.announcer.announce(t("Copied crash report"));
+        }
+        cx.notify();
+    }
+
     fn on_dismiss(&mut self, _: &Dismiss, _window: &mut Window, cx: &mut Context<Self>) {
+        self.tape_action("dismiss");
         tracing::info!(
             "on_dismiss: view_states.len()={}, action_menu={}, input='{}'",
             self.view_states.len(),
@@ -451,8 +1834,15 @@ impl LauncherPanel {
             self.search_input.read(cx).text(cx)
         );
 
-        // 1. Close action menu if open
-        if self.action_menu.take().is_some() {
+        // 1. Cancel an inline edit, or close action menu or completion view if open
+        if self.row_edit.is_some() {
+            self.end_row_edit(cx);
+            self.announce_cursor();
+            cx.notify();
+            return;
+        }
+        if self.action_menu.take().is_some() || self.completion.take().is_some() {
+            self.announce_cursor();
             cx.notify();
             return;
         }
@@ -473,7 +1863,7 @@ impl LauncherPanel {
 
         // 4. Dismiss (hide) at root
         tracing::info!("on_dismiss: dismissing at root");
-        cx.emit(LauncherPanelEvent::Dismiss);
+        self.dismiss(cx);
     }
 
     // -------------------------------------------------------------------------
@@ -488,53 +1878,241 @@ impl LauncherPanel {
     ) {
         match event {
             SearchInputEvent::Changed(query) => {
+                // A restored view sets the query it already had
+                if self.view_states.last().is_some_and(|d| d.query != *query) {
+                    self.tape(MacroStep::Query(query.clone()));
+                }
+                self.completion = None;
                 self.trigger_search(query.clone(), cx);
             }
             SearchInputEvent::Submit => {
+                self.tape_action("submit");
                 self.execute_default_action(cx);
             }
             SearchInputEvent::Back => {
+                self.tape_action("pop");
                 self.pop_view(cx);
             }
+            SearchInputEvent::CollapseGroup => {
+                if let Some(title) = self.view_states.last().and_then(|d| d.group_to_collapse()) {
+                    self.set_group_collapsed(&title, true, cx);
+                }
+            }
+            SearchInputEvent::ExpandGroup => {
+                if let Some(title) = self.view_states.last().and_then(|d| d.group_to_expand()) {
+                    self.set_group_collapsed(&title, false, cx);
+                }
+            }
         }
     }
 
+    fn on_row_editor_event(
+        &mut self,
+        _row_editor: Entity<SearchInput>,
+        event: &SearchInputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        // Only Enter means anything to the row editor
+        if let SearchInputEvent::Submit = event {
+            self.submit_row_edit(cx);
+        }
+    }
+
+    /// Put the row of the edited item into inline edit mode.
+    fn start_row_edit(&mut self, edit: InlineEdit, cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last_mut() else {
+            return;
+        };
+        let Some(item) = display.cursor_to_item(&edit.item_id) else {
+            tracing::warn!("Cannot edit item '{}': it isn't listed", edit.item_id);
+            return;
+        };
+        scroll_to_cursor(&self.scroll_handle, display.cursor_to_list_index());
+
+        self.action_menu = None;
+        self.completion = None;
+        self.row_editor.update(cx, |editor, cx| {
+            editor.set_placeholder(edit.placeholder.clone().unwrap_or_default(), cx);
+            editor.set_text(edit.value.clone(), cx);
+        });
+        self.focus_request = Some(self.row_editor.read(cx).focus_handle(cx));
+        self.announcer
+            .announce(t_args("Editing {title}", &[("title", &item.title)]));
+        self.row_edit = Some(RowEdit { edit, item });
+        cx.notify();
+    }
+
+    /// Pass the edited text to the Lua callback. The row stays in edit mode
+    /// if the callback fails, so the text can be fixed.
+    fn submit_row_edit(&mut self, cx: &mut Context<Self>) {
+        let Some(row_edit) = &self.row_edit else {
+            return;
+        };
+        let handler_key = row_edit.edit.handler_key.clone();
+        let items = vec![row_edit.item.clone()];
+        let text = self.row_editor.read(cx).text(cx).to_string();
+
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = backend.submit_edit(handler_key, text, items).await;
+            let _ = this.update(cx, |this, cx| {
+                if !matches!(result, Ok(ActionResult::Fail { .. }) | Err(_)) {
+                    this.end_row_edit(cx);
+                }
+                this.apply_action_result(result, cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Leave inline edit mode, giving focus back to the search input.
+    fn end_row_edit(&mut self, cx: &mut Context<Self>) {
+        if self.row_edit.take().is_some() {
+            self.focus_request = Some(self.search_input.read(cx).focus_handle(cx));
+            cx.notify();
+        }
+    }
+
+    /// Collapse or expand a group of the current view, and remember it.
+    fn set_group_collapsed(&mut self, title: &str, collapsed: bool, cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last_mut() else {
+            return;
+        };
+        if !display.set_group_collapsed(title, collapsed) {
+            return;
+        }
+        self.backend
+            .set_group_collapsed(display.view_key(), title, collapsed);
+        scroll_to_cursor(&self.scroll_handle, display.cursor_to_list_index());
+        self.announce_cursor();
+        self.refresh_action_hints(cx);
+        cx.notify();
+    }
+
     // -------------------------------------------------------------------------
     // Backend Integration
     // -------------------------------------------------------------------------
 
     fn trigger_search(&mut self, query: String, cx: &mut Context<Self>) {
+        self.start_search(query, true, false, cx);
+    }
+
+    /// Run a search, announcing the result count unless `announce` is false.
+    ///
+    /// A `refresh` runs behind the searches and actions the user is waiting on.
+    fn start_search(
+        &mut self,
+        query: String,
+        announce: bool,
+        refresh: bool,
+        cx: &mut Context<Self>,
+    ) {
         let Some(display) = self.view_states.last_mut() else {
             return;
         };
 
         display.generation += 1;
         let gen = display.generation;
+        let instance_id = display.instance_id;
         display.query = query.clone();
         display.loading = true;
+        display.last_search = Some(Instant::now());
         cx.notify();
 
         let backend = self.backend.clone();
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-            let result = backend.search(query).await;
+            let result = if refresh {
+                backend.refresh(query).await
+            } else {
+                backend.search(query).await
+            };
             let _ = this.update(cx, |this, cx| {
-                this.apply_search_results(gen, result, cx);
+                this.apply_search_results(instance_id, gen, result, announce, cx);
             });
         })
         .detach();
     }
 
+    /// Re-run the current view's search every `refresh_ms` while the
+    /// launcher is shown. Replaces the loop of the previous view.
+    fn restart_refresh(&mut self, cx: &mut Context<Self>) {
+        self.refresh_task = None;
+        let Some(interval) = self.view_states.last().and_then(|d| d.refresh) else {
+            return;
+        };
+        let depth = self.view_states.len();
+
+        self.refresh_task = Some(cx.spawn(
+            async move |this: WeakEntity<Self>, cx: &mut AsyncApp| loop {
+                cx.background_executor().timer(interval).await;
+                let updated = this.update(cx, |this, cx| {
+                    this.refresh_view(depth, interval, cx);
+                });
+                if updated.is_err() {
+                    break;
+                }
+            },
+        ));
+    }
+
+    /// One refresh tick for the view at `depth`.
+    ///
+    /// Coalesces with user-initiated searches: skips the tick while a search
+    /// is running or when one started less than `interval` ago.
+    fn refresh_view(&mut self, depth: usize, interval: Duration, cx: &mut Context<Self>) {
+        if self.view_states.len() != depth {
+            return;
+        }
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+        if display.loading || display.last_search.is_some_and(|t| t.elapsed() < interval) {
+            return;
+        }
+        let query = display.query.clone();
+        self.start_search(query, false, true, cx);
+    }
+
+    /// Re-run the search of the current view if it's one of `view_ids`.
+    fn refresh_views(&mut self, view_ids: &[String], cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+        let shown = display
+            .view_id
+            .as_ref()
+            .is_some_and(|id| view_ids.contains(id));
+        if shown {
+            let query = display.query.clone();
+            self.start_search(query, false, true, cx);
+        }
+    }
+
+    /// Re-run the search for the current query (root view only, where the
+    /// background sources are shown).
+    fn refresh_search(&mut self, cx: &mut Context<Self>) {
+        if self.view_states.len() != 1 {
+            return;
+        }
+        if let Some(display) = self.view_states.last() {
+            let query = display.query.clone();
+            self.start_search(query, true, true, cx);
+        }
+    }
+
     fn apply_search_results(
         &mut self,
+        instance_id: Option<u64>,
         generation: u64,
         result: Result<Vec<Group>, BackendError>,
+        announce: bool,
         cx: &mut Context<Self>,
     ) {
         let Some(view_display) = self.view_states.last_mut() else {
             return;
         };
 
-        if view_display.generation != generation {
+        if view_display.instance_id != instance_id || view_display.generation != generation {
             return;
         }
 
@@ -542,7 +2120,32 @@ impl LauncherPanel {
 
         match result {
             Ok(groups) => {
-                view_display.set_groups(groups);
+                let scroll_top = -scroll_offset(&self.scroll_handle);
+                let new_query = view_display.set_results(groups);
+                if let Some(offset) = view_display.restore_cursor() {
+                    view_display.restore = None;
+                    self.scroll_task = None;
+                    self.scroll_target = None;
+                    set_scroll_offset(&self.scroll_handle, offset);
+                } else if new_query {
+                    self.scroll_task = None;
+                    self.scroll_target = None;
+                    set_scroll_offset(&self.scroll_handle, 0.0);
+                } else {
+                    // A refresh keeps the list where it was, unless it got shorter
+                    let viewport = viewport_height(&self.scroll_handle);
+                    let max = max_scroll(view_display.entry_heights(cx.theme()), viewport);
+                    set_scroll_offset(&self.scroll_handle, -scroll_top.min(max));
+                }
+                if announce {
+                    let count = results_label(view_display.item_ids.len());
+                    let label = match view_display.cursor_label() {
+                        Some(cursor) => format!("{}, {}", count, cursor),
+                        None => count,
+                    };
+                    self.announcer.announce(label);
+                }
+                self.refresh_action_hints(cx);
             }
             Err(e) => {
                 tracing::error!("Search failed: {}", e);
@@ -552,6 +2155,110 @@ impl LauncherPanel {
         cx.notify();
     }
 
+    /// Fetch the actions of the cursor item, so their shortcuts run without
+    /// opening the action menu and show in the footer.
+    fn refresh_action_hints(&mut self, cx: &mut Context<Self>) {
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+        let item = display.cursor_item().cloned();
+        let hints_for = item
+            .as_ref()
+            .map(|item| (display.instance_id, item.item_id()));
+        if hints_for == self.action_hints_for {
+            return;
+        }
+        self.action_hints_for = hints_for.clone();
+        self.action_hints.clear();
+        self.confirm_shortcut = None;
+        let Some(item) = item else {
+            return;
+        };
+
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = backend.get_actions(vec![item]).await;
+            let _ = this.update(cx, |this, cx| {
+                if this.action_hints_for != hints_for {
+                    return;
+                }
+                match result {
+                    Ok(action_infos) => {
+                        this.action_hints = action_infos
+                            .into_iter()
+                            .filter(|info| info.shortcut.is_some())
+                            .map(action_menu_item)
+                            .collect();
+                        cx.notify();
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to get action shortcuts: {}", e);
+                    }
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Run a cursor item action by its shortcut, without the action menu.
+    fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        // Keys go to the row editor while a row is edited
+        if self.row_edit.is_some() {
+            return;
+        }
+        let Some(shortcut) = shortcut_from_keystroke(&event.keystroke) else {
+            return;
+        };
+        let Some(destructive) = self
+            .action_hints
+            .iter()
+            .find(|action| action.shortcut.as_ref() == Some(&shortcut))
+            .map(|action| action.destructive.then(|| confirm_label(action)))
+        else {
+            self.confirm_shortcut = None;
+            return;
+        };
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+        let items = display.action_items();
+        if items.is_empty() {
+            return;
+        }
+
+        cx.stop_propagation();
+
+        // Destructive actions run on the second press
+        if let Some(label) =
+            destructive.filter(|_| self.confirm_shortcut.as_ref() != Some(&shortcut))
+        {
+            self.announcer.announce(label);
+            self.confirm_shortcut = Some(shortcut);
+            cx.notify();
+            return;
+        }
+
+        self.confirm_shortcut = None;
+        self.tape(MacroStep::Shortcut(shortcut.clone()));
+        self.run_action_shortcut(shortcut, items, cx);
+    }
+
+    /// Run the action of `items` that has `shortcut`, if there is one.
+    fn run_action_shortcut(&mut self, shortcut: String, items: Vec<Item>, cx: &mut Context<Self>) {
+        self.action_menu = None;
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let result = backend.run_action_shortcut(shortcut, items).await;
+            let _ = this.update(cx, |this, cx| {
+                if let Some(result) = result.transpose() {
+                    this.apply_action_result(result, cx);
+                }
+            });
+        })
+        .detach();
+        cx.notify();
+    }
+
     fn fetch_actions(&mut self, items: Vec<Item>, cx: &mut Context<Self>) {
         let backend = self.backend.clone();
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
@@ -574,18 +2281,13 @@ impl LauncherPanel {
                     return;
                 }
 
-                let actions: Vec<ActionMenuItem> = action_infos
-                    .into_iter()
-                    .map(|info| ActionMenuItem {
-                        view_id: info.view_id,
-                        action_id: info.id,
-                        handler_key: info.handler_key,
-                        title: info.title,
-                        icon: info.icon,
-                    })
-                    .collect();
+                let actions: Vec<ActionMenuItem> =
+                    action_infos.into_iter().map(action_menu_item).collect();
 
                 self.action_menu = Some(ActionMenuState::new(actions));
+                self.completion = None;
+                self.announcer.announce(t("Actions"));
+                self.announce_action();
             }
             Err(e) => {
                 tracing::error!("Failed to get actions: {}", e);
@@ -595,63 +2297,99 @@ impl LauncherPanel {
         cx.notify();
     }
 
+    /// Open the action menu with its first (destructive) action armed.
+    fn confirm_first_action(
+        &mut self,
+        action_infos: Vec<lux_plugin_api::ActionInfo>,
+        cx: &mut Context<Self>,
+    ) {
+        let mut menu =
+            ActionMenuState::new(action_infos.into_iter().map(action_menu_item).collect());
+        menu.confirm_selected();
+        self.menu_anchor = None;
+        self.action_menu = Some(menu);
+        self.announce_confirmation();
+        cx.notify();
+    }
+
+    /// Announce that the highlighted action awaits confirmation.
+    fn announce_confirmation(&mut self) {
+        if let Some(action) = self.action_menu.as_ref().and_then(|m| m.selected_action()) {
+            self.announcer.announce(confirm_label(action));
+        }
+    }
+
     fn execute_default_action(&mut self, cx: &mut Context<Self>) {
         let Some(display) = self.view_states.last() else {
             return;
         };
 
-        let items: Vec<_> = if display.selected_ids.is_empty() {
-            display.cursor_item().cloned().into_iter().collect()
-        } else {
-            display.selected_items()
-        };
+        // The completion view runs the highlighted follow-up
+        if let Some(completion) = self.completion.take() {
+            if let Some(action) = completion.follow_ups.selected_action() {
+                self.run_menu_action(action.clone(), display.action_items(), cx);
+            }
+            cx.notify();
+            return;
+        }
+
+        let items = display.action_items();
 
         if items.is_empty() {
             return;
         }
 
+        // Destructive actions in the menu run on the second confirmation
+        if let Some(menu) = self.action_menu.as_mut() {
+            if !menu.confirm_selected() {
+                self.announce_confirmation();
+                cx.notify();
+                return;
+            }
+        }
+
         // If action menu is open, execute the selected action from it
         if let Some(action_menu) = self.action_menu.take() {
             if let Some(action) = action_menu.selected_action() {
-                let action = action.clone();
-                let backend = self.backend.clone();
-                cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-                    // Use handler_key if available, otherwise fall back to action_id
-                    let action_id = action
-                        .handler_key
-                        .unwrap_or_else(|| action.action_id.clone());
-                    let result = backend
-                        .execute_action(action.view_id, action_id, items)
-                        .await;
-                    let _ = this.update(cx, |this, cx| {
-                        this.apply_action_result(result, cx);
-                    });
-                })
-                .detach();
+                self.run_menu_action(action.clone(), items, cx);
             }
             cx.notify();
             return;
         }
 
-        // No action menu - fetch actions fresh and execute the first one
+        self.run_default_action(items, cx);
+    }
+
+    /// List the actions of `items` and run the first one in a single
+    /// backend call.
+    fn run_default_action(&self, items: Vec<Item>, cx: &mut Context<Self>) {
         let backend = self.backend.clone();
         cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
-            let actions = backend.get_actions(items.clone()).await;
-            if let Ok(action_infos) = actions {
-                if let Some(first) = action_infos.first() {
-                    // Use handler_key if available, otherwise fall back to id
-                    let action_id = first
-                        .handler_key
-                        .clone()
-                        .unwrap_or_else(|| first.id.clone());
-                    let result = backend
-                        .execute_action(first.view_id.clone(), action_id, items)
-                        .await;
-                    let _ = this.update(cx, |this, cx| {
-                        this.apply_action_result(result, cx);
-                    });
+            let outcome = backend.execute_default_action(items).await;
+            let _ = this.update(cx, |this, cx| match outcome {
+                Ok(DefaultActionOutcome::Ran(result)) => this.apply_action_result(Ok(result), cx),
+                // A destructive first action opens the menu to ask for confirmation
+                Ok(DefaultActionOutcome::NeedsConfirmation(action_infos)) => {
+                    this.confirm_first_action(action_infos, cx);
                 }
-            }
+                Ok(DefaultActionOutcome::NoAction) => {}
+                Err(e) => this.apply_action_result(Err(e), cx),
+            });
+        })
+        .detach();
+    }
+
+    /// Run an action chosen from the action menu or the completion view.
+    fn run_menu_action(&self, action: ActionMenuItem, items: Vec<Item>, cx: &mut Context<Self>) {
+        let backend = self.backend.clone();
+        cx.spawn(async move |this: WeakEntity<Self>, cx: &mut AsyncApp| {
+            let action_id = action.handler_key().to_string();
+            let result = backend
+                .execute_action(action.view_id, action_id, items)
+                .await;
+            let _ = this.update(cx, |this, cx| {
+                this.apply_action_result(result, cx);
+            });
         })
         .detach();
     }
@@ -661,9 +2399,21 @@ impl LauncherPanel {
         result: Result<ActionResult, BackendError>,
         cx: &mut Context<Self>,
     ) {
+        self.confirm_repeat = None;
+        self.offer_undo(cx);
+
         match result {
             Ok(ActionResult::Dismiss) => {
-                cx.emit(LauncherPanelEvent::Dismiss);
+                self.play_feedback(SoundEvent::Execute);
+                if self.pinned {
+                    // Stay open, with results that reflect what the action did
+                    if let Some(display) = self.view_states.last() {
+                        let query = display.query.clone();
+                        self.trigger_search(query, cx);
+                    }
+                } else {
+                    self.dismiss(cx);
+                }
             }
             Ok(ActionResult::Pop) => {
                 // State change will come via subscription
@@ -688,23 +2438,62 @@ impl LauncherPanel {
                     cx.notify();
                 }
             }
-            Ok(ActionResult::Complete { message, .. }) => {
+            Ok(ActionResult::Complete { message, actions }) => {
+                // Follow-ups are offered in a transient completion view
+                if !actions.is_empty() {
+                    let view_id = self
+                        .view_states
+                        .last()
+                        .and_then(|display| display.view_id.clone())
+                        .unwrap_or_default();
+                    self.action_menu = None;
+                    self.completion =
+                        Some(CompletionState::new(message.clone(), &view_id, actions));
+                    self.announcer.announce(message.clone());
+                    self.announce_action();
+                }
                 self.execution_feedback = Some(ExecutionFeedback::Complete { message });
+                self.play_feedback(SoundEvent::Execute);
                 cx.notify();
             }
             Ok(ActionResult::Progress { message }) => {
                 self.execution_feedback = Some(ExecutionFeedback::Progress { message });
                 cx.notify();
             }
-            Ok(ActionResult::Fail { error }) => {
-                self.execution_feedback = Some(ExecutionFeedback::Failed { error });
+            Ok(ActionResult::Fail { error, retry }) => {
+                // Shown in place of the results, with "Retry" if the handler
+                // allows it; a failed inline edit keeps its row editor instead
+                if self.row_edit.is_none() {
+                    let view_id = self
+                        .view_states
+                        .last()
+                        .and_then(|display| display.view_id.clone())
+                        .unwrap_or_default();
+                    self.action_menu = None;
+                    self.completion = Some(CompletionState::failed(&error, &view_id, retry));
+                }
+                self.announcer.announce(error.message.clone());
+                self.execution_feedback = Some(ExecutionFeedback::Failed {
+                    error: error.to_string(),
+                });
+                self.play_feedback(SoundEvent::Error);
                 cx.notify();
             }
+            Ok(ActionResult::Edit(edit)) => {
+                self.start_row_edit(edit, cx);
+            }
+            Ok(ActionResult::PlayMacro { name }) => {
+                self.play_macro(&name, cx);
+            }
+            Ok(ActionResult::RunCommand { action }) => {
+                self.run_command(&action, cx);
+            }
             Err(e) => {
                 tracing::error!("Action failed: {}", e);
                 self.execution_feedback = Some(ExecutionFeedback::Failed {
                     error: e.to_string(),
                 });
+                self.play_feedback(SoundEvent::Error);
                 cx.notify();
             }
         }
@@ -725,14 +2514,79 @@ impl LauncherPanel {
     // -------------------------------------------------------------------------
 
     fn on_item_click(&mut self, index: usize, cx: &mut Context<Self>) {
+        // A click on another row cancels the inline edit
+        let editing = self.view_states.last().map(|display| display.cursor_index);
+        if self.row_edit.is_some() && editing != Some(index) {
+            self.end_row_edit(cx);
+        }
         if let Some(display) = self.view_states.last_mut() {
             display.cursor_index = index;
+            self.refresh_action_hints(cx);
             cx.notify();
         }
     }
 
-    fn on_item_double_click(&mut self, _index: usize, cx: &mut Context<Self>) {
-        self.execute_default_action(cx);
+    fn on_item_double_click(&mut self, _index: usize, cx: &mut Context<Self>) {
+        if self.row_edit.is_some() {
+            return;
+        }
+        self.execute_default_action(cx);
+    }
+
+    /// Open the actions of a right-clicked row at the pointer.
+    fn on_item_right_click(
+        &mut self,
+        index: usize,
+        position: Point<Pixels>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.row_edit.is_some() || self.completion.is_some() {
+            return;
+        }
+        self.on_item_click(index, cx);
+        let Some(display) = self.view_states.last() else {
+            return;
+        };
+        let items = display.action_items();
+        if !items.is_empty() {
+            self.action_menu = None;
+            self.menu_anchor = Some(position);
+            self.fetch_actions(items, cx);
+        }
+    }
+
+    /// Move the cursor to the row under the pointer (`lux.ui.mouse{ hover = true }`).
+    fn on_item_hover(&mut self, index: usize, cx: &mut Context<Self>) {
+        if self.open_menu().is_some() || self.row_edit.is_some() {
+            return;
+        }
+        if let Some(display) = self.view_states.last_mut() {
+            if display.cursor_index != index {
+                display.cursor_index = index;
+                self.refresh_action_hints(cx);
+                cx.notify();
+            }
+        }
+    }
+
+    /// Run a clicked action of the open menu (a destructive one on the second click).
+    fn on_menu_click(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(menu) = self.open_menu_mut() {
+            if menu.cursor_index != index {
+                menu.cursor_index = index;
+                menu.confirming = false;
+            }
+        }
+        self.execute_default_action(cx);
+    }
+
+    /// Close the action menu floating at the pointer.
+    fn close_context_menu(&mut self, cx: &mut Context<Self>) {
+        if self.menu_anchor.take().is_some() {
+            self.action_menu = None;
+            self.refresh_action_hints(cx);
+            cx.notify();
+        }
     }
 
     // -------------------------------------------------------------------------
@@ -758,11 +2612,63 @@ impl LauncherPanel {
             .into_any_element()
     }
 
+    /// Render a results group header. A collapsible one shows a disclosure
+    /// arrow (and its item count while collapsed) and toggles on click.
+    fn render_results_header(
+        entry: &ListEntry,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        let ListEntry::GroupHeader {
+            title,
+            collapsible,
+            collapsed,
+            item_count,
+        } = entry
+        else {
+            return div().into_any_element();
+        };
+        if !collapsible {
+            return Self::render_group_header(title, theme);
+        }
+
+        let muted = |text: String| div().text_color(theme.text_muted).text_xs().child(text);
+        let toggle_title = title.clone();
+        let expand = *collapsed;
+        div()
+            .id(ElementId::Name(SharedString::from(format!(
+                "group-{}",
+                title
+            ))))
+            .w_full()
+            .h(theme.group_header_height)
+            .px_3()
+            .flex()
+            .items_end()
+            .gap_1()
+            .pb_1()
+            .cursor_pointer()
+            .child(muted(if expand { "▸" } else { "▾" }.to_string()))
+            .child(muted(title.to_uppercase()).font_weight(gpui::FontWeight::SEMIBOLD))
+            .when(expand, |this| this.child(muted(item_count.to_string())))
+            .on_click(cx.listener(
+                move |this: &mut Self, _event: &gpui::ClickEvent, _window, cx| {
+                    this.set_group_collapsed(&toggle_title, !expand, cx);
+                },
+            ))
+            .into_any_element()
+    }
+
     /// Render a result item row (without click handler - that's added by caller).
+    /// Render a result row, with `editor` in place of the title while the
+    /// row is edited inline.
     fn render_result_item(
         item: &Item,
         is_cursor: bool,
         is_selected: bool,
+        badge: Option<usize>,
+        editor: Option<gpui::AnyElement>,
+        dev_mode: bool,
         theme: &crate::theme::Theme,
     ) -> gpui::Stateful<gpui::Div> {
         let bg_color = if is_cursor {
@@ -774,8 +2680,27 @@ impl LauncherPanel {
         };
 
         let item_id = item.id.clone();
-        let title = item.title.clone();
-        let subtitle = item.subtitle.clone();
+        let display = item.display.unwrap_or_default();
+        let title_lines = display.title_lines(item.subtitle.is_some());
+        let subtitle_below = display.subtitle == SubtitlePosition::Below;
+        let middle = display.truncate == Truncate::Middle;
+        let title = if middle && title_lines == 1 {
+            truncate_middle(&item.title, MIDDLE_TRUNCATE_CHARS).into_owned()
+        } else {
+            item.title.clone()
+        };
+        let subtitle = item
+            .subtitle
+            .as_deref()
+            .map(|sub| match (middle, subtitle_below) {
+                (true, true) => truncate_middle(sub, MIDDLE_TRUNCATE_CHARS).into_owned(),
+                (true, false) => truncate_middle(sub, MIDDLE_TRUNCATE_TRAILING_CHARS).into_owned(),
+                (false, _) => sub.to_string(),
+            });
+        let mut accessories = item.accessories.clone();
+        if dev_mode {
+            accessories.push(item.id.clone());
+        }
         let icon = item.icon.clone();
 
         let mut row = div()
@@ -784,7 +2709,7 @@ impl LauncherPanel {
                 item_id
             ))))
             .w_full()
-            .h(theme.item_height)
+            .h(theme.row_height(item.row_lines()))
             .px_3()
             .flex()
             .items_center()
@@ -793,15 +2718,31 @@ impl LauncherPanel {
             .rounded(theme.radius)
             .cursor_pointer()
             // Add subtle accent border when cursor is on this item
+            // (thicker with Increase Contrast)
             .when(is_cursor, |this| {
-                this.border_1().border_color(theme.accent.alpha(0.5))
+                let this = if theme.high_contrast {
+                    this.border_2()
+                } else {
+                    this.border_1()
+                };
+                this.border_color(theme.cursor_border)
             })
             .hover(|style| style.bg(theme.surface_hover));
 
         // Icon (always rendered - placeholder if not provided)
         let icon_size = theme.icon_size;
         let icon_el = if let Some(icon_str) = icon {
-            if icon_str.starts_with('/') {
+            if let Some(color) = swatch_color(&icon_str) {
+                // Hex color icons render as a swatch
+                div()
+                    .w(icon_size)
+                    .h(icon_size)
+                    .rounded(px(4.0))
+                    .bg(gpui::Hsla::from(color))
+                    .border_1()
+                    .border_color(theme.border)
+                    .into_any_element()
+            } else if icon_str.starts_with('/') {
                 use std::path::PathBuf;
                 img(PathBuf::from(icon_str))
                     .size(icon_size)
@@ -827,7 +2768,44 @@ impl LauncherPanel {
         };
         row = row.child(icon_el);
 
-        // Title and subtitle on same line
+        // Title, wrapping onto a second line if the item asks for it
+        let title_el = match editor {
+            Some(editor) => div()
+                .flex_1()
+                .px_1()
+                .rounded(px(4.0))
+                .border_1()
+                .border_color(theme.cursor_border)
+                .bg(theme.background)
+                .child(editor),
+            None => div()
+                .text_color(theme.text)
+                .text_ellipsis()
+                .overflow_hidden()
+                .when(title_lines > 1, |this| this.line_clamp(title_lines))
+                .child(title),
+        };
+        let subtitle_el = |sub: String| {
+            div()
+                .text_color(theme.text_muted)
+                .text_sm()
+                .text_ellipsis()
+                .child(sub)
+        };
+
+        // Title and subtitle on same line, unless the subtitle goes below
+        let (subtitle_trailing, leading) = match subtitle {
+            Some(sub) if subtitle_below => (
+                None,
+                div()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
+                    .child(title_el)
+                    .child(subtitle_el(sub).overflow_hidden()),
+            ),
+            sub => (sub, title_el),
+        };
         let mut content = div()
             .flex_1()
             .flex()
@@ -835,26 +2813,415 @@ impl LauncherPanel {
             .justify_between()
             .gap_2()
             .overflow_hidden()
+            .child(leading);
+
+        // Subtitle and accessories share the trailing edge
+        if subtitle_trailing.is_some() || !accessories.is_empty() {
+            let mut trailing = div().flex().items_center().gap_2().flex_shrink_0();
+            if let Some(sub) = subtitle_trailing {
+                trailing = trailing.child(subtitle_el(sub));
+            }
+            for accessory in accessories {
+                trailing = trailing.child(
+                    div()
+                        .px_1p5()
+                        .rounded(px(4.0))
+                        .bg(theme.surface_hover)
+                        .text_color(theme.text_muted)
+                        .text_xs()
+                        .child(accessory),
+                );
+            }
+            content = content.child(trailing);
+        }
+
+        // Quick-select number while Cmd is held
+        row.child(content).when_some(badge, |this, number| {
+            this.child(Self::render_shortcut(&format!("cmd+{}", number), theme))
+        })
+    }
+
+    /// Render the action menu (highlighted row follows the menu cursor).
+    fn render_action_menu(
+        menu: &ActionMenuState,
+        header: &str,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        let mut list = div()
+            .id("action-menu")
+            .w_full()
+            .p_2()
+            .border_t_1()
+            .border_color(theme.border)
+            .flex()
+            .flex_col()
+            .child(Self::render_group_header(header, theme));
+
+        for (ix, action) in menu.actions.iter().enumerate() {
+            let is_cursor = ix == menu.cursor_index;
+            let confirming = is_cursor && menu.confirming;
+
+            // Separator and header before each section
+            if menu.starts_section(ix) {
+                list = list.child(div().w_full().h(px(1.0)).my_1().bg(theme.border));
+            }
+            if ix == 0 || menu.starts_section(ix) {
+                if let Some(section) = action.section.as_deref().filter(|s| !s.is_empty()) {
+                    list = list.child(Self::render_group_header(section, theme));
+                }
+            }
+
+            list = list.child(
+                div()
+                    .id(ElementId::Name(SharedString::from(format!(
+                        "action-{}",
+                        ix
+                    ))))
+                    .w_full()
+                    .h(theme.item_height)
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .gap_3()
+                    .rounded(theme.radius)
+                    .when(is_cursor, |this| {
+                        let this = if theme.high_contrast {
+                            this.border_2()
+                        } else {
+                            this.border_1()
+                        };
+                        this.bg(theme.cursor).border_color(theme.cursor_border)
+                    })
+                    .when_some(action.icon.clone(), |this, icon| this.child(icon))
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(if action.destructive {
+                                theme.error
+                            } else {
+                                theme.text
+                            })
+                            .text_ellipsis()
+                            .overflow_hidden()
+                            .child(action.title.clone()),
+                    )
+                    .when(confirming, |this| {
+                        this.child(Self::render_confirm_hint(theme))
+                    })
+                    .when_some(
+                        action.shortcut.as_deref().filter(|_| !confirming),
+                        |this, shortcut| this.child(Self::render_shortcut(shortcut, theme)),
+                    )
+                    .on_click(
+                        cx.listener(move |this, _event, _window, cx| this.on_menu_click(ix, cx)),
+                    ),
+            );
+        }
+
+        list.into_any_element()
+    }
+
+    /// Render the completion view: the message (and detail of a failure),
+    /// then the follow-up actions.
+    fn render_completion(
+        completion: &CompletionState,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        let (mark, color) = if completion.failed {
+            ("✕", theme.error)
+        } else {
+            ("✓", theme.success)
+        };
+        div()
+            .id("completion-view")
+            .w_full()
+            .h_full()
+            .flex()
+            .flex_col()
+            .child(
+                div()
+                    .w_full()
+                    .px_3()
+                    .py_2()
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .text_color(color)
+                    .child(mark)
+                    .child(completion.message.clone()),
+            )
+            .when_some(completion.detail.clone(), |this, detail| {
+                this.child(
+                    div()
+                        .w_full()
+                        .px_3()
+                        .pb_2()
+                        .text_sm()
+                        .text_color(theme.text_muted)
+                        .child(detail),
+                )
+            })
+            .when(!completion.follow_ups.actions.is_empty(), |this| {
+                this.child(Self::render_action_menu(
+                    &completion.follow_ups,
+                    &t("Follow-up Actions"),
+                    theme,
+                    cx,
+                ))
+            })
+            .into_any_element()
+    }
+
+    /// Render a shortcut hint like `⌘O`.
+    fn render_shortcut(shortcut: &str, theme: &crate::theme::Theme) -> gpui::AnyElement {
+        div()
+            .px_1p5()
+            .rounded(px(4.0))
+            .bg(theme.surface_hover)
+            .text_color(theme.text_muted)
+            .text_xs()
+            .child(shortcut_label(shortcut))
+            .into_any_element()
+    }
+
+    /// Render the prompt shown next to a destructive action awaiting confirmation.
+    fn render_confirm_hint(theme: &crate::theme::Theme) -> gpui::AnyElement {
+        div()
+            .text_color(theme.error)
+            .text_xs()
+            .child(t("Press again to confirm"))
+            .into_any_element()
+    }
+
+    /// Render the footer asking to press `repeat_last_action` again.
+    fn render_repeat_confirm(title: &str, theme: &crate::theme::Theme) -> gpui::AnyElement {
+        div()
+            .id("repeat-confirm")
+            .w_full()
+            .px_3()
+            .py_1p5()
+            .border_t_1()
+            .border_color(theme.border)
+            .flex()
+            .items_center()
+            .justify_end()
+            .gap_1p5()
+            .child(
+                div()
+                    .text_color(theme.error)
+                    .text_xs()
+                    .child(title.to_string()),
+            )
+            .child(Self::render_confirm_hint(theme))
+            .into_any_element()
+    }
+
+    /// Render the toast offering to undo the last operation.
+    fn render_undo_toast(
+        info: &UndoInfo,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        div()
+            .id("undo-toast")
+            .w_full()
+            .px_3()
+            .py_1p5()
+            .border_t_1()
+            .border_color(theme.border)
+            .bg(theme.surface_hover)
+            .flex()
+            .items_center()
+            .gap_3()
             .child(
                 div()
+                    .flex_1()
                     .text_color(theme.text)
+                    .text_sm()
                     .text_ellipsis()
                     .overflow_hidden()
-                    .child(title),
-            );
+                    .child(info.description.clone()),
+            )
+            .child(
+                div()
+                    .id("undo-toast-button")
+                    .px_2()
+                    .rounded(px(4.0))
+                    .text_color(theme.accent)
+                    .text_sm()
+                    .cursor_pointer()
+                    .child(t("Undo"))
+                    .on_click(cx.listener(|this, _event, _window, cx| this.undo(cx))),
+            )
+            .child(Self::render_shortcut("cmd+z", theme))
+            .into_any_element()
+    }
+
+    /// Render the toast offering the report of the last crash.
+    fn render_crash_toast(theme: &crate::theme::Theme, cx: &Context<Self>) -> gpui::AnyElement {
+        let button = |id: &'static str, label: String| {
+            div()
+                .id(id)
+                .px_2()
+                .rounded(px(4.0))
+                .text_color(theme.accent)
+                .text_sm()
+                .cursor_pointer()
+                .child(label)
+        };
 
-        if let Some(sub) = subtitle {
-            content = content.child(
+        div()
+            .id("crash-toast")
+            .w_full()
+            .px_3()
+            .py_1p5()
+            .border_t_1()
+            .border_color(theme.border)
+            .bg(theme.surface_hover)
+            .flex()
+            .items_center()
+            .gap_3()
+            .child(
                 div()
-                    .text_color(theme.text_muted)
+                    .flex_1()
+                    .text_color(theme.text)
                     .text_sm()
                     .text_ellipsis()
-                    .flex_shrink_0()
-                    .child(sub),
+                    .overflow_hidden()
+                    .child(t("Lux quit unexpectedly last time")),
+            )
+            .child(
+                button("crash-toast-copy", t("Copy Report"))
+                    .on_click(cx.listener(|this, _event, _window, cx| this.copy_crash_report(cx))),
+            )
+            .child(
+                button("crash-toast-dismiss", t("Dismiss")).on_click(cx.listener(
+                    |this, _event, _window, cx| {
+                        this.crash_report = None;
+                        cx.notify();
+                    },
+                )),
+            )
+            .into_any_element()
+    }
+
+    /// Render the badge counting the items in the basket.
+    fn render_basket_badge(
+        count: usize,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        div()
+            .id("basket-badge")
+            .flex_none()
+            .px_2()
+            .py_0p5()
+            .rounded(px(10.0))
+            .bg(theme.accent)
+            .text_color(theme.background)
+            .text_xs()
+            .cursor_pointer()
+            .child(t_args("Basket: {count}", &[("count", &count.to_string())]))
+            .on_click(cx.listener(|this, _event, _window, cx| this.open_basket(cx)))
+            .into_any_element()
+    }
+
+    /// Render the badge shown while a macro is recorded; clicking it stops recording.
+    fn render_recording_badge(
+        name: &str,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        div()
+            .id("recording-badge")
+            .flex_none()
+            .px_2()
+            .py_0p5()
+            .rounded(px(10.0))
+            .bg(theme.error)
+            .text_color(theme.background)
+            .text_xs()
+            .cursor_pointer()
+            .child(t_args("Recording {name}", &[("name", name)]))
+            .on_click(cx.listener(|this, _event, _window, cx| this.toggle_macro_recording(cx)))
+            .into_any_element()
+    }
+
+    /// Render the button that pins the launcher open.
+    fn render_pin_button(
+        pinned: bool,
+        theme: &crate::theme::Theme,
+        cx: &Context<Self>,
+    ) -> gpui::AnyElement {
+        div()
+            .id("pin-button")
+            .flex_none()
+            .px_2()
+            .py_0p5()
+            .rounded(px(10.0))
+            .border_1()
+            .border_color(if pinned { theme.accent } else { theme.border })
+            .when(pinned, |this| this.bg(theme.accent))
+            .text_color(if pinned {
+                theme.background
+            } else {
+                theme.text_muted
+            })
+            .text_xs()
+            .cursor_pointer()
+            .child(t("Pin"))
+            .on_click(cx.listener(|this, _event, _window, cx| this.toggle_pin(cx)))
+            .into_any_element()
+    }
+
+    /// Render the footer listing the cursor item's action shortcuts.
+    fn render_action_hints(
+        hints: &[ActionMenuItem],
+        confirm_shortcut: Option<&str>,
+        theme: &crate::theme::Theme,
+    ) -> gpui::AnyElement {
+        let mut footer = div()
+            .id("action-hints")
+            .w_full()
+            .px_3()
+            .py_1p5()
+            .border_t_1()
+            .border_color(theme.border)
+            .flex()
+            .items_center()
+            .justify_end()
+            .gap_3();
+
+        for action in hints {
+            let Some(shortcut) = action.shortcut.as_deref() else {
+                continue;
+            };
+            let confirming = confirm_shortcut == Some(shortcut);
+            footer = footer.child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap_1p5()
+                    .child(
+                        div()
+                            .text_color(if action.destructive {
+                                theme.error
+                            } else {
+                                theme.text_muted
+                            })
+                            .text_xs()
+                            .child(action.title.clone()),
+                    )
+                    .child(Self::render_shortcut(shortcut, theme))
+                    .when(confirming, |this| {
+                        this.child(Self::render_confirm_hint(theme))
+                    }),
             );
         }
 
-        row.child(content)
+        footer.into_any_element()
     }
 }
 
@@ -879,25 +3246,45 @@ impl EventEmitter<LauncherPanelEvent> for LauncherPanel {}
 // =============================================================================
 
 impl Render for LauncherPanel {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if let Some(handle) = self.focus_request.take() {
+            window.focus(&handle, cx);
+        }
         let theme = cx.theme();
+        // Paddings and text helpers are in rems, so density and scale apply to them too
+        window.set_rem_size(theme.rem_size);
 
         let Some(display) = self.view_states.last() else {
             return div().id("launcher-panel-empty").into_any_element();
         };
 
-        // Build item sizes based on entry type (headers vs items have different heights)
+        // Build item sizes based on entry type (headers vs items have different heights,
+        // and items with two lines of text are taller)
         let item_sizes: Rc<Vec<Size<Pixels>>> = Rc::new(
             display
-                .flat_entries
-                .iter()
-                .map(|entry| match entry {
-                    ListEntry::GroupHeader { .. } => size(px(0.0), theme.group_header_height),
-                    ListEntry::Item { .. } => size(px(0.0), theme.item_height),
-                })
+                .entry_heights(theme)
+                .map(|height| size(px(0.0), px(height)))
                 .collect(),
         );
 
+        // The header of the group at the top stays pinned while its rows
+        // scroll under it
+        let sticky = sticky_header(
+            &display.flat_entries,
+            item_sizes.iter().map(|size| f32::from(size.height)),
+            -scroll_offset(&self.scroll_handle),
+        )
+        .and_then(|index| display.flat_entries.get(index))
+        .map(|entry| {
+            div()
+                .absolute()
+                .top_0()
+                .left_0()
+                .w_full()
+                .bg(theme.background)
+                .child(Self::render_results_header(entry, theme, cx))
+        });
+
         // Build results list with VirtualList or empty state
         let results_list = if display.flat_entries.is_empty() {
             div()
@@ -907,16 +3294,24 @@ impl Render for LauncherPanel {
                 .flex()
                 .items_center()
                 .justify_center()
-                .child(div().text_color(theme.text_muted).child("No results"))
+                .child(
+                    div()
+                        .text_color(theme.text_muted)
+                        .child(SharedString::from(t("No results"))),
+                )
                 .into_any_element()
         } else {
             let entity = cx.entity().clone();
-            v_virtual_list(
+            let list = v_virtual_list(
                 entity,
                 "results-list",
                 item_sizes,
                 |this, range, _window, cx| {
                     let theme = cx.theme().clone();
+                    let hover = this.backend.mouse_settings().hover_moves_cursor();
+                    let dev_mode = cx
+                        .try_global::<DevMode>()
+                        .is_some_and(|dev_mode| dev_mode.0);
                     let Some(display) = this.view_states.last() else {
                         return vec![];
                     };
@@ -929,8 +3324,8 @@ impl Render for LauncherPanel {
                         };
 
                         match entry {
-                            ListEntry::GroupHeader { title } => {
-                                elements.push(Self::render_group_header(title, &theme));
+                            ListEntry::GroupHeader { .. } => {
+                                elements.push(Self::render_results_header(entry, &theme, cx));
                             }
                             ListEntry::Item { item, flat_index } => {
                                 let is_cursor = *flat_index == display.cursor_index;
@@ -940,8 +3335,22 @@ impl Render for LauncherPanel {
                                     .map(|id| display.selected_ids.contains(id))
                                     .unwrap_or(false);
 
-                                let row =
-                                    Self::render_result_item(item, is_cursor, is_selected, &theme);
+                                let editor = this
+                                    .row_edit
+                                    .as_ref()
+                                    .filter(|row_edit| row_edit.edit.item_id == item.id)
+                                    .map(|_| this.row_editor.clone().into_any_element());
+                                let badge = (this.quick_select && *flat_index < QUICK_SELECT_COUNT)
+                                    .then_some(*flat_index + 1);
+                                let row = Self::render_result_item(
+                                    item,
+                                    is_cursor,
+                                    is_selected,
+                                    badge,
+                                    editor,
+                                    dev_mode,
+                                    &theme,
+                                );
                                 let item_index = *flat_index;
                                 let row = row.on_click(cx.listener(
                                     move |this: &mut Self,
@@ -955,6 +3364,41 @@ impl Render for LauncherPanel {
                                         }
                                     },
                                 ));
+                                let row = row.on_mouse_down(
+                                    MouseButton::Right,
+                                    cx.listener(
+                                        move |this, event: &MouseDownEvent, _window, cx| {
+                                            this.on_item_right_click(
+                                                item_index,
+                                                event.position,
+                                                cx,
+                                            );
+                                        },
+                                    ),
+                                );
+                                let row = if hover {
+                                    row.on_mouse_move(cx.listener(
+                                        move |this, _event: &MouseMoveEvent, _window, cx| {
+                                            this.on_item_hover(item_index, cx);
+                                        },
+                                    ))
+                                } else {
+                                    row
+                                };
+
+                                // Rows with drag metadata can be dragged into other apps
+                                let row = match item.drag.clone() {
+                                    Some(payload) => row.on_drag(
+                                        DraggedItem { payload },
+                                        |dragged: &DraggedItem, _offset, _window, cx| {
+                                            let animate = !cx.theme().reduce_motion;
+                                            platform::current()
+                                                .begin_drag(&dragged.payload, animate);
+                                            cx.new(|_| DragPreview)
+                                        },
+                                    ),
+                                    None => row,
+                                };
                                 elements.push(row.into_any_element());
                             }
                         }
@@ -964,10 +3408,87 @@ impl Render for LauncherPanel {
             )
             .track_scroll(&self.scroll_handle)
             .w_full()
-            .h_full()
-            .into_any_element()
+            .h_full();
+            div()
+                .relative()
+                .size_full()
+                .child(list)
+                .children(sticky)
+                .into_any_element()
+        };
+
+        // Follow-up actions replace the results until dismissed
+        let results_list = match &self.completion {
+            Some(completion) => Self::render_completion(completion, theme, cx),
+            None => results_list,
+        };
+
+        // A menu opened by a right click floats at the pointer, otherwise it
+        // sits below the results
+        let (context_menu, action_menu) = match (&self.action_menu, self.menu_anchor) {
+            (Some(menu), Some(position)) => {
+                let menu = div()
+                    .id("context-menu")
+                    .occlude()
+                    .w(px(CONTEXT_MENU_WIDTH))
+                    .bg(theme.background)
+                    .border_1()
+                    .border_color(theme.border)
+                    .rounded(theme.radius)
+                    .shadow_lg()
+                    .on_mouse_down_out(cx.listener(|this, event: &MouseDownEvent, _window, cx| {
+                        // A right click elsewhere opens another menu instead
+                        if event.button == MouseButton::Left {
+                            this.close_context_menu(cx);
+                        }
+                    }))
+                    .child(Self::render_action_menu(menu, &t("Actions"), theme, cx));
+                let popover = anchored()
+                    .position(position)
+                    .snap_to_window_with_margin(px(8.0))
+                    .child(menu);
+                (Some(deferred(popover).with_priority(1)), None)
+            }
+            (Some(menu), None) => (
+                None,
+                Some(Self::render_action_menu(menu, &t("Actions"), theme, cx)),
+            ),
+            (None, _) => (None, None),
         };
 
+        // Shortcut hints for the cursor item while the menu is closed
+        let action_hints =
+            (self.open_menu().is_none() && !self.action_hints.is_empty()).then(|| {
+                Self::render_action_hints(
+                    &self.action_hints,
+                    self.confirm_shortcut.as_deref(),
+                    theme,
+                )
+            });
+
+        let repeat_confirm = self
+            .confirm_repeat
+            .as_ref()
+            .map(|(_, title)| Self::render_repeat_confirm(title, theme));
+        let undo_toast = self
+            .undo_toast
+            .as_ref()
+            .map(|info| Self::render_undo_toast(info, theme, cx));
+        let crash_toast = self
+            .crash_report
+            .is_some()
+            .then(|| Self::render_crash_toast(theme, cx));
+
+        let basket_count = self.backend.basket_count();
+        let basket_badge =
+            (basket_count > 0).then(|| Self::render_basket_badge(basket_count, theme, cx));
+        let recording_badge = self
+            .backend
+            .macros()
+            .recording()
+            .map(|name| Self::render_recording_badge(&name, theme, cx));
+        let pin_button = (!self.detached).then(|| Self::render_pin_button(self.pinned, theme, cx));
+
         // Build dynamic key context with view ID
         let mut key_context = KeyContext::default();
         key_context.add("Launcher");
@@ -982,10 +3503,30 @@ impl Render for LauncherPanel {
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::on_cursor_up))
             .on_action(cx.listener(Self::on_cursor_down))
+            .on_action(cx.listener(Self::on_page_up))
+            .on_action(cx.listener(Self::on_page_down))
+            .on_action(cx.listener(Self::on_next_group))
+            .on_action(cx.listener(Self::on_prev_group))
+            .on_action(cx.listener(Self::on_jump_to_group))
+            .on_action(cx.listener(Self::on_execute_item))
+            .on_modifiers_changed(cx.listener(Self::on_modifiers_changed))
             .on_action(cx.listener(Self::on_open_action_menu))
             .on_action(cx.listener(Self::on_toggle_selection))
             .on_action(cx.listener(Self::on_run_lua_handler))
             .on_action(cx.listener(Self::on_dismiss))
+            .on_action(cx.listener(Self::on_undo))
+            .on_action(cx.listener(Self::on_repeat_last_action))
+            .on_action(cx.listener(Self::on_toggle_pin))
+            .on_action(cx.listener(Self::on_detach_view))
+            .on_action(cx.listener(Self::on_toggle_macro_recording))
+            .on_action(cx.listener(Self::on_play_macro))
+            .on_action(cx.listener(Self::on_reload_config))
+            .on_action(cx.listener(Self::on_open_config))
+            .on_action(cx.listener(Self::on_open_logs))
+            .on_action(cx.listener(Self::on_toggle_theme))
+            .on_action(cx.listener(Self::on_toggle_dev_mode))
+            .on_action(cx.listener(Self::on_clear_caches))
+            .on_key_down(cx.listener(Self::on_key_down))
             .w_full()
             .h_full()
             .flex()
@@ -1000,7 +3541,13 @@ impl Render for LauncherPanel {
                     .p_2()
                     .border_b_1()
                     .border_color(theme.border)
-                    .child(self.search_input.clone()),
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .child(div().flex_1().child(self.search_input.clone()))
+                    .children(recording_badge)
+                    .children(basket_badge)
+                    .children(pin_button),
             )
             // Results list with padding
             .child(
@@ -1011,10 +3558,60 @@ impl Render for LauncherPanel {
                     .p_2()
                     .child(results_list),
             )
+            // Action menu below the results while open
+            .children(action_menu)
+            .children(context_menu)
+            .children(action_hints)
+            .children(repeat_confirm)
+            .children(undo_toast)
+            .children(crash_toast)
             .into_any_element()
     }
 }
 
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Action menu entry for an action listed by the backend.
+fn action_menu_item(info: lux_plugin_api::ActionInfo) -> ActionMenuItem {
+    ActionMenuItem {
+        view_id: info.view_id,
+        action_id: info.id,
+        handler_key: info.handler_key,
+        title: info.title,
+        icon: info.icon,
+        shortcut: info.shortcut,
+        section: info.section,
+        destructive: info.destructive,
+    }
+}
+
+/// Item IDs as strings, sorted so they are sent in a stable order.
+fn sorted_ids(ids: &HashSet<ItemId>) -> Vec<String> {
+    let mut ids: Vec<String> = ids.iter().map(|id| id.0.clone()).collect();
+    ids.sort();
+    ids
+}
+
+/// Parse a `#rgb`, `#rrggbb`, or `#rrggbbaa` icon into a swatch color.
+fn swatch_color(icon: &str) -> Option<gpui::Rgba> {
+    let hex = icon.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = match hex.len() {
+        3 => {
+            let expanded: String = hex.chars().flat_map(|c| [c, c]).collect();
+            u32::from_str_radix(&expanded, 16).ok()? << 8 | 0xff
+        }
+        6 => u32::from_str_radix(hex, 16).ok()? << 8 | 0xff,
+        8 => u32::from_str_radix(hex, 16).ok()?,
+        _ => return None,
+    };
+    Some(gpui::rgba(value))
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -1023,9 +3620,21 @@ impl Render for LauncherPanel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_swatch_color() {
+        assert_eq!(swatch_color("#ff0088"), Some(gpui::rgba(0xff0088ff)));
+        assert_eq!(swatch_color("#f08"), Some(gpui::rgba(0xff0088ff)));
+        assert_eq!(swatch_color("#00000080"), Some(gpui::rgba(0x00000080)));
+        assert_eq!(swatch_color("#"), None);
+        assert_eq!(swatch_color("#general"), None);
+        assert_eq!(swatch_color("🎨"), None);
+    }
+
     #[test]
     fn test_launcher_panel_events() {
         let _event = LauncherPanelEvent::Dismiss;
+        let _event = LauncherPanelEvent::PinChanged(true);
+        let _event = LauncherPanelEvent::Detach("timers".to_string());
     }
 
     #[test]
@@ -1058,4 +3667,173 @@ mod tests {
         state.cursor_up();
         assert_eq!(state.cursor_index, 0);
     }
+
+    #[test]
+    fn test_view_display_state_keeps_cursor_on_refresh() {
+        let items = |ids: &[&str]| {
+            vec![lux_core::Group::new(
+                "Test",
+                ids.iter()
+                    .map(|id| lux_core::Item::new(*id, *id))
+                    .collect::<Vec<_>>(),
+            )]
+        };
+        let mut state = ViewDisplayState {
+            query: "a".to_string(),
+            ..Default::default()
+        };
+        assert!(state.set_results(items(&["1", "2", "3", "4"])));
+        state.cursor_by_page(2, true);
+        assert_eq!(state.cursor_item().unwrap().id, "3");
+
+        // A refresh with a new item on top keeps the cursor on its item
+        assert!(!state.set_results(items(&["0", "1", "2", "3", "4"])));
+        assert_eq!(state.cursor_item().unwrap().id, "3");
+        state.cursor_by_page(9, true);
+        assert_eq!(state.cursor_item().unwrap().id, "4");
+        state.cursor_by_page(3, false);
+        assert_eq!(state.cursor_item().unwrap().id, "1");
+
+        // A new query starts over at the top
+        state.query = "ab".to_string();
+        assert!(state.set_results(items(&["2", "1"])));
+        assert_eq!(state.cursor_index, 0);
+    }
+
+    #[test]
+    fn test_view_display_state_collapses_groups() {
+        let mut state = ViewDisplayState::default();
+        state.set_groups(vec![
+            lux_core::Group::new("Recent", vec![lux_core::Item::new("1", "Item 1")])
+                .with_collapsible(true),
+            lux_core::Group::new(
+                "All",
+                vec![
+                    lux_core::Item::new("2", "Item 2"),
+                    lux_core::Item::new("3", "Item 3"),
+                ],
+            )
+            .with_collapsible(true),
+            lux_core::Group::new("Fixed", vec![lux_core::Item::new("4", "Item 4")]),
+        ]);
+        state.cursor_down();
+        assert_eq!(state.group_to_collapse().as_deref(), Some("All"));
+
+        // The cursor leaves the collapsed group for the next one
+        assert!(state.set_group_collapsed("All", true));
+        assert!(!state.set_group_collapsed("All", true));
+        assert_eq!(state.item_ids.len(), 2);
+        assert_eq!(state.cursor_item().unwrap().id, "4");
+        assert!(matches!(
+            state.flat_entries[2],
+            ListEntry::GroupHeader {
+                collapsed: true,
+                item_count: 2,
+                ..
+            }
+        ));
+        assert_eq!(state.group_to_collapse(), None);
+        assert_eq!(state.group_to_expand().as_deref(), Some("All"));
+
+        // New results keep the group collapsed
+        state.set_groups(vec![lux_core::Group::new(
+            "All",
+            vec![lux_core::Item::new("5", "Item 5")],
+        )
+        .with_collapsible(true)]);
+        assert!(state.item_ids.is_empty());
+        assert_eq!(state.group_to_expand().as_deref(), Some("All"));
+        assert!(state.set_group_collapsed("All", false));
+        assert_eq!(state.cursor_item().unwrap().id, "5");
+
+        // Groups that aren't collapsible stay open
+        assert!(!state.set_group_collapsed("Fixed", true));
+    }
+
+    #[test]
+    fn test_view_display_state_jumps_between_groups() {
+        let mut state = ViewDisplayState::default();
+        state.set_groups(vec![
+            lux_core::Group::new(
+                "Apps",
+                vec![
+                    lux_core::Item::new("1", "Item 1"),
+                    lux_core::Item::new("2", "Item 2"),
+                ],
+            ),
+            lux_core::Group::new("Files", vec![lux_core::Item::new("3", "Item 3")]),
+        ]);
+        state.cursor_next_group();
+        assert_eq!(state.cursor_item().unwrap().id, "3");
+        state.cursor_next_group();
+        assert_eq!(state.cursor_item().unwrap().id, "3");
+        state.cursor_prev_group();
+        assert_eq!(state.cursor_item().unwrap().id, "1");
+        state.cursor_to_group(2);
+        assert_eq!(state.cursor_item().unwrap().id, "3");
+        state.cursor_to_group(3);
+        state.cursor_to_group(0);
+        assert_eq!(state.cursor_item().unwrap().id, "3");
+    }
+
+    #[test]
+    fn test_view_display_state_cursor_to_number() {
+        let mut state = ViewDisplayState::default();
+        state.set_groups(vec![
+            lux_core::Group::new("Apps", vec![lux_core::Item::new("1", "Item 1")]),
+            lux_core::Group::new(
+                "Files",
+                vec![
+                    lux_core::Item::new("2", "Item 2"),
+                    lux_core::Item::new("3", "Item 3"),
+                ],
+            ),
+        ]);
+        // Numbers count items across groups, skipping headers
+        assert_eq!(state.cursor_to_number(3).unwrap().id, "3");
+        assert_eq!(state.cursor_index, 2);
+        assert!(state.cursor_to_number(4).is_none());
+        assert!(state.cursor_to_number(0).is_none());
+        assert_eq!(state.cursor_index, 2);
+    }
+
+    #[test]
+    fn test_view_display_state_restores_saved_state() {
+        let view = ViewState {
+            instance_id: 7,
+            id: Some("files".to_string()),
+            title: None,
+            placeholder: None,
+            selection: SelectionMode::Multi,
+            selected: Vec::new(),
+            has_on_selection_changed: false,
+            refresh_ms: None,
+            initial_query: Some("ignored".to_string()),
+            saved: Some(SavedViewState {
+                query: "it".to_string(),
+                cursor_item: Some("3".to_string()),
+                scroll_offset: -48.0,
+                selected: vec!["1".to_string()],
+            }),
+        };
+        let mut state = ViewDisplayState::for_view(&view);
+        assert_eq!(state.instance_id, Some(7));
+        assert_eq!(state.query, "it");
+        assert!(state.selected_ids.contains(&ItemId("1".to_string())));
+
+        state.set_groups(vec![lux_core::Group::new(
+            "Test",
+            vec![
+                lux_core::Item::new("1", "Item 1"),
+                lux_core::Item::new("2", "Item 2"),
+                lux_core::Item::new("3", "Item 3"),
+            ],
+        )]);
+        assert_eq!(state.restore_cursor(), Some(-48.0));
+        assert_eq!(state.cursor_index, 2);
+
+        // Saving captures the same state again
+        let saved = state.saved_state(-48.0);
+        assert_eq!(Some(saved), view.saved);
+    }
 }
@@ -7,5 +7,7 @@ mod results_panel;
 mod search_input;
 
 pub use launcher_panel::{LauncherPanel, LauncherPanelEvent};
-pub use results_panel::scroll_to_cursor;
+pub use results_panel::{
+    scroll_offset, scroll_to_cursor, set_scroll_offset, viewport_height, DragPreview, DraggedItem,
+};
 pub use search_input::{SearchInput, SearchInputEvent};
@@ -2,8 +2,9 @@
 //!
 //! Helper functions for the results list.
 
-use gpui::ScrollStrategy;
+use gpui::{point, px, Context, Empty, IntoElement, Render, ScrollStrategy, Window};
 use gpui_component::VirtualListScrollHandle;
+use lux_core::DragPayload;
 
 /// Scroll the results list to make the cursor visible.
 ///
@@ -11,3 +12,38 @@ use gpui_component::VirtualListScrollHandle;
 pub fn scroll_to_cursor(scroll_handle: &VirtualListScrollHandle, cursor_list_index: usize) {
     scroll_handle.scroll_to_item(cursor_list_index, ScrollStrategy::Nearest);
 }
+
+/// Vertical scroll offset of the results list, in pixels.
+pub fn scroll_offset(scroll_handle: &VirtualListScrollHandle) -> f32 {
+    f32::from(scroll_handle.offset().y)
+}
+
+/// Scroll the results list back to an offset saved with [`scroll_offset`].
+pub fn set_scroll_offset(scroll_handle: &VirtualListScrollHandle, offset: f32) {
+    scroll_handle.set_offset(point(px(0.), px(offset)));
+}
+
+/// Height of the visible part of the results list, in pixels (0 before
+/// the list is laid out).
+pub fn viewport_height(scroll_handle: &VirtualListScrollHandle) -> f32 {
+    f32::from(scroll_handle.bounds().size.height)
+}
+
+/// Value GPUI carries while a result row is being dragged.
+#[derive(Debug, Clone)]
+pub struct DraggedItem {
+    /// Payload handed to the native drag session.
+    pub payload: DragPayload,
+}
+
+/// In-window drag preview for result rows.
+///
+/// Renders nothing: the native drag session draws its own image, and the drag
+/// is expected to leave the launcher window.
+pub struct DragPreview;
+
+impl Render for DragPreview {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
@@ -34,6 +34,10 @@ pub enum SearchInputEvent {
     Submit,
     /// Backspace on empty input - pop view stack.
     Back,
+    /// Left arrow on empty input - collapse the cursor's group.
+    CollapseGroup,
+    /// Right arrow on empty input - expand the collapsed group next to the cursor.
+    ExpandGroup,
 }
 
 // =============================================================================
@@ -312,6 +316,11 @@ impl TextEditor {
     }
 
     fn left(&mut self, _: &MoveLeft, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.text.is_empty() {
+            cx.emit(SearchInputEvent::CollapseGroup);
+            return;
+        }
+
         if self.selected_range.is_empty() {
             self.move_to(self.previous_boundary(self.cursor_offset()), cx);
         } else {
@@ -320,6 +329,11 @@ impl TextEditor {
     }
 
     fn right(&mut self, _: &MoveRight, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.text.is_empty() {
+            cx.emit(SearchInputEvent::ExpandGroup);
+            return;
+        }
+
         if self.selected_range.is_empty() {
             self.move_to(self.next_boundary(self.cursor_offset()), cx);
         } else {
@@ -606,6 +620,7 @@ impl Render for TextEditor {
             .bg(theme.surface)
             .rounded(theme.radius)
             .border_1()
+            .when(theme.high_contrast, |this| this.border_2())
             .border_color(theme.border)
             .when(is_focused, |this| this.border_color(theme.border_focused))
             // Text element
@@ -1,25 +1,35 @@
 //! Window management for the Lux launcher.
 //!
 //! This module provides `LauncherWindow` which owns the window lifecycle,
-//! hotkey management, and activation handling.
+//! hotkey management, activation handling, the socket later launches hand
+//! off to, and the optional remote control server. Views detached from the
+//! launcher open in windows of their own (see [`crate::detached`]).
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use gpui::{
     px, size, App, AppContext, AsyncApp, Bounds, Entity, Task, WindowBackgroundAppearance,
-    WindowBounds, WindowHandle, WindowKind, WindowOptions,
+    WindowBounds, WindowHandle, WindowOptions,
 };
-use tokio::sync::mpsc::{self, Receiver};
+use serde_json::{json, Value};
+use tokio::sync::mpsc::{self, Receiver, Sender};
 
-use lux_plugin_api::{BuiltInHotkey, GlobalHandler, KeymapRegistry};
+use lux_plugin_api::{
+    BuiltInHotkey, GlobalHandler, KeymapRegistry, ProfileContext, RemoteCommand, RemoteRequest,
+    RemoteServer, ServerConfig, SEND_TO_LUX_KEY,
+};
 
 use crate::backend::Backend;
+use crate::crash::{take_report, CrashReport};
+use crate::detached::DetachedWindows;
+use crate::doctor::{accessibility_check, hotkey_check};
+use crate::instance::{InstanceCommand, InstanceListener, InstanceServer};
 use crate::keymap::apply_keybindings;
 use crate::platform::{
-    has_accessibility_permission, parse_hotkey, prompt_accessibility_permission,
-    set_activation_policy_accessory, Hotkey, HotkeyCallback, HotkeyManager, MultiHotkeyManager,
+    self, parse_hotkey, Hotkey, HotkeyCallback, HotkeyManager, MultiHotkeyManager,
 };
-use crate::theme::Theme;
+use crate::theme::{Theme, ThemeSettings};
 use crate::views::{LauncherPanel, LauncherPanelEvent};
 
 // =============================================================================
@@ -30,6 +40,12 @@ use crate::views::{LauncherPanel, LauncherPanelEvent};
 pub const DEFAULT_WIDTH: f32 = 760.0;
 pub const DEFAULT_HEIGHT: f32 = 480.0;
 
+/// How often timers are ticked (fires finished timers, updates the menu bar).
+const TIMER_TICK: Duration = Duration::from_secs(1);
+
+/// How long quitting waits for `on_shutdown` hooks and the Lua thread.
+const SHUTDOWN_DEADLINE: Duration = Duration::from_secs(2);
+
 /// Create window options for the launcher panel.
 ///
 /// Note: Window bounds will be set after creation since we need App context.
@@ -39,13 +55,32 @@ fn create_window_options() -> WindowOptions {
         titlebar: None,
         focus: true,
         show: false, // Start hidden, show on hotkey
-        kind: WindowKind::PopUp,
+        kind: platform::current().window_kind(),
         is_movable: false,
         window_background: WindowBackgroundAppearance::Blurred,
         ..Default::default()
     }
 }
 
+/// Rebuild the theme from Lua overrides and the current system settings.
+///
+/// Also turns window animations off when motion should be reduced.
+pub(crate) fn refresh_theme(backend: &dyn Backend, cx: &mut App) {
+    let settings = ThemeSettings::default().with_overrides(&backend.theme_overrides());
+    let platform = platform::current();
+    let theme = Theme::from_system(&settings, &platform.system_preferences());
+    platform.set_window_animations(!theme.reduce_motion);
+    cx.set_global(theme);
+}
+
+/// Hide the launcher, leaving detached windows on screen.
+fn hide_launcher(cx: &mut App) {
+    if DetachedWindows::any_open(cx) {
+        platform::current().order_out_launcher();
+    }
+    cx.hide();
+}
+
 // =============================================================================
 // Hotkey Event Channel
 // =============================================================================
@@ -55,6 +90,8 @@ fn create_window_options() -> WindowOptions {
 pub enum HotkeyEvent {
     /// Toggle launcher visibility.
     Toggle,
+    /// Show and focus the launcher (nothing if it's already focused).
+    Show,
     /// Run a Lua handler by ID.
     RunLuaHandler(String),
 }
@@ -95,6 +132,14 @@ pub struct LauncherWindow {
     _multi_hotkey_manager: Option<MultiHotkeyManager>,
     /// Task polling the hotkey channel (kept alive).
     _hotkey_task: Task<()>,
+    /// Task ticking timers and the menu-bar countdown (kept alive).
+    _timer_task: Task<()>,
+    /// Remote control listener, if enabled with `lux.server.enable()`.
+    _remote_server: Option<RemoteServer>,
+    /// Task running remote control commands (kept alive).
+    _remote_task: Option<Task<()>>,
+    /// Socket later launches send `show` to (kept alive).
+    _instance_server: Option<InstanceServer>,
 }
 
 impl LauncherWindow {
@@ -111,13 +156,17 @@ impl LauncherWindow {
         hotkey: Hotkey,
         backend: Arc<dyn Backend>,
         keymap: &KeymapRegistry,
+        instance: Option<InstanceListener>,
         cx: &mut App,
     ) -> Option<Self> {
+        let platform = platform::current();
+
         // Check accessibility permissions
-        if !has_accessibility_permission() {
+        if !platform.has_accessibility_permission() {
             tracing::warn!("Accessibility permissions not granted, prompting user");
-            prompt_accessibility_permission();
+            platform.prompt_accessibility_permission();
         }
+        backend.record_check(accessibility_check());
 
         // Create window options with centered bounds
         let window_size = size(px(DEFAULT_WIDTH), px(DEFAULT_HEIGHT));
@@ -132,8 +181,7 @@ impl LauncherWindow {
         let window_handle = cx
             .open_window(options, |window, cx| {
                 // Initialize theme as a global
-                let theme = Theme::default();
-                cx.set_global(theme);
+                refresh_theme(backend.as_ref(), cx);
 
                 // Create the root view - capture window in the closure
                 let panel =
@@ -145,11 +193,32 @@ impl LauncherWindow {
 
         // Subscribe to panel events (dismiss on escape)
         let panel_entity = panel_entity?;
+
+        // Offer the report the last run left if it crashed
+        if let Some(report) = CrashReport::default_path().and_then(|path| take_report(&path)) {
+            panel_entity.update(cx, |panel, cx| panel.show_crash_report(report, cx));
+        }
+        let detach_backend = backend.clone();
         cx.subscribe(
             &panel_entity,
-            |_, event: &LauncherPanelEvent, cx| match event {
+            move |panel, event: &LauncherPanelEvent, cx| match event {
                 LauncherPanelEvent::Dismiss => {
-                    cx.hide();
+                    hide_launcher(cx);
+                }
+                LauncherPanelEvent::PinChanged(pinned) => {
+                    platform::current().set_keep_visible(*pinned);
+                }
+                LauncherPanelEvent::Detach(view_id) => {
+                    // The launcher closes once the view has its own window
+                    let opening =
+                        DetachedWindows::open(detach_backend.clone(), view_id.clone(), cx);
+                    cx.spawn(async move |cx: &mut AsyncApp| {
+                        if opening.await {
+                            let _ = panel.update(cx, |panel, cx| panel.view_detached(cx));
+                            let _ = cx.update(hide_launcher);
+                        }
+                    })
+                    .detach();
                 }
             },
         )
@@ -158,13 +227,29 @@ impl LauncherWindow {
         // Create hotkey channel (tokio async mpsc)
         let (tx, rx) = mpsc::channel::<HotkeyEvent>(32);
 
+        // Show the launcher when `lux` is launched again
+        let instance_server = instance.and_then(|listener| {
+            let tx_show = tx.clone();
+            listener
+                .serve(move |command| match command {
+                    InstanceCommand::Show => {
+                        let _ = tx_show.try_send(HotkeyEvent::Show);
+                    }
+                })
+                .map_err(|e| tracing::error!("Failed to serve instance socket: {}", e))
+                .ok()
+        });
+
         // Create legacy hotkey manager with channel sender (for the default toggle)
         let tx_toggle = tx.clone();
-        let hotkey_manager = HotkeyManager::new(hotkey, move || {
-            // Just signal, don't touch GPUI from here
-            // Use try_send to avoid blocking if channel is full
-            let _ = tx_toggle.try_send(HotkeyEvent::Toggle);
-        });
+        let hotkey_manager = platform.hotkey_manager(
+            hotkey,
+            Arc::new(move || {
+                // Just signal, don't touch GPUI from here
+                // Use try_send to avoid blocking if channel is full
+                let _ = tx_toggle.try_send(HotkeyEvent::Toggle);
+            }),
+        );
 
         if hotkey_manager.is_none() {
             tracing::warn!(
@@ -173,15 +258,32 @@ impl LauncherWindow {
         }
 
         // Create multi-hotkey manager for Lua-configured hotkeys
-        let multi_hotkey_manager = MultiHotkeyManager::new();
+        let multi_hotkey_manager = platform.multi_hotkey_manager();
         if let Some(ref manager) = multi_hotkey_manager {
-            apply_global_hotkeys(keymap, manager, tx.clone());
+            apply_global_hotkeys(keymap, manager, tx.clone(), backend.as_ref());
         } else {
             tracing::warn!(
                 "Failed to create multi-hotkey manager - accessibility permissions may be required"
             );
+            for pending in keymap.take_hotkeys() {
+                backend.record_check(hotkey_check(&pending.key, Some(false)));
+            }
         }
 
+        // Spawn task to tick timers (runs while the launcher is hidden too)
+        let timer_backend = backend.clone();
+        let timer_task = cx.spawn(async move |cx: &mut AsyncApp| {
+            Self::tick_timers(timer_backend, cx).await;
+        });
+
+        // Start the remote control server if the config enabled it
+        let (remote_server, remote_task) = match backend.server_config() {
+            Some(config) => {
+                Self::start_remote_control(config, tx.clone(), window_handle, backend.clone(), cx)
+            }
+            None => (None, None),
+        };
+
         // Spawn task to receive hotkey events
         let handle_clone = window_handle;
         let backend_clone = backend;
@@ -194,9 +296,139 @@ impl LauncherWindow {
             _hotkey_manager: hotkey_manager,
             _multi_hotkey_manager: multi_hotkey_manager,
             _hotkey_task: hotkey_task,
+            _timer_task: timer_task,
+            _remote_server: remote_server,
+            _remote_task: remote_task,
+            _instance_server: instance_server,
         })
     }
 
+    /// Start the remote control listener and the task that runs its commands.
+    fn start_remote_control(
+        config: ServerConfig,
+        hotkey_tx: Sender<HotkeyEvent>,
+        handle: WindowHandle<LauncherPanel>,
+        backend: Arc<dyn Backend>,
+        cx: &mut App,
+    ) -> (Option<RemoteServer>, Option<Task<()>>) {
+        let (tx, mut rx) = mpsc::channel::<RemoteRequest>(8);
+        let server = match RemoteServer::start(config, tx) {
+            Ok(server) => server,
+            Err(e) => {
+                tracing::error!("Failed to start remote control server: {}", e);
+                return (None, None);
+            }
+        };
+
+        let task = cx.spawn(async move |cx: &mut AsyncApp| {
+            while let Some(request) = rx.recv().await {
+                let reply =
+                    Self::run_remote_command(request.command, &hotkey_tx, handle, &backend, cx)
+                        .await;
+                let _ = request.reply.send(reply);
+            }
+        });
+
+        (Some(server), Some(task))
+    }
+
+    /// Run a remote control command and build its JSON reply.
+    async fn run_remote_command(
+        command: RemoteCommand,
+        hotkey_tx: &Sender<HotkeyEvent>,
+        handle: WindowHandle<LauncherPanel>,
+        backend: &Arc<dyn Backend>,
+        cx: &mut AsyncApp,
+    ) -> Result<Value, String> {
+        match command {
+            RemoteCommand::Search { query } => {
+                let groups = backend.search(query).await.map_err(|e| e.to_string())?;
+                Ok(json!({ "groups": groups }))
+            }
+            RemoteCommand::RunAction {
+                query,
+                item,
+                action,
+            } => {
+                let groups = backend.search(query).await.map_err(|e| e.to_string())?;
+                let mut items = groups.into_iter().flat_map(|group| group.items);
+                let item = match item {
+                    Some(id) => items
+                        .find(|item| item.id == id)
+                        .ok_or_else(|| format!("No result with ID '{}'", id))?,
+                    None => items.next().ok_or("No results")?,
+                };
+
+                let actions = backend
+                    .get_actions(vec![item.clone()])
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let mut actions = actions.into_iter();
+                let action = match action {
+                    Some(id) => actions
+                        .find(|action| action.id == id)
+                        .ok_or_else(|| format!("No action with ID '{}'", id))?,
+                    None => actions.next().ok_or("No actions for the result")?,
+                };
+
+                let action_id = action.handler_key().to_string();
+                let result = backend
+                    .execute_action(action.view_id, action_id, vec![item])
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(json!({ "result": result }))
+            }
+            // Show and hide go through Toggle so the profile and theme
+            // refresh exactly as they do for the hotkey
+            RemoteCommand::Show | RemoteCommand::Hide | RemoteCommand::Toggle => {
+                let show = matches!(command, RemoteCommand::Show);
+                let hide = matches!(command, RemoteCommand::Hide);
+                let is_active = handle
+                    .update(cx, |_panel, window, _cx| window.is_window_active())
+                    .unwrap_or(false);
+                if !((show && is_active) || (hide && !is_active)) {
+                    let _ = hotkey_tx.try_send(HotkeyEvent::Toggle);
+                }
+                Ok(json!({ "ok": true }))
+            }
+            RemoteCommand::Notify { title, body } => {
+                platform::current()
+                    .desktop()
+                    .notify(&title, &body)
+                    .map_err(|e| e.to_string())?;
+                Ok(json!({ "ok": true }))
+            }
+        }
+    }
+
+    /// Tick timers every second and mirror the countdown in the menu bar.
+    ///
+    /// Also runs the callbacks of finished OAuth sign-ins.
+    async fn tick_timers(backend: Arc<dyn Backend>, cx: &mut AsyncApp) {
+        let mut shown: Option<String> = None;
+        loop {
+            cx.background_executor().timer(TIMER_TICK).await;
+
+            if let Err(e) = backend.finish_oauth_flows().await {
+                tracing::warn!("OAuth callbacks failed: {:?}", e);
+            }
+
+            let text = match backend.tick_timers().await {
+                Ok(text) => text,
+                Err(e) => {
+                    tracing::warn!("Timer tick failed: {:?}", e);
+                    continue;
+                }
+            };
+
+            // Only touch the status item when the text changes
+            if text != shown {
+                let _ = cx.update(|_app| platform::current().set_status_text(text.as_deref()));
+                shown = text;
+            }
+        }
+    }
+
     /// Handle hotkey events from the channel.
     async fn handle_hotkey_events(
         mut rx: Receiver<HotkeyEvent>,
@@ -206,18 +438,49 @@ impl LauncherWindow {
     ) {
         while let Some(event) = rx.recv().await {
             match event {
-                HotkeyEvent::Toggle => {
+                HotkeyEvent::Toggle | HotkeyEvent::Show => {
                     // Check if window is active
                     let is_active = handle
                         .update(cx, |_panel, window, _cx| window.is_window_active())
                         .unwrap_or(false);
 
                     if is_active {
-                        // Window is focused, hide the app
-                        let _ = cx.update(|app| {
-                            app.hide();
-                        });
+                        // Window is focused, hide the app (Show leaves it up)
+                        if matches!(event, HotkeyEvent::Toggle) {
+                            let _ = cx.update(|app| {
+                                app.hide();
+                            });
+                        }
                     } else {
+                        // Pick the profile while the user's app is still frontmost
+                        let context = cx.update(|_app| {
+                            let platform = platform::current();
+                            let (app_name, bundle_id) = platform.frontmost_application();
+                            let (weekday, minutes) = platform.local_time();
+                            let context = ProfileContext {
+                                app_name,
+                                bundle_id,
+                                weekday,
+                                minutes,
+                            };
+                            if let Some(profile) = backend.auto_switch_profile(&context) {
+                                tracing::debug!("Active profile: {}", profile);
+                            }
+                            context
+                        });
+
+                        // Open on the view of a matching lux.rules.on_app() rule
+                        if let Ok(context) = context {
+                            match backend.open_for_app(context).await {
+                                Ok(Some(view)) => tracing::debug!("Opening on view: {}", view),
+                                Ok(None) => {}
+                                Err(e) => tracing::warn!("Frontmost-app rules failed: {}", e),
+                            }
+                        }
+
+                        // Pick up Lua theme overrides and changed system settings
+                        let _ = cx.update(|app| refresh_theme(backend.as_ref(), app));
+
                         // Window is not focused, show and activate it
                         let _ = handle.update(cx, |panel, window, cx| {
                             panel.show(window, cx);
@@ -262,9 +525,9 @@ impl LauncherWindow {
         });
     }
 
-    /// Hide the launcher (hides the app).
+    /// Hide the launcher (hides the app, except for detached windows).
     pub fn hide(&self, cx: &mut App) {
-        cx.hide();
+        hide_launcher(cx);
     }
 
     /// Get the window handle.
@@ -285,14 +548,18 @@ impl LauncherWindow {
 // =============================================================================
 
 /// Apply Lua-configured global hotkeys to the multi-hotkey manager.
+///
+/// Each one is recorded for `lux doctor`.
 fn apply_global_hotkeys(
     keymap: &KeymapRegistry,
     manager: &MultiHotkeyManager,
     tx: tokio::sync::mpsc::Sender<HotkeyEvent>,
+    backend: &dyn Backend,
 ) {
     for pending in keymap.take_hotkeys() {
         // Parse the hotkey string
         let Some(hotkey) = parse_hotkey(&pending.key) else {
+            backend.record_check(hotkey_check(&pending.key, None));
             tracing::warn!("Invalid hotkey string: '{}', skipping", pending.key);
             continue;
         };
@@ -305,6 +572,13 @@ fn apply_global_hotkeys(
                     let _ = tx.try_send(HotkeyEvent::Toggle);
                 })
             }
+            // Runs the built-in Lua handler that pushes the selection view
+            GlobalHandler::BuiltIn(BuiltInHotkey::SendToLux) => {
+                let tx = tx.clone();
+                Arc::new(move || {
+                    let _ = tx.try_send(HotkeyEvent::RunLuaHandler(SEND_TO_LUX_KEY.to_string()));
+                })
+            }
             GlobalHandler::Function { id } => {
                 let tx = tx.clone();
                 Arc::new(move || {
@@ -315,6 +589,7 @@ fn apply_global_hotkeys(
 
         // Register the hotkey
         manager.register(hotkey, callback);
+        backend.record_check(hotkey_check(&pending.key, Some(true)));
         tracing::debug!("Registered global hotkey from Lua: {}", pending.key);
     }
 }
@@ -336,6 +611,7 @@ fn apply_global_hotkeys(
 /// - `hotkey`: Global hotkey to toggle the launcher
 /// - `backend`: Backend for search/actions
 /// - `keymap`: KeymapRegistry with Lua-configured bindings
+/// - `instance`: Socket from `instance::claim()`, so later launches show this one
 ///
 /// ## Example
 ///
@@ -350,13 +626,18 @@ fn apply_global_hotkeys(
 ///     // ... load Lua config ...
 ///     let backend = Arc::new(RuntimeBackend::new(engine, runtime, registry.clone()));
 ///     let hotkey = Hotkey::cmd_space();
-///     run_launcher(hotkey, backend, registry.keymap());
+///     run_launcher(hotkey, backend, registry.keymap(), None);
 /// }
 /// ```
-pub fn run_launcher(hotkey: Hotkey, backend: Arc<dyn Backend>, keymap: Arc<KeymapRegistry>) {
+pub fn run_launcher(
+    hotkey: Hotkey,
+    backend: Arc<dyn Backend>,
+    keymap: Arc<KeymapRegistry>,
+    instance: Option<InstanceListener>,
+) {
     gpui::Application::new().run(move |cx| {
         // Hide from dock (run as accessory app like Spotlight)
-        set_activation_policy_accessory();
+        platform::current().set_activation_policy_accessory();
 
         // Initialize gpui-component
         gpui_component::init(cx);
@@ -365,8 +646,17 @@ pub fn run_launcher(hotkey: Hotkey, backend: Arc<dyn Backend>, keymap: Arc<Keyma
         // Defaults were registered in main.rs, user config may have modified them
         apply_keybindings(&keymap, cx);
 
+        // Shut the backend down in order before quitting. GPUI only waits
+        // briefly for quit futures, so this blocks instead.
+        let quit_backend = backend.clone();
+        cx.on_app_quit(move |_cx| {
+            quit_backend.shutdown(SHUTDOWN_DEADLINE);
+            async {}
+        })
+        .detach();
+
         // Create the launcher window (pass keymap for global hotkeys)
-        let launcher = LauncherWindow::new(hotkey, backend, &keymap, cx);
+        let launcher = LauncherWindow::new(hotkey, backend, &keymap, instance, cx);
 
         if launcher.is_none() {
             tracing::error!("Failed to create launcher window");
@@ -397,6 +687,7 @@ impl gpui::Global for LauncherWindow {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gpui::WindowKind;
 
     #[test]
     fn test_window_options() {